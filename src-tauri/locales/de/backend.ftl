@@ -0,0 +1,62 @@
+# Meldungen des Rust-Backends: Eingabeprüfung, Kompatibilitätsprüfung und
+# Warnungen beim Nest-Build.
+
+## Bezeichnungen für die Ordnerprüfung
+
+label-packs-directory = Pack-Ordner
+label-output-directory = Ausgabeordner
+label-pack-folder = Pack-Verzeichnis
+label-shared-cache-folder = Gemeinsamer Cache-Ordner
+
+## Eingabeprüfung
+
+validation-empty = { $label } darf nicht leer sein
+validation-missing = { $label } existiert nicht: { $path }
+validation-not-directory = { $label } ist kein Ordner: { $path }
+validation-pack-order-empty = Die Pack-Reihenfolge darf nicht leer sein
+validation-override-asset-empty = Asset-ID in Überschreibungen darf nicht leer sein
+validation-override-pack-empty = Pack-ID für Asset { $asset } darf nicht leer sein
+validation-override-unknown-pack = Überschreibung verweist auf ein nicht vorhandenes Pack: { $pack }
+validation-variant-path-empty = Variantenpfad für Asset { $asset } darf nicht leer sein
+validation-compression-level = Die Kompressionsstufe muss zwischen 0 und 9 liegen, erhalten: { $level }
+validation-target-version-mismatch = Der Arbeitsbereich zielt auf Minecraft { $target }, aber die Vanilla-Daten stammen aus { $cached }
+validation-target-version-mismatch-hint = Wechsle die Vanilla-Texturversion passend zum Ziel des Arbeitsbereichs
+validation-no-vanilla-data = Keine Vanilla-Daten vorhanden; lade die Vanilla-Texturen für Minecraft { $target }
+validation-output-dir-empty = Der Ausgabeordner darf nicht leer sein
+validation-output-inside-source = Der Ausgabeordner darf nicht im Quell-Pack liegen: { $path }
+validation-output-not-empty = Der Ausgabeordner muss leer sein: { $path }
+validation-zip-path-empty = Der Pfad der Ausgabe-ZIP darf nicht leer sein
+validation-zip-extension = Die Ausgabedatei muss die Endung .zip haben: { $path }
+validation-zip-inside-source = Die Ausgabe-ZIP darf nicht im Quellordner liegen: { $path }
+validation-zip-exists = Die Ausgabe-ZIP existiert bereits: { $path }
+validation-zip-folder-missing = Der Ausgabeordner existiert nicht: { $path }
+validation-pack-name-empty = Der Pack-Name darf nicht leer sein
+validation-pack-name-invalid = Der Pack-Name ist kein gültiger Dateiname: { $name }
+
+## Pack-Kompatibilität
+
+compat-format-outside-supported = supported_formats { $min }-{ $max } enthält das angegebene pack_format { $format } nicht
+compat-no-pack-format = pack.mcmeta gibt kein pack_format an
+compat-wrong-format = Das Pack ist für { $range } gemacht, aber { $version } verwendet pack_format { $target }
+compat-no-supported-formats = pack.mcmeta hat keinen supported_formats-Bereich, der weitere passende Versionen angibt
+compat-supported-formats-ignored = supported_formats wird vor 1.20.2 ignoriert, daher akzeptiert { $version } nur ein exakt passendes pack_format
+
+## Warnungen beim Nest-Build
+
+build-orphan-emissive = Emissive Textur { $file } hat in keinem Pack und nicht in Vanilla eine Basistextur
+build-rule-unreadable = Regel { $rule } konnte nicht gelesen werden: { $error }
+build-rule-missing-file = Regel { $rule } in { $pack } verweist auf die fehlende Datei { $file }
+build-merge-skipped = { $file } aus { $pack } beim Zusammenführen übersprungen: { $error }
+build-mcmeta-not-carried = { $asset }: Die Textur aus { $pack } hat keine .mcmeta; die Metadaten aus { $donors } wurden nicht übernommen
+build-mcmeta-not-carried-resized = { $asset }: Die Textur aus { $pack } hat keine .mcmeta; die Metadaten aus { $donors } wurden nicht übernommen (Texturgrößen unterscheiden sich)
+build-mixed-resolutions = Das Nest mischt Texturauflösungen: { $packs }
+build-resolution-mismatch = { $block } mischt Auflösungen: { $textures }
+build-resolution-mismatch-suggest = { $block } mischt Auflösungen: { $textures }; { $pack } liefert alle davon
+build-resolution-mismatch-more = ... und { $count } weitere Blöcke mit gemischten Auflösungen
+build-feature-flags = Die Pakete benötigen unterschiedliche experimentelle Features: { $packs }. Das Nest aktiviert { $features } und lädt nur in Welten, in denen alle aktiv sind
+
+## Kontrastanalyse
+
+contrast-transparent = Die Textur hat keine deckenden Pixel zum Messen
+contrast-low-ratio = Kontrastverhältnis { $ratio }:1 liegt unter den empfohlenen { $required }:1
+contrast-indistinct = Farben, die bei normalem Sehen verschieden sind, sind bei { $deficiency } kaum zu unterscheiden
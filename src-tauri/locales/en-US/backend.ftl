@@ -0,0 +1,62 @@
+# Messages generated by the Rust backend: input validation, compatibility
+# checks and nest build warnings. Keep placeables in sync across locales.
+
+## Labels passed to directory validation
+
+label-packs-directory = Packs directory
+label-output-directory = Output directory
+label-pack-folder = Pack folder
+label-shared-cache-folder = Shared cache folder
+
+## Input validation
+
+validation-empty = { $label } cannot be empty
+validation-missing = { $label } does not exist: { $path }
+validation-not-directory = { $label } is not a directory: { $path }
+validation-pack-order-empty = Pack order cannot be empty
+validation-override-asset-empty = Asset ID in overrides cannot be empty
+validation-override-pack-empty = Pack ID for asset { $asset } cannot be empty
+validation-override-unknown-pack = Override references non-existent pack: { $pack }
+validation-variant-path-empty = Variant path for asset { $asset } cannot be empty
+validation-compression-level = Compression level must be between 0 and 9, got { $level }
+validation-target-version-mismatch = Workspace targets Minecraft { $target } but vanilla data is cached for { $cached }
+validation-target-version-mismatch-hint = Switch the vanilla texture version to match the workspace target
+validation-no-vanilla-data = No vanilla data cached; initialize vanilla textures for Minecraft { $target }
+validation-output-dir-empty = Output directory cannot be empty
+validation-output-inside-source = Output directory cannot be inside the source pack: { $path }
+validation-output-not-empty = Output directory must be empty: { $path }
+validation-zip-path-empty = Output zip path cannot be empty
+validation-zip-extension = Output file must have a .zip extension: { $path }
+validation-zip-inside-source = Output zip cannot be inside the source folder: { $path }
+validation-zip-exists = Output zip already exists: { $path }
+validation-zip-folder-missing = Output folder does not exist: { $path }
+validation-pack-name-empty = Pack name cannot be empty
+validation-pack-name-invalid = Pack name is not a valid file name: { $name }
+
+## Pack compatibility
+
+compat-format-outside-supported = supported_formats { $min }-{ $max } does not include the declared pack_format { $format }
+compat-no-pack-format = pack.mcmeta does not declare a pack_format
+compat-wrong-format = Pack is made for { $range }, but { $version } uses pack_format { $target }
+compat-no-supported-formats = pack.mcmeta has no supported_formats range to declare other working versions
+compat-supported-formats-ignored = supported_formats is ignored before 1.20.2, so { $version } only accepts an exact pack_format match
+
+## Nest build warnings
+
+build-orphan-emissive = Emissive texture { $file } has no base texture in any pack or in vanilla
+build-rule-unreadable = Failed to read rule { $rule }: { $error }
+build-rule-missing-file = Rule { $rule } in { $pack } references missing file { $file }
+build-merge-skipped = Skipped { $file } from { $pack } while merging: { $error }
+build-mcmeta-not-carried = { $asset }: texture from { $pack } has no .mcmeta; metadata in { $donors } was not carried
+build-mcmeta-not-carried-resized = { $asset }: texture from { $pack } has no .mcmeta; metadata in { $donors } was not carried (texture dimensions differ)
+build-mixed-resolutions = The nest mixes texture resolutions: { $packs }
+build-resolution-mismatch = { $block } mixes resolutions: { $textures }
+build-resolution-mismatch-suggest = { $block } mixes resolutions: { $textures }; { $pack } provides all of them
+build-resolution-mismatch-more = ... and { $count } more blocks with mixed resolutions
+build-feature-flags = Packs need different experimental features: { $packs }. The nest enables { $features } and only loads in worlds with all of them
+
+## Contrast analysis
+
+contrast-transparent = Texture has no opaque pixels to measure
+contrast-low-ratio = Contrast ratio { $ratio }:1 is below the recommended { $required }:1
+contrast-indistinct = Colors that differ with normal vision are hard to tell apart with { $deficiency }
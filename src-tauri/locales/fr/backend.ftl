@@ -0,0 +1,62 @@
+# Messages générés par le backend Rust : validation des entrées, vérification
+# de compatibilité et avertissements de construction du nid.
+
+## Libellés utilisés par la validation des dossiers
+
+label-packs-directory = Le dossier des packs
+label-output-directory = Le dossier de sortie
+label-pack-folder = Le dossier du pack
+label-shared-cache-folder = Le dossier de cache partagé
+
+## Validation des entrées
+
+validation-empty = { $label } ne peut pas être vide
+validation-missing = { $label } n'existe pas : { $path }
+validation-not-directory = { $label } n'est pas un dossier : { $path }
+validation-pack-order-empty = L'ordre des packs ne peut pas être vide
+validation-override-asset-empty = L'ID d'asset d'un remplacement ne peut pas être vide
+validation-override-pack-empty = L'ID de pack de l'asset { $asset } ne peut pas être vide
+validation-override-unknown-pack = Le remplacement fait référence à un pack inexistant : { $pack }
+validation-variant-path-empty = Le chemin de variante de l'asset { $asset } ne peut pas être vide
+validation-compression-level = Le niveau de compression doit être compris entre 0 et 9, reçu { $level }
+validation-target-version-mismatch = L'espace de travail cible Minecraft { $target } mais les données vanilla en cache sont celles de { $cached }
+validation-target-version-mismatch-hint = Choisissez la version des textures vanilla correspondant à la cible de l'espace de travail
+validation-no-vanilla-data = Aucune donnée vanilla en cache ; initialisez les textures vanilla pour Minecraft { $target }
+validation-output-dir-empty = Le dossier de sortie ne peut pas être vide
+validation-output-inside-source = Le dossier de sortie ne peut pas se trouver dans le pack source : { $path }
+validation-output-not-empty = Le dossier de sortie doit être vide : { $path }
+validation-zip-path-empty = Le chemin du zip de sortie ne peut pas être vide
+validation-zip-extension = Le fichier de sortie doit avoir l'extension .zip : { $path }
+validation-zip-inside-source = Le zip de sortie ne peut pas se trouver dans le dossier source : { $path }
+validation-zip-exists = Le zip de sortie existe déjà : { $path }
+validation-zip-folder-missing = Le dossier de sortie n'existe pas : { $path }
+validation-pack-name-empty = Le nom du pack ne peut pas être vide
+validation-pack-name-invalid = Le nom du pack n'est pas un nom de fichier valide : { $name }
+
+## Compatibilité des packs
+
+compat-format-outside-supported = supported_formats { $min }-{ $max } n'inclut pas le pack_format déclaré { $format }
+compat-no-pack-format = pack.mcmeta ne déclare pas de pack_format
+compat-wrong-format = Le pack est conçu pour { $range }, mais { $version } utilise le pack_format { $target }
+compat-no-supported-formats = pack.mcmeta n'a pas de plage supported_formats pour déclarer d'autres versions compatibles
+compat-supported-formats-ignored = supported_formats est ignoré avant la 1.20.2, donc { $version } n'accepte que le pack_format exact
+
+## Avertissements de construction du nid
+
+build-orphan-emissive = La texture émissive { $file } n'a de texture de base dans aucun pack ni dans vanilla
+build-rule-unreadable = Impossible de lire la règle { $rule } : { $error }
+build-rule-missing-file = La règle { $rule } de { $pack } fait référence au fichier manquant { $file }
+build-merge-skipped = { $file } de { $pack } ignoré lors de la fusion : { $error }
+build-mcmeta-not-carried = { $asset } : la texture de { $pack } n'a pas de .mcmeta ; les métadonnées de { $donors } n'ont pas été reprises
+build-mcmeta-not-carried-resized = { $asset } : la texture de { $pack } n'a pas de .mcmeta ; les métadonnées de { $donors } n'ont pas été reprises (dimensions de texture différentes)
+build-mixed-resolutions = Le nid mélange plusieurs résolutions de texture : { $packs }
+build-resolution-mismatch = { $block } mélange plusieurs résolutions : { $textures }
+build-resolution-mismatch-suggest = { $block } mélange plusieurs résolutions : { $textures } ; { $pack } les fournit toutes
+build-resolution-mismatch-more = ... et { $count } autres blocs aux résolutions mélangées
+build-feature-flags = Les packs nécessitent des fonctionnalités expérimentales différentes : { $packs }. Le nid active { $features } et ne se charge que dans les mondes où elles sont toutes actives
+
+## Analyse du contraste
+
+contrast-transparent = La texture n'a aucun pixel opaque à mesurer
+contrast-low-ratio = Le rapport de contraste { $ratio }:1 est inférieur au { $required }:1 recommandé
+contrast-indistinct = Des couleurs distinctes en vision normale sont difficiles à distinguer en cas de { $deficiency }
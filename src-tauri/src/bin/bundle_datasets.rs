@@ -0,0 +1,83 @@
+/**
+ * Standalone binary to write the datasets bundled with the app
+ *
+ * Usage: cargo run --bin bundle_datasets [-- <version>...]
+ *
+ * For each version (default: every release already shipped in
+ * resources/datasets, or the cached vanilla version when none is) this will:
+ * 1. Extract particle physics, block emissions and block animations
+ *    (reusing the extraction caches when they exist)
+ * 2. Write them gzipped to resources/datasets/<dataset>/<version>.json.gz
+ *
+ * Bundle release versions only; snapshots are extracted on demand. Physics
+ * read without Java are partial and are refused.
+ */
+
+use std::path::{Path, PathBuf};
+use weaverbird_lib::commands::get_cached_vanilla_version_impl;
+use weaverbird_lib::util::block_animation_extractor::extract_block_animations;
+use weaverbird_lib::util::block_particle_extractor::extract_block_emissions;
+use weaverbird_lib::util::offline::{bundled_versions, write_bundled, Dataset};
+use weaverbird_lib::util::particle_cache;
+use weaverbird_lib::util::particle_physics_extractor::extract_particle_physics;
+
+fn main() {
+    weaverbird_lib::util::logging::init();
+
+    let output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("resources/datasets");
+    let mut versions: Vec<String> = std::env::args().skip(1).collect();
+    if versions.is_empty() {
+        // Refresh every shipped release, so each one gets all datasets
+        versions = bundled_versions(&output_dir);
+    }
+    if versions.is_empty() {
+        match get_cached_vanilla_version_impl() {
+            Ok(Some(v)) => versions.push(v),
+            Ok(None) => {
+                eprintln!("Error: No version given and no cached vanilla version found.");
+                std::process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("Error getting cached vanilla version: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let mut failed = false;
+    for version in &versions {
+        println!("[bundle_datasets] Bundling datasets for Minecraft {}", version);
+        if let Err(e) = runtime.block_on(bundle_version(version, &output_dir)) {
+            eprintln!("Error bundling {}: {:#}", version, e);
+            failed = true;
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+    println!("\n[bundle_datasets] Done! Datasets written to {:?}", output_dir);
+}
+
+async fn bundle_version(version: &str, output_dir: &Path) -> anyhow::Result<()> {
+    let jar_path = particle_cache::resolve_jar_path(version)?;
+
+    let physics = extract_particle_physics(&jar_path, version).await?;
+    if physics.partial {
+        anyhow::bail!("particle physics are partial (no Java); install Java and retry");
+    }
+    let path = write_bundled(Dataset::ParticlePhysics, version, &physics, output_dir)?;
+    println!("  {} particles -> {}", physics.particles.len(), path.display());
+
+    let emissions = extract_block_emissions(&jar_path, version).await?;
+    let path = write_bundled(Dataset::BlockEmissions, version, &emissions, output_dir)?;
+    println!("  {} blocks -> {}", emissions.blocks.len(), path.display());
+
+    let animations = extract_block_animations(&jar_path, version).await?;
+    let path = write_bundled(Dataset::BlockAnimations, version, &animations, output_dir)?;
+    println!("  {} entities -> {}", animations.entities.len(), path.display());
+
+    Ok(())
+}
@@ -15,6 +15,8 @@ use weaverbird_lib::util::particle_cache;
 use weaverbird_lib::util::block_animation_extractor::extract_block_animations;
 
 fn main() {
+    weaverbird_lib::util::logging::init();
+
     println!("[extract_block_animations] Starting extraction...");
 
     // Get cached vanilla version
@@ -47,6 +49,9 @@ fn main() {
     match runtime.block_on(extract_block_animations(&jar_path, &version)) {
         Ok(animations) => {
             println!("\n[extract_block_animations] Successfully extracted {} entities", animations.entities.len());
+            if let Ok(dir) = weaverbird_lib::util::entity_geometry::geometry_dir(&version) {
+                println!("[extract_block_animations] Entity geometry written to {}", dir.display());
+            }
 
             // Show examples
             if let Some(bell) = animations.entities.get("bell") {
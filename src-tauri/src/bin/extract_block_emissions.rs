@@ -13,6 +13,8 @@ use weaverbird_lib::commands::extract_block_emissions_impl;
 use weaverbird_lib::commands::get_cached_vanilla_version_impl;
 
 fn main() {
+    weaverbird_lib::util::logging::init();
+
     println!("[extract_block_emissions] Starting extraction...");
 
     // Get cached vanilla version
@@ -14,6 +14,8 @@ use weaverbird_lib::util::particle_cache;
 use weaverbird_lib::util::particle_physics_extractor::extract_particle_physics;
 
 fn main() {
+    weaverbird_lib::util::logging::init();
+
     println!("[extract_particle_physics] Starting extraction...");
 
     // Get cached vanilla version
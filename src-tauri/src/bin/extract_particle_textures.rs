@@ -13,6 +13,8 @@ use weaverbird_lib::commands::get_cached_vanilla_version_impl;
 use weaverbird_lib::util::{particle_cache, particle_data};
 
 fn main() {
+    weaverbird_lib::util::logging::init();
+
     println!("[extract_particle_textures] Starting extraction...");
 
     let version = match get_cached_vanilla_version_impl() {
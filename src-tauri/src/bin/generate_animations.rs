@@ -5,6 +5,7 @@
  *
  * This will:
  * 1. Load cached animation data from ~/Library/Caches/weaverbird/block_animations/
+ *    (or the bundled dataset when nothing was extracted)
  * 2. Generate TypeScript files in src/constants/animations/generated/
  * 3. Create individual files per entity (bell.ts, chest.ts, etc.)
  * 4. Create index.ts with VANILLA_ANIMATIONS export
@@ -13,9 +14,12 @@
 use weaverbird_lib::commands::get_cached_vanilla_version_impl;
 use weaverbird_lib::util::block_animation_extractor::load_cached_animation_data;
 use weaverbird_lib::util::animation_typescript_gen::generate_animation_typescript;
+use weaverbird_lib::util::offline::{self, Dataset};
 use std::path::PathBuf;
 
 fn main() {
+    weaverbird_lib::util::logging::init();
+
     println!("[generate_animations] Generating TypeScript animation files...\n");
 
     // Get cached vanilla version
@@ -33,11 +37,13 @@ fn main() {
 
     println!("[generate_animations] Using Minecraft version: {}", version);
 
-    // Load cached animation data
-    let animations = match load_cached_animation_data(&version) {
+    // Load cached animation data, or the copy bundled for this release
+    let cached = load_cached_animation_data(&version)
+        .map(|data| data.or_else(|| offline::load_bundled(Dataset::BlockAnimations, &version)));
+    let animations = match cached {
         Ok(Some(data)) => data,
         Ok(None) => {
-            eprintln!("Error: No cached animation data found for version {}", version);
+            eprintln!("Error: No cached or bundled animation data found for version {}", version);
             eprintln!("Run 'cargo run --bin extract_block_animations' first to extract animations");
             std::process::exit(1);
         }
@@ -47,7 +53,7 @@ fn main() {
         }
     };
 
-    println!("[generate_animations] Loaded {} entities from cache", animations.entities.len());
+    println!("[generate_animations] Loaded {} entities", animations.entities.len());
 
     // Determine output directory (src/constants/animations/generated)
     let output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -4,6 +4,8 @@
 use weaverbird_lib::util::particle_cache;
 
 fn main() {
+    weaverbird_lib::util::logging::init();
+
     println!("Generating TypeScript particle data from cached extractions...");
     println!();
 
@@ -1,6 +1,8 @@
 use weaverbird_lib::util::vanilla_textures;
 
 fn main() {
+    weaverbird_lib::util::logging::init();
+
     println!("[init_vanilla_cache] Initializing vanilla textures...");
 
     match vanilla_textures::initialize_vanilla_textures() {
@@ -4,6 +4,8 @@
 use weaverbird_lib::util::particle_cache;
 
 fn main() {
+    weaverbird_lib::util::logging::init();
+
     let args: Vec<String> = std::env::args().skip(1).collect();
     if args.iter().any(|arg| arg == "--help" || arg == "-h") {
         println!("Usage: cargo run --bin rebuild_particle_cache [--full]");
@@ -48,6 +48,8 @@ fn decompile_campfire(jar_path: &Path, version: &str) -> anyhow::Result<PathBuf>
 }
 
 fn main() {
+    weaverbird_lib::util::logging::init();
+
     println!("[show_campfire] Getting Minecraft version...");
 
     let version = match get_cached_vanilla_version_impl() {
@@ -0,0 +1,293 @@
+/**
+ * Headless command line frontend for scanning packs and building a Weaver Nest
+ *
+ * Usage:
+ *   weaverbird-cli scan <packs_dir> [--json <file>]
+ *   weaverbird-cli build <packs_dir> --order <id,id,...> --out <dir>
+ *                  [--zip] [--dedupe] [--target <version>] [--highest-resolution]
+ *   weaverbird-cli validate <packs_dir> --target <version> [--pack <id>]...
+ *   weaverbird-cli doctor <packs_dir> --pack <id> [--json <file>] [--strict]
+ *   weaverbird-cli extract-physics <version>
+ *
+ * Runs the same code as the app's commands without opening a window, so
+ * pack merging can be automated (e.g. in CI). `--order` lists pack IDs as
+ * printed by `scan`, highest priority first. `validate` exits with status 1
+ * when a pack doesn't load cleanly in the target version, and `doctor` when a
+ * pack has broken references (with `--strict`, unused files too). Scan and
+ * doctor reports are written to a file rather than stdout, which also carries
+ * the progress log.
+ */
+use std::collections::HashMap;
+use std::process::ExitCode;
+use weaverbird_lib::commands::{
+    build_weaver_nest_impl, check_pack_integrity_impl, extract_particle_physics_impl,
+    scan_packs_folder_impl, validate_pack_compatibility_impl, BuildWeaverNestRequest,
+};
+use weaverbird_lib::model::{ConflictStrategy, NestCompression};
+use weaverbird_lib::util::pack_format::{self, CompatibilityStatus};
+
+const USAGE: &str = "Usage:
+  weaverbird-cli scan <packs_dir> [--json <file>]
+  weaverbird-cli build <packs_dir> --order <id,id,...> --out <dir>
+                 [--zip] [--dedupe] [--target <version>] [--highest-resolution]
+  weaverbird-cli validate <packs_dir> --target <version> [--pack <id>]...
+  weaverbird-cli doctor <packs_dir> --pack <id> [--json <file>] [--strict]
+  weaverbird-cli extract-physics <version>";
+
+/// Positional arguments, flags and `--name value` options of a subcommand
+struct Args {
+    positional: Vec<String>,
+    flags: Vec<String>,
+    options: HashMap<String, Vec<String>>,
+}
+
+impl Args {
+    /// Split arguments, rejecting options not in `with_value` (taking a value)
+    /// or `flags`
+    fn parse(args: &[String], with_value: &[&str], flags: &[&str]) -> Result<Self, String> {
+        let mut parsed = Args {
+            positional: Vec::new(),
+            flags: Vec::new(),
+            options: HashMap::new(),
+        };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.strip_prefix("--") {
+                Some(name) if with_value.contains(&name) => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| format!("--{} needs a value", name))?;
+                    parsed
+                        .options
+                        .entry(name.to_string())
+                        .or_default()
+                        .push(value.clone());
+                }
+                Some(name) if flags.contains(&name) => parsed.flags.push(name.to_string()),
+                Some(name) => return Err(format!("Unknown option --{}", name)),
+                None => parsed.positional.push(arg.clone()),
+            }
+        }
+        Ok(parsed)
+    }
+
+    fn flag(&self, name: &str) -> bool {
+        self.flags.iter().any(|flag| flag == name)
+    }
+
+    fn option(&self, name: &str) -> Option<&str> {
+        self.options
+            .get(name)
+            .and_then(|values| values.last())
+            .map(String::as_str)
+    }
+
+    fn required(&self, name: &str) -> Result<&str, String> {
+        self.option(name)
+            .ok_or_else(|| format!("--{} is required", name))
+    }
+
+    /// The single positional argument, named `what` in errors
+    fn single(&self, what: &str) -> Result<&str, String> {
+        match self.positional.as_slice() {
+            [value] => Ok(value),
+            [] => Err(format!("Missing {}", what)),
+            _ => Err(format!("Expected one {}", what)),
+        }
+    }
+}
+
+fn scan(args: &Args) -> Result<bool, String> {
+    let packs_dir = args.single("packs directory")?;
+    let result = scan_packs_folder_impl(packs_dir.to_string()).map_err(|e| e.to_string())?;
+
+    if let Some(json_path) = args.option("json") {
+        let json = serde_json::to_string_pretty(&result).map_err(|e| e.to_string())?;
+        std::fs::write(json_path, json).map_err(|e| format!("{}: {}", json_path, e))?;
+    }
+
+    println!(
+        "{} pack(s), {} asset(s)",
+        result.packs.len(),
+        result.assets.len()
+    );
+    for pack in &result.packs {
+        println!(
+            "  {}\t{}{}",
+            pack.id,
+            pack.name,
+            if pack.is_zip { " (zip)" } else { "" }
+        );
+    }
+    for duplicate in &result.duplicate_packs {
+        println!("Duplicate packs: {}", duplicate.pack_ids.join(", "));
+    }
+    Ok(true)
+}
+
+fn build(args: &Args) -> Result<bool, String> {
+    let packs_dir = args.single("packs directory")?;
+    let pack_order: Vec<String> = args
+        .required("order")?
+        .split(',')
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .collect();
+    let target_pack_format = match args.option("target") {
+        Some(version) => Some(
+            pack_format::pack_format_for_version(version)
+                .ok_or_else(|| format!("Unknown Minecraft version: {}", version))?,
+        ),
+        None => None,
+    };
+
+    let output_dir = args.required("out")?;
+    std::fs::create_dir_all(output_dir).map_err(|e| format!("{}: {}", output_dir, e))?;
+
+    let request = BuildWeaverNestRequest {
+        packs_dir: packs_dir.to_string(),
+        pack_order,
+        overrides: HashMap::new(),
+        output_dir: output_dir.to_string(),
+        compression: args.flag("zip").then(NestCompression::default),
+        target_pack_format,
+        mcmeta_pairing: Default::default(),
+        dedupe_identical: args.flag("dedupe"),
+        conflict_strategy: if args.flag("highest-resolution") {
+            ConflictStrategy::HighestResolution
+        } else {
+            ConflictStrategy::PackOrder
+        },
+    };
+    let message = build_weaver_nest_impl(request).map_err(|e| e.to_string())?;
+    println!("{}", message);
+    Ok(true)
+}
+
+fn validate(args: &Args) -> Result<bool, String> {
+    let packs_dir = args.single("packs directory")?;
+    let target = args.required("target")?;
+    let pack_ids = match args.options.get("pack") {
+        Some(ids) => ids.clone(),
+        None => scan_packs_folder_impl(packs_dir.to_string())
+            .map_err(|e| e.to_string())?
+            .packs
+            .into_iter()
+            // Vanilla and the built-in packs always load
+            .filter(|pack| !pack.id.starts_with("minecraft:"))
+            .map(|pack| pack.id)
+            .collect(),
+    };
+
+    let mut all_compatible = true;
+    for pack_id in pack_ids {
+        let report = validate_pack_compatibility_impl(
+            pack_id.clone(),
+            packs_dir.to_string(),
+            target.to_string(),
+        )
+        .map_err(|e| e.to_string())?;
+        let compatible = report.status == CompatibilityStatus::Compatible;
+        all_compatible &= compatible;
+        println!(
+            "{} {}: {:?}",
+            if compatible { "ok  " } else { "FAIL" },
+            pack_id,
+            report.status
+        );
+        for issue in &report.issues {
+            println!("       {}", issue);
+        }
+    }
+    Ok(all_compatible)
+}
+
+fn doctor(args: &Args) -> Result<bool, String> {
+    let packs_dir = args.single("packs directory")?;
+    let pack_id = args.required("pack")?;
+    let report = check_pack_integrity_impl(pack_id.to_string(), packs_dir.to_string(), None)
+        .map_err(|e| e.to_string())?;
+
+    if let Some(json_path) = args.option("json") {
+        let json = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+        std::fs::write(json_path, json).map_err(|e| format!("{}: {}", json_path, e))?;
+    }
+
+    let (cleanup, broken): (Vec<_>, Vec<_>) =
+        report.issues.iter().partition(|issue| issue.is_cleanup());
+    println!(
+        "{}: {} file(s), {} broken reference(s), {} unused file(s){}",
+        report.pack_id,
+        report.files_checked,
+        broken.len(),
+        cleanup.len(),
+        if report.truncated { " (truncated)" } else { "" }
+    );
+    for issue in broken.iter().chain(&cleanup) {
+        println!("  {}", issue);
+    }
+    Ok(broken.is_empty() && (cleanup.is_empty() || !args.flag("strict")))
+}
+
+fn extract_physics(args: &Args) -> Result<bool, String> {
+    let version = args.single("Minecraft version")?;
+    let runtime = tokio::runtime::Runtime::new().map_err(|e| e.to_string())?;
+    let physics = runtime
+        .block_on(extract_particle_physics_impl(version.to_string()))
+        .map_err(|e| e.to_string())?;
+    println!(
+        "Extracted physics for {} particle type(s)",
+        physics.particles.len()
+    );
+    Ok(true)
+}
+
+fn main() -> ExitCode {
+    weaverbird_lib::util::logging::init();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some((command, rest)) = args.split_first() else {
+        eprintln!("{}", USAGE);
+        return ExitCode::from(2);
+    };
+
+    let parsed = match command.as_str() {
+        "scan" => Args::parse(rest, &["json"], &[]),
+        "build" => Args::parse(
+            rest,
+            &["order", "out", "target"],
+            &["zip", "dedupe", "highest-resolution"],
+        ),
+        "validate" => Args::parse(rest, &["target", "pack"], &[]),
+        "doctor" => Args::parse(rest, &["pack", "json"], &["strict"]),
+        "extract-physics" => Args::parse(rest, &[], &[]),
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        _ => Err(format!("Unknown command: {}", command)),
+    };
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let outcome = match command.as_str() {
+        "scan" => scan(&parsed),
+        "build" => build(&parsed),
+        "validate" => validate(&parsed),
+        "doctor" => doctor(&parsed),
+        _ => extract_physics(&parsed),
+    };
+    match outcome {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -2,18 +2,54 @@
 pub mod packs;
 
 pub use packs::{
-    build_weaver_nest_impl, check_minecraft_installed_impl, detect_launchers_impl,
-    extract_block_emissions_impl, extract_particle_physics_impl,
-    generate_particle_typescript_impl, get_block_emissions_impl,
-    get_block_state_schema_impl, get_cached_vanilla_version_impl, get_colormap_path_impl,
-    get_default_packs_dir_impl, get_entity_version_variants_impl,
-    get_launcher_resourcepacks_dir_impl, get_pack_texture_path_impl,
-    get_particle_data_impl, get_particle_data_for_version_impl, get_particle_physics_impl,
-    get_suggested_minecraft_paths_impl, get_vanilla_mcmeta_path_impl,
-    get_vanilla_texture_path_impl, identify_launcher_impl,
-    initialize_vanilla_textures_from_custom_dir_impl, initialize_vanilla_textures_impl,
-    is_block_emissions_cached_impl, is_particle_physics_cached_impl,
-    list_available_minecraft_versions_impl, load_model_json_impl, read_block_model_impl,
-    read_pack_file_impl, read_vanilla_jem_impl, resolve_block_state_impl, scan_packs_folder_impl,
-    set_vanilla_texture_version_impl, BuildWeaverNestRequest,
+    analyze_contrast_impl, build_weaver_nest_impl, cancel_job_impl, check_minecraft_installed_impl,
+    check_pack_integrity_impl, clear_cache_impl, close_edit_session_impl,
+    compare_vanilla_asset_impl, compute_tint_impl,
+    compute_block_tint_impl, convert_pack_format_impl,
+    copy_vanilla_asset_to_pack_impl, delete_pack_impl, detect_launchers_impl,
+    diagnose_model_impl, ensure_particle_assets, evaluate_cem_animations_impl,
+    export_block_model_impl, extract_block_emissions_impl,
+    extract_particle_physics_impl, find_duplicate_assets_impl, generate_particle_typescript_impl,
+    get_animation_frames_impl, get_asset_graph_impl, get_asset_thumbnail_impl,
+    get_block_emissions_impl,
+    get_block_state_schema_impl, get_cache_maintenance_settings_impl, get_cache_usage_impl,
+    get_cached_vanilla_version_impl, get_colormap_path_impl,
+    get_default_packs_dir_impl, get_download_settings_impl, get_emitter_preset_impl,
+    get_entity_textures_impl, get_entity_version_variants_impl,
+    get_indexed_providers_impl, get_job_impl, get_launcher_resourcepacks_dir_impl,
+    get_log_level_impl, get_low_memory_mode_impl,
+    get_offline_status_impl, get_pack_atlases_impl, get_pack_compatibility_impl,
+    get_pack_texture_path_impl,
+    get_pack_texture_paths_batch_impl, get_particle_data_impl, get_particle_data_for_version_impl,
+    get_particle_definition_impl, get_particle_physics_impl, get_perf_metrics_impl,
+    get_random_entity_variants_impl,
+    get_recent_logs_impl, get_shared_cache_status_impl, get_suggested_minecraft_paths_impl,
+    get_vanilla_mcmeta_path_impl, get_vanilla_texture_categories_impl,
+    get_vanilla_texture_path_impl, get_vanilla_texture_provenance_impl, identify_launcher_impl,
+    import_bbmodel_impl, import_pack_impl, initialize_vanilla_textures_from_custom_dir_impl,
+    initialize_vanilla_textures_impl,
+    install_nest_to_launcher_impl, is_block_emissions_cached_impl, is_particle_physics_cached_impl,
+    lint_pack_impl, list_available_minecraft_versions_impl, list_block_state_permutations_impl,
+    list_cached_vanilla_versions_impl,
+    list_interrupted_builds_impl, list_jobs_impl, list_launcher_instances_impl,
+    load_model_json_impl, load_scan_index_impl, open_asset_in_editor_impl, pick_entity_variant_impl,
+    read_block_model_impl,
+    read_pack_file_impl, read_vanilla_jem_impl, recover_interrupted_builds_impl, refresh_asset_impl,
+    rename_pack_impl, repack_pack_impl, resolve_block_state_impl,
+    resolve_block_states_batch_impl, resolve_item_model_impl, resolve_particle_textures_impl,
+    resolve_texture_paths_bulk_impl, run_cache_maintenance_impl, run_cancellable,
+    run_perf_selftest_impl, sample_animation_frame_impl,
+    sample_quad_size_curve_impl, scan_packs_folder_impl, search_assets_impl,
+    search_indexed_assets_impl, set_backend_locale_impl, set_cache_maintenance_settings_impl,
+    set_download_settings_impl, set_log_level_impl, set_low_memory_mode_impl,
+    set_offline_mode_impl, set_shared_cache_dir_impl, set_vanilla_texture_categories_impl,
+    set_vanilla_texture_version_impl,
+    submit_job_impl, texture_cache_dir, unpack_pack_impl, validate_jem_impl,
+    validate_pack_compatibility_impl,
+    write_back_edited_asset_impl,
+    AssetUpdatedEvent, BatchResult, BlockStateRequest, BuildWeaverNestRequest, JobRequest,
+    PackImportResult, PackLibraryChange, ResolvedTexturePath, TexturePathRequest,
+    VanillaTextureProvenance,
 };
+#[cfg(feature = "selftest")]
+pub use packs::run_selftest_suite_impl;
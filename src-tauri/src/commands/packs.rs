@@ -5,15 +5,23 @@
 /// - Validates all inputs before processing
 /// - Separates concerns: validation → execution → response
 /// - Reduces boilerplate with validation module
-use crate::model::{OverrideSelection, ScanResult};
+use crate::model::{
+    AssetRecord, AtlasDefinition, MissingAssetPolicy, OverrideSelection, ScanResult, TargetVersion,
+};
 use crate::util::{
-    asset_indexer, launcher_detection, mc_paths, pack_scanner, particle_cache, particle_data,
-    texture_index, vanilla_textures, weaver_nest,
+    asset_editor, asset_graph, asset_hashes, asset_indexer, asset_protocol, asset_search,
+    build_journal, cache_location, cache_maintenance, cache_usage, cancellation, contrast, ctm,
+    download, i18n, jobs, launcher_detection, launcher_instances, logging, low_memory, mc_paths,
+    nest_install, offline, pack_converter, pack_files, pack_format, pack_scanner, particle_cache,
+    particle_data, perf_metrics, perf_selftest, random_entities, scan_index, texture_animation,
+    texture_index, texture_info, thumbnails, vanilla_diff, vanilla_textures, weaver_nest,
 };
 use crate::{validation, AppError};
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BuildWeaverNestRequest {
@@ -21,23 +29,425 @@ pub struct BuildWeaverNestRequest {
     pub pack_order: Vec<String>,
     pub overrides: HashMap<String, OverrideSelection>, // asset_id -> override payload
     pub output_dir: String,
+    /// Zip compression settings; when omitted the nest is written as a folder
+    #[serde(default)]
+    pub compression: Option<crate::model::NestCompression>,
+    /// pack_format of the target Minecraft version; when set, pack overlays are
+    /// flattened for it instead of being carried into the nest
+    #[serde(default)]
+    pub target_pack_format: Option<u32>,
+    /// How texture .mcmeta files are paired with the winning textures
+    #[serde(default)]
+    pub mcmeta_pairing: crate::model::McmetaPairing,
+    /// Leave out files byte-identical to their vanilla copy; the game falls
+    /// back to vanilla for them anyway
+    #[serde(default)]
+    pub dedupe_identical: bool,
+    /// Which provider wins an asset without an override
+    #[serde(default)]
+    pub conflict_strategy: crate::model::ConflictStrategy,
+}
+
+/// One blockstate to resolve in a `resolve_block_states_batch` call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockStateRequest {
+    pub pack_id: String,
+    pub block_id: String,
+    #[serde(default)]
+    pub state_props: Option<HashMap<String, String>>,
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Pick weighted variants as the game does at this position, instead of by seed
+    #[serde(default)]
+    pub position: Option<crate::util::blockstates::BlockPosition>,
+}
+
+/// One texture to locate in a `get_pack_texture_paths_batch` call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TexturePathRequest {
+    pub pack_path: String,
+    pub asset_id: String,
+    pub is_zip: bool,
+    #[serde(default)]
+    pub version_folders: Option<Vec<String>>,
+}
+
+/// Where `resolve_texture_paths_bulk` found an asset's winning texture
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedTexturePath {
+    /// Pack the texture comes from; "minecraft:vanilla" when no pack has it
+    pub pack_id: String,
+    /// Full path to the texture file (extracted to the texture cache for ZIPs)
+    pub path: String,
+}
+
+/// Outcome of one request in a batch command; exactly one field is set
+///
+/// A failing request doesn't fail the batch, so results stay index-aligned
+/// with the requests.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchResult<T> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<AppError>,
+}
+
+impl<T> From<Result<T, AppError>> for BatchResult<T> {
+    fn from(outcome: Result<T, AppError>) -> Self {
+        match outcome {
+            Ok(result) => BatchResult {
+                result: Some(result),
+                error: None,
+            },
+            Err(error) => BatchResult {
+                result: None,
+                error: Some(error),
+            },
+        }
+    }
 }
 
 /// Create a virtual vanilla pack entry
 fn create_vanilla_pack() -> Result<crate::model::PackMeta, AppError> {
     let cache_dir = vanilla_textures::get_vanilla_cache_dir()
         .map_err(|e| AppError::io(format!("Failed to get vanilla cache dir: {}", e)))?;
+    Ok(vanilla_pack_at(&cache_dir))
+}
 
-    Ok(crate::model::PackMeta {
+/// The vanilla pack read from one version's cache directory
+fn vanilla_pack_at(cache_dir: &std::path::Path) -> crate::model::PackMeta {
+    crate::model::PackMeta {
         id: "minecraft:vanilla".to_string(),
         name: "Minecraft (Vanilla)".to_string(),
         path: cache_dir.to_string_lossy().to_string(),
         size: 0,
         is_zip: false,
         description: Some("Default Minecraft textures".to_string()),
-        icon_data: None,
+        has_icon: false,
         pack_format: None, // Vanilla textures don't have a pack format
-    })
+        supported_formats: None,
+        overlays: Vec::new(),
+        features: Vec::new(),
+        texture_resolution: Some(16),
+        cem_dialect: None,
+        builtin: false,
+        credits: None,
+        quarantine: Vec::new(),
+        resolved_path: None,
+    }
+}
+
+/// Create read-only entries for the packs built into the Minecraft JAR
+///
+/// IDs are "minecraft:<folder>" ("minecraft:programmer_art"). The packs are
+/// extracted from the cached vanilla version's JAR on first use; without one
+/// (or with a JAR that predates built-in packs) there are none.
+fn create_builtin_packs() -> Vec<crate::model::PackMeta> {
+    let builtin_dir = match vanilla_textures::ensure_builtin_packs() {
+        Ok(Some(builtin_dir)) if builtin_dir.is_dir() => builtin_dir,
+        Ok(_) => return Vec::new(),
+        Err(e) => {
+            warn!("Built-in packs unavailable: {:#}", e);
+            return Vec::new();
+        }
+    };
+    let packs = match pack_scanner::scan_packs(&builtin_dir.to_string_lossy()) {
+        Ok(packs) => packs,
+        Err(e) => {
+            warn!("Failed to scan built-in packs: {}", e);
+            return Vec::new();
+        }
+    };
+    packs
+        .into_iter()
+        .map(|pack| crate::model::PackMeta {
+            id: format!("minecraft:{}", pack.id),
+            // "programmer_art" -> "Programmer Art"
+            name: pack
+                .id
+                .split('_')
+                .map(|word| {
+                    let mut chars = word.chars();
+                    chars
+                        .next()
+                        .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                        .unwrap_or_default()
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+            builtin: true,
+            ..pack
+        })
+        .collect()
+}
+
+/// Scan the packs directory and add the packs built into the Minecraft JAR
+fn scan_library(packs_dir: &str) -> anyhow::Result<Vec<crate::model::PackMeta>> {
+    let mut packs = pack_scanner::scan_packs(packs_dir)?;
+    packs.extend(create_builtin_packs());
+    Ok(packs)
+}
+
+/// Create the vanilla pack for a workspace's target version
+///
+/// Without a target this is the cached vanilla pack. With one, it is read from
+/// the target version's cache, which may sit next to the active version's;
+/// results never silently come from another version's vanilla assets.
+fn vanilla_pack_for_target(
+    target: Option<&TargetVersion>,
+) -> Result<crate::model::PackMeta, AppError> {
+    let Some(target) = target else {
+        return create_vanilla_pack();
+    };
+    let mut vanilla_pack = if vanilla_textures::is_version_extracted(&target.version) {
+        let cache_dir = vanilla_textures::get_version_cache_dir(&target.version)
+            .map_err(|e| AppError::io(format!("Failed to get vanilla cache dir: {}", e)))?;
+        vanilla_pack_at(&cache_dir)
+    } else {
+        let cached = vanilla_textures::get_cached_version()
+            .map_err(|e| AppError::io(format!("Failed to read cached version: {}", e)))?;
+        validation::validate_target_version(target, cached.as_deref())?;
+        create_vanilla_pack()?
+    };
+    vanilla_pack.pack_format = target.pack_format();
+    Ok(vanilla_pack)
+}
+
+/// Look up a pack by ID, returning the virtual vanilla pack for "minecraft:vanilla"
+fn find_pack(
+    pack_id: &str,
+    packs_dir: &str,
+    vanilla_pack: &crate::model::PackMeta,
+) -> Result<crate::model::PackMeta, AppError> {
+    if pack_id == vanilla_pack.id {
+        return Ok(vanilla_pack.clone());
+    }
+
+    let packs = scan_library(packs_dir)
+        .map_err(|e| AppError::scan(format!("Failed to scan packs: {}", e)))?;
+    packs
+        .into_iter()
+        .find(|p| p.id == pack_id)
+        .ok_or_else(|| AppError::pack_not_found(pack_id))
+}
+
+/// Scan packs and arrange them by priority (highest first)
+///
+/// Packs missing from `pack_order` are skipped. Vanilla sits wherever it appears in
+/// `pack_order`, or at the end (lowest priority) if it isn't listed.
+fn ordered_packs(
+    pack_order: &[String],
+    packs_dir: &str,
+) -> Result<Vec<crate::model::PackMeta>, AppError> {
+    let vanilla_pack = create_vanilla_pack()?;
+    let packs = scan_library(packs_dir)
+        .map_err(|e| AppError::scan(format!("Failed to scan packs: {}", e)))?;
+
+    let mut ordered: Vec<crate::model::PackMeta> = pack_order
+        .iter()
+        .filter_map(|id| {
+            if *id == vanilla_pack.id {
+                Some(vanilla_pack.clone())
+            } else {
+                packs.iter().find(|p| p.id == *id).cloned()
+            }
+        })
+        .collect();
+
+    if !ordered.iter().any(|p| p.id == vanilla_pack.id) {
+        ordered.push(vanilla_pack);
+    }
+
+    Ok(ordered)
+}
+
+/// Run a long command impl as a job that `cancel_job` can stop
+///
+/// Without a job ID the impl simply runs. A job cancelled before it finished
+/// fails with a CANCELLED error instead of the error of the interrupted worker.
+///
+/// # Arguments
+/// * `job_id` - ID chosen by the caller, unique among running jobs
+/// * `run` - The command impl
+pub fn run_cancellable<T>(
+    job_id: Option<String>,
+    run: impl FnOnce() -> Result<T, AppError>,
+) -> Result<T, AppError> {
+    let Some(job_id) = job_id else {
+        return run();
+    };
+    match cancellation::run_job(&job_id, run) {
+        (Err(_), true) => Err(AppError::cancelled(format!("Job {} was cancelled", job_id))),
+        (result, _) => result,
+    }
+}
+
+/// IO error for a failed extraction, or the coded error of a known failure
+/// (busy cache, corrupt ZIP, missing mappings or Java) the UI can help with
+fn extraction_error(err: anyhow::Error, context: &str) -> AppError {
+    AppError::known(&err).unwrap_or_else(|| AppError::io(format!("{}: {:#}", context, err)))
+}
+
+/// Cancel a scan, nest build or vanilla extraction started with a job ID, or
+/// a background job; a queued job is dropped before it starts
+///
+/// A cancelled nest build stays in its journal like an interrupted one, so it
+/// can be resumed or rolled back with `recover_interrupted_builds`.
+///
+/// # Arguments
+/// * `job_id` - ID the command was started with
+///
+/// # Returns
+/// Whether a running job was found; false if it already finished
+pub fn cancel_job_impl(job_id: String) -> Result<bool, AppError> {
+    Ok(jobs::cancel(&job_id))
+}
+
+/// A heavy command to run as a background job
+#[derive(Debug, Clone, Deserialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "snake_case",
+    rename_all_fields = "camelCase"
+)]
+pub enum JobRequest {
+    /// `scan_packs_folder`
+    Scan { packs_dir: String },
+    /// `build_weaver_nest`
+    Build { request: BuildWeaverNestRequest },
+    /// `initialize_vanilla_textures`
+    InitializeVanillaTextures {
+        #[serde(default)]
+        categories: Option<Vec<vanilla_textures::TextureCategory>>,
+    },
+    /// `initialize_vanilla_textures_from_custom_dir`
+    InitializeVanillaTexturesFromDir { minecraft_dir: String },
+    /// `set_vanilla_texture_version`
+    SetVanillaTextureVersion { version: String },
+    /// `extract_particle_physics`
+    ExtractParticlePhysics { version: String },
+    /// `extract_block_emissions`
+    ExtractBlockEmissions { version: String },
+}
+
+impl JobRequest {
+    fn kind(&self) -> jobs::JobKind {
+        match self {
+            JobRequest::Scan { .. } => jobs::JobKind::Scan,
+            JobRequest::Build { .. } => jobs::JobKind::Build,
+            JobRequest::InitializeVanillaTextures { .. }
+            | JobRequest::InitializeVanillaTexturesFromDir { .. }
+            | JobRequest::SetVanillaTextureVersion { .. } => jobs::JobKind::VanillaExtraction,
+            JobRequest::ExtractParticlePhysics { .. }
+            | JobRequest::ExtractBlockEmissions { .. } => jobs::JobKind::Decompilation,
+        }
+    }
+
+    /// Run the command, returning what it returns as JSON
+    fn run(self, context: &jobs::JobContext) -> Result<serde_json::Value, AppError> {
+        let progress = {
+            let context = context.clone();
+            std::sync::Arc::new(move |current: usize, total: usize| {
+                context.report_progress("Extracting", current as u64, total as u64);
+            }) as vanilla_textures::ProgressCallback
+        };
+        let extracted = |path: anyhow::Result<PathBuf>| -> Result<serde_json::Value, AppError> {
+            let path = path.map_err(|e| {
+                AppError::io(format!("Failed to initialize vanilla textures: {}", e))
+            })?;
+            if let Ok(Some(version)) = get_cached_vanilla_version_impl() {
+                block_on(ensure_particle_assets(context.id(), &version))?;
+            }
+            Ok(serde_json::json!(path.to_string_lossy()))
+        };
+
+        match self {
+            JobRequest::Scan { packs_dir } => {
+                Ok(serde_json::to_value(scan_packs_folder_impl(packs_dir)?)?)
+            }
+            JobRequest::Build { request } => {
+                Ok(serde_json::to_value(build_weaver_nest_impl(request)?)?)
+            }
+            JobRequest::InitializeVanillaTextures { categories } => {
+                if let Some(categories) = categories {
+                    vanilla_textures::set_texture_categories(&categories);
+                }
+                extracted(vanilla_textures::initialize_vanilla_textures_with_progress(
+                    Some(progress),
+                ))
+            }
+            JobRequest::InitializeVanillaTexturesFromDir { minecraft_dir } => extracted(
+                vanilla_textures::initialize_vanilla_textures_from_dir_with_progress(
+                    Path::new(&minecraft_dir),
+                    Some(progress),
+                ),
+            ),
+            JobRequest::SetVanillaTextureVersion { version } => extracted(
+                vanilla_textures::extract_vanilla_textures_for_version_with_progress(
+                    &version,
+                    Some(progress),
+                ),
+            ),
+            JobRequest::ExtractParticlePhysics { version } => Ok(serde_json::to_value(block_on(
+                extract_particle_physics_impl(version),
+            )??)?),
+            JobRequest::ExtractBlockEmissions { version } => Ok(serde_json::to_value(block_on(
+                extract_block_emissions_impl(version),
+            )??)?),
+        }
+    }
+}
+
+/// Run an async command on the job's worker thread, which has no runtime
+fn block_on<F: std::future::Future>(future: F) -> Result<F::Output, AppError> {
+    let runtime = tokio::runtime::Runtime::new()
+        .map_err(|e| AppError::internal("Failed to start async runtime", e.to_string()))?;
+    Ok(runtime.block_on(future))
+}
+
+/// Queue a heavy command as a background job
+///
+/// Jobs of the same kind (scans, builds, vanilla extractions, decompilations)
+/// run one at a time in submission order, so concurrent extractions can't
+/// corrupt the vanilla cache. Follow the job with `get_job` or the
+/// `job-updated` events, and stop it with `cancel_job`.
+///
+/// # Arguments
+/// * `request` - The command and its arguments
+///
+/// # Returns
+/// ID of the queued job
+pub fn submit_job_impl(request: JobRequest) -> Result<String, AppError> {
+    Ok(jobs::submit(request.kind(), move |context| {
+        request.run(context)
+    }))
+}
+
+/// Event emitted with a job's `JobInfo` whenever its status or progress changes
+pub const JOB_UPDATED_EVENT: &str = "job-updated";
+
+/// Get the latest state of a background job
+///
+/// # Arguments
+/// * `job_id` - ID returned by `submit_job`
+///
+/// # Returns
+/// Status, progress and, once finished, the result or error; None if the job
+/// is unknown or finished long ago
+pub fn get_job_impl(job_id: String) -> Result<Option<jobs::JobInfo>, AppError> {
+    Ok(jobs::get_job(&job_id))
+}
+
+/// List queued, running and recently finished background jobs
+///
+/// # Returns
+/// Jobs in submission order
+pub fn list_jobs_impl() -> Result<Vec<jobs::JobInfo>, AppError> {
+    Ok(jobs::list_jobs())
 }
 
 /// Scan a resource packs directory and return all packs and assets
@@ -52,38 +462,79 @@ pub fn scan_packs_folder_impl(packs_dir: String) -> Result<ScanResult, AppError>
     // Validate input
     validation::validate_directory(&packs_dir, "Packs directory")?;
 
-    // Scan for packs
-    let mut packs =
-        pack_scanner::scan_packs(&packs_dir).map_err(|e| AppError::scan(e.to_string()))?;
+    // Scan for packs (one at a time in low-memory mode); damaged ZIPs are
+    // repaired or left out and reported instead of failing the scan
+    let (mut packs, errors) = low_memory::run(|| pack_scanner::scan_packs_with_errors(&packs_dir))
+        .map_err(|e| AppError::scan(e.to_string()))?;
+    packs.extend(create_builtin_packs());
+
+    // A rescan may have found files that shadow models resolved before
+    crate::util::model_resolver::clear_cache();
+
+    // Report copies of the same pack instead of silently indexing both
+    let duplicate_packs = low_memory::run(|| pack_scanner::find_duplicate_packs(&packs));
+    for duplicate in &duplicate_packs {
+        info!("Duplicate packs: {}", duplicate.pack_ids.join(", "));
+    }
 
     // Add vanilla pack at the end (lowest priority)
     let vanilla_pack = create_vanilla_pack()?;
     packs.push(vanilla_pack);
 
     // Index assets (including vanilla)
-    let (assets, mut providers) = asset_indexer::index_assets(&packs)
-        .map_err(|e| AppError::scan(format!("Asset indexing failed: {}", e)))?;
+    let (assets, mut providers) =
+        low_memory::run(|| asset_indexer::index_assets(&packs)).map_err(|e| {
+            AppError::known(&e)
+                .unwrap_or_else(|| AppError::scan(format!("Asset indexing failed: {}", e)))
+        })?;
 
     // For each asset, ensure vanilla pack is listed as a provider if texture exists
     for asset in &assets {
         let provider_list = providers.entry(asset.id.clone()).or_insert_with(Vec::new);
         if !provider_list.contains(&"minecraft:vanilla".to_string()) {
             // Check if vanilla texture exists for this asset
-            if vanilla_textures::get_vanilla_texture_path(&asset.id).is_ok() {
+            if vanilla_textures::get_vanilla_texture_path(&asset.id, None).is_ok() {
                 provider_list.push("minecraft:vanilla".to_string());
             }
         }
     }
 
-    Ok(ScanResult {
+    let ctm_conflicts = ctm::find_ctm_conflicts(&providers);
+
+    // Texture sizes for resolution badges, from PNG headers only
+    let texture_info =
+        low_memory::run(|| texture_info::read_texture_info(&packs, &assets, &providers));
+
+    let result = ScanResult {
         packs,
         assets,
         providers,
-    })
+        ctm_conflicts,
+        duplicate_packs,
+        texture_info,
+        errors,
+    };
+    asset_search::replace_index(&result.assets);
+    asset_protocol::register_packs(&result.packs);
+
+    // Persist the scan so it can be queried without rescanning; a failure
+    // here only costs the next startup a rescan
+    if let Err(e) =
+        scan_index::ScanIndex::open_default().and_then(|mut index| index.store(&packs_dir, &result))
+    {
+        warn!("Failed to store scan index: {}", e);
+    }
+
+    Ok(result)
 }
 
 /// Build the Weaver Nest optimized resource pack
 ///
+/// The build is journaled in its output directory until it finishes, so a
+/// build interrupted by a crash can be found and resumed or rolled back with
+/// `recover_interrupted_builds`. Re-running a build whose journal is still
+/// there resumes it.
+///
 /// # Errors
 /// - VALIDATION_ERROR: Invalid input parameters
 /// - SCAN_ERROR: Failed to scan packs
@@ -96,34 +547,218 @@ pub fn build_weaver_nest_impl(request: BuildWeaverNestRequest) -> Result<String,
         &request.overrides,
         &request.output_dir,
     )?;
+    if let Some(compression) = &request.compression {
+        validation::validate_compression(compression)?;
+    }
 
-    // Scan packs
-    let packs = pack_scanner::scan_packs(&request.packs_dir)
+    // Scan packs; built-in packs only take part when they are in the order
+    let mut packs = low_memory::run(|| pack_scanner::scan_packs(&request.packs_dir))
         .map_err(|e| AppError::scan(format!("Pack scanning failed: {}", e)))?;
+    if request
+        .pack_order
+        .iter()
+        .any(|id| id.starts_with("minecraft:"))
+    {
+        packs.extend(
+            create_builtin_packs()
+                .into_iter()
+                .filter(|pack| request.pack_order.contains(&pack.id)),
+        );
+    }
 
     if packs.is_empty() {
         return Err(AppError::scan("No packs found in specified directory"));
     }
 
+    // Indexing skips quarantined packs; overrides mustn't pull their files in either
+    if let Some(pack) = packs.iter().find(|pack| {
+        !pack.quarantine.is_empty()
+            && request
+                .overrides
+                .values()
+                .any(|selection| selection.pack_id == pack.id)
+    }) {
+        return Err(AppError::validation(format!(
+            "Pack {} is quarantined for suspicious contents and can't provide overrides",
+            pack.name
+        )));
+    }
+
     // Index assets
-    let (assets, providers) = asset_indexer::index_assets(&packs)
-        .map_err(|e| AppError::scan(format!("Asset indexing failed: {}", e)))?;
+    let (assets, providers) =
+        low_memory::run(|| asset_indexer::index_assets(&packs)).map_err(|e| {
+            AppError::known(&e)
+                .unwrap_or_else(|| AppError::scan(format!("Asset indexing failed: {}", e)))
+        })?;
+
+    // Files identical to vanilla are compared against the vanilla cache
+    let vanilla = create_vanilla_pack()
+        .map_err(|e| warn!("Vanilla textures unavailable: {}", e.message))
+        .ok();
+    let dedupe_against = if request.dedupe_identical {
+        if vanilla.is_none() {
+            warn!("Not deduplicating, vanilla textures unavailable");
+        }
+        vanilla.clone()
+    } else {
+        None
+    };
+
+    let prefer_highest_resolution = match request.conflict_strategy {
+        crate::model::ConflictStrategy::PackOrder => None,
+        crate::model::ConflictStrategy::HighestResolution => Some(low_memory::run(|| {
+            texture_info::read_texture_info(&packs, &assets, &providers)
+        })),
+    };
+
+    let output_dir = Path::new(&request.output_dir);
+    let journaled_request = serde_json::to_value(&request)
+        .map_err(|e| AppError::internal("Failed to serialize build request", e.to_string()))?;
+    build_journal::begin(output_dir, &journaled_request)
+        .map_err(|e| AppError::io(format!("Failed to write build journal: {}", e)))?;
 
     // Build Weaver Nest
-    weaver_nest::build_weaver_nest(
-        &packs,
-        &assets,
-        &providers,
-        &request.pack_order,
-        &request.overrides,
-        &request.output_dir,
-    )
+    let output = low_memory::run(|| {
+        weaver_nest::build_weaver_nest(
+            &packs,
+            &assets,
+            &providers,
+            &request.pack_order,
+            &request.overrides,
+            &request.output_dir,
+            &weaver_nest::NestBuildOptions {
+                compression: request.compression,
+                target_pack_format: request.target_pack_format,
+                mcmeta_pairing: request.mcmeta_pairing,
+                dedupe_against,
+                prefer_highest_resolution,
+                vanilla,
+                journal: true,
+            },
+        )
+    })
     .map_err(|e| AppError::build(format!("Weaver Nest generation failed: {}", e)))?;
+    if let Err(e) = build_journal::finish(output_dir) {
+        warn!("Failed to remove build journal: {}", e);
+    }
 
-    Ok(format!(
-        "Weaver Nest built successfully with {} assets",
-        assets.len()
-    ))
+    let mut message = format!(
+        "Weaver Nest built successfully with {} assets at {}",
+        assets.len(),
+        output.path.display()
+    );
+    if !output.warnings.is_empty() {
+        message.push_str(&format!("\n{} warning(s):", output.warnings.len()));
+        for warning in &output.warnings {
+            message.push_str(&format!("\n- {}", warning));
+        }
+    }
+
+    // Report translation keys replaced by higher-priority packs, capped to keep
+    // the message readable
+    const MAX_LANG_OVERRIDES_LISTED: usize = 20;
+    let lang_overrides: Vec<_> = output
+        .lang_reports
+        .iter()
+        .flat_map(|report| {
+            report
+                .overrides
+                .iter()
+                .map(move |key_override| (&report.file, key_override))
+        })
+        .collect();
+    if !lang_overrides.is_empty() {
+        message.push_str(&format!(
+            "\n{} overridden translation key(s):",
+            lang_overrides.len()
+        ));
+        for (file, key_override) in lang_overrides.iter().take(MAX_LANG_OVERRIDES_LISTED) {
+            message.push_str(&format!(
+                "\n- {} in {}: {} over {}",
+                key_override.key,
+                file,
+                key_override.winner,
+                key_override.overridden.join(", ")
+            ));
+        }
+        if lang_overrides.len() > MAX_LANG_OVERRIDES_LISTED {
+            message.push_str(&format!(
+                "\n... and {} more",
+                lang_overrides.len() - MAX_LANG_OVERRIDES_LISTED
+            ));
+        }
+    }
+    Ok(message)
+}
+
+/// List nest builds that started but never finished (e.g. the app was killed)
+///
+/// # Returns
+/// Interrupted builds with their output directory, request and how many files
+/// they wrote, oldest first
+pub fn list_interrupted_builds_impl() -> Result<Vec<build_journal::InterruptedBuild>, AppError> {
+    build_journal::find_interrupted()
+        .map_err(|e| AppError::io(format!("Failed to read build journals: {}", e)))
+}
+
+/// Resume or roll back interrupted nest builds
+///
+/// Resuming re-runs the journaled request, keeping the files the interrupted
+/// run finished if the packs haven't changed since. Rolling back deletes the
+/// files it wrote; files that were in the output directory before are not
+/// restored.
+///
+/// # Arguments
+/// * `output_dirs` - Output directories of builds from `list_interrupted_builds`
+/// * `action` - Resume or roll back
+///
+/// # Returns
+/// Build message (resume) or summary (rollback) per directory, index-aligned
+/// with `output_dirs`
+pub fn recover_interrupted_builds_impl(
+    output_dirs: Vec<String>,
+    action: build_journal::RecoveryAction,
+) -> Vec<BatchResult<String>> {
+    output_dirs
+        .into_iter()
+        .map(|output_dir| recover_interrupted_build(&output_dir, action).into())
+        .collect()
+}
+
+fn recover_interrupted_build(
+    output_dir: &str,
+    action: build_journal::RecoveryAction,
+) -> Result<String, AppError> {
+    let journal = build_journal::read_journal(Path::new(output_dir))
+        .map_err(|e| AppError::io(format!("Failed to read build journal: {}", e)))?
+        .ok_or_else(|| AppError::validation(format!("No interrupted build in {}", output_dir)))?;
+
+    match action {
+        build_journal::RecoveryAction::Resume => {
+            let request: BuildWeaverNestRequest =
+                serde_json::from_value(journal.request).map_err(|e| {
+                    AppError::validation(format!("Build journal has an invalid request: {}", e))
+                })?;
+            if Path::new(&request.output_dir) != Path::new(output_dir) {
+                return Err(AppError::validation(format!(
+                    "Build journal in {} belongs to {}",
+                    output_dir, request.output_dir
+                )));
+            }
+            build_weaver_nest_impl(request)
+        }
+        build_journal::RecoveryAction::Rollback => {
+            let deleted = build_journal::rollback(
+                Path::new(output_dir),
+                &[weaver_nest::NEST_ZIP_PARTIAL_NAME],
+            )
+            .map_err(|e| AppError::io(format!("Failed to roll back build: {}", e)))?;
+            Ok(format!(
+                "Removed {} file(s) of the interrupted build in {}",
+                deleted, output_dir
+            ))
+        }
+    }
 }
 
 /// Get the default Minecraft resourcepacks directory
@@ -138,47 +773,111 @@ pub fn get_default_packs_dir_impl() -> Result<String, AppError> {
 
 /// Initialize vanilla textures (extract from Minecraft JAR if needed)
 ///
+/// Categories missing from an existing cache are extracted into it without
+/// extracting the rest again.
+///
 /// # Arguments
 /// * `window` - Tauri window handle for emitting progress events
+/// * `categories` - Optional texture categories to extract from now on; the
+///   current selection is kept when None
 ///
 /// # Returns
 /// Path to the vanilla textures cache directory
-pub fn initialize_vanilla_textures_impl(window: tauri::Window) -> Result<String, AppError> {
+pub fn initialize_vanilla_textures_impl(
+    window: tauri::Window,
+    categories: Option<Vec<vanilla_textures::TextureCategory>>,
+) -> Result<String, AppError> {
     use std::sync::Arc;
     use tauri::Emitter;
 
+    if let Some(categories) = categories {
+        vanilla_textures::set_texture_categories(&categories);
+    }
+
     // Create progress callback that emits events to the frontend
     let progress_callback = Arc::new(move |current: usize, total: usize| {
-        println!(
-            "[initialize_vanilla_textures] Emitting progress: {}/{}",
-            current, total
-        );
+        info!("Emitting progress: {}/{}", current, total);
         if let Err(e) = window.emit("vanilla-texture-progress", (current, total)) {
-            eprintln!(
-                "[initialize_vanilla_textures] Failed to emit progress event: {}",
-                e
-            );
+            warn!("Failed to emit progress event: {}", e);
         }
     });
 
     vanilla_textures::initialize_vanilla_textures_with_progress(Some(progress_callback))
         .map(|p| p.to_string_lossy().to_string())
-        .map_err(|e| AppError::io(format!("Failed to initialize vanilla textures: {}", e)))
+        .map_err(|e| extraction_error(e, "Failed to initialize vanilla textures"))
 }
 
 /// Get the path to a vanilla texture file
 ///
 /// # Arguments
 /// * `asset_id` - Asset ID like "minecraft:block/stone"
+/// * `version` - Cached version to read from; defaults to the version in use
 ///
 /// # Returns
 /// Absolute path to the texture PNG file
-pub fn get_vanilla_texture_path_impl(asset_id: String) -> Result<String, AppError> {
-    vanilla_textures::get_vanilla_texture_path(&asset_id)
+pub fn get_vanilla_texture_path_impl(
+    asset_id: String,
+    version: Option<String>,
+) -> Result<String, AppError> {
+    vanilla_textures::get_vanilla_texture_path(&asset_id, version.as_deref())
         .map(|p| p.to_string_lossy().to_string())
         .map_err(|e| AppError::io(format!("Vanilla texture not found: {}", e)))
 }
 
+/// List the vanilla versions whose textures are extracted
+///
+/// # Returns
+/// Versions kept side by side in the cache, newest first
+pub fn list_cached_vanilla_versions_impl() -> Result<Vec<String>, AppError> {
+    vanilla_textures::list_cached_versions()
+        .map_err(|e| AppError::io(format!("Failed to list cached vanilla versions: {}", e)))
+}
+
+/// Compare a vanilla texture between two cached versions
+///
+/// # Arguments
+/// * `asset_id` - Asset ID like "minecraft:block/grass_block_side"
+/// * `version_a` - Version to compare from
+/// * `version_b` - Version to compare to
+///
+/// # Returns
+/// The texture's path in each version and a summary of what changed
+pub fn compare_vanilla_asset_impl(
+    asset_id: String,
+    version_a: String,
+    version_b: String,
+) -> Result<vanilla_diff::VanillaAssetComparison, AppError> {
+    vanilla_diff::compare_vanilla_asset(&asset_id, &version_a, &version_b)
+        .map_err(|e| AppError::validation(format!("Failed to compare vanilla asset: {}", e)))
+}
+
+/// A vanilla texture's path together with where the cache came from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VanillaTextureProvenance {
+    pub path: String,
+    /// JAR the texture was extracted from; None for caches extracted before
+    /// provenance was recorded
+    pub provenance: Option<vanilla_textures::VanillaProvenance>,
+}
+
+/// Get the path to a vanilla texture file and the JAR it was extracted from
+///
+/// # Arguments
+/// * `asset_id` - Asset ID like "minecraft:block/stone"
+///
+/// # Returns
+/// Absolute path to the texture PNG file, with the client JAR's version,
+/// SHA-1 and asset index
+pub fn get_vanilla_texture_provenance_impl(
+    asset_id: String,
+) -> Result<VanillaTextureProvenance, AppError> {
+    let path = get_vanilla_texture_path_impl(asset_id, None)?;
+    let provenance = vanilla_textures::get_vanilla_provenance()
+        .map_err(|e| AppError::io(format!("Failed to read vanilla provenance: {}", e)))?;
+    Ok(VanillaTextureProvenance { path, provenance })
+}
+
 /// Get the path to a vanilla texture's .mcmeta file (if it exists)
 ///
 /// # Arguments
@@ -205,6 +904,86 @@ pub fn get_colormap_path_impl(colormap_type: String) -> Result<String, AppError>
         .map_err(|e| AppError::io(format!("Colormap not found: {}", e)))
 }
 
+/// Packs to take colormaps from: the given pack (if any), then vanilla
+fn colormap_layers(
+    pack_id: Option<&str>,
+    packs_dir: Option<&str>,
+    target: Option<&TargetVersion>,
+) -> Result<Vec<crate::model::PackMeta>, AppError> {
+    let vanilla_pack = vanilla_pack_for_target(target)?;
+    let Some(pack_id) = pack_id else {
+        return Ok(vec![vanilla_pack]);
+    };
+    let packs_dir =
+        packs_dir.ok_or_else(|| AppError::validation("A packs directory is needed with a pack"))?;
+    validation::validate_directory(packs_dir, "Packs directory")?;
+    let pack = find_pack(pack_id, packs_dir, &vanilla_pack)?;
+    if pack.id == vanilla_pack.id {
+        return Ok(vec![vanilla_pack]);
+    }
+    Ok(vec![pack, vanilla_pack])
+}
+
+/// Compute a biome tint from a grass, foliage or dry foliage colormap
+///
+/// Samples the colormap the way the game does, so the frontend only has to
+/// multiply tinted faces by the result.
+///
+/// # Arguments
+/// * `colormap_type` - "grass", "foliage" or "dry_foliage"
+/// * `climate` - Biome ID, or temperature and downfall
+/// * `pack_id` - Pack whose colormap overrides vanilla's (optional)
+/// * `packs_dir` - Directory containing resource packs, needed with `pack_id`
+/// * `target` - Workspace target version; vanilla fallback must match it
+///
+/// # Errors
+/// - VALIDATION_ERROR: Unknown biome, no climate given or colormap missing
+///
+/// # Returns
+/// The tint color and its RGB multiplier
+pub fn compute_tint_impl(
+    colormap_type: crate::util::biome_tint::ColormapType,
+    climate: crate::util::biome_tint::Climate,
+    pack_id: Option<String>,
+    packs_dir: Option<String>,
+    target: Option<TargetVersion>,
+) -> Result<crate::util::biome_tint::Tint, AppError> {
+    let layers = colormap_layers(pack_id.as_deref(), packs_dir.as_deref(), target.as_ref())?;
+    let layers: Vec<&crate::model::PackMeta> = layers.iter().collect();
+    crate::util::biome_tint::compute_tint(&layers, colormap_type, &climate)
+        .map_err(|e| AppError::validation(e.to_string()))
+}
+
+/// Compute the tint a block's tinted faces get in a biome
+///
+/// Faces of the block's models with a `tintindex` are multiplied by the
+/// result; blocks the game doesn't tint give None.
+///
+/// # Arguments
+/// * `block_id` - Block ID (e.g., "minecraft:oak_leaves")
+/// * `climate` - Biome ID, or temperature and downfall
+/// * `pack_id` - Pack whose colormaps override vanilla's (optional)
+/// * `packs_dir` - Directory containing resource packs, needed with `pack_id`
+/// * `target` - Workspace target version; vanilla fallback must match it
+///
+/// # Errors
+/// - VALIDATION_ERROR: Unknown biome, no climate given or colormap missing
+///
+/// # Returns
+/// Where the block's tint comes from and its color, or None
+pub fn compute_block_tint_impl(
+    block_id: String,
+    climate: crate::util::biome_tint::Climate,
+    pack_id: Option<String>,
+    packs_dir: Option<String>,
+    target: Option<TargetVersion>,
+) -> Result<Option<crate::util::biome_tint::BlockTint>, AppError> {
+    let layers = colormap_layers(pack_id.as_deref(), packs_dir.as_deref(), target.as_ref())?;
+    let layers: Vec<&crate::model::PackMeta> = layers.iter().collect();
+    crate::util::biome_tint::compute_block_tint(&layers, &block_id, &climate)
+        .map_err(|e| AppError::validation(e.to_string()))
+}
+
 /// List all available Minecraft versions
 ///
 /// # Returns
@@ -241,15 +1020,9 @@ pub fn set_vanilla_texture_version_impl(
 
     // Create progress callback that emits events to the frontend
     let progress_callback = Arc::new(move |current: usize, total: usize| {
-        println!(
-            "[set_vanilla_texture_version] Emitting progress: {}/{}",
-            current, total
-        );
+        info!("Emitting progress: {}/{}", current, total);
         if let Err(e) = window.emit("vanilla-texture-progress", (current, total)) {
-            eprintln!(
-                "[set_vanilla_texture_version] Failed to emit progress event: {}",
-                e
-            );
+            warn!("Failed to emit progress event: {}", e);
         }
     });
 
@@ -259,29 +1032,30 @@ pub fn set_vanilla_texture_version_impl(
     )
     .map(|p| p.to_string_lossy().to_string())
     .map_err(|e| {
-        AppError::io(format!(
-            "Failed to extract vanilla textures for version {}: {}",
-            version, e
-        ))
+        extraction_error(
+            e,
+            &format!("Failed to extract vanilla textures for version {}", version),
+        )
     })
 }
 
 /// Check if Minecraft is installed
 ///
+/// Looks in the platform's usual location, then Flatpak, Snap and portable
+/// .minecraft directories.
+///
 /// # Returns
-/// true if Minecraft installation found, false otherwise
-pub fn check_minecraft_installed_impl() -> Result<bool, AppError> {
-    match mc_paths::get_default_minecraft_dir() {
-        Ok(mc_dir) => Ok(vanilla_textures::check_minecraft_installation(&mc_dir).unwrap_or(false)),
-        Err(_) => Ok(false),
-    }
+/// The installation found and how it was installed, None if there is none
+pub fn check_minecraft_installed_impl() -> Result<Option<mc_paths::MinecraftInstall>, AppError> {
+    Ok(vanilla_textures::find_minecraft_install())
 }
 
 /// Get suggested Minecraft installation paths
 ///
 /// # Returns
-/// List of likely paths where Minecraft might be installed
-pub fn get_suggested_minecraft_paths_impl() -> Result<Vec<String>, AppError> {
+/// List of likely paths where Minecraft might be installed, each with how it
+/// would have been installed
+pub fn get_suggested_minecraft_paths_impl() -> Result<Vec<mc_paths::MinecraftInstall>, AppError> {
     Ok(vanilla_textures::get_suggested_minecraft_paths())
 }
 
@@ -299,7 +1073,7 @@ pub fn initialize_vanilla_textures_from_custom_dir_impl(
 
     vanilla_textures::initialize_vanilla_textures_from_dir(&path)
         .map(|p| p.to_string_lossy().to_string())
-        .map_err(|e| AppError::io(format!("Failed to initialize vanilla textures: {}", e)))
+        .map_err(|e| extraction_error(e, "Failed to initialize vanilla textures"))
 }
 
 /// Detect all Minecraft launchers on the system
@@ -329,14 +1103,7 @@ pub fn identify_launcher_impl(path: String) -> Result<launcher_detection::Launch
     let launcher_type = launcher_detection::identify_launcher_from_path(&path_buf)
         .map_err(|e| AppError::io(format!("Failed to identify launcher: {}", e)))?;
 
-    Ok(launcher_detection::LauncherInfo {
-        launcher_type: launcher_type.clone(),
-        name: launcher_type.display_name().to_string(),
-        minecraft_dir: path,
-        found: true,
-        icon: launcher_type.icon().to_string(),
-        icon_path: launcher_detection::get_launcher_icon_path(&launcher_type),
-    })
+    Ok(launcher_detection::LauncherInfo::new(launcher_type, path))
 }
 
 /// Get resourcepacks directory for a launcher
@@ -358,6 +1125,58 @@ pub fn get_launcher_resourcepacks_dir_impl(
     Ok(resourcepacks_dir.to_string_lossy().to_string())
 }
 
+/// List the instances of a multi-instance launcher
+///
+/// # Arguments
+/// * `launcher_info` - Launcher information with path and type
+///
+/// # Returns
+/// Each instance's name, Minecraft version, mod loader and resourcepacks
+/// directory, sorted by name
+pub fn list_launcher_instances_impl(
+    launcher_info: launcher_detection::LauncherInfo,
+) -> Result<Vec<launcher_instances::LauncherInstance>, AppError> {
+    validation::validate_directory(&launcher_info.minecraft_dir, "Launcher directory")?;
+
+    launcher_instances::list_instances(&launcher_info)
+        .map_err(|e| AppError::io(format!("Failed to list launcher instances: {}", e)))
+}
+
+/// Install the built nest into a launcher instance
+///
+/// # Arguments
+/// * `nest_path` - Built nest, WeaverNest.zip or a pack folder
+/// * `launcher_info` - Launcher information with path and type
+/// * `instance_id` - Instance to install into (see `list_launcher_instances`),
+///   optional for launchers with a single instance
+/// * `mode` - Copy the nest, or symlink it so rebuilds apply directly
+/// * `enable` - Also enable the nest on top of the pack stack in options.txt
+///
+/// # Returns
+/// Where the nest was installed and its options.txt entry
+pub fn install_nest_to_launcher_impl(
+    nest_path: String,
+    launcher_info: launcher_detection::LauncherInfo,
+    instance_id: Option<String>,
+    mode: nest_install::InstallMode,
+    enable: bool,
+) -> Result<nest_install::NestInstallResult, AppError> {
+    if !Path::new(&nest_path).exists() {
+        return Err(AppError::validation(format!(
+            "Built nest not found: {}",
+            nest_path
+        )));
+    }
+    validation::validate_directory(&launcher_info.minecraft_dir, "Launcher directory")?;
+
+    let resourcepacks_dir =
+        launcher_instances::instance_resourcepacks_dir(&launcher_info, instance_id.as_deref())
+            .map_err(|e| AppError::validation(e.to_string()))?;
+
+    nest_install::install_nest(Path::new(&nest_path), &resourcepacks_dir, mode, enable)
+        .map_err(|e| AppError::io(format!("Failed to install nest: {}", e)))
+}
+
 /// Get the full path to a texture file from a resource pack
 ///
 /// # Arguments
@@ -374,9 +1193,7 @@ pub fn get_pack_texture_path_impl(
     version_folders: Option<Vec<String>>,
     app_handle: &tauri::AppHandle,
 ) -> Result<String, AppError> {
-    // Parse asset ID: "minecraft:block/stone" -> "assets/minecraft/textures/block/stone.png"
-    let texture_path = asset_id.strip_prefix("minecraft:").unwrap_or(&asset_id);
-    let relative_path = format!("assets/minecraft/textures/{}.png", texture_path);
+    let relative_path = pack_files::texture_entry_path(&asset_id);
 
     let mut candidate_paths: Vec<String> = Vec::new();
     candidate_paths.push(relative_path.clone());
@@ -413,26 +1230,13 @@ pub fn get_pack_texture_path_impl(
         let chosen_rel = chosen_rel.unwrap_or(relative_path.clone());
 
         // Create a cache directory for this ZIP using Tauri's cache directory
-        use tauri::Manager;
-        let cache_dir = app_handle
-            .path()
-            .cache_dir()
-            .map_err(|e| AppError::io(format!("Failed to get cache dir: {}", e)))?
-            .join("weaverbird_textures");
+        let cache_dir = texture_cache_dir(app_handle)?;
 
         std::fs::create_dir_all(&cache_dir)
             .map_err(|e| AppError::io(format!("Failed to create cache dir: {}", e)))?;
 
         // Create a unique filename based on the ZIP path and texture path
-        let zip_path_buf = PathBuf::from(&pack_path);
-        let zip_name = zip_path_buf
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("unknown");
-
-        // Sanitize the chosen relative path for filesystem
-        let safe_texture_path = chosen_rel.replace("/", "_").replace("\\", "_");
-        let cache_file = cache_dir.join(format!("{}_{}", zip_name, safe_texture_path));
+        let cache_file = pack_files::cached_entry_path(&cache_dir, &pack_path, &chosen_rel);
 
         // Write the texture to cache if it doesn't exist
         if !cache_file.exists() {
@@ -448,10 +1252,7 @@ pub fn get_pack_texture_path_impl(
                 crate::util::zip::extract_zip_entry(zip_path_str, &mcmeta_rel)
             {
                 if let Err(err) = std::fs::write(&mcmeta_cache_file, &mcmeta_bytes) {
-                    eprintln!(
-                        "[packs] Failed to write cached mcmeta for {}: {}",
-                        mcmeta_rel, err
-                    );
+                    warn!("Failed to write cached mcmeta for {}: {}", mcmeta_rel, err);
                 }
             }
         }
@@ -474,7 +1275,1522 @@ pub fn get_pack_texture_path_impl(
     }
 }
 
-/// Load a model JSON directly by model ID (after blockstate resolution)
+/// Event emitted after an asset is refreshed so open previews can reload it
+pub const ASSET_UPDATED_EVENT: &str = "asset-updated";
+
+/// Payload of the `asset-updated` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetUpdatedEvent {
+    pub asset_id: String,
+    pub pack_id: String,
+    /// Path of the pack the asset belongs to (cache key used by the previews)
+    pub pack_path: String,
+    /// Number of cached files removed on the backend
+    pub invalidated: usize,
+}
+
+/// Directory that holds textures extracted from ZIP packs
+pub fn texture_cache_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, AppError> {
+    use tauri::Manager;
+    Ok(app_handle
+        .path()
+        .cache_dir()
+        .map_err(|e| AppError::io(format!("Failed to get cache dir: {}", e)))?
+        .join(pack_files::TEXTURE_CACHE_NAME))
+}
+
+/// Delete cached files extracted from one ZIP pack whose name (after the pack
+/// prefix) satisfies `matches`
+///
+/// Returns the number of files deleted; a missing cache directory means nothing
+/// was cached.
+fn remove_cached_files(
+    cache_dir: &Path,
+    pack_path: &str,
+    matches: impl Fn(&str) -> bool,
+) -> std::io::Result<usize> {
+    let prefix = pack_files::cached_entry_prefix(pack_path);
+
+    let entries = match std::fs::read_dir(cache_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e),
+    };
+
+    let mut removed = 0;
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Some(rest) = name.strip_prefix(&prefix) else {
+            continue;
+        };
+        if matches(rest) {
+            std::fs::remove_file(entry.path())?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// Delete the cached copies of one pack texture and its .mcmeta
+///
+/// Version-folder variants of the texture are removed too. Returns the number
+/// of files deleted.
+fn invalidate_cached_texture(
+    cache_dir: &Path,
+    pack_path: &str,
+    asset_id: &str,
+) -> std::io::Result<usize> {
+    let texture_suffix = pack_files::cached_entry_name(&pack_files::texture_entry_path(asset_id));
+    let mcmeta_suffix = format!("{}.mcmeta", texture_suffix);
+
+    remove_cached_files(cache_dir, pack_path, |rest| {
+        rest.ends_with(&texture_suffix) || rest.ends_with(&mcmeta_suffix)
+    })
+}
+
+/// Drop every cached copy of an asset after it was edited outside the app
+///
+/// Removes the asset's extracted files and thumbnails from the backend caches
+/// and emits an `asset-updated` event so open previews reload it without a
+/// full rescan.
+///
+/// # Arguments
+/// * `asset_id` - Asset that changed (e.g., "minecraft:block/stone")
+/// * `pack_id` - Pack containing the edited file
+/// * `packs_dir` - Resource packs directory
+///
+/// # Returns
+/// The payload of the emitted event
+pub fn refresh_asset_impl(
+    asset_id: String,
+    pack_id: String,
+    packs_dir: String,
+    app_handle: &tauri::AppHandle,
+) -> Result<AssetUpdatedEvent, AppError> {
+    validation::validate_directory(&packs_dir, "Packs directory")?;
+    let vanilla_pack = create_vanilla_pack()?;
+    let pack = find_pack(&pack_id, &packs_dir, &vanilla_pack)?;
+    refresh_pack_asset(&pack, asset_id, app_handle)
+}
+
+/// Invalidate the cached copies of one asset of `pack` and emit `asset-updated`
+fn refresh_pack_asset(
+    pack: &crate::model::PackMeta,
+    asset_id: String,
+    app_handle: &tauri::AppHandle,
+) -> Result<AssetUpdatedEvent, AppError> {
+    use tauri::Emitter;
+
+    let extracted = if pack.is_zip {
+        invalidate_cached_texture(&texture_cache_dir(app_handle)?, &pack.path, &asset_id)
+            .map_err(|e| AppError::io(format!("Failed to invalidate texture cache: {}", e)))?
+    } else {
+        0
+    };
+    let invalidated = extracted + thumbnails::invalidate_thumbnails(&pack.path, &asset_id);
+
+    let event = AssetUpdatedEvent {
+        asset_id,
+        pack_id: pack.id.clone(),
+        pack_path: pack.path.clone(),
+        invalidated,
+    };
+    info!(
+        "{} in {}: removed {} cached file(s)",
+        event.asset_id, event.pack_id, event.invalidated
+    );
+    if let Err(e) = app_handle.emit(ASSET_UPDATED_EVENT, event.clone()) {
+        warn!("Failed to emit {}: {}", ASSET_UPDATED_EVENT, e);
+    }
+
+    Ok(event)
+}
+
+/// Event emitted when a ZIP asset opened in an external editor is saved
+pub const ASSET_EDITED_EVENT: &str = "asset-edited";
+
+/// Open an asset in an external editor and watch it for changes
+///
+/// Folder pack files are edited in place and every save emits `asset-updated`
+/// so previews reload. ZIP pack files are extracted to a temp workspace first;
+/// saves emit `asset-edited` so the UI can offer writing the change back.
+///
+/// # Arguments
+/// * `asset_id` - Asset to edit (e.g., "minecraft:block/stone")
+/// * `pack_id` - Pack containing the asset
+/// * `packs_dir` - Resource packs directory
+/// * `editor` - Editor executable (or .app bundle on macOS); None uses the system default
+///
+/// # Returns
+/// The editing session, used for write-back and closing
+pub fn open_asset_in_editor_impl(
+    asset_id: String,
+    pack_id: String,
+    packs_dir: String,
+    editor: Option<String>,
+    app_handle: &tauri::AppHandle,
+) -> Result<asset_editor::EditSession, AppError> {
+    use tauri::Emitter;
+
+    validation::validate_directory(&packs_dir, "Packs directory")?;
+    let vanilla_pack = create_vanilla_pack()?;
+    let pack = find_pack(&pack_id, &packs_dir, &vanilla_pack)?;
+    if pack.id == vanilla_pack.id || pack.builtin {
+        return Err(AppError::validation(
+            "Vanilla assets can't be edited; copy them into a pack first",
+        ));
+    }
+
+    let workspace = asset_editor::workspace_dir();
+    let session = asset_editor::prepare_edit_session(&pack, &asset_id, &workspace)
+        .map_err(|e| AppError::io(format!("Failed to prepare {} for editing: {}", asset_id, e)))?;
+    asset_editor::launch_editor(editor.as_deref(), Path::new(&session.edit_path))
+        .map_err(|e| AppError::io(format!("{}", e)))?;
+
+    let app_handle = app_handle.clone();
+    asset_editor::start_session(session.clone(), move |session| {
+        if session.in_place {
+            // Same invalidation as a refresh after a ZIP write-back
+            if let Err(e) = refresh_pack_asset(&pack, session.asset_id.clone(), &app_handle) {
+                warn!("Failed to refresh {}: {}", session.asset_id, e);
+            }
+        } else if let Err(e) = app_handle.emit(ASSET_EDITED_EVENT, session.clone()) {
+            warn!("Failed to emit change event: {}", e);
+        }
+    });
+
+    info!(
+        "Editing {} from {} at {}",
+        session.asset_id, session.pack_id, session.edit_path
+    );
+    Ok(session)
+}
+
+/// Write an edited asset back into a working copy of its pack
+///
+/// # Arguments
+/// * `session_id` - Session returned by `open_asset_in_editor`
+///
+/// # Returns
+/// Path of the working copy folder (the pack itself for folder packs)
+pub fn write_back_edited_asset_impl(session_id: String) -> Result<String, AppError> {
+    let session = asset_editor::get_session(&session_id)
+        .ok_or_else(|| AppError::validation(format!("No editing session {}", session_id)))?;
+
+    asset_editor::write_back(&session)
+        .map(|path| path.to_string_lossy().to_string())
+        .map_err(|e| AppError::io(format!("Failed to write back {}: {}", session.entry, e)))
+}
+
+/// Stop watching an edited asset and delete its temp files
+///
+/// # Arguments
+/// * `session_id` - Session returned by `open_asset_in_editor`
+pub fn close_edit_session_impl(session_id: String) -> Result<(), AppError> {
+    asset_editor::close_session(&session_id).map_err(|e| AppError::validation(format!("{}", e)))
+}
+
+/// Copy one kind of file for an asset from the vanilla cache into a folder pack
+///
+/// Textures bring their .mcmeta along. Existing pack files are never overwritten.
+/// Returns the pack-relative paths written.
+fn copy_vanilla_files(
+    vanilla_dir: &Path,
+    pack_dir: &Path,
+    asset_id: &str,
+    kind: crate::model::VanillaAssetKind,
+) -> Result<Vec<String>, AppError> {
+    let entry = pack_files::safe_entry_path(&pack_files::kind_entry_path(asset_id, kind))
+        .map_err(|e| AppError::validation(e.to_string()))?;
+    if !vanilla_dir.join(&entry).is_file() {
+        return Err(AppError::validation(format!(
+            "{} is not in the vanilla cache ({})",
+            asset_id, entry
+        )));
+    }
+    if pack_dir.join(&entry).exists() {
+        return Err(AppError::validation(format!(
+            "Pack already contains {}",
+            entry
+        )));
+    }
+
+    let mut entries = vec![entry.clone()];
+    let mcmeta = format!("{}.mcmeta", entry);
+    if kind == crate::model::VanillaAssetKind::Texture
+        && vanilla_dir.join(&mcmeta).is_file()
+        && !pack_dir.join(&mcmeta).exists()
+    {
+        entries.push(mcmeta);
+    }
+
+    for entry in &entries {
+        let target = pack_dir.join(entry);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                AppError::io(format!("Failed to create {}: {}", parent.display(), e))
+            })?;
+        }
+        std::fs::copy(vanilla_dir.join(entry), &target)
+            .map_err(|e| AppError::io(format!("Failed to copy {}: {}", entry, e)))?;
+    }
+
+    Ok(entries)
+}
+
+/// Copy a vanilla texture, model or blockstate into a folder pack as the
+/// starting point for an override
+///
+/// # Arguments
+/// * `asset_id` - Asset to copy (e.g., "minecraft:block/stone")
+/// * `pack_id` - Folder pack to copy into
+/// * `packs_dir` - Resource packs directory
+/// * `kind` - Which file to copy; defaults to the texture
+///
+/// # Returns
+/// Pack-relative paths of the files written
+pub fn copy_vanilla_asset_to_pack_impl(
+    asset_id: String,
+    pack_id: String,
+    packs_dir: String,
+    kind: Option<crate::model::VanillaAssetKind>,
+) -> Result<Vec<String>, AppError> {
+    validation::validate_directory(&packs_dir, "Packs directory")?;
+
+    let vanilla_pack = create_vanilla_pack()?;
+    if pack_id == vanilla_pack.id {
+        return Err(AppError::validation(
+            "Cannot copy vanilla assets into the vanilla pack".to_string(),
+        ));
+    }
+    let pack = find_pack(&pack_id, &packs_dir, &vanilla_pack)?;
+    if pack.builtin {
+        return Err(AppError::validation(format!(
+            "Pack {} is built into Minecraft and can't be changed",
+            pack_id
+        )));
+    }
+    if pack.is_zip {
+        return Err(AppError::validation(format!(
+            "Pack {} is a zip; unpack it to a folder first",
+            pack_id
+        )));
+    }
+
+    let written = copy_vanilla_files(
+        Path::new(&vanilla_pack.path),
+        Path::new(&pack.path),
+        &asset_id,
+        kind.unwrap_or_default(),
+    )?;
+    info!("Copied {} into {}: {:?}", asset_id, pack_id, written);
+    Ok(written)
+}
+
+/// Locate many pack textures in one call, in parallel
+///
+/// # Arguments
+/// * `requests` - Textures to locate (same fields as `get_pack_texture_path`)
+///
+/// # Returns
+/// One result or error per request, in request order
+pub fn get_pack_texture_paths_batch_impl(
+    requests: Vec<TexturePathRequest>,
+    app_handle: &tauri::AppHandle,
+) -> Result<Vec<BatchResult<String>>, AppError> {
+    Ok(requests
+        .into_par_iter()
+        .map(|request| {
+            get_pack_texture_path_impl(
+                request.pack_path,
+                request.asset_id,
+                request.is_zip,
+                request.version_folders,
+                app_handle,
+            )
+            .into()
+        })
+        .collect())
+}
+
+/// Resolve the winning texture of a page of assets in one call, in parallel
+///
+/// Built for the virtualized asset grid, which would otherwise locate each
+/// visible row's texture with its own call. Each asset's texture comes from the
+/// first pack in `ordered_packs` that has it, or from vanilla. Pack listings
+/// come from the ZIP archive cache, and textures already extracted to the
+/// texture cache are returned without reading the ZIP again.
+///
+/// # Arguments
+/// * `asset_ids` - Texture asset IDs (e.g., "minecraft:block/stone")
+/// * `ordered_packs` - Packs as returned by the scan, highest priority first
+///
+/// # Returns
+/// One result or error per asset, in `asset_ids` order
+pub fn resolve_texture_paths_bulk_impl(
+    asset_ids: Vec<String>,
+    ordered_packs: Vec<crate::model::PackMeta>,
+    app_handle: &tauri::AppHandle,
+) -> Result<Vec<BatchResult<ResolvedTexturePath>>, AppError> {
+    let cache_dir = texture_cache_dir(app_handle)?;
+
+    Ok(asset_ids
+        .into_par_iter()
+        .map(|asset_id| {
+            let relative_path = pack_files::texture_entry_path(&asset_id);
+            let winner = ordered_packs
+                .iter()
+                .find(|pack| pack_files::pack_entry_exists(pack, &relative_path));
+
+            let resolved = match winner {
+                Some(pack) if pack.is_zip => {
+                    let cached =
+                        pack_files::cached_entry_path(&cache_dir, &pack.path, &relative_path);
+                    let path = if cached.exists() {
+                        Ok(cached.to_string_lossy().to_string())
+                    } else {
+                        get_pack_texture_path_impl(
+                            pack.path.clone(),
+                            asset_id,
+                            true,
+                            None,
+                            app_handle,
+                        )
+                    };
+                    path.map(|path| ResolvedTexturePath {
+                        pack_id: pack.id.clone(),
+                        path,
+                    })
+                }
+                Some(pack) => Ok(ResolvedTexturePath {
+                    pack_id: pack.id.clone(),
+                    path: Path::new(&pack.path)
+                        .join(&relative_path)
+                        .to_string_lossy()
+                        .to_string(),
+                }),
+                None => vanilla_textures::get_vanilla_texture_path(&asset_id, None)
+                    .map(|path| ResolvedTexturePath {
+                        pack_id: "minecraft:vanilla".to_string(),
+                        path: path.to_string_lossy().to_string(),
+                    })
+                    .map_err(|e| AppError::validation(e.to_string())),
+            };
+            resolved.into()
+        })
+        .collect())
+}
+
+/// Get a downscaled copy of a texture for the asset grid, generating it if needed
+///
+/// # Arguments
+/// * `pack_path` - Base path to the resource pack, or None for vanilla
+/// * `asset_id` - Texture asset ID (e.g., "minecraft:block/stone")
+/// * `is_zip` - Whether the pack is a ZIP file
+/// * `size` - Edge length each frame is fitted into (defaults to 64)
+///
+/// # Returns
+/// Full path to the cached thumbnail PNG
+pub fn get_asset_thumbnail_impl(
+    pack_path: Option<String>,
+    asset_id: String,
+    is_zip: bool,
+    size: Option<u32>,
+) -> Result<String, AppError> {
+    let size = size.unwrap_or(thumbnails::THUMBNAIL_SIZE);
+    if size == 0 || size > thumbnails::MAX_THUMBNAIL_SIZE {
+        return Err(AppError::validation(format!(
+            "Thumbnail size must be between 1 and {}, got {}",
+            thumbnails::MAX_THUMBNAIL_SIZE,
+            size
+        )));
+    }
+
+    let pack = match pack_path {
+        Some(path) => crate::model::PackMeta {
+            id: path.clone(),
+            name: path.clone(),
+            path,
+            is_zip,
+            ..Default::default()
+        },
+        None => create_vanilla_pack()?,
+    };
+
+    let thumbnail = thumbnails::get_thumbnail(&pack, &asset_id, size).map_err(|e| {
+        AppError::io(format!("Failed to create thumbnail for {}", asset_id))
+            .with_details(e.to_string())
+    })?;
+    Ok(thumbnail.to_string_lossy().to_string())
+}
+
+/// Sample an animated texture at a game tick using vanilla animation timing
+///
+/// The texture and its .mcmeta are read from the same pack (a pack's PNG never pairs
+/// with another pack's .mcmeta); if the pack doesn't provide the texture, vanilla is used.
+///
+/// # Arguments
+/// * `asset_id` - Texture asset ID (e.g., "minecraft:block/magma")
+/// * `pack_id` - ID of the resource pack to read from
+/// * `packs_dir` - Directory containing resource packs
+/// * `tick` - Game tick to sample (wraps around the animation cycle)
+/// * `target` - Workspace target version; vanilla fallback must match it
+/// * `policy` - What to do when the pack lacks the asset (defaults to vanilla fallback)
+///
+/// # Returns
+/// The frame to display (plus blend target and alpha when `interpolate` is set),
+/// or None if the texture is not animated
+pub fn sample_animation_frame_impl(
+    asset_id: String,
+    pack_id: String,
+    packs_dir: String,
+    tick: u64,
+    target: Option<TargetVersion>,
+    policy: Option<MissingAssetPolicy>,
+) -> Result<Option<texture_animation::AnimationSample>, AppError> {
+    validation::validate_directory(&packs_dir, "Packs directory")?;
+
+    let policy = policy.unwrap_or_default();
+    let vanilla_pack = vanilla_pack_for_target(target.as_ref())?;
+    let target_pack = find_pack(&pack_id, &packs_dir, &vanilla_pack)?;
+
+    let texture_path = pack_files::texture_entry_path(&asset_id);
+    let source_pack = if pack_files::pack_entry_exists(&target_pack, &texture_path) {
+        &target_pack
+    } else {
+        match policy {
+            MissingAssetPolicy::UseVanilla => &vanilla_pack,
+            MissingAssetPolicy::PackOnly => {
+                return Err(AppError::validation(format!(
+                    "Texture {} is not provided by pack {}",
+                    asset_id, target_pack.name
+                )))
+            }
+            // The placeholder texture is not animated
+            MissingAssetPolicy::Placeholder => return Ok(None),
+        }
+    };
+
+    let png_bytes = pack_files::read_pack_entry(source_pack, &texture_path)
+        .map_err(|e| AppError::validation(format!("Texture not found: {}", e)))?;
+
+    let mcmeta_bytes =
+        match pack_files::read_pack_entry(source_pack, &format!("{}.mcmeta", texture_path)) {
+            Ok(bytes) => bytes,
+            Err(_) => return Ok(None),
+        };
+    let mcmeta_json = String::from_utf8(mcmeta_bytes)
+        .map_err(|e| AppError::validation(format!("Invalid UTF-8 in .mcmeta: {}", e)))?;
+
+    texture_animation::sample_texture_animation(&png_bytes, &mcmeta_json, tick)
+        .map_err(|e| AppError::validation(format!("Failed to sample animation: {}", e)))
+}
+
+/// Get the frame layout and playback sequence of an animated texture
+///
+/// # Arguments
+/// * `pack_path` - Base path to the resource pack (folder or ZIP), or None for vanilla
+/// * `asset_id` - Texture asset ID (e.g., "minecraft:block/water_still")
+/// * `include_images` - Also return each frame sliced out as base64 PNG
+///
+/// # Returns
+/// Frame rectangles, sequence and timing, or None if the texture has no
+/// animated .mcmeta
+pub fn get_animation_frames_impl(
+    pack_path: Option<String>,
+    asset_id: String,
+    include_images: Option<bool>,
+) -> Result<Option<texture_animation::AnimationFrames>, AppError> {
+    let pack = match pack_path {
+        Some(path) => crate::model::PackMeta {
+            id: path.clone(),
+            name: path.clone(),
+            // ZIP packs are files, folder packs directories
+            is_zip: Path::new(&path).is_file(),
+            path,
+            ..Default::default()
+        },
+        None => create_vanilla_pack()?,
+    };
+
+    let texture_path = pack_files::texture_entry_path(&asset_id);
+    let png_bytes = pack_files::read_pack_entry(&pack, &texture_path)
+        .map_err(|e| AppError::validation(format!("Texture not found: {}", e)))?;
+    let mcmeta_path = format!("{}.mcmeta", texture_path);
+    let Ok(mcmeta_bytes) = pack_files::read_pack_entry(&pack, &mcmeta_path) else {
+        return Ok(None);
+    };
+    let mcmeta_json = String::from_utf8(mcmeta_bytes)
+        .map_err(|e| AppError::validation(format!("Invalid UTF-8 in .mcmeta: {}", e)))?;
+
+    texture_animation::animation_frames(&png_bytes, &mcmeta_json, include_images.unwrap_or(false))
+        .map_err(|e| AppError::validation(format!("Failed to read animation frames: {}", e)))
+}
+
+/// Check whether a pack will load in a target Minecraft version
+///
+/// Compares the pack.mcmeta pack_format (and `supported_formats` range, where the
+/// target honors it) against the built-in pack_format table.
+///
+/// # Arguments
+/// * `pack_id` - ID of the resource pack to check
+/// * `packs_dir` - Directory containing resource packs
+/// * `target_version` - Minecraft release version (e.g., "1.21.4")
+///
+/// # Returns
+/// Compatibility status plus human-readable issues (empty when compatible)
+///
+/// # Errors
+/// Returns a validation error if the pack or target version is unknown
+pub fn validate_pack_compatibility_impl(
+    pack_id: String,
+    packs_dir: String,
+    target_version: String,
+) -> Result<pack_format::PackCompatibility, AppError> {
+    validation::validate_directory(&packs_dir, "Packs directory")?;
+
+    let vanilla_pack = create_vanilla_pack()?;
+    let pack = find_pack(&pack_id, &packs_dir, &vanilla_pack)?;
+
+    pack_format::check_pack_compatibility(&pack, &target_version)
+        .map_err(|e| AppError::validation(e.to_string()))
+}
+
+/// Check that GUI and font textures stay readable, including for colorblind players
+///
+/// Measures the WCAG contrast ratio between each texture's two main tones and
+/// simulates protanopia, deuteranopia, tritanopia and achromatopsia on them.
+///
+/// # Arguments
+/// * `pack_id` - ID of the resource pack to analyze
+/// * `packs_dir` - Directory containing resource packs
+/// * `asset_ids` - Texture asset IDs to analyze (e.g., "minecraft:gui/widgets")
+/// * `include_images` - Also return each texture recolored per deficiency (base64 PNG)
+///
+/// # Returns
+/// Per-texture analysis plus issues for the validation report
+pub fn analyze_contrast_impl(
+    pack_id: String,
+    packs_dir: String,
+    asset_ids: Vec<String>,
+    include_images: Option<bool>,
+) -> Result<contrast::ContrastReport, AppError> {
+    validation::validate_directory(&packs_dir, "Packs directory")?;
+
+    let vanilla_pack = create_vanilla_pack()?;
+    let pack = find_pack(&pack_id, &packs_dir, &vanilla_pack)?;
+
+    let report = contrast::analyze_contrast(&pack, &asset_ids, include_images.unwrap_or(false));
+    info!(
+        "{}: {} analyzed, {} missing, {} issues",
+        pack_id,
+        report.analyses.len(),
+        report.missing.len(),
+        report.issues.len()
+    );
+    Ok(report)
+}
+
+/// Check which Minecraft releases a pack works on
+///
+/// # Arguments
+/// * `pack_id` - ID of the resource pack to check
+/// * `packs_dir` - Directory containing resource packs
+///
+/// # Returns
+/// Per-pack_format status, ignored files and files needing conversion, plus the
+/// release range where every asset loads as-is
+pub fn get_pack_compatibility_impl(
+    pack_id: String,
+    packs_dir: String,
+) -> Result<pack_converter::CompatibilityMatrix, AppError> {
+    validation::validate_directory(&packs_dir, "Packs directory")?;
+
+    let vanilla_pack = create_vanilla_pack()?;
+    let pack = find_pack(&pack_id, &packs_dir, &vanilla_pack)?;
+    let from_format = pack
+        .pack_format
+        .or(pack.supported_formats.map(|range| range.min))
+        .ok_or_else(|| {
+            AppError::validation(format!("Pack {} does not declare a pack_format", pack_id))
+        })?;
+
+    pack_converter::compatibility_matrix(&pack, from_format).map_err(|e| {
+        AppError::scan(format!("Failed to check compatibility of {}", pack_id))
+            .with_details(e.to_string())
+    })
+}
+
+/// Convert a pack to another pack format, writing the result to a new folder
+///
+/// # Arguments
+/// * `pack_id` - ID of the resource pack to convert
+/// * `packs_dir` - Directory containing resource packs
+/// * `target_format` - pack_format to convert to
+/// * `output_dir` - Empty (or missing) folder for the converted pack
+/// * `from_format` - Source pack_format; defaults to the pack's pack.mcmeta value
+///
+/// # Returns
+/// Report of renamed and rewritten files plus anything that needs manual migration
+pub fn convert_pack_format_impl(
+    pack_id: String,
+    packs_dir: String,
+    target_format: u32,
+    output_dir: String,
+    from_format: Option<u32>,
+) -> Result<pack_converter::ConversionReport, AppError> {
+    validation::validate_directory(&packs_dir, "Packs directory")?;
+
+    let vanilla_pack = create_vanilla_pack()?;
+    let pack = find_pack(&pack_id, &packs_dir, &vanilla_pack)?;
+    validation::validate_conversion_output(&output_dir, &pack.path)?;
+
+    let from_format = from_format.or(pack.pack_format).ok_or_else(|| {
+        AppError::validation(format!(
+            "Pack {} does not declare a pack_format; specify the source format",
+            pack_id
+        ))
+    })?;
+
+    pack_converter::convert_pack(
+        &pack,
+        from_format,
+        target_format,
+        &PathBuf::from(&output_dir),
+    )
+    .map_err(|e| AppError::build(format!("Pack conversion failed: {}", e)))
+}
+
+/// Event emitted while a pack is unpacked or repacked, with (current, total) entries
+pub const PACK_ARCHIVE_PROGRESS_EVENT: &str = "pack-archive-progress";
+
+/// Build a progress callback that forwards archive progress to the frontend
+fn archive_progress_emitter(window: tauri::Window, label: &'static str) -> impl Fn(usize, usize) {
+    use tauri::Emitter;
+
+    move |current: usize, total: usize| {
+        if let Err(e) = window.emit(PACK_ARCHIVE_PROGRESS_EVENT, (current, total)) {
+            warn!("[{}] Failed to emit progress event: {}", label, e);
+        }
+    }
+}
+
+/// Extract a zip pack into an editable folder
+///
+/// # Arguments
+/// * `pack_id` - ID of the zip pack to unpack
+/// * `packs_dir` - Directory containing resource packs
+/// * `dest` - Empty (or missing) folder to extract into
+/// * `window` - Tauri window handle for emitting progress events
+///
+/// # Returns
+/// Path of the extracted pack folder
+pub fn unpack_pack_impl(
+    pack_id: String,
+    packs_dir: String,
+    dest: String,
+    window: tauri::Window,
+) -> Result<String, AppError> {
+    validation::validate_directory(&packs_dir, "Packs directory")?;
+
+    let vanilla_pack = create_vanilla_pack()?;
+    let pack = find_pack(&pack_id, &packs_dir, &vanilla_pack)?;
+    if !pack.is_zip {
+        return Err(AppError::validation(format!(
+            "Pack {} is already a folder",
+            pack_id
+        )));
+    }
+    validation::validate_conversion_output(&dest, &pack.path)?;
+    crate::util::zip::extract_zip_entry(&pack.path, "pack.mcmeta").map_err(|_| {
+        AppError::validation(format!("Pack {} has no pack.mcmeta at its root", pack_id))
+    })?;
+
+    let progress = archive_progress_emitter(window, "unpack_pack");
+    let written = crate::util::zip::extract_zip_to_dir_with_progress(
+        &pack.path,
+        Path::new(&dest),
+        Some(&progress),
+    )
+    .map_err(|e| AppError::io(format!("Failed to unpack {}: {}", pack_id, e)))?;
+    info!("Extracted {} files from {} to {}", written, pack_id, dest);
+
+    Ok(dest)
+}
+
+/// Zip a pack folder into a new archive
+///
+/// # Arguments
+/// * `dir` - Pack folder to zip; must contain pack.mcmeta
+/// * `dest_zip` - Path of the zip to create; must not exist yet
+/// * `window` - Tauri window handle for emitting progress events
+///
+/// # Returns
+/// Path of the created zip
+pub fn repack_pack_impl(
+    dir: String,
+    dest_zip: String,
+    window: tauri::Window,
+) -> Result<String, AppError> {
+    validation::validate_directory(&dir, "Pack folder")?;
+    if !Path::new(&dir).join("pack.mcmeta").is_file() {
+        return Err(AppError::validation(format!(
+            "Pack folder has no pack.mcmeta: {}",
+            dir
+        )));
+    }
+    validation::validate_zip_output(&dest_zip, &dir)?;
+
+    let progress = archive_progress_emitter(window, "repack_pack");
+    let written = crate::util::zip::zip_dir_with_progress(
+        Path::new(&dir),
+        Path::new(&dest_zip),
+        Some(&progress),
+    )
+    .map_err(|e| AppError::io(format!("Failed to repack {}: {}", dir, e)))?;
+    info!("Zipped {} files from {} to {}", written, dir, dest_zip);
+
+    Ok(dest_zip)
+}
+
+/// Event emitted after a pack is deleted or renamed so the library can update without a rescan
+pub const PACK_LIBRARY_CHANGED_EVENT: &str = "pack-library-changed";
+
+/// Payload of the `pack-library-changed` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackLibraryChange {
+    /// ID the pack had before the change
+    pub pack_id: String,
+    /// The pack after a rename, or None when it was deleted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pack: Option<crate::model::PackMeta>,
+}
+
+/// Look up a pack the user may delete or rename (anything but vanilla and the
+/// built-in packs)
+fn find_library_pack(pack_id: &str, packs_dir: &str) -> Result<crate::model::PackMeta, AppError> {
+    validation::validate_directory(packs_dir, "Packs directory")?;
+    let vanilla_pack = create_vanilla_pack()?;
+    if pack_id == vanilla_pack.id {
+        return Err(AppError::validation(
+            "The vanilla pack can't be deleted or renamed",
+        ));
+    }
+    let pack = find_pack(pack_id, packs_dir, &vanilla_pack)?;
+    if pack.builtin {
+        return Err(AppError::validation(format!(
+            "Pack {} is built into Minecraft and can't be deleted or renamed",
+            pack_id
+        )));
+    }
+    Ok(pack)
+}
+
+/// Drop textures extracted from a ZIP pack that no longer exists under its path
+fn forget_cached_pack(pack: &crate::model::PackMeta, app_handle: &tauri::AppHandle) {
+    if !pack.is_zip {
+        return;
+    }
+    let removed = texture_cache_dir(app_handle).and_then(|cache_dir| {
+        remove_cached_files(&cache_dir, &pack.path, |_| true)
+            .map_err(|e| AppError::io(format!("Failed to clear texture cache: {}", e)))
+    });
+    match removed {
+        Ok(removed) => info!("Removed {} cached file(s) of {}", removed, pack.id),
+        Err(e) => warn!("{}: {}", pack.id, e.message),
+    }
+}
+
+/// Drop the indexed scan of a packs directory whose packs changed, logging failures
+fn forget_scan_index(packs_dir: &str) {
+    if let Err(e) =
+        scan_index::ScanIndex::open_default().and_then(|mut index| index.forget(packs_dir))
+    {
+        warn!("Failed to update scan index: {}", e);
+    }
+}
+
+/// Emit a `pack-library-changed` event, logging failures
+fn emit_pack_library_change(app_handle: &tauri::AppHandle, change: &PackLibraryChange) {
+    use tauri::Emitter;
+
+    if let Err(e) = app_handle.emit(PACK_LIBRARY_CHANGED_EVENT, change.clone()) {
+        warn!("Failed to emit {}: {}", PACK_LIBRARY_CHANGED_EVENT, e);
+    }
+}
+
+/// Replace the description in a folder pack's pack.mcmeta, keeping its other fields
+fn set_pack_description(pack_dir: &Path, description: &str) -> Result<(), AppError> {
+    let mcmeta_path = pack_dir.join("pack.mcmeta");
+    let text = std::fs::read_to_string(&mcmeta_path)
+        .map_err(|e| AppError::io(format!("Failed to read pack.mcmeta: {}", e)))?;
+    let mut mcmeta: serde_json::Value = serde_json::from_str(&text)
+        .map_err(|e| AppError::validation(format!("Invalid pack.mcmeta: {}", e)))?;
+
+    let pack_section = mcmeta
+        .get_mut("pack")
+        .and_then(|pack| pack.as_object_mut())
+        .ok_or_else(|| AppError::validation("pack.mcmeta has no \"pack\" section"))?;
+    pack_section.insert(
+        "description".to_string(),
+        serde_json::Value::String(description.to_string()),
+    );
+
+    let text = serde_json::to_string_pretty(&mcmeta)
+        .map_err(|e| AppError::internal("Failed to serialize pack.mcmeta", e.to_string()))?;
+    std::fs::write(&mcmeta_path, text)
+        .map_err(|e| AppError::io(format!("Failed to write pack.mcmeta: {}", e)))
+}
+
+/// Move a pack to the OS trash
+///
+/// Cached textures of ZIP packs are dropped and a `pack-library-changed` event
+/// is emitted so the library removes the pack without a rescan.
+///
+/// # Arguments
+/// * `pack_id` - Pack to delete; the vanilla pack can't be deleted
+/// * `packs_dir` - Resource packs directory
+///
+/// # Returns
+/// The payload of the emitted event
+pub fn delete_pack_impl(
+    pack_id: String,
+    packs_dir: String,
+    app_handle: &tauri::AppHandle,
+) -> Result<PackLibraryChange, AppError> {
+    let pack = find_library_pack(&pack_id, &packs_dir)?;
+
+    crate::util::zip::evict_cached_archive(&pack.path);
+    trash::delete(&pack.path)
+        .map_err(|e| AppError::io(format!("Failed to move {} to the trash: {}", pack_id, e)))?;
+    info!("Moved {} to the trash", pack.path);
+    forget_cached_pack(&pack, app_handle);
+    forget_scan_index(&packs_dir);
+
+    let change = PackLibraryChange {
+        pack_id,
+        pack: None,
+    };
+    emit_pack_library_change(app_handle, &change);
+    Ok(change)
+}
+
+/// Rename a pack
+///
+/// ZIP packs are renamed to "<new_name>.zip" and folder packs to "<new_name>";
+/// the file name is the pack's ID and display name. Folder packs can also get
+/// a new description written into their pack.mcmeta. Emits a
+/// `pack-library-changed` event with the renamed pack.
+///
+/// # Arguments
+/// * `pack_id` - Pack to rename; the vanilla pack can't be renamed
+/// * `new_name` - New name, without the .zip extension
+/// * `description` - New pack.mcmeta description (folder packs only)
+/// * `packs_dir` - Resource packs directory
+///
+/// # Returns
+/// The payload of the emitted event, holding the pack under its new ID
+pub fn rename_pack_impl(
+    pack_id: String,
+    new_name: String,
+    description: Option<String>,
+    packs_dir: String,
+    app_handle: &tauri::AppHandle,
+) -> Result<PackLibraryChange, AppError> {
+    validation::validate_pack_name(&new_name)?;
+    let pack = find_library_pack(&pack_id, &packs_dir)?;
+    if pack.is_zip && description.is_some() {
+        return Err(AppError::validation(
+            "The description of a ZIP pack can't be edited in place; unpack it first",
+        ));
+    }
+
+    let new_id = if pack.is_zip {
+        format!("{}.zip", new_name)
+    } else {
+        new_name
+    };
+    let old_path = Path::new(&pack.path);
+    let new_path = old_path.with_file_name(&new_id);
+    if new_id != pack_id && new_path.exists() {
+        return Err(AppError::validation(format!(
+            "A pack named {} already exists",
+            new_id
+        )));
+    }
+
+    if let Some(description) = &description {
+        set_pack_description(old_path, description)?;
+    }
+    if new_id != pack_id {
+        crate::util::zip::evict_cached_archive(&pack.path);
+        std::fs::rename(old_path, &new_path)
+            .map_err(|e| AppError::io(format!("Failed to rename {}: {}", pack_id, e)))?;
+        info!("Renamed {} to {}", pack_id, new_id);
+        forget_cached_pack(&pack, app_handle);
+    }
+    forget_scan_index(&packs_dir);
+
+    let vanilla_pack = create_vanilla_pack()?;
+    let renamed = find_pack(&new_id, &packs_dir, &vanilla_pack)?;
+    let change = PackLibraryChange {
+        pack_id,
+        pack: Some(renamed),
+    };
+    emit_pack_library_change(app_handle, &change);
+    Ok(change)
+}
+
+/// Result of importing a pack into the packs directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackImportResult {
+    /// The imported pack, or None when it was skipped as a duplicate
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pack: Option<crate::model::PackMeta>,
+    /// Library packs with the same content, together with the imported file name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duplicates: Option<crate::model::DuplicatePacks>,
+}
+
+/// Library packs with the same content as a pack about to be imported
+///
+/// Only packs whose content size matches get hashed.
+fn find_library_duplicates<'a>(
+    source: &Path,
+    is_zip: bool,
+    library: &'a [crate::model::PackMeta],
+) -> Result<Option<(String, Vec<&'a crate::model::PackMeta>)>, AppError> {
+    let read_error =
+        |e: anyhow::Error| AppError::io(format!("Failed to read {}: {}", source.display(), e));
+    let size = pack_scanner::pack_content_size(source, is_zip).map_err(read_error)?;
+    let candidates: Vec<&crate::model::PackMeta> = library
+        .iter()
+        .filter(|pack| {
+            pack_scanner::pack_content_size(Path::new(&pack.path), pack.is_zip)
+                .is_ok_and(|pack_size| pack_size == size)
+        })
+        .collect();
+    if candidates.is_empty() {
+        return Ok(None);
+    }
+
+    let hash = pack_scanner::pack_content_hash(source, is_zip).map_err(read_error)?;
+    let matches: Vec<&crate::model::PackMeta> = candidates
+        .into_iter()
+        .filter(|pack| {
+            pack_scanner::pack_content_hash(Path::new(&pack.path), pack.is_zip)
+                .is_ok_and(|pack_hash| pack_hash == hash)
+        })
+        .collect();
+    Ok((!matches.is_empty()).then_some((hash, matches)))
+}
+
+/// Copy a pack (ZIP or folder) into the packs directory
+///
+/// Packs whose content matches a pack already in the library, even under a
+/// different file name, are reported instead of imported unless
+/// `allow_duplicate` is set.
+///
+/// # Arguments
+/// * `source_path` - ZIP file or folder with a pack.mcmeta to import
+/// * `packs_dir` - Resource packs directory to copy into
+/// * `allow_duplicate` - Import even when the content is already in the library
+///
+/// # Returns
+/// The imported pack and any duplicates found
+pub fn import_pack_impl(
+    source_path: String,
+    packs_dir: String,
+    allow_duplicate: Option<bool>,
+) -> Result<PackImportResult, AppError> {
+    validation::validate_directory(&packs_dir, "Packs directory")?;
+
+    let source = Path::new(&source_path);
+    let is_zip = source.is_file()
+        && source
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"));
+    if !is_zip && !source.join("pack.mcmeta").is_file() {
+        return Err(AppError::validation(format!(
+            "Not a resource pack (expected a .zip or a folder with pack.mcmeta): {}",
+            source_path
+        )));
+    }
+    if source.starts_with(&packs_dir) {
+        return Err(AppError::validation(format!(
+            "Pack is already in the packs directory: {}",
+            source_path
+        )));
+    }
+
+    let file_name = source
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| AppError::validation(format!("Invalid pack path: {}", source_path)))?
+        .to_string();
+    let dest = Path::new(&packs_dir).join(&file_name);
+    if dest.exists() {
+        return Err(AppError::validation(format!(
+            "A pack named {} already exists",
+            file_name
+        )));
+    }
+
+    let library = pack_scanner::scan_packs(&packs_dir)
+        .map_err(|e| AppError::scan(format!("Failed to scan packs: {}", e)))?;
+    let duplicates = find_library_duplicates(source, is_zip, &library)?.map(|(hash, matches)| {
+        let name = file_name.strip_suffix(".zip").unwrap_or(&file_name);
+        let mut pack_ids: Vec<String> = matches.iter().map(|pack| pack.id.clone()).collect();
+        pack_ids.push(file_name.clone());
+        pack_ids.sort();
+        crate::model::DuplicatePacks {
+            content_hash: hash,
+            pack_ids,
+            different_names: matches.iter().any(|pack| pack.name != name),
+        }
+    });
+
+    if let Some(duplicates) = &duplicates {
+        info!(
+            "{} duplicates {}",
+            file_name,
+            duplicates.pack_ids.join(", ")
+        );
+        if !allow_duplicate.unwrap_or(false) {
+            return Ok(PackImportResult {
+                pack: None,
+                duplicates: Some(duplicates.clone()),
+            });
+        }
+    }
+
+    if is_zip {
+        std::fs::copy(source, &dest).map(|_| 1)
+    } else {
+        pack_files::copy_dir_all(source, &dest)
+    }
+    .map_err(|e| AppError::io(format!("Failed to import {}: {}", file_name, e)))?;
+    info!("Imported {} into {}", file_name, packs_dir);
+    forget_scan_index(&packs_dir);
+
+    let vanilla_pack = create_vanilla_pack()?;
+    let pack = find_pack(&file_name, &packs_dir, &vanilla_pack)?;
+    Ok(PackImportResult {
+        pack: Some(pack),
+        duplicates,
+    })
+}
+
+/// Find files that several packs provide with identical content
+///
+/// Every provider of an asset with more than one provider (vanilla included)
+/// is hashed, so packs that ship unchanged copies of shared upstream art show
+/// up. Building with `dedupe_identical` leaves files identical to vanilla out
+/// of the nest.
+///
+/// # Arguments
+/// * `packs_dir` - Resource packs directory
+///
+/// # Returns
+/// Identical files with the packs providing them, sorted by file path
+pub fn find_duplicate_assets_impl(
+    packs_dir: String,
+) -> Result<Vec<crate::model::DuplicateAsset>, AppError> {
+    validation::validate_directory(&packs_dir, "Packs directory")?;
+
+    let mut packs = low_memory::run(|| scan_library(&packs_dir))
+        .map_err(|e| AppError::scan(format!("Failed to scan packs: {}", e)))?;
+    packs.push(create_vanilla_pack()?);
+    let (assets, providers) =
+        low_memory::run(|| asset_indexer::index_assets(&packs)).map_err(|e| {
+            AppError::known(&e)
+                .unwrap_or_else(|| AppError::scan(format!("Asset indexing failed: {}", e)))
+        })?;
+
+    let duplicates =
+        low_memory::run(|| asset_hashes::find_duplicate_assets(&packs, &assets, &providers));
+    info!(
+        "{} file(s) are provided identically by several packs",
+        duplicates.len()
+    );
+    Ok(duplicates)
+}
+
+/// Turn low-memory mode on or off
+///
+/// Mirrors the app setting. While enabled, scans and builds process packs one
+/// at a time and nest builds stream each file to the output instead of reading
+/// every winner into memory first.
+///
+/// # Arguments
+/// * `enabled` - Whether low-memory mode should be on
+///
+/// # Returns
+/// The mode now in effect
+pub fn set_low_memory_mode_impl(enabled: bool) -> Result<bool, AppError> {
+    low_memory::set_enabled(enabled);
+    Ok(low_memory::is_enabled())
+}
+
+/// Whether low-memory mode is on
+pub fn get_low_memory_mode_impl() -> Result<bool, AppError> {
+    Ok(low_memory::is_enabled())
+}
+
+/// Change the level the backend logs at
+///
+/// # Arguments
+/// * `level` - "error", "warn", "info", "debug", "trace" or "off"
+///
+/// # Returns
+/// The level now in effect
+pub fn set_log_level_impl(level: String) -> Result<String, AppError> {
+    logging::set_level(&level).map_err(|e| AppError::validation(e.to_string()))
+}
+
+/// Level the backend currently logs at
+pub fn get_log_level_impl() -> Result<String, AppError> {
+    Ok(logging::level())
+}
+
+/// The backend's most recent log lines, oldest first, for attaching to bug
+/// reports
+///
+/// # Arguments
+/// * `limit` - Most lines to return (default 500)
+pub fn get_recent_logs_impl(limit: Option<usize>) -> Result<Vec<String>, AppError> {
+    Ok(logging::recent_logs(limit.unwrap_or(500)))
+}
+
+/// Point the vanilla texture and decompiled source caches at a shared folder
+///
+/// Lets several machines (a household NAS, a lab share) reuse one extraction
+/// instead of each extracting gigabytes. If the folder turns out to be
+/// read-only, caches already in it are still used and anything missing is
+/// extracted locally.
+///
+/// # Arguments
+/// * `path` - Shared cache folder, or None to keep every cache on this machine
+///
+/// # Returns
+/// The cache locations now in effect
+pub fn set_shared_cache_dir_impl(
+    path: Option<String>,
+) -> Result<cache_location::SharedCacheStatus, AppError> {
+    if let Some(path) = &path {
+        validation::validate_directory(path, "Shared cache folder")?;
+    }
+    cache_location::set_shared_cache_root(path.as_deref().map(Path::new))
+        .map_err(|e| AppError::io(format!("Failed to set shared cache folder: {}", e)))
+}
+
+/// Current shared cache folder and whether this machine can write to it
+pub fn get_shared_cache_status_impl() -> Result<cache_location::SharedCacheStatus, AppError> {
+    cache_location::shared_cache_status()
+        .map_err(|e| AppError::io(format!("Failed to read cache locations: {}", e)))
+}
+
+/// Turn offline mode on or off
+///
+/// While on, nothing is downloaded: particle physics and block emissions come
+/// from the extraction caches or, failing that, the datasets bundled with the
+/// app. Vanilla textures are read from the local JAR or the existing cache as
+/// usual.
+///
+/// # Arguments
+/// * `enabled` - Whether offline mode should be on
+///
+/// # Returns
+/// The mode now in effect
+pub fn set_offline_mode_impl(enabled: bool) -> Result<bool, AppError> {
+    offline::set_enabled(enabled);
+    Ok(offline::is_enabled())
+}
+
+/// Where particle physics, block emissions and animations for a version come
+/// from, and why extraction fell back to bundled data
+///
+/// # Arguments
+/// * `version` - Minecraft version (defaults to the cached vanilla version)
+///
+/// # Returns
+/// Offline mode, whether the app runs degraded, and each dataset's source
+pub fn get_offline_status_impl(
+    version: Option<String>,
+) -> Result<offline::OfflineStatus, AppError> {
+    offline::offline_status(version)
+        .map_err(|e| AppError::io(format!("Failed to read offline status: {}", e)))
+}
+
+/// Set the mirrors, proxy and retry policy for Mojang and tool downloads
+///
+/// Used for the version manifest, mappings and CFR downloads behind particle
+/// and animation extraction. Vanilla textures are read from the local JAR and
+/// need no download.
+///
+/// # Arguments
+/// * `settings` - Mirror base URLs, proxy URL, timeout and retry count
+///
+/// # Returns
+/// The settings now in effect
+pub fn set_download_settings_impl(
+    settings: download::DownloadSettings,
+) -> Result<download::DownloadSettings, AppError> {
+    download::set_settings(settings).map_err(|e| AppError::validation(format!("{:#}", e)))
+}
+
+/// Download settings currently in effect
+pub fn get_download_settings_impl() -> Result<download::DownloadSettings, AppError> {
+    Ok(download::settings())
+}
+
+/// Choose which optional vanilla texture categories are extracted
+///
+/// Block, item and colormap textures are always extracted. Enabling a category
+/// the cache lacks re-extracts it on the next vanilla texture initialization.
+///
+/// # Arguments
+/// * `categories` - Categories to extract (entity, gui, particle, mob_effect, misc, other)
+///
+/// # Returns
+/// The categories now extracted
+pub fn set_vanilla_texture_categories_impl(
+    categories: Vec<vanilla_textures::TextureCategory>,
+) -> Result<Vec<vanilla_textures::TextureCategory>, AppError> {
+    Ok(vanilla_textures::set_texture_categories(&categories))
+}
+
+/// Optional vanilla texture categories currently extracted
+pub fn get_vanilla_texture_categories_impl(
+) -> Result<Vec<vanilla_textures::TextureCategory>, AppError> {
+    Ok(vanilla_textures::texture_categories())
+}
+
+/// Disk space taken by each extraction cache, per Minecraft version
+///
+/// Covers vanilla textures, decompiled sources, data extracted from them,
+/// mappings and the CFR decompiler.
+pub fn get_cache_usage_impl() -> Result<cache_usage::CacheUsage, AppError> {
+    cache_usage::cache_usage().map_err(|e| AppError::io(format!("Failed to measure caches: {}", e)))
+}
+
+/// Event emitted with a `MaintenanceReport` after each background maintenance run
+pub const CACHE_MAINTENANCE_EVENT: &str = "cache-maintenance-completed";
+
+/// Set the cache TTLs and turn background maintenance on or off
+///
+/// # Arguments
+/// * `settings` - TTLs to apply; a TTL of zero keeps that cache
+///
+/// # Returns
+/// The settings now in effect
+pub fn set_cache_maintenance_settings_impl(
+    settings: cache_maintenance::MaintenanceSettings,
+) -> Result<cache_maintenance::MaintenanceSettings, AppError> {
+    cache_maintenance::set_settings(settings);
+    Ok(cache_maintenance::settings())
+}
+
+/// Cache TTLs currently in effect
+pub fn get_cache_maintenance_settings_impl(
+) -> Result<cache_maintenance::MaintenanceSettings, AppError> {
+    Ok(cache_maintenance::settings())
+}
+
+/// Prune every cache past its TTL now, whether or not background maintenance
+/// is enabled
+///
+/// # Returns
+/// The space reclaimed per cache
+pub fn run_cache_maintenance_impl() -> Result<cache_maintenance::MaintenanceReport, AppError> {
+    cache_maintenance::run(&cache_maintenance::settings())
+        .map_err(|e| extraction_error(e, "Cache maintenance failed"))
+}
+
+/// Delete an extraction cache, or one version of it
+///
+/// Everything cleared is rebuilt on demand, by extracting from the JAR again or
+/// downloading and decompiling again.
+///
+/// # Arguments
+/// * `category` - Cache to clear
+/// * `version` - Minecraft version to clear; None clears every version
+///
+/// # Returns
+/// Bytes freed
+pub fn clear_cache_impl(
+    category: cache_usage::CacheCategory,
+    version: Option<String>,
+) -> Result<u64, AppError> {
+    cache_usage::clear_cache(category, version.as_deref())
+        .map_err(|e| AppError::io(format!("Failed to clear cache: {}", e)))
+}
+
+/// Set the language of backend messages
+///
+/// Validation errors, pack compatibility issues and nest build warnings are
+/// produced in the negotiated locale from now on, so exported reports match the
+/// app language.
+///
+/// # Arguments
+/// * `locales` - Preferred locales, most preferred first (e.g. navigator.languages)
+///
+/// # Returns
+/// The locale in effect, falling back to en-US when none is available
+pub fn set_backend_locale_impl(locales: Vec<String>) -> Result<String, AppError> {
+    Ok(i18n::set_locale(&locales))
+}
+
+/// Benchmark this machine and recommend scan/build settings
+///
+/// Reads up to 64 MB of the packs directory and decompresses up to 32 MB of
+/// its ZIP packs (at most a couple of seconds each). With `apply`, scans and
+/// builds are capped to the recommended thread count right away; low-memory
+/// mode is only recommended, since it is a user setting.
+///
+/// # Arguments
+/// * `packs_dir` - Resource packs directory to measure
+/// * `apply` - Use the recommended thread count from now on
+///
+/// # Returns
+/// Measurements and the recommended settings
+pub fn run_perf_selftest_impl(
+    packs_dir: String,
+    apply: Option<bool>,
+) -> Result<perf_selftest::PerfReport, AppError> {
+    validation::validate_directory(&packs_dir, "Packs directory")?;
+
+    let report = perf_selftest::run_perf_selftest(Path::new(&packs_dir));
+    if apply.unwrap_or(false) {
+        low_memory::set_thread_limit(Some(report.recommended.thread_pool_size));
+        info!(
+            "Scans and builds now use {} thread(s)",
+            report.recommended.thread_pool_size
+        );
+    }
+    Ok(report)
+}
+
+/// Get the call count and latency of each backend command
+///
+/// # Returns
+/// Count and p50/p95/max latency per command since the app started, the
+/// command with the most total time first
+pub fn get_perf_metrics_impl() -> Result<Vec<perf_metrics::CommandMetrics>, AppError> {
+    Ok(perf_metrics::get_metrics())
+}
+
+/// Run the synthetic-pack self-test suite (scan, validate, build)
+///
+/// Only available in builds with the `selftest` feature. Packs are generated
+/// in a temporary directory and removed afterwards; the user's packs directory
+/// is never touched.
+///
+/// # Arguments
+/// * `config` - Pack count, textures per pack, conflicts and broken files
+///   (defaults when omitted)
+///
+/// # Returns
+/// Per-stage results and whether the whole pipeline passed
+#[cfg(feature = "selftest")]
+pub fn run_selftest_suite_impl(
+    config: Option<crate::util::selftest::SyntheticSuiteConfig>,
+) -> Result<crate::util::selftest::SelftestReport, AppError> {
+    let config = config.unwrap_or_default();
+    if config.packs == 0 {
+        return Err(AppError::validation("Self-test needs at least one pack"));
+    }
+    Ok(crate::util::selftest::run_selftest_suite(&config))
+}
+
+/// Open the scan index, mapping failures to an IO error
+fn open_scan_index() -> Result<scan_index::ScanIndex, AppError> {
+    scan_index::ScanIndex::open_default()
+        .map_err(|e| AppError::io(format!("Failed to open scan index: {}", e)))
+}
+
+/// Last scan of a packs directory stored in the scan index
+///
+/// Lets the library show up on startup before a fresh scan finishes. The index
+/// is dropped whenever packs are imported, deleted or renamed through the app,
+/// but changes made outside it are only picked up by the next scan.
+///
+/// # Arguments
+/// * `packs_dir` - Resource packs directory the scan was made of
+///
+/// # Returns
+/// The stored ScanResult, or None if the directory hasn't been indexed
+pub fn load_scan_index_impl(packs_dir: String) -> Result<Option<ScanResult>, AppError> {
+    let scan = open_scan_index()?
+        .load(&packs_dir)
+        .map_err(|e| AppError::io(format!("Failed to read scan index: {}", e)))?;
+    if let Some(scan) = &scan {
+        asset_search::replace_index(&scan.assets);
+        asset_protocol::register_packs(&scan.packs);
+    }
+    Ok(scan)
+}
+
+/// Fuzzy search over the assets of the last scan
+///
+/// Matches asset IDs and labels by name, prefix, substring, abbreviation and
+/// (for typos) shared trigrams, so the webview doesn't have to filter every
+/// asset itself. The index is rebuilt by every scan and by `load_scan_index`.
+///
+/// # Arguments
+/// * `query` - Search text; whitespace-separated terms must all match
+/// * `limit` - Maximum number of results (defaults to 100)
+///
+/// # Returns
+/// Matching asset IDs with their scores, best match first
+pub fn search_assets_impl(
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<asset_search::AssetSearchHit>, AppError> {
+    Ok(asset_search::search(&query, limit.unwrap_or(100)))
+}
+
+/// Search the scan index for assets carrying a label
+///
+/// # Arguments
+/// * `packs_dir` - Resource packs directory the scan was made of
+/// * `label` - Label to match, case-insensitively (e.g. "block", "emissive")
+/// * `limit` - Maximum number of assets to return (defaults to 200)
+///
+/// # Returns
+/// Matching assets in scan order
+pub fn search_indexed_assets_impl(
+    packs_dir: String,
+    label: String,
+    limit: Option<usize>,
+) -> Result<Vec<AssetRecord>, AppError> {
+    open_scan_index()?
+        .search_by_label(&packs_dir, label.trim(), limit.unwrap_or(200))
+        .map_err(|e| AppError::io(format!("Failed to search scan index: {}", e)))
+}
+
+/// Providers of an asset according to the scan index
+///
+/// # Arguments
+/// * `packs_dir` - Resource packs directory the scan was made of
+/// * `asset_id` - Asset ID (e.g. "minecraft:block/stone")
+///
+/// # Returns
+/// Pack IDs providing the asset, in scan order; empty if it isn't indexed
+pub fn get_indexed_providers_impl(
+    packs_dir: String,
+    asset_id: String,
+) -> Result<Vec<String>, AppError> {
+    open_scan_index()?
+        .providers(&packs_dir, &asset_id)
+        .map_err(|e| AppError::io(format!("Failed to read scan index: {}", e)))
+}
+
+/// Load a model JSON directly by model ID (after blockstate resolution)
 ///
 /// This is a simpler version that just loads the model JSON without going through
 /// blockstate resolution. Use this after you've already resolved a blockstate to a model ID.
@@ -483,6 +2799,8 @@ pub fn get_pack_texture_path_impl(
 /// * `pack_id` - ID of the resource pack to read from
 /// * `model_id` - Model ID (e.g., "minecraft:block/acacia_log_horizontal" or "block/dirt")
 /// * `packs_dir` - Directory containing resource packs
+/// * `target` - Workspace target version; vanilla fallback must match it
+/// * `policy` - What to do when the pack lacks the asset (defaults to vanilla fallback)
 ///
 /// # Returns
 /// BlockModel JSON with parent inheritance applied
@@ -490,29 +2808,128 @@ pub fn load_model_json_impl(
     pack_id: String,
     model_id: String,
     packs_dir: String,
+    target: Option<TargetVersion>,
+    policy: Option<MissingAssetPolicy>,
 ) -> Result<crate::util::block_models::BlockModel, AppError> {
     // Validate inputs
     validation::validate_directory(&packs_dir, "Packs directory")?;
 
     // Create vanilla pack
-    let vanilla_pack = create_vanilla_pack()?;
+    let policy = policy.unwrap_or_default();
+    let vanilla_pack = vanilla_pack_for_target(target.as_ref())?;
 
     // Get target pack
     let target_pack = if pack_id == "minecraft:vanilla" {
         vanilla_pack.clone()
     } else {
-        let packs = pack_scanner::scan_packs(&packs_dir)
+        let packs = scan_library(&packs_dir)
             .map_err(|e| AppError::scan(format!("Failed to scan packs: {}", e)))?;
         packs
             .iter()
             .find(|p| p.id == pack_id)
-            .ok_or_else(|| AppError::validation(format!("Pack not found: {}", pack_id)))?
+            .ok_or_else(|| AppError::pack_not_found(&pack_id))?
             .clone()
     };
 
-    // Load model with parent inheritance and vanilla fallback
-    crate::util::block_models::resolve_block_model(&target_pack, &model_id, &vanilla_pack)
-        .map_err(|e| AppError::io(format!("Failed to load model: {}", e)))
+    // Load model with parent inheritance and vanilla fallback per policy
+    crate::util::block_models::resolve_block_model_with_policy(
+        &target_pack,
+        &model_id,
+        &vanilla_pack,
+        policy,
+    )
+    .map_err(|e| AppError::io(format!("Failed to load model: {}", e)))
+}
+
+/// Diagnose a model's textures after parent merging
+///
+/// Lists unresolved `#variable` references and textures that exist in neither
+/// the pack nor vanilla, which render as the missing texture in game.
+///
+/// # Arguments
+/// * `pack_id` - ID of the resource pack the model is in
+/// * `model_id` - Model ID (e.g., "minecraft:block/furnace" or "block/dirt")
+/// * `packs_dir` - Directory containing resource packs
+/// * `target` - Workspace target version; vanilla fallback must match it
+///
+/// # Errors
+/// - VALIDATION_ERROR: Model not found, invalid, or its parent chain is circular
+///
+/// # Returns
+/// The resolved texture map and the broken references found
+pub fn diagnose_model_impl(
+    pack_id: String,
+    model_id: String,
+    packs_dir: String,
+    target: Option<TargetVersion>,
+) -> Result<crate::util::block_models::ModelDiagnosis, AppError> {
+    validation::validate_directory(&packs_dir, "Packs directory")?;
+
+    let vanilla_pack = vanilla_pack_for_target(target.as_ref())?;
+    let target_pack = find_pack(&pack_id, &packs_dir, &vanilla_pack)?;
+
+    crate::util::block_models::diagnose_model(&target_pack, &model_id, &vanilla_pack)
+}
+
+/// Lint every blockstate and model JSON file in a pack
+///
+/// Flags unknown keys, unsupported rotations, missing models and parents,
+/// out-of-range UVs and elements outside the allowed bounds.
+///
+/// # Arguments
+/// * `pack_id` - ID of the resource pack to lint
+/// * `packs_dir` - Directory containing resource packs
+/// * `target` - Workspace target version; vanilla fallback must match it
+///
+/// # Errors
+/// - PACK_NOT_FOUND: No pack with that ID
+/// - IO_ERROR: The pack's files couldn't be listed
+///
+/// # Returns
+/// Diagnostics with file, line hint and severity, plus counts
+pub fn lint_pack_impl(
+    pack_id: String,
+    packs_dir: String,
+    target: Option<TargetVersion>,
+) -> Result<crate::util::pack_lint::LintReport, AppError> {
+    validation::validate_directory(&packs_dir, "Packs directory")?;
+
+    let vanilla_pack = vanilla_pack_for_target(target.as_ref())?;
+    let target_pack = find_pack(&pack_id, &packs_dir, &vanilla_pack)?;
+
+    crate::util::pack_lint::lint_pack(&target_pack, &vanilla_pack)
+        .map_err(|e| AppError::io(format!("Failed to lint pack: {}", e)))
+}
+
+/// Check the references between a pack's files
+///
+/// Reports textures models point at that don't exist, .mcmeta files without
+/// their PNG, sounds.json entries without their .ogg, CIT rules referencing
+/// absent files, and block/item textures and models nothing uses.
+///
+/// # Arguments
+/// * `pack_id` - ID of the resource pack to check
+/// * `packs_dir` - Directory containing resource packs
+/// * `target` - Workspace target version; vanilla fallback must match it
+///
+/// # Errors
+/// - PACK_NOT_FOUND: No pack with that ID
+/// - IO_ERROR: The pack's files couldn't be listed
+///
+/// # Returns
+/// The issues found, in file order
+pub fn check_pack_integrity_impl(
+    pack_id: String,
+    packs_dir: String,
+    target: Option<TargetVersion>,
+) -> Result<crate::util::pack_doctor::PackIntegrityReport, AppError> {
+    validation::validate_directory(&packs_dir, "Packs directory")?;
+
+    let vanilla_pack = vanilla_pack_for_target(target.as_ref())?;
+    let target_pack = find_pack(&pack_id, &packs_dir, &vanilla_pack)?;
+
+    crate::util::pack_doctor::check_pack_integrity(&target_pack, &vanilla_pack)
+        .map_err(|e| AppError::io(format!("Failed to check pack: {}", e)))
 }
 
 /// Read a Minecraft block model JSON file from texture ID
@@ -523,6 +2940,8 @@ pub fn load_model_json_impl(
 /// * `pack_id` - ID of the resource pack to read from
 /// * `texture_id` - Texture/asset ID (e.g., "minecraft:block/dirt")
 /// * `packs_dir` - Directory containing resource packs
+/// * `target` - Workspace target version; vanilla fallback must match it
+/// * `policy` - What to do when the pack lacks the asset (defaults to vanilla fallback)
 ///
 /// # Returns
 /// Fully resolved BlockModel JSON with parent inheritance applied
@@ -530,26 +2949,29 @@ pub fn read_block_model_impl(
     pack_id: String,
     texture_id: String,
     packs_dir: String,
+    target: Option<TargetVersion>,
+    policy: Option<MissingAssetPolicy>,
 ) -> Result<crate::util::block_models::BlockModel, AppError> {
     // Validate inputs
     validation::validate_directory(&packs_dir, "Packs directory")?;
 
     // Create vanilla pack first
-    let vanilla_pack = create_vanilla_pack()?;
+    let policy = policy.unwrap_or_default();
+    let vanilla_pack = vanilla_pack_for_target(target.as_ref())?;
 
     // If requesting vanilla directly, use it
     let target_pack = if pack_id == "minecraft:vanilla" {
         vanilla_pack.clone()
     } else {
         // Scan packs to find the requested pack
-        let packs = pack_scanner::scan_packs(&packs_dir)
+        let packs = scan_library(&packs_dir)
             .map_err(|e| AppError::scan(format!("Failed to scan packs: {}", e)))?;
 
         // Find the target pack
         packs
             .iter()
             .find(|p| p.id == pack_id)
-            .ok_or_else(|| AppError::validation(format!("Pack not found: {}", pack_id)))?
+            .ok_or_else(|| AppError::pack_not_found(&pack_id))?
             .clone()
     };
 
@@ -624,6 +3046,13 @@ pub fn read_block_model_impl(
 
         if let Some(bs) = found_blockstate {
             (bs, found_block_id)
+        } else if policy == MissingAssetPolicy::Placeholder {
+            return Ok(crate::util::block_models::placeholder_model());
+        } else if policy == MissingAssetPolicy::PackOnly {
+            return Err(AppError::validation(format!(
+                "Blockstate for {} is not provided by pack {}",
+                texture_id, target_pack.name
+            )));
         } else {
             // Try vanilla blockstate with all candidates
             let mut found_vanilla = None;
@@ -660,19 +3089,68 @@ pub fn read_block_model_impl(
     })?;
 
     // Resolve the model with parent inheritance
-    let result =
-        crate::util::block_models::resolve_block_model(&target_pack, &model_id, &vanilla_pack)
-            .map_err(|e| AppError::io(format!("Failed to read block model: {}", e)));
+    let result = crate::util::block_models::resolve_block_model_with_policy(
+        &target_pack,
+        &model_id,
+        &vanilla_pack,
+        policy,
+    )
+    .map_err(|e| AppError::io(format!("Failed to read block model: {}", e)));
 
     result
 }
 
+/// Find and read a blockstate, falling back to vanilla only under `MissingAssetPolicy::UseVanilla`
+///
+/// Uses the universal blockstate finder, which matches names by normalizing them
+/// (removing underscores). Returns None when the pack lacks the blockstate and the
+/// policy is `Placeholder`.
+fn find_blockstate_with_policy(
+    target_pack: &crate::model::PackMeta,
+    vanilla_pack: &crate::model::PackMeta,
+    block_id: &str,
+    policy: MissingAssetPolicy,
+) -> Result<Option<(crate::util::blockstates::Blockstate, String)>, AppError> {
+    let mut search_packs = vec![target_pack];
+    if policy == MissingAssetPolicy::UseVanilla {
+        search_packs.push(vanilla_pack);
+    }
+
+    for pack in search_packs {
+        let pack_path = PathBuf::from(&pack.path);
+        if let Some(actual_block_id) =
+            crate::util::blockstates::find_blockstate_file(&pack_path, block_id, pack.is_zip)
+        {
+            let blockstate = crate::util::blockstates::read_blockstate(
+                &pack_path,
+                &actual_block_id,
+                pack.is_zip,
+            )?;
+            return Ok(Some((blockstate, actual_block_id)));
+        }
+    }
+
+    match policy {
+        MissingAssetPolicy::Placeholder => Ok(None),
+        MissingAssetPolicy::PackOnly => Err(AppError::validation(format!(
+            "Blockstate {} is not provided by pack {}",
+            block_id, target_pack.name
+        ))),
+        MissingAssetPolicy::UseVanilla => Err(AppError::validation(format!(
+            "Blockstate not found: {}",
+            block_id
+        ))),
+    }
+}
+
 /// Get the blockstate schema for a block (for UI generation)
 ///
 /// # Arguments
 /// * `pack_id` - Pack ID to search
 /// * `block_id` - Block name (e.g., "oak_stairs")
 /// * `packs_dir` - Root directory containing packs
+/// * `target` - Workspace target version; vanilla fallback must match it
+/// * `policy` - What to do when the pack lacks the asset (defaults to vanilla fallback)
 ///
 /// # Errors
 /// - VALIDATION_ERROR: Invalid inputs or block not found
@@ -683,70 +3161,42 @@ pub fn get_block_state_schema_impl(
     pack_id: String,
     block_id: String,
     packs_dir: String,
+    target: Option<TargetVersion>,
+    policy: Option<MissingAssetPolicy>,
 ) -> Result<crate::util::blockstates::BlockStateSchema, AppError> {
-    // Normalize block_id to strip texture path prefixes
-    let normalized_block_id = if let Some(stripped) = block_id.strip_prefix("minecraft:block/") {
-        stripped.to_string()
-    } else if let Some(stripped) = block_id.strip_prefix("block/") {
-        stripped.to_string()
-    } else if let Some(stripped) = block_id.strip_prefix("minecraft:") {
-        stripped.to_string()
-    } else {
-        block_id.clone()
-    };
+    // Normalize block_id to strip texture path prefixes, keeping modded namespaces
+    let normalized_block_id = crate::util::blockstates::normalize_block_id(&block_id);
 
     // Validate inputs
     validation::validate_directory(&packs_dir, "Packs directory")?;
 
     // Create vanilla pack
-    let vanilla_pack = create_vanilla_pack()?;
+    let policy = policy.unwrap_or_default();
+    let vanilla_pack = vanilla_pack_for_target(target.as_ref())?;
 
     // Get target pack
     let target_pack = if pack_id == "minecraft:vanilla" {
         vanilla_pack.clone()
     } else {
-        let packs = pack_scanner::scan_packs(&packs_dir)
+        let packs = scan_library(&packs_dir)
             .map_err(|e| AppError::scan(format!("Failed to scan packs: {}", e)))?;
         packs
             .iter()
             .find(|p| p.id == pack_id)
-            .ok_or_else(|| AppError::validation(format!("Pack not found: {}", pack_id)))?
+            .ok_or_else(|| AppError::pack_not_found(&pack_id))?
             .clone()
     };
 
     // Use universal blockstate finder to locate the file
-    let (blockstate, used_block_id) = {
-        // Try target pack first
-        if let Some(actual_block_id) = crate::util::blockstates::find_blockstate_file(
-            &PathBuf::from(&target_pack.path),
-            &normalized_block_id,
-            target_pack.is_zip,
-        ) {
-            let bs = crate::util::blockstates::read_blockstate(
-                &PathBuf::from(&target_pack.path),
-                &actual_block_id,
-                target_pack.is_zip,
-            )?;
-            (bs, actual_block_id)
-        }
-        // Fallback to vanilla
-        else if let Some(actual_block_id) = crate::util::blockstates::find_blockstate_file(
-            &PathBuf::from(&vanilla_pack.path),
-            &normalized_block_id,
-            vanilla_pack.is_zip,
-        ) {
-            let bs = crate::util::blockstates::read_blockstate(
-                &PathBuf::from(&vanilla_pack.path),
-                &actual_block_id,
-                vanilla_pack.is_zip,
-            )?;
-            (bs, actual_block_id)
-        } else {
-            return Err(AppError::validation(format!(
-                "Blockstate not found: {}",
-                normalized_block_id
-            )));
-        }
+    let Some((blockstate, used_block_id)) =
+        find_blockstate_with_policy(&target_pack, &vanilla_pack, &normalized_block_id, policy)?
+    else {
+        return Ok(crate::util::blockstates::BlockStateSchema {
+            block_id: normalized_block_id,
+            properties: Vec::new(),
+            default_state: HashMap::new(),
+            variants_map: None,
+        });
     };
 
     // Build schema
@@ -763,85 +3213,237 @@ pub fn get_block_state_schema_impl(
 /// * `packs_dir` - Root directory containing packs
 /// * `state_props` - Block state properties (e.g., {"facing": "north", "half": "bottom"})
 /// * `seed` - Random seed for weighted variant selection
+/// * `target` - Workspace target version; vanilla fallback must match it
+/// * `policy` - What to do when the pack lacks the asset (defaults to vanilla fallback)
+/// * `position` - World position to pick weighted variants at, as the target
+///   version of the game does; takes precedence over `seed`
 ///
 /// # Errors
 /// - VALIDATION_ERROR: Invalid inputs or resolution failed
 ///
 /// # Returns
 /// ResolutionResult with resolved models and their rotations
+#[allow(clippy::too_many_arguments)]
 pub fn resolve_block_state_impl(
     pack_id: String,
     block_id: String,
     packs_dir: String,
     state_props: Option<HashMap<String, String>>,
     seed: Option<u64>,
+    target: Option<TargetVersion>,
+    policy: Option<MissingAssetPolicy>,
+    position: Option<crate::util::blockstates::BlockPosition>,
 ) -> Result<crate::util::blockstates::ResolutionResult, AppError> {
-    // CRITICAL: Normalize block_id to strip texture path prefixes
-    // Input might be "minecraft:block/dark_oak_planks" but we need just "dark_oak_planks"
-    let normalized_block_id = if let Some(stripped) = block_id.strip_prefix("minecraft:block/") {
-        stripped.to_string()
-    } else if let Some(stripped) = block_id.strip_prefix("block/") {
-        stripped.to_string()
-    } else if let Some(stripped) = block_id.strip_prefix("minecraft:") {
-        stripped.to_string()
-    } else {
-        block_id.clone()
-    };
-
     // Validate inputs
     validation::validate_directory(&packs_dir, "Packs directory")?;
 
     // Create vanilla pack
-    let vanilla_pack = create_vanilla_pack()?;
+    let policy = policy.unwrap_or_default();
+    let vanilla_pack = vanilla_pack_for_target(target.as_ref())?;
 
     // Get target pack
     let target_pack = if pack_id == "minecraft:vanilla" {
         vanilla_pack.clone()
     } else {
-        let packs = pack_scanner::scan_packs(&packs_dir)
+        let packs = scan_library(&packs_dir)
             .map_err(|e| AppError::scan(format!("Failed to scan packs: {}", e)))?;
         packs
             .iter()
             .find(|p| p.id == pack_id)
-            .ok_or_else(|| AppError::validation(format!("Pack not found: {}", pack_id)))?
+            .ok_or_else(|| AppError::pack_not_found(&pack_id))?
             .clone()
     };
 
+    resolve_block_state_in_pack(
+        &target_pack,
+        &vanilla_pack,
+        &block_id,
+        state_props,
+        seed,
+        position,
+        policy,
+    )
+}
+
+/// Export a block, as a blockstate resolves it, to a glTF or OBJ file
+///
+/// Geometry, UVs and rotations follow the game; textures are embedded (glTF)
+/// or written next to the model (OBJ), with tinted faces baked in their
+/// plains color.
+///
+/// # Arguments
+/// * `pack_id` - Pack ID to search
+/// * `block_id` - Block name (e.g., "oak_stairs")
+/// * `packs_dir` - Root directory containing packs
+/// * `state_props` - Block state properties (e.g., {"facing": "north", "half": "bottom"})
+/// * `output_path` - File to write, ending in .gltf or .obj
+/// * `target` - Workspace target version; vanilla fallback must match it
+/// * `policy` - What to do when the pack lacks the asset (defaults to vanilla fallback)
+///
+/// # Errors
+/// - VALIDATION_ERROR: Bad output path, resolution failed or the block has no geometry
+/// - IO_ERROR: The files couldn't be written
+///
+/// # Returns
+/// The files written and the size of the exported mesh
+pub fn export_block_model_impl(
+    pack_id: String,
+    block_id: String,
+    packs_dir: String,
+    state_props: Option<HashMap<String, String>>,
+    output_path: String,
+    target: Option<TargetVersion>,
+    policy: Option<MissingAssetPolicy>,
+) -> Result<crate::util::model_export::ExportSummary, AppError> {
+    use crate::util::{biome_tint, model_export};
+
+    validation::validate_directory(&packs_dir, "Packs directory")?;
+    let output = PathBuf::from(&output_path);
+    if model_export::ExportFormat::from_path(&output).is_none() {
+        return Err(AppError::validation(format!(
+            "Export path must end in .gltf or .obj: {}",
+            output_path
+        )));
+    }
+    if !output.parent().is_some_and(Path::is_dir) {
+        return Err(AppError::validation(format!(
+            "Export folder doesn't exist: {}",
+            output_path
+        )));
+    }
+
+    let policy = policy.unwrap_or_default();
+    let vanilla_pack = vanilla_pack_for_target(target.as_ref())?;
+    let target_pack = find_pack(&pack_id, &packs_dir, &vanilla_pack)?;
+    let resolution = resolve_block_state_in_pack(
+        &target_pack,
+        &vanilla_pack,
+        &block_id,
+        state_props,
+        None,
+        None,
+        policy,
+    )?;
+
+    let layers = [&target_pack, &vanilla_pack];
+    let plains = biome_tint::Climate {
+        biome: Some("minecraft:plains".to_string()),
+        ..Default::default()
+    };
+    let tint = biome_tint::compute_block_tint(&layers, &resolution.block_id, &plains)
+        .ok()
+        .flatten()
+        .map(|block_tint| {
+            let [r, g, b] = block_tint.tint.rgb;
+            u32::from_be_bytes([0, r, g, b])
+        });
+
+    let mut builder = model_export::MeshBuilder::new(&layers, tint);
+    for placement in &resolution.models {
+        let model = crate::util::block_models::resolve_block_model_with_policy(
+            &target_pack,
+            &placement.model_id,
+            &vanilla_pack,
+            policy,
+        )?;
+        builder.add_block_model(&model, placement);
+    }
+    let mesh = builder.finish();
+    if mesh.quads.is_empty() {
+        return Err(AppError::validation(format!(
+            "{} has no model geometry to export",
+            resolution.block_id
+        )));
+    }
+
+    model_export::export_mesh(&mesh, &output)
+        .map_err(|e| AppError::io(format!("Failed to export model: {}", e)))
+}
+
+/// Import a Blockbench project (.bbmodel) into a pack
+///
+/// Block and item projects become model JSON; OptiFine entity projects become
+/// a JEM model. Embedded textures are written alongside. ZIP packs are written
+/// to their working copy, never the archive itself.
+///
+/// # Arguments
+/// * `project_path` - The .bbmodel file
+/// * `target_pack` - Pack ID to write into
+/// * `asset_id` - Model ID (e.g., "minecraft:block/lamp") or, for entity
+///   projects, entity ID (e.g., "minecraft:cow")
+/// * `packs_dir` - Root directory containing packs
+///
+/// # Errors
+/// - VALIDATION_ERROR: Project not found, target is the vanilla pack or the
+///   project couldn't be converted
+/// - PACK_NOT_FOUND: No pack with the given ID
+///
+/// # Returns
+/// The files written, relative to the pack, and conversion warnings
+pub fn import_bbmodel_impl(
+    project_path: String,
+    target_pack: String,
+    asset_id: String,
+    packs_dir: String,
+) -> Result<crate::util::bbmodel::BbmodelImport, AppError> {
+    use crate::util::{asset_editor, bbmodel};
+
+    validation::validate_directory(&packs_dir, "Packs directory")?;
+    if !Path::new(&project_path).is_file() {
+        return Err(AppError::validation(format!(
+            "Project not found: {}",
+            project_path
+        )));
+    }
+
+    let vanilla_pack = create_vanilla_pack()?;
+    let pack = find_pack(&target_pack, &packs_dir, &vanilla_pack)?;
+    if pack.id == vanilla_pack.id {
+        return Err(AppError::validation("Can't import into the vanilla pack"));
+    }
+    let pack_dir = asset_editor::writable_pack_dir(&pack.path, pack.is_zip)
+        .map_err(|e| AppError::validation(e.to_string()))?;
+
+    let imported = bbmodel::import_bbmodel(Path::new(&project_path), &pack_dir, &asset_id)
+        .map_err(|e| AppError::validation(format!("Failed to import project: {}", e)))?;
+
+    // The new model may shadow one resolved before
+    crate::util::model_resolver::clear_cache();
+    Ok(imported)
+}
+
+/// Resolve a blockstate against an already-loaded pack and vanilla pack
+fn resolve_block_state_in_pack(
+    target_pack: &crate::model::PackMeta,
+    vanilla_pack: &crate::model::PackMeta,
+    block_id: &str,
+    state_props: Option<HashMap<String, String>>,
+    seed: Option<u64>,
+    position: Option<crate::util::blockstates::BlockPosition>,
+    policy: MissingAssetPolicy,
+) -> Result<crate::util::blockstates::ResolutionResult, AppError> {
+    // CRITICAL: Normalize block_id to strip texture path prefixes
+    // Input might be "minecraft:block/dark_oak_planks" but we need just "dark_oak_planks";
+    // modded blocks keep their namespace ("create:block/cogwheel" -> "create:cogwheel")
+    let normalized_block_id = crate::util::blockstates::normalize_block_id(block_id);
+
     // Use universal blockstate finder to locate the file
     // This scans the directory and matches by normalizing names (removing underscores)
     // Works with any block type without needing a hardcoded list
-    let (blockstate, used_block_id) = {
-        // Try target pack first
-        if let Some(actual_block_id) = crate::util::blockstates::find_blockstate_file(
-            &PathBuf::from(&target_pack.path),
-            &normalized_block_id,
-            target_pack.is_zip,
-        ) {
-            let bs = crate::util::blockstates::read_blockstate(
-                &PathBuf::from(&target_pack.path),
-                &actual_block_id,
-                target_pack.is_zip,
-            )?;
-            (bs, actual_block_id)
-        }
-        // Fallback to vanilla
-        else if let Some(actual_block_id) = crate::util::blockstates::find_blockstate_file(
-            &PathBuf::from(&vanilla_pack.path),
-            &normalized_block_id,
-            vanilla_pack.is_zip,
-        ) {
-            let bs = crate::util::blockstates::read_blockstate(
-                &PathBuf::from(&vanilla_pack.path),
-                &actual_block_id,
-                vanilla_pack.is_zip,
-            )?;
-            (bs, actual_block_id)
-        } else {
-            return Err(AppError::validation(format!(
-                "Blockstate not found: {}",
-                normalized_block_id
-            )));
-        }
+    let Some((blockstate, used_block_id)) =
+        find_blockstate_with_policy(target_pack, vanilla_pack, &normalized_block_id, policy)?
+    else {
+        return Ok(crate::util::blockstates::ResolutionResult {
+            block_id: normalized_block_id,
+            state_props: HashMap::new(),
+            models: vec![crate::util::blockstates::ResolvedModel {
+                model_id: crate::util::block_models::PLACEHOLDER_MODEL_ID.to_string(),
+                rot_x: 0,
+                rot_y: 0,
+                rot_z: 0,
+                uvlock: false,
+            }],
+        });
     };
 
     // Build schema to get valid properties for this block
@@ -885,19 +3487,222 @@ pub fn resolve_block_state_impl(
         _ => Some(schema.default_state.clone()),
     };
 
-    // Resolve blockstate
-    crate::util::blockstates::resolve_blockstate(
-        &blockstate,
-        &used_block_id,
-        final_props,
-        seed,
+    // Resolve blockstate
+    match position {
+        Some(position) => {
+            // Vanilla carries the target's format; without a target it is the active version's
+            let pack_format = vanilla_pack.pack_format.or_else(|| {
+                vanilla_textures::get_cached_version()
+                    .ok()
+                    .flatten()
+                    .and_then(|version| crate::util::pack_format::pack_format_for_version(&version))
+            });
+            crate::util::blockstates::resolve_blockstate_at(
+                &blockstate,
+                &used_block_id,
+                final_props,
+                position,
+                crate::util::blockstates::WeightedRoll::for_pack_format(pack_format),
+            )
+        }
+        None => crate::util::blockstates::resolve_blockstate(
+            &blockstate,
+            &used_block_id,
+            final_props,
+            seed,
+        ),
+    }
+}
+
+/// Resolve many blockstates in one call, in parallel
+///
+/// Packs are scanned once for the whole batch, so a screen of blocks costs one
+/// IPC round trip instead of one per block.
+///
+/// # Arguments
+/// * `packs_dir` - Root directory containing packs
+/// * `requests` - Blockstates to resolve (pack, block, state props, seed)
+/// * `target` - Workspace target version; vanilla fallback must match it
+/// * `policy` - What to do when a pack lacks the asset (defaults to vanilla fallback)
+///
+/// # Errors
+/// - VALIDATION_ERROR: Invalid packs directory or target version mismatch
+///
+/// # Returns
+/// One result or error per request, in request order
+pub fn resolve_block_states_batch_impl(
+    packs_dir: String,
+    requests: Vec<BlockStateRequest>,
+    target: Option<TargetVersion>,
+    policy: Option<MissingAssetPolicy>,
+) -> Result<Vec<BatchResult<crate::util::blockstates::ResolutionResult>>, AppError> {
+    validation::validate_directory(&packs_dir, "Packs directory")?;
+
+    let policy = policy.unwrap_or_default();
+    let vanilla_pack = vanilla_pack_for_target(target.as_ref())?;
+
+    let packs = if requests.iter().any(|r| r.pack_id != vanilla_pack.id) {
+        scan_library(&packs_dir)
+            .map_err(|e| AppError::scan(format!("Failed to scan packs: {}", e)))?
+    } else {
+        Vec::new()
+    };
+
+    Ok(requests
+        .into_par_iter()
+        .map(|request| {
+            let target_pack = if request.pack_id == vanilla_pack.id {
+                Ok(&vanilla_pack)
+            } else {
+                packs
+                    .iter()
+                    .find(|p| p.id == request.pack_id)
+                    .ok_or_else(|| AppError::pack_not_found(&request.pack_id))
+            };
+            target_pack
+                .and_then(|pack| {
+                    resolve_block_state_in_pack(
+                        pack,
+                        &vanilla_pack,
+                        &request.block_id,
+                        request.state_props,
+                        request.seed,
+                        request.position,
+                        policy,
+                    )
+                })
+                .into()
+        })
+        .collect())
+}
+
+/// List every property combination of a block, each resolved to its models
+///
+/// For a "gallery of all variants" view, in pages so a block with hundreds of
+/// states doesn't need one call per state. Blocks with more than
+/// `MAX_STATE_PERMUTATIONS` combinations are cut off there.
+///
+/// # Arguments
+/// * `pack_id` - Pack ID to search
+/// * `block_id` - Block name (e.g., "oak_stairs")
+/// * `packs_dir` - Root directory containing packs
+/// * `offset` - Index of the first permutation to return (default 0)
+/// * `limit` - Most permutations to return (default 64, at most 256)
+/// * `target` - Workspace target version; vanilla fallback must match it
+/// * `policy` - What to do when the pack lacks the asset (defaults to vanilla fallback)
+///
+/// # Errors
+/// - VALIDATION_ERROR: Invalid inputs or block not found
+///
+/// # Returns
+/// The page of permutations with the total, in a stable order
+pub fn list_block_state_permutations_impl(
+    pack_id: String,
+    block_id: String,
+    packs_dir: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    target: Option<TargetVersion>,
+    policy: Option<MissingAssetPolicy>,
+) -> Result<crate::util::blockstates::StatePermutationPage, AppError> {
+    use crate::util::blockstates::{self, StatePermutationPage, MAX_STATE_PERMUTATIONS};
+
+    let normalized_block_id = blockstates::normalize_block_id(&block_id);
+    validation::validate_directory(&packs_dir, "Packs directory")?;
+
+    let policy = policy.unwrap_or_default();
+    let vanilla_pack = vanilla_pack_for_target(target.as_ref())?;
+    let target_pack = find_pack(&pack_id, &packs_dir, &vanilla_pack)?;
+
+    let offset = offset.unwrap_or(0);
+    let Some((blockstate, used_block_id)) =
+        find_blockstate_with_policy(&target_pack, &vanilla_pack, &normalized_block_id, policy)?
+    else {
+        return Ok(StatePermutationPage {
+            block_id: normalized_block_id,
+            total: 0,
+            truncated: false,
+            offset,
+            permutations: Vec::new(),
+        });
+    };
+
+    let schema = blockstates::build_block_state_schema(&blockstate, &used_block_id);
+    let count = blockstates::state_permutation_count(&schema);
+    let total = count.min(MAX_STATE_PERMUTATIONS);
+    let limit = limit
+        .unwrap_or(64)
+        .min(256)
+        .min(total.saturating_sub(offset));
+
+    let permutations = blockstates::state_permutations(&schema, offset, limit)
+        .into_par_iter()
+        .map(|state| {
+            blockstates::resolve_blockstate(&blockstate, &used_block_id, Some(state.clone()), None)
+                .unwrap_or_else(|_| blockstates::ResolutionResult {
+                    block_id: used_block_id.clone(),
+                    state_props: state,
+                    models: Vec::new(),
+                })
+        })
+        .collect();
+
+    Ok(StatePermutationPage {
+        block_id: used_block_id,
+        total,
+        truncated: count > MAX_STATE_PERMUTATIONS,
+        offset,
+        permutations,
+    })
+}
+
+/// Resolve an item to the model shown for a set of predicate values
+///
+/// Evaluates the item model's `overrides` (custom_model_data, damaged,
+/// pulling, ...) so CustomModelData variants can be previewed, then applies
+/// parent inheritance.
+///
+/// # Arguments
+/// * `pack_id` - Pack ID to search
+/// * `item_id` - Item ID (e.g., "minecraft:bow")
+/// * `packs_dir` - Root directory containing packs
+/// * `predicates` - Predicate values (e.g., {"custom_model_data": 2}); missing ones count as 0
+/// * `target` - Workspace target version; vanilla fallback must match it
+/// * `policy` - What to do when the pack lacks the asset (defaults to vanilla fallback)
+///
+/// # Errors
+/// - VALIDATION_ERROR: Invalid inputs or item model not found
+///
+/// # Returns
+/// The selected model with parent inheritance applied, and the item's overrides
+pub fn resolve_item_model_impl(
+    pack_id: String,
+    item_id: String,
+    packs_dir: String,
+    predicates: Option<HashMap<String, f64>>,
+    target: Option<TargetVersion>,
+    policy: Option<MissingAssetPolicy>,
+) -> Result<crate::util::item_models::ResolvedItemModel, AppError> {
+    validation::validate_directory(&packs_dir, "Packs directory")?;
+
+    let policy = policy.unwrap_or_default();
+    let vanilla_pack = vanilla_pack_for_target(target.as_ref())?;
+    let target_pack = find_pack(&pack_id, &packs_dir, &vanilla_pack)?;
+
+    crate::util::item_models::resolve_item_model(
+        &target_pack,
+        &item_id,
+        &vanilla_pack,
+        &predicates.unwrap_or_default(),
+        policy,
     )
 }
 
 /// Read a file from a resource pack (directory or ZIP)
 ///
 /// Generic file reading command for loading any file from a pack.
-/// Supports both directory-based packs and ZIP packs.
+/// Supports both directory-based packs and ZIP packs. Paths that would leave
+/// the pack ("..", absolute paths, links pointing outside it) are refused.
 ///
 /// # Arguments
 /// * `pack_path` - Path to the pack (directory or ZIP file), or "." for project root
@@ -914,11 +3719,14 @@ pub fn read_pack_file_impl(
     use std::fs;
     use std::path::Path;
 
-    println!(
-        "[read_pack_file] pack_path: {}, file_path: {}, is_zip: {}",
+    info!(
+        "pack_path: {}, file_path: {}, is_zip: {}",
         pack_path, file_path, is_zip
     );
 
+    let file_path =
+        pack_files::safe_entry_path(&file_path).map_err(|e| AppError::validation(e.to_string()))?;
+
     if is_zip {
         // Read from ZIP file
         let zip_file = fs::File::open(&pack_path)
@@ -937,22 +3745,99 @@ pub fn read_pack_file_impl(
 
         Ok(contents)
     } else {
-        // Read from directory
-        let full_path = if pack_path == "." {
-            // Special case: read from project root (for __mocks__/cem/)
-            PathBuf::from(&file_path)
-        } else {
-            Path::new(&pack_path).join(&file_path)
-        };
+        // Read from directory; "." is the project root (for __mocks__/cem/)
+        let full_path = pack_files::resolve_dir_entry(Path::new(&pack_path), &file_path)
+            .map_err(|e| AppError::validation(e.to_string()))?;
 
-        println!("[read_pack_file] Reading from: {}", full_path.display());
+        info!("Reading from: {}", full_path.display());
 
         fs::read_to_string(&full_path)
             .map_err(|e| AppError::io(format!("Failed to read file: {}", e)))
     }
 }
 
-/// Read a vanilla JEM file from __mocks__/cem/ directory
+/// List the sprite atlas definitions a pack registers (assets/*/atlases/*.json)
+///
+/// # Arguments
+/// * `pack_path` - Path to the pack (directory or ZIP file)
+/// * `is_zip` - Whether the pack is a ZIP file
+///
+/// # Returns
+/// Atlas definitions with their sprite sources, sorted by atlas ID
+pub fn get_pack_atlases_impl(
+    pack_path: String,
+    is_zip: bool,
+) -> Result<Vec<AtlasDefinition>, AppError> {
+    let pack = crate::model::PackMeta {
+        path: pack_path,
+        is_zip,
+        ..Default::default()
+    };
+
+    asset_indexer::list_pack_atlases(&pack)
+        .map_err(|e| AppError::io(format!("Failed to list atlases: {}", e)))
+}
+
+/// List the OptiFine random texture variants a pack defines for an entity texture
+///
+/// # Arguments
+/// * `pack_path` - Path to the pack (directory or ZIP file)
+/// * `is_zip` - Whether the pack is a ZIP file
+/// * `asset_id` - Base texture (e.g., "minecraft:entity/creeper/creeper") or its
+///   random set asset ID
+///
+/// # Returns
+/// Variants sorted by index (variant 1 is the base texture) plus the selection
+/// rules, or None if the pack has no random textures for it
+pub fn get_random_entity_variants_impl(
+    pack_path: String,
+    is_zip: bool,
+    asset_id: String,
+) -> Result<Option<random_entities::RandomEntityVariants>, AppError> {
+    let pack = crate::model::PackMeta {
+        path: pack_path,
+        is_zip,
+        ..Default::default()
+    };
+
+    let files = asset_indexer::list_pack_files(&pack)
+        .map_err(|e| AppError::io(format!("Failed to list pack files: {}", e)))?;
+    let texture_asset_id = random_entities::texture_asset_id(&asset_id);
+    Ok(random_entities::collect_variants(
+        &pack,
+        &files,
+        &texture_asset_id,
+    ))
+}
+
+/// Pick the random texture variant OptiFine would show for an entity
+///
+/// # Arguments
+/// * `pack_path` - Path to the pack (directory or ZIP file)
+/// * `is_zip` - Whether the pack is a ZIP file
+/// * `asset_id` - Base texture (e.g., "minecraft:entity/zombie/zombie") or its
+///   random set asset ID
+/// * `context` - Biome, height, name and so on of the entity
+/// * `seed` - Stands in for the entity's UUID; the same seed picks the same variant
+///
+/// # Returns
+/// The chosen variant and the rule that chose it, or None if the pack has no
+/// random textures for the entity
+pub fn pick_entity_variant_impl(
+    pack_path: String,
+    is_zip: bool,
+    asset_id: String,
+    context: random_entities::EntityContext,
+    seed: u64,
+) -> Result<Option<random_entities::EntityVariantPick>, AppError> {
+    let variants = get_random_entity_variants_impl(pack_path, is_zip, asset_id)?;
+    Ok(variants.map(|variants| random_entities::pick_variant(&variants, &context, seed)))
+}
+
+/// Read a vanilla JEM file from the vanilla cache or __mocks__/cem/ directory
+///
+/// The cache holds geometry extracted from the active version's model
+/// classes, when that has run; the bundled templates fill in the rest.
 ///
 /// # Arguments
 /// * `entity_type` - Entity type (e.g., "cow", "pig", "chest")
@@ -960,47 +3845,16 @@ pub fn read_pack_file_impl(
 /// # Returns
 /// JEM file contents as a string
 pub fn read_vanilla_jem_impl(entity_type: String) -> Result<String, AppError> {
-    use std::fs;
-    use std::path::PathBuf;
-
-    if let Ok(cache_dir) = vanilla_textures::get_vanilla_cache_dir() {
-        let cache_path = cache_dir
-            .join("assets/minecraft/optifine/cem")
-            .join(format!("{}.jem", entity_type));
-        if cache_path.exists() {
-            println!(
-                "[read_vanilla_jem] Reading vanilla JEM from cache: {}",
-                cache_path.display()
-            );
-            return fs::read_to_string(&cache_path).map_err(|e| {
-                AppError::io(format!(
-                    "Failed to read vanilla JEM at {}: {}",
-                    cache_path.display(),
-                    e
-                ))
-            });
-        }
-    }
-
-    // Use the manifest directory as the base (src-tauri's parent directory)
-    let manifest_dir = env!("CARGO_MANIFEST_DIR");
-    let base_path = PathBuf::from(manifest_dir);
-    let project_root = base_path
-        .parent()
-        .ok_or_else(|| AppError::io("Could not determine project root".to_string()))?;
-
-    // Construct path to vanilla JEM file relative to project root
-    let jem_path = project_root
-        .join("__mocks__")
-        .join("cem")
-        .join(format!("{}.jem", entity_type));
+    let file_name = format!("{}.jem", entity_type);
+    let jem_path = crate::util::jem::vanilla_jem_dirs()
+        .into_iter()
+        .map(|dir| dir.join(&file_name))
+        .find(|path| path.exists())
+        .ok_or_else(|| AppError::io(format!("No vanilla JEM for {}", entity_type)))?;
 
-    println!(
-        "[read_vanilla_jem] Reading vanilla JEM from: {}",
-        jem_path.display()
-    );
+    info!("Reading vanilla JEM from: {}", jem_path.display());
 
-    fs::read_to_string(&jem_path).map_err(|e| {
+    std::fs::read_to_string(&jem_path).map_err(|e| {
         AppError::io(format!(
             "Failed to read vanilla JEM at {}: {}",
             jem_path.display(),
@@ -1009,6 +3863,99 @@ pub fn read_vanilla_jem_impl(entity_type: String) -> Result<String, AppError> {
     })
 }
 
+/// Validate an OptiFine CEM model (.jem) in a pack
+///
+/// Checks structure, box texturing and part names (against the vanilla
+/// template for the target version), and fills in JPM imports.
+///
+/// # Arguments
+/// * `pack_id` - Pack ID containing the model
+/// * `jem_path` - Pack-relative path (e.g., "assets/minecraft/optifine/cem/cow.jem")
+/// * `packs_dir` - Root directory containing packs
+/// * `target` - Workspace target version; part names are checked against
+///   that version's layout, or the current one when omitted
+///
+/// # Errors
+/// - VALIDATION_ERROR: Not a .jem path, or the file couldn't be read
+/// - PACK_NOT_FOUND: No pack with the given ID
+///
+/// # Returns
+/// The parsed model (with JPM imports) and every problem found
+pub fn validate_jem_impl(
+    pack_id: String,
+    jem_path: String,
+    packs_dir: String,
+    target: Option<TargetVersion>,
+) -> Result<crate::util::jem::JemValidation, AppError> {
+    validation::validate_directory(&packs_dir, "Packs directory")?;
+    if !jem_path.ends_with(".jem") {
+        return Err(AppError::validation(format!(
+            "Not a JEM file: {}",
+            jem_path
+        )));
+    }
+
+    let vanilla_pack = create_vanilla_pack()?;
+    let pack = find_pack(&pack_id, &packs_dir, &vanilla_pack)?;
+    crate::util::jem::validate_jem(
+        &pack,
+        &jem_path,
+        target.as_ref().map(|target| target.version.as_str()),
+    )
+    .map_err(|e| AppError::validation(format!("Failed to read {}: {}", jem_path, e)))
+}
+
+/// Evaluate a CEM model's animations for one frame
+///
+/// # Arguments
+/// * `model` - The parsed model, as `validate_jem` returns it
+/// * `inputs` - Entity inputs and the previous frame's custom variables
+///
+/// # Returns
+/// Every part's transform, the custom variables for the next frame and any
+/// animation entries that couldn't be compiled
+pub fn evaluate_cem_animations_impl(
+    model: crate::util::jem::JemFile,
+    inputs: crate::util::cem_animation::AnimationInputs,
+) -> Result<crate::util::cem_animation::AnimationFrame, AppError> {
+    Ok(crate::util::cem_animation::evaluate_cem_animations(
+        &model, &inputs,
+    ))
+}
+
+/// Map entity IDs to their vanilla textures for a version
+///
+/// Covers each entity's own texture and its variants (cat skins, horse
+/// markings, villager professions, ...), read from the version's JAR.
+///
+/// # Arguments
+/// * `target` - Workspace target version, or the cached vanilla version when
+///   omitted
+///
+/// # Errors
+/// - VALIDATION_ERROR: No version given and none cached
+/// - IO_ERROR: The version's JAR couldn't be found or read
+///
+/// # Returns
+/// Textures of every entity, as asset IDs for get_pack_texture_path
+pub fn get_entity_textures_impl(
+    target: Option<TargetVersion>,
+) -> Result<crate::util::entity_textures::EntityTextureMap, AppError> {
+    let version = match target {
+        Some(target) => target.version,
+        None => vanilla_textures::get_cached_version()
+            .map_err(|e| AppError::io(format!("Failed to read cached version: {}", e)))?
+            .ok_or_else(|| AppError::validation("No vanilla version is cached"))?,
+    };
+
+    crate::util::entity_textures::entity_textures_for_version(&version).map_err(|e| {
+        AppError::io(format!(
+            "Failed to read entity textures of {}: {}",
+            version, e
+        ))
+    })
+}
+
 /// Get all entities that have version variants in JEM files
 /// Returns a map of entity ID -> list of version folders
 ///
@@ -1022,7 +3969,7 @@ pub fn get_entity_version_variants_impl(
     validation::validate_directory(&packs_dir, "Packs directory")?;
 
     // Scan for packs
-    let packs = pack_scanner::scan_packs(&packs_dir).map_err(|e| AppError::scan(e.to_string()))?;
+    let packs = scan_library(&packs_dir).map_err(|e| AppError::scan(e.to_string()))?;
 
     // Scan for version variants
     let variants = asset_indexer::scan_entity_version_variants(&packs)
@@ -1037,7 +3984,7 @@ pub fn get_particle_data_impl() -> Result<Option<particle_data::ParticleData>, A
         .map(Some)
         .or_else(|e| {
             // Return None instead of error if data doesn't exist yet
-            eprintln!("Particle data not available: {}", e);
+            warn!("Particle data not available: {}", e);
             Ok(None)
         })
 }
@@ -1050,13 +3997,122 @@ pub fn get_particle_data_for_version_impl(
         .map_err(|e| AppError::io(format!("Failed to get particle data for {}: {}", version, e)))
 }
 
+/// Reference graph of an asset across the pack stack
+///
+/// Follows blockstate -> models -> parents and textures, item definitions ->
+/// models, and entity CEM models -> textures, for dependency diagrams.
+///
+/// # Arguments
+/// * `root_asset` - Asset ID ("minecraft:block/oak_log", "minecraft:item/diamond")
+///   or a kind-prefixed node ID ("entity:minecraft:cow")
+/// * `depth` - Edges to follow from the root
+/// * `pack_order` - Pack IDs in priority order (highest first)
+/// * `packs_dir` - Directory containing resource packs
+///
+/// # Returns
+/// Nodes with the pack each is read from, and the edges between them
+pub fn get_asset_graph_impl(
+    root_asset: String,
+    depth: usize,
+    pack_order: Vec<String>,
+    packs_dir: String,
+) -> Result<asset_graph::AssetGraph, AppError> {
+    validation::validate_directory(&packs_dir, "Packs directory")?;
+
+    let packs = ordered_packs(&pack_order, &packs_dir)?;
+    asset_graph::build_asset_graph(&root_asset, depth, &packs)
+        .map_err(|e| AppError::validation(format!("Failed to build asset graph: {}", e)))
+}
+
+/// Resolve a particle's sprite textures across the pack stack
+///
+/// # Arguments
+/// * `particle_id` - Particle ID (e.g., "flame" or "minecraft:flame")
+/// * `pack_order` - Pack IDs in priority order (highest first)
+/// * `packs_dir` - Directory containing resource packs
+///
+/// # Returns
+/// Ordered sprite list, each sprite resolved to the pack (or vanilla) that provides it
+pub fn resolve_particle_textures_impl(
+    particle_id: String,
+    pack_order: Vec<String>,
+    packs_dir: String,
+) -> Result<particle_data::ResolvedParticleTextures, AppError> {
+    validation::validate_directory(&packs_dir, "Packs directory")?;
+
+    let packs = ordered_packs(&pack_order, &packs_dir)?;
+    particle_data::resolve_particle_textures(&particle_id, &packs).map_err(|e| {
+        AppError::io(format!(
+            "Failed to resolve particle textures for {}: {}",
+            particle_id, e
+        ))
+    })
+}
+
+/// Get everything the particle preview needs for one particle, in one call
+///
+/// Combines the physics extracted for `version` (or the bundled dataset
+/// offline), the sprite list of the version's particles/<id>.json from the
+/// client JAR, and that list's sprites resolved across the pack stack. A
+/// pack's own particles/<id>.json still replaces the vanilla list.
+///
+/// # Arguments
+/// * `particle_id` - Particle ID (e.g., "flame" or "minecraft:flame")
+/// * `version` - Minecraft version string (e.g., "1.21.4")
+/// * `pack_order` - Pack IDs in priority order (highest first)
+/// * `packs_dir` - Directory containing resource packs
+///
+/// # Errors
+/// - VALIDATION_ERROR: Directory doesn't exist or is invalid
+/// - IO_ERROR: Neither the packs, the JAR nor the physics know the particle
+pub fn get_particle_definition_impl(
+    particle_id: String,
+    version: String,
+    pack_order: Vec<String>,
+    packs_dir: String,
+) -> Result<particle_data::ParticleDefinition, AppError> {
+    validation::validate_directory(&packs_dir, "Packs directory")?;
+
+    let packs = ordered_packs(&pack_order, &packs_dir)?;
+    let physics = crate::util::particle_physics_extractor::load_cached_physics_data(&version)
+        .unwrap_or_else(|e| {
+            warn!("Failed to load cached physics for {}: {}", version, e);
+            None
+        })
+        .or_else(|| offline::load_bundled(offline::Dataset::ParticlePhysics, &version));
+    // The texture lists are cheap to read, so a missing cache is filled from the JAR
+    let vanilla = particle_data::get_particle_data_for_version(&version)
+        .or_else(|_| {
+            let jar_path = particle_cache::resolve_jar_path(&version)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::NotFound, e.to_string()))?;
+            particle_data::extract_particle_textures(&jar_path, &version)
+        })
+        .map_err(|e| warn!("No particle texture lists for {}: {}", version, e))
+        .ok();
+
+    particle_data::particle_definition(
+        &particle_id,
+        &version,
+        physics.as_ref(),
+        vanilla.as_ref(),
+        &packs,
+    )
+    .map_err(|e| {
+        AppError::io(format!(
+            "Failed to get particle definition for {}: {}",
+            particle_id, e
+        ))
+    })
+}
+
 /// Get cached particle physics data for the current Minecraft version
 ///
-/// Returns physics data if already cached, otherwise returns None.
+/// Returns physics data if already cached, or the bundled dataset for
+/// releases shipped with the app, otherwise returns None.
 /// Use `extract_particle_physics` to extract and cache physics data.
 ///
 /// # Returns
-/// Optional ExtractedPhysicsData if cached
+/// Optional ExtractedPhysicsData if cached or bundled
 pub fn get_particle_physics_impl(
 ) -> Result<Option<crate::util::particle_physics_extractor::ExtractedPhysicsData>, AppError> {
     // Get the currently cached vanilla version
@@ -1067,9 +4123,38 @@ pub fn get_particle_physics_impl(
     };
 
     crate::util::particle_physics_extractor::load_cached_physics_data(&version)
+        .map(|cached| {
+            cached.or_else(|| offline::load_bundled(offline::Dataset::ParticlePhysics, &version))
+        })
         .map_err(|e| AppError::io(format!("Failed to load cached physics: {}", e)))
 }
 
+/// Sample a particle quad size curve into a lookup table
+///
+/// # Arguments
+/// * `curve` - QuadSizeCurve from extracted particle physics
+/// * `samples` - Number of samples across the lifetime (2-4096)
+/// * `lifetime` - Lifetime in ticks, only used by age-driven curves (sine wave)
+///
+/// # Returns
+/// Normalized age → size multiplier (or absolute size) table
+pub fn sample_quad_size_curve_impl(
+    curve: crate::util::particle_physics_extractor::QuadSizeCurve,
+    samples: usize,
+    lifetime: Option<f32>,
+) -> Result<crate::util::particle_physics_extractor::QuadSizeCurveTable, AppError> {
+    if !(2..=4096).contains(&samples) {
+        return Err(AppError::validation(format!(
+            "Sample count must be between 2 and 4096, got {}",
+            samples
+        )));
+    }
+
+    Ok(crate::util::particle_physics_extractor::sample_quad_size_curve(
+        &curve, samples, lifetime,
+    ))
+}
+
 /// Check if particle physics data is cached for a version
 ///
 /// # Arguments
@@ -1097,20 +4182,67 @@ pub fn is_particle_physics_cached_impl(version: String) -> Result<bool, AppError
 pub async fn extract_particle_physics_impl(
     version: String,
 ) -> Result<crate::util::particle_physics_extractor::ExtractedPhysicsData, AppError> {
-    // Get the JAR path for this version
-    let versions = vanilla_textures::list_all_available_versions()
-        .map_err(|e| AppError::io(format!("Failed to list versions: {}", e)))?;
+    // Without the JAR, network or Java, fall back to the bundled dataset
+    offline::extract_or_bundled(offline::Dataset::ParticlePhysics, &version, async {
+        let jar_path = particle_cache::resolve_jar_path(&version)?;
+        crate::util::particle_physics_extractor::extract_particle_physics(&jar_path, &version)
+            .await
+    })
+    .await
+    .map_err(|e| extraction_error(e, "Failed to extract particle physics"))
+}
 
-    let version_info = versions
-        .iter()
-        .find(|v| v.version == version)
-        .ok_or_else(|| AppError::validation(format!("Version not found: {}", version)))?;
+/// Make sure the particle caches and generated TypeScript exist for a version
+///
+/// Run after vanilla textures are extracted; failures are only logged, since
+/// the textures themselves are usable without particle data.
+///
+/// # Arguments
+/// * `context` - Name of the caller, for the log
+/// * `version` - Minecraft version whose JAR was extracted
+pub async fn ensure_particle_assets(context: &str, version: &str) {
+    info!(
+        "[{}] Ensuring particle caches and TypeScript for {}...",
+        context, version
+    );
+
+    let jar_path = match particle_cache::resolve_jar_path(version) {
+        Ok(path) => path,
+        Err(err) => {
+            warn!(
+                "[{}] Failed to resolve Minecraft JAR path: {}",
+                context, err
+            );
+            return;
+        }
+    };
 
-    let jar_path = std::path::PathBuf::from(&version_info.jar_path);
+    let output_path = match particle_cache::resolve_generated_ts_path() {
+        Ok(path) => path,
+        Err(err) => {
+            warn!(
+                "[{}] Failed to resolve TypeScript output path: {}",
+                context, err
+            );
+            return;
+        }
+    };
 
-    crate::util::particle_physics_extractor::extract_particle_physics(&jar_path, &version)
-        .await
-        .map_err(|e| AppError::io(format!("Failed to extract particle physics: {}", e)))
+    match particle_cache::ensure_particle_typescript(version, &jar_path, &output_path).await {
+        Ok(data) => {
+            info!(
+                "[{}] Particle data ready: {} physics, {} blocks, {} entities, {} textures",
+                context,
+                data.physics.particles.len(),
+                data.emissions.blocks.len(),
+                data.emissions.entities.len(),
+                data.textures.particles.len()
+            );
+        }
+        Err(err) => {
+            warn!("[{}] Failed to build particle caches: {}", context, err);
+        }
+    }
 }
 
 // ============================================================================
@@ -1137,6 +4269,45 @@ pub fn get_block_emissions_impl(
         .map_err(|e| AppError::io(format!("Failed to load cached emissions: {}", e)))
 }
 
+/// Get a ready-to-use particle emitter preset for a block or entity
+///
+/// Combines cached block emissions with cached particle physics so the frontend
+/// doesn't have to derive spawn rates, offsets and velocity ranges itself.
+///
+/// # Arguments
+/// * `source` - Block or entity ID (e.g., "campfire", "torch", "nether_portal")
+/// * `version` - Minecraft version (defaults to the cached vanilla version)
+///
+/// # Errors
+/// - VALIDATION_ERROR: No emissions cached for the version, or none for the source
+pub fn get_emitter_preset_impl(
+    source: String,
+    version: Option<String>,
+) -> Result<crate::util::emitter_presets::EmitterPreset, AppError> {
+    let version = match version {
+        Some(v) => v,
+        None => vanilla_textures::get_cached_version()
+            .map_err(|e| AppError::io(format!("Failed to get cached version: {}", e)))?
+            .ok_or_else(|| AppError::validation("No cached vanilla version"))?,
+    };
+
+    let emissions = crate::util::block_particle_extractor::load_cached_block_emissions(&version)
+        .map_err(|e| AppError::io(format!("Failed to load cached emissions: {}", e)))?
+        .ok_or_else(|| {
+            AppError::validation(format!("Block emissions not extracted for {}", version))
+        })?;
+
+    // Physics is optional: presets still carry spawn ranges without it
+    let physics = crate::util::particle_physics_extractor::load_cached_physics_data(&version)
+        .unwrap_or_else(|e| {
+            warn!("Failed to load physics for {}: {}", version, e);
+            None
+        });
+
+    crate::util::emitter_presets::build_emitter_preset(&source, &emissions, physics.as_ref())
+        .map_err(|e| AppError::validation(e.to_string()))
+}
+
 /// Check if block emissions data is cached for a version
 ///
 /// # Arguments
@@ -1164,20 +4335,13 @@ pub fn is_block_emissions_cached_impl(version: String) -> Result<bool, AppError>
 pub async fn extract_block_emissions_impl(
     version: String,
 ) -> Result<crate::util::block_particle_extractor::ExtractedBlockEmissions, AppError> {
-    // Get the JAR path for this version
-    let versions = vanilla_textures::list_all_available_versions()
-        .map_err(|e| AppError::io(format!("Failed to list versions: {}", e)))?;
-
-    let version_info = versions
-        .iter()
-        .find(|v| v.version == version)
-        .ok_or_else(|| AppError::validation(format!("Version not found: {}", version)))?;
-
-    let jar_path = std::path::PathBuf::from(&version_info.jar_path);
-
-    crate::util::block_particle_extractor::extract_block_emissions(&jar_path, &version)
-        .await
-        .map_err(|e| AppError::io(format!("Failed to extract block emissions: {}", e)))
+    // Without the JAR, network or Java, fall back to the bundled dataset
+    offline::extract_or_bundled(offline::Dataset::BlockEmissions, &version, async {
+        let jar_path = particle_cache::resolve_jar_path(&version)?;
+        crate::util::block_particle_extractor::extract_block_emissions(&jar_path, &version).await
+    })
+    .await
+    .map_err(|e| extraction_error(e, "Failed to extract block emissions"))
 }
 
 /// Generate TypeScript particle data file from cached extractions
@@ -1226,4 +4390,227 @@ mod tests {
         let result = get_default_packs_dir_impl();
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_resolve_block_states_batch() {
+        let packs_dir = std::env::temp_dir().join("test_resolve_block_states_batch");
+        let _ = std::fs::remove_dir_all(&packs_dir);
+        let blockstates = packs_dir.join("batch_pack/assets/minecraft/blockstates");
+        std::fs::create_dir_all(&blockstates).unwrap();
+        std::fs::write(
+            packs_dir.join("batch_pack/pack.mcmeta"),
+            r#"{"pack": {"pack_format": 34, "description": "Batch"}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            blockstates.join("stone.json"),
+            r#"{"variants": {"": {"model": "minecraft:block/stone"}}}"#,
+        )
+        .unwrap();
+
+        let request = |pack_id: &str, block_id: &str| BlockStateRequest {
+            pack_id: pack_id.to_string(),
+            block_id: block_id.to_string(),
+            state_props: None,
+            seed: None,
+            position: None,
+        };
+        let results = resolve_block_states_batch_impl(
+            packs_dir.to_string_lossy().to_string(),
+            vec![
+                request("batch_pack", "minecraft:block/stone"),
+                request("missing_pack", "stone"),
+                request("batch_pack", "dirt"),
+            ],
+            None,
+            Some(MissingAssetPolicy::PackOnly),
+        );
+
+        let _ = std::fs::remove_dir_all(&packs_dir);
+
+        let results = results.unwrap();
+        assert_eq!(results.len(), 3);
+        let stone = results[0].result.as_ref().unwrap();
+        assert_eq!(stone.models[0].model_id, "minecraft:block/stone");
+        assert!(results[1].error.as_ref().unwrap().message.contains("Pack not found"));
+        assert!(results[2].result.is_none());
+        assert!(results[2].error.is_some());
+    }
+
+    #[test]
+    fn test_invalidate_cached_texture() {
+        let cache_dir = std::env::temp_dir().join("test_invalidate_cached_texture");
+        let _ = std::fs::remove_dir_all(&cache_dir);
+        std::fs::create_dir_all(&cache_dir).unwrap();
+        for name in [
+            "Pack.zip_assets_minecraft_textures_block_stone.png",
+            "Pack.zip_assets_minecraft_textures_block_stone.png.mcmeta",
+            "Pack.zip_1.20_assets_minecraft_textures_block_stone.png",
+            "Pack.zip_assets_minecraft_textures_block_dirt.png",
+            "Other.zip_assets_minecraft_textures_block_stone.png",
+        ] {
+            std::fs::write(cache_dir.join(name), b"png").unwrap();
+        }
+
+        let removed =
+            invalidate_cached_texture(&cache_dir, "/packs/Pack.zip", "minecraft:block/stone");
+        let missing_dir = invalidate_cached_texture(
+            &cache_dir.join("missing"),
+            "/packs/Pack.zip",
+            "minecraft:block/stone",
+        );
+        let mut remaining: Vec<String> = std::fs::read_dir(&cache_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        remaining.sort();
+
+        let _ = std::fs::remove_dir_all(&cache_dir);
+
+        assert_eq!(removed.unwrap(), 3);
+        assert_eq!(missing_dir.unwrap(), 0);
+        assert_eq!(
+            remaining,
+            vec![
+                "Other.zip_assets_minecraft_textures_block_stone.png",
+                "Pack.zip_assets_minecraft_textures_block_dirt.png",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_import_pack_reports_duplicates() {
+        let temp_dir = std::env::temp_dir().join("test_import_pack_reports_duplicates");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let packs_dir = temp_dir.join("packs");
+        let incoming = temp_dir.join("incoming");
+        let write_pack = |dir: &Path, texture: &[u8]| {
+            std::fs::create_dir_all(dir.join("assets/minecraft/textures/block")).unwrap();
+            std::fs::write(dir.join("pack.mcmeta"), r#"{"pack": {"pack_format": 15}}"#).unwrap();
+            std::fs::write(
+                dir.join("assets/minecraft/textures/block/stone.png"),
+                texture,
+            )
+            .unwrap();
+        };
+        write_pack(&packs_dir.join("Faithful"), b"stone-a");
+        write_pack(&incoming.join("Other"), b"stone-b");
+        let copy_zip = incoming.join("Faithful copy.zip");
+        crate::util::zip::zip_dir_with_progress(&packs_dir.join("Faithful"), &copy_zip, None)
+            .unwrap();
+        let packs = packs_dir.to_string_lossy().to_string();
+        let import = |source: &Path, allow: Option<bool>| {
+            import_pack_impl(source.to_string_lossy().to_string(), packs.clone(), allow)
+        };
+
+        let skipped = import(&copy_zip, None).unwrap();
+        let skipped_copied = packs_dir.join("Faithful copy.zip").exists();
+        let forced = import(&copy_zip, Some(true)).unwrap();
+        let other = import(&incoming.join("Other"), None).unwrap();
+        let again = import(&incoming.join("Other"), None);
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+
+        assert!(skipped.pack.is_none());
+        assert!(!skipped_copied);
+        let duplicates = skipped.duplicates.unwrap();
+        assert_eq!(duplicates.pack_ids, vec!["Faithful", "Faithful copy.zip"]);
+        assert!(duplicates.different_names);
+        assert_eq!(forced.pack.unwrap().id, "Faithful copy.zip");
+        assert!(forced.duplicates.is_some());
+        assert_eq!(other.pack.unwrap().id, "Other");
+        assert!(other.duplicates.is_none());
+        assert!(again.unwrap_err().message.contains("already exists"));
+    }
+
+    #[test]
+    fn test_set_pack_description() {
+        let pack_dir = std::env::temp_dir().join("test_set_pack_description");
+        let _ = std::fs::remove_dir_all(&pack_dir);
+        std::fs::create_dir_all(&pack_dir).unwrap();
+        std::fs::write(
+            pack_dir.join("pack.mcmeta"),
+            r#"{"pack": {"pack_format": 15, "description": "Old"}, "overlays": {"entries": []}}"#,
+        )
+        .unwrap();
+
+        let result = set_pack_description(&pack_dir, "New description");
+        let mcmeta: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(pack_dir.join("pack.mcmeta")).unwrap())
+                .unwrap();
+        std::fs::write(pack_dir.join("pack.mcmeta"), "{}").unwrap();
+        let missing_section = set_pack_description(&pack_dir, "New description");
+
+        let _ = std::fs::remove_dir_all(&pack_dir);
+
+        assert!(result.is_ok());
+        assert_eq!(mcmeta["pack"]["description"], "New description");
+        assert_eq!(mcmeta["pack"]["pack_format"], 15);
+        assert!(mcmeta["overlays"].is_object());
+        assert_eq!(missing_section.unwrap_err().code, "VALIDATION_ERROR");
+    }
+
+    #[test]
+    fn test_copy_vanilla_files() {
+        use crate::model::VanillaAssetKind;
+
+        let temp_dir = std::env::temp_dir().join("test_copy_vanilla_files");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let vanilla_dir = temp_dir.join("vanilla");
+        let pack_dir = temp_dir.join("pack");
+        let textures = vanilla_dir.join("assets/minecraft/textures/block");
+        std::fs::create_dir_all(&textures).unwrap();
+        std::fs::create_dir_all(vanilla_dir.join("assets/minecraft/blockstates")).unwrap();
+        std::fs::create_dir_all(&pack_dir).unwrap();
+        std::fs::write(textures.join("magma.png"), b"magma").unwrap();
+        std::fs::write(textures.join("magma.png.mcmeta"), "{}").unwrap();
+        std::fs::write(
+            vanilla_dir.join("assets/minecraft/blockstates/magma_block.json"),
+            "{}",
+        )
+        .unwrap();
+
+        let texture = copy_vanilla_files(
+            &vanilla_dir,
+            &pack_dir,
+            "minecraft:block/magma",
+            VanillaAssetKind::Texture,
+        );
+        let copied = std::fs::read(pack_dir.join("assets/minecraft/textures/block/magma.png"));
+        let again = copy_vanilla_files(
+            &vanilla_dir,
+            &pack_dir,
+            "minecraft:block/magma",
+            VanillaAssetKind::Texture,
+        );
+        let blockstate = copy_vanilla_files(
+            &vanilla_dir,
+            &pack_dir,
+            "minecraft:block/magma_block",
+            VanillaAssetKind::Blockstate,
+        );
+        let missing = copy_vanilla_files(
+            &vanilla_dir,
+            &pack_dir,
+            "minecraft:block/magma",
+            VanillaAssetKind::Model,
+        );
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(
+            texture.unwrap(),
+            vec![
+                "assets/minecraft/textures/block/magma.png",
+                "assets/minecraft/textures/block/magma.png.mcmeta",
+            ]
+        );
+        assert_eq!(copied.unwrap(), b"magma");
+        assert!(again.unwrap_err().message.contains("already contains"));
+        assert_eq!(
+            blockstate.unwrap(),
+            vec!["assets/minecraft/blockstates/magma_block.json"]
+        );
+        assert!(missing.unwrap_err().message.contains("not in the vanilla cache"));
+    }
 }
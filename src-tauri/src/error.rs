@@ -3,9 +3,17 @@
  *
  * This implements the modern Tauri v2 pattern of custom error types
  * that automatically serialize to JSON for frontend consumption.
+ *
+ * Codes are stable so the UI can offer a fix for known failures (install Java,
+ * go online for mappings, re-download a corrupt pack); those errors also carry
+ * the facts needed for it in `data`.
  */
 
+use crate::util::cache_location::CacheBusy;
+use crate::util::particle_physics_extractor::{JavaMissing, MappingsUnavailable};
+use crate::util::zip::ZipCorrupt;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use std::fmt;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,6 +21,10 @@ pub struct AppError {
     pub code: String,
     pub message: String,
     pub details: Option<String>,
+    /// Machine-readable context of known failures (camelCase keys), e.g.
+    /// `{"packId": ...}` for PACK_NOT_FOUND
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
 }
 
 impl AppError {
@@ -22,6 +34,7 @@ impl AppError {
             code: "VALIDATION_ERROR".to_string(),
             message: message.into(),
             details: None,
+            data: None,
         }
     }
 
@@ -31,6 +44,7 @@ impl AppError {
             code: "IO_ERROR".to_string(),
             message: message.into(),
             details: None,
+            data: None,
         }
     }
 
@@ -40,6 +54,7 @@ impl AppError {
             code: "SCAN_ERROR".to_string(),
             message: message.into(),
             details: None,
+            data: None,
         }
     }
 
@@ -49,7 +64,91 @@ impl AppError {
             code: "BUILD_ERROR".to_string(),
             message: message.into(),
             details: None,
+            data: None,
+        }
+    }
+
+    /// Create the error of a job stopped with `cancel_job`
+    pub fn cancelled(message: impl Into<String>) -> Self {
+        Self {
+            code: "CANCELLED".to_string(),
+            message: message.into(),
+            details: None,
+            data: None,
+        }
+    }
+
+    /// Create the error of a cache another extraction is writing to
+    pub fn cache_busy(message: impl Into<String>) -> Self {
+        Self {
+            code: "CACHE_BUSY".to_string(),
+            message: message.into(),
+            details: None,
+            data: None,
+        }
+    }
+
+    /// Create the error of a pack ID that isn't in the library
+    pub fn pack_not_found(pack_id: &str) -> Self {
+        Self {
+            code: "PACK_NOT_FOUND".to_string(),
+            message: format!("Pack not found: {}", pack_id),
+            details: None,
+            data: Some(json!({ "packId": pack_id })),
+        }
+    }
+
+    /// Create the error of a ZIP file that can't be read as an archive
+    pub fn zip_corrupt(path: &str, reason: impl Into<String>) -> Self {
+        Self {
+            code: "ZIP_CORRUPT".to_string(),
+            message: format!("Not a readable ZIP archive: {}", path),
+            details: Some(reason.into()),
+            data: Some(json!({ "path": path })),
+        }
+    }
+
+    /// Create the error of Mojang mappings that couldn't be downloaded
+    pub fn mappings_unavailable(version: &str, reason: impl Into<String>) -> Self {
+        Self {
+            code: "MAPPINGS_UNAVAILABLE".to_string(),
+            message: format!("Mojang mappings for {} are unavailable", version),
+            details: Some(reason.into()),
+            data: Some(json!({ "version": version })),
+        }
+    }
+
+    /// Create the error of decompiling without a Java runtime
+    pub fn java_missing() -> Self {
+        Self {
+            code: "JAVA_MISSING".to_string(),
+            message: "Java is needed to decompile Minecraft classes, but wasn't found".to_string(),
+            details: None,
+            data: None,
+        }
+    }
+
+    /// The error for a known failure anywhere in `err`'s chain (busy cache,
+    /// corrupt ZIP, missing mappings or Java), or None for anything else
+    pub fn known(err: &anyhow::Error) -> Option<Self> {
+        if let Some(busy) = err.downcast_ref::<CacheBusy>() {
+            let mut error = AppError::cache_busy(busy.to_string());
+            error.data = Some(json!({ "dir": busy.dir.to_string_lossy() }));
+            return Some(error);
+        }
+        if let Some(corrupt) = err.downcast_ref::<ZipCorrupt>() {
+            return Some(AppError::zip_corrupt(&corrupt.path, &corrupt.reason));
+        }
+        if let Some(missing) = err.downcast_ref::<MappingsUnavailable>() {
+            return Some(AppError::mappings_unavailable(
+                &missing.version,
+                &missing.reason,
+            ));
         }
+        if err.downcast_ref::<JavaMissing>().is_some() {
+            return Some(AppError::java_missing());
+        }
+        None
     }
 
     /// Create an internal error
@@ -58,6 +157,7 @@ impl AppError {
             code: "INTERNAL_ERROR".to_string(),
             message: message.into(),
             details: Some(details.into()),
+            data: None,
         }
     }
 
@@ -76,7 +176,8 @@ impl fmt::Display for AppError {
 
 impl From<anyhow::Error> for AppError {
     fn from(err: anyhow::Error) -> Self {
-        AppError::internal("Operation failed", err.to_string())
+        AppError::known(&err)
+            .unwrap_or_else(|| AppError::internal("Operation failed", err.to_string()))
     }
 }
 
@@ -131,6 +232,14 @@ mod tests {
         assert_eq!(err.details, None);
     }
 
+    #[test]
+    fn test_cancelled_error() {
+        let err = AppError::cancelled("Build cancelled");
+        assert_eq!(err.code, "CANCELLED");
+        assert_eq!(err.message, "Build cancelled");
+        assert_eq!(err.details, None);
+    }
+
     #[test]
     fn test_internal_error() {
         let err = AppError::internal("operation failed", "detailed info");
@@ -179,6 +288,38 @@ mod tests {
         assert_eq!(app_err.details, Some("something went wrong".to_string()));
     }
 
+    #[test]
+    fn test_from_cache_busy_error() {
+        let busy = anyhow::Error::new(CacheBusy {
+            dir: std::path::PathBuf::from("vanilla_textures"),
+        })
+        .context("Failed to extract");
+        let app_err: AppError = busy.into();
+        assert_eq!(app_err.code, "CACHE_BUSY");
+        assert!(app_err.message.contains("vanilla_textures is busy"));
+    }
+
+    #[test]
+    fn test_known_error_data() {
+        let corrupt = anyhow::Error::new(ZipCorrupt {
+            path: "packs/broken.zip".to_string(),
+            reason: "invalid Zip archive: Could not find central directory end".to_string(),
+        })
+        .context("Failed to list pack files");
+        let app_err = AppError::known(&corrupt).unwrap();
+        assert_eq!(app_err.code, "ZIP_CORRUPT");
+        assert_eq!(app_err.data, Some(json!({ "path": "packs/broken.zip" })));
+
+        let json = serde_json::to_string(&AppError::pack_not_found("faithful.zip")).unwrap();
+        assert!(json.contains("\"code\":\"PACK_NOT_FOUND\""));
+        assert!(json.contains("\"data\":{\"packId\":\"faithful.zip\"}"));
+        // Errors without data don't send the field at all
+        assert!(!serde_json::to_string(&AppError::java_missing())
+            .unwrap()
+            .contains("data"));
+        assert!(AppError::known(&anyhow::anyhow!("something else")).is_none());
+    }
+
     #[test]
     fn test_error_serialization() {
         let err = AppError::validation("test error").with_details("test details");
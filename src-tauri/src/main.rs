@@ -3,98 +3,149 @@
     windows_subsystem = "windows"
 )]
 
+use tracing::{info, warn};
 use weaverbird_lib::commands::{
-    build_weaver_nest_impl, check_minecraft_installed_impl, detect_launchers_impl,
-    extract_block_emissions_impl, extract_particle_physics_impl,
-    generate_particle_typescript_impl, get_block_emissions_impl,
-    get_block_state_schema_impl, get_cached_vanilla_version_impl, get_colormap_path_impl,
-    get_default_packs_dir_impl, get_entity_version_variants_impl,
-    get_launcher_resourcepacks_dir_impl, get_pack_texture_path_impl,
-    get_particle_data_impl, get_particle_data_for_version_impl, get_particle_physics_impl,
-    get_suggested_minecraft_paths_impl, get_vanilla_mcmeta_path_impl,
-    get_vanilla_texture_path_impl, identify_launcher_impl,
-    initialize_vanilla_textures_from_custom_dir_impl, initialize_vanilla_textures_impl,
-    is_block_emissions_cached_impl, is_particle_physics_cached_impl,
-    list_available_minecraft_versions_impl, load_model_json_impl, read_block_model_impl,
-    read_pack_file_impl, read_vanilla_jem_impl, resolve_block_state_impl, scan_packs_folder_impl,
-    set_vanilla_texture_version_impl, BuildWeaverNestRequest,
+    analyze_contrast_impl, build_weaver_nest_impl, cancel_job_impl, check_minecraft_installed_impl,
+    check_pack_integrity_impl, clear_cache_impl, close_edit_session_impl,
+    compare_vanilla_asset_impl, compute_tint_impl,
+    compute_block_tint_impl, convert_pack_format_impl,
+    copy_vanilla_asset_to_pack_impl, delete_pack_impl, detect_launchers_impl,
+    diagnose_model_impl, ensure_particle_assets, evaluate_cem_animations_impl,
+    export_block_model_impl, extract_block_emissions_impl,
+    extract_particle_physics_impl, find_duplicate_assets_impl, generate_particle_typescript_impl,
+    get_animation_frames_impl, get_asset_graph_impl, get_asset_thumbnail_impl,
+    get_block_emissions_impl,
+    get_block_state_schema_impl, get_cache_maintenance_settings_impl, get_cache_usage_impl,
+    get_cached_vanilla_version_impl, get_colormap_path_impl,
+    get_default_packs_dir_impl, get_download_settings_impl, get_emitter_preset_impl,
+    get_entity_textures_impl, get_entity_version_variants_impl,
+    get_indexed_providers_impl, get_job_impl, get_launcher_resourcepacks_dir_impl,
+    get_log_level_impl, get_low_memory_mode_impl,
+    get_offline_status_impl, get_pack_atlases_impl, get_pack_compatibility_impl,
+    get_pack_texture_path_impl,
+    get_pack_texture_paths_batch_impl, get_particle_data_impl, get_particle_data_for_version_impl,
+    get_particle_definition_impl, get_particle_physics_impl, get_perf_metrics_impl,
+    get_random_entity_variants_impl,
+    get_recent_logs_impl, get_shared_cache_status_impl, get_suggested_minecraft_paths_impl,
+    get_vanilla_mcmeta_path_impl, get_vanilla_texture_categories_impl,
+    get_vanilla_texture_path_impl, get_vanilla_texture_provenance_impl, identify_launcher_impl,
+    import_bbmodel_impl, import_pack_impl, initialize_vanilla_textures_from_custom_dir_impl,
+    initialize_vanilla_textures_impl,
+    install_nest_to_launcher_impl, is_block_emissions_cached_impl, is_particle_physics_cached_impl,
+    lint_pack_impl, list_available_minecraft_versions_impl, list_block_state_permutations_impl,
+    list_cached_vanilla_versions_impl,
+    list_interrupted_builds_impl, list_jobs_impl, list_launcher_instances_impl,
+    load_model_json_impl, load_scan_index_impl, open_asset_in_editor_impl, pick_entity_variant_impl,
+    read_block_model_impl,
+    read_pack_file_impl, read_vanilla_jem_impl, recover_interrupted_builds_impl, refresh_asset_impl,
+    rename_pack_impl, repack_pack_impl, resolve_block_state_impl,
+    resolve_block_states_batch_impl, resolve_item_model_impl, resolve_particle_textures_impl,
+    resolve_texture_paths_bulk_impl, run_cache_maintenance_impl, run_cancellable,
+    run_perf_selftest_impl, sample_animation_frame_impl,
+    sample_quad_size_curve_impl, scan_packs_folder_impl, search_assets_impl,
+    search_indexed_assets_impl, set_backend_locale_impl, set_cache_maintenance_settings_impl,
+    set_download_settings_impl, set_log_level_impl, set_low_memory_mode_impl,
+    set_offline_mode_impl, set_shared_cache_dir_impl, set_vanilla_texture_categories_impl,
+    set_vanilla_texture_version_impl,
+    submit_job_impl, texture_cache_dir, unpack_pack_impl, validate_jem_impl,
+    validate_pack_compatibility_impl,
+    write_back_edited_asset_impl,
+    AssetUpdatedEvent, BatchResult, BlockStateRequest, BuildWeaverNestRequest, JobRequest,
+    PackImportResult, PackLibraryChange, ResolvedTexturePath, TexturePathRequest,
+    VanillaTextureProvenance,
+};
+#[cfg(feature = "selftest")]
+use weaverbird_lib::commands::run_selftest_suite_impl;
+use weaverbird_lib::util::{
+    asset_protocol, cache_maintenance, jobs, logging, particle_cache, perf_metrics,
 };
-use weaverbird_lib::util::particle_cache;
 
 /// Tauri command wrapper for scanning resource packs (async for non-blocking UI)
 #[tauri::command]
 async fn scan_packs_folder(
     packs_dir: String,
+    job_id: Option<String>,
 ) -> Result<weaverbird_lib::model::ScanResult, weaverbird_lib::AppError> {
     // Use spawn_blocking for CPU/IO-heavy work with rayon parallelism
-    tokio::task::spawn_blocking(move || scan_packs_folder_impl(packs_dir))
-        .await
-        .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("scan_packs_folder", || {
+            run_cancellable(job_id, || scan_packs_folder_impl(packs_dir))
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
 }
 
 /// Tauri command wrapper for building Weaver Nest (async for non-blocking UI)
 #[tauri::command]
 async fn build_weaver_nest(
     request: BuildWeaverNestRequest,
+    job_id: Option<String>,
 ) -> Result<String, weaverbird_lib::AppError> {
     // Use spawn_blocking for CPU/IO-heavy work with rayon parallelism
-    tokio::task::spawn_blocking(move || build_weaver_nest_impl(request))
-        .await
-        .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("build_weaver_nest", || {
+            run_cancellable(job_id, || build_weaver_nest_impl(request))
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
+
+/// Tauri command wrapper for cancelling a running scan, build or extraction
+#[tauri::command]
+fn cancel_job(job_id: String) -> Result<bool, weaverbird_lib::AppError> {
+    perf_metrics::timed("cancel_job", || cancel_job_impl(job_id))
+}
+
+/// Tauri command wrapper for queueing a heavy command as a background job
+#[tauri::command]
+fn submit_job(request: JobRequest) -> Result<String, weaverbird_lib::AppError> {
+    perf_metrics::timed("submit_job", || submit_job_impl(request))
+}
+
+/// Tauri command wrapper for getting the state of a background job
+#[tauri::command]
+fn get_job(
+    job_id: String,
+) -> Result<Option<weaverbird_lib::util::jobs::JobInfo>, weaverbird_lib::AppError> {
+    perf_metrics::timed("get_job", || get_job_impl(job_id))
+}
+
+/// Tauri command wrapper for listing background jobs
+#[tauri::command]
+fn list_jobs() -> Result<Vec<weaverbird_lib::util::jobs::JobInfo>, weaverbird_lib::AppError> {
+    perf_metrics::timed("list_jobs", list_jobs_impl)
+}
+
+/// Tauri command wrapper for listing nest builds that never finished
+#[tauri::command]
+fn list_interrupted_builds() -> Result<
+    Vec<weaverbird_lib::util::build_journal::InterruptedBuild>,
+    weaverbird_lib::AppError,
+> {
+    perf_metrics::timed("list_interrupted_builds", list_interrupted_builds_impl)
+}
+
+/// Tauri command wrapper for resuming or rolling back interrupted nest builds
+#[tauri::command]
+async fn recover_interrupted_builds(
+    output_dirs: Vec<String>,
+    action: weaverbird_lib::util::build_journal::RecoveryAction,
+) -> Result<Vec<BatchResult<String>>, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("recover_interrupted_builds", || {
+            recover_interrupted_builds_impl(output_dirs, action)
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))
 }
 
 /// Tauri command wrapper for getting default packs directory
 #[tauri::command]
 fn get_default_packs_dir() -> Result<String, weaverbird_lib::AppError> {
-    get_default_packs_dir_impl()
-}
-
-async fn ensure_particle_assets(context: &str, version: &str) {
-    println!(
-        "[{}] Ensuring particle caches and TypeScript for {}...",
-        context, version
-    );
-
-    let jar_path = match particle_cache::resolve_jar_path(version) {
-        Ok(path) => path,
-        Err(err) => {
-            eprintln!(
-                "[{}] Warning: Failed to resolve Minecraft JAR path: {}",
-                context, err
-            );
-            return;
-        }
-    };
-
-    let output_path = match particle_cache::resolve_generated_ts_path() {
-        Ok(path) => path,
-        Err(err) => {
-            eprintln!(
-                "[{}] Warning: Failed to resolve TypeScript output path: {}",
-                context, err
-            );
-            return;
-        }
-    };
-
-    match particle_cache::ensure_particle_typescript(version, &jar_path, &output_path).await {
-        Ok(data) => {
-            println!(
-                "[{}] Particle data ready: {} physics, {} blocks, {} entities, {} textures",
-                context,
-                data.physics.particles.len(),
-                data.emissions.blocks.len(),
-                data.emissions.entities.len(),
-                data.textures.particles.len()
-            );
-        }
-        Err(err) => {
-            eprintln!(
-                "[{}] Warning: Failed to build particle caches: {}",
-                context, err
-            );
-        }
-    }
+    perf_metrics::timed("get_default_packs_dir", get_default_packs_dir_impl)
 }
 
 /// Tauri command wrapper for initializing vanilla textures (async for non-blocking UI)
@@ -102,11 +153,17 @@ async fn ensure_particle_assets(context: &str, version: &str) {
 #[tauri::command]
 async fn initialize_vanilla_textures(
     window: tauri::Window,
+    categories: Option<Vec<weaverbird_lib::util::vanilla_textures::TextureCategory>>,
+    job_id: Option<String>,
 ) -> Result<String, weaverbird_lib::AppError> {
     // Use spawn_blocking for CPU/IO-heavy vanilla texture extraction
-    let result = tokio::task::spawn_blocking(move || initialize_vanilla_textures_impl(window))
-        .await
-        .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))??;
+    let result = tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("initialize_vanilla_textures", || {
+            run_cancellable(job_id, || initialize_vanilla_textures_impl(window, categories))
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))??;
 
     if let Ok(Some(version)) = get_cached_vanilla_version_impl() {
         ensure_particle_assets("initialize_vanilla_textures", &version).await;
@@ -117,121 +174,785 @@ async fn initialize_vanilla_textures(
 
 /// Tauri command wrapper for getting vanilla texture path
 #[tauri::command]
-fn get_vanilla_texture_path(asset_id: String) -> Result<String, weaverbird_lib::AppError> {
-    get_vanilla_texture_path_impl(asset_id)
+fn get_vanilla_texture_path(
+    asset_id: String,
+    version: Option<String>,
+) -> Result<String, weaverbird_lib::AppError> {
+    perf_metrics::timed("get_vanilla_texture_path", || {
+        get_vanilla_texture_path_impl(asset_id, version)
+    })
 }
 
 /// Tauri command wrapper for getting vanilla .mcmeta path
 #[tauri::command]
 fn get_vanilla_mcmeta_path(asset_id: String) -> Result<Option<String>, weaverbird_lib::AppError> {
-    get_vanilla_mcmeta_path_impl(asset_id)
+    perf_metrics::timed("get_vanilla_mcmeta_path", || {
+        get_vanilla_mcmeta_path_impl(asset_id)
+    })
 }
 
 /// Tauri command wrapper for getting colormap path
 #[tauri::command]
 fn get_colormap_path(colormap_type: String) -> Result<String, weaverbird_lib::AppError> {
-    get_colormap_path_impl(colormap_type)
+    perf_metrics::timed("get_colormap_path", || {
+        get_colormap_path_impl(colormap_type)
+    })
+}
+
+/// Tauri command wrapper for computing a biome tint from a colormap
+#[tauri::command]
+async fn compute_tint(
+    colormap_type: weaverbird_lib::util::biome_tint::ColormapType,
+    climate: weaverbird_lib::util::biome_tint::Climate,
+    pack_id: Option<String>,
+    packs_dir: Option<String>,
+    target: Option<weaverbird_lib::model::TargetVersion>,
+) -> Result<weaverbird_lib::util::biome_tint::Tint, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("compute_tint", || {
+            compute_tint_impl(colormap_type, climate, pack_id, packs_dir, target)
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
+
+/// Tauri command wrapper for computing a block's biome tint
+#[tauri::command]
+async fn compute_block_tint(
+    block_id: String,
+    climate: weaverbird_lib::util::biome_tint::Climate,
+    pack_id: Option<String>,
+    packs_dir: Option<String>,
+    target: Option<weaverbird_lib::model::TargetVersion>,
+) -> Result<Option<weaverbird_lib::util::biome_tint::BlockTint>, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("compute_block_tint", || {
+            compute_block_tint_impl(block_id, climate, pack_id, packs_dir, target)
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
 }
 
 /// Tauri command wrapper for checking Minecraft installation
 #[tauri::command]
-fn check_minecraft_installed() -> Result<bool, weaverbird_lib::AppError> {
-    check_minecraft_installed_impl()
+fn check_minecraft_installed(
+) -> Result<Option<weaverbird_lib::util::mc_paths::MinecraftInstall>, weaverbird_lib::AppError> {
+    perf_metrics::timed("check_minecraft_installed", check_minecraft_installed_impl)
+}
+
+/// Tauri command wrapper for getting suggested Minecraft paths
+#[tauri::command]
+fn get_suggested_minecraft_paths(
+) -> Result<Vec<weaverbird_lib::util::mc_paths::MinecraftInstall>, weaverbird_lib::AppError> {
+    perf_metrics::timed(
+        "get_suggested_minecraft_paths",
+        get_suggested_minecraft_paths_impl,
+    )
+}
+
+/// Tauri command wrapper for initializing vanilla textures from custom directory (async)
+#[tauri::command]
+async fn initialize_vanilla_textures_from_custom_dir(
+    minecraft_dir: String,
+    job_id: Option<String>,
+) -> Result<String, weaverbird_lib::AppError> {
+    // Use spawn_blocking for CPU/IO-heavy vanilla texture extraction
+    let result = tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("initialize_vanilla_textures_from_custom_dir", || {
+            run_cancellable(job_id, || {
+                initialize_vanilla_textures_from_custom_dir_impl(minecraft_dir)
+            })
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))??;
+
+    if let Ok(Some(version)) = get_cached_vanilla_version_impl() {
+        ensure_particle_assets("initialize_vanilla_textures_from_custom_dir", &version).await;
+    }
+
+    Ok(result)
+}
+
+/// Tauri command wrapper for listing available Minecraft versions
+#[tauri::command]
+fn list_available_minecraft_versions(
+) -> Result<Vec<weaverbird_lib::util::vanilla_textures::MinecraftVersion>, weaverbird_lib::AppError>
+{
+    perf_metrics::timed(
+        "list_available_minecraft_versions",
+        list_available_minecraft_versions_impl,
+    )
+}
+
+/// Tauri command wrapper for getting cached vanilla texture version
+#[tauri::command]
+fn get_cached_vanilla_version() -> Result<Option<String>, weaverbird_lib::AppError> {
+    perf_metrics::timed(
+        "get_cached_vanilla_version",
+        get_cached_vanilla_version_impl,
+    )
+}
+
+/// Tauri command wrapper for setting vanilla texture version (async for non-blocking UI)
+/// Also ensures particle caches and generated TypeScript are up to date
+#[tauri::command]
+async fn set_vanilla_texture_version(
+    version: String,
+    window: tauri::Window,
+    job_id: Option<String>,
+) -> Result<String, weaverbird_lib::AppError> {
+    let version_clone = version.clone();
+
+    // Use spawn_blocking for CPU/IO-heavy vanilla texture extraction
+    let result = tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("set_vanilla_texture_version", || {
+            run_cancellable(job_id, || set_vanilla_texture_version_impl(version_clone, window))
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))??;
+
+    ensure_particle_assets("set_vanilla_texture_version", &version).await;
+
+    Ok(result)
+}
+
+/// Tauri command wrapper for detecting all launchers
+#[tauri::command]
+fn detect_launchers(
+) -> Result<Vec<weaverbird_lib::util::launcher_detection::LauncherInfo>, weaverbird_lib::AppError> {
+    perf_metrics::timed("detect_launchers", detect_launchers_impl)
+}
+
+/// Tauri command wrapper for identifying launcher from path
+#[tauri::command]
+fn identify_launcher(
+    path: String,
+) -> Result<weaverbird_lib::util::launcher_detection::LauncherInfo, weaverbird_lib::AppError> {
+    perf_metrics::timed("identify_launcher", || identify_launcher_impl(path))
+}
+
+/// Tauri command wrapper for getting launcher resourcepacks directory
+#[tauri::command]
+fn get_launcher_resourcepacks_dir(
+    launcher_info: weaverbird_lib::util::launcher_detection::LauncherInfo,
+) -> Result<String, weaverbird_lib::AppError> {
+    perf_metrics::timed("get_launcher_resourcepacks_dir", || {
+        get_launcher_resourcepacks_dir_impl(launcher_info)
+    })
+}
+
+/// Tauri command wrapper for getting pack texture path
+#[tauri::command]
+fn get_pack_texture_path(
+    app_handle: tauri::AppHandle,
+    pack_path: String,
+    asset_id: String,
+    is_zip: bool,
+    version_folders: Option<Vec<String>>,
+) -> Result<String, weaverbird_lib::AppError> {
+    perf_metrics::timed("get_pack_texture_path", || {
+        get_pack_texture_path_impl(pack_path, asset_id, is_zip, version_folders, &app_handle)
+    })
+}
+
+/// Tauri command wrapper for getting a cached grid thumbnail of a texture (async, decodes images)
+#[tauri::command]
+async fn get_asset_thumbnail(
+    pack_path: Option<String>,
+    asset_id: String,
+    is_zip: bool,
+    size: Option<u32>,
+) -> Result<String, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("get_asset_thumbnail", || {
+            get_asset_thumbnail_impl(pack_path, asset_id, is_zip, size)
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
+
+/// Tauri command wrapper for locating many pack textures in one call (async, parallel)
+#[tauri::command]
+async fn get_pack_texture_paths_batch(
+    app_handle: tauri::AppHandle,
+    requests: Vec<TexturePathRequest>,
+) -> Result<Vec<BatchResult<String>>, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("get_pack_texture_paths_batch", || {
+            get_pack_texture_paths_batch_impl(requests, &app_handle)
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
+
+/// Tauri command wrapper for resolving the winning textures of a page of assets (async, parallel)
+#[tauri::command]
+async fn resolve_texture_paths_bulk(
+    app_handle: tauri::AppHandle,
+    asset_ids: Vec<String>,
+    ordered_packs: Vec<weaverbird_lib::model::PackMeta>,
+) -> Result<Vec<BatchResult<ResolvedTexturePath>>, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("resolve_texture_paths_bulk", || {
+            resolve_texture_paths_bulk_impl(asset_ids, ordered_packs, &app_handle)
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
+
+/// Tauri command wrapper for dropping cached copies of an edited asset
+#[tauri::command]
+fn refresh_asset(
+    app_handle: tauri::AppHandle,
+    asset_id: String,
+    pack_id: String,
+    packs_dir: String,
+) -> Result<AssetUpdatedEvent, weaverbird_lib::AppError> {
+    perf_metrics::timed("refresh_asset", || {
+        refresh_asset_impl(asset_id, pack_id, packs_dir, &app_handle)
+    })
+}
+
+/// Tauri command wrapper for opening an asset in an external editor
+#[tauri::command]
+fn open_asset_in_editor(
+    app_handle: tauri::AppHandle,
+    asset_id: String,
+    pack_id: String,
+    packs_dir: String,
+    editor: Option<String>,
+) -> Result<weaverbird_lib::util::asset_editor::EditSession, weaverbird_lib::AppError> {
+    perf_metrics::timed("open_asset_in_editor", || {
+        open_asset_in_editor_impl(asset_id, pack_id, packs_dir, editor, &app_handle)
+    })
+}
+
+/// Tauri command wrapper for copying a vanilla asset into a folder pack
+#[tauri::command]
+fn copy_vanilla_asset_to_pack(
+    asset_id: String,
+    pack_id: String,
+    packs_dir: String,
+    kind: Option<weaverbird_lib::model::VanillaAssetKind>,
+) -> Result<Vec<String>, weaverbird_lib::AppError> {
+    perf_metrics::timed("copy_vanilla_asset_to_pack", || {
+        copy_vanilla_asset_to_pack_impl(asset_id, pack_id, packs_dir, kind)
+    })
+}
+
+/// Tauri command wrapper for writing an edited asset back into a working copy
+#[tauri::command]
+fn write_back_edited_asset(session_id: String) -> Result<String, weaverbird_lib::AppError> {
+    perf_metrics::timed("write_back_edited_asset", || {
+        write_back_edited_asset_impl(session_id)
+    })
+}
+
+/// Tauri command wrapper for closing an external editing session
+#[tauri::command]
+fn close_edit_session(session_id: String) -> Result<(), weaverbird_lib::AppError> {
+    perf_metrics::timed("close_edit_session", || close_edit_session_impl(session_id))
+}
+
+/// Tauri command wrapper for sampling an animated texture frame at a game tick
+#[tauri::command]
+fn sample_animation_frame(
+    asset_id: String,
+    pack_id: String,
+    packs_dir: String,
+    tick: u64,
+    target: Option<weaverbird_lib::model::TargetVersion>,
+    policy: Option<weaverbird_lib::model::MissingAssetPolicy>,
+) -> Result<Option<weaverbird_lib::util::texture_animation::AnimationSample>, weaverbird_lib::AppError>
+{
+    perf_metrics::timed("sample_animation_frame", || {
+        sample_animation_frame_impl(asset_id, pack_id, packs_dir, tick, target, policy)
+    })
+}
+
+/// Tauri command wrapper for getting the frames of an animated texture (async, may decode images)
+#[tauri::command]
+async fn get_animation_frames(
+    pack_path: Option<String>,
+    asset_id: String,
+    include_images: Option<bool>,
+) -> Result<Option<weaverbird_lib::util::texture_animation::AnimationFrames>, weaverbird_lib::AppError>
+{
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("get_animation_frames", || {
+            get_animation_frames_impl(pack_path, asset_id, include_images)
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
+
+/// Tauri command wrapper for checking a pack against a target Minecraft version
+#[tauri::command]
+fn validate_pack_compatibility(
+    pack_id: String,
+    packs_dir: String,
+    target_version: String,
+) -> Result<weaverbird_lib::util::pack_format::PackCompatibility, weaverbird_lib::AppError> {
+    perf_metrics::timed("validate_pack_compatibility", || {
+        validate_pack_compatibility_impl(pack_id, packs_dir, target_version)
+    })
+}
+
+/// Tauri command wrapper for contrast and colorblind analysis of GUI/font textures (async, decodes images)
+#[tauri::command]
+async fn analyze_contrast(
+    pack_id: String,
+    packs_dir: String,
+    asset_ids: Vec<String>,
+    include_images: Option<bool>,
+) -> Result<weaverbird_lib::util::contrast::ContrastReport, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("analyze_contrast", || {
+            analyze_contrast_impl(pack_id, packs_dir, asset_ids, include_images)
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
+
+/// Tauri command wrapper for checking a pack against every Minecraft release (async, reads the pack)
+#[tauri::command]
+async fn get_pack_compatibility(
+    pack_id: String,
+    packs_dir: String,
+) -> Result<weaverbird_lib::util::pack_converter::CompatibilityMatrix, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("get_pack_compatibility", || {
+            get_pack_compatibility_impl(pack_id, packs_dir)
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
+
+/// Tauri command wrapper for converting a pack to another pack format (async, copies the whole pack)
+#[tauri::command]
+async fn convert_pack_format(
+    pack_id: String,
+    packs_dir: String,
+    target_format: u32,
+    output_dir: String,
+    from_format: Option<u32>,
+) -> Result<weaverbird_lib::util::pack_converter::ConversionReport, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("convert_pack_format", || {
+            convert_pack_format_impl(pack_id, packs_dir, target_format, output_dir, from_format)
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
+
+/// Tauri command wrapper for unpacking a zip pack into a folder (async for non-blocking UI)
+#[tauri::command]
+async fn unpack_pack(
+    window: tauri::Window,
+    pack_id: String,
+    packs_dir: String,
+    dest: String,
+) -> Result<String, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("unpack_pack", || {
+            unpack_pack_impl(pack_id, packs_dir, dest, window)
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
+
+/// Tauri command wrapper for zipping a pack folder (async for non-blocking UI)
+#[tauri::command]
+async fn repack_pack(
+    window: tauri::Window,
+    dir: String,
+    dest_zip: String,
+) -> Result<String, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("repack_pack", || repack_pack_impl(dir, dest_zip, window))
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
+
+/// Tauri command wrapper for importing a pack (async for non-blocking UI)
+#[tauri::command]
+async fn import_pack(
+    source_path: String,
+    packs_dir: String,
+    allow_duplicate: Option<bool>,
+) -> Result<PackImportResult, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("import_pack", || {
+            import_pack_impl(source_path, packs_dir, allow_duplicate)
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
+
+/// Tauri command wrapper for finding files packs provide with identical content
+#[tauri::command]
+async fn find_duplicate_assets(
+    packs_dir: String,
+) -> Result<Vec<weaverbird_lib::model::DuplicateAsset>, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("find_duplicate_assets", || {
+            find_duplicate_assets_impl(packs_dir)
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
+
+/// Tauri command wrapper for turning low-memory mode on or off
+#[tauri::command]
+fn set_low_memory_mode(enabled: bool) -> Result<bool, weaverbird_lib::AppError> {
+    perf_metrics::timed("set_low_memory_mode", || set_low_memory_mode_impl(enabled))
+}
+
+/// Tauri command wrapper for reading whether low-memory mode is on
+#[tauri::command]
+fn get_low_memory_mode() -> Result<bool, weaverbird_lib::AppError> {
+    perf_metrics::timed("get_low_memory_mode", get_low_memory_mode_impl)
+}
+
+/// Tauri command wrapper for changing the backend log level
+#[tauri::command]
+fn set_log_level(level: String) -> Result<String, weaverbird_lib::AppError> {
+    perf_metrics::timed("set_log_level", || set_log_level_impl(level))
+}
+
+/// Tauri command wrapper for reading the backend log level
+#[tauri::command]
+fn get_log_level() -> Result<String, weaverbird_lib::AppError> {
+    perf_metrics::timed("get_log_level", get_log_level_impl)
+}
+
+/// Tauri command wrapper for the backend's most recent log lines
+#[tauri::command]
+fn get_recent_logs(limit: Option<usize>) -> Result<Vec<String>, weaverbird_lib::AppError> {
+    perf_metrics::timed("get_recent_logs", || get_recent_logs_impl(limit))
+}
+
+/// Tauri command wrapper for a vanilla texture's path and extraction provenance
+#[tauri::command]
+fn get_vanilla_texture_provenance(
+    asset_id: String,
+) -> Result<VanillaTextureProvenance, weaverbird_lib::AppError> {
+    perf_metrics::timed("get_vanilla_texture_provenance", || {
+        get_vanilla_texture_provenance_impl(asset_id)
+    })
+}
+
+/// Tauri command wrapper for pointing the vanilla and decompile caches at a shared folder
+#[tauri::command]
+fn set_shared_cache_dir(
+    path: Option<String>,
+) -> Result<weaverbird_lib::util::cache_location::SharedCacheStatus, weaverbird_lib::AppError> {
+    perf_metrics::timed("set_shared_cache_dir", || set_shared_cache_dir_impl(path))
+}
+
+/// Tauri command wrapper for reading the current cache locations
+#[tauri::command]
+fn get_shared_cache_status(
+) -> Result<weaverbird_lib::util::cache_location::SharedCacheStatus, weaverbird_lib::AppError> {
+    perf_metrics::timed("get_shared_cache_status", get_shared_cache_status_impl)
+}
+
+/// Tauri command wrapper for turning offline mode on or off
+#[tauri::command]
+fn set_offline_mode(enabled: bool) -> Result<bool, weaverbird_lib::AppError> {
+    perf_metrics::timed("set_offline_mode", || set_offline_mode_impl(enabled))
+}
+
+/// Tauri command wrapper for reading which datasets are extracted or bundled
+#[tauri::command]
+fn get_offline_status(
+    version: Option<String>,
+) -> Result<weaverbird_lib::util::offline::OfflineStatus, weaverbird_lib::AppError> {
+    perf_metrics::timed("get_offline_status", || get_offline_status_impl(version))
+}
+
+/// Tauri command wrapper for setting download mirrors, proxy and retries
+#[tauri::command]
+fn set_download_settings(
+    settings: weaverbird_lib::util::download::DownloadSettings,
+) -> Result<weaverbird_lib::util::download::DownloadSettings, weaverbird_lib::AppError> {
+    perf_metrics::timed("set_download_settings", || {
+        set_download_settings_impl(settings)
+    })
+}
+
+/// Tauri command wrapper for reading the download settings in effect
+#[tauri::command]
+fn get_download_settings(
+) -> Result<weaverbird_lib::util::download::DownloadSettings, weaverbird_lib::AppError> {
+    perf_metrics::timed("get_download_settings", get_download_settings_impl)
+}
+
+/// Tauri command wrapper for choosing the vanilla texture categories to extract
+#[tauri::command]
+fn set_vanilla_texture_categories(
+    categories: Vec<weaverbird_lib::util::vanilla_textures::TextureCategory>,
+) -> Result<Vec<weaverbird_lib::util::vanilla_textures::TextureCategory>, weaverbird_lib::AppError>
+{
+    perf_metrics::timed("set_vanilla_texture_categories", || {
+        set_vanilla_texture_categories_impl(categories)
+    })
+}
+
+/// Tauri command wrapper for reading the vanilla texture categories extracted
+#[tauri::command]
+fn get_vanilla_texture_categories(
+) -> Result<Vec<weaverbird_lib::util::vanilla_textures::TextureCategory>, weaverbird_lib::AppError>
+{
+    perf_metrics::timed(
+        "get_vanilla_texture_categories",
+        get_vanilla_texture_categories_impl,
+    )
+}
+
+/// Tauri command wrapper for measuring the extraction caches
+#[tauri::command]
+fn get_cache_usage(
+) -> Result<weaverbird_lib::util::cache_usage::CacheUsage, weaverbird_lib::AppError> {
+    perf_metrics::timed("get_cache_usage", get_cache_usage_impl)
+}
+
+/// Tauri command wrapper for clearing an extraction cache
+#[tauri::command]
+fn clear_cache(
+    category: weaverbird_lib::util::cache_usage::CacheCategory,
+    version: Option<String>,
+) -> Result<u64, weaverbird_lib::AppError> {
+    perf_metrics::timed("clear_cache", || clear_cache_impl(category, version))
+}
+
+/// Tauri command wrapper for setting the cache TTLs
+#[tauri::command]
+fn set_cache_maintenance_settings(
+    settings: weaverbird_lib::util::cache_maintenance::MaintenanceSettings,
+) -> Result<weaverbird_lib::util::cache_maintenance::MaintenanceSettings, weaverbird_lib::AppError>
+{
+    perf_metrics::timed("set_cache_maintenance_settings", || {
+        set_cache_maintenance_settings_impl(settings)
+    })
+}
+
+/// Tauri command wrapper for reading the cache TTLs
+#[tauri::command]
+fn get_cache_maintenance_settings(
+) -> Result<weaverbird_lib::util::cache_maintenance::MaintenanceSettings, weaverbird_lib::AppError>
+{
+    perf_metrics::timed(
+        "get_cache_maintenance_settings",
+        get_cache_maintenance_settings_impl,
+    )
+}
+
+/// Tauri command wrapper for running cache maintenance now
+#[tauri::command]
+async fn run_cache_maintenance(
+) -> Result<weaverbird_lib::util::cache_maintenance::MaintenanceReport, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(|| {
+        perf_metrics::timed("run_cache_maintenance", run_cache_maintenance_impl)
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
+
+/// Tauri command wrapper for building an asset's reference graph
+#[tauri::command]
+fn get_asset_graph(
+    root_asset: String,
+    depth: usize,
+    pack_order: Vec<String>,
+    packs_dir: String,
+) -> Result<weaverbird_lib::util::asset_graph::AssetGraph, weaverbird_lib::AppError> {
+    perf_metrics::timed("get_asset_graph", || {
+        get_asset_graph_impl(root_asset, depth, pack_order, packs_dir)
+    })
+}
+
+/// Tauri command wrapper for listing the cached vanilla versions
+#[tauri::command]
+fn list_cached_vanilla_versions() -> Result<Vec<String>, weaverbird_lib::AppError> {
+    perf_metrics::timed(
+        "list_cached_vanilla_versions",
+        list_cached_vanilla_versions_impl,
+    )
+}
+
+/// Tauri command wrapper for comparing a vanilla texture between versions
+#[tauri::command]
+fn compare_vanilla_asset(
+    asset_id: String,
+    version_a: String,
+    version_b: String,
+) -> Result<weaverbird_lib::util::vanilla_diff::VanillaAssetComparison, weaverbird_lib::AppError>
+{
+    perf_metrics::timed("compare_vanilla_asset", || {
+        compare_vanilla_asset_impl(asset_id, version_a, version_b)
+    })
+}
+
+/// Tauri command wrapper for listing the instances of a launcher
+#[tauri::command]
+fn list_launcher_instances(
+    launcher_info: weaverbird_lib::util::launcher_detection::LauncherInfo,
+) -> Result<Vec<weaverbird_lib::util::launcher_instances::LauncherInstance>, weaverbird_lib::AppError>
+{
+    perf_metrics::timed("list_launcher_instances", || {
+        list_launcher_instances_impl(launcher_info)
+    })
+}
+
+/// Tauri command wrapper for installing the built nest into a launcher instance
+#[tauri::command]
+fn install_nest_to_launcher(
+    nest_path: String,
+    launcher_info: weaverbird_lib::util::launcher_detection::LauncherInfo,
+    instance_id: Option<String>,
+    mode: weaverbird_lib::util::nest_install::InstallMode,
+    enable: bool,
+) -> Result<weaverbird_lib::util::nest_install::NestInstallResult, weaverbird_lib::AppError> {
+    perf_metrics::timed("install_nest_to_launcher", || {
+        install_nest_to_launcher_impl(nest_path, launcher_info, instance_id, mode, enable)
+    })
+}
+
+/// Tauri command wrapper for getting per-command timing metrics
+#[tauri::command]
+fn get_perf_metrics(
+) -> Result<Vec<weaverbird_lib::util::perf_metrics::CommandMetrics>, weaverbird_lib::AppError> {
+    get_perf_metrics_impl()
 }
 
-/// Tauri command wrapper for getting suggested Minecraft paths
+/// Tauri command wrapper for setting the language of backend messages
 #[tauri::command]
-fn get_suggested_minecraft_paths() -> Result<Vec<String>, weaverbird_lib::AppError> {
-    get_suggested_minecraft_paths_impl()
+fn set_backend_locale(locales: Vec<String>) -> Result<String, weaverbird_lib::AppError> {
+    perf_metrics::timed("set_backend_locale", || set_backend_locale_impl(locales))
 }
 
-/// Tauri command wrapper for initializing vanilla textures from custom directory (async)
+/// Tauri command wrapper for the performance self-test
 #[tauri::command]
-async fn initialize_vanilla_textures_from_custom_dir(
-    minecraft_dir: String,
-) -> Result<String, weaverbird_lib::AppError> {
-    // Use spawn_blocking for CPU/IO-heavy vanilla texture extraction
-    let result = tokio::task::spawn_blocking(move || {
-        initialize_vanilla_textures_from_custom_dir_impl(minecraft_dir)
+async fn run_perf_selftest(
+    packs_dir: String,
+    apply: Option<bool>,
+) -> Result<weaverbird_lib::util::perf_selftest::PerfReport, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("run_perf_selftest", || {
+            run_perf_selftest_impl(packs_dir, apply)
+        })
     })
     .await
-    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))??;
-
-    if let Ok(Some(version)) = get_cached_vanilla_version_impl() {
-        ensure_particle_assets("initialize_vanilla_textures_from_custom_dir", &version).await;
-    }
-
-    Ok(result)
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
 }
 
-/// Tauri command wrapper for listing available Minecraft versions
+/// Tauri command wrapper for the synthetic-pack self-test suite
+#[cfg(feature = "selftest")]
 #[tauri::command]
-fn list_available_minecraft_versions(
-) -> Result<Vec<weaverbird_lib::util::vanilla_textures::MinecraftVersion>, weaverbird_lib::AppError>
-{
-    list_available_minecraft_versions_impl()
+async fn run_selftest_suite(
+    config: Option<weaverbird_lib::util::selftest::SyntheticSuiteConfig>,
+) -> Result<weaverbird_lib::util::selftest::SelftestReport, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("run_selftest_suite", || run_selftest_suite_impl(config))
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
 }
 
-/// Tauri command wrapper for getting cached vanilla texture version
+/// Stand-in for builds without the self-test suite, so the frontend gets a
+/// clear error instead of an unknown command
+#[cfg(not(feature = "selftest"))]
 #[tauri::command]
-fn get_cached_vanilla_version() -> Result<Option<String>, weaverbird_lib::AppError> {
-    get_cached_vanilla_version_impl()
+fn run_selftest_suite() -> Result<(), weaverbird_lib::AppError> {
+    Err(weaverbird_lib::AppError::validation(
+        "Self-test suite not included in this build (build with --features selftest)",
+    ))
 }
 
-/// Tauri command wrapper for setting vanilla texture version (async for non-blocking UI)
-/// Also ensures particle caches and generated TypeScript are up to date
+/// Tauri command wrapper for loading the last indexed scan of a packs directory
 #[tauri::command]
-async fn set_vanilla_texture_version(
-    version: String,
-    window: tauri::Window,
-) -> Result<String, weaverbird_lib::AppError> {
-    let version_clone = version.clone();
-
-    // Use spawn_blocking for CPU/IO-heavy vanilla texture extraction
-    let result = tokio::task::spawn_blocking(move || {
-        set_vanilla_texture_version_impl(version_clone, window)
+async fn load_scan_index(
+    packs_dir: String,
+) -> Result<Option<weaverbird_lib::model::ScanResult>, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("load_scan_index", || load_scan_index_impl(packs_dir))
     })
     .await
-    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))??;
-
-    ensure_particle_assets("set_vanilla_texture_version", &version).await;
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
 
-    Ok(result)
+/// Tauri command wrapper for fuzzy asset search
+#[tauri::command]
+fn search_assets(
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<weaverbird_lib::util::asset_search::AssetSearchHit>, weaverbird_lib::AppError> {
+    perf_metrics::timed("search_assets", || search_assets_impl(query, limit))
 }
 
-/// Tauri command wrapper for detecting all launchers
+/// Tauri command wrapper for searching indexed assets by label
 #[tauri::command]
-fn detect_launchers(
-) -> Result<Vec<weaverbird_lib::util::launcher_detection::LauncherInfo>, weaverbird_lib::AppError> {
-    detect_launchers_impl()
+fn search_indexed_assets(
+    packs_dir: String,
+    label: String,
+    limit: Option<usize>,
+) -> Result<Vec<weaverbird_lib::model::AssetRecord>, weaverbird_lib::AppError> {
+    perf_metrics::timed("search_indexed_assets", || {
+        search_indexed_assets_impl(packs_dir, label, limit)
+    })
 }
 
-/// Tauri command wrapper for identifying launcher from path
+/// Tauri command wrapper for listing indexed providers of an asset
 #[tauri::command]
-fn identify_launcher(
-    path: String,
-) -> Result<weaverbird_lib::util::launcher_detection::LauncherInfo, weaverbird_lib::AppError> {
-    identify_launcher_impl(path)
+fn get_indexed_providers(
+    packs_dir: String,
+    asset_id: String,
+) -> Result<Vec<String>, weaverbird_lib::AppError> {
+    perf_metrics::timed("get_indexed_providers", || {
+        get_indexed_providers_impl(packs_dir, asset_id)
+    })
 }
 
-/// Tauri command wrapper for getting launcher resourcepacks directory
+/// Tauri command wrapper for moving a pack to the trash
 #[tauri::command]
-fn get_launcher_resourcepacks_dir(
-    launcher_info: weaverbird_lib::util::launcher_detection::LauncherInfo,
-) -> Result<String, weaverbird_lib::AppError> {
-    get_launcher_resourcepacks_dir_impl(launcher_info)
+fn delete_pack(
+    app_handle: tauri::AppHandle,
+    pack_id: String,
+    packs_dir: String,
+) -> Result<PackLibraryChange, weaverbird_lib::AppError> {
+    perf_metrics::timed("delete_pack", || {
+        delete_pack_impl(pack_id, packs_dir, &app_handle)
+    })
 }
 
-/// Tauri command wrapper for getting pack texture path
+/// Tauri command wrapper for renaming a pack
 #[tauri::command]
-fn get_pack_texture_path(
+fn rename_pack(
     app_handle: tauri::AppHandle,
-    pack_path: String,
-    asset_id: String,
-    is_zip: bool,
-    version_folders: Option<Vec<String>>,
-) -> Result<String, weaverbird_lib::AppError> {
-    get_pack_texture_path_impl(pack_path, asset_id, is_zip, version_folders, &app_handle)
+    pack_id: String,
+    new_name: String,
+    description: Option<String>,
+    packs_dir: String,
+) -> Result<PackLibraryChange, weaverbird_lib::AppError> {
+    perf_metrics::timed("rename_pack", || {
+        rename_pack_impl(pack_id, new_name, description, packs_dir, &app_handle)
+    })
 }
 
 /// Tauri command wrapper for reading block model JSON (legacy - goes through blockstate resolution)
@@ -240,8 +961,12 @@ fn read_block_model(
     pack_id: String,
     model_id: String,
     packs_dir: String,
+    target: Option<weaverbird_lib::model::TargetVersion>,
+    policy: Option<weaverbird_lib::model::MissingAssetPolicy>,
 ) -> Result<weaverbird_lib::util::block_models::BlockModel, weaverbird_lib::AppError> {
-    read_block_model_impl(pack_id, model_id, packs_dir)
+    perf_metrics::timed("read_block_model", || {
+        read_block_model_impl(pack_id, model_id, packs_dir, target, policy)
+    })
 }
 
 /// Tauri command wrapper for reading any file from a pack (directory or ZIP)
@@ -252,13 +977,98 @@ fn read_pack_file(
     file_path: String,
     is_zip: bool,
 ) -> Result<String, weaverbird_lib::AppError> {
-    read_pack_file_impl(pack_path, file_path, is_zip)
+    perf_metrics::timed("read_pack_file", || {
+        read_pack_file_impl(pack_path, file_path, is_zip)
+    })
+}
+
+/// Tauri command wrapper for listing a pack's sprite atlas definitions
+#[tauri::command]
+fn get_pack_atlases(
+    pack_path: String,
+    is_zip: bool,
+) -> Result<Vec<weaverbird_lib::model::AtlasDefinition>, weaverbird_lib::AppError> {
+    perf_metrics::timed("get_pack_atlases", || {
+        get_pack_atlases_impl(pack_path, is_zip)
+    })
+}
+
+/// Tauri command wrapper for listing an entity texture's random variants in a pack
+#[tauri::command]
+fn get_random_entity_variants(
+    pack_path: String,
+    is_zip: bool,
+    asset_id: String,
+) -> Result<
+    Option<weaverbird_lib::util::random_entities::RandomEntityVariants>,
+    weaverbird_lib::AppError,
+> {
+    perf_metrics::timed("get_random_entity_variants", || {
+        get_random_entity_variants_impl(pack_path, is_zip, asset_id)
+    })
+}
+
+/// Tauri command wrapper for picking the random variant shown for an entity
+#[tauri::command]
+fn pick_entity_variant(
+    pack_path: String,
+    is_zip: bool,
+    asset_id: String,
+    context: weaverbird_lib::util::random_entities::EntityContext,
+    seed: u64,
+) -> Result<
+    Option<weaverbird_lib::util::random_entities::EntityVariantPick>,
+    weaverbird_lib::AppError,
+> {
+    perf_metrics::timed("pick_entity_variant", || {
+        pick_entity_variant_impl(pack_path, is_zip, asset_id, context, seed)
+    })
 }
 
 /// Tauri command wrapper for reading vanilla JEM files from __mocks__/cem/
 #[tauri::command]
 fn read_vanilla_jem(entity_type: String) -> Result<String, weaverbird_lib::AppError> {
-    read_vanilla_jem_impl(entity_type)
+    perf_metrics::timed("read_vanilla_jem", || read_vanilla_jem_impl(entity_type))
+}
+
+/// Tauri command wrapper for validating a CEM model in a pack
+#[tauri::command]
+async fn validate_jem(
+    pack_id: String,
+    jem_path: String,
+    packs_dir: String,
+    target: Option<weaverbird_lib::model::TargetVersion>,
+) -> Result<weaverbird_lib::util::jem::JemValidation, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("validate_jem", || {
+            validate_jem_impl(pack_id, jem_path, packs_dir, target)
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
+
+/// Tauri command wrapper for evaluating CEM animations for one frame
+#[tauri::command]
+fn evaluate_cem_animations(
+    model: weaverbird_lib::util::jem::JemFile,
+    inputs: weaverbird_lib::util::cem_animation::AnimationInputs,
+) -> Result<weaverbird_lib::util::cem_animation::AnimationFrame, weaverbird_lib::AppError> {
+    perf_metrics::timed("evaluate_cem_animations", || {
+        evaluate_cem_animations_impl(model, inputs)
+    })
+}
+
+/// Tauri command wrapper for mapping entities to their vanilla textures
+#[tauri::command]
+async fn get_entity_textures(
+    target: Option<weaverbird_lib::model::TargetVersion>,
+) -> Result<weaverbird_lib::util::entity_textures::EntityTextureMap, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("get_entity_textures", || get_entity_textures_impl(target))
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
 }
 
 /// Tauri command wrapper for loading model JSON directly by model ID
@@ -267,8 +1077,12 @@ fn load_model_json(
     pack_id: String,
     model_id: String,
     packs_dir: String,
+    target: Option<weaverbird_lib::model::TargetVersion>,
+    policy: Option<weaverbird_lib::model::MissingAssetPolicy>,
 ) -> Result<weaverbird_lib::util::block_models::BlockModel, weaverbird_lib::AppError> {
-    load_model_json_impl(pack_id, model_id, packs_dir)
+    perf_metrics::timed("load_model_json", || {
+        load_model_json_impl(pack_id, model_id, packs_dir, target, policy)
+    })
 }
 
 /// Tauri command wrapper for getting block state schema
@@ -277,22 +1091,194 @@ fn get_block_state_schema(
     pack_id: String,
     block_id: String,
     packs_dir: String,
+    target: Option<weaverbird_lib::model::TargetVersion>,
+    policy: Option<weaverbird_lib::model::MissingAssetPolicy>,
 ) -> Result<weaverbird_lib::util::blockstates::BlockStateSchema, weaverbird_lib::AppError> {
-    get_block_state_schema_impl(pack_id, block_id, packs_dir)
+    perf_metrics::timed("get_block_state_schema", || {
+        get_block_state_schema_impl(pack_id, block_id, packs_dir, target, policy)
+    })
 }
 
 /// Tauri command wrapper for resolving block state to models (async for non-blocking)
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 async fn resolve_block_state(
     pack_id: String,
     block_id: String,
     packs_dir: String,
     state_props: Option<std::collections::HashMap<String, String>>,
     seed: Option<u64>,
+    target: Option<weaverbird_lib::model::TargetVersion>,
+    policy: Option<weaverbird_lib::model::MissingAssetPolicy>,
+    position: Option<weaverbird_lib::util::blockstates::BlockPosition>,
 ) -> Result<weaverbird_lib::util::blockstates::ResolutionResult, weaverbird_lib::AppError> {
     // Use spawn_blocking for potentially recursive model resolution
     tokio::task::spawn_blocking(move || {
-        resolve_block_state_impl(pack_id, block_id, packs_dir, state_props, seed)
+        perf_metrics::timed("resolve_block_state", || {
+            resolve_block_state_impl(
+                pack_id,
+                block_id,
+                packs_dir,
+                state_props,
+                seed,
+                target,
+                policy,
+                position,
+            )
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
+
+/// Tauri command wrapper for resolving many block states in one call (async, parallel)
+#[tauri::command]
+async fn resolve_block_states_batch(
+    packs_dir: String,
+    requests: Vec<BlockStateRequest>,
+    target: Option<weaverbird_lib::model::TargetVersion>,
+    policy: Option<weaverbird_lib::model::MissingAssetPolicy>,
+) -> Result<
+    Vec<BatchResult<weaverbird_lib::util::blockstates::ResolutionResult>>,
+    weaverbird_lib::AppError,
+> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("resolve_block_states_batch", || {
+            resolve_block_states_batch_impl(packs_dir, requests, target, policy)
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
+
+/// Tauri command wrapper for listing a block's state permutations (async, parallel)
+#[tauri::command]
+async fn list_block_state_permutations(
+    pack_id: String,
+    block_id: String,
+    packs_dir: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    target: Option<weaverbird_lib::model::TargetVersion>,
+    policy: Option<weaverbird_lib::model::MissingAssetPolicy>,
+) -> Result<weaverbird_lib::util::blockstates::StatePermutationPage, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("list_block_state_permutations", || {
+            list_block_state_permutations_impl(
+                pack_id, block_id, packs_dir, offset, limit, target, policy,
+            )
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
+
+/// Tauri command wrapper for diagnosing a model's textures
+#[tauri::command]
+async fn diagnose_model(
+    pack_id: String,
+    model_id: String,
+    packs_dir: String,
+    target: Option<weaverbird_lib::model::TargetVersion>,
+) -> Result<weaverbird_lib::util::block_models::ModelDiagnosis, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("diagnose_model", || {
+            diagnose_model_impl(pack_id, model_id, packs_dir, target)
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
+
+/// Tauri command wrapper for linting a pack's blockstates and models
+#[tauri::command]
+async fn lint_pack(
+    pack_id: String,
+    packs_dir: String,
+    target: Option<weaverbird_lib::model::TargetVersion>,
+) -> Result<weaverbird_lib::util::pack_lint::LintReport, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("lint_pack", || lint_pack_impl(pack_id, packs_dir, target))
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
+
+/// Tauri command wrapper for checking the references between a pack's files
+#[tauri::command]
+async fn check_pack_integrity(
+    pack_id: String,
+    packs_dir: String,
+    target: Option<weaverbird_lib::model::TargetVersion>,
+) -> Result<weaverbird_lib::util::pack_doctor::PackIntegrityReport, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("check_pack_integrity", || {
+            check_pack_integrity_impl(pack_id, packs_dir, target)
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
+
+/// Tauri command wrapper for exporting a block's models to glTF or OBJ
+#[tauri::command]
+async fn export_block_model(
+    pack_id: String,
+    block_id: String,
+    packs_dir: String,
+    state_props: Option<std::collections::HashMap<String, String>>,
+    output_path: String,
+    target: Option<weaverbird_lib::model::TargetVersion>,
+    policy: Option<weaverbird_lib::model::MissingAssetPolicy>,
+) -> Result<weaverbird_lib::util::model_export::ExportSummary, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("export_block_model", || {
+            export_block_model_impl(
+                pack_id,
+                block_id,
+                packs_dir,
+                state_props,
+                output_path,
+                target,
+                policy,
+            )
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
+
+/// Tauri command wrapper for importing a Blockbench project into a pack
+#[tauri::command]
+async fn import_bbmodel(
+    project_path: String,
+    target_pack: String,
+    asset_id: String,
+    packs_dir: String,
+) -> Result<weaverbird_lib::util::bbmodel::BbmodelImport, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("import_bbmodel", || {
+            import_bbmodel_impl(project_path, target_pack, asset_id, packs_dir)
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
+
+/// Tauri command wrapper for resolving an item model's overrides
+#[tauri::command]
+async fn resolve_item_model(
+    pack_id: String,
+    item_id: String,
+    packs_dir: String,
+    predicates: Option<std::collections::HashMap<String, f64>>,
+    target: Option<weaverbird_lib::model::TargetVersion>,
+    policy: Option<weaverbird_lib::model::MissingAssetPolicy>,
+) -> Result<weaverbird_lib::util::item_models::ResolvedItemModel, weaverbird_lib::AppError> {
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("resolve_item_model", || {
+            resolve_item_model_impl(pack_id, item_id, packs_dir, predicates, target, policy)
+        })
     })
     .await
     .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
@@ -304,9 +1290,13 @@ async fn get_entity_version_variants(
     packs_dir: String,
 ) -> Result<std::collections::HashMap<String, Vec<String>>, weaverbird_lib::AppError> {
     // Use spawn_blocking for I/O-heavy pack scanning
-    tokio::task::spawn_blocking(move || get_entity_version_variants_impl(packs_dir))
-        .await
-        .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("get_entity_version_variants", || {
+            get_entity_version_variants_impl(packs_dir)
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
 }
 
 // NOTE: Deprecated - particle data is now generated as TypeScript files
@@ -317,7 +1307,7 @@ async fn get_entity_version_variants(
 fn get_particle_data(
 ) -> Result<Option<weaverbird_lib::util::particle_data::ParticleData>, weaverbird_lib::AppError>
 {
-    get_particle_data_impl()
+    perf_metrics::timed("get_particle_data", get_particle_data_impl)
 }
 
 /// Tauri command wrapper for getting particle texture mappings for a specific version
@@ -326,7 +1316,45 @@ fn get_particle_data_for_version(
     version: String,
 ) -> Result<weaverbird_lib::util::particle_data::ParticleData, weaverbird_lib::AppError>
 {
-    get_particle_data_for_version_impl(version)
+    perf_metrics::timed("get_particle_data_for_version", || {
+        get_particle_data_for_version_impl(version)
+    })
+}
+
+/// Tauri command wrapper for resolving particle sprite textures across the pack stack
+#[tauri::command]
+async fn resolve_particle_textures(
+    particle_id: String,
+    pack_order: Vec<String>,
+    packs_dir: String,
+) -> Result<weaverbird_lib::util::particle_data::ResolvedParticleTextures, weaverbird_lib::AppError>
+{
+    // Use spawn_blocking for pack scanning and ZIP lookups
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("resolve_particle_textures", || {
+            resolve_particle_textures_impl(particle_id, pack_order, packs_dir)
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
+}
+
+/// Tauri command wrapper for getting a particle's combined preview data
+#[tauri::command]
+async fn get_particle_definition(
+    particle_id: String,
+    version: String,
+    pack_order: Vec<String>,
+    packs_dir: String,
+) -> Result<weaverbird_lib::util::particle_data::ParticleDefinition, weaverbird_lib::AppError> {
+    // Use spawn_blocking for pack scanning, cache reads and JAR lookups
+    tokio::task::spawn_blocking(move || {
+        perf_metrics::timed("get_particle_definition", || {
+            get_particle_definition_impl(particle_id, version, pack_order, packs_dir)
+        })
+    })
+    .await
+    .map_err(|e| weaverbird_lib::AppError::internal("Task join error", format!("{}", e)))?
 }
 
 /// Tauri command wrapper for getting cached particle physics data
@@ -334,13 +1362,28 @@ fn get_particle_data_for_version(
 fn get_particle_physics(
 ) -> Result<Option<weaverbird_lib::util::particle_physics_extractor::ExtractedPhysicsData>, weaverbird_lib::AppError>
 {
-    get_particle_physics_impl()
+    perf_metrics::timed("get_particle_physics", get_particle_physics_impl)
+}
+
+/// Tauri command wrapper for sampling a particle quad size curve
+#[tauri::command]
+fn sample_quad_size_curve(
+    curve: weaverbird_lib::util::particle_physics_extractor::QuadSizeCurve,
+    samples: usize,
+    lifetime: Option<f32>,
+) -> Result<weaverbird_lib::util::particle_physics_extractor::QuadSizeCurveTable, weaverbird_lib::AppError>
+{
+    perf_metrics::timed("sample_quad_size_curve", || {
+        sample_quad_size_curve_impl(curve, samples, lifetime)
+    })
 }
 
 /// Tauri command wrapper for checking if particle physics is cached
 #[tauri::command]
 fn is_particle_physics_cached(version: String) -> Result<bool, weaverbird_lib::AppError> {
-    is_particle_physics_cached_impl(version)
+    perf_metrics::timed("is_particle_physics_cached", || {
+        is_particle_physics_cached_impl(version)
+    })
 }
 
 /// Tauri command wrapper for extracting particle physics (async, expensive operation)
@@ -349,7 +1392,11 @@ async fn extract_particle_physics(
     version: String,
 ) -> Result<weaverbird_lib::util::particle_physics_extractor::ExtractedPhysicsData, weaverbird_lib::AppError>
 {
-    extract_particle_physics_impl(version).await
+    perf_metrics::timed_async(
+        "extract_particle_physics",
+        extract_particle_physics_impl(version),
+    )
+    .await
 }
 
 /// Tauri command wrapper for getting cached block emissions
@@ -357,13 +1404,26 @@ async fn extract_particle_physics(
 fn get_block_emissions(
 ) -> Result<Option<weaverbird_lib::util::block_particle_extractor::ExtractedBlockEmissions>, weaverbird_lib::AppError>
 {
-    get_block_emissions_impl()
+    perf_metrics::timed("get_block_emissions", get_block_emissions_impl)
+}
+
+/// Tauri command wrapper for getting a particle emitter preset for a block or entity
+#[tauri::command]
+fn get_emitter_preset(
+    source: String,
+    version: Option<String>,
+) -> Result<weaverbird_lib::util::emitter_presets::EmitterPreset, weaverbird_lib::AppError> {
+    perf_metrics::timed("get_emitter_preset", || {
+        get_emitter_preset_impl(source, version)
+    })
 }
 
 /// Tauri command wrapper for checking if block emissions are cached
 #[tauri::command]
 fn is_block_emissions_cached(version: String) -> Result<bool, weaverbird_lib::AppError> {
-    is_block_emissions_cached_impl(version)
+    perf_metrics::timed("is_block_emissions_cached", || {
+        is_block_emissions_cached_impl(version)
+    })
 }
 
 /// Tauri command wrapper for extracting block emissions (async, expensive operation)
@@ -372,20 +1432,42 @@ async fn extract_block_emissions(
     version: String,
 ) -> Result<weaverbird_lib::util::block_particle_extractor::ExtractedBlockEmissions, weaverbird_lib::AppError>
 {
-    extract_block_emissions_impl(version).await
+    perf_metrics::timed_async(
+        "extract_block_emissions",
+        extract_block_emissions_impl(version),
+    )
+    .await
 }
 
 /// Tauri command wrapper for generating TypeScript particle data from cache
 #[tauri::command]
 fn generate_particle_typescript() -> Result<String, weaverbird_lib::AppError> {
-    generate_particle_typescript_impl()
+    perf_metrics::timed(
+        "generate_particle_typescript",
+        generate_particle_typescript_impl,
+    )
 }
 
 fn main() {
+    logging::init();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_shell::init())
+        .register_asynchronous_uri_scheme_protocol(
+            asset_protocol::SCHEME,
+            |ctx, request, responder| {
+                let texture_cache = texture_cache_dir(ctx.app_handle()).ok();
+                // Reads can hit large zips, so keep them off the webview thread
+                tauri::async_runtime::spawn_blocking(move || {
+                    responder.respond(asset_protocol::handle_asset_request(
+                        &request,
+                        texture_cache.as_deref(),
+                    ));
+                });
+            },
+        )
         .setup(|app| {
             #[cfg(target_os = "macos")]
             {
@@ -427,20 +1509,45 @@ fn main() {
                 });
             }
 
+            // Forward background job updates to the frontend
+            {
+                use tauri::Emitter;
+                let app_handle = app.handle().clone();
+                jobs::set_listener(move |job| {
+                    let event = weaverbird_lib::commands::packs::JOB_UPDATED_EVENT;
+                    if let Err(e) = app_handle.emit(event, job) {
+                        warn!("Failed to emit {}: {}", event, e);
+                    }
+                });
+            }
+
+            // Run cache maintenance in the background (while enabled in the
+            // settings) and report the space it reclaims
+            {
+                use tauri::Emitter;
+                let app_handle = app.handle().clone();
+                cache_maintenance::start(move |report| {
+                    let event = weaverbird_lib::commands::packs::CACHE_MAINTENANCE_EVENT;
+                    if let Err(e) = app_handle.emit(event, report) {
+                        warn!("Failed to emit {}: {}", event, e);
+                    }
+                });
+            }
+
             // Generate TypeScript particle data from cached extractions (if available)
             if let Ok(Some(version)) = get_cached_vanilla_version_impl() {
                 if let Ok(Some(cache)) = particle_cache::load_cached_particle_cache(&version) {
                     if let Ok(ts_output) = particle_cache::resolve_generated_ts_path() {
-                        println!("[startup] Attempting to generate TypeScript at: {:?}", ts_output);
+                        info!("Attempting to generate TypeScript at: {:?}", ts_output);
                         if let Err(e) = weaverbird_lib::util::particle_typescript_gen::generate_particle_data_typescript(
                             &cache.physics,
                             &cache.emissions,
                             &cache.textures,
                             &ts_output,
                         ) {
-                            eprintln!("[startup] Warning: Failed to generate TypeScript particle data: {}", e);
+                            warn!("Failed to generate TypeScript particle data: {}", e);
                         } else {
-                            println!("[startup] Generated TypeScript particle data from cache");
+                            info!("Generated TypeScript particle data from cache");
                         }
                     }
                 }
@@ -451,11 +1558,19 @@ fn main() {
         .invoke_handler(tauri::generate_handler![
             scan_packs_folder,
             build_weaver_nest,
+            cancel_job,
+            submit_job,
+            get_job,
+            list_jobs,
+            list_interrupted_builds,
+            recover_interrupted_builds,
             get_default_packs_dir,
             initialize_vanilla_textures,
             get_vanilla_texture_path,
             get_vanilla_mcmeta_path,
             get_colormap_path,
+            compute_tint,
+            compute_block_tint,
             check_minecraft_installed,
             get_suggested_minecraft_paths,
             initialize_vanilla_textures_from_custom_dir,
@@ -466,19 +1581,89 @@ fn main() {
             identify_launcher,
             get_launcher_resourcepacks_dir,
             get_pack_texture_path,
+            get_pack_texture_paths_batch,
+            resolve_texture_paths_bulk,
+            get_asset_thumbnail,
+            refresh_asset,
+            open_asset_in_editor,
+            copy_vanilla_asset_to_pack,
+            write_back_edited_asset,
+            close_edit_session,
+            sample_animation_frame,
+            get_animation_frames,
+            validate_pack_compatibility,
+            get_pack_compatibility,
+            analyze_contrast,
+            convert_pack_format,
+            unpack_pack,
+            repack_pack,
+            import_pack,
+            find_duplicate_assets,
+            set_low_memory_mode,
+            get_low_memory_mode,
+            set_log_level,
+            get_log_level,
+            get_recent_logs,
+            get_vanilla_texture_provenance,
+            set_offline_mode,
+            get_offline_status,
+            set_download_settings,
+            get_download_settings,
+            set_vanilla_texture_categories,
+            get_vanilla_texture_categories,
+            get_cache_usage,
+            clear_cache,
+            set_cache_maintenance_settings,
+            get_cache_maintenance_settings,
+            run_cache_maintenance,
+            get_asset_graph,
+            list_cached_vanilla_versions,
+            compare_vanilla_asset,
+            list_launcher_instances,
+            install_nest_to_launcher,
+            set_shared_cache_dir,
+            get_shared_cache_status,
+            set_backend_locale,
+            run_perf_selftest,
+            get_perf_metrics,
+            run_selftest_suite,
+            load_scan_index,
+            search_assets,
+            search_indexed_assets,
+            get_indexed_providers,
+            delete_pack,
+            rename_pack,
             read_block_model,
             read_pack_file,
+            get_pack_atlases,
+            get_random_entity_variants,
+            pick_entity_variant,
             read_vanilla_jem,
+            validate_jem,
+            evaluate_cem_animations,
+            get_entity_textures,
             load_model_json,
             get_block_state_schema,
             resolve_block_state,
+            resolve_block_states_batch,
+            list_block_state_permutations,
+            resolve_item_model,
+            diagnose_model,
+            lint_pack,
+            check_pack_integrity,
+            export_block_model,
+            import_bbmodel,
             get_entity_version_variants,
             get_particle_data,
             get_particle_data_for_version,
+            resolve_particle_textures,
+            get_particle_definition,
             get_particle_physics,
             is_particle_physics_cached,
+            sample_quad_size_curve,
             extract_particle_physics,
             get_block_emissions,
+            get_emitter_preset,
             is_block_emissions_cached,
             extract_block_emissions,
             generate_particle_typescript
@@ -4,7 +4,7 @@ use std::collections::HashMap;
 /// Metadata about a discovered resource pack
 ///
 /// Either a zip file or directory containing pack.mcmeta
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PackMeta {
     /// Unique identifier for this pack
     pub id: String,
@@ -19,16 +19,146 @@ pub struct PackMeta {
     /// Description from pack.mcmeta (may contain Minecraft color codes)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    /// Base64-encoded PNG icon data from pack.png
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub icon_data: Option<String>,
+    /// The pack has a pack.png icon, loaded over `weaverbird://`
+    #[serde(default)]
+    pub has_icon: bool,
     /// Pack format version from pack.mcmeta (indicates Minecraft version compatibility)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pack_format: Option<u32>,
+    /// Range from `supported_formats` (or `min_format`/`max_format`) in pack.mcmeta
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supported_formats: Option<PackFormatRange>,
+    /// Overlay directories declared in pack.mcmeta (pack_format 18+)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overlays: Vec<PackOverlay>,
+    /// Experimental feature flags from `features.enabled` in pack.mcmeta
+    /// ("minecraft:trade_rebalance"); the pack only loads in worlds with them on
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub features: Vec<String>,
+    /// Most common width of the pack's block textures (16, 32, 128, ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub texture_resolution: Option<u32>,
+    /// Which custom entity feature dialect the pack's CEM models and random
+    /// entity files are written for; None when it has neither
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cem_dialect: Option<CemDialect>,
+    /// A pack shipped inside the Minecraft JAR (Programmer Art, High Contrast);
+    /// read-only
+    #[serde(default)]
+    pub builtin: bool,
+    /// Author and license from the pack's weaverbird.json and LICENSE files
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credits: Option<PackCredits>,
+    /// Suspicious contents found by the scan; a pack with any is not indexed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quarantine: Vec<QuarantineReason>,
+    /// Where the pack really is when `path` is a symlink or junction (e.g. a
+    /// Git checkout linked into the packs folder)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_path: Option<String>,
+}
+
+/// Why a scan quarantined a pack
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum QuarantineReason {
+    /// Native program or script (.exe, .dll, .bat, .sh, ...)
+    Executable { path: String },
+    /// Compiled Java class, which belongs in mods rather than resource packs
+    ClassFile { path: String },
+    /// ZIP entry that expands to hundreds of times its compressed size
+    CompressionRatio { path: String, ratio: u64 },
+    /// ZIP entry whose path leads out of the pack ("../", absolute or drive paths)
+    UnsafePath { path: String },
+}
+
+/// Flavor of OptiFine's custom entity models and textures a pack relies on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CemDialect {
+    /// Only what OptiFine itself reads
+    Optifine,
+    /// Uses extensions of Entity Model Features / Entity Texture Features,
+    /// as Fresh Animations does; parts of it are lost under OptiFine
+    Emf,
+}
+
+/// Authorship and licensing a pack declares in sidecar files
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackCredits {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
+    /// License name or SPDX identifier ("CC-BY-4.0")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// License file at the pack root ("LICENSE.txt")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license_file: Option<String>,
+}
+
+/// Inclusive range of pack formats
+///
+/// pack.mcmeta writes these as a single number, `[min, max]`, or
+/// `{"min_inclusive": min, "max_inclusive": max}`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackFormatRange {
+    pub min: u32,
+    pub max: u32,
+}
+
+impl PackFormatRange {
+    /// Parse any of the pack.mcmeta range encodings
+    pub fn from_json(value: &serde_json::Value) -> Option<Self> {
+        if let Some(n) = value.as_u64() {
+            return Some(PackFormatRange {
+                min: n as u32,
+                max: n as u32,
+            });
+        }
+        if let Some(arr) = value.as_array() {
+            let min = arr.first()?.as_u64()? as u32;
+            let max = arr
+                .get(1)
+                .and_then(|v| v.as_u64())
+                .map_or(min, |v| v as u32);
+            return Some(PackFormatRange { min, max });
+        }
+        let min = value.get("min_inclusive")?.as_u64()? as u32;
+        let max = value.get("max_inclusive")?.as_u64()? as u32;
+        Some(PackFormatRange { min, max })
+    }
+
+    /// Parse a `min_format`/`max_format` pair, where a `[major, minor]` version
+    /// contributes only its major number
+    pub fn from_min_max(min: &serde_json::Value, max: &serde_json::Value) -> Option<Self> {
+        let major = |v: &serde_json::Value| {
+            v.as_u64()
+                .or_else(|| v.as_array()?.first()?.as_u64())
+                .map(|n| n as u32)
+        };
+        Some(PackFormatRange {
+            min: major(min)?,
+            max: major(max)?,
+        })
+    }
+
+    pub fn contains(&self, format: u32) -> bool {
+        self.min <= format && format <= self.max
+    }
+}
+
+/// An overlay entry from pack.mcmeta: a subdirectory applied on top of the
+/// pack's root when the game's pack format falls within `formats`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackOverlay {
+    pub directory: String,
+    pub formats: PackFormatRange,
 }
 
 /// A single asset (texture, model, config, etc.) with metadata
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AssetRecord {
     /// Asset ID (e.g., "minecraft:block/stone")
     pub id: String,
@@ -38,6 +168,40 @@ pub struct AssetRecord {
     pub files: Vec<String>,
 }
 
+/// Dimensions and format of a PNG texture, read from its header
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextureInfo {
+    pub width: u32,
+    pub height: u32,
+    /// Bits per sample (1, 2, 4, 8 or 16)
+    pub bit_depth: u8,
+    /// PNG color type (0 gray, 2 RGB, 3 indexed, 4 gray + alpha, 6 RGBA)
+    pub color_type: u8,
+}
+
+impl TextureInfo {
+    /// Side length of one frame: animated textures stack square frames
+    /// vertically, so this is the smaller of width and height
+    pub fn resolution(&self) -> u32 {
+        self.width.min(self.height)
+    }
+}
+
+/// Asset ID -> pack ID -> header of that pack's texture for the asset
+pub type TextureInfoMap = HashMap<String, HashMap<String, TextureInfo>>;
+
+/// A sprite atlas definition from a pack's atlases/ folder (1.19.3+)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AtlasDefinition {
+    /// Atlas ID (e.g., "minecraft:blocks")
+    pub id: String,
+    /// Pack-relative path (e.g., "assets/minecraft/atlases/blocks.json")
+    pub file: String,
+    /// Sprite sources registered by this pack, in file order
+    pub sources: Vec<serde_json::Value>,
+}
+
 /// Information about which pack provides an asset
 ///
 /// Currently defined but not actively used
@@ -56,6 +220,157 @@ pub struct OverrideSelection {
     pub variant_path: Option<String>,
 }
 
+/// Compression method for zipped Weaver Nest output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompressionMethod {
+    /// No compression (fastest to build and for the game to load)
+    Store,
+    #[default]
+    Deflate,
+    Zstd,
+}
+
+/// Zip output settings for a Weaver Nest build
+///
+/// When present on a build request, the nest is written as a .zip archive
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NestCompression {
+    #[serde(default)]
+    pub method: CompressionMethod,
+    /// Compression level 0-9 (ignored for store); None uses the method default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<u8>,
+}
+
+/// Minecraft version a workspace is pinned to
+///
+/// Commands that fall back to vanilla data take this so a workspace targeting
+/// one version is never previewed against another version's vanilla cache.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetVersion {
+    /// Minecraft release version (e.g., "1.20.1")
+    pub version: String,
+    /// Resource pack format; derived from `version` when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pack_format: Option<u32>,
+}
+
+impl TargetVersion {
+    /// Pack format for the target, from the request or the built-in table
+    pub fn pack_format(&self) -> Option<u32> {
+        self.pack_format
+            .or_else(|| crate::util::pack_format::pack_format_for_version(&self.version))
+    }
+}
+
+/// What texture/model/blockstate resolution does when a pack lacks the asset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MissingAssetPolicy {
+    /// Fill the gap from vanilla, as the game does
+    #[default]
+    UseVanilla,
+    /// Fail with an error naming the missing asset
+    PackOnly,
+    /// Substitute a placeholder so the gap stays visible in previews
+    Placeholder,
+}
+
+/// Which file of an asset ID to copy out of the vanilla cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VanillaAssetKind {
+    /// assets/<ns>/textures/<path>.png, plus its .mcmeta when present
+    #[default]
+    Texture,
+    /// assets/<ns>/models/<path>.json
+    Model,
+    /// assets/<ns>/blockstates/<name>.json for "block/<name>"
+    Blockstate,
+}
+
+/// How a nest build pairs texture .mcmeta files with the winning textures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum McmetaPairing {
+    /// Only carry the .mcmeta from the texture's own pack; warn when another
+    /// provider has metadata the winner lacks
+    #[default]
+    SamePack,
+    /// Like SamePack, but borrow another provider's .mcmeta when its texture
+    /// has the same dimensions as the winner
+    BorrowMatching,
+    /// Drop all texture .mcmeta files
+    Strip,
+}
+
+/// How a nest build picks the winner among packs providing the same asset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictStrategy {
+    /// The highest pack in the pack order wins
+    #[default]
+    PackOrder,
+    /// For textures, the provider with the highest resolution wins; ties and
+    /// non-texture assets fall back to pack order
+    HighestResolution,
+}
+
+/// A block (or tile) that several packs define connected textures for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CtmConflict {
+    /// CTM asset ID (e.g., "minecraft:optifine/ctm/block/glass")
+    pub asset_id: String,
+    /// Packs providing CTM for it
+    pub packs: Vec<String>,
+}
+
+/// Packs with identical content
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicatePacks {
+    /// SHA-256 over the packs' file paths and contents
+    pub content_hash: String,
+    /// IDs of the packs sharing this content
+    pub pack_ids: Vec<String>,
+    /// True when the copies go by different names ("same content, different filename")
+    pub different_names: bool,
+}
+
+/// A file that several packs provide with byte-identical content
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateAsset {
+    pub asset_id: String,
+    /// Pack-relative path of the file
+    pub file: String,
+    /// xxh3-64 of the file content, as 16 hex digits
+    pub content_hash: String,
+    /// Size of the file in bytes
+    pub size: u64,
+    /// IDs of the packs providing this exact content
+    pub pack_ids: Vec<String>,
+}
+
+/// A ZIP pack the scan couldn't read as it is
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackScanError {
+    /// ID the pack would have (its file name)
+    pub pack_id: String,
+    pub path: String,
+    /// Why the archive couldn't be opened
+    pub reason: String,
+    /// Copy rebuilt from the entries still readable; the pack is in the scan
+    /// result with this path
+    pub repaired_path: Option<String>,
+    /// The file was still being written (e.g. downloading); scan again later
+    pub retry: bool,
+}
+
 /// Result of scanning a resource packs directory
 ///
 /// Contains all discovered packs and their assets
@@ -67,11 +382,23 @@ pub struct ScanResult {
     pub assets: Vec<AssetRecord>,
     /// Mapping of asset IDs to the pack IDs that provide them
     pub providers: HashMap<String, Vec<String>>,
+    /// Blocks with CTM rules from more than one pack
+    #[serde(default)]
+    pub ctm_conflicts: Vec<CtmConflict>,
+    /// Groups of packs that are copies of each other
+    #[serde(default)]
+    pub duplicate_packs: Vec<DuplicatePacks>,
+    /// Texture dimensions and bit depth per asset and providing pack
+    #[serde(default)]
+    pub texture_info: TextureInfoMap,
+    /// ZIP packs that were damaged or incomplete; not kept in the scan index
+    #[serde(default)]
+    pub errors: Vec<PackScanError>,
 }
 
 /// Progress tracking for long-running operations
 ///
-/// Currently defined but not yet implemented
+/// Reported by background jobs (see `util::jobs`)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Progress {
     pub phase: String,
@@ -93,8 +420,9 @@ mod tests {
             size: 1024,
             is_zip: false,
             description: Some("Test description".to_string()),
-            icon_data: Some("base64_icon_data".to_string()),
+            has_icon: true,
             pack_format: None,
+            ..Default::default()
         };
 
         let json = serde_json::to_string(&pack).expect("should serialize");
@@ -104,6 +432,7 @@ mod tests {
         assert_eq!(deserialized.name, "Test Pack");
         assert_eq!(deserialized.size, 1024);
         assert_eq!(deserialized.is_zip, false);
+        assert!(deserialized.has_icon);
     }
 
     #[test]
@@ -174,8 +503,9 @@ mod tests {
                 size: 2048,
                 is_zip: true,
                 description: None,
-                icon_data: None,
+                has_icon: false,
                 pack_format: None,
+                ..Default::default()
             }],
             assets: vec![AssetRecord {
                 id: "minecraft:block/dirt".to_string(),
@@ -194,6 +524,10 @@ mod tests {
                 );
                 map
             },
+            ctm_conflicts: Vec::new(),
+            duplicate_packs: Vec::new(),
+            texture_info: HashMap::new(),
+            errors: Vec::new(),
         };
 
         let json = serde_json::to_string(&scan_result).expect("should serialize");
@@ -231,8 +565,9 @@ mod tests {
             size: 512,
             is_zip: true,
             description: Some("Description".to_string()),
-            icon_data: None,
+            has_icon: false,
             pack_format: None,
+            ..Default::default()
         };
 
         let pack2 = pack1.clone();
@@ -254,4 +589,49 @@ mod tests {
         assert_eq!(asset1.labels, asset2.labels);
         assert_eq!(asset1.files, asset2.files);
     }
+
+    #[test]
+    fn test_nest_compression_deserialization() {
+        let compression: NestCompression =
+            serde_json::from_str(r#"{"method": "zstd", "level": 3}"#).unwrap();
+        assert_eq!(compression.method, CompressionMethod::Zstd);
+        assert_eq!(compression.level, Some(3));
+
+        let defaulted: NestCompression = serde_json::from_str("{}").unwrap();
+        assert_eq!(defaulted.method, CompressionMethod::Deflate);
+        assert_eq!(defaulted.level, None);
+
+        let pairing: McmetaPairing = serde_json::from_str(r#""borrow_matching""#).unwrap();
+        assert_eq!(pairing, McmetaPairing::BorrowMatching);
+    }
+
+    #[test]
+    fn test_target_version_pack_format() {
+        let target: TargetVersion = serde_json::from_str(r#"{"version": "1.20.1"}"#).unwrap();
+        assert_eq!(target.pack_format(), Some(15));
+
+        let pinned: TargetVersion =
+            serde_json::from_str(r#"{"version": "24w14a", "packFormat": 31}"#).unwrap();
+        assert_eq!(pinned.pack_format(), Some(31));
+    }
+
+    #[test]
+    fn test_pack_format_range_from_json() {
+        use serde_json::json;
+
+        assert_eq!(
+            PackFormatRange::from_json(&json!(18)),
+            Some(PackFormatRange { min: 18, max: 18 })
+        );
+        assert_eq!(
+            PackFormatRange::from_json(&json!([18, 34])),
+            Some(PackFormatRange { min: 18, max: 34 })
+        );
+        assert_eq!(
+            PackFormatRange::from_json(&json!({"min_inclusive": 22, "max_inclusive": 48})),
+            Some(PackFormatRange { min: 22, max: 48 })
+        );
+        assert_eq!(PackFormatRange::from_json(&json!("18")), None);
+        assert!(PackFormatRange { min: 18, max: 34 }.contains(34));
+    }
 }
@@ -10,6 +10,7 @@
 use anyhow::{Context, Result};
 use std::fs;
 use std::path::Path;
+use tracing::info;
 use super::block_animation_extractor::{ExtractedAnimationData, EntityAnimations, Animation, AnimationTrigger, PartAnimation, Keyframe, MobModel};
 
 /// Generate TypeScript animation files from extracted data
@@ -61,8 +62,8 @@ pub fn generate_animation_typescript(
     // Generate index file
     generate_index_file(&all_entity_ids, output_dir, &animations.version, &datetime)?;
 
-    println!(
-        "[animation_typescript] Generated {} animation files ({} blocks, {} mobs) in {:?}",
+    info!(
+        "Generated {} animation files ({} blocks, {} mobs) in {:?}",
         all_entity_ids.len(),
         block_entity_ids.len(),
         mob_entity_ids.len(),
@@ -162,7 +163,7 @@ fn apply_entity_post_processing(mob_model: &MobModel) -> MobModel {
             layer.expressions.remove("base.rx");
         }
 
-        println!("[animation_typescript] Applied bell post-processing: body.rx and body.rz kept for direction-based swing (base inherits as child)");
+        info!("Applied bell post-processing: body.rx and body.rz kept for direction-based swing (base inherits as child)");
     }
 
     result
@@ -0,0 +1,370 @@
+/// Round-trip editing of pack assets in an external editor
+///
+/// Files from folder packs are opened in place. Files from ZIP packs are first
+/// extracted into a temp workspace; once the user saves, the edit can be written
+/// back into a working copy of the pack (a folder next to the zip), so the
+/// original archive is never modified.
+use crate::model::PackMeta;
+use crate::util::{pack_files, zip};
+use anyhow::{anyhow, Context, Result};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use tracing::info;
+
+/// How often the watcher checks the edited file for changes
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Open editing sessions by session ID; a watcher stops once its session is removed
+static SESSIONS: Lazy<Mutex<HashMap<String, EditSession>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static NEXT_SESSION: AtomicU64 = AtomicU64::new(1);
+
+/// An asset opened in an external editor
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EditSession {
+    pub id: String,
+    pub asset_id: String,
+    pub pack_id: String,
+    /// Path of the pack the asset came from
+    pub pack_path: String,
+    /// Pack-relative path of the edited file
+    pub entry: String,
+    /// File handed to the editor
+    pub edit_path: String,
+    /// True when the editor works on the pack's own file (folder packs)
+    pub in_place: bool,
+}
+
+/// Temp workspace holding files extracted from ZIP packs for editing
+pub fn workspace_dir() -> PathBuf {
+    std::env::temp_dir().join("weaverbird_edit")
+}
+
+/// Folder a ZIP pack's edits are written back to ("Pack.zip" -> "Pack (working copy)")
+pub fn working_copy_dir(pack_path: &str) -> PathBuf {
+    let path = Path::new(pack_path);
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "pack".to_string());
+    path.with_file_name(format!("{} (working copy)", stem))
+}
+
+/// Create an editing session for an asset, extracting it if the pack is a ZIP
+pub fn prepare_edit_session(
+    pack: &PackMeta,
+    asset_id: &str,
+    workspace: &Path,
+) -> Result<EditSession> {
+    let entry = pack_files::safe_entry_path(&pack_files::asset_entry_path(asset_id))?;
+    let id = format!(
+        "{}-{}",
+        chrono::Utc::now().timestamp_millis(),
+        NEXT_SESSION.fetch_add(1, Ordering::Relaxed)
+    );
+
+    let edit_path = if pack.is_zip {
+        let bytes = pack_files::read_pack_entry(pack, &entry)?;
+        let edit_path = workspace.join(&id).join(&entry);
+        if let Some(parent) = edit_path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(&edit_path, bytes)
+            .with_context(|| format!("Failed to write {}", edit_path.display()))?;
+        edit_path
+    } else {
+        let edit_path = pack_files::resolve_dir_entry(Path::new(&pack.path), &entry)?;
+        if !edit_path.is_file() {
+            return Err(anyhow!("{} not found in {}", entry, pack.name));
+        }
+        edit_path
+    };
+
+    Ok(EditSession {
+        id,
+        asset_id: asset_id.to_string(),
+        pack_id: pack.id.clone(),
+        pack_path: pack.path.clone(),
+        entry,
+        edit_path: edit_path.to_string_lossy().to_string(),
+        in_place: !pack.is_zip,
+    })
+}
+
+/// Build the command that opens `file` in `editor`, or in the system default
+/// application when no editor is configured
+///
+/// Returns `None` on Windows without an editor; the file is then opened with
+/// `ShellExecuteW`, since `cmd /C start` would let cmd.exe interpret `&`, `|`
+/// or `^` in pack file names.
+pub fn editor_command(editor: Option<&str>, file: &Path) -> Option<Command> {
+    match editor.map(str::trim).filter(|editor| !editor.is_empty()) {
+        Some(editor) if cfg!(target_os = "macos") && editor.ends_with(".app") => {
+            let mut command = Command::new("open");
+            command.arg("-a").arg(editor).arg(file);
+            Some(command)
+        }
+        Some(editor) => {
+            let mut command = Command::new(editor);
+            command.arg(file);
+            Some(command)
+        }
+        None => default_open_command(std::env::consts::OS, file),
+    }
+}
+
+/// Command that opens `file` in the default application on `os`, if one is used
+fn default_open_command(os: &str, file: &Path) -> Option<Command> {
+    let program = match os {
+        "macos" => "open",
+        "windows" => return None,
+        _ => "xdg-open",
+    };
+    let mut command = Command::new(program);
+    command.arg(file);
+    Some(command)
+}
+
+/// Launch the editor without waiting for it to exit
+pub fn launch_editor(editor: Option<&str>, file: &Path) -> Result<()> {
+    match editor_command(editor, file) {
+        Some(mut command) => command.spawn().map(|_| ()),
+        None => shell_open(file),
+    }
+    .with_context(|| format!("Failed to launch editor for {}", file.display()))
+}
+
+/// Open `file` in its default application through the shell, with no
+/// command line to parse
+#[cfg(windows)]
+fn shell_open(file: &Path) -> std::io::Result<()> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::UI::Shell::ShellExecuteW;
+    use windows_sys::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+
+    let wide = |s: &std::ffi::OsStr| s.encode_wide().chain(Some(0)).collect::<Vec<u16>>();
+    let verb = wide("open".as_ref());
+    let path = wide(file.as_os_str());
+    // SAFETY: both strings are NUL-terminated and outlive the call
+    let result = unsafe {
+        ShellExecuteW(
+            std::ptr::null_mut(),
+            verb.as_ptr(),
+            path.as_ptr(),
+            std::ptr::null(),
+            std::ptr::null(),
+            SW_SHOWNORMAL,
+        )
+    };
+    // Values above 32 mean success
+    if result as isize <= 32 {
+        return Err(std::io::Error::other(format!(
+            "ShellExecuteW failed with code {}",
+            result as isize
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(not(windows))]
+fn shell_open(_file: &Path) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "ShellExecuteW is only available on Windows",
+    ))
+}
+
+/// Register a session and watch its file, calling `on_change` after each save
+///
+/// The watcher polls the file's modification time and stops when the session
+/// is closed.
+pub fn start_session<F>(session: EditSession, on_change: F)
+where
+    F: Fn(&EditSession) + Send + 'static,
+{
+    let path = PathBuf::from(&session.edit_path);
+    let id = session.id.clone();
+    SESSIONS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(id.clone(), session);
+
+    std::thread::spawn(move || {
+        let modified = |path: &Path| -> Option<SystemTime> {
+            std::fs::metadata(path).and_then(|m| m.modified()).ok()
+        };
+        let mut last_modified = modified(&path);
+
+        loop {
+            std::thread::sleep(WATCH_INTERVAL);
+            let Some(session) = get_session(&id) else {
+                break;
+            };
+            let current = modified(&path);
+            if current.is_some() && current != last_modified {
+                last_modified = current;
+                on_change(&session);
+            }
+        }
+        info!("Stopped watching {}", path.display());
+    });
+}
+
+/// Look up an open session
+pub fn get_session(session_id: &str) -> Option<EditSession> {
+    SESSIONS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(session_id)
+        .cloned()
+}
+
+/// Close a session: stop its watcher and delete its extracted files
+pub fn close_session(session_id: &str) -> Result<()> {
+    let session = SESSIONS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(session_id)
+        .ok_or_else(|| anyhow!("No editing session {}", session_id))?;
+
+    if !session.in_place {
+        let session_dir = workspace_dir().join(&session.id);
+        if session_dir.exists() {
+            std::fs::remove_dir_all(&session_dir)
+                .with_context(|| format!("Failed to remove {}", session_dir.display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Folder new files for a pack are written into
+///
+/// Folder packs are written in place. A ZIP pack gets a working copy, created
+/// from the ZIP on first use, so the original archive is never modified.
+pub fn writable_pack_dir(pack_path: &str, is_zip: bool) -> Result<PathBuf> {
+    if !is_zip {
+        return Ok(PathBuf::from(pack_path));
+    }
+
+    let working_copy = working_copy_dir(pack_path);
+    if !working_copy.exists() {
+        let written = zip::extract_zip_to_dir(pack_path, &working_copy)?;
+        info!(
+            "Created working copy {} ({} files)",
+            working_copy.display(),
+            written
+        );
+    }
+    Ok(working_copy)
+}
+
+/// Write an edited file back into the pack's working copy
+///
+/// Folder packs are edited in place, so their own folder is returned unchanged.
+///
+/// Returns the working copy folder.
+pub fn write_back(session: &EditSession) -> Result<PathBuf> {
+    if session.in_place {
+        return Ok(PathBuf::from(&session.pack_path));
+    }
+
+    let working_copy = writable_pack_dir(&session.pack_path, true)?;
+    let target = working_copy.join(&session.entry);
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    std::fs::copy(&session.edit_path, &target)
+        .with_context(|| format!("Failed to write {}", target.display()))?;
+    Ok(working_copy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_working_copy_dir() {
+        assert_eq!(
+            working_copy_dir("/packs/Faithful.zip"),
+            PathBuf::from("/packs/Faithful (working copy)")
+        );
+    }
+
+    #[test]
+    fn test_editor_command_uses_configured_editor() {
+        let command = editor_command(Some("/usr/bin/gimp"), Path::new("/tmp/stone.png")).unwrap();
+        assert_eq!(command.get_program(), "/usr/bin/gimp");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            vec![Path::new("/tmp/stone.png").as_os_str()]
+        );
+    }
+
+    #[test]
+    fn test_default_open_command_never_uses_cmd() {
+        // A pack entry like "x&calc.png" must not reach cmd.exe
+        let file = Path::new("textures/block/x&calc.png");
+        assert!(default_open_command("windows", file).is_none());
+        for os in ["macos", "linux"] {
+            let command = default_open_command(os, file).unwrap();
+            assert_ne!(command.get_program(), "cmd");
+            assert_eq!(
+                command.get_args().collect::<Vec<_>>(),
+                vec![file.as_os_str()]
+            );
+        }
+    }
+
+    #[test]
+    fn test_zip_edit_round_trip() {
+        let temp_dir = std::env::temp_dir().join("test_asset_editor_round_trip");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+
+        let zip_path = temp_dir.join("Pack.zip");
+        let mut writer = ::zip::ZipWriter::new(std::fs::File::create(&zip_path).unwrap());
+        let options = ::zip::write::FileOptions::default();
+        writer.start_file("pack.mcmeta", options).unwrap();
+        writer.write_all(b"{}").unwrap();
+        writer
+            .start_file("assets/minecraft/textures/block/stone.png", options)
+            .unwrap();
+        writer.write_all(b"original").unwrap();
+        writer.finish().unwrap();
+
+        let pack = PackMeta {
+            id: "Pack.zip".to_string(),
+            name: "Pack".to_string(),
+            path: zip_path.to_string_lossy().to_string(),
+            is_zip: true,
+            ..Default::default()
+        };
+        let session = prepare_edit_session(&pack, "minecraft:block/stone", &temp_dir.join("ws"));
+        let session = session.unwrap();
+        let extracted = std::fs::read(&session.edit_path).unwrap();
+        std::fs::write(&session.edit_path, b"edited").unwrap();
+        let working_copy = write_back(&session).unwrap();
+        let written =
+            std::fs::read(working_copy.join("assets/minecraft/textures/block/stone.png")).unwrap();
+        let mcmeta_copied = working_copy.join("pack.mcmeta").is_file();
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+
+        assert!(!session.in_place);
+        assert_eq!(extracted, b"original");
+        assert_eq!(written, b"edited");
+        assert!(mcmeta_copied);
+        assert_eq!(working_copy, temp_dir.join("Pack (working copy)"));
+    }
+}
@@ -0,0 +1,520 @@
+/// Reference graph between assets, for dependency diagrams
+///
+/// Starting from one asset, follows what it points at: a blockstate's models, a
+/// model's parent and textures, an item definition's models (items/*.json from
+/// 1.21.4, the item model before that) and the textures of an entity's CEM
+/// model. Each file is read from the highest-priority pack that has it, vanilla
+/// last, so the graph shows the pack stack as it renders.
+///
+/// One resource location can name several assets, so node IDs carry their kind:
+/// "blockstate:minecraft:oak_log", "model:minecraft:block/oak_log",
+/// "texture:minecraft:block/oak_log", "item:minecraft:diamond",
+/// "entity:minecraft:cow".
+use crate::model::PackMeta;
+use crate::util::pack_files;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
+
+/// Graphs stop growing past this many nodes (a block using a shared parent
+/// chain stays far below it)
+const MAX_NODES: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeKind {
+    Blockstate,
+    Model,
+    Texture,
+    Item,
+    Entity,
+}
+
+impl NodeKind {
+    const ALL: [NodeKind; 5] = [
+        NodeKind::Blockstate,
+        NodeKind::Model,
+        NodeKind::Texture,
+        NodeKind::Item,
+        NodeKind::Entity,
+    ];
+
+    fn prefix(self) -> &'static str {
+        match self {
+            NodeKind::Blockstate => "blockstate",
+            NodeKind::Model => "model",
+            NodeKind::Texture => "texture",
+            NodeKind::Item => "item",
+            NodeKind::Entity => "entity",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeKind {
+    /// Blockstate or item definition -> model it renders
+    Model,
+    /// Model -> model it inherits from
+    Parent,
+    /// Model or entity -> texture it draws
+    Texture,
+    /// Item model -> model an override switches to (before 1.21.4)
+    Override,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetNode {
+    /// Kind-prefixed ID ("model:minecraft:block/stone")
+    pub id: String,
+    pub kind: NodeKind,
+    /// Resource location ("minecraft:block/stone")
+    pub location: String,
+    /// Pack the file is read from; None when no pack has it, including models
+    /// built into the game ("minecraft:builtin/generated")
+    pub pack_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetEdge {
+    pub from: String,
+    pub to: String,
+    pub kind: EdgeKind,
+    /// Texture variables ("top, bottom"), blockstate variants ("axis=x, axis=z")
+    /// or multipart conditions leading along this edge
+    pub label: Option<String>,
+}
+
+/// Result of `build_asset_graph`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetGraph {
+    /// Node ID of the root
+    pub root: String,
+    /// Breadth-first from the root
+    pub nodes: Vec<AssetNode>,
+    pub edges: Vec<AssetEdge>,
+    /// True when the depth or node limit left references unexplored
+    pub truncated: bool,
+}
+
+/// References going out of one node
+type References = BTreeMap<(String, EdgeKind), BTreeSet<String>>;
+
+/// Node ID for an asset: a kind-prefixed node ID is kept, a library asset ID is
+/// read as a blockstate ("minecraft:block/stone"), an item ("minecraft:item/diamond")
+/// or otherwise a texture
+pub fn root_node_id(asset_id: &str) -> String {
+    if let Some((kind, location)) = parse_node_id(asset_id) {
+        return node_id(kind, &location);
+    }
+    let (namespace, path) = pack_files::split_asset_id(asset_id);
+    if let Some(block) = path.strip_prefix("block/") {
+        node_id(NodeKind::Blockstate, &format!("{}:{}", namespace, block))
+    } else if let Some(item) = path.strip_prefix("item/") {
+        node_id(NodeKind::Item, &format!("{}:{}", namespace, item))
+    } else {
+        node_id(NodeKind::Texture, &format!("{}:{}", namespace, path))
+    }
+}
+
+/// Follow references from `root_asset` through the pack stack
+///
+/// # Arguments
+/// * `root_asset` - Node ID or library asset ID, see `root_node_id`
+/// * `depth` - Edges to follow from the root; nodes this far away are included
+///   but not expanded
+/// * `packs` - Packs in priority order (highest first), vanilla last
+pub fn build_asset_graph(root_asset: &str, depth: usize, packs: &[PackMeta]) -> Result<AssetGraph> {
+    let root = root_node_id(root_asset);
+    let Some((kind, location)) = parse_node_id(&root) else {
+        bail!("Invalid asset: {}", root_asset);
+    };
+
+    let mut nodes: Vec<AssetNode> = Vec::new();
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut edges: Vec<AssetEdge> = Vec::new();
+    let mut truncated = false;
+    let mut queue: VecDeque<(NodeKind, String, usize)> = VecDeque::new();
+    seen.insert(root.clone());
+    queue.push_back((kind, location, 0));
+
+    while let Some((kind, location, level)) = queue.pop_front() {
+        let (pack_id, references) = expand(kind, &location, packs);
+        let id = node_id(kind, &location);
+        nodes.push(AssetNode {
+            id: id.clone(),
+            kind,
+            location,
+            pack_id,
+        });
+        if references.is_empty() {
+            continue;
+        }
+        if level >= depth {
+            truncated = true;
+            continue;
+        }
+
+        for ((target, edge_kind), labels) in references {
+            if !seen.contains(&target) {
+                if seen.len() >= MAX_NODES {
+                    truncated = true;
+                    continue;
+                }
+                let Some((target_kind, target_location)) = parse_node_id(&target) else {
+                    continue;
+                };
+                seen.insert(target.clone());
+                queue.push_back((target_kind, target_location, level + 1));
+            }
+            edges.push(AssetEdge {
+                from: id.clone(),
+                to: target,
+                kind: edge_kind,
+                label: (!labels.is_empty())
+                    .then(|| labels.into_iter().collect::<Vec<_>>().join(", ")),
+            });
+        }
+    }
+
+    Ok(AssetGraph {
+        root,
+        nodes,
+        edges,
+        truncated,
+    })
+}
+
+fn node_id(kind: NodeKind, location: &str) -> String {
+    format!("{}:{}", kind.prefix(), location)
+}
+
+/// (kind, resource location) of a node ID, None for other strings
+fn parse_node_id(id: &str) -> Option<(NodeKind, String)> {
+    let (prefix, location) = id.split_once(':')?;
+    let kind = NodeKind::ALL
+        .into_iter()
+        .find(|kind| kind.prefix() == prefix)?;
+    // "texture:block/stone" has no namespace of its own
+    let (namespace, path) = pack_files::split_asset_id(location);
+    Some((kind, format!("{}:{}", namespace, path)))
+}
+
+/// Resource location with the default namespace filled in
+fn qualify(location: &str) -> String {
+    let (namespace, path) = pack_files::split_asset_id(location);
+    format!("{}:{}", namespace, path)
+}
+
+/// Pack providing a node's file, and the references in it
+fn expand(kind: NodeKind, location: &str, packs: &[PackMeta]) -> (Option<String>, References) {
+    let (namespace, path) = pack_files::split_asset_id(location);
+    let mut references = References::new();
+    let pack_id = match kind {
+        NodeKind::Texture => first_pack_with(packs, &pack_files::texture_entry_path(location)),
+        NodeKind::Blockstate => {
+            let file = format!("assets/{}/blockstates/{}.json", namespace, path);
+            let found = read_json(packs, &file);
+            if let Some((_, json)) = &found {
+                blockstate_references(json, &mut references);
+            }
+            found.map(|(pack_id, _)| pack_id)
+        }
+        NodeKind::Model => {
+            let file = format!("assets/{}/models/{}.json", namespace, path);
+            let found = read_json(packs, &file);
+            if let Some((_, json)) = &found {
+                model_references(json, &mut references);
+            }
+            found.map(|(pack_id, _)| pack_id)
+        }
+        NodeKind::Item => {
+            let file = format!("assets/{}/items/{}.json", namespace, path);
+            let found = read_json(packs, &file);
+            match &found {
+                Some((_, json)) => item_references(json, &mut references),
+                // Before item definitions, an item is drawn by its item model
+                None => add(
+                    &mut references,
+                    node_id(NodeKind::Model, &format!("{}:item/{}", namespace, path)),
+                    EdgeKind::Model,
+                    None,
+                ),
+            }
+            found.map(|(pack_id, _)| pack_id)
+        }
+        NodeKind::Entity => {
+            let file = format!("assets/{}/optifine/cem/{}.jem", namespace, path);
+            let found = read_json(packs, &file);
+            match &found {
+                Some((_, json)) => entity_references(json, namespace, &mut references),
+                None => {
+                    // Vanilla keeps most mob skins at entity/<name>/<name>
+                    let nested = format!("{}:entity/{}/{}", namespace, path, path);
+                    let nested_file = pack_files::texture_entry_path(&nested);
+                    let texture = if first_pack_with(packs, &nested_file).is_some() {
+                        nested
+                    } else {
+                        format!("{}:entity/{}", namespace, path)
+                    };
+                    add(
+                        &mut references,
+                        node_id(NodeKind::Texture, &texture),
+                        EdgeKind::Texture,
+                        None,
+                    );
+                }
+            }
+            found.map(|(pack_id, _)| pack_id)
+        }
+    };
+    (pack_id, references)
+}
+
+fn add(references: &mut References, target: String, kind: EdgeKind, label: Option<String>) {
+    let labels = references.entry((target, kind)).or_default();
+    labels.extend(label);
+}
+
+fn first_pack_with(packs: &[PackMeta], file: &str) -> Option<String> {
+    packs
+        .iter()
+        .find(|pack| pack_files::pack_entry_exists(pack, file))
+        .map(|pack| pack.id.clone())
+}
+
+/// First pack whose copy of `file` is valid JSON, and the JSON
+fn read_json(packs: &[PackMeta], file: &str) -> Option<(String, serde_json::Value)> {
+    packs.iter().find_map(|pack| {
+        let bytes = pack_files::read_pack_entry(pack, file).ok()?;
+        let json = serde_json::from_slice(&bytes).ok()?;
+        Some((pack.id.clone(), json))
+    })
+}
+
+/// Models of every variant and multipart case
+fn blockstate_references(json: &serde_json::Value, references: &mut References) {
+    let mut add_apply = |apply: &serde_json::Value, label: Option<String>| {
+        let models: Vec<&serde_json::Value> = match apply {
+            serde_json::Value::Array(options) => options.iter().collect(),
+            single => vec![single],
+        };
+        for model in models {
+            if let Some(model) = model.get("model").and_then(|m| m.as_str()) {
+                add(
+                    references,
+                    node_id(NodeKind::Model, &qualify(model)),
+                    EdgeKind::Model,
+                    label.clone(),
+                );
+            }
+        }
+    };
+
+    if let Some(variants) = json.get("variants").and_then(|v| v.as_object()) {
+        for (key, apply) in variants {
+            add_apply(apply, (!key.is_empty()).then(|| key.clone()));
+        }
+    }
+    if let Some(cases) = json.get("multipart").and_then(|m| m.as_array()) {
+        for case in cases {
+            if let Some(apply) = case.get("apply") {
+                add_apply(apply, case.get("when").map(|when| when.to_string()));
+            }
+        }
+    }
+}
+
+/// Parent, textures and (legacy item) override models
+fn model_references(json: &serde_json::Value, references: &mut References) {
+    if let Some(parent) = json.get("parent").and_then(|p| p.as_str()) {
+        add(
+            references,
+            node_id(NodeKind::Model, &qualify(parent)),
+            EdgeKind::Parent,
+            None,
+        );
+    }
+    if let Some(textures) = json.get("textures").and_then(|t| t.as_object()) {
+        for (variable, texture) in textures {
+            // "#side" points at another variable, not a texture
+            let Some(texture) = texture.as_str().filter(|t| !t.starts_with('#')) else {
+                continue;
+            };
+            add(
+                references,
+                node_id(NodeKind::Texture, &qualify(texture)),
+                EdgeKind::Texture,
+                Some(variable.clone()),
+            );
+        }
+    }
+    if let Some(overrides) = json.get("overrides").and_then(|o| o.as_array()) {
+        for model in overrides
+            .iter()
+            .filter_map(|entry| entry.get("model")?.as_str())
+        {
+            add(
+                references,
+                node_id(NodeKind::Model, &qualify(model)),
+                EdgeKind::Override,
+                None,
+            );
+        }
+    }
+}
+
+/// Models anywhere in an item definition: `{"type": "minecraft:model", "model": ...}`
+/// nodes nested in conditions, selects and range dispatches
+fn item_references(json: &serde_json::Value, references: &mut References) {
+    match json {
+        serde_json::Value::Object(object) => {
+            let is_model = object
+                .get("type")
+                .and_then(|t| t.as_str())
+                .is_some_and(|t| qualify(t) == "minecraft:model");
+            if let (true, Some(model)) = (is_model, object.get("model").and_then(|m| m.as_str())) {
+                add(
+                    references,
+                    node_id(NodeKind::Model, &qualify(model)),
+                    EdgeKind::Model,
+                    None,
+                );
+            }
+            for value in object.values() {
+                item_references(value, references);
+            }
+        }
+        serde_json::Value::Array(values) => {
+            for value in values {
+                item_references(value, references);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Textures of a JEM file and its parts
+fn entity_references(json: &serde_json::Value, namespace: &str, references: &mut References) {
+    let parts = json
+        .get("models")
+        .and_then(|m| m.as_array())
+        .into_iter()
+        .flatten();
+    let part_textures = parts.map(|part| {
+        (
+            part.get("texture"),
+            part.get("part").and_then(|p| p.as_str()),
+        )
+    });
+    for (texture, part) in std::iter::once((json.get("texture"), None)).chain(part_textures) {
+        let Some(texture) = texture
+            .and_then(|t| t.as_str())
+            .and_then(|t| jem_texture_location(t, namespace))
+        else {
+            continue;
+        };
+        add(
+            references,
+            node_id(NodeKind::Texture, &texture),
+            EdgeKind::Texture,
+            part.map(|part| part.to_string()),
+        );
+    }
+}
+
+/// Texture location of a JEM "texture" value
+/// "textures/entity/cow/red_cow.png" and "entity/cow/red_cow" -> "minecraft:entity/cow/red_cow";
+/// a bare name relative to the JEM's folder has no texture location
+fn jem_texture_location(texture: &str, namespace: &str) -> Option<String> {
+    let (namespace, path) = match texture.split_once(':') {
+        Some((namespace, path)) => (namespace, path),
+        None => (namespace, texture),
+    };
+    let path = path.strip_suffix(".png").unwrap_or(path);
+    let path = path.strip_prefix("textures/").unwrap_or(path);
+    (path.contains('/') && !path.starts_with("optifine/"))
+        .then(|| format!("{}:{}", namespace, path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_build_asset_graph() {
+        let temp_dir = std::env::temp_dir().join("test_asset_graph");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let assets = temp_dir.join("assets/minecraft");
+        for (file, contents) in [
+            (
+                "blockstates/oak_log.json",
+                r#"{"variants": {"axis=y": {"model": "minecraft:block/oak_log"},
+                    "axis=x": {"model": "block/oak_log_horizontal", "x": 90},
+                    "axis=z": {"model": "minecraft:block/oak_log_horizontal", "x": 90, "y": 90}}}"#,
+            ),
+            (
+                "models/block/oak_log.json",
+                r##"{"parent": "block/cube_column", "textures": {"end": "block/oak_log_top", "side": "block/oak_log", "particle": "#side"}}"##,
+            ),
+            (
+                "models/block/oak_log_horizontal.json",
+                r#"{"parent": "block/cube_column_horizontal", "textures": {"end": "block/oak_log_top", "side": "block/oak_log"}}"#,
+            ),
+            ("textures/block/oak_log.png", ""),
+        ] {
+            let path = assets.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        let packs = vec![PackMeta {
+            id: "pack".to_string(),
+            path: temp_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        }];
+
+        let full = build_asset_graph("minecraft:block/oak_log", 3, &packs).unwrap();
+        let shallow = build_asset_graph("minecraft:block/oak_log", 1, &packs).unwrap();
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(full.root, "blockstate:minecraft:oak_log");
+        assert!(!full.truncated);
+        // Blockstate, 2 models, 2 parents and 2 textures
+        assert_eq!(full.nodes.len(), 7);
+        let horizontal = full
+            .edges
+            .iter()
+            .find(|edge| edge.to == "model:minecraft:block/oak_log_horizontal")
+            .unwrap();
+        assert_eq!(horizontal.label.as_deref(), Some("axis=x, axis=z"));
+        let node = |id: &str| full.nodes.iter().find(|node| node.id == id).unwrap();
+        assert_eq!(
+            node("texture:minecraft:block/oak_log").pack_id.as_deref(),
+            Some("pack")
+        );
+        assert_eq!(node("texture:minecraft:block/oak_log_top").pack_id, None);
+        assert_eq!(node("model:minecraft:block/cube_column").pack_id, None);
+
+        assert!(shallow.truncated);
+        assert_eq!(shallow.nodes.len(), 3);
+        assert_eq!(shallow.edges.len(), 2);
+    }
+
+    #[test]
+    fn test_jem_texture_location() {
+        assert_eq!(
+            jem_texture_location("textures/entity/cow/red_cow.png", "minecraft").as_deref(),
+            Some("minecraft:entity/cow/red_cow")
+        );
+        assert_eq!(
+            jem_texture_location("entity/cow/red_cow", "minecraft").as_deref(),
+            Some("minecraft:entity/cow/red_cow")
+        );
+        assert_eq!(jem_texture_location("red_cow.png", "minecraft"), None);
+    }
+}
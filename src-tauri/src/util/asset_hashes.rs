@@ -0,0 +1,218 @@
+/// Content hashes of pack asset files
+///
+/// Files are hashed with xxh3-64: it only has to tell identical files apart,
+/// and is fast enough to run over every provider of every asset. Used to find
+/// assets several packs ship unchanged (usually art copied from a shared
+/// upstream pack) and to leave files identical to vanilla out of nests.
+use crate::model::{AssetRecord, DuplicateAsset, PackMeta};
+use anyhow::Result;
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use tracing::warn;
+use xxhash_rust::xxh3::Xxh3;
+
+/// Size and xxh3-64 hash of a file's content
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ContentHash {
+    pub size: u64,
+    pub hash: u64,
+}
+
+impl ContentHash {
+    /// Hash everything a reader yields
+    pub fn of_reader(reader: &mut dyn Read) -> Result<Self> {
+        let mut hasher = Xxh3::new();
+        let mut buffer = [0u8; 64 * 1024];
+        let mut size = 0;
+        loop {
+            let read = reader.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+            size += read as u64;
+        }
+        Ok(Self {
+            size,
+            hash: hasher.digest(),
+        })
+    }
+
+    /// The hash as 16 lowercase hex digits
+    pub fn hex(&self) -> String {
+        format!("{:016x}", self.hash)
+    }
+}
+
+/// Hash pack-relative files of a pack, skipping files the pack doesn't have
+///
+/// ZIP packs are opened once for all files.
+pub fn hash_pack_files<'a>(
+    pack: &PackMeta,
+    files: impl IntoIterator<Item = &'a str>,
+) -> Result<HashMap<String, ContentHash>> {
+    let mut hashes = HashMap::new();
+    if pack.is_zip {
+        let mut archive = crate::util::zip::open_cached_archive(&pack.path)?;
+        for file in files {
+            if let Ok(mut entry) = archive.by_name(file) {
+                hashes.insert(file.to_string(), ContentHash::of_reader(&mut entry)?);
+            }
+        }
+    } else {
+        for file in files {
+            let path = Path::new(&pack.path).join(file);
+            if path.is_file() {
+                let hash = ContentHash::of_reader(&mut fs::File::open(&path)?)?;
+                hashes.insert(file.to_string(), hash);
+            }
+        }
+    }
+    Ok(hashes)
+}
+
+/// Files that two or more packs provide with identical content
+///
+/// Only assets with several providers are hashed, each pack's files in one
+/// pass. Results are sorted by file path.
+pub fn find_duplicate_assets(
+    packs: &[PackMeta],
+    assets: &[AssetRecord],
+    providers: &HashMap<String, Vec<String>>,
+) -> Vec<DuplicateAsset> {
+    let pack_map: HashMap<&str, &PackMeta> = packs.iter().map(|p| (p.id.as_str(), p)).collect();
+
+    // Pack ID -> files to hash, and file -> the asset it belongs to
+    let mut wanted: HashMap<&str, HashSet<&str>> = HashMap::new();
+    let mut file_assets: HashMap<&str, &str> = HashMap::new();
+    for asset in assets {
+        let Some(pack_ids) = providers.get(&asset.id) else {
+            continue;
+        };
+        if pack_ids.len() < 2 {
+            continue;
+        }
+        for pack_id in pack_ids {
+            let Some(pack) = pack_map.get(pack_id.as_str()) else {
+                continue;
+            };
+            let files = wanted.entry(pack.id.as_str()).or_default();
+            for file in &asset.files {
+                files.insert(file);
+                file_assets.entry(file).or_insert(&asset.id);
+            }
+        }
+    }
+
+    let hashed: Vec<(&str, HashMap<String, ContentHash>)> = wanted
+        .par_iter()
+        .filter_map(|(pack_id, files)| {
+            match hash_pack_files(pack_map[pack_id], files.iter().copied()) {
+                Ok(hashes) => Some((*pack_id, hashes)),
+                Err(e) => {
+                    warn!("Skipping {}: {}", pack_id, e);
+                    None
+                }
+            }
+        })
+        .collect();
+
+    let mut groups: HashMap<(String, ContentHash), Vec<String>> = HashMap::new();
+    for (pack_id, hashes) in hashed {
+        for (file, hash) in hashes {
+            groups
+                .entry((file, hash))
+                .or_default()
+                .push(pack_id.to_string());
+        }
+    }
+
+    let mut duplicates: Vec<DuplicateAsset> = groups
+        .into_iter()
+        .filter(|(_, pack_ids)| pack_ids.len() > 1)
+        .map(|((file, hash), mut pack_ids)| {
+            pack_ids.sort();
+            DuplicateAsset {
+                asset_id: file_assets[file.as_str()].to_string(),
+                content_hash: hash.hex(),
+                size: hash.size,
+                file,
+                pack_ids,
+            }
+        })
+        .collect();
+    duplicates.sort_by(|a, b| {
+        a.file
+            .cmp(&b.file)
+            .then_with(|| a.content_hash.cmp(&b.content_hash))
+    });
+    duplicates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_duplicate_assets() {
+        let temp_dir = std::env::temp_dir().join("test_find_duplicate_assets");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let stone = "assets/minecraft/textures/block/stone.png";
+        let dirt = "assets/minecraft/textures/block/dirt.png";
+        let write_pack = |name: &str, files: &[(&str, &str)]| {
+            let dir = temp_dir.join(name);
+            for (file, content) in files {
+                let path = dir.join(file);
+                std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                std::fs::write(path, content).unwrap();
+            }
+            PackMeta {
+                id: name.to_string(),
+                name: name.to_string(),
+                path: dir.to_string_lossy().to_string(),
+                ..Default::default()
+            }
+        };
+        let packs = vec![
+            write_pack("a", &[(stone, "same"), (dirt, "dirt a")]),
+            write_pack("b", &[(stone, "same"), (dirt, "dirt b")]),
+            write_pack("c", &[(stone, "other")]),
+        ];
+        let assets = vec![
+            AssetRecord {
+                id: "minecraft:block/stone".to_string(),
+                labels: Vec::new(),
+                files: vec![stone.to_string()],
+            },
+            AssetRecord {
+                id: "minecraft:block/dirt".to_string(),
+                labels: Vec::new(),
+                files: vec![dirt.to_string()],
+            },
+        ];
+        let providers = HashMap::from([
+            (
+                "minecraft:block/stone".to_string(),
+                vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            ),
+            (
+                "minecraft:block/dirt".to_string(),
+                vec!["a".to_string(), "b".to_string()],
+            ),
+        ]);
+
+        let duplicates = find_duplicate_assets(&packs, &assets, &providers);
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].asset_id, "minecraft:block/stone");
+        assert_eq!(duplicates[0].file, stone);
+        assert_eq!(duplicates[0].size, 4);
+        assert_eq!(duplicates[0].pack_ids, vec!["a", "b"]);
+        assert_eq!(duplicates[0].content_hash.len(), 16);
+    }
+}
@@ -1,48 +1,61 @@
 /// Index assets from resource packs (both zip and uncompressed)
-use crate::model::{AssetRecord, PackMeta};
+use crate::model::{AssetRecord, AtlasDefinition, PackMeta};
 use crate::util::zip;
-use anyhow::Result;
+use crate::util::{cancellation, cit, ctm, emissive, jem, pack_files, random_entities};
+use anyhow::{Context, Result};
 use rayon::prelude::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
-use walkdir::WalkDir;
+use tracing::{info, warn};
 
 const ASSET_PATH_PREFIX: &str = "assets/";
 const TEXTURE_PATH: &str = "textures/";
 const BLOCKSTATE_PATH: &str = "blockstates/";
 const CEM_PATH: &str = "assets/minecraft/optifine/cem/";
+const SOUNDS_JSON: &str = "sounds.json";
+const ATLAS_PATH: &str = "atlases/";
+
+/// Non-texture asset folders and the class label given to their records
+const RESOURCE_CLASSES: &[(&str, &str)] = &[
+    ("sounds/", "sound"),
+    ("lang/", "lang"),
+    ("font/", "font"),
+    ("shaders/", "shader"),
+    ("atlases/", "atlas"),
+];
 
 /// Index all assets from a list of packs
 pub fn index_assets(
     packs: &[PackMeta],
 ) -> Result<(Vec<AssetRecord>, HashMap<String, Vec<String>>)> {
-    println!(
-        "[index_assets] Starting PARALLEL asset indexing for {} packs",
-        packs.len()
-    );
+    info!("Starting PARALLEL asset indexing for {} packs", packs.len());
 
     // Parallelize indexing of individual packs
+    let cancel = cancellation::current();
     let pack_results: Vec<_> = packs
         .par_iter()
         .enumerate()
         .map(|(i, pack)| {
-            println!(
-                "[index_assets] Indexing pack {}/{}: {} (is_zip: {})",
+            cancel.check()?;
+            info!(
+                "Indexing pack {}/{}: {} (is_zip: {})",
                 i + 1,
                 packs.len(),
                 pack.name,
                 pack.is_zip
             );
 
-            let pack_assets = index_pack(pack);
+            // A quarantined pack's files are never read
+            let pack_assets = if pack.quarantine.is_empty() {
+                index_pack(pack)
+            } else {
+                warn!("Skipping quarantined pack {}", pack.name);
+                Ok(Default::default())
+            };
             match pack_assets {
-                Ok(assets) => {
-                    println!(
-                        "[index_assets] Found {} assets in pack {}",
-                        assets.len(),
-                        pack.name
-                    );
-                    Ok((pack.id.clone(), assets))
+                Ok((assets, content_labels)) => {
+                    info!("Found {} assets in pack {}", assets.len(), pack.name);
+                    Ok((pack.id.clone(), assets, content_labels))
                 }
                 Err(e) => Err(e),
             }
@@ -53,8 +66,10 @@ pub fn index_assets(
     let mut assets_map: HashMap<String, AssetRecord> = HashMap::new();
     let mut providers: HashMap<String, Vec<String>> = HashMap::new();
 
-    for (pack_id, pack_assets) in pack_results {
+    for (pack_id, pack_assets, mut content_labels) in pack_results {
         for (asset_id, files) in pack_assets {
+            let extra_labels = content_labels.remove(&asset_id).unwrap_or_default();
+
             // Track provider
             providers
                 .entry(asset_id.clone())
@@ -62,7 +77,7 @@ pub fn index_assets(
                 .push(pack_id.clone());
 
             // Merge into assets map
-            assets_map
+            let record = assets_map
                 .entry(asset_id.clone())
                 .and_modify(|record| {
                     for file in &files {
@@ -76,6 +91,11 @@ pub fn index_assets(
                     labels: extract_labels(&asset_id),
                     files,
                 });
+            for label in extra_labels {
+                if !record.labels.contains(&label) {
+                    record.labels.push(label);
+                }
+            }
         }
     }
 
@@ -85,43 +105,62 @@ pub fn index_assets(
     Ok((assets, providers))
 }
 
+/// Asset ID -> strings collected for it within one pack (files or labels)
+type AssetIdMap = HashMap<String, Vec<String>>;
+
 /// Index assets from a pack (zip or folder) using shared file listing logic
-fn index_pack(pack: &PackMeta) -> Result<HashMap<String, Vec<String>>> {
+///
+/// Returns the pack's asset files plus labels read from file contents (CIT and
+/// CTM rules) or implied by file names (emissive and random textures), keyed
+/// by asset ID. CEM models are grouped per entity.
+fn index_pack(pack: &PackMeta) -> Result<(AssetIdMap, AssetIdMap)> {
     let files = list_pack_files(pack)?;
-    println!(
-        "[index_assets] Found {} files in pack {}",
-        files.len(),
-        pack.name
-    );
+    info!("Found {} files in pack {}", files.len(), pack.name);
 
     for (i, file) in files.iter().take(10).enumerate() {
-        println!("[index_assets] Sample file {}: {}", i, file);
+        info!("Sample file {}: {}", i, file);
     }
 
     let mut rejected_count = 0;
     for file in files.iter() {
-        if extract_asset_id(file).is_none() {
+        if extract_asset_id(file)
+            .or_else(|| extract_resource_asset_id(file))
+            .or_else(|| cit::cit_asset_id(file))
+            .is_none()
+            && !ctm::is_ctm_properties(file)
+            && random_entities::parse_random_file(file).is_none()
+            && jem::cem_asset_id(file).is_none()
+        {
             if rejected_count < 5 {
-                println!("[index_assets] REJECTED (not an asset): {}", file);
+                info!("REJECTED (not an asset): {}", file);
             }
             rejected_count += 1;
         }
     }
-    println!(
-        "[index_assets] Total rejected files in {}: {}",
-        pack.name, rejected_count
-    );
+    info!("Total rejected files in {}: {}", pack.name, rejected_count);
 
     let mut assets_map: HashMap<String, Vec<String>> = HashMap::new();
+    let mut content_labels: HashMap<String, Vec<String>> = HashMap::new();
+
+    // Emissive companions ("stone_e.png") belong to their base texture's asset
+    let emissive_suffix = emissive::emissive_suffix(pack, &files);
+    let file_set: HashSet<&str> = files.iter().map(String::as_str).collect();
+    let mut emissive_files = Vec::new();
 
     for (i, file) in files.iter().enumerate() {
         if i % 1000 == 0 {
-            println!(
-                "[index_assets] Processing file {}/{} in {}",
-                i,
-                files.len(),
-                pack.name
-            );
+            info!("Processing file {}/{} in {}", i, files.len(), pack.name);
+        }
+        if let Some(base) = emissive::emissive_base_path(file, &emissive_suffix) {
+            if file_set.contains(base.as_str()) {
+                emissive_files.push((base, file.clone()));
+                continue;
+            }
+            // A glow layer without its base stays an asset of its own, marked
+            // so the nest builder doesn't pair it with another pack's base
+            if let Some(asset_id) = extract_texture_asset_id(file) {
+                content_labels.insert(asset_id, vec![emissive::EMISSIVE_LABEL.to_string()]);
+            }
         }
         if let Some(asset_id) = extract_texture_asset_id(file) {
             assets_map
@@ -131,6 +170,16 @@ fn index_pack(pack: &PackMeta) -> Result<HashMap<String, Vec<String>>> {
         }
     }
 
+    // Added after the base textures so each asset's first file stays the base
+    for (base, file) in emissive_files {
+        if let Some(asset_id) = extract_texture_asset_id(&base) {
+            content_labels
+                .entry(asset_id.clone())
+                .or_insert_with(|| vec![emissive::EMISSIVE_LABEL.to_string()]);
+            assets_map.entry(asset_id).or_default().push(file);
+        }
+    }
+
     for file in files.iter() {
         if let Some(asset_id) = extract_blockstate_asset_id(file) {
             if !assets_map.contains_key(&asset_id) {
@@ -142,11 +191,58 @@ fn index_pack(pack: &PackMeta) -> Result<HashMap<String, Vec<String>>> {
         }
     }
 
-    Ok(assets_map)
+    for file in files.iter() {
+        if let Some(asset_id) = extract_resource_asset_id(file) {
+            assets_map.entry(asset_id).or_default().push(file.clone());
+        }
+    }
+
+    // CIT rules are assets of their own, labelled with the items they match
+    for (file, rule) in cit::read_cit_rules(pack, &files) {
+        if let Some(asset_id) = cit::cit_asset_id(&file) {
+            content_labels.insert(asset_id.clone(), cit::cit_labels(&rule));
+            assets_map.entry(asset_id).or_default().push(file);
+        }
+    }
+
+    // CTM rules are keyed by the blocks (or textures) they apply to; one block
+    // may collect several rule files
+    for (file, rule) in ctm::read_ctm_rules(pack, &files) {
+        let labels = ctm::ctm_labels(&rule);
+        for asset_id in ctm::ctm_asset_ids(&rule) {
+            let block_labels = content_labels.entry(asset_id.clone()).or_default();
+            for label in &labels {
+                if !block_labels.contains(label) {
+                    block_labels.push(label.clone());
+                }
+            }
+            assets_map.entry(asset_id).or_default().push(file.clone());
+        }
+    }
+
+    // Random entity textures are grouped per base texture, variants and rules together
+    for file in files.iter() {
+        if let Some(random_file) = random_entities::parse_random_file(file) {
+            let asset_id = random_file.set_asset_id();
+            content_labels
+                .entry(asset_id.clone())
+                .or_insert_with(|| random_entities::random_labels(&random_file));
+            assets_map.entry(asset_id).or_default().push(file.clone());
+        }
+    }
+
+    // Likewise an entity's CEM models and random-model rules
+    for file in files.iter() {
+        if let Some(asset_id) = jem::cem_asset_id(file) {
+            assets_map.entry(asset_id).or_default().push(file.clone());
+        }
+    }
+
+    Ok((assets_map, content_labels))
 }
 
 /// List all files in a pack (zip or folder) with normalized relative paths
-fn list_pack_files(pack: &PackMeta) -> Result<Vec<String>> {
+pub fn list_pack_files(pack: &PackMeta) -> Result<Vec<String>> {
     if pack.is_zip {
         return zip::list_zip_files(&pack.path);
     }
@@ -154,11 +250,7 @@ fn list_pack_files(pack: &PackMeta) -> Result<Vec<String>> {
     let path = Path::new(&pack.path);
     let mut files = Vec::new();
 
-    for entry in WalkDir::new(path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.path().is_file())
-    {
+    for entry in pack_files::pack_dir_files(path) {
         let rel_path = entry
             .path()
             .strip_prefix(path)
@@ -169,6 +261,51 @@ fn list_pack_files(pack: &PackMeta) -> Result<Vec<String>> {
     Ok(files)
 }
 
+/// List the atlas definitions a pack registers under assets/<namespace>/atlases/
+///
+/// Files that aren't valid JSON are skipped with a warning so one broken atlas
+/// doesn't hide the rest. Results are sorted by atlas ID.
+pub fn list_pack_atlases(pack: &PackMeta) -> Result<Vec<AtlasDefinition>> {
+    let mut atlases = Vec::new();
+
+    for file in list_pack_files(pack)? {
+        let Some((namespace, rest)) = split_asset_path(&file) else {
+            continue;
+        };
+        let Some(name) = rest
+            .strip_prefix(ATLAS_PATH)
+            .and_then(|name| name.strip_suffix(".json"))
+        else {
+            continue;
+        };
+
+        let parsed = pack_files::read_pack_entry(pack, &file).and_then(|bytes| {
+            serde_json::from_slice::<serde_json::Value>(&bytes)
+                .with_context(|| format!("Invalid atlas JSON in {}", file))
+        });
+        let atlas = match parsed {
+            Ok(atlas) => atlas,
+            Err(e) => {
+                warn!("Skipping {}: {}", file, e);
+                continue;
+            }
+        };
+
+        atlases.push(AtlasDefinition {
+            id: format!("{}:{}", namespace, name),
+            sources: atlas
+                .get("sources")
+                .and_then(|sources| sources.as_array())
+                .cloned()
+                .unwrap_or_default(),
+            file,
+        });
+    }
+
+    atlases.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(atlases)
+}
+
 fn split_asset_path(file_path: &str) -> Option<(&str, &str)> {
     if !file_path.starts_with(ASSET_PATH_PREFIX) {
         return None;
@@ -216,11 +353,47 @@ fn extract_blockstate_asset_id(file_path: &str) -> Option<String> {
     Some(format!("{}:block/{}", namespace, block_id))
 }
 
+/// Extract asset ID from a non-texture resource path (sounds, lang, fonts, shaders)
+///
+/// The ID keeps the file extension so it can never collide with a texture ID.
+/// E.g., "assets/minecraft/sounds/ambient/cave/cave1.ogg" -> "minecraft:sounds/ambient/cave/cave1.ogg"
+/// and "assets/minecraft/sounds.json" -> "minecraft:sounds.json"
+fn extract_resource_asset_id(file_path: &str) -> Option<String> {
+    let (namespace, rest) = split_asset_path(file_path)?;
+    let is_resource = rest == SOUNDS_JSON
+        || RESOURCE_CLASSES.iter().any(|(folder, _)| {
+            rest.len() > folder.len() && rest.starts_with(folder) && !rest.ends_with('/')
+        });
+    if !is_resource {
+        return None;
+    }
+
+    Some(format!("{}:{}", namespace, rest))
+}
+
+/// Class label for a non-texture resource asset ID ("sound", "lang", "font", "shader")
+pub fn resource_class(asset_id: &str) -> Option<&'static str> {
+    let path = asset_id.split_once(':').map_or(asset_id, |(_, path)| path);
+    if path == SOUNDS_JSON {
+        return Some("sound");
+    }
+    RESOURCE_CLASSES
+        .iter()
+        .find(|(folder, _)| path.starts_with(folder) && path.contains('.'))
+        .map(|(_, label)| *label)
+}
+
 /// Extract asset ID from a file path (textures or blockstates)
 fn extract_asset_id(file_path: &str) -> Option<String> {
     extract_texture_asset_id(file_path).or_else(|| extract_blockstate_asset_id(file_path))
 }
 
+/// Asset ID a pack file is indexed under (textures, blockstates and other
+/// resources), if any
+pub fn file_asset_id(file_path: &str) -> Option<String> {
+    extract_asset_id(file_path).or_else(|| extract_resource_asset_id(file_path))
+}
+
 /// Extract labels from an asset ID
 /// E.g., "minecraft:block/stone" -> ["minecraft", "block", "stone"]
 fn extract_labels(asset_id: &str) -> Vec<String> {
@@ -244,6 +417,11 @@ fn extract_labels(asset_id: &str) -> Vec<String> {
         }
     }
 
+    // Non-texture resources also get their asset class
+    if let Some(class) = resource_class(asset_id) {
+        labels.push(class.to_string());
+    }
+
     labels
 }
 
@@ -257,10 +435,7 @@ fn extract_labels(asset_id: &str) -> Vec<String> {
 ///
 /// Returns: {"cow": ["21.4", "21.5"]}
 pub fn scan_entity_version_variants(packs: &[PackMeta]) -> Result<HashMap<String, Vec<String>>> {
-    println!(
-        "[scan_entity_version_variants] Scanning {} packs for JEM version variants",
-        packs.len()
-    );
+    info!("Scanning {} packs for JEM version variants", packs.len());
 
     let mut entity_variants: HashMap<String, std::collections::HashSet<String>> = HashMap::new();
 
@@ -295,15 +470,9 @@ pub fn scan_entity_version_variants(packs: &[PackMeta]) -> Result<HashMap<String
         })
         .collect();
 
-    println!(
-        "[scan_entity_version_variants] Found {} entities with version variants",
-        result.len()
-    );
+    info!("Found {} entities with version variants", result.len());
     for (entity, versions) in &result {
-        println!(
-            "[scan_entity_version_variants]   {}: {:?}",
-            entity, versions
-        );
+        info!("{}: {:?}", entity, versions);
     }
 
     Ok(result)
@@ -329,10 +498,8 @@ fn scan_jem_files_in_folder(folder_path: &str) -> Result<Vec<String>> {
 
     let mut jem_files = Vec::new();
 
-    for entry in WalkDir::new(&cem_dir)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.path().is_file() && e.path().extension().map_or(false, |ext| ext == "jem"))
+    for entry in pack_files::pack_dir_files(&cem_dir)
+        .filter(|e| e.path().extension().map_or(false, |ext| ext == "jem"))
     {
         let rel_path = entry
             .path()
@@ -456,6 +623,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_extract_resource_asset_id() {
+        assert_eq!(
+            extract_resource_asset_id("assets/minecraft/sounds/ambient/cave/cave1.ogg"),
+            Some("minecraft:sounds/ambient/cave/cave1.ogg".to_string())
+        );
+        assert_eq!(
+            extract_resource_asset_id("assets/minecraft/sounds.json"),
+            Some("minecraft:sounds.json".to_string())
+        );
+        assert_eq!(
+            extract_resource_asset_id("assets/mymod/lang/en_us.json"),
+            Some("mymod:lang/en_us.json".to_string())
+        );
+        assert_eq!(
+            extract_resource_asset_id("assets/minecraft/font/default.json"),
+            Some("minecraft:font/default.json".to_string())
+        );
+        assert_eq!(
+            extract_resource_asset_id("assets/minecraft/shaders/core/entity.fsh"),
+            Some("minecraft:shaders/core/entity.fsh".to_string())
+        );
+
+        // Textures and models are not resources
+        assert_eq!(
+            extract_resource_asset_id("assets/minecraft/textures/font/ascii.png"),
+            None
+        );
+        assert_eq!(
+            extract_resource_asset_id("assets/minecraft/models/block/stone.json"),
+            None
+        );
+        assert_eq!(extract_resource_asset_id("assets/minecraft/sounds/"), None);
+    }
+
+    #[test]
+    fn test_extract_labels_resource_class() {
+        let labels = extract_labels("minecraft:sounds/ambient/cave/cave1.ogg");
+        assert!(labels.contains(&"sounds".to_string()));
+        assert!(labels.contains(&"sound".to_string()));
+
+        assert!(extract_labels("minecraft:sounds.json").contains(&"sound".to_string()));
+        assert!(extract_labels("minecraft:lang/en_us.json").contains(&"lang".to_string()));
+        // Textures under textures/font/ are not font resources
+        assert_eq!(resource_class("minecraft:font/ascii"), None);
+        assert_eq!(resource_class("minecraft:font/default.json"), Some("font"));
+    }
+
     #[test]
     fn test_extract_labels() {
         let labels = extract_labels("minecraft:block/stone");
@@ -523,8 +738,9 @@ mod tests {
             size: 1000,
             is_zip: false,
             description: None,
-            icon_data: None,
+            has_icon: false,
             pack_format: None,
+            ..Default::default()
         };
 
         let result = index_assets(&[pack]);
@@ -549,6 +765,180 @@ mod tests {
         assert_eq!(providers["minecraft:block/stone"], vec!["test_pack"]);
     }
 
+    #[test]
+    fn test_index_assets_non_texture_resources() {
+        let temp_dir = std::env::temp_dir().join("test_asset_index_resources");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let pack_dir = temp_dir.join("resource_pack");
+        let ns_dir = pack_dir.join("assets/minecraft");
+        for folder in ["sounds/ambient", "lang", "font", "shaders/core"] {
+            std::fs::create_dir_all(ns_dir.join(folder)).unwrap();
+        }
+        std::fs::write(ns_dir.join("sounds.json"), "{}").unwrap();
+        std::fs::write(ns_dir.join("sounds/ambient/cave1.ogg"), "ogg").unwrap();
+        std::fs::write(ns_dir.join("lang/en_us.json"), "{}").unwrap();
+        std::fs::write(ns_dir.join("font/default.json"), "{}").unwrap();
+        std::fs::write(ns_dir.join("shaders/core/entity.fsh"), "void main() {}").unwrap();
+
+        let pack = PackMeta {
+            id: "resource_pack".to_string(),
+            name: "Resource Pack".to_string(),
+            path: pack_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        };
+
+        let result = index_assets(&[pack]);
+
+        std::fs::remove_dir_all(&temp_dir).ok();
+
+        let (assets, providers) = result.unwrap();
+        assert_eq!(assets.len(), 5);
+        let cave = assets
+            .iter()
+            .find(|a| a.id == "minecraft:sounds/ambient/cave1.ogg")
+            .unwrap();
+        assert_eq!(
+            cave.files,
+            vec!["assets/minecraft/sounds/ambient/cave1.ogg"]
+        );
+        assert!(cave.labels.contains(&"sound".to_string()));
+        assert_eq!(providers["minecraft:sounds.json"], vec!["resource_pack"]);
+        assert!(providers.contains_key("minecraft:lang/en_us.json"));
+        assert!(providers.contains_key("minecraft:font/default.json"));
+        assert!(providers.contains_key("minecraft:shaders/core/entity.fsh"));
+    }
+
+    #[test]
+    fn test_index_assets_cit_rules() {
+        let temp_dir = std::env::temp_dir().join("test_asset_index_cit");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let pack_dir = temp_dir.join("cit_pack");
+        let cit_dir = pack_dir.join("assets/minecraft/optifine/cit/swords");
+        std::fs::create_dir_all(&cit_dir).unwrap();
+        std::fs::write(
+            cit_dir.join("fire.properties"),
+            "type=item\nitems=diamond_sword golden_sword\ntexture=fire_sword",
+        )
+        .unwrap();
+        std::fs::write(cit_dir.join("fire_sword.png"), "png").unwrap();
+
+        let pack = PackMeta {
+            id: "cit_pack".to_string(),
+            name: "CIT Pack".to_string(),
+            path: pack_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        };
+
+        let result = index_assets(&[pack]);
+
+        std::fs::remove_dir_all(&temp_dir).ok();
+
+        let (assets, providers) = result.unwrap();
+        assert_eq!(assets.len(), 1);
+        assert_eq!(
+            assets[0].id,
+            "minecraft:optifine/cit/swords/fire.properties"
+        );
+        assert!(assets[0].labels.contains(&"diamond_sword".to_string()));
+        assert!(assets[0].labels.contains(&"golden_sword".to_string()));
+        assert_eq!(
+            assets[0]
+                .labels
+                .iter()
+                .filter(|label| *label == "cit")
+                .count(),
+            1
+        );
+        assert_eq!(
+            providers["minecraft:optifine/cit/swords/fire.properties"],
+            vec!["cit_pack"]
+        );
+    }
+
+    #[test]
+    fn test_index_assets_emissive_companions() {
+        let temp_dir = std::env::temp_dir().join("test_asset_index_emissive");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let pack_dir = temp_dir.join("glow_pack");
+        let block_dir = pack_dir.join("assets/minecraft/textures/block");
+        std::fs::create_dir_all(&block_dir).unwrap();
+        std::fs::create_dir_all(pack_dir.join("assets/minecraft/optifine")).unwrap();
+        std::fs::write(
+            pack_dir.join("assets/minecraft/optifine/emissive.properties"),
+            "suffix.emissive=_glow\n",
+        )
+        .unwrap();
+        for name in ["stone.png", "stone_glow.png", "dirt_glow.png", "ore_e.png"] {
+            std::fs::write(block_dir.join(name), "png").unwrap();
+        }
+
+        let pack = PackMeta {
+            id: "glow_pack".to_string(),
+            name: "Glow Pack".to_string(),
+            path: pack_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        };
+
+        let result = index_assets(&[pack]);
+
+        std::fs::remove_dir_all(&temp_dir).ok();
+
+        let (assets, _) = result.unwrap();
+        let find = |id: &str| assets.iter().find(|asset| asset.id == id).unwrap();
+        let stone = find("minecraft:block/stone");
+        assert_eq!(
+            stone.files,
+            vec![
+                "assets/minecraft/textures/block/stone.png",
+                "assets/minecraft/textures/block/stone_glow.png",
+            ]
+        );
+        assert!(stone.labels.contains(&"emissive".to_string()));
+        assert!(!assets
+            .iter()
+            .any(|asset| asset.id == "minecraft:block/stone_glow"));
+        // A glow layer without its base stays on its own, marked emissive
+        assert!(emissive::is_orphan_layer(find("minecraft:block/dirt_glow")));
+        // "_e" isn't this pack's suffix
+        assert!(!find("minecraft:block/ore_e")
+            .labels
+            .contains(&"emissive".to_string()));
+    }
+
+    #[test]
+    fn test_list_pack_atlases() {
+        let temp_dir = std::env::temp_dir().join("test_asset_index_atlases");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let pack_dir = temp_dir.join("ctm_pack");
+        std::fs::create_dir_all(pack_dir.join("assets/minecraft/atlases")).unwrap();
+        std::fs::create_dir_all(pack_dir.join("assets/ctm/atlases")).unwrap();
+        std::fs::write(
+            pack_dir.join("assets/minecraft/atlases/blocks.json"),
+            r#"{"sources": [{"type": "directory", "source": "ctm", "prefix": "ctm/"}]}"#,
+        )
+        .unwrap();
+        std::fs::write(pack_dir.join("assets/ctm/atlases/broken.json"), "{").unwrap();
+
+        let pack = PackMeta {
+            id: "ctm_pack".to_string(),
+            name: "CTM Pack".to_string(),
+            path: pack_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        };
+
+        let atlases = list_pack_atlases(&pack);
+        let labels = extract_labels("minecraft:atlases/blocks.json");
+
+        std::fs::remove_dir_all(&temp_dir).ok();
+
+        let atlases = atlases.unwrap();
+        assert_eq!(atlases.len(), 1);
+        assert_eq!(atlases[0].id, "minecraft:blocks");
+        assert_eq!(atlases[0].file, "assets/minecraft/atlases/blocks.json");
+        assert_eq!(atlases[0].sources[0]["source"], "ctm");
+        assert!(labels.contains(&"atlas".to_string()));
+    }
+
     #[test]
     fn test_index_assets_multiple_packs_same_asset() {
         // Create two temporary test pack directories with the same asset
@@ -573,8 +963,9 @@ mod tests {
             size: 1000,
             is_zip: false,
             description: None,
-            icon_data: None,
+            has_icon: false,
             pack_format: None,
+            ..Default::default()
         };
 
         let pack2 = PackMeta {
@@ -584,8 +975,9 @@ mod tests {
             size: 1000,
             is_zip: false,
             description: None,
-            icon_data: None,
+            has_icon: false,
             pack_format: None,
+            ..Default::default()
         };
 
         let result = index_assets(&[pack1, pack2]);
@@ -628,8 +1020,9 @@ mod tests {
             size: 1000,
             is_zip: false,
             description: None,
-            icon_data: None,
+            has_icon: false,
             pack_format: None,
+            ..Default::default()
         };
 
         let result = index_assets(&[pack]);
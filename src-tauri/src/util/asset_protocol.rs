@@ -0,0 +1,463 @@
+/// `weaverbird://` URI scheme for streaming binary assets to the webview
+///
+/// Textures, icons and sounds are served as raw bytes instead of base64 strings
+/// inside IPC JSON. Request paths:
+/// - `/asset/vanilla/<entry>` reads from the vanilla cache
+/// - `/asset/pack/<entry>?pack=<pack id>` reads from a pack of the last scan;
+///   files of ZIP packs go through the texture cache
+///
+/// Only packs a scan registered can be read, so a URL can't point the protocol
+/// at other files on disk, and only the app's own pages get a CORS grant.
+///
+/// A PNG asked for with `&format=ktx2` (or `?format=ktx2`) is answered with a
+/// GPU-compressed KTX2 copy when it can be block-compressed, see `ktx2`.
+///
+/// Per platform the same request arrives as `weaverbird://localhost/asset/...`
+/// (macOS, Linux) or `http://weaverbird.localhost/asset/...` (Windows);
+/// `weaverbird://asset/...` is accepted too.
+use crate::model::PackMeta;
+use crate::util::{ktx2, pack_files, vanilla_textures};
+use anyhow::{anyhow, Context, Result};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::UNIX_EPOCH;
+use tauri::http::{header, Request, Response, StatusCode};
+use tracing::info;
+use xxhash_rust::xxh3::Xxh3;
+
+/// URI scheme name registered with the webview
+pub const SCHEME: &str = "weaverbird";
+
+/// File types the protocol will serve; anything else is rejected so a crafted
+/// URL can't read arbitrary files through a pack
+const SERVED_EXTENSIONS: &[&str] = &["png", "mcmeta", "json", "ogg", "jem", "jpm", "properties"];
+
+/// File types of ZIP packs read through the texture cache, the ones its
+/// invalidation knows about
+const CACHED_EXTENSIONS: &[&str] = &["png", "mcmeta"];
+
+/// Origins the app's pages are loaded from (macOS/Linux, Windows over http
+/// and https); requests from anywhere else get no CORS grant
+const APP_ORIGINS: &[&str] = &[
+    "tauri://localhost",
+    "http://tauri.localhost",
+    "https://tauri.localhost",
+];
+
+/// Vite dev server (`build.devUrl`), trusted in debug builds only
+const DEV_ORIGIN: &str = "http://localhost:5173";
+
+/// Packs of the last scan by ID, the only ones requests can read
+static SERVED_PACKS: Lazy<RwLock<HashMap<String, PackMeta>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Where a protocol request reads its bytes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssetSource {
+    /// A file in the vanilla cache (e.g., "assets/minecraft/textures/block/stone.png")
+    Vanilla { entry: String },
+    /// A file inside a pack of the last scan
+    Pack { pack_id: String, entry: String },
+}
+
+/// Make a scan's packs readable through the protocol, replacing the packs of
+/// the previous scan; quarantined packs are left out
+pub fn register_packs(packs: &[PackMeta]) {
+    let served = packs
+        .iter()
+        .filter(|pack| pack.quarantine.is_empty())
+        .map(|pack| (pack.id.clone(), pack.clone()))
+        .collect();
+    if let Ok(mut packs) = SERVED_PACKS.write() {
+        *packs = served;
+    }
+}
+
+/// The registered pack with this ID
+fn served_pack(pack_id: &str) -> Result<PackMeta> {
+    SERVED_PACKS
+        .read()
+        .map_err(|_| anyhow!("Pack registry unavailable"))?
+        .get(pack_id)
+        .cloned()
+        .ok_or_else(|| anyhow!("Unknown pack: {}", pack_id))
+}
+
+/// Decode %XX escapes in a URL component
+fn percent_decode(value: &str) -> Result<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = value
+                .get(i + 1..i + 3)
+                .ok_or_else(|| anyhow!("Truncated escape in {}", value))?;
+            decoded.push(u8::from_str_radix(hex, 16).context("Invalid percent escape")?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).context("Decoded URL is not UTF-8")
+}
+
+/// Check a pack-relative entry path is safe and of a served file type
+fn validate_entry(entry: &str) -> Result<()> {
+    if entry.is_empty() || entry.starts_with('/') || entry.contains('\\') {
+        return Err(anyhow!("Invalid entry path: {}", entry));
+    }
+    if entry
+        .split('/')
+        .any(|part| part.is_empty() || part == "." || part == "..")
+    {
+        return Err(anyhow!("Invalid entry path: {}", entry));
+    }
+
+    let extension = entry
+        .rsplit_once('.')
+        .map(|(_, ext)| ext)
+        .unwrap_or_default();
+    if !SERVED_EXTENSIONS.contains(&extension) {
+        return Err(anyhow!("File type not served: {}", entry));
+    }
+    Ok(())
+}
+
+/// Parse a protocol request into the file it asks for
+///
+/// `path` and `query` are the raw (still percent-encoded) URI parts.
+pub fn parse_asset_request(
+    host: Option<&str>,
+    path: &str,
+    query: Option<&str>,
+) -> Result<AssetSource> {
+    let path = percent_decode(path.trim_start_matches('/'))?;
+    let route = if host == Some("asset") {
+        path.as_str()
+    } else {
+        path.strip_prefix("asset/")
+            .ok_or_else(|| anyhow!("Unknown route: {}", path))?
+    };
+
+    let source = if let Some(entry) = route.strip_prefix("vanilla/") {
+        AssetSource::Vanilla {
+            entry: entry.to_string(),
+        }
+    } else if let Some(entry) = route.strip_prefix("pack/") {
+        let pack_id = query
+            .unwrap_or_default()
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("pack="))
+            .ok_or_else(|| anyhow!("Missing pack parameter"))?;
+        AssetSource::Pack {
+            pack_id: percent_decode(&pack_id.replace('+', " "))?,
+            entry: entry.to_string(),
+        }
+    } else {
+        return Err(anyhow!("Unknown route: {}", route));
+    };
+
+    match &source {
+        AssetSource::Vanilla { entry } | AssetSource::Pack { entry, .. } => validate_entry(entry)?,
+    }
+    Ok(source)
+}
+
+/// Read the bytes for a parsed request
+///
+/// Textures of ZIP packs are read from `texture_cache` (the directory
+/// `get_pack_texture_path` extracts them to) when extracted before, and
+/// extracted there otherwise.
+pub fn read_asset(source: &AssetSource, texture_cache: Option<&Path>) -> Result<Vec<u8>> {
+    match source {
+        AssetSource::Vanilla { entry } => {
+            let path = vanilla_textures::get_vanilla_cache_dir()?.join(entry);
+            std::fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))
+        }
+        AssetSource::Pack { pack_id, entry } => {
+            let pack = served_pack(pack_id)?;
+            let extension = entry.rsplit_once('.').map(|(_, ext)| ext);
+            let cached = texture_cache
+                .filter(|_| pack.is_zip)
+                .filter(|_| extension.is_some_and(|ext| CACHED_EXTENSIONS.contains(&ext)))
+                .map(|dir| pack_files::cached_entry_path(dir, &pack.path, entry));
+            let Some(cached) = cached else {
+                return pack_files::read_pack_entry(&pack, entry);
+            };
+            if let Ok(bytes) = std::fs::read(&cached) {
+                return Ok(bytes);
+            }
+
+            let bytes = pack_files::read_pack_entry(&pack, entry)?;
+            // The cache only saves work next time; serving doesn't depend on it
+            let written = cached
+                .parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|_| std::fs::write(&cached, &bytes));
+            if let Err(e) = written {
+                info!("Not caching {}: {}", cached.display(), e);
+            }
+            Ok(bytes)
+        }
+    }
+}
+
+/// Whether the query asks for a GPU-compressed copy of a texture
+pub fn wants_ktx2(query: Option<&str>) -> bool {
+    query
+        .unwrap_or_default()
+        .split('&')
+        .any(|pair| pair == "format=ktx2")
+}
+
+/// Hash identifying the current version of the file a request reads
+fn source_cache_key(source: &AssetSource) -> Result<u64> {
+    // A ZIP changes as a whole; folders are tracked per file
+    let (file, entry) = match source {
+        AssetSource::Vanilla { entry } => (
+            vanilla_textures::get_vanilla_cache_dir()?.join(entry),
+            entry,
+        ),
+        AssetSource::Pack { pack_id, entry } => {
+            let pack = served_pack(pack_id)?;
+            let file = if pack.is_zip {
+                PathBuf::from(&pack.path)
+            } else {
+                pack_files::resolve_dir_entry(Path::new(&pack.path), entry)?
+            };
+            (file, entry)
+        }
+    };
+    let metadata =
+        std::fs::metadata(&file).with_context(|| format!("Not found: {}", file.display()))?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|since| since.as_nanos())
+        .unwrap_or(0);
+
+    let mut hasher = Xxh3::new();
+    hasher.update(file.to_string_lossy().as_bytes());
+    hasher.update(&[0]);
+    hasher.update(entry.as_bytes());
+    hasher.update(&modified.to_le_bytes());
+    hasher.update(&metadata.len().to_le_bytes());
+    Ok(hasher.digest())
+}
+
+/// KTX2 copy of a requested PNG
+fn read_ktx2(source: &AssetSource, texture_cache: Option<&Path>) -> Result<Vec<u8>> {
+    ktx2::get_or_encode(source_cache_key(source)?, || {
+        read_asset(source, texture_cache)
+    })
+}
+
+/// MIME type for a served entry
+pub fn content_type(entry: &str) -> &'static str {
+    match entry.rsplit_once('.').map(|(_, ext)| ext) {
+        Some("png") => "image/png",
+        Some("ogg") => "audio/ogg",
+        Some("json") | Some("mcmeta") | Some("jem") | Some("jpm") => "application/json",
+        Some("properties") => "text/plain; charset=utf-8",
+        _ => "application/octet-stream",
+    }
+}
+
+/// The request's `Origin` when it is one of the app's own pages
+fn allowed_origin(request: &Request<Vec<u8>>) -> Option<&str> {
+    let origin = request.headers().get(header::ORIGIN)?.to_str().ok()?;
+    let trusted = APP_ORIGINS.contains(&origin) || (cfg!(debug_assertions) && origin == DEV_ORIGIN);
+    trusted.then_some(origin)
+}
+
+/// Response with the CORS headers for `origin`
+fn respond(
+    status: StatusCode,
+    content_type: &str,
+    origin: Option<&str>,
+    body: Vec<u8>,
+) -> Response<Vec<u8>> {
+    let mut response = Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, content_type)
+        .header(header::VARY, "Origin");
+    if let Some(origin) = origin {
+        response = response.header(header::ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+    }
+    response.body(body).unwrap_or_default()
+}
+
+/// Answer a `weaverbird://` request: 200 with the file, 400 for malformed
+/// URLs, 404 when the file can't be read
+///
+/// `texture_cache` is the directory textures of ZIP packs are extracted to.
+pub fn handle_asset_request(
+    request: &Request<Vec<u8>>,
+    texture_cache: Option<&Path>,
+) -> Response<Vec<u8>> {
+    let uri = request.uri();
+    let origin = allowed_origin(request);
+    let error = |status, message: String| {
+        respond(
+            status,
+            "text/plain; charset=utf-8",
+            origin,
+            message.into_bytes(),
+        )
+    };
+    let source = match parse_asset_request(uri.host(), uri.path(), uri.query()) {
+        Ok(source) => source,
+        Err(e) => return error(StatusCode::BAD_REQUEST, e.to_string()),
+    };
+
+    let entry = match &source {
+        AssetSource::Vanilla { entry } | AssetSource::Pack { entry, .. } => entry,
+    };
+
+    if entry.ends_with(".png") && wants_ktx2(uri.query()) {
+        match read_ktx2(&source, texture_cache) {
+            Ok(bytes) => return respond(StatusCode::OK, "image/ktx2", origin, bytes),
+            // Odd sizes and undecodable files still load as PNG
+            Err(e) => info!("Serving {} as PNG: {}", entry, e),
+        }
+    }
+
+    match read_asset(&source, texture_cache) {
+        Ok(bytes) => respond(StatusCode::OK, content_type(entry), origin, bytes),
+        Err(e) => error(StatusCode::NOT_FOUND, e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_asset_request() {
+        assert_eq!(
+            parse_asset_request(
+                Some("localhost"),
+                "/asset/vanilla/assets/minecraft/textures/block/stone.png",
+                None
+            )
+            .unwrap(),
+            AssetSource::Vanilla {
+                entry: "assets/minecraft/textures/block/stone.png".to_string()
+            }
+        );
+        assert_eq!(
+            parse_asset_request(Some("asset"), "/pack/pack.png", Some("pack=My%20Pack.zip"))
+                .unwrap(),
+            AssetSource::Pack {
+                pack_id: "My Pack.zip".to_string(),
+                entry: "pack.png".to_string()
+            }
+        );
+
+        // Traversal, unserved types and missing parameters are rejected
+        assert!(parse_asset_request(None, "/asset/vanilla/../secret.png", None).is_err());
+        assert!(parse_asset_request(None, "/asset/vanilla/assets/x.exe", None).is_err());
+        assert!(parse_asset_request(None, "/asset/pack/pack.png", None).is_err());
+        assert!(parse_asset_request(None, "/other/pack.png", None).is_err());
+
+        assert!(wants_ktx2(Some("pack=A.zip&format=ktx2")));
+        assert!(!wants_ktx2(Some("pack=format=ktx2")));
+        assert!(!wants_ktx2(None));
+    }
+
+    #[test]
+    fn test_handle_asset_request_from_pack() {
+        let temp_dir = std::env::temp_dir().join("test_asset_protocol_pack");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let folder = temp_dir.join("Folder Pack");
+        std::fs::create_dir_all(&folder).unwrap();
+        std::fs::write(folder.join("pack.mcmeta"), "{}").unwrap();
+        std::fs::write(folder.join("pack.png"), b"png bytes").unwrap();
+
+        let zip_path = temp_dir.join("Zipped.zip");
+        let mut writer = ::zip::ZipWriter::new(std::fs::File::create(&zip_path).unwrap());
+        let options = ::zip::write::FileOptions::default();
+        writer.start_file("pack.png", options).unwrap();
+        std::io::Write::write_all(&mut writer, b"zipped png").unwrap();
+        writer.finish().unwrap();
+
+        let pack = |id: &str, path: &Path, is_zip: bool| PackMeta {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: path.to_string_lossy().to_string(),
+            is_zip,
+            ..Default::default()
+        };
+        register_packs(&[
+            pack("Folder Pack", &folder, false),
+            pack("Zipped.zip", &zip_path, true),
+        ]);
+
+        let cache = temp_dir.join("cache");
+        let request = |uri: &str, origin: &str| {
+            Request::builder()
+                .uri(format!("weaverbird://localhost{}", uri))
+                .header(header::ORIGIN, origin)
+                .body(Vec::new())
+                .unwrap()
+        };
+        let get =
+            |uri: &str| handle_asset_request(&request(uri, "tauri://localhost"), Some(&cache));
+
+        let found = get("/asset/pack/pack.png?pack=Folder%20Pack");
+        let missing = get("/asset/pack/assets/missing.png?pack=Folder%20Pack");
+        let invalid = get("/asset/pack/pack.mcmeta.exe?pack=Folder%20Pack");
+        // Only scanned packs are served, never a path from the URL
+        let unknown = get("/asset/pack/pack.png?pack=Other");
+        let by_path = get(&format!(
+            "/asset/pack/pack.png?pack={}",
+            folder.to_string_lossy().replace(' ', "%20")
+        ));
+
+        // ZIP textures are extracted to the texture cache and read back from it
+        let zipped = get("/asset/pack/pack.png?pack=Zipped.zip");
+        let cached = pack_files::cached_entry_path(&cache, &zip_path.to_string_lossy(), "pack.png");
+        let cached_bytes = std::fs::read(&cached).unwrap_or_default();
+        std::fs::write(&cached, b"from cache").unwrap();
+        let from_cache = get("/asset/pack/pack.png?pack=Zipped.zip");
+
+        let foreign = handle_asset_request(
+            &request(
+                "/asset/pack/pack.png?pack=Folder%20Pack",
+                "https://example.com",
+            ),
+            Some(&cache),
+        );
+
+        register_packs(&[]);
+        let _ = std::fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(found.status(), StatusCode::OK);
+        assert_eq!(found.headers()[header::CONTENT_TYPE], "image/png");
+        assert_eq!(found.body(), b"png bytes");
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+        assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(unknown.status(), StatusCode::NOT_FOUND);
+        assert_eq!(by_path.status(), StatusCode::NOT_FOUND);
+
+        assert_eq!(zipped.body(), b"zipped png");
+        assert_eq!(cached_bytes, b"zipped png");
+        assert_eq!(from_cache.body(), b"from cache");
+
+        // CORS is granted to the app's own pages only
+        assert_eq!(
+            found.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "tauri://localhost"
+        );
+        assert_eq!(foreign.status(), StatusCode::OK);
+        assert!(foreign
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .is_none());
+    }
+}
@@ -0,0 +1,299 @@
+/// Ranked fuzzy search over asset IDs and labels
+///
+/// A trigram index is built from each scan so queries don't have to walk every
+/// asset in the webview. Queries are split on whitespace and every term has to
+/// match: exact and prefix matches on the asset's file name rank highest,
+/// followed by label matches, substring matches anywhere in the ID, and
+/// finally typo-tolerant matches found through shared trigrams.
+use crate::model::AssetRecord;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use tracing::info;
+
+/// Share of a term's trigrams an asset must contain to count as a fuzzy match
+const MIN_TRIGRAM_SIMILARITY: f32 = 0.5;
+
+/// Shorter terms have too few trigrams to find abbreviations ("cbst") through
+/// the index, so they are checked against every asset instead
+const MIN_INDEXED_TERM_LEN: usize = 5;
+
+/// Index of the last scan, searched by `search`
+static SEARCH_INDEX: Lazy<RwLock<AssetSearchIndex>> =
+    Lazy::new(|| RwLock::new(AssetSearchIndex::default()));
+
+/// A ranked search result
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetSearchHit {
+    pub asset_id: String,
+    /// Higher is better; only meaningful relative to other hits of the same query
+    pub score: u32,
+}
+
+struct SearchEntry {
+    asset_id: String,
+    /// Lowercased asset ID followed by labels that don't already appear in it
+    haystack: String,
+    /// Lowercased last path segment ("minecraft:block/oak_log" -> "oak_log")
+    name: String,
+    labels: Vec<String>,
+}
+
+/// Trigram index over a set of assets
+#[derive(Default)]
+pub struct AssetSearchIndex {
+    entries: Vec<SearchEntry>,
+    /// Trigram -> indices of entries whose haystack contains it
+    trigrams: HashMap<[u8; 3], Vec<u32>>,
+}
+
+impl AssetSearchIndex {
+    pub fn build(assets: &[AssetRecord]) -> Self {
+        let mut index = Self::default();
+        for asset in assets {
+            let asset_id = asset.id.to_lowercase();
+            let labels: Vec<String> = asset.labels.iter().map(|l| l.to_lowercase()).collect();
+            let mut haystack = asset_id.clone();
+            for label in &labels {
+                if !asset_id.contains(label.as_str()) {
+                    haystack.push(' ');
+                    haystack.push_str(label);
+                }
+            }
+            let name = asset_id
+                .rsplit(['/', ':'])
+                .next()
+                .unwrap_or(&asset_id)
+                .to_string();
+
+            let position = index.entries.len() as u32;
+            let mut seen = trigrams(&haystack);
+            seen.sort_unstable();
+            seen.dedup();
+            for trigram in seen {
+                index.trigrams.entry(trigram).or_default().push(position);
+            }
+            index.entries.push(SearchEntry {
+                asset_id: asset.id.clone(),
+                haystack,
+                name,
+                labels,
+            });
+        }
+        index
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Best `limit` matches for a query, highest score first
+    pub fn search(&self, query: &str, limit: usize) -> Vec<AssetSearchHit> {
+        let query = query.to_lowercase();
+        let terms: Vec<&str> = query.split_whitespace().collect();
+        if terms.is_empty() || limit == 0 {
+            return Vec::new();
+        }
+
+        // Entry index -> (accumulated score, number of terms matched)
+        let mut matches: HashMap<u32, (u32, usize)> = HashMap::new();
+        for (term_index, term) in terms.iter().enumerate() {
+            let mut term_trigrams = trigrams(term);
+            term_trigrams.sort_unstable();
+            term_trigrams.dedup();
+            let shared = self.shared_trigrams(&term_trigrams);
+            let candidates: Vec<u32> = if term_index > 0 {
+                // Later terms only need to check entries every earlier term matched
+                matches
+                    .iter()
+                    .filter(|(_, (_, matched))| *matched == term_index)
+                    .map(|(&entry, _)| entry)
+                    .collect()
+            } else if term.len() < MIN_INDEXED_TERM_LEN {
+                (0..self.entries.len() as u32).collect()
+            } else {
+                shared.keys().copied().collect()
+            };
+
+            for entry_index in candidates {
+                let entry = &self.entries[entry_index as usize];
+                let similarity = if term_trigrams.is_empty() {
+                    0.0
+                } else {
+                    shared.get(&entry_index).copied().unwrap_or(0) as f32
+                        / term_trigrams.len() as f32
+                };
+                if let Some(score) = score_term(entry, term, similarity) {
+                    let (total, matched) = matches.entry(entry_index).or_insert((0, 0));
+                    *total += score;
+                    *matched += 1;
+                }
+            }
+        }
+
+        let mut hits: Vec<(u32, &SearchEntry)> = matches
+            .into_iter()
+            .filter(|(_, (_, matched))| *matched == terms.len())
+            .map(|(entry, (score, _))| (score, &self.entries[entry as usize]))
+            .collect();
+        // Best score first, then shorter (closer) IDs, then alphabetical for stability
+        hits.sort_by(|(a_score, a), (b_score, b)| {
+            b_score
+                .cmp(a_score)
+                .then(a.asset_id.len().cmp(&b.asset_id.len()))
+                .then(a.asset_id.cmp(&b.asset_id))
+        });
+        hits.into_iter()
+            .take(limit)
+            .map(|(score, entry)| AssetSearchHit {
+                asset_id: entry.asset_id.clone(),
+                score,
+            })
+            .collect()
+    }
+
+    /// Entry index -> number of the given (distinct) trigrams its haystack
+    /// contains, for entries sharing enough of them to be a fuzzy match
+    fn shared_trigrams(&self, term_trigrams: &[[u8; 3]]) -> HashMap<u32, u32> {
+        let mut shared: HashMap<u32, u32> = HashMap::new();
+        for trigram in term_trigrams {
+            for &entry in self.trigrams.get(trigram).into_iter().flatten() {
+                *shared.entry(entry).or_insert(0) += 1;
+            }
+        }
+        let needed = term_trigrams.len() as f32 * MIN_TRIGRAM_SIMILARITY;
+        shared.retain(|_, count| *count as f32 >= needed);
+        shared
+    }
+}
+
+/// Trigrams of a lowercased string, over bytes so non-ASCII IDs still index
+fn trigrams(text: &str) -> Vec<[u8; 3]> {
+    text.as_bytes()
+        .windows(3)
+        .map(|window| [window[0], window[1], window[2]])
+        .collect()
+}
+
+/// How well one query term matches an entry, or None if it doesn't
+///
+/// `similarity` is the share of the term's trigrams found in the entry.
+fn score_term(entry: &SearchEntry, term: &str, similarity: f32) -> Option<u32> {
+    if entry.name == term {
+        return Some(1000);
+    }
+    if entry.name.starts_with(term) {
+        return Some(800);
+    }
+    if entry.labels.iter().any(|label| label == term) {
+        return Some(700);
+    }
+    let word_prefix = entry
+        .haystack
+        .split(['/', ':', '_', ' '])
+        .any(|word| word.starts_with(term));
+    if word_prefix {
+        return Some(600);
+    }
+    if entry.haystack.contains(term) {
+        return Some(500);
+    }
+    if is_subsequence(term, &entry.name) {
+        return Some(300);
+    }
+    if similarity >= MIN_TRIGRAM_SIMILARITY {
+        return Some((similarity * 250.0) as u32);
+    }
+    None
+}
+
+/// Whether every character of `needle` appears in `haystack`, in order
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut haystack = haystack.chars();
+    needle.chars().all(|c| haystack.any(|h| h == c))
+}
+
+/// Replace the index searched by `search` with one built from a new scan
+pub fn replace_index(assets: &[AssetRecord]) {
+    let index = AssetSearchIndex::build(assets);
+    info!("Indexed {} assets", index.len());
+    match SEARCH_INDEX.write() {
+        Ok(mut current) => *current = index,
+        Err(poisoned) => *poisoned.into_inner() = index,
+    }
+}
+
+/// Search the assets of the last scan
+pub fn search(query: &str, limit: usize) -> Vec<AssetSearchHit> {
+    match SEARCH_INDEX.read() {
+        Ok(index) => index.search(query, limit),
+        Err(poisoned) => poisoned.into_inner().search(query, limit),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index(ids: &[&str]) -> AssetSearchIndex {
+        let assets: Vec<AssetRecord> = ids
+            .iter()
+            .map(|id| AssetRecord {
+                id: id.to_string(),
+                labels: vec!["minecraft".to_string()],
+                files: Vec::new(),
+            })
+            .collect();
+        AssetSearchIndex::build(&assets)
+    }
+
+    fn ids(hits: &[AssetSearchHit]) -> Vec<&str> {
+        hits.iter().map(|hit| hit.asset_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_search_ranks_names_before_substrings() {
+        let index = index(&[
+            "minecraft:block/dark_oak_planks",
+            "minecraft:block/oak_log",
+            "minecraft:block/oak",
+            "minecraft:block/stone",
+        ]);
+
+        assert_eq!(
+            ids(&index.search("oak", 10)),
+            vec![
+                "minecraft:block/oak",
+                "minecraft:block/oak_log",
+                "minecraft:block/dark_oak_planks",
+            ]
+        );
+        assert_eq!(ids(&index.search("oak", 1)), vec!["minecraft:block/oak"]);
+        assert_eq!(
+            ids(&index.search("Oak LOG", 10)),
+            vec!["minecraft:block/oak_log"]
+        );
+        assert!(index.search("   ", 10).is_empty());
+    }
+
+    #[test]
+    fn test_search_tolerates_typos() {
+        let index = index(&["minecraft:block/cobblestone", "minecraft:block/dirt"]);
+
+        assert_eq!(
+            ids(&index.search("coblestone", 10)),
+            vec!["minecraft:block/cobblestone"]
+        );
+        assert_eq!(
+            ids(&index.search("cbst", 10)),
+            vec!["minecraft:block/cobblestone"]
+        );
+        assert!(index.search("zzzzzz", 10).is_empty());
+    }
+}
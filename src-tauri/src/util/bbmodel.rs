@@ -0,0 +1,720 @@
+/// Import of Blockbench projects (.bbmodel) into packs
+///
+/// Java block and item projects become vanilla model JSON, the way
+/// Blockbench's own export writes them: element rotations collapse to the
+/// single axis the game supports, face UVs are rescaled to the 0..16 model
+/// space and textures become `#0`, `#1`, ... variables. OptiFine entity
+/// projects become a JEM file, each top-level group a part and nested groups
+/// submodels. Textures embedded in the project are written into the pack
+/// next to the output; textures the project only links to are referenced by
+/// their namespace and folder.
+///
+/// Anything the target format can't express (rotated entity cubes, mesh
+/// elements, multi-axis rotations) is reported as a warning rather than
+/// failing the import.
+use crate::util::block_models::DisplayTransform;
+use crate::util::pack_files;
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Element rotation angles the game accepts
+const ALLOWED_ANGLES: [f32; 5] = [-45.0, -22.5, 0.0, 22.5, 45.0];
+
+const AXES: [&str; 3] = ["x", "y", "z"];
+
+/// Face directions, with their JEM per-face UV keys
+const FACES: [(&str, &str); 6] = [
+    ("north", "uvNorth"),
+    ("east", "uvEast"),
+    ("south", "uvSouth"),
+    ("west", "uvWest"),
+    ("up", "uvUp"),
+    ("down", "uvDown"),
+];
+
+/// A Blockbench project, reduced to what the import needs
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BbModel {
+    #[serde(default)]
+    pub meta: BbMeta,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub resolution: BbResolution,
+    #[serde(default)]
+    pub elements: Vec<BbElement>,
+    #[serde(default)]
+    pub outliner: Vec<BbOutlinerNode>,
+    #[serde(default)]
+    pub textures: Vec<BbTexture>,
+    #[serde(default)]
+    pub display: Option<HashMap<String, DisplayTransform>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BbMeta {
+    /// "java_block", "optifine_entity", "free", ...
+    #[serde(default)]
+    pub model_format: String,
+    /// Whether cubes use box UV (one texture offset per cube) by default
+    #[serde(default)]
+    pub box_uv: bool,
+}
+
+/// Texture size the project's UVs are measured in
+#[derive(Debug, Clone, Deserialize)]
+pub struct BbResolution {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Default for BbResolution {
+    fn default() -> Self {
+        Self {
+            width: 16.0,
+            height: 16.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BbElement {
+    #[serde(default)]
+    pub name: String,
+    /// "cube" or "mesh"
+    #[serde(rename = "type", default = "cube_type")]
+    pub kind: String,
+    #[serde(default)]
+    pub uuid: String,
+    #[serde(default)]
+    pub from: [f32; 3],
+    #[serde(default)]
+    pub to: [f32; 3],
+    /// Rotation pivot
+    #[serde(default)]
+    pub origin: [f32; 3],
+    /// Rotation in degrees around [x, y, z]
+    #[serde(default)]
+    pub rotation: [f32; 3],
+    #[serde(default)]
+    pub rescale: bool,
+    #[serde(default)]
+    pub shade: Option<bool>,
+    /// Growth in every direction, kept off the UV size (JEM `sizeAdd`)
+    #[serde(default)]
+    pub inflate: f32,
+    #[serde(default)]
+    pub box_uv: Option<bool>,
+    #[serde(default)]
+    pub uv_offset: Option<[f32; 2]>,
+    #[serde(default)]
+    pub faces: HashMap<String, BbFace>,
+}
+
+fn cube_type() -> String {
+    "cube".to_string()
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BbFace {
+    #[serde(default)]
+    pub uv: [f32; 4],
+    /// Index into the project's textures; null or false for no texture
+    #[serde(default)]
+    pub texture: Value,
+    #[serde(default)]
+    pub rotation: Option<u32>,
+    #[serde(default)]
+    pub cullface: Option<String>,
+    /// Tint index, -1 for none
+    #[serde(default)]
+    pub tint: Option<i32>,
+}
+
+impl BbFace {
+    fn texture_index(&self) -> Option<usize> {
+        self.texture.as_u64().map(|index| index as usize)
+    }
+}
+
+/// An outliner entry: an element by UUID, or a group of them
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum BbOutlinerNode {
+    Element(String),
+    Group(BbGroup),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BbGroup {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub origin: [f32; 3],
+    #[serde(default)]
+    pub rotation: [f32; 3],
+    #[serde(default)]
+    pub children: Vec<BbOutlinerNode>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BbTexture {
+    /// File name, usually with ".png"
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub namespace: String,
+    /// Folder under textures/ (e.g., "block")
+    #[serde(default)]
+    pub folder: String,
+    /// PNG as a data URL, when embedded in the project
+    #[serde(default)]
+    pub source: String,
+    #[serde(default)]
+    pub particle: bool,
+    /// Size the texture's UVs are measured in, when it differs from the project's
+    #[serde(default)]
+    pub uv_width: Option<f32>,
+    #[serde(default)]
+    pub uv_height: Option<f32>,
+}
+
+impl BbTexture {
+    /// File name without extension, made safe for a resource location
+    fn stem(&self, index: usize) -> String {
+        let name = self.name.trim_end_matches(".png");
+        let stem: String = name
+            .to_ascii_lowercase()
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | '0'..='9' | '_' | '-' | '.' => c,
+                _ => '_',
+            })
+            .collect();
+        if stem.is_empty() {
+            format!("texture_{}", index)
+        } else {
+            stem
+        }
+    }
+
+    /// PNG bytes of an embedded texture
+    fn png(&self) -> Option<Vec<u8>> {
+        use base64::{engine::general_purpose, Engine as _};
+
+        let (_, data) = self.source.split_once("base64,")?;
+        general_purpose::STANDARD.decode(data.trim()).ok()
+    }
+}
+
+/// What an import wrote into the pack
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BbmodelImport {
+    pub asset_id: String,
+    /// "model" or "entity"
+    pub kind: String,
+    /// Pack-relative paths written, the model file first
+    pub files: Vec<String>,
+    /// What couldn't be carried over exactly
+    pub warnings: Vec<String>,
+}
+
+/// A converted project: its files by pack-relative path, and warnings
+struct Converted {
+    files: Vec<(String, Vec<u8>)>,
+    warnings: Vec<String>,
+}
+
+/// Read a .bbmodel project
+pub fn read_bbmodel(path: &Path) -> Result<BbModel> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&text).with_context(|| format!("Invalid bbmodel: {}", path.display()))
+}
+
+/// Whether a project is an OptiFine entity model rather than a block or item model
+pub fn is_entity_project(project: &BbModel) -> bool {
+    project.meta.model_format == "optifine_entity"
+}
+
+/// Convert a block or item project to model JSON
+///
+/// `asset_id` names the model ("minecraft:block/lamp" ->
+/// assets/minecraft/models/block/lamp.json); embedded textures go to the
+/// texture's own folder, or the model's ("block" or "item").
+fn convert_model(project: &BbModel, asset_id: &str) -> Result<Converted> {
+    let (namespace, path) = pack_files::split_asset_id(asset_id);
+    if path.is_empty() {
+        bail!("Invalid model ID: {}", asset_id);
+    }
+    let default_folder = match path.split('/').next() {
+        Some("item") => "item",
+        _ => "block",
+    };
+
+    let mut files = Vec::new();
+    let mut warnings = Vec::new();
+    let mut textures = Map::new();
+    for (index, texture) in project.textures.iter().enumerate() {
+        let texture_namespace = if texture.namespace.is_empty() {
+            namespace
+        } else {
+            texture.namespace.as_str()
+        };
+        let folder = if texture.folder.is_empty() {
+            default_folder
+        } else {
+            texture.folder.as_str()
+        };
+        let texture_id = format!("{}:{}/{}", texture_namespace, folder, texture.stem(index));
+        match texture.png() {
+            Some(png) => files.push((pack_files::texture_entry_path(&texture_id), png)),
+            None => warnings.push(format!(
+                "Texture {} isn't embedded in the project; the model refers to {}",
+                texture.name, texture_id
+            )),
+        }
+        if texture.particle {
+            textures.insert("particle".to_string(), json!(texture_id));
+        }
+        textures.insert(index.to_string(), json!(texture_id));
+    }
+    if !textures.contains_key("particle") {
+        if let Some(first) = textures.get("0").cloned() {
+            textures.insert("particle".to_string(), first);
+        }
+    }
+
+    let mut elements = Vec::new();
+    for element in &project.elements {
+        if element.kind != "cube" {
+            warnings.push(format!(
+                "Skipped {} element \"{}\"; block models only have cubes",
+                element.kind, element.name
+            ));
+            continue;
+        }
+        elements.push(model_element(project, element, &mut warnings));
+    }
+
+    let mut model = Map::new();
+    if !textures.is_empty() {
+        model.insert("textures".to_string(), Value::Object(textures));
+    }
+    model.insert("elements".to_string(), Value::Array(elements));
+    if let Some(display) = &project.display {
+        model.insert("display".to_string(), serde_json::to_value(display)?);
+    }
+
+    let model_path = format!("assets/{}/models/{}.json", namespace, path);
+    let json = serde_json::to_vec_pretty(&Value::Object(model))?;
+    files.insert(0, (model_path, json));
+    Ok(Converted { files, warnings })
+}
+
+/// A Blockbench cube as a model element
+fn model_element(project: &BbModel, element: &BbElement, warnings: &mut Vec<String>) -> Value {
+    let mut json = Map::new();
+    json.insert("name".to_string(), json!(element.name));
+    json.insert("from".to_string(), json!(element.from));
+    json.insert("to".to_string(), json!(element.to));
+
+    let rotated: Vec<usize> = (0..3).filter(|&i| element.rotation[i] != 0.0).collect();
+    if let Some(&axis) = rotated.first() {
+        if rotated.len() > 1 {
+            warnings.push(format!(
+                "Element \"{}\" rotates on several axes; only {} was kept",
+                element.name, AXES[axis]
+            ));
+        }
+        let angle = element.rotation[axis];
+        let nearest = ALLOWED_ANGLES
+            .into_iter()
+            .min_by(|a, b| (a - angle).abs().total_cmp(&(b - angle).abs()))
+            .unwrap_or(0.0);
+        if nearest != angle {
+            warnings.push(format!(
+                "Element \"{}\" rotates by {}; rounded to {}",
+                element.name, angle, nearest
+            ));
+        }
+        let mut rotation = json!({
+            "angle": nearest,
+            "axis": AXES[axis],
+            "origin": element.origin,
+        });
+        if element.rescale {
+            rotation["rescale"] = json!(true);
+        }
+        json.insert("rotation".to_string(), rotation);
+    }
+    if element.shade == Some(false) {
+        json.insert("shade".to_string(), json!(false));
+    }
+
+    let mut faces = Map::new();
+    for (direction, _) in FACES {
+        let Some(face) = element.faces.get(direction) else {
+            continue;
+        };
+        let Some(texture_index) = face.texture_index() else {
+            continue;
+        };
+        let texture = project.textures.get(texture_index);
+        let uv_width = texture
+            .and_then(|texture| texture.uv_width)
+            .unwrap_or(project.resolution.width);
+        let uv_height = texture
+            .and_then(|texture| texture.uv_height)
+            .unwrap_or(project.resolution.height);
+        let [u1, v1, u2, v2] = face.uv;
+        let uv = [
+            u1 * 16.0 / uv_width,
+            v1 * 16.0 / uv_height,
+            u2 * 16.0 / uv_width,
+            v2 * 16.0 / uv_height,
+        ];
+
+        let mut face_json = json!({"uv": uv, "texture": format!("#{}", texture_index)});
+        if let Some(rotation) = face.rotation.filter(|&rotation| rotation != 0) {
+            face_json["rotation"] = json!(rotation);
+        }
+        if let Some(cullface) = face.cullface.as_deref().filter(|c| !c.is_empty()) {
+            face_json["cullface"] = json!(cullface);
+        }
+        if let Some(tint) = face.tint.filter(|&tint| tint >= 0) {
+            face_json["tintindex"] = json!(tint);
+        }
+        faces.insert(direction.to_string(), face_json);
+    }
+    json.insert("faces".to_string(), Value::Object(faces));
+    Value::Object(json)
+}
+
+/// Convert an OptiFine entity project to a JEM file
+///
+/// `asset_id` names the entity ("minecraft:cow" ->
+/// assets/minecraft/optifine/cem/cow.jem); the first embedded texture is
+/// written beside it.
+///
+/// JEM space has X mirrored from Blockbench's. A top-level part's boxes are
+/// in entity space and its `translate` is its negated pivot; a submodel's
+/// `translate` is its pivot relative to its parent's and its boxes are
+/// relative to its own pivot.
+fn convert_entity(project: &BbModel, asset_id: &str) -> Result<Converted> {
+    let (namespace, path) = pack_files::split_asset_id(asset_id);
+    let entity = path.rsplit('/').next().unwrap_or(path);
+    if entity.is_empty() {
+        bail!("Invalid entity ID: {}", asset_id);
+    }
+    let folder = format!("assets/{}/optifine/cem", namespace);
+
+    let elements: HashMap<&str, &BbElement> = project
+        .elements
+        .iter()
+        .map(|element| (element.uuid.as_str(), element))
+        .collect();
+    let mut warnings = Vec::new();
+    let mut models = Vec::new();
+    for node in &project.outliner {
+        match node {
+            BbOutlinerNode::Group(group) => {
+                models.push(jem_part(project, group, None, &elements, &mut warnings))
+            }
+            BbOutlinerNode::Element(uuid) => {
+                let name = elements.get(uuid.as_str()).map_or("?", |e| e.name.as_str());
+                warnings.push(format!(
+                    "Skipped cube \"{}\" outside any group; JEM boxes belong to parts",
+                    name
+                ));
+            }
+        }
+    }
+
+    let mut jem = json!({
+        "textureSize": [project.resolution.width, project.resolution.height],
+        "models": models,
+    });
+    let mut files = Vec::new();
+    if let Some(texture) = project.textures.first() {
+        match texture.png() {
+            Some(png) => {
+                let file_name = format!("{}.png", entity);
+                jem["texture"] = json!(file_name);
+                files.push((format!("{}/{}", folder, file_name), png));
+            }
+            None => warnings.push(format!(
+                "Texture {} isn't embedded in the project; the JEM uses the entity's own",
+                texture.name
+            )),
+        }
+        if project.textures.len() > 1 {
+            warnings.push("JEM models use one texture; only the first was kept".to_string());
+        }
+    }
+
+    let jem_path = format!("{}/{}.jem", folder, entity);
+    files.insert(0, (jem_path, serde_json::to_vec_pretty(&jem)?));
+    Ok(Converted { files, warnings })
+}
+
+/// A group as a JEM part (no parent) or submodel
+fn jem_part(
+    project: &BbModel,
+    group: &BbGroup,
+    parent_pivot: Option<[f32; 3]>,
+    elements: &HashMap<&str, &BbElement>,
+    warnings: &mut Vec<String>,
+) -> Value {
+    // Pivot in JEM space
+    let pivot = [-group.origin[0], group.origin[1], group.origin[2]];
+    let (translate, box_origin) = match parent_pivot {
+        None => (pivot.map(|v| -v), [0.0; 3]),
+        Some(parent) => (
+            [
+                pivot[0] - parent[0],
+                pivot[1] - parent[1],
+                pivot[2] - parent[2],
+            ],
+            pivot,
+        ),
+    };
+
+    let mut part = Map::new();
+    if parent_pivot.is_none() {
+        part.insert("part".to_string(), json!(group.name));
+    }
+    part.insert("id".to_string(), json!(group.name));
+    part.insert("invertAxis".to_string(), json!("xy"));
+    part.insert("translate".to_string(), json!(translate));
+    if group.rotation != [0.0; 3] {
+        let [x, y, z] = group.rotation;
+        part.insert("rotate".to_string(), json!([-x, -y, z]));
+    }
+
+    let mut boxes = Vec::new();
+    let mut submodels = Vec::new();
+    for child in &group.children {
+        match child {
+            BbOutlinerNode::Group(child) => {
+                submodels.push(jem_part(project, child, Some(pivot), elements, warnings))
+            }
+            BbOutlinerNode::Element(uuid) => {
+                let Some(element) = elements.get(uuid.as_str()) else {
+                    continue;
+                };
+                if element.kind != "cube" {
+                    warnings.push(format!(
+                        "Skipped {} element \"{}\"",
+                        element.kind, element.name
+                    ));
+                    continue;
+                }
+                if element.rotation != [0.0; 3] {
+                    warnings.push(format!(
+                        "Cube \"{}\" is rotated; JEM only rotates parts, so its rotation was dropped",
+                        element.name
+                    ));
+                }
+                boxes.push(jem_box(project, element, box_origin));
+            }
+        }
+    }
+    if !boxes.is_empty() {
+        part.insert("boxes".to_string(), Value::Array(boxes));
+    }
+    if !submodels.is_empty() {
+        part.insert("submodels".to_string(), Value::Array(submodels));
+    }
+    Value::Object(part)
+}
+
+fn jem_box(project: &BbModel, element: &BbElement, origin: [f32; 3]) -> Value {
+    let size = [
+        element.to[0] - element.from[0],
+        element.to[1] - element.from[1],
+        element.to[2] - element.from[2],
+    ];
+    let mut json = json!({
+        "coordinates": [
+            -element.to[0] - origin[0],
+            element.from[1] - origin[1],
+            element.from[2] - origin[2],
+            size[0],
+            size[1],
+            size[2],
+        ],
+    });
+    if element.box_uv.unwrap_or(project.meta.box_uv) {
+        json["textureOffset"] = json!(element.uv_offset.unwrap_or_default());
+    } else {
+        for (direction, key) in FACES {
+            if let Some(face) = element.faces.get(direction) {
+                if face.texture_index().is_some() {
+                    json[key] = json!(face.uv);
+                }
+            }
+        }
+    }
+    if element.inflate != 0.0 {
+        json["sizeAdd"] = json!(element.inflate);
+    }
+    json
+}
+
+/// Convert a project and write the result into a pack folder
+///
+/// # Arguments
+/// * `project_path` - The .bbmodel file
+/// * `pack_dir` - Folder of the pack to write into
+/// * `asset_id` - Model ID ("minecraft:block/lamp") for block and item
+///   projects, entity ID ("minecraft:cow") for OptiFine entity projects
+pub fn import_bbmodel(
+    project_path: &Path,
+    pack_dir: &Path,
+    asset_id: &str,
+) -> Result<BbmodelImport> {
+    let project = read_bbmodel(project_path)?;
+    let entity = is_entity_project(&project);
+    let converted = if entity {
+        convert_entity(&project, asset_id)?
+    } else {
+        convert_model(&project, asset_id)?
+    };
+
+    let mut files = Vec::new();
+    for (relative_path, bytes) in &converted.files {
+        let target = pack_dir.join(pack_files::safe_entry_path(relative_path)?);
+        let parent = target
+            .parent()
+            .ok_or_else(|| anyhow!("Invalid path: {}", relative_path))?;
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+        fs::write(&target, bytes).with_context(|| format!("Failed to write {}", relative_path))?;
+        files.push(relative_path.clone());
+    }
+
+    Ok(BbmodelImport {
+        asset_id: asset_id.to_string(),
+        kind: if entity { "entity" } else { "model" }.to_string(),
+        files,
+        warnings: converted.warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1x1 transparent PNG
+    const PNG_DATA_URL: &str = "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChwGA60e6kgAAAABJRU5ErkJggg==";
+
+    #[test]
+    fn test_convert_model() {
+        let project: BbModel = serde_json::from_value(json!({
+            "meta": {"model_format": "java_block", "box_uv": false},
+            "resolution": {"width": 32, "height": 32},
+            "elements": [{
+                "name": "post",
+                "type": "cube",
+                "uuid": "a",
+                "from": [6, 0, 6],
+                "to": [10, 16, 10],
+                "origin": [8, 8, 8],
+                "rotation": [0, 30, 0],
+                "faces": {
+                    "north": {"uv": [0, 0, 8, 32], "texture": 0, "tint": 0},
+                    "up": {"uv": [0, 0, 8, 8], "texture": null}
+                }
+            }],
+            "textures": [{"name": "Lamp Post.png", "source": PNG_DATA_URL}]
+        }))
+        .unwrap();
+
+        let converted = convert_model(&project, "mypack:block/lamp").unwrap();
+        let (model_path, model_json) = &converted.files[0];
+        let model: Value = serde_json::from_slice(model_json).unwrap();
+
+        assert_eq!(model_path, "assets/mypack/models/block/lamp.json");
+        assert_eq!(
+            converted.files[1].0,
+            "assets/mypack/textures/block/lamp_post.png"
+        );
+        assert_eq!(model["textures"]["0"], "mypack:block/lamp_post");
+        assert_eq!(model["textures"]["particle"], "mypack:block/lamp_post");
+        let element = &model["elements"][0];
+        assert_eq!(element["rotation"]["axis"], "y");
+        assert_eq!(element["rotation"]["angle"], 22.5);
+        assert_eq!(
+            element["faces"]["north"]["uv"],
+            json!([0.0, 0.0, 4.0, 16.0])
+        );
+        assert_eq!(element["faces"]["north"]["tintindex"], 0);
+        assert!(element["faces"].get("up").is_none());
+        assert_eq!(converted.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_import_entity() {
+        let temp_dir = std::env::temp_dir().join("test_import_bbmodel_entity");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+        let project_path = temp_dir.join("creeper.bbmodel");
+        fs::write(
+            &project_path,
+            json!({
+                "meta": {"model_format": "optifine_entity", "box_uv": true},
+                "resolution": {"width": 64, "height": 32},
+                "elements": [
+                    {"name": "head", "uuid": "h", "from": [-4, 18, -4], "to": [4, 26, 4], "uv_offset": [0, 0]},
+                    {"name": "nose", "uuid": "n", "from": [-1, 20, -6], "to": [1, 22, -4], "uv_offset": [24, 0]}
+                ],
+                "outliner": [{
+                    "name": "head",
+                    "origin": [0, 18, 0],
+                    "children": ["h", {"name": "nose", "origin": [0, 21, -4], "children": ["n"]}]
+                }],
+                "textures": [{"name": "creeper.png", "source": PNG_DATA_URL}]
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let pack_dir = temp_dir.join("pack");
+
+        let imported = import_bbmodel(&project_path, &pack_dir, "creeper").unwrap();
+        let jem: Value = serde_json::from_slice(
+            &fs::read(pack_dir.join("assets/minecraft/optifine/cem/creeper.jem")).unwrap(),
+        )
+        .unwrap();
+        let texture_written = pack_dir
+            .join("assets/minecraft/optifine/cem/creeper.png")
+            .is_file();
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(imported.kind, "entity");
+        assert_eq!(imported.files.len(), 2);
+        assert!(texture_written);
+        assert_eq!(jem["texture"], "creeper.png");
+        assert_eq!(jem["textureSize"], json!([64.0, 32.0]));
+        let head = &jem["models"][0];
+        assert_eq!(head["part"], "head");
+        assert_eq!(head["translate"], json!([0.0, -18.0, 0.0]));
+        assert_eq!(
+            head["boxes"][0]["coordinates"],
+            json!([-4.0, 18.0, -4.0, 8.0, 8.0, 8.0])
+        );
+        let nose = &head["submodels"][0];
+        assert!(nose.get("part").is_none());
+        assert_eq!(nose["translate"], json!([0.0, 3.0, -4.0]));
+        assert_eq!(
+            nose["boxes"][0]["coordinates"],
+            json!([-1.0, -1.0, -2.0, 2.0, 2.0, 2.0])
+        );
+        assert_eq!(nose["boxes"][0]["textureOffset"], json!([24.0, 0.0]));
+    }
+}
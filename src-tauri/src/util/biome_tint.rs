@@ -0,0 +1,488 @@
+/// Biome tints: sampling the grass, foliage and dry foliage colormaps
+///
+/// The game colors grass, leaves and water by multiplying faces that have a
+/// `tintindex` with a color from the biome. Grass and foliage colors come from
+/// sampling a 256x256 colormap at the biome's temperature and downfall (packs
+/// may override the colormaps); a few biomes replace or adjust that color, and
+/// some blocks use a fixed color instead. Biome climates and colors are read
+/// from the game's worldgen biome definitions. State-dependent colors (redstone
+/// wire power, stem age) aren't covered.
+use crate::model::PackMeta;
+use crate::util::{pack_files, vanilla_textures};
+use anyhow::{anyhow, Result};
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+
+/// Which colormap a tint samples
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColormapType {
+    Grass,
+    Foliage,
+    /// Leaf litter, since 1.21.5
+    DryFoliage,
+}
+
+impl ColormapType {
+    /// Pack-relative path of the colormap texture
+    fn entry_path(self) -> &'static str {
+        match self {
+            ColormapType::Grass => "assets/minecraft/textures/colormap/grass.png",
+            ColormapType::Foliage => "assets/minecraft/textures/colormap/foliage.png",
+            ColormapType::DryFoliage => "assets/minecraft/textures/colormap/dry_foliage.png",
+        }
+    }
+
+    /// Color the game uses when a colormap is too small to sample
+    fn fallback(self) -> u32 {
+        match self {
+            ColormapType::Grass => 0xFF00FF,
+            ColormapType::Foliage => 0x48B518,
+            ColormapType::DryFoliage => 0x8C5E37,
+        }
+    }
+}
+
+/// Where to take a tint from: a biome, or a temperature and downfall
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Climate {
+    /// Biome ID (e.g., "minecraft:swamp"); applies the biome's own color rules
+    #[serde(default)]
+    pub biome: Option<String>,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub downfall: Option<f32>,
+}
+
+/// A tint color, with the multiplier applied to tinted faces
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tint {
+    pub rgb: [u8; 3],
+    /// "#RRGGBB"
+    pub hex: String,
+    /// Each channel over 255
+    pub multiplier: [f32; 3],
+}
+
+impl Tint {
+    fn from_color(color: u32) -> Self {
+        let rgb = [(color >> 16) as u8, (color >> 8) as u8, color as u8];
+        Tint {
+            rgb,
+            hex: format!("#{:02X}{:02X}{:02X}", rgb[0], rgb[1], rgb[2]),
+            multiplier: rgb.map(|channel| f32::from(channel) / 255.0),
+        }
+    }
+}
+
+/// What a block's tinted faces are colored by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TintSource {
+    Colormap {
+        colormap: ColormapType,
+    },
+    /// The biome's water color
+    Water,
+    /// The same color in every biome (birch and spruce leaves, lily pads)
+    Fixed {
+        color: u32,
+    },
+}
+
+/// A block's tint in a biome
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockTint {
+    pub block_id: String,
+    pub source: TintSource,
+    /// Multiplies the faces of the block's models that have a tintindex
+    pub tint: Tint,
+}
+
+/// How a biome changes its grass color (`grass_color_modifier`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum GrassModifier {
+    #[default]
+    None,
+    /// Averaged with a dark green
+    DarkForest,
+    /// One of two fixed colors, picked by noise over the world position
+    Swamp,
+}
+
+/// Swamp grass where the noise doesn't darken it, which is most of a swamp
+const SWAMP_GRASS: u32 = 0x6A7039;
+
+const DEFAULT_WATER: u32 = 0x3F76E4;
+
+/// The colors in a biome's `effects`
+#[derive(Debug, Clone, Deserialize)]
+struct BiomeEffects {
+    #[serde(default, deserialize_with = "deserialize_color")]
+    water_color: Option<u32>,
+    #[serde(default, deserialize_with = "deserialize_color")]
+    grass_color: Option<u32>,
+    #[serde(default, deserialize_with = "deserialize_color")]
+    foliage_color: Option<u32>,
+    /// Since 1.21.5
+    #[serde(default, deserialize_with = "deserialize_color")]
+    dry_foliage_color: Option<u32>,
+    #[serde(default)]
+    grass_color_modifier: GrassModifier,
+}
+
+/// A worldgen biome definition, as far as colors go
+#[derive(Debug, Clone, Deserialize)]
+struct Biome {
+    temperature: f32,
+    downfall: f32,
+    effects: BiomeEffects,
+}
+
+/// Colors are integers, or "#RRGGBB" strings in newer versions
+fn deserialize_color<'de, D>(deserializer: D) -> Result<Option<u32>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Color {
+        Int(u32),
+        Hex(String),
+    }
+    match Option::<Color>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(Color::Int(color)) => Ok(Some(color & 0xFFFFFF)),
+        Some(Color::Hex(hex)) => u32::from_str_radix(hex.trim_start_matches('#'), 16)
+            .map(|color| Some(color & 0xFFFFFF))
+            .map_err(serde::de::Error::custom),
+    }
+}
+
+/// Read a biome's definition from the vanilla layer
+///
+/// Biomes are data, not resources, so packs can't change them; they are read
+/// from the worldgen JSON the vanilla cache extracts from the JAR.
+///
+/// # Arguments
+/// * `layers` - Packs highest priority first, ending with vanilla
+/// * `biome_id` - Biome ID (e.g., "minecraft:swamp")
+fn load_biome(layers: &[&PackMeta], biome_id: &str) -> Result<Biome> {
+    let name = biome_id.strip_prefix("minecraft:").unwrap_or(biome_id);
+    let entry = format!("{}{}.json", vanilla_textures::BIOME_DEFINITIONS_DIR, name);
+    let bytes = layers
+        .last()
+        .and_then(|vanilla| pack_files::read_pack_entry(vanilla, &entry).ok())
+        .ok_or_else(|| anyhow!("Unknown biome: {}", biome_id))?;
+    serde_json::from_slice(&bytes).map_err(|e| anyhow!("Invalid biome {}: {}", biome_id, e))
+}
+
+/// What a vanilla block's tinted faces are colored by, or None if it isn't tinted
+pub fn block_tint_source(block_id: &str) -> Option<TintSource> {
+    let name = block_id.strip_prefix("minecraft:").unwrap_or(block_id);
+    let name = name.strip_prefix("block/").unwrap_or(name);
+    let colormap = |colormap| Some(TintSource::Colormap { colormap });
+    match name {
+        "grass_block" | "grass" | "short_grass" | "fern" | "tall_grass" | "large_fern"
+        | "potted_fern" | "sugar_cane" | "bush" => colormap(ColormapType::Grass),
+        "oak_leaves" | "jungle_leaves" | "acacia_leaves" | "dark_oak_leaves"
+        | "mangrove_leaves" | "vine" => colormap(ColormapType::Foliage),
+        "leaf_litter" => colormap(ColormapType::DryFoliage),
+        "water" | "bubble_column" | "water_cauldron" => Some(TintSource::Water),
+        "birch_leaves" => Some(TintSource::Fixed { color: 0x80A755 }),
+        "spruce_leaves" => Some(TintSource::Fixed { color: 0x619961 }),
+        "lily_pad" => Some(TintSource::Fixed { color: 0x208030 }),
+        _ => None,
+    }
+}
+
+/// Read a colormap from the first pack layer that has it
+///
+/// # Arguments
+/// * `layers` - Packs highest priority first, ending with vanilla
+/// * `colormap` - Colormap to read
+pub fn load_colormap(layers: &[&PackMeta], colormap: ColormapType) -> Result<RgbaImage> {
+    let bytes = layers
+        .iter()
+        .find_map(|pack| pack_files::read_pack_entry(pack, colormap.entry_path()).ok())
+        .ok_or_else(|| anyhow!("Colormap not found: {}", colormap.entry_path()))?;
+    Ok(image::load_from_memory(&bytes)?.to_rgba8())
+}
+
+/// Sample a colormap at a temperature and downfall, as the game does
+///
+/// Both are clamped to 0..1 and downfall is scaled by temperature, which is
+/// why the lower right half of a colormap is never used.
+pub fn sample_colormap(
+    colormap: &RgbaImage,
+    colormap_type: ColormapType,
+    temperature: f32,
+    downfall: f32,
+) -> u32 {
+    // The game widens the biome's float climate to double before sampling
+    let temperature = f64::from(temperature).clamp(0.0, 1.0);
+    let downfall = f64::from(downfall).clamp(0.0, 1.0) * temperature;
+    let x = ((1.0 - temperature) * 255.0) as u32;
+    let y = ((1.0 - downfall) * 255.0) as u32;
+    if x >= colormap.width() || y >= colormap.height() {
+        return colormap_type.fallback();
+    }
+    let [r, g, b, _] = colormap.get_pixel(x, y).0;
+    u32::from_be_bytes([0, r, g, b])
+}
+
+/// Color of a colormap in a climate, with the biome's own color rules applied
+///
+/// A biome's color overrides replace the sampled color, then its grass
+/// modifier applies, in the order the game uses.
+fn colormap_color(
+    colormap: &RgbaImage,
+    colormap_type: ColormapType,
+    climate: &Climate,
+    biome: Option<&Biome>,
+) -> Result<u32> {
+    let (temperature, downfall) = match (biome, climate.temperature, climate.downfall) {
+        (_, Some(temperature), Some(downfall)) => (temperature, downfall),
+        (Some(biome), _, _) => (biome.temperature, biome.downfall),
+        _ => return Err(anyhow!("A biome or a temperature and downfall is needed")),
+    };
+    let sample = || sample_colormap(colormap, colormap_type, temperature, downfall);
+
+    let Some(effects) = biome.map(|biome| &biome.effects) else {
+        return Ok(sample());
+    };
+    Ok(match colormap_type {
+        ColormapType::Grass => {
+            let color = effects.grass_color.unwrap_or_else(sample);
+            match effects.grass_color_modifier {
+                GrassModifier::None => color,
+                GrassModifier::DarkForest => ((color & 0xFEFEFE) + 0x28340A) >> 1,
+                GrassModifier::Swamp => SWAMP_GRASS,
+            }
+        }
+        ColormapType::Foliage => effects.foliage_color.unwrap_or_else(sample),
+        ColormapType::DryFoliage => effects.dry_foliage_color.unwrap_or_else(sample),
+    })
+}
+
+/// The climate's biome definition, if it names one
+fn climate_biome(layers: &[&PackMeta], climate: &Climate) -> Result<Option<Biome>> {
+    climate
+        .biome
+        .as_deref()
+        .map(|biome_id| load_biome(layers, biome_id))
+        .transpose()
+}
+
+/// Tint of a colormap in a climate
+///
+/// # Arguments
+/// * `layers` - Packs to take the colormap from, highest priority first
+/// * `colormap` - Colormap to sample
+/// * `climate` - Biome, or temperature and downfall (which win over the biome's)
+pub fn compute_tint(
+    layers: &[&PackMeta],
+    colormap: ColormapType,
+    climate: &Climate,
+) -> Result<Tint> {
+    let biome = climate_biome(layers, climate)?;
+    let image = load_colormap(layers, colormap)?;
+    let color = colormap_color(&image, colormap, climate, biome.as_ref())?;
+    Ok(Tint::from_color(color))
+}
+
+/// Tint of a block's tinted faces in a climate, or None for untinted blocks
+pub fn compute_block_tint(
+    layers: &[&PackMeta],
+    block_id: &str,
+    climate: &Climate,
+) -> Result<Option<BlockTint>> {
+    let Some(source) = block_tint_source(block_id) else {
+        return Ok(None);
+    };
+    let biome = climate_biome(layers, climate)?;
+    let color = match source {
+        TintSource::Colormap { colormap } => {
+            let image = load_colormap(layers, colormap)?;
+            colormap_color(&image, colormap, climate, biome.as_ref())?
+        }
+        TintSource::Water => biome
+            .and_then(|biome| biome.effects.water_color)
+            .unwrap_or(DEFAULT_WATER),
+        TintSource::Fixed { color } => color,
+    };
+    Ok(Some(BlockTint {
+        block_id: block_id.to_string(),
+        source,
+        tint: Tint::from_color(color),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Colormap whose pixel at (x, y) is (x, y, 0)
+    fn coordinate_colormap() -> RgbaImage {
+        RgbaImage::from_fn(256, 256, |x, y| image::Rgba([x as u8, y as u8, 0, 255]))
+    }
+
+    /// Vanilla layer with the biome definitions the tests use, as in the JAR
+    fn vanilla_layer(dir: &std::path::Path) -> PackMeta {
+        let biomes = dir.join(vanilla_textures::BIOME_DEFINITIONS_DIR);
+        std::fs::create_dir_all(&biomes).unwrap();
+        let biome = |temperature: f32, downfall: f32, effects: serde_json::Value| {
+            serde_json::json!({
+                "temperature": temperature,
+                "downfall": downfall,
+                "effects": effects,
+            })
+        };
+        let water = serde_json::json!({"water_color": 4159204});
+        let definitions = [
+            ("plains", biome(0.8, 0.4, water.clone())),
+            ("desert", biome(2.0, 0.0, water.clone())),
+            ("snowy_taiga", biome(-0.5, 0.4, water.clone())),
+            ("forest", biome(0.7, 0.8, water.clone())),
+            (
+                "dark_forest",
+                biome(
+                    0.7,
+                    0.8,
+                    serde_json::json!({"grass_color_modifier": "dark_forest"}),
+                ),
+            ),
+            (
+                "swamp",
+                biome(
+                    0.8,
+                    0.9,
+                    serde_json::json!({"foliage_color": 6975545, "grass_color_modifier": "swamp"}),
+                ),
+            ),
+            (
+                "warm_ocean",
+                biome(0.5, 0.5, serde_json::json!({"water_color": 4445678})),
+            ),
+            // Newer versions write colors as hex strings
+            (
+                "pale_garden",
+                biome(
+                    0.7,
+                    0.8,
+                    serde_json::json!({
+                        "grass_color": "#778272",
+                        "foliage_color": "#878D76",
+                        "dry_foliage_color": "#A0A69C",
+                        "water_color": "#76889D",
+                    }),
+                ),
+            ),
+        ];
+        for (name, definition) in definitions {
+            std::fs::write(
+                biomes.join(format!("{}.json", name)),
+                definition.to_string(),
+            )
+            .unwrap();
+        }
+        PackMeta {
+            id: "vanilla".to_string(),
+            path: dir.to_string_lossy().to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_colormap_color() {
+        let temp_dir = std::env::temp_dir().join("test_biome_colormap_color");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let vanilla = vanilla_layer(&temp_dir);
+        let colormap = coordinate_colormap();
+        let in_biome = |biome: &str, colormap_type| {
+            let climate = Climate {
+                biome: Some(biome.to_string()),
+                ..Default::default()
+            };
+            let biome = climate_biome(&[&vanilla], &climate).unwrap();
+            colormap_color(&colormap, colormap_type, &climate, biome.as_ref()).unwrap()
+        };
+
+        // Float climate values widened to double land where the game's do
+        assert_eq!(in_biome("minecraft:plains", ColormapType::Grass), 0x32AD00);
+        assert_eq!(in_biome("desert", ColormapType::Foliage), 0x00FF00);
+        assert_eq!(in_biome("snowy_taiga", ColormapType::Grass), 0xFFFF00);
+        assert_eq!(in_biome("swamp", ColormapType::Grass), 0x6A7039);
+        assert_eq!(in_biome("swamp", ColormapType::Foliage), 0x6A7039);
+        let forest = in_biome("forest", ColormapType::Grass);
+        assert_eq!(
+            in_biome("dark_forest", ColormapType::Grass),
+            ((forest & 0xFEFEFE) + 0x28340A) >> 1
+        );
+        assert_eq!(in_biome("pale_garden", ColormapType::Grass), 0x778272);
+        assert_eq!(in_biome("pale_garden", ColormapType::DryFoliage), 0xA0A69C);
+        assert_eq!(in_biome("forest", ColormapType::DryFoliage), forest);
+
+        let explicit = Climate {
+            biome: None,
+            temperature: Some(0.8),
+            downfall: Some(0.4),
+        };
+        assert_eq!(
+            colormap_color(&colormap, ColormapType::Grass, &explicit, None).unwrap(),
+            0x32AD00
+        );
+        assert!(colormap_color(&colormap, ColormapType::Grass, &Climate::default(), None).is_err());
+        assert!(load_biome(&[&vanilla], "minecraft:moon").is_err());
+        let _ = std::fs::remove_dir_all(&temp_dir);
+
+        let tiny = RgbaImage::new(16, 16);
+        assert_eq!(
+            sample_colormap(&tiny, ColormapType::Foliage, 0.8, 0.4),
+            0x48B518
+        );
+        assert_eq!(Tint::from_color(0x32AD00).hex, "#32AD00");
+    }
+
+    #[test]
+    fn test_compute_block_tint() {
+        let temp_dir = std::env::temp_dir().join("test_compute_block_tint");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let vanilla = vanilla_layer(&temp_dir.join("vanilla"));
+        let colormaps = temp_dir.join("vanilla/assets/minecraft/textures/colormap");
+        std::fs::create_dir_all(&colormaps).unwrap();
+        coordinate_colormap()
+            .save(colormaps.join("foliage.png"))
+            .unwrap();
+        let in_biome = |biome: &str| Climate {
+            biome: Some(biome.to_string()),
+            ..Default::default()
+        };
+        let plains = in_biome("plains");
+
+        let oak = compute_block_tint(&[&vanilla], "minecraft:oak_leaves", &plains);
+        let grass = compute_block_tint(&[&vanilla], "grass_block", &plains);
+        let water = compute_block_tint(&[&vanilla], "water", &in_biome("warm_ocean"));
+        let pale_water = compute_block_tint(&[&vanilla], "water", &in_biome("pale_garden"));
+        let _ = std::fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(oak.unwrap().unwrap().tint.rgb, [0x32, 0xAD, 0x00]);
+        // No grass colormap in this pack
+        assert!(grass.is_err());
+        let birch = compute_block_tint(&[], "birch_leaves", &Climate::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(birch.tint.hex, "#80A755");
+        let water = water.unwrap().unwrap();
+        assert_eq!(water.source, TintSource::Water);
+        assert_eq!(water.tint.rgb, [0x43, 0xD5, 0xEE]);
+        assert_eq!(pale_water.unwrap().unwrap().tint.hex, "#76889D");
+        let stone = compute_block_tint(&[], "stone", &Climate::default());
+        assert!(stone.unwrap().is_none());
+    }
+}
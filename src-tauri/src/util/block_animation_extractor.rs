@@ -14,6 +14,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use tracing::{info, warn};
 
 /// Animation trigger types
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -174,10 +175,7 @@ pub fn load_cached_animation_data(version: &str) -> Result<Option<ExtractedAnima
     let content = match fs::read_to_string(&cache_file) {
         Ok(content) => content,
         Err(error) => {
-            println!(
-                "[block_animations] Failed to read animation cache for {}: {}",
-                version, error
-            );
+            warn!("Failed to read animation cache for {}: {}", version, error);
             return Ok(None);
         }
     };
@@ -185,27 +183,24 @@ pub fn load_cached_animation_data(version: &str) -> Result<Option<ExtractedAnima
     let data: ExtractedAnimationData = match serde_json::from_str(&content) {
         Ok(data) => data,
         Err(error) => {
-            println!(
-                "[block_animations] Failed to parse animation cache for {}: {}",
-                version, error
-            );
+            warn!("Failed to parse animation cache for {}: {}", version, error);
             return Ok(None);
         }
     };
 
     // Schema version check
-    const CURRENT_SCHEMA_VERSION: u32 = 3;
+    const CURRENT_SCHEMA_VERSION: u32 = 4;
     if data.schema_version < CURRENT_SCHEMA_VERSION {
-        println!(
-            "[block_animations] Cached animation schema {} is older than {}, re-extracting...",
+        info!(
+            "Cached animation schema {} is older than {}, re-extracting...",
             data.schema_version, CURRENT_SCHEMA_VERSION
         );
         return Ok(None);
     }
 
     if data.entities.is_empty() && data.mob_models.is_empty() {
-        println!(
-            "[block_animations] Cached animations for {} has no data, re-extracting...",
+        info!(
+            "Cached animations for {} has no data, re-extracting...",
             version
         );
         return Ok(None);
@@ -220,8 +215,8 @@ fn save_animation_data_to_cache(data: &ExtractedAnimationData) -> Result<()> {
     let content = serde_json::to_string_pretty(data).context("Failed to serialize animation data")?;
     fs::write(&cache_file, content).context("Failed to write animation cache file")?;
 
-    println!(
-        "[block_animations] Cached animation data for version {} ({} blocks, {} mobs)",
+    info!(
+        "Cached animation data for version {} ({} blocks, {} mobs)",
         data.version,
         data.entities.len(),
         data.mob_models.len()
@@ -237,17 +232,15 @@ pub async fn extract_block_animations(
 ) -> Result<ExtractedAnimationData> {
     // Check cache first
     if let Some(cached) = load_cached_animation_data(version)? {
-        println!(
-            "[block_animations] Using cached animation data for {}",
-            version
-        );
+        info!("Using cached animation data for {}", version);
         return Ok(cached);
     }
 
-    println!(
-        "[block_animations] Extracting animations for Minecraft {}...",
-        version
-    );
+    // Another command or app window may be extracting the same version
+    let _lock =
+        super::cache_location::CacheLock::try_acquire(&get_animation_cache_dir()?.join(version))?;
+
+    info!("Extracting animations for Minecraft {}...", version);
 
     // Reuse particle extractor infrastructure for decompilation and mappings
     use super::particle_physics_extractor::{
@@ -266,10 +259,7 @@ pub async fn extract_block_animations(
         let cfr_path = ensure_cfr_available().await?;
         decompile_animation_classes(&cfr_path, jar_path, &decompile_dir, &mappings_path, &class_mappings)?;
     } else {
-        println!(
-            "[block_animations] Using cached decompiled source at {:?}",
-            decompile_dir
-        );
+        info!("Using cached decompiled source at {:?}", decompile_dir);
     }
 
     // Extract animations from decompiled classes
@@ -282,8 +272,16 @@ pub async fn extract_block_animations(
     let mut mob_models = HashMap::new();
     extract_mob_models(&decompile_dir, &class_mappings, &mut mob_models)?;
 
+    // Rebuild vanilla entity geometry from the same model classes
+    let geometry = super::entity_geometry::extract_entity_geometry(
+        &decompile_dir.join("net/minecraft/client/model"),
+    );
+    if let Err(error) = super::entity_geometry::save_entity_geometry(version, &geometry) {
+        warn!("Failed to save entity geometry for {}: {}", version, error);
+    }
+
     let data = ExtractedAnimationData {
-        schema_version: 3, // Bumped for duration_ticks field
+        schema_version: 4, // Bumped so older caches also extract entity geometry
         version: version.to_string(),
         entities,
         mob_models,
@@ -292,8 +290,8 @@ pub async fn extract_block_animations(
     // Save to cache
     save_animation_data_to_cache(&data)?;
 
-    println!(
-        "[block_animations] Successfully extracted animations: {} block entities, {} mob models",
+    info!(
+        "Successfully extracted animations: {} block entities, {} mob models",
         data.entities.len(),
         data.mob_models.len()
     );
@@ -333,12 +331,14 @@ fn decompile_animation_classes(
     mappings_path: &Path,
     class_mappings: &HashMap<String, String>,
 ) -> Result<()> {
-    use std::process::Command;
+    use super::particle_physics_extractor::run_java;
     use std::collections::HashSet;
 
-    println!("[block_animations] Decompiling animation classes...");
+    info!("Decompiling animation classes...");
 
     fs::create_dir_all(output_dir).context("Failed to create decompile directory")?;
+    // The decompile cache may be shared with other machines
+    let _lock = super::cache_location::CacheLock::acquire(output_dir)?;
 
     let mut classes_to_decompile: HashSet<String> = HashSet::new();
 
@@ -377,10 +377,7 @@ fn decompile_animation_classes(
         args.push(obf);
     }
 
-    let output = Command::new("java")
-        .args(&args)
-        .output()
-        .context("Failed to run CFR decompiler")?;
+    let output = run_java(&args)?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -389,7 +386,10 @@ fn decompile_animation_classes(
         }
     }
 
-    println!("[block_animations] ✓ Class decompilation complete ({} classes)", obf_refs.len());
+    info!(
+        "✓ Class decompilation complete ({} classes)",
+        obf_refs.len()
+    );
     Ok(())
 }
 
@@ -402,7 +402,7 @@ fn extract_all_block_animations(
     let block_entity_dir = decompile_dir.join("net/minecraft/world/level/block/entity");
 
     if !block_entity_dir.exists() {
-        println!("[block_animations] BlockEntity directory not found, skipping block animations");
+        warn!("BlockEntity directory not found, skipping block animations");
         return Ok(());
     }
 
@@ -445,17 +445,13 @@ fn extract_all_block_animations(
                         animations,
                     },
                 );
-                println!(
-                    "[block_animations]   ✓ {} ({} animations)",
-                    entity_id,
-                    anim_count
-                );
+                info!("✓ {} ({} animations)", entity_id, anim_count);
             }
         }
     }
 
-    println!(
-        "[block_animations] Scanned {} files, extracted {} block entity animations",
+    info!(
+        "Scanned {} files, extracted {} block entity animations",
         scanned_count,
         entities.len()
     );
@@ -620,7 +616,7 @@ fn extract_bell_animation(
     let bell_path = decompile_dir.join("net/minecraft/world/level/block/entity/BellBlockEntity.java");
 
     if !bell_path.exists() {
-        println!("[block_animations] BellBlockEntity.java not found, skipping bell animation");
+        warn!("BellBlockEntity.java not found, skipping bell animation");
         return Ok(());
     }
 
@@ -679,7 +675,10 @@ fn extract_bell_animation(
         },
     );
 
-    println!("[block_animations] ✓ Extracted bell animation (duration: {} ticks)", duration_ticks);
+    info!(
+        "✓ Extracted bell animation (duration: {} ticks)",
+        duration_ticks
+    );
     Ok(())
 }
 
@@ -693,17 +692,14 @@ fn extract_mob_models(
     let model_dir = decompile_dir.join("net/minecraft/client/model");
 
     if !model_dir.exists() {
-        println!("[block_animations] Model directory not found, skipping mob animations");
+        warn!("Model directory not found, skipping mob animations");
         return Ok(());
     }
 
     // Recursively scan for all *Model.java files
     scan_model_directory(&model_dir, mob_models)?;
 
-    println!(
-        "[block_animations] ✓ Extracted {} mob models",
-        mob_models.len()
-    );
+    info!("✓ Extracted {} mob models", mob_models.len());
 
     Ok(())
 }
@@ -824,11 +820,18 @@ fn extract_single_mob_model_from_path(
         String::new()
     };
 
-    println!(
-        "[block_animations]   ✓ {} ({} expressions{}{}{})",
+    info!(
+        "✓ {} ({} expressions{}{}{})",
         entity_id,
-        animation_layers.iter().map(|l| l.expressions.len()).sum::<usize>(),
-        if let Some(ref t) = trigger { format!(", trigger: {:?}", t) } else { String::new() },
+        animation_layers
+            .iter()
+            .map(|l| l.expressions.len())
+            .sum::<usize>(),
+        if let Some(ref t) = trigger {
+            format!(", trigger: {:?}", t)
+        } else {
+            String::new()
+        },
         hierarchy_info,
         duration_info
     );
@@ -1051,7 +1054,10 @@ fn parse_setup_anim_method(source: &str, entity_id: &str, is_block_entity: bool)
         }
         Err(e) => {
             // AST parsing failed, fall back to regex parsing
-            println!("[block_animations] AST parsing failed for {}: {}, trying regex fallback", entity_id, e);
+            warn!(
+                "AST parsing failed for {}: {}, trying regex fallback",
+                entity_id, e
+            );
         }
     }
 
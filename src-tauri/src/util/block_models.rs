@@ -5,18 +5,25 @@
 /// - Falling back to vanilla models
 /// - Resolving parent model inheritance
 /// - Extracting texture references
-use crate::model::PackMeta;
+use crate::model::{MissingAssetPolicy, PackMeta};
+use crate::util::{model_resolver, pack_files};
 use crate::{AppError, AppResult};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
+/// Model ID handed out for assets replaced under `MissingAssetPolicy::Placeholder`
+pub const PLACEHOLDER_MODEL_ID: &str = "weaverbird:block/placeholder";
+
+/// Texture shown on placeholder models (the game's missing texture)
+pub const PLACEHOLDER_TEXTURE_ID: &str = "minecraft:missingno";
+
 /// A Minecraft block model JSON structure
 ///
 /// Simplified version that captures the key fields we need.
 /// Full spec: https://minecraft.wiki/w/Model#Block_models
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct BlockModel {
     /// Parent model to inherit from (e.g., "minecraft:block/cube_all")
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -33,6 +40,43 @@ pub struct BlockModel {
     /// Ambient occlusion flag
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ambientocclusion: Option<bool>,
+
+    /// How the model is posed in each display context (gui, ground, fixed,
+    /// head, firstperson_righthand, thirdperson_lefthand, ...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display: Option<HashMap<String, DisplayTransform>>,
+}
+
+/// Transform applied to a model in one display context
+///
+/// Applied in the order rotation, translation, scale, as the game does.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DisplayTransform {
+    /// Rotation in degrees around [x, y, z]
+    #[serde(default)]
+    pub rotation: [f32; 3],
+
+    /// Translation [x, y, z] in 1/16ths of a block, clamped to -80..80
+    #[serde(default)]
+    pub translation: [f32; 3],
+
+    /// Scale [x, y, z], clamped to -4..4
+    #[serde(default = "unit_scale")]
+    pub scale: [f32; 3],
+}
+
+fn unit_scale() -> [f32; 3] {
+    [1.0, 1.0, 1.0]
+}
+
+impl Default for DisplayTransform {
+    fn default() -> Self {
+        Self {
+            rotation: [0.0; 3],
+            translation: [0.0; 3],
+            scale: unit_scale(),
+        }
+    }
 }
 
 /// A cuboid element in a Minecraft model
@@ -167,51 +211,87 @@ pub fn read_block_model_with_fallback(
     }
 }
 
-/// Resolve a block model with all parent inheritance
+/// Full cube using the missing texture on every face, standing in for a missing model
+pub fn placeholder_model() -> BlockModel {
+    let faces = ["north", "south", "east", "west", "up", "down"]
+        .iter()
+        .map(|face| {
+            (
+                face.to_string(),
+                ElementFace {
+                    texture: "#missing".to_string(),
+                    uv: Some([0.0, 0.0, 16.0, 16.0]),
+                    rotation: None,
+                    cullface: Some(face.to_string()),
+                    tintindex: None,
+                },
+            )
+        })
+        .collect();
+
+    BlockModel {
+        parent: None,
+        textures: Some(HashMap::from([
+            ("missing".to_string(), PLACEHOLDER_TEXTURE_ID.to_string()),
+            ("particle".to_string(), PLACEHOLDER_TEXTURE_ID.to_string()),
+        ])),
+        elements: Some(vec![ModelElement {
+            from: [0.0, 0.0, 0.0],
+            to: [16.0, 16.0, 16.0],
+            rotation: None,
+            faces,
+            shade: None,
+        }]),
+        ambientocclusion: None,
+        display: None,
+    }
+}
+
+/// Resolve a block model, applying a missing-asset policy to the requested model
 ///
-/// Recursively loads parent models and merges textures/elements
-pub fn resolve_block_model(
+/// The policy only decides where the requested model may come from. Its parents
+/// are templates (cube_all, stairs, ...) and still resolve through vanilla, as
+/// they do in game.
+pub fn resolve_block_model_with_policy(
     pack: &PackMeta,
     model_id: &str,
     vanilla_pack: &PackMeta,
+    policy: MissingAssetPolicy,
 ) -> AppResult<BlockModel> {
-    resolve_block_model_with_depth(pack, model_id, vanilla_pack, 0)
+    if policy != MissingAssetPolicy::UseVanilla {
+        let relative_path = model_id_to_path(&normalize_model_id(model_id));
+        if !pack_files::pack_entry_exists(pack, &relative_path) {
+            if policy == MissingAssetPolicy::Placeholder {
+                return Ok(placeholder_model());
+            }
+            return Err(AppError::validation(format!(
+                "Model {} is not provided by pack {}",
+                model_id, pack.name
+            )));
+        }
+    }
+
+    resolve_block_model(pack, model_id, vanilla_pack)
 }
 
-/// Internal function with depth tracking to prevent infinite recursion
-fn resolve_block_model_with_depth(
+/// Resolve a block model with all parent inheritance
+///
+/// Parents are looked up in the pack, then vanilla, through the memoized
+/// resolver in `model_resolver`.
+pub fn resolve_block_model(
     pack: &PackMeta,
     model_id: &str,
     vanilla_pack: &PackMeta,
-    depth: usize,
 ) -> AppResult<BlockModel> {
-    const MAX_DEPTH: usize = 20;
-
-    if depth > MAX_DEPTH {
-        return Err(AppError::validation(format!(
-            "Model parent chain too deep (possible circular reference): {}",
-            model_id
-        )));
-    }
-
-    let mut model = read_block_model_with_fallback(pack, model_id, vanilla_pack)?;
-
-    // If there's a parent, recursively resolve it
-    if let Some(parent_id) = &model.parent.clone() {
-        let parent_model =
-            resolve_block_model_with_depth(pack, parent_id, vanilla_pack, depth + 1)?;
-
-        // Merge parent into current model
-        model = merge_models(parent_model, model);
-    }
-
+    let mut model = model_resolver::resolve(&[pack, vanilla_pack], model_id)?.model;
+    apply_display_defaults(&mut model);
     Ok(model)
 }
 
 /// Merge a parent model with a child model
 ///
 /// Child properties override parent properties
-fn merge_models(parent: BlockModel, child: BlockModel) -> BlockModel {
+pub(crate) fn merge_models(parent: BlockModel, child: BlockModel) -> BlockModel {
     let mut merged = parent;
 
     // Child textures override/extend parent textures
@@ -233,17 +313,50 @@ fn merge_models(parent: BlockModel, child: BlockModel) -> BlockModel {
         merged.ambientocclusion = child.ambientocclusion;
     }
 
+    // Child display transforms override the parent's context by context
+    if let Some(child_display) = child.display {
+        if let Some(parent_display) = &mut merged.display {
+            parent_display.extend(child_display);
+        } else {
+            merged.display = Some(child_display);
+        }
+    }
+
     // Clear parent reference since we've merged
     merged.parent = None;
 
     merged
 }
 
+/// Fill in a fully resolved model's display transforms as the game does
+///
+/// Left-hand contexts a model doesn't define copy the right hand's, and
+/// translation and scale are clamped to the ranges the game accepts.
+pub(crate) fn apply_display_defaults(model: &mut BlockModel) {
+    let Some(display) = &mut model.display else {
+        return;
+    };
+    for (left, right) in [
+        ("thirdperson_lefthand", "thirdperson_righthand"),
+        ("firstperson_lefthand", "firstperson_righthand"),
+    ] {
+        if let Some(transform) = display.get(right).copied() {
+            display.entry(left.to_string()).or_insert(transform);
+        }
+    }
+    for transform in display.values_mut() {
+        for axis in 0..3 {
+            transform.translation[axis] = transform.translation[axis].clamp(-80.0, 80.0);
+            transform.scale[axis] = transform.scale[axis].clamp(-4.0, 4.0);
+        }
+    }
+}
+
 /// Normalize a model ID to full form
 ///
 /// "block/dirt" -> "minecraft:block/dirt"
 /// "minecraft:block/dirt" -> "minecraft:block/dirt"
-fn normalize_model_id(model_id: &str) -> String {
+pub(crate) fn normalize_model_id(model_id: &str) -> String {
     if model_id.contains(':') {
         model_id.to_string()
     } else {
@@ -254,7 +367,7 @@ fn normalize_model_id(model_id: &str) -> String {
 /// Convert a model ID to a relative file path
 ///
 /// "minecraft:block/dirt" -> "assets/minecraft/models/block/dirt.json"
-fn model_id_to_path(model_id: &str) -> String {
+pub(crate) fn model_id_to_path(model_id: &str) -> String {
     let parts: Vec<&str> = model_id.split(':').collect();
     if parts.len() == 2 {
         format!("assets/{}/models/{}.json", parts[0], parts[1])
@@ -293,6 +406,104 @@ pub fn resolve_textures(model: &BlockModel) -> HashMap<String, String> {
     resolved
 }
 
+/// Problem with a model's textures, each of which shows up as the missing
+/// (purple and black) texture in game
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TextureProblem {
+    /// A texture variable refers to one no model in the chain defines, or the
+    /// references loop
+    UnresolvedVariable { variable: String, reference: String },
+    /// An element face uses a variable no model in the chain defines
+    UndefinedFaceVariable {
+        element: usize,
+        face: String,
+        reference: String,
+    },
+    /// A variable resolves to a texture neither the pack nor vanilla has
+    MissingTexture { variable: String, texture: String },
+}
+
+/// A model's textures after parent merging, with what is broken about them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelDiagnosis {
+    pub model_id: String,
+    /// Models merged, from the requested one up to its root
+    pub chain: Vec<String>,
+    /// Texture variables resolved to resource locations; unresolved ones are
+    /// left out
+    pub textures: HashMap<String, String>,
+    pub problems: Vec<TextureProblem>,
+}
+
+/// Resolve a model's texture variables and report the broken ones
+///
+/// # Arguments
+/// * `pack` - The resource pack to diagnose the model in
+/// * `model_id` - Model ID like "minecraft:block/dirt" or "block/dirt"
+/// * `vanilla_pack` - Vanilla assets, which parents and textures fall back to
+///
+/// # Returns
+/// The resolved texture map and the problems found, variables in name order
+pub fn diagnose_model(
+    pack: &PackMeta,
+    model_id: &str,
+    vanilla_pack: &PackMeta,
+) -> AppResult<ModelDiagnosis> {
+    let resolved = model_resolver::resolve(&[pack, vanilla_pack], model_id)?;
+    let mut variables: Vec<(String, String)> =
+        resolve_textures(&resolved.model).into_iter().collect();
+    variables.sort();
+
+    let mut textures = HashMap::new();
+    let mut problems = Vec::new();
+    for (variable, value) in variables {
+        if value.starts_with('#') {
+            problems.push(TextureProblem::UnresolvedVariable {
+                variable,
+                reference: value,
+            });
+            continue;
+        }
+        let entry_path = pack_files::texture_entry_path(&value);
+        if !pack_files::pack_entry_exists(pack, &entry_path)
+            && !pack_files::pack_entry_exists(vanilla_pack, &entry_path)
+        {
+            problems.push(TextureProblem::MissingTexture {
+                variable: variable.clone(),
+                texture: value.clone(),
+            });
+        }
+        textures.insert(variable, value);
+    }
+
+    let defined = resolved.model.textures.unwrap_or_default();
+    for (index, element) in resolved.model.elements.iter().flatten().enumerate() {
+        let mut faces: Vec<(&String, &ElementFace)> = element.faces.iter().collect();
+        faces.sort_by_key(|(face, _)| face.as_str());
+        for (face, element_face) in faces {
+            let Some(variable) = element_face.texture.strip_prefix('#') else {
+                continue;
+            };
+            if !defined.contains_key(variable) {
+                problems.push(TextureProblem::UndefinedFaceVariable {
+                    element: index,
+                    face: face.clone(),
+                    reference: element_face.texture.clone(),
+                });
+            }
+        }
+    }
+
+    Ok(ModelDiagnosis {
+        model_id: resolved.chain[0].clone(),
+        chain: resolved.chain,
+        textures,
+        problems,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -349,6 +560,7 @@ mod tests {
             ])),
             elements: None,
             ambientocclusion: None,
+            display: None,
         };
 
         let resolved = resolve_textures(&model);
@@ -372,6 +584,7 @@ mod tests {
             ])),
             elements: None,
             ambientocclusion: None,
+            display: None,
         };
 
         let resolved = resolve_textures(&model);
@@ -396,6 +609,7 @@ mod tests {
             ])),
             elements: None,
             ambientocclusion: None,
+            display: None,
         };
 
         let resolved = resolve_textures(&model);
@@ -420,6 +634,7 @@ mod tests {
             textures: None,
             elements: None,
             ambientocclusion: None,
+            display: None,
         };
 
         let resolved = resolve_textures(&model);
@@ -436,6 +651,7 @@ mod tests {
             )])),
             elements: None,
             ambientocclusion: None,
+            display: None,
         };
 
         let resolved = resolve_textures(&model);
@@ -453,6 +669,7 @@ mod tests {
             ])),
             elements: None,
             ambientocclusion: Some(true),
+            display: None,
         };
 
         let child = BlockModel {
@@ -463,6 +680,7 @@ mod tests {
             )])),
             elements: None,
             ambientocclusion: None,
+            display: None,
         };
 
         let merged = merge_models(parent, child);
@@ -504,6 +722,7 @@ mod tests {
             textures: None,
             elements: Some(parent_elements),
             ambientocclusion: None,
+            display: None,
         };
 
         let child = BlockModel {
@@ -511,6 +730,7 @@ mod tests {
             textures: None,
             elements: Some(child_elements.clone()),
             ambientocclusion: None,
+            display: None,
         };
 
         let merged = merge_models(parent, child);
@@ -531,6 +751,7 @@ mod tests {
             textures: None,
             elements: None,
             ambientocclusion: Some(true),
+            display: None,
         };
 
         let child = BlockModel {
@@ -538,6 +759,7 @@ mod tests {
             textures: None,
             elements: None,
             ambientocclusion: Some(false),
+            display: None,
         };
 
         let merged = merge_models(parent, child);
@@ -546,6 +768,38 @@ mod tests {
         assert_eq!(merged.ambientocclusion, Some(false));
     }
 
+    #[test]
+    fn test_merge_models_display() {
+        let parent: BlockModel = serde_json::from_value(serde_json::json!({
+            "display": {
+                "gui": {"rotation": [30, 225, 0], "scale": [0.625, 0.625, 0.625]},
+                "thirdperson_righthand": {"rotation": [75, 45, 0], "translation": [0, 2.5, 0]},
+                "fixed": {"scale": [0.5, 0.5, 0.5]}
+            }
+        }))
+        .unwrap();
+        let child: BlockModel = serde_json::from_value(serde_json::json!({
+            "parent": "block/block",
+            "display": {"gui": {"rotation": [0, 90, 0]}, "ground": {"translation": [0, 200, 0]}}
+        }))
+        .unwrap();
+
+        let mut merged = merge_models(parent, child);
+        apply_display_defaults(&mut merged);
+        let display = merged.display.unwrap();
+
+        // The child's gui replaces the parent's whole transform
+        assert_eq!(display["gui"].rotation, [0.0, 90.0, 0.0]);
+        assert_eq!(display["gui"].scale, [1.0, 1.0, 1.0]);
+        assert_eq!(display["fixed"].scale, [0.5, 0.5, 0.5]);
+        assert_eq!(display["ground"].translation, [0.0, 80.0, 0.0]);
+        assert_eq!(
+            display["thirdperson_lefthand"],
+            display["thirdperson_righthand"]
+        );
+        assert!(!display.contains_key("firstperson_lefthand"));
+    }
+
     #[test]
     fn test_block_model_serialization() {
         let model = BlockModel {
@@ -556,6 +810,7 @@ mod tests {
             )])),
             elements: None,
             ambientocclusion: Some(true),
+            display: None,
         };
 
         let json = serde_json::to_string(&model).expect("should serialize");
@@ -604,4 +859,148 @@ mod tests {
         assert!(deserialized.rotation.is_some());
         assert_eq!(deserialized.rotation.as_ref().unwrap().angle, 45.0);
     }
+
+    #[test]
+    fn test_diagnose_model() {
+        let temp_dir = std::env::temp_dir().join("test_diagnose_model");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let pack_models = temp_dir.join("pack/assets/minecraft/models/block");
+        let vanilla_models = temp_dir.join("vanilla/assets/minecraft/models/block");
+        let vanilla_textures = temp_dir.join("vanilla/assets/minecraft/textures/block");
+        fs::create_dir_all(&pack_models).unwrap();
+        fs::create_dir_all(&vanilla_models).unwrap();
+        fs::create_dir_all(&vanilla_textures).unwrap();
+        fs::write(vanilla_textures.join("stone.png"), b"png").unwrap();
+        fs::write(
+            vanilla_models.join("lamp_base.json"),
+            r##"{
+                "textures": {"particle": "#side"},
+                "elements": [{"from": [0, 0, 0], "to": [16, 16, 16], "faces": {
+                    "up": {"texture": "#top"},
+                    "north": {"texture": "#side"},
+                    "down": {"texture": "#bottom"}
+                }}]
+            }"##,
+        )
+        .unwrap();
+        fs::write(
+            pack_models.join("lamp.json"),
+            r##"{"parent": "block/lamp_base", "textures": {
+                "side": "block/stone",
+                "top": "block/lamp_top",
+                "glow": "#light"
+            }}"##,
+        )
+        .unwrap();
+
+        let make_pack = |id: &str| PackMeta {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: temp_dir.join(id).to_string_lossy().to_string(),
+            ..Default::default()
+        };
+        let diagnosis = diagnose_model(&make_pack("pack"), "block/lamp", &make_pack("vanilla"));
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let diagnosis = diagnosis.unwrap();
+        assert_eq!(diagnosis.model_id, "minecraft:block/lamp");
+        assert_eq!(diagnosis.chain.len(), 2);
+        assert_eq!(diagnosis.textures["particle"], "block/stone");
+        assert!(!diagnosis.textures.contains_key("glow"));
+        assert_eq!(
+            diagnosis.problems,
+            vec![
+                TextureProblem::UnresolvedVariable {
+                    variable: "glow".to_string(),
+                    reference: "#light".to_string()
+                },
+                TextureProblem::MissingTexture {
+                    variable: "top".to_string(),
+                    texture: "block/lamp_top".to_string()
+                },
+                TextureProblem::UndefinedFaceVariable {
+                    element: 0,
+                    face: "down".to_string(),
+                    reference: "#bottom".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_block_model_with_policy() {
+        let temp_dir = std::env::temp_dir().join("test_resolve_model_policy");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let pack_models = temp_dir.join("pack/assets/minecraft/models/block");
+        let vanilla_models = temp_dir.join("vanilla/assets/minecraft/models/block");
+        fs::create_dir_all(&pack_models).unwrap();
+        fs::create_dir_all(&vanilla_models).unwrap();
+        fs::write(
+            pack_models.join("stone.json"),
+            r#"{"parent": "block/cube_all", "textures": {"all": "block/stone"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            vanilla_models.join("cube_all.json"),
+            r##"{"textures": {"particle": "#all"}}"##,
+        )
+        .unwrap();
+        fs::write(
+            vanilla_models.join("dirt.json"),
+            r#"{"textures": {"all": "block/dirt"}}"#,
+        )
+        .unwrap();
+
+        let make_pack = |id: &str| PackMeta {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: temp_dir.join(id).to_string_lossy().to_string(),
+            ..Default::default()
+        };
+        let pack = make_pack("pack");
+        let vanilla = make_pack("vanilla");
+
+        let stone = resolve_block_model_with_policy(
+            &pack,
+            "block/stone",
+            &vanilla,
+            MissingAssetPolicy::PackOnly,
+        );
+        let dirt_vanilla = resolve_block_model_with_policy(
+            &pack,
+            "block/dirt",
+            &vanilla,
+            MissingAssetPolicy::UseVanilla,
+        );
+        let dirt_pack_only = resolve_block_model_with_policy(
+            &pack,
+            "block/dirt",
+            &vanilla,
+            MissingAssetPolicy::PackOnly,
+        );
+        let dirt_placeholder = resolve_block_model_with_policy(
+            &pack,
+            "block/dirt",
+            &vanilla,
+            MissingAssetPolicy::Placeholder,
+        );
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        // Parents still come from vanilla under PackOnly
+        let stone = stone.unwrap();
+        assert!(stone.textures.unwrap().contains_key("particle"));
+        assert!(dirt_vanilla.is_ok());
+        assert!(dirt_pack_only
+            .unwrap_err()
+            .message
+            .contains("not provided by pack"));
+        let placeholder = dirt_placeholder.unwrap();
+        assert_eq!(
+            placeholder.textures.unwrap().get("missing"),
+            Some(&PLACEHOLDER_TEXTURE_ID.to_string())
+        );
+        assert_eq!(placeholder.elements.unwrap()[0].faces.len(), 6);
+    }
 }
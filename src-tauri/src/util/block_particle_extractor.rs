@@ -12,10 +12,11 @@ use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use tracing::{info, warn};
 
 use super::particle_physics_extractor::{
     clear_shared_decompile_dir, download_mojang_mappings, ensure_cfr_available,
-    get_shared_decompile_dir,
+    get_shared_decompile_dir, run_java,
 };
 
 /// Extracted particle options (for ParticleOptions-based emissions like dust)
@@ -152,20 +153,14 @@ pub fn load_cached_block_emissions(version: &str) -> Result<Option<ExtractedBloc
     let content = match fs::read_to_string(&cache_file) {
         Ok(content) => content,
         Err(error) => {
-            println!(
-                "[block_emissions] Failed to read emissions cache for {}: {}",
-                version, error
-            );
+            warn!("Failed to read emissions cache for {}: {}", version, error);
             return Ok(None);
         }
     };
     let data: ExtractedBlockEmissions = match serde_json::from_str(&content) {
         Ok(data) => data,
         Err(error) => {
-            println!(
-                "[block_emissions] Failed to parse emissions cache for {}: {}",
-                version, error
-            );
+            warn!("Failed to parse emissions cache for {}: {}", version, error);
             return Ok(None);
         }
     };
@@ -173,16 +168,16 @@ pub fn load_cached_block_emissions(version: &str) -> Result<Option<ExtractedBloc
     // If the cache is from an older schema, force re-extraction to populate new fields.
     const CURRENT_SCHEMA_VERSION: u32 = 8;
     if data.schema_version < CURRENT_SCHEMA_VERSION {
-        println!(
-            "[block_emissions] Cached emissions schema {} is older than {}, re-extracting...",
+        info!(
+            "Cached emissions schema {} is older than {}, re-extracting...",
             data.schema_version, CURRENT_SCHEMA_VERSION
         );
         return Ok(None);
     }
 
     if data.blocks.is_empty() {
-        println!(
-            "[block_emissions] Cached emissions for {} has no block data, re-extracting...",
+        info!(
+            "Cached emissions for {} has no block data, re-extracting...",
             version
         );
         return Ok(None);
@@ -208,17 +203,13 @@ pub fn clear_block_emissions_data_cache(version: &str) -> Result<()> {
 /// Save block emissions to cache
 fn save_emissions_to_cache(data: &ExtractedBlockEmissions) -> Result<()> {
     let cache_file = get_emissions_cache_file(&data.version)?;
-    println!(
-        "[block_emissions] Saving {} blocks to {:?}",
-        data.blocks.len(),
-        cache_file
-    );
+    info!("Saving {} blocks to {:?}", data.blocks.len(), cache_file);
 
     let content = serde_json::to_string_pretty(data).context("Failed to serialize emissions")?;
     fs::write(&cache_file, &content).context("Failed to write emissions cache file")?;
 
-    println!(
-        "[block_emissions] ✓ Cached emissions for version {} ({} blocks, {} bytes)",
+    info!(
+        "✓ Cached emissions for version {} ({} blocks, {} bytes)",
         data.version,
         data.blocks.len(),
         content.len()
@@ -787,7 +778,10 @@ fn get_particle_emitting_entity_classes(decompile_dir: &Path) -> Result<HashMap<
 
     scan_dir(&entity_dir, &entity_dir, &mut entity_classes)?;
 
-    println!("[entity_emissions] Found {} entity classes with addParticle calls", entity_classes.len());
+    info!(
+        "Found {} entity classes with addParticle calls",
+        entity_classes.len()
+    );
 
     Ok(entity_classes)
 }
@@ -841,7 +835,7 @@ fn load_dust_particle_option_constants(
     } else if let Some(obf_path) = dust_obf_path.as_ref().filter(|path| path.exists()) {
         obf_path.clone()
     } else {
-        println!("[dust_options] DustParticleOptions class not found in decompile output");
+        info!("DustParticleOptions class not found in decompile output");
         return out;
     };
 
@@ -901,7 +895,12 @@ fn load_dust_particle_option_constants(
         // Deobfuscate the field name if we have a mapping
         let deobf_name = field_mappings.get(field_name).unwrap_or(&field_name.to_string()).clone();
 
-        println!("[dust_options] Found constant: {} = 0x{:06X} @ scale {}", deobf_name, color_int, scale.unwrap_or(1.0));
+        info!(
+            "Found constant: {} = 0x{:06X} @ scale {}",
+            deobf_name,
+            color_int,
+            scale.unwrap_or(1.0)
+        );
 
         out.insert(
             deobf_name,
@@ -1526,16 +1525,14 @@ fn batch_decompile_classes(
     output_dir: &Path,
     mappings_path: &Path,
 ) -> Result<()> {
-    use std::process::Command;
-
     if obfuscated_names.is_empty() {
         return Ok(());
     }
 
-    println!(
-        "[block_emissions] Batch decompiling {} classes...",
-        obfuscated_names.len()
-    );
+    // The decompile cache may be shared with other machines
+    let _lock = super::cache_location::CacheLock::acquire(output_dir)?;
+
+    info!("Batch decompiling {} classes...", obfuscated_names.len());
 
     // Build CFR command with all class names
     let mut args = vec![
@@ -1553,10 +1550,7 @@ fn batch_decompile_classes(
         args.push(name.to_string());
     }
 
-    let output = Command::new("java")
-        .args(&args)
-        .output()
-        .context("Failed to run CFR decompiler")?;
+    let output = run_java(&args)?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -1566,7 +1560,7 @@ fn batch_decompile_classes(
         }
     }
 
-    println!("[block_emissions] Batch decompilation complete");
+    info!("Batch decompilation complete");
     Ok(())
 }
 
@@ -1640,8 +1634,8 @@ fn build_field_value_map(
         }
     }
 
-    println!(
-        "[field_tracking] {} ({}): {:?}",
+    info!(
+        "{} ({}): {:?}",
         block_id,
         class_name.rsplit('.').next().unwrap_or(class_name),
         field_values
@@ -1711,7 +1705,7 @@ fn parse_block_registrations(
         && !blocks_obf_path.as_ref().map(|path| path.exists()).unwrap_or(false)
     {
         if let Some(obf) = blocks_obf {
-            println!("[block_emissions] Decompiling Blocks class...");
+            info!("Decompiling Blocks class...");
             batch_decompile_classes(cfr_path, jar_path, &[obf], decompile_dir, mappings_path)?;
         }
     }
@@ -1826,8 +1820,8 @@ fn parse_block_registrations(
             constructor_params.insert(block_id.to_string(), params);
         }
 
-        println!(
-            "[block_emissions] Parsed {} block registrations, {} with constructor params",
+        info!(
+            "Parsed {} block registrations, {} with constructor params",
             block_id_to_class.len(),
             constructor_params.len()
         );
@@ -1842,18 +1836,19 @@ pub async fn extract_block_emissions(
 ) -> Result<ExtractedBlockEmissions> {
     // Check cache first
     if let Some(cached) = load_cached_block_emissions(version)? {
-        println!(
-            "[block_emissions] Using cached emissions for {} ({} blocks)",
+        info!(
+            "Using cached emissions for {} ({} blocks)",
             version,
             cached.blocks.len()
         );
         return Ok(cached);
     }
 
-    println!(
-        "[block_emissions] Extracting block emissions for {}...",
-        version
-    );
+    // Another command or app window may be extracting the same version
+    let _lock =
+        super::cache_location::CacheLock::try_acquire(&get_emissions_cache_dir()?.join(version))?;
+
+    info!("Extracting block emissions for {}...", version);
 
     // Download mappings
     let mappings_path = download_mojang_mappings(version).await?;
@@ -1868,7 +1863,7 @@ pub async fn extract_block_emissions(
 
     // Step 1: Decompile entire block and block entity packages for automatic discovery
     // This is more comprehensive than hardcoding specific classes
-    println!("[block_emissions] Decompiling block packages for automatic discovery...");
+    info!("Decompiling block packages for automatic discovery...");
 
     let packages_to_decompile = vec![
         "net.minecraft.world.level.block",
@@ -1905,7 +1900,7 @@ pub async fn extract_block_emissions(
 
     // Batch decompile all classes at once (much faster than one-by-one)
     if !classes_to_decompile.is_empty() {
-        println!("[block_emissions] Decompiling {} classes...", classes_to_decompile.len());
+        info!("Decompiling {} classes...", classes_to_decompile.len());
         let obf_refs: Vec<&str> = classes_to_decompile.iter().map(|s| s.as_str()).collect();
 
         batch_decompile_classes(
@@ -1918,7 +1913,7 @@ pub async fn extract_block_emissions(
     }
 
     // Step 2: Scan decompiled packages for particle-emitting classes
-    println!("[block_emissions] Scanning for particle-emitting classes...");
+    info!("Scanning for particle-emitting classes...");
     let block_emitters = scan_for_particle_emitting_classes(
         &decompile_dir,
         "net.minecraft.world.level.block",
@@ -1928,8 +1923,8 @@ pub async fn extract_block_emissions(
         "net.minecraft.world.level.block.entity",
     )?;
 
-    println!(
-        "[block_emissions] Found {} block classes with particles, {} block entities with particles",
+    info!(
+        "Found {} block classes with particles, {} block entities with particles",
         block_emitters.len(),
         entity_emitters.len()
     );
@@ -1996,8 +1991,8 @@ pub async fn extract_block_emissions(
                         );
 
                         if !emissions.is_empty() {
-                            println!(
-                                "[block_emissions] {} ({}) -> {:?}",
+                            info!(
+                                "{} ({}) -> {:?}",
                                 block_id,
                                 class_name,
                                 emissions.iter().map(|e| &e.particle_id).collect::<Vec<_>>()
@@ -2013,7 +2008,7 @@ pub async fn extract_block_emissions(
                     }
                 }
                 Err(e) => {
-                    println!("[block_emissions] Failed to read {}: {}", class_name, e);
+                    warn!("Failed to read {}: {}", class_name, e);
                 }
             }
         }
@@ -2060,8 +2055,8 @@ pub async fn extract_block_emissions(
             );
 
             if !emissions.is_empty() {
-                println!(
-                    "[block_emissions] Inherited {} emissions for {} from {}",
+                info!(
+                    "Inherited {} emissions for {} from {}",
                     emissions.len(),
                     block_id,
                     parent
@@ -2082,8 +2077,8 @@ pub async fn extract_block_emissions(
     }
 
     if inherited_blocks > 0 {
-        println!(
-            "[block_emissions] Inherited emissions for {} blocks from parent classes",
+        info!(
+            "Inherited emissions for {} blocks from parent classes",
             inherited_blocks
         );
     }
@@ -2146,8 +2141,8 @@ pub async fn extract_block_emissions(
     }
 
     if candle_overrides > 0 {
-        println!(
-            "[block_emissions] Applied candle offset overrides for {} blocks",
+        info!(
+            "Applied candle offset overrides for {} blocks",
             candle_overrides
         );
     }
@@ -2179,8 +2174,8 @@ pub async fn extract_block_emissions(
                         if block_class.ends_with(&format!(".{}Block", simple_name))
                             || block_class.ends_with(&format!(".{}", simple_name))
                         {
-                            println!(
-                                "[block_entity_emissions] {} ({}) -> {:?}",
+                            info!(
+                                "{} ({}) -> {:?}",
                                 block_id,
                                 class_name,
                                 emissions.iter().map(|e| &e.particle_id).collect::<Vec<_>>()
@@ -2202,7 +2197,7 @@ pub async fn extract_block_emissions(
                 }
             }
             Err(e) => {
-                println!("[block_entity_emissions] Failed to read {}: {}", class_name, e);
+                warn!("Failed to read {}: {}", class_name, e);
             }
         }
     }
@@ -2222,8 +2217,8 @@ pub async fn extract_block_emissions(
                     &empty_field_values,
                 );
                 if !emissions.is_empty() {
-                    println!(
-                        "[entity_emissions] {} -> {:?}",
+                    info!(
+                        "{} -> {:?}",
                         entity_id,
                         emissions.iter().map(|e| &e.particle_id).collect::<Vec<_>>()
                     );
@@ -2237,7 +2232,7 @@ pub async fn extract_block_emissions(
                 }
             }
             Err(e) => {
-                println!("[entity_emissions] Failed to read {}: {}", entity_id, e);
+                warn!("Failed to read {}: {}", entity_id, e);
             }
         }
     }
@@ -2252,8 +2247,8 @@ pub async fn extract_block_emissions(
     // Cache the results
     save_emissions_to_cache(&data)?;
 
-    println!(
-        "[emissions] Extraction complete: {} blocks, {} entities",
+    info!(
+        "Extraction complete: {} blocks, {} entities",
         data.blocks.len(),
         data.entities.len()
     );
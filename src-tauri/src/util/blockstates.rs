@@ -70,22 +70,56 @@ pub struct MultipartCase {
     pub apply: BlockstateVariant,
 }
 
+/// Split a block ID into (namespace, block name)
+///
+/// Accepts bare, namespaced and texture-style IDs, defaulting to the minecraft namespace:
+/// "dirt" -> ("minecraft", "dirt"), "create:block/cogwheel" -> ("create", "cogwheel")
+pub fn split_block_id(block_id: &str) -> (&str, &str) {
+    let (namespace, path) = crate::util::pack_files::split_asset_id(block_id);
+    (namespace, path.strip_prefix("block/").unwrap_or(path))
+}
+
+/// Normalize a block ID to the form blockstate lookups return
+///
+/// Vanilla blocks drop their namespace, modded blocks keep it:
+/// "minecraft:block/dirt" -> "dirt", "create:block/cogwheel" -> "create:cogwheel"
+pub fn normalize_block_id(block_id: &str) -> String {
+    match split_block_id(block_id) {
+        ("minecraft", name) => name.to_string(),
+        (namespace, name) => format!("{}:{}", namespace, name),
+    }
+}
+
+/// Pack-relative path of a block's blockstate file
+///
+/// "dirt" -> "assets/minecraft/blockstates/dirt.json"
+/// "create:cogwheel" -> "assets/create/blockstates/cogwheel.json"
+pub fn blockstate_entry_path(block_id: &str) -> String {
+    let (namespace, name) = split_block_id(block_id);
+    format!("assets/{}/blockstates/{}.json", namespace, name)
+}
+
 /// Find the actual blockstate filename by fuzzy matching
 ///
-/// This function scans the blockstates directory and finds a file that matches
-/// the requested block_id when underscores are removed. This provides universal
-/// support for all block types without needing to maintain a hardcoded list.
+/// This function scans the blockstates directory of the block's namespace and
+/// finds a file that matches the requested block_id when underscores are removed.
+/// This provides universal support for all block types without needing to
+/// maintain a hardcoded list.
 ///
 /// # Arguments
 /// * `pack_path` - Path to the resource pack
-/// * `block_id` - Block ID to search for (e.g., "acaciabutton" or "acacia_button")
+/// * `block_id` - Block ID to search for (e.g., "acaciabutton", "acacia_button" or "create:cogwheel")
 /// * `is_zip` - Whether the pack is a ZIP file
 ///
 /// # Returns
-/// The actual block ID as it appears in the blockstate filename, or None if not found
+/// The actual block ID as it appears in the blockstate filename, namespaced for
+/// non-minecraft blocks (see `normalize_block_id`), or None if not found
 pub fn find_blockstate_file(pack_path: &Path, block_id: &str, is_zip: bool) -> Option<String> {
+    let (namespace, name) = split_block_id(block_id);
+    let blockstates_prefix = format!("assets/{}/blockstates/", namespace);
+
     // Normalize the input by removing underscores for comparison
-    let normalized_input = block_id.replace('_', "").to_lowercase();
+    let normalized_input = name.replace('_', "").to_lowercase();
 
     let blockstate_files: Vec<String> = if is_zip {
         // For ZIP files, list entries and filter to blockstates
@@ -94,10 +128,10 @@ pub fn find_blockstate_file(pack_path: &Path, block_id: &str, is_zip: bool) -> O
 
         all_files
             .into_iter()
-            .filter(|f| f.starts_with("assets/minecraft/blockstates/") && f.ends_with(".json"))
+            .filter(|f| f.starts_with(&blockstates_prefix) && f.ends_with(".json"))
             .map(|f| {
                 // Extract just the filename without path and extension
-                f.strip_prefix("assets/minecraft/blockstates/")
+                f.strip_prefix(&blockstates_prefix)
                     .unwrap_or(&f)
                     .strip_suffix(".json")
                     .unwrap_or(&f)
@@ -106,7 +140,7 @@ pub fn find_blockstate_file(pack_path: &Path, block_id: &str, is_zip: bool) -> O
             .collect()
     } else {
         // For directories, read the blockstates folder
-        let blockstates_dir = pack_path.join("assets/minecraft/blockstates");
+        let blockstates_dir = pack_path.join(&blockstates_prefix);
         if !blockstates_dir.exists() {
             return None;
         }
@@ -129,34 +163,30 @@ pub fn find_blockstate_file(pack_path: &Path, block_id: &str, is_zip: bool) -> O
         }
     };
 
-    // First try exact match
-    if blockstate_files.contains(&block_id.to_string()) {
-        return Some(block_id.to_string());
-    }
-
-    // Then try normalized match (remove underscores)
-    for file in blockstate_files {
-        let normalized_file = file.replace('_', "").to_lowercase();
-        if normalized_file == normalized_input {
-            return Some(file);
-        }
-    }
+    // First try exact match, then normalized match (remove underscores)
+    let file = if blockstate_files.iter().any(|file| file == name) {
+        name.to_string()
+    } else {
+        blockstate_files
+            .into_iter()
+            .find(|file| file.replace('_', "").to_lowercase() == normalized_input)?
+    };
 
-    None
+    Some(normalize_block_id(&format!("{}:{}", namespace, file)))
 }
 
 /// Read a blockstate file from a resource pack
 ///
 /// # Arguments
 /// * `pack_path` - Path to the resource pack
-/// * `block_id` - Block ID without "minecraft:" prefix (e.g., "dirt", "stone")
+/// * `block_id` - Block ID, namespaced for modded blocks (e.g., "dirt", "create:cogwheel")
 /// * `is_zip` - Whether the pack is a ZIP file
 ///
 /// # Returns
 /// The parsed Blockstate structure
 pub fn read_blockstate(pack_path: &Path, block_id: &str, is_zip: bool) -> AppResult<Blockstate> {
-    // Blockstates are at: assets/minecraft/blockstates/{block_id}.json
-    let relative_path = format!("assets/minecraft/blockstates/{}.json", block_id);
+    // Blockstates are at: assets/{namespace}/blockstates/{block}.json
+    let relative_path = blockstate_entry_path(block_id);
 
     let contents = if is_zip {
         // Read from ZIP archive
@@ -299,6 +329,73 @@ pub struct ResolutionResult {
     pub models: Vec<ResolvedModel>,
 }
 
+/// One page of a block's state permutations, each resolved to its models
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatePermutationPage {
+    #[serde(rename = "blockId")]
+    pub block_id: String,
+    /// Permutations enumerated, at most MAX_STATE_PERMUTATIONS
+    pub total: usize,
+    /// Whether the block has more permutations than are enumerated
+    pub truncated: bool,
+    /// Index of the first permutation on this page
+    pub offset: usize,
+    /// Permutations a missing variant or no multipart case applies to have
+    /// no models, as they render nothing in game
+    pub permutations: Vec<ResolutionResult>,
+}
+
+/// Most state permutations enumerated for one block; redstone wire, one of
+/// the largest, has 1296
+pub const MAX_STATE_PERMUTATIONS: usize = 4096;
+
+/// Values each property of a schema takes, in schema order
+fn property_domains(schema: &BlockStateSchema) -> Vec<(&str, Vec<String>)> {
+    schema
+        .properties
+        .iter()
+        .map(|property| {
+            let values = match (&property.values, property.min, property.max) {
+                (Some(values), _, _) => values.clone(),
+                (None, Some(min), Some(max)) => (min..=max).map(|n| n.to_string()).collect(),
+                _ => vec![property.default.clone()],
+            };
+            (property.name.as_str(), values)
+        })
+        .collect()
+}
+
+/// Number of property combinations a schema allows
+pub fn state_permutation_count(schema: &BlockStateSchema) -> usize {
+    property_domains(schema)
+        .iter()
+        .fold(1, |count, (_, values)| count.saturating_mul(values.len()))
+}
+
+/// Property combinations `offset` to `offset + limit` of a schema
+///
+/// Combinations count up like an odometer over the properties, the last
+/// property changing fastest, so pages line up across calls.
+pub fn state_permutations(
+    schema: &BlockStateSchema,
+    offset: usize,
+    limit: usize,
+) -> Vec<HashMap<String, String>> {
+    let domains = property_domains(schema);
+    let end = state_permutation_count(schema).min(offset.saturating_add(limit));
+    (offset..end)
+        .map(|index| {
+            let mut rest = index;
+            let mut state = HashMap::new();
+            for (name, values) in domains.iter().rev() {
+                state.insert(name.to_string(), values[rest % values.len()].clone());
+                rest /= values.len();
+            }
+            state
+        })
+        .collect()
+}
+
 /// Build a BlockStateSchema from a blockstate file for UI generation
 pub fn build_block_state_schema(blockstate: &Blockstate, block_id: &str) -> BlockStateSchema {
     let mut property_values: HashMap<String, HashSet<String>> = HashMap::new();
@@ -439,8 +536,8 @@ fn extract_properties_from_when(
                     .or_insert_with(HashSet::new);
 
                 if let Some(s) = value.as_str() {
-                    // Single value or pipe-separated values
-                    for val in s.split('|') {
+                    // Single value or pipe-separated values, possibly negated
+                    for val in s.trim_start_matches('!').split('|') {
                         values_set.insert(val.to_string());
                     }
                 } else if let Some(arr) = value.as_array() {
@@ -463,12 +560,136 @@ fn extract_properties_from_when(
     }
 }
 
+/// World coordinates of a block, which the game picks weighted variants by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockPosition {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+/// How weighted model lists pick their model
+#[derive(Debug, Clone, Copy)]
+enum VariantSelection {
+    /// Always the first model
+    First,
+    /// A preview seed, varied per multipart case
+    Seed(u64),
+    /// The model the game shows at a position
+    Position(BlockPosition, WeightedRoll),
+}
+
+/// How the game turns its generator into a weighted model pick
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WeightedRoll {
+    /// `abs((int) nextLong()) % totalWeight` (WeightedBakedModel, before 1.21.5)
+    AbsNextLong,
+    /// `nextInt(totalWeight)` (WeightedList, since 1.21.5)
+    #[default]
+    NextInt,
+}
+
+impl WeightedRoll {
+    /// First pack_format whose game picks with `nextInt` (1.21.5)
+    const NEXT_INT_MIN_PACK_FORMAT: u32 = 55;
+
+    /// The pick of the game version a pack_format belongs to; unknown formats
+    /// (snapshots) get the current one
+    pub fn for_pack_format(pack_format: Option<u32>) -> Self {
+        match pack_format {
+            Some(format) if format < Self::NEXT_INT_MIN_PACK_FORMAT => Self::AbsNextLong,
+            _ => Self::NextInt,
+        }
+    }
+}
+
+/// Seed the game derives from a block position (Mth.getSeed)
+pub fn position_seed(position: BlockPosition) -> i64 {
+    let BlockPosition { x, y, z } = position;
+    let mut seed =
+        (x.wrapping_mul(3129871) as i64) ^ (z as i64).wrapping_mul(116129781) ^ (y as i64);
+    seed = seed
+        .wrapping_mul(seed)
+        .wrapping_mul(42317861)
+        .wrapping_add(seed.wrapping_mul(11));
+    seed >> 16
+}
+
+/// java.util.Random, the generator the game's chunk renderer reseeds per block
+struct JavaRandom {
+    seed: i64,
+}
+
+impl JavaRandom {
+    const MULTIPLIER: i64 = 0x5DEECE66D;
+    const MASK: i64 = (1 << 48) - 1;
+
+    fn new(seed: i64) -> Self {
+        Self {
+            seed: (seed ^ Self::MULTIPLIER) & Self::MASK,
+        }
+    }
+
+    fn next(&mut self, bits: u32) -> i32 {
+        self.seed = self.seed.wrapping_mul(Self::MULTIPLIER).wrapping_add(0xB) & Self::MASK;
+        (self.seed >> (48 - bits)) as i32
+    }
+
+    fn next_long(&mut self) -> i64 {
+        ((self.next(32) as i64) << 32).wrapping_add(self.next(32) as i64)
+    }
+
+    fn next_int(&mut self, bound: i32) -> i32 {
+        if bound & (bound - 1) == 0 {
+            return ((bound as i64 * self.next(31) as i64) >> 31) as i32;
+        }
+        loop {
+            let bits = self.next(31);
+            let value = bits % bound;
+            // Rejects the uneven tail of the range, overflowing like Java's int
+            if bits.wrapping_sub(value).wrapping_add(bound - 1) >= 0 {
+                return value;
+            }
+        }
+    }
+}
+
 /// Resolve a blockstate with given properties to a list of models
 pub fn resolve_blockstate(
     blockstate: &Blockstate,
     block_id: &str,
     state_props: Option<HashMap<String, String>>,
     seed: Option<u64>,
+) -> AppResult<ResolutionResult> {
+    let selection = seed.map_or(VariantSelection::First, VariantSelection::Seed);
+    resolve_blockstate_with(blockstate, block_id, state_props, selection)
+}
+
+/// Resolve a blockstate to the models the game shows at a world position
+///
+/// Weighted variants are picked the way the chunk renderer of the target
+/// version (`roll`) does, so a grid of positions shows the variant
+/// distribution players see in the world.
+pub fn resolve_blockstate_at(
+    blockstate: &Blockstate,
+    block_id: &str,
+    state_props: Option<HashMap<String, String>>,
+    position: BlockPosition,
+    roll: WeightedRoll,
+) -> AppResult<ResolutionResult> {
+    resolve_blockstate_with(
+        blockstate,
+        block_id,
+        state_props,
+        VariantSelection::Position(position, roll),
+    )
+}
+
+fn resolve_blockstate_with(
+    blockstate: &Blockstate,
+    block_id: &str,
+    state_props: Option<HashMap<String, String>>,
+    selection: VariantSelection,
 ) -> AppResult<ResolutionResult> {
     let props = state_props.unwrap_or_default();
     let mut resolved_models = Vec::new();
@@ -503,7 +724,14 @@ pub fn resolve_blockstate(
         };
 
         if let Some(var) = variant {
-            collect_models_from_variant(var, seed, &mut resolved_models)?;
+            let pick = match selection {
+                VariantSelection::First => WeightedPick::First,
+                VariantSelection::Seed(seed) => WeightedPick::Seed(seed),
+                VariantSelection::Position(position, roll) => {
+                    WeightedPick::Game(position_seed(position), roll)
+                }
+            };
+            collect_models_from_variant(var, pick, &mut resolved_models)?;
         } else {
             return Err(AppError::validation(format!(
                 "No variant found for key: '{}' in block '{}'",
@@ -514,6 +742,13 @@ pub fn resolve_blockstate(
 
     // Handle multipart format
     if let Some(multipart) = &blockstate.multipart {
+        // The game draws one number for the block and reseeds every part with it
+        let part_seed = match selection {
+            VariantSelection::Position(position, _) => {
+                JavaRandom::new(position_seed(position)).next_long()
+            }
+            _ => 0,
+        };
         for (index, case) in multipart.iter().enumerate() {
             let matches = if let Some(when) = &case.when {
                 matches_when_clause(&props, when)?
@@ -522,9 +757,15 @@ pub fn resolve_blockstate(
             };
 
             if matches {
-                // Use different seed for each multipart case to get variety
-                let case_seed = seed.map(|s| s.wrapping_add(index as u64));
-                collect_models_from_variant(&case.apply, case_seed, &mut resolved_models)?;
+                let pick = match selection {
+                    VariantSelection::First => WeightedPick::First,
+                    // Use different seed for each multipart case to get variety
+                    VariantSelection::Seed(seed) => {
+                        WeightedPick::Seed(seed.wrapping_add(index as u64))
+                    }
+                    VariantSelection::Position(_, roll) => WeightedPick::Game(part_seed, roll),
+                };
+                collect_models_from_variant(&case.apply, pick, &mut resolved_models)?;
             }
         }
     }
@@ -578,10 +819,20 @@ fn canonicalize_variant_key(key: &str) -> String {
         .join(",")
 }
 
+/// How one weighted model list picks its model
+#[derive(Debug, Clone, Copy)]
+enum WeightedPick {
+    First,
+    /// Preview seed
+    Seed(u64),
+    /// Seed of the game's generator when it reaches this list
+    Game(i64, WeightedRoll),
+}
+
 /// Collect models from a variant (handles weighted random selection)
 fn collect_models_from_variant(
     variant: &BlockstateVariant,
-    seed: Option<u64>,
+    pick: WeightedPick,
     output: &mut Vec<ResolvedModel>,
 ) -> AppResult<()> {
     match variant {
@@ -594,11 +845,11 @@ fn collect_models_from_variant(
             }
 
             // Pick one model based on weights
-            let model_ref = if let Some(seed_val) = seed {
-                pick_weighted_with_seed(models, seed_val)
-            } else {
+            let model_ref = match pick {
+                WeightedPick::Seed(seed) => pick_weighted_with_seed(models, seed),
+                WeightedPick::Game(seed, roll) => pick_weighted_like_game(models, seed, roll),
                 // Default to first model if no seed
-                &models[0]
+                WeightedPick::First => &models[0],
             };
 
             output.push(to_resolved_model(model_ref));
@@ -630,6 +881,29 @@ fn pick_weighted_with_seed(models: &[ModelReference], seed: u64) -> &ModelRefere
     &models[0] // Fallback
 }
 
+/// Pick a weighted model as the game does
+fn pick_weighted_like_game(
+    models: &[ModelReference],
+    seed: i64,
+    roll: WeightedRoll,
+) -> &ModelReference {
+    let total_weight: i32 = models.iter().map(|m| m.weight.unwrap_or(1).max(1)).sum();
+    let mut random = JavaRandom::new(seed);
+    let mut roll = match roll {
+        WeightedRoll::AbsNextLong => (random.next_long() as i32).wrapping_abs() % total_weight,
+        WeightedRoll::NextInt => random.next_int(total_weight),
+    };
+
+    for model in models {
+        roll -= model.weight.unwrap_or(1).max(1);
+        if roll < 0 {
+            return model;
+        }
+    }
+
+    &models[0] // Fallback
+}
+
 /// Convert ModelReference to ResolvedModel
 fn to_resolved_model(model_ref: &ModelReference) -> ResolvedModel {
     ResolvedModel {
@@ -642,82 +916,65 @@ fn to_resolved_model(model_ref: &ModelReference) -> ResolvedModel {
 }
 
 /// Check if state properties match a when clause
+///
+/// Every key of the clause must hold: "OR" needs one of its clauses to match,
+/// "AND" needs all of them, and any other key is a property condition. The
+/// combinators nest, and may sit next to plain properties in one object.
 fn matches_when_clause(
     props: &HashMap<String, String>,
     when: &serde_json::Value,
 ) -> AppResult<bool> {
-    if let Some(obj) = when.as_object() {
-        // Check for OR clause
-        if let Some(or_value) = obj.get("OR") {
-            if let Some(or_array) = or_value.as_array() {
-                // OR: any child must match
-                for child in or_array {
-                    if matches_when_clause(props, child)? {
-                        return Ok(true);
-                    }
-                }
-                return Ok(false);
-            }
-        }
+    let Some(obj) = when.as_object() else {
+        return Ok(false);
+    };
 
-        // Check for AND clause
-        if let Some(and_value) = obj.get("AND") {
-            if let Some(and_array) = and_value.as_array() {
-                // AND: all children must match
-                for child in and_array {
-                    if !matches_when_clause(props, child)? {
-                        return Ok(false);
+    for (key, value) in obj {
+        let matches = match key.as_str() {
+            "OR" | "AND" => {
+                let Some(clauses) = value.as_array() else {
+                    return Ok(false);
+                };
+                let is_or = key == "OR";
+                let mut matched = !is_or;
+                for clause in clauses {
+                    // OR stops at the first match, AND at the first mismatch
+                    if matches_when_clause(props, clause)? == is_or {
+                        matched = is_or;
+                        break;
                     }
                 }
-                return Ok(true);
+                matched
             }
+            _ => matches_property_condition(props.get(key), value),
+        };
+        if !matches {
+            return Ok(false);
         }
+    }
 
-        // Simple property matching (all properties must match)
-        for (key, value) in obj {
-            if key == "OR" || key == "AND" {
-                continue; // Already handled
-            }
-
-            let prop_value = props.get(key);
-
-            let matches_value = if let Some(s) = value.as_str() {
-                // Handle pipe-separated OR values: "up|side|none"
-                let allowed: Vec<&str> = s.split('|').collect();
-                prop_value
-                    .map(|v| allowed.contains(&v.as_str()))
-                    .unwrap_or(false)
-            } else if let Some(n) = value.as_i64() {
-                prop_value.map(|v| v == &n.to_string()).unwrap_or(false)
-            } else if let Some(b) = value.as_bool() {
-                prop_value.map(|v| v == &b.to_string()).unwrap_or(false)
-            } else if let Some(arr) = value.as_array() {
-                arr.iter().any(|item| {
-                    if let Some(s) = item.as_str() {
-                        let allowed: Vec<&str> = s.split('|').collect();
-                        prop_value
-                            .map(|v| allowed.contains(&v.as_str()))
-                            .unwrap_or(false)
-                    } else if let Some(n) = item.as_i64() {
-                        prop_value.map(|v| v == &n.to_string()).unwrap_or(false)
-                    } else if let Some(b) = item.as_bool() {
-                        prop_value.map(|v| v == &b.to_string()).unwrap_or(false)
-                    } else {
-                        false
-                    }
-                })
-            } else {
-                false
-            };
-
-            if !matches_value {
-                return Ok(false);
-            }
-        }
+    Ok(true)
+}
 
-        Ok(true)
-    } else {
-        Ok(false)
+/// Check a property value against one condition of a when clause
+///
+/// Strings list the allowed values separated by "|" ("up|side"); a leading
+/// "!" inverts the list ("!none"), as in vanilla. Numbers and booleans match
+/// their string form, and an array matches if any of its items does.
+fn matches_property_condition(prop_value: Option<&String>, condition: &serde_json::Value) -> bool {
+    let Some(prop_value) = prop_value else {
+        return false;
+    };
+    match condition {
+        serde_json::Value::String(s) => match s.strip_prefix('!') {
+            Some(excluded) => !excluded.split('|').any(|v| v == prop_value),
+            None => s.split('|').any(|v| v == prop_value),
+        },
+        serde_json::Value::Number(n) => n.to_string() == *prop_value,
+        serde_json::Value::Bool(b) => b.to_string() == *prop_value,
+        serde_json::Value::Array(items) => items
+            .iter()
+            .any(|item| matches_property_condition(Some(prop_value), item)),
+        _ => false,
     }
 }
 
@@ -730,13 +987,13 @@ fn matches_when_clause(
 /// "minecraft:block/dirt" -> "dirt"
 /// "minecraft:block/amethyst_block1" -> "amethyst_block" (strips variant suffix)
 /// "minecraft:block/acacia_log_top" -> "acacia_log" (strips texture part suffix)
+/// "create:block/cogwheel_side" -> "create:cogwheel" (modded blocks keep their namespace)
 /// "minecraft:item/stick" -> None (not a block)
 pub fn texture_id_to_block_id(texture_id: &str) -> Option<String> {
-    // Remove "minecraft:" prefix if present
-    let without_namespace = texture_id.strip_prefix("minecraft:").unwrap_or(texture_id);
+    let (namespace, path) = crate::util::pack_files::split_asset_id(texture_id);
 
     // Check if it's a block texture
-    if let Some(block_path) = without_namespace.strip_prefix("block/") {
+    if let Some(block_path) = path.strip_prefix("block/") {
         let mut block_id = block_path.to_string();
 
         // Strip common texture part suffixes (these are texture variants, not separate blocks)
@@ -765,7 +1022,7 @@ pub fn texture_id_to_block_id(texture_id: &str) -> Option<String> {
             }
         }
 
-        Some(block_id)
+        Some(normalize_block_id(&format!("{}:{}", namespace, block_id)))
     } else {
         None
     }
@@ -818,6 +1075,60 @@ mod tests {
             texture_id_to_block_id("minecraft:block/grass_block_side"),
             Some("grass_block".to_string())
         );
+
+        // Modded blocks keep their namespace
+        assert_eq!(
+            texture_id_to_block_id("create:block/cogwheel_side"),
+            Some("create:cogwheel".to_string())
+        );
+    }
+
+    #[test]
+    fn test_blockstate_entry_path() {
+        assert_eq!(
+            blockstate_entry_path("dirt"),
+            "assets/minecraft/blockstates/dirt.json"
+        );
+        assert_eq!(
+            blockstate_entry_path("minecraft:block/oak_stairs"),
+            "assets/minecraft/blockstates/oak_stairs.json"
+        );
+        assert_eq!(
+            blockstate_entry_path("create:block/cogwheel"),
+            "assets/create/blockstates/cogwheel.json"
+        );
+        assert_eq!(normalize_block_id("minecraft:block/dirt"), "dirt");
+        assert_eq!(
+            normalize_block_id("create:block/cogwheel"),
+            "create:cogwheel"
+        );
+        assert_eq!(normalize_block_id("block/stone"), "stone");
+    }
+
+    #[test]
+    fn test_read_modded_blockstate() {
+        let temp_dir = std::env::temp_dir().join("test_read_modded_blockstate");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let blockstates_dir = temp_dir.join("assets/create/blockstates");
+        fs::create_dir_all(&blockstates_dir).unwrap();
+        fs::write(
+            blockstates_dir.join("large_cogwheel.json"),
+            r#"{"variants": {"": {"model": "create:block/large_cogwheel"}}}"#,
+        )
+        .unwrap();
+
+        let found = find_blockstate_file(&temp_dir, "create:block/largecogwheel", false);
+        let blockstate = read_blockstate(&temp_dir, "create:large_cogwheel", false);
+        let vanilla = find_blockstate_file(&temp_dir, "large_cogwheel", false);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(found, Some("create:large_cogwheel".to_string()));
+        assert_eq!(
+            get_default_model(&blockstate.unwrap()),
+            Some("create:block/large_cogwheel".to_string())
+        );
+        assert_eq!(vanilla, None);
     }
 
     #[test]
@@ -917,6 +1228,183 @@ mod tests {
         assert!(!matches_when_clause(&props, &when).unwrap());
     }
 
+    #[test]
+    fn test_matches_when_clause_and_nested() {
+        let props: HashMap<String, String> = [
+            ("facing", "north"),
+            ("slot_0_occupied", "true"),
+            ("slot_1_occupied", "false"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let when = serde_json::json!({
+            "AND": [
+                {"facing": "north|south"},
+                {"OR": [{"slot_0_occupied": "false"}, {"slot_1_occupied": false}]}
+            ]
+        });
+        assert!(matches_when_clause(&props, &when).unwrap());
+
+        let when = serde_json::json!({
+            "AND": [{"facing": "north"}, {"slot_0_occupied": "false"}]
+        });
+        assert!(!matches_when_clause(&props, &when).unwrap());
+
+        // Combinators and plain properties in one object must all hold
+        let when = serde_json::json!({
+            "OR": [{"slot_0_occupied": "true"}],
+            "facing": "east"
+        });
+        assert!(!matches_when_clause(&props, &when).unwrap());
+
+        let when = serde_json::json!({"facing": "!east|west"});
+        assert!(matches_when_clause(&props, &when).unwrap());
+        let when = serde_json::json!({"facing": "!north"});
+        assert!(!matches_when_clause(&props, &when).unwrap());
+
+        let mut property_values = HashMap::new();
+        extract_properties_from_when(
+            &serde_json::json!({
+                "AND": [{"facing": "!east|west"}, {"OR": [{"slot_0_occupied": "true"}]}]
+            }),
+            &mut property_values,
+        );
+        assert_eq!(
+            property_values["facing"],
+            HashSet::from(["east".to_string(), "west".to_string()])
+        );
+        assert!(property_values.contains_key("slot_0_occupied"));
+    }
+
+    #[test]
+    fn test_resolve_blockstate_at_position() {
+        let mut random = JavaRandom::new(42);
+        assert_eq!(random.next(32), -1170105035);
+        assert_eq!(random.next(32), 234785527);
+        assert_eq!(JavaRandom::new(42).next_long(), -5025562857975149833);
+        assert_eq!(JavaRandom::new(42).next_int(4), 2);
+        assert_eq!(JavaRandom::new(42).next_int(5), 0);
+        assert_eq!(JavaRandom::new(-33674130277896).next_int(5), 1);
+        let at = |x, y, z| BlockPosition { x, y, z };
+        assert_eq!(position_seed(at(0, 0, 0)), 0);
+        assert_eq!(position_seed(at(1, 2, 3)), -33674130277896);
+        assert_eq!(position_seed(at(-100, 64, 250)), -81216377325037);
+
+        let models = serde_json::json!([
+            {"model": "block/stone_0"},
+            {"model": "block/stone_1"},
+            {"model": "block/stone_2"},
+            {"model": "block/stone_3"}
+        ]);
+        let variants: Blockstate =
+            serde_json::from_value(serde_json::json!({"variants": {"": models}})).unwrap();
+        let multipart: Blockstate =
+            serde_json::from_value(serde_json::json!({"multipart": [{"apply": models}]})).unwrap();
+        let model_at = |blockstate: &Blockstate, position| {
+            resolve_blockstate_at(
+                blockstate,
+                "stone",
+                None,
+                position,
+                WeightedRoll::AbsNextLong,
+            )
+            .unwrap()
+            .models[0]
+                .model_id
+                .clone()
+        };
+
+        assert_eq!(model_at(&variants, at(0, 0, 0)), "block/stone_0");
+        assert_eq!(model_at(&variants, at(1, 2, 3)), "block/stone_2");
+        assert_eq!(model_at(&variants, at(-100, 64, 250)), "block/stone_1");
+        // Multipart parts draw from a generator reseeded once per block
+        assert_eq!(model_at(&multipart, at(0, 0, 0)), "block/stone_3");
+        assert_eq!(model_at(&multipart, at(1, 2, 3)), "block/stone_0");
+    }
+
+    #[test]
+    fn test_resolve_blockstate_at_position_next_int() {
+        assert_eq!(
+            WeightedRoll::for_pack_format(Some(46)),
+            WeightedRoll::AbsNextLong
+        );
+        assert_eq!(
+            WeightedRoll::for_pack_format(Some(55)),
+            WeightedRoll::NextInt
+        );
+        assert_eq!(WeightedRoll::for_pack_format(None), WeightedRoll::NextInt);
+
+        let at = |x, y, z| BlockPosition { x, y, z };
+        let models = serde_json::json!([
+            {"model": "block/stone_0"},
+            {"model": "block/stone_1"},
+            {"model": "block/stone_2"},
+            {"model": "block/stone_3"}
+        ]);
+        // Total weight 5 takes nextInt's rejection path instead of the power of two one
+        let uneven = serde_json::json!([
+            {"model": "block/stone_0"},
+            {"model": "block/stone_1"},
+            {"model": "block/stone_2"},
+            {"model": "block/stone_3", "weight": 2}
+        ]);
+        let variants: Blockstate =
+            serde_json::from_value(serde_json::json!({"variants": {"": models}})).unwrap();
+        let uneven: Blockstate =
+            serde_json::from_value(serde_json::json!({"variants": {"": uneven}})).unwrap();
+        let multipart: Blockstate =
+            serde_json::from_value(serde_json::json!({"multipart": [{"apply": models}]})).unwrap();
+        let model_at = |blockstate: &Blockstate, position| {
+            resolve_blockstate_at(blockstate, "stone", None, position, WeightedRoll::NextInt)
+                .unwrap()
+                .models[0]
+                .model_id
+                .clone()
+        };
+
+        assert_eq!(model_at(&variants, at(0, 0, 0)), "block/stone_2");
+        assert_eq!(model_at(&variants, at(1, 2, 3)), "block/stone_3");
+        assert_eq!(model_at(&variants, at(-100, 64, 250)), "block/stone_2");
+        assert_eq!(model_at(&uneven, at(0, 0, 0)), "block/stone_0");
+        assert_eq!(model_at(&uneven, at(1, 2, 3)), "block/stone_1");
+        assert_eq!(model_at(&uneven, at(-100, 64, 250)), "block/stone_3");
+        assert_eq!(model_at(&multipart, at(0, 0, 0)), "block/stone_1");
+        assert_eq!(model_at(&multipart, at(1, 2, 3)), "block/stone_2");
+    }
+
+    #[test]
+    fn test_state_permutations() {
+        let blockstate: Blockstate = serde_json::from_value(serde_json::json!({
+            "multipart": [
+                {"apply": {"model": "block/wall_post"}},
+                {"when": {"north": "low|tall", "power": 2}, "apply": {"model": "block/wall_side"}},
+                {"when": {"power": 0}, "apply": {"model": "block/wall_side"}},
+                {"when": {"up": "true"}, "apply": {"model": "block/wall_post"}},
+                {"when": {"up": "false"}, "apply": {"model": "block/wall_post"}}
+            ]
+        }))
+        .unwrap();
+        let schema = build_block_state_schema(&blockstate, "test_wall");
+
+        // north (low, tall) x power (0..=2) x up (false, true)
+        assert_eq!(state_permutation_count(&schema), 12);
+        let all = state_permutations(&schema, 0, usize::MAX);
+        assert_eq!(all.len(), 12);
+        assert_eq!(all[0]["north"], "low");
+        assert_eq!(all[0]["power"], "0");
+        assert_eq!(all[0]["up"], "false");
+        assert_eq!(all[1]["up"], "true");
+        assert_eq!(all[2]["power"], "1");
+        let unique: HashSet<String> = all.iter().map(make_variant_key).collect();
+        assert_eq!(unique.len(), 12);
+
+        let page = state_permutations(&schema, 10, 5);
+        assert_eq!(page, all[10..].to_vec());
+        assert!(state_permutations(&schema, 12, 5).is_empty());
+    }
+
     #[test]
     fn test_pick_weighted_with_seed() {
         let models = vec![
@@ -0,0 +1,370 @@
+/// Journal of in-progress nest builds, for recovery after a crash
+///
+/// Before writing anything, a build stores its request in JOURNAL_FILE inside
+/// the output directory and leaves a pointer to that directory under
+/// <cache dir>/weaverbird/build_journals, so interrupted builds can be found on
+/// the next launch. Each output file is appended to PROGRESS_LOG once it has
+/// been fully written. A finished build removes all of it.
+///
+/// An interrupted build can be resumed, re-running the journaled request and
+/// skipping files the log marks as written as long as the planned output hasn't
+/// changed, or rolled back, deleting every file it wrote.
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+use tracing::{info, warn};
+use xxhash_rust::xxh3::xxh3_64;
+
+/// Journal file written into the output directory of a running build
+pub const JOURNAL_FILE: &str = ".weaverbird-build.json";
+
+/// Output files the running build has finished, one pack-relative path per line
+pub const PROGRESS_LOG: &str = ".weaverbird-build.log";
+
+/// What a build journal records
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildJournal {
+    /// When the build started (RFC 3339)
+    pub started_at: String,
+    /// The build request as sent, replayed on resume
+    pub request: serde_json::Value,
+    /// Fingerprint of the planned output files, set once the build has planned
+    /// them; the progress log only applies to a build with the same plan
+    #[serde(default)]
+    pub plan_hash: Option<String>,
+}
+
+/// A build that didn't finish
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InterruptedBuild {
+    pub output_dir: String,
+    pub started_at: String,
+    /// Output files written before the build stopped
+    pub files_written: usize,
+    pub request: serde_json::Value,
+}
+
+/// What to do with an interrupted build
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoveryAction {
+    /// Run the build again, keeping files it already wrote
+    Resume,
+    /// Delete the files it wrote
+    Rollback,
+}
+
+/// Directory holding a pointer file per running build
+pub fn journals_dir() -> Result<PathBuf> {
+    let journals_dir = dirs::cache_dir()
+        .ok_or_else(|| anyhow!("Could not find cache directory"))?
+        .join("weaverbird")
+        .join("build_journals");
+
+    fs::create_dir_all(&journals_dir).context("Failed to create build journal directory")?;
+
+    Ok(journals_dir)
+}
+
+/// Pointer file naming an output directory, stable per directory
+fn pointer_path(journals_dir: &Path, output_dir: &Path) -> PathBuf {
+    let key = xxh3_64(output_dir.to_string_lossy().as_bytes());
+    journals_dir.join(format!("{:016x}", key))
+}
+
+/// Read the journal of an output directory, if a build left one
+pub fn read_journal(output_dir: &Path) -> Result<Option<BuildJournal>> {
+    let path = output_dir.join(JOURNAL_FILE);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let text = fs::read_to_string(&path)?;
+    let journal = serde_json::from_str(&text)
+        .with_context(|| format!("Invalid build journal: {}", path.display()))?;
+    Ok(Some(journal))
+}
+
+/// Write a journal under a temporary name and move it into place, so a crash
+/// never leaves half of one
+fn write_journal(output_dir: &Path, journal: &BuildJournal) -> Result<()> {
+    let path = output_dir.join(JOURNAL_FILE);
+    let partial = output_dir.join(format!("{}.partial", JOURNAL_FILE));
+    fs::write(&partial, serde_json::to_string_pretty(journal)?)?;
+    fs::rename(&partial, &path).context("Failed to write build journal")?;
+    Ok(())
+}
+
+/// Record that a build into `output_dir` is starting
+///
+/// A journal left by an interrupted build keeps its plan, so the build can skip
+/// the files that one already wrote.
+pub fn begin(output_dir: &Path, request: &serde_json::Value) -> Result<()> {
+    begin_in(&journals_dir()?, output_dir, request)
+}
+
+fn begin_in(journals_dir: &Path, output_dir: &Path, request: &serde_json::Value) -> Result<()> {
+    fs::create_dir_all(output_dir)?;
+    let plan_hash = read_journal(output_dir)
+        .ok()
+        .flatten()
+        .and_then(|journal| journal.plan_hash);
+    write_journal(
+        output_dir,
+        &BuildJournal {
+            started_at: chrono::Utc::now().to_rfc3339(),
+            request: request.clone(),
+            plan_hash,
+        },
+    )?;
+    fs::write(
+        pointer_path(journals_dir, output_dir),
+        output_dir.to_string_lossy().as_bytes(),
+    )
+    .context("Failed to register build journal")?;
+    Ok(())
+}
+
+/// Record that the build into `output_dir` finished, removing its journal
+pub fn finish(output_dir: &Path) -> Result<()> {
+    finish_in(&journals_dir()?, output_dir)
+}
+
+fn finish_in(journals_dir: &Path, output_dir: &Path) -> Result<()> {
+    for path in [
+        output_dir.join(JOURNAL_FILE),
+        output_dir.join(PROGRESS_LOG),
+        pointer_path(journals_dir, output_dir),
+    ] {
+        if path.exists() {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Builds that started but never finished
+///
+/// Pointers to output directories without a journal (deleted by the user, or
+/// cleaned up by hand) are dropped.
+pub fn find_interrupted() -> Result<Vec<InterruptedBuild>> {
+    find_interrupted_in(&journals_dir()?)
+}
+
+fn find_interrupted_in(journals_dir: &Path) -> Result<Vec<InterruptedBuild>> {
+    let mut builds = Vec::new();
+    for entry in fs::read_dir(journals_dir)? {
+        let pointer = entry?.path();
+        let Ok(output_dir) = fs::read_to_string(&pointer) else {
+            continue;
+        };
+        let journal = match read_journal(Path::new(&output_dir)) {
+            Ok(Some(journal)) => journal,
+            Ok(None) => {
+                let _ = fs::remove_file(&pointer);
+                continue;
+            }
+            Err(e) => {
+                warn!("{}: {}", output_dir, e);
+                continue;
+            }
+        };
+        let files_written = written_files(Path::new(&output_dir)).len();
+        builds.push(InterruptedBuild {
+            output_dir,
+            started_at: journal.started_at,
+            files_written,
+            request: journal.request,
+        });
+    }
+    builds.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+    Ok(builds)
+}
+
+/// Files the progress log of an output directory lists
+fn written_files(output_dir: &Path) -> Vec<String> {
+    fs::read_to_string(output_dir.join(PROGRESS_LOG))
+        .map(|log| {
+            log.lines()
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Delete the files an interrupted build wrote, then its journal
+///
+/// Also removes `partial_files` (e.g. a half-written nest zip) and directories
+/// left empty. Files that were in the output directory before the build are not
+/// restored.
+///
+/// # Returns
+/// Number of files deleted
+pub fn rollback(output_dir: &Path, partial_files: &[&str]) -> Result<usize> {
+    rollback_in(&journals_dir()?, output_dir, partial_files)
+}
+
+fn rollback_in(journals_dir: &Path, output_dir: &Path, partial_files: &[&str]) -> Result<usize> {
+    let mut deleted = 0;
+    let mut parents = HashSet::new();
+    let written = written_files(output_dir);
+    let files = written
+        .iter()
+        .map(String::as_str)
+        .chain(partial_files.iter().copied());
+    for relative in files {
+        // The log is ours, but never delete anything outside the output directory
+        if !Path::new(relative)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+        {
+            warn!("Skipping unsafe path: {}", relative);
+            continue;
+        }
+        let path = output_dir.join(relative);
+        if path.is_file() {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+            deleted += 1;
+        }
+        let mut parent = path.parent();
+        while let Some(dir) = parent.filter(|dir| *dir != output_dir) {
+            parents.insert(dir.to_path_buf());
+            parent = dir.parent();
+        }
+    }
+
+    // Deepest directories first, so emptied parents can go too
+    let mut parents: Vec<PathBuf> = parents.into_iter().collect();
+    parents.sort_by_key(|dir| std::cmp::Reverse(dir.components().count()));
+    for dir in parents {
+        // Fails for directories that still hold other files, which is fine
+        let _ = fs::remove_dir(dir);
+    }
+
+    finish_in(journals_dir, output_dir)?;
+    info!("Removed {} file(s) from {}", deleted, output_dir.display());
+    Ok(deleted)
+}
+
+/// Progress log of a running build
+pub struct BuildProgress {
+    /// Files a previous run of the same plan already wrote
+    written: HashSet<String>,
+    log: Mutex<fs::File>,
+}
+
+impl BuildProgress {
+    /// Open the progress log of the build journaled in `output_dir`
+    ///
+    /// Files logged by an earlier run count as written only if that run had
+    /// the same plan; otherwise the log starts over.
+    pub fn open(output_dir: &Path, plan_hash: u64) -> Result<Self> {
+        let mut journal = read_journal(output_dir)?
+            .ok_or_else(|| anyhow!("No build journal in {}", output_dir.display()))?;
+        let plan_hash = format!("{:016x}", plan_hash);
+        let log_path = output_dir.join(PROGRESS_LOG);
+
+        let written = if journal.plan_hash.as_deref() == Some(plan_hash.as_str()) {
+            written_files(output_dir).into_iter().collect()
+        } else {
+            journal.plan_hash = Some(plan_hash);
+            write_journal(output_dir, &journal)?;
+            let _ = fs::remove_file(&log_path);
+            HashSet::new()
+        };
+        let log = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_path)
+            .context("Failed to open build progress log")?;
+        Ok(Self {
+            written,
+            log: Mutex::new(log),
+        })
+    }
+
+    /// Whether an earlier run of this build already wrote `output_path`
+    pub fn is_written(&self, output_path: &str) -> bool {
+        self.written.contains(output_path)
+    }
+
+    /// Number of files an earlier run already wrote
+    pub fn resumed_files(&self) -> usize {
+        self.written.len()
+    }
+
+    /// Log `output_path` as fully written
+    pub fn record(&self, output_path: &str) -> Result<()> {
+        let mut log = self.log.lock().unwrap_or_else(|e| e.into_inner());
+        // One write per line, so a crash can't interleave two entries
+        log.write_all(format!("{}\n", output_path).as_bytes())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_journal_progress_and_rollback() {
+        let temp_dir = std::env::temp_dir().join("test_build_journal");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let journals_dir = temp_dir.join("journals");
+        let output_dir = temp_dir.join("nest");
+        fs::create_dir_all(&journals_dir).unwrap();
+        let request = serde_json::json!({ "packs_dir": "/packs" });
+
+        begin_in(&journals_dir, &output_dir, &request).unwrap();
+        let progress = BuildProgress::open(&output_dir, 1).unwrap();
+        let stone = output_dir.join("assets/minecraft/textures/block/stone.png");
+        fs::create_dir_all(stone.parent().unwrap()).unwrap();
+        fs::write(&stone, b"stone").unwrap();
+        progress
+            .record("assets/minecraft/textures/block/stone.png")
+            .unwrap();
+        drop(progress);
+        let interrupted = find_interrupted_in(&journals_dir).unwrap();
+
+        // Resuming the same plan skips the file; a different plan starts over
+        begin_in(&journals_dir, &output_dir, &request).unwrap();
+        let resumed = BuildProgress::open(&output_dir, 1).unwrap();
+        let skips_stone = resumed.is_written("assets/minecraft/textures/block/stone.png");
+        drop(resumed);
+        let replanned = BuildProgress::open(&output_dir, 2).unwrap().resumed_files();
+        BuildProgress::open(&output_dir, 2)
+            .unwrap()
+            .record("assets/minecraft/textures/block/stone.png")
+            .unwrap();
+
+        let before_existing = output_dir.join("keep.txt");
+        fs::write(&before_existing, b"keep").unwrap();
+        fs::write(output_dir.join("WeaverNest.zip.part"), b"partial").unwrap();
+        let deleted = rollback_in(&journals_dir, &output_dir, &["WeaverNest.zip.part"]).unwrap();
+        let textures_dir_left = output_dir.join("assets").exists();
+        let kept = before_existing.exists();
+        let remaining = find_interrupted_in(&journals_dir).unwrap();
+        let journal_left = output_dir.join(JOURNAL_FILE).exists();
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(interrupted.len(), 1);
+        assert_eq!(interrupted[0].files_written, 1);
+        assert_eq!(interrupted[0].request, request);
+        assert!(skips_stone);
+        assert_eq!(replanned, 0);
+        assert_eq!(deleted, 2);
+        assert!(!textures_dir_left);
+        assert!(kept);
+        assert!(remaining.is_empty());
+        assert!(!journal_left);
+    }
+}
@@ -1,13 +1,12 @@
 /// Direct Java bytecode parser for extracting particle physics values
 ///
 /// This module parses Java .class files directly to extract field assignments
-/// from constructors, avoiding the need for slow full decompilation with CFR.
+/// from constructors, avoiding the need for slow full decompilation with CFR
+/// (and for a Java runtime at all).
 ///
 /// Performance: ~10-50ms per class vs 1-2 seconds with CFR decompilation
 
 use anyhow::{anyhow, Result};
-use cafebabe::{parse_class, attributes::AttributeData};
-use std::collections::HashMap;
 
 /// Constant value extracted from bytecode
 #[derive(Debug, Clone, Copy)]
@@ -42,6 +41,7 @@ impl ConstantValue {
 #[derive(Debug, Clone)]
 pub struct FieldAssignment {
     pub field_name: String,  // Obfuscated field name (e.g., "A", "B")
+    pub descriptor: String,  // Field type descriptor (e.g., "F", "I", "Z")
     pub value: ConstantValue,
 }
 
@@ -59,189 +59,421 @@ pub struct BytecodeExtraction {
     pub super_call: Option<SuperConstructorCall>,
 }
 
-/// Parse a Java class file and extract field assignments from constructors
-pub fn extract_field_assignments(class_bytes: &[u8]) -> Result<Vec<FieldAssignment>> {
-    let extraction = extract_bytecode_info(class_bytes)?;
-    Ok(extraction.field_assignments)
+/// Constant pool entry, as far as the extraction needs it
+#[derive(Debug, Clone)]
+enum Constant {
+    Utf8(String),
+    Value(ConstantValue),
+    Class(u16),
+    NameAndType(u16, u16),
+    /// Fieldref, Methodref or InterfaceMethodref: (class, name_and_type)
+    MemberRef(u16, u16),
+    /// MethodHandle: reference index (the kind doesn't matter here)
+    MethodHandle(u16),
+    /// InvokeDynamic: bootstrap method index
+    InvokeDynamic(u16),
+    /// Strings, method types, modules and the unused slot after longs/doubles
+    Other,
 }
 
-/// Parse a Java class file and extract both field assignments and super constructor calls
-pub fn extract_bytecode_info(class_bytes: &[u8]) -> Result<BytecodeExtraction> {
-    let class_file = parse_class(class_bytes)
-        .map_err(|e| anyhow!("Failed to parse class file: {}", e))?;
-
-    // Build constant pool lookup tables
-    let (const_pool, field_names, method_refs) = build_constant_pool_tables(&class_file);
-
-    // Extract from all constructors
-    let mut all_assignments = Vec::new();
-    let mut super_call = None;
-
-    for method in &class_file.methods {
-        if method.name == "<init>" {
-            let extraction = extract_from_constructor(method, &const_pool, &field_names, &method_refs)?;
-            all_assignments.extend(extraction.field_assignments);
-            if super_call.is_none() {
-                super_call = extraction.super_call;
-            }
-        }
-    }
-
-    Ok(BytecodeExtraction {
-        field_assignments: all_assignments,
-        super_call,
-    })
+/// Method of a class file with its bytecode
+#[derive(Debug, Clone)]
+pub struct MethodInfo {
+    pub name: String,
+    pub descriptor: String,
+    pub code: Option<Vec<u8>>,
 }
 
-/// Build lookup tables from constant pool for fast access during bytecode parsing
+/// Parsed Java class file
 ///
-/// Note: We can't use cafebabe's iterator because it doesn't expose the actual CP indices.
-/// Instead, we manually parse the constant pool to build our lookup tables.
-fn build_constant_pool_tables<'a>(
-    _class_file: &'a cafebabe::ClassFile<'a>,
-) -> (HashMap<u16, ConstantValue>, HashMap<u16, String>, HashMap<u16, (String, String)>) {
-    let const_pool = HashMap::new();
-    let field_names = HashMap::new();
-    let method_refs = HashMap::new();
-
-    // Since cafebabe doesn't expose direct constant pool access with indices,
-    // we'll use the iterator but track encountered refs ourselves.
-    // This is a workaround - we'll populate tables on-demand during bytecode parsing instead.
-
-    (const_pool, field_names, method_refs)
+/// Class names are returned in dotted form (`net.minecraft.client.particle.Particle`,
+/// or `abc$a` for obfuscated inner classes) to match the Mojang mappings.
+#[derive(Debug, Clone)]
+pub struct ClassFile {
+    constants: Vec<Constant>,
+    bootstrap_arguments: Vec<Vec<u16>>,
+    pub this_class: String,
+    pub super_class: Option<String>,
+    pub methods: Vec<MethodInfo>,
 }
 
-/// Extract field assignments and super calls from a single constructor method
-fn extract_from_constructor(
-    method: &cafebabe::MethodInfo,
-    const_pool: &HashMap<u16, ConstantValue>,
-    field_names: &HashMap<u16, String>,
-    method_refs: &HashMap<u16, (String, String)>,
-) -> Result<BytecodeExtraction> {
-    let mut assignments = Vec::new();
-    let mut super_call = None;
-
-    // Get the Code attribute
-    let code_data = method.attributes.iter()
-        .find_map(|attr| {
-            if let AttributeData::Code(code) = &attr.data {
-                Some(code)
-            } else {
-                None
-            }
-        })
-        .ok_or_else(|| anyhow!("No Code attribute found"))?;
+/// Big-endian reader over the raw class bytes
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
 
-    // Parse bytecode instructions
-    let bytecode = code_data.code;
-    let mut pc = 0; // Program counter
-    let mut stack: Vec<ConstantValue> = Vec::new(); // Simulate stack
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.pos + len;
+        let slice = self
+            .bytes
+            .get(self.pos..end)
+            .ok_or_else(|| anyhow!("Truncated class file at byte {}", self.pos))?;
+        self.pos = end;
+        Ok(slice)
+    }
 
-    while pc < bytecode.len() {
-        let opcode = bytecode[pc];
+    fn u1(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
 
-        match opcode {
-            // iconst_m1 through iconst_5: push -1 to 5
-            0x02 => { stack.push(ConstantValue::Int(-1)); pc += 1; }
-            0x03 => { stack.push(ConstantValue::Int(0)); pc += 1; }
-            0x04 => { stack.push(ConstantValue::Int(1)); pc += 1; }
-            0x05 => { stack.push(ConstantValue::Int(2)); pc += 1; }
-            0x06 => { stack.push(ConstantValue::Int(3)); pc += 1; }
-            0x07 => { stack.push(ConstantValue::Int(4)); pc += 1; }
-            0x08 => { stack.push(ConstantValue::Int(5)); pc += 1; }
-
-            // fconst_0, fconst_1, fconst_2
-            0x0b => { stack.push(ConstantValue::Float(0.0)); pc += 1; }
-            0x0c => { stack.push(ConstantValue::Float(1.0)); pc += 1; }
-            0x0d => { stack.push(ConstantValue::Float(2.0)); pc += 1; }
-
-            // bipush: push byte as integer
-            0x10 => {
-                if pc + 1 < bytecode.len() {
-                    let value = bytecode[pc + 1] as i8 as i32;
-                    stack.push(ConstantValue::Int(value));
+    fn u2(&mut self) -> Result<u16> {
+        let b = self.take(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u4(&mut self) -> Result<u32> {
+        let b = self.take(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn u8(&mut self) -> Result<u64> {
+        Ok((self.u4()? as u64) << 32 | self.u4()? as u64)
+    }
+}
+
+impl ClassFile {
+    /// Parse the constant pool, class names, methods and bootstrap methods of a class file
+    pub fn parse(class_bytes: &[u8]) -> Result<Self> {
+        let mut r = Reader { bytes: class_bytes, pos: 0 };
+        if r.u4()? != 0xCAFE_BABE {
+            return Err(anyhow!("Not a Java class file"));
+        }
+        r.take(4)?; // minor + major version
+
+        let pool_count = r.u2()? as usize;
+        let mut constants = vec![Constant::Other];
+        while constants.len() < pool_count {
+            let tag = r.u1()?;
+            let constant = match tag {
+                1 => {
+                    let len = r.u2()? as usize;
+                    Constant::Utf8(String::from_utf8_lossy(r.take(len)?).into_owned())
                 }
-                pc += 2;
+                3 => Constant::Value(ConstantValue::Int(r.u4()? as i32)),
+                4 => Constant::Value(ConstantValue::Float(f32::from_bits(r.u4()?))),
+                5 => Constant::Value(ConstantValue::Long(r.u8()? as i64)),
+                6 => Constant::Value(ConstantValue::Double(f64::from_bits(r.u8()?))),
+                7 => Constant::Class(r.u2()?),
+                8 | 16 | 19 | 20 => {
+                    r.u2()?;
+                    Constant::Other
+                }
+                9..=11 => Constant::MemberRef(r.u2()?, r.u2()?),
+                12 => Constant::NameAndType(r.u2()?, r.u2()?),
+                15 => {
+                    r.u1()?;
+                    Constant::MethodHandle(r.u2()?)
+                }
+                17 => {
+                    r.take(4)?;
+                    Constant::Other
+                }
+                18 => {
+                    let bootstrap = r.u2()?;
+                    r.u2()?;
+                    Constant::InvokeDynamic(bootstrap)
+                }
+                _ => return Err(anyhow!("Unknown constant pool tag {}", tag)),
+            };
+            constants.push(constant);
+            // Longs and doubles take up two slots
+            if matches!(tag, 5 | 6) {
+                constants.push(Constant::Other);
             }
+        }
+
+        let mut class = ClassFile {
+            constants,
+            bootstrap_arguments: Vec::new(),
+            this_class: String::new(),
+            super_class: None,
+            methods: Vec::new(),
+        };
+
+        r.u2()?; // access flags
+        let this_index = r.u2()?;
+        class.this_class = class
+            .class_name(this_index)
+            .ok_or_else(|| anyhow!("Invalid this_class index {}", this_index))?;
+        class.super_class = class.class_name(r.u2()?);
+        let interfaces = r.u2()? as usize;
+        r.take(interfaces * 2)?;
+
+        // Fields only need skipping
+        for _ in 0..r.u2()? {
+            r.take(6)?;
+            skip_attributes(&mut r)?;
+        }
 
-            // sipush: push short as integer
-            0x11 => {
-                if pc + 2 < bytecode.len() {
-                    let value = i16::from_be_bytes([bytecode[pc + 1], bytecode[pc + 2]]) as i32;
-                    stack.push(ConstantValue::Int(value));
+        for _ in 0..r.u2()? {
+            r.u2()?; // access flags
+            let name = class.utf8(r.u2()?).unwrap_or_default().to_string();
+            let descriptor = class.utf8(r.u2()?).unwrap_or_default().to_string();
+            let mut code = None;
+            for _ in 0..r.u2()? {
+                let attribute_name = r.u2()?;
+                let len = r.u4()? as usize;
+                let data = r.take(len)?;
+                if class.utf8(attribute_name) == Some("Code") && data.len() >= 8 {
+                    let code_len =
+                        u32::from_be_bytes([data[4], data[5], data[6], data[7]]) as usize;
+                    code = data.get(8..8 + code_len).map(<[u8]>::to_vec);
                 }
-                pc += 3;
             }
+            class.methods.push(MethodInfo {
+                name,
+                descriptor,
+                code,
+            });
+        }
+
+        for _ in 0..r.u2()? {
+            let attribute_name = r.u2()?;
+            let len = r.u4()? as usize;
+            let data = r.take(len)?;
+            if class.utf8(attribute_name) == Some("BootstrapMethods") {
+                class.bootstrap_arguments = parse_bootstrap_methods(data)?;
+            }
+        }
+
+        Ok(class)
+    }
+
+    fn utf8(&self, index: u16) -> Option<&str> {
+        match self.constants.get(index as usize)? {
+            Constant::Utf8(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn class_name(&self, index: u16) -> Option<String> {
+        match self.constants.get(index as usize)? {
+            Constant::Class(name) => self.utf8(*name).map(|n| n.replace('/', ".")),
+            _ => None,
+        }
+    }
+
+    fn value(&self, index: u16) -> Option<ConstantValue> {
+        match self.constants.get(index as usize)? {
+            Constant::Value(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Resolve a field/method reference to (class, name, descriptor)
+    fn member(&self, index: u16) -> Option<(String, &str, &str)> {
+        let Constant::MemberRef(class, nat) = self.constants.get(index as usize)? else {
+            return None;
+        };
+        let Constant::NameAndType(name, descriptor) = self.constants.get(*nat as usize)? else {
+            return None;
+        };
+        Some((self.class_name(*class)?, self.utf8(*name)?, self.utf8(*descriptor)?))
+    }
+
+    /// Class whose constructor an `invokedynamic` binds (`Provider::new`), if any
+    fn dynamic_constructor(&self, index: u16) -> Option<String> {
+        let Constant::InvokeDynamic(bootstrap) = self.constants.get(index as usize)? else {
+            return None;
+        };
+        self.bootstrap_arguments
+            .get(*bootstrap as usize)?
+            .iter()
+            .find_map(|arg| match self.constants.get(*arg as usize)? {
+                Constant::MethodHandle(reference) => {
+                    let (class, name, _) = self.member(*reference)?;
+                    (name == "<init>").then_some(class)
+                }
+                _ => None,
+            })
+    }
 
-            // ldc: load constant from pool (1-byte index)
-            0x12 => {
-                if let Some(index) = read_u1_index(bytecode, pc) {
-                    if let Some(&value) = const_pool.get(&index) {
-                        stack.push(value);
+    /// Classes instantiated with `new` anywhere in the class, in bytecode order
+    pub fn instantiated_classes(&self) -> Vec<String> {
+        let mut classes = Vec::new();
+        for code in self.methods.iter().filter_map(|m| m.code.as_deref()) {
+            for (pc, opcode) in instructions(code) {
+                if opcode == 0xbb {
+                    if let Some(class) = read_u2_index(code, pc).and_then(|i| self.class_name(i)) {
+                        if !classes.contains(&class) {
+                            classes.push(class);
+                        }
                     }
                 }
-                pc += 2;
             }
+        }
+        classes
+    }
 
-            // ldc_w / ldc2_w: load constant from pool (2-byte index)
-            0x13 | 0x14 => {
-                if let Some(index) = read_u2_index(bytecode, pc) {
-                    if let Some(&value) = const_pool.get(&index) {
-                        stack.push(value);
+    /// Pair each static field of `owner` that is read with the class constructed next
+    ///
+    /// This recovers registrations like
+    /// `this.register(ParticleTypes.FLAME, FlameParticle.Provider::new)` or
+    /// `this.register(ParticleTypes.FLASH, new FlashProvider())`, returning
+    /// `(field name, constructed class)` pairs.
+    pub fn static_field_constructors(&self, owner: &str) -> Vec<(String, String)> {
+        let mut pairs = Vec::new();
+        for code in self.methods.iter().filter_map(|m| m.code.as_deref()) {
+            let mut pending: Option<String> = None;
+            for (pc, opcode) in instructions(code) {
+                let Some(index) = read_u2_index(code, pc) else {
+                    continue;
+                };
+                let constructed = match opcode {
+                    // getstatic
+                    0xb2 => {
+                        pending = self
+                            .member(index)
+                            .filter(|(class, _, _)| class == owner)
+                            .map(|(_, name, _)| name.to_string());
+                        None
+                    }
+                    0xbb => self.class_name(index),
+                    0xba => self.dynamic_constructor(index),
+                    _ => None,
+                };
+                if let Some(class) = constructed {
+                    if let Some(field) = pending.take() {
+                        pairs.push((field, class));
                     }
                 }
-                pc += 3;
             }
+        }
+        pairs
+    }
 
-            // invokespecial: call constructor (could be super or this)
-            0xb7 => {
-                if let Some(method_ref) = read_u2_index(bytecode, pc) {
-                    if let Some((class_name, method_name)) = method_refs.get(&method_ref) {
-                        if method_name == "<init>" && super_call.is_none() {
-                            // Capture up to 4 arguments from stack (common for particle constructors)
-                            let args = (0..stack.len().min(4))
-                                .filter_map(|_| stack.pop())
-                                .rev()
-                                .collect();
-
-                            super_call = Some(SuperConstructorCall {
-                                parent_class: class_name.clone(),
-                                arguments: args,
-                            });
-                        }
+    /// Extract `this.field = <constant>` assignments and the super constructor call
+    /// from every constructor
+    ///
+    /// Only assignments whose value is a literal are recovered; anything computed
+    /// (random ranges, constructor arguments) is left out.
+    pub fn constructor_info(&self) -> BytecodeExtraction {
+        let mut assignments = Vec::new();
+        let mut super_call = None;
+
+        for method in self.methods.iter().filter(|m| m.name == "<init>") {
+            let Some(code) = method.code.as_deref() else {
+                continue;
+            };
+            // Constants pushed back to back, plus whether `this` was loaded just before them
+            let mut constants: Vec<ConstantValue> = Vec::new();
+            let mut after_this = false;
+            let mut constants_after_this = false;
+
+            for (pc, opcode) in instructions(code) {
+                if let Some(value) = self.push_constant(code, pc, opcode) {
+                    if constants.is_empty() {
+                        constants_after_this = after_this;
                     }
+                    constants.push(value);
+                    after_this = false;
+                    continue;
                 }
-                pc += 3;
-            }
 
-            // putfield: set instance field
-            0xb5 => {
-                if let Some(field_ref) = read_u2_index(bytecode, pc) {
-                    if let Some(value) = stack.pop() {
-                        if let Some(field_name) = field_names.get(&field_ref) {
+                match opcode {
+                    // putfield
+                    0xb5 if constants_after_this && constants.len() == 1 => {
+                        if let Some((_, name, descriptor)) =
+                            read_u2_index(code, pc).and_then(|i| self.member(i))
+                        {
                             assignments.push(FieldAssignment {
-                                field_name: field_name.clone(),
-                                value,
+                                field_name: name.to_string(),
+                                descriptor: descriptor.to_string(),
+                                value: constants[0],
                             });
                         }
                     }
+                    // invokespecial
+                    0xb7 if super_call.is_none() => {
+                        if let Some((class, name, _)) =
+                            read_u2_index(code, pc).and_then(|i| self.member(i))
+                        {
+                            if name == "<init>" && class != self.this_class {
+                                super_call = Some(SuperConstructorCall {
+                                    parent_class: class,
+                                    arguments: constants.clone(),
+                                });
+                            }
+                        }
+                    }
+                    _ => {}
                 }
-                pc += 3;
+
+                constants.clear();
+                after_this = opcode == 0x2a; // aload_0
             }
+        }
 
-            // aload_0 (load 'this'), aload_1, etc. - just skip
-            0x19 ..= 0x2d => { pc += if opcode == 0x19 { 2 } else { 1 }; }
+        BytecodeExtraction {
+            field_assignments: assignments,
+            super_call,
+        }
+    }
 
-            // Most other instructions - skip with varying lengths
-            _ => {
-                pc += instruction_length(opcode);
-            }
+    /// Constant pushed by the instruction at `pc`, if it is a constant push
+    fn push_constant(&self, code: &[u8], pc: usize, opcode: u8) -> Option<ConstantValue> {
+        match opcode {
+            // iconst_m1 through iconst_5
+            0x02..=0x08 => Some(ConstantValue::Int(opcode as i32 - 0x03)),
+            0x09 | 0x0a => Some(ConstantValue::Long(opcode as i64 - 0x09)),
+            0x0b..=0x0d => Some(ConstantValue::Float((opcode - 0x0b) as f32)),
+            0x0e | 0x0f => Some(ConstantValue::Double((opcode - 0x0e) as f64)),
+            // bipush
+            0x10 => code.get(pc + 1).map(|&b| ConstantValue::Int(b as i8 as i32)),
+            // sipush
+            0x11 => read_u2_index(code, pc).map(|v| ConstantValue::Int(v as i16 as i32)),
+            // ldc
+            0x12 => read_u1_index(code, pc).and_then(|i| self.value(i)),
+            // ldc_w / ldc2_w
+            0x13 | 0x14 => read_u2_index(code, pc).and_then(|i| self.value(i)),
+            _ => None,
         }
     }
+}
 
-    Ok(BytecodeExtraction {
-        field_assignments: assignments,
-        super_call,
+/// Parse a Java class file and extract field assignments from constructors
+pub fn extract_field_assignments(class_bytes: &[u8]) -> Result<Vec<FieldAssignment>> {
+    let extraction = extract_bytecode_info(class_bytes)?;
+    Ok(extraction.field_assignments)
+}
+
+/// Parse a Java class file and extract both field assignments and super constructor calls
+pub fn extract_bytecode_info(class_bytes: &[u8]) -> Result<BytecodeExtraction> {
+    Ok(ClassFile::parse(class_bytes)?.constructor_info())
+}
+
+fn skip_attributes(r: &mut Reader) -> Result<()> {
+    for _ in 0..r.u2()? {
+        r.u2()?;
+        let len = r.u4()? as usize;
+        r.take(len)?;
+    }
+    Ok(())
+}
+
+/// Arguments of each bootstrap method in a `BootstrapMethods` attribute
+fn parse_bootstrap_methods(data: &[u8]) -> Result<Vec<Vec<u16>>> {
+    let mut r = Reader { bytes: data, pos: 0 };
+    let mut methods = Vec::new();
+    for _ in 0..r.u2()? {
+        r.u2()?; // bootstrap method handle
+        let count = r.u2()?;
+        let arguments = (0..count).map(|_| r.u2()).collect::<Result<_>>()?;
+        methods.push(arguments);
+    }
+    Ok(methods)
+}
+
+/// Iterate over (pc, opcode) of each instruction, stopping at malformed code
+fn instructions(code: &[u8]) -> impl Iterator<Item = (usize, u8)> + '_ {
+    let mut pc = 0;
+    std::iter::from_fn(move || {
+        let opcode = *code.get(pc)?;
+        let current = pc;
+        pc += instruction_length(code, pc)?;
+        Some((current, opcode))
     })
 }
 
@@ -261,36 +493,111 @@ fn read_u2_index(bytecode: &[u8], pc: usize) -> Option<u16> {
     }
 }
 
-/// Get the length of a bytecode instruction
-fn instruction_length(opcode: u8) -> usize {
-    match opcode {
-        // Most instructions are 1 byte
-        0x00 ..= 0x0f => 1,  // nop, constants, loads
+fn read_i32(bytecode: &[u8], at: usize) -> Option<i32> {
+    let b = bytecode.get(at..at + 4)?;
+    Some(i32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// Get the length of the instruction at `pc`
+fn instruction_length(code: &[u8], pc: usize) -> Option<usize> {
+    let opcode = *code.get(pc)?;
+    Some(match opcode {
         0x10 => 2,           // bipush
         0x11 => 3,           // sipush
         0x12 => 2,           // ldc
         0x13 ..= 0x14 => 3,  // ldc_w, ldc2_w
         0x15 ..= 0x19 => 2,  // iload, lload, fload, dload, aload (with index)
-        0x1a ..= 0x35 => 1,  // typed loads/stores (no operands)
-        0x36 ..= 0x39 => 2,  // istore, lstore, fstore, dstore (with index)
-        0x3a ..= 0x95 => 1,  // more loads/stores, arithmetic, etc.
-        0x99 ..= 0x9e => 3,  // if comparisons
-        0xa7 => 3,           // goto
-        0xb2 ..= 0xb6 => 3,  // getstatic, putstatic, getfield, putfield, invokevirtual
-        0xb7 ..= 0xb9 => 3,  // invokespecial, invokestatic, invokeinterface
-        0xba => 5,           // invokedynamic
+        0x36 ..= 0x3a => 2,  // istore, lstore, fstore, dstore, astore (with index)
+        0x84 => 3,           // iinc
+        0x99 ..= 0xa8 => 3,  // if comparisons, goto, jsr
+        0xa9 => 2,           // ret
+        // tableswitch: padding, default, low, high, jump offsets
+        0xaa => {
+            let base = (pc + 4) & !3;
+            let low = read_i32(code, base + 4)?;
+            let high = read_i32(code, base + 8)?;
+            let count = usize::try_from(high.checked_sub(low)?).ok()? + 1;
+            base + 12 + count * 4 - pc
+        }
+        // lookupswitch: padding, default, npairs, match/offset pairs
+        0xab => {
+            let base = (pc + 4) & !3;
+            let pairs = usize::try_from(read_i32(code, base + 4)?).ok()?;
+            base + 8 + pairs * 8 - pc
+        }
+        0xb2 ..= 0xb8 => 3,  // field access, invokevirtual/special/static
+        0xb9 ..= 0xba => 5,  // invokeinterface, invokedynamic
         0xbb => 3,           // new
+        0xbc => 2,           // newarray
         0xbd => 3,           // anewarray
         0xc0 ..= 0xc1 => 3,  // checkcast, instanceof
+        // wide: widened load/store, or iinc with a 2-byte constant
+        0xc4 => if *code.get(pc + 1)? == 0x84 { 6 } else { 4 },
+        0xc5 => 4,           // multianewarray
         0xc6 ..= 0xc7 => 3,  // ifnull, ifnonnull
-        _ => 1,              // default to 1 byte
-    }
+        0xc8 ..= 0xc9 => 5,  // goto_w, jsr_w
+        _ => 1,
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Assemble a minimal class file from constant pool entries and methods
+    fn build_class(
+        pool: &[Vec<u8>],
+        this_class: u16,
+        super_class: u16,
+        methods: &[(u16, u16, Vec<u8>)],
+        code_name: u16,
+    ) -> Vec<u8> {
+        let mut out = vec![0xCA, 0xFE, 0xBA, 0xBE, 0, 0, 0, 61];
+        out.extend_from_slice(&(pool.len() as u16 + 1).to_be_bytes());
+        for entry in pool {
+            out.extend_from_slice(entry);
+        }
+        out.extend_from_slice(&[0, 0x21]);
+        out.extend_from_slice(&this_class.to_be_bytes());
+        out.extend_from_slice(&super_class.to_be_bytes());
+        out.extend_from_slice(&[0, 0, 0, 0]); // interfaces, fields
+        out.extend_from_slice(&(methods.len() as u16).to_be_bytes());
+        for (name, descriptor, code) in methods {
+            out.extend_from_slice(&[0, 1]);
+            out.extend_from_slice(&name.to_be_bytes());
+            out.extend_from_slice(&descriptor.to_be_bytes());
+            out.extend_from_slice(&[0, 1]);
+            out.extend_from_slice(&code_name.to_be_bytes());
+            out.extend_from_slice(&(code.len() as u32 + 12).to_be_bytes());
+            out.extend_from_slice(&[0, 4, 0, 4]);
+            out.extend_from_slice(&(code.len() as u32).to_be_bytes());
+            out.extend_from_slice(code);
+            out.extend_from_slice(&[0, 0, 0, 0]); // exception table, attributes
+        }
+        out.extend_from_slice(&[0, 0]);
+        out
+    }
+
+    fn utf8(s: &str) -> Vec<u8> {
+        let mut out = vec![1];
+        out.extend_from_slice(&(s.len() as u16).to_be_bytes());
+        out.extend_from_slice(s.as_bytes());
+        out
+    }
+
+    fn class_ref(name: u16) -> Vec<u8> {
+        let mut out = vec![7];
+        out.extend_from_slice(&name.to_be_bytes());
+        out
+    }
+
+    fn entry(tag: u8, a: u16, b: u16) -> Vec<u8> {
+        let mut out = vec![tag];
+        out.extend_from_slice(&a.to_be_bytes());
+        out.extend_from_slice(&b.to_be_bytes());
+        out
+    }
+
     #[test]
     fn test_constant_value_conversions() {
         let int_val = ConstantValue::Int(42);
@@ -301,4 +608,51 @@ mod tests {
         assert_eq!(float_val.as_f32(), Some(3.14));
         assert_eq!(float_val.as_i32(), Some(3));
     }
+
+    #[test]
+    fn test_constructor_constants_from_class_bytes() {
+        let pool = vec![
+            utf8("abc"),                           // 1
+            class_ref(1),                          // 2: Class abc
+            utf8("xyz"),                           // 3
+            class_ref(3),                          // 4: Class xyz
+            utf8("<init>"),                        // 5
+            utf8("()V"),                           // 6
+            entry(12, 5, 6),                       // 7: <init>()V
+            entry(10, 4, 7),                       // 8: xyz.<init>
+            utf8("u"),                             // 9
+            utf8("F"),                             // 10
+            entry(12, 9, 10),                      // 11: u F
+            entry(9, 2, 11),                       // 12: abc.u
+            entry(4, 0x3f40, 0x0000),              // 13: 0.75F
+            utf8("t"),                             // 14
+            utf8("I"),                             // 15
+            entry(12, 14, 15),                     // 16: t I
+            entry(9, 2, 16),                       // 17: abc.t
+            utf8("Code"),                          // 18
+        ];
+        let code = vec![
+            0x2a, 0xb7, 0, 8, // aload_0; invokespecial xyz.<init>
+            0x2a, 0x12, 13, 0xb5, 0, 12, // this.u = 0.75F
+            0x2a, 0x10, 40, 0xb5, 0, 17, // this.t = 40
+            // this.t = (int)(4.0F * 2) is not a literal and is skipped
+            0x2a, 0x12, 13, 0x05, 0x86, 0x6a, 0x8b, 0xb5, 0, 17,
+            0xb1,
+        ];
+        let bytes = build_class(&pool, 2, 4, &[(5, 6, code)], 18);
+
+        let class = ClassFile::parse(&bytes).unwrap();
+        assert_eq!(class.this_class, "abc");
+        assert_eq!(class.super_class.as_deref(), Some("xyz"));
+
+        let info = class.constructor_info();
+        let values: Vec<(String, f32)> = info
+            .field_assignments
+            .iter()
+            .map(|a| (a.field_name.clone(), a.value.as_f32().unwrap()))
+            .collect();
+        assert_eq!(values, vec![("u".to_string(), 0.75), ("t".to_string(), 40.0)]);
+        assert_eq!(info.field_assignments[0].descriptor, "F");
+        assert_eq!(info.super_call.unwrap().parent_class, "xyz");
+    }
 }
@@ -0,0 +1,503 @@
+/// Where the shareable caches live
+///
+/// Vanilla textures and decompiled sources take gigabytes and minutes to
+/// produce but are identical on every machine for a given Minecraft version, so
+/// they can be pointed at a shared folder (a NAS or network drive) from the
+/// settings and extracted once per household or lab. Machine-specific caches
+/// (scan index, thumbnails, build journals, tools) always stay local.
+///
+/// A shared folder that can't be written to is used read-only: caches already
+/// in it are read from there, anything missing is extracted into the local cache,
+/// and a local copy wins from then on. Whoever fills a cache holds a lock file
+/// next to it, so two machines, app windows or commands never extract into the
+/// same directory at once; whoever comes second gets a `CacheBusy` error. The
+/// holder keeps touching the lock while it works, so a lock that stops being
+/// touched, or whose process is gone from this machine, is taken over.
+use anyhow::{anyhow, bail, Context, Result};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::RwLock;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant, SystemTime};
+use tracing::info;
+
+/// A lock another machine has not touched for this long is assumed to belong
+/// to a machine that crashed or lost the network mid-extraction
+const LOCK_STALE_AFTER: Duration = Duration::from_secs(5 * 60);
+
+/// How often the holder touches its lock; well below `LOCK_STALE_AFTER`
+const LOCK_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long to wait for another machine to finish filling a cache
+const LOCK_WAIT: Duration = Duration::from_secs(20 * 60);
+
+const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone)]
+struct SharedRoot {
+    path: PathBuf,
+    writable: bool,
+}
+
+static SHARED_ROOT: Lazy<RwLock<Option<SharedRoot>>> = Lazy::new(|| RwLock::new(None));
+
+/// Current cache locations, as shown in the settings
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedCacheStatus {
+    /// Shared cache folder, if one is set
+    pub shared_path: Option<String>,
+    /// Whether this machine can write to the shared folder; false means caches
+    /// missing from it are extracted locally
+    pub writable: bool,
+    /// This machine's own cache folder
+    pub local_path: String,
+}
+
+/// This machine's weaverbird cache directory
+pub fn local_cache_root() -> Result<PathBuf> {
+    let cache_dir = dirs::cache_dir()
+        .ok_or_else(|| anyhow!("Could not find cache directory"))?
+        .join("weaverbird");
+
+    fs::create_dir_all(&cache_dir).context("Failed to create weaverbird cache directory")?;
+
+    Ok(cache_dir)
+}
+
+/// Use `path` as the shared cache root, or go back to local-only caches with None
+///
+/// The folder is probed for write access once here; a read-only folder is still
+/// accepted and used as described in the module docs.
+pub fn set_shared_cache_root(path: Option<&Path>) -> Result<SharedCacheStatus> {
+    let root = match path {
+        Some(path) => {
+            if !path.is_dir() {
+                bail!("Shared cache folder does not exist: {}", path.display());
+            }
+            let writable = probe_writable(path);
+            info!(
+                "Shared cache at {} ({})",
+                path.display(),
+                if writable { "read-write" } else { "read-only" }
+            );
+            Some(SharedRoot {
+                path: path.to_path_buf(),
+                writable,
+            })
+        }
+        None => {
+            info!("Shared cache disabled");
+            None
+        }
+    };
+    *SHARED_ROOT.write().unwrap_or_else(|e| e.into_inner()) = root;
+    shared_cache_status()
+}
+
+/// Where caches currently live
+pub fn shared_cache_status() -> Result<SharedCacheStatus> {
+    let shared = shared_root();
+    Ok(SharedCacheStatus {
+        shared_path: shared
+            .as_ref()
+            .map(|root| root.path.to_string_lossy().to_string()),
+        writable: shared.as_ref().is_some_and(|root| root.writable),
+        local_path: local_cache_root()?.to_string_lossy().to_string(),
+    })
+}
+
+fn shared_root() -> Option<SharedRoot> {
+    SHARED_ROOT
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Directory to read the shareable cache `name` (e.g. "vanilla_textures" or
+/// "decompiled/1.21.4") from
+///
+/// The directory isn't created, and may not exist yet.
+pub fn cache_dir(name: &str) -> Result<PathBuf> {
+    Ok(resolve(shared_root().as_ref(), &local_cache_root()?, name))
+}
+
+/// Directory to (re)build the shareable cache `name` in: the shared folder when
+/// it's writable, the local cache otherwise
+pub fn writable_cache_dir(name: &str) -> Result<PathBuf> {
+    let root = match shared_root() {
+        Some(shared) if shared.writable => shared.path,
+        _ => local_cache_root()?,
+    };
+    Ok(root.join(name))
+}
+
+fn resolve(shared: Option<&SharedRoot>, local_root: &Path, name: &str) -> PathBuf {
+    let local = local_root.join(name);
+    let Some(shared) = shared else {
+        return local;
+    };
+    let shared_dir = shared.path.join(name);
+    if shared.writable {
+        return shared_dir;
+    }
+    // Read-only: a local copy was extracted because the shared one was missing
+    // or outdated, so it takes precedence
+    if has_entries(&local) || !shared_dir.is_dir() {
+        local
+    } else {
+        shared_dir
+    }
+}
+
+fn has_entries(dir: &Path) -> bool {
+    fs::read_dir(dir)
+        .map(|mut entries| entries.next().is_some())
+        .unwrap_or(false)
+}
+
+fn probe_writable(dir: &Path) -> bool {
+    let probe = dir.join(format!(".weaverbird-write-test-{}", std::process::id()));
+    let writable = fs::write(&probe, b"").is_ok();
+    let _ = fs::remove_file(&probe);
+    writable
+}
+
+/// Error of a cache another extraction holds the lock of
+#[derive(Debug)]
+pub struct CacheBusy {
+    pub dir: PathBuf,
+}
+
+impl fmt::Display for CacheBusy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The cache at {} is busy: another extraction (in this or another Weaverbird \
+             window, or on another machine sharing it) is writing to it. Try again once it \
+             has finished.",
+            self.dir.display()
+        )
+    }
+}
+
+impl std::error::Error for CacheBusy {}
+
+/// Exclusive hold on a cache directory while it is being filled
+///
+/// The lock is a `<dir>.lock` file next to the directory, so it works across
+/// machines on a network share. It records the holder's pid and host, and a
+/// background thread refreshes its modification time until it is dropped,
+/// which releases it.
+#[derive(Debug)]
+pub struct CacheLock {
+    path: PathBuf,
+    stop_refresh: Option<mpsc::Sender<()>>,
+    refresher: Option<JoinHandle<()>>,
+}
+
+impl CacheLock {
+    /// Take the lock for `dir`, waiting while another process holds it
+    ///
+    /// Locks whose process has exited on this machine, or that another machine
+    /// stopped refreshing a few minutes ago, are taken over.
+    pub fn acquire(dir: &Path) -> Result<Self> {
+        Self::acquire_within(dir, LOCK_WAIT)
+    }
+
+    /// Take the lock for `dir`, failing with `CacheBusy` right away if another
+    /// process holds it
+    pub fn try_acquire(dir: &Path) -> Result<Self> {
+        Self::acquire_within(dir, Duration::ZERO)
+    }
+
+    fn acquire_within(dir: &Path, wait: Duration) -> Result<Self> {
+        Self::acquire_refreshing(dir, wait, LOCK_REFRESH_INTERVAL)
+    }
+
+    fn acquire_refreshing(dir: &Path, wait: Duration, refresh: Duration) -> Result<Self> {
+        let path = lock_path(dir);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create cache directory")?;
+        }
+        let started = Instant::now();
+        let mut announced = false;
+        loop {
+            match fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(mut file) => {
+                    let _ = write!(file, "pid {}\nhost {}\n", std::process::id(), host_name());
+                    let (stop_refresh, stopped) = mpsc::channel::<()>();
+                    let refreshed = path.clone();
+                    let refresher = std::thread::spawn(move || {
+                        while let Err(mpsc::RecvTimeoutError::Timeout) =
+                            stopped.recv_timeout(refresh)
+                        {
+                            touch(&refreshed);
+                        }
+                    });
+                    return Ok(Self {
+                        path,
+                        stop_refresh: Some(stop_refresh),
+                        refresher: Some(refresher),
+                    });
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    if is_stale(&path) {
+                        info!("Taking over stale lock {}", path.display());
+                        let _ = fs::remove_file(&path);
+                        continue;
+                    }
+                    if started.elapsed() >= wait {
+                        return Err(CacheBusy {
+                            dir: dir.to_path_buf(),
+                        }
+                        .into());
+                    }
+                    if !announced {
+                        info!("Waiting for {} to be released", path.display());
+                        announced = true;
+                    }
+                    std::thread::sleep(LOCK_POLL_INTERVAL);
+                }
+                Err(e) => {
+                    return Err(e).with_context(|| format!("Failed to lock {}", dir.display()));
+                }
+            }
+        }
+    }
+}
+
+impl Drop for CacheLock {
+    fn drop(&mut self) {
+        // Stop refreshing first so the thread can't touch a lock someone else
+        // took after the removal
+        drop(self.stop_refresh.take());
+        if let Some(refresher) = self.refresher.take() {
+            let _ = refresher.join();
+        }
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn lock_path(dir: &Path) -> PathBuf {
+    let mut name = dir
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_default();
+    name.push(".lock");
+    dir.with_file_name(name)
+}
+
+/// Whether a lock can be taken over
+///
+/// A lock taken on this machine is stale once its process has exited; one
+/// from another machine (or without a recorded host) once it stopped being
+/// refreshed for `LOCK_STALE_AFTER`.
+fn is_stale(lock: &Path) -> bool {
+    let contents = fs::read_to_string(lock).unwrap_or_default();
+    let field = |key: &str| {
+        contents
+            .lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix(' '))
+            .map(str::trim)
+    };
+    if let (Some(pid), Some(host)) = (field("pid").and_then(|pid| pid.parse().ok()), field("host"))
+    {
+        if host == host_name() {
+            return !process_alive(pid);
+        }
+    }
+
+    fs::metadata(lock)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age > LOCK_STALE_AFTER)
+}
+
+/// Bump a lock's modification time so other machines see it is still held
+fn touch(lock: &Path) {
+    if let Ok(file) = fs::OpenOptions::new().write(true).open(lock) {
+        let _ = file.set_modified(SystemTime::now());
+    }
+}
+
+/// Name of this machine, as recorded in lock files
+fn host_name() -> String {
+    static HOST: Lazy<String> = Lazy::new(|| {
+        std::env::var("COMPUTERNAME")
+            .ok()
+            .filter(|_| cfg!(windows))
+            .or_else(|| fs::read_to_string("/proc/sys/kernel/hostname").ok())
+            .or_else(|| {
+                std::process::Command::new("hostname")
+                    .output()
+                    .ok()
+                    .filter(|output| output.status.success())
+                    .and_then(|output| String::from_utf8(output.stdout).ok())
+            })
+            .map(|name| name.trim().to_string())
+            .unwrap_or_default()
+    });
+    HOST.clone()
+}
+
+/// Whether a process with this pid is running on this machine
+///
+/// Errs on the side of "alive" when it can't tell, so a live lock is never
+/// taken over.
+#[cfg(unix)]
+fn process_alive(pid: u32) -> bool {
+    if Path::new("/proc/self").exists() {
+        return Path::new(&format!("/proc/{}", pid)).exists();
+    }
+    std::process::Command::new("ps")
+        .args(["-p", &pid.to_string()])
+        .output()
+        .map_or(true, |output| output.status.success())
+}
+
+#[cfg(windows)]
+fn process_alive(pid: u32) -> bool {
+    use windows_sys::Win32::Foundation::{
+        CloseHandle, GetLastError, ERROR_ACCESS_DENIED, STILL_ACTIVE,
+    };
+    use windows_sys::Win32::System::Threading::{
+        GetExitCodeProcess, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    // SAFETY: the handle is checked before use and closed once queried
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle.is_null() {
+            // Processes of other users can't be opened but do exist
+            return GetLastError() == ERROR_ACCESS_DENIED;
+        }
+        let mut code = 0;
+        let queried = GetExitCodeProcess(handle, &mut code) != 0;
+        CloseHandle(handle);
+        !queried || code == STILL_ACTIVE as u32
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn process_alive(_pid: u32) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_and_lock() {
+        let temp_dir = std::env::temp_dir().join("test_cache_location");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let local = temp_dir.join("local");
+        let shared_path = temp_dir.join("shared");
+        fs::create_dir_all(shared_path.join("vanilla_textures")).unwrap();
+        fs::write(
+            shared_path.join("vanilla_textures/.extracted_version"),
+            "1.21.4",
+        )
+        .unwrap();
+
+        let writable = SharedRoot {
+            path: shared_path.clone(),
+            writable: true,
+        };
+        let read_only = SharedRoot {
+            path: shared_path.clone(),
+            writable: false,
+        };
+        let no_share = resolve(None, &local, "vanilla_textures");
+        let shared_rw = resolve(Some(&writable), &local, "decompiled/1.21.4");
+        let shared_ro = resolve(Some(&read_only), &local, "vanilla_textures");
+        let missing_ro = resolve(Some(&read_only), &local, "decompiled/1.21.4");
+        // Once extracted locally, the local copy wins over the read-only share
+        fs::create_dir_all(local.join("vanilla_textures")).unwrap();
+        fs::write(local.join("vanilla_textures/.extracted_version"), "1.21.5").unwrap();
+        let local_ro = resolve(Some(&read_only), &local, "vanilla_textures");
+
+        let cache = shared_path.join("vanilla_textures");
+        let lock = CacheLock::acquire_within(&cache, Duration::ZERO).unwrap();
+        let lock_existed = shared_path.join("vanilla_textures.lock").exists();
+        let contended = CacheLock::try_acquire(&cache);
+        drop(lock);
+        let reacquired = CacheLock::acquire_within(&cache, Duration::ZERO).is_ok();
+        let lock_removed = !shared_path.join("vanilla_textures.lock").exists();
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(no_share, local.join("vanilla_textures"));
+        assert_eq!(shared_rw, shared_path.join("decompiled/1.21.4"));
+        assert_eq!(shared_ro, shared_path.join("vanilla_textures"));
+        assert_eq!(missing_ro, local.join("decompiled/1.21.4"));
+        assert_eq!(local_ro, local.join("vanilla_textures"));
+        assert!(lock_existed);
+        assert!(contended.unwrap_err().downcast_ref::<CacheBusy>().is_some());
+        assert!(reacquired);
+        assert!(lock_removed);
+    }
+
+    #[test]
+    fn test_lock_staleness_and_refresh() {
+        let temp_dir = std::env::temp_dir().join("test_cache_lock_stale");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+        let cache = temp_dir.join("decompiled");
+        let lock = temp_dir.join("decompiled.lock");
+        let hour_ago = SystemTime::now() - Duration::from_secs(60 * 60);
+
+        // A fresh lock of a process that no longer runs on this machine
+        fs::write(&lock, format!("pid {}\nhost {}\n", u32::MAX, host_name())).unwrap();
+        let dead_same_host = is_stale(&lock);
+        let taken_over = CacheLock::try_acquire(&cache).is_ok();
+
+        // Another machine's lock only goes stale once it stops being refreshed
+        fs::write(
+            &lock,
+            format!("pid {}\nhost other-{}\n", u32::MAX, host_name()),
+        )
+        .unwrap();
+        let fresh_other_host = is_stale(&lock);
+        let old = fs::OpenOptions::new().write(true).open(&lock).unwrap();
+        old.set_modified(hour_ago).unwrap();
+        drop(old);
+        let old_other_host = is_stale(&lock);
+        fs::remove_file(&lock).unwrap();
+
+        // A held lock stays live on this machine and keeps being refreshed
+        let held = CacheLock::acquire_refreshing(&cache, Duration::ZERO, Duration::from_millis(20))
+            .unwrap();
+        let held_same_host = is_stale(&lock);
+        let backdated = fs::OpenOptions::new().write(true).open(&lock).unwrap();
+        backdated.set_modified(hour_ago).unwrap();
+        drop(backdated);
+        std::thread::sleep(Duration::from_millis(300));
+        let refreshed_age = SystemTime::now()
+            .duration_since(fs::metadata(&lock).unwrap().modified().unwrap())
+            .unwrap_or_default();
+        drop(held);
+        let released = !lock.exists();
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert!(dead_same_host);
+        assert!(taken_over);
+        assert!(!fresh_other_host);
+        assert!(old_other_host);
+        assert!(!held_same_host);
+        assert!(refreshed_age < LOCK_STALE_AFTER);
+        assert!(released);
+    }
+}
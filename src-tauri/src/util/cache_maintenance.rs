@@ -0,0 +1,282 @@
+/// Periodic cache maintenance, opt-in from the settings
+///
+/// Caches only ever grow on their own: a thumbnail for every texture and size
+/// shown, gigabytes of decompiled sources per Minecraft version, and the odd
+/// partial download or editing workspace left behind by a crash. While
+/// maintenance is enabled, a background thread regularly deletes thumbnails
+/// not shown within their TTL, decompiled sources of versions not used within
+/// theirs and orphaned temp files, then reports the space reclaimed to the
+/// listener given to `start` (the app emits it as an event). A TTL of zero
+/// keeps that cache.
+use crate::util::cache_usage::{self, CacheCategory};
+use crate::util::{asset_editor, cache_location, thumbnails};
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+use tracing::{info, warn};
+use walkdir::WalkDir;
+
+/// How often the background thread checks whether maintenance is due
+const CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Time between two maintenance runs
+const RUN_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Temp files are looked for this deep in the cache root; partial downloads
+/// and thumbnails sit one folder down
+const TEMP_SEARCH_DEPTH: usize = 2;
+
+/// Suffixes of files only ever left behind by an interrupted write
+const TEMP_SUFFIXES: [&str; 2] = [".part", ".partial"];
+
+/// Prefix of the probes `cache_location` writes to test a folder
+const WRITE_PROBE_PREFIX: &str = ".weaverbird-write-test-";
+
+static SETTINGS: Lazy<Mutex<MaintenanceSettings>> =
+    Lazy::new(|| Mutex::new(MaintenanceSettings::default()));
+
+static STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Cache TTLs; a TTL of zero keeps that cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceSettings {
+    /// Whether the background task runs at all
+    pub enabled: bool,
+    /// Thumbnails not shown for this many days are deleted
+    pub thumbnail_ttl_days: u32,
+    /// Decompiled sources of versions not used for this many days are deleted
+    pub decompiled_ttl_days: u32,
+    /// Partial downloads and editing workspaces older than this are deleted
+    pub temp_ttl_hours: u32,
+}
+
+impl Default for MaintenanceSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            thumbnail_ttl_days: 30,
+            decompiled_ttl_days: 60,
+            temp_ttl_hours: 24,
+        }
+    }
+}
+
+/// Space reclaimed by a maintenance run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceReport {
+    pub thumbnail_bytes: u64,
+    pub decompiled_bytes: u64,
+    /// Minecraft versions whose decompiled sources were deleted
+    pub decompiled_versions: Vec<String>,
+    pub temp_bytes: u64,
+    pub reclaimed_bytes: u64,
+}
+
+/// Replace the TTLs and turn the background task on or off
+pub fn set_settings(settings: MaintenanceSettings) {
+    *SETTINGS.lock().unwrap_or_else(|e| e.into_inner()) = settings;
+    info!("Settings now {:?}", settings);
+}
+
+/// TTLs currently in effect
+pub fn settings() -> MaintenanceSettings {
+    *SETTINGS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Start the background task, which runs maintenance every few hours while
+/// it is enabled and passes each report to `listener`; later calls do nothing
+pub fn start(listener: impl Fn(&MaintenanceReport) + Send + 'static) {
+    if STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    std::thread::spawn(move || {
+        let mut last_run: Option<Instant> = None;
+        loop {
+            std::thread::sleep(CHECK_INTERVAL);
+            let settings = settings();
+            let due = last_run.map_or(true, |time| time.elapsed() >= RUN_INTERVAL);
+            if !settings.enabled || !due {
+                continue;
+            }
+            last_run = Some(Instant::now());
+            match run(&settings) {
+                Ok(report) => listener(&report),
+                Err(e) => warn!("Maintenance failed: {:#}", e),
+            }
+        }
+    });
+}
+
+/// Prune every cache past its TTL now
+pub fn run(settings: &MaintenanceSettings) -> Result<MaintenanceReport> {
+    let mut report = MaintenanceReport::default();
+
+    if settings.thumbnail_ttl_days > 0 {
+        report.thumbnail_bytes = prune_thumbnails(
+            &thumbnails::thumbnail_cache_dir()?,
+            days(settings.thumbnail_ttl_days),
+        );
+    }
+    if settings.decompiled_ttl_days > 0 {
+        let (versions, bytes) = cache_usage::clear_unused_versions(
+            CacheCategory::DecompiledSources,
+            days(settings.decompiled_ttl_days),
+        )?;
+        report.decompiled_versions = versions;
+        report.decompiled_bytes = bytes;
+    }
+    if settings.temp_ttl_hours > 0 {
+        let max_age = Duration::from_secs(u64::from(settings.temp_ttl_hours) * 60 * 60);
+        report.temp_bytes = prune_temp_files(&cache_location::local_cache_root()?, max_age)
+            + prune_edit_workspaces(&asset_editor::workspace_dir(), max_age);
+    }
+
+    report.reclaimed_bytes = report.thumbnail_bytes + report.decompiled_bytes + report.temp_bytes;
+    info!(
+        "Reclaimed {} bytes ({} thumbnails, {} decompiled, {} temp)",
+        report.reclaimed_bytes, report.thumbnail_bytes, report.decompiled_bytes, report.temp_bytes
+    );
+    Ok(report)
+}
+
+fn days(days: u32) -> Duration {
+    Duration::from_secs(u64::from(days) * 24 * 60 * 60)
+}
+
+/// Whether `path` was last modified at least `max_age` ago
+fn is_older_than(path: &Path, max_age: Duration) -> bool {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age >= max_age)
+}
+
+/// Delete a file, returning its size, or 0 if it couldn't be deleted
+fn remove_file(path: &Path) -> u64 {
+    let size = fs::metadata(path)
+        .map(|metadata| metadata.len())
+        .unwrap_or(0);
+    match fs::remove_file(path) {
+        Ok(()) => size,
+        Err(_) => 0,
+    }
+}
+
+/// Delete thumbnails not shown within `max_age`, with their .mcmeta copies
+fn prune_thumbnails(dir: &Path, max_age: Duration) -> u64 {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    let mut freed = 0;
+    for path in entries.flatten().map(|entry| entry.path()) {
+        let is_png = path.extension().is_some_and(|extension| extension == "png");
+        if is_png && is_older_than(&path, max_age) {
+            freed += remove_file(&path);
+            freed += remove_file(&path.with_extension("png.mcmeta"));
+        }
+    }
+    freed
+}
+
+/// Delete partial downloads, partial thumbnails and write probes older than
+/// `max_age` from the cache root
+fn prune_temp_files(cache_root: &Path, max_age: Duration) -> u64 {
+    WalkDir::new(cache_root)
+        .max_depth(TEMP_SEARCH_DEPTH)
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            TEMP_SUFFIXES.iter().any(|suffix| name.ends_with(suffix))
+                || name.starts_with(WRITE_PROBE_PREFIX)
+        })
+        .filter(|entry| is_older_than(entry.path(), max_age))
+        .map(|entry| remove_file(entry.path()))
+        .sum()
+}
+
+/// Delete the editing workspaces of sessions that are no longer open and
+/// weren't touched within `max_age`
+fn prune_edit_workspaces(workspace: &Path, max_age: Duration) -> u64 {
+    let Ok(entries) = fs::read_dir(workspace) else {
+        return 0;
+    };
+    let mut freed = 0;
+    for entry in entries.flatten() {
+        let session_id = entry.file_name().to_string_lossy().to_string();
+        if asset_editor::get_session(&session_id).is_some() {
+            continue;
+        }
+        let files: Vec<_> = WalkDir::new(entry.path())
+            .into_iter()
+            .flatten()
+            .filter(|file| file.file_type().is_file())
+            .collect();
+        if files.iter().all(|file| is_older_than(file.path(), max_age))
+            && is_older_than(&entry.path(), max_age)
+        {
+            let size: u64 = files
+                .iter()
+                .filter_map(|file| file.metadata().ok())
+                .map(|metadata| metadata.len())
+                .sum();
+            if fs::remove_dir_all(entry.path()).is_ok() {
+                freed += size;
+            }
+        }
+    }
+    freed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn age(path: &Path, age: Duration) {
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(SystemTime::now() - age)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_prune_old_files() {
+        let temp_dir = std::env::temp_dir().join("test_cache_maintenance");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let thumbnails = temp_dir.join("thumbnails");
+        fs::create_dir_all(&thumbnails).unwrap();
+
+        for name in ["old.png", "old.png.mcmeta", "new.png", "stale.png.partial"] {
+            fs::write(thumbnails.join(name), b"0123456789").unwrap();
+        }
+        fs::write(temp_dir.join("1.21.4-mappings.txt.part"), b"0123").unwrap();
+        fs::write(temp_dir.join("1.21.4-mappings.txt"), b"0123").unwrap();
+        for name in ["old.png", "stale.png.partial"] {
+            age(&thumbnails.join(name), days(3));
+        }
+        age(&temp_dir.join("1.21.4-mappings.txt.part"), days(3));
+        age(&temp_dir.join("1.21.4-mappings.txt"), days(3));
+
+        // The .mcmeta goes with its thumbnail, however new it is
+        assert_eq!(prune_thumbnails(&thumbnails, days(2)), 20);
+        assert!(!thumbnails.join("old.png.mcmeta").exists());
+        assert!(thumbnails.join("new.png").exists());
+
+        assert_eq!(prune_temp_files(&temp_dir, days(2)), 14);
+        assert!(!thumbnails.join("stale.png.partial").exists());
+        assert!(temp_dir.join("1.21.4-mappings.txt").exists());
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+}
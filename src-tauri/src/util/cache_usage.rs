@@ -0,0 +1,395 @@
+/// Disk usage of the extraction caches, and clearing them selectively
+///
+/// Everything here can be rebuilt: vanilla textures from the local JAR,
+/// decompiled sources, mappings and CFR by downloading and decompiling again.
+/// Decompiled sources alone take gigabytes per version, so the settings show
+/// what each category takes and let the user drop a category or one version
+/// of it.
+///
+/// Only copies this machine may write are removed; a read-only shared cache
+/// (see `cache_location`) is reported but left alone.
+use crate::util::{cache_location, vanilla_textures};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tracing::{info, warn};
+use walkdir::WalkDir;
+
+/// Cache folders of the data extracted from decompiled sources, one
+/// `<version>.json` per version
+const DATA_DIRS: [&str; 4] = [
+    "particle_physics",
+    "block_emissions",
+    "block_animations",
+    "particle_data",
+];
+
+/// Data folders that also held decompiled sources before they were shared
+const LEGACY_DECOMPILE_DIRS: [&str; 3] =
+    ["particle_physics", "block_emissions", "block_animations"];
+
+/// Shared cache holding one decompiled source tree per version
+const DECOMPILED_CACHE_NAME: &str = "decompiled";
+
+/// Mappings are downloaded next to the physics data as `<version>-mappings.txt`
+const MAPPINGS_DIR: &str = "particle_physics";
+const MAPPINGS_SUFFIX: &str = "-mappings.txt";
+
+const TOOLS_DIR: &str = "tools";
+const CFR_FILE: &str = "cfr.jar";
+
+/// Marker whose modification time records when a cache folder was last used
+const LAST_USED_FILE: &str = ".last-used";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheCategory {
+    /// Textures, models and built-in packs extracted from the Minecraft JAR
+    VanillaTextures,
+    /// Minecraft source decompiled with CFR
+    DecompiledSources,
+    /// Particle physics, block emissions, animations and particle textures
+    /// extracted from the decompiled sources
+    ExtractedData,
+    /// Mojang's mappings
+    Mappings,
+    /// The CFR decompiler
+    Cfr,
+}
+
+impl CacheCategory {
+    pub const ALL: [CacheCategory; 5] = [
+        CacheCategory::VanillaTextures,
+        CacheCategory::DecompiledSources,
+        CacheCategory::ExtractedData,
+        CacheCategory::Mappings,
+        CacheCategory::Cfr,
+    ];
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionUsage {
+    pub version: String,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryUsage {
+    pub category: CacheCategory,
+    pub bytes: u64,
+    /// Per Minecraft version, largest first; empty for CFR
+    pub versions: Vec<VersionUsage>,
+}
+
+/// Result of `cache_usage`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheUsage {
+    pub total_bytes: u64,
+    pub categories: Vec<CategoryUsage>,
+}
+
+/// Files or folders making up one version of a category (or all of it, for
+/// unversioned caches)
+#[derive(Debug, Default)]
+struct CachedItem {
+    version: Option<String>,
+    paths: Vec<PathBuf>,
+}
+
+/// How much disk space each cache category takes
+pub fn cache_usage() -> Result<CacheUsage> {
+    let mut categories = Vec::new();
+    for category in CacheCategory::ALL {
+        let mut versions: Vec<VersionUsage> = Vec::new();
+        let mut bytes = 0;
+        for item in locate(category)? {
+            let size: u64 = item.paths.iter().map(|path| disk_size(path)).sum();
+            bytes += size;
+            if let Some(version) = item.version {
+                versions.push(VersionUsage {
+                    version,
+                    bytes: size,
+                });
+            }
+        }
+        versions.sort_by(|a, b| {
+            b.bytes
+                .cmp(&a.bytes)
+                .then_with(|| a.version.cmp(&b.version))
+        });
+        categories.push(CategoryUsage {
+            category,
+            bytes,
+            versions,
+        });
+    }
+
+    Ok(CacheUsage {
+        total_bytes: categories.iter().map(|category| category.bytes).sum(),
+        categories,
+    })
+}
+
+/// Delete a cache category, or only one version of it
+///
+/// # Arguments
+/// * `category` - Category to clear
+/// * `version` - Minecraft version to clear; None clears every version. CFR
+///   isn't versioned and is cleared either way.
+///
+/// # Returns
+/// Bytes freed
+pub fn clear_cache(category: CacheCategory, version: Option<&str>) -> Result<u64> {
+    let removable_roots = removable_roots()?;
+    let mut freed = 0;
+    for item in locate(category)? {
+        if version.is_some() && item.version.is_some() && item.version.as_deref() != version {
+            continue;
+        }
+        for path in item.paths {
+            if !removable_roots.iter().any(|root| path.starts_with(root)) {
+                info!("Leaving read-only shared cache {}", path.display());
+                continue;
+            }
+            freed += disk_size(&path);
+            remove(&path)?;
+        }
+    }
+    info!(
+        "Cleared {:?} ({}), freed {} bytes",
+        category,
+        version.unwrap_or("all versions"),
+        freed
+    );
+    Ok(freed)
+}
+
+/// Delete the versions of a category not used for `max_age`
+///
+/// A version was last used when its newest folder or file was modified, or
+/// its `mark_used` marker written. Versions another extraction holds are left
+/// for next time.
+///
+/// # Returns
+/// The versions deleted and the bytes freed
+pub fn clear_unused_versions(
+    category: CacheCategory,
+    max_age: Duration,
+) -> Result<(Vec<String>, u64)> {
+    let now = SystemTime::now();
+    let mut cleared = Vec::new();
+    let mut freed = 0;
+    for item in locate(category)? {
+        let Some(version) = item.version else {
+            continue;
+        };
+        let last_used = item.paths.iter().filter_map(|path| last_used(path)).max();
+        let unused = last_used
+            .and_then(|time| now.duration_since(time).ok())
+            .is_some_and(|age| age >= max_age);
+        if !unused {
+            continue;
+        }
+        match clear_cache(category, Some(&version)) {
+            Ok(bytes) => {
+                freed += bytes;
+                cleared.push(version);
+            }
+            Err(e) => warn!("Leaving {:?} {} for now: {:#}", category, version, e),
+        }
+    }
+    Ok((cleared, freed))
+}
+
+/// Record that a cache folder is in use, for `clear_unused_versions`; a
+/// read-only shared folder is left as is
+pub fn mark_used(dir: &Path) {
+    let _ = fs::write(dir.join(LAST_USED_FILE), b"");
+}
+
+fn last_used(path: &Path) -> Option<SystemTime> {
+    let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+    modified(path).max(modified(&path.join(LAST_USED_FILE)))
+}
+
+/// Cache roots this machine may delete from
+fn removable_roots() -> Result<Vec<PathBuf>> {
+    let local_root = cache_location::local_cache_root()?;
+    let writable_root = cache_location::writable_cache_dir("")?;
+    Ok(vec![local_root, writable_root])
+}
+
+fn remove(path: &Path) -> Result<()> {
+    if path.is_dir() {
+        // Don't pull a cache out from under an extraction filling it
+        let _lock = cache_location::CacheLock::acquire(path)?;
+        fs::remove_dir_all(path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    } else if path.exists() {
+        fs::remove_file(path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Everything cached for a category, grouped by version
+fn locate(category: CacheCategory) -> Result<Vec<CachedItem>> {
+    let local_root = cache_location::local_cache_root()?;
+    let items = match category {
+        CacheCategory::VanillaTextures => {
+            // One folder per version, for the textures and the built-in packs
+            let mut versions: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+            for name in [
+                vanilla_textures::VANILLA_CACHE_NAME,
+                vanilla_textures::BUILTIN_PACKS_CACHE_NAME,
+            ] {
+                for dir in shareable_dirs(&local_root, name)? {
+                    for (version, path) in subdirs(&dir) {
+                        versions.entry(version).or_default().push(path);
+                    }
+                }
+            }
+            by_version(versions)
+        }
+        CacheCategory::DecompiledSources => {
+            let mut versions: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+            for dir in shareable_dirs(&local_root, DECOMPILED_CACHE_NAME)? {
+                for (version, path) in subdirs(&dir) {
+                    versions.entry(version).or_default().push(path);
+                }
+            }
+            for legacy in LEGACY_DECOMPILE_DIRS {
+                for (version, path) in subdirs(&local_root.join(legacy).join("decompiled")) {
+                    versions.entry(version).or_default().push(path);
+                }
+            }
+            by_version(versions)
+        }
+        CacheCategory::ExtractedData => {
+            let mut versions: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+            for dir in DATA_DIRS {
+                for (name, path) in files(&local_root.join(dir)) {
+                    if let Some(version) = name.strip_suffix(".json") {
+                        versions.entry(version.to_string()).or_default().push(path);
+                    }
+                }
+            }
+            by_version(versions)
+        }
+        CacheCategory::Mappings => {
+            let mut versions: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+            for (name, path) in files(&local_root.join(MAPPINGS_DIR)) {
+                // Includes partial downloads and their sidecars
+                if let Some((version, _)) = name.split_once(MAPPINGS_SUFFIX) {
+                    versions.entry(version.to_string()).or_default().push(path);
+                }
+            }
+            by_version(versions)
+        }
+        CacheCategory::Cfr => vec![CachedItem {
+            version: None,
+            paths: files(&local_root.join(TOOLS_DIR))
+                .into_iter()
+                .filter(|(name, _)| name.starts_with(CFR_FILE))
+                .map(|(_, path)| path)
+                .collect(),
+        }],
+    };
+    Ok(items)
+}
+
+/// The copy of a shareable cache in effect, and the local one if different
+fn shareable_dirs(local_root: &Path, name: &str) -> Result<Vec<PathBuf>> {
+    let mut dirs = vec![cache_location::cache_dir(name)?];
+    let local = local_root.join(name);
+    if !dirs.contains(&local) {
+        dirs.push(local);
+    }
+    dirs.retain(|dir| dir.exists());
+    Ok(dirs)
+}
+
+fn by_version(versions: BTreeMap<String, Vec<PathBuf>>) -> Vec<CachedItem> {
+    versions
+        .into_iter()
+        .map(|(version, paths)| CachedItem {
+            version: Some(version),
+            paths,
+        })
+        .collect()
+}
+
+/// (name, path) of the folders directly in `dir`
+fn subdirs(dir: &Path) -> Vec<(String, PathBuf)> {
+    entries(dir, true)
+}
+
+/// (name, path) of the files directly in `dir`
+fn files(dir: &Path) -> Vec<(String, PathBuf)> {
+    entries(dir, false)
+}
+
+fn entries(dir: &Path, want_dirs: bool) -> Vec<(String, PathBuf)> {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    read_dir
+        .flatten()
+        .filter(|entry| {
+            entry
+                .file_type()
+                .is_ok_and(|kind| kind.is_dir() == want_dirs)
+        })
+        .map(|entry| {
+            (
+                entry.file_name().to_string_lossy().to_string(),
+                entry.path(),
+            )
+        })
+        .collect()
+}
+
+/// Size of a file, or of everything under a folder
+fn disk_size(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disk_size_and_entries() {
+        let temp_dir = std::env::temp_dir().join("test_cache_usage");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(temp_dir.join("1.21.4/net")).unwrap();
+        fs::write(temp_dir.join("1.21.4/net/Main.java"), b"class Main {}").unwrap();
+        fs::write(temp_dir.join("1.21.4-mappings.txt"), b"mappings").unwrap();
+        fs::write(temp_dir.join("1.21.4-mappings.txt.part"), b"map").unwrap();
+
+        let size = disk_size(&temp_dir);
+        let dirs = subdirs(&temp_dir);
+        let mut mapping_versions: Vec<String> = files(&temp_dir)
+            .into_iter()
+            .filter_map(|(name, _)| Some(name.split_once(MAPPINGS_SUFFIX)?.0.to_string()))
+            .collect();
+        mapping_versions.sort();
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(size, 13 + 8 + 3);
+        assert_eq!(dirs.len(), 1);
+        assert_eq!(dirs[0].0, "1.21.4");
+        assert_eq!(mapping_versions, vec!["1.21.4", "1.21.4"]);
+    }
+}
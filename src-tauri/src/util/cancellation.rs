@@ -0,0 +1,117 @@
+/// Cancelling long-running commands (scans, nest builds, vanilla extraction)
+///
+/// A command started with a job ID runs inside `run_job`, which registers a
+/// token under that ID and makes it the calling thread's current token.
+/// Workers pick it up with `current()` before fanning out to rayon or scoped
+/// threads, and call `check()` between files, so `cancel` stops them within a
+/// file or so. Code running outside a job gets a token that is never
+/// cancelled, so helpers shared with other commands need no extra parameter.
+use anyhow::{bail, Result};
+use once_cell::sync::Lazy;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::info;
+
+static JOBS: Lazy<Mutex<HashMap<String, CancelToken>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancelToken>> = const { RefCell::new(None) };
+}
+
+/// Message of the error workers return once cancelled
+pub const CANCELLED_MESSAGE: &str = "Cancelled";
+
+/// Shared flag telling a job's workers to stop
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Fail with `CANCELLED_MESSAGE` once the job was cancelled
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            bail!(CANCELLED_MESSAGE);
+        }
+        Ok(())
+    }
+
+    fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Token of the job running on this thread, or one that is never cancelled
+pub fn current() -> CancelToken {
+    CURRENT.with(|current| current.borrow().clone().unwrap_or_default())
+}
+
+/// Run `op` with `token` as this thread's current token, e.g. on a pool
+/// thread working for a job
+pub fn with_token<R>(token: CancelToken, op: impl FnOnce() -> R) -> R {
+    let previous = CURRENT.with(|current| current.replace(Some(token)));
+    let result = op();
+    CURRENT.with(|current| *current.borrow_mut() = previous);
+    result
+}
+
+/// Run `op` as the job `job_id`, cancellable with `cancel` until it returns
+///
+/// Returns the result and whether the job was cancelled; a cancelled job's
+/// result is usually the error of the worker that noticed.
+pub fn run_job<R>(job_id: &str, op: impl FnOnce() -> R) -> (R, bool) {
+    let token = CancelToken::default();
+    JOBS.lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(job_id.to_string(), token.clone());
+
+    let result = with_token(token.clone(), op);
+
+    JOBS.lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(job_id);
+    (result, token.is_cancelled())
+}
+
+/// Ask a running job to stop
+///
+/// Returns false if no job with that ID is running (e.g. it already finished).
+pub fn cancel(job_id: &str) -> bool {
+    let jobs = JOBS.lock().unwrap_or_else(|e| e.into_inner());
+    match jobs.get(job_id) {
+        Some(token) => {
+            info!("Cancelling job {}", job_id);
+            token.cancel();
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_running_job() {
+        assert!(current().check().is_ok());
+
+        let (result, cancelled) = run_job("test-cancel-job", || {
+            let token = current();
+            assert!(token.check().is_ok());
+            assert!(cancel("test-cancel-job"));
+            // Workers on other threads see the same flag
+            std::thread::spawn(move || token.check()).join().unwrap()
+        });
+
+        assert!(cancelled);
+        assert_eq!(result.unwrap_err().to_string(), CANCELLED_MESSAGE);
+        // Finished jobs are forgotten, and the thread has no job anymore
+        assert!(!cancel("test-cancel-job"));
+        assert!(!current().is_cancelled());
+    }
+}
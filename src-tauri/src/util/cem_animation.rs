@@ -0,0 +1,960 @@
+/// Evaluation of OptiFine CEM animation expressions
+///
+/// A JEM model's `animations` assign expressions to part properties
+/// ("head.rx"), custom variables ("var.swing", "varb.open") and render
+/// settings ("render.shadow_size"). Layers run in order, and entries within
+/// a layer in file order, so later assignments see earlier results.
+///
+/// Expressions follow OptiFine's cem_animation.txt: arithmetic, comparison
+/// and boolean operators, the documented functions, plus `c ? a : b`
+/// ternaries and the extra functions EMF accepts. Booleans are 1 and 0. Division by zero gives 0
+/// rather than infinity, as the preview has always done.
+///
+/// A model is compiled once with [`CemAnimator::new`] and evaluated per
+/// frame; custom variables carry over between frames through
+/// [`AnimationInputs::variables`].
+use crate::util::jem::{JemFile, JemPart};
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+/// Functions an expression may call
+const FUNCTIONS: &[&str] = &[
+    "sin", "cos", "tan", "asin", "acos", "atan", "atan2", "torad", "todeg", "min", "max", "clamp",
+    "abs", "floor", "ceil", "round", "sqrt", "pow", "exp", "log", "frac", "fmod", "signum", "lerp",
+    "if", "ifb", "between", "equals", "in", "random", "print", "printb",
+];
+
+/// Functions Entity Model Features adds on top of OptiFine's; Fresh
+/// Animations-style packs lean on the keyframe and curve helpers
+pub const EMF_FUNCTIONS: &[&str] = &[
+    "keyframe",
+    "keyframeloop",
+    "catmullrom",
+    "quadbezier",
+    "cubicbezier",
+    "wrapdeg",
+    "wraprad",
+    "degdiff",
+    "raddiff",
+    "randomb",
+];
+
+/// Part properties an animation can read and write
+const PART_PROPERTIES: &[&str] = &[
+    "tx",
+    "ty",
+    "tz",
+    "rx",
+    "ry",
+    "rz",
+    "sx",
+    "sy",
+    "sz",
+    "visible",
+    "visible_boxes",
+];
+
+/// Binary operators, loosest binding first
+const BINARY_OPERATORS: &[(&str, u8)] = &[
+    ("||", 1),
+    ("&&", 2),
+    ("==", 3),
+    ("!=", 3),
+    ("<", 4),
+    (">", 4),
+    ("<=", 4),
+    (">=", 4),
+    ("+", 5),
+    ("-", 5),
+    ("*", 6),
+    ("/", 6),
+    ("%", 6),
+];
+
+/// A parsed expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    /// An entity input ("limb_swing"), custom variable ("var.x") or part
+    /// property ("head.rx")
+    Variable(String),
+    /// "-", "+" or "!"
+    Unary(char, Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>),
+    Ternary(Box<Expr>, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Identifier(String),
+    Operator(&'static str),
+    LParen,
+    RParen,
+    Comma,
+    Question,
+    Colon,
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>> {
+    const OPERATORS: &[&str] = &[
+        "==", "!=", "<=", ">=", "&&", "||", "+", "-", "*", "/", "%", "<", ">", "!",
+    ];
+
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    while pos < chars.len() {
+        let c = chars[pos];
+        let start = pos;
+        if c.is_whitespace() {
+            pos += 1;
+            continue;
+        }
+        if c.is_ascii_digit() || (c == '.' && chars.get(pos + 1).is_some_and(char::is_ascii_digit))
+        {
+            while pos < chars.len() && (chars[pos].is_ascii_digit() || chars[pos] == '.') {
+                pos += 1;
+            }
+            if pos < chars.len() && (chars[pos] == 'e' || chars[pos] == 'E') {
+                let exponent_start = pos;
+                pos += 1;
+                if pos < chars.len() && (chars[pos] == '+' || chars[pos] == '-') {
+                    pos += 1;
+                }
+                if !chars.get(pos).is_some_and(char::is_ascii_digit) {
+                    pos = exponent_start;
+                }
+                while pos < chars.len() && chars[pos].is_ascii_digit() {
+                    pos += 1;
+                }
+            }
+            let text: String = chars[start..pos].iter().collect();
+            let number = text
+                .parse()
+                .map_err(|_| anyhow!("Invalid number \"{}\" at {}", text, start))?;
+            tokens.push((Token::Number(number), start));
+            continue;
+        }
+        if c.is_ascii_alphabetic() || c == '_' {
+            while pos < chars.len()
+                && (chars[pos].is_ascii_alphanumeric() || chars[pos] == '_' || chars[pos] == '.')
+            {
+                pos += 1;
+            }
+            tokens.push((Token::Identifier(chars[start..pos].iter().collect()), start));
+            continue;
+        }
+        let token = match c {
+            '(' => Some(Token::LParen),
+            ')' => Some(Token::RParen),
+            ',' => Some(Token::Comma),
+            '?' => Some(Token::Question),
+            ':' => Some(Token::Colon),
+            _ => None,
+        };
+        if let Some(token) = token {
+            tokens.push((token, start));
+            pos += 1;
+            continue;
+        }
+        let rest: String = chars[pos..chars.len().min(pos + 2)].iter().collect();
+        match OPERATORS.iter().find(|op| rest.starts_with(**op)) {
+            Some(op) => {
+                tokens.push((Token::Operator(op), start));
+                pos += op.len();
+            }
+            None => bail!("Unexpected character '{}' at {}", c, start),
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    len: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens.get(self.pos).map_or(self.len, |(_, at)| *at)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token, what: &str) -> Result<()> {
+        let at = self.position();
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            _ => bail!("Expected {} at {}", what, at),
+        }
+    }
+
+    /// Ternaries bind loosest and nest to the right
+    fn ternary(&mut self) -> Result<Expr> {
+        let condition = self.binary(0)?;
+        if self.peek() != Some(&Token::Question) {
+            return Ok(condition);
+        }
+        self.next();
+        let then = self.ternary()?;
+        self.expect(Token::Colon, "':'")?;
+        let otherwise = self.ternary()?;
+        Ok(Expr::Ternary(
+            Box::new(condition),
+            Box::new(then),
+            Box::new(otherwise),
+        ))
+    }
+
+    fn binary(&mut self, min_precedence: u8) -> Result<Expr> {
+        let mut left = self.unary()?;
+        while let Some(Token::Operator(op)) = self.peek() {
+            let Some(&(op, precedence)) = BINARY_OPERATORS.iter().find(|(name, _)| name == op)
+            else {
+                break;
+            };
+            if precedence < min_precedence {
+                break;
+            }
+            self.next();
+            let right = self.binary(precedence + 1)?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr> {
+        if let Some(Token::Operator(op @ ("-" | "+" | "!"))) = self.peek() {
+            let op = op.chars().next().unwrap_or('-');
+            self.next();
+            return Ok(Expr::Unary(op, Box::new(self.unary()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr> {
+        let at = self.position();
+        match self.next() {
+            Some(Token::Number(number)) => Ok(Expr::Number(number)),
+            Some(Token::LParen) => {
+                let inner = self.ternary()?;
+                self.expect(Token::RParen, "')'")?;
+                Ok(inner)
+            }
+            Some(Token::Identifier(name)) => {
+                if self.peek() == Some(&Token::LParen) {
+                    if !FUNCTIONS.contains(&name.as_str())
+                        && !EMF_FUNCTIONS.contains(&name.as_str())
+                    {
+                        bail!("Unknown function \"{}\" at {}", name, at);
+                    }
+                    self.next();
+                    let mut args = Vec::new();
+                    if self.peek() != Some(&Token::RParen) {
+                        args.push(self.ternary()?);
+                        while self.peek() == Some(&Token::Comma) {
+                            self.next();
+                            args.push(self.ternary()?);
+                        }
+                    }
+                    self.expect(Token::RParen, "')'")?;
+                    return Ok(Expr::Call(name, args));
+                }
+                Ok(match name.as_str() {
+                    "pi" => Expr::Number(std::f64::consts::PI),
+                    "true" => Expr::Number(1.0),
+                    "false" => Expr::Number(0.0),
+                    _ => Expr::Variable(name),
+                })
+            }
+            Some(token) => bail!("Unexpected {:?} at {}", token, at),
+            None => bail!("Expression ends early"),
+        }
+    }
+}
+
+/// Parse an animation expression
+pub fn parse_expression(source: &str) -> Result<Expr> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser {
+        len: source.chars().count(),
+        tokens,
+        pos: 0,
+    };
+    let expr = parser.ternary()?;
+    if parser.pos < parser.tokens.len() {
+        bail!("Unexpected text at {}", parser.position());
+    }
+    Ok(expr)
+}
+
+/// What an animation feeds on for one frame
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimationInputs {
+    /// Entity inputs by name ("limb_swing", "head_yaw", "is_child", ...);
+    /// missing ones take the preview's defaults
+    #[serde(default)]
+    pub entity: HashMap<String, f64>,
+    /// Custom variables ("var.x", "varb.y") from the previous frame
+    #[serde(default)]
+    pub variables: HashMap<String, f64>,
+}
+
+impl AnimationInputs {
+    fn entity_value(&self, name: &str) -> f64 {
+        if let Some(value) = self.entity.get(name) {
+            return *value;
+        }
+        // The preview's resting entity: alive on the ground at noon, past
+        // the first ticks packs use to initialise
+        match name {
+            "age" => 100.0,
+            "day_time" => 6000.0,
+            "frame_time" => 0.05,
+            "health" | "max_health" => 20.0,
+            "swing_direction" => 3.0,
+            "is_alive" | "is_on_ground" => 1.0,
+            "id" => 1.0,
+            _ => 0.0,
+        }
+    }
+}
+
+/// A part's animated values; rotations in radians, translations in pixels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartTransform {
+    pub tx: f64,
+    pub ty: f64,
+    pub tz: f64,
+    pub rx: f64,
+    pub ry: f64,
+    pub rz: f64,
+    pub sx: f64,
+    pub sy: f64,
+    pub sz: f64,
+    pub visible: bool,
+    pub visible_boxes: bool,
+}
+
+impl PartTransform {
+    /// A part at rest: its JEM translate and rotate, at its scale
+    fn rest(part: &JemPart) -> Self {
+        let [tx, ty, tz] = part.translate.unwrap_or_default();
+        let [rx, ry, rz] = part.rotate.unwrap_or_default();
+        let scale = f64::from(part.scale.unwrap_or(1.0));
+        Self {
+            tx: f64::from(tx),
+            ty: f64::from(ty),
+            tz: f64::from(tz),
+            rx: f64::from(rx).to_radians(),
+            ry: f64::from(ry).to_radians(),
+            rz: f64::from(rz).to_radians(),
+            sx: scale,
+            sy: scale,
+            sz: scale,
+            visible: true,
+            visible_boxes: true,
+        }
+    }
+
+    fn get(&self, property: &str) -> f64 {
+        match property {
+            "tx" => self.tx,
+            "ty" => self.ty,
+            "tz" => self.tz,
+            "rx" => self.rx,
+            "ry" => self.ry,
+            "rz" => self.rz,
+            "sx" => self.sx,
+            "sy" => self.sy,
+            "sz" => self.sz,
+            "visible" => f64::from(u8::from(self.visible)),
+            "visible_boxes" => f64::from(u8::from(self.visible_boxes)),
+            _ => 0.0,
+        }
+    }
+
+    fn set(&mut self, property: &str, value: f64) {
+        match property {
+            "tx" => self.tx = value,
+            "ty" => self.ty = value,
+            "tz" => self.tz = value,
+            "rx" => self.rx = value,
+            "ry" => self.ry = value,
+            "rz" => self.rz = value,
+            "sx" => self.sx = value,
+            "sy" => self.sy = value,
+            "sz" => self.sz = value,
+            "visible" => self.visible = value != 0.0,
+            "visible_boxes" => self.visible_boxes = value != 0.0,
+            _ => {}
+        }
+    }
+}
+
+/// A problem compiling one animation entry; the entry is skipped
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimationError {
+    /// The assignment target as written (e.g., "head.rx")
+    pub target: String,
+    pub message: String,
+}
+
+/// Everything the animations produced for one frame
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimationFrame {
+    /// Every part by ID, animated or at rest
+    pub parts: BTreeMap<String, PartTransform>,
+    /// Custom variables, to pass back in for the next frame
+    pub variables: BTreeMap<String, f64>,
+    /// Render settings the animations set (e.g., "shadow_size")
+    pub render: BTreeMap<String, f64>,
+    pub errors: Vec<AnimationError>,
+}
+
+#[derive(Debug, Clone)]
+enum Target {
+    Part(String, String),
+    Variable(String),
+    Render(String),
+}
+
+#[derive(Debug, Clone)]
+struct Assignment {
+    target: Target,
+    expr: Expr,
+}
+
+/// A model's animations, compiled for repeated evaluation
+#[derive(Debug, Clone)]
+pub struct CemAnimator {
+    rest: BTreeMap<String, PartTransform>,
+    assignments: Vec<Assignment>,
+    errors: Vec<AnimationError>,
+}
+
+/// Name a part is referred to by in animations
+fn part_name(part: &JemPart) -> Option<&str> {
+    part.id.as_deref().or(part.part.as_deref())
+}
+
+fn collect_rest(part: &JemPart, rest: &mut BTreeMap<String, PartTransform>) {
+    if let Some(name) = part_name(part) {
+        rest.entry(name.to_string())
+            .or_insert_with(|| PartTransform::rest(part));
+    }
+    for child in part.children() {
+        collect_rest(child, rest);
+    }
+}
+
+/// Drop a hierarchy prefix ("body:head" -> "head"), which names the same part
+fn last_segment(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+impl CemAnimator {
+    /// Compile a model's animations; entries that don't parse or that target
+    /// unknown parts are reported and skipped
+    pub fn new(model: &JemFile) -> Self {
+        let mut rest = BTreeMap::new();
+        for part in &model.models {
+            collect_rest(part, &mut rest);
+        }
+
+        let mut assignments = Vec::new();
+        let mut errors = Vec::new();
+        for part in &model.models {
+            let this = part_name(part).unwrap_or_default();
+            for layer in &part.animations {
+                for (target, expression) in &layer.0 {
+                    match compile(target, expression, this, &rest) {
+                        Ok(assignment) => assignments.push(assignment),
+                        Err(e) => errors.push(AnimationError {
+                            target: target.clone(),
+                            message: e.to_string(),
+                        }),
+                    }
+                }
+            }
+        }
+
+        Self {
+            rest,
+            assignments,
+            errors,
+        }
+    }
+
+    /// Compilation problems
+    pub fn errors(&self) -> &[AnimationError] {
+        &self.errors
+    }
+
+    /// Run every assignment for one frame
+    pub fn evaluate(&self, inputs: &AnimationInputs) -> AnimationFrame {
+        let mut state = State {
+            inputs,
+            parts: self.rest.clone(),
+            variables: inputs
+                .variables
+                .iter()
+                .map(|(name, value)| (name.clone(), *value))
+                .collect(),
+            render: BTreeMap::new(),
+            random_calls: 0,
+        };
+
+        for assignment in &self.assignments {
+            let value = state.eval(&assignment.expr);
+            let value = if value.is_finite() { value } else { 0.0 };
+            match &assignment.target {
+                Target::Part(part, property) => {
+                    if let Some(transform) = state.parts.get_mut(part) {
+                        transform.set(property, value);
+                    }
+                }
+                Target::Variable(name) => {
+                    let value = if name.starts_with("varb.") {
+                        f64::from(u8::from(value != 0.0))
+                    } else {
+                        value
+                    };
+                    state.variables.insert(name.clone(), value);
+                }
+                Target::Render(name) => {
+                    state.render.insert(name.clone(), value);
+                }
+            }
+        }
+
+        AnimationFrame {
+            parts: state.parts,
+            variables: state.variables,
+            render: state.render,
+            errors: self.errors.clone(),
+        }
+    }
+}
+
+/// Resolve `this.` and hierarchy prefixes in variable names, so evaluation
+/// only sees part IDs
+fn normalize_variables(expr: &mut Expr, this: &str) {
+    match expr {
+        Expr::Variable(name) => {
+            if let Some((target, property)) = name.split_once('.') {
+                let target = if target == "this" {
+                    this
+                } else {
+                    last_segment(target)
+                };
+                *name = format!("{}.{}", target, property);
+            }
+        }
+        Expr::Unary(_, operand) => normalize_variables(operand, this),
+        Expr::Binary(_, left, right) => {
+            normalize_variables(left, this);
+            normalize_variables(right, this);
+        }
+        Expr::Call(_, args) => {
+            for arg in args {
+                normalize_variables(arg, this);
+            }
+        }
+        Expr::Ternary(condition, then, otherwise) => {
+            normalize_variables(condition, this);
+            normalize_variables(then, this);
+            normalize_variables(otherwise, this);
+        }
+        Expr::Number(_) => {}
+    }
+}
+
+fn compile(
+    target: &str,
+    expression: &Value,
+    this: &str,
+    parts: &BTreeMap<String, PartTransform>,
+) -> Result<Assignment> {
+    let mut expr = match expression {
+        Value::Number(number) => Expr::Number(number.as_f64().unwrap_or_default()),
+        Value::String(source) => parse_expression(source)?,
+        other => bail!("Expected an expression, found {}", other),
+    };
+    normalize_variables(&mut expr, this);
+
+    let (object, property) = target
+        .split_once('.')
+        .ok_or_else(|| anyhow!("Target must be \"part.property\" or \"var.name\""))?;
+    let target = match object {
+        "var" | "varb" => Target::Variable(target.to_string()),
+        "render" => Target::Render(property.to_string()),
+        _ => {
+            let part = if object == "this" {
+                this
+            } else {
+                last_segment(object)
+            };
+            if !parts.contains_key(part) {
+                bail!("No part with ID \"{}\"", part);
+            }
+            if !PART_PROPERTIES.contains(&property) {
+                bail!("Unknown part property \"{}\"", property);
+            }
+            Target::Part(part.to_string(), property.to_string())
+        }
+    };
+    Ok(Assignment { target, expr })
+}
+
+struct State<'a> {
+    inputs: &'a AnimationInputs,
+    parts: BTreeMap<String, PartTransform>,
+    variables: BTreeMap<String, f64>,
+    render: BTreeMap<String, f64>,
+    random_calls: u64,
+}
+
+/// Deterministic value in [0, 1) for a seed (splitmix64)
+fn seeded_random(seed: u64) -> f64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Wrap an angle into [-half_turn, half_turn)
+fn wrap_angle(angle: f64, half_turn: f64) -> f64 {
+    (angle + half_turn).rem_euclid(2.0 * half_turn) - half_turn
+}
+
+fn truth(value: bool) -> f64 {
+    if value {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+impl State<'_> {
+    fn variable(&self, name: &str) -> f64 {
+        match name.split_once('.') {
+            Some(("var" | "varb", _)) => self.variables.get(name).copied().unwrap_or(0.0),
+            Some(("render", property)) => self.render.get(property).copied().unwrap_or(0.0),
+            Some((part, property)) => self
+                .parts
+                .get(part)
+                .map_or(0.0, |transform| transform.get(property)),
+            None => self.inputs.entity_value(name),
+        }
+    }
+
+    fn eval(&mut self, expr: &Expr) -> f64 {
+        match expr {
+            Expr::Number(number) => *number,
+            Expr::Variable(name) => self.variable(name),
+            Expr::Unary(op, operand) => {
+                let value = self.eval(operand);
+                match op {
+                    '-' => -value,
+                    '!' => truth(value == 0.0),
+                    _ => value,
+                }
+            }
+            Expr::Binary(op, left, right) => {
+                let left = self.eval(left);
+                // Boolean operators short-circuit
+                match *op {
+                    "&&" if left == 0.0 => return 0.0,
+                    "||" if left != 0.0 => return 1.0,
+                    _ => {}
+                }
+                let right = self.eval(right);
+                match *op {
+                    "+" => left + right,
+                    "-" => left - right,
+                    "*" => left * right,
+                    "/" if right == 0.0 => 0.0,
+                    "/" => left / right,
+                    "%" if right == 0.0 => 0.0,
+                    "%" => left % right,
+                    "==" => truth(left == right),
+                    "!=" => truth(left != right),
+                    "<" => truth(left < right),
+                    ">" => truth(left > right),
+                    "<=" => truth(left <= right),
+                    ">=" => truth(left >= right),
+                    "&&" | "||" => truth(right != 0.0),
+                    _ => 0.0,
+                }
+            }
+            Expr::Ternary(condition, then, otherwise) => {
+                if self.eval(condition) != 0.0 {
+                    self.eval(then)
+                } else {
+                    self.eval(otherwise)
+                }
+            }
+            Expr::Call(name, args) => self.call(name, args),
+        }
+    }
+
+    fn call(&mut self, name: &str, args: &[Expr]) -> f64 {
+        // Conditionals only evaluate the branch they pick
+        if name == "if" || name == "ifb" {
+            let mut pairs = args.chunks_exact(2);
+            for pair in pairs.by_ref() {
+                if self.eval(&pair[0]) != 0.0 {
+                    let value = self.eval(&pair[1]);
+                    return if name == "ifb" {
+                        truth(value != 0.0)
+                    } else {
+                        value
+                    };
+                }
+            }
+            return match pairs.remainder() {
+                [otherwise] => {
+                    let value = self.eval(otherwise);
+                    if name == "ifb" {
+                        truth(value != 0.0)
+                    } else {
+                        value
+                    }
+                }
+                _ => 0.0,
+            };
+        }
+
+        let values: Vec<f64> = args.iter().map(|arg| self.eval(arg)).collect();
+        let arg = |index: usize| values.get(index).copied().unwrap_or(0.0);
+        match name {
+            "sin" => arg(0).sin(),
+            "cos" => arg(0).cos(),
+            "tan" => arg(0).tan(),
+            "asin" => arg(0).asin(),
+            "acos" => arg(0).acos(),
+            "atan" => arg(0).atan(),
+            "atan2" => arg(0).atan2(arg(1)),
+            "torad" => arg(0).to_radians(),
+            "todeg" => arg(0).to_degrees(),
+            "min" => values.iter().copied().fold(f64::INFINITY, f64::min),
+            "max" => values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            "clamp" => arg(0).max(arg(1)).min(arg(2)),
+            "abs" => arg(0).abs(),
+            "floor" => arg(0).floor(),
+            "ceil" => arg(0).ceil(),
+            "round" => arg(0).round(),
+            "sqrt" => arg(0).sqrt(),
+            "pow" => arg(0).powf(arg(1)),
+            "exp" => arg(0).exp(),
+            "log" => arg(0).ln(),
+            "frac" => arg(0) - arg(0).floor(),
+            "fmod" if arg(1) == 0.0 => 0.0,
+            "fmod" => arg(0) - arg(1) * (arg(0) / arg(1)).floor(),
+            "signum" if arg(0) == 0.0 => 0.0,
+            "signum" => arg(0).signum(),
+            "lerp" => arg(1) + (arg(2) - arg(1)) * arg(0),
+            "between" => truth(arg(0) >= arg(1) && arg(0) <= arg(2)),
+            "equals" => truth((arg(0) - arg(1)).abs() <= arg(2)),
+            "in" => truth(values.iter().skip(1).any(|value| *value == arg(0))),
+            "random" | "randomb" => {
+                let seed = if values.is_empty() {
+                    self.random_calls += 1;
+                    (self.inputs.entity_value("id") as u64) ^ (self.random_calls << 32)
+                } else {
+                    arg(0).to_bits()
+                };
+                let value = seeded_random(seed);
+                if name == "randomb" {
+                    truth(value < 0.5)
+                } else {
+                    value
+                }
+            }
+            "print" | "printb" => values.last().copied().unwrap_or(0.0),
+            // keyframe(k, v0, v1, ...): linear through the values, k indexing them
+            "keyframe" | "keyframeloop" if values.len() < 2 => 0.0,
+            "keyframe" => {
+                let frames = &values[1..];
+                let k = arg(0).clamp(0.0, (frames.len() - 1) as f64);
+                let index = k.floor() as usize;
+                let next = frames[(index + 1).min(frames.len() - 1)];
+                frames[index] + (next - frames[index]) * (k - index as f64)
+            }
+            // The last value runs back into the first
+            "keyframeloop" => {
+                let frames = &values[1..];
+                let k = arg(0).rem_euclid(frames.len() as f64);
+                let index = (k.floor() as usize).min(frames.len() - 1);
+                let next = frames[(index + 1) % frames.len()];
+                frames[index] + (next - frames[index]) * (k - index as f64)
+            }
+            "catmullrom" => {
+                let (t, p0, p1, p2, p3) = (arg(0), arg(1), arg(2), arg(3), arg(4));
+                0.5 * (2.0 * p1
+                    + (p2 - p0) * t
+                    + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t * t
+                    + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t * t * t)
+            }
+            "quadbezier" => {
+                let (t, p0, p1, p2) = (arg(0), arg(1), arg(2), arg(3));
+                let u = 1.0 - t;
+                u * u * p0 + 2.0 * u * t * p1 + t * t * p2
+            }
+            "cubicbezier" => {
+                let (t, p0, p1, p2, p3) = (arg(0), arg(1), arg(2), arg(3), arg(4));
+                let u = 1.0 - t;
+                u * u * u * p0 + 3.0 * u * u * t * p1 + 3.0 * u * t * t * p2 + t * t * t * p3
+            }
+            // Angles wrapped into [-180, 180) degrees or [-pi, pi) radians;
+            // the differences are the shortest turn from the first to the second
+            "wrapdeg" => wrap_angle(arg(0), 180.0),
+            "wraprad" => wrap_angle(arg(0), std::f64::consts::PI),
+            "degdiff" => wrap_angle(arg(1) - arg(0), 180.0),
+            "raddiff" => wrap_angle(arg(1) - arg(0), std::f64::consts::PI),
+            _ => 0.0,
+        }
+    }
+}
+
+/// Evaluate a model's animations for one frame
+///
+/// Convenience for one-off evaluation; keep a [`CemAnimator`] to evaluate
+/// the same model many times.
+pub fn evaluate_cem_animations(model: &JemFile, inputs: &AnimationInputs) -> AnimationFrame {
+    CemAnimator::new(model).evaluate(inputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(source: &str, entity: &[(&str, f64)]) -> f64 {
+        let inputs = AnimationInputs {
+            entity: entity
+                .iter()
+                .map(|(name, value)| (name.to_string(), *value))
+                .collect(),
+            ..Default::default()
+        };
+        let mut state = State {
+            inputs: &inputs,
+            parts: BTreeMap::new(),
+            variables: BTreeMap::new(),
+            render: BTreeMap::new(),
+            random_calls: 0,
+        };
+        state.eval(&parse_expression(source).unwrap())
+    }
+
+    #[test]
+    fn test_expressions() {
+        assert_eq!(eval("1 + 2 * 3 - 4 / 2", &[]), 5.0);
+        assert_eq!(eval("-(2 + 3) % 4", &[]), -1.0);
+        assert_eq!(eval("1 < 2 && !(3 == 4) || 0", &[]), 1.0);
+        assert_eq!(eval("is_child ? 0.5 : 1.5e0", &[("is_child", 1.0)]), 0.5);
+        assert_eq!(
+            eval(
+                "if(limb_speed > 0.5, 2, limb_speed > 0, 1, 0)",
+                &[("limb_speed", 0.3)]
+            ),
+            1.0
+        );
+        assert_eq!(eval("clamp(age, 0, 20)", &[]), 20.0);
+        assert!((eval("todeg(torad(90))", &[]) - 90.0).abs() < 1e-9);
+        assert_eq!(eval("max(1, 5, 3) + min(4, -2)", &[]), 3.0);
+        assert_eq!(eval("fmod(-1, 3)", &[]), 2.0);
+        assert_eq!(
+            eval(
+                "between(limb_swing, 1, 2) + in(2, 1, 2)",
+                &[("limb_swing", 1.5)]
+            ),
+            2.0
+        );
+        assert_eq!(eval("1 / 0", &[]), 0.0);
+        assert!(eval("random(4)", &[]) == eval("random(4)", &[]));
+
+        assert_eq!(eval("keyframe(1.5, 0, 10, 30)", &[]), 20.0);
+        assert_eq!(eval("keyframe(7, 0, 10, 30)", &[]), 30.0);
+        assert_eq!(eval("keyframeloop(2.5, 0, 10, 30)", &[]), 15.0);
+        assert_eq!(eval("catmullrom(0, 5, 1, 2, 9)", &[]), 1.0);
+        assert_eq!(eval("cubicbezier(1, 0, 4, 4, 8)", &[]), 8.0);
+        assert_eq!(eval("wrapdeg(270) + degdiff(350, 10)", &[]), -70.0);
+
+        assert!(parse_expression("sin(1").is_err());
+        assert!(parse_expression("wobble(1)").is_err());
+        assert!(parse_expression("1 +").is_err());
+    }
+
+    #[test]
+    fn test_animator_layers_in_order() {
+        let model: JemFile = crate::util::jem::parse_jem(
+            r#"{
+                "models": [
+                    {"part": "head", "id": "head", "translate": [0, -20, 8], "rotate": [90, 0, 0],
+                     "boxes": [], "submodels": [{"id": "jaw", "boxes": []}]},
+                    {"part": "body", "id": "body", "boxes": [], "animations": [
+                        {
+                            "var.swing": "sin(limb_swing) * limb_speed",
+                            "body.ry": "torad(45)",
+                            "head.rx": "var.swing + this.ry",
+                            "jaw.visible": "var.frame > 0",
+                            "var.frame": "var.frame + 1",
+                            "tail.rx": "1",
+                            "render.shadow_size": 0.4
+                        },
+                        {"body:head.ty": "head.ty - 1", "head.rz": "head.rx"}
+                    ]}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let animator = CemAnimator::new(&model);
+        let inputs = AnimationInputs {
+            entity: [
+                ("limb_swing".to_string(), std::f64::consts::FRAC_PI_2),
+                ("limb_speed".to_string(), 0.5),
+            ]
+            .into(),
+            ..Default::default()
+        };
+
+        let first = animator.evaluate(&inputs);
+        let second = animator.evaluate(&AnimationInputs {
+            variables: first.variables.clone().into_iter().collect(),
+            ..inputs.clone()
+        });
+
+        assert_eq!(animator.errors().len(), 1);
+        assert_eq!(animator.errors()[0].target, "tail.rx");
+        let head = first.parts["head"];
+        // "this" is the part the animations belong to (body)
+        assert!((head.rx - (0.5 + std::f64::consts::FRAC_PI_4)).abs() < 1e-9);
+        assert_eq!(head.rz, head.rx);
+        assert_eq!(head.ty, -21.0);
+        assert!(!first.parts["jaw"].visible);
+        assert!(second.parts["jaw"].visible);
+        assert_eq!(second.variables["var.frame"], 2.0);
+        assert_eq!(first.render["shadow_size"], 0.4);
+        assert!((first.parts["body"].rx).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,186 @@
+/// Which dialect of OptiFine's custom entity features a pack is written for
+///
+/// Entity Model Features and Entity Texture Features (EMF/ETF) read the same
+/// files as OptiFine's CEM and random entities, and accept more: extra
+/// animation functions (`keyframe`, `catmullrom`, ...), animations inside
+/// .jpm parts, more random entity rule keys ("angry", "speed", ...) and
+/// blinking eye textures ("creeper_blink.png"). Packs built around them, like
+/// Fresh Animations, partly stop working under OptiFine alone, so the scan
+/// tags every pack with entity files by the dialect it needs.
+use crate::model::{CemDialect, PackMeta};
+use crate::util::optifine_properties::read_properties_files;
+use crate::util::{asset_indexer, cem_animation::EMF_FUNCTIONS, random_entities};
+use serde_json::Value;
+
+/// Random entity rule keys only ETF understands
+pub const ETF_RULE_KEYS: &[&str] = &[
+    "angry",
+    "creeperCharged",
+    "distanceFromPlayer",
+    "hiddenGene",
+    "items",
+    "jumpStrength",
+    "llamaInventory",
+    "maxHealth",
+    "moving",
+    "nbt",
+    "playerCreated",
+    "screamingGoat",
+    "speed",
+    "statusEffect",
+    "teams",
+];
+
+/// Suffixes of ETF's blinking eye textures
+const BLINK_SUFFIXES: &[&str] = &["_blink.png", "_blink2.png"];
+
+/// Whether a pack file is a CEM model, part or random model rule
+fn is_cem_file(file: &str) -> bool {
+    file.starts_with("assets/") && file.contains("/optifine/cem/")
+}
+
+/// Whether a pack file is one of ETF's blinking eye textures
+fn is_blink_texture(file: &str) -> bool {
+    (file.contains("/textures/entity/") || file.contains("/optifine/random/"))
+        && BLINK_SUFFIXES.iter().any(|suffix| file.ends_with(suffix))
+}
+
+/// Whether a model file's text calls a function only EMF provides
+pub fn uses_emf_functions(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    let is_word = |b: u8| b.is_ascii_alphanumeric() || b == b'_' || b == b'.';
+    EMF_FUNCTIONS.iter().any(|name| {
+        text.match_indices(name).any(|(start, _)| {
+            let end = start + name.len();
+            (start == 0 || !is_word(bytes[start - 1])) && text[end..].trim_start().starts_with('(')
+        })
+    })
+}
+
+/// Whether a .jpm part carries animations of its own, which only EMF runs
+fn jpm_has_animations(text: &str) -> bool {
+    serde_json::from_str::<Value>(text)
+        .ok()
+        .and_then(|jpm| jpm.get("animations")?.as_array().map(|a| !a.is_empty()))
+        .unwrap_or(false)
+}
+
+/// Dialect of a pack from its file listing and the text of its entity files
+///
+/// `texts` holds (path, text) for the pack's .jem, .jpm and .properties entity
+/// files. Returns None when the pack has no entity model or random entity
+/// files at all.
+pub fn detect_dialect(files: &[String], texts: &[(String, String)]) -> Option<CemDialect> {
+    let has_entity_files = files.iter().any(|file| {
+        is_cem_file(file)
+            || is_blink_texture(file)
+            || random_entities::parse_random_file(file).is_some()
+    });
+    if !has_entity_files {
+        return None;
+    }
+
+    let blinks = files.iter().any(|file| is_blink_texture(file));
+    let extended = texts.iter().any(|(file, text)| {
+        if file.ends_with(".properties") {
+            random_entities::parse_random_rules(text)
+                .iter()
+                .any(|rule| {
+                    rule.conditions
+                        .keys()
+                        .any(|key| ETF_RULE_KEYS.contains(&key.as_str()))
+                })
+        } else {
+            uses_emf_functions(text) || (file.ends_with(".jpm") && jpm_has_animations(text))
+        }
+    });
+
+    Some(if blinks || extended {
+        CemDialect::Emf
+    } else {
+        CemDialect::Optifine
+    })
+}
+
+/// Dialect of a scanned pack; None for packs without entity files or that
+/// can't be listed
+pub fn pack_dialect(pack: &PackMeta) -> Option<CemDialect> {
+    let files = asset_indexer::list_pack_files(pack).ok()?;
+    let entity_files: Vec<&String> = files
+        .iter()
+        .filter(|file| {
+            let is_text = [".jem", ".jpm", ".properties"]
+                .iter()
+                .any(|extension| file.ends_with(extension));
+            is_text && (is_cem_file(file) || random_entities::parse_random_file(file).is_some())
+        })
+        .collect();
+    let texts = read_properties_files(pack, entity_files);
+    detect_dialect(&files, &texts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    #[test]
+    fn test_detect_dialect() {
+        let jem = "assets/minecraft/optifine/cem/cow.jem".to_string();
+        let files = strings(&["assets/minecraft/optifine/cem/cow.jem"]);
+        let plain = r#"{"models": [{"part": "head", "animations": [{"head.rx": "sin(age)"}]}]}"#;
+        let keyframed = r#"{"models": [{"part": "head",
+            "animations": [{"head.rx": "keyframe(age % 3, 0, 0.2, 0)"}]}]}"#;
+
+        assert_eq!(detect_dialect(&strings(&["pack.mcmeta"]), &[]), None);
+        assert_eq!(
+            detect_dialect(&files, &[(jem.clone(), plain.to_string())]),
+            Some(CemDialect::Optifine)
+        );
+        assert_eq!(
+            detect_dialect(&files, &[(jem, keyframed.to_string())]),
+            Some(CemDialect::Emf)
+        );
+
+        let jpm = "assets/minecraft/optifine/cem/cow_head.jpm".to_string();
+        assert_eq!(
+            detect_dialect(
+                &strings(&["assets/minecraft/optifine/cem/cow_head.jpm"]),
+                &[(jpm, r#"{"animations": [{"this.rx": "0"}]}"#.to_string())]
+            ),
+            Some(CemDialect::Emf)
+        );
+        assert!(!uses_emf_functions(r#"{"var.keyframe": "my_keyframe(1)"}"#));
+    }
+
+    #[test]
+    fn test_detect_etf_textures_and_rules() {
+        let random = "assets/minecraft/optifine/random/entity/wolf/wolf.properties".to_string();
+        let files = strings(&[
+            "assets/minecraft/optifine/random/entity/wolf/wolf.properties",
+            "assets/minecraft/optifine/random/entity/wolf/wolf2.png",
+        ]);
+
+        assert_eq!(
+            detect_dialect(
+                &files,
+                &[(random.clone(), "textures.1=2\nbiomes.1=taiga\n".into())]
+            ),
+            Some(CemDialect::Optifine)
+        );
+        assert_eq!(
+            detect_dialect(&files, &[(random, "textures.1=2\nangry.1=true\n".into())]),
+            Some(CemDialect::Emf)
+        );
+        assert_eq!(
+            detect_dialect(
+                &strings(&["assets/minecraft/textures/entity/creeper/creeper_blink.png"]),
+                &[]
+            ),
+            Some(CemDialect::Emf)
+        );
+    }
+}
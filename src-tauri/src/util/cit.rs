@@ -0,0 +1,194 @@
+/// OptiFine custom item textures (assets/<namespace>/optifine/cit/**/*.properties)
+///
+/// Each properties file is one CIT rule: which items it matches, under which
+/// conditions, and the textures or models it swaps in. Rules are indexed as
+/// assets of their own ("minecraft:optifine/cit/swords/fire.properties") so
+/// they show up in scans, and the nest builder copies each winning rule
+/// together with the files it references.
+use crate::model::PackMeta;
+use crate::util::optifine_properties::{
+    parse_properties, read_properties_files, resolve_reference,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const CIT_PATH: &str = "optifine/cit/";
+
+/// A parsed CIT rule
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CitRule {
+    /// Rule type: "item" (default), "enchantment", "armor" or "elytra"
+    pub kind: String,
+    /// Matched item IDs, namespaced (e.g., "minecraft:diamond_sword")
+    pub items: Vec<String>,
+    /// Texture keys ("texture", "texture.bow_pulling_0") and their values as written
+    pub textures: BTreeMap<String, String>,
+    /// Model keys ("model", "model.bow_pulling_0") and their values as written
+    pub models: BTreeMap<String, String>,
+    /// Remaining matching conditions (damage, stackSize, enchantments, nbt.*)
+    pub conditions: BTreeMap<String, String>,
+}
+
+/// Asset ID for a CIT properties file, or None if the path isn't one
+/// "assets/minecraft/optifine/cit/swords/fire.properties" -> "minecraft:optifine/cit/swords/fire.properties"
+pub fn cit_asset_id(file_path: &str) -> Option<String> {
+    let rest = file_path.strip_prefix("assets/")?;
+    let (namespace, path) = rest.split_once('/')?;
+    if !path.starts_with(CIT_PATH) || !path.ends_with(".properties") {
+        return None;
+    }
+    Some(format!("{}:{}", namespace, path))
+}
+
+/// Parse the text of a CIT properties file
+pub fn parse_cit_rule(text: &str) -> CitRule {
+    let mut rule = CitRule {
+        kind: "item".to_string(),
+        ..Default::default()
+    };
+
+    for (key, value) in parse_properties(text) {
+        match key.as_str() {
+            "type" => rule.kind = value,
+            "items" | "matchItems" => {
+                rule.items = value
+                    .split_whitespace()
+                    .map(|item| {
+                        if item.contains(':') {
+                            item.to_string()
+                        } else {
+                            format!("minecraft:{}", item)
+                        }
+                    })
+                    .collect();
+            }
+            _ if key == "texture" || key.starts_with("texture.") => {
+                rule.textures.insert(key, value);
+            }
+            _ if key == "model" || key.starts_with("model.") => {
+                rule.models.insert(key, value);
+            }
+            _ => {
+                rule.conditions.insert(key, value);
+            }
+        }
+    }
+
+    rule
+}
+
+/// Pack-relative paths of every file a rule needs, sorted
+///
+/// A rule without texture or model keys uses the PNG named after its
+/// properties file, as OptiFine does.
+pub fn referenced_files(properties_path: &str, rule: &CitRule) -> Vec<String> {
+    let mut files: Vec<String> = rule
+        .textures
+        .values()
+        .filter_map(|value| resolve_reference(properties_path, value, ".png"))
+        .chain(
+            rule.models
+                .values()
+                .filter_map(|value| resolve_reference(properties_path, value, ".json")),
+        )
+        .collect();
+
+    if rule.textures.is_empty() && rule.models.is_empty() {
+        if let Some(stem) = properties_path.strip_suffix(".properties") {
+            files.push(format!("{}.png", stem));
+        }
+    }
+
+    files.sort();
+    files.dedup();
+    files
+}
+
+/// Search labels for a rule: "cit", its type and the matched item names
+pub fn cit_labels(rule: &CitRule) -> Vec<String> {
+    let mut labels = vec!["cit".to_string(), rule.kind.clone()];
+    for item in &rule.items {
+        let name = item.split_once(':').map_or(item.as_str(), |(_, name)| name);
+        if !labels.iter().any(|label| label == name) {
+            labels.push(name.to_string());
+        }
+    }
+    labels
+}
+
+/// Read and parse the CIT rules among a pack's files
+///
+/// Returns (properties path, rule) pairs.
+pub fn read_cit_rules(pack: &PackMeta, files: &[String]) -> Vec<(String, CitRule)> {
+    let cit_files = files.iter().filter(|file| cit_asset_id(file).is_some());
+    read_properties_files(pack, cit_files)
+        .into_iter()
+        .map(|(file, text)| {
+            let rule = parse_cit_rule(&text);
+            (file, rule)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cit_asset_id() {
+        assert_eq!(
+            cit_asset_id("assets/minecraft/optifine/cit/swords/fire.properties"),
+            Some("minecraft:optifine/cit/swords/fire.properties".to_string())
+        );
+        assert_eq!(
+            cit_asset_id("assets/minecraft/optifine/cit/swords/fire.png"),
+            None
+        );
+        assert_eq!(
+            cit_asset_id("assets/minecraft/optifine/ctm/glass/glass.properties"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_cit_rule() {
+        let rule = parse_cit_rule(
+            "type=item\nitems=diamond_sword minecraft:iron_sword\ntexture=fire_sword\n\
+             model.bow_pulling_0=./bow_0\nnbt.display.Name=ipattern:*fire*\n",
+        );
+
+        assert_eq!(rule.kind, "item");
+        assert_eq!(
+            rule.items,
+            vec!["minecraft:diamond_sword", "minecraft:iron_sword"]
+        );
+        assert_eq!(rule.textures["texture"], "fire_sword");
+        assert_eq!(rule.models["model.bow_pulling_0"], "./bow_0");
+        assert_eq!(rule.conditions["nbt.display.Name"], "ipattern:*fire*");
+        assert_eq!(
+            cit_labels(&rule),
+            vec!["cit", "item", "diamond_sword", "iron_sword"]
+        );
+    }
+
+    #[test]
+    fn test_referenced_files() {
+        let props = "assets/minecraft/optifine/cit/swords/fire.properties";
+        let rule = parse_cit_rule("items=diamond_sword\ntexture=fire_sword\nmodel=./fire_model");
+        assert_eq!(
+            referenced_files(props, &rule),
+            vec![
+                "assets/minecraft/optifine/cit/swords/fire_model.json",
+                "assets/minecraft/optifine/cit/swords/fire_sword.png",
+            ]
+        );
+
+        // Without texture keys the PNG named after the properties file is used
+        let rule = parse_cit_rule("items=diamond_sword");
+        assert_eq!(
+            referenced_files(props, &rule),
+            vec!["assets/minecraft/optifine/cit/swords/fire.png"]
+        );
+    }
+}
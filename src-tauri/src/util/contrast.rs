@@ -0,0 +1,509 @@
+/// Contrast and color-vision analysis of GUI textures and fonts
+///
+/// Each texture's opaque pixels are split into two tones (2-means in linear
+/// RGB); the larger group is taken as the background and the smaller as the
+/// foreground. Textures with a single tone, such as font glyphs drawn on
+/// transparency, are measured against the vanilla GUI panel color instead.
+/// Contrast ratios follow WCAG 2.x, and both tones are run through the
+/// Machado et al. (2009) color-vision-deficiency matrices to find pairs that
+/// only differ by hue a colorblind player can't see.
+use crate::model::PackMeta;
+use crate::util::i18n::tr;
+use crate::util::pack_files;
+use anyhow::{bail, Result};
+use image::{Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// Minimum ratio for text (WCAG 1.4.3, level AA)
+pub const TEXT_CONTRAST_RATIO: f64 = 4.5;
+
+/// Minimum ratio for UI components and graphics (WCAG 1.4.11)
+pub const UI_CONTRAST_RATIO: f64 = 3.0;
+
+/// CIE76 color difference below which two colors are hard to tell apart
+const MIN_COLOR_DIFFERENCE: f64 = 10.0;
+
+/// Pixels with less alpha don't count towards either tone
+const MIN_ALPHA: u8 = 128;
+
+/// Textures larger than this are sampled on a grid rather than read pixel by pixel
+const MAX_SAMPLED_PIXELS: u32 = 256 * 256;
+
+/// Background of vanilla GUI panels (#C6C6C6), used for single-tone textures
+const GUI_PANEL: [u8; 3] = [0xc6, 0xc6, 0xc6];
+
+/// Linear-RGB simulation matrices (Machado, Oliveira & Fernandes 2009, severity 1.0)
+const PROTANOPIA: [[f64; 3]; 3] = [
+    [0.152286, 1.052583, -0.204868],
+    [0.114503, 0.786281, 0.099216],
+    [-0.003882, -0.048116, 1.051998],
+];
+const DEUTERANOPIA: [[f64; 3]; 3] = [
+    [0.367322, 0.860646, -0.227968],
+    [0.280085, 0.672501, 0.047413],
+    [-0.011820, 0.042940, 0.968881],
+];
+const TRITANOPIA: [[f64; 3]; 3] = [
+    [1.255528, -0.076749, -0.178779],
+    [-0.078411, 0.930809, 0.147602],
+    [0.004733, 0.691367, 0.303900],
+];
+
+/// Simulated color vision deficiency
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorVisionDeficiency {
+    /// No red cones
+    Protanopia,
+    /// No green cones
+    Deuteranopia,
+    /// No blue cones
+    Tritanopia,
+    /// No color vision at all
+    Achromatopsia,
+}
+
+impl ColorVisionDeficiency {
+    pub const ALL: [ColorVisionDeficiency; 4] = [
+        ColorVisionDeficiency::Protanopia,
+        ColorVisionDeficiency::Deuteranopia,
+        ColorVisionDeficiency::Tritanopia,
+        ColorVisionDeficiency::Achromatopsia,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            ColorVisionDeficiency::Protanopia => "protanopia",
+            ColorVisionDeficiency::Deuteranopia => "deuteranopia",
+            ColorVisionDeficiency::Tritanopia => "tritanopia",
+            ColorVisionDeficiency::Achromatopsia => "achromatopsia",
+        }
+    }
+
+    /// Simulate an sRGB color as seen with this deficiency
+    pub fn simulate(self, rgb: [u8; 3]) -> [u8; 3] {
+        let linear = rgb.map(srgb_to_linear);
+        let simulated = match self {
+            ColorVisionDeficiency::Protanopia => apply_matrix(&PROTANOPIA, linear),
+            ColorVisionDeficiency::Deuteranopia => apply_matrix(&DEUTERANOPIA, linear),
+            ColorVisionDeficiency::Tritanopia => apply_matrix(&TRITANOPIA, linear),
+            ColorVisionDeficiency::Achromatopsia => [luminance_linear(linear); 3],
+        };
+        simulated.map(linear_to_srgb)
+    }
+}
+
+/// The two tones of a texture as seen with one deficiency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColorblindSimulation {
+    pub deficiency: ColorVisionDeficiency,
+    /// "#rrggbb"
+    pub foreground: String,
+    pub background: String,
+    pub contrast_ratio: f64,
+    /// CIE76 difference between the simulated tones
+    pub color_difference: f64,
+    /// The whole texture as seen with this deficiency, base64 PNG; only when
+    /// images were requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+}
+
+/// Contrast of one texture
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContrastAnalysis {
+    pub asset_id: String,
+    /// Pack-relative path of the analyzed texture
+    pub file: String,
+    /// "#rrggbb"
+    pub foreground: String,
+    pub background: String,
+    /// True when the texture has one tone and was measured against the GUI panel color
+    pub against_gui_panel: bool,
+    pub contrast_ratio: f64,
+    /// WCAG minimum for this kind of texture (text or graphics)
+    pub required_ratio: f64,
+    /// CIE76 difference between the tones with normal color vision
+    pub color_difference: f64,
+    pub simulations: Vec<ColorblindSimulation>,
+    /// Readability problems, empty when the texture passes
+    pub issues: Vec<String>,
+}
+
+/// Contrast of a set of textures in one pack
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContrastReport {
+    pub pack_id: String,
+    pub analyses: Vec<ContrastAnalysis>,
+    /// Requested assets the pack has no texture for
+    pub missing: Vec<String>,
+    /// Every analysis' issues, prefixed with the asset ID, for the validation report
+    pub issues: Vec<String>,
+}
+
+/// Analyze the textures of `asset_ids` in a pack
+///
+/// Font textures (asset paths under "font/") are held to the text ratio, all
+/// others to the graphics ratio. With `include_images`, each simulation
+/// carries the recolored texture.
+pub fn analyze_contrast(
+    pack: &PackMeta,
+    asset_ids: &[String],
+    include_images: bool,
+) -> ContrastReport {
+    let mut report = ContrastReport {
+        pack_id: pack.id.clone(),
+        analyses: Vec::new(),
+        missing: Vec::new(),
+        issues: Vec::new(),
+    };
+
+    for asset_id in asset_ids {
+        let file = pack_files::texture_entry_path(asset_id);
+        let image = match pack_files::read_pack_entry(pack, &file) {
+            Ok(bytes) => image::load_from_memory(&bytes).map(|image| image.to_rgba8()),
+            Err(_) => {
+                report.missing.push(asset_id.clone());
+                continue;
+            }
+        };
+        let analysis = image
+            .map_err(anyhow::Error::from)
+            .and_then(|image| analyze_texture(asset_id, &file, &image, include_images));
+        match analysis {
+            Ok(analysis) => {
+                for issue in &analysis.issues {
+                    report.issues.push(format!("{}: {}", asset_id, issue));
+                }
+                report.analyses.push(analysis);
+            }
+            Err(e) => report.issues.push(format!("{}: {}", asset_id, e)),
+        }
+    }
+    report
+}
+
+/// Analyze one decoded texture
+pub fn analyze_texture(
+    asset_id: &str,
+    file: &str,
+    image: &RgbaImage,
+    include_images: bool,
+) -> Result<ContrastAnalysis> {
+    let (foreground, background, against_gui_panel) = match two_tones(image) {
+        Some(Tones::Two {
+            foreground,
+            background,
+        }) => (foreground, background, false),
+        Some(Tones::One(color)) => (color, GUI_PANEL, true),
+        None => bail!("{}", tr("contrast-transparent", &[])),
+    };
+
+    let is_font = pack_files::split_asset_id(asset_id).1.starts_with("font/");
+    let required_ratio = if is_font {
+        TEXT_CONTRAST_RATIO
+    } else {
+        UI_CONTRAST_RATIO
+    };
+    let ratio = contrast_ratio(foreground, background);
+    let color_difference = delta_e(foreground, background);
+
+    let mut issues = Vec::new();
+    if ratio < required_ratio {
+        issues.push(tr(
+            "contrast-low-ratio",
+            &[
+                ("ratio", format!("{:.2}", ratio).into()),
+                ("required", format!("{:.1}", required_ratio).into()),
+            ],
+        ));
+    }
+
+    let simulations: Vec<ColorblindSimulation> = ColorVisionDeficiency::ALL
+        .iter()
+        .map(|&deficiency| {
+            let simulated_foreground = deficiency.simulate(foreground);
+            let simulated_background = if against_gui_panel {
+                background
+            } else {
+                deficiency.simulate(background)
+            };
+            let simulated_difference = delta_e(simulated_foreground, simulated_background);
+            if color_difference >= MIN_COLOR_DIFFERENCE
+                && simulated_difference < MIN_COLOR_DIFFERENCE
+            {
+                issues.push(tr(
+                    "contrast-indistinct",
+                    &[("deficiency", deficiency.name().into())],
+                ));
+            }
+            ColorblindSimulation {
+                deficiency,
+                foreground: hex(simulated_foreground),
+                background: hex(simulated_background),
+                contrast_ratio: contrast_ratio(simulated_foreground, simulated_background),
+                color_difference: simulated_difference,
+                image: include_images.then(|| simulate_image(image, deficiency)),
+            }
+        })
+        .collect();
+
+    Ok(ContrastAnalysis {
+        asset_id: asset_id.to_string(),
+        file: file.to_string(),
+        foreground: hex(foreground),
+        background: hex(background),
+        against_gui_panel,
+        contrast_ratio: ratio,
+        required_ratio,
+        color_difference,
+        simulations,
+        issues,
+    })
+}
+
+enum Tones {
+    One([u8; 3]),
+    Two {
+        foreground: [u8; 3],
+        background: [u8; 3],
+    },
+}
+
+/// Split opaque pixels into two tones, None for fully transparent textures
+fn two_tones(image: &RgbaImage) -> Option<Tones> {
+    let pixels = sample_pixels(image);
+    if pixels.is_empty() {
+        return None;
+    }
+
+    // Start from the darkest and lightest pixels
+    let by_luminance = |pixel: &&[f64; 3]| luminance_linear(**pixel);
+    let mut centers = [
+        *pixels
+            .iter()
+            .min_by(|a, b| by_luminance(a).total_cmp(&by_luminance(b)))?,
+        *pixels
+            .iter()
+            .max_by(|a, b| by_luminance(a).total_cmp(&by_luminance(b)))?,
+    ];
+    let mut counts = [0usize; 2];
+    for _ in 0..16 {
+        let mut sums = [[0.0f64; 3]; 2];
+        counts = [0; 2];
+        for pixel in &pixels {
+            let nearest =
+                usize::from(distance_sq(pixel, &centers[1]) < distance_sq(pixel, &centers[0]));
+            counts[nearest] += 1;
+            for channel in 0..3 {
+                sums[nearest][channel] += pixel[channel];
+            }
+        }
+        let mut moved = false;
+        for cluster in 0..2 {
+            if counts[cluster] == 0 {
+                continue;
+            }
+            let center = sums[cluster].map(|sum| sum / counts[cluster] as f64);
+            moved |= distance_sq(&center, &centers[cluster]) > 1e-9;
+            centers[cluster] = center;
+        }
+        if !moved {
+            break;
+        }
+    }
+
+    let colors = centers.map(|center| center.map(linear_to_srgb));
+    if counts[0] == 0 || counts[1] == 0 || delta_e(colors[0], colors[1]) < 1.0 {
+        let single = if counts[0] >= counts[1] {
+            colors[0]
+        } else {
+            colors[1]
+        };
+        return Some(Tones::One(single));
+    }
+    let (background, foreground) = if counts[0] >= counts[1] {
+        (colors[0], colors[1])
+    } else {
+        (colors[1], colors[0])
+    };
+    Some(Tones::Two {
+        foreground,
+        background,
+    })
+}
+
+/// Linear RGB of the opaque pixels, on a grid for large textures
+fn sample_pixels(image: &RgbaImage) -> Vec<[f64; 3]> {
+    let total = image.width() * image.height();
+    let step = if total > MAX_SAMPLED_PIXELS {
+        ((total as f64 / MAX_SAMPLED_PIXELS as f64).sqrt().ceil()) as u32
+    } else {
+        1
+    };
+    let mut pixels = Vec::new();
+    for y in (0..image.height()).step_by(step as usize) {
+        for x in (0..image.width()).step_by(step as usize) {
+            let Rgba([r, g, b, a]) = *image.get_pixel(x, y);
+            if a >= MIN_ALPHA {
+                pixels.push([r, g, b].map(srgb_to_linear));
+            }
+        }
+    }
+    pixels
+}
+
+/// The texture recolored as seen with a deficiency, as base64 PNG
+fn simulate_image(image: &RgbaImage, deficiency: ColorVisionDeficiency) -> String {
+    use base64::{engine::general_purpose, Engine as _};
+
+    let mut simulated = image.clone();
+    for pixel in simulated.pixels_mut() {
+        let Rgba([r, g, b, a]) = *pixel;
+        let [r, g, b] = deficiency.simulate([r, g, b]);
+        *pixel = Rgba([r, g, b, a]);
+    }
+    let mut png = Vec::new();
+    if let Err(e) = simulated.write_to(
+        &mut std::io::Cursor::new(&mut png),
+        image::ImageOutputFormat::Png,
+    ) {
+        warn!("Failed to encode simulation: {}", e);
+    }
+    general_purpose::STANDARD.encode(&png)
+}
+
+/// WCAG contrast ratio of two sRGB colors (1 to 21)
+pub fn contrast_ratio(a: [u8; 3], b: [u8; 3]) -> f64 {
+    let la = luminance_linear(a.map(srgb_to_linear));
+    let lb = luminance_linear(b.map(srgb_to_linear));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// CIE76 difference (Euclidean distance in CIELAB) of two sRGB colors
+fn delta_e(a: [u8; 3], b: [u8; 3]) -> f64 {
+    let (la, lb) = (to_lab(a), to_lab(b));
+    distance_sq(&la, &lb).sqrt()
+}
+
+/// sRGB (D65) to CIELAB
+fn to_lab(rgb: [u8; 3]) -> [f64; 3] {
+    let [r, g, b] = rgb.map(srgb_to_linear);
+    let x = (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047;
+    let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    let z = (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883;
+    let f = |t: f64| {
+        if t > 0.008856 {
+            t.cbrt()
+        } else {
+            7.787 * t + 16.0 / 116.0
+        }
+    };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+fn srgb_to_linear(channel: u8) -> f64 {
+    let c = channel as f64 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(channel: f64) -> u8 {
+    let c = channel.clamp(0.0, 1.0);
+    let encoded = if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    };
+    (encoded * 255.0).round() as u8
+}
+
+/// WCAG relative luminance of a linear RGB color
+fn luminance_linear([r, g, b]: [f64; 3]) -> f64 {
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+fn apply_matrix(matrix: &[[f64; 3]; 3], color: [f64; 3]) -> [f64; 3] {
+    matrix.map(|row| row[0] * color[0] + row[1] * color[1] + row[2] * color[2])
+}
+
+fn distance_sq(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    (0..3).map(|i| (a[i] - b[i]).powi(2)).sum()
+}
+
+fn hex([r, g, b]: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Texture with `background` everywhere except a `foreground` square in the middle
+    fn texture(foreground: [u8; 4], background: [u8; 4]) -> RgbaImage {
+        RgbaImage::from_fn(16, 16, |x, y| {
+            if (4..12).contains(&x) && (6..10).contains(&y) {
+                Rgba(foreground)
+            } else {
+                Rgba(background)
+            }
+        })
+    }
+
+    #[test]
+    fn test_contrast_ratio() {
+        assert!((contrast_ratio([0, 0, 0], [255, 255, 255]) - 21.0).abs() < 0.01);
+        assert!((contrast_ratio([128, 128, 128], [128, 128, 128]) - 1.0).abs() < 1e-9);
+        assert_eq!(
+            ColorVisionDeficiency::Achromatopsia.simulate([255, 255, 255]),
+            [255, 255, 255]
+        );
+    }
+
+    #[test]
+    fn test_analyze_texture() {
+        let readable = texture([255, 255, 255, 255], [0, 0, 0, 255]);
+        let analysis =
+            analyze_texture("minecraft:gui/widgets", "widgets.png", &readable, false).unwrap();
+        assert_eq!(analysis.foreground, "#ffffff");
+        assert_eq!(analysis.background, "#000000");
+        assert!(analysis.issues.is_empty());
+        assert_eq!(analysis.simulations.len(), 4);
+        assert!(analysis.simulations[0].image.is_none());
+
+        // Rust red on olive differs in hue only, which red-green deficiencies lose
+        let red_green = texture([180, 80, 40, 255], [110, 120, 40, 255]);
+        let analysis = analyze_texture("minecraft:gui/bars", "bars.png", &red_green, true).unwrap();
+        let indistinct: Vec<_> = analysis
+            .simulations
+            .iter()
+            .filter(|simulation| simulation.color_difference < MIN_COLOR_DIFFERENCE)
+            .map(|simulation| simulation.deficiency)
+            .collect();
+        assert!(indistinct.contains(&ColorVisionDeficiency::Deuteranopia));
+        assert!(analysis
+            .issues
+            .iter()
+            .any(|issue| issue.contains("deuteranopia")));
+        assert!(analysis.simulations[0].image.is_some());
+
+        // Pale glyphs on transparency are measured against the GUI panel
+        let glyphs = texture([220, 220, 220, 255], [0, 0, 0, 0]);
+        let analysis =
+            analyze_texture("minecraft:font/ascii", "ascii.png", &glyphs, false).unwrap();
+        assert!(analysis.against_gui_panel);
+        assert_eq!(analysis.required_ratio, TEXT_CONTRAST_RATIO);
+        assert!(!analysis.issues.is_empty());
+
+        let empty = texture([0, 0, 0, 0], [0, 0, 0, 0]);
+        assert!(analyze_texture("minecraft:gui/empty", "empty.png", &empty, false).is_err());
+    }
+}
@@ -0,0 +1,313 @@
+/// OptiFine connected textures (assets/<namespace>/optifine/ctm/**/*.properties)
+///
+/// A CTM rule swaps a block's texture for a set of tiles picked by method
+/// (ctm, horizontal, random, ...). Rules are indexed per block they apply to, so
+/// two packs with CTM for the same block show up as competing providers of
+/// one asset ("minecraft:optifine/ctm/block/glass"). The nest builder then
+/// copies the winning pack's properties and tiles for that block together.
+use crate::model::{CtmConflict, PackMeta};
+use crate::util::optifine_properties::{
+    parse_properties, read_properties_files, resolve_reference,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Folder of CTM rules within a namespace, also the prefix of CTM asset IDs
+const CTM_PATH: &str = "optifine/ctm/";
+
+/// A parsed CTM rule
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CtmRule {
+    /// Connection method (e.g., "ctm", "horizontal", "random")
+    pub method: String,
+    /// Tile entries as written ("0-46", "glass_top", "<skip>")
+    pub tiles: Vec<String>,
+    /// Block IDs this rule applies to, namespaced and without state properties
+    pub match_blocks: Vec<String>,
+    /// Texture names this rule applies to
+    pub match_tiles: Vec<String>,
+    /// Remaining options (connect, faces, biomes, heights, ...)
+    pub conditions: BTreeMap<String, String>,
+}
+
+/// Whether a pack-relative path is a CTM properties file
+pub fn is_ctm_properties(file_path: &str) -> bool {
+    file_path
+        .strip_prefix("assets/")
+        .and_then(|rest| rest.split_once('/'))
+        .is_some_and(|(_, path)| path.starts_with(CTM_PATH) && path.ends_with(".properties"))
+}
+
+/// Whether an asset ID is a per-block (or per-tile) CTM asset
+pub fn is_ctm_asset_id(asset_id: &str) -> bool {
+    asset_id
+        .split_once(':')
+        .is_some_and(|(_, path)| path.starts_with(CTM_PATH))
+}
+
+/// Normalize a matchBlocks entry to a namespaced block ID
+/// "oak_log:axis=y" -> "minecraft:oak_log", "mymod:stone" -> "mymod:stone"
+fn normalize_block(entry: &str) -> Option<String> {
+    let parts: Vec<&str> = entry
+        .split(':')
+        .take_while(|part| !part.contains('='))
+        .collect();
+    let (namespace, name) = match parts.as_slice() {
+        [name] => ("minecraft", *name),
+        [namespace, name, ..] => (*namespace, *name),
+        [] => return None,
+    };
+    // Legacy numeric block IDs can't be mapped to a block
+    if name.is_empty() || name.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(format!("{}:{}", namespace, name))
+}
+
+/// Parse the text of a CTM properties file
+pub fn parse_ctm_rule(text: &str) -> CtmRule {
+    let mut rule = CtmRule::default();
+
+    for (key, value) in parse_properties(text) {
+        match key.as_str() {
+            "method" => rule.method = value,
+            "tiles" => rule.tiles = value.split_whitespace().map(str::to_string).collect(),
+            "matchBlocks" => {
+                rule.match_blocks = value
+                    .split_whitespace()
+                    .filter_map(normalize_block)
+                    .collect();
+            }
+            "matchTiles" => {
+                rule.match_tiles = value.split_whitespace().map(str::to_string).collect();
+            }
+            _ => {
+                rule.conditions.insert(key, value);
+            }
+        }
+    }
+
+    rule
+}
+
+/// Fill in what a rule without matchBlocks or matchTiles applies to, like
+/// OptiFine: "block_<name>.properties" matches the block, any other
+/// "<name>.properties" the texture of that name
+fn apply_file_name_matches(properties_path: &str, rule: &mut CtmRule) {
+    if !rule.match_blocks.is_empty() || !rule.match_tiles.is_empty() {
+        return;
+    }
+    let stem = properties_path
+        .rsplit('/')
+        .next()
+        .and_then(|name| name.strip_suffix(".properties"));
+    let Some(stem) = stem else {
+        return;
+    };
+    match stem.strip_prefix("block_") {
+        Some(block) => rule.match_blocks.extend(normalize_block(block)),
+        None => rule.match_tiles.push(stem.to_string()),
+    }
+}
+
+/// Asset IDs a CTM rule is indexed under
+///
+/// Rules are keyed by each matched block ("minecraft:optifine/ctm/block/glass")
+/// or, for matchTiles rules, by texture ("minecraft:optifine/ctm/tile/glass").
+pub fn ctm_asset_ids(rule: &CtmRule) -> Vec<String> {
+    let mut ids: Vec<String> = rule
+        .match_blocks
+        .iter()
+        .map(|block| {
+            let (namespace, name) = block.split_once(':').unwrap_or(("minecraft", block));
+            format!("{}:{}block/{}", namespace, CTM_PATH, name)
+        })
+        .chain(rule.match_tiles.iter().map(|tile| {
+            let (namespace, name) = tile.split_once(':').unwrap_or(("minecraft", tile));
+            let name = name.rsplit('/').next().unwrap_or(name);
+            format!("{}:{}tile/{}", namespace, CTM_PATH, name)
+        }))
+        .collect();
+    ids.sort();
+    ids.dedup();
+    ids
+}
+
+/// Pack-relative paths of the tile textures a rule uses, sorted
+///
+/// Numeric ranges ("0-46") expand to numbered PNGs next to the properties
+/// file; "<skip>" and "<default>" don't refer to files.
+pub fn tile_files(properties_path: &str, rule: &CtmRule) -> Vec<String> {
+    let mut files = Vec::new();
+
+    for tile in &rule.tiles {
+        if tile.starts_with('<') {
+            continue;
+        }
+        let range = tile
+            .split_once('-')
+            .and_then(|(start, end)| Some((start.parse::<u32>().ok()?, end.parse::<u32>().ok()?)));
+        match range {
+            Some((start, end)) => {
+                for n in start..=end {
+                    files.extend(resolve_reference(properties_path, &n.to_string(), ".png"));
+                }
+            }
+            None => files.extend(resolve_reference(properties_path, tile, ".png")),
+        }
+    }
+
+    files.sort();
+    files.dedup();
+    files
+}
+
+/// Search labels for a rule: "ctm", its method and the matched block names
+pub fn ctm_labels(rule: &CtmRule) -> Vec<String> {
+    let mut labels = vec!["ctm".to_string()];
+    let names = rule
+        .match_blocks
+        .iter()
+        .chain(&rule.match_tiles)
+        .map(|name| name.split_once(':').map_or(name.as_str(), |(_, name)| name));
+    for label in std::iter::once(rule.method.as_str()).chain(names) {
+        if !label.is_empty() && !labels.iter().any(|existing| existing == label) {
+            labels.push(label.to_string());
+        }
+    }
+    labels
+}
+
+/// Read and parse the CTM rules among a pack's files
+///
+/// Returns (properties path, rule) pairs. Rules without matchBlocks or
+/// matchTiles match what their file name names.
+pub fn read_ctm_rules(pack: &PackMeta, files: &[String]) -> Vec<(String, CtmRule)> {
+    let ctm_files = files.iter().filter(|file| is_ctm_properties(file));
+    read_properties_files(pack, ctm_files)
+        .into_iter()
+        .map(|(file, text)| {
+            let mut rule = parse_ctm_rule(&text);
+            apply_file_name_matches(&file, &mut rule);
+            (file, rule)
+        })
+        .collect()
+}
+
+/// Blocks that more than one pack defines CTM for, sorted by asset ID
+pub fn find_ctm_conflicts(providers: &HashMap<String, Vec<String>>) -> Vec<CtmConflict> {
+    let mut conflicts: Vec<CtmConflict> = providers
+        .iter()
+        .filter(|(asset_id, packs)| is_ctm_asset_id(asset_id) && packs.len() > 1)
+        .map(|(asset_id, packs)| CtmConflict {
+            asset_id: asset_id.clone(),
+            packs: packs.clone(),
+        })
+        .collect();
+    conflicts.sort_by(|a, b| a.asset_id.cmp(&b.asset_id));
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ctm_rule() {
+        let rule = parse_ctm_rule(
+            "method=ctm\ntiles=0-46\nmatchBlocks=glass minecraft:oak_log:axis=y 20\nconnect=block\n",
+        );
+
+        assert_eq!(rule.method, "ctm");
+        assert_eq!(rule.tiles, vec!["0-46"]);
+        assert_eq!(
+            rule.match_blocks,
+            vec!["minecraft:glass", "minecraft:oak_log"]
+        );
+        assert_eq!(rule.conditions["connect"], "block");
+        assert_eq!(ctm_labels(&rule), vec!["ctm", "glass", "oak_log"]);
+    }
+
+    #[test]
+    fn test_ctm_asset_ids() {
+        let props = "assets/minecraft/optifine/ctm/glass/block_glass.properties";
+        let rule = parse_ctm_rule("method=ctm\ntiles=0-46\nmatchBlocks=glass white_stained_glass");
+        assert_eq!(
+            ctm_asset_ids(&rule),
+            vec![
+                "minecraft:optifine/ctm/block/glass",
+                "minecraft:optifine/ctm/block/white_stained_glass",
+            ]
+        );
+
+        let rule = parse_ctm_rule("method=horizontal\ntiles=0-3\nmatchTiles=block/bookshelf");
+        assert_eq!(
+            ctm_asset_ids(&rule),
+            vec!["minecraft:optifine/ctm/tile/bookshelf"]
+        );
+
+        // Without match keys "block_<name>" names a block and any other file
+        // name a texture
+        let file_name_ids = |props: &str| {
+            let mut rule = parse_ctm_rule("method=random\ntiles=a b");
+            apply_file_name_matches(props, &mut rule);
+            ctm_asset_ids(&rule)
+        };
+        assert_eq!(
+            file_name_ids(props),
+            vec!["minecraft:optifine/ctm/block/glass"]
+        );
+        assert_eq!(
+            file_name_ids("assets/minecraft/optifine/ctm/stone/stone_bricks.properties"),
+            vec!["minecraft:optifine/ctm/tile/stone_bricks"]
+        );
+
+        // A match key in the file wins over the file name
+        let mut rule = parse_ctm_rule("method=ctm\ntiles=0-46\nmatchTiles=glass_pane_top");
+        apply_file_name_matches(props, &mut rule);
+        assert_eq!(
+            ctm_asset_ids(&rule),
+            vec!["minecraft:optifine/ctm/tile/glass_pane_top"]
+        );
+    }
+
+    #[test]
+    fn test_tile_files() {
+        let props = "assets/minecraft/optifine/ctm/glass/glass.properties";
+        let rule = parse_ctm_rule("method=ctm\ntiles=0-2 <skip> glass_top\nmatchBlocks=glass");
+        assert_eq!(
+            tile_files(props, &rule),
+            vec![
+                "assets/minecraft/optifine/ctm/glass/0.png",
+                "assets/minecraft/optifine/ctm/glass/1.png",
+                "assets/minecraft/optifine/ctm/glass/2.png",
+                "assets/minecraft/optifine/ctm/glass/glass_top.png",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_ctm_conflicts() {
+        let providers = HashMap::from([
+            (
+                "minecraft:optifine/ctm/block/glass".to_string(),
+                vec!["pack_a".to_string(), "pack_b".to_string()],
+            ),
+            (
+                "minecraft:optifine/ctm/block/stone".to_string(),
+                vec!["pack_a".to_string()],
+            ),
+            (
+                "minecraft:block/glass".to_string(),
+                vec!["pack_a".to_string(), "pack_b".to_string()],
+            ),
+        ]);
+
+        let conflicts = find_ctm_conflicts(&providers);
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].asset_id, "minecraft:optifine/ctm/block/glass");
+        assert_eq!(conflicts[0].packs, vec!["pack_a", "pack_b"]);
+    }
+}
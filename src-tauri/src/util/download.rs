@@ -0,0 +1,514 @@
+/// Verified, resumable downloads for cached tools and Mojang files
+///
+/// Files are downloaded to `<file>.part` and only moved into place once their
+/// SHA1 matches the one piston-meta lists, so a dropped connection never leaves
+/// a truncated mappings file or jar in the cache. The next attempt continues the
+/// `.part` file with an HTTP range request instead of starting over.
+///
+/// The hash of every finished download is kept in a `<file>.sha1` sidecar, so a
+/// cached file that was damaged later (disk full, interrupted copy) is noticed
+/// and downloaded again before it produces confusing errors downstream.
+///
+/// Where piston-meta, piston-data or GitHub are blocked, the settings can point
+/// them at mirrors and route downloads through a proxy; every request made here
+/// follows those settings and their timeout and retry policy.
+use anyhow::{bail, Context, Result};
+use once_cell::sync::Lazy;
+use reqwest::header::{CONTENT_RANGE, RANGE};
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::fs;
+use std::future::Future;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Hosts a mirror can stand in for, by the setting that replaces them
+const MOJANG_META_HOSTS: [&str; 2] = [
+    "https://piston-meta.mojang.com",
+    "https://launchermeta.mojang.com",
+];
+const MOJANG_DATA_HOSTS: [&str; 2] = [
+    "https://piston-data.mojang.com",
+    "https://launcher.mojang.com",
+];
+const TOOLS_HOSTS: [&str; 1] = ["https://github.com"];
+
+static SETTINGS: Lazy<RwLock<DownloadSettings>> =
+    Lazy::new(|| RwLock::new(DownloadSettings::default()));
+
+/// Mirrors, proxy and retry policy for downloads
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DownloadSettings {
+    /// Base URL replacing piston-meta.mojang.com (version manifest and JSON)
+    pub meta_mirror: Option<String>,
+    /// Base URL replacing piston-data.mojang.com (mappings)
+    pub data_mirror: Option<String>,
+    /// Base URL replacing github.com (CFR decompiler)
+    pub tools_mirror: Option<String>,
+    /// HTTP(S) proxy for every download
+    pub proxy: Option<String>,
+    /// Seconds to wait for a connection or the next chunk of data
+    pub timeout_secs: u64,
+    /// Further attempts after a failed request, with growing pauses
+    pub retries: u32,
+}
+
+impl Default for DownloadSettings {
+    fn default() -> Self {
+        Self {
+            meta_mirror: None,
+            data_mirror: None,
+            tools_mirror: None,
+            proxy: None,
+            timeout_secs: 30,
+            retries: 2,
+        }
+    }
+}
+
+/// Replace the download settings after checking the URLs in them
+pub fn set_settings(settings: DownloadSettings) -> Result<DownloadSettings> {
+    for mirror in [
+        &settings.meta_mirror,
+        &settings.data_mirror,
+        &settings.tools_mirror,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        let url = reqwest::Url::parse(mirror)
+            .with_context(|| format!("Invalid mirror URL: {}", mirror))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            bail!("Mirror URL must use http or https: {}", mirror);
+        }
+    }
+    if let Some(proxy) = &settings.proxy {
+        reqwest::Proxy::all(proxy).with_context(|| format!("Invalid proxy URL: {}", proxy))?;
+    }
+    if settings.timeout_secs == 0 {
+        bail!("Download timeout must be at least one second");
+    }
+
+    info!(
+        "Settings: mirrors {}, proxy {}, timeout {}s, {} retries",
+        if settings.meta_mirror.is_some()
+            || settings.data_mirror.is_some()
+            || settings.tools_mirror.is_some()
+        {
+            "on"
+        } else {
+            "off"
+        },
+        settings.proxy.as_deref().unwrap_or("none"),
+        settings.timeout_secs,
+        settings.retries
+    );
+    *SETTINGS.write().unwrap_or_else(|e| e.into_inner()) = settings.clone();
+    Ok(settings)
+}
+
+/// Download settings currently in effect
+pub fn settings() -> DownloadSettings {
+    SETTINGS.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// HTTP client using the configured proxy and timeouts
+pub fn client() -> Result<reqwest::Client> {
+    let settings = settings();
+    let timeout = Duration::from_secs(settings.timeout_secs);
+    let mut builder = reqwest::Client::builder()
+        .connect_timeout(timeout)
+        .read_timeout(timeout);
+    if let Some(proxy) = &settings.proxy {
+        builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+    }
+    builder.build().context("Failed to create HTTP client")
+}
+
+/// `url` with its host replaced by the configured mirror, if any
+pub fn mirrored_url(url: &str) -> String {
+    rewrite_url(&settings(), url)
+}
+
+fn rewrite_url(settings: &DownloadSettings, url: &str) -> String {
+    let groups: [(&[&str], &Option<String>); 3] = [
+        (&MOJANG_META_HOSTS, &settings.meta_mirror),
+        (&MOJANG_DATA_HOSTS, &settings.data_mirror),
+        (&TOOLS_HOSTS, &settings.tools_mirror),
+    ];
+    for (hosts, mirror) in groups {
+        let Some(mirror) = mirror else {
+            continue;
+        };
+        for host in hosts {
+            if let Some(rest) = url.strip_prefix(host) {
+                if rest.is_empty() || rest.starts_with('/') {
+                    return format!("{}{}", mirror.trim_end_matches('/'), rest);
+                }
+            }
+        }
+    }
+    url.to_string()
+}
+
+/// Fetch and parse a JSON document, through mirrors and with retries
+pub async fn get_json<T: DeserializeOwned>(url: &str) -> Result<T> {
+    crate::util::offline::ensure_online(url)?;
+    let client = client()?;
+    let url = mirrored_url(url);
+    with_retries(&url, |_| async {
+        let response = client
+            .get(&url)
+            .send()
+            .await
+            .with_context(|| format!("Failed to fetch {}", url))?;
+        if !response.status().is_success() {
+            bail!("Failed to fetch {}: HTTP {}", url, response.status());
+        }
+        response
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse {}", url))
+    })
+    .await
+}
+
+/// Run `attempt` until it succeeds or the configured retries are used up
+///
+/// The closure gets the attempt number, starting at 0.
+async fn with_retries<T, F, Fut>(what: &str, mut attempt: F) -> Result<T>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let retries = settings().retries;
+    let mut number = 0;
+    loop {
+        match attempt(number).await {
+            Ok(value) => return Ok(value),
+            Err(e) if number < retries => {
+                let pause = Duration::from_secs(1 << number.min(5));
+                warn!(
+                    "{} failed ({:#}), retrying in {}s",
+                    what,
+                    e,
+                    pause.as_secs()
+                );
+                tokio::time::sleep(pause).await;
+                number += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Download `url` to `dest`, resuming a previous partial download
+///
+/// The URL goes through the configured mirrors, and a dropped connection is
+/// retried (continuing where it stopped) as often as the settings allow.
+///
+/// # Arguments
+/// * `client` - HTTP client to download with, usually from `client()`
+/// * `url` - File to download
+/// * `dest` - Where the finished file goes; replaced if it exists
+/// * `expected_sha1` - Lowercase hex SHA1 the file must have, when known
+pub async fn download_verified(
+    client: &reqwest::Client,
+    url: &str,
+    dest: &Path,
+    expected_sha1: Option<&str>,
+) -> Result<()> {
+    crate::util::offline::ensure_online(url)?;
+
+    let url = &mirrored_url(url);
+    let part = sidecar_path(dest, "part");
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent).context("Failed to create download directory")?;
+    }
+
+    // A resumed download that fails verification may have been continued from
+    // a stale partial file (e.g. of an older release), so retry once from scratch
+    for attempt in 0..2 {
+        let resumed = with_retries(url, |retry| {
+            fetch_into(client, url, &part, attempt == 0 || retry > 0)
+        })
+        .await?;
+        let actual = file_sha1(&part)?;
+        match expected_sha1 {
+            Some(expected) if !expected.eq_ignore_ascii_case(&actual) => {
+                let _ = fs::remove_file(&part);
+                if resumed {
+                    warn!(
+                        "Resumed download of {} failed verification, restarting",
+                        url
+                    );
+                    continue;
+                }
+                bail!(
+                    "Downloaded {} is corrupt (SHA1 {}, expected {}); try again",
+                    url,
+                    actual,
+                    expected
+                );
+            }
+            _ => {}
+        }
+
+        fs::rename(&part, dest).context("Failed to move download into place")?;
+        fs::write(sidecar_path(dest, "sha1"), &actual)
+            .context("Failed to record download checksum")?;
+        return Ok(());
+    }
+    unreachable!("the second attempt never resumes")
+}
+
+/// Fetch `url` into `part`, continuing it when `resume` is set and it exists
+///
+/// Returns whether the download continued existing partial data.
+async fn fetch_into(
+    client: &reqwest::Client,
+    url: &str,
+    part: &Path,
+    resume: bool,
+) -> Result<bool> {
+    let offset = if resume {
+        fs::metadata(part)
+            .map(|metadata| metadata.len())
+            .unwrap_or(0)
+    } else {
+        0
+    };
+
+    let mut request = client.get(url);
+    if offset > 0 {
+        info!("Resuming {} from byte {}", url, offset);
+        request = request.header(RANGE, format!("bytes={}-", offset));
+    }
+    let mut response = request
+        .send()
+        .await
+        .with_context(|| format!("Failed to download {}", url))?;
+
+    let append = match response.status() {
+        StatusCode::PARTIAL_CONTENT if offset > 0 => {
+            let range_start = response
+                .headers()
+                .get(CONTENT_RANGE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_content_range_start);
+            if range_start != Some(offset) {
+                bail!("Server resumed {} at the wrong offset", url);
+            }
+            true
+        }
+        // The partial file already holds everything; verification decides
+        StatusCode::RANGE_NOT_SATISFIABLE if offset > 0 => return Ok(true),
+        status if status.is_success() => false,
+        status => bail!("Failed to download {}: HTTP {}", url, status),
+    };
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(part)
+        .context("Failed to open partial download")?;
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .with_context(|| format!("Connection lost while downloading {}", url))?
+    {
+        file.write_all(&chunk)
+            .context("Failed to write partial download")?;
+    }
+    file.flush()?;
+    Ok(append)
+}
+
+/// First byte of a `Content-Range: bytes <start>-<end>/<total>` header
+fn parse_content_range_start(value: &str) -> Option<u64> {
+    value
+        .strip_prefix("bytes ")?
+        .split('-')
+        .next()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Whether a previously downloaded file is still intact
+///
+/// Files without a recorded checksum (downloaded by older versions) are
+/// trusted as long as they exist.
+pub fn is_cached_file_valid(path: &Path) -> bool {
+    if !path.is_file() {
+        return false;
+    }
+    let Ok(recorded) = fs::read_to_string(sidecar_path(path, "sha1")) else {
+        return true;
+    };
+    file_sha1(path).is_ok_and(|actual| actual.eq_ignore_ascii_case(recorded.trim()))
+}
+
+/// Remove a cached download along with its checksum and any partial data
+pub fn discard_cached_file(path: &Path) {
+    for file in [
+        path.to_path_buf(),
+        sidecar_path(path, "sha1"),
+        sidecar_path(path, "part"),
+    ] {
+        let _ = fs::remove_file(file);
+    }
+}
+
+/// Lowercase hex SHA1 of a file
+pub fn file_sha1(path: &Path) -> Result<String> {
+    let mut file =
+        fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut hasher = Sha1::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn sidecar_path(path: &Path, extension: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".");
+    name.push(extension);
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+
+    /// Serve `body` for each incoming connection, honoring `Range: bytes=N-`
+    fn serve(body: Vec<u8>, connections: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/file.bin", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming().take(connections) {
+                let mut stream = stream.unwrap();
+                let mut offset = 0;
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some(range) = line.to_ascii_lowercase().strip_prefix("range: bytes=") {
+                        offset = range.trim().trim_end_matches('-').parse().unwrap();
+                    }
+                }
+                let head = if offset > 0 {
+                    format!(
+                        "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes {}-{}/{}\r\n",
+                        offset,
+                        body.len() - 1,
+                        body.len()
+                    )
+                } else {
+                    "HTTP/1.1 200 OK\r\n".to_string()
+                };
+                let rest = &body[offset..];
+                write!(
+                    stream,
+                    "{}Content-Length: {}\r\nConnection: close\r\n\r\n",
+                    head,
+                    rest.len()
+                )
+                .unwrap();
+                stream.write_all(rest).unwrap();
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn test_download_resumes_and_verifies() {
+        let temp_dir = std::env::temp_dir().join("test_download_verified");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+        let dest = temp_dir.join("client.txt");
+        let body: Vec<u8> = (0..10_000u32).flat_map(|n| n.to_le_bytes()).collect();
+        let sha1 = format!("{:x}", Sha1::digest(&body));
+
+        // Half the file is left over from an interrupted download
+        fs::write(sidecar_path(&dest, "part"), &body[..body.len() / 2]).unwrap();
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let resumed = runtime.block_on(download_verified(
+            &client,
+            &serve(body.clone(), 1),
+            &dest,
+            Some(&sha1),
+        ));
+        let downloaded = fs::read(&dest).ok();
+        let valid = is_cached_file_valid(&dest);
+
+        // A damaged cached file is detected by its recorded checksum
+        fs::write(&dest, b"truncated").unwrap();
+        let damaged_valid = is_cached_file_valid(&dest);
+
+        // A wrong checksum never reaches the destination
+        let other = temp_dir.join("other.txt");
+        let mismatch = runtime.block_on(download_verified(
+            &client,
+            &serve(body.clone(), 1),
+            &other,
+            Some("0000000000000000000000000000000000000000"),
+        ));
+        let other_exists = other.exists() || sidecar_path(&other, "part").exists();
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert!(resumed.is_ok(), "{:?}", resumed);
+        assert_eq!(downloaded, Some(body));
+        assert!(valid);
+        assert!(!damaged_valid);
+        assert!(mismatch.is_err());
+        assert!(!other_exists);
+    }
+
+    #[test]
+    fn test_rewrite_url() {
+        let settings = DownloadSettings {
+            meta_mirror: Some("https://mirror.example/meta/".to_string()),
+            data_mirror: Some("https://mirror.example/data".to_string()),
+            ..Default::default()
+        };
+        let manifest = "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json";
+
+        assert_eq!(
+            rewrite_url(&settings, manifest),
+            "https://mirror.example/meta/mc/game/version_manifest_v2.json"
+        );
+        assert_eq!(
+            rewrite_url(
+                &settings,
+                "https://piston-data.mojang.com/v1/objects/abc/client.txt"
+            ),
+            "https://mirror.example/data/v1/objects/abc/client.txt"
+        );
+        // No tools mirror set, and look-alike hosts are left alone
+        let cfr = "https://github.com/leibnitz27/cfr/releases/download/0.152/cfr-0.152.jar";
+        assert_eq!(rewrite_url(&settings, cfr), cfr);
+        assert_eq!(
+            rewrite_url(&settings, "https://piston-meta.mojang.com.evil/x"),
+            "https://piston-meta.mojang.com.evil/x"
+        );
+        assert_eq!(
+            rewrite_url(&DownloadSettings::default(), manifest),
+            manifest
+        );
+    }
+}
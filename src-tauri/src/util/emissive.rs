@@ -0,0 +1,161 @@
+/// Emissive companion textures (OptiFine / Entity Texture Features)
+///
+/// An emissive texture is drawn as a glow layer over the texture it is named
+/// after: "block/stone_e.png" lights up "block/stone.png". The suffix is set by
+/// suffix.emissive in assets/minecraft/optifine/emissive.properties and is
+/// "_e" by convention. Companions are indexed under their base texture's asset
+/// ID, so the nest builder always takes both layers from the same pack.
+use crate::model::{AssetRecord, PackMeta};
+use crate::util::optifine_properties::{parse_properties, read_properties_files};
+
+/// Pack-relative path of the file that configures the emissive suffix
+pub const EMISSIVE_PROPERTIES: &str = "assets/minecraft/optifine/emissive.properties";
+
+/// Label given to textures with a glow layer and to glow layers without a base
+pub const EMISSIVE_LABEL: &str = "emissive";
+
+/// Suffix used when a pack doesn't configure one, and the suffix nests are written with
+pub const DEFAULT_EMISSIVE_SUFFIX: &str = "_e";
+
+/// Emissive suffix a pack uses, read from its emissive.properties if present
+pub fn emissive_suffix(pack: &PackMeta, files: &[String]) -> String {
+    let config = files.iter().filter(|file| *file == EMISSIVE_PROPERTIES);
+    read_properties_files(pack, config)
+        .into_iter()
+        .find_map(|(_, text)| {
+            parse_properties(&text)
+                .remove("suffix.emissive")
+                .filter(|suffix| !suffix.is_empty())
+        })
+        .unwrap_or_else(|| DEFAULT_EMISSIVE_SUFFIX.to_string())
+}
+
+/// Path of the base texture an emissive texture belongs to, or None if `file`
+/// isn't an emissive texture
+/// "assets/minecraft/textures/block/stone_e.png" -> "assets/minecraft/textures/block/stone.png"
+pub fn emissive_base_path(file: &str, suffix: &str) -> Option<String> {
+    if !file.starts_with("assets/") || !file.contains("/textures/") {
+        return None;
+    }
+    let stem = file.strip_suffix(".png")?;
+    let base = stem.strip_suffix(suffix)?;
+    if base.ends_with('/') {
+        return None;
+    }
+    Some(format!("{}.png", base))
+}
+
+/// Suffix of an emissive companion relative to its base texture
+/// ("textures/block/stone.png", "textures/block/stone_glow.png") -> "_glow"
+pub fn companion_suffix<'a>(base_file: &str, companion: &'a str) -> Option<&'a str> {
+    let base_stem = base_file.strip_suffix(".png")?;
+    let suffix = companion.strip_suffix(".png")?.strip_prefix(base_stem)?;
+    if suffix.is_empty() || suffix.contains('/') {
+        return None;
+    }
+    Some(suffix)
+}
+
+/// Emissive companions to copy with a winning texture
+///
+/// `asset_files` are the files indexed for the asset, whose first entry is its
+/// base texture; `source_file` is the texture actually copied (the base, or an
+/// override variant). Returns (source path, output path) pairs for every
+/// suffix seen across packs, since any one pack only has some of them; outputs
+/// are renamed to DEFAULT_EMISSIVE_SUFFIX so layers from packs with different
+/// suffixes work under the nest's single emissive.properties.
+pub fn companion_paths(asset_files: &[String], source_file: &str) -> Vec<(String, String)> {
+    let (Some(base_file), Some(source_stem)) =
+        (asset_files.first(), source_file.strip_suffix(".png"))
+    else {
+        return Vec::new();
+    };
+
+    let mut paths: Vec<(String, String)> = asset_files
+        .iter()
+        .skip(1)
+        .filter_map(|file| companion_suffix(base_file, file))
+        .map(|suffix| {
+            (
+                format!("{}{}.png", source_stem, suffix),
+                format!("{}{}.png", source_stem, DEFAULT_EMISSIVE_SUFFIX),
+            )
+        })
+        .collect();
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+/// Whether an asset is a glow layer indexed without its base texture
+///
+/// Indexed base textures carry their companions in their file list, so an
+/// emissive-labelled asset without companions is a lone glow layer.
+pub fn is_orphan_layer(asset: &AssetRecord) -> bool {
+    asset.labels.iter().any(|label| label == EMISSIVE_LABEL)
+        && asset
+            .files
+            .first()
+            .is_some_and(|file| companion_paths(&asset.files, file).is_empty())
+}
+
+/// Contents of the emissive.properties written into a nest
+pub fn nest_emissive_properties() -> String {
+    format!("suffix.emissive={}\n", DEFAULT_EMISSIVE_SUFFIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emissive_base_path() {
+        assert_eq!(
+            emissive_base_path("assets/minecraft/textures/block/stone_e.png", "_e"),
+            Some("assets/minecraft/textures/block/stone.png".to_string())
+        );
+        assert_eq!(
+            emissive_base_path("assets/minecraft/textures/entity/creeper_glow.png", "_glow"),
+            Some("assets/minecraft/textures/entity/creeper.png".to_string())
+        );
+        assert_eq!(
+            emissive_base_path("assets/minecraft/textures/block/stone.png", "_e"),
+            None
+        );
+        assert_eq!(
+            emissive_base_path("assets/minecraft/textures/block/_e.png", "_e"),
+            None
+        );
+        assert_eq!(
+            emissive_base_path("assets/minecraft/models/block/stone_e.png", "_e"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_companion_paths() {
+        let files = vec![
+            "assets/minecraft/textures/block/stone.png".to_string(),
+            "assets/minecraft/models/block/stone.json".to_string(),
+            "assets/minecraft/textures/block/stone_glow.png".to_string(),
+        ];
+
+        assert_eq!(
+            companion_paths(&files, "assets/minecraft/textures/block/stone.png"),
+            vec![(
+                "assets/minecraft/textures/block/stone_glow.png".to_string(),
+                "assets/minecraft/textures/block/stone_e.png".to_string(),
+            )]
+        );
+        assert_eq!(
+            companion_paths(&files, "assets/minecraft/textures/block/stone_mossy.png"),
+            vec![(
+                "assets/minecraft/textures/block/stone_mossy_glow.png".to_string(),
+                "assets/minecraft/textures/block/stone_mossy_e.png".to_string(),
+            )]
+        );
+        assert!(
+            companion_paths(&files[..1], "assets/minecraft/textures/block/stone.png").is_empty()
+        );
+    }
+}
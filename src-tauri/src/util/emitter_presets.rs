@@ -0,0 +1,605 @@
+/// Emitter Presets
+///
+/// Turns extracted block/entity emissions plus particle physics into numeric emitter
+/// configs (spawn chance, count, offset and velocity ranges) the frontend particle
+/// system can consume directly instead of evaluating Java expressions itself.
+///
+/// Expressions are bounded with interval arithmetic relative to the block origin.
+/// Anything the evaluator can't bound (direction math, gaussians, entity state) is
+/// left as None alongside the raw expression.
+use crate::util::block_particle_extractor::{ExtractedBlockEmission, ExtractedBlockEmissions};
+use crate::util::particle_physics_extractor::{ExtractedParticlePhysics, ExtractedPhysicsData};
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Chance the block at the player's position is picked by the client's
+/// animateTick sampling in one tick, `animate_tick_chance([0, 0, 0])`
+///
+/// Previews are watched from close by, so presets use this chance; a block 8
+/// blocks off is picked only about half as often.
+pub const ANIMATE_TICK_SAMPLE_CHANCE: f32 = 0.167_412_7;
+
+/// Rounds of `ClientLevel.animateTick` per tick
+const ANIMATE_TICK_ROUNDS: i32 = 667;
+
+/// Radii of the two `doAnimateTick` samples each round takes
+const ANIMATE_TICK_RADII: [i32; 2] = [16, 32];
+
+/// Chance a block at `offset` from the player is picked by animateTick in one
+/// tick
+///
+/// Each round samples one position per radius r, every axis offset by
+/// `nextInt(r) - nextInt(r)`: a triangular distribution that hits d with
+/// chance (r - |d|) / r². The block is picked when any sample lands on it.
+pub fn animate_tick_chance(offset: [i32; 3]) -> f64 {
+    let round_miss: f64 = ANIMATE_TICK_RADII
+        .iter()
+        .map(|&radius| {
+            let hit: f64 = offset
+                .iter()
+                .map(|d| (radius - d.abs()).max(0) as f64 / (radius * radius) as f64)
+                .product();
+            1.0 - hit
+        })
+        .product();
+    1.0 - round_miss.powi(ANIMATE_TICK_ROUNDS)
+}
+
+static RANDOM_THRESHOLD_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:[\w$.]*\.)?(?:nextFloat|nextDouble|random)\(\)\s*<=?\s*([0-9.]+)[fFdD]?$")
+        .unwrap()
+});
+static NEXT_INT_ZERO_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?:[\w$.]*\.)?nextInt\((\d+)\)\s*==\s*0$").unwrap());
+static CAST_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\((?:double|float|int)\)").unwrap());
+
+/// A ready-to-consume emitter for one particle emission of a source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmitterConfig {
+    pub particle_id: String,
+    /// Block state condition required to emit (e.g., "LIT")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+    /// Method the emission comes from (e.g., "animateTick", "particleTick", "extinguish")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emission_source: Option<String>,
+    /// True when the emission runs every tick rather than on an event
+    pub periodic: bool,
+    /// Chance per tick that an emission attempt happens (sampling × probability).
+    /// None if the probability expression couldn't be evaluated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spawn_chance_per_tick: Option<f32>,
+    /// Particles per emission attempt [min, max] (includes loop counts)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<[u32; 2]>,
+    /// Spawn position range relative to the block origin
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset_min: Option<[f32; 3]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset_max: Option<[f32; 3]>,
+    /// Initial velocity range (blocks per tick)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub velocity_min: Option<[f32; 3]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub velocity_max: Option<[f32; 3]>,
+    pub always_visible: bool,
+    /// Extracted physics for the particle, if available
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub physics: Option<ExtractedParticlePhysics>,
+    /// Original emission (raw expressions) for anything the preset couldn't bound
+    pub emission: ExtractedBlockEmission,
+}
+
+/// All emitters for a block or entity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmitterPreset {
+    /// Normalized source ID (e.g., "campfire")
+    pub source: String,
+    /// "block" or "entity"
+    pub source_kind: String,
+    pub version: String,
+    pub emitters: Vec<EmitterConfig>,
+}
+
+/// Build the emitter preset for a block or entity source
+///
+/// # Arguments
+/// * `source` - Block or entity ID (e.g., "campfire", "minecraft:torch", "nether_portal")
+/// * `emissions` - Extracted emission data for the version
+/// * `physics` - Extracted particle physics for the version (optional)
+pub fn build_emitter_preset(
+    source: &str,
+    emissions: &ExtractedBlockEmissions,
+    physics: Option<&ExtractedPhysicsData>,
+) -> Result<EmitterPreset> {
+    let source_id = source.strip_prefix("minecraft:").unwrap_or(source);
+
+    let (source_kind, data) = if let Some(data) = emissions.blocks.get(source_id) {
+        ("block", data)
+    } else if let Some(data) = emissions.entities.get(source_id) {
+        ("entity", data)
+    } else {
+        return Err(anyhow!("No particle emissions found for {}", source));
+    };
+    let is_campfire = data.class_name.ends_with("CampfireBlock");
+
+    let emitters = data
+        .emissions
+        .iter()
+        .map(|emission| {
+            let particle_id = emission.particle_id.to_lowercase();
+            let (periodic, sample_chance) =
+                source_sampling(emission.emission_source.as_deref(), is_campfire);
+
+            let probability = match emission.probability_expr.as_deref() {
+                Some(expr) => parse_probability(expr),
+                None => Some(1.0),
+            };
+
+            let count = emission
+                .count_expr
+                .as_deref()
+                .map_or(Some(Interval::point(1.0)), eval_interval)
+                .zip(
+                    emission
+                        .loop_count_expr
+                        .as_deref()
+                        .map_or(Some(Interval::point(1.0)), eval_interval),
+                )
+                .map(|(count, loops)| count.mul(loops))
+                .map(|range| [range.min.max(0.0) as u32, range.max.max(0.0) as u32]);
+
+            let (offset_min, offset_max) = bound_vec3(emission.position_offset.as_ref());
+            let (velocity_min, velocity_max) = bound_vec3(emission.velocity.as_ref());
+
+            EmitterConfig {
+                physics: physics.and_then(|p| p.particles.get(&particle_id).cloned()),
+                particle_id,
+                condition: emission.condition.clone(),
+                emission_source: emission.emission_source.clone(),
+                periodic,
+                spawn_chance_per_tick: probability.map(|p| p * sample_chance),
+                count,
+                offset_min,
+                offset_max,
+                velocity_min,
+                velocity_max,
+                always_visible: emission.always_visible,
+                emission: emission.clone(),
+            }
+        })
+        .collect();
+
+    Ok(EmitterPreset {
+        source: source_id.to_string(),
+        source_kind: source_kind.to_string(),
+        version: emissions.version.clone(),
+        emitters,
+    })
+}
+
+/// How often an emission method runs: (periodic, chance per tick)
+fn source_sampling(emission_source: Option<&str>, is_campfire: bool) -> (bool, f32) {
+    match emission_source {
+        // Campfires emit from their block entity tick, everything else from animateTick
+        Some("makeParticles") if is_campfire => (true, 1.0),
+        Some("animateTick") | Some("makeParticles") | Some("addParticlesAndSound") | None => {
+            (true, ANIMATE_TICK_SAMPLE_CHANCE)
+        }
+        Some("particleTick") | Some("tick") | Some("aiStep") | Some("doClientTick") => (true, 1.0),
+        // Event-driven (extinguish, onHit, ...): fires once per event
+        Some(_) => (false, 1.0),
+    }
+}
+
+/// Parse a probability expression into a chance in [0, 1]
+fn parse_probability(expr: &str) -> Option<f32> {
+    let expr = expr.trim();
+    if let Some(caps) = NEXT_INT_ZERO_RE.captures(expr) {
+        let bound: f32 = caps[1].parse().ok()?;
+        return (bound > 0.0).then_some(1.0 / bound);
+    }
+    if let Some(caps) = RANDOM_THRESHOLD_RE.captures(expr) {
+        let threshold: f32 = caps[1].parse().ok()?;
+        return Some(threshold.clamp(0.0, 1.0));
+    }
+    None
+}
+
+fn bound_vec3(exprs: Option<&[String; 3]>) -> (Option<[f32; 3]>, Option<[f32; 3]>) {
+    let Some(exprs) = exprs else {
+        return (None, None);
+    };
+    let bounds: Option<Vec<Interval>> = exprs.iter().map(|e| eval_interval(e)).collect();
+    match bounds {
+        Some(b) => (
+            Some([b[0].min as f32, b[1].min as f32, b[2].min as f32]),
+            Some([b[0].max as f32, b[1].max as f32, b[2].max as f32]),
+        ),
+        None => (None, None),
+    }
+}
+
+/// Closed numeric range produced by the expression evaluator
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Interval {
+    fn new(a: f64, b: f64) -> Self {
+        Interval {
+            min: a.min(b),
+            max: a.max(b),
+        }
+    }
+
+    fn point(v: f64) -> Self {
+        Interval { min: v, max: v }
+    }
+
+    fn add(self, o: Interval) -> Self {
+        Interval::new(self.min + o.min, self.max + o.max)
+    }
+
+    fn sub(self, o: Interval) -> Self {
+        Interval::new(self.min - o.max, self.max - o.min)
+    }
+
+    fn mul(self, o: Interval) -> Self {
+        let products = [
+            self.min * o.min,
+            self.min * o.max,
+            self.max * o.min,
+            self.max * o.max,
+        ];
+        Interval {
+            min: products.iter().cloned().fold(f64::INFINITY, f64::min),
+            max: products.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+
+    fn div(self, o: Interval) -> Option<Self> {
+        if o.min <= 0.0 && o.max >= 0.0 {
+            return None;
+        }
+        Some(self.mul(Interval::new(1.0 / o.min, 1.0 / o.max)))
+    }
+
+    fn union(self, o: Interval) -> Self {
+        Interval::new(self.min.min(o.min), self.max.max(o.max))
+    }
+}
+
+/// Bound a decompiled Java expression relative to the block origin
+///
+/// Supports literals, + - * /, parentheses, casts, block position getters (→ 0),
+/// `Math.random()` / `nextFloat()` / `nextDouble()` (→ [0, 1]), `nextInt(n)`
+/// (→ [0, n-1]) and ternaries (→ union of both branches).
+pub fn eval_interval(expr: &str) -> Option<Interval> {
+    let cleaned = CAST_RE.replace_all(expr, "");
+    let tokens = tokenize(&cleaned)?;
+    let mut parser = IntervalParser { tokens, pos: 0 };
+    let result = parser.ternary()?;
+    (parser.pos == parser.tokens.len()).then_some(result)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Num(f64),
+    Ident(String),
+    Op(char),
+}
+
+fn tokenize(expr: &str) -> Option<Vec<Token>> {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit()
+            || (c == '.' && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit()))
+        {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_ascii_digit()
+                    || chars[i] == '.'
+                    || ((chars[i] == 'E' || chars[i] == 'e')
+                        && chars
+                            .get(i + 1)
+                            .is_some_and(|n| n.is_ascii_digit() || *n == '-')))
+            {
+                if chars[i] == 'E' || chars[i] == 'e' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            let literal: String = chars[start..i].iter().collect();
+            // Java float/double suffixes
+            if i < chars.len() && matches!(chars[i], 'f' | 'F' | 'd' | 'D') {
+                i += 1;
+            }
+            tokens.push(Token::Num(literal.parse().ok()?));
+        } else if c.is_alphabetic() || c == '$' || c == '_' {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || matches!(chars[i], '$' | '_' | '.'))
+            {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else if "+-*/()?:,".contains(c) {
+            tokens.push(Token::Op(c));
+            i += 1;
+        } else {
+            return None;
+        }
+    }
+
+    Some(tokens)
+}
+
+struct IntervalParser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl IntervalParser {
+    fn peek_op(&self) -> Option<char> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Op(c)) => Some(*c),
+            _ => None,
+        }
+    }
+
+    fn expect_op(&mut self, op: char) -> Option<()> {
+        (self.peek_op() == Some(op)).then(|| self.pos += 1)
+    }
+
+    fn ternary(&mut self) -> Option<Interval> {
+        let condition = self.additive()?;
+        if self.expect_op('?').is_none() {
+            return Some(condition);
+        }
+        // Conditions are random or state-dependent, so either branch can be taken
+        let a = self.ternary()?;
+        self.expect_op(':')?;
+        let b = self.ternary()?;
+        Some(a.union(b))
+    }
+
+    fn additive(&mut self) -> Option<Interval> {
+        let mut value = self.multiplicative()?;
+        while let Some(op) = self.peek_op() {
+            match op {
+                '+' => {
+                    self.pos += 1;
+                    value = value.add(self.multiplicative()?);
+                }
+                '-' => {
+                    self.pos += 1;
+                    value = value.sub(self.multiplicative()?);
+                }
+                _ => break,
+            }
+        }
+        Some(value)
+    }
+
+    fn multiplicative(&mut self) -> Option<Interval> {
+        let mut value = self.unary()?;
+        while let Some(op) = self.peek_op() {
+            match op {
+                '*' => {
+                    self.pos += 1;
+                    value = value.mul(self.unary()?);
+                }
+                '/' => {
+                    self.pos += 1;
+                    value = value.div(self.unary()?)?;
+                }
+                _ => break,
+            }
+        }
+        Some(value)
+    }
+
+    fn unary(&mut self) -> Option<Interval> {
+        if self.peek_op() == Some('-') {
+            self.pos += 1;
+            return Some(Interval::point(0.0).sub(self.unary()?));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Option<Interval> {
+        match self.tokens.get(self.pos).cloned()? {
+            Token::Num(v) => {
+                self.pos += 1;
+                Some(Interval::point(v))
+            }
+            Token::Op('(') => {
+                self.pos += 1;
+                let value = self.ternary()?;
+                self.expect_op(')')?;
+                Some(value)
+            }
+            Token::Ident(name) => {
+                self.pos += 1;
+                self.expect_op('(')?;
+                let method = name.rsplit('.').next().unwrap_or(&name);
+                match method {
+                    // Block/entity origin: offsets are reported relative to it
+                    "getX" | "getY" | "getZ" => {
+                        self.expect_op(')')?;
+                        Some(Interval::point(0.0))
+                    }
+                    "random" | "nextFloat" | "nextDouble" | "nextBoolean" => {
+                        self.expect_op(')')?;
+                        Some(Interval::new(0.0, 1.0))
+                    }
+                    "nextInt" => {
+                        let bound = self.ternary()?;
+                        self.expect_op(')')?;
+                        Some(Interval::new(0.0, (bound.max - 1.0).max(0.0)))
+                    }
+                    _ => None,
+                }
+            }
+            Token::Op(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::block_particle_extractor::BlockEmissionData;
+    use std::collections::HashMap;
+
+    fn emission(
+        particle_id: &str,
+        position: [&str; 3],
+        velocity: [&str; 3],
+    ) -> ExtractedBlockEmission {
+        ExtractedBlockEmission {
+            particle_id: particle_id.to_string(),
+            options: None,
+            condition: None,
+            position_offset: Some(position.map(String::from)),
+            velocity: Some(velocity.map(String::from)),
+            probability_expr: None,
+            count_expr: None,
+            loop_count_expr: None,
+            loop_index_var: None,
+            always_visible: false,
+            emission_source: Some("animateTick".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_animate_tick_chance() {
+        // Chance one sample lands on `offset`, counting every pair of nextInt
+        // rolls per axis
+        let sample_hit = |radius: i32, offset: [i32; 3]| -> f64 {
+            offset
+                .iter()
+                .map(|&d| {
+                    let pairs = (0..radius)
+                        .flat_map(|a| (0..radius).map(move |b| a - b))
+                        .filter(|&delta| delta == d)
+                        .count();
+                    pairs as f64 / (radius * radius) as f64
+                })
+                .product()
+        };
+        for offset in [[0, 0, 0], [1, -2, 3], [15, 0, -4], [20, 1, 1]] {
+            let round_miss = (1.0 - sample_hit(16, offset)) * (1.0 - sample_hit(32, offset));
+            let expected = 1.0 - round_miss.powi(667);
+            assert!((animate_tick_chance(offset) - expected).abs() < 1e-12);
+        }
+
+        assert!((animate_tick_chance([0, 0, 0]) as f32 - ANIMATE_TICK_SAMPLE_CHANCE).abs() < 1e-6);
+        assert!(animate_tick_chance([8, 0, 0]) < animate_tick_chance([0, 0, 0]) * 0.6);
+        assert_eq!(animate_tick_chance([32, 0, 0]), 0.0);
+    }
+
+    #[test]
+    fn test_eval_interval_basic_arithmetic() {
+        assert_eq!(
+            eval_interval("($2.getX() + 0.5)"),
+            Some(Interval::point(0.5))
+        );
+        assert_eq!(eval_interval("5.0E-4"), Some(Interval::point(0.0005)));
+        assert_eq!(
+            eval_interval("$2.getY() + Math.random() + Math.random()"),
+            Some(Interval::new(0.0, 2.0))
+        );
+        assert_eq!(
+            eval_interval("((Math.random() - 0.5) * 0.5)"),
+            Some(Interval::new(-0.25, 0.25))
+        );
+        assert_eq!(
+            eval_interval("(double)$3.nextInt(4) / 2.0f"),
+            Some(Interval::new(0.0, 1.5))
+        );
+    }
+
+    #[test]
+    fn test_eval_interval_ternary_and_unknowns() {
+        let range =
+            eval_interval("$1.getX() + 0.5 + Math.random() / 3.0 * ($4.nextBoolean() ? 1 : -1)")
+                .unwrap();
+        assert!((range.min - (0.5 - 1.0 / 3.0)).abs() < 1e-9);
+        assert!((range.max - (0.5 + 1.0 / 3.0)).abs() < 1e-9);
+
+        assert!(eval_interval("Direction.from2DDataValue(2).getStepX() * 0.3").is_none());
+        assert!(eval_interval("$1.nextGaussian()").is_none());
+        assert!(eval_interval("1 / Math.random()").is_none());
+    }
+
+    #[test]
+    fn test_parse_probability() {
+        assert_eq!(parse_probability("$3.nextInt(5) == 0"), Some(0.2));
+        assert_eq!(parse_probability("nextFloat() < 0.3"), Some(0.3));
+        assert_eq!(parse_probability("Math.random() <= 0.7"), Some(0.7));
+        assert_eq!(parse_probability("Math.random() < $1"), None);
+    }
+
+    #[test]
+    fn test_build_emitter_preset() {
+        let mut portal = emission(
+            "PORTAL",
+            [
+                "($2.getX() + Math.random())",
+                "($2.getY() + Math.random())",
+                "($2.getZ() + Math.random())",
+            ],
+            [
+                "((Math.random() - 0.5) * 0.5)",
+                "0.0",
+                "Direction.UP.getStepZ()",
+            ],
+        );
+        portal.count_expr = Some("4".to_string());
+        portal.probability_expr = Some("$3.nextInt(100) == 0".to_string());
+
+        let emissions = ExtractedBlockEmissions {
+            schema_version: 8,
+            version: "1.21.4".to_string(),
+            blocks: HashMap::from([(
+                "nether_portal".to_string(),
+                BlockEmissionData {
+                    class_name: "net.minecraft.world.level.block.NetherPortalBlock".to_string(),
+                    emissions: vec![portal],
+                },
+            )]),
+            entities: HashMap::new(),
+        };
+
+        let preset = build_emitter_preset("minecraft:nether_portal", &emissions, None).unwrap();
+        assert_eq!(preset.source, "nether_portal");
+        assert_eq!(preset.source_kind, "block");
+
+        let emitter = &preset.emitters[0];
+        assert_eq!(emitter.particle_id, "portal");
+        assert!(emitter.periodic);
+        assert_eq!(emitter.count, Some([4, 4]));
+        assert_eq!(emitter.offset_min, Some([0.0, 0.0, 0.0]));
+        assert_eq!(emitter.offset_max, Some([1.0, 1.0, 1.0]));
+        // One velocity axis can't be bounded, so the whole range is withheld
+        assert_eq!(emitter.velocity_min, None);
+        let chance = emitter.spawn_chance_per_tick.unwrap();
+        assert!((chance - ANIMATE_TICK_SAMPLE_CHANCE * 0.01).abs() < 1e-6);
+
+        assert!(build_emitter_preset("stone", &emissions, None).is_err());
+    }
+}
@@ -0,0 +1,1259 @@
+/// Vanilla entity geometry from decompiled model classes
+///
+/// Every entity model class builds its geometry in a static
+/// `createBodyLayer()`: a MeshDefinition whose parts are added with
+/// `addOrReplaceChild(name, CubeListBuilder, PartPose)` and wrapped in
+/// `LayerDefinition.create(mesh, width, height)`. Rather than pattern-match
+/// that code, this module runs it on a small interpreter over the tree-sitter
+/// AST, following local variables, counted loops and calls into helpers such
+/// as `QuadrupedModel.createBodyMesh(...)`, so each version's geometry comes
+/// out exactly as the game builds it.
+///
+/// The result is written as a JEM in OptiFine's layout next to the version's
+/// vanilla assets, where `jem::vanilla_jem_dirs` looks before the bundled
+/// `__mocks__/cem` templates. A layer using code the interpreter doesn't
+/// follow (lambdas, arrays, instance state) is logged and left out, and that
+/// entity keeps its bundled template.
+use super::jem::{self, JemBox, JemFile, JemPart};
+use anyhow::{anyhow, bail, Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use tracing::{info, warn};
+use tree_sitter::{Node, Parser, Tree};
+use walkdir::WalkDir;
+
+/// Helper calls followed inside one another before giving up
+const MAX_CALL_DEPTH: usize = 8;
+
+/// Loop iterations run for one layer before giving up
+const MAX_LOOP_STEPS: usize = 10_000;
+
+/// A cuboid as `addBox` defines it, relative to its part's pivot
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModelCube {
+    /// Minimum corner
+    pub origin: [f32; 3],
+    pub size: [f32; 3],
+    pub tex_offset: [f32; 2],
+    pub mirror: bool,
+    /// CubeDeformation growth along x, y and z
+    pub grow: [f32; 3],
+}
+
+/// A part of a vanilla model, in the game's model space (y down, ground at 24)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModelPart {
+    pub name: String,
+    /// Pivot, relative to the parent's pivot
+    pub offset: [f32; 3],
+    /// Radians around x, y and z
+    pub rotation: [f32; 3],
+    pub cubes: Vec<ModelCube>,
+    pub children: Vec<ModelPart>,
+}
+
+/// The geometry a LayerDefinition describes
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LayerGeometry {
+    pub texture_size: [f32; 2],
+    pub parts: Vec<ModelPart>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Pose {
+    offset: [f32; 3],
+    rotation: [f32; 3],
+}
+
+/// A CubeListBuilder; builders are mutable, so values refer to them by index
+#[derive(Debug, Clone, Default)]
+struct CubeList {
+    tex_offset: [f32; 2],
+    mirror: bool,
+    cubes: Vec<ModelCube>,
+}
+
+#[derive(Debug, Clone)]
+struct PartNode {
+    name: String,
+    parent: Option<usize>,
+    pose: Pose,
+    cubes: Vec<ModelCube>,
+    attached: bool,
+}
+
+/// A value the interpreted code computes
+#[derive(Debug, Clone)]
+enum JavaValue {
+    Int(i64),
+    Float(f32),
+    Bool(bool),
+    Str(String),
+    Mesh(usize),
+    /// Mesh index, node index
+    Part(usize, usize),
+    Cubes(usize),
+    Pose(Pose),
+    Deformation([f32; 3]),
+    /// Mesh index, texture size
+    Layer(usize, [f32; 2]),
+    /// Something the geometry doesn't depend on (a MeshTransformer, the
+    /// visible faces of a box)
+    Ignored,
+}
+
+impl JavaValue {
+    fn number(&self) -> Result<f32> {
+        match self {
+            JavaValue::Int(value) => Ok(*value as f32),
+            JavaValue::Float(value) => Ok(*value),
+            other => bail!("Expected a number, got {:?}", other),
+        }
+    }
+
+    fn boolean(&self) -> Result<bool> {
+        match self {
+            JavaValue::Bool(value) => Ok(*value),
+            other => bail!("Expected a boolean, got {:?}", other),
+        }
+    }
+
+    fn string(&self) -> Result<String> {
+        match self {
+            JavaValue::Str(value) => Ok(value.clone()),
+            JavaValue::Int(value) => Ok(value.to_string()),
+            JavaValue::Float(value) => Ok(value.to_string()),
+            JavaValue::Bool(value) => Ok(value.to_string()),
+            other => bail!("Expected a string, got {:?}", other),
+        }
+    }
+}
+
+/// A parsed class source
+struct ClassSource {
+    name: String,
+    text: String,
+    tree: Tree,
+}
+
+/// Local variables of one method call
+struct Frame<'t> {
+    class: &'t ClassSource,
+    locals: HashMap<String, JavaValue>,
+}
+
+impl<'t> Frame<'t> {
+    fn text(&self, node: Node<'t>) -> &'t str {
+        node.utf8_text(self.class.text.as_bytes()).unwrap_or("")
+    }
+}
+
+struct Interpreter<'a> {
+    /// Source of a class by simple name
+    lookup: &'a dyn Fn(&str) -> Option<String>,
+    classes: HashMap<String, Rc<ClassSource>>,
+    meshes: Vec<Vec<PartNode>>,
+    builders: Vec<CubeList>,
+    depth: usize,
+    steps: usize,
+}
+
+impl<'a> Interpreter<'a> {
+    fn new(lookup: &'a dyn Fn(&str) -> Option<String>) -> Self {
+        Self {
+            lookup,
+            classes: HashMap::new(),
+            meshes: Vec::new(),
+            builders: Vec::new(),
+            depth: 0,
+            steps: 0,
+        }
+    }
+
+    fn class(&mut self, name: &str) -> Result<Rc<ClassSource>> {
+        if let Some(class) = self.classes.get(name) {
+            return Ok(class.clone());
+        }
+        let text = (self.lookup)(name).ok_or_else(|| anyhow!("Unknown class {}", name))?;
+        let mut parser = Parser::new();
+        parser
+            .set_language(tree_sitter_java::language())
+            .context("Failed to set Java language")?;
+        let tree = parser
+            .parse(&text, None)
+            .ok_or_else(|| anyhow!("Failed to parse {}", name))?;
+        let class = Rc::new(ClassSource {
+            name: name.to_string(),
+            text,
+            tree,
+        });
+        self.classes.insert(name.to_string(), class.clone());
+        Ok(class)
+    }
+
+    /// Call a static method of a class
+    fn call(&mut self, class_name: &str, method: &str, args: Vec<JavaValue>) -> Result<JavaValue> {
+        if self.depth >= MAX_CALL_DEPTH {
+            bail!("Calls nest deeper than {}", MAX_CALL_DEPTH);
+        }
+        let class = self.class(class_name)?;
+        let declaration = find_nodes(class.tree.root_node(), "method_declaration")
+            .into_iter()
+            .find(|node| {
+                node.child_by_field_name("name")
+                    .and_then(|name| name.utf8_text(class.text.as_bytes()).ok())
+                    == Some(method)
+                    && parameter_names(*node, &class.text).len() == args.len()
+            })
+            .ok_or_else(|| {
+                anyhow!(
+                    "No method {}.{} taking {} arguments",
+                    class_name,
+                    method,
+                    args.len()
+                )
+            })?;
+        let body = declaration
+            .child_by_field_name("body")
+            .ok_or_else(|| anyhow!("{}.{} has no body", class_name, method))?;
+
+        let mut frame = Frame {
+            class: &class,
+            locals: parameter_names(declaration, &class.text)
+                .into_iter()
+                .zip(args)
+                .collect(),
+        };
+        self.depth += 1;
+        let result = self.exec(body, &mut frame);
+        self.depth -= 1;
+        result?.ok_or_else(|| anyhow!("{}.{} returned nothing", class_name, method))
+    }
+
+    /// Run a statement; `Some` once a `return` is reached
+    fn exec<'t>(&mut self, node: Node<'t>, frame: &mut Frame<'t>) -> Result<Option<JavaValue>> {
+        match node.kind() {
+            "block" => {
+                let mut cursor = node.walk();
+                for statement in node.named_children(&mut cursor) {
+                    if let Some(value) = self.exec(statement, frame)? {
+                        return Ok(Some(value));
+                    }
+                }
+                Ok(None)
+            }
+            "local_variable_declaration" => {
+                let mut cursor = node.walk();
+                for declarator in node.children_by_field_name("declarator", &mut cursor) {
+                    let name = declarator
+                        .child_by_field_name("name")
+                        .map(|name| frame.text(name).to_string())
+                        .unwrap_or_default();
+                    let value = match declarator.child_by_field_name("value") {
+                        Some(value) => self.eval(value, frame)?,
+                        None => JavaValue::Int(0),
+                    };
+                    frame.locals.insert(name, value);
+                }
+                Ok(None)
+            }
+            "expression_statement" => {
+                if let Some(expression) = node.named_child(0) {
+                    self.eval(expression, frame)?;
+                }
+                Ok(None)
+            }
+            "return_statement" => match node.named_child(0) {
+                Some(value) => Ok(Some(self.eval(value, frame)?)),
+                None => bail!("Bare return in {}", frame.class.name),
+            },
+            "if_statement" => {
+                let condition = field(node, "condition")?;
+                if self.eval(condition, frame)?.boolean()? {
+                    self.exec(field(node, "consequence")?, frame)
+                } else if let Some(alternative) = node.child_by_field_name("alternative") {
+                    self.exec(alternative, frame)
+                } else {
+                    Ok(None)
+                }
+            }
+            "for_statement" => {
+                let mut cursor = node.walk();
+                let init: Vec<Node> = node.children_by_field_name("init", &mut cursor).collect();
+                let update: Vec<Node> =
+                    node.children_by_field_name("update", &mut cursor).collect();
+                for statement in init {
+                    if statement.kind() == "local_variable_declaration" {
+                        self.exec(statement, frame)?;
+                    } else {
+                        self.eval(statement, frame)?;
+                    }
+                }
+                let body = field(node, "body")?;
+                loop {
+                    if let Some(condition) = node.child_by_field_name("condition") {
+                        if !self.eval(condition, frame)?.boolean()? {
+                            return Ok(None);
+                        }
+                    }
+                    self.steps += 1;
+                    if self.steps > MAX_LOOP_STEPS {
+                        bail!("Loop ran more than {} times", MAX_LOOP_STEPS);
+                    }
+                    if let Some(value) = self.exec(body, frame)? {
+                        return Ok(Some(value));
+                    }
+                    for expression in &update {
+                        self.eval(*expression, frame)?;
+                    }
+                }
+            }
+            "line_comment" | "block_comment" | ";" => Ok(None),
+            other => bail!("Unsupported statement {} in {}", other, frame.class.name),
+        }
+    }
+
+    fn eval<'t>(&mut self, node: Node<'t>, frame: &mut Frame<'t>) -> Result<JavaValue> {
+        let text = frame.text(node);
+        match node.kind() {
+            "decimal_integer_literal" => text
+                .trim_end_matches(['l', 'L'])
+                .replace('_', "")
+                .parse()
+                .map(JavaValue::Int)
+                .with_context(|| format!("Bad integer {}", text)),
+            "decimal_floating_point_literal" => text
+                .trim_end_matches(['f', 'F', 'd', 'D'])
+                .replace('_', "")
+                .parse()
+                .map(JavaValue::Float)
+                .with_context(|| format!("Bad number {}", text)),
+            "true" => Ok(JavaValue::Bool(true)),
+            "false" => Ok(JavaValue::Bool(false)),
+            "string_literal" => Ok(JavaValue::Str(
+                text.trim_start_matches('"')
+                    .trim_end_matches('"')
+                    .to_string(),
+            )),
+            "parenthesized_expression" => match node.named_child(0) {
+                Some(inner) => self.eval(inner, frame),
+                None => bail!("Empty parentheses"),
+            },
+            "identifier" => match frame.locals.get(text) {
+                Some(value) => Ok(value.clone()),
+                None => {
+                    let class = frame.class.name.clone();
+                    self.static_field(&class, text)
+                }
+            },
+            "field_access" => {
+                let object = field(node, "object")?;
+                let name = frame.text(field(node, "field")?);
+                let class = frame.text(object);
+                if object.kind() == "identifier" && !frame.locals.contains_key(class) {
+                    self.static_field(class, name)
+                } else {
+                    bail!("Unsupported field access {}", text)
+                }
+            }
+            "cast_expression" => {
+                let value = self.eval(field(node, "value")?, frame)?;
+                match frame.text(field(node, "type")?) {
+                    "float" | "double" => Ok(JavaValue::Float(value.number()?)),
+                    "int" | "long" | "short" | "byte" => {
+                        Ok(JavaValue::Int(value.number()?.trunc() as i64))
+                    }
+                    _ => Ok(value),
+                }
+            }
+            "unary_expression" => {
+                let operand = self.eval(field(node, "operand")?, frame)?;
+                match (frame.text(field(node, "operator")?), operand) {
+                    ("-", JavaValue::Int(value)) => Ok(JavaValue::Int(-value)),
+                    ("-", value) => Ok(JavaValue::Float(-value.number()?)),
+                    ("+", value) => Ok(value),
+                    ("!", value) => Ok(JavaValue::Bool(!value.boolean()?)),
+                    (operator, _) => bail!("Unsupported operator {}", operator),
+                }
+            }
+            "binary_expression" => {
+                let left = self.eval(field(node, "left")?, frame)?;
+                let right = self.eval(field(node, "right")?, frame)?;
+                binary(frame.text(field(node, "operator")?), left, right)
+            }
+            "ternary_expression" => {
+                let condition = self.eval(field(node, "condition")?, frame)?;
+                let branch = if condition.boolean()? {
+                    "consequence"
+                } else {
+                    "alternative"
+                };
+                self.eval(field(node, branch)?, frame)
+            }
+            "assignment_expression" => {
+                let name = frame.text(field(node, "left")?).to_string();
+                let right = self.eval(field(node, "right")?, frame)?;
+                let value = match frame.text(field(node, "operator")?) {
+                    "=" => right,
+                    operator => {
+                        let current = frame
+                            .locals
+                            .get(&name)
+                            .cloned()
+                            .ok_or_else(|| anyhow!("Unknown variable {}", name))?;
+                        binary(operator.trim_end_matches('='), current, right)?
+                    }
+                };
+                frame.locals.insert(name, value.clone());
+                Ok(value)
+            }
+            "update_expression" => {
+                let target = node
+                    .named_child(0)
+                    .ok_or_else(|| anyhow!("Bad update {}", text))?;
+                let name = frame.text(target).to_string();
+                let current = frame
+                    .locals
+                    .get(&name)
+                    .cloned()
+                    .ok_or_else(|| anyhow!("Unknown variable {}", name))?;
+                let step = if text.contains("++") { "+" } else { "-" };
+                let value = binary(step, current, JavaValue::Int(1))?;
+                frame.locals.insert(name, value.clone());
+                Ok(value)
+            }
+            "object_creation_expression" => {
+                let args = self.eval_args(node, frame)?;
+                self.construct(frame.text(field(node, "type")?), args)
+            }
+            "method_invocation" => self.invoke(node, frame),
+            other => bail!("Unsupported expression {} ({})", other, text),
+        }
+    }
+
+    fn eval_args<'t>(&mut self, node: Node<'t>, frame: &mut Frame<'t>) -> Result<Vec<JavaValue>> {
+        let arguments = field(node, "arguments")?;
+        let mut cursor = arguments.walk();
+        let nodes: Vec<Node> = arguments.named_children(&mut cursor).collect();
+        nodes
+            .into_iter()
+            .filter(|node| !node.is_extra())
+            .map(|node| self.eval(node, frame))
+            .collect()
+    }
+
+    fn invoke<'t>(&mut self, node: Node<'t>, frame: &mut Frame<'t>) -> Result<JavaValue> {
+        let name = frame.text(field(node, "name")?);
+        let object = node.child_by_field_name("object");
+
+        // A static call on a class name rather than a value
+        let class = match object {
+            None => Some(frame.class.name.clone()),
+            Some(object)
+                if object.kind() == "identifier"
+                    && !frame.locals.contains_key(frame.text(object)) =>
+            {
+                Some(frame.text(object).to_string())
+            }
+            Some(_) => None,
+        };
+
+        let args = self.eval_args(node, frame)?;
+        if let Some(class) = class {
+            return match builtin_call(&class, name, &args) {
+                Some(result) => result,
+                None if class == "CubeListBuilder" && name == "create" => {
+                    self.builders.push(CubeList::default());
+                    Ok(JavaValue::Cubes(self.builders.len() - 1))
+                }
+                None => self.call(&class, name, args),
+            };
+        }
+
+        let receiver = self.eval(object.unwrap(), frame)?;
+        self.method(receiver, name, args)
+    }
+
+    /// Call a method on a value
+    fn method(
+        &mut self,
+        receiver: JavaValue,
+        name: &str,
+        args: Vec<JavaValue>,
+    ) -> Result<JavaValue> {
+        match (receiver, name) {
+            (JavaValue::Mesh(mesh), "getRoot") => Ok(JavaValue::Part(mesh, 0)),
+            (JavaValue::Part(mesh, parent), "addOrReplaceChild") => {
+                let [name, cubes, pose] = args.as_slice() else {
+                    bail!("addOrReplaceChild takes a name, cubes and a pose");
+                };
+                let (JavaValue::Cubes(builder), JavaValue::Pose(pose)) = (cubes, pose) else {
+                    bail!("addOrReplaceChild takes a name, cubes and a pose");
+                };
+                let node = PartNode {
+                    name: name.string()?,
+                    parent: Some(parent),
+                    pose: *pose,
+                    cubes: self.builders[*builder].cubes.clone(),
+                    attached: true,
+                };
+                Ok(JavaValue::Part(mesh, self.replace_child(mesh, node)))
+            }
+            (JavaValue::Part(mesh, parent), "getChild") => {
+                let name = args.first().map(JavaValue::string).transpose()?;
+                let child = self.meshes[mesh]
+                    .iter()
+                    .position(|node| {
+                        node.attached
+                            && node.parent == Some(parent)
+                            && Some(&node.name) == name.as_ref()
+                    })
+                    .ok_or_else(|| anyhow!("No child {:?}", name))?;
+                Ok(JavaValue::Part(mesh, child))
+            }
+            (JavaValue::Part(mesh, parent), "clearChild") => {
+                let name = args.first().map(JavaValue::string).transpose()?;
+                for node in &mut self.meshes[mesh] {
+                    if node.parent == Some(parent) && Some(&node.name) == name.as_ref() {
+                        node.attached = false;
+                    }
+                }
+                Ok(JavaValue::Ignored)
+            }
+            (JavaValue::Cubes(builder), "texOffs") => {
+                let [u, v] = args.as_slice() else {
+                    bail!("texOffs takes two numbers");
+                };
+                self.builders[builder].tex_offset = [u.number()?, v.number()?];
+                Ok(JavaValue::Cubes(builder))
+            }
+            (JavaValue::Cubes(builder), "mirror") => {
+                self.builders[builder].mirror = match args.first() {
+                    Some(value) => value.boolean()?,
+                    None => true,
+                };
+                Ok(JavaValue::Cubes(builder))
+            }
+            (JavaValue::Cubes(builder), "addBox") => {
+                self.add_box(builder, args)?;
+                Ok(JavaValue::Cubes(builder))
+            }
+            (JavaValue::Pose(pose), "translated") => {
+                let offset = numbers::<3>(&args)?;
+                Ok(JavaValue::Pose(Pose {
+                    offset: std::array::from_fn(|axis| pose.offset[axis] + offset[axis]),
+                    ..pose
+                }))
+            }
+            (JavaValue::Pose(pose), "withScale" | "scaled") => Ok(JavaValue::Pose(pose)),
+            (JavaValue::Deformation(grow), "extend") => {
+                let extra = match args.len() {
+                    1 => [args[0].number()?; 3],
+                    _ => numbers::<3>(&args)?,
+                };
+                Ok(JavaValue::Deformation(std::array::from_fn(|axis| {
+                    grow[axis] + extra[axis]
+                })))
+            }
+            (layer @ JavaValue::Layer(..), "apply") => Ok(layer),
+            (receiver, name) => bail!("Unsupported call {:?}.{}", receiver, name),
+        }
+    }
+
+    /// Add a part, overwriting a sibling of the same name in place
+    fn replace_child(&mut self, mesh: usize, node: PartNode) -> usize {
+        let nodes = &mut self.meshes[mesh];
+        let existing = nodes.iter().position(|other| {
+            other.attached && other.parent == node.parent && other.name == node.name
+        });
+        match existing {
+            Some(index) => {
+                for other in nodes.iter_mut() {
+                    if other.parent == Some(index) {
+                        other.attached = false;
+                    }
+                }
+                nodes[index] = node;
+                index
+            }
+            None => {
+                nodes.push(node);
+                nodes.len() - 1
+            }
+        }
+    }
+
+    /// `addBox([name,] x, y, z, w, h, d[, deformation | mirror | u, v | faces])`
+    fn add_box(&mut self, builder: usize, mut args: Vec<JavaValue>) -> Result<()> {
+        if matches!(args.first(), Some(JavaValue::Str(_))) {
+            args.remove(0);
+        }
+        if args.len() < 6 {
+            bail!("addBox takes at least six numbers");
+        }
+        let rest = args.split_off(6);
+        let bounds = numbers::<6>(&args)?;
+
+        let list = &mut self.builders[builder];
+        let mut mirror = list.mirror;
+        let mut grow = [0.0; 3];
+        let mut tex_offset = Vec::new();
+        for value in rest {
+            match value {
+                JavaValue::Deformation(deformation) => grow = deformation,
+                JavaValue::Bool(value) => mirror = value,
+                JavaValue::Int(_) | JavaValue::Float(_) => tex_offset.push(value.number()?),
+                JavaValue::Ignored => {}
+                other => bail!("Unexpected addBox argument {:?}", other),
+            }
+        }
+        if let [u, v] = tex_offset[..] {
+            list.tex_offset = [u, v];
+        }
+
+        list.cubes.push(ModelCube {
+            origin: [bounds[0], bounds[1], bounds[2]],
+            size: [bounds[3], bounds[4], bounds[5]],
+            tex_offset: list.tex_offset,
+            mirror,
+            grow,
+        });
+        Ok(())
+    }
+
+    fn construct(&mut self, type_name: &str, args: Vec<JavaValue>) -> Result<JavaValue> {
+        match type_name {
+            "MeshDefinition" => {
+                self.meshes.push(vec![PartNode {
+                    name: String::new(),
+                    parent: None,
+                    pose: Pose::default(),
+                    cubes: Vec::new(),
+                    attached: true,
+                }]);
+                Ok(JavaValue::Mesh(self.meshes.len() - 1))
+            }
+            "CubeDeformation" => Ok(JavaValue::Deformation(match args.len() {
+                1 => [args[0].number()?; 3],
+                _ => numbers::<3>(&args)?,
+            })),
+            "CubeListBuilder" => {
+                self.builders.push(CubeList::default());
+                Ok(JavaValue::Cubes(self.builders.len() - 1))
+            }
+            other => bail!("Unsupported constructor {}", other),
+        }
+    }
+
+    /// A static field: a few well-known ones, else a `static final` of the class
+    fn static_field(&mut self, class_name: &str, name: &str) -> Result<JavaValue> {
+        match (class_name, name) {
+            ("Math" | "Mth", "PI") => return Ok(JavaValue::Float(std::f32::consts::PI)),
+            ("Mth", "HALF_PI") => return Ok(JavaValue::Float(std::f32::consts::FRAC_PI_2)),
+            ("Mth", "TWO_PI") => return Ok(JavaValue::Float(std::f32::consts::TAU)),
+            ("Mth", "DEG_TO_RAD") => return Ok(JavaValue::Float(std::f32::consts::PI / 180.0)),
+            ("Mth", "RAD_TO_DEG") => return Ok(JavaValue::Float(180.0 / std::f32::consts::PI)),
+            ("CubeDeformation", "NONE") => return Ok(JavaValue::Deformation([0.0; 3])),
+            ("PartPose", "ZERO") => return Ok(JavaValue::Pose(Pose::default())),
+            ("Direction", _) => return Ok(JavaValue::Ignored),
+            _ => {}
+        }
+
+        let class = self.class(class_name)?;
+        let declarator = find_nodes(class.tree.root_node(), "field_declaration")
+            .into_iter()
+            .flat_map(|declaration| {
+                let mut cursor = declaration.walk();
+                declaration
+                    .children_by_field_name("declarator", &mut cursor)
+                    .collect::<Vec<_>>()
+            })
+            .find(|declarator| {
+                declarator
+                    .child_by_field_name("name")
+                    .and_then(|node| node.utf8_text(class.text.as_bytes()).ok())
+                    == Some(name)
+            })
+            .ok_or_else(|| anyhow!("Unknown field {}.{}", class_name, name))?;
+        let value = declarator
+            .child_by_field_name("value")
+            .ok_or_else(|| anyhow!("{}.{} has no value", class_name, name))?;
+        let mut frame = Frame {
+            class: &class,
+            locals: HashMap::new(),
+        };
+        self.eval(value, &mut frame)
+    }
+
+    /// Turn a mesh into a part tree
+    fn layer(&self, mesh: usize, texture_size: [f32; 2]) -> LayerGeometry {
+        LayerGeometry {
+            texture_size,
+            parts: self.children(mesh, 0),
+        }
+    }
+
+    fn children(&self, mesh: usize, parent: usize) -> Vec<ModelPart> {
+        self.meshes[mesh]
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.attached && node.parent == Some(parent))
+            .map(|(index, node)| ModelPart {
+                name: node.name.clone(),
+                offset: node.pose.offset,
+                rotation: node.pose.rotation,
+                cubes: node.cubes.clone(),
+                children: self.children(mesh, index),
+            })
+            .collect()
+    }
+}
+
+/// Calls on well-known classes; `None` for everything else
+fn builtin_call(class: &str, name: &str, args: &[JavaValue]) -> Option<Result<JavaValue>> {
+    let result = match (class, name) {
+        ("PartPose", "offset") => numbers::<3>(args).map(|offset| {
+            JavaValue::Pose(Pose {
+                offset,
+                rotation: [0.0; 3],
+            })
+        }),
+        ("PartPose", "rotation") => numbers::<3>(args).map(|rotation| {
+            JavaValue::Pose(Pose {
+                offset: [0.0; 3],
+                rotation,
+            })
+        }),
+        ("PartPose", "offsetAndRotation") => numbers::<6>(args).map(|values| {
+            JavaValue::Pose(Pose {
+                offset: [values[0], values[1], values[2]],
+                rotation: [values[3], values[4], values[5]],
+            })
+        }),
+        ("LayerDefinition", "create") => match args {
+            [JavaValue::Mesh(mesh), width, height] => width
+                .number()
+                .and_then(|width| Ok(JavaValue::Layer(*mesh, [width, height.number()?]))),
+            _ => Err(anyhow!("LayerDefinition.create takes a mesh and a size")),
+        },
+        ("Math" | "Mth", "sin" | "cos" | "abs" | "sqrt" | "toRadians") => {
+            numbers::<1>(args).map(|[value]| {
+                JavaValue::Float(match name {
+                    "sin" => value.sin(),
+                    "cos" => value.cos(),
+                    "abs" => value.abs(),
+                    "sqrt" => value.sqrt(),
+                    _ => value.to_radians(),
+                })
+            })
+        }
+        ("MeshTransformer" | "EnumSet" | "Set", _) => Ok(JavaValue::Ignored),
+        _ => return None,
+    };
+    Some(result)
+}
+
+fn binary(operator: &str, left: JavaValue, right: JavaValue) -> Result<JavaValue> {
+    if operator == "+" && (matches!(left, JavaValue::Str(_)) || matches!(right, JavaValue::Str(_)))
+    {
+        return Ok(JavaValue::Str(left.string()? + &right.string()?));
+    }
+    if let ("&&" | "||", JavaValue::Bool(a), JavaValue::Bool(b)) = (operator, &left, &right) {
+        return Ok(JavaValue::Bool(if operator == "&&" {
+            *a && *b
+        } else {
+            *a || *b
+        }));
+    }
+    if let (JavaValue::Int(a), JavaValue::Int(b)) = (&left, &right) {
+        let (a, b) = (*a, *b);
+        return Ok(match operator {
+            "+" => JavaValue::Int(a + b),
+            "-" => JavaValue::Int(a - b),
+            "*" => JavaValue::Int(a * b),
+            "/" | "%" if b == 0 => bail!("Division by zero"),
+            "/" => JavaValue::Int(a / b),
+            "%" => JavaValue::Int(a % b),
+            "<" => JavaValue::Bool(a < b),
+            "<=" => JavaValue::Bool(a <= b),
+            ">" => JavaValue::Bool(a > b),
+            ">=" => JavaValue::Bool(a >= b),
+            "==" => JavaValue::Bool(a == b),
+            "!=" => JavaValue::Bool(a != b),
+            other => bail!("Unsupported operator {}", other),
+        });
+    }
+
+    let (a, b) = (left.number()?, right.number()?);
+    Ok(match operator {
+        "+" => JavaValue::Float(a + b),
+        "-" => JavaValue::Float(a - b),
+        "*" => JavaValue::Float(a * b),
+        "/" => JavaValue::Float(a / b),
+        "%" => JavaValue::Float(a % b),
+        "<" => JavaValue::Bool(a < b),
+        "<=" => JavaValue::Bool(a <= b),
+        ">" => JavaValue::Bool(a > b),
+        ">=" => JavaValue::Bool(a >= b),
+        "==" => JavaValue::Bool(a == b),
+        "!=" => JavaValue::Bool(a != b),
+        other => bail!("Unsupported operator {}", other),
+    })
+}
+
+fn numbers<const N: usize>(args: &[JavaValue]) -> Result<[f32; N]> {
+    if args.len() != N {
+        bail!("Expected {} numbers, got {} arguments", N, args.len());
+    }
+    let mut values = [0.0; N];
+    for (value, arg) in values.iter_mut().zip(args) {
+        *value = arg.number()?;
+    }
+    Ok(values)
+}
+
+fn field<'t>(node: Node<'t>, name: &str) -> Result<Node<'t>> {
+    node.child_by_field_name(name)
+        .ok_or_else(|| anyhow!("{} has no {}", node.kind(), name))
+}
+
+fn parameter_names(method: Node, source: &str) -> Vec<String> {
+    let Some(parameters) = method.child_by_field_name("parameters") else {
+        return Vec::new();
+    };
+    let mut cursor = parameters.walk();
+    let names = parameters
+        .named_children(&mut cursor)
+        .filter_map(|parameter| parameter.child_by_field_name("name"))
+        .filter_map(|name| name.utf8_text(source.as_bytes()).ok())
+        .map(str::to_string)
+        .collect();
+    names
+}
+
+fn find_nodes<'t>(node: Node<'t>, kind: &str) -> Vec<Node<'t>> {
+    let mut found = Vec::new();
+    if node.kind() == kind {
+        found.push(node);
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        found.extend(find_nodes(child, kind));
+    }
+    found
+}
+
+/// Run a static method returning a LayerDefinition (e.g.,
+/// `CowModel.createBodyLayer`)
+///
+/// # Arguments
+/// * `lookup` - Decompiled source of a class by simple name
+/// * `class` - Class declaring the method
+/// * `method` - A method taking no arguments
+pub fn extract_layer(
+    lookup: &dyn Fn(&str) -> Option<String>,
+    class: &str,
+    method: &str,
+) -> Result<LayerGeometry> {
+    let mut interpreter = Interpreter::new(lookup);
+    match interpreter.call(class, method, Vec::new())? {
+        JavaValue::Layer(mesh, texture_size) => Ok(interpreter.layer(mesh, texture_size)),
+        other => bail!("{}.{} returned {:?}, not a layer", class, method, other),
+    }
+}
+
+/// Rebuild the body layer of every entity model class under `model_dir`
+///
+/// Keyed by entity ID (IronGolemModel -> "iron_golem"). Classes whose layer
+/// couldn't be followed are logged and left out.
+pub fn extract_entity_geometry(model_dir: &Path) -> HashMap<String, LayerGeometry> {
+    let mut classes: HashMap<String, PathBuf> = HashMap::new();
+    for entry in WalkDir::new(model_dir).into_iter().filter_map(Result::ok) {
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "java") {
+            if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+                classes
+                    .entry(stem.to_string())
+                    .or_insert_with(|| path.to_path_buf());
+            }
+        }
+    }
+    let lookup = |name: &str| {
+        classes
+            .get(name)
+            .and_then(|path| fs::read_to_string(path).ok())
+    };
+
+    let mut geometry = HashMap::new();
+    let mut failed = 0;
+    for class in classes.keys() {
+        let Some(entity) = class
+            .strip_suffix("Model")
+            .filter(|entity| !entity.is_empty())
+        else {
+            continue;
+        };
+        let has_layer = lookup(class).is_some_and(|source| source.contains("createBodyLayer()"));
+        if !has_layer {
+            continue;
+        }
+        match extract_layer(&lookup, class, "createBodyLayer") {
+            Ok(layer) if !layer.parts.is_empty() => {
+                let entity = super::block_animation_extractor::camel_to_snake_case(entity);
+                geometry.insert(entity, layer);
+            }
+            Ok(_) => {}
+            Err(error) => {
+                failed += 1;
+                warn!("Skipped geometry of {}: {}", class, error);
+            }
+        }
+    }
+
+    info!(
+        "✓ Extracted geometry of {} entity models ({} skipped)",
+        geometry.len(),
+        failed
+    );
+    geometry
+}
+
+/// Round away float noise (and negative zero) for readable JEM numbers
+fn tidy(value: f32) -> f32 {
+    (value * 10_000.0).round() / 10_000.0 + 0.0
+}
+
+/// Convert a layer into a JEM in OptiFine's layout
+///
+/// JEM space flips x and y (`invertAxis: "xy"`): a model-space point
+/// (x, y, z) is (-x, 24 - y, z). Root parts translate by minus their pivot
+/// and place boxes absolutely; first-level submodels translate to their
+/// absolute pivot, deeper ones by their offset from the parent, and both
+/// place boxes relative to their own pivot.
+pub fn layer_to_jem(layer: &LayerGeometry) -> JemFile {
+    JemFile {
+        texture_size: Some(layer.texture_size),
+        models: layer
+            .parts
+            .iter()
+            .map(|part| jem_part(part, [0.0; 3], 0))
+            .collect(),
+        ..Default::default()
+    }
+}
+
+fn jem_part(part: &ModelPart, parent_pivot: [f32; 3], depth: usize) -> JemPart {
+    let pivot: [f32; 3] = std::array::from_fn(|axis| parent_pivot[axis] + part.offset[axis]);
+    let translate = match depth {
+        0 => [pivot[0], pivot[1] - 24.0, -pivot[2]],
+        1 => [-pivot[0], 24.0 - pivot[1], pivot[2]],
+        _ => [-part.offset[0], -part.offset[1], part.offset[2]],
+    };
+    let (base, ground) = if depth == 0 {
+        (pivot, 24.0)
+    } else {
+        ([0.0; 3], 0.0)
+    };
+
+    let boxes = part
+        .cubes
+        .iter()
+        .map(|cube| {
+            let [x, y, z] = cube.origin;
+            let [w, h, d] = cube.size;
+            let mut jem_box = JemBox {
+                coordinates: Some(
+                    [
+                        -(base[0] + x + w),
+                        ground - (base[1] + y + h),
+                        base[2] + z,
+                        w,
+                        h,
+                        d,
+                    ]
+                    .map(tidy),
+                ),
+                texture_offset: Some(cube.tex_offset),
+                ..Default::default()
+            };
+            let [gx, gy, gz] = cube.grow.map(tidy);
+            if gx == gy && gy == gz {
+                jem_box.size_add = (gx != 0.0).then_some(gx);
+            } else {
+                jem_box.size_add_x = Some(gx);
+                jem_box.size_add_y = Some(gy);
+                jem_box.size_add_z = Some(gz);
+            }
+            jem_box
+        })
+        .collect();
+
+    let mirrored = !part.cubes.is_empty() && part.cubes.iter().all(|cube| cube.mirror);
+    let [rx, ry, rz] = part.rotation.map(f32::to_degrees);
+    JemPart {
+        part: (depth == 0).then(|| part.name.clone()),
+        id: Some(part.name.clone()),
+        invert_axis: Some("xy".to_string()),
+        translate: Some(translate.map(tidy)),
+        rotate: (part.rotation != [0.0; 3]).then(|| [-rx, -ry, rz].map(tidy)),
+        mirror_texture: mirrored.then(|| "u".to_string()),
+        boxes,
+        submodels: part
+            .children
+            .iter()
+            .map(|child| jem_part(child, pivot, depth + 1))
+            .collect(),
+        ..Default::default()
+    }
+}
+
+/// Rename root parts to the bundled template's names where they differ
+///
+/// OptiFine kept its own names for older mobs (a cow's "right_hind_leg" is
+/// "leg1"). A part without a same-named template part takes the name of the
+/// one unclaimed template part at the same pivot, if there is exactly one.
+pub fn adopt_template_names(jem: &mut JemFile, template: &JemFile) {
+    let mut claimed: Vec<String> = jem
+        .models
+        .iter()
+        .filter_map(|part| part.part.clone())
+        .filter(|name| {
+            template
+                .models
+                .iter()
+                .any(|t| t.part.as_ref() == Some(name))
+        })
+        .collect();
+
+    for part in &mut jem.models {
+        if part
+            .part
+            .as_ref()
+            .is_some_and(|name| claimed.contains(name))
+        {
+            continue;
+        }
+        let candidates: Vec<&String> = template
+            .models
+            .iter()
+            .filter(|t| {
+                t.translate.unwrap_or_default() == part.translate.unwrap_or_default()
+                    && t.part.as_ref().is_some_and(|name| !claimed.contains(name))
+            })
+            .filter_map(|t| t.part.as_ref())
+            .collect();
+        if let [name] = candidates[..] {
+            claimed.push(name.clone());
+            part.part = Some(name.clone());
+            part.id = Some(name.clone());
+        }
+    }
+}
+
+/// Where a version's extracted JEMs go: its vanilla assets folder, which
+/// `jem::vanilla_jem_dirs` searches first for the active version
+pub fn geometry_dir(version: &str) -> Result<PathBuf> {
+    Ok(super::vanilla_textures::get_version_cache_dir(version)?
+        .join("assets")
+        .join("minecraft")
+        .join("optifine")
+        .join("cem"))
+}
+
+/// Write extracted geometry as `<entity>.jem` files for a version
+///
+/// Part names follow the bundled template for the entity where one exists.
+/// Returns how many files were written.
+pub fn save_entity_geometry(
+    version: &str,
+    geometry: &HashMap<String, LayerGeometry>,
+) -> Result<usize> {
+    let version_dir = super::vanilla_textures::get_version_cache_dir(version)?;
+    if !version_dir.exists() {
+        bail!("Vanilla assets of {} aren't extracted", version);
+    }
+    let dir = geometry_dir(version)?;
+    fs::create_dir_all(&dir).context("Failed to create entity geometry directory")?;
+
+    let template_dirs: Vec<PathBuf> = jem::vanilla_jem_dirs()
+        .into_iter()
+        .filter(|template_dir| *template_dir != dir)
+        .collect();
+    for (entity, layer) in geometry {
+        let mut model = layer_to_jem(layer);
+        let template = jem::template_name(entity, Some(version), &template_dirs).and_then(|name| {
+            template_dirs
+                .iter()
+                .find_map(|dir| fs::read_to_string(dir.join(format!("{}.jem", name))).ok())
+        });
+        if let Some(template) = template.and_then(|text| jem::parse_jem(&text).ok()) {
+            adopt_template_names(&mut model, &template);
+        }
+        let content =
+            serde_json::to_string_pretty(&model).context("Failed to serialize entity geometry")?;
+        fs::write(dir.join(format!("{}.jem", entity)), content)
+            .with_context(|| format!("Failed to write geometry of {}", entity))?;
+    }
+
+    info!(
+        "Saved geometry of {} entities for {} to {}",
+        geometry.len(),
+        version,
+        dir.display()
+    );
+    Ok(geometry.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QUADRUPED: &str = r#"
+public class QuadrupedModel<T extends Entity> extends AgeableListModel<T> {
+    public static MeshDefinition createBodyMesh(int $$0, CubeDeformation $$1) {
+        MeshDefinition $$2 = new MeshDefinition();
+        PartDefinition $$3 = $$2.getRoot();
+        $$3.addOrReplaceChild("head", CubeListBuilder.create().texOffs(0, 0).addBox(-4.0f, -4.0f, -8.0f, 8.0f, 8.0f, 8.0f, $$1), PartPose.offset(0.0f, 18 - $$0, -6.0f));
+        $$3.addOrReplaceChild("body", CubeListBuilder.create().texOffs(28, 8).addBox(-5.0f, -10.0f, -7.0f, 10.0f, 16.0f, 8.0f, $$1), PartPose.offsetAndRotation(0.0f, 17 - $$0, 2.0f, 1.5707964f, 0.0f, 0.0f));
+        CubeListBuilder $$4 = CubeListBuilder.create().texOffs(0, 16).addBox(-2.0f, 0.0f, -2.0f, 4.0f, (float)$$0, 4.0f, $$1);
+        $$3.addOrReplaceChild("right_hind_leg", $$4, PartPose.offset(-3.0f, 24 - $$0, 7.0f));
+        $$3.addOrReplaceChild("left_hind_leg", $$4, PartPose.offset(3.0f, 24 - $$0, 7.0f));
+        return $$2;
+    }
+}
+"#;
+
+    const COW: &str = r#"
+public class CowModel<T extends Entity> extends QuadrupedModel<T> {
+    private static final int LEG_SIZE = 12;
+
+    public static LayerDefinition createBodyLayer() {
+        MeshDefinition $$0 = QuadrupedModel.createBodyMesh(LEG_SIZE, CubeDeformation.NONE);
+        PartDefinition $$1 = $$0.getRoot();
+        $$1.addOrReplaceChild("head", CubeListBuilder.create().texOffs(0, 0).addBox(-4.0f, -4.0f, -6.0f, 8.0f, 8.0f, 6.0f).texOffs(22, 0).addBox("right_horn", -5.0f, -5.0f, -4.0f, 1.0f, 3.0f, 1.0f), PartPose.offset(0.0f, 4.0f, -8.0f));
+        $$1.addOrReplaceChild("body", CubeListBuilder.create().texOffs(18, 4).addBox(-6.0f, -10.0f, -7.0f, 12.0f, 18.0f, 10.0f), PartPose.offsetAndRotation(0.0f, 5.0f, 2.0f, 1.5707964f, 0.0f, 0.0f));
+        CubeListBuilder $$2 = CubeListBuilder.create().mirror().texOffs(0, 16).addBox(-2.0f, 0.0f, -2.0f, 4.0f, 12.0f, 4.0f);
+        $$1.addOrReplaceChild("right_hind_leg", $$2, PartPose.offset(-4.0f, 12.0f, 7.0f));
+        return LayerDefinition.create($$0, 64, 32);
+    }
+}
+"#;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "QuadrupedModel" => Some(QUADRUPED.to_string()),
+            "CowModel" => Some(COW.to_string()),
+            "SquidModel" => Some(SQUID.to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_cow_layer_to_jem() {
+        let layer = extract_layer(&lookup, "CowModel", "createBodyLayer").unwrap();
+        let names: Vec<&str> = layer.parts.iter().map(|part| part.name.as_str()).collect();
+        assert_eq!(names, ["head", "body", "right_hind_leg", "left_hind_leg"]);
+
+        let mut jem = layer_to_jem(&layer);
+        assert_eq!(jem.texture_size, Some([64.0, 32.0]));
+        let head = &jem.models[0];
+        assert_eq!(head.translate, Some([0.0, -20.0, 8.0]));
+        assert_eq!(head.boxes.len(), 2);
+        assert_eq!(
+            head.boxes[0].coordinates,
+            Some([-4.0, 16.0, -14.0, 8.0, 8.0, 6.0])
+        );
+        assert_eq!(
+            head.boxes[1].coordinates,
+            Some([4.0, 22.0, -12.0, 1.0, 3.0, 1.0])
+        );
+        assert_eq!(head.boxes[1].texture_offset, Some([22.0, 0.0]));
+        assert_eq!(jem.models[1].translate, Some([0.0, -19.0, -2.0]));
+        assert_eq!(jem.models[1].rotate, Some([-90.0, 0.0, 0.0]));
+
+        // Replaced in place; the cow's own legs are longer and mirrored
+        let leg = &jem.models[2];
+        assert_eq!(leg.translate, Some([-4.0, -12.0, -7.0]));
+        assert_eq!(
+            leg.boxes[0].coordinates,
+            Some([2.0, 0.0, 5.0, 4.0, 12.0, 4.0])
+        );
+        assert_eq!(leg.mirror_texture.as_deref(), Some("u"));
+        assert_eq!(jem.models[3].translate, Some([3.0, -12.0, -7.0]));
+        assert_eq!(jem.models[3].mirror_texture, None);
+
+        let template = jem::parse_jem(
+            r#"{"models": [
+                {"part": "head", "translate": [0, -20, 8]},
+                {"part": "leg1", "translate": [-4, -12, -7]},
+                {"part": "leg2", "translate": [4, -12, -7]}
+            ]}"#,
+        )
+        .unwrap();
+        adopt_template_names(&mut jem, &template);
+        let names: Vec<_> = jem
+            .models
+            .iter()
+            .filter_map(|part| part.part.as_deref())
+            .collect();
+        assert_eq!(names, ["head", "body", "leg1", "left_hind_leg"]);
+    }
+
+    const SQUID: &str = r#"
+public class SquidModel<T extends Entity> extends HierarchicalModel<T> {
+    private static String createTentacleName(int $$0) {
+        return "tentacle" + $$0;
+    }
+
+    public static LayerDefinition createBodyLayer() {
+        MeshDefinition $$0 = new MeshDefinition();
+        PartDefinition $$1 = $$0.getRoot();
+        CubeDeformation $$2 = new CubeDeformation(0.02f);
+        PartDefinition $$3 = $$1.addOrReplaceChild("body", CubeListBuilder.create().addBox(-6.0f, -8.0f, -6.0f, 12.0f, 16.0f, 12.0f, $$2), PartPose.offset(0.0f, 8.0f, 0.0f));
+        CubeListBuilder $$4 = CubeListBuilder.create().texOffs(48, 0).addBox(-1.0f, 0.0f, -1.0f, 2.0f, 18.0f, 2.0f);
+        for (int $$5 = 0; $$5 < 2; ++$$5) {
+            double $$6 = (double)$$5 * Math.PI;
+            float $$7 = (float)Math.cos($$6) * 5.0f;
+            PartDefinition $$8 = $$3.addOrReplaceChild(SquidModel.createTentacleName($$5), $$4, PartPose.offsetAndRotation($$7, 7.0f, 0.0f, 0.0f, (float)$$6, 0.0f));
+            $$8.addOrReplaceChild("tip", CubeListBuilder.create().addBox(-1.0f, 0.0f, -1.0f, 2.0f, 4.0f, 2.0f, $$2.extend(0.5f)), PartPose.offset(0.0f, 18.0f, 1.0f));
+        }
+        return LayerDefinition.create($$0, 64, 32).apply(MeshTransformer.scaling(0.5f));
+    }
+}
+"#;
+
+    #[test]
+    fn test_loops_and_submodels() {
+        let layer = extract_layer(&lookup, "SquidModel", "createBodyLayer").unwrap();
+        let jem = layer_to_jem(&layer);
+        let body = &jem.models[0];
+        assert_eq!(body.translate, Some([0.0, -16.0, 0.0]));
+        assert_eq!(
+            body.boxes[0].coordinates,
+            Some([-6.0, 8.0, -6.0, 12.0, 16.0, 12.0])
+        );
+        assert_eq!(body.boxes[0].size_add, Some(0.02));
+
+        let tentacles: Vec<_> = body
+            .submodels
+            .iter()
+            .map(|part| part.id.as_deref())
+            .collect();
+        assert_eq!(tentacles, [Some("tentacle0"), Some("tentacle1")]);
+        // First-level submodels sit at their absolute pivot...
+        let tentacle = &body.submodels[1];
+        assert_eq!(tentacle.part, None);
+        assert_eq!(tentacle.translate, Some([5.0, 9.0, 0.0]));
+        assert_eq!(tentacle.rotate, Some([0.0, -180.0, 0.0]));
+        assert_eq!(
+            tentacle.boxes[0].coordinates,
+            Some([-1.0, -18.0, -1.0, 2.0, 18.0, 2.0])
+        );
+        // ...deeper ones at their offset from the parent
+        let tip = &tentacle.submodels[0];
+        assert_eq!(tip.translate, Some([0.0, -18.0, 1.0]));
+        assert_eq!(tip.boxes[0].size_add, Some(0.52));
+    }
+}
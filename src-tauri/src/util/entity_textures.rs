@@ -0,0 +1,341 @@
+/// Vanilla entity textures per version, read from the game JAR
+///
+/// Newer versions list many mob variants in data registries
+/// (data/minecraft/cat_variant/tabby.json names "minecraft:entity/cat/tabby");
+/// those are taken as they are. Everything else follows the layout of
+/// textures/entity/, which the game has kept across updates:
+///
+/// - "entity/bat.png" or "entity/cow/cow.png" is the entity's own texture
+/// - files in a subfolder are a group named after it
+///   ("entity/villager/profession/farmer.png": "profession" -> "farmer")
+/// - files sharing a prefix no file is named after form a group
+///   ("entity/horse/horse_markings_white.png": "markings" -> "white")
+/// - the rest of the folder goes in "texture", without the entity prefix
+///
+/// Reading the JAR of the exact version keeps the map right across updates
+/// that move textures (cow.png became temperate_cow.png in 1.21.5).
+use crate::util::{particle_cache, zip};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Textures of one entity
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityTextures {
+    /// Texture the entity shows without a variant (e.g., "minecraft:entity/cow/cow")
+    pub default: Option<String>,
+    /// Textures by group, then name (e.g., "markings" -> "white" ->
+    /// "minecraft:entity/horse/horse_markings_white"); "variant" holds the
+    /// registry variants, and registries with separate tame and angry
+    /// textures (wolves) add "tame" and "angry"
+    pub variants: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+/// Entity textures of a version
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityTextureMap {
+    pub version: String,
+    pub entities: BTreeMap<String, EntityTextures>,
+}
+
+impl EntityTextureMap {
+    /// Texture of an entity: a variant by group and name, else its default
+    pub fn texture(&self, entity: &str, variant: Option<(&str, &str)>) -> Option<&str> {
+        let textures = self.entities.get(entity)?;
+        variant
+            .and_then(|(group, name)| textures.variants.get(group)?.get(name))
+            .or(textures.default.as_ref())
+            .map(String::as_str)
+    }
+}
+
+/// Asset ID of an entity texture a registry names, None for other textures
+/// (painting variants name painting textures)
+fn entity_texture_id(texture: &str) -> Option<String> {
+    let (namespace, path) = texture.split_once(':').unwrap_or(("minecraft", texture));
+    path.starts_with("entity/")
+        .then(|| format!("{}:{}", namespace, path))
+}
+
+/// Whether a registry variant spawns with no condition (the fallback variant)
+fn is_unconditional(variant: &Value) -> bool {
+    variant["spawn_conditions"]
+        .as_array()
+        .is_some_and(|conditions| conditions.iter().any(|c| c.get("condition").is_none()))
+}
+
+/// Map entities to their textures from the entries of a version JAR
+///
+/// # Arguments
+/// * `entries` - File paths in the JAR
+/// * `read` - Contents of an entry, for the variant registries
+pub fn map_entity_textures(
+    entries: &[String],
+    read: impl Fn(&str) -> Option<Vec<u8>>,
+) -> BTreeMap<String, EntityTextures> {
+    let mut entities: BTreeMap<String, EntityTextures> = BTreeMap::new();
+    // Folder-level textures, grouped once every name is known
+    let mut loose: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
+    // Unconditional registry variants, for entities without their own texture
+    let mut fallbacks: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for entry in entries {
+        if let Some(rest) = entry.strip_prefix("assets/minecraft/textures/entity/") {
+            let Some(rest) = rest.strip_suffix(".png") else {
+                continue;
+            };
+            let id = format!("minecraft:entity/{}", rest);
+            let segments: Vec<&str> = rest.split('/').collect();
+            let entity = segments[0].to_string();
+            let textures = entities.entry(entity.clone()).or_default();
+            match segments[..] {
+                [_] => textures.default = Some(id),
+                [_, stem] if stem == entity => textures.default = Some(id),
+                [_, stem] => {
+                    let name = stem
+                        .strip_prefix(&format!("{}_", entity))
+                        .unwrap_or(stem)
+                        .to_string();
+                    loose.entry(entity).or_default().push((name, id));
+                }
+                [_, ref group @ .., stem] => {
+                    textures
+                        .variants
+                        .entry(group.join("/"))
+                        .or_default()
+                        .insert(stem.to_string(), id);
+                }
+                [] => {}
+            }
+            continue;
+        }
+
+        // data/minecraft/<entity>_variant/<name>.json
+        let Some(rest) = entry.strip_prefix("data/minecraft/") else {
+            continue;
+        };
+        let Some((registry, file)) = rest.split_once('/') else {
+            continue;
+        };
+        let (Some(entity), Some(name)) = (
+            registry.strip_suffix("_variant"),
+            file.strip_suffix(".json")
+                .filter(|name| !name.contains('/')),
+        ) else {
+            continue;
+        };
+        let Some(variant) =
+            read(entry).and_then(|bytes| serde_json::from_slice::<Value>(&bytes).ok())
+        else {
+            continue;
+        };
+
+        // 1.21.5+: asset_id, or assets.wild/tame/angry; before: *_texture
+        let wolf_assets = &variant["assets"];
+        let layers = [
+            (
+                "variant",
+                variant["asset_id"]
+                    .as_str()
+                    .or(wolf_assets["wild"].as_str()),
+            ),
+            ("variant", variant["wild_texture"].as_str()),
+            (
+                "tame",
+                wolf_assets["tame"]
+                    .as_str()
+                    .or(variant["tame_texture"].as_str()),
+            ),
+            (
+                "angry",
+                wolf_assets["angry"]
+                    .as_str()
+                    .or(variant["angry_texture"].as_str()),
+            ),
+        ];
+        for (group, texture) in layers {
+            let Some(id) = texture.and_then(entity_texture_id) else {
+                continue;
+            };
+            if group == "variant" && is_unconditional(&variant) {
+                fallbacks
+                    .entry(entity.to_string())
+                    .or_default()
+                    .push(id.clone());
+            }
+            entities
+                .entry(entity.to_string())
+                .or_default()
+                .variants
+                .entry(group.to_string())
+                .or_default()
+                .insert(name.to_string(), id);
+        }
+    }
+
+    for (entity, files) in loose {
+        let textures = entities.entry(entity).or_default();
+        let names: Vec<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+        for (name, id) in &files {
+            let group = name.split_once('_').filter(|(prefix, _)| {
+                !names.contains(prefix)
+                    && names
+                        .iter()
+                        .filter(|other| other.starts_with(&format!("{}_", prefix)))
+                        .count()
+                        >= 2
+            });
+            let (group, name) = group.unwrap_or(("texture", name));
+            textures
+                .variants
+                .entry(group.to_string())
+                .or_default()
+                .insert(name.to_string(), id.clone());
+        }
+    }
+
+    for (entity, textures) in &mut entities {
+        if textures.default.is_some() {
+            continue;
+        }
+        // The registry's fallback variant (first by name when several spawn
+        // unconditionally), else the folder's only texture
+        let mut fallback = fallbacks.get(entity).cloned().unwrap_or_default();
+        fallback.sort();
+        textures.default = fallback.into_iter().next().or_else(|| {
+            match textures
+                .variants
+                .get("texture")
+                .map(|group| group.values().collect::<Vec<_>>())
+            {
+                Some(only) if only.len() == 1 => Some(only[0].clone()),
+                _ => None,
+            }
+        });
+    }
+
+    entities
+}
+
+/// Read the entity textures of an installed version from its JAR
+pub fn entity_textures_for_version(version: &str) -> Result<EntityTextureMap> {
+    let jar_path = particle_cache::resolve_jar_path(version)?;
+    let jar = jar_path.to_string_lossy();
+    let entries = zip::list_zip_files(&jar)?;
+    let entities = map_entity_textures(&entries, |entry| zip::extract_zip_entry(&jar, entry).ok());
+    Ok(EntityTextureMap {
+        version: version.to_string(),
+        entities,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(|path| path.to_string()).collect()
+    }
+
+    #[test]
+    fn test_folder_layout() {
+        let entries = entries(&[
+            "assets/minecraft/textures/entity/bat.png",
+            "assets/minecraft/textures/entity/horse/horse_black.png",
+            "assets/minecraft/textures/entity/horse/horse_white.png",
+            "assets/minecraft/textures/entity/horse/horse_markings_white.png",
+            "assets/minecraft/textures/entity/horse/horse_markings_blackdots.png",
+            "assets/minecraft/textures/entity/villager/villager.png",
+            "assets/minecraft/textures/entity/villager/profession/farmer.png",
+            "assets/minecraft/textures/entity/villager/type/desert.png",
+            "assets/minecraft/textures/entity/wolf/wolf_ashen.png",
+            "assets/minecraft/textures/entity/wolf/wolf_ashen_tame.png",
+            "assets/minecraft/textures/entity/wolf/wolf_ashen_angry.png",
+            "assets/minecraft/textures/entity/wolf/wolf.png",
+            "assets/minecraft/textures/block/stone.png",
+        ]);
+        let map = EntityTextureMap {
+            version: "1.21.1".to_string(),
+            entities: map_entity_textures(&entries, |_| None),
+        };
+
+        assert_eq!(map.texture("bat", None), Some("minecraft:entity/bat"));
+        assert_eq!(
+            map.texture("horse", Some(("markings", "blackdots"))),
+            Some("minecraft:entity/horse/horse_markings_blackdots")
+        );
+        assert_eq!(
+            map.texture("horse", Some(("texture", "black"))),
+            Some("minecraft:entity/horse/horse_black")
+        );
+        // No horse.png, and more than one plain texture
+        assert_eq!(map.texture("horse", None), None);
+        assert_eq!(
+            map.texture("villager", Some(("profession", "farmer"))),
+            Some("minecraft:entity/villager/profession/farmer")
+        );
+        assert_eq!(
+            map.texture("villager", Some(("type", "plains"))),
+            Some("minecraft:entity/villager/villager")
+        );
+        // "ashen" is a texture itself, so its tame and angry files stay plain
+        let wolf = &map.entities["wolf"].variants;
+        assert_eq!(wolf.keys().collect::<Vec<_>>(), ["texture"]);
+        assert!(wolf["texture"].contains_key("ashen_tame"));
+        assert!(!map.entities.contains_key("stone"));
+    }
+
+    #[test]
+    fn test_variant_registries() {
+        let entries = entries(&[
+            "assets/minecraft/textures/entity/cow/temperate_cow.png",
+            "assets/minecraft/textures/entity/cow/warm_cow.png",
+            "data/minecraft/cow_variant/temperate.json",
+            "data/minecraft/cow_variant/warm.json",
+            "data/minecraft/wolf_variant/pale.json",
+            "data/minecraft/painting_variant/kebab.json",
+        ]);
+        let read = |entry: &str| -> Option<Vec<u8>> {
+            let json = match entry {
+                "data/minecraft/cow_variant/temperate.json" => {
+                    r#"{"asset_id": "minecraft:entity/cow/temperate_cow", "spawn_conditions": [{"priority": 0}]}"#
+                }
+                "data/minecraft/cow_variant/warm.json" => {
+                    r#"{"asset_id": "minecraft:entity/cow/warm_cow", "spawn_conditions": [{"condition": {"type": "minecraft:biome"}, "priority": 1}]}"#
+                }
+                "data/minecraft/wolf_variant/pale.json" => {
+                    r#"{"wild_texture": "minecraft:entity/wolf/wolf", "tame_texture": "minecraft:entity/wolf/wolf_tame", "angry_texture": "minecraft:entity/wolf/wolf_angry"}"#
+                }
+                "data/minecraft/painting_variant/kebab.json" => {
+                    r#"{"asset_id": "minecraft:kebab"}"#
+                }
+                _ => return None,
+            };
+            Some(json.as_bytes().to_vec())
+        };
+        let entities = map_entity_textures(&entries, read);
+
+        let cow = &entities["cow"];
+        assert_eq!(
+            cow.default.as_deref(),
+            Some("minecraft:entity/cow/temperate_cow")
+        );
+        assert_eq!(
+            cow.variants["variant"]["warm"],
+            "minecraft:entity/cow/warm_cow"
+        );
+        let wolf = &entities["wolf"];
+        assert_eq!(
+            wolf.variants["tame"]["pale"],
+            "minecraft:entity/wolf/wolf_tame"
+        );
+        assert_eq!(
+            wolf.variants["angry"]["pale"],
+            "minecraft:entity/wolf/wolf_angry"
+        );
+        assert!(!entities.contains_key("painting"));
+    }
+}
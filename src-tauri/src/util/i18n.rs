@@ -0,0 +1,181 @@
+/// Localized backend messages
+///
+/// Validation errors, compatibility issues and build warnings are produced in
+/// Rust and shown (or exported) as-is by the frontend, so they are looked up in
+/// Fluent bundles compiled into the binary from `locales/<lang>/backend.ftl`.
+/// The frontend negotiates the locale once at startup from the app language;
+/// messages missing from that locale fall back to en-US.
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use fluent_langneg::{negotiate_languages, NegotiationStrategy};
+use once_cell::sync::Lazy;
+use std::sync::RwLock;
+use tracing::{info, warn};
+use unic_langid::LanguageIdentifier;
+
+/// Locale used when the requested one isn't available, and for missing messages
+const DEFAULT_LOCALE: &str = "en-US";
+
+/// Bundled locales and their message files
+const LOCALES: &[(&str, &str)] = &[
+    ("en-US", include_str!("../../locales/en-US/backend.ftl")),
+    ("de", include_str!("../../locales/de/backend.ftl")),
+    ("fr", include_str!("../../locales/fr/backend.ftl")),
+];
+
+static BUNDLES: Lazy<Vec<(LanguageIdentifier, FluentBundle<FluentResource>)>> = Lazy::new(|| {
+    LOCALES
+        .iter()
+        .map(|(locale, source)| {
+            let langid: LanguageIdentifier = locale.parse().expect("bundled locale ID is valid");
+            let resource =
+                FluentResource::try_new(source.to_string()).unwrap_or_else(|(resource, errors)| {
+                    warn!("{} has syntax errors: {:?}", locale, errors);
+                    resource
+                });
+            let mut bundle = FluentBundle::new_concurrent(vec![langid.clone()]);
+            // Unicode isolation marks around placeables end up in exported reports
+            bundle.set_use_isolating(false);
+            if let Err(errors) = bundle.add_resource(resource) {
+                warn!("{} has duplicate messages: {:?}", locale, errors);
+            }
+            (langid, bundle)
+        })
+        .collect()
+});
+
+static CURRENT_LOCALE: Lazy<RwLock<LanguageIdentifier>> =
+    Lazy::new(|| RwLock::new(default_locale()));
+
+fn default_locale() -> LanguageIdentifier {
+    DEFAULT_LOCALE.parse().expect("default locale ID is valid")
+}
+
+/// Pick the best bundled locale for the requested ones (most preferred first)
+pub fn negotiate(requested: &[String]) -> LanguageIdentifier {
+    let requested = fluent_langneg::convert_vec_str_to_langids_lossy(requested);
+    let available: Vec<LanguageIdentifier> =
+        BUNDLES.iter().map(|(langid, _)| langid.clone()).collect();
+    let default = default_locale();
+    negotiate_languages(
+        &requested,
+        &available,
+        Some(&default),
+        NegotiationStrategy::Lookup,
+    )
+    .first()
+    .map(|langid| (*langid).clone())
+    .unwrap_or(default)
+}
+
+/// Set the locale of subsequent messages, returning the one negotiated
+pub fn set_locale(requested: &[String]) -> String {
+    let locale = negotiate(requested);
+    info!("Requested {:?}, using {}", requested, locale);
+    match CURRENT_LOCALE.write() {
+        Ok(mut current) => *current = locale.clone(),
+        Err(poisoned) => *poisoned.into_inner() = locale.clone(),
+    }
+    locale.to_string()
+}
+
+/// The locale messages are currently produced in
+pub fn current_locale() -> LanguageIdentifier {
+    match CURRENT_LOCALE.read() {
+        Ok(current) => current.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    }
+}
+
+/// Message `id` in the current locale with `args` filled in
+///
+/// Falls back to en-US, and to the ID itself if no bundle has the message.
+pub fn tr(id: &str, args: &[(&str, FluentValue)]) -> String {
+    format_in(&current_locale(), id, args)
+        .or_else(|| format_in(&default_locale(), id, args))
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// Translate a label callers pass in English ("Packs directory"), looked up as
+/// `label-packs-directory`; unknown labels are returned unchanged
+pub fn label(label: &str) -> String {
+    let id = format!("label-{}", label.to_lowercase().replace(' ', "-"));
+    format_in(&current_locale(), &id, &[])
+        .or_else(|| format_in(&default_locale(), &id, &[]))
+        .unwrap_or_else(|| label.to_string())
+}
+
+/// Format a message from one locale's bundle, None if it doesn't have it
+fn format_in(
+    locale: &LanguageIdentifier,
+    id: &str,
+    args: &[(&str, FluentValue)],
+) -> Option<String> {
+    let (_, bundle) = BUNDLES.iter().find(|(langid, _)| langid == locale)?;
+    let pattern = bundle.get_message(id)?.value()?;
+
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, value.clone());
+    }
+    let mut errors = Vec::new();
+    let text = bundle.format_pattern(pattern, Some(&fluent_args), &mut errors);
+    if !errors.is_empty() {
+        warn!("Errors formatting {} in {}: {:?}", id, locale, errors);
+    }
+    Some(text.into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn langid(locale: &str) -> LanguageIdentifier {
+        locale.parse().unwrap()
+    }
+
+    #[test]
+    fn test_negotiate() {
+        assert_eq!(negotiate(&["de-AT".to_string()]), langid("de"));
+        assert_eq!(
+            negotiate(&["ja".to_string(), "fr-CA".to_string()]),
+            langid("fr")
+        );
+        assert_eq!(negotiate(&["en-GB".to_string()]), langid("en-US"));
+        assert_eq!(negotiate(&[]), langid("en-US"));
+    }
+
+    #[test]
+    fn test_format_in() {
+        let args = [("label", FluentValue::from("Pack-Ordner"))];
+        assert_eq!(
+            format_in(&langid("de"), "validation-empty", &args).unwrap(),
+            "Pack-Ordner darf nicht leer sein"
+        );
+        let args = [("level", FluentValue::from(12))];
+        assert_eq!(
+            format_in(&langid("en-US"), "validation-compression-level", &args).unwrap(),
+            "Compression level must be between 0 and 9, got 12"
+        );
+        assert!(format_in(&langid("de"), "no-such-message", &[]).is_none());
+        assert_eq!(tr("no-such-message", &[]), "no-such-message");
+        assert_eq!(label("Unknown folder"), "Unknown folder");
+    }
+
+    #[test]
+    fn test_locales_define_every_message() {
+        let ids = |source: &str| -> HashSet<String> {
+            source
+                .lines()
+                .filter(|line| !line.starts_with(['#', ' ']) && line.contains(" = "))
+                .filter_map(|line| line.split(" = ").next())
+                .map(str::to_string)
+                .collect()
+        };
+        let english = ids(LOCALES[0].1);
+        for (locale, source) in &LOCALES[1..] {
+            assert_eq!(ids(source), english, "{} is out of sync with en-US", locale);
+        }
+    }
+}
@@ -0,0 +1,273 @@
+/// Utility for reading item models and picking their overrides
+///
+/// Item models live in `assets/<ns>/models/item` and share the block model
+/// format, plus a list of `overrides` that switch to another model once the
+/// item's predicates (custom_model_data, damaged, pulling, ...) reach given
+/// values. Resolution picks the override the game would for a supplied
+/// predicate map, then resolves parents like a block model. The
+/// `builtin/generated` and `builtin/entity` roots end a parent chain, as they
+/// are rendered by the game rather than described by a file.
+use crate::model::{MissingAssetPolicy, PackMeta};
+use crate::util::block_models::{self, BlockModel};
+use crate::util::{model_resolver, pack_files};
+use crate::{AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// An entry of an item model's `overrides`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ItemOverride {
+    /// Minimum value of each predicate for the override to apply
+    #[serde(default)]
+    pub predicate: HashMap<String, f64>,
+
+    /// Model used instead of the item's own (e.g., "minecraft:item/bow_pulling_0")
+    pub model: String,
+}
+
+/// An item model JSON file: a block model plus its overrides
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemModel {
+    #[serde(flatten)]
+    pub model: BlockModel,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<ItemOverride>,
+}
+
+/// An item resolved for a set of predicate values
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedItemModel {
+    pub item_id: String,
+    /// Model the overrides selected, or the item's own when none applied
+    pub model_id: String,
+    /// Index into `overrides` of the override applied
+    pub override_index: Option<usize>,
+    /// "generated" for flat items built from their layer textures, "entity"
+    /// for items drawn by a block entity renderer, None for element models
+    pub builtin: Option<String>,
+    /// Selected model with parent inheritance applied
+    pub model: BlockModel,
+    /// The item's overrides, so every variant can be listed
+    pub overrides: Vec<ItemOverride>,
+}
+
+/// Model ID of an item ("minecraft:bow" -> "minecraft:item/bow")
+pub fn item_model_id(item_id: &str) -> String {
+    let (namespace, path) = pack_files::split_asset_id(item_id);
+    if path.starts_with("item/") {
+        format!("{}:{}", namespace, path)
+    } else {
+        format!("{}:item/{}", namespace, path)
+    }
+}
+
+/// Read an item model JSON file, overrides included, from a resource pack
+///
+/// # Arguments
+/// * `pack` - The resource pack to search
+/// * `model_id` - Model ID like "minecraft:item/bow" or "item/bow"
+///
+/// # Returns
+/// The parsed ItemModel JSON
+pub fn read_item_model(pack: &PackMeta, model_id: &str) -> AppResult<ItemModel> {
+    let relative_path = block_models::model_id_to_path(&block_models::normalize_model_id(model_id));
+    let bytes = pack_files::read_pack_entry(pack, &relative_path)
+        .map_err(|e| AppError::validation(format!("Model not found: {}", e)))?;
+    serde_json::from_slice(&bytes)
+        .map_err(|e| AppError::validation(format!("Invalid model JSON: {}", e)))
+}
+
+/// Index of the override the game applies for the given predicate values
+///
+/// Every predicate of an override must be at least the value it lists, and
+/// the last override that matches wins. Predicates missing from `predicates`
+/// count as 0, the value the game reports for an item in its plain state.
+pub fn select_override(
+    overrides: &[ItemOverride],
+    predicates: &HashMap<String, f64>,
+) -> Option<usize> {
+    overrides.iter().rposition(|item_override| {
+        item_override
+            .predicate
+            .iter()
+            .all(|(name, minimum)| predicates.get(name).copied().unwrap_or(0.0) >= *minimum)
+    })
+}
+
+/// Resolve an item to the model shown for a set of predicate values
+///
+/// The policy only decides where the item's own model may come from; the
+/// model an override points to and all parents resolve through vanilla, as
+/// block model parents do.
+///
+/// # Arguments
+/// * `pack` - The resource pack to search
+/// * `item_id` - Item ID like "minecraft:bow" or "bow"
+/// * `vanilla_pack` - Vanilla assets to fall back to
+/// * `predicates` - Predicate values, e.g. {"pulling": 1, "pull": 0.7}
+/// * `policy` - What to do when the pack lacks the item model
+pub fn resolve_item_model(
+    pack: &PackMeta,
+    item_id: &str,
+    vanilla_pack: &PackMeta,
+    predicates: &HashMap<String, f64>,
+    policy: MissingAssetPolicy,
+) -> AppResult<ResolvedItemModel> {
+    let item_model_id = item_model_id(item_id);
+
+    let item = match read_item_model(pack, &item_model_id) {
+        Ok(item) => item,
+        Err(_) if policy == MissingAssetPolicy::UseVanilla => {
+            read_item_model(vanilla_pack, &item_model_id)?
+        }
+        Err(_) if policy == MissingAssetPolicy::Placeholder => {
+            return Ok(ResolvedItemModel {
+                item_id: item_id.to_string(),
+                model_id: block_models::PLACEHOLDER_MODEL_ID.to_string(),
+                override_index: None,
+                builtin: None,
+                model: block_models::placeholder_model(),
+                overrides: Vec::new(),
+            });
+        }
+        Err(_) => {
+            return Err(AppError::validation(format!(
+                "Model {} is not provided by pack {}",
+                item_model_id, pack.name
+            )))
+        }
+    };
+
+    let override_index = select_override(&item.overrides, predicates);
+    let model_id = match override_index {
+        Some(index) => block_models::normalize_model_id(&item.overrides[index].model),
+        None => item_model_id,
+    };
+    let mut resolved = model_resolver::resolve(&[pack, vanilla_pack], &model_id)?;
+    block_models::apply_display_defaults(&mut resolved.model);
+
+    Ok(ResolvedItemModel {
+        item_id: item_id.to_string(),
+        model_id,
+        override_index,
+        builtin: resolved.builtin,
+        model: resolved.model,
+        overrides: item.overrides,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_select_override() {
+        let overrides: Vec<ItemOverride> = serde_json::from_value(serde_json::json!([
+            {"predicate": {"pulling": 1}, "model": "item/bow_pulling_0"},
+            {"predicate": {"pulling": 1, "pull": 0.65}, "model": "item/bow_pulling_1"},
+            {"predicate": {"pulling": 1, "pull": 0.9}, "model": "item/bow_pulling_2"},
+            {"predicate": {"custom_model_data": 3}, "model": "item/fancy_bow"}
+        ]))
+        .unwrap();
+        let values = |pairs: &[(&str, f64)]| -> HashMap<String, f64> {
+            pairs.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+        };
+
+        assert_eq!(select_override(&overrides, &HashMap::new()), None);
+        assert_eq!(
+            select_override(&overrides, &values(&[("pulling", 1.0)])),
+            Some(0)
+        );
+        assert_eq!(
+            select_override(&overrides, &values(&[("pulling", 1.0), ("pull", 0.7)])),
+            Some(1)
+        );
+        assert_eq!(
+            select_override(&overrides, &values(&[("pulling", 1.0), ("pull", 1.0)])),
+            Some(2)
+        );
+        // Later overrides win
+        assert_eq!(
+            select_override(
+                &overrides,
+                &values(&[("pulling", 1.0), ("custom_model_data", 4.0)])
+            ),
+            Some(3)
+        );
+        assert_eq!(item_model_id("bow"), "minecraft:item/bow");
+        assert_eq!(item_model_id("mymod:item/wand"), "mymod:item/wand");
+    }
+
+    #[test]
+    fn test_resolve_item_model() {
+        let temp_dir = std::env::temp_dir().join("test_resolve_item_model");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let pack_models = temp_dir.join("pack/assets/minecraft/models/item");
+        let vanilla_models = temp_dir.join("vanilla/assets/minecraft/models/item");
+        fs::create_dir_all(&pack_models).unwrap();
+        fs::create_dir_all(&vanilla_models).unwrap();
+        fs::write(
+            pack_models.join("stick.json"),
+            r#"{
+                "parent": "item/handheld",
+                "textures": {"layer0": "item/stick"},
+                "overrides": [
+                    {"predicate": {"custom_model_data": 1}, "model": "item/wand"}
+                ]
+            }"#,
+        )
+        .unwrap();
+        fs::write(
+            pack_models.join("wand.json"),
+            r#"{"parent": "item/handheld", "textures": {"layer0": "item/wand"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            vanilla_models.join("handheld.json"),
+            r#"{"parent": "item/generated"}"#,
+        )
+        .unwrap();
+        fs::write(
+            vanilla_models.join("generated.json"),
+            r#"{"parent": "builtin/generated"}"#,
+        )
+        .unwrap();
+
+        let make_pack = |id: &str| PackMeta {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: temp_dir.join(id).to_string_lossy().to_string(),
+            ..Default::default()
+        };
+        let pack = make_pack("pack");
+        let vanilla = make_pack("vanilla");
+        let resolve = |item_id: &str, custom_model_data: f64, policy| {
+            let predicates = HashMap::from([("custom_model_data".to_string(), custom_model_data)]);
+            resolve_item_model(&pack, item_id, &vanilla, &predicates, policy)
+        };
+
+        let plain = resolve("minecraft:stick", 0.0, MissingAssetPolicy::UseVanilla);
+        let wand = resolve("stick", 1.0, MissingAssetPolicy::UseVanilla);
+        let missing = resolve("bow", 0.0, MissingAssetPolicy::PackOnly);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let plain = plain.unwrap();
+        assert_eq!(plain.model_id, "minecraft:item/stick");
+        assert_eq!(plain.override_index, None);
+        assert_eq!(plain.builtin.as_deref(), Some("generated"));
+        assert_eq!(plain.overrides.len(), 1);
+        let wand = wand.unwrap();
+        assert_eq!(wand.model_id, "minecraft:item/wand");
+        assert_eq!(wand.override_index, Some(0));
+        assert_eq!(
+            wand.model.textures.unwrap().get("layer0"),
+            Some(&"item/wand".to_string())
+        );
+        assert!(wand.model.parent.is_none());
+        assert!(missing.is_err());
+    }
+}
@@ -0,0 +1,1127 @@
+/// Typed parsing and validation of OptiFine CEM models (.jem and .jpm)
+///
+/// A JEM file lists root models, each replacing (or, with `attach`, adding
+/// to) one named part of the vanilla entity model. A root model may import
+/// its body from a JPM file through `model`; as in OptiFine, the JEM's own
+/// keys win and the JPM fills in the rest.
+///
+/// OptiFine logs and skips most of what it can't use: an unknown part name,
+/// a box without coordinates, a misspelt key. Validation reports those with
+/// a JSON path (e.g., "models[2].boxes[0].coordinates") so they can be fixed
+/// before the preview renders something different from the game. Part names
+/// are checked against the vanilla CEM template for the entity, picking the
+/// template for the requested game version where the layout changed.
+use crate::model::PackMeta;
+use crate::util::entity_geometry;
+use crate::util::pack_files;
+use crate::util::pack_lint::LintSeverity;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+/// Folder of CEM files within a namespace, also the prefix of their asset IDs
+const CEM_PATH: &str = "optifine/cem/";
+
+/// Keys of a JEM file
+const JEM_KEYS: &[&str] = &["texture", "textureSize", "shadowSize", "models", "credit"];
+
+/// Keys of a model part; `credit` is written by Blockbench
+const PART_KEYS: &[&str] = &[
+    "part",
+    "id",
+    "baseId",
+    "model",
+    "attach",
+    "scale",
+    "animations",
+    "texture",
+    "textureSize",
+    "invertAxis",
+    "translate",
+    "rotate",
+    "mirrorTexture",
+    "boxes",
+    "sprites",
+    "submodel",
+    "submodels",
+    "credit",
+];
+
+/// Keys of a box; `sizeAddX/Y/Z` are an EMF extension
+const BOX_KEYS: &[&str] = &[
+    "coordinates",
+    "textureOffset",
+    "uvDown",
+    "uvUp",
+    "uvNorth",
+    "uvSouth",
+    "uvWest",
+    "uvEast",
+    "sizeAdd",
+    "sizeAddX",
+    "sizeAddY",
+    "sizeAddZ",
+];
+
+/// Per-face UV keys of a box
+const FACE_UV_KEYS: &[&str] = &["uvDown", "uvUp", "uvNorth", "uvSouth", "uvWest", "uvEast"];
+
+/// A parsed .jem file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JemFile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub texture: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub texture_size: Option<[f32; 2]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shadow_size: Option<f32>,
+    #[serde(default)]
+    pub models: Vec<JemPart>,
+}
+
+/// A root model or submodel; a .jpm file holds the body of one
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JemPart {
+    /// Vanilla part this root model replaces (e.g., "head")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part: Option<String>,
+    /// Name animations refer to this part by
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// ID of another root model whose body this one copies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_id: Option<String>,
+    /// JPM file the body is imported from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Add to the vanilla part instead of replacing it
+    #[serde(
+        default,
+        deserialize_with = "bool_or_string",
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub attach: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scale: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub texture: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub texture_size: Option<[f32; 2]>,
+    /// Axes ("x", "y", "z") flipped between model space and the game's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invert_axis: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translate: Option<[f32; 3]>,
+    /// Degrees around x, y and z
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rotate: Option<[f32; 3]>,
+    /// Texture axes ("u", "v") mirrored
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mirror_texture: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub boxes: Vec<JemBox>,
+    /// Flat boxes built from a texture's opaque pixels; `textureOffset`,
+    /// `coordinates` and `sizeAdd` only
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sprites: Vec<JemBox>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub submodel: Option<Box<JemPart>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub submodels: Vec<JemPart>,
+    /// Animation layers, each mapping "part.variable" to an expression
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub animations: Vec<AnimationLayer>,
+}
+
+impl JemPart {
+    /// Child parts: `submodel`, then `submodels`
+    pub fn children(&self) -> impl Iterator<Item = &JemPart> {
+        self.submodel.as_deref().into_iter().chain(&self.submodels)
+    }
+
+    /// Copy in what a JPM defines and this part doesn't
+    fn fill_from(&mut self, jpm: JemPart) {
+        fn fill<T>(own: &mut Option<T>, other: Option<T>) {
+            if own.is_none() {
+                *own = other;
+            }
+        }
+        fn fill_vec<T>(own: &mut Vec<T>, other: Vec<T>) {
+            if own.is_empty() {
+                *own = other;
+            }
+        }
+
+        fill(&mut self.scale, jpm.scale);
+        fill(&mut self.texture, jpm.texture);
+        fill(&mut self.texture_size, jpm.texture_size);
+        fill(&mut self.invert_axis, jpm.invert_axis);
+        fill(&mut self.translate, jpm.translate);
+        fill(&mut self.rotate, jpm.rotate);
+        fill(&mut self.mirror_texture, jpm.mirror_texture);
+        fill(&mut self.submodel, jpm.submodel);
+        fill_vec(&mut self.boxes, jpm.boxes);
+        fill_vec(&mut self.sprites, jpm.sprites);
+        fill_vec(&mut self.submodels, jpm.submodels);
+        fill_vec(&mut self.animations, jpm.animations);
+    }
+}
+
+/// A cuboid, textured either from one corner (`textureOffset`, box UV) or
+/// face by face
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JemBox {
+    /// x, y, z, width, height, depth
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coordinates: Option<[f32; 6]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub texture_offset: Option<[f32; 2]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uv_down: Option<[f32; 4]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uv_up: Option<[f32; 4]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uv_north: Option<[f32; 4]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uv_south: Option<[f32; 4]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uv_west: Option<[f32; 4]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uv_east: Option<[f32; 4]>,
+    /// Growth in every direction that doesn't stretch the UVs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_add: Option<f32>,
+    #[serde(rename = "sizeAddX", default, skip_serializing_if = "Option::is_none")]
+    pub size_add_x: Option<f32>,
+    #[serde(rename = "sizeAddY", default, skip_serializing_if = "Option::is_none")]
+    pub size_add_y: Option<f32>,
+    #[serde(rename = "sizeAddZ", default, skip_serializing_if = "Option::is_none")]
+    pub size_add_z: Option<f32>,
+}
+
+/// One layer of animation assignments, in file order
+///
+/// Order matters: an assignment can read a variable set earlier in the same
+/// layer, so the entries are kept as written rather than in a map.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AnimationLayer(pub Vec<(String, Value)>);
+
+impl Serialize for AnimationLayer {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (target, expression) in &self.0 {
+            map.serialize_entry(target, expression)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for AnimationLayer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct LayerVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for LayerVisitor {
+            type Value = AnimationLayer;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("an object of animation expressions")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut access: A,
+            ) -> Result<AnimationLayer, A::Error> {
+                let mut entries = Vec::new();
+                while let Some(entry) = access.next_entry::<String, Value>()? {
+                    entries.push(entry);
+                }
+                Ok(AnimationLayer(entries))
+            }
+        }
+
+        deserializer.deserialize_map(LayerVisitor)
+    }
+}
+
+/// `attach` is written both as a boolean and as "true"/"false"
+fn bool_or_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::Bool(flag) => Ok(flag),
+        Value::String(text) => Ok(text.eq_ignore_ascii_case("true")),
+        _ => Ok(false),
+    }
+}
+
+/// One problem found in a CEM model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JemIssue {
+    /// Where in the file (e.g., "models[2].boxes[0]"); empty for the whole file
+    pub path: String,
+    pub severity: LintSeverity,
+    /// Stable identifier of the check (e.g., "unknown_part", "missing_coordinates")
+    pub code: String,
+    pub message: String,
+}
+
+/// A checked CEM model and what was found
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JemValidation {
+    /// Entity the file models (e.g., "cow"), from its file name
+    pub entity: String,
+    /// Vanilla template the part names were checked against (e.g.,
+    /// "cow_21.4"); None when no template is known
+    pub template: Option<String>,
+    /// The model with JPM imports filled in; None when it didn't parse
+    pub model: Option<JemFile>,
+    pub issues: Vec<JemIssue>,
+}
+
+impl JemValidation {
+    pub fn has_errors(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.severity == LintSeverity::Error)
+    }
+}
+
+/// Issues under construction
+struct Checker {
+    issues: Vec<JemIssue>,
+}
+
+impl Checker {
+    fn push(&mut self, path: &str, severity: LintSeverity, code: &str, message: String) {
+        self.issues.push(JemIssue {
+            path: path.to_string(),
+            severity,
+            code: code.to_string(),
+            message,
+        });
+    }
+
+    fn error(&mut self, path: &str, code: &str, message: String) {
+        self.push(path, LintSeverity::Error, code, message);
+    }
+
+    fn warning(&mut self, path: &str, code: &str, message: String) {
+        self.push(path, LintSeverity::Warning, code, message);
+    }
+
+    fn unknown_keys(&mut self, path: &str, object: &Map<String, Value>, known: &[&str]) {
+        for key in object.keys() {
+            if !known.contains(&key.as_str()) {
+                self.warning(
+                    &child_path(path, key),
+                    "unknown_key",
+                    format!("Unknown key \"{}\" is ignored", key),
+                );
+            }
+        }
+    }
+
+    /// Check a value is an array of `len` numbers
+    fn numbers(&mut self, path: &str, value: &Value, len: usize) -> Option<Vec<f64>> {
+        let numbers: Option<Vec<f64>> = value
+            .as_array()
+            .filter(|array| array.len() == len)
+            .and_then(|array| array.iter().map(Value::as_f64).collect());
+        if numbers.is_none() {
+            self.error(
+                path,
+                "invalid_value",
+                format!("Expected an array of {} numbers, found {}", len, value),
+            );
+        }
+        numbers
+    }
+}
+
+fn child_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+/// Parse a .jem file
+pub fn parse_jem(text: &str) -> Result<JemFile> {
+    serde_json::from_str(text).context("Invalid JEM")
+}
+
+/// Parse a .jpm file, the body of one part
+pub fn parse_jpm(text: &str) -> Result<JemPart> {
+    serde_json::from_str(text).context("Invalid JPM")
+}
+
+/// Pack-relative path of a JPM referenced from a JEM
+///
+/// References are relative to the JEM's folder unless they name a namespace
+/// ("minecraft:optifine/cem/cow_head.jpm") or start at "assets/"; ".jpm" is
+/// added when there's no extension, as OptiFine does.
+pub fn jpm_entry_path(jem_entry: &str, reference: &str) -> String {
+    let reference = reference.replace('\\', "/");
+    let file_name = reference.rsplit('/').next().unwrap_or(&reference);
+    let reference = if file_name.contains('.') {
+        reference
+    } else {
+        format!("{}.jpm", reference)
+    };
+    if reference.starts_with("assets/") {
+        return reference;
+    }
+    if let Some((namespace, path)) = reference.split_once(':') {
+        return format!("assets/{}/{}", namespace, path);
+    }
+    match jem_entry.rsplit_once('/') {
+        Some((folder, _)) => format!("{}/{}", folder, reference),
+        None => reference,
+    }
+}
+
+/// Entity a CEM file models, from its path
+///
+/// Version folders ("cem/1.21.4/cow.jem") and random-model numbering
+/// ("cow2.jem") are dropped.
+pub fn entity_for_path(jem_entry: &str) -> String {
+    let file = jem_entry.rsplit('/').next().unwrap_or(jem_entry);
+    let stem = file.strip_suffix(".jem").unwrap_or(file);
+    let base = stem.trim_end_matches(|c: char| c.is_ascii_digit());
+    if base.is_empty() || base.ends_with('_') {
+        stem.to_string()
+    } else {
+        base.to_string()
+    }
+}
+
+/// Asset ID of the CEM set a pack file belongs to
+///
+/// An entity's models, random-model variants ("cow2.jem"), version folders
+/// and random-model rules ("cow.properties") are indexed together as
+/// "minecraft:optifine/cem/cow", so a nest build copies a whole set from one
+/// pack. JPM files aren't part of it; they follow the JEMs importing them.
+pub fn cem_asset_id(file: &str) -> Option<String> {
+    let rest = file.strip_prefix("assets/")?;
+    let (namespace, path) = rest.split_once('/')?;
+    let relative = path.strip_prefix(CEM_PATH)?;
+    let stem = relative
+        .strip_suffix(".jem")
+        .or_else(|| relative.strip_suffix(".properties"))?;
+    Some(format!(
+        "{}:{}{}",
+        namespace,
+        CEM_PATH,
+        entity_for_path(stem)
+    ))
+}
+
+/// Whether an asset ID is an entity's CEM set
+pub fn is_cem_asset_id(asset_id: &str) -> bool {
+    asset_id
+        .split_once(':')
+        .is_some_and(|(_, path)| path.starts_with(CEM_PATH))
+}
+
+/// Pack-relative paths of the JPM files a JEM imports; none if it doesn't parse
+pub fn imported_jpms(jem_entry: &str, text: &str) -> Vec<String> {
+    let Ok(jem) = parse_jem(text) else {
+        return Vec::new();
+    };
+    jem.models
+        .iter()
+        .filter_map(|part| part.model.as_deref())
+        .map(|reference| jpm_entry_path(jem_entry, reference))
+        .collect()
+}
+
+/// Folders holding vanilla CEM templates: the vanilla cache (where
+/// `entity_geometry` writes what it extracts), then the templates bundled
+/// with the project
+pub fn vanilla_jem_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Ok(cache_dir) = crate::util::vanilla_textures::get_vanilla_cache_dir() {
+        dirs.push(cache_dir.join("assets/minecraft/optifine/cem"));
+    }
+    if let Some(project_root) = PathBuf::from(env!("CARGO_MANIFEST_DIR")).parent() {
+        dirs.push(project_root.join("__mocks__").join("cem"));
+    }
+    dirs
+}
+
+/// Parse a template's version suffix ("21.4" -> 1.21.4, "12" -> 1.12)
+fn template_version(suffix: &str) -> Option<(u32, u32)> {
+    let mut parts = suffix.split('.');
+    let minor = parts.next()?.parse().ok()?;
+    let patch = match parts.next() {
+        Some(patch) => patch.parse().ok()?,
+        None => 0,
+    };
+    parts.next().is_none().then_some((minor, patch))
+}
+
+/// Parse a game version ("1.21.1") into the form template suffixes use
+fn game_version(version: &str) -> Option<(u32, u32)> {
+    template_version(version.trim().strip_prefix("1.")?)
+}
+
+/// Name of the vanilla template for an entity in a game version
+///
+/// Templates named "<entity>_<version>" hold the layout used up to that
+/// version; the plain "<entity>" template is the current one. Without a
+/// version, or past every versioned template, the current one is used.
+pub fn template_name(entity: &str, version: Option<&str>, dirs: &[PathBuf]) -> Option<String> {
+    let prefix = format!("{}_", entity);
+    let mut candidates: Vec<((u32, u32), String)> = Vec::new();
+    let mut has_current = false;
+    for dir in dirs {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let Some(stem) = file_name.strip_suffix(".jem") else {
+                continue;
+            };
+            if stem == entity {
+                has_current = true;
+            } else if let Some(parsed) = stem.strip_prefix(&prefix).and_then(template_version) {
+                candidates.push((parsed, stem.to_string()));
+            }
+        }
+    }
+
+    if let Some(target) = version.and_then(game_version) {
+        let versioned = candidates
+            .into_iter()
+            .filter(|(parsed, _)| *parsed >= target)
+            .min_by_key(|(parsed, _)| *parsed);
+        if let Some((_, name)) = versioned {
+            return Some(name);
+        }
+    }
+    has_current.then(|| entity.to_string())
+}
+
+/// Root part names of a vanilla template
+pub fn template_parts(template: &str, dirs: &[PathBuf]) -> Option<Vec<String>> {
+    let text = dirs
+        .iter()
+        .find_map(|dir| std::fs::read_to_string(dir.join(format!("{}.jem", template))).ok())?;
+    let jem = parse_jem(&text).ok()?;
+    Some(
+        jem.models
+            .into_iter()
+            .filter_map(|part| part.part)
+            .collect(),
+    )
+}
+
+/// Check a JEM file's structure, and its part names if `known_parts` is given
+///
+/// Returns the typed model when the file parses at all.
+pub fn check_jem(text: &str, known_parts: Option<&[String]>) -> (Option<JemFile>, Vec<JemIssue>) {
+    let mut checker = Checker { issues: Vec::new() };
+    let value: Value = match serde_json::from_str(text) {
+        Ok(value) => value,
+        Err(e) => {
+            checker.error(
+                "",
+                "invalid_json",
+                format!("Not valid JSON at line {}: {}", e.line(), e),
+            );
+            return (None, checker.issues);
+        }
+    };
+    let Some(object) = value.as_object() else {
+        checker.error(
+            "",
+            "invalid_json",
+            "A JEM file must be an object".to_string(),
+        );
+        return (None, checker.issues);
+    };
+
+    checker.unknown_keys("", object, JEM_KEYS);
+    if let Some(size) = object.get("textureSize") {
+        check_texture_size(&mut checker, "textureSize", size);
+    }
+    match object.get("models").and_then(Value::as_array) {
+        Some(models) => {
+            let mut parts_seen = HashSet::new();
+            let mut ids = HashMap::new();
+            for (index, model) in models.iter().enumerate() {
+                let path = format!("models[{}]", index);
+                check_root(&mut checker, &path, model, known_parts, &mut parts_seen);
+                collect_ids(&mut checker, &path, model, &mut ids);
+            }
+        }
+        None => checker.error(
+            "models",
+            "missing_models",
+            "A JEM file needs a \"models\" array".to_string(),
+        ),
+    }
+
+    // Parsed from the text, not the value, to keep animation order
+    let model = match parse_jem(text) {
+        Ok(model) => Some(model),
+        Err(e) => {
+            // Only reachable through a type problem already reported above
+            if !checker
+                .issues
+                .iter()
+                .any(|issue| issue.severity == LintSeverity::Error)
+            {
+                checker.error("", "invalid_value", format!("{:#}", e));
+            }
+            None
+        }
+    };
+    (model, checker.issues)
+}
+
+fn check_texture_size(checker: &mut Checker, path: &str, value: &Value) {
+    if let Some(size) = checker.numbers(path, value, 2) {
+        if size.iter().any(|&side| side <= 0.0) {
+            checker.error(
+                path,
+                "invalid_value",
+                "Texture size must be positive".to_string(),
+            );
+        }
+    }
+}
+
+fn check_root(
+    checker: &mut Checker,
+    path: &str,
+    value: &Value,
+    known_parts: Option<&[String]>,
+    parts_seen: &mut HashSet<String>,
+) {
+    let Some(object) = value.as_object() else {
+        checker.error(
+            path,
+            "invalid_value",
+            "A model must be an object".to_string(),
+        );
+        return;
+    };
+
+    match object.get("part").and_then(Value::as_str) {
+        Some(part) => {
+            if let Some(known) = known_parts {
+                if !known.iter().any(|name| name == part) {
+                    checker.warning(
+                        &child_path(path, "part"),
+                        "unknown_part",
+                        format!(
+                            "\"{}\" isn't a part of this entity, so OptiFine skips it (parts: {})",
+                            part,
+                            known.join(", ")
+                        ),
+                    );
+                }
+            }
+            if !parts_seen.insert(part.to_string())
+                && object.get("attach") != Some(&Value::Bool(true))
+            {
+                checker.warning(
+                    &child_path(path, "part"),
+                    "duplicate_part",
+                    format!(
+                        "Part \"{}\" is replaced more than once; the last one wins",
+                        part
+                    ),
+                );
+            }
+        }
+        None if object.contains_key("baseId") => {}
+        None => checker.error(
+            path,
+            "missing_part",
+            "A root model needs a \"part\" naming the vanilla part it replaces".to_string(),
+        ),
+    }
+
+    if let Some(attach) = object.get("attach") {
+        let valid = attach.is_boolean()
+            || attach
+                .as_str()
+                .is_some_and(|text| text == "true" || text == "false");
+        if !valid {
+            checker.error(
+                &child_path(path, "attach"),
+                "invalid_value",
+                format!("\"attach\" must be true or false, found {}", attach),
+            );
+        }
+    }
+    if let Some(model) = object.get("model") {
+        if !model.is_string() {
+            checker.error(
+                &child_path(path, "model"),
+                "invalid_value",
+                "\"model\" must name a .jpm file".to_string(),
+            );
+        }
+    }
+
+    check_part(checker, path, object);
+}
+
+/// Checks shared by root models, submodels and JPM bodies
+fn check_part(checker: &mut Checker, path: &str, object: &Map<String, Value>) {
+    checker.unknown_keys(path, object, PART_KEYS);
+
+    for (key, len) in [("translate", 3), ("rotate", 3)] {
+        if let Some(value) = object.get(key) {
+            checker.numbers(&child_path(path, key), value, len);
+        }
+    }
+    if let Some(scale) = object.get("scale") {
+        if !scale.is_number() {
+            checker.error(
+                &child_path(path, "scale"),
+                "invalid_value",
+                format!("\"scale\" must be a number, found {}", scale),
+            );
+        }
+    }
+    if let Some(size) = object.get("textureSize") {
+        check_texture_size(checker, &child_path(path, "textureSize"), size);
+    }
+    check_axes(checker, path, object, "invertAxis", "xyz");
+    check_axes(checker, path, object, "mirrorTexture", "uv");
+
+    for key in ["boxes", "sprites"] {
+        match object.get(key) {
+            Some(Value::Array(boxes)) => {
+                for (index, value) in boxes.iter().enumerate() {
+                    check_box(checker, &format!("{}.{}[{}]", path, key, index), value);
+                }
+            }
+            Some(other) => checker.error(
+                &child_path(path, key),
+                "invalid_value",
+                format!("\"{}\" must be an array, found {}", key, other),
+            ),
+            None => {}
+        }
+    }
+
+    if let Some(submodel) = object.get("submodel") {
+        check_submodel(checker, &child_path(path, "submodel"), submodel);
+    }
+    match object.get("submodels") {
+        Some(Value::Array(submodels)) => {
+            for (index, submodel) in submodels.iter().enumerate() {
+                check_submodel(checker, &format!("{}.submodels[{}]", path, index), submodel);
+            }
+        }
+        Some(other) => checker.error(
+            &child_path(path, "submodels"),
+            "invalid_value",
+            format!("\"submodels\" must be an array, found {}", other),
+        ),
+        None => {}
+    }
+
+    if let Some(animations) = object.get("animations") {
+        check_animations(checker, &child_path(path, "animations"), animations);
+    }
+
+    let has_body = [
+        "boxes",
+        "sprites",
+        "submodel",
+        "submodels",
+        "model",
+        "baseId",
+        "animations",
+    ]
+    .iter()
+    .any(|key| object.contains_key(*key));
+    if !has_body {
+        checker.warning(
+            path,
+            "empty_part",
+            "Part has no boxes, submodels or imported model, so it renders nothing".to_string(),
+        );
+    }
+}
+
+fn check_submodel(checker: &mut Checker, path: &str, value: &Value) {
+    match value.as_object() {
+        Some(object) => check_part(checker, path, object),
+        None => checker.error(
+            path,
+            "invalid_value",
+            "A submodel must be an object".to_string(),
+        ),
+    }
+}
+
+fn check_axes(
+    checker: &mut Checker,
+    path: &str,
+    object: &Map<String, Value>,
+    key: &str,
+    allowed: &str,
+) {
+    let Some(value) = object.get(key) else {
+        return;
+    };
+    let valid = value
+        .as_str()
+        .is_some_and(|axes| axes.chars().all(|axis| allowed.contains(axis)));
+    if !valid {
+        checker.error(
+            &child_path(path, key),
+            "invalid_value",
+            format!(
+                "\"{}\" may only contain \"{}\", found {}",
+                key, allowed, value
+            ),
+        );
+    }
+}
+
+fn check_box(checker: &mut Checker, path: &str, value: &Value) {
+    let Some(object) = value.as_object() else {
+        checker.error(path, "invalid_value", "A box must be an object".to_string());
+        return;
+    };
+    checker.unknown_keys(path, object, BOX_KEYS);
+
+    match object.get("coordinates") {
+        Some(coordinates) => {
+            let coordinates_path = child_path(path, "coordinates");
+            if let Some(coordinates) = checker.numbers(&coordinates_path, coordinates, 6) {
+                if coordinates[3..].iter().any(|&side| side < 0.0) {
+                    checker.warning(
+                        &coordinates_path,
+                        "negative_size",
+                        "Box has a negative size; its faces render inside out".to_string(),
+                    );
+                }
+            }
+        }
+        None => checker.error(
+            path,
+            "missing_coordinates",
+            "Box has no \"coordinates\" and is skipped".to_string(),
+        ),
+    }
+
+    if let Some(offset) = object.get("textureOffset") {
+        checker.numbers(&child_path(path, "textureOffset"), offset, 2);
+    }
+    let mut face_uvs = 0;
+    for key in FACE_UV_KEYS {
+        if let Some(uv) = object.get(*key) {
+            face_uvs += 1;
+            checker.numbers(&child_path(path, key), uv, 4);
+        }
+    }
+    if object.contains_key("textureOffset") && face_uvs > 0 {
+        checker.warning(
+            path,
+            "mixed_uv",
+            "Box has both \"textureOffset\" and per-face UVs; the per-face UVs are ignored"
+                .to_string(),
+        );
+    } else if !object.contains_key("textureOffset") && face_uvs == 0 {
+        checker.error(
+            path,
+            "missing_uv",
+            "Box has neither \"textureOffset\" nor per-face UVs".to_string(),
+        );
+    }
+    for key in ["sizeAdd", "sizeAddX", "sizeAddY", "sizeAddZ"] {
+        if object.get(key).is_some_and(|value| !value.is_number()) {
+            checker.error(
+                &child_path(path, key),
+                "invalid_value",
+                format!("\"{}\" must be a number", key),
+            );
+        }
+    }
+}
+
+fn check_animations(checker: &mut Checker, path: &str, value: &Value) {
+    let Some(layers) = value.as_array() else {
+        checker.error(
+            path,
+            "invalid_value",
+            "\"animations\" must be an array of objects".to_string(),
+        );
+        return;
+    };
+    for (index, layer) in layers.iter().enumerate() {
+        let layer_path = format!("{}[{}]", path, index);
+        let Some(layer) = layer.as_object() else {
+            checker.error(
+                &layer_path,
+                "invalid_value",
+                "An animation layer must be an object".to_string(),
+            );
+            continue;
+        };
+        for (target, expression) in layer {
+            if !expression.is_string() && !expression.is_number() {
+                checker.error(
+                    &child_path(&layer_path, target),
+                    "invalid_value",
+                    format!("Expression for \"{}\" must be a string or number", target),
+                );
+            }
+        }
+    }
+}
+
+/// Warn about model IDs used more than once, which animations can't tell apart
+fn collect_ids(
+    checker: &mut Checker,
+    path: &str,
+    value: &Value,
+    ids: &mut HashMap<String, String>,
+) {
+    let Some(object) = value.as_object() else {
+        return;
+    };
+    if let Some(id) = object.get("id").and_then(Value::as_str) {
+        if let Some(first) = ids.get(id) {
+            checker.warning(
+                &child_path(path, "id"),
+                "duplicate_id",
+                format!("ID \"{}\" is also used at {}", id, first),
+            );
+        } else {
+            ids.insert(id.to_string(), path.to_string());
+        }
+    }
+    if let Some(submodel) = object.get("submodel") {
+        collect_ids(checker, &child_path(path, "submodel"), submodel, ids);
+    }
+    if let Some(Value::Array(submodels)) = object.get("submodels") {
+        for (index, submodel) in submodels.iter().enumerate() {
+            collect_ids(
+                checker,
+                &format!("{}.submodels[{}]", path, index),
+                submodel,
+                ids,
+            );
+        }
+    }
+}
+
+/// Fill in root models' JPM imports
+///
+/// `read` returns a pack file's text by pack-relative path. Missing or broken
+/// JPMs are reported and the part is left as the JEM defines it.
+pub fn resolve_jpm_imports(
+    jem: &mut JemFile,
+    jem_entry: &str,
+    mut read: impl FnMut(&str) -> Option<String>,
+) -> Vec<JemIssue> {
+    let mut checker = Checker { issues: Vec::new() };
+    for (index, part) in jem.models.iter_mut().enumerate() {
+        let Some(reference) = part.model.clone() else {
+            continue;
+        };
+        let path = format!("models[{}].model", index);
+        let entry = jpm_entry_path(jem_entry, &reference);
+        let Some(text) = read(&entry) else {
+            checker.error(
+                &path,
+                "missing_jpm",
+                format!("Imported model {} doesn't exist in the pack", entry),
+            );
+            continue;
+        };
+
+        let (value, jpm) = match serde_json::from_str::<Value>(&text) {
+            Ok(value) => (value, parse_jpm(&text)),
+            Err(e) => {
+                checker.error(
+                    &path,
+                    "invalid_jpm",
+                    format!("{} is not valid JSON at line {}: {}", entry, e.line(), e),
+                );
+                continue;
+            }
+        };
+        // Report problems inside the JPM under the importing part
+        if let Some(object) = value.as_object() {
+            let mut inner = Checker { issues: Vec::new() };
+            check_part(&mut inner, &entry, object);
+            checker.issues.extend(inner.issues);
+        }
+        match jpm {
+            Ok(jpm) => part.fill_from(jpm),
+            Err(e) => checker.error(&path, "invalid_jpm", format!("{}: {:#}", entry, e)),
+        }
+    }
+    checker.issues
+}
+
+/// Validate a CEM model in a pack
+///
+/// # Arguments
+/// * `pack` - Pack the file is in
+/// * `jem_entry` - Pack-relative path of the .jem file
+/// * `version` - Game version to check part names against; the current
+///   layout when None
+pub fn validate_jem(
+    pack: &PackMeta,
+    jem_entry: &str,
+    version: Option<&str>,
+) -> Result<JemValidation> {
+    let bytes = pack_files::read_pack_entry(pack, jem_entry)?;
+    let text = String::from_utf8_lossy(&bytes);
+
+    let entity = entity_for_path(jem_entry);
+    // Geometry extracted for the exact version beats the bundled templates
+    let extracted = version
+        .and_then(|version| entity_geometry::geometry_dir(version).ok())
+        .filter(|dir| dir.join(format!("{}.jem", entity)).exists());
+    let (template, known_parts) = match extracted {
+        Some(dir) => (
+            Some(entity.clone()),
+            template_parts(&entity, std::slice::from_ref(&dir)),
+        ),
+        None => {
+            let dirs = vanilla_jem_dirs();
+            let template = template_name(&entity, version, &dirs);
+            let known_parts = template
+                .as_deref()
+                .and_then(|template| template_parts(template, &dirs));
+            (template, known_parts)
+        }
+    };
+
+    let (mut model, mut issues) = check_jem(&text, known_parts.as_deref());
+    if let Some(model) = model.as_mut() {
+        issues.extend(resolve_jpm_imports(model, jem_entry, |entry| {
+            pack_files::read_pack_entry(pack, entry)
+                .ok()
+                .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        }));
+    }
+
+    Ok(JemValidation {
+        entity,
+        template: known_parts.is_some().then_some(template).flatten(),
+        model,
+        issues,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_check_jem() {
+        let text = r#"{
+            "textureSize": [64, 32],
+            "models": [
+                {
+                    "part": "head",
+                    "id": "head",
+                    "invertAxis": "xy",
+                    "translate": [0, -20, 8],
+                    "boxes": [
+                        {"coordinates": [-4, 16, -14, 8, 8, 6], "textureOffset": [0, 0]},
+                        {"coordinates": [-1, 22, -15, 2, 2], "textureOffset": [22, 0]}
+                    ],
+                    "submodels": [{"id": "horn", "boxes": [{"coordinates": [0, 0, 0, 1, 1, 1]}]}]
+                },
+                {"part": "tail", "id": "head", "boxes": [], "colour": "red"},
+                {"id": "body", "attach": "true", "boxes": []}
+            ]
+        }"#;
+        let known = vec!["head".to_string(), "body".to_string()];
+
+        let (model, issues) = check_jem(text, Some(&known));
+        let codes: Vec<(&str, &str)> = issues
+            .iter()
+            .map(|issue| (issue.path.as_str(), issue.code.as_str()))
+            .collect();
+
+        assert_eq!(
+            codes,
+            vec![
+                ("models[0].boxes[1].coordinates", "invalid_value"),
+                ("models[0].submodels[0].boxes[0]", "missing_uv"),
+                ("models[1].part", "unknown_part"),
+                ("models[1].colour", "unknown_key"),
+                ("models[1].id", "duplicate_id"),
+                ("models[2]", "missing_part"),
+            ]
+        );
+        // The bad coordinates keep the file from becoming a typed model
+        assert!(model.is_none());
+    }
+
+    #[test]
+    fn test_jpm_import_and_templates() {
+        let temp_dir = std::env::temp_dir().join("test_jem_jpm_import");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let cem_dir = temp_dir.join("assets/minecraft/optifine/cem");
+        fs::create_dir_all(&cem_dir).unwrap();
+        fs::write(
+            cem_dir.join("cow2.jem"),
+            r#"{"models": [{"part": "head", "id": "head", "model": "cow_head", "translate": [0, -20, 8]}]}"#,
+        )
+        .unwrap();
+        fs::write(
+            cem_dir.join("cow_head.jpm"),
+            r#"{"translate": [1, 1, 1], "boxes": [{"coordinates": [-4, 16, -14, 8, 8, 6], "textureOffset": [0, 0]}]}"#,
+        )
+        .unwrap();
+        let templates = temp_dir.join("templates");
+        fs::create_dir_all(&templates).unwrap();
+        for name in ["cow", "cow_21.4", "cow_12"] {
+            fs::write(templates.join(format!("{}.jem", name)), "{\"models\": []}").unwrap();
+        }
+        let pack = PackMeta {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            path: temp_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        };
+
+        let entry = "assets/minecraft/optifine/cem/cow2.jem";
+        let validation = validate_jem(&pack, entry, None).unwrap();
+        let dirs = [templates.clone()];
+        let current = template_name("cow", None, &dirs);
+        let legacy = template_name("cow", Some("1.21.1"), &dirs);
+        let oldest = template_name("cow", Some("1.8.9"), &dirs);
+        let newest = template_name("cow", Some("1.21.5"), &dirs);
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(validation.entity, "cow");
+        assert!(!validation.has_errors(), "{:?}", validation.issues);
+        let head = &validation.model.unwrap().models[0];
+        // The JEM's own translate wins over the JPM's
+        assert_eq!(head.translate, Some([0.0, -20.0, 8.0]));
+        assert_eq!(head.boxes.len(), 1);
+
+        assert_eq!(current.as_deref(), Some("cow"));
+        assert_eq!(legacy.as_deref(), Some("cow_21.4"));
+        assert_eq!(oldest.as_deref(), Some("cow_12"));
+        assert_eq!(newest.as_deref(), Some("cow"));
+    }
+}
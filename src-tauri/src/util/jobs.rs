@@ -0,0 +1,336 @@
+/// Background jobs: heavy commands queued, tracked and followed by events
+///
+/// `submit` queues a job and returns its ID straight away. Jobs of one kind
+/// run one after another in submission order, on a worker thread of their
+/// own, so two vanilla extractions never fill the cache at the same time;
+/// jobs of different kinds run side by side. Every status change and progress
+/// report goes to the listener set with `set_listener` (the app emits them as
+/// events), and the latest state of recent jobs can be polled with `get_job`
+/// and `list_jobs`. Running jobs are stopped through `cancellation`.
+use crate::model::Progress;
+use crate::util::cancellation;
+use crate::AppError;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::info;
+
+/// Finished jobs kept for polling; older ones are forgotten
+const MAX_FINISHED_JOBS: usize = 64;
+
+static REGISTRY: Lazy<Mutex<Registry>> = Lazy::new(|| Mutex::new(Registry::default()));
+
+type Listener = Box<dyn Fn(&JobInfo) + Send + Sync>;
+static LISTENER: Lazy<RwLock<Option<Listener>>> = Lazy::new(|| RwLock::new(None));
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Queue a job runs in; jobs of one kind run one at a time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    Scan,
+    Build,
+    /// Extracting vanilla textures into the cache
+    VanillaExtraction,
+    /// Decompiling Minecraft classes for particle data
+    Decompilation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+/// Latest state of a job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobInfo {
+    pub id: String,
+    pub kind: JobKind,
+    pub status: JobStatus,
+    /// Last progress reported, for jobs that report any
+    pub progress: Option<Progress>,
+    /// What the command returns, once succeeded
+    pub result: Option<serde_json::Value>,
+    /// Why the job failed or was cancelled
+    pub error: Option<AppError>,
+    /// Seconds since the Unix epoch
+    pub submitted_at: u64,
+    pub finished_at: Option<u64>,
+}
+
+/// Handle a running job reports progress through
+#[derive(Debug, Clone)]
+pub struct JobContext {
+    id: String,
+}
+
+impl JobContext {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Record progress and pass it on to the listener
+    pub fn report_progress(&self, phase: &str, completed: u64, total: u64) {
+        update(&self.id, |job| {
+            job.progress = Some(Progress {
+                phase: phase.to_string(),
+                completed,
+                total,
+                bytes: None,
+            });
+        });
+    }
+}
+
+type JobFn = Box<dyn FnOnce(&JobContext) -> Result<serde_json::Value, AppError> + Send>;
+
+#[derive(Default)]
+struct Registry {
+    jobs: HashMap<String, JobInfo>,
+    /// IDs of finished jobs, oldest first
+    finished: VecDeque<String>,
+    lanes: HashMap<JobKind, Lane>,
+}
+
+#[derive(Default)]
+struct Lane {
+    pending: VecDeque<(String, JobFn)>,
+    /// Whether a worker thread is draining `pending`
+    running: bool,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+fn lock_registry() -> std::sync::MutexGuard<'static, Registry> {
+    REGISTRY.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Call `listener` with every job update from now on
+pub fn set_listener(listener: impl Fn(&JobInfo) + Send + Sync + 'static) {
+    *LISTENER.write().unwrap_or_else(|e| e.into_inner()) = Some(Box::new(listener));
+}
+
+fn notify(job: &JobInfo) {
+    if let Some(listener) = LISTENER.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        listener(job);
+    }
+}
+
+/// Change a job and notify the listener, outside the registry lock
+fn update(job_id: &str, change: impl FnOnce(&mut JobInfo)) {
+    let updated = lock_registry().jobs.get_mut(job_id).map(|job| {
+        change(job);
+        job.clone()
+    });
+    if let Some(job) = updated {
+        notify(&job);
+    }
+}
+
+/// Queue `run` and return the new job's ID
+///
+/// `run` gets a context to report progress with and returns the command's
+/// result as JSON.
+pub fn submit(
+    kind: JobKind,
+    run: impl FnOnce(&JobContext) -> Result<serde_json::Value, AppError> + Send + 'static,
+) -> String {
+    let id = format!("job-{}", NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    let job = JobInfo {
+        id: id.clone(),
+        kind,
+        status: JobStatus::Queued,
+        progress: None,
+        result: None,
+        error: None,
+        submitted_at: now(),
+        finished_at: None,
+    };
+
+    let start_worker = {
+        let mut registry = lock_registry();
+        registry.jobs.insert(id.clone(), job.clone());
+        let lane = registry.lanes.entry(kind).or_default();
+        lane.pending.push_back((id.clone(), Box::new(run)));
+        !std::mem::replace(&mut lane.running, true)
+    };
+    info!("Queued {} ({:?})", id, kind);
+    notify(&job);
+
+    if start_worker {
+        std::thread::spawn(move || run_lane(kind));
+    }
+    id
+}
+
+/// Run a lane's queued jobs until it is empty
+fn run_lane(kind: JobKind) {
+    loop {
+        let next = {
+            let mut registry = lock_registry();
+            let lane = registry.lanes.entry(kind).or_default();
+            let next = lane.pending.pop_front();
+            if next.is_none() {
+                lane.running = false;
+            }
+            next
+        };
+        match next {
+            Some((id, run)) => run_job(id, run),
+            None => return,
+        }
+    }
+}
+
+fn run_job(id: String, run: JobFn) {
+    update(&id, |job| job.status = JobStatus::Running);
+    let context = JobContext { id: id.clone() };
+
+    let (outcome, cancelled) = cancellation::run_job(&id, || {
+        catch_unwind(AssertUnwindSafe(|| run(&context)))
+            .unwrap_or_else(|_| Err(AppError::internal("Job failed", "The job panicked")))
+    });
+    let (status, result, error) = match outcome {
+        Ok(result) => (JobStatus::Succeeded, Some(result), None),
+        Err(_) if cancelled => (
+            JobStatus::Cancelled,
+            None,
+            Some(AppError::cancelled(format!("Job {} was cancelled", id))),
+        ),
+        Err(e) => (JobStatus::Failed, None, Some(e)),
+    };
+    info!("{} finished: {:?}", id, status);
+    finish(&id, status, result, error);
+}
+
+fn finish(id: &str, status: JobStatus, result: Option<serde_json::Value>, error: Option<AppError>) {
+    update(id, |job| {
+        job.status = status;
+        job.result = result;
+        job.error = error;
+        job.finished_at = Some(now());
+    });
+
+    let mut registry = lock_registry();
+    registry.finished.push_back(id.to_string());
+    while registry.finished.len() > MAX_FINISHED_JOBS {
+        if let Some(oldest) = registry.finished.pop_front() {
+            registry.jobs.remove(&oldest);
+        }
+    }
+}
+
+/// Cancel a job: a queued one is dropped, a running one is asked to stop
+///
+/// Returns false if the job isn't queued or running.
+pub fn cancel(job_id: &str) -> bool {
+    let dropped = {
+        let mut registry = lock_registry();
+        let kind = registry.jobs.get(job_id).map(|job| job.kind);
+        kind.and_then(|kind| registry.lanes.get_mut(&kind))
+            .and_then(|lane| {
+                let position = lane.pending.iter().position(|(id, _)| id == job_id)?;
+                lane.pending.remove(position)
+            })
+            .is_some()
+    };
+    if dropped {
+        info!("Cancelled queued {}", job_id);
+        finish(
+            job_id,
+            JobStatus::Cancelled,
+            None,
+            Some(AppError::cancelled(format!("Job {} was cancelled", job_id))),
+        );
+        return true;
+    }
+    cancellation::cancel(job_id)
+}
+
+/// Latest state of a job, or None if it is unknown or long finished
+pub fn get_job(job_id: &str) -> Option<JobInfo> {
+    lock_registry().jobs.get(job_id).cloned()
+}
+
+/// Queued, running and recently finished jobs, oldest first
+pub fn list_jobs() -> Vec<JobInfo> {
+    let mut jobs: Vec<JobInfo> = lock_registry().jobs.values().cloned().collect();
+    // IDs count up in submission order
+    jobs.sort_by_key(|job| {
+        job.id
+            .trim_start_matches("job-")
+            .parse::<u64>()
+            .unwrap_or(0)
+    });
+    jobs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::time::{Duration, Instant};
+
+    fn wait_until_finished(job_id: &str) -> JobInfo {
+        let started = Instant::now();
+        loop {
+            let job = get_job(job_id).unwrap();
+            if job.finished_at.is_some() {
+                return job;
+            }
+            assert!(
+                started.elapsed() < Duration::from_secs(10),
+                "{} hangs",
+                job_id
+            );
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_jobs_of_a_kind_run_in_order() {
+        let (release, released) = mpsc::channel::<()>();
+        let first = submit(JobKind::Decompilation, move |context| {
+            context.report_progress("Waiting", 0, 1);
+            released.recv().ok();
+            Ok(serde_json::json!("first"))
+        });
+        // Queued behind the first, so it can be dropped before it starts
+        let second = submit(JobKind::Decompilation, |_| Ok(serde_json::json!("second")));
+        let third = submit(JobKind::Decompilation, |_| {
+            Err(AppError::validation("Bad input"))
+        });
+
+        assert_eq!(get_job(&second).unwrap().status, JobStatus::Queued);
+        assert!(cancel(&second));
+        release.send(()).unwrap();
+
+        let first = wait_until_finished(&first);
+        assert_eq!(first.status, JobStatus::Succeeded);
+        assert_eq!(first.result, Some(serde_json::json!("first")));
+        assert_eq!(first.progress.unwrap().phase, "Waiting");
+        let second = wait_until_finished(&second);
+        assert_eq!(second.status, JobStatus::Cancelled);
+        assert_eq!(second.error.unwrap().code, "CANCELLED");
+        let third = wait_until_finished(&third);
+        assert_eq!(third.status, JobStatus::Failed);
+        assert_eq!(third.error.unwrap().code, "VALIDATION_ERROR");
+        assert!(!cancel(&third.id));
+    }
+}
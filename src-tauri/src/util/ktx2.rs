@@ -0,0 +1,333 @@
+/// GPU-compressed KTX2 copies of preview textures
+///
+/// The 3D preview of a 512x or larger pack runs out of memory once every
+/// texture sits decoded as RGBA in the webview. Asked with `?format=ktx2`, the
+/// asset protocol serves a KTX2 texture holding BC3 (DXT5) blocks instead: a
+/// quarter of the size, uploaded to the GPU without decoding. BC3 is sampled
+/// natively by every desktop GPU; Basis Universal would also cover mobile GPUs
+/// but needs its C++ encoder, which the app doesn't ship.
+///
+/// Encoded textures are cached in <cache dir>/weaverbird/ktx2 under a key the
+/// caller derives from the source file, like thumbnails.
+use anyhow::{anyhow, bail, Context, Result};
+use image::RgbaImage;
+use std::fs;
+use std::path::PathBuf;
+
+const IDENTIFIER: [u8; 12] = [
+    0xAB, b'K', b'T', b'X', b' ', b'2', b'0', 0xBB, b'\r', b'\n', 0x1A, b'\n',
+];
+
+/// VK_FORMAT_BC3_SRGB_BLOCK
+const VK_FORMAT_BC3_SRGB_BLOCK: u32 = 138;
+
+/// Header (48 bytes), index (32) and the index of the single level (24)
+const LEVEL_INDEX_END: usize = 104;
+
+/// Bytes of one 4x4 BC3 block
+const BLOCK_BYTES: usize = 16;
+
+/// Directory compressed textures are cached in
+pub fn ktx2_cache_dir() -> Result<PathBuf> {
+    let cache_dir = dirs::cache_dir()
+        .ok_or_else(|| anyhow!("Could not find cache directory"))?
+        .join("weaverbird")
+        .join("ktx2");
+
+    fs::create_dir_all(&cache_dir).context("Failed to create KTX2 cache directory")?;
+
+    Ok(cache_dir)
+}
+
+/// KTX2 bytes of a PNG, encoding and caching them if `cache_key` is new
+///
+/// # Arguments
+/// * `cache_key` - Identifies this version of the source PNG
+/// * `read_png` - Reads the source, only called when the cache misses
+pub fn get_or_encode(
+    cache_key: u64,
+    read_png: impl FnOnce() -> Result<Vec<u8>>,
+) -> Result<Vec<u8>> {
+    let cached = ktx2_cache_dir()?.join(format!("{:016x}.ktx2", cache_key));
+    if let Ok(bytes) = fs::read(&cached) {
+        return Ok(bytes);
+    }
+
+    let png = read_png()?;
+    let image = image::load_from_memory(&png)
+        .context("Failed to decode texture")?
+        .to_rgba8();
+    let ktx2 = encode_ktx2(&image)?;
+
+    // Write under a temporary name so a concurrent request never reads half a file
+    let partial = cached.with_extension("ktx2.partial");
+    fs::write(&partial, &ktx2).context("Failed to write KTX2 texture")?;
+    fs::rename(&partial, &cached).context("Failed to move KTX2 texture into place")?;
+    Ok(ktx2)
+}
+
+/// Encode an image as a single-level BC3 KTX2 texture
+///
+/// WebGL only accepts BC textures whose sides are multiples of 4, so other
+/// sizes are refused and should be served as PNG.
+pub fn encode_ktx2(image: &RgbaImage) -> Result<Vec<u8>> {
+    let (width, height) = image.dimensions();
+    if width == 0 || height == 0 || width % 4 != 0 || height % 4 != 0 {
+        bail!(
+            "{}x{} can't be block-compressed, sides must be multiples of 4",
+            width,
+            height
+        );
+    }
+
+    let blocks = encode_bc3(image);
+    let dfd = data_format_descriptor();
+    let dfd_end = LEVEL_INDEX_END + dfd.len();
+    // Level data is aligned to the block size
+    let level_offset = dfd_end.div_ceil(BLOCK_BYTES) * BLOCK_BYTES;
+
+    let mut out = Vec::with_capacity(level_offset + blocks.len());
+    out.extend_from_slice(&IDENTIFIER);
+    for field in [
+        VK_FORMAT_BC3_SRGB_BLOCK,
+        1, // typeSize
+        width,
+        height,
+        0, // pixelDepth
+        0, // layerCount
+        1, // faceCount
+        1, // levelCount
+        0, // supercompressionScheme
+    ] {
+        out.extend_from_slice(&field.to_le_bytes());
+    }
+    // DFD, then no key/value data
+    for field in [LEVEL_INDEX_END, dfd.len(), 0, 0] {
+        out.extend_from_slice(&(field as u32).to_le_bytes());
+    }
+    // No supercompression global data, then the one level
+    for field in [0, 0, level_offset, blocks.len(), blocks.len()] {
+        out.extend_from_slice(&(field as u64).to_le_bytes());
+    }
+    out.extend_from_slice(&dfd);
+    out.resize(level_offset, 0);
+    out.extend_from_slice(&blocks);
+    Ok(out)
+}
+
+/// Khronos data format descriptor for BC3 with sRGB color
+fn data_format_descriptor() -> Vec<u8> {
+    const SAMPLE_BYTES: u16 = 16;
+    let block_size = 24 + 2 * SAMPLE_BYTES;
+
+    let mut dfd = Vec::new();
+    dfd.extend_from_slice(&(4 + u32::from(block_size)).to_le_bytes());
+    dfd.extend_from_slice(&0u32.to_le_bytes()); // Khronos vendor, basic descriptor
+    dfd.extend_from_slice(&2u16.to_le_bytes()); // version
+    dfd.extend_from_slice(&block_size.to_le_bytes());
+    // Color model BC3, BT.709 primaries, sRGB transfer, straight alpha
+    dfd.extend_from_slice(&[130, 1, 2, 0]);
+    // 4x4x1 texel blocks of 16 bytes
+    dfd.extend_from_slice(&[3, 3, 0, 0]);
+    dfd.extend_from_slice(&[16, 0, 0, 0, 0, 0, 0, 0]);
+    // Alpha (linear even in sRGB formats) in the first 64 bits, color in the last
+    for (bit_offset, channel) in [(0u16, 15u8 | 0x10), (64, 0)] {
+        dfd.extend_from_slice(&bit_offset.to_le_bytes());
+        dfd.extend_from_slice(&[63, channel]);
+        dfd.extend_from_slice(&[0; 4]);
+        dfd.extend_from_slice(&0u32.to_le_bytes());
+        dfd.extend_from_slice(&u32::MAX.to_le_bytes());
+    }
+    dfd
+}
+
+/// BC3 blocks of an image whose sides are multiples of 4, row by row
+fn encode_bc3(image: &RgbaImage) -> Vec<u8> {
+    let (width, height) = image.dimensions();
+    let mut out = Vec::with_capacity((width as usize / 4) * (height as usize / 4) * BLOCK_BYTES);
+    for block_y in (0..height).step_by(4) {
+        for block_x in (0..width).step_by(4) {
+            let mut pixels = [[0u8; 4]; 16];
+            for (i, pixel) in pixels.iter_mut().enumerate() {
+                *pixel = image
+                    .get_pixel(block_x + i as u32 % 4, block_y + i as u32 / 4)
+                    .0;
+            }
+            out.extend_from_slice(&encode_alpha_block(&pixels));
+            out.extend_from_slice(&encode_color_block(&pixels));
+        }
+    }
+    out
+}
+
+/// Alpha half of a BC3 block: two endpoints and a 3-bit index per pixel
+fn encode_alpha_block(pixels: &[[u8; 4]; 16]) -> [u8; 8] {
+    let max = pixels.iter().map(|pixel| pixel[3]).max().unwrap_or(255);
+    let min = pixels.iter().map(|pixel| pixel[3]).min().unwrap_or(255);
+    let mut block = [max, min, 0, 0, 0, 0, 0, 0];
+    if max == min {
+        return block;
+    }
+
+    // With the first endpoint larger, indices 2-7 interpolate in sevenths
+    let mut palette = [max as u32, min as u32, 0, 0, 0, 0, 0, 0];
+    for (i, value) in palette.iter_mut().enumerate().skip(2) {
+        *value = ((8 - i as u32) * max as u32 + (i as u32 - 1) * min as u32) / 7;
+    }
+    let mut indices = 0u64;
+    for (i, pixel) in pixels.iter().enumerate() {
+        let nearest = (0..8)
+            .min_by_key(|&index| palette[index].abs_diff(pixel[3] as u32))
+            .unwrap_or(0) as u64;
+        indices |= nearest << (3 * i);
+    }
+    block[2..].copy_from_slice(&indices.to_le_bytes()[..6]);
+    block
+}
+
+/// Color half of a BC3 block: two RGB565 endpoints and a 2-bit index per pixel
+///
+/// The endpoints are the two most different colors in the block, exact for
+/// the two-color blocks common in pixel art. Invisible pixels don't pick the
+/// endpoints unless the whole block is invisible.
+fn encode_color_block(pixels: &[[u8; 4]; 16]) -> [u8; 8] {
+    let visible: Vec<[u8; 3]> = pixels
+        .iter()
+        .filter(|pixel| pixel[3] > 0)
+        .map(|pixel| [pixel[0], pixel[1], pixel[2]])
+        .collect();
+    let sources = if visible.is_empty() {
+        pixels
+            .iter()
+            .map(|pixel| [pixel[0], pixel[1], pixel[2]])
+            .collect()
+    } else {
+        visible
+    };
+    let spread = |a: &[u8; 3], b: &[u8; 3]| -> u32 {
+        (0..3)
+            .map(|channel| (a[channel].abs_diff(b[channel]) as u32).pow(2))
+            .sum()
+    };
+    let mut high = sources[0];
+    let mut low = sources[0];
+    for (i, a) in sources.iter().enumerate() {
+        for b in &sources[i + 1..] {
+            if spread(a, b) > spread(&high, &low) {
+                (high, low) = (*a, *b);
+            }
+        }
+    }
+
+    // color0 above color1 selects four-color mode even in decoders reading the
+    // block as plain BC1
+    let (color0, color1) = {
+        let (a, b) = (to_rgb565(high), to_rgb565(low));
+        (a.max(b), a.min(b))
+    };
+    let mut block = [0u8; 8];
+    block[0..2].copy_from_slice(&color0.to_le_bytes());
+    block[2..4].copy_from_slice(&color1.to_le_bytes());
+    if color0 == color1 {
+        return block;
+    }
+
+    let end0 = from_rgb565(color0);
+    let end1 = from_rgb565(color1);
+    let mut palette = [end0, end1, [0; 3], [0; 3]];
+    for channel in 0..3 {
+        palette[2][channel] = (2 * end0[channel] + end1[channel]) / 3;
+        palette[3][channel] = (end0[channel] + 2 * end1[channel]) / 3;
+    }
+    let mut indices = 0u32;
+    for (i, pixel) in pixels.iter().enumerate() {
+        let distance = |color: &[u32; 3]| -> u32 {
+            (0..3)
+                .map(|channel| color[channel].abs_diff(pixel[channel] as u32).pow(2))
+                .sum()
+        };
+        let nearest = (0..4)
+            .min_by_key(|&index| distance(&palette[index]))
+            .unwrap_or(0) as u32;
+        indices |= nearest << (2 * i);
+    }
+    block[4..].copy_from_slice(&indices.to_le_bytes());
+    block
+}
+
+fn to_rgb565(color: [u8; 3]) -> u16 {
+    let quantize = |value: u8, max: u32| ((value as u32 * max + 127) / 255) as u16;
+    quantize(color[0], 31) << 11 | quantize(color[1], 63) << 5 | quantize(color[2], 31)
+}
+
+fn from_rgb565(color: u16) -> [u32; 3] {
+    let expand = |value: u16, max: u32| (value as u32 * 255 + max / 2) / max;
+    [
+        expand(color >> 11, 31),
+        expand((color >> 5) & 63, 63),
+        expand(color & 31, 31),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn test_encode_ktx2_layout() {
+        let image = RgbaImage::from_pixel(8, 4, Rgba([255, 0, 0, 255]));
+        let ktx2 = encode_ktx2(&image).unwrap();
+
+        assert_eq!(ktx2[..12], IDENTIFIER);
+        let field =
+            |offset: usize| u32::from_le_bytes(ktx2[offset..offset + 4].try_into().unwrap());
+        assert_eq!(field(12), VK_FORMAT_BC3_SRGB_BLOCK);
+        assert_eq!((field(20), field(24)), (8, 4));
+        let level_offset = u64::from_le_bytes(ktx2[80..88].try_into().unwrap()) as usize;
+        assert_eq!(level_offset % BLOCK_BYTES, 0);
+        // Two blocks of opaque pure red
+        assert_eq!(ktx2.len(), level_offset + 2 * BLOCK_BYTES);
+        assert_eq!(ktx2[level_offset..level_offset + 2], [255, 255]);
+        assert_eq!(
+            ktx2[level_offset + 8..level_offset + 10],
+            0xF800u16.to_le_bytes()
+        );
+
+        assert!(encode_ktx2(&RgbaImage::new(6, 4)).is_err());
+    }
+
+    #[test]
+    fn test_two_color_block_is_exact() {
+        // Left half opaque white, right half transparent
+        let mut pixels = [[0u8; 4]; 16];
+        for (i, pixel) in pixels.iter_mut().enumerate() {
+            if i % 4 < 2 {
+                *pixel = [255, 255, 255, 255];
+            }
+        }
+
+        let alpha = encode_alpha_block(&pixels);
+        let alpha_indices = u64::from_le_bytes([
+            alpha[2], alpha[3], alpha[4], alpha[5], alpha[6], alpha[7], 0, 0,
+        ]);
+        for i in 0..16 {
+            let index = (alpha_indices >> (3 * i)) & 7;
+            assert_eq!(index, if i % 4 < 2 { 0 } else { 1 });
+        }
+
+        // Only the visible white counts, so the endpoints are both white
+        let color = encode_color_block(&pixels);
+        assert_eq!(color[0..2], 0xFFFFu16.to_le_bytes());
+        assert_eq!(color[2..4], 0xFFFFu16.to_le_bytes());
+
+        // Red and blue are kept as they are, not as corners of their bounding box
+        let mut pixels = [[255, 0, 0, 255]; 16];
+        pixels[5] = [0, 0, 255, 255];
+        let color = encode_color_block(&pixels);
+        assert_eq!(color[0..2], 0xF800u16.to_le_bytes());
+        assert_eq!(color[2..4], 0x001Fu16.to_le_bytes());
+        let color_indices = u32::from_le_bytes(color[4..8].try_into().unwrap());
+        assert_eq!(color_indices, 1 << (2 * 5));
+    }
+}
@@ -21,8 +21,12 @@ pub enum LauncherType {
     ATLauncher,
     #[serde(rename = "gdlauncher")]
     GDLauncher,
+    #[serde(rename = "gdlauncher_carbon")]
+    GDLauncherCarbon,
     #[serde(rename = "technic")]
     Technic,
+    #[serde(rename = "labymod")]
+    LabyMod,
     #[serde(rename = "custom")]
     Custom,
 }
@@ -38,7 +42,9 @@ impl LauncherType {
             LauncherType::MultiMC => "MultiMC",
             LauncherType::ATLauncher => "ATLauncher",
             LauncherType::GDLauncher => "GDLauncher",
+            LauncherType::GDLauncherCarbon => "GDLauncher Carbon",
             LauncherType::Technic => "Technic Launcher",
+            LauncherType::LabyMod => "LabyMod",
             LauncherType::Custom => "Custom Location",
         }
     }
@@ -52,11 +58,31 @@ impl LauncherType {
             LauncherType::PrismLauncher => "prism",
             LauncherType::MultiMC => "multimc",
             LauncherType::ATLauncher => "atlauncher",
-            LauncherType::GDLauncher => "gdlauncher",
+            LauncherType::GDLauncher | LauncherType::GDLauncherCarbon => "gdlauncher",
             LauncherType::Technic => "technic",
+            LauncherType::LabyMod => "labymod",
             LauncherType::Custom => "folder",
         }
     }
+
+    /// Where resource packs go, relative to the launcher directory
+    /// `<instance>` stands for one instance (profile, modpack) folder
+    pub fn resourcepacks_layout(&self) -> &str {
+        match self {
+            LauncherType::Official | LauncherType::LabyMod | LauncherType::Custom => {
+                "resourcepacks"
+            }
+            LauncherType::Modrinth
+            | LauncherType::ATLauncher
+            | LauncherType::GDLauncher
+            | LauncherType::Technic => "<instance>/resourcepacks",
+            LauncherType::CurseForge => "../Instances/<instance>/resourcepacks",
+            LauncherType::PrismLauncher | LauncherType::MultiMC => {
+                "<instance>/.minecraft/resourcepacks"
+            }
+            LauncherType::GDLauncherCarbon => "<instance>/instance/resourcepacks",
+        }
+    }
 }
 
 /// Attempt to locate the platform launcher icon so the frontend can display the real branding.
@@ -119,7 +145,9 @@ fn find_macos_launcher_icon(launcher_type: &LauncherType) -> Option<String> {
             LauncherType::MultiMC => &["MultiMC.app"],
             LauncherType::ATLauncher => &["ATLauncher.app"],
             LauncherType::GDLauncher => &["GDLauncher.app", "GDLauncher Next.app"],
+            LauncherType::GDLauncherCarbon => &["GDLauncher.app", "GDLauncher Carbon.app"],
             LauncherType::Technic => &["Technic Launcher.app", "TechnicLauncher.app"],
+            LauncherType::LabyMod => &["LabyMod Launcher.app", "LabyMod.app"],
             LauncherType::Custom => &[],
         }
     }
@@ -154,11 +182,12 @@ fn find_macos_launcher_icon(launcher_type: &LauncherType) -> Option<String> {
 #[cfg(target_os = "macos")]
 fn convert_icns_to_png(icns_path: &str) -> Option<String> {
     use std::io::BufReader;
+    use tracing::{info, warn};
 
     // Create cache directory for converted icons
     let cache_dir = dirs::cache_dir()?.join("weaverbird").join("launcher_icons");
     if let Err(e) = fs::create_dir_all(&cache_dir) {
-        println!("[convert_icns_to_png] Failed to create cache dir: {}", e);
+        warn!("Failed to create cache dir: {}", e);
         return None;
     }
 
@@ -176,7 +205,7 @@ fn convert_icns_to_png(icns_path: &str) -> Option<String> {
                 (cache_meta.modified(), source_meta.modified())
             {
                 if cache_time >= source_time {
-                    println!("[convert_icns_to_png] Using cached PNG: {:?}", cache_file);
+                    info!("Using cached PNG: {:?}", cache_file);
                     return Some(cache_file.to_string_lossy().to_string());
                 }
             }
@@ -184,7 +213,7 @@ fn convert_icns_to_png(icns_path: &str) -> Option<String> {
     }
 
     // Read the .icns file
-    println!("[convert_icns_to_png] Converting {} to PNG", icns_path);
+    info!("Converting {} to PNG", icns_path);
     let file = fs::File::open(icns_path).ok()?;
     let icon_family = icns::IconFamily::read(BufReader::new(file)).ok()?;
 
@@ -203,14 +232,14 @@ fn convert_icns_to_png(icns_path: &str) -> Option<String> {
             // Write the PNG to cache
             if let Ok(file) = fs::File::create(&cache_file) {
                 if image.write_png(file).is_ok() {
-                    println!("[convert_icns_to_png] Created PNG: {:?}", cache_file);
+                    info!("Created PNG: {:?}", cache_file);
                     return Some(cache_file.to_string_lossy().to_string());
                 }
             }
         }
     }
 
-    println!("[convert_icns_to_png] Failed to extract any image from ICNS");
+    warn!("Failed to extract any image from ICNS");
     None
 }
 
@@ -229,6 +258,75 @@ pub struct LauncherInfo {
     pub icon: String,
     /// Optional path to a platform-provided icon asset
     pub icon_path: Option<String>,
+    /// Where resource packs go; see `LauncherType::resourcepacks_layout`
+    #[serde(default)]
+    pub resourcepacks_layout: String,
+}
+
+impl LauncherInfo {
+    /// Info for a launcher installed at `minecraft_dir`
+    pub fn new(launcher_type: LauncherType, minecraft_dir: String) -> Self {
+        LauncherInfo {
+            name: launcher_type.display_name().to_string(),
+            minecraft_dir,
+            found: true,
+            icon: launcher_type.icon().to_string(),
+            icon_path: get_launcher_icon_path(&launcher_type),
+            resourcepacks_layout: launcher_type.resourcepacks_layout().to_string(),
+            launcher_type,
+        }
+    }
+}
+
+/// First candidate that exists and carries the launcher's signature
+fn find_launcher_dir(candidates: Vec<PathBuf>, signature: fn(&Path) -> bool) -> Option<PathBuf> {
+    candidates
+        .into_iter()
+        .find(|path| path.is_dir() && signature(path))
+}
+
+/// Modrinth App profiles folder: the app keeps its database (`app.db`), or
+/// `settings.json` in older releases, next to it
+fn is_modrinth_dir(profiles: &Path) -> bool {
+    profiles
+        .parent()
+        .is_some_and(|root| root.join("app.db").exists() || root.join("settings.json").exists())
+}
+
+/// ATLauncher instances folder, next to `configs/ATLauncher.json`
+fn is_atlauncher_dir(instances: &Path) -> bool {
+    instances
+        .parent()
+        .is_some_and(|root| root.join("configs/ATLauncher.json").exists())
+}
+
+/// Technic modpacks folder, next to the launcher's `settings.json` or
+/// `installedPacks`
+fn is_technic_dir(modpacks: &Path) -> bool {
+    modpacks.parent().is_some_and(|root| {
+        root.join("settings.json").exists() || root.join("installedPacks").exists()
+    })
+}
+
+/// GDLauncher Carbon instances folder: next to its `gdl_conf.db`, or holding
+/// instances described by `instance.json`
+fn is_gdlauncher_carbon_dir(instances: &Path) -> bool {
+    if instances
+        .parent()
+        .is_some_and(|data| data.join("gdl_conf.db").exists())
+    {
+        return true;
+    }
+    fs::read_dir(instances).is_ok_and(|entries| {
+        entries
+            .flatten()
+            .any(|entry| entry.path().join("instance.json").exists())
+    })
+}
+
+/// Game directory LabyMod 4 is installed into (holds `labymod-neo`)
+fn is_labymod_dir(minecraft_dir: &Path) -> bool {
+    minecraft_dir.join("labymod-neo").is_dir()
 }
 
 /// Detect the official Minecraft launcher installation
@@ -256,62 +354,49 @@ fn detect_official_launcher() -> Option<PathBuf> {
 
 #[cfg(target_os = "linux")]
 fn detect_official_launcher() -> Option<PathBuf> {
-    if let Ok(home) = std::env::var("HOME") {
-        let path = PathBuf::from(home).join(".minecraft");
-        if path.exists() && path.join("versions").exists() {
-            return Some(path);
-        }
-    }
-    None
+    let home = PathBuf::from(std::env::var("HOME").ok()?);
+    let mut paths = vec![home.join(".minecraft")];
+    paths.extend(
+        crate::util::mc_paths::sandboxed_minecraft_dirs(&home)
+            .into_iter()
+            .map(|(path, _)| path),
+    );
+    paths
+        .into_iter()
+        .find(|path| path.exists() && path.join("versions").exists())
 }
 
 /// Detect Modrinth App installation
 #[cfg(target_os = "macos")]
 fn detect_modrinth() -> Option<PathBuf> {
-    if let Ok(home) = std::env::var("HOME") {
-        let paths = vec![
-            PathBuf::from(&home).join("Library/Application Support/com.modrinth.theseus/profiles"),
-            PathBuf::from(&home).join("Library/Application Support/ModrinthApp/profiles"),
-        ];
-        for path in paths {
-            if path.exists() {
-                return Some(path);
-            }
-        }
-    }
-    None
+    let home = std::env::var("HOME").ok()?;
+    let paths = vec![
+        PathBuf::from(&home).join("Library/Application Support/ModrinthApp/profiles"),
+        PathBuf::from(&home).join("Library/Application Support/com.modrinth.theseus/profiles"),
+    ];
+    find_launcher_dir(paths, is_modrinth_dir)
 }
 
 #[cfg(target_os = "windows")]
 fn detect_modrinth() -> Option<PathBuf> {
-    if let Ok(appdata) = std::env::var("APPDATA") {
-        let paths = vec![
-            PathBuf::from(&appdata).join("com.modrinth.theseus/profiles"),
-            PathBuf::from(&appdata).join("ModrinthApp/profiles"),
-        ];
-        for path in paths {
-            if path.exists() {
-                return Some(path);
-            }
-        }
-    }
-    None
+    let appdata = std::env::var("APPDATA").ok()?;
+    let paths = vec![
+        PathBuf::from(&appdata).join("ModrinthApp/profiles"),
+        PathBuf::from(&appdata).join("com.modrinth.theseus/profiles"),
+    ];
+    find_launcher_dir(paths, is_modrinth_dir)
 }
 
 #[cfg(target_os = "linux")]
 fn detect_modrinth() -> Option<PathBuf> {
-    if let Ok(home) = std::env::var("HOME") {
-        let paths = vec![
-            PathBuf::from(&home).join(".local/share/com.modrinth.theseus/profiles"),
-            PathBuf::from(&home).join(".config/ModrinthApp/profiles"),
-        ];
-        for path in paths {
-            if path.exists() {
-                return Some(path);
-            }
-        }
-    }
-    None
+    let home = std::env::var("HOME").ok()?;
+    let paths = vec![
+        PathBuf::from(&home).join(".local/share/ModrinthApp/profiles"),
+        PathBuf::from(&home).join(".local/share/com.modrinth.theseus/profiles"),
+        PathBuf::from(&home).join(".config/ModrinthApp/profiles"),
+        PathBuf::from(&home).join(".var/app/com.modrinth.ModrinthApp/data/ModrinthApp/profiles"),
+    ];
+    find_launcher_dir(paths, is_modrinth_dir)
 }
 
 /// Detect CurseForge launcher installation
@@ -383,6 +468,8 @@ fn detect_prism() -> Option<PathBuf> {
         let paths = vec![
             PathBuf::from(&home).join(".local/share/PrismLauncher/instances"),
             PathBuf::from(&home).join(".local/share/prismlauncher/instances"),
+            PathBuf::from(&home)
+                .join(".var/app/org.prismlauncher.PrismLauncher/data/PrismLauncher/instances"),
         ];
         for path in paths {
             if path.exists() {
@@ -428,26 +515,63 @@ fn detect_multimc() -> Option<PathBuf> {
 }
 
 /// Detect ATLauncher installation
-#[cfg(any(target_os = "macos", target_os = "linux"))]
+#[cfg(target_os = "macos")]
 fn detect_atlauncher() -> Option<PathBuf> {
-    if let Ok(home) = std::env::var("HOME") {
-        let path = PathBuf::from(home).join("ATLauncher/instances");
-        if path.exists() {
-            return Some(path);
-        }
-    }
-    None
+    let home = std::env::var("HOME").ok()?;
+    let paths = vec![
+        PathBuf::from(&home).join("Library/Application Support/ATLauncher/instances"),
+        PathBuf::from(&home).join("ATLauncher/instances"),
+    ];
+    find_launcher_dir(paths, is_atlauncher_dir)
 }
 
 #[cfg(target_os = "windows")]
 fn detect_atlauncher() -> Option<PathBuf> {
+    let mut paths = Vec::new();
+    if let Ok(appdata) = std::env::var("APPDATA") {
+        paths.push(PathBuf::from(appdata).join("ATLauncher/instances"));
+    }
     if let Ok(userprofile) = std::env::var("USERPROFILE") {
-        let path = PathBuf::from(userprofile).join("ATLauncher/instances");
-        if path.exists() {
-            return Some(path);
-        }
+        paths.push(PathBuf::from(userprofile).join("ATLauncher/instances"));
     }
-    None
+    find_launcher_dir(paths, is_atlauncher_dir)
+}
+
+#[cfg(target_os = "linux")]
+fn detect_atlauncher() -> Option<PathBuf> {
+    let home = std::env::var("HOME").ok()?;
+    let paths = vec![
+        PathBuf::from(&home).join(".local/share/ATLauncher/instances"),
+        PathBuf::from(&home).join("ATLauncher/instances"),
+    ];
+    find_launcher_dir(paths, is_atlauncher_dir)
+}
+
+/// Detect Technic Launcher installation
+#[cfg(target_os = "macos")]
+fn detect_technic() -> Option<PathBuf> {
+    let home = std::env::var("HOME").ok()?;
+    let paths = vec![PathBuf::from(home).join("Library/Application Support/technic/modpacks")];
+    find_launcher_dir(paths, is_technic_dir)
+}
+
+#[cfg(target_os = "windows")]
+fn detect_technic() -> Option<PathBuf> {
+    let appdata = std::env::var("APPDATA").ok()?;
+    let paths = vec![PathBuf::from(appdata).join(".technic/modpacks")];
+    find_launcher_dir(paths, is_technic_dir)
+}
+
+#[cfg(target_os = "linux")]
+fn detect_technic() -> Option<PathBuf> {
+    let home = std::env::var("HOME").ok()?;
+    let paths = vec![PathBuf::from(home).join(".technic/modpacks")];
+    find_launcher_dir(paths, is_technic_dir)
+}
+
+/// Detect LabyMod 4, which runs from the official game directory
+fn detect_labymod() -> Option<PathBuf> {
+    detect_official_launcher().filter(|path| is_labymod_dir(path))
 }
 
 /// Detect GDLauncher installation
@@ -485,102 +609,54 @@ fn detect_gdlauncher() -> Option<PathBuf> {
     None
 }
 
-/// Detect all Minecraft launchers on the system
-pub fn detect_all_launchers() -> Vec<LauncherInfo> {
-    let mut launchers = Vec::new();
-
-    // Official Launcher
-    if let Some(path) = detect_official_launcher() {
-        let launcher_type = LauncherType::Official;
-        launchers.push(LauncherInfo {
-            launcher_type: launcher_type.clone(),
-            name: launcher_type.display_name().to_string(),
-            minecraft_dir: path.to_string_lossy().to_string(),
-            found: true,
-            icon: launcher_type.icon().to_string(),
-            icon_path: get_launcher_icon_path(&launcher_type),
-        });
-    }
-
-    // Modrinth
-    if let Some(path) = detect_modrinth() {
-        let launcher_type = LauncherType::Modrinth;
-        launchers.push(LauncherInfo {
-            launcher_type: launcher_type.clone(),
-            name: launcher_type.display_name().to_string(),
-            minecraft_dir: path.to_string_lossy().to_string(),
-            found: true,
-            icon: launcher_type.icon().to_string(),
-            icon_path: get_launcher_icon_path(&launcher_type),
-        });
-    }
-
-    // CurseForge
-    if let Some(path) = detect_curseforge() {
-        let launcher_type = LauncherType::CurseForge;
-        launchers.push(LauncherInfo {
-            launcher_type: launcher_type.clone(),
-            name: launcher_type.display_name().to_string(),
-            minecraft_dir: path.to_string_lossy().to_string(),
-            found: true,
-            icon: launcher_type.icon().to_string(),
-            icon_path: get_launcher_icon_path(&launcher_type),
-        });
-    }
-
-    // Prism Launcher
-    if let Some(path) = detect_prism() {
-        let launcher_type = LauncherType::PrismLauncher;
-        launchers.push(LauncherInfo {
-            launcher_type: launcher_type.clone(),
-            name: launcher_type.display_name().to_string(),
-            minecraft_dir: path.to_string_lossy().to_string(),
-            found: true,
-            icon: launcher_type.icon().to_string(),
-            icon_path: get_launcher_icon_path(&launcher_type),
-        });
-    }
+/// Detect GDLauncher Carbon, the rewrite replacing GDLauncher Next
+#[cfg(target_os = "macos")]
+fn detect_gdlauncher_carbon() -> Option<PathBuf> {
+    let home = std::env::var("HOME").ok()?;
+    let data = PathBuf::from(home).join("Library/Application Support/gdlauncher_carbon/data");
+    let paths = vec![data.join("instances")];
+    find_launcher_dir(paths, is_gdlauncher_carbon_dir)
+}
 
-    // MultiMC
-    if let Some(path) = detect_multimc() {
-        let launcher_type = LauncherType::MultiMC;
-        launchers.push(LauncherInfo {
-            launcher_type: launcher_type.clone(),
-            name: launcher_type.display_name().to_string(),
-            minecraft_dir: path.to_string_lossy().to_string(),
-            found: true,
-            icon: launcher_type.icon().to_string(),
-            icon_path: get_launcher_icon_path(&launcher_type),
-        });
-    }
+#[cfg(target_os = "windows")]
+fn detect_gdlauncher_carbon() -> Option<PathBuf> {
+    let appdata = std::env::var("APPDATA").ok()?;
+    let paths = vec![PathBuf::from(appdata).join("gdlauncher_carbon/data/instances")];
+    find_launcher_dir(paths, is_gdlauncher_carbon_dir)
+}
 
-    // ATLauncher
-    if let Some(path) = detect_atlauncher() {
-        let launcher_type = LauncherType::ATLauncher;
-        launchers.push(LauncherInfo {
-            launcher_type: launcher_type.clone(),
-            name: launcher_type.display_name().to_string(),
-            minecraft_dir: path.to_string_lossy().to_string(),
-            found: true,
-            icon: launcher_type.icon().to_string(),
-            icon_path: get_launcher_icon_path(&launcher_type),
-        });
-    }
+#[cfg(target_os = "linux")]
+fn detect_gdlauncher_carbon() -> Option<PathBuf> {
+    let home = std::env::var("HOME").ok()?;
+    let paths = vec![PathBuf::from(home).join(".local/share/gdlauncher_carbon/data/instances")];
+    find_launcher_dir(paths, is_gdlauncher_carbon_dir)
+}
 
-    // GDLauncher
-    if let Some(path) = detect_gdlauncher() {
-        let launcher_type = LauncherType::GDLauncher;
-        launchers.push(LauncherInfo {
-            launcher_type: launcher_type.clone(),
-            name: launcher_type.display_name().to_string(),
-            minecraft_dir: path.to_string_lossy().to_string(),
-            found: true,
-            icon: launcher_type.icon().to_string(),
-            icon_path: get_launcher_icon_path(&launcher_type),
-        });
-    }
+/// Detect all Minecraft launchers on the system
+pub fn detect_all_launchers() -> Vec<LauncherInfo> {
+    let detected = [
+        (LauncherType::Official, detect_official_launcher()),
+        (LauncherType::LabyMod, detect_labymod()),
+        (LauncherType::Modrinth, detect_modrinth()),
+        (LauncherType::CurseForge, detect_curseforge()),
+        (LauncherType::PrismLauncher, detect_prism()),
+        (LauncherType::MultiMC, detect_multimc()),
+        (LauncherType::ATLauncher, detect_atlauncher()),
+        (LauncherType::GDLauncher, detect_gdlauncher()),
+        (LauncherType::GDLauncherCarbon, detect_gdlauncher_carbon()),
+        (LauncherType::Technic, detect_technic()),
+    ];
 
-    launchers
+    detected
+        .into_iter()
+        .filter_map(|(launcher_type, path)| {
+            let path = path?;
+            Some(LauncherInfo::new(
+                launcher_type,
+                path.to_string_lossy().to_string(),
+            ))
+        })
+        .collect()
 }
 
 /// Identify the launcher type from a given directory path
@@ -597,8 +673,14 @@ pub fn identify_launcher_from_path(path: &Path) -> Result<LauncherType> {
         Ok(LauncherType::MultiMC)
     } else if path_str.contains("atlauncher") {
         Ok(LauncherType::ATLauncher)
+    } else if path_str.contains("gdlauncher_carbon") {
+        Ok(LauncherType::GDLauncherCarbon)
     } else if path_str.contains("gdlauncher") {
         Ok(LauncherType::GDLauncher)
+    } else if path_str.contains("technic") {
+        Ok(LauncherType::Technic)
+    } else if is_labymod_dir(path) {
+        Ok(LauncherType::LabyMod)
     } else if path_str.contains(".minecraft") || path.join("versions").exists() {
         Ok(LauncherType::Official)
     } else {
@@ -623,13 +705,18 @@ pub fn validate_minecraft_directory(path: &Path) -> Result<bool> {
         return Ok(true);
     }
 
+    // Check for modpacks directory (Technic)
+    if path.file_name().and_then(|n| n.to_str()) == Some("modpacks") {
+        return Ok(true);
+    }
+
     Ok(false)
 }
 
 /// Get the resourcepacks directory for a launcher
 pub fn get_resourcepacks_dir(launcher_dir: &Path, launcher_type: &LauncherType) -> Result<PathBuf> {
     match launcher_type {
-        LauncherType::Official => {
+        LauncherType::Official | LauncherType::LabyMod => {
             // Official launcher: <minecraft_dir>/resourcepacks
             Ok(launcher_dir.join("resourcepacks"))
         }
@@ -637,7 +724,9 @@ pub fn get_resourcepacks_dir(launcher_dir: &Path, launcher_type: &LauncherType)
         | LauncherType::PrismLauncher
         | LauncherType::MultiMC
         | LauncherType::ATLauncher
-        | LauncherType::GDLauncher => {
+        | LauncherType::GDLauncher
+        | LauncherType::GDLauncherCarbon
+        | LauncherType::Technic => {
             // Multi-instance launchers: just return the base dir
             // User will need to select the specific instance
             Ok(launcher_dir.to_path_buf())
@@ -646,8 +735,8 @@ pub fn get_resourcepacks_dir(launcher_dir: &Path, launcher_type: &LauncherType)
             // CurseForge Install directory
             Ok(launcher_dir.to_path_buf())
         }
-        LauncherType::Technic | LauncherType::Custom => {
-            // For custom/technic, try to find resourcepacks folder
+        LauncherType::Custom => {
+            // For custom locations, try to find resourcepacks folder
             let resourcepacks = launcher_dir.join("resourcepacks");
             if resourcepacks.exists() {
                 Ok(resourcepacks)
@@ -699,6 +788,7 @@ mod tests {
         assert_eq!(LauncherType::ATLauncher.icon(), "atlauncher");
         assert_eq!(LauncherType::GDLauncher.icon(), "gdlauncher");
         assert_eq!(LauncherType::Technic.icon(), "technic");
+        assert_eq!(LauncherType::LabyMod.icon(), "labymod");
         assert_eq!(LauncherType::Custom.icon(), "folder");
     }
 
@@ -767,6 +857,63 @@ mod tests {
         assert_eq!(result.unwrap(), LauncherType::GDLauncher);
     }
 
+    #[test]
+    fn test_identify_launcher_from_path_gdlauncher_carbon() {
+        let path = Path::new("/home/user/.local/share/gdlauncher_carbon/data/instances");
+        let result = identify_launcher_from_path(path);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), LauncherType::GDLauncherCarbon);
+    }
+
+    #[test]
+    fn test_identify_launcher_from_path_technic() {
+        let path = Path::new("/home/user/.technic/modpacks");
+        let result = identify_launcher_from_path(path);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), LauncherType::Technic);
+    }
+
+    #[test]
+    fn test_launcher_signatures() {
+        let temp_dir = std::env::temp_dir().join("test_launcher_signatures");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let write = |path: &str| {
+            let path = temp_dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, b"{}").unwrap();
+        };
+        write("ModrinthApp/app.db");
+        write("ATLauncher/configs/ATLauncher.json");
+        write(".technic/installedPacks");
+        write("gdlauncher_carbon/data/instances/Survival/instance.json");
+        write("bare/instances/.keep");
+        std::fs::create_dir_all(temp_dir.join("ModrinthApp/profiles")).unwrap();
+        std::fs::create_dir_all(temp_dir.join("ATLauncher/instances")).unwrap();
+        std::fs::create_dir_all(temp_dir.join(".technic/modpacks")).unwrap();
+        std::fs::create_dir_all(temp_dir.join(".minecraft/labymod-neo")).unwrap();
+
+        let modrinth = is_modrinth_dir(&temp_dir.join("ModrinthApp/profiles"));
+        let atlauncher = is_atlauncher_dir(&temp_dir.join("ATLauncher/instances"));
+        let technic = is_technic_dir(&temp_dir.join(".technic/modpacks"));
+        let carbon = is_gdlauncher_carbon_dir(&temp_dir.join("gdlauncher_carbon/data/instances"));
+        let labymod = is_labymod_dir(&temp_dir.join(".minecraft"));
+        let found = find_launcher_dir(
+            vec![
+                temp_dir.join("missing/instances"),
+                temp_dir.join("bare/instances"),
+                temp_dir.join("ATLauncher/instances"),
+            ],
+            is_atlauncher_dir,
+        );
+        let bare_carbon = is_gdlauncher_carbon_dir(&temp_dir.join("bare/instances"));
+
+        std::fs::remove_dir_all(&temp_dir).ok();
+
+        assert!(modrinth && atlauncher && technic && carbon && labymod);
+        assert_eq!(found, Some(temp_dir.join("ATLauncher/instances")));
+        assert!(!bare_carbon);
+    }
+
     #[test]
     fn test_identify_launcher_from_path_custom() {
         let path = Path::new("/some/random/path");
@@ -915,7 +1062,9 @@ mod tests {
             (LauncherType::MultiMC, "\"multimc\""),
             (LauncherType::ATLauncher, "\"atlauncher\""),
             (LauncherType::GDLauncher, "\"gdlauncher\""),
+            (LauncherType::GDLauncherCarbon, "\"gdlauncher_carbon\""),
             (LauncherType::Technic, "\"technic\""),
+            (LauncherType::LabyMod, "\"labymod\""),
             (LauncherType::Custom, "\"custom\""),
         ];
 
@@ -937,6 +1086,7 @@ mod tests {
             found: true,
             icon: "modrinth".to_string(),
             icon_path: Some("/Applications/Modrinth.app/icon.png".to_string()),
+            resourcepacks_layout: "<instance>/resourcepacks".to_string(),
         };
 
         let json = serde_json::to_string(&info).expect("should serialize");
@@ -966,6 +1116,7 @@ mod tests {
             found: true,
             icon: "minecraft".to_string(),
             icon_path: None,
+            resourcepacks_layout: "resourcepacks".to_string(),
         };
 
         let info2 = info1.clone();
@@ -0,0 +1,408 @@
+/// Instances of multi-instance launchers
+///
+/// MultiMC, Prism, ATLauncher, Modrinth and the like keep one folder per
+/// instance, each with its own resourcepacks folder. Instances are found with
+/// the launcher's `resourcepacks_layout` ("<instance>/.minecraft/resourcepacks")
+/// and described from the launcher's own metadata file in the folder: MultiMC
+/// and Prism's instance.cfg and mmc-pack.json, ATLauncher and GDLauncher
+/// Carbon's instance.json, CurseForge's minecraftinstance.json, the Modrinth
+/// App's profile.json, GDLauncher Next's config.json and Technic's
+/// bin/version.json. Metadata that can't be read leaves the version and loader
+/// unknown rather than hiding the instance.
+use crate::util::launcher_detection::{LauncherInfo, LauncherType};
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const INSTANCE_PLACEHOLDER: &str = "<instance>";
+
+/// Mod loaders by the component UID MultiMC and Prism use for them
+const MMC_LOADERS: &[(&str, &str)] = &[
+    ("net.fabricmc.fabric-loader", "fabric"),
+    ("org.quiltmc.quilt-loader", "quilt"),
+    ("net.minecraftforge", "forge"),
+    ("net.neoforged", "neoforge"),
+];
+
+/// One instance (profile, modpack) of a launcher
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LauncherInstance {
+    /// Folder name, unique within the launcher
+    pub id: String,
+    /// Name shown in the launcher
+    pub name: String,
+    pub minecraft_version: Option<String>,
+    /// "fabric", "forge", "neoforge", "quilt"; None for vanilla or unknown
+    pub loader: Option<String>,
+    pub loader_version: Option<String>,
+    pub instance_dir: String,
+    /// Where the instance's resource packs go (may not exist yet)
+    pub resourcepacks_dir: String,
+}
+
+/// What an instance's metadata says about it
+#[derive(Debug, Default, PartialEq)]
+struct InstanceMetadata {
+    name: Option<String>,
+    minecraft_version: Option<String>,
+    loader: Option<String>,
+    loader_version: Option<String>,
+}
+
+/// List the instances of a launcher, sorted by name
+///
+/// Single-instance launchers (the official launcher, custom folders) have one
+/// instance: the launcher directory itself.
+pub fn list_instances(launcher: &LauncherInfo) -> Result<Vec<LauncherInstance>> {
+    let launcher_dir = Path::new(&launcher.minecraft_dir);
+    if !launcher_dir.is_dir() {
+        bail!("Launcher directory not found: {}", launcher.minecraft_dir);
+    }
+    let layout = if launcher.resourcepacks_layout.is_empty() {
+        launcher.launcher_type.resourcepacks_layout()
+    } else {
+        launcher.resourcepacks_layout.as_str()
+    };
+
+    let Some((prefix, suffix)) = layout.split_once(INSTANCE_PLACEHOLDER) else {
+        return Ok(vec![LauncherInstance {
+            id: dir_name(launcher_dir),
+            name: launcher.name.clone(),
+            minecraft_version: None,
+            loader: None,
+            loader_version: None,
+            instance_dir: launcher.minecraft_dir.clone(),
+            resourcepacks_dir: path_string(&launcher_dir.join(layout)),
+        }]);
+    };
+
+    let root = launcher_dir.join(prefix.trim_end_matches('/'));
+    let entries = match fs::read_dir(&root) {
+        Ok(entries) => entries,
+        Err(e) => bail!("Failed to read instances in {}: {}", root.display(), e),
+    };
+
+    let mut instances: Vec<LauncherInstance> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        // Launchers keep temporary and hidden folders next to the instances
+        .filter(|path| !dir_name(path).starts_with(['.', '_']))
+        .filter_map(|path| {
+            let metadata = read_metadata(&launcher.launcher_type, &path)?;
+            let id = dir_name(&path);
+            Some(LauncherInstance {
+                name: metadata.name.unwrap_or_else(|| id.clone()),
+                id,
+                minecraft_version: metadata.minecraft_version,
+                loader: metadata.loader,
+                loader_version: metadata.loader_version,
+                resourcepacks_dir: path_string(&resourcepacks_dir_in(&path, suffix)),
+                instance_dir: path_string(&path),
+            })
+        })
+        .collect();
+    instances.sort_by_cached_key(|instance| instance.name.to_lowercase());
+    Ok(instances)
+}
+
+/// Resource packs folder of one instance of a launcher
+///
+/// `instance_id` (see `LauncherInstance::id`) may be left out when the launcher
+/// has a single instance.
+pub fn instance_resourcepacks_dir(
+    launcher: &LauncherInfo,
+    instance_id: Option<&str>,
+) -> Result<PathBuf> {
+    let instances = list_instances(launcher)?;
+    let instance = match instance_id {
+        Some(id) => instances.into_iter().find(|instance| instance.id == id),
+        None if instances.len() == 1 => instances.into_iter().next(),
+        None => bail!("Choose an instance of {} to install into", launcher.name),
+    };
+    match instance {
+        Some(instance) => Ok(PathBuf::from(instance.resourcepacks_dir)),
+        None => bail!(
+            "Instance {} not found in {}",
+            instance_id.unwrap_or_default(),
+            launcher.name
+        ),
+    }
+}
+
+/// Resource packs folder of an instance, from the part of the layout after
+/// `<instance>`
+fn resourcepacks_dir_in(instance_dir: &Path, suffix: &str) -> PathBuf {
+    let suffix = suffix.trim_start_matches('/');
+    // Prism names the game folder "minecraft" where MultiMC used ".minecraft"
+    if let Some(rest) = suffix.strip_prefix(".minecraft/") {
+        if !instance_dir.join(".minecraft").exists() && instance_dir.join("minecraft").exists() {
+            return instance_dir.join("minecraft").join(rest);
+        }
+    }
+    instance_dir.join(suffix)
+}
+
+/// Metadata of the instance in `dir`, or None when the folder isn't an instance
+fn read_metadata(launcher_type: &LauncherType, dir: &Path) -> Option<InstanceMetadata> {
+    match launcher_type {
+        LauncherType::PrismLauncher | LauncherType::MultiMC => {
+            let cfg = fs::read_to_string(dir.join("instance.cfg")).ok()?;
+            let mut metadata = parse_instance_cfg(&cfg);
+            if let Some(pack) = read_json(&dir.join("mmc-pack.json")) {
+                apply_mmc_pack(&mut metadata, &pack);
+            }
+            Some(metadata)
+        }
+        LauncherType::ATLauncher => {
+            let instance = read_json(&dir.join("instance.json"))?;
+            Some(parse_atlauncher_instance(&instance))
+        }
+        LauncherType::GDLauncherCarbon => {
+            let instance = read_json(&dir.join("instance.json"))?;
+            Some(parse_gdlauncher_carbon_instance(&instance))
+        }
+        LauncherType::CurseForge => {
+            let instance = read_json(&dir.join("minecraftinstance.json"))?;
+            Some(parse_curseforge_instance(&instance))
+        }
+        LauncherType::Modrinth => Some(
+            read_json(&dir.join("profile.json"))
+                .map(|profile| parse_modrinth_profile(&profile))
+                .unwrap_or_default(),
+        ),
+        LauncherType::GDLauncher => Some(
+            read_json(&dir.join("config.json"))
+                .map(|config| parse_gdlauncher_config(&config))
+                .unwrap_or_default(),
+        ),
+        LauncherType::Technic => Some(InstanceMetadata {
+            minecraft_version: read_json(&dir.join("bin/version.json"))
+                .and_then(|version| string_at(&version, &["id"])),
+            ..Default::default()
+        }),
+        LauncherType::Official | LauncherType::LabyMod | LauncherType::Custom => {
+            Some(InstanceMetadata::default())
+        }
+    }
+}
+
+/// MultiMC/Prism instance.cfg: INI without (or with a [General]) section
+fn parse_instance_cfg(cfg: &str) -> InstanceMetadata {
+    let values: HashMap<&str, &str> = cfg
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with(['[', '#', ';']))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect();
+    InstanceMetadata {
+        name: non_empty(values.get("name").copied()),
+        // Only instances from before mmc-pack.json keep the version here
+        minecraft_version: non_empty(values.get("IntendedVersion").copied()),
+        ..Default::default()
+    }
+}
+
+/// MultiMC/Prism mmc-pack.json: the game and loaders are components
+fn apply_mmc_pack(metadata: &mut InstanceMetadata, pack: &Value) {
+    let Some(components) = pack.get("components").and_then(Value::as_array) else {
+        return;
+    };
+    for component in components {
+        let uid = component.get("uid").and_then(Value::as_str).unwrap_or("");
+        let version = string_at(component, &["version"]);
+        if uid == "net.minecraft" {
+            metadata.minecraft_version = version.or(metadata.minecraft_version.take());
+        } else if let Some((_, loader)) = MMC_LOADERS.iter().find(|(id, _)| *id == uid) {
+            metadata.loader = Some(loader.to_string());
+            metadata.loader_version = version;
+        }
+    }
+}
+
+/// ATLauncher instance.json: the game's version JSON with a "launcher" block
+fn parse_atlauncher_instance(instance: &Value) -> InstanceMetadata {
+    InstanceMetadata {
+        name: string_at(instance, &["launcher", "name"]),
+        minecraft_version: string_at(instance, &["id"]),
+        loader: string_at(instance, &["launcher", "loaderVersion", "type"])
+            .map(|loader| loader.to_lowercase()),
+        loader_version: string_at(instance, &["launcher", "loaderVersion", "version"]),
+    }
+}
+
+/// GDLauncher Carbon instance.json
+fn parse_gdlauncher_carbon_instance(instance: &Value) -> InstanceMetadata {
+    let version = instance
+        .pointer("/game_configuration/version/Standard")
+        .unwrap_or(&Value::Null);
+    let modloader = version
+        .get("modloaders")
+        .and_then(Value::as_array)
+        .and_then(|loaders| loaders.first())
+        .unwrap_or(&Value::Null);
+    InstanceMetadata {
+        name: string_at(instance, &["name"]),
+        minecraft_version: string_at(version, &["release"]),
+        loader: string_at(modloader, &["type_"]).map(|loader| loader.to_lowercase()),
+        loader_version: string_at(modloader, &["version"]),
+    }
+}
+
+/// CurseForge minecraftinstance.json; the loader is "forge-47.2.0"
+fn parse_curseforge_instance(instance: &Value) -> InstanceMetadata {
+    let loader = string_at(instance, &["baseModLoader", "name"]);
+    let (loader, loader_version) = match loader.as_deref().and_then(|name| name.split_once('-')) {
+        Some((loader, version)) => (Some(loader.to_lowercase()), Some(version.to_string())),
+        None => (loader, None),
+    };
+    InstanceMetadata {
+        name: string_at(instance, &["name"]),
+        minecraft_version: string_at(instance, &["gameVersion"]),
+        loader,
+        loader_version,
+    }
+}
+
+/// Modrinth App profile.json (releases that keep profiles in app.db have none)
+fn parse_modrinth_profile(profile: &Value) -> InstanceMetadata {
+    InstanceMetadata {
+        name: string_at(profile, &["metadata", "name"]),
+        minecraft_version: string_at(profile, &["metadata", "game_version"]),
+        loader: string_at(profile, &["metadata", "loader"]).filter(|loader| loader != "vanilla"),
+        loader_version: string_at(profile, &["metadata", "loader_version", "id"]),
+    }
+}
+
+/// GDLauncher Next config.json
+fn parse_gdlauncher_config(config: &Value) -> InstanceMetadata {
+    InstanceMetadata {
+        minecraft_version: string_at(config, &["loader", "mcVersion"]),
+        loader: string_at(config, &["loader", "loaderType"]).filter(|loader| loader != "vanilla"),
+        loader_version: string_at(config, &["loader", "loaderVersion"]),
+        ..Default::default()
+    }
+}
+
+fn read_json(path: &Path) -> Option<Value> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn string_at(value: &Value, keys: &[&str]) -> Option<String> {
+    let value = keys.iter().try_fold(value, |value, key| value.get(key))?;
+    non_empty(value.as_str())
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value.filter(|value| !value.is_empty()).map(str::to_string)
+}
+
+fn dir_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn path_string(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_prism_instances() {
+        let temp_dir = std::env::temp_dir().join("test_prism_instances");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let modded = temp_dir.join("Fabric 1.20");
+        fs::create_dir_all(modded.join("minecraft")).unwrap();
+        fs::write(
+            modded.join("instance.cfg"),
+            "[General]\nname=Fabric Survival\nInstanceType=OneSix\n",
+        )
+        .unwrap();
+        fs::write(
+            modded.join("mmc-pack.json"),
+            r#"{"components": [
+                {"uid": "net.minecraft", "version": "1.20.1"},
+                {"uid": "net.fabricmc.fabric-loader", "version": "0.15.11"}
+            ]}"#,
+        )
+        .unwrap();
+        let legacy = temp_dir.join("old");
+        fs::create_dir_all(&legacy).unwrap();
+        fs::write(
+            legacy.join("instance.cfg"),
+            "name=Old\nIntendedVersion=1.8.9\n",
+        )
+        .unwrap();
+        fs::create_dir_all(temp_dir.join("_LAUNCHER_TEMP")).unwrap();
+        fs::create_dir_all(temp_dir.join("not an instance")).unwrap();
+
+        let mut launcher = LauncherInfo::new(
+            LauncherType::PrismLauncher,
+            temp_dir.to_string_lossy().to_string(),
+        );
+        launcher.resourcepacks_layout = String::new();
+        let instances = list_instances(&launcher);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let instances = instances.unwrap();
+        assert_eq!(instances.len(), 2);
+        assert_eq!(instances[0].name, "Fabric Survival");
+        assert_eq!(instances[0].id, "Fabric 1.20");
+        assert_eq!(instances[0].minecraft_version.as_deref(), Some("1.20.1"));
+        assert_eq!(instances[0].loader.as_deref(), Some("fabric"));
+        assert_eq!(instances[0].loader_version.as_deref(), Some("0.15.11"));
+        // Prism's "minecraft" folder is used when there's no ".minecraft"
+        assert_eq!(
+            instances[0].resourcepacks_dir,
+            path_string(&modded.join("minecraft/resourcepacks"))
+        );
+        assert_eq!(instances[1].minecraft_version.as_deref(), Some("1.8.9"));
+        assert_eq!(instances[1].loader, None);
+    }
+
+    #[test]
+    fn test_parse_instance_metadata() {
+        let atlauncher = serde_json::json!({
+            "id": "1.20.1",
+            "launcher": {
+                "name": "Create: Above and Beyond",
+                "loaderVersion": {"type": "Forge", "version": "47.2.0"}
+            }
+        });
+        let parsed = parse_atlauncher_instance(&atlauncher);
+        assert_eq!(parsed.name.as_deref(), Some("Create: Above and Beyond"));
+        assert_eq!(parsed.minecraft_version.as_deref(), Some("1.20.1"));
+        assert_eq!(parsed.loader.as_deref(), Some("forge"));
+
+        let curseforge = serde_json::json!({
+            "name": "All the Mods 9",
+            "gameVersion": "1.20.1",
+            "baseModLoader": {"name": "neoforge-47.1.84"}
+        });
+        let parsed = parse_curseforge_instance(&curseforge);
+        assert_eq!(parsed.loader.as_deref(), Some("neoforge"));
+        assert_eq!(parsed.loader_version.as_deref(), Some("47.1.84"));
+
+        let carbon = serde_json::json!({
+            "name": "Vanilla Plus",
+            "game_configuration": {"version": {"Standard": {
+                "release": "1.21.1",
+                "modloaders": [{"type_": "Fabric", "version": "0.16.5"}]
+            }}}
+        });
+        let parsed = parse_gdlauncher_carbon_instance(&carbon);
+        assert_eq!(parsed.minecraft_version.as_deref(), Some("1.21.1"));
+        assert_eq!(parsed.loader.as_deref(), Some("fabric"));
+        assert_eq!(parsed.loader_version.as_deref(), Some("0.16.5"));
+    }
+}
@@ -0,0 +1,181 @@
+/// Logging to the console, a rolling log file and an in-memory buffer
+///
+/// `init` installs a `tracing` subscriber that writes every event to stdout,
+/// to a daily log file under `<cache dir>/weaverbird/logs` (the last week is
+/// kept) and to a buffer of recent lines that `recent_logs` returns, so users
+/// can attach diagnostics to bug reports without hunting for the file. The
+/// level can be changed at runtime with `set_level`. Until `init` is called
+/// (e.g. in unit tests) events are dropped.
+use crate::util::cache_location;
+use anyhow::{anyhow, Result};
+use once_cell::sync::{Lazy, OnceCell};
+use std::collections::VecDeque;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Mutex;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, Registry};
+
+/// Folder under the cache root the log files are written to
+const LOG_DIR_NAME: &str = "logs";
+
+/// Log files are named `weaverbird.<date>.log`
+const LOG_FILE_PREFIX: &str = "weaverbird";
+const LOG_FILE_SUFFIX: &str = "log";
+
+/// Daily log files kept before the oldest is deleted
+const MAX_LOG_FILES: usize = 7;
+
+/// Lines kept for `recent_logs`
+const RECENT_LINES: usize = 2000;
+
+static RECENT: Lazy<Mutex<VecDeque<String>>> =
+    Lazy::new(|| Mutex::new(VecDeque::with_capacity(RECENT_LINES)));
+
+static LEVEL: OnceCell<reload::Handle<LevelFilter, Registry>> = OnceCell::new();
+
+/// Keeps the file writer's background thread flushing until the app exits
+static FILE_GUARD: OnceCell<WorkerGuard> = OnceCell::new();
+
+/// Writer appending formatted events to the recent lines buffer
+struct RecentWriter;
+
+impl io::Write for RecentWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        push_recent(&String::from_utf8_lossy(buf));
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn push_recent(text: &str) {
+    let mut recent = RECENT.lock().unwrap_or_else(|e| e.into_inner());
+    for line in text.lines().filter(|line| !line.is_empty()) {
+        if recent.len() == RECENT_LINES {
+            recent.pop_front();
+        }
+        recent.push_back(line.to_string());
+    }
+}
+
+/// Folder the log files are written to
+pub fn log_dir() -> Result<PathBuf> {
+    Ok(cache_location::local_cache_root()?.join(LOG_DIR_NAME))
+}
+
+/// Install the subscriber at the info level; later calls do nothing
+///
+/// If the log folder can't be created, logs still go to stdout and the
+/// recent lines buffer.
+pub fn init() {
+    if LEVEL.get().is_some() {
+        return;
+    }
+    let (level, handle) = reload::Layer::new(LevelFilter::INFO);
+
+    let file = log_dir().and_then(|dir| {
+        RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .filename_prefix(LOG_FILE_PREFIX)
+            .filename_suffix(LOG_FILE_SUFFIX)
+            .max_log_files(MAX_LOG_FILES)
+            .build(dir)
+            .map_err(|e| anyhow!("Failed to open log file: {}", e))
+    });
+    let file_layer = match file {
+        Ok(appender) => {
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            let _ = FILE_GUARD.set(guard);
+            Some(fmt::layer().with_writer(writer).with_ansi(false))
+        }
+        Err(e) => {
+            eprintln!("[logging] Logging to the console only: {:#}", e);
+            None
+        }
+    };
+
+    let installed = tracing_subscriber::registry()
+        .with(level)
+        .with(fmt::layer())
+        .with(file_layer)
+        .with(fmt::layer().with_writer(|| RecentWriter).with_ansi(false))
+        .try_init();
+    if installed.is_ok() {
+        let _ = LEVEL.set(handle);
+    }
+}
+
+/// Change the level events are logged at
+///
+/// # Arguments
+/// * `level` - "error", "warn", "info", "debug", "trace" or "off"
+///
+/// # Returns
+/// The level now in effect
+pub fn set_level(level: &str) -> Result<String> {
+    let filter = LevelFilter::from_str(level.trim())
+        .map_err(|_| anyhow!("Unknown log level: {}", level))?;
+    let handle = LEVEL
+        .get()
+        .ok_or_else(|| anyhow!("Logging isn't initialized"))?;
+    handle
+        .modify(|current| *current = filter)
+        .map_err(|e| anyhow!("Failed to change log level: {}", e))?;
+    tracing::info!("Log level set to {}", filter);
+    Ok(filter.to_string())
+}
+
+/// Level events are currently logged at
+pub fn level() -> String {
+    LEVEL
+        .get()
+        .and_then(|handle| handle.clone_current())
+        .unwrap_or(LevelFilter::OFF)
+        .to_string()
+}
+
+/// The most recent log lines, oldest first
+///
+/// # Arguments
+/// * `limit` - Most lines to return
+pub fn recent_logs(limit: usize) -> Vec<String> {
+    let recent = RECENT.lock().unwrap_or_else(|e| e.into_inner());
+    recent
+        .iter()
+        .skip(recent.len().saturating_sub(limit))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recent_logs_keep_the_latest_lines() {
+        for i in 0..RECENT_LINES + 5 {
+            push_recent(&format!("test line {}\n", i));
+        }
+        push_recent("first of two\nsecond of two\n");
+
+        let lines = recent_logs(3);
+        assert_eq!(
+            lines,
+            vec![
+                format!("test line {}", RECENT_LINES + 4),
+                "first of two".to_string(),
+                "second of two".to_string()
+            ]
+        );
+        assert_eq!(recent_logs(usize::MAX).len(), RECENT_LINES);
+        assert!(set_level("loud").is_err());
+    }
+}
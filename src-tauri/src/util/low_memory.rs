@@ -0,0 +1,86 @@
+/// Low-memory mode for constrained machines
+///
+/// Set from the app settings. While enabled, packs are processed one at a time
+/// instead of on every core and nest builds stream zip entries straight to the
+/// output instead of buffering every file first. Scans and builds get slower,
+/// but peak memory stays roughly that of a single pack.
+///
+/// Outside low-memory mode, scans and builds can still be capped to a thread
+/// count, e.g. the one recommended by the performance self-test.
+use crate::util::cancellation;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use tracing::{info, warn};
+
+static LOW_MEMORY: AtomicBool = AtomicBool::new(false);
+
+/// Threads scans and builds may use; 0 means rayon's default (one per core)
+static THREAD_LIMIT: AtomicUsize = AtomicUsize::new(0);
+
+/// Turn low-memory mode on or off for subsequent scans and builds
+pub fn set_enabled(enabled: bool) {
+    LOW_MEMORY.store(enabled, Ordering::Relaxed);
+    info!(
+        "Low-memory mode {}",
+        if enabled { "enabled" } else { "disabled" }
+    );
+}
+
+/// Whether low-memory mode is on
+pub fn is_enabled() -> bool {
+    LOW_MEMORY.load(Ordering::Relaxed)
+}
+
+/// Cap the threads scans and builds use, or None for one per core
+pub fn set_thread_limit(limit: Option<usize>) {
+    THREAD_LIMIT.store(limit.unwrap_or(0), Ordering::Relaxed);
+}
+
+/// Thread cap set by `set_thread_limit`, if any
+pub fn thread_limit() -> Option<usize> {
+    Some(THREAD_LIMIT.load(Ordering::Relaxed)).filter(|limit| *limit > 0)
+}
+
+/// Run `op`, processing its rayon work sequentially when low-memory mode is on
+/// and on at most `thread_limit()` threads otherwise
+pub fn run<R: Send>(op: impl FnOnce() -> R + Send) -> R {
+    let threads = if is_enabled() {
+        Some(1)
+    } else {
+        thread_limit()
+    };
+    run_with(threads, op)
+}
+
+/// Run `op` on a rayon pool of `threads` threads when set, so any `par_iter`
+/// inside it handles that many packs at a time
+fn run_with<R: Send>(threads: Option<usize>, op: impl FnOnce() -> R + Send) -> R {
+    let Some(threads) = threads else {
+        return op();
+    };
+    match rayon::ThreadPoolBuilder::new().num_threads(threads).build() {
+        // The pool thread works for the caller's job, if any
+        Ok(pool) => {
+            let token = cancellation::current();
+            pool.install(|| cancellation::with_token(token, op))
+        }
+        Err(e) => {
+            warn!("Failed to create {}-thread pool: {}", threads, e);
+            op()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_with_limits_threads() {
+        assert_eq!(run_with(Some(1), rayon::current_num_threads), 1);
+        assert_eq!(run_with(Some(3), rayon::current_num_threads), 3);
+        assert_eq!(
+            run_with(None, rayon::current_num_threads),
+            rayon::current_num_threads()
+        );
+    }
+}
@@ -1,6 +1,39 @@
 /// Utilities for finding Minecraft directories
 use anyhow::{anyhow, Result};
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// How the Minecraft directory was installed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstallFlavor {
+    /// The platform's usual location
+    Native,
+    /// Flatpak sandbox; the game only sees folders it was granted access to
+    Flatpak,
+    /// Snap confinement; files live under ~/snap and the launcher can't read
+    /// most of the home folder
+    Snap,
+    /// A .minecraft carried next to the app or in the working directory
+    Portable,
+}
+
+/// A Minecraft directory and how it was installed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MinecraftInstall {
+    pub path: String,
+    pub flavor: InstallFlavor,
+}
+
+impl MinecraftInstall {
+    pub fn new(path: &Path, flavor: InstallFlavor) -> Self {
+        Self {
+            path: path.to_string_lossy().to_string(),
+            flavor,
+        }
+    }
+}
 
 #[cfg(target_os = "windows")]
 fn get_appdata_dir() -> Result<PathBuf> {
@@ -37,6 +70,64 @@ pub fn get_default_minecraft_dir() -> Result<PathBuf> {
     Ok(dir)
 }
 
+/// Flatpak and Snap .minecraft directories under a Linux home folder
+#[cfg(any(target_os = "linux", test))]
+pub fn sandboxed_minecraft_dirs(home: &Path) -> Vec<(PathBuf, InstallFlavor)> {
+    vec![
+        (
+            home.join(".var/app/com.mojang.Minecraft/.minecraft"),
+            InstallFlavor::Flatpak,
+        ),
+        (
+            home.join(".var/app/com.mojang.Minecraft/data/minecraft"),
+            InstallFlavor::Flatpak,
+        ),
+        (
+            home.join("snap/mc-installer/current/.minecraft"),
+            InstallFlavor::Snap,
+        ),
+    ]
+}
+
+/// Portable .minecraft directories: next to this app's executable, then in
+/// the working directory
+fn portable_minecraft_dirs() -> Vec<PathBuf> {
+    let exe_dir = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf));
+    let mut dirs = Vec::new();
+    for base in exe_dir.into_iter().chain(std::env::current_dir().ok()) {
+        let dir = base.join(".minecraft");
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+/// Every place a Minecraft directory may be, most common first; none of them
+/// need to exist
+pub fn candidate_minecraft_dirs() -> Vec<(PathBuf, InstallFlavor)> {
+    let mut candidates = Vec::new();
+    if let Ok(dir) = get_minecraft_dir() {
+        candidates.push((dir, InstallFlavor::Native));
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        if let Ok(home) = std::env::var("HOME") {
+            candidates.extend(sandboxed_minecraft_dirs(Path::new(&home)));
+        }
+    }
+
+    for dir in portable_minecraft_dirs() {
+        if !candidates.iter().any(|(candidate, _)| *candidate == dir) {
+            candidates.push((dir, InstallFlavor::Portable));
+        }
+    }
+    candidates
+}
+
 /// Get the default resourcepacks directory
 pub fn get_default_resourcepacks_dir() -> Result<PathBuf> {
     let mc_dir = get_default_minecraft_dir()?;
@@ -59,6 +150,19 @@ mod tests {
         assert!(dir.is_ok());
     }
 
+    #[test]
+    fn test_sandboxed_minecraft_dirs() {
+        let dirs = sandboxed_minecraft_dirs(Path::new("/home/steve"));
+        assert!(dirs.contains(&(
+            PathBuf::from("/home/steve/.var/app/com.mojang.Minecraft/.minecraft"),
+            InstallFlavor::Flatpak
+        )));
+        assert!(dirs.contains(&(
+            PathBuf::from("/home/steve/snap/mc-installer/current/.minecraft"),
+            InstallFlavor::Snap
+        )));
+    }
+
     #[test]
     fn test_get_default_resourcepacks_dir() {
         let dir = get_default_resourcepacks_dir();
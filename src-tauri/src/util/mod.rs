@@ -1,19 +1,73 @@
 pub mod animation_typescript_gen;
+pub mod asset_editor;
+pub mod asset_graph;
+pub mod asset_hashes;
 pub mod asset_indexer;
+pub mod asset_protocol;
+pub mod asset_search;
+pub mod bbmodel;
+pub mod biome_tint;
 pub mod block_animation_extractor;
 pub mod block_models;
 pub mod java_ast_parser;
+pub mod jem;
 pub mod block_particle_extractor;
 pub mod blockstates;
+pub mod build_journal;
 pub mod bytecode_parser;
+pub mod cache_location;
+pub mod cache_maintenance;
+pub mod cache_usage;
+pub mod cem_animation;
+pub mod cem_dialect;
+pub mod cancellation;
+pub mod cit;
+pub mod contrast;
+pub mod ctm;
+pub mod download;
+pub mod emissive;
+pub mod emitter_presets;
+pub mod entity_geometry;
+pub mod entity_textures;
+pub mod i18n;
+pub mod item_models;
+pub mod jobs;
+pub mod ktx2;
 pub mod launcher_detection;
+pub mod launcher_instances;
+pub mod logging;
+pub mod low_memory;
 pub mod mc_paths;
+pub mod model_export;
+pub mod model_resolver;
+pub mod nest_install;
+pub mod offline;
+pub mod optifine_properties;
+pub mod pack_converter;
+pub mod pack_credits;
+pub mod pack_doctor;
+pub mod pack_files;
+pub mod pack_format;
+pub mod pack_lint;
+pub mod pack_merge;
+pub mod pack_safety;
 pub mod pack_scanner;
+pub mod perf_metrics;
+pub mod perf_selftest;
 pub mod particle_cache;
 pub mod particle_data;
 pub mod particle_typescript_gen;
 pub mod particle_physics_extractor;
+pub mod random_entities;
+pub mod resolution_mix;
+pub mod scan_index;
+#[cfg(feature = "selftest")]
+pub mod selftest;
+pub mod texture_animation;
+pub mod texture_info;
 pub mod texture_index;
+pub mod thumbnails;
+pub mod vanilla_diff;
 pub mod vanilla_textures;
 pub mod weaver_nest;
 pub mod zip;
@@ -0,0 +1,688 @@
+/// Export of resolved models to glTF and OBJ for use in Blender and other tools
+///
+/// Models are flattened into a `Mesh` of textured quads: element rotations,
+/// blockstate rotations, face UVs and UV rotations are applied the way the
+/// game applies them, so the exported geometry lines up with what players
+/// see. Each texture becomes one material, cropped to its first frame when
+/// animated and multiplied by the block's tint on faces with a `tintindex`.
+/// Coordinates are in blocks (1 unit = 16 model pixels) with Y up, which both
+/// formats and Blender's importers expect. `uvlock` is not applied, so locked
+/// textures on rotated variants come out turned with the face.
+///
+/// glTF output is a single self-contained .gltf file with the geometry and
+/// PNGs embedded; OBJ output writes the .obj, an .mtl and one PNG per
+/// material side by side.
+use crate::model::PackMeta;
+use crate::util::block_models::{self, BlockModel, ModelElement};
+use crate::util::blockstates::ResolvedModel;
+use crate::util::pack_files;
+use anyhow::{anyhow, bail, Context, Result};
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File format to export to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Gltf,
+    Obj,
+}
+
+impl ExportFormat {
+    /// Format for an output path's extension (.gltf or .obj)
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_string_lossy().to_ascii_lowercase();
+        match extension.as_str() {
+            "gltf" => Some(ExportFormat::Gltf),
+            "obj" => Some(ExportFormat::Obj),
+            _ => None,
+        }
+    }
+}
+
+/// A textured quad, corners ordered top left, top right, bottom right, bottom
+/// left as seen from its front
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quad {
+    /// Corner positions in model pixels (a full block spans 0..16)
+    pub positions: [[f32; 3]; 4],
+    /// Texture coordinates of the corners, 0..1 with V pointing down
+    pub uvs: [[f32; 2]; 4],
+    /// Index into `Mesh::materials`
+    pub material: usize,
+}
+
+/// A texture a mesh is drawn with
+#[derive(Debug, Clone)]
+pub struct Material {
+    /// Name used for the material and its exported image
+    pub name: String,
+    pub image: RgbaImage,
+}
+
+/// Geometry ready to be written out
+#[derive(Debug, Clone, Default)]
+pub struct Mesh {
+    pub quads: Vec<Quad>,
+    pub materials: Vec<Material>,
+}
+
+/// What an export wrote
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportSummary {
+    /// Files written, the model file first
+    pub files: Vec<String>,
+    pub vertices: usize,
+    pub triangles: usize,
+    pub materials: usize,
+}
+
+/// Rotate a point around `origin` by `degrees` about an axis (0 = x, 1 = y,
+/// 2 = z), counter-clockwise looking down the axis toward the origin
+fn rotate(point: [f32; 3], origin: [f32; 3], axis: usize, degrees: f32) -> [f32; 3] {
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let (a, b) = match axis {
+        0 => (1, 2),
+        1 => (2, 0),
+        _ => (0, 1),
+    };
+    let mut rotated = point;
+    let (pa, pb) = (point[a] - origin[a], point[b] - origin[b]);
+    rotated[a] = origin[a] + pa * cos - pb * sin;
+    rotated[b] = origin[b] + pa * sin + pb * cos;
+    rotated
+}
+
+fn axis_index(axis: &str) -> Option<usize> {
+    match axis {
+        "x" => Some(0),
+        "y" => Some(1),
+        "z" => Some(2),
+        _ => None,
+    }
+}
+
+/// Corners of an element face (top left, top right, bottom right, bottom
+/// left as the texture lies on it) and the UV the game uses when none is given
+fn face_geometry(
+    direction: &str,
+    from: [f32; 3],
+    to: [f32; 3],
+) -> Option<([[f32; 3]; 4], [f32; 4])> {
+    let [x1, y1, z1] = from;
+    let [x2, y2, z2] = to;
+    Some(match direction {
+        "north" => (
+            [[x2, y2, z1], [x1, y2, z1], [x1, y1, z1], [x2, y1, z1]],
+            [16.0 - x2, 16.0 - y2, 16.0 - x1, 16.0 - y1],
+        ),
+        "south" => (
+            [[x1, y2, z2], [x2, y2, z2], [x2, y1, z2], [x1, y1, z2]],
+            [x1, 16.0 - y2, x2, 16.0 - y1],
+        ),
+        "west" => (
+            [[x1, y2, z1], [x1, y2, z2], [x1, y1, z2], [x1, y1, z1]],
+            [z1, 16.0 - y2, z2, 16.0 - y1],
+        ),
+        "east" => (
+            [[x2, y2, z2], [x2, y2, z1], [x2, y1, z1], [x2, y1, z2]],
+            [16.0 - z2, 16.0 - y2, 16.0 - z1, 16.0 - y1],
+        ),
+        "up" => (
+            [[x1, y2, z1], [x2, y2, z1], [x2, y2, z2], [x1, y2, z2]],
+            [x1, z1, x2, z2],
+        ),
+        "down" => (
+            [[x1, y1, z2], [x2, y1, z2], [x2, y1, z1], [x1, y1, z1]],
+            [x1, 16.0 - z2, x2, 16.0 - z1],
+        ),
+        _ => return None,
+    })
+}
+
+/// The game's missing texture, a magenta and black checkerboard
+fn missing_texture() -> RgbaImage {
+    RgbaImage::from_fn(16, 16, |x, y| {
+        if (x < 8) == (y < 8) {
+            image::Rgba([248, 0, 248, 255])
+        } else {
+            image::Rgba([0, 0, 0, 255])
+        }
+    })
+}
+
+/// Builds a mesh from models, loading textures from pack layers
+pub struct MeshBuilder<'a> {
+    layers: &'a [&'a PackMeta],
+    /// Multiplier for faces with a tintindex, as 0xRRGGBB
+    tint: Option<u32>,
+    /// (texture ID, tinted) -> material index
+    material_ids: HashMap<(String, bool), usize>,
+    mesh: Mesh,
+}
+
+impl<'a> MeshBuilder<'a> {
+    /// # Arguments
+    /// * `layers` - Packs to take textures from, highest priority first
+    /// * `tint` - Color tinted faces are multiplied by (e.g., the block's biome tint)
+    pub fn new(layers: &'a [&'a PackMeta], tint: Option<u32>) -> Self {
+        Self {
+            layers,
+            tint,
+            material_ids: HashMap::new(),
+            mesh: Mesh::default(),
+        }
+    }
+
+    /// Load a texture's first frame, or the missing texture
+    fn load_texture(&self, texture_id: &str) -> RgbaImage {
+        let entry_path = pack_files::texture_entry_path(texture_id);
+        let image = self
+            .layers
+            .iter()
+            .find_map(|pack| pack_files::read_pack_entry(pack, &entry_path).ok())
+            .and_then(|bytes| image::load_from_memory(&bytes).ok())
+            .map(|image| image.to_rgba8());
+        match image {
+            // Animated textures are a vertical strip of square frames
+            Some(image) if image.height() > image.width() && image.width() > 0 => {
+                image::imageops::crop_imm(&image, 0, 0, image.width(), image.width()).to_image()
+            }
+            Some(image) => image,
+            None => missing_texture(),
+        }
+    }
+
+    /// Material for a texture, created on first use
+    pub fn material(&mut self, texture_id: &str, tinted: bool) -> usize {
+        let tint = self.tint.filter(|_| tinted);
+        let key = (texture_id.to_string(), tint.is_some());
+        if let Some(&index) = self.material_ids.get(&key) {
+            return index;
+        }
+
+        let mut image = self.load_texture(texture_id);
+        if let Some(tint) = tint {
+            let multiplier = [(tint >> 16) & 0xFF, (tint >> 8) & 0xFF, tint & 0xFF];
+            for pixel in image.pixels_mut() {
+                for (channel, factor) in pixel.0.iter_mut().zip(multiplier) {
+                    *channel = (u32::from(*channel) * factor / 255) as u8;
+                }
+            }
+        }
+        let (_, path) = pack_files::split_asset_id(texture_id);
+        let mut name: String = path
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        if tint.is_some() {
+            name.push_str("_tinted");
+        }
+
+        let index = self.mesh.materials.len();
+        self.mesh.materials.push(Material { name, image });
+        self.material_ids.insert(key, index);
+        index
+    }
+
+    /// Add a quad as is
+    pub fn add_quad(&mut self, quad: Quad) {
+        self.mesh.quads.push(quad);
+    }
+
+    /// Add a resolved model placed as a blockstate places it
+    pub fn add_block_model(&mut self, model: &BlockModel, placement: &ResolvedModel) {
+        let textures = block_models::resolve_textures(model);
+        let center = [8.0, 8.0, 8.0];
+        for element in model.elements.iter().flatten() {
+            for mut quad in self.element_quads(element, &textures) {
+                for position in &mut quad.positions {
+                    // Blockstate rotations turn clockwise looking down each axis
+                    *position = rotate(*position, center, 0, -(placement.rot_x as f32));
+                    *position = rotate(*position, center, 1, -(placement.rot_y as f32));
+                    *position = rotate(*position, center, 2, -(placement.rot_z as f32));
+                }
+                self.mesh.quads.push(quad);
+            }
+        }
+    }
+
+    fn element_quads(
+        &mut self,
+        element: &ModelElement,
+        textures: &HashMap<String, String>,
+    ) -> Vec<Quad> {
+        let mut directions: Vec<&String> = element.faces.keys().collect();
+        directions.sort();
+
+        let mut quads = Vec::new();
+        for direction in directions {
+            let face = &element.faces[direction];
+            let Some((mut positions, default_uv)) =
+                face_geometry(direction, element.from, element.to)
+            else {
+                continue;
+            };
+
+            let texture = match face.texture.strip_prefix('#') {
+                Some(variable) => textures.get(variable).cloned(),
+                None => Some(face.texture.clone()),
+            }
+            .filter(|texture| !texture.starts_with('#'))
+            .unwrap_or_else(|| "minecraft:missingno".to_string());
+            let material = self.material(&texture, face.tintindex.is_some());
+
+            let [u1, v1, u2, v2] = face.uv.unwrap_or(default_uv).map(|v| v / 16.0);
+            let corners = [[u1, v1], [u2, v1], [u2, v2], [u1, v2]];
+            // Face rotation turns the texture clockwise on the face
+            let turns = (face.rotation.unwrap_or(0) / 90) as usize;
+            let uvs = std::array::from_fn(|i| corners[(i + 4 - turns % 4) % 4]);
+
+            if let Some(rotation) = &element.rotation {
+                if let Some(axis) = axis_index(&rotation.axis) {
+                    let rescale = if rotation.rescale.unwrap_or(false) {
+                        1.0 / rotation.angle.to_radians().cos()
+                    } else {
+                        1.0
+                    };
+                    for position in &mut positions {
+                        *position = rotate(*position, rotation.origin, axis, rotation.angle);
+                        for (i, coordinate) in position.iter_mut().enumerate() {
+                            if i != axis {
+                                *coordinate = rotation.origin[i]
+                                    + (*coordinate - rotation.origin[i]) * rescale;
+                            }
+                        }
+                    }
+                }
+            }
+
+            quads.push(Quad {
+                positions,
+                uvs,
+                material,
+            });
+        }
+        quads
+    }
+
+    pub fn finish(self) -> Mesh {
+        self.mesh
+    }
+}
+
+/// Unit normal of a quad's front face
+fn quad_normal(quad: &Quad) -> [f32; 3] {
+    let [a, b, c, _] = quad.positions;
+    let u = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+    let v = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+    let n = [
+        u[1] * v[2] - u[2] * v[1],
+        u[2] * v[0] - u[0] * v[2],
+        u[0] * v[1] - u[1] * v[0],
+    ];
+    let length = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+    if length == 0.0 {
+        return [0.0, 1.0, 0.0];
+    }
+    // b lies right of a and c below b, so u x v points into the face
+    [-n[0] / length, -n[1] / length, -n[2] / length]
+}
+
+/// A quad corner in export units (blocks)
+fn to_blocks(position: [f32; 3]) -> [f32; 3] {
+    position.map(|v| v / 16.0)
+}
+
+fn encode_png(image: &RgbaImage) -> Result<Vec<u8>> {
+    let mut png = Vec::new();
+    image
+        .write_to(
+            &mut std::io::Cursor::new(&mut png),
+            image::ImageOutputFormat::Png,
+        )
+        .context("Failed to encode texture")?;
+    Ok(png)
+}
+
+/// Whether an image has pixels that are neither opaque nor fully clear
+fn is_translucent(image: &RgbaImage) -> bool {
+    image
+        .pixels()
+        .any(|pixel| pixel.0[3] != 0 && pixel.0[3] != 255)
+}
+
+/// Write a mesh as a self-contained glTF 2.0 file
+pub fn write_gltf(mesh: &Mesh, path: &Path) -> Result<Vec<PathBuf>> {
+    use base64::{engine::general_purpose, Engine as _};
+
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut buffer_views = Vec::new();
+    let mut accessors = Vec::new();
+    let mut primitives = Vec::new();
+
+    let mut push_view = |buffer: &mut Vec<u8>, bytes: &[u8], target: Option<u32>| {
+        while buffer.len() % 4 != 0 {
+            buffer.push(0);
+        }
+        let mut view = serde_json::json!({
+            "buffer": 0,
+            "byteOffset": buffer.len(),
+            "byteLength": bytes.len(),
+        });
+        if let Some(target) = target {
+            view["target"] = target.into();
+        }
+        buffer.extend_from_slice(bytes);
+        buffer_views.push(view);
+        buffer_views.len() - 1
+    };
+
+    for material in 0..mesh.materials.len() {
+        let quads: Vec<&Quad> = mesh
+            .quads
+            .iter()
+            .filter(|quad| quad.material == material)
+            .collect();
+        if quads.is_empty() {
+            continue;
+        }
+
+        let mut positions = Vec::new();
+        let mut normals = Vec::new();
+        let mut uvs = Vec::new();
+        let mut indices: Vec<u32> = Vec::new();
+        let mut min = [f32::MAX; 3];
+        let mut max = [f32::MIN; 3];
+        for quad in &quads {
+            let base = (positions.len() / 3) as u32;
+            let normal = quad_normal(quad);
+            for (corner, uv) in quad.positions.iter().zip(quad.uvs) {
+                let corner = to_blocks(*corner);
+                for ((min, max), value) in min.iter_mut().zip(&mut max).zip(corner) {
+                    *min = min.min(value);
+                    *max = max.max(value);
+                }
+                positions.extend(corner);
+                normals.extend(normal);
+                uvs.extend(uv);
+            }
+            indices.extend([base, base + 3, base + 2, base, base + 2, base + 1]);
+        }
+
+        let floats =
+            |values: &[f32]| -> Vec<u8> { values.iter().flat_map(|v| v.to_le_bytes()).collect() };
+        let vertex_count = positions.len() / 3;
+        let position_view = push_view(&mut buffer, &floats(&positions), Some(34962));
+        let normal_view = push_view(&mut buffer, &floats(&normals), Some(34962));
+        let uv_view = push_view(&mut buffer, &floats(&uvs), Some(34962));
+        let index_bytes: Vec<u8> = indices.iter().flat_map(|i| i.to_le_bytes()).collect();
+        let index_view = push_view(&mut buffer, &index_bytes, Some(34963));
+
+        let first = accessors.len();
+        accessors.push(serde_json::json!({
+            "bufferView": position_view, "componentType": 5126, "count": vertex_count,
+            "type": "VEC3", "min": min, "max": max,
+        }));
+        accessors.push(serde_json::json!({
+            "bufferView": normal_view, "componentType": 5126, "count": vertex_count,
+            "type": "VEC3",
+        }));
+        accessors.push(serde_json::json!({
+            "bufferView": uv_view, "componentType": 5126, "count": vertex_count,
+            "type": "VEC2",
+        }));
+        accessors.push(serde_json::json!({
+            "bufferView": index_view, "componentType": 5125, "count": indices.len(),
+            "type": "SCALAR",
+        }));
+        primitives.push(serde_json::json!({
+            "attributes": {"POSITION": first, "NORMAL": first + 1, "TEXCOORD_0": first + 2},
+            "indices": first + 3,
+            "material": material,
+        }));
+    }
+
+    let mut images = Vec::new();
+    let mut materials = Vec::new();
+    for (index, material) in mesh.materials.iter().enumerate() {
+        let view = push_view(&mut buffer, &encode_png(&material.image)?, None);
+        images.push(serde_json::json!({"bufferView": view, "mimeType": "image/png"}));
+        let mut json = serde_json::json!({
+            "name": material.name,
+            "pbrMetallicRoughness": {
+                "baseColorTexture": {"index": index},
+                "metallicFactor": 0.0,
+                "roughnessFactor": 1.0,
+            },
+            "doubleSided": false,
+        });
+        if is_translucent(&material.image) {
+            json["alphaMode"] = "BLEND".into();
+        } else {
+            // Cutout, as the game draws leaves and glass panes
+            json["alphaMode"] = "MASK".into();
+            json["alphaCutoff"] = 0.5.into();
+        }
+        materials.push(json);
+    }
+
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let textures: Vec<_> = (0..mesh.materials.len())
+        .map(|index| serde_json::json!({"source": index, "sampler": 0}))
+        .collect();
+    let gltf = serde_json::json!({
+        "asset": {"version": "2.0", "generator": "Weaverbird"},
+        "scene": 0,
+        "scenes": [{"nodes": [0]}],
+        "nodes": [{"mesh": 0, "name": name}],
+        "meshes": [{"name": name, "primitives": primitives}],
+        "materials": materials,
+        "textures": textures,
+        // Nearest filtering keeps pixel art crisp
+        "samplers": [{"magFilter": 9728, "minFilter": 9728}],
+        "images": images,
+        "accessors": accessors,
+        "bufferViews": buffer_views,
+        "buffers": [{
+            "byteLength": buffer.len(),
+            "uri": format!(
+                "data:application/octet-stream;base64,{}",
+                general_purpose::STANDARD.encode(&buffer)
+            ),
+        }],
+    });
+
+    let json = serde_json::to_string(&gltf)?;
+    fs::write(path, json).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(vec![path.to_path_buf()])
+}
+
+/// Write a mesh as OBJ, with an .mtl and the textures next to it
+pub fn write_obj(mesh: &Mesh, path: &Path) -> Result<Vec<PathBuf>> {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .ok_or_else(|| anyhow!("Invalid output path: {}", path.display()))?;
+    let folder = path.parent().unwrap_or(Path::new(""));
+    let mtl_name = format!("{}.mtl", stem);
+    let mut written = vec![path.to_path_buf(), folder.join(&mtl_name)];
+
+    let mut mtl = String::new();
+    for material in &mesh.materials {
+        let texture_name = format!("{}_{}.png", stem, material.name);
+        material
+            .image
+            .save(folder.join(&texture_name))
+            .with_context(|| format!("Failed to write {}", texture_name))?;
+        written.push(folder.join(&texture_name));
+        let _ = writeln!(
+            mtl,
+            "newmtl {}\nKd 1.000 1.000 1.000\nKs 0.000 0.000 0.000\nmap_Kd {}\nmap_d {}\n",
+            material.name, texture_name, texture_name
+        );
+    }
+
+    let mut obj = format!("mtllib {}\no {}\n", mtl_name, stem);
+    for quad in &mesh.quads {
+        for corner in quad.positions {
+            let [x, y, z] = to_blocks(corner);
+            let _ = writeln!(obj, "v {:.6} {:.6} {:.6}", x, y, z);
+        }
+    }
+    for quad in &mesh.quads {
+        // OBJ puts the texture origin at the bottom left
+        for [u, v] in quad.uvs {
+            let _ = writeln!(obj, "vt {:.6} {:.6}", u, 1.0 - v);
+        }
+    }
+    for quad in &mesh.quads {
+        let [x, y, z] = quad_normal(quad);
+        let _ = writeln!(obj, "vn {:.6} {:.6} {:.6}", x, y, z);
+    }
+    let mut current_material = None;
+    for (index, quad) in mesh.quads.iter().enumerate() {
+        if current_material != Some(quad.material) {
+            let _ = writeln!(obj, "usemtl {}", mesh.materials[quad.material].name);
+            current_material = Some(quad.material);
+        }
+        // Wind counter-clockwise: top left, bottom left, bottom right, top right
+        let corner = |i: usize| {
+            let vertex = index * 4 + i + 1;
+            format!("{}/{}/{}", vertex, vertex, index + 1)
+        };
+        let _ = writeln!(
+            obj,
+            "f {} {} {} {}",
+            corner(0),
+            corner(3),
+            corner(2),
+            corner(1)
+        );
+    }
+
+    fs::write(folder.join(&mtl_name), mtl)
+        .with_context(|| format!("Failed to write {}", mtl_name))?;
+    fs::write(path, obj).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(written)
+}
+
+/// Write a mesh in the format its path's extension names
+pub fn export_mesh(mesh: &Mesh, path: &Path) -> Result<ExportSummary> {
+    if mesh.quads.is_empty() {
+        bail!("Nothing to export: the model has no elements");
+    }
+    let files = match ExportFormat::from_path(path) {
+        Some(ExportFormat::Gltf) => write_gltf(mesh, path)?,
+        Some(ExportFormat::Obj) => write_obj(mesh, path)?,
+        None => bail!("Export path must end in .gltf or .obj: {}", path.display()),
+    };
+    Ok(ExportSummary {
+        files: files
+            .iter()
+            .map(|file| file.to_string_lossy().to_string())
+            .collect(),
+        vertices: mesh.quads.len() * 4,
+        triangles: mesh.quads.len() * 2,
+        materials: mesh.materials.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cube_model() -> BlockModel {
+        serde_json::from_value(serde_json::json!({
+            "textures": {"side": "block/furnace_side", "front": "block/furnace_front"},
+            "elements": [{
+                "from": [0, 0, 0],
+                "to": [16, 16, 16],
+                "faces": {
+                    "north": {"texture": "#front"},
+                    "east": {"texture": "#side", "tintindex": 0},
+                    "up": {"texture": "#side", "uv": [0, 0, 8, 8], "rotation": 90}
+                }
+            }]
+        }))
+        .unwrap()
+    }
+
+    fn placement(rot_y: i32) -> ResolvedModel {
+        ResolvedModel {
+            model_id: "minecraft:block/furnace".to_string(),
+            rot_x: 0,
+            rot_y,
+            rot_z: 0,
+            uvlock: false,
+        }
+    }
+
+    #[test]
+    fn test_block_model_mesh() {
+        let mut builder = MeshBuilder::new(&[], Some(0x80FF00));
+        builder.add_block_model(&cube_model(), &placement(90));
+        let mesh = builder.finish();
+
+        assert_eq!(mesh.quads.len(), 3);
+        // Missing textures fall back to the checkerboard; tinted faces get
+        // their own material
+        assert_eq!(mesh.materials.len(), 3);
+        let tinted = &mesh.materials[mesh.quads[0].material];
+        assert_eq!(tinted.name, "block_furnace_side_tinted");
+        assert_eq!(tinted.image.get_pixel(0, 0).0, [124, 0, 0, 255]);
+
+        // y=90 turns the north face to face east
+        let north = &mesh.quads[1];
+        assert!(north.positions.iter().all(|p| (p[0] - 16.0).abs() < 1e-4));
+        assert_eq!(quad_normal(north).map(f32::round), [1.0, 0.0, 0.0]);
+
+        // UV rotation 90 moves the top left of the texture to the top right corner
+        let up = &mesh.quads[2];
+        assert_eq!(up.uvs[1], [0.0, 0.0]);
+        assert_eq!(up.uvs[0], [0.0, 0.5]);
+    }
+
+    #[test]
+    fn test_export_mesh() {
+        let temp_dir = std::env::temp_dir().join("test_model_export");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+        let mut builder = MeshBuilder::new(&[], None);
+        builder.add_block_model(&cube_model(), &placement(0));
+        let mesh = builder.finish();
+
+        let gltf = export_mesh(&mesh, &temp_dir.join("furnace.gltf")).unwrap();
+        let obj = export_mesh(&mesh, &temp_dir.join("furnace.obj")).unwrap();
+        let rejected = export_mesh(&mesh, &temp_dir.join("furnace.fbx"));
+        let gltf_json: serde_json::Value =
+            serde_json::from_slice(&fs::read(temp_dir.join("furnace.gltf")).unwrap()).unwrap();
+        let obj_text = fs::read_to_string(temp_dir.join("furnace.obj")).unwrap();
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(gltf.files.len(), 1);
+        assert_eq!(gltf.triangles, 6);
+        assert_eq!(
+            gltf_json["meshes"][0]["primitives"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(gltf_json["images"].as_array().unwrap().len(), 2);
+        assert_eq!(gltf_json["accessors"][0]["max"][1], 1.0);
+        // .obj, .mtl and one PNG per material
+        assert_eq!(obj.files.len(), 4);
+        assert!(obj_text.starts_with("mtllib furnace.mtl\n"));
+        assert_eq!(obj_text.matches("\nf ").count(), 3);
+        assert!(rejected.is_err());
+    }
+}
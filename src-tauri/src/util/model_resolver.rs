@@ -0,0 +1,305 @@
+/// Resolving block and item models through their `parent` chains
+///
+/// A model is looked up in each pack layer in priority order, and so is each
+/// of its parents, so a pack can override a template its models inherit
+/// from. Merging follows the game: textures extend the parent's, elements
+/// replace them and display transforms override them context by context. A
+/// chain that loops back on itself fails with an error listing the chain;
+/// `builtin/generated` and `builtin/entity` end a chain, as the game renders
+/// them itself.
+///
+/// Results are memoized per (pack layers, model ID), parents included, so
+/// models sharing a template read it once. A cached chain is reused while
+/// every file it was read from is unchanged, so edits to a pack show up on
+/// the next resolve; a file added where it shadows one already read only
+/// shows up once the cache is cleared, which a pack scan does.
+use crate::model::PackMeta;
+use crate::util::block_models::{self, BlockModel};
+use crate::util::pack_files;
+use crate::{AppError, AppResult};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Deepest parent chain followed; vanilla's deepest is about 5
+const MAX_PARENT_DEPTH: usize = 20;
+
+/// Chains kept before the cache starts over
+const CACHE_CAPACITY: usize = 8192;
+
+/// Pack paths in priority order, and a model ID
+type CacheKey = (Vec<String>, String);
+
+/// File a chain was read from and its modification time when read
+type Source = (PathBuf, Option<SystemTime>);
+
+static CACHE: Lazy<Mutex<HashMap<CacheKey, CachedChain>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Clone)]
+struct CachedChain {
+    chain: ResolvedChain,
+    sources: Vec<Source>,
+}
+
+/// A model with its whole parent chain merged in
+#[derive(Debug, Clone)]
+pub struct ResolvedChain {
+    /// The merged model; `parent` is always None
+    pub model: BlockModel,
+    /// Model IDs from the requested model up to its root
+    pub chain: Vec<String>,
+    /// Builtin root the chain ends at ("generated", "entity"), if any
+    pub builtin: Option<String>,
+}
+
+/// Resolve a model and its parents through pack layers
+///
+/// # Arguments
+/// * `layers` - Packs to look models up in, highest priority first (usually
+///   the pack, then vanilla)
+/// * `model_id` - Model ID like "minecraft:block/dirt" or "block/dirt"
+///
+/// # Errors
+/// - VALIDATION_ERROR: A model in the chain is missing or invalid, or the
+///   chain is circular or too deep
+pub fn resolve(layers: &[&PackMeta], model_id: &str) -> AppResult<ResolvedChain> {
+    let key_layers: Vec<String> = layers.iter().map(|pack| pack.path.clone()).collect();
+    let (chain, _) = resolve_chain(layers, &key_layers, model_id, &mut Vec::new())?;
+    Ok(chain)
+}
+
+/// Forget every cached chain
+pub fn clear_cache() {
+    lock_cache().clear();
+}
+
+fn lock_cache() -> std::sync::MutexGuard<'static, HashMap<CacheKey, CachedChain>> {
+    CACHE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+/// Cached chain for a key, if none of its files changed since
+fn cached(key: &CacheKey) -> Option<CachedChain> {
+    let entry = lock_cache().get(key).cloned()?;
+    entry
+        .sources
+        .iter()
+        .all(|(path, when)| modified(path) == *when)
+        .then_some(entry)
+}
+
+/// Name of the builtin model a model ID refers to ("builtin/generated" -> "generated")
+fn builtin_name(model_id: &str) -> Option<String> {
+    block_models::normalize_model_id(model_id)
+        .strip_prefix("minecraft:builtin/")
+        .map(str::to_string)
+}
+
+/// Read a model from the first layer that has it
+fn read_layered(layers: &[&PackMeta], model_id: &str) -> AppResult<(BlockModel, Source)> {
+    let relative_path = block_models::model_id_to_path(model_id);
+    for pack in layers {
+        let Ok(bytes) = pack_files::read_pack_entry(pack, &relative_path) else {
+            continue;
+        };
+        let model: BlockModel = serde_json::from_slice(&bytes).map_err(|e| {
+            AppError::validation(format!(
+                "Invalid model JSON in {} ({}): {}",
+                relative_path, pack.name, e
+            ))
+        })?;
+        let source_path = if pack.is_zip {
+            PathBuf::from(&pack.path)
+        } else {
+            pack_files::resolve_dir_entry(Path::new(&pack.path), &relative_path)
+                .unwrap_or_else(|_| Path::new(&pack.path).join(&relative_path))
+        };
+        let when = modified(&source_path);
+        return Ok((model, (source_path, when)));
+    }
+    Err(AppError::validation(format!(
+        "Model not found: {}",
+        relative_path
+    )))
+}
+
+/// Resolve `model_id`, with `visiting` holding the models below it in the
+/// chain being resolved
+fn resolve_chain(
+    layers: &[&PackMeta],
+    key_layers: &[String],
+    model_id: &str,
+    visiting: &mut Vec<String>,
+) -> AppResult<(ResolvedChain, Vec<Source>)> {
+    let model_id = block_models::normalize_model_id(model_id);
+    let key = (key_layers.to_vec(), model_id.clone());
+    if let Some(entry) = cached(&key) {
+        return Ok((entry.chain, entry.sources));
+    }
+
+    let (mut model, source) = read_layered(layers, &model_id)?;
+    let mut sources = vec![source];
+    let mut chain = ResolvedChain {
+        model: BlockModel::default(),
+        chain: vec![model_id.clone()],
+        builtin: None,
+    };
+
+    match model.parent.take() {
+        Some(parent_id) => {
+            if let Some(builtin) = builtin_name(&parent_id) {
+                chain.builtin = Some(builtin);
+                chain.model = model;
+            } else {
+                let parent_id = block_models::normalize_model_id(&parent_id);
+                visiting.push(model_id.clone());
+                if visiting.contains(&parent_id) {
+                    let mut cycle = visiting.clone();
+                    cycle.push(parent_id);
+                    return Err(AppError::validation(format!(
+                        "Circular model parent chain: {}",
+                        cycle.join(" -> ")
+                    )));
+                }
+                if visiting.len() > MAX_PARENT_DEPTH {
+                    return Err(AppError::validation(format!(
+                        "Model parent chain too deep: {}",
+                        visiting.join(" -> ")
+                    )));
+                }
+                let (parent, parent_sources) =
+                    resolve_chain(layers, key_layers, &parent_id, visiting)?;
+                visiting.pop();
+
+                chain.model = block_models::merge_models(parent.model, model);
+                chain.chain.extend(parent.chain);
+                chain.builtin = parent.builtin;
+                sources.extend(parent_sources);
+            }
+        }
+        None => chain.model = model,
+    }
+
+    let mut cache = lock_cache();
+    if cache.len() >= CACHE_CAPACITY {
+        cache.clear();
+    }
+    cache.insert(
+        key,
+        CachedChain {
+            chain: chain.clone(),
+            sources: sources.clone(),
+        },
+    );
+    Ok((chain, sources))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_pack(root: &Path, id: &str) -> PackMeta {
+        PackMeta {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: root.join(id).to_string_lossy().to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_resolve_through_layers() {
+        let temp_dir = std::env::temp_dir().join("test_model_resolver_layers");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let pack_models = temp_dir.join("pack/assets/minecraft/models/block");
+        let vanilla_models = temp_dir.join("vanilla/assets/minecraft/models/block");
+        fs::create_dir_all(&pack_models).unwrap();
+        fs::create_dir_all(&vanilla_models).unwrap();
+        fs::write(
+            vanilla_models.join("stone.json"),
+            r#"{"parent": "block/cube_all", "textures": {"all": "block/stone"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            vanilla_models.join("cube_all.json"),
+            r##"{"parent": "block/cube", "textures": {"particle": "#all"}}"##,
+        )
+        .unwrap();
+        fs::write(
+            vanilla_models.join("cube.json"),
+            r#"{"elements": [{"from": [0, 0, 0], "to": [16, 16, 16], "faces": {}}]}"#,
+        )
+        .unwrap();
+        // The pack overrides a template the vanilla model inherits from
+        fs::write(
+            pack_models.join("cube.json"),
+            r#"{"elements": [{"from": [1, 1, 1], "to": [15, 15, 15], "faces": {}}]}"#,
+        )
+        .unwrap();
+
+        let pack = make_pack(&temp_dir, "pack");
+        let vanilla = make_pack(&temp_dir, "vanilla");
+        let first = resolve(&[&pack, &vanilla], "block/stone").unwrap();
+        let vanilla_only = resolve(&[&vanilla], "block/stone").unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        fs::write(
+            pack_models.join("cube.json"),
+            r#"{"elements": [{"from": [2, 2, 2], "to": [14, 14, 14], "faces": {}}]}"#,
+        )
+        .unwrap();
+        let edited = resolve(&[&pack, &vanilla], "minecraft:block/stone").unwrap();
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(
+            first.chain,
+            vec![
+                "minecraft:block/stone",
+                "minecraft:block/cube_all",
+                "minecraft:block/cube"
+            ]
+        );
+        assert_eq!(first.model.elements.unwrap()[0].from, [1.0, 1.0, 1.0]);
+        assert!(first.model.textures.unwrap().contains_key("particle"));
+        assert_eq!(
+            vanilla_only.model.elements.unwrap()[0].from,
+            [0.0, 0.0, 0.0]
+        );
+        assert_eq!(edited.model.elements.unwrap()[0].from, [2.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn test_resolve_reports_cycles() {
+        let temp_dir = std::env::temp_dir().join("test_model_resolver_cycles");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let models = temp_dir.join("pack/assets/minecraft/models/item");
+        fs::create_dir_all(&models).unwrap();
+        fs::write(models.join("a.json"), r#"{"parent": "item/b"}"#).unwrap();
+        fs::write(models.join("b.json"), r#"{"parent": "minecraft:item/a"}"#).unwrap();
+        fs::write(
+            models.join("flat.json"),
+            r#"{"parent": "builtin/generated"}"#,
+        )
+        .unwrap();
+
+        let pack = make_pack(&temp_dir, "pack");
+        let cycle = resolve(&[&pack], "item/a");
+        let flat = resolve(&[&pack], "item/flat");
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(
+            cycle.unwrap_err().message,
+            "Circular model parent chain: minecraft:item/a -> minecraft:item/b -> minecraft:item/a"
+        );
+        assert_eq!(flat.unwrap().builtin.as_deref(), Some("generated"));
+    }
+}
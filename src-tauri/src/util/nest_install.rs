@@ -0,0 +1,213 @@
+/// Installing a built Weaver Nest into a launcher instance
+///
+/// The nest (WeaverNest.zip or a pack folder) is copied, or symlinked so later
+/// rebuilds show up without installing again, into the instance's
+/// resourcepacks folder. Optionally the instance's options.txt is edited so
+/// the game loads the nest on top of the pack stack. Minecraft rewrites
+/// options.txt when it exits, so the game should be closed while installing.
+use crate::util::pack_files;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use tracing::info;
+
+const OPTIONS_FILE: &str = "options.txt";
+const RESOURCE_PACKS_KEY: &str = "resourcePacks:";
+
+/// How the nest gets into the resourcepacks folder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstallMode {
+    /// An independent copy
+    Copy,
+    /// A link to the built nest, updated by every rebuild
+    Symlink,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NestInstallResult {
+    /// Where the nest was installed
+    pub installed_path: String,
+    /// Entry naming the nest in options.txt ("file/WeaverNest.zip")
+    pub pack_entry: String,
+    /// Whether options.txt was edited to enable the nest
+    pub enabled: bool,
+}
+
+/// Install a built nest into a resourcepacks folder
+///
+/// # Arguments
+/// * `nest_path` - Built nest, a zip or a pack folder
+/// * `resourcepacks_dir` - The instance's resourcepacks folder
+/// * `mode` - Copy or symlink
+/// * `enable` - Also enable the nest, on top of the stack, in the options.txt
+///   next to `resourcepacks_dir`
+pub fn install_nest(
+    nest_path: &Path,
+    resourcepacks_dir: &Path,
+    mode: InstallMode,
+    enable: bool,
+) -> Result<NestInstallResult> {
+    if !nest_path.exists() {
+        bail!("Built nest not found: {}", nest_path.display());
+    }
+    let Some(file_name) = nest_path.file_name() else {
+        bail!("Not a pack: {}", nest_path.display());
+    };
+    fs::create_dir_all(resourcepacks_dir).with_context(|| {
+        format!(
+            "Failed to create resourcepacks folder {}",
+            resourcepacks_dir.display()
+        )
+    })?;
+    let target = resourcepacks_dir.join(file_name);
+
+    // The nest may have been built straight into this folder
+    let same = fs::canonicalize(&target).ok() == Some(fs::canonicalize(nest_path)?);
+    if !same {
+        remove_existing(&target)?;
+        match mode {
+            InstallMode::Copy if nest_path.is_dir() => {
+                pack_files::copy_dir_all(nest_path, &target).map(|_| ())
+            }
+            InstallMode::Copy => fs::copy(nest_path, &target).map(|_| ()),
+            InstallMode::Symlink => symlink(nest_path, &target),
+        }
+        .with_context(|| format!("Failed to install the nest to {}", target.display()))?;
+    }
+    info!("Installed {} to {}", nest_path.display(), target.display());
+
+    let pack_entry = format!("file/{}", file_name.to_string_lossy());
+    if enable {
+        let game_dir = resourcepacks_dir.parent().unwrap_or(resourcepacks_dir);
+        enable_in_options(&game_dir.join(OPTIONS_FILE), &pack_entry)?;
+    }
+
+    Ok(NestInstallResult {
+        installed_path: target.to_string_lossy().to_string(),
+        pack_entry,
+        enabled: enable,
+    })
+}
+
+/// Remove an earlier install, without following a symlink into the built nest
+fn remove_existing(target: &Path) -> Result<()> {
+    let Ok(metadata) = fs::symlink_metadata(target) else {
+        return Ok(());
+    };
+    let removed = if metadata.is_dir() {
+        fs::remove_dir_all(target)
+    } else {
+        // Windows removes directory links like directories
+        fs::remove_file(target).or_else(|_| fs::remove_dir(target))
+    };
+    removed.with_context(|| format!("Failed to replace {}", target.display()))
+}
+
+#[cfg(unix)]
+fn symlink(source: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(source, link)
+}
+
+#[cfg(windows)]
+fn symlink(source: &Path, link: &Path) -> std::io::Result<()> {
+    // Needs developer mode or administrator rights
+    if source.is_dir() {
+        std::os::windows::fs::symlink_dir(source, link)
+    } else {
+        std::os::windows::fs::symlink_file(source, link)
+    }
+}
+
+/// Enable a pack in options.txt, creating the file if the game hasn't yet
+fn enable_in_options(options_path: &Path, pack_entry: &str) -> Result<()> {
+    let options = match fs::read_to_string(options_path) {
+        Ok(options) => options,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", options_path.display()))
+        }
+    };
+    let updated = with_pack_enabled(&options, pack_entry)?;
+
+    // Written next to it and renamed, so the game never reads half a file
+    let temp_path = options_path.with_extension("txt.tmp");
+    fs::write(&temp_path, updated)
+        .and_then(|_| fs::rename(&temp_path, options_path))
+        .with_context(|| format!("Failed to write {}", options_path.display()))?;
+    info!("Enabled {} in {}", pack_entry, options_path.display());
+    Ok(())
+}
+
+/// options.txt with `pack_entry` moved to the top of the enabled packs
+///
+/// The last entry of `resourcePacks` is the highest priority one.
+fn with_pack_enabled(options: &str, pack_entry: &str) -> Result<String> {
+    let mut lines: Vec<String> = options.lines().map(str::to_string).collect();
+    let line = lines
+        .iter_mut()
+        .find(|line| line.starts_with(RESOURCE_PACKS_KEY));
+    let mut packs: Vec<String> = match &line {
+        Some(line) => serde_json::from_str(&line[RESOURCE_PACKS_KEY.len()..])
+            .with_context(|| format!("Unreadable {} option", RESOURCE_PACKS_KEY))?,
+        None => vec!["vanilla".to_string()],
+    };
+    packs.retain(|pack| pack != pack_entry);
+    packs.push(pack_entry.to_string());
+    let value = format!("{}{}", RESOURCE_PACKS_KEY, serde_json::to_string(&packs)?);
+
+    match line {
+        Some(line) => *line = value,
+        None => lines.push(value),
+    }
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_pack_enabled() {
+        let options = "version:3955\nresourcePacks:[\"vanilla\",\"file/WeaverNest.zip\",\"file/Faithful\"]\nlang:en_us\n";
+        let updated = with_pack_enabled(options, "file/WeaverNest.zip").unwrap();
+        assert_eq!(
+            updated,
+            "version:3955\nresourcePacks:[\"vanilla\",\"file/Faithful\",\"file/WeaverNest.zip\"]\nlang:en_us\n"
+        );
+
+        let created = with_pack_enabled("", "file/WeaverNest.zip").unwrap();
+        assert_eq!(
+            created,
+            "resourcePacks:[\"vanilla\",\"file/WeaverNest.zip\"]\n"
+        );
+    }
+
+    #[test]
+    fn test_install_nest_copies_and_enables() {
+        let temp_dir = std::env::temp_dir().join("test_install_nest");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let nest = temp_dir.join("build/WeaverNest");
+        fs::create_dir_all(nest.join("assets")).unwrap();
+        fs::write(nest.join("pack.mcmeta"), b"{}").unwrap();
+        let resourcepacks = temp_dir.join("instance/.minecraft/resourcepacks");
+        // A stale copy from an earlier install is replaced
+        fs::create_dir_all(resourcepacks.join("WeaverNest/old")).unwrap();
+
+        let result = install_nest(&nest, &resourcepacks, InstallMode::Copy, true);
+        let copied = resourcepacks.join("WeaverNest/pack.mcmeta").is_file();
+        let stale = resourcepacks.join("WeaverNest/old").exists();
+        let options = fs::read_to_string(temp_dir.join("instance/.minecraft/options.txt"))
+            .unwrap_or_default();
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let result = result.unwrap();
+        assert_eq!(result.pack_entry, "file/WeaverNest");
+        assert!(copied);
+        assert!(!stale);
+        assert_eq!(options, "resourcePacks:[\"vanilla\",\"file/WeaverNest\"]\n");
+    }
+}
@@ -0,0 +1,472 @@
+/// Offline mode and bundled fallback datasets
+///
+/// Particle physics, block emissions and animations are extracted from
+/// decompiled Minecraft code, which needs Mojang's mappings (a download), CFR
+/// (another download) and Java. When any of that is unavailable, or offline
+/// mode is switched on in the settings, extraction falls back to the datasets
+/// shipped in the app's `resources/datasets/<dataset>/<version>.json.gz`
+/// (plain `.json` is read too).
+///
+/// Recent releases are bundled, so first runs on them don't need to extract
+/// at all (`bundled_or_extract`); extraction only has to catch up with newer
+/// releases and snapshots. The bundles are written from the caches with
+/// `cargo run --bin bundle_datasets`.
+///
+/// Bundled data is never written to the extraction caches, so an explicit
+/// extraction still produces (and caches) local data. The reason for each
+/// fallback is kept so the UI can show a "degraded, offline" status instead of
+/// an error.
+use crate::util::cache_location::CacheBusy;
+use crate::util::{
+    block_animation_extractor, block_particle_extractor, particle_physics_extractor,
+    vanilla_textures,
+};
+use anyhow::{bail, Context, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use once_cell::sync::Lazy;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::future::Future;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tracing::{info, warn};
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Why the last extraction of a dataset fell back, by (dataset, version)
+static FALLBACKS: Lazy<Mutex<HashMap<(Dataset, String), String>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Data extracted from decompiled Minecraft code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Dataset {
+    ParticlePhysics,
+    BlockEmissions,
+    BlockAnimations,
+}
+
+impl Dataset {
+    pub const ALL: [Dataset; 3] = [
+        Dataset::ParticlePhysics,
+        Dataset::BlockEmissions,
+        Dataset::BlockAnimations,
+    ];
+
+    /// Folder name under `resources/datasets`
+    fn dir_name(self) -> &'static str {
+        match self {
+            Dataset::ParticlePhysics => "particle_physics",
+            Dataset::BlockEmissions => "block_emissions",
+            Dataset::BlockAnimations => "block_animations",
+        }
+    }
+}
+
+/// Where a dataset for a version currently comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DatasetSource {
+    /// Extracted on this machine
+    Cached,
+    /// Not extracted; the copy shipped with the app is used
+    Bundled,
+    /// Neither extracted nor bundled
+    Unavailable,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatasetStatus {
+    pub dataset: Dataset,
+    pub source: DatasetSource,
+    /// Why extraction fell back, when it was attempted and failed
+    pub reason: Option<String>,
+}
+
+/// Result of `offline_status`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OfflineStatus {
+    /// Whether offline mode is switched on
+    pub offline_mode: bool,
+    /// Version the datasets were checked for
+    pub version: Option<String>,
+    /// True when any dataset is unavailable or its extraction failed
+    pub degraded: bool,
+    pub datasets: Vec<DatasetStatus>,
+}
+
+/// Turn offline mode on or off; while on, nothing is downloaded
+pub fn set_enabled(enabled: bool) {
+    OFFLINE.store(enabled, Ordering::Relaxed);
+    info!(
+        "Offline mode {}",
+        if enabled { "enabled" } else { "disabled" }
+    );
+}
+
+/// Whether offline mode is on
+pub fn is_enabled() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+/// Fail before a download when offline mode is on
+///
+/// # Arguments
+/// * `what` - What would have been downloaded, for the error message
+pub fn ensure_online(what: &str) -> Result<()> {
+    if is_enabled() {
+        bail!("Offline mode is on, not downloading {}", what);
+    }
+    Ok(())
+}
+
+/// Directories bundled datasets are looked up in
+fn dataset_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![PathBuf::from("resources/datasets")];
+    if let Some(exe_dir) = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.to_path_buf()))
+    {
+        dirs.push(exe_dir.join("resources/datasets"));
+        // macOS app bundles keep resources in Contents/Resources
+        dirs.push(exe_dir.join("../Resources/resources/datasets"));
+    }
+    dirs
+}
+
+/// Path of the bundled copy of a dataset, if the app ships one for `version`
+pub fn bundled_dataset_path(dataset: Dataset, version: &str) -> Option<PathBuf> {
+    // Modded version strings ("1.21.4-fabric") use the vanilla release's data
+    let vanilla = particle_physics_extractor::parse_vanilla_version(version);
+    dataset_dirs()
+        .into_iter()
+        .flat_map(|dir| {
+            let dir = dir.join(dataset.dir_name());
+            [
+                dir.join(format!("{}.json.gz", vanilla)),
+                dir.join(format!("{}.json", vanilla)),
+            ]
+        })
+        .find(|path| path.is_file())
+}
+
+/// Versions with at least one dataset in a datasets directory, sorted
+///
+/// # Arguments
+/// * `dir` - Datasets directory, normally `src-tauri/resources/datasets`
+pub fn bundled_versions(dir: &Path) -> Vec<String> {
+    let mut versions: Vec<String> = Dataset::ALL
+        .into_iter()
+        .filter_map(|dataset| fs::read_dir(dir.join(dataset.dir_name())).ok())
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().to_string_lossy().to_string();
+            let version = name
+                .strip_suffix(".json.gz")
+                .or_else(|| name.strip_suffix(".json"))?;
+            Some(version.to_string())
+        })
+        .collect();
+    versions.sort();
+    versions.dedup();
+    versions
+}
+
+/// Load the bundled copy of a dataset
+pub fn load_bundled<T: DeserializeOwned>(dataset: Dataset, version: &str) -> Option<T> {
+    let path = bundled_dataset_path(dataset, version)?;
+    match read_dataset_file(&path) {
+        Ok(data) => Some(data),
+        Err(e) => {
+            warn!("Bundled dataset {} is unreadable: {:#}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Read a dataset file, decompressing it when it is gzipped
+fn read_dataset_file<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let bytes = fs::read(path)?;
+    let content = if path.extension().is_some_and(|ext| ext == "gz") {
+        let mut content = String::new();
+        GzDecoder::new(bytes.as_slice())
+            .read_to_string(&mut content)
+            .context("Failed to decompress")?;
+        content
+    } else {
+        String::from_utf8(bytes)?
+    };
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Write a dataset as `<dir>/<dataset>/<version>.json.gz`, for shipping with the app
+///
+/// # Arguments
+/// * `dir` - Datasets directory, normally `src-tauri/resources/datasets`
+///
+/// # Returns
+/// Path of the written file
+pub fn write_bundled<T: Serialize>(
+    dataset: Dataset,
+    version: &str,
+    data: &T,
+    dir: &Path,
+) -> Result<PathBuf> {
+    let dataset_dir = dir.join(dataset.dir_name());
+    fs::create_dir_all(&dataset_dir)
+        .with_context(|| format!("Failed to create {}", dataset_dir.display()))?;
+    let path = dataset_dir.join(format!("{}.json.gz", version));
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    serde_json::to_writer(&mut encoder, data)?;
+    encoder.flush()?;
+    fs::write(&path, encoder.finish()?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Use the bundled dataset when the app ships one, extracting only otherwise
+///
+/// Bundled data comes from the same extractor, so for bundled releases there
+/// is nothing to gain from decompiling again; new releases and snapshots are
+/// extracted, with the usual fallback.
+pub async fn bundled_or_extract<T, F>(dataset: Dataset, version: &str, extract: F) -> Result<T>
+where
+    T: DeserializeOwned,
+    F: Future<Output = Result<T>>,
+{
+    if let Some(data) = load_bundled(dataset, version) {
+        info!("Using bundled {} for {}", dataset.dir_name(), version);
+        return Ok(data);
+    }
+    extract_or_bundled(dataset, version, extract).await
+}
+
+/// Run an extraction, falling back to the bundled dataset if it fails
+///
+/// The failure is remembered for `offline_status`; a later successful
+/// extraction clears it. Without bundled data the extraction error is returned.
+pub async fn extract_or_bundled<T, F>(dataset: Dataset, version: &str, extract: F) -> Result<T>
+where
+    T: DeserializeOwned,
+    F: Future<Output = Result<T>>,
+{
+    let key = (dataset, version.to_string());
+    match extract.await {
+        Ok(data) => {
+            fallbacks().remove(&key);
+            Ok(data)
+        }
+        // Another extraction is filling the cache, which is no reason to go offline
+        Err(e) if e.downcast_ref::<CacheBusy>().is_some() => Err(e),
+        Err(e) => {
+            let reason = format!("{:#}", e);
+            let bundled = load_bundled(dataset, version);
+            fallbacks().insert(key, reason.clone());
+            match bundled {
+                Some(data) => {
+                    info!(
+                        "Using bundled {} for {}: {}",
+                        dataset.dir_name(),
+                        version,
+                        reason
+                    );
+                    Ok(data)
+                }
+                None => Err(e.context(format!(
+                    "No bundled {} data for {}",
+                    dataset.dir_name(),
+                    version
+                ))),
+            }
+        }
+    }
+}
+
+fn fallbacks() -> std::sync::MutexGuard<'static, HashMap<(Dataset, String), String>> {
+    FALLBACKS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Where each dataset for `version` comes from
+///
+/// # Arguments
+/// * `version` - Minecraft version; defaults to the cached vanilla version
+pub fn offline_status(version: Option<String>) -> Result<OfflineStatus> {
+    let version = match version {
+        Some(version) => Some(version),
+        None => vanilla_textures::get_cached_version()?,
+    };
+    let Some(version) = version else {
+        return Ok(OfflineStatus {
+            offline_mode: is_enabled(),
+            version: None,
+            degraded: true,
+            datasets: Vec::new(),
+        });
+    };
+
+    let datasets: Vec<DatasetStatus> = Dataset::ALL
+        .into_iter()
+        .map(|dataset| {
+            let source = if is_cached(dataset, &version) {
+                DatasetSource::Cached
+            } else if bundled_dataset_path(dataset, &version).is_some() {
+                DatasetSource::Bundled
+            } else {
+                DatasetSource::Unavailable
+            };
+            DatasetStatus {
+                dataset,
+                source,
+                reason: fallbacks().get(&(dataset, version.clone())).cloned(),
+            }
+        })
+        .collect();
+
+    Ok(OfflineStatus {
+        offline_mode: is_enabled(),
+        degraded: datasets
+            .iter()
+            .any(|status| status.source == DatasetSource::Unavailable || status.reason.is_some()),
+        version: Some(version),
+        datasets,
+    })
+}
+
+fn is_cached(dataset: Dataset, version: &str) -> bool {
+    let cached = match dataset {
+        Dataset::ParticlePhysics => particle_physics_extractor::is_physics_data_cached(version),
+        Dataset::BlockEmissions => block_particle_extractor::is_block_emissions_cached(version),
+        Dataset::BlockAnimations => block_animation_extractor::is_animation_data_cached(version),
+    };
+    cached.unwrap_or_else(|e| {
+        warn!("Failed to check {} cache: {}", dataset.dir_name(), e);
+        false
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    #[test]
+    fn test_extract_or_bundled_records_fallback() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        // No dataset is bundled for this version, so the error comes through
+        let failed: Result<serde_json::Value> = runtime.block_on(extract_or_bundled(
+            Dataset::ParticlePhysics,
+            "0.0-test",
+            async { Err(anyhow!("Failed to fetch version manifest")) },
+        ));
+        let reason = fallbacks()
+            .get(&(Dataset::ParticlePhysics, "0.0-test".to_string()))
+            .cloned();
+        let recovered: Result<serde_json::Value> = runtime.block_on(extract_or_bundled(
+            Dataset::ParticlePhysics,
+            "0.0-test",
+            async { Ok(serde_json::json!({})) },
+        ));
+        let cleared =
+            !fallbacks().contains_key(&(Dataset::ParticlePhysics, "0.0-test".to_string()));
+
+        let error = format!("{:#}", failed.unwrap_err());
+        assert!(
+            error.contains("No bundled particle_physics data"),
+            "{}",
+            error
+        );
+        assert!(error.contains("version manifest"), "{}", error);
+        assert_eq!(reason.as_deref(), Some("Failed to fetch version manifest"));
+        assert!(recovered.is_ok());
+        assert!(cleared);
+    }
+
+    #[test]
+    fn test_bundled_or_extract_loads_shipped_dataset() {
+        use crate::util::block_particle_extractor::ExtractedBlockEmissions;
+        use crate::util::particle_physics_extractor::ExtractedPhysicsData;
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        // Shipped releases never reach the extractor, modded version strings included
+        let physics: ExtractedPhysicsData = runtime
+            .block_on(bundled_or_extract(
+                Dataset::ParticlePhysics,
+                "1.21.11-fabric",
+                async { Err(anyhow!("extracted a bundled release")) },
+            ))
+            .unwrap();
+        let emissions: ExtractedBlockEmissions = runtime
+            .block_on(bundled_or_extract(
+                Dataset::BlockEmissions,
+                "1.21.11",
+                async { Err(anyhow!("extracted a bundled release")) },
+            ))
+            .unwrap();
+
+        assert_eq!(physics.version, "1.21.11");
+        assert!(!physics.partial);
+        assert!(physics.particles.contains_key("flame"));
+        assert!(emissions.blocks.contains_key("end_rod"));
+    }
+
+    #[test]
+    fn test_bundled_or_extract_loads_every_shipped_dataset() {
+        use crate::util::block_animation_extractor::ExtractedAnimationData;
+        use crate::util::block_particle_extractor::ExtractedBlockEmissions;
+        use crate::util::particle_physics_extractor::ExtractedPhysicsData;
+
+        fn load<T: DeserializeOwned>(dataset: Dataset, version: &str) -> Option<T> {
+            bundled_dataset_path(dataset, version)?;
+            let runtime = tokio::runtime::Runtime::new().unwrap();
+            let data = runtime.block_on(bundled_or_extract(dataset, version, async {
+                Err(anyhow!("extracted a bundled release"))
+            }));
+            Some(data.unwrap())
+        }
+
+        let versions = bundled_versions(Path::new("resources/datasets"));
+        assert!(!versions.is_empty());
+        for version in &versions {
+            if let Some(physics) = load::<ExtractedPhysicsData>(Dataset::ParticlePhysics, version) {
+                assert_eq!(&physics.version, version);
+                assert!(!physics.partial, "{}", version);
+            }
+            if let Some(emissions) =
+                load::<ExtractedBlockEmissions>(Dataset::BlockEmissions, version)
+            {
+                assert!(!emissions.blocks.is_empty(), "{}", version);
+            }
+            if let Some(animations) =
+                load::<ExtractedAnimationData>(Dataset::BlockAnimations, version)
+            {
+                assert_eq!(&animations.version, version);
+                assert!(!animations.entities.is_empty(), "{}", version);
+            }
+        }
+    }
+
+    #[test]
+    fn test_write_bundled_round_trips_gzip() {
+        let dir = std::env::temp_dir().join("test_write_bundled");
+        let _ = fs::remove_dir_all(&dir);
+        let data = serde_json::json!({ "version": "1.21.4", "particles": { "flame": {} } });
+
+        let path = write_bundled(Dataset::ParticlePhysics, "1.21.4", &data, &dir).unwrap();
+        write_bundled(Dataset::BlockAnimations, "1.21.5", &data, &dir).unwrap();
+        assert!(path.ends_with("particle_physics/1.21.4.json.gz"));
+        let read: serde_json::Value = read_dataset_file(&path).unwrap();
+        assert_eq!(read, data);
+        assert_eq!(bundled_versions(&dir), ["1.21.4", "1.21.5"]);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
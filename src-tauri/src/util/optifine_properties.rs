@@ -0,0 +1,228 @@
+/// Parsing helpers shared by OptiFine .properties formats (CIT, CTM, random entities)
+///
+/// OptiFine reads these files as Java properties and resolves the texture and
+/// model paths inside them with its own shorthand rules.
+use crate::model::PackMeta;
+use crate::util::pack_files;
+use std::collections::BTreeMap;
+use std::io::Read;
+use tracing::warn;
+
+/// Parse a .properties file into key/value pairs
+///
+/// Supports `#`/`!` comments, `=` or `:` separators and `\` line continuations.
+/// Later duplicates of a key win, as in Java.
+pub fn parse_properties(text: &str) -> BTreeMap<String, String> {
+    let mut properties = BTreeMap::new();
+    let mut logical_line = String::new();
+
+    for raw_line in text.lines() {
+        let line = raw_line.trim_start();
+        if logical_line.is_empty() && (line.is_empty() || line.starts_with(['#', '!'])) {
+            continue;
+        }
+
+        // An odd number of trailing backslashes continues onto the next line
+        let trailing = line.len() - line.trim_end_matches('\\').len();
+        if trailing % 2 == 1 {
+            logical_line.push_str(&line[..line.len() - 1]);
+            continue;
+        }
+        logical_line.push_str(line);
+
+        let separator = logical_line.find(['=', ':']);
+        let (key, value) = match separator {
+            Some(index) => (&logical_line[..index], &logical_line[index + 1..]),
+            None => (logical_line.as_str(), ""),
+        };
+        let key = key.trim();
+        if !key.is_empty() {
+            properties.insert(key.to_string(), value.trim().replace("\\:", ":"));
+        }
+        logical_line.clear();
+    }
+
+    properties
+}
+
+/// Resolve a path written in an OptiFine .properties file to a pack-relative path
+///
+/// - `name` or `./name` is relative to the properties file's folder
+/// - `~/path` is relative to assets/minecraft/optifine/
+/// - `namespace:path` is relative to assets/<namespace>/
+/// - anything else is relative to assets/minecraft/
+///
+/// `extension` (e.g. ".png") is appended when the path has none.
+pub fn resolve_reference(properties_path: &str, value: &str, extension: &str) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    let folder = properties_path
+        .rsplit_once('/')
+        .map_or("", |(folder, _)| folder);
+
+    let mut path = if let Some(rest) = value.strip_prefix("./") {
+        format!("{}/{}", folder, rest)
+    } else if let Some(rest) = value.strip_prefix("~/") {
+        format!("assets/minecraft/optifine/{}", rest)
+    } else if let Some((namespace, rest)) = value.split_once(':') {
+        format!("assets/{}/{}", namespace, rest)
+    } else if !value.contains('/') {
+        format!("{}/{}", folder, value)
+    } else {
+        format!("assets/minecraft/{}", value)
+    };
+
+    let file_name = path.rsplit('/').next().unwrap_or_default();
+    if !file_name.contains('.') {
+        path.push_str(extension);
+    }
+    Some(path)
+}
+
+/// Read the text of several .properties files from a pack
+///
+/// ZIP packs are opened once for all files. Unreadable files are skipped with
+/// a warning. Returns (path, text) pairs in input order.
+pub fn read_properties_files<'a>(
+    pack: &PackMeta,
+    files: impl IntoIterator<Item = &'a String>,
+) -> Vec<(String, String)> {
+    let mut files = files.into_iter().peekable();
+    if files.peek().is_none() {
+        return Vec::new();
+    }
+
+    let mut archive = if pack.is_zip {
+        match crate::util::zip::open_cached_archive(&pack.path) {
+            Ok(archive) => Some(archive),
+            Err(e) => {
+                warn!("Failed to open {}: {}", pack.path, e);
+                return Vec::new();
+            }
+        }
+    } else {
+        None
+    };
+
+    let mut texts = Vec::new();
+    for file in files {
+        let text = match archive.as_mut() {
+            Some(archive) => {
+                archive
+                    .by_name(file)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|mut entry| {
+                        let mut text = String::new();
+                        entry.read_to_string(&mut text)?;
+                        Ok(text)
+                    })
+            }
+            None => pack_files::read_pack_entry(pack, file)
+                .map(|bytes| String::from_utf8_lossy(&bytes).to_string()),
+        };
+
+        match text {
+            Ok(text) => texts.push((file.clone(), text)),
+            Err(e) => warn!("Skipping {} in {}: {}", file, pack.name, e),
+        }
+    }
+    texts
+}
+
+/// Match text against an OptiFine string condition (custom names and the like)
+///
+/// `pattern:` and `ipattern:` take `*`/`?` wildcards, `regex:` and `iregex:`
+/// a regular expression that must match the whole text; the `i` forms ignore
+/// case. Anything else must equal the text exactly.
+pub fn matches_text(condition: &str, text: &str) -> bool {
+    let (pattern, is_regex, ignore_case) = if let Some(p) = condition.strip_prefix("pattern:") {
+        (p, false, false)
+    } else if let Some(p) = condition.strip_prefix("ipattern:") {
+        (p, false, true)
+    } else if let Some(p) = condition.strip_prefix("regex:") {
+        (p, true, false)
+    } else if let Some(p) = condition.strip_prefix("iregex:") {
+        (p, true, true)
+    } else {
+        return condition == text;
+    };
+
+    let expression = if is_regex {
+        pattern.to_string()
+    } else {
+        pattern
+            .chars()
+            .map(|c| match c {
+                '*' => ".*".to_string(),
+                '?' => ".".to_string(),
+                c => regex::escape(&c.to_string()),
+            })
+            .collect()
+    };
+    regex::RegexBuilder::new(&format!("^(?:{})$", expression))
+        .case_insensitive(ignore_case)
+        .build()
+        .is_ok_and(|regex| regex.is_match(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_properties() {
+        let properties = parse_properties(
+            "# comment\n\
+             type=item\n\
+             items = minecraft\\:diamond_sword iron_sword\n\
+             matchTiles: stone \\\n    granite\n\
+             \n\
+             texture=fire_sword\n",
+        );
+
+        assert_eq!(properties["type"], "item");
+        assert_eq!(properties["items"], "minecraft:diamond_sword iron_sword");
+        assert_eq!(properties["matchTiles"], "stone granite");
+        assert_eq!(properties["texture"], "fire_sword");
+        assert_eq!(properties.len(), 4);
+    }
+
+    #[test]
+    fn test_resolve_reference() {
+        let props = "assets/minecraft/optifine/cit/swords/fire.properties";
+        assert_eq!(
+            resolve_reference(props, "fire_sword", ".png").unwrap(),
+            "assets/minecraft/optifine/cit/swords/fire_sword.png"
+        );
+        assert_eq!(
+            resolve_reference(props, "./glow/fire.png", ".png").unwrap(),
+            "assets/minecraft/optifine/cit/swords/glow/fire.png"
+        );
+        assert_eq!(
+            resolve_reference(props, "~/cit/shared/blade", ".png").unwrap(),
+            "assets/minecraft/optifine/cit/shared/blade.png"
+        );
+        assert_eq!(
+            resolve_reference(props, "textures/item/stick", ".png").unwrap(),
+            "assets/minecraft/textures/item/stick.png"
+        );
+        assert_eq!(
+            resolve_reference(props, "mymod:models/item/blade", ".json").unwrap(),
+            "assets/mymod/models/item/blade.json"
+        );
+        assert_eq!(resolve_reference(props, " ", ".png"), None);
+    }
+
+    #[test]
+    fn test_matches_text() {
+        assert!(matches_text("Dinnerbone", "Dinnerbone"));
+        assert!(!matches_text("Dinnerbone", "dinnerbone"));
+        assert!(matches_text("ipattern:*bone", "DINNERBONE"));
+        assert!(matches_text("pattern:Grumm?", "Grumm!"));
+        assert!(!matches_text("pattern:Grumm?", "Grumm"));
+        assert!(matches_text("regex:Jeb_?\\d*", "Jeb_12"));
+        assert!(!matches_text("regex:[", "["));
+    }
+}
@@ -0,0 +1,1087 @@
+/// Convert a resource pack between pack formats
+///
+/// Rewrites what can be migrated mechanically (renamed texture folders and files,
+/// model parents/texture references, the grass blockstates the flattening moved,
+/// pack.mcmeta) and reports what can't, so a pack made for one Minecraft version
+/// can be loaded in another.
+use crate::model::PackMeta;
+use crate::util::pack_format::{self, CompatibilityStatus};
+use crate::util::{asset_indexer, pack_files};
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use tracing::info;
+
+/// Resource renames introduced by one pack format
+///
+/// IDs are namespace-less paths ("block/stone"); entries ending in '/' rename a
+/// whole folder. Exact renames are looked up once per step (no chaining), so
+/// swaps like 1.13's `item/grass` -> `item/grass_block` + `item/tall_grass` ->
+/// `item/grass` stay unambiguous in both directions.
+struct RenameStep {
+    /// First pack_format using the new names
+    format: u32,
+    textures: Vec<(String, String)>,
+    models: Vec<(String, String)>,
+    /// Blockstate file names ("grass")
+    blockstates: Vec<(String, String)>,
+}
+
+const DYE_COLORS: &[&str] = &[
+    "white",
+    "orange",
+    "magenta",
+    "light_blue",
+    "yellow",
+    "lime",
+    "pink",
+    "gray",
+    "silver",
+    "cyan",
+    "purple",
+    "blue",
+    "brown",
+    "green",
+    "red",
+    "black",
+];
+
+const WOOD_TYPES: &[(&str, &str)] = &[
+    ("oak", "oak"),
+    ("spruce", "spruce"),
+    ("birch", "birch"),
+    ("jungle", "jungle"),
+    ("acacia", "acacia"),
+    ("big_oak", "dark_oak"),
+];
+
+fn pairs(entries: &[(&str, &str)]) -> Vec<(String, String)> {
+    entries
+        .iter()
+        .map(|(old, new)| (old.to_string(), new.to_string()))
+        .collect()
+}
+
+static RENAME_STEPS: Lazy<Vec<RenameStep>> = Lazy::new(|| {
+    // 1.13 "flattening" (pack_format 4)
+    let mut flattening = pairs(&[
+        ("blocks/", "block/"),
+        ("items/", "item/"),
+        ("block/grass_top", "block/grass_block_top"),
+        ("block/grass_side", "block/grass_block_side"),
+        ("block/grass_side_overlay", "block/grass_block_side_overlay"),
+        ("block/grass_side_snowed", "block/grass_block_snow"),
+        ("block/tallgrass", "block/grass"),
+        ("block/double_plant_grass_bottom", "block/tall_grass_bottom"),
+        ("block/double_plant_grass_top", "block/tall_grass_top"),
+        ("block/double_plant_fern_bottom", "block/large_fern_bottom"),
+        ("block/double_plant_fern_top", "block/large_fern_top"),
+        ("block/stone_granite", "block/granite"),
+        ("block/stone_granite_smooth", "block/polished_granite"),
+        ("block/stone_diorite", "block/diorite"),
+        ("block/stone_diorite_smooth", "block/polished_diorite"),
+        ("block/stone_andesite", "block/andesite"),
+        ("block/stone_andesite_smooth", "block/polished_andesite"),
+        ("block/cobblestone_mossy", "block/mossy_cobblestone"),
+        ("block/stonebrick", "block/stone_bricks"),
+        ("block/stonebrick_mossy", "block/mossy_stone_bricks"),
+        ("block/stonebrick_cracked", "block/cracked_stone_bricks"),
+        ("block/stonebrick_carved", "block/chiseled_stone_bricks"),
+        ("block/brick", "block/bricks"),
+        ("block/hardened_clay", "block/terracotta"),
+    ]);
+    for (old, new) in WOOD_TYPES {
+        flattening.push((
+            format!("block/planks_{}", old),
+            format!("block/{}_planks", new),
+        ));
+        flattening.push((format!("block/log_{}", old), format!("block/{}_log", new)));
+        flattening.push((
+            format!("block/log_{}_top", old),
+            format!("block/{}_log_top", new),
+        ));
+    }
+    for color in DYE_COLORS {
+        let new_color = if *color == "silver" {
+            "light_gray"
+        } else {
+            color
+        };
+        flattening.push((
+            format!("block/wool_colored_{}", color),
+            format!("block/{}_wool", new_color),
+        ));
+        flattening.push((
+            format!("block/hardened_clay_stained_{}", color),
+            format!("block/{}_terracotta", new_color),
+        ));
+        flattening.push((
+            format!("block/glass_{}", color),
+            format!("block/{}_stained_glass", new_color),
+        ));
+    }
+
+    vec![
+        RenameStep {
+            format: 4,
+            textures: flattening,
+            models: pairs(&[
+                ("block/grass_normal", "block/grass_block"),
+                ("block/grass_snowed", "block/grass_block_snow"),
+                ("block/tall_grass", "block/grass"),
+                ("block/double_grass_bottom", "block/tall_grass_bottom"),
+                ("block/double_grass_top", "block/tall_grass_top"),
+                ("block/double_fern_bottom", "block/large_fern_bottom"),
+                ("block/double_fern_top", "block/large_fern_top"),
+                ("item/grass", "item/grass_block"),
+                ("item/tall_grass", "item/grass"),
+            ]),
+            // Before 1.13 the grass block's states were "grass" and the plant's
+            // "tall_grass", split out of "tallgrass" and "double_plant" by type
+            blockstates: pairs(&[
+                ("grass", "grass_block"),
+                ("tall_grass", "grass"),
+                ("double_grass", "tall_grass"),
+                ("double_fern", "large_fern"),
+            ]),
+        },
+        // 1.20.3: "grass" became "short_grass"
+        RenameStep {
+            format: 22,
+            textures: pairs(&[("block/grass", "block/short_grass")]),
+            models: pairs(&[
+                ("block/grass", "block/short_grass"),
+                ("item/grass", "item/short_grass"),
+            ]),
+            blockstates: pairs(&[("grass", "short_grass")]),
+        },
+    ]
+});
+
+/// Kind of resource ID being renamed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceKind {
+    Texture,
+    Model,
+    Blockstate,
+}
+
+/// Apply one step's renames to a namespace-less ID
+fn rename_in_step(id: &str, renames: &[(String, String)], upgrade: bool) -> Option<String> {
+    let (folders, files): (Vec<_>, Vec<_>) =
+        renames.iter().partition(|(old, _)| old.ends_with('/'));
+    let orient = |(old, new): &&(String, String)| {
+        if upgrade {
+            (old.clone(), new.clone())
+        } else {
+            (new.clone(), old.clone())
+        }
+    };
+    let folders: Vec<(String, String)> = folders.iter().map(orient).collect();
+    let files: HashMap<String, String> = files.iter().map(orient).collect();
+
+    let rename_folder = |id: &str| {
+        folders.iter().find_map(|(from, to)| {
+            id.strip_prefix(from.as_str())
+                .map(|rest| format!("{}{}", to, rest))
+        })
+    };
+
+    // Folders move before files are renamed on the way up, and after on the way down
+    if upgrade {
+        let moved = rename_folder(id);
+        let current = moved.as_deref().unwrap_or(id);
+        files.get(current).cloned().or(moved)
+    } else {
+        let renamed = files.get(id).cloned();
+        let current = renamed.as_deref().unwrap_or(id);
+        rename_folder(current).or(renamed)
+    }
+}
+
+/// Rename a resource ID (with or without namespace) from one pack format to another
+///
+/// Returns None when the ID doesn't change.
+pub fn convert_resource_id(
+    id: &str,
+    kind: ResourceKind,
+    from_format: u32,
+    to_format: u32,
+) -> Option<String> {
+    let (namespace, path) = match id.split_once(':') {
+        Some((namespace, path)) => (Some(namespace), path),
+        None => (None, id),
+    };
+    // Renames only apply to vanilla resources
+    if namespace.is_some_and(|ns| ns != "minecraft") {
+        return None;
+    }
+
+    let upgrade = to_format > from_format;
+    let (low, high) = if upgrade {
+        (from_format, to_format)
+    } else {
+        (to_format, from_format)
+    };
+    let mut steps: Vec<&RenameStep> = RENAME_STEPS
+        .iter()
+        .filter(|step| low < step.format && step.format <= high)
+        .collect();
+    if !upgrade {
+        steps.reverse();
+    }
+
+    let mut current = path.to_string();
+    for step in steps {
+        let renames = match kind {
+            ResourceKind::Texture => &step.textures,
+            ResourceKind::Model => &step.models,
+            ResourceKind::Blockstate => &step.blockstates,
+        };
+        if let Some(renamed) = rename_in_step(&current, renames, upgrade) {
+            current = renamed;
+        }
+    }
+
+    if current == path {
+        return None;
+    }
+    Some(match namespace {
+        Some(namespace) => format!("{}:{}", namespace, current),
+        None => current,
+    })
+}
+
+/// A file moved to a new path by the conversion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenamedFile {
+    pub from: String,
+    pub to: String,
+}
+
+/// A file the converter could not migrate (copied unchanged unless noted)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversionIssue {
+    pub path: String,
+    pub reason: String,
+}
+
+/// Summary of a pack conversion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversionReport {
+    pub from_format: u32,
+    pub to_format: u32,
+    pub output_path: String,
+    pub files_written: usize,
+    /// Files written under a new path
+    pub renamed: Vec<RenamedFile>,
+    /// JSON files whose contents were rewritten
+    pub rewritten: Vec<String>,
+    pub unconvertible: Vec<ConversionIssue>,
+}
+
+/// Split "assets/<ns>/<folder>/<id><ext>" into (ns, id) when folder and extension match
+fn split_resource_path<'a>(path: &'a str, folder: &str, ext: &str) -> Option<(&'a str, &'a str)> {
+    let rest = path.strip_prefix("assets/")?;
+    let (namespace, rest) = rest.split_once('/')?;
+    let id = rest
+        .strip_prefix(folder)?
+        .strip_prefix('/')?
+        .strip_suffix(ext)?;
+    Some((namespace, id))
+}
+
+/// New path for a pack file, or None if it stays where it is
+fn convert_file_path(path: &str, from_format: u32, to_format: u32) -> Option<String> {
+    for (folder, ext, kind) in [
+        ("textures", ".png.mcmeta", ResourceKind::Texture),
+        ("textures", ".png", ResourceKind::Texture),
+        ("models", ".json", ResourceKind::Model),
+        ("blockstates", ".json", ResourceKind::Blockstate),
+    ] {
+        if let Some((namespace, id)) = split_resource_path(path, folder, ext) {
+            if namespace != "minecraft" {
+                return None;
+            }
+            return convert_resource_id(id, kind, from_format, to_format)
+                .map(|id| format!("assets/{}/{}/{}{}", namespace, folder, id, ext));
+        }
+    }
+    None
+}
+
+/// Rewrite parent and texture references inside a model JSON
+///
+/// Returns whether anything changed.
+fn convert_model_json(json: &mut serde_json::Value, from_format: u32, to_format: u32) -> bool {
+    let mut changed = false;
+
+    if let Some(parent) = json.get_mut("parent") {
+        if let Some(renamed) = parent
+            .as_str()
+            .and_then(|id| convert_resource_id(id, ResourceKind::Model, from_format, to_format))
+        {
+            *parent = serde_json::Value::String(renamed);
+            changed = true;
+        }
+    }
+
+    if let Some(textures) = json.get_mut("textures").and_then(|t| t.as_object_mut()) {
+        for value in textures.values_mut() {
+            let renamed = value
+                .as_str()
+                .filter(|id| !id.starts_with('#'))
+                .and_then(|id| {
+                    convert_resource_id(id, ResourceKind::Texture, from_format, to_format)
+                });
+            if let Some(renamed) = renamed {
+                *value = serde_json::Value::String(renamed);
+                changed = true;
+            }
+        }
+    }
+
+    changed
+}
+
+/// Rename a model a blockstate variant points at
+///
+/// Before 1.13 (pack_format 4) blockstates name models relative to
+/// models/block/ ("grass_normal" for "block/grass_normal").
+fn convert_blockstate_model(id: &str, from_format: u32, to_format: u32) -> Option<String> {
+    let (namespace, path) = match id.split_once(':') {
+        Some((namespace, path)) => (Some(namespace), path),
+        None => (None, id),
+    };
+    if namespace.is_some_and(|ns| ns != "minecraft") {
+        return None;
+    }
+
+    let path = if from_format < 4 {
+        format!("block/{}", path)
+    } else {
+        path.to_string()
+    };
+    let converted =
+        convert_resource_id(&path, ResourceKind::Model, from_format, to_format).unwrap_or(path);
+    let converted = match converted.strip_prefix("block/") {
+        Some(relative) if to_format < 4 => relative.to_string(),
+        _ => converted,
+    };
+    let converted = match namespace {
+        Some(namespace) => format!("{}:{}", namespace, converted),
+        None => converted,
+    };
+    (converted != id).then_some(converted)
+}
+
+/// Rename the models of one variant (a model or a list of weighted models)
+fn convert_variant_models(
+    variant: &mut serde_json::Value,
+    from_format: u32,
+    to_format: u32,
+) -> bool {
+    let models: Vec<&mut serde_json::Value> = match variant {
+        serde_json::Value::Array(models) => models.iter_mut().collect(),
+        model => vec![model],
+    };
+    let mut changed = false;
+    for model in models {
+        if let Some(reference) = model.get_mut("model") {
+            if let Some(renamed) = reference
+                .as_str()
+                .and_then(|id| convert_blockstate_model(id, from_format, to_format))
+            {
+                *reference = serde_json::Value::String(renamed);
+                changed = true;
+            }
+        }
+    }
+    changed
+}
+
+/// Rewrite variant keys and model references inside a blockstate JSON
+///
+/// Blocks without properties have the variant "normal" before 1.13 and ""
+/// from then on. Returns whether anything changed.
+fn convert_blockstate_json(json: &mut serde_json::Value, from_format: u32, to_format: u32) -> bool {
+    let crosses_flattening = from_format.min(to_format) < 4 && from_format.max(to_format) >= 4;
+    let mut changed = false;
+
+    if let Some(variants) = json.get_mut("variants").and_then(|v| v.as_object_mut()) {
+        let (old_key, new_key) = if from_format < to_format {
+            ("normal", "")
+        } else {
+            ("", "normal")
+        };
+        if crosses_flattening && !variants.contains_key(new_key) {
+            if let Some(variant) = variants.remove(old_key) {
+                variants.insert(new_key.to_string(), variant);
+                changed = true;
+            }
+        }
+        for variant in variants.values_mut() {
+            changed |= convert_variant_models(variant, from_format, to_format);
+        }
+    }
+    if let Some(cases) = json.get_mut("multipart").and_then(|m| m.as_array_mut()) {
+        for apply in cases.iter_mut().filter_map(|case| case.get_mut("apply")) {
+            changed |= convert_variant_models(apply, from_format, to_format);
+        }
+    }
+
+    changed
+}
+
+/// Set pack.mcmeta's pack_format, dropping a supported_formats range that excludes it
+fn convert_pack_mcmeta(json: &mut serde_json::Value, to_format: u32) {
+    if let Some(pack) = json.get_mut("pack").and_then(|p| p.as_object_mut()) {
+        pack.insert("pack_format".to_string(), to_format.into());
+        let excludes_target = pack
+            .get("supported_formats")
+            .and_then(crate::model::PackFormatRange::from_json)
+            .is_some_and(|range| !range.contains(to_format));
+        if excludes_target {
+            pack.remove("supported_formats");
+        }
+    }
+}
+
+/// Whether the flattening's blockstate renames cover a blockstate of a pack
+/// made for `format`, so its variant keys carry over as they are
+fn is_flattened_blockstate(id: &str, format: u32) -> bool {
+    // Compare by the name it has from 1.13 on
+    let name = convert_resource_id(id, ResourceKind::Blockstate, format, 4);
+    let name = name.as_deref().unwrap_or(id);
+    RENAME_STEPS
+        .iter()
+        .filter(|step| step.format == 4)
+        .flat_map(|step| &step.blockstates)
+        .any(|(_, new)| new == name)
+}
+
+/// Changes between formats that need a human, reported per file
+fn check_unconvertible(
+    path: &str,
+    contents: &[u8],
+    from_format: u32,
+    to_format: u32,
+) -> Option<String> {
+    let crosses = |format: u32| {
+        let (low, high) = (from_format.min(to_format), from_format.max(to_format));
+        low < format && format <= high
+    };
+
+    if crosses(3) && to_format >= 3 && path.chars().any(|c| c.is_ascii_uppercase()) {
+        return Some(
+            "Resource paths must be lowercase from 1.11 (pack_format 3); rename the file and its references"
+                .to_string(),
+        );
+    }
+    let unconverted_blockstate =
+        split_resource_path(path, "blockstates", ".json").is_some_and(|(namespace, id)| {
+            namespace != "minecraft" || !is_flattened_blockstate(id, from_format)
+        });
+    if crosses(4) && unconverted_blockstate {
+        return Some(
+            "Blockstate variants changed in the 1.13 flattening; review variant keys and model IDs"
+                .to_string(),
+        );
+    }
+    if crosses(46) && to_format >= 46 && split_resource_path(path, "models", ".json").is_some() {
+        let has_overrides = serde_json::from_slice::<serde_json::Value>(contents)
+            .is_ok_and(|json| json.get("overrides").is_some());
+        if has_overrides {
+            return Some(
+                "Item model overrides were replaced by items/ definitions in 1.21.4 (pack_format 46)"
+                    .to_string(),
+            );
+        }
+    }
+    None
+}
+
+/// Where each file of a pack ends up after converting between two formats
+struct RenamePlan {
+    /// Source path -> output path, for every file
+    planned: BTreeMap<String, String>,
+    renamed: Vec<RenamedFile>,
+    /// Renames that were skipped because the new path is taken
+    unconvertible: Vec<ConversionIssue>,
+}
+
+/// Plan output paths so renamed files never overwrite a file that stays where
+/// it is or another renamed file
+///
+/// A path freed by a rename can be taken by another, so swaps like 1.13's
+/// grass blockstates (`grass` -> `grass_block`, `tall_grass` -> `grass`) work.
+fn plan_renames(files: &[String], from_format: u32, to_format: u32) -> RenamePlan {
+    let wanted: HashMap<&str, String> = files
+        .iter()
+        .filter_map(|file| {
+            let new_path = convert_file_path(file, from_format, to_format)?;
+            Some((file.as_str(), new_path))
+        })
+        .collect();
+
+    // Blocked renames leave their file in place, which can block others in
+    // turn, so repeat until nothing changes
+    let mut blocked: HashSet<&str> = HashSet::new();
+    loop {
+        let staying: HashSet<&str> = files
+            .iter()
+            .map(String::as_str)
+            .filter(|file| !wanted.contains_key(file) || blocked.contains(file))
+            .collect();
+        let mut claimed: HashSet<&str> = HashSet::new();
+        let newly_blocked: Vec<&str> = files
+            .iter()
+            .map(String::as_str)
+            .filter(|file| !blocked.contains(file))
+            .filter(|file| {
+                wanted.get(file).is_some_and(|new_path| {
+                    staying.contains(new_path.as_str()) || !claimed.insert(new_path.as_str())
+                })
+            })
+            .collect();
+        if newly_blocked.is_empty() {
+            break;
+        }
+        blocked.extend(newly_blocked);
+    }
+
+    let mut planned: BTreeMap<String, String> = BTreeMap::new();
+    let mut unconvertible = Vec::new();
+    let mut renamed = Vec::new();
+    for file in files {
+        let target = match wanted.get(file.as_str()) {
+            Some(new_path) if blocked.contains(file.as_str()) => {
+                unconvertible.push(ConversionIssue {
+                    path: file.clone(),
+                    reason: format!("Not renamed: {} already exists in the pack", new_path),
+                });
+                file.clone()
+            }
+            Some(new_path) => {
+                renamed.push(RenamedFile {
+                    from: file.clone(),
+                    to: new_path.clone(),
+                });
+                new_path.clone()
+            }
+            None => file.clone(),
+        };
+        planned.insert(file.clone(), target);
+    }
+    RenamePlan {
+        planned,
+        renamed,
+        unconvertible,
+    }
+}
+
+/// Convert a pack from one pack format to another, writing the result as a folder
+///
+/// Files are copied to `output_dir` with renames applied. Anything that can't be
+/// migrated automatically is still copied (unless a converted file takes its path)
+/// and listed in the report's `unconvertible` section.
+///
+/// # Arguments
+/// * `pack` - Pack to convert (folder or zip)
+/// * `from_format` - pack_format the pack was made for
+/// * `to_format` - pack_format to convert to
+/// * `output_dir` - Folder to write the converted pack into
+pub fn convert_pack(
+    pack: &PackMeta,
+    from_format: u32,
+    to_format: u32,
+    output_dir: &Path,
+) -> Result<ConversionReport> {
+    let mut files = asset_indexer::list_pack_files(pack)?;
+    files.sort();
+
+    let RenamePlan {
+        planned,
+        renamed,
+        mut unconvertible,
+    } = plan_renames(&files, from_format, to_format);
+
+    let mut reader = pack_files::PackReader::open(pack)?;
+    let mut rewritten = Vec::new();
+    for (source, target) in &planned {
+        let mut contents = reader.read(source)?;
+
+        if let Some(reason) = check_unconvertible(source, &contents, from_format, to_format) {
+            unconvertible.push(ConversionIssue {
+                path: source.clone(),
+                reason,
+            });
+        }
+
+        let is_model = split_resource_path(source, "models", ".json").is_some();
+        let is_blockstate = split_resource_path(source, "blockstates", ".json").is_some();
+        if is_model || is_blockstate || source == "pack.mcmeta" {
+            match serde_json::from_slice::<serde_json::Value>(&contents) {
+                Ok(mut json) => {
+                    let changed = if is_model {
+                        convert_model_json(&mut json, from_format, to_format)
+                    } else if is_blockstate {
+                        convert_blockstate_json(&mut json, from_format, to_format)
+                    } else {
+                        convert_pack_mcmeta(&mut json, to_format);
+                        true
+                    };
+                    if changed {
+                        contents = serde_json::to_vec_pretty(&json)?;
+                        rewritten.push(target.clone());
+                    }
+                }
+                Err(e) => unconvertible.push(ConversionIssue {
+                    path: source.clone(),
+                    reason: format!("Invalid JSON, copied unchanged: {}", e),
+                }),
+            }
+        }
+
+        let output_path = output_dir.join(target);
+        if let Some(parent) = output_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&output_path, contents)
+            .with_context(|| format!("Failed to write {}", output_path.display()))?;
+    }
+
+    info!(
+        "Converted {} from pack_format {} to {}: {} files, {} renamed, {} issues",
+        pack.name,
+        from_format,
+        to_format,
+        planned.len(),
+        renamed.len(),
+        unconvertible.len()
+    );
+
+    Ok(ConversionReport {
+        from_format,
+        to_format,
+        output_path: output_dir.to_string_lossy().to_string(),
+        files_written: planned.len(),
+        renamed,
+        rewritten,
+        unconvertible,
+    })
+}
+
+/// How a pack fares in the releases sharing one pack_format
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionCompatibility {
+    pub pack_format: u32,
+    /// Releases using this pack_format (e.g., "1.20.3 – 1.20.4")
+    pub versions: String,
+    /// Whether pack.mcmeta is accepted without the "incompatible" warning
+    pub status: CompatibilityStatus,
+    /// True when every asset loads as-is, i.e. nothing is ignored or needs conversion
+    pub assets_load: bool,
+    /// Files the game looks for under another name in these releases; the
+    /// converter renames them
+    pub renamed: Vec<RenamedFile>,
+    /// Models and blockstates referencing renamed models or textures (or
+    /// blockstate variants renamed by the flattening); the converter rewrites them
+    pub rewritten: Vec<String>,
+    /// Files these releases ignore or misread that can't be converted automatically
+    pub ignored: Vec<ConversionIssue>,
+}
+
+/// Compatibility of a pack across every known Minecraft release
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompatibilityMatrix {
+    pub pack_id: String,
+    /// pack_format the pack's assets were made for
+    pub from_format: u32,
+    /// Oldest to newest pack_format
+    pub versions: Vec<VersionCompatibility>,
+    /// Release range whose entries load every asset as-is (e.g., "1.13 – 1.20.1"),
+    /// None if there is none
+    pub works_on: Option<String>,
+}
+
+/// Check a pack against every pack_format in the format table
+///
+/// Uses the same rename tables as `convert_pack`, so "needs conversion" means
+/// converting the pack to that format would fix it. Nothing is written.
+///
+/// # Arguments
+/// * `pack` - Pack to check (folder or zip)
+/// * `from_format` - pack_format the pack was made for
+pub fn compatibility_matrix(pack: &PackMeta, from_format: u32) -> Result<CompatibilityMatrix> {
+    let mut files = asset_indexer::list_pack_files(pack)?;
+    files.sort();
+
+    // Only model and blockstate JSONs are inspected, so read them once for
+    // every format
+    let mut reader = pack_files::PackReader::open(pack)?;
+    let json_files: HashMap<&str, Vec<u8>> = files
+        .iter()
+        .filter(|file| {
+            split_resource_path(file, "models", ".json").is_some()
+                || split_resource_path(file, "blockstates", ".json").is_some()
+        })
+        .filter_map(|file| Some((file.as_str(), reader.read(file).ok()?)))
+        .collect();
+    let mut jsons: Vec<(&str, serde_json::Value)> = json_files
+        .iter()
+        .filter_map(|(file, contents)| Some((*file, serde_json::from_slice(contents).ok()?)))
+        .collect();
+    jsons.sort_by(|a, b| a.0.cmp(b.0));
+    let contents_of = |file: &str| json_files.get(file).map(Vec::as_slice).unwrap_or_default();
+
+    let mut versions = Vec::new();
+    for (format, first, _) in pack_format::PACK_FORMATS.iter() {
+        let status = pack_format::check_pack_compatibility(pack, first)?.status;
+        let RenamePlan {
+            renamed,
+            unconvertible: mut ignored,
+            ..
+        } = plan_renames(&files, from_format, *format);
+
+        for file in &files {
+            if let Some(reason) = check_unconvertible(file, contents_of(file), from_format, *format)
+            {
+                ignored.push(ConversionIssue {
+                    path: file.clone(),
+                    reason,
+                });
+            }
+            if *format < 46 && split_resource_path(file, "items", ".json").is_some() {
+                ignored.push(ConversionIssue {
+                    path: file.clone(),
+                    reason: "Item model definitions (items/) are read from 1.21.4 (pack_format 46)"
+                        .to_string(),
+                });
+            }
+        }
+
+        let rewritten: Vec<String> = jsons
+            .iter()
+            .filter(|(file, json)| {
+                let mut json = (*json).clone();
+                if split_resource_path(file, "blockstates", ".json").is_some() {
+                    convert_blockstate_json(&mut json, from_format, *format)
+                } else {
+                    convert_model_json(&mut json, from_format, *format)
+                }
+            })
+            .map(|(file, _)| file.to_string())
+            .collect();
+
+        versions.push(VersionCompatibility {
+            pack_format: *format,
+            versions: pack_format::versions_for_pack_format(*format).unwrap_or_default(),
+            status,
+            assets_load: renamed.is_empty() && rewritten.is_empty() && ignored.is_empty(),
+            renamed,
+            rewritten,
+            ignored,
+        });
+    }
+
+    let working: Vec<&VersionCompatibility> = versions
+        .iter()
+        .filter(|version| version.assets_load)
+        .collect();
+    let works_on = match (working.first(), working.last()) {
+        (Some(oldest), Some(newest)) => {
+            let first = pack_format::PACK_FORMATS
+                .iter()
+                .find(|(format, _, _)| *format == oldest.pack_format)
+                .map(|(_, first, _)| *first);
+            let last = pack_format::PACK_FORMATS
+                .iter()
+                .find(|(format, _, _)| *format == newest.pack_format)
+                .map(|(_, _, last)| *last);
+            match (first, last) {
+                (Some(first), Some(last)) if first == last => Some(first.to_string()),
+                (Some(first), Some(last)) => Some(format!("{} – {}", first, last)),
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+
+    Ok(CompatibilityMatrix {
+        pack_id: pack.id.clone(),
+        from_format,
+        versions,
+        works_on,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_resource_id_flattening() {
+        let up = |id| convert_resource_id(id, ResourceKind::Texture, 3, 15);
+        assert_eq!(up("blocks/stone").as_deref(), Some("block/stone"));
+        assert_eq!(
+            up("minecraft:blocks/planks_big_oak").as_deref(),
+            Some("minecraft:block/dark_oak_planks")
+        );
+        assert_eq!(
+            up("blocks/wool_colored_silver").as_deref(),
+            Some("block/light_gray_wool")
+        );
+        assert_eq!(up("items/apple").as_deref(), Some("item/apple"));
+        assert_eq!(up("custom:blocks/stone"), None);
+        assert_eq!(up("entity/zombie/zombie"), None);
+    }
+
+    #[test]
+    fn test_convert_resource_id_grass_chain() {
+        // 1.12 tall grass -> 1.13 "grass" -> 1.20.3 "short_grass"
+        assert_eq!(
+            convert_resource_id("blocks/tallgrass", ResourceKind::Texture, 3, 34).as_deref(),
+            Some("block/short_grass")
+        );
+        assert_eq!(
+            convert_resource_id("block/short_grass", ResourceKind::Texture, 34, 3).as_deref(),
+            Some("blocks/tallgrass")
+        );
+        // Stays "grass" when the target predates 1.20.3
+        assert_eq!(
+            convert_resource_id("blocks/tallgrass", ResourceKind::Texture, 3, 15).as_deref(),
+            Some("block/grass")
+        );
+        // Model swaps stay unambiguous
+        assert_eq!(
+            convert_resource_id("item/grass", ResourceKind::Model, 3, 15).as_deref(),
+            Some("item/grass_block")
+        );
+        assert_eq!(
+            convert_resource_id("item/grass", ResourceKind::Model, 15, 3).as_deref(),
+            Some("item/tall_grass")
+        );
+    }
+
+    #[test]
+    fn test_convert_pack_report() {
+        let temp_dir = std::env::temp_dir().join("test_convert_pack");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let pack_dir = temp_dir.join("pack");
+        let out_dir = temp_dir.join("out");
+        let write = |path: &str, contents: &str| {
+            let path = pack_dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        };
+        write(
+            "pack.mcmeta",
+            r#"{"pack": {"pack_format": 2, "description": "Old"}}"#,
+        );
+        write(
+            "assets/minecraft/textures/blocks/stone_granite.png",
+            "granite",
+        );
+        write("assets/minecraft/textures/blocks/Dirt.png", "dirt");
+        write(
+            "assets/minecraft/models/block/grass_normal.json",
+            r##"{"parent": "block/cube_bottom_top", "textures": {"top": "blocks/grass_top", "side": "#top"}}"##,
+        );
+        write("assets/minecraft/blockstates/stone.json", "{}");
+
+        let pack = PackMeta {
+            id: "pack".to_string(),
+            name: "pack".to_string(),
+            path: pack_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        };
+        let report = convert_pack(&pack, 2, 15, &out_dir);
+        let granite =
+            fs::read_to_string(out_dir.join("assets/minecraft/textures/block/granite.png"));
+        let model =
+            fs::read_to_string(out_dir.join("assets/minecraft/models/block/grass_block.json"));
+        let mcmeta = fs::read_to_string(out_dir.join("pack.mcmeta"));
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let report = report.unwrap();
+        assert_eq!(report.files_written, 5);
+        assert_eq!(report.renamed.len(), 3);
+        assert_eq!(granite.unwrap(), "granite");
+        let model: serde_json::Value = serde_json::from_str(&model.unwrap()).unwrap();
+        assert_eq!(model["textures"]["top"], "block/grass_block_top");
+        assert_eq!(model["textures"]["side"], "#top");
+        let mcmeta: serde_json::Value = serde_json::from_str(&mcmeta.unwrap()).unwrap();
+        assert_eq!(mcmeta["pack"]["pack_format"], 15);
+        assert_eq!(mcmeta["pack"]["description"], "Old");
+
+        let issue_paths: Vec<&str> = report
+            .unconvertible
+            .iter()
+            .map(|issue| issue.path.as_str())
+            .collect();
+        assert_eq!(
+            issue_paths,
+            vec![
+                "assets/minecraft/blockstates/stone.json",
+                "assets/minecraft/textures/blocks/Dirt.png",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_convert_pack_grass_blockstates() {
+        let temp_dir = std::env::temp_dir().join("test_convert_pack_grass");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+        let zip_path = temp_dir.join("old.zip");
+        let mut writer = ::zip::ZipWriter::new(fs::File::create(&zip_path).unwrap());
+        for (path, contents) in [
+            ("pack.mcmeta", r#"{"pack": {"pack_format": 3}}"#),
+            (
+                "assets/minecraft/blockstates/grass.json",
+                r#"{"variants": {"snowy=false": [{"model": "grass_normal"}, {"model": "grass_normal", "y": 90}], "snowy=true": {"model": "grass_snowed"}}}"#,
+            ),
+            (
+                "assets/minecraft/blockstates/tall_grass.json",
+                r#"{"variants": {"normal": {"model": "tall_grass"}}}"#,
+            ),
+            (
+                "assets/minecraft/blockstates/double_grass.json",
+                r#"{"variants": {"half=lower": {"model": "double_grass_bottom"}, "half=upper": {"model": "double_grass_top"}}}"#,
+            ),
+        ] {
+            writer
+                .start_file(path, ::zip::write::FileOptions::default())
+                .unwrap();
+            std::io::Write::write_all(&mut writer, contents.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let pack = PackMeta {
+            id: "old.zip".to_string(),
+            name: "old".to_string(),
+            path: zip_path.to_string_lossy().to_string(),
+            is_zip: true,
+            ..Default::default()
+        };
+        let new_dir = temp_dir.join("new");
+        let up = convert_pack(&pack, 3, 34, &new_dir);
+        let read = |dir: &Path, name: &str| {
+            let path = dir.join(format!("assets/minecraft/blockstates/{}.json", name));
+            fs::read_to_string(path)
+                .ok()
+                .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok())
+        };
+        let grass_block = read(&new_dir, "grass_block");
+        let short_grass = read(&new_dir, "short_grass");
+        let tall_grass = read(&new_dir, "tall_grass");
+        let leftover = read(&new_dir, "grass");
+
+        // And back down to 1.12 from the converted folder
+        let new_pack = PackMeta {
+            path: new_dir.to_string_lossy().to_string(),
+            is_zip: false,
+            ..pack.clone()
+        };
+        let old_dir = temp_dir.join("old");
+        let down = convert_pack(&new_pack, 34, 3, &old_dir);
+        let grass = read(&old_dir, "grass");
+        let plant = read(&old_dir, "tall_grass");
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        // The grass block and plant swap names without being reported as clashes
+        let up = up.unwrap();
+        assert!(up.unconvertible.is_empty(), "{:?}", up.unconvertible);
+        assert_eq!(up.renamed.len(), 3);
+        let grass_block = grass_block.unwrap();
+        assert_eq!(
+            grass_block["variants"]["snowy=false"][1]["model"],
+            "block/grass_block"
+        );
+        assert_eq!(
+            grass_block["variants"]["snowy=true"]["model"],
+            "block/grass_block_snow"
+        );
+        assert_eq!(
+            short_grass.unwrap()["variants"][""]["model"],
+            "block/short_grass"
+        );
+        assert_eq!(
+            tall_grass.unwrap()["variants"]["half=upper"]["model"],
+            "block/tall_grass_top"
+        );
+        assert!(leftover.is_none());
+
+        let down = down.unwrap();
+        assert!(down.unconvertible.is_empty(), "{:?}", down.unconvertible);
+        assert_eq!(
+            grass.unwrap()["variants"]["snowy=true"]["model"],
+            "grass_snowed"
+        );
+        assert_eq!(plant.unwrap()["variants"]["normal"]["model"], "tall_grass");
+    }
+
+    #[test]
+    fn test_compatibility_matrix() {
+        let temp_dir = std::env::temp_dir().join("test_compatibility_matrix");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let write = |path: &str, contents: &str| {
+            let path = temp_dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        };
+        write("pack.mcmeta", r#"{"pack": {"pack_format": 15}}"#);
+        write("assets/minecraft/textures/block/grass.png", "grass");
+        write(
+            "assets/minecraft/models/block/fern.json",
+            r#"{"textures": {"cross": "block/grass"}}"#,
+        );
+        let pack = PackMeta {
+            id: "pack".to_string(),
+            name: "pack".to_string(),
+            path: temp_dir.to_string_lossy().to_string(),
+            pack_format: Some(15),
+            ..Default::default()
+        };
+
+        let matrix = compatibility_matrix(&pack, 15);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let matrix = matrix.unwrap();
+        let version = |format: u32| {
+            matrix
+                .versions
+                .iter()
+                .find(|version| version.pack_format == format)
+                .unwrap()
+        };
+        assert_eq!(version(15).status, CompatibilityStatus::Compatible);
+        assert!(version(15).assets_load);
+        // "grass" is renamed to "short_grass" in 1.20.3 (pack_format 22)
+        assert!(version(4).assets_load);
+        assert_eq!(version(9).status, CompatibilityStatus::TooNew);
+        assert_eq!(version(22).status, CompatibilityStatus::TooOld);
+        assert!(!version(22).assets_load);
+        assert_eq!(
+            version(22).renamed[0].to,
+            "assets/minecraft/textures/block/short_grass.png"
+        );
+        assert_eq!(
+            version(22).rewritten,
+            vec!["assets/minecraft/models/block/fern.json"]
+        );
+        assert_eq!(matrix.works_on.as_deref(), Some("1.13 – 1.20.2"));
+    }
+}
@@ -0,0 +1,174 @@
+/// Pack authorship and licensing, read from sidecar files at the pack root
+///
+/// A pack may ship a `weaverbird.json` (`{"author": ..., "homepage": ...,
+/// "license": ...}`) and a license file (LICENSE, LICENSE.txt, COPYING, ...).
+/// Both are optional; the scan records what it finds in `PackMeta::credits`
+/// and a nest build lists the packs it took files from, with their terms, in
+/// CREDITS.txt so the merged pack can be redistributed properly.
+use crate::model::{PackCredits, PackMeta};
+use crate::util::pack_files;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use zip::ZipArchive;
+
+/// Sidecar metadata file at the pack root
+pub const SIDECAR_FILE: &str = "weaverbird.json";
+
+/// File a nest build writes its credits to
+pub const CREDITS_FILE: &str = "CREDITS.txt";
+
+/// Whether a root file name is a license file: LICENSE, LICENCE or COPYING,
+/// in any case, bare or as .txt/.md
+fn is_license_file(name: &str) -> bool {
+    let upper = name.to_ascii_uppercase();
+    let stem = upper
+        .strip_suffix(".TXT")
+        .or_else(|| upper.strip_suffix(".MD"))
+        .unwrap_or(&upper);
+    matches!(stem, "LICENSE" | "LICENCE" | "COPYING")
+}
+
+/// Non-empty string field of the sidecar JSON
+fn text_field(json: &serde_json::Value, key: &str) -> Option<String> {
+    json.get(key)
+        .and_then(|value| value.as_str())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// Credits from the sidecar's contents and the license file name, or None if
+/// the pack has neither
+fn parse_credits(sidecar: Option<&str>, license_file: Option<String>) -> Option<PackCredits> {
+    let json = sidecar
+        .and_then(|contents| serde_json::from_str::<serde_json::Value>(contents).ok())
+        .unwrap_or_default();
+    let credits = PackCredits {
+        author: text_field(&json, "author"),
+        homepage: text_field(&json, "homepage"),
+        license: text_field(&json, "license"),
+        license_file,
+    };
+    (credits != PackCredits::default()).then_some(credits)
+}
+
+/// Read the sidecar files of an opened ZIP pack
+pub fn credits_from_zip(archive: &mut ZipArchive<fs::File>) -> Option<PackCredits> {
+    let license_file = archive
+        .file_names()
+        .filter(|name| !name.contains('/'))
+        .filter(|name| is_license_file(name))
+        .min()
+        .map(str::to_string);
+
+    let sidecar = archive.by_name(SIDECAR_FILE).ok().and_then(|mut file| {
+        let mut contents = String::new();
+        file.read_to_string(&mut contents).ok()?;
+        Some(contents)
+    });
+
+    parse_credits(sidecar.as_deref(), license_file)
+}
+
+/// Read the sidecar files of a directory pack
+pub fn credits_from_dir(dir_path: &Path) -> Option<PackCredits> {
+    let license_file = fs::read_dir(dir_path)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| is_license_file(name))
+        .min();
+
+    let sidecar = fs::read_to_string(dir_path.join(SIDECAR_FILE)).ok();
+
+    parse_credits(sidecar.as_deref(), license_file)
+}
+
+/// Render CREDITS.txt for the packs a nest took files from, in pack order
+///
+/// Each pack's license file is included in full, since most licenses require
+/// their text to travel with redistributed work.
+pub fn render_credits(packs: &[&PackMeta]) -> String {
+    let mut out = String::from(
+        "Credits\n\nThis pack was merged by Weaverbird from the resource packs below.\n",
+    );
+
+    for pack in packs {
+        out.push_str(&format!(
+            "\n{}\n{}\n",
+            pack.name,
+            "=".repeat(pack.name.chars().count())
+        ));
+        let credits = pack.credits.clone().unwrap_or_default();
+        if let Some(author) = &credits.author {
+            out.push_str(&format!("Author: {}\n", author));
+        }
+        if let Some(homepage) = &credits.homepage {
+            out.push_str(&format!("Homepage: {}\n", homepage));
+        }
+        match &credits.license {
+            Some(license) => out.push_str(&format!("License: {}\n", license)),
+            None if credits.license_file.is_none() => {
+                out.push_str("License: not stated; check with the author before sharing\n")
+            }
+            None => {}
+        }
+
+        let license_text = credits
+            .license_file
+            .as_ref()
+            .and_then(|file| pack_files::read_pack_entry(pack, file).ok());
+        if let Some(text) = license_text {
+            out.push('\n');
+            out.push_str(String::from_utf8_lossy(&text).trim_end());
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_credits_from_dir() {
+        let dir = std::env::temp_dir().join("test_pack_credits_from_dir");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        assert_eq!(credits_from_dir(&dir), None);
+
+        fs::write(dir.join("License.txt"), "MIT License\n\nCopyright Sam").unwrap();
+        fs::write(
+            dir.join(SIDECAR_FILE),
+            r#"{"author": "Sam", "homepage": "https://example.com", "license": " "}"#,
+        )
+        .unwrap();
+        let credits = credits_from_dir(&dir).unwrap();
+        assert_eq!(credits.author.as_deref(), Some("Sam"));
+        assert_eq!(credits.homepage.as_deref(), Some("https://example.com"));
+        assert_eq!(credits.license, None);
+        assert_eq!(credits.license_file.as_deref(), Some("License.txt"));
+
+        let pack = PackMeta {
+            id: "sams_pack".to_string(),
+            name: "Sam's Pack".to_string(),
+            path: dir.to_string_lossy().to_string(),
+            credits: Some(credits),
+            ..Default::default()
+        };
+        let unlicensed = PackMeta {
+            name: "Other".to_string(),
+            ..Default::default()
+        };
+        let rendered = render_credits(&[&pack, &unlicensed]);
+        assert!(rendered.contains("Sam's Pack\n==========\nAuthor: Sam\n"));
+        assert!(rendered.contains("Copyright Sam\n"));
+        assert!(rendered.contains("Other\n=====\nLicense: not stated"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,424 @@
+/// Cross-file integrity checks for a whole pack
+///
+/// Where linting looks at one JSON file at a time, the doctor follows the
+/// references between files: textures named by models, the PNG an animation
+/// .mcmeta belongs to, the .ogg files behind sounds.json events, the files a
+/// CIT rule swaps in, and the other way round, files nothing points at.
+/// References are looked up in the pack, then in vanilla. The report is plain
+/// data so the app's problems panel and the CLI can both render it.
+use crate::model::PackMeta;
+use crate::util::{asset_indexer, block_models, cit, pack_files};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fmt;
+
+/// Folders whose textures only models use; textures elsewhere may be loaded
+/// by the game directly (entity, gui, particle, ...) and are never "unused"
+const MODEL_TEXTURE_FOLDERS: &[&str] = &["block/", "item/"];
+
+/// Issues are capped per pack so a broken export doesn't flood the report
+const MAX_ISSUES: usize = 5000;
+
+/// A broken or dangling reference between a pack's files
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum IntegrityIssue {
+    /// A model's texture variable names a texture neither the pack nor vanilla has
+    MissingTexture {
+        model: String,
+        variable: String,
+        texture: String,
+    },
+    /// An animation or texture .mcmeta with no PNG beside it in the pack;
+    /// the game only pairs them within one pack, so it is ignored
+    OrphanMcmeta { file: String },
+    /// A sounds.json event lists a sound whose .ogg file is missing
+    MissingSound {
+        file: String,
+        event: String,
+        sound: String,
+    },
+    /// A CIT rule references a texture or model that doesn't exist
+    MissingCitFile { rule: String, file: String },
+    /// A block or item texture or a model that nothing in the pack uses and
+    /// that doesn't replace a vanilla file
+    UnusedFile { file: String },
+}
+
+impl IntegrityIssue {
+    /// Whether the issue is a likely leftover rather than something broken
+    pub fn is_cleanup(&self) -> bool {
+        matches!(self, IntegrityIssue::UnusedFile { .. })
+    }
+}
+
+impl fmt::Display for IntegrityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntegrityIssue::MissingTexture {
+                model,
+                variable,
+                texture,
+            } => write!(
+                f,
+                "{}: texture \"{}\" points at missing {}",
+                model, variable, texture
+            ),
+            IntegrityIssue::OrphanMcmeta { file } => {
+                write!(f, "{}: no texture to animate", file)
+            }
+            IntegrityIssue::MissingSound { file, event, sound } => {
+                write!(f, "{}: event {} plays missing sound {}", file, event, sound)
+            }
+            IntegrityIssue::MissingCitFile { rule, file } => {
+                write!(f, "{}: references missing {}", rule, file)
+            }
+            IntegrityIssue::UnusedFile { file } => write!(f, "{}: not used", file),
+        }
+    }
+}
+
+/// Integrity findings for a whole pack
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackIntegrityReport {
+    pub pack_id: String,
+    pub files_checked: usize,
+    /// Whether issues were dropped past the cap
+    pub truncated: bool,
+    pub issues: Vec<IntegrityIssue>,
+}
+
+/// Split "assets/<namespace>/<folder>/<rest>" into (namespace, folder, rest)
+fn split_asset_path(path: &str) -> Option<(&str, &str, &str)> {
+    let rest = path.strip_prefix("assets/")?;
+    let (namespace, rest) = rest.split_once('/')?;
+    let (folder, rest) = rest.split_once('/')?;
+    Some((namespace, folder, rest))
+}
+
+/// Pack-relative path of a model ID
+fn model_path(model_id: &str) -> String {
+    block_models::model_id_to_path(&block_models::normalize_model_id(model_id))
+}
+
+/// Collect every string under a "model" or "parent" key, at any depth
+fn collect_model_refs(value: &Value, refs: &mut HashSet<String>) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object {
+                match value {
+                    Value::String(model) if key == "model" || key == "parent" => {
+                        refs.insert(model_path(model));
+                    }
+                    _ => collect_model_refs(value, refs),
+                }
+            }
+        }
+        Value::Array(values) => values
+            .iter()
+            .for_each(|value| collect_model_refs(value, refs)),
+        _ => {}
+    }
+}
+
+/// Checks one pack, with vanilla to resolve references against
+struct Doctor<'a> {
+    pack: &'a PackMeta,
+    vanilla_pack: &'a PackMeta,
+    files: HashSet<&'a str>,
+    issues: Vec<IntegrityIssue>,
+    /// Pack-relative paths something in the pack points at
+    referenced: HashSet<String>,
+}
+
+impl Doctor<'_> {
+    fn exists(&self, relative_path: &str) -> bool {
+        self.files.contains(relative_path)
+            || pack_files::pack_entry_exists(self.vanilla_pack, relative_path)
+    }
+
+    fn read_json(&self, file: &str) -> Option<Value> {
+        let bytes = pack_files::read_pack_entry(self.pack, file).ok()?;
+        let text = String::from_utf8_lossy(&bytes);
+        serde_json::from_str(text.trim_start_matches('\u{feff}')).ok()
+    }
+
+    fn check_model(&mut self, file: &str) {
+        let Some(model) = self.read_json(file) else {
+            return;
+        };
+        collect_model_refs(&model, &mut self.referenced);
+        let Some(textures) = model.get("textures").and_then(Value::as_object) else {
+            return;
+        };
+        for (variable, texture) in textures {
+            let Some(texture) = texture.as_str() else {
+                continue;
+            };
+            if texture.starts_with('#') {
+                continue;
+            }
+            let texture_path = pack_files::texture_entry_path(texture);
+            if !self.exists(&texture_path) {
+                self.issues.push(IntegrityIssue::MissingTexture {
+                    model: file.to_string(),
+                    variable: variable.clone(),
+                    texture: texture.to_string(),
+                });
+            }
+            self.referenced.insert(texture_path);
+        }
+    }
+
+    fn check_sounds(&mut self, file: &str) {
+        let Some(Value::Object(events)) = self.read_json(file) else {
+            return;
+        };
+        for (event, definition) in &events {
+            let Some(sounds) = definition.get("sounds").and_then(Value::as_array) else {
+                continue;
+            };
+            for sound in sounds {
+                let (name, kind) = match sound {
+                    Value::String(name) => (name.as_str(), "file"),
+                    Value::Object(sound) => (
+                        sound
+                            .get("name")
+                            .and_then(Value::as_str)
+                            .unwrap_or_default(),
+                        sound.get("type").and_then(Value::as_str).unwrap_or("file"),
+                    ),
+                    _ => continue,
+                };
+                if name.is_empty() || kind != "file" {
+                    continue;
+                }
+                let (sound_namespace, path) = match name.split_once(':') {
+                    Some((sound_namespace, path)) => (sound_namespace, path),
+                    None => ("minecraft", name),
+                };
+                let sound_path = format!("assets/{}/sounds/{}.ogg", sound_namespace, path);
+                self.referenced.insert(sound_path.clone());
+                // The game's own sounds come from the launcher's asset index,
+                // not the client jar, so vanilla can't vouch for them
+                if sound_namespace == "minecraft" {
+                    continue;
+                }
+                if !self.exists(&sound_path) {
+                    self.issues.push(IntegrityIssue::MissingSound {
+                        file: file.to_string(),
+                        event: event.clone(),
+                        sound: name.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    fn check_cit_rules(&mut self, files: &[String]) {
+        for (rule_file, rule) in cit::read_cit_rules(self.pack, files) {
+            for referenced in cit::referenced_files(&rule_file, &rule) {
+                if !self.exists(&referenced) {
+                    self.issues.push(IntegrityIssue::MissingCitFile {
+                        rule: rule_file.clone(),
+                        file: referenced.clone(),
+                    });
+                }
+                self.referenced.insert(referenced);
+            }
+        }
+    }
+
+    /// Whether a file is a block or item texture, or a model, that only
+    /// other pack files can make the game load
+    fn is_unused_candidate(file: &str) -> bool {
+        match split_asset_path(file) {
+            // Other namespaces belong to mods, which may load files directly
+            Some(("minecraft", "textures", rest)) => {
+                rest.ends_with(".png")
+                    && MODEL_TEXTURE_FOLDERS
+                        .iter()
+                        .any(|folder| rest.starts_with(folder))
+            }
+            Some(("minecraft", "models", rest)) => rest.ends_with(".json"),
+            _ => false,
+        }
+    }
+}
+
+/// Check the references between a pack's files
+///
+/// # Arguments
+/// * `pack` - The resource pack to check
+/// * `vanilla_pack` - Vanilla assets that references may point into
+pub fn check_pack_integrity(
+    pack: &PackMeta,
+    vanilla_pack: &PackMeta,
+) -> Result<PackIntegrityReport> {
+    let mut files = asset_indexer::list_pack_files(pack)?;
+    files.sort();
+
+    let mut doctor = Doctor {
+        pack,
+        vanilla_pack,
+        files: files.iter().map(String::as_str).collect(),
+        issues: Vec::new(),
+        referenced: HashSet::new(),
+    };
+
+    for file in &files {
+        if file.ends_with(".png.mcmeta") {
+            let texture = file.trim_end_matches(".mcmeta");
+            if !doctor.files.contains(texture) {
+                doctor
+                    .issues
+                    .push(IntegrityIssue::OrphanMcmeta { file: file.clone() });
+            }
+            continue;
+        }
+        match split_asset_path(file) {
+            Some((_, "models", _)) if file.ends_with(".json") => doctor.check_model(file),
+            Some((_, "blockstates" | "items", _)) if file.ends_with(".json") => {
+                if let Some(json) = doctor.read_json(file) {
+                    collect_model_refs(&json, &mut doctor.referenced);
+                }
+            }
+            None if file.starts_with("assets/") && file.ends_with("/sounds.json") => {
+                doctor.check_sounds(file)
+            }
+            _ => {}
+        }
+    }
+    doctor.check_cit_rules(&files);
+
+    for file in &files {
+        if Doctor::is_unused_candidate(file)
+            && !doctor.referenced.contains(file.as_str())
+            && !pack_files::pack_entry_exists(vanilla_pack, file)
+        {
+            doctor
+                .issues
+                .push(IntegrityIssue::UnusedFile { file: file.clone() });
+        }
+    }
+
+    let truncated = doctor.issues.len() > MAX_ISSUES;
+    doctor.issues.truncate(MAX_ISSUES);
+    Ok(PackIntegrityReport {
+        pack_id: pack.id.clone(),
+        files_checked: files.len(),
+        truncated,
+        issues: doctor.issues,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_check_pack_integrity() {
+        let temp_dir = std::env::temp_dir().join("test_pack_doctor");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let pack_root = temp_dir.join("pack");
+        let vanilla_root = temp_dir.join("vanilla");
+        for (root, file, content) in [
+            (
+                &pack_root,
+                "assets/minecraft/blockstates/lamp.json",
+                r#"{"variants": {"": {"model": "block/lamp"}}}"#,
+            ),
+            (
+                &pack_root,
+                "assets/minecraft/models/block/lamp.json",
+                r#"{"parent": "block/cube_all", "textures": {"all": "block/lamp", "side": "block/gone"}}"#,
+            ),
+            (&pack_root, "assets/minecraft/models/block/spare.json", "{}"),
+            (
+                &pack_root,
+                "assets/minecraft/textures/block/lamp.png",
+                "png",
+            ),
+            (
+                &pack_root,
+                "assets/minecraft/textures/block/stone.png",
+                "png",
+            ),
+            (&pack_root, "assets/minecraft/textures/block/old.png", "png"),
+            (
+                &pack_root,
+                "assets/minecraft/textures/block/ghost.png.mcmeta",
+                "{}",
+            ),
+            (
+                &pack_root,
+                "assets/mymod/sounds.json",
+                r#"{"zap": {"sounds": ["mymod:zap", {"name": "mymod:missing"}, {"name": "minecraft:other", "type": "event"}]}}"#,
+            ),
+            (&pack_root, "assets/mymod/sounds/zap.ogg", "ogg"),
+            (
+                &pack_root,
+                "assets/minecraft/optifine/cit/wand.properties",
+                "items=stick\ntexture=wand",
+            ),
+            (
+                &vanilla_root,
+                "assets/minecraft/textures/block/stone.png",
+                "png",
+            ),
+            (
+                &vanilla_root,
+                "assets/minecraft/models/block/cube_all.json",
+                "{}",
+            ),
+        ] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        let make_pack = |id: &str| PackMeta {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: temp_dir.join(id).to_string_lossy().to_string(),
+            ..Default::default()
+        };
+
+        let report = check_pack_integrity(&make_pack("pack"), &make_pack("vanilla")).unwrap();
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(report.files_checked, 10);
+        assert_eq!(
+            report.issues,
+            vec![
+                IntegrityIssue::MissingTexture {
+                    model: "assets/minecraft/models/block/lamp.json".to_string(),
+                    variable: "side".to_string(),
+                    texture: "block/gone".to_string(),
+                },
+                IntegrityIssue::OrphanMcmeta {
+                    file: "assets/minecraft/textures/block/ghost.png.mcmeta".to_string(),
+                },
+                IntegrityIssue::MissingSound {
+                    file: "assets/mymod/sounds.json".to_string(),
+                    event: "zap".to_string(),
+                    sound: "mymod:missing".to_string(),
+                },
+                IntegrityIssue::MissingCitFile {
+                    rule: "assets/minecraft/optifine/cit/wand.properties".to_string(),
+                    file: "assets/minecraft/optifine/cit/wand.png".to_string(),
+                },
+                IntegrityIssue::UnusedFile {
+                    file: "assets/minecraft/models/block/spare.json".to_string(),
+                },
+                IntegrityIssue::UnusedFile {
+                    file: "assets/minecraft/textures/block/old.png".to_string(),
+                },
+            ]
+        );
+        assert!(report.issues[5].is_cleanup());
+    }
+}
@@ -0,0 +1,379 @@
+/// Read individual files out of a resource pack regardless of how it is stored
+///
+/// Directory packs are read straight from disk, ZIP packs through the zip helpers.
+/// Asset-ID helpers map "namespace:path" IDs to their in-pack file locations.
+use crate::model::{PackMeta, VanillaAssetKind};
+use crate::util::zip;
+use anyhow::{anyhow, bail, Result};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Normalize a pack-relative path, refusing any that would leave the pack
+///
+/// Packs come from the internet, so an entry name or requested path may be
+/// crafted to reach files elsewhere on disk. Backslashes are read as
+/// separators and empty or "." segments dropped; absolute paths, drive
+/// letters and ".." segments are rejected.
+pub fn safe_entry_path(relative_path: &str) -> Result<String> {
+    let unified = relative_path.replace('\\', "/");
+    if unified.starts_with('/') || unified.get(1..2) == Some(":") || unified.contains('\0') {
+        bail!("Unsafe path in pack: {}", relative_path);
+    }
+    let mut segments = Vec::new();
+    for segment in unified.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => bail!("Unsafe path in pack: {}", relative_path),
+            segment => segments.push(segment),
+        }
+    }
+    Ok(segments.join("/"))
+}
+
+/// Full path of a file in a directory pack
+///
+/// Besides the checks of `safe_entry_path`, an existing file is resolved
+/// through any links and must still lie inside the pack folder.
+pub fn resolve_dir_entry(pack_root: &Path, relative_path: &str) -> Result<PathBuf> {
+    let full_path = pack_root.join(safe_entry_path(relative_path)?);
+    if let Ok(resolved) = full_path.canonicalize() {
+        let root = pack_root
+            .canonicalize()
+            .map_err(|e| anyhow!("Failed to resolve {}: {}", pack_root.display(), e))?;
+        if !resolved.starts_with(&root) {
+            bail!("Unsafe path in pack: {}", relative_path);
+        }
+    }
+    Ok(full_path)
+}
+
+/// Files of a directory pack, with links inside it followed
+///
+/// Linked files and folders read the same as copied ones. A link that loops
+/// back on a folder being walked is skipped rather than followed forever, and
+/// so is anything a link resolves to outside the pack folder, which
+/// `resolve_dir_entry` would refuse to read anyway.
+pub fn pack_dir_files(pack_root: &Path) -> impl Iterator<Item = walkdir::DirEntry> {
+    let resolved_root = pack_root
+        .canonicalize()
+        .unwrap_or_else(|_| pack_root.to_path_buf());
+    walkdir::WalkDir::new(pack_root)
+        .follow_links(true)
+        .into_iter()
+        .filter_entry(move |entry| {
+            !entry.path_is_symlink()
+                || entry
+                    .path()
+                    .canonicalize()
+                    .is_ok_and(|target| target.starts_with(&resolved_root))
+        })
+        .filter_map(|entry| match entry {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                if e.loop_ancestor().is_some() {
+                    warn!("Skipping link loop: {}", e);
+                }
+                None
+            }
+        })
+        .filter(|entry| entry.file_type().is_file())
+}
+
+/// Read a file from a pack by its pack-relative path
+/// (e.g., "assets/minecraft/textures/block/stone.png")
+pub fn read_pack_entry(pack: &PackMeta, relative_path: &str) -> Result<Vec<u8>> {
+    if pack.is_zip {
+        zip::extract_zip_entry(&pack.path, relative_path)
+    } else {
+        let full_path = resolve_dir_entry(Path::new(&pack.path), relative_path)?;
+        fs::read(&full_path).map_err(|e| anyhow!("Failed to read {}: {}", full_path.display(), e))
+    }
+}
+
+/// Reads many files from one pack, opening a ZIP pack only once
+pub enum PackReader {
+    Zip(::zip::ZipArchive<zip::ArchiveSource>),
+    Dir(PathBuf),
+}
+
+impl PackReader {
+    pub fn open(pack: &PackMeta) -> Result<Self> {
+        Ok(if pack.is_zip {
+            PackReader::Zip(zip::open_cached_archive(&pack.path)?)
+        } else {
+            PackReader::Dir(PathBuf::from(&pack.path))
+        })
+    }
+
+    /// Read a file by its pack-relative path, like `read_pack_entry`
+    pub fn read(&mut self, relative_path: &str) -> Result<Vec<u8>> {
+        match self {
+            PackReader::Zip(archive) => {
+                let entry_path = safe_entry_path(relative_path)?;
+                let mut file = archive
+                    .by_name(&entry_path)
+                    .map_err(|e| anyhow!("Entry not found in zip: {}", e))?;
+                let mut buffer = Vec::new();
+                file.read_to_end(&mut buffer)
+                    .map_err(|e| anyhow!("Failed to read zip entry: {}", e))?;
+                Ok(buffer)
+            }
+            PackReader::Dir(root) => {
+                let full_path = resolve_dir_entry(root, relative_path)?;
+                fs::read(&full_path)
+                    .map_err(|e| anyhow!("Failed to read {}: {}", full_path.display(), e))
+            }
+        }
+    }
+}
+
+/// Check whether a pack contains a file at the given pack-relative path
+pub fn pack_entry_exists(pack: &PackMeta, relative_path: &str) -> bool {
+    if pack.is_zip {
+        zip::has_zip_entry(&pack.path, relative_path)
+    } else {
+        resolve_dir_entry(Path::new(&pack.path), relative_path)
+            .is_ok_and(|full_path| full_path.is_file())
+    }
+}
+
+/// Folder under the app cache directory holding files extracted from ZIP packs
+pub const TEXTURE_CACHE_NAME: &str = "weaverbird_textures";
+
+/// Prefix shared by every cached file extracted from one ZIP pack
+pub fn cached_entry_prefix(pack_path: &str) -> String {
+    let zip_name = Path::new(pack_path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("unknown");
+    format!("{}_", zip_name)
+}
+
+/// File name a pack-relative path is cached under, without the pack prefix
+pub fn cached_entry_name(relative_path: &str) -> String {
+    relative_path.replace(['/', '\\'], "_")
+}
+
+/// Where a ZIP pack's file is cached inside the texture cache directory
+pub fn cached_entry_path(cache_dir: &Path, pack_path: &str, relative_path: &str) -> PathBuf {
+    cache_dir.join(format!(
+        "{}{}",
+        cached_entry_prefix(pack_path),
+        cached_entry_name(relative_path)
+    ))
+}
+
+/// Recursively copy a folder, returning the number of files copied
+pub fn copy_dir_all(src: &Path, dest: &Path) -> std::io::Result<usize> {
+    let mut copied = 0;
+    for entry in walkdir::WalkDir::new(src) {
+        let entry = entry?;
+        let relative = entry
+            .path()
+            .strip_prefix(src)
+            .map_err(std::io::Error::other)?;
+        let target = dest.join(relative);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)?;
+        } else if entry.file_type().is_file() {
+            fs::copy(entry.path(), &target)?;
+            copied += 1;
+        }
+    }
+    Ok(copied)
+}
+
+/// Split an asset ID into (namespace, path), defaulting to the minecraft namespace
+/// "block/stone" -> ("minecraft", "block/stone")
+pub fn split_asset_id(asset_id: &str) -> (&str, &str) {
+    match asset_id.split_once(':') {
+        Some((namespace, path)) => (namespace, path),
+        None => ("minecraft", asset_id),
+    }
+}
+
+/// Convert a texture asset ID into its pack-relative PNG path
+/// "minecraft:block/stone" -> "assets/minecraft/textures/block/stone.png"
+pub fn texture_entry_path(asset_id: &str) -> String {
+    let (namespace, path) = split_asset_id(asset_id);
+    format!("assets/{}/textures/{}.png", namespace, path)
+}
+
+/// Convert any asset ID into its pack-relative file path
+///
+/// Resource IDs keep their extension ("minecraft:lang/en_us.json" ->
+/// "assets/minecraft/lang/en_us.json"); IDs without one are textures.
+pub fn asset_entry_path(asset_id: &str) -> String {
+    let (namespace, path) = split_asset_id(asset_id);
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    if file_name.contains('.') {
+        format!("assets/{}/{}", namespace, path)
+    } else {
+        texture_entry_path(asset_id)
+    }
+}
+
+/// Pack-relative path of one kind of file for an asset ID
+/// ("minecraft:block/stone", Model) -> "assets/minecraft/models/block/stone.json"
+/// ("minecraft:block/stone", Blockstate) -> "assets/minecraft/blockstates/stone.json"
+pub fn kind_entry_path(asset_id: &str, kind: VanillaAssetKind) -> String {
+    let (namespace, path) = split_asset_id(asset_id);
+    match kind {
+        VanillaAssetKind::Texture => asset_entry_path(asset_id),
+        VanillaAssetKind::Model => format!("assets/{}/models/{}.json", namespace, path),
+        VanillaAssetKind::Blockstate => {
+            let name = path.strip_prefix("block/").unwrap_or(path);
+            format!("assets/{}/blockstates/{}.json", namespace, name)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_asset_id() {
+        assert_eq!(
+            split_asset_id("minecraft:block/stone"),
+            ("minecraft", "block/stone")
+        );
+        assert_eq!(split_asset_id("block/stone"), ("minecraft", "block/stone"));
+        assert_eq!(
+            split_asset_id("create:item/wrench"),
+            ("create", "item/wrench")
+        );
+    }
+
+    #[test]
+    fn test_kind_entry_path() {
+        assert_eq!(
+            kind_entry_path("minecraft:block/stone", VanillaAssetKind::Texture),
+            "assets/minecraft/textures/block/stone.png"
+        );
+        assert_eq!(
+            kind_entry_path("minecraft:block/stone", VanillaAssetKind::Model),
+            "assets/minecraft/models/block/stone.json"
+        );
+        assert_eq!(
+            kind_entry_path("minecraft:block/oak_stairs", VanillaAssetKind::Blockstate),
+            "assets/minecraft/blockstates/oak_stairs.json"
+        );
+    }
+
+    #[test]
+    fn test_texture_entry_path() {
+        assert_eq!(
+            texture_entry_path("minecraft:block/magma"),
+            "assets/minecraft/textures/block/magma.png"
+        );
+        assert_eq!(
+            texture_entry_path("custom:entity/thing"),
+            "assets/custom/textures/entity/thing.png"
+        );
+    }
+
+    #[test]
+    fn test_asset_entry_path() {
+        assert_eq!(
+            asset_entry_path("minecraft:block/stone"),
+            "assets/minecraft/textures/block/stone.png"
+        );
+        assert_eq!(
+            asset_entry_path("minecraft:sounds.json"),
+            "assets/minecraft/sounds.json"
+        );
+        assert_eq!(
+            asset_entry_path("mymod:lang/en_us.json"),
+            "assets/mymod/lang/en_us.json"
+        );
+    }
+
+    #[test]
+    fn test_read_pack_entry_from_directory() {
+        let temp_dir = std::env::temp_dir().join("test_read_pack_entry_dir");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(temp_dir.join("assets/minecraft")).unwrap();
+        fs::write(temp_dir.join("assets/minecraft/file.txt"), b"hello").unwrap();
+
+        let pack = PackMeta {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            path: temp_dir.to_string_lossy().to_string(),
+            size: 0,
+            is_zip: false,
+            description: None,
+            has_icon: false,
+            pack_format: None,
+            ..Default::default()
+        };
+
+        let bytes = read_pack_entry(&pack, "assets/minecraft/file.txt");
+        let exists = pack_entry_exists(&pack, "assets/minecraft/file.txt");
+        let missing = pack_entry_exists(&pack, "assets/minecraft/missing.txt");
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(bytes.unwrap(), b"hello");
+        assert!(exists);
+        assert!(!missing);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_pack_dir_files_follows_links_inside_the_pack() {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = std::env::temp_dir().join("test_pack_dir_files_links");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let pack_dir = temp_dir.join("pack");
+        fs::create_dir_all(pack_dir.join("assets/minecraft")).unwrap();
+        fs::write(pack_dir.join("assets/minecraft/file.txt"), b"pack").unwrap();
+        fs::write(temp_dir.join("secret.txt"), b"secret").unwrap();
+        symlink(pack_dir.join("assets"), pack_dir.join("linked_assets")).unwrap();
+        symlink(&pack_dir, pack_dir.join("assets/loop")).unwrap();
+        symlink(temp_dir.join("secret.txt"), pack_dir.join("secret.txt")).unwrap();
+        // The pack folder itself linked in, as a dev checkout would be
+        symlink(&pack_dir, temp_dir.join("linked_pack")).unwrap();
+
+        let files = |root: &Path| {
+            let mut files: Vec<String> = pack_dir_files(root)
+                .map(|entry| {
+                    let relative = entry.path().strip_prefix(root).unwrap();
+                    relative.to_string_lossy().replace('\\', "/")
+                })
+                .collect();
+            files.sort();
+            files
+        };
+        let direct = files(&pack_dir);
+        let linked = files(&temp_dir.join("linked_pack"));
+        let secret = resolve_dir_entry(&pack_dir, "secret.txt");
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(
+            direct,
+            vec![
+                "assets/minecraft/file.txt",
+                "linked_assets/minecraft/file.txt"
+            ]
+        );
+        assert_eq!(linked, direct);
+        assert!(secret.is_err());
+    }
+
+    #[test]
+    fn test_safe_entry_path() {
+        assert_eq!(
+            safe_entry_path("./assets\\minecraft//lang/en_us.json").unwrap(),
+            "assets/minecraft/lang/en_us.json"
+        );
+        assert!(safe_entry_path("assets/../../../.ssh/id_rsa").is_err());
+        assert!(safe_entry_path("/etc/passwd").is_err());
+        assert!(safe_entry_path("C:\\Windows\\evil.dll").is_err());
+        assert!(safe_entry_path("..\\startup.bat").is_err());
+    }
+}
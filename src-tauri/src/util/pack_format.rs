@@ -0,0 +1,298 @@
+/// Resource pack format table and target-version compatibility checks
+///
+/// The table is data/pack_formats.json, which src/lib/packFormatCompatibility.ts
+/// reads as well.
+/// Source: https://minecraft.wiki/w/Pack_format (Resource Pack section)
+use crate::model::{PackFormatRange, PackMeta};
+use crate::util::i18n::tr;
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+/// One row of data/pack_formats.json
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PackFormatEntry {
+    pack_format: u32,
+    first: &'static str,
+    last: &'static str,
+}
+
+/// (pack_format, first release, last release), oldest first
+pub static PACK_FORMATS: Lazy<Vec<(u32, &'static str, &'static str)>> = Lazy::new(|| {
+    let entries: Vec<PackFormatEntry> =
+        serde_json::from_str(include_str!("../../data/pack_formats.json"))
+            .expect("data/pack_formats.json is valid");
+    entries
+        .into_iter()
+        .map(|entry| (entry.pack_format, entry.first, entry.last))
+        .collect()
+});
+
+/// First pack_format whose game versions read `supported_formats` (1.20.2)
+pub const SUPPORTED_FORMATS_MIN_PACK_FORMAT: u32 = 18;
+
+/// Parse a release version ("1.21" or "1.21.4") into comparable parts
+fn parse_version(version: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = version.trim().split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let patch = match parts.next() {
+        Some(patch) => patch.parse().ok()?,
+        None => 0,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch))
+}
+
+/// Look up the resource pack format used by a release version
+///
+/// Returns None for snapshots and versions outside the table.
+pub fn pack_format_for_version(version: &str) -> Option<u32> {
+    let version = parse_version(version)?;
+    PACK_FORMATS
+        .iter()
+        .find(|(_, first, last)| {
+            parse_version(first).is_some_and(|first| first <= version)
+                && parse_version(last).is_some_and(|last| version <= last)
+        })
+        .map(|(format, _, _)| *format)
+}
+
+/// Human-readable version range for a pack_format (e.g., "1.20.3 – 1.20.4")
+pub fn versions_for_pack_format(pack_format: u32) -> Option<String> {
+    PACK_FORMATS
+        .iter()
+        .find(|(format, _, _)| *format == pack_format)
+        .map(|(_, first, last)| {
+            if first == last {
+                first.to_string()
+            } else {
+                format!("{} – {}", first, last)
+            }
+        })
+}
+
+/// Describe a pack_format range with the game versions it spans
+fn describe_range(range: &PackFormatRange) -> String {
+    let label = |format: u32| match versions_for_pack_format(format) {
+        Some(versions) => format!("{} ({})", format, versions),
+        None => format.to_string(),
+    };
+    if range.min == range.max {
+        format!("pack_format {}", label(range.min))
+    } else {
+        format!("pack_format {} to {}", label(range.min), label(range.max))
+    }
+}
+
+/// Outcome of checking a pack against a target version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompatibilityStatus {
+    Compatible,
+    /// Made for older versions than the target
+    TooOld,
+    /// Made for newer versions than the target
+    TooNew,
+    /// pack.mcmeta declares no usable format
+    Unknown,
+}
+
+/// Compatibility report for one pack and target version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackCompatibility {
+    pub pack_id: String,
+    pub target_version: String,
+    pub target_pack_format: u32,
+    pub pack_format: Option<u32>,
+    /// Versions the declared pack_format was made for
+    pub pack_versions: Option<String>,
+    pub supported_formats: Option<PackFormatRange>,
+    pub status: CompatibilityStatus,
+    /// Human-readable problems, empty when the pack loads cleanly
+    pub issues: Vec<String>,
+}
+
+/// Check whether a pack loads in the target Minecraft version without warnings
+///
+/// Follows vanilla's rules: versions from 1.20.2 accept any format inside
+/// `supported_formats`, older ones require an exact pack_format match.
+pub fn check_pack_compatibility(
+    pack: &PackMeta,
+    target_version: &str,
+) -> Result<PackCompatibility> {
+    let target = pack_format_for_version(target_version)
+        .ok_or_else(|| anyhow!("Unknown Minecraft version: {}", target_version))?;
+
+    let mut issues = Vec::new();
+
+    if let (Some(format), Some(range)) = (pack.pack_format, pack.supported_formats) {
+        if !range.contains(format) {
+            issues.push(tr(
+                "compat-format-outside-supported",
+                &[
+                    ("min", range.min.into()),
+                    ("max", range.max.into()),
+                    ("format", format.into()),
+                ],
+            ));
+        }
+    }
+
+    let declared = pack.pack_format.map(|format| PackFormatRange {
+        min: format,
+        max: format,
+    });
+    let effective = if target >= SUPPORTED_FORMATS_MIN_PACK_FORMAT {
+        pack.supported_formats.or(declared)
+    } else {
+        declared
+    };
+
+    let status = match effective {
+        None => {
+            issues.push(tr("compat-no-pack-format", &[]));
+            CompatibilityStatus::Unknown
+        }
+        Some(range) if range.contains(target) => CompatibilityStatus::Compatible,
+        Some(range) => {
+            issues.push(tr(
+                "compat-wrong-format",
+                &[
+                    ("range", describe_range(&range).into()),
+                    ("version", target_version.into()),
+                    ("target", target.into()),
+                ],
+            ));
+            if pack.supported_formats.is_none() && target >= SUPPORTED_FORMATS_MIN_PACK_FORMAT {
+                issues.push(tr("compat-no-supported-formats", &[]));
+            } else if pack.supported_formats.is_some() && target < SUPPORTED_FORMATS_MIN_PACK_FORMAT
+            {
+                issues.push(tr(
+                    "compat-supported-formats-ignored",
+                    &[("version", target_version.into())],
+                ));
+            }
+
+            if range.max < target {
+                CompatibilityStatus::TooOld
+            } else {
+                CompatibilityStatus::TooNew
+            }
+        }
+    };
+
+    Ok(PackCompatibility {
+        pack_id: pack.id.clone(),
+        target_version: target_version.to_string(),
+        target_pack_format: target,
+        pack_format: pack.pack_format,
+        pack_versions: pack.pack_format.and_then(versions_for_pack_format),
+        supported_formats: pack.supported_formats,
+        status,
+        issues,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_pack(pack_format: Option<u32>, supported_formats: Option<PackFormatRange>) -> PackMeta {
+        PackMeta {
+            id: "pack".to_string(),
+            name: "Pack".to_string(),
+            pack_format,
+            supported_formats,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_pack_format_table_is_ordered() {
+        // The table shared with the frontend lists each format once, oldest
+        // first, with ranges that don't overlap
+        for pair in PACK_FORMATS.windows(2) {
+            let (format, _, last) = pair[0];
+            let (next_format, next_first, _) = pair[1];
+            assert!(format < next_format);
+            assert!(parse_version(last) < parse_version(next_first));
+        }
+        for (_, first, last) in PACK_FORMATS.iter() {
+            assert!(parse_version(first).is_some() && parse_version(first) <= parse_version(last));
+        }
+    }
+
+    #[test]
+    fn test_pack_format_for_version() {
+        assert_eq!(pack_format_for_version("1.8"), Some(1));
+        assert_eq!(pack_format_for_version("1.12.2"), Some(3));
+        assert_eq!(pack_format_for_version("1.20"), Some(15));
+        assert_eq!(pack_format_for_version("1.20.4"), Some(22));
+        assert_eq!(pack_format_for_version("1.21.10"), Some(69));
+        assert_eq!(pack_format_for_version("24w14a"), None);
+        assert_eq!(pack_format_for_version("1.5.2"), None);
+    }
+
+    #[test]
+    fn test_versions_for_pack_format() {
+        assert_eq!(
+            versions_for_pack_format(22).as_deref(),
+            Some("1.20.3 – 1.20.4")
+        );
+        assert_eq!(versions_for_pack_format(46).as_deref(), Some("1.21.4"));
+        assert_eq!(versions_for_pack_format(10), None);
+    }
+
+    #[test]
+    fn test_check_exact_match() {
+        let report = check_pack_compatibility(&make_pack(Some(34), None), "1.21.1").unwrap();
+        assert_eq!(report.status, CompatibilityStatus::Compatible);
+        assert!(report.issues.is_empty());
+        assert_eq!(report.pack_versions.as_deref(), Some("1.21 – 1.21.1"));
+    }
+
+    #[test]
+    fn test_check_too_old_without_supported_formats() {
+        let report = check_pack_compatibility(&make_pack(Some(15), None), "1.21.4").unwrap();
+        assert_eq!(report.status, CompatibilityStatus::TooOld);
+        assert_eq!(report.target_pack_format, 46);
+        assert_eq!(report.issues.len(), 2);
+        assert!(report.issues[1].contains("supported_formats"));
+    }
+
+    #[test]
+    fn test_check_supported_formats_range() {
+        let range = Some(PackFormatRange { min: 18, max: 46 });
+        let pack = make_pack(Some(34), range);
+        assert_eq!(
+            check_pack_compatibility(&pack, "1.21.4").unwrap().status,
+            CompatibilityStatus::Compatible
+        );
+        assert_eq!(
+            check_pack_compatibility(&pack, "1.21.5").unwrap().status,
+            CompatibilityStatus::TooOld
+        );
+        // Versions before 1.20.2 ignore supported_formats
+        let report = check_pack_compatibility(&pack, "1.20.1").unwrap();
+        assert_eq!(report.status, CompatibilityStatus::TooNew);
+        assert!(report.issues[1].contains("ignored before 1.20.2"));
+    }
+
+    #[test]
+    fn test_check_inconsistent_and_unknown() {
+        let pack = make_pack(Some(15), Some(PackFormatRange { min: 18, max: 22 }));
+        let report = check_pack_compatibility(&pack, "1.20.4").unwrap();
+        assert_eq!(report.status, CompatibilityStatus::Compatible);
+        assert!(report.issues[0].contains("does not include"));
+
+        let report = check_pack_compatibility(&make_pack(None, None), "1.20.4").unwrap();
+        assert_eq!(report.status, CompatibilityStatus::Unknown);
+
+        assert!(check_pack_compatibility(&make_pack(Some(15), None), "2.0").is_err());
+    }
+}
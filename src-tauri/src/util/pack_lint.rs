@@ -0,0 +1,763 @@
+/// Linting of a pack's blockstate and model JSON files
+///
+/// The game silently skips or misrenders much of what it can't make sense
+/// of: a misspelt key is ignored, a rotation it doesn't support drops the
+/// model, an element outside its bounds fails the whole model. Each file is
+/// checked as raw JSON, so unknown keys are seen, and every problem is
+/// reported with the file, a line hint and a severity for a problems panel.
+/// Model and parent references are looked up in the pack, then in vanilla.
+use crate::model::PackMeta;
+use crate::util::{asset_indexer, block_models, pack_files};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Keys of a blockstate file
+const BLOCKSTATE_KEYS: &[&str] = &["variants", "multipart"];
+
+/// Keys of a model reference in a blockstate
+const MODEL_REFERENCE_KEYS: &[&str] = &["model", "x", "y", "z", "uvlock", "weight"];
+
+/// Keys of a multipart case
+const MULTIPART_KEYS: &[&str] = &["when", "apply"];
+
+/// Keys of a model file; `credit`, `texture_size` and `groups` are written
+/// by Blockbench and ignored by the game, and `overrides` belongs to items
+const MODEL_KEYS: &[&str] = &[
+    "parent",
+    "textures",
+    "elements",
+    "ambientocclusion",
+    "display",
+    "gui_light",
+    "overrides",
+    "credit",
+    "texture_size",
+    "groups",
+];
+
+/// Keys of a model element; `name` and `color` are written by Blockbench
+const ELEMENT_KEYS: &[&str] = &[
+    "from",
+    "to",
+    "rotation",
+    "faces",
+    "shade",
+    "light_emission",
+    "name",
+    "color",
+];
+
+/// Keys of an element face
+const FACE_KEYS: &[&str] = &["texture", "uv", "rotation", "cullface", "tintindex"];
+
+/// Face directions
+const DIRECTIONS: &[&str] = &["north", "south", "east", "west", "up", "down"];
+
+/// Element coordinates the game accepts
+const ELEMENT_BOUNDS: (f64, f64) = (-16.0, 48.0);
+
+/// Diagnostics are capped per pack so a broken export doesn't flood the panel
+const MAX_DIAGNOSTICS: usize = 5000;
+
+/// How serious a lint finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintSeverity {
+    /// The game rejects or misrenders the file
+    Error,
+    /// Likely a mistake, but the game carries on
+    Warning,
+}
+
+/// One problem found in a file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintDiagnostic {
+    /// Pack-relative path of the file
+    pub file: String,
+    /// 1-based line the problem is most likely on; a hint, as the JSON is
+    /// matched by text rather than parsed with positions
+    pub line: Option<usize>,
+    pub severity: LintSeverity,
+    /// Stable identifier of the check (e.g., "unknown_key", "invalid_rotation")
+    pub code: String,
+    pub message: String,
+}
+
+/// Findings for a whole pack
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintReport {
+    pub pack_id: String,
+    pub blockstates_checked: usize,
+    pub models_checked: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    /// Whether diagnostics were dropped past the cap
+    pub truncated: bool,
+    pub diagnostics: Vec<LintDiagnostic>,
+}
+
+/// What kind of JSON a pack file is, from its path
+enum FileKind {
+    Blockstate,
+    Model,
+}
+
+fn file_kind(path: &str) -> Option<FileKind> {
+    let mut segments = path.split('/');
+    if segments.next() != Some("assets") || !path.ends_with(".json") {
+        return None;
+    }
+    segments.next()?;
+    match segments.next()? {
+        "blockstates" => Some(FileKind::Blockstate),
+        "models" => Some(FileKind::Model),
+        _ => None,
+    }
+}
+
+/// Collects a file's diagnostics, finding line hints in its text
+struct FileLinter<'a> {
+    file: &'a str,
+    text: &'a str,
+    layers: &'a [&'a PackMeta],
+    diagnostics: Vec<LintDiagnostic>,
+}
+
+impl<'a> FileLinter<'a> {
+    /// Line of the `occurrence`th (0-based) appearance of `needle`
+    fn line_of(&self, needle: &str, occurrence: usize) -> Option<usize> {
+        let (offset, _) = self.text.match_indices(needle).nth(occurrence)?;
+        Some(self.text[..offset].matches('\n').count() + 1)
+    }
+
+    fn report(&mut self, severity: LintSeverity, code: &str, line: Option<usize>, message: String) {
+        self.diagnostics.push(LintDiagnostic {
+            file: self.file.to_string(),
+            line,
+            severity,
+            code: code.to_string(),
+            message,
+        });
+    }
+
+    fn check_keys(&mut self, object: &Map<String, Value>, known: &[&str], context: &str) {
+        for key in object.keys() {
+            // "__comment" and similar are a common way to annotate pack JSON
+            if known.contains(&key.as_str()) || key.starts_with("__") {
+                continue;
+            }
+            let line = self.line_of(&format!("\"{}\"", key), 0);
+            self.report(
+                LintSeverity::Warning,
+                "unknown_key",
+                line,
+                format!("Unknown key \"{}\" in {}", key, context),
+            );
+        }
+    }
+
+    /// Whether a model exists in any layer
+    fn model_exists(&self, model_id: &str) -> bool {
+        let relative_path =
+            block_models::model_id_to_path(&block_models::normalize_model_id(model_id));
+        self.layers
+            .iter()
+            .any(|pack| pack_files::pack_entry_exists(pack, &relative_path))
+    }
+
+    fn check_blockstate(&mut self, root: &Map<String, Value>) {
+        self.check_keys(root, BLOCKSTATE_KEYS, "blockstate");
+        if !root.contains_key("variants") && !root.contains_key("multipart") {
+            self.report(
+                LintSeverity::Error,
+                "missing_key",
+                Some(1),
+                "Blockstate has neither \"variants\" nor \"multipart\"".to_string(),
+            );
+        }
+
+        if let Some(variants) = root.get("variants") {
+            match variants.as_object() {
+                Some(variants) => {
+                    for (state, variant) in variants {
+                        self.check_variant(variant, &format!("variant \"{}\"", state));
+                    }
+                }
+                None => self.report(
+                    LintSeverity::Error,
+                    "invalid_type",
+                    self.line_of("\"variants\"", 0),
+                    "\"variants\" must be an object".to_string(),
+                ),
+            }
+        }
+
+        if let Some(multipart) = root.get("multipart") {
+            match multipart.as_array() {
+                Some(cases) => {
+                    for (index, case) in cases.iter().enumerate() {
+                        let context = format!("multipart case {}", index);
+                        let Some(case) = case.as_object() else {
+                            self.report(
+                                LintSeverity::Error,
+                                "invalid_type",
+                                self.line_of("\"apply\"", index),
+                                format!("{} must be an object", context),
+                            );
+                            continue;
+                        };
+                        self.check_keys(case, MULTIPART_KEYS, &context);
+                        match case.get("apply") {
+                            Some(apply) => self.check_variant(apply, &context),
+                            None => self.report(
+                                LintSeverity::Error,
+                                "missing_key",
+                                self.line_of("\"when\"", index),
+                                format!("{} has no \"apply\"", context),
+                            ),
+                        }
+                    }
+                }
+                None => self.report(
+                    LintSeverity::Error,
+                    "invalid_type",
+                    self.line_of("\"multipart\"", 0),
+                    "\"multipart\" must be an array".to_string(),
+                ),
+            }
+        }
+    }
+
+    /// Check a variant: one model reference or a weighted list of them
+    fn check_variant(&mut self, variant: &Value, context: &str) {
+        match variant {
+            Value::Array(references) if references.is_empty() => self.report(
+                LintSeverity::Error,
+                "missing_model",
+                self.line_of(&format!("\"{}\"", context_key(context)), 0),
+                format!("{} lists no models", context),
+            ),
+            Value::Array(references) => {
+                for reference in references {
+                    self.check_model_reference(reference, context);
+                }
+            }
+            reference => self.check_model_reference(reference, context),
+        }
+    }
+
+    fn check_model_reference(&mut self, reference: &Value, context: &str) {
+        let Some(reference) = reference.as_object() else {
+            self.report(
+                LintSeverity::Error,
+                "invalid_type",
+                self.line_of(&format!("\"{}\"", context_key(context)), 0),
+                format!("{} must be a model object", context),
+            );
+            return;
+        };
+        self.check_keys(reference, MODEL_REFERENCE_KEYS, context);
+
+        let Some(model) = reference.get("model").and_then(Value::as_str) else {
+            self.report(
+                LintSeverity::Error,
+                "missing_key",
+                self.line_of(&format!("\"{}\"", context_key(context)), 0),
+                format!("{} has no \"model\"", context),
+            );
+            return;
+        };
+        let model_line = self.line_of(&format!("\"{}\"", model), 0);
+
+        for axis in ["x", "y", "z"] {
+            let Some(value) = reference.get(axis) else {
+                continue;
+            };
+            let valid = value.as_f64().is_some_and(|angle| {
+                angle.rem_euclid(90.0) == 0.0 && (0.0..360.0).contains(&angle)
+            });
+            if !valid {
+                self.report(
+                    LintSeverity::Error,
+                    "invalid_rotation",
+                    model_line,
+                    format!(
+                        "{} rotates {} by {}; only 0, 90, 180 and 270 are allowed",
+                        context, axis, value
+                    ),
+                );
+            }
+        }
+
+        if let Some(weight) = reference.get("weight") {
+            if !weight.as_i64().is_some_and(|weight| weight > 0) {
+                self.report(
+                    LintSeverity::Error,
+                    "invalid_weight",
+                    model_line,
+                    format!(
+                        "{} has weight {}; weights must be at least 1",
+                        context, weight
+                    ),
+                );
+            }
+        }
+
+        if !self.model_exists(model) {
+            self.report(
+                LintSeverity::Error,
+                "missing_model",
+                model_line,
+                format!("{} uses model {}, which doesn't exist", context, model),
+            );
+        }
+    }
+
+    fn check_model(&mut self, root: &Map<String, Value>) {
+        self.check_keys(root, MODEL_KEYS, "model");
+
+        if let Some(parent) = root.get("parent") {
+            let line = self.line_of("\"parent\"", 0);
+            match parent.as_str() {
+                Some(parent)
+                    if block_models::normalize_model_id(parent)
+                        .starts_with("minecraft:builtin/") => {}
+                Some(parent) if !self.model_exists(parent) => self.report(
+                    LintSeverity::Error,
+                    "missing_parent",
+                    line,
+                    format!("Parent model {} doesn't exist", parent),
+                ),
+                Some(_) => {}
+                None => self.report(
+                    LintSeverity::Error,
+                    "invalid_type",
+                    line,
+                    "\"parent\" must be a model ID".to_string(),
+                ),
+            }
+        }
+
+        let Some(elements) = root.get("elements") else {
+            return;
+        };
+        let Some(elements) = elements.as_array() else {
+            self.report(
+                LintSeverity::Error,
+                "invalid_type",
+                self.line_of("\"elements\"", 0),
+                "\"elements\" must be an array".to_string(),
+            );
+            return;
+        };
+        for (index, element) in elements.iter().enumerate() {
+            // Each element has exactly one "from", in order
+            let line = self
+                .line_of("\"from\"", index)
+                .or_else(|| self.line_of("\"elements\"", 0));
+            match element.as_object() {
+                Some(element) => self.check_element(element, index, line),
+                None => self.report(
+                    LintSeverity::Error,
+                    "invalid_type",
+                    line,
+                    format!("Element {} must be an object", index),
+                ),
+            }
+        }
+    }
+
+    fn check_element(&mut self, element: &Map<String, Value>, index: usize, line: Option<usize>) {
+        let context = format!("element {}", index);
+        self.check_keys(element, ELEMENT_KEYS, &context);
+
+        for corner in ["from", "to"] {
+            match number_array::<3>(element.get(corner)) {
+                Some(position) => {
+                    let (min, max) = ELEMENT_BOUNDS;
+                    if position.iter().any(|&v| v < min || v > max) {
+                        self.report(
+                            LintSeverity::Error,
+                            "element_out_of_bounds",
+                            line,
+                            format!(
+                                "Element {} \"{}\" {:?} is outside {} to {}",
+                                index, corner, position, min, max
+                            ),
+                        );
+                    }
+                }
+                None => self.report(
+                    LintSeverity::Error,
+                    "invalid_type",
+                    line,
+                    format!("Element {} needs \"{}\" as three numbers", index, corner),
+                ),
+            }
+        }
+
+        if let Some(rotation) = element.get("rotation") {
+            self.check_element_rotation(rotation, index, line);
+        }
+
+        match element.get("faces").and_then(Value::as_object) {
+            Some(faces) => {
+                for (direction, face) in faces {
+                    self.check_face(index, direction, face, line);
+                }
+            }
+            None => self.report(
+                LintSeverity::Error,
+                "missing_key",
+                line,
+                format!("Element {} has no \"faces\"", index),
+            ),
+        }
+    }
+
+    fn check_element_rotation(&mut self, rotation: &Value, index: usize, line: Option<usize>) {
+        let Some(rotation) = rotation.as_object() else {
+            return self.report(
+                LintSeverity::Error,
+                "invalid_type",
+                line,
+                format!("Element {} \"rotation\" must be an object", index),
+            );
+        };
+        self.check_keys(
+            rotation,
+            &["origin", "axis", "angle", "rescale"],
+            &format!("element {} rotation", index),
+        );
+        let axis = rotation.get("axis").and_then(Value::as_str);
+        if !matches!(axis, Some("x" | "y" | "z")) {
+            self.report(
+                LintSeverity::Error,
+                "invalid_rotation",
+                line,
+                format!("Element {} rotation axis must be x, y or z", index),
+            );
+        }
+        let angle = rotation.get("angle").and_then(Value::as_f64).unwrap_or(0.0);
+        if angle.rem_euclid(22.5) != 0.0 || !(-45.0..=45.0).contains(&angle) {
+            self.report(
+                LintSeverity::Error,
+                "invalid_rotation",
+                line,
+                format!(
+                    "Element {} rotates by {}; only -45, -22.5, 0, 22.5 and 45 are allowed",
+                    index, angle
+                ),
+            );
+        }
+    }
+
+    fn check_face(&mut self, index: usize, direction: &str, face: &Value, line: Option<usize>) {
+        let context = format!("element {} face {}", index, direction);
+        if !DIRECTIONS.contains(&direction) {
+            self.report(
+                LintSeverity::Warning,
+                "unknown_key",
+                line,
+                format!("Element {} has a face named \"{}\"", index, direction),
+            );
+        }
+        let Some(face) = face.as_object() else {
+            return self.report(
+                LintSeverity::Error,
+                "invalid_type",
+                line,
+                format!("{} must be an object", context),
+            );
+        };
+        self.check_keys(face, FACE_KEYS, &context);
+
+        if face.get("texture").and_then(Value::as_str).is_none() {
+            self.report(
+                LintSeverity::Error,
+                "missing_key",
+                line,
+                format!("{} has no \"texture\"", context),
+            );
+        }
+
+        if let Some(uv) = face.get("uv") {
+            match number_array::<4>(Some(uv)) {
+                Some(uv) if uv.iter().any(|&v| !(0.0..=16.0).contains(&v)) => self.report(
+                    LintSeverity::Error,
+                    "uv_out_of_range",
+                    line,
+                    format!("{} UV {:?} is outside 0 to 16", context, uv),
+                ),
+                Some(_) => {}
+                None => self.report(
+                    LintSeverity::Error,
+                    "invalid_type",
+                    line,
+                    format!("{} \"uv\" must be four numbers", context),
+                ),
+            }
+        }
+
+        if let Some(rotation) = face.get("rotation") {
+            let valid = rotation.as_f64().is_some_and(|angle| {
+                angle.rem_euclid(90.0) == 0.0 && (0.0..360.0).contains(&angle)
+            });
+            if !valid {
+                self.report(
+                    LintSeverity::Error,
+                    "invalid_rotation",
+                    line,
+                    format!(
+                        "{} rotates its UV by {}; only 0, 90, 180 and 270 are allowed",
+                        context, rotation
+                    ),
+                );
+            }
+        }
+
+        if let Some(cullface) = face.get("cullface").and_then(Value::as_str) {
+            if !DIRECTIONS.contains(&cullface) && cullface != "bottom" {
+                self.report(
+                    LintSeverity::Warning,
+                    "invalid_cullface",
+                    line,
+                    format!("{} culls against unknown face \"{}\"", context, cullface),
+                );
+            }
+        }
+    }
+}
+
+/// The quoted key a context string names ("variant \"facing=north\"" -> "facing=north")
+fn context_key(context: &str) -> &str {
+    context.split('"').nth(1).unwrap_or(context)
+}
+
+/// A JSON array of exactly N numbers
+fn number_array<const N: usize>(value: Option<&Value>) -> Option<[f64; N]> {
+    let values = value?.as_array()?;
+    if values.len() != N {
+        return None;
+    }
+    let mut numbers = [0.0; N];
+    for (number, value) in numbers.iter_mut().zip(values) {
+        *number = value.as_f64()?;
+    }
+    Some(numbers)
+}
+
+/// Lint one file's text
+///
+/// # Arguments
+/// * `file` - Pack-relative path, which decides whether it is a blockstate or model
+/// * `text` - The file's contents
+/// * `layers` - Packs that referenced models are looked up in, the linted pack first
+pub fn lint_file(file: &str, text: &str, layers: &[&PackMeta]) -> Vec<LintDiagnostic> {
+    let Some(kind) = file_kind(file) else {
+        return Vec::new();
+    };
+    let mut linter = FileLinter {
+        file,
+        text,
+        layers,
+        diagnostics: Vec::new(),
+    };
+
+    let root: Value = match serde_json::from_str(text.trim_start_matches('\u{feff}')) {
+        Ok(root) => root,
+        Err(e) => {
+            linter.report(
+                LintSeverity::Error,
+                "invalid_json",
+                Some(e.line()).filter(|&line| line > 0),
+                format!("Invalid JSON: {}", e),
+            );
+            return linter.diagnostics;
+        }
+    };
+    let Some(root) = root.as_object() else {
+        linter.report(
+            LintSeverity::Error,
+            "invalid_type",
+            Some(1),
+            "File must contain a JSON object".to_string(),
+        );
+        return linter.diagnostics;
+    };
+
+    match kind {
+        FileKind::Blockstate => linter.check_blockstate(root),
+        FileKind::Model => linter.check_model(root),
+    }
+    linter.diagnostics
+}
+
+/// Lint every blockstate and model JSON file in a pack
+///
+/// # Arguments
+/// * `pack` - The resource pack to lint
+/// * `vanilla_pack` - Vanilla assets that model references may point into
+pub fn lint_pack(pack: &PackMeta, vanilla_pack: &PackMeta) -> Result<LintReport> {
+    let layers = [pack, vanilla_pack];
+    let mut files: Vec<String> = asset_indexer::list_pack_files(pack)?
+        .into_iter()
+        .filter(|path| file_kind(path).is_some())
+        .collect();
+    files.sort();
+
+    let mut report = LintReport {
+        pack_id: pack.id.clone(),
+        blockstates_checked: 0,
+        models_checked: 0,
+        errors: 0,
+        warnings: 0,
+        truncated: false,
+        diagnostics: Vec::new(),
+    };
+
+    for file in &files {
+        match file_kind(file) {
+            Some(FileKind::Blockstate) => report.blockstates_checked += 1,
+            Some(FileKind::Model) => report.models_checked += 1,
+            None => continue,
+        }
+        let diagnostics = match pack_files::read_pack_entry(pack, file) {
+            Ok(bytes) => lint_file(file, &String::from_utf8_lossy(&bytes), &layers),
+            Err(e) => vec![LintDiagnostic {
+                file: file.clone(),
+                line: None,
+                severity: LintSeverity::Error,
+                code: "unreadable".to_string(),
+                message: format!("Failed to read {}: {}", file, e),
+            }],
+        };
+        for diagnostic in diagnostics {
+            match diagnostic.severity {
+                LintSeverity::Error => report.errors += 1,
+                LintSeverity::Warning => report.warnings += 1,
+            }
+            if report.diagnostics.len() < MAX_DIAGNOSTICS {
+                report.diagnostics.push(diagnostic);
+            } else {
+                report.truncated = true;
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_lint_model() {
+        let temp_dir = std::env::temp_dir().join("test_pack_lint_model");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let models = temp_dir.join("pack/assets/minecraft/models/block");
+        fs::create_dir_all(&models).unwrap();
+        fs::write(models.join("base.json"), "{}").unwrap();
+        let pack = PackMeta {
+            id: "pack".to_string(),
+            name: "pack".to_string(),
+            path: temp_dir.join("pack").to_string_lossy().to_string(),
+            ..Default::default()
+        };
+
+        let text = r##"{
+  "parent": "block/base",
+  "textures": {"all": "block/stone"},
+  "elemnts": [],
+  "elements": [
+    {
+      "from": [0, 0, 0],
+      "to": [16, 16, 16],
+      "faces": {"north": {"texture": "#all"}}
+    },
+    {
+      "from": [0, -20, 0],
+      "to": [8, 8, 8],
+      "rotation": {"origin": [8, 8, 8], "axis": "y", "angle": 30},
+      "faces": {"up": {"texture": "#all", "uv": [0, 0, 17, 16], "rotation": 45}}
+    }
+  ]
+}"##;
+        let diagnostics = lint_file("assets/minecraft/models/block/test.json", text, &[&pack]);
+        let broken_parent = lint_file(
+            "assets/minecraft/models/block/other.json",
+            r#"{"parent": "block/nonexistent"}"#,
+            &[&pack],
+        );
+        let invalid = lint_file(
+            "assets/minecraft/models/block/bad.json",
+            "{\n  \"a\": ,\n}",
+            &[],
+        );
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let codes: Vec<(&str, Option<usize>)> = diagnostics
+            .iter()
+            .map(|d| (d.code.as_str(), d.line))
+            .collect();
+        assert_eq!(
+            codes,
+            vec![
+                ("unknown_key", Some(4)),
+                ("element_out_of_bounds", Some(12)),
+                ("invalid_rotation", Some(12)),
+                ("uv_out_of_range", Some(12)),
+                ("invalid_rotation", Some(12)),
+            ]
+        );
+        assert_eq!(diagnostics[0].severity, LintSeverity::Warning);
+        assert_eq!(broken_parent.len(), 1);
+        assert_eq!(broken_parent[0].code, "missing_parent");
+        assert_eq!(invalid[0].code, "invalid_json");
+        assert_eq!(invalid[0].line, Some(2));
+    }
+
+    #[test]
+    fn test_lint_pack_blockstates() {
+        let temp_dir = std::env::temp_dir().join("test_pack_lint_blockstates");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let blockstates = temp_dir.join("pack/assets/minecraft/blockstates");
+        let models = temp_dir.join("vanilla/assets/minecraft/models/block");
+        fs::create_dir_all(&blockstates).unwrap();
+        fs::create_dir_all(&models).unwrap();
+        fs::write(models.join("lamp.json"), "{}").unwrap();
+        fs::write(
+            blockstates.join("lamp.json"),
+            r#"{
+  "variants": {
+    "lit=false": {"model": "block/lamp", "y": 45},
+    "lit=true": [{"model": "block/lamp_on", "wieght": 2}]
+  }
+}"#,
+        )
+        .unwrap();
+        let make_pack = |id: &str| PackMeta {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: temp_dir.join(id).to_string_lossy().to_string(),
+            ..Default::default()
+        };
+
+        let report = lint_pack(&make_pack("pack"), &make_pack("vanilla")).unwrap();
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(report.blockstates_checked, 1);
+        assert_eq!(report.errors, 2);
+        assert_eq!(report.warnings, 1);
+        let by_code = |code: &str| report.diagnostics.iter().find(|d| d.code == code).unwrap();
+        assert_eq!(by_code("invalid_rotation").line, Some(3));
+        assert_eq!(by_code("missing_model").line, Some(4));
+        assert_eq!(by_code("unknown_key").line, Some(4));
+    }
+}
@@ -0,0 +1,277 @@
+/// JSON-level merging of resource files that the game combines across packs
+///
+/// Some files are not winner-takes-all in game: every pack in the stack
+/// contributes to them. Copying only the winning pack's file into the nest would
+/// drop whatever the lower-priority packs added, so the nest builder merges them
+/// with the same rules the game uses.
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// A translation key that more than one pack defines with different text
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LangKeyOverride {
+    pub key: String,
+    /// Pack whose text ends up in the merged file
+    pub winner: String,
+    /// Lower-priority packs whose text was replaced, highest first
+    pub overridden: Vec<String>,
+}
+
+/// Overridden keys for one merged lang file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LangMergeReport {
+    /// Pack-relative path (e.g., "assets/minecraft/lang/en_us.json")
+    pub file: String,
+    pub overrides: Vec<LangKeyOverride>,
+}
+
+/// Merge sounds.json files the way the game stacks them
+///
+/// `layers` are ordered lowest priority first. For each sound event, a layer
+/// with `"replace": true` discards the sounds collected so far; otherwise its
+/// sounds are appended. As in game, the `subtitle` (and anything else besides
+/// the sounds) comes from the layer that created or last replaced the event;
+/// appending layers can't change it. A merged event keeps `"replace": true`
+/// when any contributing layer replaced, so it still replaces vanilla's sounds.
+pub fn merge_sounds_json(layers: &[Value]) -> Value {
+    let mut merged = Map::new();
+
+    for layer in layers {
+        let Some(events) = layer.as_object() else {
+            continue;
+        };
+
+        for (event_name, event) in events {
+            let Some(event) = event.as_object() else {
+                continue;
+            };
+
+            let replaces = event.get("replace").and_then(Value::as_bool) == Some(true);
+            let existing = match merged.get_mut(event_name) {
+                Some(Value::Object(existing)) if !replaces => existing,
+                _ => {
+                    merged.insert(event_name.clone(), Value::Object(event.clone()));
+                    continue;
+                }
+            };
+
+            if let Some(added) = event.get("sounds").and_then(Value::as_array) {
+                let sounds = existing
+                    .entry("sounds")
+                    .or_insert_with(|| Value::Array(Vec::new()));
+                if let Some(sounds) = sounds.as_array_mut() {
+                    sounds.extend(added.iter().cloned());
+                }
+            }
+        }
+    }
+
+    Value::Object(merged)
+}
+
+/// Merge atlases/*.json files the way the game stacks them
+///
+/// `layers` are ordered lowest priority first. The game runs every pack's atlas
+/// sources in stack order, so the merged `sources` array is the concatenation of
+/// all layers. Other top-level fields come from the highest layer that sets them.
+pub fn merge_atlas_json(layers: &[Value]) -> Value {
+    let mut merged = Map::new();
+    let mut sources = Vec::new();
+
+    for layer in layers {
+        let Some(atlas) = layer.as_object() else {
+            continue;
+        };
+
+        for (key, value) in atlas {
+            if key == "sources" {
+                if let Some(added) = value.as_array() {
+                    sources.extend(added.iter().cloned());
+                }
+            } else {
+                merged.insert(key.clone(), value.clone());
+            }
+        }
+    }
+
+    merged.insert("sources".to_string(), Value::Array(sources));
+    Value::Object(merged)
+}
+
+/// Merge lang/*.json files key by key
+///
+/// `layers` are (pack name, file) pairs ordered lowest priority first. Keys from
+/// all packs are kept and the highest-priority pack wins each key. Returns the
+/// merged file plus every key some lower pack gave a different text than the
+/// winner, sorted by key; packs whose text matches the winner's aren't counted
+/// as overridden.
+pub fn merge_lang_json(layers: &[(String, Value)]) -> (Value, Vec<LangKeyOverride>) {
+    let mut merged = Map::new();
+    // Key -> (pack, text) of every layer defining it, lowest priority first
+    let mut definitions: BTreeMap<&str, Vec<(&str, &Value)>> = BTreeMap::new();
+
+    for (pack, layer) in layers {
+        let Some(entries) = layer.as_object() else {
+            continue;
+        };
+
+        for (key, text) in entries {
+            merged.insert(key.clone(), text.clone());
+            definitions
+                .entry(key.as_str())
+                .or_default()
+                .push((pack.as_str(), text));
+        }
+    }
+
+    let overrides = definitions
+        .into_iter()
+        .filter_map(|(key, definitions)| {
+            let ((winner, text), lower) = definitions.split_last()?;
+            let overridden: Vec<String> = lower
+                .iter()
+                .rev()
+                .filter(|(_, lower_text)| lower_text != text)
+                .map(|(pack, _)| pack.to_string())
+                .collect();
+            (!overridden.is_empty()).then(|| LangKeyOverride {
+                key: key.to_string(),
+                winner: winner.to_string(),
+                overridden,
+            })
+        })
+        .collect();
+
+    (Value::Object(merged), overrides)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_merge_sounds_json_appends_events() {
+        let low = json!({
+            "block.note_block.harp": {"sounds": ["custom/harp1"]},
+            "entity.cow.ambient": {"sounds": ["custom/moo"], "subtitle": "low.moo"}
+        });
+        let high = json!({
+            "block.note_block.harp": {"sounds": ["custom/harp2"]},
+            "entity.cow.ambient": {"sounds": [{"name": "custom/moo2", "volume": 0.5}], "subtitle": "high.moo"},
+            "custom.event": {"sounds": ["custom/new"]}
+        });
+
+        let merged = merge_sounds_json(&[low, high]);
+
+        assert_eq!(
+            merged["block.note_block.harp"]["sounds"],
+            json!(["custom/harp1", "custom/harp2"])
+        );
+        assert_eq!(
+            merged["entity.cow.ambient"]["sounds"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+        // Appending doesn't change the subtitle the event was created with
+        assert_eq!(merged["entity.cow.ambient"]["subtitle"], "low.moo");
+        assert_eq!(merged["custom.event"]["sounds"], json!(["custom/new"]));
+    }
+
+    #[test]
+    fn test_merge_sounds_json_honors_replace() {
+        let low = json!({
+            "ambient.cave": {"sounds": ["custom/cave1"], "subtitle": "low.cave"},
+            "music.menu": {"replace": true, "sounds": ["custom/menu1"], "subtitle": "low.menu"}
+        });
+        let high = json!({
+            "ambient.cave": {"replace": true, "sounds": ["custom/cave2"]},
+            "music.menu": {"sounds": ["custom/menu2"], "subtitle": "high.menu"}
+        });
+
+        let merged = merge_sounds_json(&[low, high]);
+
+        // The higher layer's replace drops the lower layer's sounds
+        assert_eq!(merged["ambient.cave"]["sounds"], json!(["custom/cave2"]));
+        assert_eq!(merged["ambient.cave"]["replace"], true);
+        // Replacing recreates the event, without the lower layer's subtitle
+        assert!(merged["ambient.cave"].get("subtitle").is_none());
+        // A lower replace still applies to vanilla after the higher layer appends
+        assert_eq!(
+            merged["music.menu"]["sounds"],
+            json!(["custom/menu1", "custom/menu2"])
+        );
+        assert_eq!(merged["music.menu"]["replace"], true);
+        assert_eq!(merged["music.menu"]["subtitle"], "low.menu");
+    }
+
+    #[test]
+    fn test_merge_atlas_json_concatenates_sources() {
+        let low =
+            json!({"sources": [{"type": "directory", "source": "block", "prefix": "block/"}]});
+        let high = json!({"sources": [{"type": "directory", "source": "ctm", "prefix": "ctm/"}]});
+
+        let merged = merge_atlas_json(&[low, json!({}), high]);
+
+        assert_eq!(
+            merged["sources"],
+            json!([
+                {"type": "directory", "source": "block", "prefix": "block/"},
+                {"type": "directory", "source": "ctm", "prefix": "ctm/"}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_merge_lang_json() {
+        let layers = vec![
+            (
+                "Textures".to_string(),
+                json!({"block.minecraft.stone": "Stone", "item.minecraft.stick": "Stick"}),
+            ),
+            (
+                "Names".to_string(),
+                json!({"item.minecraft.stick": "Twig", "custom.key": "Custom"}),
+            ),
+            (
+                "Top".to_string(),
+                json!({"item.minecraft.stick": "Branch", "block.minecraft.stone": "Stone"}),
+            ),
+            (
+                "Pebbles".to_string(),
+                json!({"item.minecraft.stick": "Branch", "block.minecraft.stone": "Rock"}),
+            ),
+            (
+                "Cobbles".to_string(),
+                json!({"block.minecraft.stone": "Stone"}),
+            ),
+        ];
+
+        let (merged, overrides) = merge_lang_json(&layers);
+
+        assert_eq!(merged["item.minecraft.stick"], "Branch");
+        assert_eq!(merged["custom.key"], "Custom");
+        assert_eq!(merged["block.minecraft.stone"], "Stone");
+        // Only packs whose text differs from the winner's count as overridden
+        assert_eq!(
+            overrides,
+            vec![
+                LangKeyOverride {
+                    key: "block.minecraft.stone".to_string(),
+                    winner: "Cobbles".to_string(),
+                    overridden: vec!["Pebbles".to_string()],
+                },
+                LangKeyOverride {
+                    key: "item.minecraft.stick".to_string(),
+                    winner: "Pebbles".to_string(),
+                    overridden: vec!["Names".to_string(), "Textures".to_string()],
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,147 @@
+/// Sanity checks that quarantine suspicious packs during a scan
+///
+/// Packs are often downloaded from rehost sites that bundle malware, and a
+/// crafted ZIP can attack the tools that open it. A pack containing programs,
+/// Java classes, entries that expand absurdly (zip bombs) or entries whose path
+/// escapes the pack (zip slip) is flagged with the reasons found and left out
+/// of indexing, so none of its files are read, previewed or copied into a nest.
+use crate::model::QuarantineReason;
+use crate::util::pack_files;
+use std::fs;
+use std::path::Path;
+use zip::ZipArchive;
+
+/// Extensions of native programs and scripts, which no resource pack needs
+const EXECUTABLE_EXTENSIONS: &[&str] = &[
+    "exe", "dll", "msi", "com", "scr", "bat", "cmd", "ps1", "vbs", "lnk", "jar", "sh", "so",
+    "dylib",
+];
+
+/// Ratio of expanded to compressed size above which an entry is a zip bomb;
+/// text compresses around 10:1 and solid-colour PNGs well under 100:1
+const MAX_COMPRESSION_RATIO: u64 = 200;
+
+/// Entries smaller than this are never flagged for their ratio
+const MIN_RATIO_CHECK_SIZE: u64 = 1024 * 1024;
+
+/// Reasons are capped so a pack full of classes doesn't flood the scan result
+const MAX_REASONS: usize = 20;
+
+/// Reason a file found in the pack gives for quarantining it
+fn file_reason(path: &str) -> Option<QuarantineReason> {
+    let extension = Path::new(path)
+        .extension()?
+        .to_string_lossy()
+        .to_ascii_lowercase();
+    if extension == "class" {
+        Some(QuarantineReason::ClassFile {
+            path: path.to_string(),
+        })
+    } else if EXECUTABLE_EXTENSIONS.contains(&extension.as_str()) {
+        Some(QuarantineReason::Executable {
+            path: path.to_string(),
+        })
+    } else {
+        None
+    }
+}
+
+/// Whether a ZIP entry name stays inside the pack once extracted; Windows
+/// separators and drive letters count, as the pack may be extracted there
+fn is_enclosed(name: &str) -> bool {
+    pack_files::safe_entry_path(name).is_ok()
+}
+
+/// Check a ZIP pack's entries, from the central directory only, so nothing is
+/// decompressed
+pub fn check_zip(archive: &mut ZipArchive<fs::File>) -> Vec<QuarantineReason> {
+    let mut reasons = Vec::new();
+    for index in 0..archive.len() {
+        if reasons.len() >= MAX_REASONS {
+            break;
+        }
+        let Ok(entry) = archive.by_index_raw(index) else {
+            continue;
+        };
+        let name = entry.name().to_string();
+        if !is_enclosed(&name) {
+            reasons.push(QuarantineReason::UnsafePath { path: name });
+            continue;
+        }
+        if entry.is_dir() {
+            continue;
+        }
+        if let Some(reason) = file_reason(&name) {
+            reasons.push(reason);
+            continue;
+        }
+        let ratio = entry.size() / entry.compressed_size().max(1);
+        if entry.size() >= MIN_RATIO_CHECK_SIZE && ratio > MAX_COMPRESSION_RATIO {
+            reasons.push(QuarantineReason::CompressionRatio { path: name, ratio });
+        }
+    }
+    reasons
+}
+
+/// Check a directory pack's files
+pub fn check_dir(dir_path: &Path) -> Vec<QuarantineReason> {
+    pack_files::pack_dir_files(dir_path)
+        .filter_map(|entry| {
+            let relative = entry.path().strip_prefix(dir_path).ok()?;
+            file_reason(&relative.to_string_lossy().replace('\\', "/"))
+        })
+        .take(MAX_REASONS)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_check_zip() {
+        let temp_dir = std::env::temp_dir().join("test_pack_safety_zip");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+        let zip_path = temp_dir.join("pack.zip");
+
+        let mut writer = zip::ZipWriter::new(fs::File::create(&zip_path).unwrap());
+        let deflate =
+            zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+        for (name, content) in [
+            ("pack.mcmeta", b"{}".to_vec()),
+            ("assets/minecraft/textures/block/stone.png", b"png".to_vec()),
+            ("../../startup/evil.png", b"png".to_vec()),
+            ("tools/Setup.EXE", b"MZ".to_vec()),
+            ("net/example/Payload.class", vec![0xCA, 0xFE, 0xBA, 0xBE]),
+            ("assets/minecraft/bomb.txt", vec![0; 4 * 1024 * 1024]),
+        ] {
+            writer.start_file(name, deflate).unwrap();
+            writer.write_all(&content).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let mut archive = ZipArchive::new(fs::File::open(&zip_path).unwrap()).unwrap();
+        let reasons = check_zip(&mut archive);
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(reasons.len(), 4);
+        assert_eq!(
+            reasons[0],
+            QuarantineReason::UnsafePath {
+                path: "../../startup/evil.png".to_string()
+            }
+        );
+        assert!(
+            matches!(&reasons[1], QuarantineReason::Executable { path } if path == "tools/Setup.EXE")
+        );
+        assert!(matches!(&reasons[2], QuarantineReason::ClassFile { .. }));
+        assert!(
+            matches!(&reasons[3], QuarantineReason::CompressionRatio { ratio, .. } if *ratio > 200)
+        );
+        assert!(!is_enclosed("C:\\Windows\\evil.dll"));
+        assert!(!is_enclosed("/etc/passwd"));
+        assert!(is_enclosed("./assets/minecraft/lang/en_us.json"));
+    }
+}
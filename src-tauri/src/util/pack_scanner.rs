@@ -1,21 +1,71 @@
 /// Scan a directory for resource packs (both .zip and uncompressed folders)
-use crate::model::PackMeta;
+use crate::model::{
+    DuplicatePacks, PackCredits, PackFormatRange, PackMeta, PackOverlay, PackScanError,
+    QuarantineReason,
+};
+use crate::util::{
+    cache_location, cem_dialect, pack_credits, pack_files, pack_safety, texture_info,
+};
 use anyhow::Result;
 use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::fs;
 use std::io::Read;
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
+use std::time::{Duration, UNIX_EPOCH};
+use tracing::{info, warn};
 use zip::ZipArchive;
 
+/// Fields read from a pack's pack.mcmeta
+#[derive(Debug, Default)]
+struct McmetaInfo {
+    description: Option<String>,
+    pack_format: Option<u32>,
+    supported_formats: Option<PackFormatRange>,
+    overlays: Vec<PackOverlay>,
+    features: Vec<String>,
+}
+
+/// What a scan reads from a pack besides its file listing
+///
+/// A quarantined pack gets nothing but its reasons; none of its files are read.
+#[derive(Debug, Default)]
+struct PackDetails {
+    mcmeta: McmetaInfo,
+    has_icon: bool,
+    credits: Option<PackCredits>,
+    quarantine: Vec<QuarantineReason>,
+}
+
+/// Folder under the local cache that repaired copies of damaged ZIPs go in
+const REPAIRED_DIR_NAME: &str = "repaired_packs";
+
+/// A ZIP written to this recently is taken to be still downloading
+const DOWNLOAD_GRACE: Duration = Duration::from_secs(60);
+
 enum PackEntry {
     Zip(PathBuf, String, u64), // path, name, size
     Dir(PathBuf, String),      // path, name
 }
 
 /// Scan a directory for resource packs (.zip files and uncompressed folders)
+///
+/// Packs that are symlinks or junctions are followed and their target recorded
+/// in `PackMeta::resolved_path`, unless the target holds the packs folder
+/// itself; links inside a folder pack are followed as far as they stay in the
+/// pack (see `pack_files::pack_dir_files`).
 pub fn scan_packs(packs_dir: &str) -> Result<Vec<PackMeta>> {
-    println!("[scan_packs] Starting PARALLEL scan of: {}", packs_dir);
+    Ok(scan_packs_with_errors(packs_dir)?.0)
+}
+
+/// Scan a directory for resource packs, also returning the ZIPs that couldn't
+/// be read as they are
+///
+/// A damaged ZIP never fails the scan: it is repaired into a copy the pack is
+/// scanned from, or left out, and reported either way.
+pub fn scan_packs_with_errors(packs_dir: &str) -> Result<(Vec<PackMeta>, Vec<PackScanError>)> {
+    info!("Starting PARALLEL scan of: {}", packs_dir);
     let path = Path::new(packs_dir);
 
     if !path.exists() {
@@ -28,8 +78,9 @@ pub fn scan_packs(packs_dir: &str) -> Result<Vec<PackMeta>> {
 
     // First pass: collect all pack entries
     let mut pack_entries = Vec::new();
+    let resolved_packs_dir = path.canonicalize()?;
 
-    println!("[scan_packs] Reading directory entries...");
+    info!("Reading directory entries...");
     for entry in fs::read_dir(path)? {
         let entry = entry?;
         let entry_path = entry.path();
@@ -41,9 +92,22 @@ pub fn scan_packs(packs_dir: &str) -> Result<Vec<PackMeta>> {
             continue;
         }
 
+        // A pack linked to a folder holding the packs folder would contain
+        // itself
+        if resolved_link_target(&entry_path)
+            .is_some_and(|target| resolved_packs_dir.starts_with(target))
+        {
+            warn!(
+                "Skipping {}: it links back to the packs folder",
+                file_name_str
+            );
+            continue;
+        }
+
         // Check for .zip files
         if entry_path.is_file() && entry_path.extension().map_or(false, |ext| ext == "zip") {
-            if let Ok(metadata) = entry.metadata() {
+            // Sized through any link, unlike entry.metadata()
+            if let Ok(metadata) = fs::metadata(&entry_path) {
                 pack_entries.push(PackEntry::Zip(
                     entry_path.clone(),
                     file_name_str.clone(),
@@ -61,119 +125,308 @@ pub fn scan_packs(packs_dir: &str) -> Result<Vec<PackMeta>> {
         }
     }
 
-    println!(
-        "[scan_packs] Found {} packs, extracting metadata in PARALLEL",
+    info!(
+        "Found {} packs, extracting metadata in PARALLEL",
         pack_entries.len()
     );
 
     // Second pass: extract metadata in parallel
-    let packs: Vec<PackMeta> = pack_entries
+    let results: Vec<(Option<PackMeta>, Option<PackScanError>)> = pack_entries
         .par_iter()
-        .filter_map(|entry| match entry {
+        .map(|entry| match entry {
             PackEntry::Zip(entry_path, file_name_str, size) => {
-                println!("[scan_packs] Processing ZIP: {}", file_name_str);
-                let (description, icon_data, pack_format) =
-                    extract_pack_metadata_from_zip(entry_path);
-
-                Some(PackMeta {
-                    id: file_name_str.clone(),
-                    name: file_name_str.trim_end_matches(".zip").to_string(),
-                    path: entry_path.to_string_lossy().to_string(),
-                    size: *size,
-                    is_zip: true,
-                    description,
-                    icon_data,
-                    pack_format,
-                })
+                info!("Processing ZIP: {}", file_name_str);
+                scan_zip_pack(entry_path, file_name_str, *size)
             }
             PackEntry::Dir(entry_path, file_name_str) => {
-                println!("[scan_packs] Processing directory: {}", file_name_str);
+                info!("Processing directory: {}", file_name_str);
                 let size = calculate_dir_size(entry_path);
-                let (description, icon_data, pack_format) =
-                    extract_pack_metadata_from_dir(entry_path);
-
-                Some(PackMeta {
+                let details = extract_pack_metadata_from_dir(entry_path);
+                let texture_resolution = details
+                    .quarantine
+                    .is_empty()
+                    .then(|| texture_info::dominant_block_resolution(entry_path, false))
+                    .flatten();
+
+                let mut pack = PackMeta {
                     id: file_name_str.clone(),
                     name: file_name_str.clone(),
                     path: entry_path.to_string_lossy().to_string(),
                     size,
                     is_zip: false,
-                    description,
-                    icon_data,
-                    pack_format,
-                })
+                    description: details.mcmeta.description,
+                    has_icon: details.has_icon,
+                    pack_format: details.mcmeta.pack_format,
+                    supported_formats: details.mcmeta.supported_formats,
+                    overlays: details.mcmeta.overlays,
+                    features: details.mcmeta.features,
+                    texture_resolution,
+                    cem_dialect: None,
+                    builtin: false,
+                    credits: details.credits,
+                    quarantine: details.quarantine,
+                    resolved_path: resolved_link_target(entry_path)
+                        .map(|target| target.to_string_lossy().to_string()),
+                };
+                if pack.quarantine.is_empty() {
+                    pack.cem_dialect = cem_dialect::pack_dialect(&pack);
+                }
+                (Some(pack), None)
             }
         })
         .collect();
+    let (packs, errors): (Vec<_>, Vec<_>) = results.into_iter().unzip();
+    let mut errors: Vec<PackScanError> = errors.into_iter().flatten().collect();
+    errors.sort_by(|a, b| a.pack_id.cmp(&b.pack_id));
 
     // Sort packs by name for consistent ordering
-    let mut sorted_packs = packs;
+    let mut sorted_packs: Vec<PackMeta> = packs.into_iter().flatten().collect();
     sorted_packs.sort_by(|a, b| a.name.cmp(&b.name));
 
-    println!("[scan_packs] Found {} packs total:", sorted_packs.len());
+    info!("Found {} packs total:", sorted_packs.len());
     for pack in &sorted_packs {
-        println!("[scan_packs]   - {} (is_zip: {})", pack.name, pack.is_zip);
+        info!("- {} (is_zip: {})", pack.name, pack.is_zip);
+        if !pack.quarantine.is_empty() {
+            info!(
+                "Quarantined, {} suspicious file(s), first: {:?}",
+                pack.quarantine.len(),
+                pack.quarantine[0]
+            );
+        }
+    }
+
+    for error in &errors {
+        warn!("Damaged ZIP {}: {}", error.pack_id, error.reason);
+    }
+
+    Ok((sorted_packs, errors))
+}
+
+/// Scan one ZIP pack, repairing it first if its central directory is damaged
+///
+/// Returns the pack, unless it couldn't be read at all, and the problem found
+/// with the archive, if any.
+fn scan_zip_pack(
+    zip_path: &Path,
+    file_name: &str,
+    size: u64,
+) -> (Option<PackMeta>, Option<PackScanError>) {
+    let (pack_path, archive, error) = match open_zip(zip_path) {
+        Ok(archive) => (zip_path.to_path_buf(), archive, None),
+        Err(reason) => match repair_or_requeue(zip_path, file_name, reason) {
+            Ok((repaired_path, archive, error)) => (repaired_path, archive, Some(error)),
+            Err(error) => return (None, Some(error)),
+        },
+    };
+
+    let details = extract_pack_metadata_from_zip(archive);
+    let texture_resolution = details
+        .quarantine
+        .is_empty()
+        .then(|| texture_info::dominant_block_resolution(&pack_path, true))
+        .flatten();
+
+    let mut pack = PackMeta {
+        id: file_name.to_string(),
+        name: file_name.trim_end_matches(".zip").to_string(),
+        path: pack_path.to_string_lossy().to_string(),
+        size,
+        is_zip: true,
+        description: details.mcmeta.description,
+        has_icon: details.has_icon,
+        pack_format: details.mcmeta.pack_format,
+        supported_formats: details.mcmeta.supported_formats,
+        overlays: details.mcmeta.overlays,
+        features: details.mcmeta.features,
+        texture_resolution,
+        cem_dialect: None,
+        builtin: false,
+        credits: details.credits,
+        quarantine: details.quarantine,
+        resolved_path: resolved_link_target(zip_path)
+            .map(|target| target.to_string_lossy().to_string()),
+    };
+    if pack.quarantine.is_empty() {
+        pack.cem_dialect = cem_dialect::pack_dialect(&pack);
+    }
+    (Some(pack), error)
+}
+
+/// Open a ZIP, returning why it can't be read on failure
+fn open_zip(zip_path: &Path) -> std::result::Result<ZipArchive<fs::File>, String> {
+    let file = fs::File::open(zip_path).map_err(|e| e.to_string())?;
+    ZipArchive::new(file).map_err(|e| e.to_string())
+}
+
+/// Recover a ZIP whose central directory can't be read
+///
+/// A file written to within the last minute is most likely still downloading,
+/// so it is left for a later scan. Otherwise the entries still readable are
+/// rebuilt into a copy under the cache, reused while the original stays
+/// unchanged, and the pack is scanned from that copy.
+fn repair_or_requeue(
+    zip_path: &Path,
+    file_name: &str,
+    reason: String,
+) -> std::result::Result<(PathBuf, ZipArchive<fs::File>, PackScanError), PackScanError> {
+    let mut error = PackScanError {
+        pack_id: file_name.to_string(),
+        path: zip_path.to_string_lossy().to_string(),
+        reason,
+        repaired_path: None,
+        retry: false,
+    };
+    let metadata = fs::metadata(zip_path).ok();
+    let modified = metadata
+        .as_ref()
+        .and_then(|metadata| metadata.modified().ok());
+    if modified.is_some_and(|modified| {
+        modified
+            .elapsed()
+            .is_ok_and(|elapsed| elapsed < DOWNLOAD_GRACE)
+    }) {
+        error.retry = true;
+        return Err(error);
+    }
+
+    // Keyed on the original's size and modification time, so a replaced
+    // download gets repaired afresh
+    let stem = file_name.trim_end_matches(".zip");
+    let version = format!(
+        "{}-{}",
+        metadata.map_or(0, |metadata| metadata.len()),
+        modified
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |since| since.as_secs())
+    );
+    let repaired = cache_location::local_cache_root().and_then(|root| {
+        let dir = root.join(REPAIRED_DIR_NAME);
+        fs::create_dir_all(&dir)?;
+        let repaired_path = dir.join(format!("{}-{}.zip", stem, version));
+        if !repaired_path.is_file() {
+            let recovered =
+                crate::util::zip::recover_zip(&zip_path.to_string_lossy(), &repaired_path)?;
+            info!("Repaired {}, recovering {} file(s)", file_name, recovered);
+        }
+        Ok(repaired_path)
+    });
+    let opened = repaired.and_then(|repaired_path| {
+        let archive = open_zip(&repaired_path).map_err(|e| anyhow::anyhow!(e))?;
+        Ok((repaired_path, archive))
+    });
+    match opened {
+        Ok((repaired_path, archive)) => {
+            error.repaired_path = Some(repaired_path.to_string_lossy().to_string());
+            Ok((repaired_path, archive, error))
+        }
+        Err(e) => {
+            warn!("Failed to repair {}: {}", file_name, e);
+            Err(error)
+        }
     }
+}
 
-    Ok(sorted_packs)
+/// Where a pack that is a symlink or junction leads, or None for a plain
+/// file or folder
+fn resolved_link_target(path: &Path) -> Option<PathBuf> {
+    let is_link =
+        fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_symlink());
+    is_link.then(|| path.canonicalize().ok()).flatten()
 }
 
 /// Calculate total size of a directory recursively
 fn calculate_dir_size(path: &Path) -> u64 {
-    WalkDir::new(path)
-        .into_iter()
-        .filter_map(|e| e.ok())
+    pack_files::pack_dir_files(path)
         .filter_map(|e| e.metadata().ok())
-        .filter(|m| m.is_file())
         .map(|m| m.len())
         .sum()
 }
 
-/// Extract metadata from pack.mcmeta and icon from pack.png in a ZIP file
-fn extract_pack_metadata_from_zip(
-    zip_path: &Path,
-) -> (Option<String>, Option<String>, Option<u32>) {
-    let file = match fs::File::open(zip_path) {
-        Ok(f) => f,
-        Err(_) => return (None, None, None),
-    };
+/// Check a ZIP file's entries, then extract metadata from pack.mcmeta and
+/// credits from the sidecar files
+fn extract_pack_metadata_from_zip(mut archive: ZipArchive<fs::File>) -> PackDetails {
+    let quarantine = pack_safety::check_zip(&mut archive);
+    if !quarantine.is_empty() {
+        return PackDetails {
+            quarantine,
+            ..Default::default()
+        };
+    }
 
-    let mut archive = match ZipArchive::new(file) {
-        Ok(a) => a,
-        Err(_) => return (None, None, None),
-    };
+    // Extract description, pack_format and overlays from pack.mcmeta
+    let mcmeta = extract_mcmeta_from_zip(&mut archive);
 
-    // Extract description and pack_format from pack.mcmeta
-    let (description, pack_format) = extract_mcmeta_from_zip(&mut archive);
+    // The icon itself is served by the asset protocol
+    let has_icon = archive.by_name("pack.png").is_ok();
 
-    // Extract icon from pack.png
-    let icon_data = extract_icon_from_zip(&mut archive);
+    let credits = pack_credits::credits_from_zip(&mut archive);
 
-    (description, icon_data, pack_format)
+    PackDetails {
+        mcmeta,
+        has_icon,
+        credits,
+        quarantine,
+    }
 }
 
-/// Extract description and pack_format from pack.mcmeta in ZIP archive
-fn extract_mcmeta_from_zip(archive: &mut ZipArchive<fs::File>) -> (Option<String>, Option<u32>) {
+/// Extract pack.mcmeta fields from ZIP archive
+fn extract_mcmeta_from_zip(archive: &mut ZipArchive<fs::File>) -> McmetaInfo {
     // Try to find pack.mcmeta
     let mut mcmeta_file = match archive.by_name("pack.mcmeta") {
         Ok(file) => file,
-        Err(_) => return (None, None),
+        Err(_) => return McmetaInfo::default(),
     };
 
     let mut contents = String::new();
     if mcmeta_file.read_to_string(&mut contents).is_err() {
-        return (None, None);
+        return McmetaInfo::default();
+    }
+
+    parse_mcmeta(&contents)
+}
+
+/// Check an uncompressed directory's files, then extract metadata and credits
+/// from it
+fn extract_pack_metadata_from_dir(dir_path: &Path) -> PackDetails {
+    let quarantine = pack_safety::check_dir(dir_path);
+    if !quarantine.is_empty() {
+        return PackDetails {
+            quarantine,
+            ..Default::default()
+        };
+    }
+
+    // Extract description, pack_format and overlays from pack.mcmeta
+    let mcmeta = extract_mcmeta_from_dir(dir_path);
+
+    PackDetails {
+        mcmeta,
+        has_icon: dir_path.join("pack.png").is_file(),
+        credits: pack_credits::credits_from_dir(dir_path),
+        quarantine,
+    }
+}
+
+/// Extract pack.mcmeta fields from directory
+fn extract_mcmeta_from_dir(dir_path: &Path) -> McmetaInfo {
+    let mcmeta_path = dir_path.join("pack.mcmeta");
+    match fs::read_to_string(mcmeta_path) {
+        Ok(contents) => parse_mcmeta(&contents),
+        Err(_) => McmetaInfo::default(),
     }
+}
 
-    // Parse JSON and extract description and pack_format
-    let json: serde_json::Value = match serde_json::from_str(&contents) {
+/// Parse description, pack_format, overlays and feature flags out of
+/// pack.mcmeta JSON
+fn parse_mcmeta(contents: &str) -> McmetaInfo {
+    let json: serde_json::Value = match serde_json::from_str(contents) {
         Ok(json) => json,
-        Err(_) => return (None, None),
+        Err(_) => return McmetaInfo::default(),
     };
 
     let pack_obj = match json.get("pack") {
         Some(pack) => pack,
-        None => return (None, None),
+        None => return McmetaInfo::default(),
     };
 
     let description = pack_obj
@@ -186,75 +439,209 @@ fn extract_mcmeta_from_zip(archive: &mut ZipArchive<fs::File>) -> (Option<String
         .and_then(|v| v.as_u64())
         .map(|v| v as u32);
 
-    (description, pack_format)
+    let supported_formats = pack_obj
+        .get("supported_formats")
+        .and_then(PackFormatRange::from_json)
+        .or_else(|| {
+            PackFormatRange::from_min_max(pack_obj.get("min_format")?, pack_obj.get("max_format")?)
+        });
+
+    let overlays = json
+        .get("overlays")
+        .and_then(|o| o.get("entries"))
+        .and_then(|e| e.as_array())
+        .map(|entries| entries.iter().filter_map(parse_overlay_entry).collect())
+        .unwrap_or_default();
+
+    let mut features: Vec<String> = json
+        .get("features")
+        .and_then(|f| f.get("enabled"))
+        .and_then(|e| e.as_array())
+        .map(|enabled| {
+            enabled
+                .iter()
+                .filter_map(|flag| flag.as_str())
+                .map(|flag| flag.to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    features.sort();
+    features.dedup();
+
+    McmetaInfo {
+        description,
+        pack_format,
+        supported_formats,
+        overlays,
+        features,
+    }
 }
 
-/// Extract icon from pack.png in ZIP archive as base64
-fn extract_icon_from_zip(archive: &mut ZipArchive<fs::File>) -> Option<String> {
-    // Try to find pack.png
-    let mut icon_file = archive.by_name("pack.png").ok()?;
+/// Parse one `overlays.entries` item
+///
+/// Accepts both `formats` (pack_format 18+) and `min_format`/`max_format`
+/// (where a `[major, minor]` version only contributes its major number).
+/// Directory names outside vanilla's `[a-z0-9_.-]` charset are skipped.
+fn parse_overlay_entry(entry: &serde_json::Value) -> Option<PackOverlay> {
+    let directory = entry.get("directory")?.as_str()?;
+    let valid_directory = !directory.is_empty()
+        && directory != "."
+        && directory != ".."
+        && directory
+            .chars()
+            .all(|c| matches!(c, 'a'..='z' | '0'..='9' | '_' | '.' | '-'));
+    if !valid_directory {
+        warn!("Skipping overlay with invalid directory: {}", directory);
+        return None;
+    }
 
-    let mut buffer = Vec::new();
-    icon_file.read_to_end(&mut buffer).ok()?;
+    let formats = match entry.get("formats") {
+        Some(formats) => PackFormatRange::from_json(formats)?,
+        None => PackFormatRange::from_min_max(entry.get("min_format")?, entry.get("max_format")?)?,
+    };
 
-    // Encode as base64
-    use base64::{engine::general_purpose, Engine as _};
-    Some(general_purpose::STANDARD.encode(&buffer))
+    Some(PackOverlay {
+        directory: directory.to_string(),
+        formats,
+    })
 }
 
-/// Extract metadata and icon from an uncompressed directory
-fn extract_pack_metadata_from_dir(
-    dir_path: &Path,
-) -> (Option<String>, Option<String>, Option<u32>) {
-    // Extract description and pack_format from pack.mcmeta
-    let (description, pack_format) = extract_mcmeta_from_dir(dir_path);
-
-    // Extract icon from pack.png
-    let icon_data = extract_icon_from_dir(dir_path);
+/// Files left by operating systems that don't count as pack content
+fn is_junk_file(relative_path: &str) -> bool {
+    let file_name = relative_path.rsplit('/').next().unwrap_or(relative_path);
+    relative_path.starts_with("__MACOSX/")
+        || matches!(file_name, ".DS_Store" | "Thumbs.db" | "desktop.ini")
+}
 
-    (description, icon_data, pack_format)
+/// Pack-relative paths and sizes of a folder pack's files
+fn dir_content_files(dir_path: &Path) -> Vec<(String, u64)> {
+    pack_files::pack_dir_files(dir_path)
+        .filter_map(|e| {
+            let relative = e.path().strip_prefix(dir_path).ok()?;
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            let size = e.metadata().ok()?.len();
+            Some((relative, size))
+        })
+        .filter(|(relative, _)| !is_junk_file(relative))
+        .collect()
 }
 
-/// Extract description from pack.mcmeta in directory
-fn extract_mcmeta_from_dir(dir_path: &Path) -> (Option<String>, Option<u32>) {
-    let mcmeta_path = dir_path.join("pack.mcmeta");
-    let contents = match fs::read_to_string(mcmeta_path) {
-        Ok(contents) => contents,
-        Err(_) => return (None, None),
-    };
+/// Total uncompressed size of a pack's files
+///
+/// Unlike `PackMeta.size` this matches between a ZIP and the same pack
+/// unpacked, so it is a cheap first filter before hashing.
+pub fn pack_content_size(pack_path: &Path, is_zip: bool) -> Result<u64> {
+    if !is_zip {
+        return Ok(dir_content_files(pack_path)
+            .iter()
+            .map(|(_, size)| size)
+            .sum());
+    }
 
-    // Parse JSON and extract description and pack_format
-    let json: serde_json::Value = match serde_json::from_str(&contents) {
-        Ok(json) => json,
-        Err(_) => return (None, None),
-    };
+    let mut archive = ZipArchive::new(fs::File::open(pack_path)?)?;
+    let mut total = 0;
+    for i in 0..archive.len() {
+        let entry = archive.by_index_raw(i)?;
+        if entry.is_file() && !is_junk_file(entry.name()) {
+            total += entry.size();
+        }
+    }
+    Ok(total)
+}
 
-    let pack_obj = match json.get("pack") {
-        Some(pack) => pack,
-        None => return (None, None),
+/// SHA-256 over a pack's file paths and contents, as lowercase hex
+///
+/// Files are hashed in path order, so a ZIP and the same pack unpacked (or the
+/// same pack under another file name) hash the same.
+pub fn pack_content_hash(pack_path: &Path, is_zip: bool) -> Result<String> {
+    let mut hasher = Sha256::new();
+    let mut hash_file = |relative: &str, reader: &mut dyn Read| -> Result<()> {
+        hasher.update(relative.as_bytes());
+        hasher.update([0]);
+        std::io::copy(reader, &mut hasher)?;
+        Ok(())
     };
 
-    let description = pack_obj
-        .get("description")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
+    if is_zip {
+        let mut archive = ZipArchive::new(fs::File::open(pack_path)?)?;
+        let mut names: Vec<String> = archive
+            .file_names()
+            .filter(|name| !name.ends_with('/') && !is_junk_file(name))
+            .map(str::to_string)
+            .collect();
+        names.sort();
+        for name in names {
+            hash_file(&name, &mut archive.by_name(&name)?)?;
+        }
+    } else {
+        let mut files = dir_content_files(pack_path);
+        files.sort();
+        for (relative, _) in files {
+            hash_file(&relative, &mut fs::File::open(pack_path.join(&relative))?)?;
+        }
+    }
 
-    let pack_format = pack_obj
-        .get("pack_format")
-        .and_then(|v| v.as_u64())
-        .map(|v| v as u32);
+    Ok(format!("{:x}", hasher.finalize()))
+}
 
-    (description, pack_format)
+/// Keep a pack's size or hash, logging packs that couldn't be read
+fn readable<T>(pack: &PackMeta, result: Result<T>) -> Option<T> {
+    result
+        .map_err(|e| warn!("Skipping {}: {}", pack.id, e))
+        .ok()
 }
 
-/// Extract icon from pack.png in directory as base64
-fn extract_icon_from_dir(dir_path: &Path) -> Option<String> {
-    let icon_path = dir_path.join("pack.png");
-    let buffer = fs::read(icon_path).ok()?;
+/// Group packs that are copies of each other
+///
+/// Only packs whose content sizes collide get hashed. Groups are sorted by
+/// their first pack ID.
+pub fn find_duplicate_packs(packs: &[PackMeta]) -> Vec<DuplicatePacks> {
+    let sizes: Vec<(&PackMeta, u64)> = packs
+        .par_iter()
+        .filter_map(|pack| {
+            let size = pack_content_size(Path::new(&pack.path), pack.is_zip);
+            readable(pack, size).map(|size| (pack, size))
+        })
+        .collect();
+
+    let mut by_size: HashMap<u64, Vec<&PackMeta>> = HashMap::new();
+    for (pack, size) in sizes {
+        by_size.entry(size).or_default().push(pack);
+    }
+    let candidates: Vec<&PackMeta> = by_size
+        .into_values()
+        .filter(|group| group.len() > 1)
+        .flatten()
+        .collect();
+
+    let hashes: Vec<(&PackMeta, String)> = candidates
+        .par_iter()
+        .filter_map(|pack| {
+            let hash = pack_content_hash(Path::new(&pack.path), pack.is_zip);
+            readable(pack, hash).map(|hash| (*pack, hash))
+        })
+        .collect();
+
+    let mut by_hash: HashMap<String, Vec<&PackMeta>> = HashMap::new();
+    for (pack, hash) in hashes {
+        by_hash.entry(hash).or_default().push(pack);
+    }
 
-    // Encode as base64
-    use base64::{engine::general_purpose, Engine as _};
-    Some(general_purpose::STANDARD.encode(&buffer))
+    let mut duplicates: Vec<DuplicatePacks> = by_hash
+        .into_iter()
+        .filter(|(_, group)| group.len() > 1)
+        .map(|(content_hash, group)| {
+            let mut pack_ids: Vec<String> = group.iter().map(|pack| pack.id.clone()).collect();
+            pack_ids.sort();
+            DuplicatePacks {
+                content_hash,
+                pack_ids,
+                different_names: group.iter().any(|pack| pack.name != group[0].name),
+            }
+        })
+        .collect();
+    duplicates.sort_by(|a, b| a.pack_ids.cmp(&b.pack_ids));
+    duplicates
 }
 
 #[cfg(test)]
@@ -341,6 +728,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_scan_packs_reports_damaged_zip() {
+        let temp_dir = std::env::temp_dir().join("test_scan_packs_damaged_zip");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        let mut writer = zip::ZipWriter::new(fs::File::create(temp_dir.join("good.zip")).unwrap());
+        writer
+            .start_file("pack.mcmeta", zip::write::FileOptions::default())
+            .unwrap();
+        writer
+            .write_all(br#"{"pack": {"pack_format": 15, "description": "Good"}}"#)
+            .unwrap();
+        writer.finish().unwrap();
+        // Just written and cut off, like a download in progress
+        fs::write(temp_dir.join("partial.zip"), b"PK\x03\x04\x14\x00").unwrap();
+
+        let result = scan_packs_with_errors(temp_dir.to_str().unwrap());
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let (packs, errors) = result.unwrap();
+        assert_eq!(packs.len(), 1);
+        assert_eq!(packs[0].id, "good.zip");
+        assert_eq!(packs[0].description.as_deref(), Some("Good"));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].pack_id, "partial.zip");
+        assert!(errors[0].retry);
+        assert_eq!(errors[0].repaired_path, None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scan_packs_follows_linked_pack() {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = std::env::temp_dir().join("test_scan_packs_linked_pack");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let packs_dir = temp_dir.join("resourcepacks");
+        let checkout = temp_dir.join("checkout");
+        fs::create_dir_all(&packs_dir).unwrap();
+        fs::create_dir_all(&checkout).unwrap();
+        let mcmeta = r#"{"pack": {"pack_format": 15}}"#;
+        fs::write(checkout.join("pack.mcmeta"), mcmeta).unwrap();
+        fs::write(temp_dir.join("pack.mcmeta"), mcmeta).unwrap();
+        symlink(&checkout, packs_dir.join("dev_pack")).unwrap();
+        symlink(&temp_dir, packs_dir.join("loop")).unwrap();
+
+        let result = scan_packs(packs_dir.to_str().unwrap());
+        let resolved_checkout = checkout.canonicalize().unwrap();
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let packs = result.unwrap();
+        assert_eq!(packs.len(), 1);
+        assert_eq!(packs[0].id, "dev_pack");
+        assert_eq!(
+            packs[0].resolved_path.as_deref(),
+            Some(resolved_checkout.to_string_lossy().as_ref())
+        );
+    }
+
     #[test]
     fn test_scan_packs_skips_hidden_files() {
         // Create a temporary directory with hidden files
@@ -411,13 +858,16 @@ mod tests {
             )
             .expect("Failed to write pack.mcmeta");
 
-        let (description, _pack_format) = extract_mcmeta_from_dir(&temp_dir);
+        let mcmeta = extract_mcmeta_from_dir(&temp_dir);
 
         // Clean up
         fs::remove_file(&mcmeta_path).ok();
         fs::remove_dir(&temp_dir).ok();
 
-        assert_eq!(description, Some("My custom description".to_string()));
+        assert_eq!(
+            mcmeta.description,
+            Some("My custom description".to_string())
+        );
     }
 
     #[test]
@@ -425,25 +875,104 @@ mod tests {
         let temp_dir = std::env::temp_dir().join("test_extract_desc_missing");
         fs::create_dir_all(&temp_dir).expect("Failed to create test directory");
 
-        let (description, _pack_format) = extract_mcmeta_from_dir(&temp_dir);
+        let mcmeta = extract_mcmeta_from_dir(&temp_dir);
 
         // Clean up
         fs::remove_dir(&temp_dir).ok();
 
-        assert_eq!(description, None);
+        assert_eq!(mcmeta.description, None);
     }
 
     #[test]
-    fn test_extract_icon_from_dir_missing() {
+    fn test_parse_mcmeta_overlays() {
+        let mcmeta = parse_mcmeta(
+            r#"{
+            "pack": {"pack_format": 18, "description": "Overlays"},
+            "overlays": {
+                "entries": [
+                    {"directory": "legacy", "formats": [18, 22]},
+                    {"directory": "modern", "formats": {"min_inclusive": 32, "max_inclusive": 48}},
+                    {"directory": "newest", "min_format": [65, 0], "max_format": 69},
+                    {"directory": "../escape", "formats": 18},
+                    {"directory": "broken"}
+                ]
+            }
+        }"#,
+        );
+
+        assert_eq!(mcmeta.pack_format, Some(18));
+        assert_eq!(mcmeta.supported_formats, None);
+        assert_eq!(
+            mcmeta.overlays,
+            vec![
+                PackOverlay {
+                    directory: "legacy".to_string(),
+                    formats: PackFormatRange { min: 18, max: 22 },
+                },
+                PackOverlay {
+                    directory: "modern".to_string(),
+                    formats: PackFormatRange { min: 32, max: 48 },
+                },
+                PackOverlay {
+                    directory: "newest".to_string(),
+                    formats: PackFormatRange { min: 65, max: 69 },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_mcmeta_supported_formats() {
+        let mcmeta = parse_mcmeta(
+            r#"{"pack": {"pack_format": 34, "supported_formats": [18, 46], "description": ""}}"#,
+        );
+        assert_eq!(
+            mcmeta.supported_formats,
+            Some(PackFormatRange { min: 18, max: 46 })
+        );
+
+        let mcmeta = parse_mcmeta(
+            r#"{"pack": {"min_format": 55, "max_format": [69, 0], "description": ""}}"#,
+        );
+        assert_eq!(mcmeta.pack_format, None);
+        assert_eq!(
+            mcmeta.supported_formats,
+            Some(PackFormatRange { min: 55, max: 69 })
+        );
+    }
+
+    #[test]
+    fn test_parse_mcmeta_features() {
+        let mcmeta = parse_mcmeta(
+            r#"{
+            "pack": {"pack_format": 34, "description": ""},
+            "features": {"enabled": ["minecraft:trade_rebalance", "minecraft:bundle", "minecraft:bundle"]}
+        }"#,
+        );
+        assert_eq!(
+            mcmeta.features,
+            vec!["minecraft:bundle", "minecraft:trade_rebalance"]
+        );
+
+        let mcmeta = parse_mcmeta(r#"{"pack": {"pack_format": 34, "description": ""}}"#);
+        assert!(mcmeta.features.is_empty());
+    }
+
+    #[test]
+    fn test_dir_pack_icon_detection() {
         let temp_dir = std::env::temp_dir().join("test_extract_icon_missing");
+        let _ = fs::remove_dir_all(&temp_dir);
         fs::create_dir_all(&temp_dir).expect("Failed to create test directory");
 
-        let icon_data = extract_icon_from_dir(&temp_dir);
+        let without_icon = extract_pack_metadata_from_dir(&temp_dir).has_icon;
+        fs::write(temp_dir.join("pack.png"), b"png").unwrap();
+        let with_icon = extract_pack_metadata_from_dir(&temp_dir).has_icon;
 
         // Clean up
-        fs::remove_dir(&temp_dir).ok();
+        fs::remove_dir_all(&temp_dir).ok();
 
-        assert_eq!(icon_data, None);
+        assert!(!without_icon);
+        assert!(with_icon);
     }
 
     #[test]
@@ -464,4 +993,48 @@ mod tests {
         // Should be at least 5 bytes (the content we wrote)
         assert!(size >= 5);
     }
+
+    #[test]
+    fn test_find_duplicate_packs() {
+        let temp_dir = std::env::temp_dir().join("test_find_duplicate_packs");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let write_pack = |name: &str, texture: &[u8]| {
+            let pack_dir = temp_dir.join(name);
+            let block_dir = pack_dir.join("assets/minecraft/textures/block");
+            fs::create_dir_all(&block_dir).unwrap();
+            fs::write(
+                pack_dir.join("pack.mcmeta"),
+                r#"{"pack": {"pack_format": 15}}"#,
+            )
+            .unwrap();
+            fs::write(block_dir.join("stone.png"), texture).unwrap();
+            pack_dir
+        };
+        let original = write_pack("Faithful", b"stone-a");
+        write_pack("Lookalike", b"stone-b");
+        fs::write(original.join(".DS_Store"), b"junk").unwrap();
+        crate::util::zip::zip_dir_with_progress(
+            &original,
+            &temp_dir.join("Faithful copy.zip"),
+            None,
+        )
+        .unwrap();
+
+        let packs = scan_packs(temp_dir.to_str().unwrap()).unwrap();
+        let duplicates = find_duplicate_packs(&packs);
+        let zip_hash = pack_content_hash(&temp_dir.join("Faithful copy.zip"), true).unwrap();
+        let dir_hash = pack_content_hash(&original, false).unwrap();
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(packs.len(), 3);
+        assert_eq!(zip_hash, dir_hash);
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].content_hash, dir_hash);
+        assert_eq!(
+            duplicates[0].pack_ids,
+            vec!["Faithful", "Faithful copy.zip"]
+        );
+        assert!(duplicates[0].different_names);
+    }
 }
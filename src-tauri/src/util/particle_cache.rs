@@ -8,6 +8,7 @@ use super::block_particle_extractor::{
     clear_block_emissions_cache, clear_block_emissions_data_cache, extract_block_emissions,
     load_cached_block_emissions, ExtractedBlockEmissions,
 };
+use super::offline::{self, Dataset};
 use super::particle_data::{
     clear_particle_data_cache, extract_particle_textures, get_particle_data_for_version,
     ParticleData as ParticleTextureData,
@@ -141,16 +142,24 @@ pub async fn ensure_particle_cache(
     let _guard = PARTICLE_CACHE_MUTEX.lock().await;
     let physics = match load_cached_physics_data(version)? {
         Some(data) if data.version == version => data,
-        _ => extract_particle_physics(jar_path, version)
-            .await
-            .context("Failed to extract particle physics")?,
+        _ => offline::bundled_or_extract(
+            Dataset::ParticlePhysics,
+            version,
+            extract_particle_physics(jar_path, version),
+        )
+        .await
+        .context("Failed to extract particle physics")?,
     };
 
     let emissions = match load_cached_block_emissions(version)? {
         Some(data) if data.version == version => data,
-        _ => extract_block_emissions(jar_path, version)
-            .await
-            .context("Failed to extract block emissions")?,
+        _ => offline::bundled_or_extract(
+            Dataset::BlockEmissions,
+            version,
+            extract_block_emissions(jar_path, version),
+        )
+        .await
+        .context("Failed to extract block emissions")?,
     };
 
     let textures = match get_particle_data_for_version(version) {
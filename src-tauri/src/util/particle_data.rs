@@ -3,12 +3,16 @@
 /// Loads particle texture mappings from cached JSON files.
 /// These mappings are extracted from Minecraft's particle definition JSONs.
 
+use crate::model::PackMeta;
+use crate::util::pack_files;
+use crate::util::particle_physics_extractor::{ExtractedParticlePhysics, ExtractedPhysicsData};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
 use std::io::Read;
+use tracing::{info, warn};
 use zip::ZipArchive;
 
 /// Particle texture mapping from Minecraft
@@ -24,6 +28,190 @@ pub struct ParticleData {
     pub particles: HashMap<String, ParticleTextureMapping>,
 }
 
+/// A particle sprite resolved to the pack that provides its texture
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedParticleSprite {
+    /// Sprite ID from the particle definition (e.g., "minecraft:flame")
+    pub sprite: String,
+    /// Texture asset ID (e.g., "minecraft:particle/flame")
+    pub asset_id: String,
+    /// Winning pack for this sprite
+    pub pack_id: String,
+    pub pack_path: String,
+    pub is_zip: bool,
+    /// Pack-relative PNG path
+    pub entry_path: String,
+}
+
+/// Ordered sprite list for a particle, resolved across the pack stack
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedParticleTextures {
+    pub particle_id: String,
+    /// Pack whose particles/<id>.json supplied the sprite list
+    pub definition_pack_id: String,
+    pub sprites: Vec<ResolvedParticleSprite>,
+}
+
+/// Everything the particle preview needs for one particle in one game version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParticleDefinition {
+    pub particle_id: String,
+    pub version: String,
+    /// Physics extracted from the version's particle classes; None until
+    /// extracted (and without a bundled dataset)
+    pub physics: Option<ExtractedParticlePhysics>,
+    /// Sprite IDs from particles/<id>.json in the version's client JAR
+    pub vanilla_textures: Vec<String>,
+    /// The sprite list that wins across the pack stack, each sprite resolved
+    /// to the pack providing it
+    pub textures: ResolvedParticleTextures,
+}
+
+/// ID of the virtual pack serving the vanilla cache
+const VANILLA_PACK_ID: &str = "minecraft:vanilla";
+
+/// Resolve a particle's sprite list and textures across an ordered pack stack
+///
+/// The highest-priority pack with `assets/<ns>/particles/<id>.json` defines the
+/// sprite list (definitions replace, they don't merge); each sprite texture is then
+/// taken from the highest-priority pack that provides it, so retextures apply even
+/// when a pack doesn't override the definition.
+///
+/// # Arguments
+/// * `particle_id` - Particle ID (e.g., "flame" or "minecraft:flame")
+/// * `ordered_packs` - Packs in priority order (highest first, vanilla last)
+pub fn resolve_particle_textures(
+    particle_id: &str,
+    ordered_packs: &[PackMeta],
+) -> io::Result<ResolvedParticleTextures> {
+    let (namespace, name) = pack_files::split_asset_id(particle_id);
+    let definition_path = format!("assets/{}/particles/{}.json", namespace, name);
+
+    let (definition_pack, contents) = ordered_packs
+        .iter()
+        .find_map(|pack| {
+            pack_files::read_pack_entry(pack, &definition_path)
+                .ok()
+                .map(|bytes| (pack, bytes))
+        })
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No particle definition found for {}", particle_id),
+            )
+        })?;
+
+    let json: serde_json::Value = serde_json::from_slice(&contents).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to parse {}: {}", definition_path, e),
+        )
+    })?;
+
+    let sprite_ids: Vec<&str> = json
+        .get("textures")
+        .and_then(|t| t.as_array())
+        .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default();
+
+    Ok(ResolvedParticleTextures {
+        particle_id: format!("{}:{}", namespace, name),
+        definition_pack_id: definition_pack.id.clone(),
+        sprites: resolve_sprites(particle_id, &sprite_ids, ordered_packs),
+    })
+}
+
+/// Resolve each sprite ID to the highest-priority pack with its texture;
+/// sprites no pack provides are left out
+fn resolve_sprites(
+    particle_id: &str,
+    sprite_ids: &[&str],
+    ordered_packs: &[PackMeta],
+) -> Vec<ResolvedParticleSprite> {
+    sprite_ids
+        .iter()
+        .filter_map(|sprite| {
+            let (sprite_ns, sprite_name) = pack_files::split_asset_id(sprite);
+            let asset_id = format!("{}:particle/{}", sprite_ns, sprite_name);
+            let entry_path = pack_files::texture_entry_path(&asset_id);
+
+            let pack = ordered_packs
+                .iter()
+                .find(|pack| pack_files::pack_entry_exists(pack, &entry_path));
+            if pack.is_none() {
+                warn!("No pack provides sprite {} for {}", sprite, particle_id);
+            }
+
+            pack.map(|pack| ResolvedParticleSprite {
+                sprite: format!("{}:{}", sprite_ns, sprite_name),
+                asset_id,
+                pack_id: pack.id.clone(),
+                pack_path: pack.path.clone(),
+                is_zip: pack.is_zip,
+                entry_path,
+            })
+        })
+        .collect()
+}
+
+/// Combine a particle's physics, vanilla sprite list and resolved textures
+///
+/// A pack's particles/<id>.json still replaces the sprite list; when the list
+/// would come from the vanilla cache, the JAR's list for `version` is used
+/// instead, since the cache may hold another version.
+///
+/// # Arguments
+/// * `particle_id` - Particle ID (e.g., "flame" or "minecraft:flame")
+/// * `version` - Minecraft version the physics and vanilla data belong to
+/// * `physics` - Extracted physics for the version, if available
+/// * `vanilla` - Particle texture lists read from the version's JAR, if available
+/// * `ordered_packs` - Packs in priority order (highest first, vanilla last)
+pub fn particle_definition(
+    particle_id: &str,
+    version: &str,
+    physics: Option<&ExtractedPhysicsData>,
+    vanilla: Option<&ParticleData>,
+    ordered_packs: &[PackMeta],
+) -> io::Result<ParticleDefinition> {
+    let (namespace, name) = pack_files::split_asset_id(particle_id);
+    let particle_physics = physics.and_then(|data| data.particles.get(name)).cloned();
+    let vanilla_textures = vanilla
+        .and_then(|data| data.particles.get(name))
+        .map(|mapping| mapping.textures.clone())
+        .unwrap_or_default();
+
+    let textures = match resolve_particle_textures(particle_id, ordered_packs) {
+        Ok(resolved) if resolved.definition_pack_id != VANILLA_PACK_ID => resolved,
+        _ if !vanilla_textures.is_empty() => {
+            let sprite_ids: Vec<&str> = vanilla_textures.iter().map(String::as_str).collect();
+            ResolvedParticleTextures {
+                particle_id: format!("{}:{}", namespace, name),
+                definition_pack_id: VANILLA_PACK_ID.to_string(),
+                sprites: resolve_sprites(particle_id, &sprite_ids, ordered_packs),
+            }
+        }
+        Ok(resolved) => resolved,
+        // Particles without sprites (e.g., block markers) still have physics
+        Err(_) if particle_physics.is_some() => ResolvedParticleTextures {
+            particle_id: format!("{}:{}", namespace, name),
+            definition_pack_id: String::new(),
+            sprites: Vec::new(),
+        },
+        Err(e) => return Err(e),
+    };
+
+    Ok(ParticleDefinition {
+        particle_id: format!("{}:{}", namespace, name),
+        version: version.to_string(),
+        physics: particle_physics,
+        vanilla_textures,
+        textures,
+    })
+}
+
 /// Get the cache directory for particle data
 fn get_particle_data_cache_dir() -> io::Result<PathBuf> {
     let cache_dir = dirs::cache_dir()
@@ -47,7 +235,7 @@ fn save_particle_data(data: &ParticleData) -> io::Result<()> {
 
     fs::write(&cache_file, json)?;
 
-    println!("✓ Saved particle texture mappings to {:?}", cache_file);
+    info!("✓ Saved particle texture mappings to {:?}", cache_file);
     Ok(())
 }
 
@@ -102,7 +290,7 @@ fn parse_particle_textures(jar_path: &Path) -> io::Result<HashMap<String, Partic
 }
 
 pub fn extract_particle_textures(jar_path: &Path, version: &str) -> io::Result<ParticleData> {
-    println!("Reading particle definitions from JAR: {:?}", jar_path);
+    info!("Reading particle definitions from JAR: {:?}", jar_path);
     let particles = parse_particle_textures(jar_path)?;
     let data = ParticleData {
         version: version.to_string(),
@@ -180,3 +368,123 @@ pub fn get_or_extract_particle_data(
         Err(_) => extract_particle_textures(jar_path, version),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_pack(id: &str, path: &Path) -> PackMeta {
+        PackMeta {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: path.to_string_lossy().to_string(),
+            size: 0,
+            is_zip: false,
+            description: None,
+            has_icon: false,
+            pack_format: None,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_resolve_particle_textures_across_pack_stack() {
+        let temp_dir = std::env::temp_dir().join("test_resolve_particle_textures");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let vanilla_dir = temp_dir.join("vanilla");
+        let pack_dir = temp_dir.join("pack");
+
+        fs::create_dir_all(vanilla_dir.join("assets/minecraft/particles")).unwrap();
+        fs::create_dir_all(vanilla_dir.join("assets/minecraft/textures/particle")).unwrap();
+        fs::create_dir_all(pack_dir.join("assets/minecraft/textures/particle")).unwrap();
+        fs::write(
+            vanilla_dir.join("assets/minecraft/particles/flame.json"),
+            r#"{"textures": ["minecraft:flame", "flame_alt"]}"#,
+        )
+        .unwrap();
+        fs::write(
+            vanilla_dir.join("assets/minecraft/textures/particle/flame.png"),
+            b"v",
+        )
+        .unwrap();
+        fs::write(
+            vanilla_dir.join("assets/minecraft/textures/particle/flame_alt.png"),
+            b"v",
+        )
+        .unwrap();
+        fs::write(
+            pack_dir.join("assets/minecraft/textures/particle/flame.png"),
+            b"p",
+        )
+        .unwrap();
+
+        let packs = vec![
+            make_pack("pack", &pack_dir),
+            make_pack("minecraft:vanilla", &vanilla_dir),
+        ];
+        let result = resolve_particle_textures("flame", &packs);
+        let missing = resolve_particle_textures("minecraft:smoke", &packs);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let resolved = result.unwrap();
+        assert_eq!(resolved.particle_id, "minecraft:flame");
+        assert_eq!(resolved.definition_pack_id, "minecraft:vanilla");
+        assert_eq!(resolved.sprites.len(), 2);
+        assert_eq!(resolved.sprites[0].sprite, "minecraft:flame");
+        assert_eq!(resolved.sprites[0].pack_id, "pack");
+        assert_eq!(resolved.sprites[1].asset_id, "minecraft:particle/flame_alt");
+        assert_eq!(resolved.sprites[1].pack_id, "minecraft:vanilla");
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn test_particle_definition_uses_version_sprite_list() {
+        let temp_dir = std::env::temp_dir().join("test_particle_definition");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let vanilla_dir = temp_dir.join("vanilla");
+        let pack_dir = temp_dir.join("pack");
+
+        // The vanilla cache holds another version's definition
+        fs::create_dir_all(vanilla_dir.join("assets/minecraft/particles")).unwrap();
+        fs::create_dir_all(vanilla_dir.join("assets/minecraft/textures/particle")).unwrap();
+        fs::create_dir_all(pack_dir.join("assets/minecraft/textures/particle")).unwrap();
+        fs::write(
+            vanilla_dir.join("assets/minecraft/particles/flame.json"),
+            r#"{"textures": ["minecraft:flame_old"]}"#,
+        )
+        .unwrap();
+        fs::write(
+            pack_dir.join("assets/minecraft/textures/particle/flame.png"),
+            b"p",
+        )
+        .unwrap();
+
+        let packs = vec![
+            make_pack("pack", &pack_dir),
+            make_pack("minecraft:vanilla", &vanilla_dir),
+        ];
+        let vanilla = ParticleData {
+            version: "1.21.4".to_string(),
+            particles: HashMap::from([(
+                "flame".to_string(),
+                ParticleTextureMapping {
+                    textures: vec!["flame".to_string()],
+                },
+            )]),
+        };
+        let definition =
+            particle_definition("minecraft:flame", "1.21.4", None, Some(&vanilla), &packs);
+        let missing = particle_definition("smoke", "1.21.4", None, Some(&vanilla), &packs);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let definition = definition.unwrap();
+        assert_eq!(definition.vanilla_textures, vec!["flame"]);
+        assert!(definition.physics.is_none());
+        assert_eq!(definition.textures.definition_pack_id, "minecraft:vanilla");
+        assert_eq!(definition.textures.sprites.len(), 1);
+        assert_eq!(definition.textures.sprites[0].pack_id, "pack");
+        assert!(missing.is_err());
+    }
+}
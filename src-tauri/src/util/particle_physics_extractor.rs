@@ -9,10 +9,15 @@ use anyhow::{anyhow, Context, Result};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use std::ffi::OsStr;
 use std::fs;
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Output};
+use tracing::{info, warn};
+
+use super::bytecode_parser::{ClassFile, FieldAssignment};
+use super::{cache_location, cache_usage, download, offline, zip};
 
 /// Extracted particle physics data
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -118,6 +123,84 @@ pub enum QuadSizeCurve {
     Absolute { size: f32 },
 }
 
+impl QuadSizeCurve {
+    /// Whether the curve yields an absolute size instead of a quadSize multiplier
+    pub fn is_absolute(&self) -> bool {
+        matches!(
+            self,
+            QuadSizeCurve::Absolute { .. } | QuadSizeCurve::SineWave { .. }
+        )
+    }
+
+    /// Evaluate the curve at a normalized age (0 = spawn, 1 = end of lifetime)
+    ///
+    /// `lifetime` (ticks) is only used by SineWave, which is driven by absolute age.
+    pub fn evaluate(&self, age_ratio: f32, lifetime: f32) -> f32 {
+        match self {
+            QuadSizeCurve::Constant => 1.0,
+            QuadSizeCurve::LinearGrowClamped { multiplier } => {
+                (age_ratio * multiplier).clamp(0.0, 1.0)
+            }
+            QuadSizeCurve::QuadraticShrink { factor } => 1.0 - age_ratio * age_ratio * factor,
+            QuadSizeCurve::LinearShrink {
+                lifetime_multiplier,
+            } => 1.0 - age_ratio / lifetime_multiplier,
+            QuadSizeCurve::EaseInQuad => 1.0 - (1.0 - age_ratio) * (1.0 - age_ratio),
+            QuadSizeCurve::SineWave {
+                amplitude,
+                frequency,
+                phase,
+            } => {
+                let age = age_ratio * lifetime;
+                amplitude * ((age + phase) * frequency * std::f32::consts::PI).sin()
+            }
+            QuadSizeCurve::Absolute { size } => *size,
+        }
+    }
+}
+
+/// Lookup table of a quad size curve sampled over the particle's lifetime
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuadSizeCurveTable {
+    /// True if `values` are absolute sizes rather than quadSize multipliers
+    pub absolute: bool,
+    /// Normalized ages, evenly spaced from 0.0 to 1.0
+    pub ages: Vec<f32>,
+    /// Curve value at each age
+    pub values: Vec<f32>,
+}
+
+/// Sample a quad size curve into a lookup table
+///
+/// # Arguments
+/// * `curve` - Curve extracted from getQuadSize()
+/// * `samples` - Number of evenly spaced samples (at least 2: spawn and death)
+/// * `lifetime` - Lifetime in ticks for age-driven curves (SineWave); defaults to
+///   half a sine period, i.e. the positive lobe
+pub fn sample_quad_size_curve(
+    curve: &QuadSizeCurve,
+    samples: usize,
+    lifetime: Option<f32>,
+) -> QuadSizeCurveTable {
+    let samples = samples.max(2);
+    let lifetime = lifetime.unwrap_or(match curve {
+        QuadSizeCurve::SineWave { frequency, .. } if *frequency > 0.0 => 1.0 / frequency,
+        _ => 1.0,
+    });
+
+    let ages: Vec<f32> = (0..samples)
+        .map(|i| i as f32 / (samples - 1) as f32)
+        .collect();
+    let values = ages.iter().map(|&age| curve.evaluate(age, lifetime)).collect();
+
+    QuadSizeCurveTable {
+        absolute: curve.is_absolute(),
+        ages,
+        values,
+    }
+}
+
 /// Particle spawned by another particle during tick()
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpawnedParticle {
@@ -136,6 +219,10 @@ pub struct ExtractedPhysicsData {
     pub schema_version: u32,
     pub version: String,
     pub particles: HashMap<String, ExtractedParticlePhysics>,
+    /// Only constructor constants were read from the class files, because
+    /// there was no Java to decompile with; computed values are missing
+    #[serde(default)]
+    pub partial: bool,
 }
 
 /// Parse vanilla Minecraft version from potentially modded version string
@@ -146,7 +233,7 @@ pub struct ExtractedPhysicsData {
 /// - "1.21.4-fabric-0.16.5" -> "1.21.4"
 /// - "1.21.10-0.18.1" -> "1.21.10" (Fabric format)
 /// - "1.20.1-forge-47.2.0" -> "1.20.1"
-fn parse_vanilla_version(version: &str) -> String {
+pub fn parse_vanilla_version(version: &str) -> String {
     // Split by hyphen to separate version from loader info
     let parts: Vec<&str> = version.split('-').collect();
     let base = parts[0];
@@ -198,7 +285,6 @@ struct VersionDownloads {
 #[derive(Debug, Deserialize)]
 struct DownloadInfo {
     url: String,
-    #[allow(dead_code)]
     sha1: String,
 }
 
@@ -240,8 +326,10 @@ fn legacy_decompile_dirs(cache_root: &Path, version: &str) -> Vec<PathBuf> {
 
 pub fn get_shared_decompile_dir(version: &str) -> Result<PathBuf> {
     let cache_root = get_weaverbird_cache_dir()?;
-    let shared_dir = cache_root.join("decompiled").join(version);
+    let name = format!("decompiled/{}", version);
+    let shared_dir = cache_location::cache_dir(&name)?;
     if shared_dir.exists() {
+        cache_usage::mark_used(&shared_dir);
         return Ok(shared_dir);
     }
 
@@ -249,19 +337,20 @@ pub fn get_shared_decompile_dir(version: &str) -> Result<PathBuf> {
         .into_iter()
         .find(|path| path.exists())
     {
-        println!(
-            "[decompile] Using legacy decompile directory at {:?} for {}",
+        info!(
+            "Using legacy decompile directory at {:?} for {}",
             legacy, version
         );
         return Ok(legacy);
     }
 
-    Ok(shared_dir)
+    // Nothing cached yet, so decompile where this machine can write
+    cache_location::writable_cache_dir(&name)
 }
 
 pub fn clear_shared_decompile_dir(version: &str) -> Result<()> {
     let cache_root = get_weaverbird_cache_dir()?;
-    let shared_dir = cache_root.join("decompiled").join(version);
+    let shared_dir = cache_location::writable_cache_dir(&format!("decompiled/{}", version))?;
     let mut dirs = vec![shared_dir];
     dirs.extend(legacy_decompile_dirs(&cache_root, version));
 
@@ -297,20 +386,14 @@ pub fn load_cached_physics_data(version: &str) -> Result<Option<ExtractedPhysics
     let content = match fs::read_to_string(&cache_file) {
         Ok(content) => content,
         Err(error) => {
-            println!(
-                "[particle_physics] Failed to read physics cache for {}: {}",
-                version, error
-            );
+            warn!("Failed to read physics cache for {}: {}", version, error);
             return Ok(None);
         }
     };
     let data: ExtractedPhysicsData = match serde_json::from_str(&content) {
         Ok(data) => data,
         Err(error) => {
-            println!(
-                "[particle_physics] Failed to parse physics cache for {}: {}",
-                version, error
-            );
+            warn!("Failed to parse physics cache for {}: {}", version, error);
             return Ok(None);
         }
     };
@@ -318,16 +401,16 @@ pub fn load_cached_physics_data(version: &str) -> Result<Option<ExtractedPhysics
     // If the cache is from an older schema, force re-extraction to populate new fields.
     const CURRENT_SCHEMA_VERSION: u32 = 8;
     if data.schema_version < CURRENT_SCHEMA_VERSION {
-        println!(
-            "[particle_physics] Cached physics schema {} is older than {}, re-extracting...",
+        info!(
+            "Cached physics schema {} is older than {}, re-extracting...",
             data.schema_version, CURRENT_SCHEMA_VERSION
         );
         return Ok(None);
     }
 
     if data.particles.is_empty() {
-        println!(
-            "[particle_physics] Cached physics for {} has no particles, re-extracting...",
+        info!(
+            "Cached physics for {} has no particles, re-extracting...",
             version
         );
         return Ok(None);
@@ -356,8 +439,8 @@ fn save_physics_data_to_cache(data: &ExtractedPhysicsData) -> Result<()> {
     let content = serde_json::to_string_pretty(data).context("Failed to serialize physics data")?;
     fs::write(&cache_file, content).context("Failed to write physics cache file")?;
 
-    println!(
-        "[particle_physics] Cached physics data for version {} ({} particles)",
+    info!(
+        "Cached physics data for version {} ({} particles)",
         data.version,
         data.particles.len()
     );
@@ -368,8 +451,39 @@ fn save_physics_data_to_cache(data: &ExtractedPhysicsData) -> Result<()> {
 // NOTE: Deprecated - particle physics is now generated as part of the combined
 // TypeScript file in particle_typescript_gen.rs instead of individually.
 
+/// Error of Mojang mappings that couldn't be found or downloaded, so classes
+/// can't be decompiled with readable names
+#[derive(Debug)]
+pub struct MappingsUnavailable {
+    pub version: String,
+    pub reason: String,
+}
+
+impl std::fmt::Display for MappingsUnavailable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Mojang mappings for {} are unavailable: {}",
+            self.version, self.reason
+        )
+    }
+}
+
+impl std::error::Error for MappingsUnavailable {}
+
 /// Download Mojang mappings for a version
+///
+/// Fails with `MappingsUnavailable`.
 pub async fn download_mojang_mappings(version: &str) -> Result<PathBuf> {
+    fetch_mojang_mappings(version).await.map_err(|e| {
+        anyhow::Error::new(MappingsUnavailable {
+            version: version.to_string(),
+            reason: format!("{:#}", e),
+        })
+    })
+}
+
+async fn fetch_mojang_mappings(version: &str) -> Result<PathBuf> {
     // Parse vanilla version from potentially modded version string
     let vanilla_version = parse_vanilla_version(version);
 
@@ -378,28 +492,30 @@ pub async fn download_mojang_mappings(version: &str) -> Result<PathBuf> {
     let mappings_file = cache_dir.join(format!("{}-mappings.txt", version));
 
     // Check if already downloaded
+    if download::is_cached_file_valid(&mappings_file) {
+        info!("Using cached mappings for {}", version);
+        return Ok(mappings_file);
+    }
     if mappings_file.exists() {
-        println!(
-            "[particle_physics] Using cached mappings for {}",
+        warn!(
+            "Cached mappings for {} are corrupt, downloading again",
             version
         );
-        return Ok(mappings_file);
+        fs::remove_file(&mappings_file).context("Failed to remove corrupt mappings file")?;
     }
 
-    println!(
-        "[particle_physics] Downloading Mojang mappings for {} (vanilla: {})...",
+    info!(
+        "Downloading Mojang mappings for {} (vanilla: {})...",
         version, vanilla_version
     );
 
+    offline::ensure_online("Mojang mappings")?;
+
     // Step 1: Fetch version manifest
     let manifest_url = "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json";
-    let manifest_response = reqwest::get(manifest_url)
+    let manifest: VersionManifest = download::get_json(manifest_url)
         .await
         .context("Failed to fetch version manifest")?;
-    let manifest: VersionManifest = manifest_response
-        .json()
-        .await
-        .context("Failed to parse version manifest")?;
 
     // Step 2: Find the version URL using vanilla version
     let version_entry = manifest
@@ -409,13 +525,9 @@ pub async fn download_mojang_mappings(version: &str) -> Result<PathBuf> {
         .ok_or_else(|| anyhow!("Version {} (vanilla: {}) not found in manifest", version, vanilla_version))?;
 
     // Step 3: Fetch version JSON
-    let version_response = reqwest::get(&version_entry.url)
+    let version_json: VersionJson = download::get_json(&version_entry.url)
         .await
         .context("Failed to fetch version JSON")?;
-    let version_json: VersionJson = version_response
-        .json()
-        .await
-        .context("Failed to parse version JSON")?;
 
     // Step 4: Get mappings URL
     let mappings_info = version_json
@@ -423,22 +535,20 @@ pub async fn download_mojang_mappings(version: &str) -> Result<PathBuf> {
         .client_mappings
         .ok_or_else(|| anyhow!("No client mappings available for version {}", version))?;
 
-    // Step 5: Download mappings
-    let mappings_response = reqwest::get(&mappings_info.url)
-        .await
-        .context("Failed to download mappings")?;
-    let mappings_content = mappings_response
-        .bytes()
-        .await
-        .context("Failed to read mappings content")?;
-
-    // Save to cache
-    fs::write(&mappings_file, &mappings_content).context("Failed to save mappings file")?;
+    // Step 5: Download mappings, resuming a partial download and checking the SHA1
+    download::download_verified(
+        &download::client()?,
+        &mappings_info.url,
+        &mappings_file,
+        Some(&mappings_info.sha1),
+    )
+    .await
+    .context("Failed to download mappings")?;
 
-    println!(
-        "[particle_physics] Downloaded mappings for {} ({} bytes)",
+    info!(
+        "Downloaded mappings for {} ({} bytes)",
         version,
-        mappings_content.len()
+        fs::metadata(&mappings_file).map(|m| m.len()).unwrap_or(0)
     );
 
     Ok(mappings_file)
@@ -543,12 +653,9 @@ fn parse_mappings(
         }
     }
 
-    println!(
-        "[particle_physics] Parsed {} class mappings",
-        class_mappings.len()
-    );
-    println!(
-        "[particle_physics] Particle field mappings: lifetime={:?}, gravity={:?}, hasPhysics={:?}, friction={:?}, xd={:?}, yd={:?}, zd={:?}, quadSize={:?}, rCol={:?}, gCol={:?}, bCol={:?}, alpha={:?}",
+    info!("Parsed {} class mappings", class_mappings.len());
+    info!(
+        "Particle field mappings: lifetime={:?}, gravity={:?}, hasPhysics={:?}, friction={:?}, xd={:?}, yd={:?}, zd={:?}, quadSize={:?}, rCol={:?}, gCol={:?}, bCol={:?}, alpha={:?}",
         particle_fields.lifetime,
         particle_fields.gravity,
         particle_fields.has_physics,
@@ -605,7 +712,10 @@ fn extract_provider_instantiations(
         }
     }
 
-    println!("[particle_physics] Extracted {} provider → particle class mappings", mappings.len());
+    info!(
+        "Extracted {} provider → particle class mappings",
+        mappings.len()
+    );
     Ok(mappings)
 }
 
@@ -651,7 +761,10 @@ fn parse_particle_resources(
             mappings.insert(particle_name, full_provider_class);
         }
 
-        println!("[particle_physics] Parsed {} particle registrations from ParticleResources", mappings.len());
+        info!(
+            "Parsed {} particle registrations from ParticleResources",
+            mappings.len()
+        );
 
         return Ok(mappings);
     }
@@ -707,7 +820,10 @@ fn parse_particle_resources(
         mappings.insert(particle_name, provider_class.clone());
     }
 
-    println!("[particle_physics] Parsed {} particle registrations from ParticleResources (obfuscated)", mappings.len());
+    info!(
+        "Parsed {} particle registrations from ParticleResources (obfuscated)",
+        mappings.len()
+    );
 
     Ok(mappings)
 }
@@ -1259,8 +1375,10 @@ fn detect_skips_friction(source: &str) -> Option<bool> {
 
     // Debug output for campfire particles
     if source.contains("CampfireSmokeParticle") {
-        println!("[detect_skips_friction] CampfireSmokeParticle: has_tick_override={}, calls_super_tick={}, result={}",
-            has_tick_override, calls_super_tick, result);
+        info!(
+            "CampfireSmokeParticle: has_tick_override={}, calls_super_tick={}, result={}",
+            has_tick_override, calls_super_tick, result
+        );
     }
 
     Some(result)
@@ -2118,7 +2236,7 @@ fn find_cfr_jar() -> Option<PathBuf> {
     ];
 
     for loc in locations {
-        if loc.exists() {
+        if download::is_cached_file_valid(&loc) {
             return Some(loc);
         }
     }
@@ -2141,25 +2259,52 @@ pub async fn ensure_cfr_available() -> Result<PathBuf> {
 
     let cfr_path = tools_dir.join("cfr.jar");
 
-    println!("[particle_physics] Downloading CFR decompiler...");
+    info!("Downloading CFR decompiler...");
+
+    // A damaged jar fails every decompile with an unhelpful Java error
+    if cfr_path.exists() {
+        warn!("Cached CFR decompiler is corrupt, downloading again");
+        download::discard_cached_file(&cfr_path);
+    }
 
     // Download CFR from GitHub releases
     let cfr_url = "https://github.com/leibnitz27/cfr/releases/download/0.152/cfr-0.152.jar";
-    let response = reqwest::get(cfr_url)
+    download::download_verified(&download::client()?, cfr_url, &cfr_path, None)
         .await
         .context("Failed to download CFR")?;
-    let bytes = response.bytes().await.context("Failed to read CFR bytes")?;
-
-    fs::write(&cfr_path, &bytes).context("Failed to save CFR")?;
 
-    println!(
-        "[particle_physics] Downloaded CFR decompiler ({} bytes)",
-        bytes.len()
+    info!(
+        "Downloaded CFR decompiler ({} bytes)",
+        fs::metadata(&cfr_path).map(|m| m.len()).unwrap_or(0)
     );
 
     Ok(cfr_path)
 }
 
+/// Error of a decompilation without a `java` executable on the PATH
+#[derive(Debug)]
+pub struct JavaMissing;
+
+impl std::fmt::Display for JavaMissing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Java is not installed or not on the PATH")
+    }
+}
+
+impl std::error::Error for JavaMissing {}
+
+/// Run the JVM (CFR), failing with `JavaMissing` if there is none
+pub fn run_java<I, S>(args: I) -> Result<Output>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    match Command::new("java").args(args).output() {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(JavaMissing.into()),
+        output => output.context("Failed to run CFR decompiler"),
+    }
+}
+
 /// Batch decompile multiple classes from the JAR with Mojang mappings.
 fn batch_decompile_classes(
     cfr_path: &Path,
@@ -2172,10 +2317,10 @@ fn batch_decompile_classes(
         return Ok(());
     }
 
-    println!(
-        "[particle_physics] Decompiling {} classes...",
-        obfuscated_names.len()
-    );
+    // The decompile cache may be shared with other machines
+    let _lock = cache_location::CacheLock::acquire(output_dir)?;
+
+    info!("Decompiling {} classes...", obfuscated_names.len());
 
     let mut args = vec![
         "-jar".to_string(),
@@ -2191,10 +2336,7 @@ fn batch_decompile_classes(
         args.push(name.to_string());
     }
 
-    let output = Command::new("java")
-        .args(&args)
-        .output()
-        .context("Failed to run CFR decompiler")?;
+    let output = run_java(&args)?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -2203,7 +2345,7 @@ fn batch_decompile_classes(
         }
     }
 
-    println!("[particle_physics] ✓ Class decompilation complete");
+    info!("✓ Class decompilation complete");
     Ok(())
 }
 
@@ -2270,17 +2412,14 @@ fn _decompile_class(
     let class_path = outer_class.replace('.', "/") + ".class";
 
     // Run CFR on the outer class
-    let output = Command::new("java")
-        .args([
-            "-jar",
-            cfr_path.to_str().unwrap(),
-            jar_path.to_str().unwrap(),
-            "--outputdir",
-            output_dir.to_str().unwrap(),
-            &outer_class,
-        ])
-        .output()
-        .context("Failed to run CFR decompiler")?;
+    let output = run_java([
+        "-jar",
+        cfr_path.to_str().unwrap(),
+        jar_path.to_str().unwrap(),
+        "--outputdir",
+        output_dir.to_str().unwrap(),
+        &outer_class,
+    ])?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -2296,8 +2435,8 @@ fn _decompile_class(
         if is_inner {
             // The inner class name after $ (e.g., "a" from "hdp$a")
             let inner_name = class_name.split('$').last().unwrap_or("");
-            println!(
-                "[particle_physics] Looking for inner class {} in outer class {}",
+            info!(
+                "Looking for inner class {} in outer class {}",
                 inner_name, outer_class
             );
 
@@ -2343,17 +2482,14 @@ pub async fn extract_particle_physics(
 ) -> Result<ExtractedPhysicsData> {
     // Check cache first
     if let Some(cached) = load_cached_physics_data(version)? {
-        println!(
-            "[particle_physics] Using cached physics data for {}",
-            version
-        );
+        info!("Using cached physics data for {}", version);
         return Ok(cached);
     }
 
-    println!(
-        "[particle_physics] Extracting particle physics for {}...",
-        version
-    );
+    // Another command or app window may be extracting the same version
+    let _lock = cache_location::CacheLock::try_acquire(&get_physics_cache_dir()?.join(version))?;
+
+    info!("Extracting particle physics for {}...", version);
 
     // Download mappings for class lookup + obfuscation mapping during decompilation
     let mappings_path = download_mojang_mappings(version).await?;
@@ -2401,42 +2537,63 @@ pub async fn extract_particle_physics(
 
     // Only decompile if directory doesn't exist or is empty
     if needs_decompile {
-        // Ensure CFR is available
-        let cfr_path = ensure_cfr_available().await?;
-        fs::create_dir_all(&decompile_dir).context("Failed to create decompile directory")?;
-
-        let mut classes_to_decompile: HashSet<String> = HashSet::new();
-        let packages_to_decompile = vec![
-            "net.minecraft.client.particle",
-            "net.minecraft.core.particles",
-        ];
-
-        for package in &packages_to_decompile {
-            let package_prefix = format!("{}.", package);
-            for (obf, deobf) in &class_mappings {
-                if deobf.starts_with(&package_prefix) || deobf == *package {
-                    let class_path = deobf.replace('.', "/") + ".java";
-                    let output_file = decompile_dir.join(&class_path);
-                    if !output_file.exists() {
-                        classes_to_decompile.insert(obf.clone());
+        let decompiled: Result<()> = async {
+            // Ensure CFR is available
+            let cfr_path = ensure_cfr_available().await?;
+            fs::create_dir_all(&decompile_dir).context("Failed to create decompile directory")?;
+
+            let mut classes_to_decompile: HashSet<String> = HashSet::new();
+            let packages_to_decompile = vec![
+                "net.minecraft.client.particle",
+                "net.minecraft.core.particles",
+            ];
+
+            for package in &packages_to_decompile {
+                let package_prefix = format!("{}.", package);
+                for (obf, deobf) in &class_mappings {
+                    if deobf.starts_with(&package_prefix) || deobf == *package {
+                        let class_path = deobf.replace('.', "/") + ".java";
+                        let output_file = decompile_dir.join(&class_path);
+                        if !output_file.exists() {
+                            classes_to_decompile.insert(obf.clone());
+                        }
                     }
                 }
             }
-        }
 
-        let obf_refs: Vec<&str> = classes_to_decompile.iter().map(|s| s.as_str()).collect();
-        batch_decompile_classes(
-            &cfr_path,
-            jar_path,
-            &obf_refs,
-            &decompile_dir,
-            &mappings_path,
-        )?;
+            let obf_refs: Vec<&str> = classes_to_decompile.iter().map(|s| s.as_str()).collect();
+            batch_decompile_classes(
+                &cfr_path,
+                jar_path,
+                &obf_refs,
+                &decompile_dir,
+                &mappings_path,
+            )
+        }
+        .await;
+
+        if let Err(e) = decompiled {
+            // A bundled dataset is complete, unlike what the class files give
+            if e.downcast_ref::<JavaMissing>().is_none()
+                || offline::bundled_dataset_path(offline::Dataset::ParticlePhysics, version)
+                    .is_some()
+            {
+                return Err(e);
+            }
+            warn!(
+                "{}; reading constructor constants from the class files instead (partial physics)",
+                e
+            );
+            return extract_physics_from_class_files(
+                jar_path,
+                version,
+                &class_mappings,
+                &obfuscated_field_mappings,
+                &particle_type_fields,
+            );
+        }
     } else {
-        println!(
-            "[particle_physics] Using cached decompiled source at {:?}",
-            decompile_dir
-        );
+        info!("Using cached decompiled source at {:?}", decompile_dir);
     }
 
     let has_deobf_sources = resources_path.exists();
@@ -2489,8 +2646,12 @@ pub async fn extract_particle_physics(
         particle_classes.insert(instantiated_class.clone(), format!("__particle_class_{}", instantiated_class));
     }
 
-    println!("[particle_physics] Processing {} particle classes ({} particles via providers, {} unique providers)...",
-        particle_classes.len(), particle_to_provider.len(), unique_providers.len());
+    info!(
+        "Processing {} particle classes ({} particles via providers, {} unique providers)...",
+        particle_classes.len(),
+        particle_to_provider.len(),
+        unique_providers.len()
+    );
     let start_time = std::time::Instant::now();
 
     // UNIVERSAL INHERITANCE: Thread-safe cache for inheritance chain extraction
@@ -2511,7 +2672,7 @@ pub async fn extract_particle_physics(
         .filter_map(|(class_name, particle_type)| {
             let count = processed.fetch_add(1, Ordering::Relaxed) + 1;
             if count % 10 == 0 || count == total {
-                println!("[particle_physics] Progress: {}/{} particles processed", count, total);
+                info!("Progress: {}/{} particles processed", count, total);
             }
             // Use deobfuscated class name since decompiled sources are deobfuscated
             // (CFR was run with --obfuscationmappings flag during decompilation)
@@ -2522,7 +2683,7 @@ pub async fn extract_particle_physics(
             let physics = if particle_type.starts_with("__provider_") {
                 let provider_physics = extract_physics_from_provider(&source, class_name);
                 if particle_type.contains("campfire") || particle_type.contains("flame") {
-                    println!("[extraction] {} (provider): {:?}", particle_type, provider_physics);
+                    info!("{} (provider): {:?}", particle_type, provider_physics);
                 }
                 provider_physics
             } else {
@@ -2543,7 +2704,10 @@ pub async fn extract_particle_physics(
                 drop(cache_guard); // Release lock immediately
 
                 if particle_type.contains("campfire") {
-                    println!("[extraction] {} (class with inheritance): {:?}", particle_type, class_physics);
+                    info!(
+                        "{} (class with inheritance): {:?}",
+                        particle_type, class_physics
+                    );
                 }
                 class_physics
             };
@@ -2646,8 +2810,8 @@ pub async fn extract_particle_physics(
         });
 
     let elapsed = start_time.elapsed();
-    println!(
-        "[particle_physics] ✓ Extracted physics for {} particles in {:.2}s",
+    info!(
+        "✓ Extracted physics for {} particles in {:.2}s",
         extracted_particles.len(),
         elapsed.as_secs_f32()
     );
@@ -2708,14 +2872,16 @@ pub async fn extract_particle_physics(
         particles_with_providers.insert(particle_name.clone(), merged_physics);
     }
 
-    println!("[particle_physics] Applied provider + particle class physics to {} particles",
-        particles_with_providers.len() - extracted_particles.len());
+    info!(
+        "Applied provider + particle class physics to {} particles",
+        particles_with_providers.len() - extracted_particles.len()
+    );
 
     // UNIVERSAL INHERITANCE: Physics already include full inheritance chain
     // WHY: We no longer need manual inheritance application because extract_physics_with_inheritance()
     // already walked the entire Java class hierarchy for each particle. All physics from parent
     // classes (Particle, TextureSheetParticle, DustParticleBase, etc.) are already merged in.
-    println!("[particle_physics] Skipping manual inheritance (already extracted from Java hierarchy)");
+    warn!("Skipping manual inheritance (already extracted from Java hierarchy)");
     let mut final_particles = particles_with_providers;
 
     // Post-process derived values.
@@ -2739,6 +2905,7 @@ pub async fn extract_particle_physics(
         schema_version: 8,
         version: version.to_string(),
         particles: final_particles,
+        partial: false,
     };
 
     // Cache the results
@@ -2749,6 +2916,182 @@ pub async fn extract_particle_physics(
     Ok(data)
 }
 
+/// Extract physics from the JAR's class files, without decompiling
+///
+/// Fallback for when there is no Java to run CFR. Only literal assignments to
+/// the common fields (`this.gravity = 0.75F`) are visible in the constructors
+/// this way; values computed from random numbers, constructor arguments or
+/// calls like `scale(...)` are missing, so the result is marked `partial` and
+/// not cached.
+fn extract_physics_from_class_files(
+    jar_path: &Path,
+    version: &str,
+    class_mappings: &HashMap<String, String>,
+    field_mappings: &ParticleFieldMappings,
+    particle_type_fields: &HashMap<String, String>,
+) -> Result<ExtractedPhysicsData> {
+    let obf_class = |deobf: &str| {
+        class_mappings
+            .iter()
+            .find(|(_, name)| name.as_str() == deobf)
+            .map(|(obf, _)| obf.clone())
+            .ok_or_else(|| anyhow!("{} class mapping not found", deobf))
+    };
+    let resources_obf = obf_class("net.minecraft.client.particle.ParticleResources")?;
+    let particle_types_obf = obf_class("net.minecraft.core.particles.ParticleTypes")?;
+
+    let mut classes = ParticleClassFiles {
+        jar: jar_path.to_string_lossy().into_owned(),
+        class_mappings,
+        field_mappings,
+        chains: HashMap::new(),
+    };
+    let resources = classes
+        .read(&resources_obf)
+        .ok_or_else(|| anyhow!("ParticleResources class not found in {:?}", jar_path))?;
+
+    let mut particles = HashMap::new();
+    for (type_field, provider) in resources.static_field_constructors(&particle_types_obf) {
+        let Some(particle_id) = particle_type_fields.get(&type_field) else {
+            continue;
+        };
+        // Providers construct the particle class; fall back to the registered class itself
+        let particle_class = classes
+            .read(&provider)
+            .and_then(|p| {
+                p.instantiated_classes()
+                    .into_iter()
+                    .find(|c| *c != provider && classes.is_particle_class(c))
+            })
+            .unwrap_or(provider);
+
+        let physics = classes.chain_physics(&particle_class);
+        if physics.lifetime.is_some()
+            || physics.gravity.is_some()
+            || physics.size.is_some()
+            || physics.has_physics.is_some()
+            || physics.friction.is_some()
+            || physics.alpha.is_some()
+            || physics.color.is_some()
+        {
+            particles.insert(particle_id.to_lowercase(), physics);
+        }
+    }
+
+    warn!(
+        "Recovered partial physics for {} particles from class files; install Java for full extraction",
+        particles.len()
+    );
+
+    Ok(ExtractedPhysicsData {
+        schema_version: 8,
+        version: version.to_string(),
+        particles,
+        partial: true,
+    })
+}
+
+/// Particle classes read from the JAR, with the physics of each inheritance chain
+struct ParticleClassFiles<'a> {
+    jar: String,
+    class_mappings: &'a HashMap<String, String>,
+    field_mappings: &'a ParticleFieldMappings,
+    chains: HashMap<String, ExtractedParticlePhysics>,
+}
+
+impl ParticleClassFiles<'_> {
+    fn read(&self, obf_class: &str) -> Option<ClassFile> {
+        let entry = format!("{}.class", obf_class.replace('.', "/"));
+        let bytes = zip::extract_zip_entry(&self.jar, &entry).ok()?;
+        ClassFile::parse(&bytes)
+            .map_err(|e| warn!("Failed to parse class {}: {}", obf_class, e))
+            .ok()
+    }
+
+    fn is_particle_class(&self, obf_class: &str) -> bool {
+        self.class_mappings
+            .get(obf_class)
+            .is_some_and(|deobf| deobf.starts_with("net.minecraft.client.particle."))
+    }
+
+    /// Constructor constants of a class merged over those of its particle superclasses
+    fn chain_physics(&mut self, obf_class: &str) -> ExtractedParticlePhysics {
+        if let Some(physics) = self.chains.get(obf_class) {
+            return physics.clone();
+        }
+        let Some(class) = self.read(obf_class) else {
+            return ExtractedParticlePhysics::default();
+        };
+
+        let parent = match class.super_class.as_deref() {
+            Some(parent) if self.is_particle_class(parent) => self.chain_physics(parent),
+            _ => ExtractedParticlePhysics::default(),
+        };
+        let own = physics_from_assignments(
+            &class.constructor_info().field_assignments,
+            self.field_mappings,
+        );
+        let physics = ExtractedParticlePhysics {
+            lifetime: own.lifetime.or(parent.lifetime),
+            gravity: own.gravity.or(parent.gravity),
+            size: own.size.or(parent.size),
+            has_physics: own.has_physics.or(parent.has_physics),
+            alpha: own.alpha.or(parent.alpha),
+            friction: own.friction.or(parent.friction),
+            color: own.color.or(parent.color),
+            ..Default::default()
+        };
+
+        self.chains.insert(obf_class.to_string(), physics.clone());
+        physics
+    }
+}
+
+/// Map constant field assignments of a constructor onto the physics fields
+///
+/// Assignments are matched by obfuscated name and type, since a subclass may
+/// reuse a short obfuscated name for a field of its own.
+fn physics_from_assignments(
+    assignments: &[FieldAssignment],
+    mappings: &ParticleFieldMappings,
+) -> ExtractedParticlePhysics {
+    let mut physics = ExtractedParticlePhysics::default();
+    let mut color = [None; 3];
+
+    for assignment in assignments {
+        let is = |field: &Option<String>, descriptor: &str| {
+            field.as_deref() == Some(assignment.field_name.as_str())
+                && assignment.descriptor == descriptor
+        };
+        let value = assignment.value;
+
+        if is(&mappings.lifetime, "I") {
+            physics.lifetime = value.as_i32().map(|ticks| [ticks, ticks]);
+        } else if is(&mappings.gravity, "F") {
+            physics.gravity = value.as_f32();
+        } else if is(&mappings.friction, "F") {
+            physics.friction = value.as_f32();
+        } else if is(&mappings.quad_size, "F") {
+            physics.size = value.as_f32();
+        } else if is(&mappings.alpha, "F") {
+            physics.alpha = value.as_f32();
+        } else if is(&mappings.has_physics, "Z") {
+            physics.has_physics = value.as_i32().map(|flag| flag != 0);
+        } else if is(&mappings.r_col, "F") {
+            color[0] = value.as_f32();
+        } else if is(&mappings.g_col, "F") {
+            color[1] = value.as_f32();
+        } else if is(&mappings.b_col, "F") {
+            color[2] = value.as_f32();
+        }
+    }
+
+    if let [Some(r), Some(g), Some(b)] = color {
+        physics.color = Some([r, g, b]);
+    }
+    physics
+}
+
 /// Get particle physics, preferring extracted data over hardcoded defaults
 pub async fn get_particle_physics_for_version(
     jar_path: &Path,
@@ -2870,4 +3213,74 @@ mod tests {
         // Forge modded versions
         assert_eq!(parse_vanilla_version("1.20.1-forge-47.2.0"), "1.20.1");
     }
+
+    #[test]
+    fn test_sample_quad_size_curve_multipliers() {
+        let table = sample_quad_size_curve(&QuadSizeCurve::QuadraticShrink { factor: 0.5 }, 3, None);
+        assert!(!table.absolute);
+        assert_eq!(table.ages, vec![0.0, 0.5, 1.0]);
+        assert_eq!(table.values, vec![1.0, 0.875, 0.5]);
+
+        let table = sample_quad_size_curve(
+            &QuadSizeCurve::LinearGrowClamped { multiplier: 32.0 },
+            5,
+            None,
+        );
+        assert_eq!(table.values, vec![0.0, 1.0, 1.0, 1.0, 1.0]);
+
+        let table = sample_quad_size_curve(&QuadSizeCurve::EaseInQuad, 2, None);
+        assert_eq!(table.values, vec![0.0, 1.0]);
+
+        let table = sample_quad_size_curve(
+            &QuadSizeCurve::LinearShrink {
+                lifetime_multiplier: 1.5,
+            },
+            2,
+            None,
+        );
+        assert!((table.values[1] - (1.0 - 1.0 / 1.5)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_sample_quad_size_curve_absolute() {
+        let table = sample_quad_size_curve(&QuadSizeCurve::Absolute { size: 0.5 }, 1, None);
+        assert!(table.absolute);
+        // Always at least spawn and death samples
+        assert_eq!(table.values, vec![0.5, 0.5]);
+
+        // Firework overlay: 7.1 * sin((age - 1) * 0.25 * PI) over a 4 tick lifetime
+        let firework = QuadSizeCurve::SineWave {
+            amplitude: 7.1,
+            frequency: 0.25,
+            phase: -1.0,
+        };
+        let table = sample_quad_size_curve(&firework, 5, Some(4.0));
+        assert!(table.absolute);
+        assert!((table.values[3] - 7.1).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_physics_from_class_file_assignments() {
+        use crate::util::bytecode_parser::ConstantValue;
+
+        let assign = |field_name: &str, descriptor: &str, value| FieldAssignment {
+            field_name: field_name.to_string(),
+            descriptor: descriptor.to_string(),
+            value,
+        };
+        let assignments = vec![
+            assign("u", "F", ConstantValue::Float(0.75)),
+            assign("t", "I", ConstantValue::Int(40)),
+            assign("n", "Z", ConstantValue::Int(0)),
+            // A subclass field reusing the name "u" with another type
+            assign("u", "D", ConstantValue::Double(3.0)),
+        ];
+
+        let physics = physics_from_assignments(&assignments, &obfuscated_field_mappings());
+        assert_eq!(physics.gravity, Some(0.75));
+        assert_eq!(physics.lifetime, Some([40, 40]));
+        assert_eq!(physics.has_physics, Some(false));
+        assert_eq!(physics.friction, None);
+        assert_eq!(physics.color, None);
+    }
 }
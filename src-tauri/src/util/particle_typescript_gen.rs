@@ -11,6 +11,7 @@ use anyhow::{Context, Result};
 use serde_json;
 use std::fs;
 use std::path::Path;
+use tracing::info;
 use super::block_particle_extractor::ExtractedBlockEmissions;
 use super::particle_data::ParticleData as ParticleTextureData;
 use super::particle_physics_extractor::ExtractedPhysicsData;
@@ -80,6 +81,6 @@ export const particleData: ParticleData = {{
     fs::rename(&tmp_path, output_path)
         .context("Failed to finalize TypeScript particle data file")?;
 
-    println!("[particle_data] Generated TypeScript at {:?}", output_path);
+    info!("Generated TypeScript at {:?}", output_path);
     Ok(())
 }
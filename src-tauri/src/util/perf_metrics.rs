@@ -0,0 +1,129 @@
+/// Timing of backend commands, for "it's slow" reports
+///
+/// Every command wrapper runs its impl through `timed` (or `timed_async`),
+/// which records how long the call took. Only the most recent samples of each
+/// command are kept, so the percentiles follow current behaviour and memory
+/// stays bounded however long the app runs. Nothing is written to disk.
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Samples kept per command for the percentiles
+const MAX_SAMPLES: usize = 512;
+
+static METRICS: Lazy<Mutex<HashMap<&'static str, CommandSamples>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Default)]
+struct CommandSamples {
+    count: u64,
+    total: Duration,
+    recent: VecDeque<Duration>,
+}
+
+/// Latency summary of one command
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandMetrics {
+    pub command: String,
+    /// Calls since the app started
+    pub count: u64,
+    /// Time spent in all those calls
+    pub total_ms: f64,
+    /// Median and 95th percentile of the recent calls
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    /// Slowest of the recent calls
+    pub max_ms: f64,
+}
+
+/// Run a command impl and record how long it took
+pub fn timed<T>(command: &'static str, run: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = run();
+    record(command, start.elapsed());
+    result
+}
+
+/// `timed` for async command impls
+pub async fn timed_async<T>(command: &'static str, run: impl Future<Output = T>) -> T {
+    let start = Instant::now();
+    let result = run.await;
+    record(command, start.elapsed());
+    result
+}
+
+fn record(command: &'static str, elapsed: Duration) {
+    let mut metrics = METRICS.lock().unwrap_or_else(|e| e.into_inner());
+    let samples = metrics.entry(command).or_default();
+    samples.count += 1;
+    samples.total += elapsed;
+    if samples.recent.len() == MAX_SAMPLES {
+        samples.recent.pop_front();
+    }
+    samples.recent.push_back(elapsed);
+}
+
+/// Latency of every command called so far, slowest in total first
+pub fn get_metrics() -> Vec<CommandMetrics> {
+    let metrics = METRICS.lock().unwrap_or_else(|e| e.into_inner());
+    let mut summaries: Vec<CommandMetrics> = metrics
+        .iter()
+        .map(|(command, samples)| summarize(command, samples))
+        .collect();
+    summaries.sort_by(|a, b| b.total_ms.total_cmp(&a.total_ms));
+    summaries
+}
+
+fn summarize(command: &str, samples: &CommandSamples) -> CommandMetrics {
+    let mut recent: Vec<Duration> = samples.recent.iter().copied().collect();
+    recent.sort();
+    CommandMetrics {
+        command: command.to_string(),
+        count: samples.count,
+        total_ms: millis(samples.total),
+        p50_ms: percentile(&recent, 0.50),
+        p95_ms: percentile(&recent, 0.95),
+        max_ms: recent.last().copied().map(millis).unwrap_or(0.0),
+    }
+}
+
+/// Nearest-rank percentile of sorted samples, in milliseconds
+fn percentile(sorted: &[Duration], fraction: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (fraction * sorted.len() as f64).ceil() as usize;
+    millis(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_percentiles() {
+        let mut samples = CommandSamples::default();
+        for ms in (1..=100).rev() {
+            samples.count += 1;
+            samples.total += Duration::from_millis(ms);
+            samples.recent.push_back(Duration::from_millis(ms));
+        }
+
+        let summary = summarize("scan_packs_folder", &samples);
+        assert_eq!(summary.count, 100);
+        assert_eq!(summary.total_ms, 5050.0);
+        assert_eq!(summary.p50_ms, 50.0);
+        assert_eq!(summary.p95_ms, 95.0);
+        assert_eq!(summary.max_ms, 100.0);
+
+        assert_eq!(percentile(&[], 0.5), 0.0);
+    }
+}
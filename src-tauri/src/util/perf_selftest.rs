@@ -0,0 +1,263 @@
+/// Performance self-test used to tune scan and build settings per machine
+///
+/// Measures how fast the packs directory can be read and how fast ZIP packs
+/// decompress, each within a small byte and time budget so the test finishes
+/// in a few seconds. Files recently read come from the OS cache, so the disk
+/// figure is an upper bound on a warm machine rather than raw drive speed.
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tracing::info;
+use walkdir::WalkDir;
+use zip::ZipArchive;
+
+/// Bytes read from the packs directory before the disk test stops
+const DISK_BUDGET_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Bytes decompressed from ZIP packs before the extraction test stops
+const ZIP_BUDGET_BYTES: u64 = 32 * 1024 * 1024;
+
+/// Longest either test runs
+const TEST_TIME_BUDGET: Duration = Duration::from_secs(2);
+
+/// Below this read speed (MB/s) the disk is likely a hard drive or network
+/// share, where parallel reads mostly add seeking
+const SLOW_DISK_MB_PER_SEC: f64 = 80.0;
+
+/// Below this decompression speed (MB/s) the CPU is likely a low-power one
+const SLOW_ZIP_MB_PER_SEC: f64 = 40.0;
+
+/// Settings the self-test recommends for this machine
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecommendedSettings {
+    /// Threads scans and builds should use
+    pub thread_pool_size: usize,
+    /// Whether low-memory mode (sequential packs, no icon cache, streamed
+    /// builds) should be on
+    pub low_memory_mode: bool,
+    /// Why these values were picked, one line per decision
+    pub reasons: Vec<String>,
+}
+
+/// Result of `run_perf_selftest`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerfReport {
+    /// Logical cores available to the app
+    pub thread_count: usize,
+    /// Read throughput of the packs directory, None if it had no files
+    pub disk_read_mb_per_sec: Option<f64>,
+    pub disk_bytes_read: u64,
+    /// Decompression throughput of ZIP packs, None if there were none
+    pub zip_extract_mb_per_sec: Option<f64>,
+    pub zip_bytes_extracted: u64,
+    /// How long the whole self-test took
+    pub duration_ms: u64,
+    pub recommended: RecommendedSettings,
+}
+
+/// Measure this machine and recommend settings
+///
+/// # Arguments
+/// * `packs_dir` - Resource packs directory whose files are read for the test
+pub fn run_perf_selftest(packs_dir: &Path) -> PerfReport {
+    let started = Instant::now();
+    let thread_count = std::thread::available_parallelism()
+        .map(|threads| threads.get())
+        .unwrap_or(1);
+
+    let (disk_bytes_read, disk_elapsed) = measure_disk_read(packs_dir);
+    let (zip_bytes_extracted, zip_elapsed) = measure_zip_extraction(packs_dir);
+    let disk_read_mb_per_sec = throughput_mb_per_sec(disk_bytes_read, disk_elapsed);
+    let zip_extract_mb_per_sec = throughput_mb_per_sec(zip_bytes_extracted, zip_elapsed);
+
+    let report = PerfReport {
+        thread_count,
+        disk_read_mb_per_sec,
+        disk_bytes_read,
+        zip_extract_mb_per_sec,
+        zip_bytes_extracted,
+        duration_ms: started.elapsed().as_millis() as u64,
+        recommended: recommend(thread_count, disk_read_mb_per_sec, zip_extract_mb_per_sec),
+    };
+    info!(
+        "{} threads, disk {:?} MB/s, zip {:?} MB/s -> {} threads, low memory {}",
+        report.thread_count,
+        report.disk_read_mb_per_sec,
+        report.zip_extract_mb_per_sec,
+        report.recommended.thread_pool_size,
+        report.recommended.low_memory_mode
+    );
+    report
+}
+
+/// Pick settings from the measurements
+pub fn recommend(
+    thread_count: usize,
+    disk_read_mb_per_sec: Option<f64>,
+    zip_extract_mb_per_sec: Option<f64>,
+) -> RecommendedSettings {
+    let mut reasons = Vec::new();
+
+    // Leave a core for the UI once there are enough of them
+    let mut thread_pool_size = if thread_count > 4 {
+        thread_count - 1
+    } else {
+        thread_count
+    };
+    if disk_read_mb_per_sec.is_some_and(|speed| speed < SLOW_DISK_MB_PER_SEC) {
+        thread_pool_size = thread_pool_size.min(2);
+        reasons.push(format!(
+            "Packs directory reads below {} MB/s; limiting parallel reads",
+            SLOW_DISK_MB_PER_SEC
+        ));
+    }
+    let thread_pool_size = thread_pool_size.max(1);
+
+    let slow_zip = zip_extract_mb_per_sec.is_some_and(|speed| speed < SLOW_ZIP_MB_PER_SEC);
+    let low_memory_mode = thread_count <= 2 || slow_zip;
+    if thread_count <= 2 {
+        reasons.push(format!(
+            "Only {} core(s); processing packs one at a time",
+            thread_count
+        ));
+    } else if slow_zip {
+        reasons.push(format!(
+            "ZIP extraction below {} MB/s; processing packs one at a time",
+            SLOW_ZIP_MB_PER_SEC
+        ));
+    }
+    if reasons.is_empty() {
+        reasons.push(format!(
+            "Using {} of {} cores",
+            thread_pool_size, thread_count
+        ));
+    }
+
+    RecommendedSettings {
+        thread_pool_size,
+        low_memory_mode,
+        reasons,
+    }
+}
+
+fn throughput_mb_per_sec(bytes: u64, elapsed: Duration) -> Option<f64> {
+    if bytes == 0 {
+        return None;
+    }
+    let seconds = elapsed.as_secs_f64().max(1e-6);
+    Some(bytes as f64 / (1024.0 * 1024.0) / seconds)
+}
+
+/// Read files under the packs directory within the budget, returning bytes read
+/// and the time spent reading
+fn measure_disk_read(packs_dir: &Path) -> (u64, Duration) {
+    let started = Instant::now();
+    let mut buffer = vec![0u8; 256 * 1024];
+    let mut total = 0u64;
+
+    let files = WalkDir::new(packs_dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file());
+    'files: for entry in files {
+        let Ok(mut file) = fs::File::open(entry.path()) else {
+            continue;
+        };
+        loop {
+            match file.read(&mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(read) => total += read as u64,
+            }
+            if total >= DISK_BUDGET_BYTES || started.elapsed() >= TEST_TIME_BUDGET {
+                break 'files;
+            }
+        }
+    }
+    (total, started.elapsed())
+}
+
+/// Decompress entries of ZIP packs within the budget, returning uncompressed
+/// bytes produced and the time spent
+fn measure_zip_extraction(packs_dir: &Path) -> (u64, Duration) {
+    let started = Instant::now();
+    let mut total = 0u64;
+
+    let zips = fs::read_dir(packs_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"))
+        });
+    'zips: for zip_path in zips {
+        let Some(mut archive) = fs::File::open(&zip_path)
+            .ok()
+            .and_then(|file| ZipArchive::new(file).ok())
+        else {
+            continue;
+        };
+        for i in 0..archive.len() {
+            let Ok(mut entry) = archive.by_index(i) else {
+                continue;
+            };
+            if let Ok(copied) = std::io::copy(&mut entry, &mut std::io::sink()) {
+                total += copied;
+            }
+            if total >= ZIP_BUDGET_BYTES || started.elapsed() >= TEST_TIME_BUDGET {
+                break 'zips;
+            }
+        }
+    }
+    (total, started.elapsed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recommend() {
+        let fast = recommend(8, Some(900.0), Some(300.0));
+        assert_eq!(fast.thread_pool_size, 7);
+        assert!(!fast.low_memory_mode);
+
+        let slow_disk = recommend(8, Some(30.0), Some(300.0));
+        assert_eq!(slow_disk.thread_pool_size, 2);
+        assert!(!slow_disk.low_memory_mode);
+
+        let small = recommend(2, None, None);
+        assert_eq!(small.thread_pool_size, 2);
+        assert!(small.low_memory_mode);
+
+        let slow_cpu = recommend(4, Some(500.0), Some(10.0));
+        assert_eq!(slow_cpu.thread_pool_size, 4);
+        assert!(slow_cpu.low_memory_mode);
+    }
+
+    #[test]
+    fn test_run_perf_selftest_reads_packs_dir() {
+        let temp_dir = std::env::temp_dir().join("test_run_perf_selftest");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let pack_dir = temp_dir.join("pack");
+        fs::create_dir_all(&pack_dir).unwrap();
+        fs::write(pack_dir.join("pack.mcmeta"), vec![b'x'; 4096]).unwrap();
+
+        let report = run_perf_selftest(&temp_dir);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(report.disk_bytes_read, 4096);
+        assert!(report.disk_read_mb_per_sec.is_some());
+        assert_eq!(report.zip_bytes_extracted, 0);
+        assert!(report.zip_extract_mb_per_sec.is_none());
+        assert!(report.thread_count >= 1);
+    }
+}
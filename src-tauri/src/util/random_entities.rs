@@ -0,0 +1,627 @@
+/// OptiFine random entity textures (assets/<namespace>/optifine/random/**)
+///
+/// Variants of an entity texture sit in a mirror of the textures/ folder and
+/// are numbered after the texture they replace: "optifine/random/entity/creeper/creeper2.png"
+/// is variant 2 of "textures/entity/creeper/creeper.png" (variant 1 is the
+/// texture itself). An optional creeper.properties next to them picks variants
+/// by biome, height, name and so on. Older packs use optifine/mob/ with the
+/// "entity/" folder left out.
+///
+/// A texture's variants and rules are indexed together as one asset
+/// ("minecraft:optifine/random/entity/creeper/creeper"), so the nest builder
+/// copies a whole set from a single pack.
+use crate::model::PackMeta;
+use crate::util::optifine_properties::{matches_text, parse_properties, read_properties_files};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Folder of random textures within a namespace, also the prefix of their asset IDs
+const RANDOM_PATH: &str = "optifine/random/";
+
+/// Legacy folder, relative to textures/entity/ instead of textures/
+const MOB_PATH: &str = "optifine/mob/";
+
+/// A file belonging to a texture's random set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RandomFile {
+    /// Namespace of the textures the set belongs to
+    pub namespace: String,
+    /// Base texture path relative to textures/, without extension (e.g., "entity/creeper/creeper")
+    pub texture: String,
+    /// Variant number, or None for the properties file and other companions
+    pub index: Option<u32>,
+}
+
+/// One numbered rule of a random entity .properties file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RandomEntityRule {
+    /// Variant numbers this rule chooses between
+    pub textures: Vec<u32>,
+    /// Weight of each variant, in `textures` order (empty for equal weights)
+    pub weights: Vec<u32>,
+    /// Remaining conditions (biomes, heights, name, professions, ...)
+    pub conditions: BTreeMap<String, String>,
+}
+
+/// A numbered texture variant
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RandomEntityVariant {
+    pub index: u32,
+    /// Pack-relative path (variant 1 is the base texture)
+    pub file: String,
+}
+
+/// The random texture set a pack defines for one entity texture
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RandomEntityVariants {
+    /// Base texture asset ID (e.g., "minecraft:entity/creeper/creeper")
+    pub asset_id: String,
+    /// Variants sorted by index, starting with the base texture
+    pub variants: Vec<RandomEntityVariant>,
+    /// Rules from the .properties file in number order; empty when variants
+    /// are picked uniformly
+    pub rules: Vec<RandomEntityRule>,
+    /// Pack-relative path of the .properties file, if any
+    pub properties: Option<String>,
+}
+
+/// Split "creeper12" into ("creeper", Some(12)); names without a number are returned whole
+fn split_variant_number(stem: &str) -> (&str, Option<u32>) {
+    let name = stem.trim_end_matches(|c: char| c.is_ascii_digit());
+    if name.is_empty() || name.len() == stem.len() {
+        return (stem, None);
+    }
+    (name, stem[name.len()..].parse().ok())
+}
+
+/// Classify a pack-relative path as part of a random texture set
+///
+/// Numbered PNGs are variants; a .properties file, and companions of a variant
+/// such as "creeper2_e.png", belong to the set without an index.
+pub fn parse_random_file(file: &str) -> Option<RandomFile> {
+    let rest = file.strip_prefix("assets/")?;
+    let (namespace, path) = rest.split_once('/')?;
+    let relative = if let Some(relative) = path.strip_prefix(RANDOM_PATH) {
+        relative.to_string()
+    } else {
+        format!("entity/{}", path.strip_prefix(MOB_PATH)?)
+    };
+
+    let (folder, file_name) = relative.rsplit_once('/')?;
+    let (stem, index) = if let Some(stem) = file_name.strip_suffix(".properties") {
+        (stem, None)
+    } else {
+        let stem = file_name.strip_suffix(".png")?;
+        match split_variant_number(stem) {
+            (name, Some(index)) => (name, Some(index)),
+            // "creeper2_e": a companion of variant 2
+            _ => {
+                let (variant, _) = stem.rsplit_once('_')?;
+                match split_variant_number(variant) {
+                    (name, Some(_)) => (name, None),
+                    _ => return None,
+                }
+            }
+        }
+    };
+
+    Some(RandomFile {
+        namespace: namespace.to_string(),
+        texture: format!("{}/{}", folder, stem),
+        index,
+    })
+}
+
+impl RandomFile {
+    /// Asset ID the set is indexed under
+    pub fn set_asset_id(&self) -> String {
+        format!("{}:{}{}", self.namespace, RANDOM_PATH, self.texture)
+    }
+
+    /// Asset ID of the base texture
+    pub fn texture_asset_id(&self) -> String {
+        format!("{}:{}", self.namespace, self.texture)
+    }
+}
+
+/// Whether an asset ID is a random texture set
+pub fn is_random_asset_id(asset_id: &str) -> bool {
+    asset_id
+        .split_once(':')
+        .is_some_and(|(_, path)| path.starts_with(RANDOM_PATH))
+}
+
+/// Base texture asset ID for a random set's asset ID; other IDs are returned unchanged
+/// "minecraft:optifine/random/entity/creeper/creeper" -> "minecraft:entity/creeper/creeper"
+pub fn texture_asset_id(asset_id: &str) -> String {
+    match asset_id.split_once(':') {
+        Some((namespace, path)) => match path.strip_prefix(RANDOM_PATH) {
+            Some(texture) => format!("{}:{}", namespace, texture),
+            None => asset_id.to_string(),
+        },
+        None => asset_id.to_string(),
+    }
+}
+
+/// Search labels for a random set: "random" and the texture name
+pub fn random_labels(random_file: &RandomFile) -> Vec<String> {
+    let name = random_file
+        .texture
+        .rsplit('/')
+        .next()
+        .unwrap_or(&random_file.texture);
+    vec!["random".to_string(), name.to_string()]
+}
+
+/// Expand "2-4 7" into [2, 3, 4, 7]
+fn parse_numbers(value: &str) -> Vec<u32> {
+    let mut numbers = Vec::new();
+    for part in value.split_whitespace() {
+        match part.split_once('-') {
+            Some((start, end)) => {
+                if let (Ok(start), Ok(end)) = (start.parse::<u32>(), end.parse::<u32>()) {
+                    numbers.extend(start..=end);
+                }
+            }
+            None => numbers.extend(part.parse::<u32>().ok()),
+        }
+    }
+    numbers
+}
+
+/// Parse the numbered rules of a random entity .properties file
+///
+/// Keys look like "textures.1", "weights.1", "biomes.1"; "skins.N" is the
+/// legacy name for "textures.N", and random entity models (optifine/cem/*.properties)
+/// pick "models.N" the same way. Rules are returned in number order.
+pub fn parse_random_rules(text: &str) -> Vec<RandomEntityRule> {
+    let mut rules: BTreeMap<u32, RandomEntityRule> = BTreeMap::new();
+
+    for (key, value) in parse_properties(text) {
+        let Some((name, number)) = key.rsplit_once('.') else {
+            continue;
+        };
+        let Ok(number) = number.parse::<u32>() else {
+            continue;
+        };
+        let rule = rules.entry(number).or_default();
+        match name {
+            "textures" | "skins" | "models" => rule.textures = parse_numbers(&value),
+            "weights" => rule.weights = parse_numbers(&value),
+            _ => {
+                rule.conditions.insert(name.to_string(), value);
+            }
+        }
+    }
+
+    rules.into_values().collect()
+}
+
+/// Collect the random set a pack defines for a base texture
+///
+/// `files` is the pack's file listing. Returns None if the pack has no random
+/// files for the texture.
+pub fn collect_variants(
+    pack: &PackMeta,
+    files: &[String],
+    texture_asset_id: &str,
+) -> Option<RandomEntityVariants> {
+    let mut variants = Vec::new();
+    let mut properties = None;
+
+    for file in files {
+        let Some(random_file) = parse_random_file(file) else {
+            continue;
+        };
+        if random_file.texture_asset_id() != texture_asset_id {
+            continue;
+        }
+        match random_file.index {
+            Some(index) => variants.push(RandomEntityVariant {
+                index,
+                file: file.clone(),
+            }),
+            // optifine/random/ takes precedence over the legacy folder
+            None if file.ends_with(".properties")
+                && (properties.is_none() || file.contains(RANDOM_PATH)) =>
+            {
+                properties = Some(file.clone());
+            }
+            None => {}
+        }
+    }
+    if variants.is_empty() && properties.is_none() {
+        return None;
+    }
+
+    let (namespace, path) = texture_asset_id
+        .split_once(':')
+        .unwrap_or(("minecraft", texture_asset_id));
+    variants.retain(|variant| variant.index > 1);
+    variants.push(RandomEntityVariant {
+        index: 1,
+        file: format!("assets/{}/textures/{}.png", namespace, path),
+    });
+    variants.sort_by_key(|variant| variant.index);
+    variants.dedup_by_key(|variant| variant.index);
+
+    let rules = properties
+        .iter()
+        .flat_map(|file| read_properties_files(pack, std::iter::once(file)))
+        .flat_map(|(_, text)| parse_random_rules(&text))
+        .collect();
+
+    Some(RandomEntityVariants {
+        asset_id: texture_asset_id.to_string(),
+        variants,
+        rules,
+        properties,
+    })
+}
+
+/// What is known about the entity a variant is picked for
+///
+/// A rule condition on a field left unset never matches, like a name rule on
+/// an unnamed mob.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct EntityContext {
+    /// Biome ID ("desert" or "minecraft:desert")
+    pub biome: Option<String>,
+    /// Block height the entity spawned at
+    pub y: Option<i32>,
+    /// Custom name (name tag)
+    pub name: Option<String>,
+    pub baby: Option<bool>,
+    /// Villager profession ("farmer")
+    pub profession: Option<String>,
+    /// Collar, wool or shulker color ("red")
+    pub color: Option<String>,
+    /// "clear", "rain" or "thunder"
+    pub weather: Option<String>,
+    /// Moon phase, 0-7
+    pub moon_phase: Option<u32>,
+    /// Time of day in ticks, 0-23999
+    pub day_time: Option<u32>,
+}
+
+/// The variant OptiFine would show for an entity
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityVariantPick {
+    pub index: u32,
+    /// Pack-relative path, or None if the pack lacks the variant a rule names
+    pub file: Option<String>,
+    /// Position in `rules` of the rule that chose it; None when no rule
+    /// matched (the base texture) or the variants are picked uniformly
+    pub rule: Option<usize>,
+}
+
+/// Strip the namespace OptiFine allows on biome and profession IDs
+fn without_namespace(id: &str) -> String {
+    id.strip_prefix("minecraft:").unwrap_or(id).to_lowercase()
+}
+
+/// Whether `value` is in a range list such as "0-40 60-80"; negative bounds
+/// are written "(-64)-0"
+fn in_ranges(ranges: &str, value: i64) -> bool {
+    ranges.split_whitespace().any(|part| {
+        let part = part.replace(['(', ')'], "");
+        // A leading '-' is a sign, not a separator
+        let separator = part.get(1..).and_then(|rest| rest.find('-')).map(|i| i + 1);
+        let bounds = match separator {
+            Some(i) => (part[..i].parse::<i64>(), part[i + 1..].parse::<i64>()),
+            None => (part.parse::<i64>(), part.parse::<i64>()),
+        };
+        matches!(bounds, (Ok(min), Ok(max)) if (min..=max).contains(&value))
+    })
+}
+
+/// Whether a space separated ID list contains `id`; a leading '!' inverts the list
+fn in_id_list(list: &str, id: &str) -> bool {
+    let (list, negated) = match list.strip_prefix('!') {
+        Some(list) => (list, true),
+        None => (list, false),
+    };
+    let id = without_namespace(id);
+    let found = list.split_whitespace().any(|entry| {
+        let entry = without_namespace(entry);
+        // "farmer:1,2" also names the profession levels, which aren't previewed
+        let name = match entry.rsplit_once(':') {
+            Some((name, levels))
+                if levels
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || c == ',' || c == '-') =>
+            {
+                name
+            }
+            _ => entry.as_str(),
+        };
+        name == id
+    });
+    found != negated
+}
+
+/// Whether one rule condition holds for the entity; conditions the preview
+/// can't evaluate (health, NBT, ...) are ignored
+fn condition_matches(key: &str, value: &str, context: &EntityContext) -> bool {
+    match key {
+        "biomes" => context
+            .biome
+            .as_deref()
+            .is_some_and(|biome| in_id_list(value, biome)),
+        "heights" => context.y.is_some_and(|y| in_ranges(value, y.into())),
+        "minHeight" => context
+            .y
+            .is_some_and(|y| value.parse::<i32>().is_ok_and(|min| y >= min)),
+        "maxHeight" => context
+            .y
+            .is_some_and(|y| value.parse::<i32>().is_ok_and(|max| y <= max)),
+        "name" => context
+            .name
+            .as_deref()
+            .is_some_and(|name| matches_text(value, name)),
+        "baby" => context
+            .baby
+            .is_some_and(|baby| value.parse::<bool>() == Ok(baby)),
+        "professions" => context
+            .profession
+            .as_deref()
+            .is_some_and(|profession| in_id_list(value, profession)),
+        "colors" => context
+            .color
+            .as_deref()
+            .is_some_and(|color| in_id_list(value, color)),
+        "weather" => context
+            .weather
+            .as_deref()
+            .is_some_and(|weather| in_id_list(value, weather)),
+        "moonPhase" => context
+            .moon_phase
+            .is_some_and(|phase| in_ranges(value, phase.into())),
+        "dayTime" => context
+            .day_time
+            .is_some_and(|time| in_ranges(value, time.into())),
+        // ETF's extra keys ("angry", "speed", ...) depend on state the preview
+        // doesn't model, so like unknown keys they never rule a variant out
+        _ => true,
+    }
+}
+
+/// Position in `textures` of the variant a rule picks for an entity ID,
+/// following OptiFine's weighted selection
+fn pick_in_rule(rule: &RandomEntityRule, random_id: u32) -> usize {
+    let count = rule.textures.len();
+    if rule.weights.is_empty() {
+        return random_id as usize % count;
+    }
+
+    // Extra weights are dropped; missing ones get the average of the others
+    let mut weights: Vec<u32> = rule.weights.iter().copied().take(count).collect();
+    let average = (rule.weights.iter().sum::<u32>() / rule.weights.len() as u32).max(1);
+    weights.resize(count, average);
+    let total: u32 = weights.iter().sum();
+    if total == 0 {
+        return random_id as usize % count;
+    }
+
+    let target = random_id % total;
+    let mut cumulative = 0;
+    for (position, weight) in weights.iter().enumerate() {
+        cumulative += weight;
+        if cumulative > target {
+            return position;
+        }
+    }
+    count - 1
+}
+
+/// Pick the variant OptiFine would show for an entity
+///
+/// `seed` stands in for the entity's UUID: OptiFine derives the entity's
+/// random ID from the UUID's least significant bits, so the same seed gives
+/// the same variant. The first rule whose conditions all hold chooses among
+/// its textures by weight; without a matching rule the base texture is used,
+/// and a set without rules picks uniformly.
+pub fn pick_variant(
+    variants: &RandomEntityVariants,
+    context: &EntityContext,
+    seed: u64,
+) -> EntityVariantPick {
+    let random_id = (seed & 0x7FFF_FFFF) as u32;
+    let file_of = |index: u32| {
+        variants
+            .variants
+            .iter()
+            .find(|variant| variant.index == index)
+            .map(|variant| variant.file.clone())
+    };
+
+    if variants.rules.is_empty() {
+        let count = variants.variants.len().max(1);
+        let variant = variants.variants.get(random_id as usize % count);
+        return EntityVariantPick {
+            index: variant.map_or(1, |variant| variant.index),
+            file: variant.map(|variant| variant.file.clone()),
+            rule: None,
+        };
+    }
+
+    let matching = variants.rules.iter().enumerate().find(|(_, rule)| {
+        !rule.textures.is_empty()
+            && rule
+                .conditions
+                .iter()
+                .all(|(key, value)| condition_matches(key, value, context))
+    });
+    match matching {
+        Some((position, rule)) => {
+            let index = rule.textures[pick_in_rule(rule, random_id)];
+            EntityVariantPick {
+                index,
+                file: file_of(index),
+                rule: Some(position),
+            }
+        }
+        None => EntityVariantPick {
+            index: 1,
+            file: file_of(1),
+            rule: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_random_file() {
+        let variant =
+            parse_random_file("assets/minecraft/optifine/random/entity/creeper/creeper2.png")
+                .unwrap();
+        assert_eq!(variant.texture, "entity/creeper/creeper");
+        assert_eq!(variant.index, Some(2));
+        assert_eq!(
+            variant.set_asset_id(),
+            "minecraft:optifine/random/entity/creeper/creeper"
+        );
+        assert_eq!(
+            texture_asset_id(&variant.set_asset_id()),
+            "minecraft:entity/creeper/creeper"
+        );
+
+        let legacy =
+            parse_random_file("assets/minecraft/optifine/mob/creeper/creeper12.png").unwrap();
+        assert_eq!(legacy.texture, "entity/creeper/creeper");
+        assert_eq!(legacy.index, Some(12));
+
+        let properties =
+            parse_random_file("assets/minecraft/optifine/random/entity/creeper/creeper.properties")
+                .unwrap();
+        assert_eq!(properties.texture, "entity/creeper/creeper");
+        assert_eq!(properties.index, None);
+
+        let emissive =
+            parse_random_file("assets/minecraft/optifine/random/entity/creeper/creeper2_e.png")
+                .unwrap();
+        assert_eq!(emissive.texture, "entity/creeper/creeper");
+        assert_eq!(emissive.index, None);
+
+        assert_eq!(
+            parse_random_file("assets/minecraft/optifine/random/entity/creeper/readme.txt"),
+            None
+        );
+        assert_eq!(
+            parse_random_file("assets/minecraft/textures/entity/creeper/creeper2.png"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_random_rules() {
+        let rules = parse_random_rules(
+            "textures.1=2-4 6\nweights.1=10 5 5 1\nbiomes.1=desert badlands\n\
+             skins.2=5\nheights.2=0-40\n",
+        );
+
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].textures, vec![2, 3, 4, 6]);
+        assert_eq!(rules[0].weights, vec![10, 5, 5, 1]);
+        assert_eq!(rules[0].conditions["biomes"], "desert badlands");
+        assert_eq!(rules[1].textures, vec![5]);
+        assert_eq!(rules[1].conditions["heights"], "0-40");
+    }
+
+    #[test]
+    fn test_collect_variants() {
+        let temp_dir = std::env::temp_dir().join("test_random_entities_collect");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let random_dir = temp_dir.join("assets/minecraft/optifine/random/entity/creeper");
+        std::fs::create_dir_all(&random_dir).unwrap();
+        std::fs::write(random_dir.join("creeper.properties"), "textures.1=2 3\n").unwrap();
+        let files = vec![
+            "assets/minecraft/optifine/random/entity/creeper/creeper3.png".to_string(),
+            "assets/minecraft/optifine/random/entity/creeper/creeper2.png".to_string(),
+            "assets/minecraft/optifine/random/entity/creeper/creeper.properties".to_string(),
+            "assets/minecraft/optifine/random/entity/pig/pig2.png".to_string(),
+        ];
+        let pack = PackMeta {
+            id: "pack".to_string(),
+            name: "Pack".to_string(),
+            path: temp_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        };
+
+        let variants = collect_variants(&pack, &files, "minecraft:entity/creeper/creeper");
+        let missing = collect_variants(&pack, &files, "minecraft:entity/cow/cow");
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+
+        let variants = variants.unwrap();
+        let indices: Vec<u32> = variants.variants.iter().map(|v| v.index).collect();
+        assert_eq!(indices, vec![1, 2, 3]);
+        assert_eq!(
+            variants.variants[0].file,
+            "assets/minecraft/textures/entity/creeper/creeper.png"
+        );
+        assert_eq!(variants.rules.len(), 1);
+        assert_eq!(variants.rules[0].textures, vec![2, 3]);
+        assert!(missing.is_none());
+    }
+
+    #[test]
+    fn test_pick_variant() {
+        let variants = RandomEntityVariants {
+            asset_id: "minecraft:entity/zombie/zombie".to_string(),
+            variants: (1..=4)
+                .map(|index| RandomEntityVariant {
+                    index,
+                    file: format!("zombie{}.png", index),
+                })
+                .collect(),
+            rules: parse_random_rules(
+                "textures.1=4\nname.1=ipattern:dinner*\n\
+                 textures.2=2 3\nweights.2=1 3\nbiomes.2=minecraft:desert badlands\nheights.2=(-64)-40\n",
+            ),
+            properties: None,
+        };
+        let desert = EntityContext {
+            biome: Some("desert".to_string()),
+            y: Some(40),
+            ..Default::default()
+        };
+
+        // Weights 1 and 3: ID 0 picks the first texture, IDs 1-3 the second
+        let pick = pick_variant(&variants, &desert, 0);
+        assert_eq!((pick.index, pick.rule), (2, Some(1)));
+        assert_eq!(pick.file.as_deref(), Some("zombie2.png"));
+        // Only the low 31 bits of the seed count
+        assert_eq!(pick_variant(&variants, &desert, (1 << 40) | 5).index, 3);
+
+        let named = EntityContext {
+            name: Some("Dinnerbone".to_string()),
+            ..desert.clone()
+        };
+        assert_eq!(pick_variant(&variants, &named, 0).rule, Some(0));
+
+        let high = EntityContext {
+            y: Some(41),
+            ..desert
+        };
+        let pick = pick_variant(&variants, &high, 0);
+        assert_eq!((pick.index, pick.rule), (1, None));
+
+        let uniform = RandomEntityVariants {
+            rules: Vec::new(),
+            ..variants
+        };
+        assert_eq!(
+            pick_variant(&uniform, &EntityContext::default(), 6).index,
+            3
+        );
+    }
+}
@@ -0,0 +1,202 @@
+/// Neighboring textures taken from packs of different resolutions
+///
+/// Textures drawn side by side on one block (grass_block_top next to
+/// grass_block_side, a log's bark next to its end, a block next to its CTM
+/// tiles) look jarring when one is 16x and the other 32x. Textures are grouped
+/// by the block they belong to, and a group whose winners come from packs of
+/// different resolution (`PackMeta::texture_resolution`) is reported together
+/// with a pack that could provide the whole group instead.
+use crate::model::PackMeta;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Suffixes naming one face or state of a block's texture, stripped to find the
+/// block the texture belongs to ("grass_block_side_overlay" -> "grass_block")
+const FACE_SUFFIXES: &[&str] = &[
+    "_top", "_bottom", "_side", "_front", "_back", "_end", "_overlay", "_snow", "_inner", "_outer",
+    "_on", "_off", "_lit", "_open", "_closed", "_base", "_tip",
+];
+
+const BLOCK_PREFIX: &str = "block/";
+const CTM_BLOCK_PREFIX: &str = "optifine/ctm/block/";
+const CTM_TILE_PREFIX: &str = "optifine/ctm/tile/";
+
+/// One texture of a mixed group and where it comes from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MixedTexture {
+    pub asset_id: String,
+    pub pack_id: String,
+    pub resolution: u32,
+}
+
+/// Textures of one block that come from packs of different resolutions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolutionMismatch {
+    /// Block the textures belong to ("minecraft:block/grass_block")
+    pub group: String,
+    /// Highest resolution first
+    pub textures: Vec<MixedTexture>,
+    /// Highest-priority pack providing every texture of the group, if any
+    pub suggested_pack: Option<String>,
+}
+
+/// Block a texture or CTM asset belongs to, or None for other assets
+/// "minecraft:block/oak_log_top" -> "minecraft:block/oak_log"
+/// "minecraft:optifine/ctm/block/glass" -> "minecraft:block/glass"
+pub fn neighbor_group(asset_id: &str) -> Option<String> {
+    let (namespace, path) = asset_id.split_once(':')?;
+    let name = path
+        .strip_prefix(BLOCK_PREFIX)
+        .or_else(|| path.strip_prefix(CTM_BLOCK_PREFIX))
+        .or_else(|| path.strip_prefix(CTM_TILE_PREFIX))?;
+    if name.is_empty() || name.contains('/') {
+        return None;
+    }
+    Some(format!(
+        "{}:{}{}",
+        namespace,
+        BLOCK_PREFIX,
+        block_stem(name)
+    ))
+}
+
+/// Name with face suffixes removed, keeping at least one word
+fn block_stem(name: &str) -> &str {
+    let mut stem = name;
+    while let Some(shorter) = FACE_SUFFIXES
+        .iter()
+        .find_map(|suffix| stem.strip_suffix(suffix))
+        .filter(|shorter| !shorter.is_empty())
+    {
+        stem = shorter;
+    }
+    stem
+}
+
+/// Groups of neighboring textures whose winning packs differ in resolution
+///
+/// # Arguments
+/// * `winners` - Asset ID -> pack the nest takes it from
+/// * `packs` - Scanned packs, for their resolution
+/// * `providers` - Asset ID -> packs providing it
+/// * `pack_order` - Pack IDs, highest priority first
+pub fn find_resolution_mismatches(
+    winners: &HashMap<String, String>,
+    packs: &[PackMeta],
+    providers: &HashMap<String, Vec<String>>,
+    pack_order: &[String],
+) -> Vec<ResolutionMismatch> {
+    let resolutions: HashMap<&str, u32> = packs
+        .iter()
+        .filter_map(|pack| Some((pack.id.as_str(), pack.texture_resolution?)))
+        .collect();
+
+    let mut groups: BTreeMap<String, Vec<MixedTexture>> = BTreeMap::new();
+    for (asset_id, pack_id) in winners {
+        let (Some(group), Some(&resolution)) =
+            (neighbor_group(asset_id), resolutions.get(pack_id.as_str()))
+        else {
+            continue;
+        };
+        groups.entry(group).or_default().push(MixedTexture {
+            asset_id: asset_id.clone(),
+            pack_id: pack_id.clone(),
+            resolution,
+        });
+    }
+
+    groups
+        .into_iter()
+        .filter(|(_, textures)| {
+            textures
+                .iter()
+                .any(|texture| texture.resolution != textures[0].resolution)
+        })
+        .map(|(group, mut textures)| {
+            textures.sort_by(|a, b| {
+                b.resolution
+                    .cmp(&a.resolution)
+                    .then_with(|| a.asset_id.cmp(&b.asset_id))
+            });
+            let suggested_pack = pack_order
+                .iter()
+                .find(|pack_id| {
+                    resolutions.contains_key(pack_id.as_str())
+                        && textures.iter().all(|texture| {
+                            providers
+                                .get(&texture.asset_id)
+                                .is_some_and(|list| list.contains(pack_id))
+                        })
+                })
+                .cloned();
+            ResolutionMismatch {
+                group,
+                textures,
+                suggested_pack,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_resolution_mismatches() {
+        assert_eq!(
+            neighbor_group("minecraft:block/grass_block_side_overlay").as_deref(),
+            Some("minecraft:block/grass_block")
+        );
+        assert_eq!(
+            neighbor_group("minecraft:optifine/ctm/block/glass").as_deref(),
+            Some("minecraft:block/glass")
+        );
+        assert_eq!(neighbor_group("minecraft:item/diamond"), None);
+        // A name that is only a suffix keeps it
+        assert_eq!(
+            neighbor_group("minecraft:block/end").as_deref(),
+            Some("minecraft:block/end")
+        );
+
+        let pack = |id: &str, resolution: u32| PackMeta {
+            id: id.to_string(),
+            name: id.to_string(),
+            texture_resolution: Some(resolution),
+            ..Default::default()
+        };
+        let packs = vec![pack("hd", 32), pack("classic", 16)];
+        let winners = HashMap::from([
+            (
+                "minecraft:block/grass_block_top".to_string(),
+                "hd".to_string(),
+            ),
+            (
+                "minecraft:block/grass_block_side".to_string(),
+                "classic".to_string(),
+            ),
+            ("minecraft:block/stone".to_string(), "hd".to_string()),
+            ("minecraft:block/oak_log".to_string(), "classic".to_string()),
+            (
+                "minecraft:block/oak_log_top".to_string(),
+                "classic".to_string(),
+            ),
+        ]);
+        let both = vec!["hd".to_string(), "classic".to_string()];
+        let providers = HashMap::from([
+            ("minecraft:block/grass_block_top".to_string(), both.clone()),
+            ("minecraft:block/grass_block_side".to_string(), both),
+        ]);
+        let order = vec!["hd".to_string(), "classic".to_string()];
+
+        let mismatches = find_resolution_mismatches(&winners, &packs, &providers, &order);
+
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].group, "minecraft:block/grass_block");
+        assert_eq!(mismatches[0].textures[0].pack_id, "hd");
+        assert_eq!(mismatches[0].textures[1].resolution, 16);
+        assert_eq!(mismatches[0].suggested_pack.as_deref(), Some("hd"));
+    }
+}
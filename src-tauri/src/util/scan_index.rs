@@ -0,0 +1,357 @@
+/// Persistent scan index backed by SQLite
+///
+/// Every scan is stored under the packs directory it came from, so the app can
+/// show the last known library on startup and answer label searches and
+/// provider lookups without rescanning. The database lives at
+/// <cache dir>/weaverbird/scan_index.sqlite3 and is rebuilt from scratch when
+/// its schema version changes.
+use crate::model::{AssetRecord, PackMeta, ScanResult};
+use anyhow::{anyhow, Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::{Path, PathBuf};
+
+/// Bumped whenever the tables change; older databases are dropped and recreated
+const SCHEMA_VERSION: i64 = 2;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS scans (
+        packs_dir TEXT PRIMARY KEY,
+        scanned_at INTEGER NOT NULL,
+        packs TEXT NOT NULL,
+        ctm_conflicts TEXT NOT NULL,
+        duplicate_packs TEXT NOT NULL,
+        texture_info TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS assets (
+        packs_dir TEXT NOT NULL,
+        asset_id TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        labels TEXT NOT NULL,
+        files TEXT NOT NULL,
+        PRIMARY KEY (packs_dir, asset_id)
+    );
+    CREATE TABLE IF NOT EXISTS asset_labels (
+        packs_dir TEXT NOT NULL,
+        label TEXT NOT NULL COLLATE NOCASE,
+        asset_id TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS asset_labels_by_label ON asset_labels (packs_dir, label);
+    CREATE TABLE IF NOT EXISTS providers (
+        packs_dir TEXT NOT NULL,
+        asset_id TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        pack_id TEXT NOT NULL,
+        PRIMARY KEY (packs_dir, asset_id, position)
+    );
+";
+
+const TABLES: &[&str] = &["scans", "assets", "asset_labels", "providers"];
+
+/// Location of the scan index database
+pub fn default_index_path() -> Result<PathBuf> {
+    let cache_dir = dirs::cache_dir()
+        .ok_or_else(|| anyhow!("Could not find cache directory"))?
+        .join("weaverbird");
+    std::fs::create_dir_all(&cache_dir).context("Failed to create weaverbird cache directory")?;
+    Ok(cache_dir.join("scan_index.sqlite3"))
+}
+
+/// Key a packs directory is stored under
+///
+/// Canonicalized when possible so "packs" and "packs/" share one entry.
+pub fn index_key(packs_dir: &str) -> String {
+    std::fs::canonicalize(packs_dir)
+        .map(|path| path.to_string_lossy().to_string())
+        .unwrap_or_else(|_| packs_dir.trim_end_matches(['/', '\\']).to_string())
+}
+
+/// An open scan index
+pub struct ScanIndex {
+    conn: Connection,
+}
+
+impl ScanIndex {
+    /// Open (or create) the index at `path`
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open scan index {}", path.display()))?;
+
+        let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        if version != SCHEMA_VERSION {
+            for table in TABLES {
+                conn.execute(&format!("DROP TABLE IF EXISTS {}", table), [])?;
+            }
+            conn.execute(&format!("PRAGMA user_version = {}", SCHEMA_VERSION), [])?;
+        }
+        conn.execute_batch(SCHEMA)?;
+
+        Ok(Self { conn })
+    }
+
+    /// Open the index in the weaverbird cache directory
+    pub fn open_default() -> Result<Self> {
+        Self::open(&default_index_path()?)
+    }
+
+    /// Replace the stored scan of a packs directory
+    pub fn store(&mut self, packs_dir: &str, scan: &ScanResult) -> Result<()> {
+        let key = index_key(packs_dir);
+        let tx = self.conn.transaction()?;
+        delete_scan(&tx, &key)?;
+
+        tx.execute(
+            "INSERT INTO scans
+                 (packs_dir, scanned_at, packs, ctm_conflicts, duplicate_packs, texture_info)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                key,
+                chrono::Utc::now().timestamp(),
+                serde_json::to_string(&scan.packs)?,
+                serde_json::to_string(&scan.ctm_conflicts)?,
+                serde_json::to_string(&scan.duplicate_packs)?,
+                serde_json::to_string(&scan.texture_info)?,
+            ],
+        )?;
+
+        {
+            let mut insert_asset = tx.prepare(
+                "INSERT INTO assets (packs_dir, asset_id, position, labels, files)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?;
+            let mut insert_label = tx.prepare(
+                "INSERT INTO asset_labels (packs_dir, label, asset_id) VALUES (?1, ?2, ?3)",
+            )?;
+            for (position, asset) in scan.assets.iter().enumerate() {
+                insert_asset.execute(params![
+                    key,
+                    asset.id,
+                    position as i64,
+                    serde_json::to_string(&asset.labels)?,
+                    serde_json::to_string(&asset.files)?,
+                ])?;
+                for label in &asset.labels {
+                    insert_label.execute(params![key, label, asset.id])?;
+                }
+            }
+
+            let mut insert_provider = tx.prepare(
+                "INSERT INTO providers (packs_dir, asset_id, position, pack_id)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for (asset_id, pack_ids) in &scan.providers {
+                for (position, pack_id) in pack_ids.iter().enumerate() {
+                    insert_provider.execute(params![key, asset_id, position as i64, pack_id])?;
+                }
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// The stored scan of a packs directory, if it was ever indexed
+    pub fn load(&self, packs_dir: &str) -> Result<Option<ScanResult>> {
+        let key = index_key(packs_dir);
+        let scan = self
+            .conn
+            .query_row(
+                "SELECT packs, ctm_conflicts, duplicate_packs, texture_info
+                 FROM scans WHERE packs_dir = ?1",
+                params![key],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, String>(3)?,
+                    ))
+                },
+            )
+            .optional()?;
+        let Some((packs, ctm_conflicts, duplicate_packs, texture_info)) = scan else {
+            return Ok(None);
+        };
+        let packs: Vec<PackMeta> = serde_json::from_str(&packs)?;
+
+        let mut statement = self.conn.prepare(
+            "SELECT asset_id, labels, files FROM assets WHERE packs_dir = ?1 ORDER BY position",
+        )?;
+        let assets = statement
+            .query_map(params![key], read_asset)?
+            .collect::<rusqlite::Result<Vec<_>>>()?
+            .into_iter()
+            .map(parse_asset)
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut providers: std::collections::HashMap<String, Vec<String>> =
+            std::collections::HashMap::new();
+        let mut statement = self.conn.prepare(
+            "SELECT asset_id, pack_id FROM providers WHERE packs_dir = ?1
+             ORDER BY asset_id, position",
+        )?;
+        let rows = statement.query_map(params![key], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        for row in rows {
+            let (asset_id, pack_id) = row?;
+            providers.entry(asset_id).or_default().push(pack_id);
+        }
+
+        Ok(Some(ScanResult {
+            packs,
+            assets,
+            providers,
+            ctm_conflicts: serde_json::from_str(&ctm_conflicts)?,
+            duplicate_packs: serde_json::from_str(&duplicate_packs)?,
+            texture_info: serde_json::from_str(&texture_info)?,
+            errors: Vec::new(),
+        }))
+    }
+
+    /// Indexed assets carrying a label (case-insensitive), in scan order
+    pub fn search_by_label(
+        &self,
+        packs_dir: &str,
+        label: &str,
+        limit: usize,
+    ) -> Result<Vec<AssetRecord>> {
+        let mut statement = self.conn.prepare(
+            "SELECT assets.asset_id, assets.labels, assets.files
+             FROM asset_labels
+             JOIN assets ON assets.packs_dir = asset_labels.packs_dir
+                 AND assets.asset_id = asset_labels.asset_id
+             WHERE asset_labels.packs_dir = ?1 AND asset_labels.label = ?2
+             GROUP BY assets.asset_id
+             ORDER BY assets.position
+             LIMIT ?3",
+        )?;
+        let rows = statement
+            .query_map(
+                params![index_key(packs_dir), label, limit as i64],
+                read_asset,
+            )?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        rows.into_iter().map(parse_asset).collect()
+    }
+
+    /// Indexed providers of an asset, in the order the scan listed them
+    pub fn providers(&self, packs_dir: &str, asset_id: &str) -> Result<Vec<String>> {
+        let mut statement = self.conn.prepare(
+            "SELECT pack_id FROM providers WHERE packs_dir = ?1 AND asset_id = ?2
+             ORDER BY position",
+        )?;
+        let pack_ids = statement
+            .query_map(params![index_key(packs_dir), asset_id], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+        Ok(pack_ids)
+    }
+
+    /// Drop the stored scan of a packs directory, e.g. after its packs changed
+    pub fn forget(&mut self, packs_dir: &str) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        delete_scan(&tx, &index_key(packs_dir))?;
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+/// Delete every row stored for an index key
+fn delete_scan(conn: &Connection, key: &str) -> Result<()> {
+    for table in TABLES {
+        conn.execute(
+            &format!("DELETE FROM {} WHERE packs_dir = ?1", table),
+            params![key],
+        )?;
+    }
+    Ok(())
+}
+
+/// Raw (asset ID, labels JSON, files JSON) columns of an assets row
+fn read_asset(row: &rusqlite::Row) -> rusqlite::Result<(String, String, String)> {
+    Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+}
+
+fn parse_asset((id, labels, files): (String, String, String)) -> Result<AssetRecord> {
+    Ok(AssetRecord {
+        id,
+        labels: serde_json::from_str(&labels)?,
+        files: serde_json::from_str(&files)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::TextureInfo;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_scan_index_round_trip() {
+        let temp_dir = std::env::temp_dir().join("test_scan_index_round_trip");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+        let asset = |id: &str, labels: &[&str]| AssetRecord {
+            id: id.to_string(),
+            labels: labels.iter().map(|label| label.to_string()).collect(),
+            files: vec![format!("{}.png", id)],
+        };
+        let scan = ScanResult {
+            packs: vec![PackMeta {
+                id: "pack_a".to_string(),
+                name: "pack_a".to_string(),
+                ..Default::default()
+            }],
+            assets: vec![
+                asset("minecraft:block/stone", &["minecraft", "block", "stone"]),
+                asset("minecraft:block/dirt", &["minecraft", "block", "dirt"]),
+            ],
+            providers: HashMap::from([(
+                "minecraft:block/stone".to_string(),
+                vec!["pack_a".to_string(), "minecraft:vanilla".to_string()],
+            )]),
+            texture_info: HashMap::from([(
+                "minecraft:block/stone".to_string(),
+                HashMap::from([(
+                    "pack_a".to_string(),
+                    TextureInfo {
+                        width: 32,
+                        height: 32,
+                        bit_depth: 8,
+                        color_type: 6,
+                    },
+                )]),
+            )]),
+            ..Default::default()
+        };
+        let packs_dir = temp_dir.to_string_lossy().to_string();
+
+        let mut index = ScanIndex::open(&temp_dir.join("index.sqlite3")).unwrap();
+        let missing = index.load(&packs_dir).unwrap();
+        index.store(&packs_dir, &scan).unwrap();
+        // Storing again replaces the previous scan
+        index.store(&format!("{}/", packs_dir), &scan).unwrap();
+        let loaded = index.load(&packs_dir).unwrap().unwrap();
+        let blocks = index.search_by_label(&packs_dir, "BLOCK", 10).unwrap();
+        let first_block = index.search_by_label(&packs_dir, "block", 1).unwrap();
+        let providers = index
+            .providers(&packs_dir, "minecraft:block/stone")
+            .unwrap();
+        index.forget(&packs_dir).unwrap();
+        let forgotten = index.load(&packs_dir).unwrap();
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+
+        assert!(missing.is_none());
+        assert_eq!(loaded.packs[0].id, "pack_a");
+        assert_eq!(loaded.assets, scan.assets);
+        assert_eq!(loaded.providers, scan.providers);
+        assert_eq!(loaded.texture_info, scan.texture_info);
+        assert_eq!(
+            blocks.iter().map(|a| a.id.as_str()).collect::<Vec<_>>(),
+            vec!["minecraft:block/stone", "minecraft:block/dirt"]
+        );
+        assert_eq!(first_block.len(), 1);
+        assert_eq!(providers, vec!["pack_a", "minecraft:vanilla"]);
+        assert!(forgotten.is_none());
+    }
+}
@@ -0,0 +1,337 @@
+/// End-to-end self-test suite driven by synthetic packs
+///
+/// Only compiled with the `selftest` feature. Generates a packs directory of
+/// folder and ZIP packs with a configurable number of textures, textures shared
+/// between packs (conflicts) and corrupt files, then runs it through the same
+/// scan -> validate -> build pipeline the app uses and checks the results. Meant
+/// for local regression runs without CI: `cargo test --features selftest` or the
+/// `run_selftest_suite` command from a dev build.
+use crate::model::{OverrideSelection, PackMeta};
+use crate::util::{asset_indexer, pack_format, pack_scanner, texture_info, weaver_nest};
+use crate::validation;
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tracing::info;
+
+/// pack_format written to every synthetic pack (1.21.4)
+const SYNTHETIC_PACK_FORMAT: u32 = 46;
+
+/// Shape of the generated packs directory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SyntheticSuiteConfig {
+    /// Number of packs; every second one is written as a ZIP
+    pub packs: usize,
+    /// Textures only one pack provides, per pack
+    pub unique_textures: usize,
+    /// Textures every pack provides, so the build has to pick a winner
+    pub shared_textures: usize,
+    /// Corrupt PNGs and model JSONs per pack, which must not break the pipeline
+    pub broken_files: usize,
+}
+
+impl Default for SyntheticSuiteConfig {
+    fn default() -> Self {
+        Self {
+            packs: 4,
+            unique_textures: 50,
+            shared_textures: 10,
+            broken_files: 2,
+        }
+    }
+}
+
+/// Outcome of one pipeline stage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelftestStep {
+    pub name: String,
+    pub passed: bool,
+    pub duration_ms: u64,
+    /// What was checked, or why it failed
+    pub detail: String,
+}
+
+/// Result of `run_selftest_suite`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelftestReport {
+    pub config: SyntheticSuiteConfig,
+    pub steps: Vec<SelftestStep>,
+    /// True when every step passed
+    pub passed: bool,
+}
+
+/// Texture asset ID of a pack's unique texture
+fn unique_texture_id(pack: usize, texture: usize) -> String {
+    format!("minecraft:block/synthetic_{}_{}", pack, texture)
+}
+
+/// Texture asset ID of a texture every pack provides
+fn shared_texture_id(texture: usize) -> String {
+    format!("minecraft:block/shared_{}", texture)
+}
+
+fn pack_name(pack: usize) -> String {
+    format!("synthetic_{}", pack)
+}
+
+/// 16x16 PNG filled with a color derived from `seed`, so each pack's copy of a
+/// shared texture has different bytes
+fn synthetic_png(seed: usize) -> Result<Vec<u8>> {
+    let color = image::Rgba([(seed * 37) as u8, (seed * 91) as u8, (seed * 13) as u8, 255]);
+    let mut png = Vec::new();
+    image::RgbaImage::from_pixel(16, 16, color).write_to(
+        &mut std::io::Cursor::new(&mut png),
+        image::ImageOutputFormat::Png,
+    )?;
+    Ok(png)
+}
+
+/// Pack-relative files of one synthetic pack
+fn synthetic_pack_files(
+    pack: usize,
+    config: &SyntheticSuiteConfig,
+) -> Result<Vec<(String, Vec<u8>)>> {
+    let texture_path = |id: &str| crate::util::pack_files::texture_entry_path(id);
+    let mut files = vec![(
+        "pack.mcmeta".to_string(),
+        format!(
+            r#"{{"pack": {{"pack_format": {}, "description": "Synthetic pack {}"}}}}"#,
+            SYNTHETIC_PACK_FORMAT, pack
+        )
+        .into_bytes(),
+    )];
+    for texture in 0..config.unique_textures {
+        files.push((
+            texture_path(&unique_texture_id(pack, texture)),
+            synthetic_png(pack * 1000 + texture)?,
+        ));
+    }
+    for texture in 0..config.shared_textures {
+        files.push((
+            texture_path(&shared_texture_id(texture)),
+            synthetic_png(pack * 1000 + 500 + texture)?,
+        ));
+    }
+    for broken in 0..config.broken_files {
+        files.push((
+            texture_path(&format!("minecraft:block/broken_{}_{}", pack, broken)),
+            b"not a png".to_vec(),
+        ));
+        files.push((
+            format!(
+                "assets/minecraft/models/block/broken_{}_{}.json",
+                pack, broken
+            ),
+            b"{ \"parent\": ".to_vec(),
+        ));
+    }
+    Ok(files)
+}
+
+/// Write the synthetic packs into `packs_dir`
+///
+/// Packs are numbered from 0; odd-numbered ones are ZIP files.
+pub fn generate_synthetic_packs(packs_dir: &Path, config: &SyntheticSuiteConfig) -> Result<()> {
+    fs::create_dir_all(packs_dir)?;
+    for pack in 0..config.packs {
+        let files = synthetic_pack_files(pack, config)?;
+        if pack % 2 == 1 {
+            let zip_path = packs_dir.join(format!("{}.zip", pack_name(pack)));
+            let mut writer = ::zip::ZipWriter::new(fs::File::create(&zip_path)?);
+            for (path, contents) in files {
+                writer.start_file(path, ::zip::write::FileOptions::default())?;
+                writer.write_all(&contents)?;
+            }
+            writer.finish()?;
+        } else {
+            let root = packs_dir.join(pack_name(pack));
+            for (path, contents) in files {
+                let path = root.join(path);
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(path, contents)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Generate synthetic packs in a temporary directory and run them through
+/// scan, validation and build, checking each stage's output
+pub fn run_selftest_suite(config: &SyntheticSuiteConfig) -> SelftestReport {
+    let root = std::env::temp_dir().join(format!("weaverbird_selftest_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    let packs_dir = root.join("packs");
+    let output_dir = root.join("nest");
+
+    let mut steps = Vec::new();
+    let mut run_step = |name: &str, step: &mut dyn FnMut() -> Result<String>| {
+        let started = Instant::now();
+        let outcome = step();
+        let passed = outcome.is_ok();
+        let detail = outcome.unwrap_or_else(|e| format!("{:#}", e));
+        info!(
+            "{} {}: {}",
+            name,
+            if passed { "passed" } else { "FAILED" },
+            detail
+        );
+        steps.push(SelftestStep {
+            name: name.to_string(),
+            passed,
+            duration_ms: started.elapsed().as_millis() as u64,
+            detail,
+        });
+        passed
+    };
+
+    let mut packs: Vec<PackMeta> = Vec::new();
+    let mut assets = Vec::new();
+    let mut providers: HashMap<String, Vec<String>> = HashMap::new();
+    let mut pack_order: Vec<String> = Vec::new();
+
+    // Each stage needs the previous one, so stop at the first failure
+    let _ = run_step("generate", &mut || {
+        generate_synthetic_packs(&packs_dir, config)?;
+        Ok(format!("{} packs in {}", config.packs, packs_dir.display()))
+    }) && run_step("scan", &mut || {
+        packs = pack_scanner::scan_packs(&packs_dir.to_string_lossy())?;
+        if packs.len() != config.packs {
+            bail!("Expected {} packs, found {}", config.packs, packs.len());
+        }
+        (assets, providers) = asset_indexer::index_assets(&packs)?;
+        check_scan(&packs, &assets, &providers, config)?;
+        pack_order = (0..config.packs)
+            .map(|pack| find_pack_id(&packs, pack))
+            .collect::<Result<_>>()?;
+        Ok(format!("{} packs, {} assets", packs.len(), assets.len()))
+    }) && run_step("validate", &mut || {
+        fs::create_dir_all(&output_dir)?;
+        validation::validate_build_request(
+            &packs_dir.to_string_lossy(),
+            &pack_order,
+            &HashMap::new(),
+            &output_dir.to_string_lossy(),
+        )
+        .map_err(|e| anyhow!(e.message))?;
+        for pack in &packs {
+            let compatibility = pack_format::check_pack_compatibility(pack, "1.21.4")?;
+            if compatibility.status != pack_format::CompatibilityStatus::Compatible {
+                bail!("{} reported as {:?}", pack.id, compatibility.status);
+            }
+        }
+        let sizes = texture_info::read_texture_info(&packs, &assets, &providers);
+        let broken_measured = sizes.keys().filter(|id| id.contains("broken_")).count();
+        if broken_measured > 0 {
+            bail!("{} corrupt textures were measured", broken_measured);
+        }
+        Ok(format!("{} packs compatible", packs.len()))
+    }) && run_step("build", &mut || {
+        let output = weaver_nest::build_weaver_nest(
+            &packs,
+            &assets,
+            &providers,
+            &pack_order,
+            &HashMap::<String, OverrideSelection>::new(),
+            &output_dir.to_string_lossy(),
+            &weaver_nest::NestBuildOptions::default(),
+        )?;
+        check_build(&output.path, config)?;
+        Ok(format!(
+            "Nest written with {} warning(s)",
+            output.warnings.len()
+        ))
+    });
+
+    let _ = fs::remove_dir_all(&root);
+
+    SelftestReport {
+        config: config.clone(),
+        passed: steps.iter().all(|step| step.passed),
+        steps,
+    }
+}
+
+/// ID the scanner gave synthetic pack `pack`
+fn find_pack_id(packs: &[PackMeta], pack: usize) -> Result<String> {
+    let name = pack_name(pack);
+    packs
+        .iter()
+        .find(|meta| meta.id == name || meta.id == format!("{}.zip", name))
+        .map(|meta| meta.id.clone())
+        .ok_or_else(|| anyhow!("Pack {} was not scanned", name))
+}
+
+/// Every texture is indexed, and shared ones list every pack as a provider
+fn check_scan(
+    packs: &[PackMeta],
+    assets: &[crate::model::AssetRecord],
+    providers: &HashMap<String, Vec<String>>,
+    config: &SyntheticSuiteConfig,
+) -> Result<()> {
+    let indexed: std::collections::HashSet<&str> =
+        assets.iter().map(|asset| asset.id.as_str()).collect();
+    for pack in 0..config.packs {
+        for texture in 0..config.unique_textures {
+            let id = unique_texture_id(pack, texture);
+            if !indexed.contains(id.as_str()) {
+                bail!("{} was not indexed", id);
+            }
+        }
+    }
+    for texture in 0..config.shared_textures {
+        let id = shared_texture_id(texture);
+        let count = providers.get(&id).map_or(0, Vec::len);
+        if count != packs.len() {
+            bail!("{} has {} providers, expected {}", id, count, packs.len());
+        }
+    }
+    Ok(())
+}
+
+/// Every texture made it into the nest, and shared ones came from the
+/// highest-priority pack
+fn check_build(nest: &Path, config: &SyntheticSuiteConfig) -> Result<()> {
+    let read = |id: &str| -> Result<Vec<u8>> {
+        let path: PathBuf = nest.join(crate::util::pack_files::texture_entry_path(id));
+        fs::read(&path).with_context(|| format!("{} missing from the nest", id))
+    };
+    for pack in 0..config.packs {
+        for texture in 0..config.unique_textures {
+            read(&unique_texture_id(pack, texture))?;
+        }
+    }
+    for texture in 0..config.shared_textures {
+        let id = shared_texture_id(texture);
+        if read(&id)? != synthetic_png(500 + texture)? {
+            bail!("{} was not taken from the highest-priority pack", id);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_selftest_suite() {
+        let config = SyntheticSuiteConfig {
+            packs: 3,
+            unique_textures: 5,
+            shared_textures: 3,
+            broken_files: 1,
+        };
+        let report = run_selftest_suite(&config);
+        assert!(report.passed, "{:#?}", report.steps);
+        assert_eq!(report.steps.len(), 4);
+    }
+}
@@ -0,0 +1,558 @@
+/// Texture animation (.mcmeta) parsing and frame sampling
+///
+/// Mirrors vanilla's animated sprite ticking so the 2D and 3D previews show the
+/// exact frame (or interpolated frame pair) the game would show at a given tick.
+/// Spec: https://minecraft.wiki/w/Resource_pack#Animation
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// The `animation` section of a texture .mcmeta file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnimationSection {
+    /// Default ticks per frame (vanilla default: 1)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frametime: Option<u32>,
+
+    /// Blend between consecutive frames
+    #[serde(default)]
+    pub interpolate: bool,
+
+    /// Explicit frame order; defaults to every frame of the sheet in order
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frames: Option<Vec<FrameEntry>>,
+
+    /// Frame width in pixels (defaults derived from the image)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+
+    /// Frame height in pixels (defaults derived from the image)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+}
+
+/// A single entry of the `frames` list: either a bare index or `{index, time}`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FrameEntry {
+    Index(u32),
+    Timed {
+        index: u32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        time: Option<u32>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct McmetaFile {
+    animation: Option<AnimationSection>,
+}
+
+/// Result of sampling an animation at a specific tick
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnimationSample {
+    /// Sprite sheet frame to display
+    #[serde(rename = "frameIndex")]
+    pub frame_index: u32,
+
+    /// Frame to blend towards (only set when interpolating between distinct frames)
+    #[serde(rename = "nextFrameIndex", skip_serializing_if = "Option::is_none")]
+    pub next_frame_index: Option<u32>,
+
+    /// Blend weight of `next_frame_index` (0.0 = current frame only)
+    pub alpha: f32,
+
+    /// Position within the frame sequence
+    #[serde(rename = "sequenceIndex")]
+    pub sequence_index: usize,
+
+    /// Total frames available in the sprite sheet
+    #[serde(rename = "frameCount")]
+    pub frame_count: u32,
+
+    #[serde(rename = "frameWidth")]
+    pub frame_width: u32,
+
+    #[serde(rename = "frameHeight")]
+    pub frame_height: u32,
+
+    /// Length of one full animation cycle in ticks
+    #[serde(rename = "cycleTicks")]
+    pub cycle_ticks: u64,
+}
+
+/// Parse the animation section from .mcmeta JSON
+///
+/// Returns None if the file has no `animation` section (e.g., texture-only metadata)
+pub fn parse_animation_section(mcmeta_json: &str) -> Result<Option<AnimationSection>> {
+    let file: McmetaFile = serde_json::from_str(mcmeta_json).context("Invalid .mcmeta JSON")?;
+    Ok(file.animation)
+}
+
+/// Frame dimensions for a sprite sheet, matching vanilla's defaulting rules:
+/// explicit width/height win, otherwise frames are square with the smaller image side
+pub fn frame_size(section: &AnimationSection, image_width: u32, image_height: u32) -> (u32, u32) {
+    match (section.width, section.height) {
+        (Some(w), Some(h)) => (w, h),
+        (Some(w), None) => (w, image_height),
+        (None, Some(h)) => (image_width, h),
+        (None, None) => {
+            let side = image_width.min(image_height);
+            (side, side)
+        }
+    }
+}
+
+/// Expand the frame list into (sheet index, duration) pairs
+///
+/// Returns None when a frame index lies outside the sprite sheet or a frame
+/// time is zero; vanilla rejects such metadata and leaves the texture unanimated.
+pub fn expand_frames(section: &AnimationSection, frame_count: u32) -> Option<Vec<(u32, u32)>> {
+    let default_time = section.frametime.unwrap_or(1);
+    if default_time == 0 {
+        return None;
+    }
+
+    match &section.frames {
+        Some(frames) if !frames.is_empty() => frames
+            .iter()
+            .map(|entry| match entry {
+                FrameEntry::Index(index) => (*index, default_time),
+                FrameEntry::Timed { index, time } => (*index, time.unwrap_or(default_time)),
+            })
+            .map(|(index, time)| (index < frame_count && time > 0).then_some((index, time)))
+            .collect(),
+        _ => Some((0..frame_count).map(|i| (i, default_time)).collect()),
+    }
+}
+
+/// Sample the animation at a game tick
+///
+/// # Arguments
+/// * `section` - Parsed animation section
+/// * `image_width` / `image_height` - Sprite sheet dimensions in pixels
+/// * `tick` - Game tick (wraps around the animation cycle)
+///
+/// Returns None when the game would not animate the texture (see [`expand_frames`]).
+pub fn sample_animation(
+    section: &AnimationSection,
+    image_width: u32,
+    image_height: u32,
+    tick: u64,
+) -> Result<Option<AnimationSample>> {
+    let (frame_width, frame_height) = frame_size(section, image_width, image_height);
+    if frame_width == 0 || frame_height == 0 {
+        anyhow::bail!(
+            "Invalid animation frame size {}x{}",
+            frame_width,
+            frame_height
+        );
+    }
+
+    // Frames are laid out row-major across the sheet
+    let frame_count = (image_width / frame_width) * (image_height / frame_height);
+    let frames = match expand_frames(section, frame_count) {
+        Some(frames) => frames,
+        None => return Ok(None),
+    };
+    if frames.is_empty() {
+        anyhow::bail!("Animation has no valid frames");
+    }
+
+    let cycle_ticks: u64 = frames.iter().map(|(_, time)| *time as u64).sum();
+    let mut remaining = tick % cycle_ticks;
+    let mut sequence_index = 0;
+    for (i, (_, time)) in frames.iter().enumerate() {
+        if remaining < *time as u64 {
+            sequence_index = i;
+            break;
+        }
+        remaining -= *time as u64;
+    }
+
+    let (frame_index, time) = frames[sequence_index];
+    let (next_frame_index, alpha) = if section.interpolate {
+        let next = frames[(sequence_index + 1) % frames.len()].0;
+        if next != frame_index {
+            (Some(next), remaining as f32 / time as f32)
+        } else {
+            (None, 0.0)
+        }
+    } else {
+        (None, 0.0)
+    };
+
+    Ok(Some(AnimationSample {
+        frame_index,
+        next_frame_index,
+        alpha,
+        sequence_index,
+        frame_count,
+        frame_width,
+        frame_height,
+        cycle_ticks,
+    }))
+}
+
+/// Sample an animated texture from its raw PNG bytes and .mcmeta JSON
+///
+/// Returns None when the .mcmeta has no `animation` section or the game would
+/// not animate the texture.
+pub fn sample_texture_animation(
+    png_bytes: &[u8],
+    mcmeta_json: &str,
+    tick: u64,
+) -> Result<Option<AnimationSample>> {
+    let section = match parse_animation_section(mcmeta_json)? {
+        Some(section) => section,
+        None => return Ok(None),
+    };
+
+    let (image_width, image_height) = image::io::Reader::new(std::io::Cursor::new(png_bytes))
+        .with_guessed_format()
+        .context("Failed to detect texture format")?
+        .into_dimensions()
+        .context("Failed to read texture dimensions")?;
+
+    sample_animation(&section, image_width, image_height, tick)
+}
+
+/// Position of one frame within the sprite sheet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameRect {
+    /// Sheet frame index (row-major)
+    pub index: u32,
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// One step of the playback sequence
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SequenceStep {
+    /// Sheet frame shown during this step
+    pub index: u32,
+    /// Ticks the frame stays on screen
+    pub time: u32,
+}
+
+/// Every frame of an animated texture and the order they play in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimationFrames {
+    /// Sprite sheet dimensions in pixels
+    pub sheet_width: u32,
+    pub sheet_height: u32,
+    pub frame_width: u32,
+    pub frame_height: u32,
+    /// Every frame of the sheet, in index order
+    pub frames: Vec<FrameRect>,
+    /// Playback order after applying the .mcmeta `frames` list and times
+    pub sequence: Vec<SequenceStep>,
+    /// Blend between consecutive frames
+    pub interpolate: bool,
+    /// Length of one full animation cycle in ticks
+    pub cycle_ticks: u64,
+    /// Each sheet frame sliced out as base64 PNG, in index order; only when
+    /// images were requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub images: Option<Vec<String>>,
+}
+
+/// Lay out the frames and playback sequence of an animated texture
+///
+/// # Arguments
+/// * `png_bytes` - Sprite sheet PNG
+/// * `mcmeta_json` - The texture's .mcmeta
+/// * `include_images` - Also slice every frame into its own PNG
+///
+/// Returns None when the .mcmeta has no `animation` section or the game would
+/// not animate the texture.
+pub fn animation_frames(
+    png_bytes: &[u8],
+    mcmeta_json: &str,
+    include_images: bool,
+) -> Result<Option<AnimationFrames>> {
+    let section = match parse_animation_section(mcmeta_json)? {
+        Some(section) => section,
+        None => return Ok(None),
+    };
+
+    let reader = image::io::Reader::new(std::io::Cursor::new(png_bytes))
+        .with_guessed_format()
+        .context("Failed to detect texture format")?;
+    // Only decode pixels when frames are sliced out
+    let (sheet, (sheet_width, sheet_height)) = if include_images {
+        let sheet = reader
+            .decode()
+            .context("Failed to decode texture")?
+            .to_rgba8();
+        let dimensions = sheet.dimensions();
+        (Some(sheet), dimensions)
+    } else {
+        let dimensions = reader
+            .into_dimensions()
+            .context("Failed to read texture dimensions")?;
+        (None, dimensions)
+    };
+
+    let (frame_width, frame_height) = frame_size(&section, sheet_width, sheet_height);
+    if frame_width == 0 || frame_height == 0 {
+        anyhow::bail!(
+            "Invalid animation frame size {}x{}",
+            frame_width,
+            frame_height
+        );
+    }
+    let columns = sheet_width / frame_width;
+    let frame_count = columns * (sheet_height / frame_height);
+    let sequence: Vec<SequenceStep> = match expand_frames(&section, frame_count) {
+        Some(frames) => frames
+            .into_iter()
+            .map(|(index, time)| SequenceStep { index, time })
+            .collect(),
+        None => return Ok(None),
+    };
+    if sequence.is_empty() {
+        anyhow::bail!("Animation has no valid frames");
+    }
+
+    let frames: Vec<FrameRect> = (0..frame_count)
+        .map(|index| FrameRect {
+            index,
+            x: (index % columns) * frame_width,
+            y: (index / columns) * frame_height,
+            width: frame_width,
+            height: frame_height,
+        })
+        .collect();
+
+    let images = match sheet {
+        Some(sheet) => Some(
+            frames
+                .iter()
+                .map(|rect| slice_frame(&sheet, rect))
+                .collect::<Result<Vec<_>>>()?,
+        ),
+        None => None,
+    };
+
+    Ok(Some(AnimationFrames {
+        sheet_width,
+        sheet_height,
+        frame_width,
+        frame_height,
+        cycle_ticks: sequence.iter().map(|step| step.time as u64).sum(),
+        frames,
+        sequence,
+        interpolate: section.interpolate,
+        images,
+    }))
+}
+
+/// One frame of the sheet as base64 PNG
+fn slice_frame(sheet: &image::RgbaImage, rect: &FrameRect) -> Result<String> {
+    use base64::{engine::general_purpose, Engine as _};
+
+    let frame =
+        image::imageops::crop_imm(sheet, rect.x, rect.y, rect.width, rect.height).to_image();
+    let mut png = Vec::new();
+    frame
+        .write_to(
+            &mut std::io::Cursor::new(&mut png),
+            image::ImageOutputFormat::Png,
+        )
+        .context("Failed to encode frame")?;
+    Ok(general_purpose::STANDARD.encode(&png))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_animation_section() {
+        let json = r#"{"animation": {"frametime": 2, "interpolate": true, "frames": [0, {"index": 2, "time": 5}]}}"#;
+        let section = parse_animation_section(json).unwrap().unwrap();
+        assert_eq!(section.frametime, Some(2));
+        assert!(section.interpolate);
+        assert_eq!(section.frames.as_ref().unwrap().len(), 2);
+
+        let no_anim = parse_animation_section(r#"{"texture": {"blur": true}}"#).unwrap();
+        assert!(no_anim.is_none());
+    }
+
+    #[test]
+    fn test_frame_size_defaults() {
+        let section = AnimationSection::default();
+        assert_eq!(frame_size(&section, 16, 64), (16, 16));
+
+        let section = AnimationSection {
+            width: Some(32),
+            ..Default::default()
+        };
+        assert_eq!(frame_size(&section, 64, 16), (32, 16));
+    }
+
+    #[test]
+    fn test_sample_default_frame_order() {
+        let section = AnimationSection {
+            frametime: Some(2),
+            ..Default::default()
+        };
+        // 4 frames, 2 ticks each
+        let sample = sample_animation(&section, 16, 64, 0).unwrap().unwrap();
+        assert_eq!(sample.frame_index, 0);
+        assert_eq!(sample.cycle_ticks, 8);
+
+        assert_eq!(
+            sample_animation(&section, 16, 64, 1)
+                .unwrap()
+                .unwrap()
+                .frame_index,
+            0
+        );
+        assert_eq!(
+            sample_animation(&section, 16, 64, 2)
+                .unwrap()
+                .unwrap()
+                .frame_index,
+            1
+        );
+        assert_eq!(
+            sample_animation(&section, 16, 64, 7)
+                .unwrap()
+                .unwrap()
+                .frame_index,
+            3
+        );
+        // Wraps around
+        assert_eq!(
+            sample_animation(&section, 16, 64, 8)
+                .unwrap()
+                .unwrap()
+                .frame_index,
+            0
+        );
+    }
+
+    #[test]
+    fn test_sample_explicit_frames_with_times() {
+        let json = r#"{"animation": {"frames": [{"index": 2, "time": 3}, 0]}}"#;
+        let section = parse_animation_section(json).unwrap().unwrap();
+        let sample = sample_animation(&section, 16, 48, 2).unwrap().unwrap();
+        assert_eq!(sample.frame_index, 2);
+        assert_eq!(sample.cycle_ticks, 4);
+        assert_eq!(
+            sample_animation(&section, 16, 48, 3)
+                .unwrap()
+                .unwrap()
+                .frame_index,
+            0
+        );
+    }
+
+    #[test]
+    fn test_sample_interpolated() {
+        let section = AnimationSection {
+            frametime: Some(4),
+            interpolate: true,
+            ..Default::default()
+        };
+        let sample = sample_animation(&section, 16, 32, 1).unwrap().unwrap();
+        assert_eq!(sample.frame_index, 0);
+        assert_eq!(sample.next_frame_index, Some(1));
+        assert!((sample.alpha - 0.25).abs() < f32::EPSILON);
+
+        // Last frame blends back to the first
+        let sample = sample_animation(&section, 16, 32, 6).unwrap().unwrap();
+        assert_eq!(sample.frame_index, 1);
+        assert_eq!(sample.next_frame_index, Some(0));
+        assert!((sample.alpha - 0.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_sample_rejects_invalid_frames() {
+        // Index 9 is out of range for a 3-frame sheet
+        let json = r#"{"animation": {"frames": [{"index": 2, "time": 3}, 0, 9]}}"#;
+        let section = parse_animation_section(json).unwrap().unwrap();
+        assert!(sample_animation(&section, 16, 48, 0).unwrap().is_none());
+
+        let json = r#"{"animation": {"frames": [0, {"index": 1, "time": 0}]}}"#;
+        let section = parse_animation_section(json).unwrap().unwrap();
+        assert!(sample_animation(&section, 16, 48, 0).unwrap().is_none());
+
+        let section = AnimationSection {
+            frametime: Some(0),
+            ..Default::default()
+        };
+        assert!(sample_animation(&section, 16, 48, 0).unwrap().is_none());
+
+        // Frames taller than the sheet leave nothing to play
+        let section = AnimationSection {
+            height: Some(32),
+            ..Default::default()
+        };
+        assert!(sample_animation(&section, 16, 16, 0).is_err());
+    }
+
+    #[test]
+    fn test_animation_frames() {
+        let mut sheet = image::RgbaImage::new(16, 48);
+        sheet.put_pixel(0, 16, image::Rgba([255, 0, 0, 255]));
+        let mut png = Vec::new();
+        sheet
+            .write_to(
+                &mut std::io::Cursor::new(&mut png),
+                image::ImageOutputFormat::Png,
+            )
+            .unwrap();
+        let json = r#"{"animation": {"frametime": 2, "interpolate": true, "frames": [1, {"index": 0, "time": 5}]}}"#;
+
+        let frames = animation_frames(&png, json, false).unwrap().unwrap();
+        assert_eq!((frames.frame_width, frames.frame_height), (16, 16));
+        assert_eq!(frames.frames.len(), 3);
+        assert_eq!(
+            frames.frames[2],
+            FrameRect {
+                index: 2,
+                x: 0,
+                y: 32,
+                width: 16,
+                height: 16,
+            }
+        );
+        assert_eq!(
+            frames.sequence,
+            vec![
+                SequenceStep { index: 1, time: 2 },
+                SequenceStep { index: 0, time: 5 },
+            ]
+        );
+        assert_eq!(frames.cycle_ticks, 7);
+        assert!(frames.interpolate);
+        assert!(frames.images.is_none());
+
+        let frames = animation_frames(&png, json, true).unwrap().unwrap();
+        let images = frames.images.unwrap();
+        assert_eq!(images.len(), 3);
+        use base64::{engine::general_purpose, Engine as _};
+        let second =
+            image::load_from_memory(&general_purpose::STANDARD.decode(&images[1]).unwrap())
+                .unwrap()
+                .to_rgba8();
+        assert_eq!(second.dimensions(), (16, 16));
+        assert_eq!(second.get_pixel(0, 0), &image::Rgba([255, 0, 0, 255]));
+
+        assert!(animation_frames(&png, r#"{"texture": {}}"#, false)
+            .unwrap()
+            .is_none());
+        assert!(
+            animation_frames(&png, r#"{"animation": {"frames": [3]}}"#, false)
+                .unwrap()
+                .is_none()
+        );
+    }
+}
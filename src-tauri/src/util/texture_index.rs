@@ -25,24 +25,29 @@ impl TextureIndex {
     pub fn build(pack: &PackMeta, vanilla_pack: &PackMeta) -> Result<Self> {
         let mut texture_to_blocks: HashMap<String, HashSet<String>> = HashMap::new();
 
-        let blockstates_dir = if pack.is_zip {
+        if pack.is_zip {
             // For ZIP packs, we'd need to enumerate ZIP entries
             // For now, we'll return empty index and rely on fallback
             return Ok(Self {
                 texture_to_blocks: HashMap::new(),
             });
-        } else {
-            Path::new(&pack.path).join("assets/minecraft/blockstates")
-        };
+        }
 
-        // If blockstates directory doesn't exist, try vanilla
-        let blockstates_to_scan = if blockstates_dir.exists() {
-            vec![blockstates_dir.clone()]
-        } else {
-            vec![Path::new(&vanilla_pack.path).join("assets/minecraft/blockstates")]
-        };
+        // Every namespace's blockstates folder, so modded blocks are indexed too
+        let mut blockstates_to_scan = namespace_blockstate_dirs(Path::new(&pack.path));
+
+        // If the pack has no vanilla blockstates, try vanilla
+        if !blockstates_to_scan
+            .iter()
+            .any(|(namespace, _)| namespace == "minecraft")
+        {
+            blockstates_to_scan.push((
+                "minecraft".to_string(),
+                Path::new(&vanilla_pack.path).join("assets/minecraft/blockstates"),
+            ));
+        }
 
-        for blockstates_path in blockstates_to_scan {
+        for (namespace, blockstates_path) in blockstates_to_scan {
             if !blockstates_path.exists() {
                 continue;
             }
@@ -60,16 +65,19 @@ impl TextureIndex {
                     continue;
                 }
 
-                if let Some(block_id) = path.file_stem().and_then(|s| s.to_str()) {
+                if let Some(file_stem) = path.file_stem().and_then(|s| s.to_str()) {
+                    let block_id =
+                        blockstates::normalize_block_id(&format!("{}:{}", namespace, file_stem));
+
                     // Parse blockstate and extract textures
-                    if let Ok(textures) = extract_textures_from_block(block_id, pack, vanilla_pack)
+                    if let Ok(textures) = extract_textures_from_block(&block_id, pack, vanilla_pack)
                     {
                         // Add mappings
                         for texture in textures {
                             texture_to_blocks
                                 .entry(texture)
                                 .or_insert_with(HashSet::new)
-                                .insert(block_id.to_string());
+                                .insert(block_id.clone());
                         }
                     }
                 }
@@ -99,6 +107,26 @@ impl TextureIndex {
     }
 }
 
+/// (namespace, blockstates folder) for every namespace in a directory pack
+fn namespace_blockstate_dirs(pack_path: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = fs::read_dir(pack_path.join("assets")) else {
+        return Vec::new();
+    };
+
+    let mut dirs: Vec<(String, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let namespace = entry.file_name().to_str()?.to_string();
+            let blockstates_dir = entry.path().join("blockstates");
+            blockstates_dir
+                .is_dir()
+                .then_some((namespace, blockstates_dir))
+        })
+        .collect();
+    dirs.sort();
+    dirs
+}
+
 /// Extract all texture paths used by a block
 fn extract_textures_from_block(
     block_id: &str,
@@ -0,0 +1,304 @@
+/// Texture dimensions gathered during scans
+///
+/// Only the PNG signature and IHDR chunk (the first 29 bytes) are read, so
+/// every provider of every texture can be measured without decoding images.
+/// Feeds the resolution badges in the UI and the "highest resolution" conflict
+/// strategy of nest builds.
+use crate::model::{AssetRecord, PackMeta, TextureInfo, TextureInfoMap};
+use anyhow::{bail, Result};
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use tracing::warn;
+
+/// Block textures read per pack to infer its resolution
+const RESOLUTION_SAMPLE_SIZE: usize = 64;
+
+const BLOCK_TEXTURE_DIR: &str = "assets/minecraft/textures/block/";
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// Read dimensions, bit depth and color type from the start of a PNG stream
+pub fn read_png_header(reader: &mut dyn Read) -> Result<TextureInfo> {
+    let mut header = [0u8; 29];
+    reader.read_exact(&mut header)?;
+    if header[..8] != PNG_SIGNATURE {
+        bail!("Not a PNG file");
+    }
+    // The first chunk must be IHDR with a 13-byte payload
+    if header[8..12] != [0, 0, 0, 13] || &header[12..16] != b"IHDR" {
+        bail!("PNG does not start with an IHDR chunk");
+    }
+
+    let width = u32::from_be_bytes([header[16], header[17], header[18], header[19]]);
+    let height = u32::from_be_bytes([header[20], header[21], header[22], header[23]]);
+    if width == 0 || height == 0 {
+        bail!("PNG has zero width or height");
+    }
+    Ok(TextureInfo {
+        width,
+        height,
+        bit_depth: header[24],
+        color_type: header[25],
+    })
+}
+
+/// Header of each provider's texture for every texture asset
+///
+/// An asset's texture in a pack is the first PNG in its file list that the
+/// pack contains. Each pack is opened once and read in parallel with the
+/// others; files that aren't valid PNGs are left out.
+pub fn read_texture_info(
+    packs: &[PackMeta],
+    assets: &[AssetRecord],
+    providers: &HashMap<String, Vec<String>>,
+) -> TextureInfoMap {
+    let pack_map: HashMap<&str, &PackMeta> = packs.iter().map(|p| (p.id.as_str(), p)).collect();
+
+    // Pack ID -> (asset ID, candidate PNG files) for the assets it provides
+    let mut wanted: HashMap<&str, Vec<(&str, Vec<&str>)>> = HashMap::new();
+    for asset in assets {
+        let pngs: Vec<&str> = asset
+            .files
+            .iter()
+            .map(String::as_str)
+            .filter(|file| file.ends_with(".png"))
+            .collect();
+        if pngs.is_empty() {
+            continue;
+        }
+        for pack_id in providers.get(&asset.id).into_iter().flatten() {
+            if let Some(pack) = pack_map.get(pack_id.as_str()) {
+                wanted
+                    .entry(pack.id.as_str())
+                    .or_default()
+                    .push((&asset.id, pngs.clone()));
+            }
+        }
+    }
+
+    let read: Vec<(&str, Vec<(&str, TextureInfo)>)> = wanted
+        .par_iter()
+        .filter_map(
+            |(pack_id, entries)| match read_pack_textures(pack_map[pack_id], entries) {
+                Ok(infos) => Some((*pack_id, infos)),
+                Err(e) => {
+                    warn!("Skipping {}: {}", pack_id, e);
+                    None
+                }
+            },
+        )
+        .collect();
+
+    let mut texture_info = TextureInfoMap::new();
+    for (pack_id, infos) in read {
+        for (asset_id, info) in infos {
+            texture_info
+                .entry(asset_id.to_string())
+                .or_default()
+                .insert(pack_id.to_string(), info);
+        }
+    }
+    texture_info
+}
+
+/// Most common width among a sample of a pack's block textures
+///
+/// Width rather than height, so animated strips count at their frame size.
+/// Returns None for packs without readable block textures (e.g. sound or
+/// language packs).
+pub fn dominant_block_resolution(pack_path: &Path, is_zip: bool) -> Option<u32> {
+    if is_zip {
+        let mut archive =
+            crate::util::zip::open_cached_archive(&pack_path.to_string_lossy()).ok()?;
+        let names: Vec<String> = archive
+            .file_names()
+            .filter(|name| is_block_texture(name))
+            .map(str::to_string)
+            .collect();
+        dominant_width(sample(names), |name| {
+            let mut entry = archive.by_name(name).ok()?;
+            read_png_header(&mut entry).ok()
+        })
+    } else {
+        let block_dir = pack_path.join(BLOCK_TEXTURE_DIR);
+        let names: Vec<String> = fs::read_dir(&block_dir)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| {
+                format!(
+                    "{}{}",
+                    BLOCK_TEXTURE_DIR,
+                    entry.file_name().to_string_lossy()
+                )
+            })
+            .filter(|name| is_block_texture(name))
+            .collect();
+        dominant_width(sample(names), |name| {
+            let mut file = fs::File::open(pack_path.join(name)).ok()?;
+            read_png_header(&mut file).ok()
+        })
+    }
+}
+
+fn is_block_texture(name: &str) -> bool {
+    name.strip_prefix(BLOCK_TEXTURE_DIR)
+        .is_some_and(|file| !file.contains('/') && file.ends_with(".png"))
+}
+
+/// Up to RESOLUTION_SAMPLE_SIZE names spread evenly over the sorted list
+fn sample(mut names: Vec<String>) -> Vec<String> {
+    names.sort();
+    let step = (names.len() / RESOLUTION_SAMPLE_SIZE).max(1);
+    names
+        .into_iter()
+        .step_by(step)
+        .take(RESOLUTION_SAMPLE_SIZE)
+        .collect()
+}
+
+/// Mode of the widths of the readable headers; ties go to the larger width
+fn dominant_width(
+    names: Vec<String>,
+    mut read_header: impl FnMut(&str) -> Option<TextureInfo>,
+) -> Option<u32> {
+    let mut counts: HashMap<u32, usize> = HashMap::new();
+    for name in &names {
+        if let Some(info) = read_header(name) {
+            *counts.entry(info.width).or_default() += 1;
+        }
+    }
+    counts
+        .into_iter()
+        .max_by_key(|&(width, count)| (count, width))
+        .map(|(width, _)| width)
+}
+
+/// Header of the first candidate file each asset has in one pack
+fn read_pack_textures<'a>(
+    pack: &PackMeta,
+    entries: &[(&'a str, Vec<&str>)],
+) -> Result<Vec<(&'a str, TextureInfo)>> {
+    if pack.is_zip {
+        let mut archive = crate::util::zip::open_cached_archive(&pack.path)?;
+        Ok(first_headers(entries, |file| {
+            let mut entry = archive.by_name(file).ok()?;
+            read_png_header(&mut entry).ok()
+        }))
+    } else {
+        let root = Path::new(&pack.path);
+        Ok(first_headers(entries, |file| {
+            let mut file = fs::File::open(root.join(file)).ok()?;
+            read_png_header(&mut file).ok()
+        }))
+    }
+}
+
+fn first_headers<'a>(
+    entries: &[(&'a str, Vec<&str>)],
+    mut read_header: impl FnMut(&str) -> Option<TextureInfo>,
+) -> Vec<(&'a str, TextureInfo)> {
+    entries
+        .iter()
+        .filter_map(|(asset_id, files)| {
+            let info = files.iter().find_map(|file| read_header(file))?;
+            Some((*asset_id, info))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Blank PNG of the given size
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        image::RgbaImage::new(width, height)
+            .write_to(
+                &mut std::io::Cursor::new(&mut buffer),
+                image::ImageOutputFormat::Png,
+            )
+            .unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_read_png_header() {
+        let info = read_png_header(&mut png(32, 64).as_slice()).unwrap();
+        assert_eq!(
+            info,
+            TextureInfo {
+                width: 32,
+                height: 64,
+                bit_depth: 8,
+                color_type: 6,
+            }
+        );
+        assert_eq!(info.resolution(), 32);
+
+        assert!(read_png_header(&mut b"not a png at all, definitely not".as_slice()).is_err());
+        assert!(read_png_header(&mut &png(16, 16)[..20]).is_err());
+    }
+
+    #[test]
+    fn test_read_texture_info() {
+        let temp_dir = std::env::temp_dir().join("test_read_texture_info");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let stone = "assets/minecraft/textures/block/stone.png";
+        let write_pack = |name: &str, size: u32| {
+            let path = temp_dir.join(name).join(stone);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, png(size, size)).unwrap();
+            PackMeta {
+                id: name.to_string(),
+                name: name.to_string(),
+                path: temp_dir.join(name).to_string_lossy().to_string(),
+                ..Default::default()
+            }
+        };
+        let packs = vec![write_pack("low", 16), write_pack("high", 128)];
+        let assets = vec![AssetRecord {
+            id: "minecraft:block/stone".to_string(),
+            labels: Vec::new(),
+            files: vec![stone.to_string()],
+        }];
+        let providers = HashMap::from([(
+            "minecraft:block/stone".to_string(),
+            vec!["low".to_string(), "high".to_string(), "missing".to_string()],
+        )]);
+
+        let info = read_texture_info(&packs, &assets, &providers);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let stone_info = &info["minecraft:block/stone"];
+        assert_eq!(stone_info.len(), 2);
+        assert_eq!(stone_info["low"].resolution(), 16);
+        assert_eq!(stone_info["high"].resolution(), 128);
+    }
+
+    #[test]
+    fn test_dominant_block_resolution() {
+        let temp_dir = std::env::temp_dir().join("test_dominant_block_resolution");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let block_dir = temp_dir.join("pack").join(BLOCK_TEXTURE_DIR);
+        fs::create_dir_all(&block_dir).unwrap();
+        fs::write(block_dir.join("stone.png"), png(32, 32)).unwrap();
+        // Animated strips count at their frame width
+        fs::write(block_dir.join("magma.png"), png(32, 96)).unwrap();
+        fs::write(block_dir.join("dirt.png"), png(16, 16)).unwrap();
+        fs::write(block_dir.join("notes.txt"), "not a texture").unwrap();
+        fs::create_dir_all(temp_dir.join("sounds_only")).unwrap();
+
+        let resolution = dominant_block_resolution(&temp_dir.join("pack"), false);
+        let no_blocks = dominant_block_resolution(&temp_dir.join("sounds_only"), false);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(resolution, Some(32));
+        assert_eq!(no_blocks, None);
+    }
+}
@@ -0,0 +1,246 @@
+/// Downscaled texture thumbnails for the asset grid
+///
+/// Grid cards only need a small preview, so textures are decoded once, scaled
+/// down with nearest-neighbor filtering (keeping pixel art crisp) and written to
+/// <cache dir>/weaverbird/thumbnails. The file name hashes the pack, asset, size
+/// and the source's modification time and length, so an edited texture or
+/// replaced ZIP gets a fresh thumbnail instead of a stale one.
+///
+/// Animation strips keep all their frames, each scaled like a single texture,
+/// and the texture's .mcmeta is copied next to the thumbnail so the grid can
+/// still animate it.
+///
+/// Thumbnails handed out are remembered per texture, so an edit made through
+/// the app can drop them right away instead of leaving them to cache pruning.
+use crate::model::PackMeta;
+use crate::util::pack_files;
+use anyhow::{anyhow, Context, Result};
+use image::imageops::{self, FilterType};
+use image::RgbaImage;
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::warn;
+use xxhash_rust::xxh3::Xxh3;
+
+/// Edge length thumbnails fit into unless another size is asked for
+pub const THUMBNAIL_SIZE: u32 = 64;
+
+/// Largest thumbnail edge accepted
+pub const MAX_THUMBNAIL_SIZE: u32 = 512;
+
+/// Thumbnail files by (pack path, texture path)
+type IssuedThumbnails = HashMap<(String, String), HashSet<PathBuf>>;
+
+/// Thumbnails handed out this session
+static ISSUED: Lazy<Mutex<IssuedThumbnails>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Directory thumbnails are cached in
+pub fn thumbnail_cache_dir() -> Result<PathBuf> {
+    let cache_dir = dirs::cache_dir()
+        .ok_or_else(|| anyhow!("Could not find cache directory"))?
+        .join("weaverbird")
+        .join("thumbnails");
+
+    fs::create_dir_all(&cache_dir).context("Failed to create thumbnail cache directory")?;
+
+    Ok(cache_dir)
+}
+
+/// Path of a texture's thumbnail, generating it first if it isn't cached
+///
+/// # Arguments
+/// * `pack` - Pack to read the texture from
+/// * `asset_id` - Texture asset ID (e.g., "minecraft:block/stone")
+/// * `size` - Edge length each frame is fitted into
+pub fn get_thumbnail(pack: &PackMeta, asset_id: &str, size: u32) -> Result<PathBuf> {
+    thumbnail_in(&thumbnail_cache_dir()?, pack, asset_id, size)
+}
+
+fn thumbnail_in(cache_dir: &Path, pack: &PackMeta, asset_id: &str, size: u32) -> Result<PathBuf> {
+    let texture_path = pack_files::texture_entry_path(asset_id);
+    let thumbnail = cache_dir.join(format!(
+        "{:016x}.png",
+        cache_key(pack, &texture_path, size)?
+    ));
+    issued()
+        .entry((pack.path.clone(), texture_path.clone()))
+        .or_default()
+        .insert(thumbnail.clone());
+    if thumbnail.is_file() {
+        // Shown thumbnails count as used for cache maintenance
+        let _ = fs::File::options()
+            .write(true)
+            .open(&thumbnail)
+            .and_then(|file| file.set_modified(SystemTime::now()));
+        return Ok(thumbnail);
+    }
+
+    let bytes = pack_files::read_pack_entry(pack, &texture_path)?;
+    let image = image::load_from_memory(&bytes)
+        .with_context(|| format!("Failed to decode {}", texture_path))?
+        .to_rgba8();
+    let scaled = downscale(&image, size);
+
+    // Write under a temporary name so a concurrent request never sees half a PNG
+    let partial = thumbnail.with_extension("png.partial");
+    scaled
+        .save_with_format(&partial, image::ImageFormat::Png)
+        .with_context(|| format!("Failed to write thumbnail for {}", asset_id))?;
+    fs::rename(&partial, &thumbnail).context("Failed to move thumbnail into place")?;
+
+    if let Ok(mcmeta) = pack_files::read_pack_entry(pack, &format!("{}.mcmeta", texture_path)) {
+        let mcmeta_path = PathBuf::from(format!("{}.mcmeta", thumbnail.display()));
+        if let Err(e) = fs::write(&mcmeta_path, mcmeta) {
+            warn!("Failed to copy .mcmeta for {}: {}", asset_id, e);
+        }
+    }
+    Ok(thumbnail)
+}
+
+/// Delete the thumbnails handed out for a texture, with their .mcmeta copies
+///
+/// Returns the number of files deleted.
+pub fn invalidate_thumbnails(pack_path: &str, asset_id: &str) -> usize {
+    let key = (
+        pack_path.to_string(),
+        pack_files::texture_entry_path(asset_id),
+    );
+    let Some(thumbnails) = issued().remove(&key) else {
+        return 0;
+    };
+
+    let mut removed = 0;
+    for thumbnail in thumbnails {
+        let mcmeta = PathBuf::from(format!("{}.mcmeta", thumbnail.display()));
+        removed += [thumbnail, mcmeta]
+            .iter()
+            .filter(|path| fs::remove_file(path).is_ok())
+            .count();
+    }
+    removed
+}
+
+fn issued() -> std::sync::MutexGuard<'static, IssuedThumbnails> {
+    ISSUED.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Scale a texture so each frame fits within `size` x `size`
+///
+/// Textures that already fit are returned unchanged. A texture taller than it is
+/// wide by a whole multiple is treated as an animation strip of square frames.
+pub fn downscale(image: &RgbaImage, size: u32) -> RgbaImage {
+    let (width, height) = image.dimensions();
+    let frames = if height > width && height % width == 0 {
+        height / width
+    } else {
+        1
+    };
+    let frame_height = height / frames;
+    if width <= size && frame_height <= size {
+        return image.clone();
+    }
+
+    let scale = size as f64 / width.max(frame_height) as f64;
+    let scaled_width = ((width as f64 * scale).round() as u32).max(1);
+    let scaled_frame_height = ((frame_height as f64 * scale).round() as u32).max(1);
+    imageops::resize(
+        image,
+        scaled_width,
+        scaled_frame_height * frames,
+        FilterType::Nearest,
+    )
+}
+
+/// Hash identifying one version of one texture at one size
+fn cache_key(pack: &PackMeta, texture_path: &str, size: u32) -> Result<u64> {
+    // A ZIP changes as a whole; folder packs are tracked per file
+    let source = if pack.is_zip {
+        PathBuf::from(&pack.path)
+    } else {
+        Path::new(&pack.path).join(texture_path)
+    };
+    let metadata = fs::metadata(&source)
+        .with_context(|| format!("Texture not found: {}", source.display()))?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|since| since.as_nanos())
+        .unwrap_or(0);
+
+    let mut hasher = Xxh3::new();
+    hasher.update(pack.path.as_bytes());
+    hasher.update(&[0]);
+    hasher.update(texture_path.as_bytes());
+    hasher.update(&size.to_le_bytes());
+    hasher.update(&modified.to_le_bytes());
+    hasher.update(&metadata.len().to_le_bytes());
+    Ok(hasher.digest())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_downscale() {
+        let large = RgbaImage::new(512, 512);
+        assert_eq!(downscale(&large, 64).dimensions(), (64, 64));
+
+        let small = RgbaImage::new(16, 16);
+        assert_eq!(downscale(&small, 64).dimensions(), (16, 16));
+
+        // A 4-frame animation strip keeps its frames
+        let strip = RgbaImage::new(128, 512);
+        assert_eq!(downscale(&strip, 64).dimensions(), (64, 256));
+
+        let wide = RgbaImage::new(256, 128);
+        assert_eq!(downscale(&wide, 64).dimensions(), (64, 32));
+    }
+
+    #[test]
+    fn test_thumbnail_cached_per_source_version() {
+        let temp_dir = std::env::temp_dir().join("test_thumbnail_cached");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let pack_dir = temp_dir.join("pack");
+        let cache_dir = temp_dir.join("cache");
+        let texture = pack_dir.join("assets/minecraft/textures/block/stone.png");
+        fs::create_dir_all(texture.parent().unwrap()).unwrap();
+        fs::create_dir_all(&cache_dir).unwrap();
+        RgbaImage::from_pixel(256, 256, image::Rgba([90, 90, 90, 255]))
+            .save(&texture)
+            .unwrap();
+        let pack = PackMeta {
+            id: "pack".to_string(),
+            name: "pack".to_string(),
+            path: pack_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        };
+
+        let first = thumbnail_in(&cache_dir, &pack, "minecraft:block/stone", 64).unwrap();
+        let again = thumbnail_in(&cache_dir, &pack, "minecraft:block/stone", 64).unwrap();
+        let first_size = image::image_dimensions(&first).unwrap();
+
+        // Replacing the texture with a different file produces a new thumbnail
+        RgbaImage::new(128, 128).save(&texture).unwrap();
+        let edited = thumbnail_in(&cache_dir, &pack, "minecraft:block/stone", 64).unwrap();
+        let missing = thumbnail_in(&cache_dir, &pack, "minecraft:block/dirt", 64);
+
+        // Both versions handed out are dropped on invalidation
+        let removed = invalidate_thumbnails(&pack.path, "minecraft:block/stone");
+        let leftover = (first.exists(), edited.exists());
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(first, again);
+        assert_eq!(first_size, (64, 64));
+        assert_ne!(first, edited);
+        assert!(missing.is_err());
+        assert_eq!(removed, 2);
+        assert_eq!(leftover, (false, false));
+    }
+}
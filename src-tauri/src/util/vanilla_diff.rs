@@ -0,0 +1,212 @@
+/// Comparing one vanilla texture between two cached Minecraft versions
+///
+/// Both versions have to be extracted already (see `vanilla_textures`); they
+/// are kept side by side, so the comparison only reads the two PNGs. The
+/// summary says whether Mojang changed the texture and where, which tells a
+/// pack author whether their version of it still matches the new look.
+use crate::util::vanilla_textures;
+use anyhow::{bail, Result};
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// How a texture changed from the first version to the second
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TextureChange {
+    Unchanged,
+    Modified,
+    /// Only in the second version
+    Added,
+    /// Only in the first version
+    Removed,
+}
+
+/// What changed between the two versions of a texture
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextureDiff {
+    pub change: TextureChange,
+    /// [width, height] in each version
+    pub size_a: Option<[u32; 2]>,
+    pub size_b: Option<[u32; 2]>,
+    /// Pixels that differ, counted at the larger of the two sizes
+    pub changed_pixels: u64,
+    /// `changed_pixels` over all pixels compared, 0.0 to 1.0
+    pub changed_fraction: f64,
+    /// [x, y, width, height] of the area holding every changed pixel
+    pub changed_bounds: Option<[u32; 4]>,
+    /// Whether the animation (.png.mcmeta) was added, removed or edited
+    pub animation_changed: bool,
+}
+
+/// Result of `compare_vanilla_asset`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VanillaAssetComparison {
+    pub asset_id: String,
+    pub version_a: String,
+    pub version_b: String,
+    /// Texture path in each version, None when the version lacks it
+    pub path_a: Option<String>,
+    pub path_b: Option<String>,
+    pub diff: TextureDiff,
+}
+
+/// Compare a vanilla texture between two cached versions
+///
+/// # Arguments
+/// * `asset_id` - Asset ID like "minecraft:block/grass_block_side"
+/// * `version_a` - Older version, by convention
+/// * `version_b` - Newer version
+pub fn compare_vanilla_asset(
+    asset_id: &str,
+    version_a: &str,
+    version_b: &str,
+) -> Result<VanillaAssetComparison> {
+    let cached = vanilla_textures::list_cached_versions()?;
+    for version in [version_a, version_b] {
+        if !cached.iter().any(|cached| cached == version) {
+            bail!("Vanilla {} is not extracted", version);
+        }
+    }
+
+    let path_a = vanilla_textures::get_vanilla_texture_path(asset_id, Some(version_a)).ok();
+    let path_b = vanilla_textures::get_vanilla_texture_path(asset_id, Some(version_b)).ok();
+    if path_a.is_none() && path_b.is_none() {
+        bail!("{} is in neither {} nor {}", asset_id, version_a, version_b);
+    }
+
+    let image_a = path_a.as_deref().map(load_texture).transpose()?;
+    let image_b = path_b.as_deref().map(load_texture).transpose()?;
+    let mut diff = diff_textures(image_a.as_ref(), image_b.as_ref());
+    diff.animation_changed = read_mcmeta(path_a.as_deref()) != read_mcmeta(path_b.as_deref());
+    if diff.animation_changed && diff.change == TextureChange::Unchanged {
+        diff.change = TextureChange::Modified;
+    }
+
+    Ok(VanillaAssetComparison {
+        asset_id: asset_id.to_string(),
+        version_a: version_a.to_string(),
+        version_b: version_b.to_string(),
+        path_a: path_a.map(|path| path.to_string_lossy().to_string()),
+        path_b: path_b.map(|path| path.to_string_lossy().to_string()),
+        diff,
+    })
+}
+
+fn load_texture(path: &Path) -> Result<RgbaImage> {
+    Ok(image::open(path)?.to_rgba8())
+}
+
+fn read_mcmeta(texture: Option<&Path>) -> Option<Vec<u8>> {
+    let texture = texture?;
+    let mut mcmeta = texture.as_os_str().to_owned();
+    mcmeta.push(".mcmeta");
+    fs::read(mcmeta).ok()
+}
+
+/// Pixel difference of two decoded textures
+///
+/// Textures of different sizes (a 16x texture redrawn at 32x, an animation
+/// that gained frames) are compared at the larger size by nearest-neighbor
+/// sampling. Fully transparent pixels are equal whatever their color.
+pub fn diff_textures(a: Option<&RgbaImage>, b: Option<&RgbaImage>) -> TextureDiff {
+    let size = |image: &RgbaImage| [image.width(), image.height()];
+    let mut diff = TextureDiff {
+        change: TextureChange::Unchanged,
+        size_a: a.map(size),
+        size_b: b.map(size),
+        changed_pixels: 0,
+        changed_fraction: 0.0,
+        changed_bounds: None,
+        animation_changed: false,
+    };
+    let (a, b) = match (a, b) {
+        (Some(a), Some(b)) => (a, b),
+        (None, Some(_)) => {
+            diff.change = TextureChange::Added;
+            return diff;
+        }
+        (Some(_), None) => {
+            diff.change = TextureChange::Removed;
+            return diff;
+        }
+        (None, None) => return diff,
+    };
+
+    let width = a.width().max(b.width());
+    let height = a.height().max(b.height());
+    let sample = |image: &RgbaImage, x: u32, y: u32| {
+        let pixel = image.get_pixel(x * image.width() / width, y * image.height() / height);
+        if pixel[3] == 0 {
+            [0; 4]
+        } else {
+            pixel.0
+        }
+    };
+
+    let mut bounds: Option<[u32; 4]> = None;
+    for y in 0..height {
+        for x in 0..width {
+            if sample(a, x, y) == sample(b, x, y) {
+                continue;
+            }
+            diff.changed_pixels += 1;
+            bounds = Some(match bounds {
+                Some([min_x, min_y, max_x, max_y]) => {
+                    [min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y)]
+                }
+                None => [x, y, x, y],
+            });
+        }
+    }
+
+    let total = u64::from(width) * u64::from(height);
+    if total > 0 {
+        diff.changed_fraction = diff.changed_pixels as f64 / total as f64;
+    }
+    diff.changed_bounds = bounds
+        .map(|[min_x, min_y, max_x, max_y]| [min_x, min_y, max_x - min_x + 1, max_y - min_y + 1]);
+    if diff.changed_pixels > 0 || diff.size_a != diff.size_b {
+        diff.change = TextureChange::Modified;
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn test_diff_textures() {
+        let old = RgbaImage::from_pixel(4, 4, Rgba([10, 20, 30, 255]));
+        let mut new = old.clone();
+        new.put_pixel(1, 2, Rgba([200, 20, 30, 255]));
+        new.put_pixel(2, 3, Rgba([10, 20, 31, 255]));
+
+        let modified = diff_textures(Some(&old), Some(&new));
+        assert_eq!(modified.change, TextureChange::Modified);
+        assert_eq!(modified.changed_pixels, 2);
+        assert_eq!(modified.changed_fraction, 2.0 / 16.0);
+        assert_eq!(modified.changed_bounds, Some([1, 2, 2, 2]));
+
+        // The same texture redrawn at twice the size is compared pixel by pixel
+        let upscaled = image::imageops::resize(&old, 8, 8, image::imageops::FilterType::Nearest);
+        let resized = diff_textures(Some(&old), Some(&upscaled));
+        assert_eq!(resized.change, TextureChange::Modified);
+        assert_eq!(resized.changed_pixels, 0);
+        assert_eq!(resized.size_b, Some([8, 8]));
+
+        // Invisible pixels don't count, whatever color they hold
+        let clear_a = RgbaImage::from_pixel(2, 2, Rgba([255, 0, 0, 0]));
+        let clear_b = RgbaImage::from_pixel(2, 2, Rgba([0, 0, 255, 0]));
+        let unchanged = diff_textures(Some(&clear_a), Some(&clear_b));
+        assert_eq!(unchanged.change, TextureChange::Unchanged);
+        assert_eq!(unchanged.changed_bounds, None);
+
+        assert_eq!(diff_textures(None, Some(&old)).change, TextureChange::Added);
+    }
+}
@@ -1,20 +1,153 @@
 /// Utilities for extracting and caching vanilla Minecraft textures
 use anyhow::{anyhow, Context, Result};
+use flate2::read::DeflateDecoder;
+use once_cell::sync::Lazy;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 use std::fs;
+use std::io::Read;
 
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{mpsc, Arc, Mutex, RwLock};
+use tracing::{info, warn};
 use walkdir::WalkDir;
-use zip::ZipArchive;
+use zip::{CompressionMethod, ZipArchive};
 
-use crate::util::mc_paths;
+use crate::util::cache_location;
+use crate::util::cancellation;
+use crate::util::mc_paths::{self, InstallFlavor, MinecraftInstall};
 
 /// Progress callback type for extraction
 pub type ProgressCallback = Arc<dyn Fn(usize, usize) + Send + Sync>;
 
+/// JAR entries read ahead of the extraction workers; vanilla entries are small
+/// (a few KB, rarely over 100 KB), so this caps the queue at a few MB
+const EXTRACT_QUEUE_LEN: usize = 256;
+
+/// Provenance manifest written next to the version marker after extraction
+const PROVENANCE_FILE: &str = ".extraction.json";
+
+/// Texture folders that are always extracted: blocks and items are what packs
+/// override, and colormaps tint them
+const REQUIRED_TEXTURE_FOLDERS: [&str; 3] = ["block", "item", "colormap"];
+
+/// Optional texture categories to extract, all of them unless changed in the settings
+static TEXTURE_CATEGORIES: Lazy<RwLock<BTreeSet<TextureCategory>>> =
+    Lazy::new(|| RwLock::new(TextureCategory::ALL.into_iter().collect()));
+
+/// Held while an extraction checks and fills the cache; `CacheLock` keeps other
+/// processes out, this keeps concurrent commands of this one apart
+static EXTRACTION: Mutex<()> = Mutex::new(());
+
+/// Optional groups of vanilla textures, by folder under assets/minecraft/textures
+///
+/// Block, item and colormap textures are always extracted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TextureCategory {
+    /// Mob skins and other entity textures (entity/)
+    Entity,
+    /// GUI sprites and containers (gui/)
+    Gui,
+    /// Particle sprites (particle/)
+    Particle,
+    /// Status effect icons (mob_effect/)
+    MobEffect,
+    /// Overlays such as pumpkin blur and enchantment glint (misc/)
+    Misc,
+    /// Everything else: environment, paintings, armor models, trims, fonts, ...
+    Other,
+}
+
+impl TextureCategory {
+    pub const ALL: [TextureCategory; 6] = [
+        TextureCategory::Entity,
+        TextureCategory::Gui,
+        TextureCategory::Particle,
+        TextureCategory::MobEffect,
+        TextureCategory::Misc,
+        TextureCategory::Other,
+    ];
+
+    /// Category of a texture folder, None for the always extracted ones
+    fn of_folder(folder: &str) -> Option<Self> {
+        if REQUIRED_TEXTURE_FOLDERS.contains(&folder) {
+            return None;
+        }
+        Some(match folder {
+            "entity" => TextureCategory::Entity,
+            "gui" => TextureCategory::Gui,
+            "particle" => TextureCategory::Particle,
+            "mob_effect" => TextureCategory::MobEffect,
+            "misc" => TextureCategory::Misc,
+            _ => TextureCategory::Other,
+        })
+    }
+}
+
+/// Choose which optional texture categories later extractions include
+///
+/// A newly enabled category is extracted into the existing cache the next time
+/// vanilla textures are initialized; disabling one keeps what is cached.
+pub fn set_texture_categories(categories: &[TextureCategory]) -> Vec<TextureCategory> {
+    let enabled: BTreeSet<TextureCategory> = categories.iter().copied().collect();
+    info!("Extracting texture categories {:?}", enabled);
+    *TEXTURE_CATEGORIES
+        .write()
+        .unwrap_or_else(|e| e.into_inner()) = enabled;
+    texture_categories()
+}
+
+/// Optional texture categories currently extracted
+pub fn texture_categories() -> Vec<TextureCategory> {
+    TEXTURE_CATEGORIES
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .copied()
+        .collect()
+}
+
+/// Whether a texture path ("block/stone.png") is in an enabled category
+fn is_texture_enabled(texture_path: &str, enabled: &BTreeSet<TextureCategory>) -> bool {
+    // Loose files directly under textures/ belong to no folder
+    let folder = texture_path
+        .split_once('/')
+        .map_or("", |(folder, _)| folder);
+    TextureCategory::of_folder(folder).map_or(true, |category| enabled.contains(&category))
+}
+
+/// Categories recorded before categories existed: everything was extracted
+fn all_categories() -> Vec<TextureCategory> {
+    TextureCategory::ALL.to_vec()
+}
+
+/// Which JAR a vanilla texture cache was extracted from
+///
+/// Lets a cache be traced back to the exact client JAR, and lets a replaced or
+/// re-downloaded JAR be noticed even when the version name is unchanged.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct VanillaProvenance {
+    /// Version folder the JAR came from (e.g., "1.21.4")
+    pub version: String,
+    pub jar_path: String,
+    /// Lowercase hex SHA-1 of the JAR
+    pub jar_sha1: String,
+    pub jar_size: u64,
+    /// JAR modification time (seconds since the epoch), used to skip rehashing
+    pub jar_modified: u64,
+    /// Asset index ID from the version JSON next to the JAR, when present
+    pub asset_index: Option<String>,
+    /// RFC 3339 time of the extraction
+    pub extracted_at: String,
+    /// Optional texture categories the cache includes
+    #[serde(default = "all_categories")]
+    pub categories: Vec<TextureCategory>,
+}
+
 /// Information about a Minecraft version
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct MinecraftVersion {
@@ -77,18 +210,131 @@ impl MinecraftVersion {
     }
 }
 
-/// Get the directory where vanilla textures are cached
+/// Name of the vanilla textures cache within the (local or shared) cache root,
+/// holding one folder per extracted version
+pub const VANILLA_CACHE_NAME: &str = "vanilla_textures";
+
+/// Name of the cache holding the packs built into the JAR (Programmer Art,
+/// High Contrast), one folder per version and within it one per pack
+pub const BUILTIN_PACKS_CACHE_NAME: &str = "vanilla_builtin_packs";
+
+/// File in the local cache root naming the vanilla version in use
+///
+/// Kept locally even with a shared cache, so machines sharing one can each use
+/// their own version.
+const ACTIVE_VERSION_FILE: &str = "vanilla_active_version";
+
+/// Marker written into a version's cache once it is completely extracted
+const VERSION_MARKER: &str = ".extracted_version";
+
+/// Where the JAR keeps its built-in packs
+const BUILTIN_PACKS_JAR_DIR: &str = "resourcepacks/";
+
+/// Folder of the worldgen biome definitions, in the JAR and the vanilla cache
+pub const BIOME_DEFINITIONS_DIR: &str = "data/minecraft/worldgen/biome/";
+
+/// Get the directory where the vanilla textures in use are cached
+///
+/// This is the folder of the active version (see `get_cached_version`), and may
+/// be on the shared cache folder; see `cache_location`. With nothing extracted
+/// yet it is the empty cache root.
 pub fn get_vanilla_cache_dir() -> Result<PathBuf> {
-    let cache_dir = dirs::cache_dir()
-        .ok_or_else(|| anyhow!("Could not find cache directory"))?
-        .join("weaverbird")
-        .join("vanilla_textures");
+    let cache_dir = match get_cached_version()? {
+        Some(version) => get_version_cache_dir(&version)?,
+        None => cache_location::cache_dir(VANILLA_CACHE_NAME)?,
+    };
 
     fs::create_dir_all(&cache_dir).context("Failed to create vanilla textures cache directory")?;
 
     Ok(cache_dir)
 }
 
+/// Directory holding the extracted assets of one version
+///
+/// The directory isn't created, and may not exist yet.
+pub fn get_version_cache_dir(version: &str) -> Result<PathBuf> {
+    cache_location::cache_dir(&format!("{}/{}", VANILLA_CACHE_NAME, version))
+}
+
+/// Versions with a complete cache, newest first
+pub fn list_cached_versions() -> Result<Vec<String>> {
+    migrate_legacy_cache();
+    let mut roots = vec![cache_location::cache_dir(VANILLA_CACHE_NAME)?];
+    roots.push(cache_location::local_cache_root()?.join(VANILLA_CACHE_NAME));
+
+    let versions: BTreeSet<String> = roots
+        .iter()
+        .filter_map(|root| fs::read_dir(root).ok())
+        .flat_map(|entries| entries.flatten())
+        .filter_map(|entry| entry.file_name().to_str().map(|name| name.to_string()))
+        .filter(|version| is_version_extracted(version))
+        .collect();
+    let mut versions: Vec<String> = versions.into_iter().collect();
+    versions.sort_by(|a, b| MinecraftVersion::compare_versions(a, b));
+    Ok(versions)
+}
+
+/// Whether `version` has a complete cache, active or not
+pub fn is_version_extracted(version: &str) -> bool {
+    get_version_cache_dir(version)
+        .ok()
+        .and_then(|dir| fs::read_to_string(dir.join(VERSION_MARKER)).ok())
+        .is_some_and(|marker| marker.trim() == version)
+}
+
+/// Use the already extracted `version` from now on
+fn set_active_version(version: &str) -> Result<()> {
+    let path = cache_location::local_cache_root()?.join(ACTIVE_VERSION_FILE);
+    fs::write(path, version).context("Failed to record the vanilla version in use")
+}
+
+/// Move a cache from before versions were kept side by side, with the
+/// assets directly in the cache root, into its version's folder
+///
+/// Runs once per process. A read-only shared cache is left as it is.
+fn migrate_legacy_cache() {
+    static MIGRATED: std::sync::Once = std::sync::Once::new();
+    MIGRATED.call_once(|| {
+        let Ok(root) = cache_location::writable_cache_dir(VANILLA_CACHE_NAME) else {
+            return;
+        };
+        let Ok(version) = fs::read_to_string(root.join(VERSION_MARKER)) else {
+            return;
+        };
+        let version = version.trim().to_string();
+        if let Err(e) = move_into_version_dir(&root, &version) {
+            warn!(
+                "Failed to move the cache of {} into its own folder: {}",
+                version, e
+            );
+            return;
+        }
+        info!("Moved the cache of {} into its own folder", version);
+        let path = cache_location::local_cache_root().map(|root| root.join(ACTIVE_VERSION_FILE));
+        if path.is_ok_and(|path| !path.exists()) {
+            let _ = set_active_version(&version);
+        }
+    });
+}
+
+fn move_into_version_dir(root: &Path, version: &str) -> Result<()> {
+    let _lock = cache_location::CacheLock::acquire(root)?;
+    let version_dir = root.join(version);
+    fs::create_dir_all(&version_dir)?;
+    for entry in fs::read_dir(root)?.flatten() {
+        if entry.file_name() == version {
+            continue;
+        }
+        // The marker moves last, so an interrupted move is retried
+        if entry.file_name() == VERSION_MARKER {
+            continue;
+        }
+        fs::rename(entry.path(), version_dir.join(entry.file_name()))?;
+    }
+    fs::rename(root.join(VERSION_MARKER), version_dir.join(VERSION_MARKER))?;
+    Ok(())
+}
+
 /// Check if Minecraft is installed at the given directory
 /// Works with both official launcher (.minecraft/versions) and Modrinth (meta/versions)
 pub fn check_minecraft_installation(mc_dir: &Path) -> Result<bool> {
@@ -114,19 +360,31 @@ pub fn check_minecraft_installation(mc_dir: &Path) -> Result<bool> {
     Ok(false)
 }
 
+/// First Minecraft installation found among the native, Flatpak, Snap and
+/// portable locations
+pub fn find_minecraft_install() -> Option<MinecraftInstall> {
+    mc_paths::candidate_minecraft_dirs()
+        .into_iter()
+        .find(|(dir, _)| check_minecraft_installation(dir).unwrap_or(false))
+        .map(|(dir, flavor)| MinecraftInstall::new(&dir, flavor))
+}
+
 /// Get suggested Minecraft directory paths for the current platform
-pub fn get_suggested_minecraft_paths() -> Vec<String> {
-    let mut paths = Vec::new();
+pub fn get_suggested_minecraft_paths() -> Vec<MinecraftInstall> {
+    let mut paths: Vec<MinecraftInstall> = mc_paths::candidate_minecraft_dirs()
+        .iter()
+        .map(|(dir, flavor)| MinecraftInstall::new(dir, *flavor))
+        .collect();
+    let mut modrinth = Vec::new();
 
     #[cfg(target_os = "macos")]
     {
         if let Ok(home) = std::env::var("HOME") {
-            paths.push(format!("{}/Library/Application Support/minecraft", home));
-            paths.push(format!(
+            modrinth.push(format!(
                 "{}/Library/Application Support/ModrinthApp/meta",
                 home
             ));
-            paths.push(format!(
+            modrinth.push(format!(
                 "{}/Library/Application Support/com.modrinth.theseus/meta",
                 home
             ));
@@ -136,22 +394,30 @@ pub fn get_suggested_minecraft_paths() -> Vec<String> {
     #[cfg(target_os = "windows")]
     {
         if let Ok(appdata) = std::env::var("APPDATA") {
-            paths.push(format!("{}/.minecraft", appdata));
-            paths.push(format!("{}\\.minecraft", appdata));
-            paths.push(format!("{}\\ModrinthApp\\meta", appdata));
-            paths.push(format!("{}\\com.modrinth.theseus\\meta", appdata));
+            modrinth.push(format!("{}\\ModrinthApp\\meta", appdata));
+            modrinth.push(format!("{}\\com.modrinth.theseus\\meta", appdata));
         }
     }
 
     #[cfg(target_os = "linux")]
     {
         if let Ok(home) = std::env::var("HOME") {
-            paths.push(format!("{}/.minecraft", home));
-            paths.push(format!("{}/.local/share/ModrinthApp/meta", home));
-            paths.push(format!("{}/.local/share/com.modrinth.theseus/meta", home));
+            modrinth.push(format!("{}/.local/share/ModrinthApp/meta", home));
+            modrinth.push(format!("{}/.local/share/com.modrinth.theseus/meta", home));
+            paths.push(MinecraftInstall {
+                path: format!(
+                    "{}/.var/app/com.modrinth.ModrinthApp/data/ModrinthApp/meta",
+                    home
+                ),
+                flavor: InstallFlavor::Flatpak,
+            });
         }
     }
 
+    paths.extend(modrinth.into_iter().map(|path| MinecraftInstall {
+        path,
+        flavor: InstallFlavor::Native,
+    }));
     paths
 }
 
@@ -225,8 +491,8 @@ pub fn list_all_available_versions() -> Result<Vec<MinecraftVersion>> {
     let mut all_versions = Vec::new();
     let mut search_paths = Vec::new();
 
-    // Try official Minecraft launcher first
-    if let Ok(mc_dir) = mc_paths::get_default_minecraft_dir() {
+    // Try the official launcher first, including Flatpak, Snap and portable copies
+    for (mc_dir, _) in mc_paths::candidate_minecraft_dirs() {
         search_paths.push(mc_dir);
     }
 
@@ -288,17 +554,20 @@ pub fn find_latest_version_jar() -> Result<PathBuf> {
     Ok(PathBuf::from(&latest.jar_path))
 }
 
-/// Get the currently cached version info (if any)
+/// Get the vanilla version in use (if any)
+///
+/// This is the version last extracted or switched to; when its cache is gone,
+/// the newest version still cached.
 pub fn get_cached_version() -> Result<Option<String>> {
-  let cache_dir = get_vanilla_cache_dir()?;
-  let marker_file = cache_dir.join(".extracted_version");
-
-    if marker_file.exists() {
-        let version = fs::read_to_string(marker_file).context("Failed to read version marker")?;
-        Ok(Some(version.trim().to_string()))
-    } else {
-        Ok(None)
-  }
+    migrate_legacy_cache();
+    let active_file = cache_location::local_cache_root()?.join(ACTIVE_VERSION_FILE);
+    if let Ok(version) = fs::read_to_string(active_file) {
+        let version = version.trim();
+        if !version.is_empty() && is_version_extracted(version) {
+            return Ok(Some(version.to_string()));
+        }
+    }
+    Ok(list_cached_versions()?.into_iter().next())
 }
 
 fn has_any_file_with_suffix(dir: &Path, suffix: &str) -> bool {
@@ -357,6 +626,22 @@ fn jar_contains_cem(jar_path: &Path) -> Result<bool> {
     Ok(false)
 }
 
+fn jar_contains_biomes(jar_path: &Path) -> Result<bool> {
+    let jar_file = fs::File::open(jar_path).context("Failed to open Minecraft JAR file")?;
+    let mut archive = ZipArchive::new(jar_file).context("Failed to read JAR archive")?;
+
+    for i in 0..archive.len() {
+        let file = archive
+            .by_index(i)
+            .context("Failed to read archive entry")?;
+        if is_biome_definition(file.name()) {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
 fn is_cache_complete(cache_dir: &Path, jar_path: &Path) -> Result<bool> {
     let textures_dir = cache_dir.join("assets/minecraft/textures");
     let models_dir = cache_dir.join("assets/minecraft/models");
@@ -381,6 +666,12 @@ fn is_cache_complete(cache_dir: &Path, jar_path: &Path) -> Result<bool> {
         return Ok(false);
     }
 
+    // Biome definitions are only in the JARs of 1.18 and later
+    if !cache_dir.join(BIOME_DEFINITIONS_DIR).exists() && jar_contains_biomes(jar_path)? {
+        info!("Cache missing biome definitions");
+        return Ok(false);
+    }
+
     let has_mcmeta = has_any_file_with_suffix(&textures_dir, ".png.mcmeta");
     if has_mcmeta {
         return Ok(true);
@@ -388,7 +679,7 @@ fn is_cache_complete(cache_dir: &Path, jar_path: &Path) -> Result<bool> {
 
     let jar_has_mcmeta = jar_contains_mcmeta(jar_path)?;
     if jar_has_mcmeta {
-        println!("[vanilla_textures] Cache missing .png.mcmeta files");
+        info!("Cache missing .png.mcmeta files");
         return Ok(false);
     }
 
@@ -397,15 +688,370 @@ fn is_cache_complete(cache_dir: &Path, jar_path: &Path) -> Result<bool> {
         let has_cem =
             has_any_file_with_suffix(&cem_dir, ".jem") || has_any_file_with_suffix(&cem_dir, ".jpm");
         if !has_cem {
-            println!("[vanilla_textures] Cache missing CEM files");
+            info!("Cache missing CEM files");
             return Ok(false);
         }
     }
 
-    println!("[vanilla_textures] No .png.mcmeta files found in jar");
+    info!("No .png.mcmeta files found in jar");
     Ok(true)
 }
 
+/// Whether a JAR entry belongs in the vanilla cache: textures (PNG) of the
+/// enabled categories and their animation metadata (PNG.MCMETA), models,
+/// blockstates, particles (JSON), CEM models and biome definitions
+fn should_extract(file_path: &str) -> bool {
+    let enabled = TEXTURE_CATEGORIES.read().unwrap_or_else(|e| e.into_inner());
+    should_extract_with(file_path, &enabled)
+}
+
+/// `should_extract` for the given optional texture categories
+fn should_extract_with(file_path: &str, enabled: &BTreeSet<TextureCategory>) -> bool {
+    (file_path
+        .strip_prefix("assets/minecraft/textures/")
+        .is_some_and(|texture| is_texture_enabled(texture, enabled))
+        && (file_path.ends_with(".png") || file_path.ends_with(".png.mcmeta")))
+        || (file_path.starts_with("assets/minecraft/models/") && file_path.ends_with(".json"))
+        || (file_path.starts_with("assets/minecraft/blockstates/") && file_path.ends_with(".json"))
+        || (file_path.starts_with("assets/minecraft/particles/") && file_path.ends_with(".json"))
+        || (file_path.starts_with("assets/minecraft/optifine/cem/")
+            && (file_path.ends_with(".jem") || file_path.ends_with(".jpm")))
+        || is_biome_definition(file_path)
+}
+
+/// Worldgen biome JSON, which holds each biome's climate and colors
+fn is_biome_definition(file_path: &str) -> bool {
+    file_path
+        .strip_prefix(BIOME_DEFINITIONS_DIR)
+        .is_some_and(|name| name.ends_with(".json") && !name.contains('/'))
+}
+
+/// Where a vanilla cache entry is written, relative to the cache
+fn vanilla_entry_path(file_path: &str) -> Option<String> {
+    should_extract(file_path).then(|| file_path.to_string())
+}
+
+/// Where an entry of a built-in pack (resourcepacks/<name>/...) is written,
+/// relative to the built-in packs cache: "<name>/..." for the same kinds of
+/// files as vanilla plus the pack's pack.mcmeta and pack.png
+fn builtin_pack_entry_path(file_path: &str) -> Option<String> {
+    let rest = file_path.strip_prefix(BUILTIN_PACKS_JAR_DIR)?;
+    let (name, inner) = rest.split_once('/')?;
+    if name.is_empty() {
+        return None;
+    }
+    (inner == "pack.mcmeta" || inner == "pack.png" || should_extract(inner))
+        .then(|| rest.to_string())
+}
+
+/// A JAR entry read by the reader thread, decompressed by a worker
+struct JarEntry {
+    /// Destination, relative to the directory being extracted into
+    path: String,
+    data: Vec<u8>,
+    /// Whether `data` is still deflate-compressed
+    deflated: bool,
+    crc32: u32,
+    size: u64,
+}
+
+/// Extract the cacheable entries of a JAR into `dest_dir`
+///
+/// One thread reads entries in archive order, so the JAR is read front to back
+/// (what hard drives are fast at), and hands the still-compressed bytes to rayon
+/// workers that inflate, verify and write them in parallel. The queue between
+/// them holds at most EXTRACT_QUEUE_LEN entries, bounding memory use.
+///
+/// # Returns
+/// Number of files written
+fn extract_jar_assets(
+    jar_path: &Path,
+    dest_dir: &Path,
+    progress_callback: Option<ProgressCallback>,
+) -> Result<usize> {
+    extract_jar_entries(jar_path, dest_dir, &vanilla_entry_path, progress_callback)
+}
+
+/// Extract the JAR entries `select` maps to a destination path into `dest_dir`
+fn extract_jar_entries(
+    jar_path: &Path,
+    dest_dir: &Path,
+    select: &(dyn Fn(&str) -> Option<String> + Sync),
+    progress_callback: Option<ProgressCallback>,
+) -> Result<usize> {
+    let jar_file = fs::File::open(jar_path).context("Failed to open Minecraft JAR file")?;
+    let mut archive =
+        ZipArchive::new(std::io::BufReader::new(jar_file)).context("Failed to read JAR archive")?;
+
+    // Names come from the central directory, so counting reads no entry data
+    let total_files = archive
+        .file_names()
+        .filter(|name| select(name).is_some())
+        .count();
+    info!(
+        "Found {} files to extract, extracting in PARALLEL",
+        total_files
+    );
+    if let Some(ref callback) = progress_callback {
+        callback(0, total_files);
+    }
+
+    let extracted_count = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::sync_channel::<JarEntry>(EXTRACT_QUEUE_LEN);
+
+    // A cancelled extraction never writes its marker, so it is redone next time
+    let cancel = cancellation::current();
+    std::thread::scope(|scope| -> Result<usize> {
+        let reader = scope.spawn(move || -> Result<()> {
+            for i in 0..archive.len() {
+                cancel.check()?;
+                let entry = read_jar_entry(&mut archive, i, select)?;
+                // A closed queue means a worker failed; its error is reported instead
+                if let Some(entry) = entry {
+                    if sender.send(entry).is_err() {
+                        break;
+                    }
+                }
+            }
+            Ok(())
+        });
+
+        let written = receiver
+            .into_iter()
+            .par_bridge()
+            .try_for_each(|entry| -> Result<()> {
+                write_jar_entry(entry, dest_dir)?;
+
+                let count = extracted_count.fetch_add(1, Ordering::Relaxed) + 1;
+                if let Some(ref callback) = progress_callback {
+                    // Report progress every 50 files or on completion
+                    if count % 50 == 0 || count == total_files {
+                        callback(count, total_files);
+                    }
+                }
+                Ok(())
+            });
+
+        let read = reader
+            .join()
+            .map_err(|_| anyhow!("JAR reader thread panicked"))?;
+        if let Err(e) = written.and(read) {
+            warn!("ERROR during extraction: {}", e);
+            return Err(e);
+        }
+        Ok(extracted_count.load(Ordering::Relaxed))
+    })
+}
+
+/// Read entry `index` of a JAR if `select` gives it a destination, leaving
+/// deflated data compressed for a worker to inflate
+fn read_jar_entry<R: Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+    index: usize,
+    select: &(dyn Fn(&str) -> Option<String> + Sync),
+) -> Result<Option<JarEntry>> {
+    let mut raw = archive
+        .by_index_raw(index)
+        .context("Failed to read archive entry")?;
+    if !raw.is_file() {
+        return Ok(None);
+    }
+    let Some(path) = select(raw.name()) else {
+        return Ok(None);
+    };
+    let (crc32, size) = (raw.crc32(), raw.size());
+
+    match raw.compression() {
+        CompressionMethod::Stored | CompressionMethod::Deflated => {
+            let deflated = raw.compression() == CompressionMethod::Deflated;
+            let mut data = Vec::with_capacity(raw.compressed_size() as usize);
+            raw.read_to_end(&mut data)
+                .with_context(|| format!("Failed to read {}", path))?;
+            Ok(Some(JarEntry {
+                path,
+                data,
+                deflated,
+                crc32,
+                size,
+            }))
+        }
+        // Anything else is rare enough to decompress right here
+        _ => {
+            drop(raw);
+            let mut file = archive
+                .by_index(index)
+                .context("Failed to read archive entry")?;
+            let mut data = Vec::with_capacity(size as usize);
+            file.read_to_end(&mut data)
+                .with_context(|| format!("Failed to read {}", path))?;
+            Ok(Some(JarEntry {
+                path,
+                data,
+                deflated: false,
+                crc32,
+                size,
+            }))
+        }
+    }
+}
+
+/// Inflate a JAR entry, check it against its CRC and write it under `dest_dir`
+/// at its destination path
+fn write_jar_entry(entry: JarEntry, dest_dir: &Path) -> Result<()> {
+    let contents = if entry.deflated {
+        let mut contents = Vec::with_capacity(entry.size as usize);
+        DeflateDecoder::new(entry.data.as_slice())
+            .read_to_end(&mut contents)
+            .with_context(|| format!("Failed to inflate {}", entry.path))?;
+        contents
+    } else {
+        entry.data
+    };
+    if crc32fast::hash(&contents) != entry.crc32 {
+        return Err(anyhow!("Corrupt entry in JAR: {}", entry.path));
+    }
+
+    let output_path = dest_dir.join(&entry.path);
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent).context("Failed to create directory")?;
+    }
+    fs::write(&output_path, contents).context("Failed to write file")?;
+    Ok(())
+}
+
+/// How a version's cache compares to what extraction would produce
+#[derive(Debug, PartialEq)]
+enum CacheState {
+    /// Complete, with every enabled texture category
+    Current,
+    /// Complete, but lacking these enabled categories; only they are extracted
+    MissingCategories(Vec<TextureCategory>),
+    /// Missing, partial, or from another JAR; extracted again from scratch
+    Stale,
+}
+
+/// Whether `cache_dir` holds a complete extraction of `version_name` from the
+/// JAR at `jar_path`
+fn cache_state(cache_dir: &Path, jar_path: &Path, version_name: &str) -> Result<CacheState> {
+    let marker_file = cache_dir.join(VERSION_MARKER);
+    let Ok(cached_version) = fs::read_to_string(&marker_file) else {
+        return Ok(CacheState::Stale);
+    };
+    if cached_version.trim() != version_name {
+        return Ok(CacheState::Stale);
+    }
+    // Caches from before provenance was recorded are trusted as they are
+    let provenance = read_provenance(cache_dir);
+    if let Some(provenance) = &provenance {
+        if !jar_matches(provenance, jar_path)? {
+            info!(
+                "JAR for {} differs from the one extracted (SHA-1 {}), re-extracting",
+                version_name, provenance.jar_sha1
+            );
+            return Ok(CacheState::Stale);
+        }
+    }
+    if !is_cache_complete(cache_dir, jar_path)? {
+        info!(
+            "Cache missing required assets for {}, re-extracting",
+            version_name
+        );
+        return Ok(CacheState::Stale);
+    }
+    let missing = provenance
+        .map(|provenance| missing_categories(&provenance))
+        .unwrap_or_default();
+    if missing.is_empty() {
+        Ok(CacheState::Current)
+    } else {
+        Ok(CacheState::MissingCategories(missing))
+    }
+}
+
+/// Provenance of the vanilla texture cache, None if it predates provenance
+/// tracking or nothing has been extracted
+pub fn get_vanilla_provenance() -> Result<Option<VanillaProvenance>> {
+    Ok(read_provenance(&get_vanilla_cache_dir()?))
+}
+
+fn read_provenance(cache_dir: &Path) -> Option<VanillaProvenance> {
+    let content = fs::read_to_string(cache_dir.join(PROVENANCE_FILE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Describe the JAR at `jar_path`, hashing it
+fn jar_provenance(jar_path: &Path, version_name: &str) -> Result<VanillaProvenance> {
+    let (jar_size, jar_modified) = jar_stamp(jar_path)?;
+    Ok(VanillaProvenance {
+        version: version_name.to_string(),
+        jar_path: jar_path.to_string_lossy().to_string(),
+        jar_sha1: crate::util::download::file_sha1(jar_path)?,
+        jar_size,
+        jar_modified,
+        asset_index: read_asset_index_id(jar_path, version_name),
+        extracted_at: chrono::Utc::now().to_rfc3339(),
+        categories: texture_categories(),
+    })
+}
+
+/// Enabled categories a cache extracted with `provenance` doesn't hold
+fn missing_categories(provenance: &VanillaProvenance) -> Vec<TextureCategory> {
+    texture_categories()
+        .into_iter()
+        .filter(|category| !provenance.categories.contains(category))
+        .collect()
+}
+
+/// JAR to extract from when the cache lacks a newly enabled category
+fn jar_for_missing_categories(cache_dir: &Path) -> Option<PathBuf> {
+    let provenance = read_provenance(cache_dir)?;
+    if missing_categories(&provenance).is_empty() {
+        return None;
+    }
+    let jar_path = PathBuf::from(&provenance.jar_path);
+    jar_path.is_file().then_some(jar_path)
+}
+
+fn jar_stamp(jar_path: &Path) -> Result<(u64, u64)> {
+    let metadata =
+        fs::metadata(jar_path).with_context(|| format!("Failed to read {}", jar_path.display()))?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|since| since.as_secs())
+        .unwrap_or(0);
+    Ok((metadata.len(), modified))
+}
+
+/// Whether `jar_path` is the JAR `provenance` was extracted from
+///
+/// The JAR is only hashed when its size or modification time changed (or it
+/// lives elsewhere, e.g. on another machine sharing the cache).
+fn jar_matches(provenance: &VanillaProvenance, jar_path: &Path) -> Result<bool> {
+    let (size, modified) = jar_stamp(jar_path)?;
+    if size != provenance.jar_size {
+        return Ok(false);
+    }
+    if modified == provenance.jar_modified && Path::new(&provenance.jar_path) == jar_path {
+        return Ok(true);
+    }
+    Ok(crate::util::download::file_sha1(jar_path)?.eq_ignore_ascii_case(&provenance.jar_sha1))
+}
+
+/// Asset index ID ("assetIndex.id", or the older "assets" field) from the
+/// version JSON the launcher keeps next to the JAR
+fn read_asset_index_id(jar_path: &Path, version_name: &str) -> Option<String> {
+    let json_path = jar_path.with_file_name(format!("{}.json", version_name));
+    let version_json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(json_path).ok()?).ok()?;
+    version_json
+        .pointer("/assetIndex/id")
+        .or_else(|| version_json.get("assets"))
+        .and_then(|id| id.as_str())
+        .map(str::to_string)
+}
+
 /// Extract vanilla textures from the Minecraft JAR to cache
 pub fn extract_vanilla_textures(jar_path: &Path) -> Result<PathBuf> {
     extract_vanilla_textures_with_progress(jar_path, None)
@@ -416,7 +1062,7 @@ pub fn extract_vanilla_textures_with_progress(
     jar_path: &Path,
     progress_callback: Option<ProgressCallback>,
 ) -> Result<PathBuf> {
-    let cache_dir = get_vanilla_cache_dir()?;
+    let _extracting = EXTRACTION.lock().unwrap_or_else(|e| e.into_inner());
 
     // Extract version name from jar path
     let version_name = jar_path
@@ -425,156 +1071,203 @@ pub fn extract_vanilla_textures_with_progress(
         .and_then(|n| n.to_str())
         .ok_or_else(|| anyhow!("Could not determine version name from JAR path"))?;
 
-    // Check if already extracted with this version
-    let marker_file = cache_dir.join(".extracted_version");
-    if marker_file.exists() {
-        if let Ok(cached_version) = fs::read_to_string(&marker_file) {
-            if cached_version.trim() == version_name {
-                if is_cache_complete(&cache_dir, jar_path)? {
-                    println!("[vanilla_textures] Version {} already cached", version_name);
-                    return Ok(cache_dir);
-                }
-                println!(
-                    "[vanilla_textures] Cache missing required assets for {}, re-extracting",
-                    version_name
-                );
-            }
+    // Versions are kept side by side, so switching back to one is instant
+    migrate_legacy_cache();
+    let cache_dir = get_version_cache_dir(version_name)?;
+    if cache_state(&cache_dir, jar_path, version_name)? == CacheState::Current {
+        info!("Version {} already cached", version_name);
+        set_active_version(version_name)?;
+        return Ok(cache_dir);
+    }
+
+    // A read-only shared cache is never extracted into; see `cache_location`
+    let cache_dir =
+        cache_location::writable_cache_dir(&format!("{}/{}", VANILLA_CACHE_NAME, version_name))?;
+    let _lock = cache_location::CacheLock::acquire(&cache_dir)?;
+
+    // Another machine sharing the cache may have extracted it while we waited
+    match cache_state(&cache_dir, jar_path, version_name)? {
+        CacheState::Current => {
+            info!("Version {} was extracted by another instance", version_name);
+            set_active_version(version_name)?;
+            return Ok(cache_dir);
+        }
+        CacheState::MissingCategories(missing) => {
+            extract_categories(jar_path, &cache_dir, &missing, progress_callback)?;
+            set_active_version(version_name)?;
+            return Ok(cache_dir);
         }
+        CacheState::Stale => {}
     }
+    let marker_file = cache_dir.join(VERSION_MARKER);
 
-    // Clean old cache if it exists
+    // Clean an outdated or partial extraction of this version
     if cache_dir.exists() {
-        println!(
-            "[vanilla_textures] Cleaning old cache to extract version {}",
-            version_name
-        );
+        info!("Cleaning old cache to extract version {}", version_name);
         fs::remove_dir_all(&cache_dir).context("Failed to clean old cache")?;
-        fs::create_dir_all(&cache_dir).context("Failed to recreate cache directory")?;
     }
+    fs::create_dir_all(&cache_dir).context("Failed to recreate cache directory")?;
 
-    // First pass: collect all files that need to be extracted
-    let jar_file = fs::File::open(jar_path).context("Failed to open Minecraft JAR file")?;
-    let mut archive = ZipArchive::new(jar_file).context("Failed to read JAR archive")?;
+    // Hash before extracting, so the provenance describes the JAR that was read
+    let provenance = jar_provenance(jar_path, version_name)?;
+    let extracted = extract_jar_assets(jar_path, &cache_dir, progress_callback)?;
+    info!("All {} files extracted successfully", extracted);
 
-    let mut files_to_extract = Vec::new();
-
-    for i in 0..archive.len() {
-        let file = archive
-            .by_index(i)
-            .context("Failed to read archive entry")?;
-
-        let file_path = file.name().to_string();
+    // Create marker file with version name
+    info!("Writing marker file for version: {}", version_name);
+    fs::write(
+        cache_dir.join(PROVENANCE_FILE),
+        serde_json::to_string_pretty(&provenance)?,
+    )
+    .context("Failed to write extraction provenance")?;
+    fs::write(&marker_file, version_name).context("Failed to create extraction marker")?;
+    info!("Marker file written to: {}", marker_file.display());
+    set_active_version(version_name)?;
 
-        // Extract textures (PNG), animation metadata (PNG.MCMETA), models (JSON), blockstates (JSON), and particles (JSON)
-        let should_extract = (file_path.starts_with("assets/minecraft/textures/")
-            && (file_path.ends_with(".png") || file_path.ends_with(".png.mcmeta")))
-            || (file_path.starts_with("assets/minecraft/models/") && file_path.ends_with(".json"))
-            || (file_path.starts_with("assets/minecraft/blockstates/")
-                && file_path.ends_with(".json"))
-            || (file_path.starts_with("assets/minecraft/particles/")
-                && file_path.ends_with(".json"))
-            || (file_path.starts_with("assets/minecraft/optifine/cem/")
-                && (file_path.ends_with(".jem") || file_path.ends_with(".jpm")));
+    info!(
+        "Successfully extracted vanilla assets for version {} (textures, .mcmeta, models, blockstates, particles) in PARALLEL",
+        version_name
+    );
+    Ok(cache_dir)
+}
 
-        if should_extract {
-            files_to_extract.push((i, file_path));
+/// Add texture categories to a complete cache, extracting only their textures
+///
+/// The provenance is updated last, so an interrupted extraction is repeated.
+fn extract_categories(
+    jar_path: &Path,
+    cache_dir: &Path,
+    categories: &[TextureCategory],
+    progress_callback: Option<ProgressCallback>,
+) -> Result<()> {
+    info!(
+        "Extracting texture categories {:?} into the existing cache",
+        categories
+    );
+    let wanted: BTreeSet<TextureCategory> = categories.iter().copied().collect();
+    let select = |file_path: &str| {
+        category_texture(file_path)
+            .is_some_and(|category| wanted.contains(&category))
+            .then(|| file_path.to_string())
+    };
+    let extracted = extract_jar_entries(jar_path, cache_dir, &select, progress_callback)?;
+
+    let mut provenance = read_provenance(cache_dir)
+        .ok_or_else(|| anyhow!("Vanilla cache has no extraction provenance"))?;
+    for category in categories {
+        if !provenance.categories.contains(category) {
+            provenance.categories.push(*category);
         }
     }
+    provenance.categories.sort();
+    fs::write(
+        cache_dir.join(PROVENANCE_FILE),
+        serde_json::to_string_pretty(&provenance)?,
+    )
+    .context("Failed to write extraction provenance")?;
+    info!("Added {} files for {:?}", extracted, categories);
+    Ok(())
+}
 
-    let total_files = files_to_extract.len();
-    println!(
-        "[vanilla_textures] Found {} files to extract, extracting in PARALLEL",
-        total_files
-    );
-
-    // Report initial progress
-    if let Some(ref callback) = progress_callback {
-        callback(0, total_files);
+/// Optional category of a JAR texture entry (PNG or PNG.MCMETA), None for
+/// other entries and always extracted textures
+fn category_texture(file_path: &str) -> Option<TextureCategory> {
+    let texture = file_path.strip_prefix("assets/minecraft/textures/")?;
+    if !(texture.ends_with(".png") || texture.ends_with(".png.mcmeta")) {
+        return None;
     }
+    let folder = texture.split_once('/').map_or("", |(folder, _)| folder);
+    TextureCategory::of_folder(folder)
+}
 
-    // Second pass: extract files in parallel using chunked batches
-    // This is much faster than opening the JAR for each file
-    let jar_path_clone = jar_path.to_path_buf();
-    let cache_dir_clone = cache_dir.clone();
-    let extracted_count = Arc::new(AtomicUsize::new(0));
-    let progress_callback_clone = progress_callback.clone();
-
-    // Determine optimal chunk size based on CPU count
-    let num_threads = rayon::current_num_threads();
-    let chunk_size = (total_files + num_threads - 1) / num_threads; // Ceiling division
-
-    let extraction_result: Result<()> =
-        files_to_extract
-            .par_chunks(chunk_size)
-            .try_for_each(|chunk| -> Result<()> {
-                // Open JAR once per chunk (per thread)
-                let jar_file =
-                    fs::File::open(&jar_path_clone).context("Failed to open Minecraft JAR file")?;
-                let mut archive =
-                    ZipArchive::new(jar_file).context("Failed to read JAR archive")?;
-
-                // Process all files in this chunk
-                for (index, file_path) in chunk {
-                    let mut file = archive
-                        .by_index(*index)
-                        .context("Failed to read archive entry")?;
-
-                    // Keep the full structure: assets/minecraft/...
-                    let output_path = cache_dir_clone.join(file_path);
-
-                    // Create parent directories
-                    if let Some(parent) = output_path.parent() {
-                        fs::create_dir_all(parent).context("Failed to create directory")?;
-                    }
-
-                    // Extract the file
-                    let mut output_file =
-                        fs::File::create(&output_path).context("Failed to create file")?;
-                    std::io::copy(&mut file, &mut output_file).context("Failed to write file")?;
-
-                    // Update progress
-                    let count = extracted_count.fetch_add(1, Ordering::Relaxed) + 1;
-                    if let Some(ref callback) = progress_callback_clone {
-                        // Report progress every 50 files or on completion
-                        if count % 50 == 0 || count == total_files {
-                            callback(count, total_files);
-                        }
-                    }
-                }
+/// Directory holding a version's extracted built-in packs, one folder per pack
+///
+/// The directory isn't created, and may not exist yet.
+pub fn get_builtin_packs_dir(version: &str) -> Result<PathBuf> {
+    cache_location::cache_dir(&format!("{}/{}", BUILTIN_PACKS_CACHE_NAME, version))
+}
 
-                Ok(())
-            });
+/// Built-in packs of the cached vanilla version, extracting them on first use
+///
+/// The JAR is the one the vanilla cache was extracted from. Returns None when
+/// no vanilla version is cached or its JAR is gone.
+pub fn ensure_builtin_packs() -> Result<Option<PathBuf>> {
+    let Some(version) = get_cached_version()? else {
+        return Ok(None);
+    };
+    let cache_dir = get_builtin_packs_dir(&version)?;
+    if is_builtin_cached(&cache_dir, &version) {
+        return Ok(Some(cache_dir));
+    }
 
-    // Check if extraction succeeded
-    if let Err(e) = extraction_result {
-        eprintln!("[vanilla_textures] ERROR during extraction: {}", e);
-        return Err(e);
+    // The provenance may name a JAR on another machine sharing the cache
+    let jar_path = get_vanilla_provenance()?
+        .map(|provenance| PathBuf::from(provenance.jar_path))
+        .filter(|jar| jar.is_file())
+        .or_else(|| {
+            list_all_available_versions()
+                .ok()?
+                .into_iter()
+                .find(|candidate| candidate.version == version)
+                .map(|found| PathBuf::from(found.jar_path))
+        });
+    match jar_path {
+        Some(jar_path) => extract_builtin_packs(&jar_path, &version).map(Some),
+        None => Ok(None),
     }
+}
 
-    println!("[vanilla_textures] All files extracted successfully");
+fn is_builtin_cached(cache_dir: &Path, version_name: &str) -> bool {
+    fs::read_to_string(cache_dir.join(VERSION_MARKER))
+        .is_ok_and(|cached| cached.trim() == version_name)
+}
 
-    // Create marker file with version name
-    println!(
-        "[vanilla_textures] Writing marker file for version: {}",
-        version_name
-    );
-    fs::write(&marker_file, version_name).context("Failed to create extraction marker")?;
-    println!(
-        "[vanilla_textures] Marker file written to: {}",
-        marker_file.display()
-    );
+/// Extract the packs built into the JAR (resourcepacks/programmer_art,
+/// resourcepacks/high_contrast) unless they are cached for `version_name`
+///
+/// Only the same kinds of files as the vanilla cache are extracted, plus each
+/// pack's pack.mcmeta and pack.png. Older JARs without built-in packs leave
+/// an empty cache.
+pub fn extract_builtin_packs(jar_path: &Path, version_name: &str) -> Result<PathBuf> {
+    let cache_dir = get_builtin_packs_dir(version_name)?;
+    if is_builtin_cached(&cache_dir, version_name) {
+        return Ok(cache_dir);
+    }
 
-    println!(
-        "[vanilla_textures] Successfully extracted vanilla assets for version {} (textures, .mcmeta, models, blockstates, particles) in PARALLEL",
-        version_name
+    let cache_dir = cache_location::writable_cache_dir(&format!(
+        "{}/{}",
+        BUILTIN_PACKS_CACHE_NAME, version_name
+    ))?;
+    let _lock = cache_location::CacheLock::acquire(&cache_dir)?;
+    if is_builtin_cached(&cache_dir, version_name) {
+        return Ok(cache_dir);
+    }
+    if cache_dir.exists() {
+        fs::remove_dir_all(&cache_dir).context("Failed to clean old built-in packs")?;
+    }
+    fs::create_dir_all(&cache_dir).context("Failed to create built-in packs directory")?;
+
+    let extracted = extract_jar_entries(jar_path, &cache_dir, &builtin_pack_entry_path, None)?;
+    fs::write(cache_dir.join(VERSION_MARKER), version_name)
+        .context("Failed to create extraction marker")?;
+    info!(
+        "Extracted {} files of built-in packs for {}",
+        extracted, version_name
     );
     Ok(cache_dir)
 }
 
 /// Get the path to a vanilla texture file by asset ID
 /// Example: "minecraft:block/stone" -> cache_dir/assets/minecraft/textures/block/stone.png
-pub fn get_vanilla_texture_path(asset_id: &str) -> Result<PathBuf> {
-    let cache_dir = get_vanilla_cache_dir()?;
+///
+/// `version` picks another cached version than the one in use; it must have
+/// been extracted already.
+pub fn get_vanilla_texture_path(asset_id: &str, version: Option<&str>) -> Result<PathBuf> {
+    let cache_dir = match version {
+        Some(version) if is_version_extracted(version) => get_version_cache_dir(version)?,
+        Some(version) => return Err(anyhow!("Vanilla {} is not extracted", version)),
+        None => get_vanilla_cache_dir()?,
+    };
 
     // Parse asset ID: "minecraft:block/stone" -> "block/stone"
     let texture_path = asset_id.strip_prefix("minecraft:").unwrap_or(asset_id);
@@ -639,9 +1332,12 @@ pub fn initialize_vanilla_textures_from_dir_with_progress(
 ) -> Result<PathBuf> {
     let cache_dir = get_vanilla_cache_dir()?;
 
-    // If already extracted, return cache dir
+    // If already extracted, return cache dir, unless categories were enabled since
     if get_cached_version()?.is_some() {
-        return Ok(cache_dir);
+        return match jar_for_missing_categories(&cache_dir) {
+            Some(jar_path) => extract_vanilla_textures_with_progress(&jar_path, progress_callback),
+            None => Ok(cache_dir),
+        };
     }
 
     // Find and extract from latest version JAR in the given directory
@@ -660,9 +1356,12 @@ pub fn initialize_vanilla_textures_with_progress(
 ) -> Result<PathBuf> {
     let cache_dir = get_vanilla_cache_dir()?;
 
-    // If already extracted, return cache dir
+    // If already extracted, return cache dir, unless categories were enabled since
     if get_cached_version()?.is_some() {
-        return Ok(cache_dir);
+        return match jar_for_missing_categories(&cache_dir) {
+            Some(jar_path) => extract_vanilla_textures_with_progress(&jar_path, progress_callback),
+            None => Ok(cache_dir),
+        };
     }
 
     // Find and extract from latest version JAR
@@ -700,6 +1399,222 @@ pub fn extract_vanilla_textures_for_version_with_progress(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_extract_jar_assets() {
+        use std::io::Write;
+        use zip::write::FileOptions;
+
+        let temp_dir = std::env::temp_dir().join("test_extract_jar_assets");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+        let jar_path = temp_dir.join("client.jar");
+        let dest_dir = temp_dir.join("cache");
+
+        let stone = vec![7u8; 4096];
+        let mut writer = zip::ZipWriter::new(fs::File::create(&jar_path).unwrap());
+        let deflated = FileOptions::default().compression_method(CompressionMethod::Deflated);
+        let stored = FileOptions::default().compression_method(CompressionMethod::Stored);
+        writer
+            .start_file("assets/minecraft/textures/block/stone.png", deflated)
+            .unwrap();
+        writer.write_all(&stone).unwrap();
+        writer
+            .start_file("assets/minecraft/models/block/stone.json", stored)
+            .unwrap();
+        writer.write_all(b"{}").unwrap();
+        writer
+            .start_file("net/minecraft/client/Main.class", deflated)
+            .unwrap();
+        writer.write_all(b"class").unwrap();
+        writer
+            .start_file(
+                "resourcepacks/programmer_art/assets/minecraft/textures/block/stone.png",
+                deflated,
+            )
+            .unwrap();
+        writer.write_all(&stone).unwrap();
+        writer
+            .start_file("resourcepacks/programmer_art/pack.mcmeta", stored)
+            .unwrap();
+        writer.write_all(b"{}").unwrap();
+        writer.finish().unwrap();
+
+        let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = calls.clone();
+        let progress: ProgressCallback = Arc::new(move |current, total| {
+            recorded.lock().unwrap().push((current, total));
+        });
+        let extracted = extract_jar_assets(&jar_path, &dest_dir, Some(progress)).unwrap();
+        let stone_out = fs::read(dest_dir.join("assets/minecraft/textures/block/stone.png"));
+        let model_out = fs::read(dest_dir.join("assets/minecraft/models/block/stone.json"));
+        let class_out = dest_dir.join("net/minecraft/client/Main.class").exists();
+        // Built-in packs go to their own cache, without the resourcepacks/ prefix
+        let builtin_dir = temp_dir.join("builtin");
+        let builtin_extracted =
+            extract_jar_entries(&jar_path, &builtin_dir, &builtin_pack_entry_path, None).unwrap();
+        let builtin_stone = builtin_dir
+            .join("programmer_art/assets/minecraft/textures/block/stone.png")
+            .exists();
+        let builtin_mcmeta = builtin_dir.join("programmer_art/pack.mcmeta").exists();
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(extracted, 2);
+        assert_eq!(stone_out.unwrap(), stone);
+        assert_eq!(model_out.unwrap(), b"{}");
+        assert!(!class_out);
+        assert_eq!(builtin_extracted, 2);
+        assert!(builtin_stone);
+        assert!(builtin_mcmeta);
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.first(), Some(&(0, 2)));
+        assert_eq!(calls.last(), Some(&(2, 2)));
+    }
+
+    #[test]
+    fn test_extract_categories_adds_to_cache() {
+        use std::io::Write;
+
+        let temp_dir = std::env::temp_dir().join("test_extract_categories");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let cache_dir = temp_dir.join("cache");
+        fs::create_dir_all(&cache_dir).unwrap();
+        let jar_path = temp_dir.join("client.jar");
+        let mut writer = zip::ZipWriter::new(fs::File::create(&jar_path).unwrap());
+        for entry in [
+            "assets/minecraft/textures/block/stone.png",
+            "assets/minecraft/textures/entity/cow/cow.png",
+            "assets/minecraft/textures/entity/cow/cow.png.mcmeta",
+            "assets/minecraft/textures/gui/title/minecraft.png",
+        ] {
+            writer
+                .start_file(entry, zip::write::FileOptions::default())
+                .unwrap();
+            writer.write_all(b"png").unwrap();
+        }
+        writer.finish().unwrap();
+        let provenance = VanillaProvenance {
+            version: "1.21.4".to_string(),
+            jar_path: jar_path.to_string_lossy().to_string(),
+            jar_sha1: String::new(),
+            jar_size: 0,
+            jar_modified: 0,
+            asset_index: None,
+            extracted_at: String::new(),
+            categories: vec![TextureCategory::Gui],
+        };
+        fs::write(
+            cache_dir.join(PROVENANCE_FILE),
+            serde_json::to_string(&provenance).unwrap(),
+        )
+        .unwrap();
+
+        let result = extract_categories(&jar_path, &cache_dir, &[TextureCategory::Entity], None);
+        let textures = cache_dir.join("assets/minecraft/textures");
+        let cow = textures.join("entity/cow/cow.png").exists()
+            && textures.join("entity/cow/cow.png.mcmeta").exists();
+        // Only the new category is read from the JAR
+        let others = textures.join("block").exists() || textures.join("gui").exists();
+        let recorded = read_provenance(&cache_dir).map(|provenance| provenance.categories);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert!(result.is_ok());
+        assert!(cow);
+        assert!(!others);
+        assert_eq!(
+            recorded,
+            Some(vec![TextureCategory::Entity, TextureCategory::Gui])
+        );
+    }
+
+    #[test]
+    fn test_should_extract_texture_categories() {
+        let enabled: BTreeSet<TextureCategory> = [TextureCategory::Entity].into_iter().collect();
+        let extracted = |path: &str| should_extract_with(path, &enabled);
+
+        // Block, item and colormap textures don't depend on the categories
+        assert!(extracted("assets/minecraft/textures/block/stone.png"));
+        assert!(extracted(
+            "assets/minecraft/textures/item/diamond.png.mcmeta"
+        ));
+        assert!(extracted("assets/minecraft/textures/colormap/grass.png"));
+        assert!(extracted("assets/minecraft/textures/entity/cow/cow.png"));
+        assert!(!extracted(
+            "assets/minecraft/textures/gui/sprites/hud/heart/full.png"
+        ));
+        assert!(!extracted("assets/minecraft/textures/mob_effect/speed.png"));
+        assert!(!extracted("assets/minecraft/textures/environment/sun.png"));
+        assert!(extracted("assets/minecraft/models/block/stone.json"));
+        assert!(extracted("data/minecraft/worldgen/biome/pale_garden.json"));
+        assert!(!extracted("data/minecraft/worldgen/noise_settings/overworld.json"));
+    }
+
+    #[test]
+    fn test_jar_provenance_detects_changed_jar() {
+        let temp_dir = std::env::temp_dir().join("test_jar_provenance");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let version_dir = temp_dir.join("1.21.4");
+        fs::create_dir_all(&version_dir).unwrap();
+        let jar_path = version_dir.join("1.21.4.jar");
+        fs::write(&jar_path, b"original jar").unwrap();
+        fs::write(
+            version_dir.join("1.21.4.json"),
+            r#"{"assetIndex": {"id": "19"}}"#,
+        )
+        .unwrap();
+
+        let provenance = jar_provenance(&jar_path, "1.21.4").unwrap();
+        let unchanged = jar_matches(&provenance, &jar_path).unwrap();
+        // A re-downloaded JAR of the same size: only the hash can tell
+        fs::write(&jar_path, b"replaced jar").unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&jar_path)
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(60))
+            .unwrap();
+        let replaced = jar_matches(&provenance, &jar_path).unwrap();
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(provenance.asset_index.as_deref(), Some("19"));
+        assert_eq!(
+            provenance.jar_sha1,
+            format!(
+                "{:x}",
+                <sha1::Sha1 as sha1::Digest>::digest(b"original jar")
+            )
+        );
+        assert!(unchanged);
+        assert!(!replaced);
+    }
+
+    #[test]
+    fn test_move_into_version_dir() {
+        let root = std::env::temp_dir().join("test_vanilla_legacy_cache");
+        let _ = fs::remove_dir_all(&root);
+        let texture = Path::new("assets/minecraft/textures/block/stone.png");
+        fs::create_dir_all(root.join(texture).parent().unwrap()).unwrap();
+        fs::write(root.join(texture), b"png").unwrap();
+        fs::write(root.join(PROVENANCE_FILE), b"{}").unwrap();
+        fs::write(root.join(VERSION_MARKER), b"1.20.1").unwrap();
+
+        let moved = move_into_version_dir(&root, "1.20.1");
+        let version_dir = root.join("1.20.1");
+        let moved_texture = version_dir.join(texture).is_file();
+        let moved_files = version_dir.join(PROVENANCE_FILE).is_file()
+            && version_dir.join(VERSION_MARKER).is_file();
+        let left: Vec<_> = fs::read_dir(&root).unwrap().flatten().collect();
+
+        let _ = fs::remove_dir_all(&root);
+
+        assert!(moved.is_ok());
+        assert!(moved_texture);
+        assert!(moved_files);
+        assert_eq!(left.len(), 1);
+    }
+
     #[test]
     fn test_get_vanilla_cache_dir() {
         let cache_dir = get_vanilla_cache_dir();
@@ -1,11 +1,74 @@
 /// Build Weaver Nest - the optimized output resource pack
-use crate::model::{AssetRecord, OverrideSelection, PackMeta};
-use crate::util::zip;
+use crate::model::{
+    AssetRecord, CompressionMethod, McmetaPairing, NestCompression, OverrideSelection,
+    PackFormatRange, PackMeta, TextureInfoMap,
+};
+use crate::util::{
+    asset_hashes, asset_indexer, build_journal, cancellation, cit, ctm, emissive, i18n::tr, jem,
+    low_memory, pack_credits, pack_files, pack_merge, random_entities, resolution_mix, zip,
+};
 use anyhow::{anyhow, Result};
 use rayon::prelude::*;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tracing::{info, warn};
+use xxhash_rust::xxh3::Xxh3;
+
+/// File name of the archive written when the nest is built as a zip
+pub const NEST_ZIP_NAME: &str = "WeaverNest.zip";
+
+/// File name the nest zip is written under until it is complete
+pub const NEST_ZIP_PARTIAL_NAME: &str = "WeaverNest.zip.part";
+
+/// pack_format written to the output pack.mcmeta when no target is given
+const DEFAULT_PACK_FORMAT: u32 = 48;
+
+const PACK_DESCRIPTION: &str = "Weaverbird - Optimized Resource Pack";
+
+/// Fixed modification time for every output file (1980-01-01, the zip DOS epoch)
+/// so identical inputs produce byte-identical nests
+const NORMALIZED_MTIME_SECS: u64 = 315_532_800;
+
+/// Optional settings for a nest build
+#[derive(Debug, Clone, Default)]
+pub struct NestBuildOptions {
+    /// When set, write a zip archive (NEST_ZIP_NAME) inside output_dir instead of loose files
+    pub compression: Option<NestCompression>,
+    /// pack_format of the target Minecraft version. When set, pack overlays are
+    /// resolved for that format and flattened into the output; otherwise they are
+    /// carried through as overlays of the nest itself.
+    pub target_pack_format: Option<u32>,
+    /// How texture .mcmeta files are paired with the winning textures
+    pub mcmeta_pairing: McmetaPairing,
+    /// When set, winners byte-identical to this pack's copy of the same file
+    /// (the vanilla cache) are left out, since the game falls back to it anyway
+    pub dedupe_against: Option<PackMeta>,
+    /// When set, textures without an override go to the provider with the
+    /// highest resolution in this map instead of the highest pack in order;
+    /// pack order still breaks ties
+    pub prefer_highest_resolution: Option<TextureInfoMap>,
+    /// Vanilla pack the packs are layered over, where the base textures of
+    /// lone glow layers may come from
+    pub vanilla: Option<PackMeta>,
+    /// Log written files to the build journal in output_dir (see
+    /// `build_journal::begin`), skipping files an interrupted run of the same
+    /// plan already wrote
+    pub journal: bool,
+}
+
+/// Result of a nest build
+#[derive(Debug, Clone)]
+pub struct NestBuildOutput {
+    /// Path of the written pack (directory or zip file)
+    pub path: PathBuf,
+    /// Non-fatal problems, e.g. textures whose .mcmeta could not be paired
+    pub warnings: Vec<String>,
+    /// Translation keys that several packs defined differently, per merged lang file
+    pub lang_reports: Vec<pack_merge::LangMergeReport>,
+}
 
 /// Entry representing a winning asset to be copied
 #[allow(dead_code)]
@@ -14,7 +77,11 @@ struct WinnerEntry {
     asset_id: String,
     source_pack_id: String,
     source_path: String,
+    /// Path inside the nest (differs from source_path for overlay files)
+    output_path: String,
     source_is_zip: bool,
+    /// Generated content that replaces the source file (merged sounds.json)
+    merged_content: Option<Vec<u8>>,
 }
 
 /// Build Weaver Nest output pack
@@ -22,6 +89,16 @@ struct WinnerEntry {
 /// pack_order: List of pack IDs in priority order (top = highest priority)
 /// overrides: Map of asset_id -> override payload (pack + optional variant path)
 /// output_dir: Where to write the Weaver Nest pack
+/// options: Output compression, target pack format and .mcmeta pairing
+///
+/// sounds.json, lang/*.json and atlases/*.json are merged across all providers
+/// instead of copied from the winner. Winning CIT and CTM rules bring along the
+/// textures and models they reference, and a block's CTM rules all come from
+/// one pack, as do an entity texture's random variants. Emissive layers are
+/// taken from the pack that provides their base texture, unless a higher pack
+/// ships the layer on its own.
+///
+/// Returns the path of the written pack (directory or zip file) and build warnings
 pub fn build_weaver_nest(
     packs: &[PackMeta],
     assets: &[AssetRecord],
@@ -29,19 +106,27 @@ pub fn build_weaver_nest(
     pack_order: &[String],
     overrides: &HashMap<String, OverrideSelection>, // asset_id -> override payload
     output_dir: &str,
-) -> Result<()> {
+    options: &NestBuildOptions,
+) -> Result<NestBuildOutput> {
     let output_path = Path::new(output_dir);
 
     // Create output directory
     fs::create_dir_all(output_path)?;
 
-    // Create pack.mcmeta
-    create_pack_mcmeta(output_path)?;
-
     // Determine winners for each asset
     let mut winners = Vec::new();
+    let mut warnings = Vec::new();
+    let mut set_outputs = HashSet::new();
+    let mut emissive_outputs = HashSet::new();
+    let mut lone_layers = Vec::new();
 
     for asset in assets {
+        // Glow layers without a base in their pack are resolved once all bases are known
+        if emissive::is_orphan_layer(asset) {
+            lone_layers.push(asset);
+            continue;
+        }
+
         let mut override_source_path: Option<String> = None;
         let winner_pack_id = if let Some(override_entry) = overrides.get(&asset.id) {
             if let Some(path) = &override_entry.variant_path {
@@ -55,13 +140,21 @@ pub fn build_weaver_nest(
                 continue;
             }
 
+            let resolutions = options
+                .prefer_highest_resolution
+                .as_ref()
+                .and_then(|texture_info| texture_info.get(&asset.id));
             let winner = providing_packs
                 .iter()
                 .min_by_key(|pack_id| {
-                    pack_order
+                    let resolution = resolutions
+                        .and_then(|infos| infos.get(*pack_id))
+                        .map_or(0, |info| info.resolution());
+                    let position = pack_order
                         .iter()
                         .position(|id| id == *pack_id)
-                        .unwrap_or(usize::MAX)
+                        .unwrap_or(usize::MAX);
+                    (std::cmp::Reverse(resolution), position)
                 })
                 .cloned();
 
@@ -77,61 +170,1412 @@ pub fn build_weaver_nest(
             .find(|p| p.id == winner_pack_id)
             .ok_or_else(|| anyhow!("Pack not found: {}", winner_pack_id))?;
 
+        // A block's CTM rules, or an entity's random textures or CEM models,
+        // may live in differently named files in each pack; take every file of
+        // the set the winning pack has, so the whole set comes from one pack
+        let is_file_set = ctm::is_ctm_asset_id(&asset.id)
+            || random_entities::is_random_asset_id(&asset.id)
+            || jem::is_cem_asset_id(&asset.id);
+        if is_file_set && override_source_path.is_none() {
+            for file in &asset.files {
+                if set_outputs.contains(file) || !pack_files::pack_entry_exists(winner_pack, file) {
+                    continue;
+                }
+                set_outputs.insert(file.clone());
+                winners.push(WinnerEntry {
+                    asset_id: asset.id.clone(),
+                    source_pack_id: winner_pack.id.clone(),
+                    output_path: file.clone(),
+                    source_path: file.clone(),
+                    source_is_zip: winner_pack.is_zip,
+                    merged_content: None,
+                });
+            }
+            continue;
+        }
+
         // Find the file to copy (first one in the asset's file list or overridden variant)
         // In a real implementation, you might want to merge multiple files
         if let Some(source_file) = override_source_path.or_else(|| asset.files.first().cloned()) {
+            // Emissive layers always come from the pack the texture itself comes from
+            for (source_path, output_path) in emissive::companion_paths(&asset.files, &source_file)
+            {
+                if emissive_outputs.contains(&output_path)
+                    || !pack_files::pack_entry_exists(winner_pack, &source_path)
+                {
+                    continue;
+                }
+                emissive_outputs.insert(output_path.clone());
+                winners.push(WinnerEntry {
+                    asset_id: asset.id.clone(),
+                    source_pack_id: winner_pack.id.clone(),
+                    output_path,
+                    source_path,
+                    source_is_zip: winner_pack.is_zip,
+                    merged_content: None,
+                });
+            }
+
             winners.push(WinnerEntry {
                 asset_id: asset.id.clone(),
                 source_pack_id: winner_pack.id.clone(),
+                output_path: source_file.clone(),
                 source_path: source_file,
                 source_is_zip: winner_pack.is_zip,
+                merged_content: None,
             });
         }
     }
 
-    // Copy winner files to output in parallel
-    println!("[build_weaver_nest] Copying {} files in PARALLEL", winners.len());
     let pack_map: HashMap<String, &PackMeta> = packs.iter().map(|p| (p.id.clone(), p)).collect();
+    warnings.extend(add_lone_emissive_layers(
+        &mut winners,
+        &mut emissive_outputs,
+        &lone_layers,
+        &pack_map,
+        providers,
+        pack_order,
+        overrides,
+        options.vanilla.as_ref(),
+    ));
 
-    // Process files in parallel
-    winners
-        .par_iter()
-        .try_for_each(|winner| -> Result<()> {
-            let source_pack = pack_map
-                .get(&winner.source_pack_id)
-                .ok_or_else(|| anyhow!("Pack not found: {}", winner.source_pack_id))?;
-
-            let content = if winner.source_is_zip {
-                zip::extract_zip_entry(&source_pack.path, &winner.source_path)?
+    // Companions are renamed to the default suffix, so the nest declares it itself
+    if let Some(first) = winners
+        .iter()
+        .find(|w| emissive_outputs.contains(&w.output_path))
+    {
+        let source_pack_id = first.source_pack_id.clone();
+        winners.push(WinnerEntry {
+            asset_id: "minecraft:optifine/emissive.properties".to_string(),
+            source_pack_id,
+            output_path: emissive::EMISSIVE_PROPERTIES.to_string(),
+            source_path: emissive::EMISSIVE_PROPERTIES.to_string(),
+            source_is_zip: false,
+            merged_content: Some(emissive::nest_emissive_properties().into_bytes()),
+        });
+    }
+
+    // Zip packs that will be probed for overlay or .mcmeta files
+    let index = PackEntryIndex::build(packs.iter().filter(|pack| {
+        !pack.overlays.is_empty() || options.mcmeta_pairing != McmetaPairing::Strip
+    }))?;
+
+    warnings.extend(add_rule_companions(&mut winners, &pack_map));
+    warnings.extend(mixed_resolution_warning(&winners, &pack_map));
+    let (features, features_warning) = nest_features(&winners, &pack_map);
+    warnings.extend(features_warning);
+    warnings.extend(resolution_mismatch_warnings(&winners, packs, providers, pack_order));
+
+    let (mut winners, mut overlay_ranges) = apply_overlays(
+        winners,
+        &pack_map,
+        pack_order,
+        overrides,
+        &index,
+        options.target_pack_format,
+    )?;
+    let (stacked_warnings, lang_reports) = merge_stacked_files(
+        &mut winners,
+        &mut overlay_ranges,
+        &pack_map,
+        pack_order,
+        overrides,
+        &index,
+        options.target_pack_format,
+    )?;
+    warnings.extend(stacked_warnings);
+    if options.mcmeta_pairing != McmetaPairing::Strip {
+        let (mcmeta_entries, mcmeta_warnings) = pair_mcmeta(
+            &winners,
+            &pack_map,
+            providers,
+            pack_order,
+            &index,
+            options.mcmeta_pairing,
+        );
+        winners.extend(mcmeta_entries);
+        warnings.extend(mcmeta_warnings);
+    }
+    winners.extend(credits_entry(&winners, &pack_map, pack_order));
+    warnings.extend(sanitize_output_paths(&mut winners));
+    for warning in &warnings {
+        warn!("{}", warning);
+    }
+
+    let mcmeta = pack_mcmeta_json(
+        options.target_pack_format.unwrap_or(DEFAULT_PACK_FORMAT),
+        &overlay_ranges,
+        &features,
+    )?;
+
+    // Stable output order; when two assets map to the same file the first asset (by ID) wins
+    winners.sort_by(|a, b| {
+        a.output_path
+            .cmp(&b.output_path)
+            .then_with(|| a.asset_id.cmp(&b.asset_id))
+    });
+    winners.dedup_by(|a, b| a.output_path == b.output_path);
+
+    if let Some(base) = &options.dedupe_against {
+        let before = winners.len();
+        winners = drop_base_duplicates(winners, &pack_map, base);
+        info!(
+            "Left out {} file(s) identical to {}",
+            before - winners.len(),
+            base.id
+        );
+    }
+
+    if let Some(compression) = &options.compression {
+        // A zip can't be resumed midway; an interrupted one is simply rebuilt
+        let zip_path = output_path.join(NEST_ZIP_NAME);
+        let partial_path = output_path.join(NEST_ZIP_PARTIAL_NAME);
+        write_nest_zip(&winners, &pack_map, &mcmeta, &partial_path, compression)?;
+        fs::rename(&partial_path, &zip_path)?;
+        info!("Wrote {} files to {}", winners.len(), zip_path.display());
+        return Ok(NestBuildOutput {
+            path: zip_path,
+            warnings,
+            lang_reports,
+        });
+    }
+
+    let progress = if options.journal {
+        let progress = build_journal::BuildProgress::open(
+            output_path,
+            plan_hash(&winners, &pack_map, &mcmeta),
+        )?;
+        if progress.resumed_files() > 0 {
+            info!(
+                "Resuming, {} file(s) already written",
+                progress.resumed_files()
+            );
+        }
+        Some(progress)
+    } else {
+        None
+    };
+
+    // Create pack.mcmeta
+    create_pack_mcmeta(output_path, &mcmeta)?;
+    if let Some(progress) = &progress {
+        progress.record("pack.mcmeta")?;
+    }
+
+    // Copy winner files to output in parallel
+    info!("Copying {} files in PARALLEL", winners.len());
+
+    // Process files in parallel; a cancelled build stays journaled, so it can
+    // be resumed or rolled back like an interrupted one
+    let stream_files = low_memory::is_enabled();
+    let cancel = cancellation::current();
+    winners.par_iter().try_for_each(|winner| -> Result<()> {
+        cancel.check()?;
+        // Write to output
+        let output_file_path = output_path.join(&winner.output_path);
+        if progress
+            .as_ref()
+            .is_some_and(|progress| progress.is_written(&winner.output_path))
+            && output_file_path.is_file()
+        {
+            return Ok(());
+        }
+        fs::create_dir_all(output_file_path.parent().unwrap())?;
+        if stream_files {
+            let mut out = fs::File::create(&output_file_path)?;
+            copy_winner_content(winner, &pack_map, &mut out)?;
+        } else {
+            let content = read_winner_content(winner, &pack_map)?;
+            fs::write(&output_file_path, content)?;
+        }
+        normalize_mtime(&output_file_path)?;
+        if let Some(progress) = &progress {
+            progress.record(&winner.output_path)?;
+        }
+
+        Ok(())
+    })?;
+
+    info!("Successfully copied all files");
+    Ok(NestBuildOutput {
+        path: output_path.to_path_buf(),
+        warnings,
+        lang_reports,
+    })
+}
+
+/// File listings of zip packs, so existence checks don't reopen archives per file
+struct PackEntryIndex<'a> {
+    zip_files: HashMap<&'a str, HashSet<String>>,
+}
+
+impl<'a> PackEntryIndex<'a> {
+    fn build(packs: impl Iterator<Item = &'a PackMeta>) -> Result<Self> {
+        let zip_packs: Vec<&PackMeta> = packs.filter(|pack| pack.is_zip).collect();
+        let zip_files = zip_packs
+            .par_iter()
+            .map(|pack| {
+                let files = zip::list_zip_files(&pack.path)?;
+                Ok((pack.id.as_str(), files.into_iter().collect()))
+            })
+            .collect::<Result<_>>()?;
+        Ok(PackEntryIndex { zip_files })
+    }
+
+    fn contains(&self, pack: &PackMeta, relative_path: &str) -> bool {
+        match self.zip_files.get(pack.id.as_str()) {
+            Some(files) => files.contains(relative_path),
+            None => pack_files::pack_entry_exists(pack, relative_path),
+        }
+    }
+
+    /// Files under a folder of the pack, relative to that folder
+    fn files_under(&self, pack: &PackMeta, directory: &str) -> Vec<String> {
+        let prefix = format!("{}/", directory);
+        if let Some(files) = self.zip_files.get(pack.id.as_str()) {
+            return files
+                .iter()
+                .filter_map(|file| file.strip_prefix(&prefix))
+                .map(str::to_string)
+                .collect();
+        }
+        let Ok(root) = pack_files::resolve_dir_entry(Path::new(&pack.path), directory) else {
+            return Vec::new();
+        };
+        pack_files::pack_dir_files(&root)
+            .filter_map(|entry| {
+                let relative = entry.path().strip_prefix(&root).ok()?;
+                Some(relative.to_string_lossy().replace('\\', "/"))
+            })
+            .collect()
+    }
+}
+
+/// Copy the glow layers that packs ship without their base texture
+///
+/// The game resolves every file on its own, so a pack can light up a texture
+/// that comes from a lower pack or vanilla with just the layer. Each layer is
+/// taken from the pack chosen in the overrides, or else the highest-priority
+/// one, and renamed to the nest's suffix; it replaces a layer copied along with
+/// the base texture only when its pack ranks higher. Returns a warning for each
+/// layer whose base texture no pack (nor vanilla) has.
+#[allow(clippy::too_many_arguments)]
+fn add_lone_emissive_layers(
+    winners: &mut Vec<WinnerEntry>,
+    emissive_outputs: &mut HashSet<String>,
+    lone_layers: &[&AssetRecord],
+    pack_map: &HashMap<String, &PackMeta>,
+    providers: &HashMap<String, Vec<String>>,
+    pack_order: &[String],
+    overrides: &HashMap<String, OverrideSelection>,
+    vanilla: Option<&PackMeta>,
+) -> Vec<String> {
+    let rank = |pack_id: &str| {
+        pack_order
+            .iter()
+            .position(|id| id == pack_id)
+            .unwrap_or(usize::MAX)
+    };
+    let mut warnings = Vec::new();
+
+    for layer in lone_layers {
+        let Some(file) = layer.files.first() else {
+            continue;
+        };
+        let pack_id = match overrides.get(&layer.id) {
+            Some(selection) => Some(&selection.pack_id),
+            None => providers
+                .get(&layer.id)
+                .and_then(|ids| ids.iter().min_by_key(|id| rank(id))),
+        };
+        let Some(pack) = pack_id.and_then(|id| pack_map.get(id).copied()) else {
+            continue;
+        };
+        let suffix = if pack_files::pack_entry_exists(pack, emissive::EMISSIVE_PROPERTIES) {
+            emissive::emissive_suffix(pack, &[emissive::EMISSIVE_PROPERTIES.to_string()])
+        } else {
+            emissive::DEFAULT_EMISSIVE_SUFFIX.to_string()
+        };
+        let Some(base) = emissive::emissive_base_path(file, &suffix) else {
+            continue;
+        };
+
+        let has_base = pack_order
+            .iter()
+            .filter_map(|id| pack_map.get(id))
+            .chain(vanilla.as_ref())
+            .any(|source| pack_files::pack_entry_exists(source, &base));
+        if !has_base {
+            warnings.push(tr(
+                "build-orphan-emissive",
+                &[("file", file.as_str().into())],
+            ));
+        }
+
+        let output_path = format!(
+            "{}{}.png",
+            base.trim_end_matches(".png"),
+            emissive::DEFAULT_EMISSIVE_SUFFIX
+        );
+        if !emissive_outputs.insert(output_path.clone()) {
+            match winners.iter().position(|w| w.output_path == output_path) {
+                Some(i) if rank(&pack.id) < rank(&winners[i].source_pack_id) => {
+                    winners.remove(i);
+                }
+                _ => continue,
+            }
+        }
+        winners.push(WinnerEntry {
+            asset_id: layer.id.clone(),
+            source_pack_id: pack.id.clone(),
+            output_path,
+            source_path: file.clone(),
+            source_is_zip: pack.is_zip,
+            merged_content: None,
+        });
+    }
+
+    warnings
+}
+
+/// Add the files referenced by winning CIT and CTM rules and the JPM parts
+/// imported by winning JEMs, taken from the rule's pack
+///
+/// A rule only works with the textures and models it points at, so they are
+/// copied from the same pack instead of being resolved independently. Files
+/// another winner already writes are left alone. Returns a warning for every
+/// referenced file the pack doesn't contain.
+fn add_rule_companions(
+    winners: &mut Vec<WinnerEntry>,
+    pack_map: &HashMap<String, &PackMeta>,
+) -> Vec<String> {
+    let mut outputs: HashSet<String> = winners.iter().map(|w| w.output_path.clone()).collect();
+    let mut companions = Vec::new();
+    let mut warnings = Vec::new();
+
+    for winner in winners.iter() {
+        let is_cit = cit::cit_asset_id(&winner.source_path).is_some();
+        let is_jem = jem::is_cem_asset_id(&winner.asset_id) && winner.source_path.ends_with(".jem");
+        if !is_cit && !is_jem && !ctm::is_ctm_properties(&winner.source_path) {
+            continue;
+        }
+        let Some(pack) = pack_map.get(&winner.source_pack_id) else {
+            continue;
+        };
+        let text = match read_winner_content(winner, pack_map) {
+            Ok(bytes) => String::from_utf8_lossy(&bytes).to_string(),
+            Err(e) => {
+                warnings.push(tr(
+                    "build-rule-unreadable",
+                    &[
+                        ("rule", winner.source_path.as_str().into()),
+                        ("error", e.to_string().into()),
+                    ],
+                ));
+                continue;
+            }
+        };
+        let referenced = if is_cit {
+            cit::referenced_files(&winner.source_path, &cit::parse_cit_rule(&text))
+        } else if is_jem {
+            jem::imported_jpms(&winner.source_path, &text)
+        } else {
+            ctm::tile_files(&winner.source_path, &ctm::parse_ctm_rule(&text))
+        };
+
+        for file in referenced {
+            if outputs.contains(&file) {
+                continue;
+            }
+            if !pack_files::pack_entry_exists(pack, &file) {
+                warnings.push(tr(
+                    "build-rule-missing-file",
+                    &[
+                        ("rule", winner.source_path.as_str().into()),
+                        ("pack", pack.name.as_str().into()),
+                        ("file", file.as_str().into()),
+                    ],
+                ));
+                continue;
+            }
+            outputs.insert(file.clone());
+            companions.push(WinnerEntry {
+                asset_id: winner.asset_id.clone(),
+                source_pack_id: winner.source_pack_id.clone(),
+                output_path: file.clone(),
+                source_path: file,
+                source_is_zip: winner.source_is_zip,
+                merged_content: None,
+            });
+        }
+    }
+
+    winners.extend(companions);
+    warnings
+}
+
+/// Warn when the packs whose textures made it into the nest differ in resolution
+///
+/// Blocks from a 16x pack next to 32x ones look out of place in game, so the
+/// packs are listed with their resolution, highest first.
+fn mixed_resolution_warning(
+    winners: &[WinnerEntry],
+    pack_map: &HashMap<String, &PackMeta>,
+) -> Option<String> {
+    let contributing: BTreeSet<&str> = winners
+        .iter()
+        .filter(|winner| winner.output_path.contains("/textures/"))
+        .map(|winner| winner.source_pack_id.as_str())
+        .collect();
+    let mut resolutions: Vec<(u32, &str)> = contributing
+        .into_iter()
+        .filter_map(|pack_id| {
+            let pack = pack_map.get(pack_id)?;
+            Some((pack.texture_resolution?, pack.name.as_str()))
+        })
+        .collect();
+    if resolutions
+        .iter()
+        .all(|(resolution, _)| *resolution == resolutions[0].0)
+    {
+        return None;
+    }
+    resolutions.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(b.1)));
+    let packs = resolutions
+        .iter()
+        .map(|(resolution, name)| format!("{} ({}x)", name, resolution))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some(tr("build-mixed-resolutions", &[("packs", packs.into())]))
+}
+
+/// Feature flags the nest needs: every flag a contributing pack enables
+///
+/// A pack with feature flags only loads in worlds that enable them, and so does
+/// the nest once it declares them, which takes the other packs' assets out of
+/// worlds without those flags. Packs that disagree are reported.
+fn nest_features(
+    winners: &[WinnerEntry],
+    pack_map: &HashMap<String, &PackMeta>,
+) -> (Vec<String>, Option<String>) {
+    let contributing: BTreeSet<&str> = winners
+        .iter()
+        .map(|winner| winner.source_pack_id.as_str())
+        .collect();
+    let packs: Vec<&PackMeta> = contributing
+        .into_iter()
+        .filter_map(|pack_id| pack_map.get(pack_id).copied())
+        .collect();
+    let features: Vec<String> = packs
+        .iter()
+        .flat_map(|pack| pack.features.iter().cloned())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    if packs.iter().all(|pack| pack.features == features) {
+        return (features, None);
+    }
+
+    let pack_features = packs
+        .iter()
+        .map(|pack| {
+            let flags = if pack.features.is_empty() {
+                "-".to_string()
             } else {
-                let full_path = Path::new(&source_pack.path).join(&winner.source_path);
-                fs::read(&full_path)?
+                pack.features.join(", ")
             };
+            format!("{} ({})", pack.name, flags)
+        })
+        .collect::<Vec<_>>()
+        .join("; ");
+    let warning = tr(
+        "build-feature-flags",
+        &[
+            ("packs", pack_features.into()),
+            ("features", features.join(", ").into()),
+        ],
+    );
+    (features, Some(warning))
+}
 
-            // Write to output
-            let output_file_path = output_path.join(&winner.source_path);
-            fs::create_dir_all(output_file_path.parent().unwrap())?;
-            fs::write(&output_file_path, content)?;
+/// Warn about blocks whose textures come from packs of different resolutions
+///
+/// Lists at most MAX_RESOLUTION_MISMATCHES_LISTED blocks, each with a pack
+/// that could provide all of its textures when there is one.
+fn resolution_mismatch_warnings(
+    winners: &[WinnerEntry],
+    packs: &[PackMeta],
+    providers: &HashMap<String, Vec<String>>,
+    pack_order: &[String],
+) -> Vec<String> {
+    const MAX_RESOLUTION_MISMATCHES_LISTED: usize = 20;
+
+    let winning_packs: HashMap<String, String> = winners
+        .iter()
+        .filter(|winner| winner.merged_content.is_none())
+        .map(|winner| (winner.asset_id.clone(), winner.source_pack_id.clone()))
+        .collect();
+    let mismatches =
+        resolution_mix::find_resolution_mismatches(&winning_packs, packs, providers, pack_order);
+
+    let mut warnings: Vec<String> = mismatches
+        .iter()
+        .take(MAX_RESOLUTION_MISMATCHES_LISTED)
+        .map(|mismatch| {
+            let textures = mismatch
+                .textures
+                .iter()
+                .map(|texture| {
+                    format!(
+                        "{} ({}, {}x)",
+                        texture.asset_id, texture.pack_id, texture.resolution
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            let mut args = vec![
+                ("block", mismatch.group.as_str().into()),
+                ("textures", textures.into()),
+            ];
+            match &mismatch.suggested_pack {
+                Some(pack) => {
+                    args.push(("pack", pack.as_str().into()));
+                    tr("build-resolution-mismatch-suggest", &args)
+                }
+                None => tr("build-resolution-mismatch", &args),
+            }
+        })
+        .collect();
+    if mismatches.len() > MAX_RESOLUTION_MISMATCHES_LISTED {
+        warnings.push(tr(
+            "build-resolution-mismatch-more",
+            &[(
+                "count",
+                (mismatches.len() - MAX_RESOLUTION_MISMATCHES_LISTED).into(),
+            )],
+        ));
+    }
+    warnings
+}
+
+/// Files the game merges across the whole pack stack
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StackedFile {
+    Sounds,
+    Lang,
+    Atlas,
+}
+
+impl StackedFile {
+    /// Stacked file kind for an asset ID, e.g. "minecraft:lang/en_us.json"
+    fn for_asset(asset_id: &str) -> Option<Self> {
+        let (_, path) = pack_files::split_asset_id(asset_id);
+        if path == "sounds.json" {
+            Some(StackedFile::Sounds)
+        } else if path.starts_with("lang/") && path.ends_with(".json") {
+            Some(StackedFile::Lang)
+        } else if path.starts_with("atlases/") && path.ends_with(".json") {
+            Some(StackedFile::Atlas)
+        } else {
+            None
+        }
+    }
+}
+
+/// Merge files that every pack in the stack contributes to (sounds.json,
+/// lang/*.json, atlases/*.json)
+///
+/// The game combines these across all enabled packs instead of using the top
+/// one, so the winner's content is replaced by a merge of every pack's file,
+/// lowest priority first, each read the way the pack presents it: from its
+/// last overlay active for the target format, or else its base folder. When
+/// overlays are carried through, the base folders are merged into the base and
+/// each format range with a different set of active overlays gets its own
+/// merged overlay copy. An explicit override keeps winner-takes-all. Files that
+/// can't be read or parsed are skipped with a warning.
+///
+/// Runs after `apply_overlays`, which leaves these files alone. Returns the
+/// warnings plus a report of overridden keys for each merged lang file.
+fn merge_stacked_files(
+    winners: &mut Vec<WinnerEntry>,
+    ranges: &mut Vec<PackFormatRange>,
+    pack_map: &HashMap<String, &PackMeta>,
+    pack_order: &[String],
+    overrides: &HashMap<String, OverrideSelection>,
+    index: &PackEntryIndex,
+    target_pack_format: Option<u32>,
+) -> Result<(Vec<String>, Vec<pack_merge::LangMergeReport>)> {
+    let mut warnings = Vec::new();
+    let mut lang_reports = Vec::new();
+    // Lowest priority first; quarantined packs are never read
+    let layers: Vec<&PackMeta> = pack_order
+        .iter()
+        .rev()
+        .filter_map(|id| pack_map.get(id).copied())
+        .filter(|pack| pack.quarantine.is_empty())
+        .collect();
+
+    let (stacked, rest): (Vec<_>, Vec<_>) = std::mem::take(winners)
+        .into_iter()
+        .partition(|winner| is_merged_stacked_file(winner, overrides));
+    *winners = rest;
+
+    for winner in stacked {
+        let Some(kind) = StackedFile::for_asset(&winner.asset_id) else {
+            continue;
+        };
+        let file = winner.output_path.clone();
+
+        // (format the packs are read at, overlay of the nest); no format reads
+        // base folders
+        let mut variants = vec![(target_pack_format, None)];
+        if target_pack_format.is_none() {
+            variants.extend(
+                overlay_segments(&layers, &file, index)
+                    .into_iter()
+                    .map(|range| (Some(range.min), Some(range))),
+            );
+        }
+
+        let mut base: Option<(String, String, Option<Vec<u8>>)> = None;
+        for (format, overlay) in variants {
+            let sources: Vec<(&PackMeta, String)> = layers
+                .iter()
+                .filter_map(|pack| {
+                    stacked_source_path(pack, &file, format, index).map(|path| (*pack, path))
+                })
+                .collect();
+            let Some((top_pack, top_path)) = sources.last() else {
+                continue;
+            };
+            let mut entry = WinnerEntry {
+                asset_id: winner.asset_id.clone(),
+                source_pack_id: top_pack.id.clone(),
+                output_path: match &overlay {
+                    Some(range) => format!("{}/{}", overlay_directory(range), file),
+                    None => file.clone(),
+                },
+                source_path: top_path.clone(),
+                source_is_zip: top_pack.is_zip,
+                merged_content: None,
+            };
+
+            if sources.len() > 1 {
+                let mut values = Vec::with_capacity(sources.len());
+                for (pack, path) in &sources {
+                    let parsed = pack_files::read_pack_entry(pack, path).and_then(|bytes| {
+                        serde_json::from_slice::<serde_json::Value>(&bytes)
+                            .map_err(anyhow::Error::from)
+                    });
+                    match parsed {
+                        Ok(value) => values.push((pack.name.clone(), value)),
+                        Err(e) => {
+                            let warning = tr(
+                                "build-merge-skipped",
+                                &[
+                                    ("file", path.as_str().into()),
+                                    ("pack", pack.name.as_str().into()),
+                                    ("error", e.to_string().into()),
+                                ],
+                            );
+                            if !warnings.contains(&warning) {
+                                warnings.push(warning);
+                            }
+                        }
+                    }
+                }
+
+                let merged = match kind {
+                    StackedFile::Sounds => {
+                        let values: Vec<_> = values.into_iter().map(|(_, value)| value).collect();
+                        pack_merge::merge_sounds_json(&values)
+                    }
+                    StackedFile::Atlas => {
+                        let values: Vec<_> = values.into_iter().map(|(_, value)| value).collect();
+                        pack_merge::merge_atlas_json(&values)
+                    }
+                    StackedFile::Lang => {
+                        let (merged, key_overrides) = pack_merge::merge_lang_json(&values);
+                        // Overlay copies would repeat the base file's report
+                        if overlay.is_none() && !key_overrides.is_empty() {
+                            lang_reports.push(pack_merge::LangMergeReport {
+                                file: file.clone(),
+                                overrides: key_overrides,
+                            });
+                        }
+                        merged
+                    }
+                };
+
+                let mut content = serde_json::to_vec_pretty(&merged)?;
+                content.push(b'\n');
+                entry.merged_content = Some(content);
+            }
+
+            let content = (
+                entry.source_pack_id.clone(),
+                entry.source_path.clone(),
+                entry.merged_content.clone(),
+            );
+            match overlay {
+                None => base = Some(content),
+                // Formats whose stack reads the same as the base folders need no copy
+                Some(_) if base.as_ref() == Some(&content) => continue,
+                Some(range) => ranges.push(range),
+            }
+            winners.push(entry);
+        }
+    }
+
+    ranges.sort_by_key(|range| (range.min, range.max));
+    ranges.dedup();
+    lang_reports.sort_by(|a, b| a.file.cmp(&b.file));
+    Ok((warnings, lang_reports))
+}
+
+/// Whether a winner is a stacked file merged across packs (not overridden)
+fn is_merged_stacked_file(
+    winner: &WinnerEntry,
+    overrides: &HashMap<String, OverrideSelection>,
+) -> bool {
+    StackedFile::for_asset(&winner.asset_id).is_some() && !overrides.contains_key(&winner.asset_id)
+}
+
+/// Where a pack serves a file from at a pack_format: its last active overlay
+/// that has the file, or else its base folder; None reads the base folder only
+fn stacked_source_path(
+    pack: &PackMeta,
+    file: &str,
+    format: Option<u32>,
+    index: &PackEntryIndex,
+) -> Option<String> {
+    format
+        .and_then(|format| {
+            pack.overlays
+                .iter()
+                .rev()
+                .filter(|overlay| overlay.formats.contains(format))
+                .map(|overlay| format!("{}/{}", overlay.directory, file))
+                .find(|path| index.contains(pack, path))
+        })
+        .or_else(|| index.contains(pack, file).then(|| file.to_string()))
+}
+
+/// Split the format ranges of every overlay that has `file` into ranges over
+/// which the same overlays are active
+fn overlay_segments(
+    layers: &[&PackMeta],
+    file: &str,
+    index: &PackEntryIndex,
+) -> Vec<PackFormatRange> {
+    let ranges: Vec<PackFormatRange> = layers
+        .iter()
+        .flat_map(|pack| {
+            pack.overlays
+                .iter()
+                .filter(|overlay| index.contains(pack, &format!("{}/{}", overlay.directory, file)))
+                .map(|overlay| overlay.formats)
+        })
+        .collect();
+    let bounds: BTreeSet<u64> = ranges
+        .iter()
+        .flat_map(|range| [range.min as u64, range.max as u64 + 1])
+        .collect();
+    let bounds: Vec<u64> = bounds.into_iter().collect();
+    bounds
+        .windows(2)
+        .map(|pair| PackFormatRange {
+            min: pair[0] as u32,
+            max: (pair[1] - 1) as u32,
+        })
+        .filter(|segment| ranges.iter().any(|range| range.contains(segment.min)))
+        .collect()
+}
+
+/// Route winners through the overlays of their source packs
+///
+/// With a target format, each file is read from the last active overlay that
+/// provides it (vanilla applies later entries on top of earlier ones). Without
+/// one, every overlay variant is copied into an `overlay_<min>_<max>` directory
+/// of the nest, and the format ranges used are returned for its pack.mcmeta.
+/// Files packs only have inside their overlays are added without a base copy.
+/// Stacked files that get merged are passed through untouched.
+fn apply_overlays(
+    winners: Vec<WinnerEntry>,
+    pack_map: &HashMap<String, &PackMeta>,
+    pack_order: &[String],
+    overrides: &HashMap<String, OverrideSelection>,
+    index: &PackEntryIndex,
+    target_pack_format: Option<u32>,
+) -> Result<(Vec<WinnerEntry>, Vec<PackFormatRange>)> {
+    let mut routed = Vec::with_capacity(winners.len());
+    let mut ranges = BTreeSet::new();
+
+    let overlay_only = overlay_only_winners(
+        &winners,
+        pack_map,
+        pack_order,
+        overrides,
+        index,
+        target_pack_format,
+    );
+    let entries = winners
+        .into_iter()
+        .map(|winner| (winner, true))
+        .chain(overlay_only.into_iter().map(|winner| (winner, false)));
+
+    for (mut winner, has_base) in entries {
+        // Merged across every pack's overlays by `merge_stacked_files`
+        if is_merged_stacked_file(&winner, overrides) {
+            routed.push(winner);
+            continue;
+        }
+        let pack = match pack_map.get(&winner.source_pack_id) {
+            Some(pack) if !pack.overlays.is_empty() => *pack,
+            _ => {
+                routed.push(winner);
+                continue;
+            }
+        };
+
+        match target_pack_format {
+            Some(format) => {
+                let overlay_path = pack
+                    .overlays
+                    .iter()
+                    .rev()
+                    .filter(|overlay| overlay.formats.contains(format))
+                    .map(|overlay| format!("{}/{}", overlay.directory, winner.output_path))
+                    .find(|path| index.contains(pack, path));
+                match overlay_path {
+                    Some(path) => winner.source_path = path,
+                    None if !has_base => continue,
+                    None => {}
+                }
+            }
+            None => {
+                for overlay in &pack.overlays {
+                    let source_path = format!("{}/{}", overlay.directory, winner.output_path);
+                    if !index.contains(pack, &source_path) {
+                        continue;
+                    }
+                    ranges.insert((overlay.formats.min, overlay.formats.max));
+                    routed.push(WinnerEntry {
+                        asset_id: winner.asset_id.clone(),
+                        source_pack_id: winner.source_pack_id.clone(),
+                        output_path: format!(
+                            "{}/{}",
+                            overlay_directory(&overlay.formats),
+                            winner.output_path
+                        ),
+                        source_path,
+                        source_is_zip: winner.source_is_zip,
+                        merged_content: None,
+                    });
+                }
+                if !has_base {
+                    continue;
+                }
+            }
+        }
+        routed.push(winner);
+    }
+
+    let ranges = ranges
+        .into_iter()
+        .map(|(min, max)| PackFormatRange { min, max })
+        .collect();
+    Ok((routed, ranges))
+}
+
+/// Files under `assets/` that packs only provide inside their overlays
+///
+/// The asset index only covers a pack's base folder, so these never become
+/// winners on their own. Only overlays active for the target format count (all
+/// of them when carrying overlays through). Each file is taken from the pack
+/// chosen in the overrides, or else from the highest-priority pack that has it;
+/// its .mcmeta is left to `pair_mcmeta`.
+fn overlay_only_winners(
+    winners: &[WinnerEntry],
+    pack_map: &HashMap<String, &PackMeta>,
+    pack_order: &[String],
+    overrides: &HashMap<String, OverrideSelection>,
+    index: &PackEntryIndex,
+    target_pack_format: Option<u32>,
+) -> Vec<WinnerEntry> {
+    let outputs: HashSet<&str> = winners.iter().map(|w| w.output_path.as_str()).collect();
+
+    // Overlay-only file -> packs providing it in a usable overlay, in pack order
+    let mut providers: BTreeMap<String, Vec<&PackMeta>> = BTreeMap::new();
+    for pack in pack_order.iter().filter_map(|id| pack_map.get(id)) {
+        let files: HashSet<String> = pack
+            .overlays
+            .iter()
+            .filter(|overlay| target_pack_format.map_or(true, |f| overlay.formats.contains(f)))
+            .flat_map(|overlay| index.files_under(pack, &overlay.directory))
+            .collect();
+        for file in files {
+            if file.starts_with("assets/")
+                && !file.ends_with(".mcmeta")
+                && !outputs.contains(file.as_str())
+            {
+                providers.entry(file).or_default().push(*pack);
+            }
+        }
+    }
+
+    providers
+        .into_iter()
+        .map(|(file, packs)| {
+            let asset_id = asset_indexer::file_asset_id(&file).unwrap_or_else(|| file.clone());
+            let pack = overrides
+                .get(&asset_id)
+                .and_then(|selection| packs.iter().find(|pack| pack.id == selection.pack_id))
+                .unwrap_or(&packs[0]);
+            WinnerEntry {
+                asset_id,
+                source_pack_id: pack.id.clone(),
+                output_path: file.clone(),
+                source_path: file,
+                source_is_zip: pack.is_zip,
+                merged_content: None,
+            }
+        })
+        .collect()
+}
+
+/// Find .mcmeta files to ship alongside the winning textures
+///
+/// A texture's .mcmeta is always taken from the pack (and overlay) the texture
+/// came from. When that pack has none but another provider does, the texture
+/// would silently lose its animation: `SamePack` reports this, while
+/// `BorrowMatching` borrows the metadata from the highest-priority provider
+/// whose texture has identical dimensions (so the frame layout still fits).
+///
+/// Returns the extra entries to write plus warnings for unpaired textures.
+fn pair_mcmeta(
+    winners: &[WinnerEntry],
+    pack_map: &HashMap<String, &PackMeta>,
+    providers: &HashMap<String, Vec<String>>,
+    pack_order: &[String],
+    index: &PackEntryIndex,
+    pairing: McmetaPairing,
+) -> (Vec<WinnerEntry>, Vec<String>) {
+    let mut entries = Vec::new();
+    let mut warnings = Vec::new();
+
+    for winner in winners {
+        if !winner.output_path.ends_with(".png") {
+            continue;
+        }
+        let Some(pack) = pack_map.get(&winner.source_pack_id) else {
+            continue;
+        };
+
+        let source_mcmeta = format!("{}.mcmeta", winner.source_path);
+        let output_mcmeta = format!("{}.mcmeta", winner.output_path);
+        if index.contains(pack, &source_mcmeta) {
+            entries.push(WinnerEntry {
+                asset_id: winner.asset_id.clone(),
+                source_pack_id: winner.source_pack_id.clone(),
+                source_path: source_mcmeta,
+                output_path: output_mcmeta,
+                source_is_zip: winner.source_is_zip,
+                merged_content: None,
+            });
+            continue;
+        }
+
+        // Overlay files and override variants have no counterpart in other packs
+        if winner.source_path != winner.output_path {
+            continue;
+        }
+
+        // Other providers with metadata for this texture, highest priority first
+        let mut donors: Vec<&PackMeta> = providers
+            .get(&winner.asset_id)
+            .into_iter()
+            .flatten()
+            .filter(|pack_id| **pack_id != winner.source_pack_id)
+            .filter_map(|pack_id| pack_map.get(pack_id).copied())
+            .filter(|donor| index.contains(donor, &output_mcmeta))
+            .collect();
+        if donors.is_empty() {
+            continue;
+        }
+        donors.sort_by_key(|donor| {
+            pack_order
+                .iter()
+                .position(|id| *id == donor.id)
+                .unwrap_or(usize::MAX)
+        });
+
+        if pairing == McmetaPairing::BorrowMatching {
+            let winner_size = texture_dimensions(pack, &winner.source_path);
+            let matching = donors.iter().find(|donor| {
+                winner_size.is_some()
+                    && texture_dimensions(donor, &winner.output_path) == winner_size
+            });
+            if let Some(donor) = matching {
+                entries.push(WinnerEntry {
+                    asset_id: winner.asset_id.clone(),
+                    source_pack_id: donor.id.clone(),
+                    source_path: output_mcmeta.clone(),
+                    output_path: output_mcmeta,
+                    source_is_zip: donor.is_zip,
+                    merged_content: None,
+                });
+                continue;
+            }
+        }
+
+        let donor_ids: Vec<&str> = donors.iter().map(|donor| donor.id.as_str()).collect();
+        let message = if pairing == McmetaPairing::BorrowMatching {
+            "build-mcmeta-not-carried-resized"
+        } else {
+            "build-mcmeta-not-carried"
+        };
+        warnings.push(tr(
+            message,
+            &[
+                ("asset", winner.asset_id.as_str().into()),
+                ("pack", winner.source_pack_id.as_str().into()),
+                ("donors", donor_ids.join(", ").into()),
+            ],
+        ));
+    }
+
+    (entries, warnings)
+}
+
+/// Pixel dimensions of a texture inside a pack
+fn texture_dimensions(pack: &PackMeta, relative_path: &str) -> Option<(u32, u32)> {
+    let bytes = pack_files::read_pack_entry(pack, relative_path).ok()?;
+    image::io::Reader::new(std::io::Cursor::new(bytes))
+        .with_guessed_format()
+        .ok()?
+        .into_dimensions()
+        .ok()
+}
+
+/// Nest directory holding carried-over overlay files for a format range
+fn overlay_directory(formats: &PackFormatRange) -> String {
+    format!("overlay_{}_{}", formats.min, formats.max)
+}
+
+/// Normalize every output path, leaving out files whose path would escape the
+/// nest (a crafted pack can name entries "../../..."); returns a warning for
+/// each one left out
+fn sanitize_output_paths(winners: &mut Vec<WinnerEntry>) -> Vec<String> {
+    let mut warnings = Vec::new();
+    winners.retain_mut(
+        |winner| match pack_files::safe_entry_path(&winner.output_path) {
+            Ok(path) if !path.is_empty() => {
+                winner.output_path = path;
+                true
+            }
+            _ => {
+                warnings.push(format!(
+                    "Left out {} from {}: its path leaves the pack",
+                    winner.output_path, winner.source_pack_id
+                ));
+                false
+            }
+        },
+    );
+    warnings
+}
 
-            Ok(())
-        })?;
+/// CREDITS.txt naming every pack the nest takes files from, in pack order
+///
+/// Vanilla and the packs built into the game need no credit; a nest made only
+/// from those gets no credits file.
+fn credits_entry(
+    winners: &[WinnerEntry],
+    pack_map: &HashMap<String, &PackMeta>,
+    pack_order: &[String],
+) -> Option<WinnerEntry> {
+    let sources: HashSet<&str> = winners.iter().map(|w| w.source_pack_id.as_str()).collect();
+    let packs: Vec<&PackMeta> = pack_order
+        .iter()
+        .filter(|id| sources.contains(id.as_str()) && !id.starts_with("minecraft:"))
+        .filter_map(|id| pack_map.get(id).copied())
+        .collect();
+    let first = packs.first()?;
+
+    Some(WinnerEntry {
+        asset_id: pack_credits::CREDITS_FILE.to_string(),
+        source_pack_id: first.id.clone(),
+        source_path: pack_credits::CREDITS_FILE.to_string(),
+        output_path: pack_credits::CREDITS_FILE.to_string(),
+        source_is_zip: false,
+        merged_content: Some(pack_credits::render_credits(&packs).into_bytes()),
+    })
+}
 
-    println!("[build_weaver_nest] Successfully copied all files");
+/// Render the nest's pack.mcmeta, including overlays and features sections when
+/// needed
+fn pack_mcmeta_json(
+    pack_format: u32,
+    overlays: &[PackFormatRange],
+    features: &[String],
+) -> Result<String> {
+    let mut mcmeta = serde_json::json!({
+        "pack": {
+            "pack_format": pack_format,
+            "description": PACK_DESCRIPTION,
+        }
+    });
+
+    if !overlays.is_empty() {
+        let entries: Vec<serde_json::Value> = overlays
+            .iter()
+            .map(|formats| {
+                serde_json::json!({
+                    "directory": overlay_directory(formats),
+                    "formats": [formats.min, formats.max],
+                })
+            })
+            .collect();
+        mcmeta["overlays"] = serde_json::json!({ "entries": entries });
+    }
+    if !features.is_empty() {
+        mcmeta["features"] = serde_json::json!({ "enabled": features });
+    }
+
+    let mut json = serde_json::to_string_pretty(&mcmeta)?;
+    json.push('\n');
+    Ok(json)
+}
+
+/// Fingerprint of a build's planned output: every output file with its source,
+/// plus the identity of each ZIP source, so a resumed build only trusts files
+/// written for the same plan
+fn plan_hash(winners: &[WinnerEntry], pack_map: &HashMap<String, &PackMeta>, mcmeta: &str) -> u64 {
+    let mut hasher = Xxh3::new();
+    hasher.update(mcmeta.as_bytes());
+    for winner in winners {
+        for part in [
+            &winner.output_path,
+            &winner.source_pack_id,
+            &winner.source_path,
+        ] {
+            hasher.update(part.as_bytes());
+            hasher.update(&[0]);
+        }
+        if let Some(content) = &winner.merged_content {
+            hasher.update(content);
+        }
+        hasher.update(&[0]);
+    }
+
+    let mut packs: Vec<&&PackMeta> = pack_map.values().collect();
+    packs.sort_by(|a, b| a.id.cmp(&b.id));
+    for pack in packs.into_iter().filter(|pack| pack.is_zip) {
+        hasher.update(pack.path.as_bytes());
+        if let Ok(metadata) = fs::metadata(&pack.path) {
+            hasher.update(&metadata.len().to_le_bytes());
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |since| since.as_nanos());
+            hasher.update(&modified.to_le_bytes());
+        }
+    }
+    hasher.digest()
+}
+
+/// Read the bytes of a winning asset from its source pack
+fn read_winner_content(
+    winner: &WinnerEntry,
+    pack_map: &HashMap<String, &PackMeta>,
+) -> Result<Vec<u8>> {
+    if let Some(content) = &winner.merged_content {
+        return Ok(content.clone());
+    }
+
+    let source_pack = pack_map
+        .get(&winner.source_pack_id)
+        .ok_or_else(|| anyhow!("Pack not found: {}", winner.source_pack_id))?;
+
+    if winner.source_is_zip {
+        zip::extract_zip_entry(&source_pack.path, &winner.source_path)
+    } else {
+        let full_path =
+            pack_files::resolve_dir_entry(Path::new(&source_pack.path), &winner.source_path)?;
+        Ok(fs::read(&full_path)?)
+    }
+}
+
+/// Remove winners whose content is byte-identical to `base`'s copy of the same
+/// output file
+///
+/// Generated files are always kept, as are the files of packs that can't be
+/// read.
+fn drop_base_duplicates(
+    winners: Vec<WinnerEntry>,
+    pack_map: &HashMap<String, &PackMeta>,
+    base: &PackMeta,
+) -> Vec<WinnerEntry> {
+    let candidates: Vec<&WinnerEntry> = winners
+        .iter()
+        .filter(|w| w.merged_content.is_none() && w.source_pack_id != base.id)
+        .collect();
+    let base_hashes = match asset_hashes::hash_pack_files(
+        base,
+        candidates.iter().map(|w| w.output_path.as_str()),
+    ) {
+        Ok(hashes) => hashes,
+        Err(e) => {
+            warn!("Can't read {}: {}", base.id, e);
+            return winners;
+        }
+    };
+
+    // Only files the base pack also has need hashing in their source pack
+    let mut by_pack: HashMap<String, Vec<String>> = HashMap::new();
+    for winner in candidates {
+        if base_hashes.contains_key(&winner.output_path) {
+            by_pack
+                .entry(winner.source_pack_id.clone())
+                .or_default()
+                .push(winner.source_path.clone());
+        }
+    }
+    let source_hashes: HashMap<String, HashMap<String, asset_hashes::ContentHash>> = by_pack
+        .into_par_iter()
+        .filter_map(|(pack_id, files)| {
+            let pack = pack_map.get(&pack_id)?;
+            asset_hashes::hash_pack_files(pack, files.iter().map(String::as_str))
+                .map_err(|e| warn!("Can't read {}: {}", pack_id, e))
+                .ok()
+                .map(|hashes| (pack_id, hashes))
+        })
+        .collect();
+
+    winners
+        .into_iter()
+        .filter(|w| {
+            let source_hash = source_hashes
+                .get(&w.source_pack_id)
+                .and_then(|hashes| hashes.get(&w.source_path));
+            w.merged_content.is_some()
+                || source_hash.is_none()
+                || source_hash != base_hashes.get(&w.output_path)
+        })
+        .collect()
+}
+
+/// Stream the bytes of a winning asset from its source pack into `out`
+fn copy_winner_content(
+    winner: &WinnerEntry,
+    pack_map: &HashMap<String, &PackMeta>,
+    out: &mut dyn Write,
+) -> Result<()> {
+    if let Some(content) = &winner.merged_content {
+        out.write_all(content)?;
+        return Ok(());
+    }
+
+    let source_pack = pack_map
+        .get(&winner.source_pack_id)
+        .ok_or_else(|| anyhow!("Pack not found: {}", winner.source_pack_id))?;
+
+    if winner.source_is_zip {
+        zip::copy_zip_entry(&source_pack.path, &winner.source_path, out)?;
+    } else {
+        let full_path =
+            pack_files::resolve_dir_entry(Path::new(&source_pack.path), &winner.source_path)?;
+        std::io::copy(&mut fs::File::open(&full_path)?, out)?;
+    }
     Ok(())
 }
 
-/// Create pack.mcmeta file
-fn create_pack_mcmeta(output_path: &Path) -> Result<()> {
-    let pack_mcmeta = r#"{
-  "pack": {
-    "pack_format": 48,
-    "description": "Weaverbird - Optimized Resource Pack"
-  }
+/// Map nest compression settings onto zip writer options
+fn zip_file_options(compression: &NestCompression) -> ::zip::write::FileOptions {
+    let (method, level) = match compression.method {
+        // Stored entries reject any level
+        CompressionMethod::Store => (::zip::CompressionMethod::Stored, None),
+        CompressionMethod::Deflate => (
+            ::zip::CompressionMethod::Deflated,
+            compression.level.map(i32::from),
+        ),
+        CompressionMethod::Zstd => (
+            ::zip::CompressionMethod::Zstd,
+            compression.level.map(i32::from),
+        ),
+    };
+
+    ::zip::write::FileOptions::default()
+        .compression_method(method)
+        .compression_level(level)
+        .last_modified_time(::zip::DateTime::default())
+        .unix_permissions(0o644)
 }
-"#;
 
+/// Reset a written file's modification time to NORMALIZED_MTIME_SECS
+fn normalize_mtime(path: &Path) -> Result<()> {
+    let mtime = std::time::UNIX_EPOCH + std::time::Duration::from_secs(NORMALIZED_MTIME_SECS);
+    fs::File::options()
+        .write(true)
+        .open(path)?
+        .set_modified(mtime)?;
+    Ok(())
+}
+
+/// Write the nest as a single zip archive
+///
+/// Sources are read in parallel; entries are then written sequentially since
+/// ZipWriter is not thread-safe. In low-memory mode each source is instead
+/// streamed into the archive as its entry is written.
+fn write_nest_zip(
+    winners: &[WinnerEntry],
+    pack_map: &HashMap<String, &PackMeta>,
+    mcmeta: &str,
+    zip_path: &Path,
+    compression: &NestCompression,
+) -> Result<()> {
+    let options = zip_file_options(compression);
+    let mut writer = ::zip::ZipWriter::new(fs::File::create(zip_path)?);
+
+    writer.start_file("pack.mcmeta", options)?;
+    writer.write_all(mcmeta.as_bytes())?;
+
+    let cancel = cancellation::current();
+    if low_memory::is_enabled() {
+        for winner in winners {
+            cancel.check()?;
+            writer.start_file(winner.output_path.as_str(), options)?;
+            copy_winner_content(winner, pack_map, &mut writer)?;
+        }
+    } else {
+        let contents: Vec<(&str, Vec<u8>)> = winners
+            .par_iter()
+            .map(|winner| {
+                cancel.check()?;
+                Ok((
+                    winner.output_path.as_str(),
+                    read_winner_content(winner, pack_map)?,
+                ))
+            })
+            .collect::<Result<_>>()?;
+
+        for (entry_path, content) in contents {
+            writer.start_file(entry_path, options)?;
+            writer.write_all(&content)?;
+        }
+    }
+
+    writer.finish()?;
+    Ok(())
+}
+
+/// Create pack.mcmeta file
+fn create_pack_mcmeta(output_path: &Path, mcmeta: &str) -> Result<()> {
     let mcmeta_path = output_path.join("pack.mcmeta");
-    fs::write(mcmeta_path, pack_mcmeta)?;
+    fs::write(&mcmeta_path, mcmeta)?;
+    normalize_mtime(&mcmeta_path)?;
 
     Ok(())
 }
@@ -139,9 +1583,1258 @@ fn create_pack_mcmeta(output_path: &Path) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::{McmetaPairing, PackOverlay};
 
     #[test]
     fn test_create_pack_mcmeta() {
         // Placeholder test
     }
+
+    #[test]
+    fn test_build_weaver_nest_zip_output() {
+        let temp_dir = std::env::temp_dir().join("test_weaver_nest_zip");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let pack_dir = temp_dir.join("pack");
+        let out_dir = temp_dir.join("out");
+        fs::create_dir_all(pack_dir.join("assets/minecraft/textures/block")).unwrap();
+        fs::write(
+            pack_dir.join("assets/minecraft/textures/block/stone.png"),
+            b"stone",
+        )
+        .unwrap();
+
+        let packs = vec![PackMeta {
+            id: "pack".to_string(),
+            name: "Pack".to_string(),
+            path: pack_dir.to_string_lossy().to_string(),
+            size: 0,
+            is_zip: false,
+            description: None,
+            has_icon: false,
+            pack_format: None,
+            ..Default::default()
+        }];
+        let assets = vec![AssetRecord {
+            id: "minecraft:block/stone".to_string(),
+            labels: vec![],
+            files: vec!["assets/minecraft/textures/block/stone.png".to_string()],
+        }];
+        let providers = HashMap::from([(
+            "minecraft:block/stone".to_string(),
+            vec!["pack".to_string()],
+        )]);
+        let options = NestBuildOptions {
+            compression: Some(NestCompression {
+                method: CompressionMethod::Store,
+                level: Some(5),
+            }),
+            ..Default::default()
+        };
+
+        let result = build_weaver_nest(
+            &packs,
+            &assets,
+            &providers,
+            &["pack".to_string()],
+            &HashMap::new(),
+            out_dir.to_str().unwrap(),
+            &options,
+        );
+        let entries = result
+            .as_ref()
+            .ok()
+            .and_then(|out| zip::list_zip_files(out.path.to_str().unwrap()).ok());
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(result.unwrap().path.file_name().unwrap(), NEST_ZIP_NAME);
+        let entries = entries.unwrap();
+        assert!(entries.contains(&"pack.mcmeta".to_string()));
+        assert!(entries.contains(&"assets/minecraft/textures/block/stone.png".to_string()));
+        assert!(entries.contains(&pack_credits::CREDITS_FILE.to_string()));
+    }
+
+    #[test]
+    fn test_build_weaver_nest_zip_is_reproducible() {
+        let temp_dir = std::env::temp_dir().join("test_weaver_nest_reproducible");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let pack_dir = temp_dir.join("pack");
+        fs::create_dir_all(pack_dir.join("assets/minecraft/textures/block")).unwrap();
+        let mut assets = Vec::new();
+        let mut providers = HashMap::new();
+        for name in ["stone", "dirt", "andesite"] {
+            let file = format!("assets/minecraft/textures/block/{}.png", name);
+            fs::write(pack_dir.join(&file), name.as_bytes()).unwrap();
+            let id = format!("minecraft:block/{}", name);
+            providers.insert(id.clone(), vec!["pack".to_string()]);
+            assets.push(AssetRecord {
+                id,
+                labels: vec![],
+                files: vec![file],
+            });
+        }
+
+        let packs = vec![PackMeta {
+            id: "pack".to_string(),
+            name: "Pack".to_string(),
+            path: pack_dir.to_string_lossy().to_string(),
+            size: 0,
+            is_zip: false,
+            description: None,
+            has_icon: false,
+            pack_format: None,
+            ..Default::default()
+        }];
+        let options = NestBuildOptions {
+            compression: Some(NestCompression::default()),
+            ..Default::default()
+        };
+
+        let build = |out: &str, assets: &[AssetRecord]| {
+            let out_dir = temp_dir.join(out);
+            build_weaver_nest(
+                &packs,
+                assets,
+                &providers,
+                &["pack".to_string()],
+                &HashMap::new(),
+                out_dir.to_str().unwrap(),
+                &options,
+            )
+            .and_then(|out| Ok(fs::read(out.path)?))
+        };
+        let first = build("out_a", &assets);
+        assets.reverse();
+        let second = build("out_b", &assets);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(first.unwrap(), second.unwrap());
+    }
+
+    fn overlay_fixture(
+        temp_dir: &Path,
+    ) -> (
+        Vec<PackMeta>,
+        Vec<AssetRecord>,
+        HashMap<String, Vec<String>>,
+    ) {
+        let pack_dir = temp_dir.join("pack");
+        let file = "assets/minecraft/textures/block/stone.png";
+        for (dir, content) in [("", "base"), ("old/", "old"), ("new/", "new")] {
+            let path = pack_dir.join(format!("{}{}", dir, file));
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        // Only the newer overlay has this one
+        let moss = pack_dir.join("new/assets/minecraft/textures/block/moss_block.png");
+        fs::write(moss, "new moss").unwrap();
+
+        let packs = vec![PackMeta {
+            id: "pack".to_string(),
+            name: "Pack".to_string(),
+            path: pack_dir.to_string_lossy().to_string(),
+            size: 0,
+            is_zip: false,
+            overlays: vec![
+                PackOverlay {
+                    directory: "old".to_string(),
+                    formats: PackFormatRange { min: 18, max: 22 },
+                },
+                PackOverlay {
+                    directory: "new".to_string(),
+                    formats: PackFormatRange { min: 32, max: 48 },
+                },
+            ],
+            ..Default::default()
+        }];
+        let assets = vec![AssetRecord {
+            id: "minecraft:block/stone".to_string(),
+            labels: vec![],
+            files: vec![file.to_string()],
+        }];
+        let providers = HashMap::from([(
+            "minecraft:block/stone".to_string(),
+            vec!["pack".to_string()],
+        )]);
+        (packs, assets, providers)
+    }
+
+    #[test]
+    fn test_build_weaver_nest_flattens_overlay_for_target() {
+        let temp_dir = std::env::temp_dir().join("test_weaver_nest_overlay_flatten");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let (packs, assets, providers) = overlay_fixture(&temp_dir);
+        let out_dir = temp_dir.join("out");
+
+        let build = |target: u32| {
+            let _ = fs::remove_dir_all(&out_dir);
+            build_weaver_nest(
+                &packs,
+                &assets,
+                &providers,
+                &["pack".to_string()],
+                &HashMap::new(),
+                out_dir.to_str().unwrap(),
+                &NestBuildOptions {
+                    target_pack_format: Some(target),
+                    ..Default::default()
+                },
+            )
+            .and_then(|out| {
+                let read = |file: &str| fs::read_to_string(out.path.join(file)).ok();
+                Ok((
+                    read("assets/minecraft/textures/block/stone.png"),
+                    read("assets/minecraft/textures/block/moss_block.png"),
+                ))
+            })
+        };
+        let modern = build(34);
+        let unmatched = build(26);
+        let mcmeta = fs::read_to_string(out_dir.join("pack.mcmeta"));
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let (stone, moss) = modern.unwrap();
+        assert_eq!(stone.as_deref(), Some("new"));
+        assert_eq!(moss.as_deref(), Some("new moss"));
+        let (stone, moss) = unmatched.unwrap();
+        assert_eq!(stone.as_deref(), Some("base"));
+        assert_eq!(moss, None);
+        let mcmeta: serde_json::Value = serde_json::from_str(&mcmeta.unwrap()).unwrap();
+        assert_eq!(mcmeta["pack"]["pack_format"], 26);
+        assert!(mcmeta.get("overlays").is_none());
+    }
+
+    #[test]
+    fn test_build_weaver_nest_carries_overlays() {
+        let temp_dir = std::env::temp_dir().join("test_weaver_nest_overlay_carry");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let (packs, assets, providers) = overlay_fixture(&temp_dir);
+        let out_dir = temp_dir.join("out");
+
+        let result = build_weaver_nest(
+            &packs,
+            &assets,
+            &providers,
+            &["pack".to_string()],
+            &HashMap::new(),
+            out_dir.to_str().unwrap(),
+            &NestBuildOptions::default(),
+        );
+        let read = |path: &str| fs::read_to_string(out_dir.join(path)).ok();
+        let base = read("assets/minecraft/textures/block/stone.png");
+        let old = read("overlay_18_22/assets/minecraft/textures/block/stone.png");
+        let new = read("overlay_32_48/assets/minecraft/textures/block/stone.png");
+        let moss = read("overlay_32_48/assets/minecraft/textures/block/moss_block.png");
+        let base_moss = read("assets/minecraft/textures/block/moss_block.png");
+        let mcmeta = read("pack.mcmeta");
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert!(result.is_ok());
+        assert_eq!(base.as_deref(), Some("base"));
+        assert_eq!(old.as_deref(), Some("old"));
+        assert_eq!(new.as_deref(), Some("new"));
+        assert_eq!(moss.as_deref(), Some("new moss"));
+        assert_eq!(base_moss, None);
+        let mcmeta: serde_json::Value = serde_json::from_str(&mcmeta.unwrap()).unwrap();
+        assert_eq!(mcmeta["pack"]["pack_format"], DEFAULT_PACK_FORMAT);
+        assert_eq!(
+            mcmeta["overlays"]["entries"],
+            serde_json::json!([
+                {"directory": "overlay_18_22", "formats": [18, 22]},
+                {"directory": "overlay_32_48", "formats": [32, 48]},
+            ])
+        );
+    }
+
+    #[test]
+    fn test_build_weaver_nest_overlay_only_file_falls_through() {
+        let temp_dir = std::env::temp_dir().join("test_weaver_nest_overlay_only_fallthrough");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let moss = "assets/minecraft/textures/block/moss_block.png";
+        let pack = |id: &str, overlay: PackOverlay| {
+            let path = temp_dir.join(id).join(&overlay.directory).join(moss);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, format!("{} moss", id)).unwrap();
+            PackMeta {
+                id: id.to_string(),
+                name: id.to_string(),
+                path: temp_dir.join(id).to_string_lossy().to_string(),
+                is_zip: false,
+                overlays: vec![overlay],
+                ..Default::default()
+            }
+        };
+        let packs = vec![
+            pack(
+                "top",
+                PackOverlay {
+                    directory: "old".to_string(),
+                    formats: PackFormatRange { min: 18, max: 22 },
+                },
+            ),
+            pack(
+                "low",
+                PackOverlay {
+                    directory: "new".to_string(),
+                    formats: PackFormatRange { min: 32, max: 48 },
+                },
+            ),
+        ];
+        let pack_order = ["top".to_string(), "low".to_string()];
+        let out_dir = temp_dir.join("out");
+        let build = |target: Option<u32>, overrides: &HashMap<String, OverrideSelection>| {
+            let _ = fs::remove_dir_all(&out_dir);
+            build_weaver_nest(
+                &packs,
+                &[],
+                &HashMap::new(),
+                &pack_order,
+                overrides,
+                out_dir.to_str().unwrap(),
+                &NestBuildOptions {
+                    target_pack_format: target,
+                    ..Default::default()
+                },
+            )
+            .map(|_| {
+                let read = |path: String| fs::read_to_string(out_dir.join(path)).ok();
+                (
+                    read(moss.to_string()),
+                    read(format!("overlay_18_22/{}", moss)),
+                    read(format!("overlay_32_48/{}", moss)),
+                )
+            })
+        };
+        let targeted = build(Some(34), &HashMap::new());
+        let overridden = build(
+            None,
+            &HashMap::from([(
+                "minecraft:block/moss_block".to_string(),
+                OverrideSelection {
+                    pack_id: "low".to_string(),
+                    variant_path: None,
+                },
+            )]),
+        );
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        // The top pack's overlay doesn't apply to format 34
+        assert_eq!(
+            targeted.unwrap(),
+            (Some("low moss".to_string()), None, None)
+        );
+        assert_eq!(
+            overridden.unwrap(),
+            (None, None, Some("low moss".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_build_weaver_nest_dedupes_against_vanilla() {
+        let temp_dir = std::env::temp_dir().join("test_weaver_nest_dedupes_against_vanilla");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let stone = "assets/minecraft/textures/block/stone.png";
+        let dirt = "assets/minecraft/textures/block/dirt.png";
+        let write_pack = |id: &str, files: &[(&str, &str)]| {
+            let pack_dir = temp_dir.join(id);
+            for (file, content) in files {
+                let path = pack_dir.join(file);
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(path, content).unwrap();
+            }
+            PackMeta {
+                id: id.to_string(),
+                name: id.to_string(),
+                path: pack_dir.to_string_lossy().to_string(),
+                ..Default::default()
+            }
+        };
+        let vanilla = write_pack("vanilla", &[(stone, "stone"), (dirt, "dirt")]);
+        let packs = vec![write_pack("pack", &[(stone, "stone"), (dirt, "new dirt")])];
+        let assets: Vec<AssetRecord> = [("stone", stone), ("dirt", dirt)]
+            .iter()
+            .map(|(name, file)| AssetRecord {
+                id: format!("minecraft:block/{}", name),
+                labels: vec![],
+                files: vec![file.to_string()],
+            })
+            .collect();
+        let providers: HashMap<String, Vec<String>> = assets
+            .iter()
+            .map(|asset| (asset.id.clone(), vec!["pack".to_string()]))
+            .collect();
+
+        let out_dir = temp_dir.join("out");
+        let result = build_weaver_nest(
+            &packs,
+            &assets,
+            &providers,
+            &["pack".to_string()],
+            &HashMap::new(),
+            out_dir.to_str().unwrap(),
+            &NestBuildOptions {
+                dedupe_against: Some(vanilla),
+                ..Default::default()
+            },
+        );
+        let stone_written = out_dir.join(stone).exists();
+        let dirt_written = fs::read_to_string(out_dir.join(dirt));
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert!(result.is_ok());
+        assert!(!stone_written);
+        assert_eq!(dirt_written.unwrap(), "new dirt");
+    }
+
+    #[test]
+    fn test_build_weaver_nest_prefers_highest_resolution() {
+        let temp_dir = std::env::temp_dir().join("test_weaver_nest_prefers_highest_resolution");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let file = "assets/minecraft/textures/block/stone.png";
+        let packs: Vec<PackMeta> = [("low", 16), ("high", 64)]
+            .iter()
+            .map(|(id, size)| {
+                let path = temp_dir.join(id).join(file);
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                image::RgbaImage::new(*size, *size).save(&path).unwrap();
+                PackMeta {
+                    id: id.to_string(),
+                    name: id.to_string(),
+                    path: temp_dir.join(id).to_string_lossy().to_string(),
+                    ..Default::default()
+                }
+            })
+            .collect();
+        let assets = vec![AssetRecord {
+            id: "minecraft:block/stone".to_string(),
+            labels: vec![],
+            files: vec![file.to_string()],
+        }];
+        let providers = HashMap::from([(
+            "minecraft:block/stone".to_string(),
+            vec!["low".to_string(), "high".to_string()],
+        )]);
+        let pack_order = vec!["low".to_string(), "high".to_string()];
+        let texture_info =
+            crate::util::texture_info::read_texture_info(&packs, &assets, &providers);
+
+        let built_resolution = |name: &str, options: NestBuildOptions| {
+            let out_dir = temp_dir.join(name);
+            build_weaver_nest(
+                &packs,
+                &assets,
+                &providers,
+                &pack_order,
+                &HashMap::new(),
+                out_dir.to_str().unwrap(),
+                &options,
+            )
+            .unwrap();
+            let mut output = fs::File::open(out_dir.join(file)).unwrap();
+            crate::util::texture_info::read_png_header(&mut output)
+                .unwrap()
+                .resolution()
+        };
+        let by_order = built_resolution("by_order", NestBuildOptions::default());
+        let by_resolution = built_resolution(
+            "by_resolution",
+            NestBuildOptions {
+                prefer_highest_resolution: Some(texture_info),
+                ..Default::default()
+            },
+        );
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(by_order, 16);
+        assert_eq!(by_resolution, 64);
+    }
+
+    #[test]
+    fn test_build_weaver_nest_mcmeta_pairing() {
+        let temp_dir = std::env::temp_dir().join("test_weaver_nest_mcmeta_pairing");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let file = "assets/minecraft/textures/block/magma.png";
+        let mut packs = Vec::new();
+        // "top" has a static texture; "animated" ships the same size plus .mcmeta;
+        // "wide" has .mcmeta for a differently sized texture
+        for (id, size, mcmeta) in [
+            ("top", 16, false),
+            ("animated", 16, true),
+            ("wide", 32, true),
+        ] {
+            let pack_dir = temp_dir.join(id);
+            let path = pack_dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            image::RgbaImage::new(size, size).save(&path).unwrap();
+            if mcmeta {
+                fs::write(
+                    pack_dir.join(format!("{}.mcmeta", file)),
+                    format!(r#"{{"animation": {{"frametime": {}}}}}"#, size),
+                )
+                .unwrap();
+            }
+            packs.push(PackMeta {
+                id: id.to_string(),
+                name: id.to_string(),
+                path: pack_dir.to_string_lossy().to_string(),
+                ..Default::default()
+            });
+        }
+        let assets = vec![AssetRecord {
+            id: "minecraft:block/magma".to_string(),
+            labels: vec![],
+            files: vec![file.to_string()],
+        }];
+        let providers = HashMap::from([(
+            "minecraft:block/magma".to_string(),
+            vec![
+                "wide".to_string(),
+                "animated".to_string(),
+                "top".to_string(),
+            ],
+        )]);
+
+        let build = |out: &str, order: &[&str], pairing: McmetaPairing| {
+            let out_dir = temp_dir.join(out);
+            let order: Vec<String> = order.iter().map(|id| id.to_string()).collect();
+            build_weaver_nest(
+                &packs,
+                &assets,
+                &providers,
+                &order,
+                &HashMap::new(),
+                out_dir.to_str().unwrap(),
+                &NestBuildOptions {
+                    mcmeta_pairing: pairing,
+                    ..Default::default()
+                },
+            )
+            .map(|out| {
+                let mcmeta = fs::read_to_string(out.path.join(format!("{}.mcmeta", file))).ok();
+                (mcmeta, out.warnings)
+            })
+        };
+        let same_pack = build(
+            "same",
+            &["top", "wide", "animated"],
+            McmetaPairing::SamePack,
+        );
+        let borrowed = build(
+            "borrow",
+            &["top", "wide", "animated"],
+            McmetaPairing::BorrowMatching,
+        );
+        let own = build("own", &["wide", "top", "animated"], McmetaPairing::SamePack);
+        let stripped = build("strip", &["wide", "top", "animated"], McmetaPairing::Strip);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let (mcmeta, warnings) = same_pack.unwrap();
+        assert_eq!(mcmeta, None);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("wide, animated"));
+
+        let (mcmeta, warnings) = borrowed.unwrap();
+        assert_eq!(
+            mcmeta.as_deref(),
+            Some(r#"{"animation": {"frametime": 16}}"#)
+        );
+        assert!(warnings.is_empty());
+
+        let (mcmeta, warnings) = own.unwrap();
+        assert_eq!(
+            mcmeta.as_deref(),
+            Some(r#"{"animation": {"frametime": 32}}"#)
+        );
+        assert!(warnings.is_empty());
+
+        let (mcmeta, warnings) = stripped.unwrap();
+        assert_eq!(mcmeta, None);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_build_weaver_nest_merges_sounds_json() {
+        let temp_dir = std::env::temp_dir().join("test_weaver_nest_sounds_merge");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let file = "assets/minecraft/sounds.json";
+        let mut packs = Vec::new();
+        for (id, sounds) in [
+            (
+                "low",
+                r#"{"ambient.cave": {"sounds": ["low/cave"]}, "low.only": {"sounds": ["low/x"]}}"#,
+            ),
+            ("high", r#"{"ambient.cave": {"sounds": ["high/cave"]}}"#),
+        ] {
+            let pack_dir = temp_dir.join(id);
+            fs::create_dir_all(pack_dir.join("assets/minecraft")).unwrap();
+            fs::write(pack_dir.join(file), sounds).unwrap();
+            packs.push(PackMeta {
+                id: id.to_string(),
+                name: id.to_string(),
+                path: pack_dir.to_string_lossy().to_string(),
+                ..Default::default()
+            });
+        }
+        let asset_id = "minecraft:sounds.json".to_string();
+        let assets = vec![AssetRecord {
+            id: asset_id.clone(),
+            labels: vec![],
+            files: vec![file.to_string()],
+        }];
+        let providers = HashMap::from([(
+            asset_id.clone(),
+            vec!["low".to_string(), "high".to_string()],
+        )]);
+        let order = vec!["high".to_string(), "low".to_string()];
+
+        let build = |out: &str, overrides: &HashMap<String, OverrideSelection>| {
+            let out_dir = temp_dir.join(out);
+            build_weaver_nest(
+                &packs,
+                &assets,
+                &providers,
+                &order,
+                overrides,
+                out_dir.to_str().unwrap(),
+                &NestBuildOptions::default(),
+            )
+            .map(|out| fs::read_to_string(out.path.join(file)).unwrap())
+        };
+        let merged = build("merged", &HashMap::new());
+        let overridden = build(
+            "overridden",
+            &HashMap::from([(
+                asset_id.clone(),
+                OverrideSelection {
+                    pack_id: "high".to_string(),
+                    variant_path: None,
+                },
+            )]),
+        );
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let merged: serde_json::Value = serde_json::from_str(&merged.unwrap()).unwrap();
+        assert_eq!(
+            merged["ambient.cave"]["sounds"],
+            serde_json::json!(["low/cave", "high/cave"])
+        );
+        assert_eq!(merged["low.only"]["sounds"], serde_json::json!(["low/x"]));
+        assert!(!overridden.unwrap().contains("low.only"));
+    }
+
+    #[test]
+    fn test_build_weaver_nest_merges_overlay_sounds_json() {
+        let temp_dir = std::env::temp_dir().join("test_weaver_nest_sounds_overlays");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let file = "assets/minecraft/sounds.json";
+        let mut packs = Vec::new();
+        for (id, overlay, formats) in [
+            ("low", "new", PackFormatRange { min: 32, max: 48 }),
+            ("high", "old", PackFormatRange { min: 18, max: 22 }),
+        ] {
+            let pack_dir = temp_dir.join(id);
+            for (dir, sound) in [("", "cave"), (overlay, overlay)] {
+                let path = pack_dir.join(dir).join(file);
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                let sounds = format!(r#"{{"ambient.cave": {{"sounds": ["{}/{}"]}}}}"#, id, sound);
+                fs::write(path, sounds).unwrap();
+            }
+            packs.push(PackMeta {
+                id: id.to_string(),
+                name: id.to_string(),
+                path: pack_dir.to_string_lossy().to_string(),
+                overlays: vec![PackOverlay {
+                    directory: overlay.to_string(),
+                    formats,
+                }],
+                ..Default::default()
+            });
+        }
+        let asset_id = "minecraft:sounds.json".to_string();
+        let assets = vec![AssetRecord {
+            id: asset_id.clone(),
+            labels: vec![],
+            files: vec![file.to_string()],
+        }];
+        let providers = HashMap::from([(
+            asset_id.clone(),
+            vec!["low".to_string(), "high".to_string()],
+        )]);
+        let order = vec!["high".to_string(), "low".to_string()];
+
+        let build = |out: &str, target_pack_format: Option<u32>| {
+            let out_dir = temp_dir.join(out);
+            build_weaver_nest(
+                &packs,
+                &assets,
+                &providers,
+                &order,
+                &HashMap::new(),
+                out_dir.to_str().unwrap(),
+                &NestBuildOptions {
+                    target_pack_format,
+                    ..Default::default()
+                },
+            )
+            .map(|out| {
+                let read = |path: &str| {
+                    fs::read_to_string(out.path.join(path))
+                        .ok()
+                        .map(|json| serde_json::from_str::<serde_json::Value>(&json).unwrap())
+                        .map(|sounds| sounds["ambient.cave"]["sounds"].clone())
+                };
+                (
+                    read(file),
+                    read(&format!("overlay_18_22/{}", file)),
+                    read(&format!("overlay_32_48/{}", file)),
+                    fs::read_to_string(out.path.join("pack.mcmeta")).unwrap(),
+                )
+            })
+        };
+        let modern = build("modern", Some(34));
+        let old = build("old", Some(20));
+        let carried = build("carried", None);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let (modern, ..) = modern.unwrap();
+        assert_eq!(modern, Some(serde_json::json!(["low/new", "high/cave"])));
+        let (old, ..) = old.unwrap();
+        assert_eq!(old, Some(serde_json::json!(["low/cave", "high/old"])));
+        let (base, overlay_old, overlay_new, mcmeta) = carried.unwrap();
+        assert_eq!(base, Some(serde_json::json!(["low/cave", "high/cave"])));
+        assert_eq!(
+            overlay_old,
+            Some(serde_json::json!(["low/cave", "high/old"]))
+        );
+        assert_eq!(
+            overlay_new,
+            Some(serde_json::json!(["low/new", "high/cave"]))
+        );
+        let mcmeta: serde_json::Value = serde_json::from_str(&mcmeta).unwrap();
+        assert_eq!(
+            mcmeta["overlays"]["entries"],
+            serde_json::json!([
+                {"directory": "overlay_18_22", "formats": [18, 22]},
+                {"directory": "overlay_32_48", "formats": [32, 48]},
+            ])
+        );
+    }
+
+    #[test]
+    fn test_stacked_file_for_asset() {
+        assert_eq!(
+            StackedFile::for_asset("minecraft:sounds.json"),
+            Some(StackedFile::Sounds)
+        );
+        assert_eq!(
+            StackedFile::for_asset("mymod:lang/en_us.json"),
+            Some(StackedFile::Lang)
+        );
+        assert_eq!(
+            StackedFile::for_asset("minecraft:atlases/blocks.json"),
+            Some(StackedFile::Atlas)
+        );
+        assert_eq!(StackedFile::for_asset("minecraft:block/stone"), None);
+    }
+
+    #[test]
+    fn test_sanitize_output_paths() {
+        let winner = |output_path: &str| WinnerEntry {
+            asset_id: "minecraft:block/stone".to_string(),
+            source_pack_id: "evil".to_string(),
+            source_path: output_path.to_string(),
+            output_path: output_path.to_string(),
+            source_is_zip: true,
+            merged_content: None,
+        };
+        let mut winners = vec![
+            winner("assets\\minecraft/textures/block/stone.png"),
+            winner("assets/../../.bashrc"),
+            winner("/tmp/evil.png"),
+        ];
+
+        let warnings = sanitize_output_paths(&mut winners);
+        assert_eq!(winners.len(), 1);
+        assert_eq!(
+            winners[0].output_path,
+            "assets/minecraft/textures/block/stone.png"
+        );
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("assets/../../.bashrc"));
+    }
+
+    #[test]
+    fn test_build_weaver_nest_merges_lang_files() {
+        let temp_dir = std::env::temp_dir().join("test_weaver_nest_lang_merge");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let file = "assets/minecraft/lang/en_us.json";
+        let mut packs = Vec::new();
+        for (id, lang) in [
+            (
+                "low",
+                r#"{"item.minecraft.stick": "Twig", "low.only": "Low"}"#,
+            ),
+            ("high", r#"{"item.minecraft.stick": "Branch"}"#),
+        ] {
+            let pack_dir = temp_dir.join(id);
+            fs::create_dir_all(pack_dir.join("assets/minecraft/lang")).unwrap();
+            fs::write(pack_dir.join(file), lang).unwrap();
+            packs.push(PackMeta {
+                id: id.to_string(),
+                name: id.to_string(),
+                path: pack_dir.to_string_lossy().to_string(),
+                ..Default::default()
+            });
+        }
+        // From 1.21 the top pack goes back to the lower pack's name
+        let overlay_lang = temp_dir.join("high/new").join(file);
+        fs::create_dir_all(overlay_lang.parent().unwrap()).unwrap();
+        fs::write(
+            &overlay_lang,
+            r#"{"item.minecraft.stick": "Twig", "overlay.only": "Overlay"}"#,
+        )
+        .unwrap();
+        packs[1].overlays = vec![PackOverlay {
+            directory: "new".to_string(),
+            formats: PackFormatRange { min: 34, max: 48 },
+        }];
+        let asset_id = "minecraft:lang/en_us.json".to_string();
+        let assets = vec![AssetRecord {
+            id: asset_id.clone(),
+            labels: vec![],
+            files: vec![file.to_string()],
+        }];
+        let providers = HashMap::from([(
+            asset_id.clone(),
+            vec!["low".to_string(), "high".to_string()],
+        )]);
+        let order = vec!["high".to_string(), "low".to_string()];
+
+        let build = |out: &str, target_pack_format: Option<u32>| {
+            build_weaver_nest(
+                &packs,
+                &assets,
+                &providers,
+                &order,
+                &HashMap::new(),
+                temp_dir.join(out).to_str().unwrap(),
+                &NestBuildOptions {
+                    target_pack_format,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+        };
+        let output = build("out", None);
+        let merged = fs::read_to_string(output.path.join(file)).unwrap();
+        let modern = build("modern", Some(34));
+        let modern_merged = fs::read_to_string(modern.path.join(file)).unwrap();
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let merged: serde_json::Value = serde_json::from_str(&merged).unwrap();
+        assert_eq!(merged["item.minecraft.stick"], "Branch");
+        assert_eq!(merged["low.only"], "Low");
+        assert_eq!(output.lang_reports.len(), 1);
+        assert_eq!(output.lang_reports[0].file, file);
+        assert_eq!(
+            output.lang_reports[0].overrides[0].key,
+            "item.minecraft.stick"
+        );
+        assert_eq!(output.lang_reports[0].overrides[0].winner, "high");
+        assert_eq!(output.lang_reports[0].overrides[0].overridden, vec!["low"]);
+        let modern_merged: serde_json::Value = serde_json::from_str(&modern_merged).unwrap();
+        assert_eq!(modern_merged["item.minecraft.stick"], "Twig");
+        assert_eq!(modern_merged["overlay.only"], "Overlay");
+        assert_eq!(modern_merged["low.only"], "Low");
+        // Both packs name the stick the same for this target
+        assert!(modern.lang_reports.is_empty());
+    }
+
+    #[test]
+    fn test_build_weaver_nest_copies_cit_textures() {
+        let temp_dir = std::env::temp_dir().join("test_weaver_nest_cit");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let pack_dir = temp_dir.join("cit_pack");
+        let cit_dir = pack_dir.join("assets/minecraft/optifine/cit/swords");
+        fs::create_dir_all(&cit_dir).unwrap();
+        fs::write(pack_dir.join("pack.mcmeta"), "{}").unwrap();
+        fs::write(
+            cit_dir.join("fire.properties"),
+            "items=diamond_sword\ntexture=fire_sword\nmodel=./fire_model\n",
+        )
+        .unwrap();
+        fs::write(cit_dir.join("fire_sword.png"), b"png").unwrap();
+
+        let packs = vec![PackMeta {
+            id: "cit_pack".to_string(),
+            name: "CIT Pack".to_string(),
+            path: pack_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        }];
+        let (assets, providers) = crate::util::asset_indexer::index_assets(&packs).unwrap();
+        let output = build_weaver_nest(
+            &packs,
+            &assets,
+            &providers,
+            &["cit_pack".to_string()],
+            &HashMap::new(),
+            temp_dir.join("out").to_str().unwrap(),
+            &NestBuildOptions::default(),
+        )
+        .unwrap();
+        let out_cit = output.path.join("assets/minecraft/optifine/cit/swords");
+        let properties_copied = out_cit.join("fire.properties").is_file();
+        let texture_copied = out_cit.join("fire_sword.png").is_file();
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert!(properties_copied);
+        assert!(texture_copied);
+        assert_eq!(output.warnings.len(), 1);
+        assert!(output.warnings[0].contains("fire_model.json"));
+    }
+
+    #[test]
+    fn test_build_weaver_nest_keeps_ctm_per_block() {
+        let temp_dir = std::env::temp_dir().join("test_weaver_nest_ctm");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let mut packs = Vec::new();
+        for (id, rule_file, tiles) in [
+            (
+                "high",
+                "glass/glass.properties",
+                vec!["glass/0.png", "glass/1.png"],
+            ),
+            (
+                "low",
+                "clear/clear_glass.properties",
+                vec!["clear/0.png", "clear/1.png"],
+            ),
+        ] {
+            let pack_dir = temp_dir.join(id);
+            let ctm_dir = pack_dir.join("assets/minecraft/optifine/ctm");
+            fs::create_dir_all(ctm_dir.join(rule_file).parent().unwrap()).unwrap();
+            fs::write(pack_dir.join("pack.mcmeta"), "{}").unwrap();
+            fs::write(
+                ctm_dir.join(rule_file),
+                "method=ctm\ntiles=0-1\nmatchBlocks=glass\n",
+            )
+            .unwrap();
+            for tile in tiles {
+                fs::write(ctm_dir.join(tile), b"png").unwrap();
+            }
+            packs.push(PackMeta {
+                id: id.to_string(),
+                name: id.to_string(),
+                path: pack_dir.to_string_lossy().to_string(),
+                ..Default::default()
+            });
+        }
+        let (assets, providers) = crate::util::asset_indexer::index_assets(&packs).unwrap();
+        let conflicts = ctm::find_ctm_conflicts(&providers);
+
+        let output = build_weaver_nest(
+            &packs,
+            &assets,
+            &providers,
+            &["high".to_string(), "low".to_string()],
+            &HashMap::new(),
+            temp_dir.join("out").to_str().unwrap(),
+            &NestBuildOptions::default(),
+        )
+        .unwrap();
+        let out_ctm = output.path.join("assets/minecraft/optifine/ctm");
+        let high_copied = ["glass/glass.properties", "glass/0.png", "glass/1.png"]
+            .iter()
+            .all(|file| out_ctm.join(file).is_file());
+        let low_copied = out_ctm.join("clear").exists();
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].asset_id, "minecraft:optifine/ctm/block/glass");
+        assert!(high_copied);
+        assert!(!low_copied);
+        assert!(output.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_build_weaver_nest_keeps_emissive_with_base() {
+        let temp_dir = std::env::temp_dir().join("test_weaver_nest_emissive");
+        let _ = fs::remove_dir_all(&temp_dir);
+        // "high" overrides stone without a glow layer and ships lone glow layers
+        // for dirt (base in vanilla), ore (base in "low") and moss (no base);
+        // "low" has glowing stone and ore, using a custom suffix
+        let mut packs = Vec::new();
+        for (id, files, suffix) in [
+            (
+                "high",
+                vec!["stone.png", "dirt_e.png", "ore_e.png", "moss_e.png"],
+                None,
+            ),
+            (
+                "low",
+                vec!["stone.png", "stone_glow.png", "ore.png", "ore_glow.png"],
+                Some("_glow"),
+            ),
+            ("vanilla", vec!["dirt.png"], None),
+        ] {
+            let pack_dir = temp_dir.join(id);
+            let block_dir = pack_dir.join("assets/minecraft/textures/block");
+            fs::create_dir_all(&block_dir).unwrap();
+            fs::write(pack_dir.join("pack.mcmeta"), "{}").unwrap();
+            if let Some(suffix) = suffix {
+                fs::create_dir_all(pack_dir.join("assets/minecraft/optifine")).unwrap();
+                fs::write(
+                    pack_dir.join(emissive::EMISSIVE_PROPERTIES),
+                    format!("suffix.emissive={}\n", suffix),
+                )
+                .unwrap();
+            }
+            for file in files {
+                fs::write(block_dir.join(file), format!("{}/{}", id, file)).unwrap();
+            }
+            packs.push(PackMeta {
+                id: id.to_string(),
+                name: id.to_string(),
+                path: pack_dir.to_string_lossy().to_string(),
+                ..Default::default()
+            });
+        }
+        let vanilla = packs.pop();
+        let (assets, providers) = crate::util::asset_indexer::index_assets(&packs).unwrap();
+
+        let output = build_weaver_nest(
+            &packs,
+            &assets,
+            &providers,
+            &["high".to_string(), "low".to_string()],
+            &HashMap::new(),
+            temp_dir.join("out").to_str().unwrap(),
+            &NestBuildOptions {
+                vanilla,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let block_dir = output.path.join("assets/minecraft/textures/block");
+        let read = |name: &str| fs::read_to_string(block_dir.join(name)).ok();
+        let stone = read("stone.png");
+        let stone_glow = read("stone_e.png");
+        let ore = read("ore.png");
+        let ore_glow = read("ore_e.png");
+        let dirt_glow = read("dirt_e.png");
+        let moss_glow = read("moss_e.png");
+        let properties = fs::read_to_string(output.path.join(emissive::EMISSIVE_PROPERTIES));
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(stone.as_deref(), Some("high/stone.png"));
+        assert_eq!(stone_glow, None);
+        assert_eq!(ore.as_deref(), Some("low/ore.png"));
+        assert_eq!(ore_glow.as_deref(), Some("high/ore_e.png"));
+        assert_eq!(dirt_glow.as_deref(), Some("high/dirt_e.png"));
+        assert_eq!(moss_glow.as_deref(), Some("high/moss_e.png"));
+        assert_eq!(properties.unwrap(), "suffix.emissive=_e\n");
+        assert_eq!(output.warnings.len(), 1);
+        assert!(output.warnings[0].contains("moss_e.png"));
+    }
+
+    #[test]
+    fn test_build_weaver_nest_keeps_random_set_together() {
+        let temp_dir = std::env::temp_dir().join("test_weaver_nest_random");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let mut packs = Vec::new();
+        for (id, files) in [
+            (
+                "high",
+                vec![
+                    "optifine/random/entity/creeper/creeper2.png",
+                    "optifine/random/entity/creeper/creeper.properties",
+                ],
+            ),
+            (
+                "low",
+                vec![
+                    "optifine/mob/creeper/creeper2.png",
+                    "optifine/mob/creeper/creeper3.png",
+                ],
+            ),
+        ] {
+            let pack_dir = temp_dir.join(id);
+            fs::create_dir_all(&pack_dir).unwrap();
+            fs::write(pack_dir.join("pack.mcmeta"), "{}").unwrap();
+            for file in files {
+                let path = pack_dir.join("assets/minecraft").join(file);
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(path, id).unwrap();
+            }
+            packs.push(PackMeta {
+                id: id.to_string(),
+                name: id.to_string(),
+                path: pack_dir.to_string_lossy().to_string(),
+                ..Default::default()
+            });
+        }
+        let (assets, providers) = crate::util::asset_indexer::index_assets(&packs).unwrap();
+
+        let output = build_weaver_nest(
+            &packs,
+            &assets,
+            &providers,
+            &["high".to_string(), "low".to_string()],
+            &HashMap::new(),
+            temp_dir.join("out").to_str().unwrap(),
+            &NestBuildOptions::default(),
+        )
+        .unwrap();
+        let optifine = output.path.join("assets/minecraft/optifine");
+        let high_copied = optifine
+            .join("random/entity/creeper/creeper.properties")
+            .is_file()
+            && optifine
+                .join("random/entity/creeper/creeper2.png")
+                .is_file();
+        let low_copied = optifine.join("mob").exists();
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(
+            providers["minecraft:optifine/random/entity/creeper/creeper"],
+            vec!["high", "low"]
+        );
+        assert!(high_copied);
+        assert!(!low_copied);
+    }
+
+    #[test]
+    fn test_build_weaver_nest_keeps_cem_models_with_parts() {
+        let temp_dir = std::env::temp_dir().join("test_weaver_nest_cem");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let mut packs = Vec::new();
+        for (id, files) in [
+            (
+                "fresh",
+                vec![
+                    (
+                        "optifine/cem/cow.jem",
+                        r#"{"models": [{"part": "head", "model": "cow_head"}]}"#,
+                    ),
+                    ("optifine/cem/cow2.jem", "{}"),
+                    ("optifine/cem/cow.properties", "models.1=2\nangry.1=true\n"),
+                    ("optifine/cem/cow_head.jpm", r#"{"animations": []}"#),
+                ],
+            ),
+            ("plain", vec![("optifine/cem/cow.jem", "{}")]),
+        ] {
+            let pack_dir = temp_dir.join(id);
+            fs::create_dir_all(&pack_dir).unwrap();
+            fs::write(pack_dir.join("pack.mcmeta"), "{}").unwrap();
+            for (file, content) in files {
+                let path = pack_dir.join("assets/minecraft").join(file);
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(path, content).unwrap();
+            }
+            packs.push(PackMeta {
+                id: id.to_string(),
+                name: id.to_string(),
+                path: pack_dir.to_string_lossy().to_string(),
+                ..Default::default()
+            });
+        }
+        let (assets, providers) = crate::util::asset_indexer::index_assets(&packs).unwrap();
+
+        let output = build_weaver_nest(
+            &packs,
+            &assets,
+            &providers,
+            &["fresh".to_string(), "plain".to_string()],
+            &HashMap::new(),
+            temp_dir.join("out").to_str().unwrap(),
+            &NestBuildOptions::default(),
+        )
+        .unwrap();
+        let cem = output.path.join("assets/minecraft/optifine/cem");
+        let copied: Vec<bool> = ["cow.jem", "cow2.jem", "cow.properties", "cow_head.jpm"]
+            .iter()
+            .map(|file| cem.join(file).is_file())
+            .collect();
+        let model = fs::read_to_string(cem.join("cow.jem")).unwrap_or_default();
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(
+            providers["minecraft:optifine/cem/cow"],
+            vec!["fresh", "plain"]
+        );
+        assert_eq!(copied, vec![true; 4]);
+        assert!(model.contains("cow_head"));
+    }
+
+    #[test]
+    fn test_build_weaver_nest_combines_feature_flags() {
+        let temp_dir = std::env::temp_dir().join("test_weaver_nest_features");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let mut packs = Vec::new();
+        let mut assets = Vec::new();
+        let mut providers = HashMap::new();
+        for (id, features) in [
+            ("experimental", vec!["minecraft:bundle".to_string()]),
+            ("plain", Vec::new()),
+        ] {
+            let file = format!("assets/minecraft/textures/block/{}.png", id);
+            let pack_dir = temp_dir.join(id);
+            fs::create_dir_all(pack_dir.join("assets/minecraft/textures/block")).unwrap();
+            fs::write(pack_dir.join(&file), id).unwrap();
+            let asset_id = format!("minecraft:block/{}", id);
+            providers.insert(asset_id.clone(), vec![id.to_string()]);
+            assets.push(AssetRecord {
+                id: asset_id,
+                labels: vec![],
+                files: vec![file],
+            });
+            packs.push(PackMeta {
+                id: id.to_string(),
+                name: id.to_string(),
+                path: pack_dir.to_string_lossy().to_string(),
+                features,
+                ..Default::default()
+            });
+        }
+
+        let output = build_weaver_nest(
+            &packs,
+            &assets,
+            &providers,
+            &["experimental".to_string(), "plain".to_string()],
+            &HashMap::new(),
+            temp_dir.join("out").to_str().unwrap(),
+            &NestBuildOptions::default(),
+        )
+        .unwrap();
+        let mcmeta: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(output.path.join("pack.mcmeta")).unwrap())
+                .unwrap();
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(
+            mcmeta["features"]["enabled"],
+            serde_json::json!(["minecraft:bundle"])
+        );
+        let warned = output.warnings.iter().any(|warning| {
+            warning.contains("experimental (minecraft:bundle)") && warning.contains("plain (-)")
+        });
+        assert!(warned);
+    }
 }
@@ -1,26 +1,250 @@
 //! Zip file utilities for indexing and extracting pack entries
+//!
+//! Reads go through a process-wide LRU of parsed archives keyed by path,
+//! modification time and length, so repeated reads from one pack don't reopen
+//! the file and re-parse its central directory each time.
 
+use crate::util::pack_files::safe_entry_path;
 use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
 use std::fs::File;
-use std::io::Read;
-use std::path::Path;
-use zip::ZipArchive;
+use std::io::{BufReader, Cursor, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use tracing::{info, warn};
+use walkdir::WalkDir;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Most archives kept open at once
+const ARCHIVE_CACHE_CAPACITY: usize = 16;
+
+/// ZIPs up to this size are held in memory once opened; larger ones are read
+/// through a shared file handle
+const IN_MEMORY_ZIP_MAX_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Total size of in-memory ZIPs the cache keeps before evicting
+const IN_MEMORY_BUDGET_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Parsed archives by path
+static ARCHIVE_CACHE: Lazy<Mutex<ArchiveCache>> = Lazy::new(|| Mutex::new(ArchiveCache::default()));
+
+#[derive(Default)]
+struct ArchiveCache {
+    entries: HashMap<PathBuf, CachedArchive>,
+    /// Incremented on every access; entries remember when they were last used
+    clock: u64,
+}
+
+struct CachedArchive {
+    modified: Option<SystemTime>,
+    len: u64,
+    in_memory: bool,
+    last_used: u64,
+    archive: ZipArchive<ArchiveSource>,
+}
+
+impl ArchiveCache {
+    fn in_memory_bytes(&self) -> u64 {
+        self.entries
+            .values()
+            .filter(|entry| entry.in_memory)
+            .map(|entry| entry.len)
+            .sum()
+    }
+
+    /// Drop least recently used archives until the cache is within its limits,
+    /// never evicting `keep`
+    fn evict(&mut self, keep: &Path) {
+        while self.entries.len() > ARCHIVE_CACHE_CAPACITY
+            || self.in_memory_bytes() > IN_MEMORY_BUDGET_BYTES
+        {
+            let oldest = self
+                .entries
+                .iter()
+                .filter(|(path, _)| path.as_path() != keep)
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(path, _)| path.clone());
+            match oldest {
+                Some(path) => {
+                    self.entries.remove(&path);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+/// Reader behind a cached archive; clones share the underlying bytes or file
+/// but keep their own position, so one archive can be read from many threads
+#[derive(Clone)]
+pub enum ArchiveSource {
+    Memory(Cursor<Arc<[u8]>>),
+    File(SharedFile),
+}
+
+impl Read for ArchiveSource {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            ArchiveSource::Memory(cursor) => cursor.read(buf),
+            ArchiveSource::File(file) => file.read(buf),
+        }
+    }
+}
+
+impl Seek for ArchiveSource {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        match self {
+            ArchiveSource::Memory(cursor) => cursor.seek(pos),
+            ArchiveSource::File(file) => file.seek(pos),
+        }
+    }
+}
+
+/// File handle read with positional reads, so clones never move each other's
+/// offset
+#[derive(Clone)]
+pub struct SharedFile {
+    file: Arc<File>,
+    len: u64,
+    pos: u64,
+}
+
+impl Read for SharedFile {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        #[cfg(unix)]
+        let read = std::os::unix::fs::FileExt::read_at(&*self.file, buf, self.pos)?;
+        #[cfg(windows)]
+        let read = std::os::windows::fs::FileExt::seek_read(&*self.file, buf, self.pos)?;
+        self.pos += read as u64;
+        Ok(read)
+    }
+}
+
+impl Seek for SharedFile {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+        };
+        self.pos = target.ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Seek before start of file",
+            )
+        })?;
+        Ok(self.pos)
+    }
+}
+
+/// Error of a file that exists but can't be parsed as a ZIP archive
+#[derive(Debug)]
+pub struct ZipCorrupt {
+    pub path: String,
+    pub reason: String,
+}
+
+impl ZipCorrupt {
+    fn new(zip_path: &str, err: zip::result::ZipError) -> Self {
+        Self {
+            path: zip_path.to_string(),
+            reason: err.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for ZipCorrupt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to read zip {}: {}", self.path, self.reason)
+    }
+}
+
+impl std::error::Error for ZipCorrupt {}
+
+/// Open a zip through the archive cache
+///
+/// Returns a clone of the cached archive, which shares its parsed central
+/// directory. A ZIP modified or replaced since it was cached is parsed again.
+pub fn open_cached_archive(zip_path: &str) -> Result<ZipArchive<ArchiveSource>> {
+    let metadata = std::fs::metadata(zip_path)
+        .map_err(|e| anyhow!("Failed to open zip {}: {}", zip_path, e))?;
+    let modified = metadata.modified().ok();
+    let len = metadata.len();
+    let key = PathBuf::from(zip_path);
+
+    {
+        let mut cache = ARCHIVE_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        cache.clock += 1;
+        let clock = cache.clock;
+        if let Some(entry) = cache.entries.get_mut(&key) {
+            if entry.modified == modified && entry.len == len {
+                entry.last_used = clock;
+                return Ok(entry.archive.clone());
+            }
+        }
+    }
+
+    // Parse outside the lock so other packs stay readable meanwhile
+    let in_memory = len <= IN_MEMORY_ZIP_MAX_BYTES;
+    let source = if in_memory {
+        let bytes = std::fs::read(zip_path)
+            .map_err(|e| anyhow!("Failed to open zip {}: {}", zip_path, e))?;
+        ArchiveSource::Memory(Cursor::new(Arc::from(bytes)))
+    } else {
+        let file =
+            File::open(zip_path).map_err(|e| anyhow!("Failed to open zip {}: {}", zip_path, e))?;
+        ArchiveSource::File(SharedFile {
+            file: Arc::new(file),
+            len,
+            pos: 0,
+        })
+    };
+    let archive = ZipArchive::new(source).map_err(|e| ZipCorrupt::new(zip_path, e))?;
+
+    let mut cache = ARCHIVE_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    cache.clock += 1;
+    let last_used = cache.clock;
+    cache.entries.insert(
+        key.clone(),
+        CachedArchive {
+            modified,
+            len,
+            in_memory,
+            last_used,
+            archive: archive.clone(),
+        },
+    );
+    cache.evict(&key);
+    Ok(archive)
+}
+
+/// Drop a zip from the archive cache, releasing its file handle
+///
+/// Call before moving, renaming or deleting a ZIP pack: Windows refuses to
+/// touch files that are still open.
+pub fn evict_cached_archive(zip_path: &str) {
+    ARCHIVE_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .entries
+        .remove(Path::new(zip_path));
+}
 
 /// List all files in a zip archive without extracting
 pub fn list_zip_files(zip_path: &str) -> Result<Vec<String>> {
-    println!("[list_zip_files] Opening ZIP: {}", zip_path);
-    let file =
-        File::open(zip_path).map_err(|e| anyhow!("Failed to open zip {}: {}", zip_path, e))?;
-    let mut archive =
-        ZipArchive::new(file).map_err(|e| anyhow!("Failed to read zip {}: {}", zip_path, e))?;
+    info!("Opening ZIP: {}", zip_path);
+    let mut archive = open_cached_archive(zip_path)?;
 
     let archive_len = archive.len();
-    println!("[list_zip_files] ZIP contains {} entries", archive_len);
+    info!("ZIP contains {} entries", archive_len);
     let mut files = Vec::new();
 
     for i in 0..archive_len {
         if i % 5000 == 0 && i > 0 {
-            println!("[list_zip_files] Processed {}/{} entries", i, archive_len);
+            info!("Processed {}/{} entries", i, archive_len);
         }
         let file = archive
             .by_index(i)
@@ -29,22 +253,26 @@ pub fn list_zip_files(zip_path: &str) -> Result<Vec<String>> {
             files.push(file.name().to_string());
         }
     }
-    println!(
-        "[list_zip_files] Found {} files (excluding directories)",
-        files.len()
-    );
+    info!("Found {} files (excluding directories)", files.len());
 
     Ok(files)
 }
 
+/// Check whether a zip has a file at `entry_path`, without decompressing it
+pub fn has_zip_entry(zip_path: &str, entry_path: &str) -> bool {
+    let Ok(entry_path) = safe_entry_path(entry_path) else {
+        return false;
+    };
+    open_cached_archive(zip_path).is_ok_and(|mut archive| archive.by_name(&entry_path).is_ok())
+}
+
 /// Extract a specific file from a zip to bytes
 pub fn extract_zip_entry(zip_path: &str, entry_path: &str) -> Result<Vec<u8>> {
-    let file =
-        File::open(zip_path).map_err(|e| anyhow!("Failed to open zip {}: {}", zip_path, e))?;
-    let mut archive = ZipArchive::new(file).map_err(|e| anyhow!("Failed to read zip: {}", e))?;
+    let entry_path = safe_entry_path(entry_path)?;
+    let mut archive = open_cached_archive(zip_path)?;
 
     let mut file = archive
-        .by_name(entry_path)
+        .by_name(&entry_path)
         .map_err(|e| anyhow!("Entry not found in zip: {}", e))?;
 
     let mut buffer = Vec::new();
@@ -54,6 +282,232 @@ pub fn extract_zip_entry(zip_path: &str, entry_path: &str) -> Result<Vec<u8>> {
     Ok(buffer)
 }
 
+/// Stream a specific file from a zip into a writer without buffering it,
+/// returning the number of bytes copied
+pub fn copy_zip_entry(zip_path: &str, entry_path: &str, out: &mut dyn Write) -> Result<u64> {
+    let entry_path = safe_entry_path(entry_path)?;
+    let mut archive = open_cached_archive(zip_path)?;
+
+    let mut file = archive
+        .by_name(&entry_path)
+        .map_err(|e| anyhow!("Entry not found in zip: {}", e))?;
+
+    std::io::copy(&mut file, out).map_err(|e| anyhow!("Failed to read zip entry: {}", e))
+}
+
+/// How many entries are processed between progress callbacks
+const PROGRESS_INTERVAL: usize = 100;
+
+/// Progress callback for archive operations: (entries done, total entries)
+pub type ArchiveProgress<'a> = Option<&'a dyn Fn(usize, usize)>;
+
+/// Report progress every PROGRESS_INTERVAL entries and once at the end
+fn report_progress(progress: ArchiveProgress, current: usize, total: usize) {
+    if let Some(progress) = progress {
+        if current % PROGRESS_INTERVAL == 0 || current == total {
+            progress(current, total);
+        }
+    }
+}
+
+/// Extract every file in a zip into a directory, returning the number of files written
+///
+/// Entries whose names would escape `dest_dir` (absolute paths, drive letters,
+/// "..") are skipped.
+pub fn extract_zip_to_dir(zip_path: &str, dest_dir: &Path) -> Result<usize> {
+    extract_zip_to_dir_with_progress(zip_path, dest_dir, None)
+}
+
+/// Extract every file in a zip into a directory, reporting progress per entry
+pub fn extract_zip_to_dir_with_progress(
+    zip_path: &str,
+    dest_dir: &Path,
+    progress: ArchiveProgress,
+) -> Result<usize> {
+    let file =
+        File::open(zip_path).map_err(|e| anyhow!("Failed to open zip {}: {}", zip_path, e))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| ZipCorrupt::new(zip_path, e))?;
+
+    let total = archive.len();
+    let mut written = 0;
+    for i in 0..total {
+        let mut entry = archive
+            .by_index(i)
+            .map_err(|e| anyhow!("Failed to read zip entry {}: {}", i, e))?;
+        report_progress(progress, i + 1, total);
+        if entry.is_dir() {
+            continue;
+        }
+        let Ok(relative) = safe_entry_path(entry.name()) else {
+            warn!("Skipping unsafe entry: {}", entry.name());
+            continue;
+        };
+
+        let out_path = dest_dir.join(relative);
+        if let Some(parent) = out_path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| anyhow!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let mut out = File::create(&out_path)
+            .map_err(|e| anyhow!("Failed to create {}: {}", out_path.display(), e))?;
+        std::io::copy(&mut entry, &mut out)
+            .map_err(|e| anyhow!("Failed to extract {}: {}", out_path.display(), e))?;
+        written += 1;
+    }
+
+    Ok(written)
+}
+
+/// Zip every file under `src_dir` into `zip_path`, returning the number of files written
+///
+/// Entries are written in sorted order with forward-slash names. The archive is
+/// written to a temporary file next to `zip_path` and renamed into place once
+/// complete, so a failed run never leaves a truncated zip behind.
+pub fn zip_dir_with_progress(
+    src_dir: &Path,
+    zip_path: &Path,
+    progress: ArchiveProgress,
+) -> Result<usize> {
+    let files: Vec<PathBuf> = WalkDir::new(src_dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .collect();
+
+    let partial_path = zip_path.with_extension("zip.part");
+    match write_files_to_zip(src_dir, &files, &partial_path, progress) {
+        Ok(written) => {
+            std::fs::rename(&partial_path, zip_path)
+                .map_err(|e| anyhow!("Failed to move zip into place: {}", e))?;
+            Ok(written)
+        }
+        Err(e) => {
+            let _ = std::fs::remove_file(&partial_path);
+            Err(e)
+        }
+    }
+}
+
+/// Write `files` (all under `src_dir`) into a new zip at `zip_path`
+fn write_files_to_zip(
+    src_dir: &Path,
+    files: &[PathBuf],
+    zip_path: &Path,
+    progress: ArchiveProgress,
+) -> Result<usize> {
+    let out = File::create(zip_path)
+        .map_err(|e| anyhow!("Failed to create {}: {}", zip_path.display(), e))?;
+    let mut writer = ZipWriter::new(out);
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let total = files.len();
+    for (i, path) in files.iter().enumerate() {
+        let relative = path
+            .strip_prefix(src_dir)
+            .map_err(|e| anyhow!("Failed to relativize {}: {}", path.display(), e))?;
+        let name = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        writer
+            .start_file(name.as_str(), options)
+            .map_err(|e| anyhow!("Failed to add {} to zip: {}", name, e))?;
+        let mut input =
+            File::open(path).map_err(|e| anyhow!("Failed to open {}: {}", path.display(), e))?;
+        std::io::copy(&mut input, &mut writer)
+            .map_err(|e| anyhow!("Failed to compress {}: {}", path.display(), e))?;
+        report_progress(progress, i + 1, total);
+    }
+
+    writer
+        .finish()
+        .map_err(|e| anyhow!("Failed to finish zip: {}", e))?;
+    Ok(total)
+}
+
+/// Rebuild a zip whose central directory is damaged or missing from the local
+/// headers in front of each entry, returning the number of files recovered
+///
+/// Entries are read front to back until the central directory, stopping at
+/// the first one that is cut off or fails its checksum, which is where a
+/// partial download ends. Like `zip_dir_with_progress`, the copy is written
+/// next to `dest` and renamed into place; it fails if nothing was recovered.
+pub fn recover_zip(zip_path: &str, dest: &Path) -> Result<usize> {
+    let partial_path = dest.with_extension("zip.part");
+    match write_recovered_entries(zip_path, &partial_path) {
+        Ok(0) => {
+            let _ = std::fs::remove_file(&partial_path);
+            Err(anyhow!("No entries could be recovered from {}", zip_path))
+        }
+        Ok(recovered) => {
+            std::fs::rename(&partial_path, dest)
+                .map_err(|e| anyhow!("Failed to move zip into place: {}", e))?;
+            Ok(recovered)
+        }
+        Err(e) => {
+            let _ = std::fs::remove_file(&partial_path);
+            Err(e)
+        }
+    }
+}
+
+/// Copy every readable entry of `zip_path`, by its local headers, into a new
+/// zip at `out_path`
+fn write_recovered_entries(zip_path: &str, out_path: &Path) -> Result<usize> {
+    let mut reader = BufReader::new(
+        File::open(zip_path).map_err(|e| anyhow!("Failed to open zip {}: {}", zip_path, e))?,
+    );
+    let out = File::create(out_path)
+        .map_err(|e| anyhow!("Failed to create {}: {}", out_path.display(), e))?;
+    let mut writer = ZipWriter::new(out);
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let mut recovered = 0;
+    loop {
+        let mut entry = match zip::read::read_zipfile_from_stream(&mut reader) {
+            Ok(Some(entry)) => entry,
+            // Reached the central directory
+            Ok(None) => break,
+            Err(e) => {
+                warn!(
+                    "Stopped recovering {} at an unreadable entry: {}",
+                    zip_path, e
+                );
+                break;
+            }
+        };
+        if entry.is_dir() {
+            continue;
+        }
+        let Ok(name) = safe_entry_path(entry.name()) else {
+            warn!("Skipping unsafe entry: {}", entry.name());
+            continue;
+        };
+        let mut contents = Vec::new();
+        if let Err(e) = entry.read_to_end(&mut contents) {
+            warn!("Stopped recovering {} at {}: {}", zip_path, name, e);
+            break;
+        }
+
+        writer
+            .start_file(name.as_str(), options)
+            .map_err(|e| anyhow!("Failed to add {} to zip: {}", name, e))?;
+        writer
+            .write_all(&contents)
+            .map_err(|e| anyhow!("Failed to compress {}: {}", name, e))?;
+        recovered += 1;
+    }
+
+    writer
+        .finish()
+        .map_err(|e| anyhow!("Failed to finish zip: {}", e))?;
+    Ok(recovered)
+}
+
 /// Get size of a zip file
 pub fn get_zip_size(zip_path: &str) -> Result<u64> {
     let path = Path::new(zip_path);
@@ -71,4 +525,163 @@ mod tests {
         // This test requires a test zip file
         // Skipping for now
     }
+
+    #[test]
+    fn test_cached_archive_follows_file_changes() {
+        let temp_dir = std::env::temp_dir().join("test_cached_archive");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+        let zip_path = temp_dir.join("pack.zip");
+        let zip_str = zip_path.to_str().unwrap();
+        let write_zip = |contents: &[u8]| {
+            let mut writer = ZipWriter::new(File::create(&zip_path).unwrap());
+            writer
+                .start_file("pack.mcmeta", FileOptions::default())
+                .unwrap();
+            writer.write_all(contents).unwrap();
+            writer.finish().unwrap();
+        };
+
+        write_zip(b"one");
+        let first = extract_zip_entry(zip_str, "pack.mcmeta").unwrap();
+        let again = extract_zip_entry(zip_str, "pack.mcmeta").unwrap();
+        write_zip(b"three");
+        let replaced = extract_zip_entry(zip_str, "pack.mcmeta").unwrap();
+
+        // Large archives are read through a shared handle with positional reads
+        let file = File::open(&zip_path).unwrap();
+        let len = file.metadata().unwrap().len();
+        let source = ArchiveSource::File(SharedFile {
+            file: Arc::new(file),
+            len,
+            pos: 0,
+        });
+        let archive = ZipArchive::new(source).unwrap();
+        let mut clone = archive.clone();
+        let mut from_file = String::new();
+        clone
+            .by_name("pack.mcmeta")
+            .unwrap()
+            .read_to_string(&mut from_file)
+            .unwrap();
+
+        evict_cached_archive(zip_str);
+        let _ = std::fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(first, b"one");
+        assert_eq!(again, b"one");
+        assert_eq!(replaced, b"three");
+        assert_eq!(from_file, "three");
+    }
+
+    #[test]
+    fn test_zip_dir_round_trip() {
+        let temp_dir = std::env::temp_dir().join("test_zip_dir_round_trip");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let src_dir = temp_dir.join("pack");
+        std::fs::create_dir_all(src_dir.join("assets/minecraft/textures/block")).unwrap();
+        std::fs::write(src_dir.join("pack.mcmeta"), "{}").unwrap();
+        std::fs::write(
+            src_dir.join("assets/minecraft/textures/block/stone.png"),
+            b"stone",
+        )
+        .unwrap();
+
+        let zip_path = temp_dir.join("pack.zip");
+        let calls = std::cell::RefCell::new(Vec::new());
+        let record = |current: usize, total: usize| calls.borrow_mut().push((current, total));
+        let zipped = zip_dir_with_progress(&src_dir, &zip_path, Some(&record)).unwrap();
+        let entries = list_zip_files(zip_path.to_str().unwrap()).unwrap();
+        let partial_left = temp_dir.join("pack.zip.part").exists();
+
+        let out_dir = temp_dir.join("out");
+        let extracted =
+            extract_zip_to_dir_with_progress(zip_path.to_str().unwrap(), &out_dir, None).unwrap();
+        let stone = std::fs::read(out_dir.join("assets/minecraft/textures/block/stone.png"));
+        let mut streamed = Vec::new();
+        let streamed_len = copy_zip_entry(
+            zip_path.to_str().unwrap(),
+            "assets/minecraft/textures/block/stone.png",
+            &mut streamed,
+        )
+        .unwrap();
+        let has_stone = has_zip_entry(
+            zip_path.to_str().unwrap(),
+            "assets/minecraft/textures/block/stone.png",
+        );
+        let has_dirt = has_zip_entry(
+            zip_path.to_str().unwrap(),
+            "assets/minecraft/textures/block/dirt.png",
+        );
+        evict_cached_archive(zip_path.to_str().unwrap());
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+
+        assert_eq!(zipped, 2);
+        assert_eq!(
+            entries,
+            vec!["assets/minecraft/textures/block/stone.png", "pack.mcmeta"]
+        );
+        assert_eq!(calls.into_inner().last(), Some(&(2, 2)));
+        assert!(!partial_left);
+        assert_eq!(extracted, 2);
+        assert_eq!(stone.unwrap(), b"stone");
+        assert_eq!(
+            (streamed_len, streamed.as_slice()),
+            (5, b"stone".as_slice())
+        );
+        assert!(has_stone);
+        assert!(!has_dirt);
+    }
+
+    #[test]
+    fn test_recover_zip_without_central_directory() {
+        let temp_dir = std::env::temp_dir().join("test_recover_zip");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+        let zip_path = temp_dir.join("pack.zip");
+
+        let mut writer = ZipWriter::new(File::create(&zip_path).unwrap());
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+        for (name, content) in [
+            ("pack.mcmeta", "{}"),
+            ("assets/minecraft/lang/en_us.json", "{}"),
+            ("assets/minecraft/textures/block/stone.png", "stone"),
+        ] {
+            writer.start_file(name, options).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+
+        // Cut the file short in the last entry's data, as an interrupted
+        // download would
+        let bytes = std::fs::read(&zip_path).unwrap();
+        let last_entry = bytes
+            .windows(4)
+            .rposition(|window| window == b"PK\x03\x04")
+            .unwrap();
+        std::fs::write(&zip_path, &bytes[..last_entry + 50]).unwrap();
+        let zip_str = zip_path.to_str().unwrap();
+        let unreadable = ZipArchive::new(File::open(&zip_path).unwrap()).is_err();
+
+        let repaired_path = temp_dir.join("repaired.zip");
+        let recovered = recover_zip(zip_str, &repaired_path).unwrap();
+        let entries = list_zip_files(repaired_path.to_str().unwrap()).unwrap();
+        evict_cached_archive(repaired_path.to_str().unwrap());
+
+        std::fs::write(&zip_path, b"not a zip").unwrap();
+        let nothing = recover_zip(zip_str, &temp_dir.join("nothing.zip"));
+        let nothing_left = temp_dir.join("nothing.zip.part").exists();
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+
+        assert!(unreadable);
+        assert_eq!(recovered, 2);
+        assert_eq!(
+            entries,
+            vec!["pack.mcmeta", "assets/minecraft/lang/en_us.json"]
+        );
+        assert!(nothing.is_err());
+        assert!(!nothing_left);
+    }
 }
@@ -1,4 +1,5 @@
 use crate::error::AppResult;
+use crate::util::i18n::{label as tr_label, tr};
 /**
  * Input validation utilities for Tauri commands
  *
@@ -10,24 +11,24 @@ use std::path::Path;
 /// Validates a directory path exists and is readable
 pub fn validate_directory(path: &str, label: &str) -> AppResult<()> {
     if path.is_empty() {
-        return Err(crate::error::AppError::validation(format!(
-            "{} cannot be empty",
-            label
+        return Err(crate::error::AppError::validation(tr(
+            "validation-empty",
+            &[("label", tr_label(label).into())],
         )));
     }
 
     let p = Path::new(path);
     if !p.exists() {
-        return Err(crate::error::AppError::io(format!(
-            "{} does not exist: {}",
-            label, path
+        return Err(crate::error::AppError::io(tr(
+            "validation-missing",
+            &[("label", tr_label(label).into()), ("path", path.into())],
         )));
     }
 
     if !p.is_dir() {
-        return Err(crate::error::AppError::validation(format!(
-            "{} is not a directory: {}",
-            label, path
+        return Err(crate::error::AppError::validation(tr(
+            "validation-not-directory",
+            &[("label", tr_label(label).into()), ("path", path.into())],
         )));
     }
 
@@ -37,9 +38,10 @@ pub fn validate_directory(path: &str, label: &str) -> AppResult<()> {
 /// Validates that a pack order is not empty
 pub fn validate_pack_order(order: &[String]) -> AppResult<()> {
     if order.is_empty() {
-        return Err(crate::error::AppError::validation(
-            "Pack order cannot be empty".to_string(),
-        ));
+        return Err(crate::error::AppError::validation(tr(
+            "validation-pack-order-empty",
+            &[],
+        )));
     }
     Ok(())
 }
@@ -51,28 +53,29 @@ pub fn validate_overrides(
 ) -> AppResult<()> {
     for (asset_id, override_entry) in overrides {
         if asset_id.is_empty() {
-            return Err(crate::error::AppError::validation(
-                "Asset ID in overrides cannot be empty".to_string(),
-            ));
+            return Err(crate::error::AppError::validation(tr(
+                "validation-override-asset-empty",
+                &[],
+            )));
         }
         let pack_id = &override_entry.pack_id;
         if pack_id.is_empty() {
-            return Err(crate::error::AppError::validation(format!(
-                "Pack ID for asset {} cannot be empty",
-                asset_id
+            return Err(crate::error::AppError::validation(tr(
+                "validation-override-pack-empty",
+                &[("asset", asset_id.as_str().into())],
             )));
         }
         if !pack_order.contains(pack_id) {
-            return Err(crate::error::AppError::validation(format!(
-                "Override references non-existent pack: {}",
-                pack_id
+            return Err(crate::error::AppError::validation(tr(
+                "validation-override-unknown-pack",
+                &[("pack", pack_id.as_str().into())],
             )));
         }
         if let Some(path) = &override_entry.variant_path {
             if path.trim().is_empty() {
-                return Err(crate::error::AppError::validation(format!(
-                    "Variant path for asset {} cannot be empty",
-                    asset_id
+                return Err(crate::error::AppError::validation(tr(
+                    "validation-variant-path-empty",
+                    &[("asset", asset_id.as_str().into())],
                 )));
             }
         }
@@ -94,6 +97,140 @@ pub fn validate_build_request(
     Ok(())
 }
 
+/// Validates zip compression settings for a nest build
+pub fn validate_compression(compression: &crate::model::NestCompression) -> AppResult<()> {
+    if let Some(level) = compression.level {
+        if level > 9 {
+            return Err(crate::error::AppError::validation(tr(
+                "validation-compression-level",
+                &[("level", level.into())],
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Validates that cached vanilla data matches a workspace's target version
+pub fn validate_target_version(
+    target: &crate::model::TargetVersion,
+    cached_version: Option<&str>,
+) -> AppResult<()> {
+    match cached_version {
+        Some(cached) if cached == target.version => Ok(()),
+        Some(cached) => Err(crate::error::AppError::validation(tr(
+            "validation-target-version-mismatch",
+            &[
+                ("target", target.version.as_str().into()),
+                ("cached", cached.into()),
+            ],
+        ))
+        .with_details(tr("validation-target-version-mismatch-hint", &[]))),
+        None => Err(crate::error::AppError::validation(tr(
+            "validation-no-vanilla-data",
+            &[("target", target.version.as_str().into())],
+        ))),
+    }
+}
+
+/// Validates that a conversion output folder is empty (or missing) and outside the source pack
+pub fn validate_conversion_output(output_dir: &str, source_path: &str) -> AppResult<()> {
+    if output_dir.is_empty() {
+        return Err(crate::error::AppError::validation(tr(
+            "validation-output-dir-empty",
+            &[],
+        )));
+    }
+
+    let output = Path::new(output_dir);
+    if output.starts_with(source_path) {
+        return Err(crate::error::AppError::validation(tr(
+            "validation-output-inside-source",
+            &[("path", output_dir.into())],
+        )));
+    }
+
+    if output.exists() {
+        let is_empty = output
+            .read_dir()
+            .map(|mut entries| entries.next().is_none())
+            .unwrap_or(false);
+        if !is_empty {
+            return Err(crate::error::AppError::validation(tr(
+                "validation-output-not-empty",
+                &[("path", output_dir.into())],
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates that a zip output path is new, ends in .zip and lies outside the source folder
+pub fn validate_zip_output(zip_path: &str, source_dir: &str) -> AppResult<()> {
+    if zip_path.is_empty() {
+        return Err(crate::error::AppError::validation(tr(
+            "validation-zip-path-empty",
+            &[],
+        )));
+    }
+
+    let output = Path::new(zip_path);
+    let is_zip = output
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"));
+    if !is_zip {
+        return Err(crate::error::AppError::validation(tr(
+            "validation-zip-extension",
+            &[("path", zip_path.into())],
+        )));
+    }
+
+    if output.starts_with(source_dir) {
+        return Err(crate::error::AppError::validation(tr(
+            "validation-zip-inside-source",
+            &[("path", zip_path.into())],
+        )));
+    }
+
+    if output.exists() {
+        return Err(crate::error::AppError::validation(tr(
+            "validation-zip-exists",
+            &[("path", zip_path.into())],
+        )));
+    }
+
+    match output.parent() {
+        Some(parent) if parent.as_os_str().is_empty() || parent.is_dir() => Ok(()),
+        _ => Err(crate::error::AppError::io(tr(
+            "validation-zip-folder-missing",
+            &[("path", zip_path.into())],
+        ))),
+    }
+}
+
+/// Validates a new pack name, which becomes a file or folder name in the packs directory
+pub fn validate_pack_name(name: &str) -> AppResult<()> {
+    if name.trim().is_empty() {
+        return Err(crate::error::AppError::validation(tr(
+            "validation-pack-name-empty",
+            &[],
+        )));
+    }
+
+    let invalid_char = name.chars().any(|c| {
+        c.is_control() || matches!(c, '/' | '\\' | '<' | '>' | ':' | '"' | '|' | '?' | '*')
+    });
+    // Windows drops trailing dots and spaces, which also rules out "." and ".."
+    if invalid_char || name.ends_with(['.', ' ']) {
+        return Err(crate::error::AppError::validation(tr(
+            "validation-pack-name-invalid",
+            &[("name", name.into())],
+        )));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -352,4 +489,94 @@ mod tests {
         let err = result.unwrap_err();
         assert!(err.message.contains("non-existent pack"));
     }
+
+    #[test]
+    fn test_validate_compression_level_range() {
+        use crate::model::{CompressionMethod, NestCompression};
+
+        let valid = NestCompression {
+            method: CompressionMethod::Deflate,
+            level: Some(9),
+        };
+        assert!(validate_compression(&valid).is_ok());
+
+        let invalid = NestCompression {
+            method: CompressionMethod::Zstd,
+            level: Some(10),
+        };
+        let err = validate_compression(&invalid).unwrap_err();
+        assert_eq!(err.code, "VALIDATION_ERROR");
+    }
+
+    #[test]
+    fn test_validate_conversion_output() {
+        let temp_dir = std::env::temp_dir().join("test_validate_conversion_output");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let pack_dir = temp_dir.join("pack");
+        fs::create_dir_all(&pack_dir).unwrap();
+        fs::write(pack_dir.join("pack.mcmeta"), "{}").unwrap();
+        let source = pack_dir.to_str().unwrap().to_string();
+
+        let fresh = validate_conversion_output(temp_dir.join("out").to_str().unwrap(), &source);
+        let inside = validate_conversion_output(pack_dir.join("out").to_str().unwrap(), &source);
+        let non_empty = validate_conversion_output(&source, "/elsewhere");
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert!(fresh.is_ok());
+        assert!(inside.unwrap_err().message.contains("inside the source pack"));
+        assert!(non_empty.unwrap_err().message.contains("must be empty"));
+    }
+
+    #[test]
+    fn test_validate_zip_output() {
+        let temp_dir = std::env::temp_dir().join("test_validate_zip_output");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let pack_dir = temp_dir.join("pack");
+        fs::create_dir_all(&pack_dir).unwrap();
+        fs::write(temp_dir.join("taken.zip"), b"").unwrap();
+        let source = pack_dir.to_str().unwrap().to_string();
+        let path = |name: &str| temp_dir.join(name).to_string_lossy().to_string();
+
+        let fresh = validate_zip_output(&path("pack.zip"), &source);
+        let wrong_extension = validate_zip_output(&path("pack.tar"), &source);
+        let inside = validate_zip_output(&path("pack/pack.zip"), &source);
+        let existing = validate_zip_output(&path("taken.zip"), &source);
+        let missing_parent = validate_zip_output(&path("missing/pack.zip"), &source);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert!(fresh.is_ok());
+        assert!(wrong_extension.unwrap_err().message.contains(".zip extension"));
+        assert!(inside.unwrap_err().message.contains("inside the source folder"));
+        assert!(existing.unwrap_err().message.contains("already exists"));
+        assert_eq!(missing_parent.unwrap_err().code, "IO_ERROR");
+    }
+
+    #[test]
+    fn test_validate_pack_name() {
+        assert!(validate_pack_name("Faithful 32x").is_ok());
+        assert!(validate_pack_name(" ").unwrap_err().message.contains("cannot be empty"));
+        for name in ["../escape", "a/b", "a\\b", "pack?", "..", "pack."] {
+            assert!(
+                validate_pack_name(name).is_err(),
+                "{} should be rejected",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_target_version() {
+        let target = crate::model::TargetVersion {
+            version: "1.20.1".to_string(),
+            pack_format: None,
+        };
+
+        assert!(validate_target_version(&target, Some("1.20.1")).is_ok());
+        let mismatch = validate_target_version(&target, Some("1.21.4")).unwrap_err();
+        assert_eq!(mismatch.code, "VALIDATION_ERROR");
+        assert!(mismatch.message.contains("cached for 1.21.4"));
+        assert!(validate_target_version(&target, None).is_err());
+    }
 }
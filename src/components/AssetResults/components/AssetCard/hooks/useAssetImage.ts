@@ -1,6 +1,6 @@
 import { useState, useEffect, useRef } from "react";
 import { convertFileSrc } from "@tauri-apps/api/core";
-import { getVanillaTexturePath, getPackTexturePath } from "@lib/tauri";
+import { getAssetThumbnail } from "@lib/tauri";
 import {
   isBiomeColormapAsset,
   normalizeAssetId,
@@ -68,10 +68,11 @@ export function useAssetImage(
         }
 
         // Priority: 1. Pack texture (if exists), 2. Vanilla texture (fallback)
+        // Cards show 64px thumbnails rather than full-resolution textures
         if (winnerPackId && winnerPack) {
           try {
             // Try to load from the winning pack
-            texturePath = await getPackTexturePath(
+            texturePath = await getAssetThumbnail(
               winnerPack.path,
               normalizedAssetId,
               winnerPack.is_zip,
@@ -82,11 +83,11 @@ export function useAssetImage(
               `Pack texture not found for ${normalizedAssetId}, using vanilla.`,
               packError,
             );
-            texturePath = await getVanillaTexturePath(normalizedAssetId);
+            texturePath = await getAssetThumbnail(null, normalizedAssetId, false);
           }
         } else {
           // No pack provides this texture, use vanilla
-          texturePath = await getVanillaTexturePath(normalizedAssetId);
+          texturePath = await getAssetThumbnail(null, normalizedAssetId, false);
         }
 
         if (mounted) {
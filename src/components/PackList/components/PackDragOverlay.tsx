@@ -16,11 +16,7 @@ export function PackDragOverlay({ activeItem }: PackDragOverlayProps) {
         <div className={s.cardWrapper}>
           <ResourcePackCard
             name={activeItem.name}
-            iconSrc={
-              activeItem.icon_data
-                ? `data:image/png;base64,${activeItem.icon_data}`
-                : undefined
-            }
+            iconSrc={activeItem.icon_url}
             metadata={
               activeItem.size
                 ? [
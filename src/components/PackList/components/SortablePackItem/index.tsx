@@ -9,6 +9,7 @@ import {
   type ResourcePackCardMetadata,
 } from "@components/ResourcePackCard";
 import { useSort } from "../../useSort";
+import { usePackCompatibilityBadge } from "../../usePackCompatibility";
 import { formatPackSize } from "../../utilities";
 import type { SortablePackItemProps } from "./types";
 import s from "../../styles.module.scss";
@@ -29,6 +30,22 @@ export const SortablePackItem = ({
     !isDraggable,
   );
 
+  const compatibilityBadge = usePackCompatibilityBadge(item.id);
+  const badges = useMemo(() => {
+    const list = item.texture_resolution ? [`${item.texture_resolution}x`] : [];
+    if (item.builtin) list.push("Built-in");
+    if (item.features?.length) list.push("Experimental");
+    if (item.cem_dialect === "emf") list.push("EMF");
+    if (compatibilityBadge) list.push(compatibilityBadge);
+    return list;
+  }, [
+    item.texture_resolution,
+    item.builtin,
+    item.features,
+    item.cem_dialect,
+    compatibilityBadge,
+  ]);
+
   const descriptionHTML = useMemo(() => {
     if (!item.description) return "";
     return minecraftTextToHTML(item.description);
@@ -36,13 +53,14 @@ export const SortablePackItem = ({
 
   const metadata = useMemo<ResourcePackCardMetadata[]>(() => {
     const sizeLabel = formatPackSize(item.size);
-    return sizeLabel ? [{ label: "Size", value: sizeLabel }] : [];
-  }, [item.size]);
-
-  const iconSrc = useMemo(() => {
-    if (!item.icon_data) return undefined;
-    return `data:image/png;base64,${item.icon_data}`;
-  }, [item.icon_data]);
+    const list: ResourcePackCardMetadata[] = sizeLabel
+      ? [{ label: "Size", value: sizeLabel }]
+      : [];
+    if (item.features?.length) {
+      list.push({ label: "Features", value: item.features.join(", ") });
+    }
+    return list;
+  }, [item.size, item.features]);
 
   const handleActionPointerDown = useCallback(
     (event: ReactPointerEvent<HTMLButtonElement>) => {
@@ -78,8 +96,9 @@ export const SortablePackItem = ({
       <div className={s.cardWrapper}>
         <ResourcePackCard
           name={item.name}
-          iconSrc={iconSrc}
+          iconSrc={item.icon_url}
           metadata={metadata}
+          badges={badges}
           description={
             item.description ? (
               <span dangerouslySetInnerHTML={{ __html: descriptionHTML }} />
@@ -6,7 +6,7 @@ export interface PackItem {
     name: string;
     size: number;
     description?: string;
-    icon_data?: string;
+    icon_url?: string;
 }
 
 export interface PackListProps {
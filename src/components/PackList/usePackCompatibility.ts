@@ -0,0 +1,53 @@
+/**
+ * Hook to label a pack with the Minecraft releases it works on
+ */
+
+import { useEffect, useState } from "react";
+import { getPackCompatibility } from "@lib/tauri";
+import { useSelectPacksDir } from "@state/selectors";
+
+const VANILLA_PACK_ID = "minecraft:vanilla";
+
+// Checks read every model in the pack, so each pack is checked once per session
+const badgeCache = new Map<string, Promise<string | null>>();
+
+function loadBadge(packId: string, packsDir: string): Promise<string | null> {
+  const key = `${packsDir}\n${packId}`;
+  let badge = badgeCache.get(key);
+  if (!badge) {
+    badge = getPackCompatibility(packId, packsDir)
+      .then((matrix) =>
+        matrix.worksOn ? `MC ${matrix.worksOn}` : "Needs conversion",
+      )
+      .catch((error) => {
+        console.warn(
+          `[PackList] Compatibility check failed for ${packId}:`,
+          error,
+        );
+        return null;
+      });
+    badgeCache.set(key, badge);
+  }
+  return badge;
+}
+
+export function usePackCompatibilityBadge(packId: string): string | null {
+  const packsDir = useSelectPacksDir();
+  const [badge, setBadge] = useState<string | null>(null);
+
+  useEffect(() => {
+    setBadge(null);
+    if (!packsDir || packId === VANILLA_PACK_ID) return;
+
+    let mounted = true;
+    void loadBadge(packId, packsDir).then((label) => {
+      if (mounted) setBadge(label);
+    });
+
+    return () => {
+      mounted = false;
+    };
+  }, [packId, packsDir]);
+
+  return badge;
+}
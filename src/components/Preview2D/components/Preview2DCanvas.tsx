@@ -5,6 +5,7 @@ import { Canvas } from "@react-three/fiber";
 import { OrbitControls, PerspectiveCamera } from "@react-three/drei";
 import * as THREE from "three";
 import type { ParsedEntityModel } from "@lib/emf";
+import type { AnimationFrames } from "@lib/tauri";
 import {
   CheckerboardBackground,
   TextureSprite,
@@ -24,6 +25,7 @@ interface Preview2DCanvasProps {
   textureHeight: number;
   showUVWrap: boolean;
   entityModel: ParsedEntityModel | null;
+  animation: AnimationFrames | null;
   onTextureLoaded: (width: number, height: number) => void;
   onUVHover: (label: string | null, x: number, y: number) => void;
 }
@@ -39,6 +41,7 @@ export function Preview2DCanvas({
   textureHeight,
   showUVWrap,
   entityModel,
+  animation,
   onTextureLoaded,
   onUVHover,
 }: Preview2DCanvasProps) {
@@ -70,6 +73,7 @@ export function Preview2DCanvas({
       <CheckerboardBackground />
       <TextureSprite
         texturePath={texturePath}
+        animation={animation}
         onTextureLoaded={onTextureLoaded}
       />
 
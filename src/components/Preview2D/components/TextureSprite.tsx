@@ -1,11 +1,17 @@
 import { useEffect } from "react";
 import { useLoader } from "@react-three/fiber";
 import * as THREE from "three";
+import type { AnimationFrames } from "@lib/tauri";
+
+/** Length of one game tick */
+const TICK_MS = 50;
 
 export interface TextureSpriteProps {
   texturePath: string;
   textureWidth?: number;
   textureHeight?: number;
+  /** When set, only the current frame of the strip is shown */
+  animation?: AnimationFrames | null;
   onTextureLoaded?: (width: number, height: number) => void;
 }
 
@@ -15,6 +21,7 @@ export interface TextureSpriteProps {
  */
 export function TextureSprite({
   texturePath,
+  animation,
   onTextureLoaded,
 }: TextureSpriteProps) {
   // Load texture using drei's useLoader hook
@@ -33,11 +40,51 @@ export function TextureSprite({
     texture.needsUpdate = true;
   }, [texture]);
 
+  // Step through the animation sequence, showing one frame of the strip at a
+  // time (interpolated animations step without blending)
+  useEffect(() => {
+    if (!animation || animation.sequence.length === 0) return;
+
+    const showFrame = (sequenceIndex: number) => {
+      const step = animation.sequence[sequenceIndex];
+      const rect = animation.frames[step.index];
+      if (!rect) return;
+      texture.repeat.set(
+        rect.width / animation.sheetWidth,
+        rect.height / animation.sheetHeight,
+      );
+      // Texture V runs bottom-up while frame rects are top-down
+      texture.offset.set(
+        rect.x / animation.sheetWidth,
+        1 - (rect.y + rect.height) / animation.sheetHeight,
+      );
+    };
+
+    let sequenceIndex = 0;
+    let timeout: number | null = null;
+    const scheduleNext = () => {
+      const durationMs = animation.sequence[sequenceIndex].time * TICK_MS;
+      timeout = window.setTimeout(() => {
+        sequenceIndex = (sequenceIndex + 1) % animation.sequence.length;
+        showFrame(sequenceIndex);
+        scheduleNext();
+      }, durationMs);
+    };
+    showFrame(0);
+    scheduleNext();
+
+    return () => {
+      if (timeout !== null) window.clearTimeout(timeout);
+      texture.repeat.set(1, 1);
+      texture.offset.set(0, 0);
+    };
+  }, [texture, animation]);
+
   // Calculate aspect ratio and notify parent
   useEffect(() => {
     if (texture.image) {
-      const { width } = texture.image;
-      const { height } = texture.image;
+      const width = animation?.frameWidth ?? texture.image.width;
+      const height = animation?.frameHeight ?? texture.image.height;
 
       console.log(`[Preview2D] Loaded texture: ${width}x${height}`);
 
@@ -45,12 +92,14 @@ export function TextureSprite({
         onTextureLoaded(width, height);
       }
     }
-  }, [texture, onTextureLoaded]);
+  }, [texture, animation, onTextureLoaded]);
 
-  // Calculate geometry size based on aspect ratio
-  const aspectRatio = texture.image
-    ? texture.image.width / texture.image.height
-    : 1;
+  // Calculate geometry size based on aspect ratio (of one frame when animated)
+  const aspectRatio = animation
+    ? animation.frameWidth / animation.frameHeight
+    : texture.image
+      ? texture.image.width / texture.image.height
+      : 1;
   const width = aspectRatio >= 1 ? aspectRatio * 2 : 2;
   const height = aspectRatio >= 1 ? 2 : 2 / aspectRatio;
 
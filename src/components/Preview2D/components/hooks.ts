@@ -1,6 +1,11 @@
 import { useState, useEffect } from "react";
 import { convertFileSrc } from "@tauri-apps/api/core";
-import { getPackTexturePath, getVanillaTexturePath } from "@lib/tauri";
+import {
+  getAnimationFrames,
+  getPackTexturePath,
+  getVanillaTexturePath,
+} from "@lib/tauri";
+import type { AnimationFrames } from "@lib/tauri";
 import { normalizeAssetId } from "@lib/asset/parsing";
 import { loadEntityModel, getEntityInfoFromAssetId } from "@lib/emf";
 import type { ParsedEntityModel } from "@lib/emf";
@@ -67,6 +72,45 @@ export function useTextureLoader(
   return { texturePath, error };
 }
 
+/**
+ * Frames of the displayed texture when its .mcmeta animates it, so the
+ * preview plays the animation instead of showing the whole strip
+ */
+export function useAnimationFrames(
+  assetId: string | undefined,
+  winnerPackId: string | undefined,
+  winnerPack: PackInfo | null
+): AnimationFrames | null {
+  const [animation, setAnimation] = useState<AnimationFrames | null>(null);
+
+  useEffect(() => {
+    setAnimation(null);
+    if (!assetId) return;
+
+    let mounted = true;
+    const normalizedAssetId = normalizeAssetId(assetId);
+    const packPath = winnerPackId && winnerPack ? winnerPack.path : null;
+
+    getAnimationFrames(packPath, normalizedAssetId)
+      .catch(() =>
+        // Same fallback as the texture itself
+        packPath ? getAnimationFrames(null, normalizedAssetId) : null
+      )
+      .then((frames) => {
+        if (mounted) setAnimation(frames);
+      })
+      .catch((err) => {
+        console.warn("[Preview2D] Failed to load animation frames:", err);
+      });
+
+    return () => {
+      mounted = false;
+    };
+  }, [assetId, winnerPackId, winnerPack]);
+
+  return animation;
+}
+
 export function useEntityModelLoader(
   isEntity: boolean,
   showUVWrap: boolean,
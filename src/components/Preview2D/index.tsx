@@ -15,7 +15,11 @@ import {
   Preview2DLoading,
   Preview2DCanvas,
 } from "./components";
-import { useTextureLoader, useEntityModelLoader } from "./components/hooks";
+import {
+  useTextureLoader,
+  useEntityModelLoader,
+  useAnimationFrames,
+} from "./components/hooks";
 import s from "./styles.module.scss";
 
 interface Props {
@@ -45,6 +49,7 @@ export default function Preview2D({ assetId }: Props) {
 
   const { texturePath, error } = useTextureLoader(assetId, winnerPackId, winnerPack);
   const entityModel = useEntityModelLoader(isEntity, showUVWrap, assetId, winnerPack);
+  const animation = useAnimationFrames(assetId, winnerPackId, winnerPack);
 
   const handleTextureLoaded = (width: number, height: number) => {
     setTextureWidth(width);
@@ -77,6 +82,7 @@ export default function Preview2D({ assetId }: Props) {
         textureHeight={textureHeight}
         showUVWrap={showUVWrap}
         entityModel={entityModel}
+        animation={animation}
         onTextureLoaded={handleTextureLoaded}
         onUVHover={handleUVHover}
       />
@@ -41,32 +41,28 @@ export interface ParticleEmitter3DProps {
   /** Whether to center X/Z for block coordinates */
   centered?: boolean;
   /** Which method this emission comes from (determines call rate):
-   * - "animateTick": ~2% per tick (random block sampling)
+   * - "animateTick": ~17% per tick near the player (random block sampling)
    * - "particleTick": 100% per tick (called every tick)
    */
   emissionSource?: string;
 }
 
 // Minecraft's animateTick sampling mechanics:
-// - Every tick, 667 random block positions are sampled in each chunk section (32×32×32 volume)
-// - Base probability per block: 1 - (1 - 1/32768)^667 ≈ 2.04%
-//
-// This 2.04% is correct for Minecraft's world where thousands of blocks compete for sampling.
-// However, in our single-block preview, this creates an artificially low spawn rate because:
-// 1. Players focusing on one campfire in-game see it sampled more frequently in practice
-// 2. Our preview has no competing blocks - it's always "in focus"
-// 3. The base 2.04% gives campfire lava: 0.0816 particles/sec (1 every ~12 seconds)
+// - Every tick, ClientLevel.animateTick runs 667 rounds around the player, each
+//   sampling one block within 16 and one within 32 blocks per axis
+// - Axis offsets are nextInt(r) - nextInt(r), so blocks near the player are
+//   picked far more often: ~16.7% per tick at the player's position, about
+//   half that 8 blocks away
+// - The preview is watched from close by, so it uses the player's-position
+//   chance
 //
 // Investigation findings (see detailed analysis):
 // - Campfire lava particles spawn ONLY from animateTick (not block entity)
 // - Probability: 20% when animateTick fires (nextInt(5) == 0)
 // - Count: Always exactly 1 particle (nextInt(1) + 1)
 // - NO burst mechanism - observed "bursts" are pure Poisson distribution clustering
-// - Rate calculation: 20 tps × 2.04% sample × 20% probability × 1 count = 0.0816/sec
+// - Rate calculation: 20 tps × 16.7% sample × 20% probability × 1 count = 0.67/sec
 //
-// For single-block preview, we apply 5x multiplier to match player experience:
-// - Base rate: 0.0816 particles/sec (too sparse for preview)
-// - With 5x: 0.408 particles/sec (1 every ~2.4 seconds) - better represents focused observation
 // Constants moved to emissionHelpers.ts
 
 export function ParticleEmitter3D({
@@ -4,11 +4,21 @@
 import type * as THREE from "three";
 import type { CompiledMinecraftExpr, MinecraftExprContext } from "@lib/particle/minecraftExpr";
 
-const ANIMATE_TICK_SAMPLE_COUNT = 667;
-const ANIMATE_TICK_SAMPLE_VOLUME = 32 * 32 * 32;
-const ANIMATE_TICK_BASE_CHANCE =
-  1 - Math.pow(1 - 1 / ANIMATE_TICK_SAMPLE_VOLUME, ANIMATE_TICK_SAMPLE_COUNT);
-export const ANIMATE_TICK_SAMPLE_CHANCE = ANIMATE_TICK_BASE_CHANCE * 5.0;
+const ANIMATE_TICK_ROUNDS = 667;
+const ANIMATE_TICK_RADII = [16, 32];
+
+/**
+ * Chance the block at the player's position is picked by animateTick in one
+ * tick (~16.7%). Each round takes one sample per radius r with every axis
+ * offset by nextInt(r) - nextInt(r), which lands on offset 0 with chance 1/r.
+ * Matches ANIMATE_TICK_SAMPLE_CHANCE in the backend's emitter_presets.rs.
+ */
+export const ANIMATE_TICK_SAMPLE_CHANCE =
+  1 -
+  Math.pow(
+    ANIMATE_TICK_RADII.reduce((miss, r) => miss * (1 - 1 / r ** 3), 1),
+    ANIMATE_TICK_ROUNDS,
+  );
 
 /**
  * Check if animateTick should fire this tick (Minecraft's random block sampling)
@@ -0,0 +1,144 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import {
+  getCacheMaintenanceSettings,
+  runCacheMaintenance,
+  formatError,
+  CACHE_MAINTENANCE_EVENT,
+  type MaintenanceReport,
+  type MaintenanceSettings,
+} from "@lib/tauri";
+import { saveCacheMaintenancePreference } from "@lib/cacheMaintenance";
+import s from "./styles.module.scss";
+
+type TtlField = "thumbnailTtlDays" | "decompiledTtlDays" | "tempTtlHours";
+
+const TTL_FIELDS: { field: TtlField; label: string }[] = [
+  { field: "thumbnailTtlDays", label: "Unused thumbnails (days)" },
+  { field: "decompiledTtlDays", label: "Unused decompiled sources (days)" },
+  { field: "tempTtlHours", label: "Leftover temp files (hours)" },
+];
+
+function formatSize(bytes: number): string {
+  const units = ["B", "KB", "MB", "GB"];
+  let size = bytes;
+  let unit = 0;
+  while (size >= 1024 && unit < units.length - 1) {
+    size /= 1024;
+    unit++;
+  }
+  return `${size.toFixed(unit === 0 ? 0 : 1)} ${units[unit]}`;
+}
+
+export const CacheMaintenance = () => {
+  const [settings, setSettings] = useState<MaintenanceSettings | null>(null);
+  const [report, setReport] = useState<MaintenanceReport | null>(null);
+  const [running, setRunning] = useState(false);
+  const [error, setError] = useState<string>();
+  const [saved, setSaved] = useState(false);
+
+  // Load the settings currently in effect on mount
+  useEffect(() => {
+    getCacheMaintenanceSettings()
+      .then(setSettings)
+      .catch((err) => setError(formatError(err)));
+  }, []);
+
+  // Show what background runs reclaim while the settings are open
+  useEffect(() => {
+    const unlistenPromise = listen<MaintenanceReport>(
+      CACHE_MAINTENANCE_EVENT,
+      (event) => setReport(event.payload),
+    );
+    return () => {
+      void unlistenPromise.then((fn) => fn());
+    };
+  }, []);
+
+  if (!settings) {
+    return error ? <div className={s.error}>{error}</div> : null;
+  }
+
+  const update = (changes: Partial<MaintenanceSettings>) => {
+    setSettings({ ...settings, ...changes });
+    setSaved(false);
+  };
+
+  const handleSave = async () => {
+    try {
+      setSettings(await saveCacheMaintenancePreference(settings));
+      setError(undefined);
+      setSaved(true);
+    } catch (err) {
+      setError(formatError(err));
+    }
+  };
+
+  const handleRun = async () => {
+    setRunning(true);
+    try {
+      setReport(await runCacheMaintenance());
+      setError(undefined);
+    } catch (err) {
+      setError(formatError(err));
+    } finally {
+      setRunning(false);
+    }
+  };
+
+  return (
+    <div className={s.root}>
+      <div className={s.header}>
+        <h3>Cache Maintenance</h3>
+        <p>
+          Every few hours, delete what hasn't been used for a while. A value of
+          0 keeps that cache. Run now applies the saved values.
+        </p>
+      </div>
+
+      {error && <div className={s.error}>{error}</div>}
+
+      <label className={s.toggle}>
+        <input
+          type="checkbox"
+          checked={settings.enabled}
+          onChange={(e) => update({ enabled: e.target.checked })}
+        />
+        <span>Clean up caches in the background</span>
+      </label>
+
+      <div className={s.fields}>
+        {TTL_FIELDS.map(({ field, label }) => (
+          <label key={field} className={s.field}>
+            <span>{label}</span>
+            <input
+              type="number"
+              min={0}
+              value={settings[field]}
+              onChange={(e) => update({ [field]: Number(e.target.value) })}
+            />
+          </label>
+        ))}
+      </div>
+
+      <div className={s.actions}>
+        <button className={s.saveButton} onClick={() => void handleSave()}>
+          Save
+        </button>
+        <button
+          className={s.saveButton}
+          disabled={running}
+          onClick={() => void handleRun()}
+        >
+          {running ? "Running..." : "Run now"}
+        </button>
+        {saved && <small className={s.note}>Saved</small>}
+        {report && (
+          <small className={s.note}>
+            Reclaimed {formatSize(report.reclaimedBytes)}
+          </small>
+        )}
+      </div>
+    </div>
+  );
+};
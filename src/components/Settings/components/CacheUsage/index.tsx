@@ -0,0 +1,118 @@
+import { useEffect, useState } from "react";
+import {
+  getCacheUsage,
+  clearCache,
+  formatError,
+  type CacheCategory,
+  type CacheUsage as CacheUsageReport,
+} from "@lib/tauri";
+import s from "./styles.module.scss";
+
+const CATEGORY_LABELS: Record<CacheCategory, string> = {
+  vanilla_textures: "Vanilla textures",
+  decompiled_sources: "Decompiled sources",
+  extracted_data: "Particle and animation data",
+  mappings: "Mojang mappings",
+  cfr: "CFR decompiler",
+};
+
+function formatSize(bytes: number): string {
+  const units = ["B", "KB", "MB", "GB"];
+  let size = bytes;
+  let unit = 0;
+  while (size >= 1024 && unit < units.length - 1) {
+    size /= 1024;
+    unit++;
+  }
+  return `${size.toFixed(unit === 0 ? 0 : 1)} ${units[unit]}`;
+}
+
+export const CacheUsage = () => {
+  const [usage, setUsage] = useState<CacheUsageReport | null>(null);
+  const [clearing, setClearing] = useState(false);
+  const [error, setError] = useState<string>();
+
+  const refresh = () =>
+    getCacheUsage()
+      .then(setUsage)
+      .catch((err) => setError(formatError(err)));
+
+  // Measure the caches on mount
+  useEffect(() => {
+    void refresh();
+  }, []);
+
+  const handleClear = async (category: CacheCategory, version?: string) => {
+    setClearing(true);
+    try {
+      await clearCache(category, version);
+      setError(undefined);
+    } catch (err) {
+      setError(formatError(err));
+    } finally {
+      setClearing(false);
+      void refresh();
+    }
+  };
+
+  return (
+    <div className={s.root}>
+      <div className={s.header}>
+        <h3>Cache Usage</h3>
+        <p>
+          Everything here is rebuilt when needed. Decompiled sources are the
+          largest and only needed to extract particle and animation data again.
+        </p>
+      </div>
+
+      {error && <div className={s.error}>{error}</div>}
+
+      {usage && (
+        <ul className={s.categories}>
+          {usage.categories.map((category) => (
+            <li key={category.category}>
+              <div className={s.row}>
+                <span>{CATEGORY_LABELS[category.category]}</span>
+                <span className={s.size}>{formatSize(category.bytes)}</span>
+                <button
+                  className={s.clearButton}
+                  disabled={clearing || category.bytes === 0}
+                  onClick={() => void handleClear(category.category)}
+                >
+                  Clear
+                </button>
+              </div>
+              {category.versions.length > 1 && (
+                <ul className={s.versions}>
+                  {category.versions.map((version) => (
+                    <li key={version.version} className={s.row}>
+                      <span>{version.version}</span>
+                      <span className={s.size}>
+                        {formatSize(version.bytes)}
+                      </span>
+                      <button
+                        className={s.clearButton}
+                        disabled={clearing}
+                        onClick={() =>
+                          void handleClear(category.category, version.version)
+                        }
+                      >
+                        Clear
+                      </button>
+                    </li>
+                  ))}
+                </ul>
+              )}
+            </li>
+          ))}
+          <li className={s.total}>
+            <div className={s.row}>
+              <span>Total</span>
+              <span className={s.size}>{formatSize(usage.totalBytes)}</span>
+            </div>
+          </li>
+        </ul>
+      )}
+    </div>
+  );
+};
@@ -0,0 +1,84 @@
+import { useEffect, useState } from "react";
+import {
+  getLogLevel,
+  getRecentLogs,
+  formatError,
+  type LogLevel,
+} from "@lib/tauri";
+import { saveLogLevelPreference } from "@lib/logLevel";
+import s from "./styles.module.scss";
+
+const LEVELS: LogLevel[] = ["error", "warn", "info", "debug", "trace", "off"];
+
+export const Diagnostics = () => {
+  const [level, setLevel] = useState<LogLevel | null>(null);
+  const [error, setError] = useState<string>();
+  const [copied, setCopied] = useState(false);
+
+  // Load the level currently in effect on mount
+  useEffect(() => {
+    getLogLevel()
+      .then(setLevel)
+      .catch((err) => setError(formatError(err)));
+  }, []);
+
+  const handleLevel = async (next: LogLevel) => {
+    try {
+      setLevel(await saveLogLevelPreference(next));
+      setError(undefined);
+    } catch (err) {
+      setError(formatError(err));
+    }
+  };
+
+  // Copy the recent log lines, ready to paste into a bug report
+  const handleCopy = async () => {
+    try {
+      const lines = await getRecentLogs(1000);
+      await navigator.clipboard.writeText(lines.join("\n"));
+      setError(undefined);
+      setCopied(true);
+    } catch (err) {
+      setError(formatError(err));
+    }
+  };
+
+  return (
+    <div className={s.root}>
+      <div className={s.header}>
+        <h3>Diagnostics</h3>
+        <p>
+          The backend keeps a week of logs in the cache folder. When reporting
+          a bug, copy the recent log and paste it into the report.
+        </p>
+      </div>
+
+      {error && <div className={s.error}>{error}</div>}
+
+      {level && (
+        <div className={s.fields}>
+          <label className={s.field}>
+            <span>Log level</span>
+            <select
+              value={level}
+              onChange={(e) => void handleLevel(e.target.value as LogLevel)}
+            >
+              {LEVELS.map((option) => (
+                <option key={option} value={option}>
+                  {option}
+                </option>
+              ))}
+            </select>
+          </label>
+        </div>
+      )}
+
+      <div className={s.actions}>
+        <button className={s.saveButton} onClick={() => void handleCopy()}>
+          Copy recent log
+        </button>
+        {copied && <small className={s.note}>Copied</small>}
+      </div>
+    </div>
+  );
+};
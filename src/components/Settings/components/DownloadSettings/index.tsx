@@ -0,0 +1,116 @@
+import { useEffect, useState } from "react";
+import {
+  getDownloadSettings,
+  formatError,
+  type DownloadSettings as Settings,
+} from "@lib/tauri";
+import { saveDownloadSettingsPreference } from "@lib/downloadSettings";
+import s from "./styles.module.scss";
+
+type UrlField = "metaMirror" | "dataMirror" | "toolsMirror" | "proxy";
+
+const URL_FIELDS: { field: UrlField; label: string; placeholder: string }[] = [
+  {
+    field: "metaMirror",
+    label: "Version metadata mirror",
+    placeholder: "https://piston-meta.mojang.com",
+  },
+  {
+    field: "dataMirror",
+    label: "Game files mirror",
+    placeholder: "https://piston-data.mojang.com",
+  },
+  {
+    field: "toolsMirror",
+    label: "Tools mirror",
+    placeholder: "https://github.com",
+  },
+  { field: "proxy", label: "Proxy", placeholder: "http://proxy:8080" },
+];
+
+export const DownloadSettings = () => {
+  const [settings, setSettings] = useState<Settings | null>(null);
+  const [error, setError] = useState<string>();
+  const [saved, setSaved] = useState(false);
+
+  // Load the settings currently in effect on mount
+  useEffect(() => {
+    getDownloadSettings()
+      .then(setSettings)
+      .catch((err) => setError(formatError(err)));
+  }, []);
+
+  if (!settings) {
+    return error ? <div className={s.error}>{error}</div> : null;
+  }
+
+  const update = (changes: Partial<Settings>) => {
+    setSettings({ ...settings, ...changes });
+    setSaved(false);
+  };
+
+  const handleSave = async () => {
+    try {
+      setSettings(await saveDownloadSettingsPreference(settings));
+      setError(undefined);
+      setSaved(true);
+    } catch (err) {
+      setError(formatError(err));
+    }
+  };
+
+  return (
+    <div className={s.root}>
+      <div className={s.header}>
+        <h3>Downloads</h3>
+        <p>
+          Mappings and the decompiler are downloaded for particle and animation
+          extraction. If Mojang or GitHub are blocked on your network, point
+          them at a mirror or go through a proxy.
+        </p>
+      </div>
+
+      {error && <div className={s.error}>{error}</div>}
+
+      <div className={s.fields}>
+        {URL_FIELDS.map(({ field, label, placeholder }) => (
+          <label key={field} className={s.field}>
+            <span>{label}</span>
+            <input
+              type="text"
+              value={settings[field] ?? ""}
+              placeholder={placeholder}
+              onChange={(e) => update({ [field]: e.target.value || null })}
+            />
+          </label>
+        ))}
+        <label className={s.field}>
+          <span>Timeout (seconds)</span>
+          <input
+            type="number"
+            min={1}
+            value={settings.timeoutSecs}
+            onChange={(e) => update({ timeoutSecs: Number(e.target.value) })}
+          />
+        </label>
+        <label className={s.field}>
+          <span>Retries</span>
+          <input
+            type="number"
+            min={0}
+            max={10}
+            value={settings.retries}
+            onChange={(e) => update({ retries: Number(e.target.value) })}
+          />
+        </label>
+      </div>
+
+      <div className={s.actions}>
+        <button className={s.saveButton} onClick={() => void handleSave()}>
+          Save
+        </button>
+        {saved && <small className={s.note}>Saved</small>}
+      </div>
+    </div>
+  );
+};
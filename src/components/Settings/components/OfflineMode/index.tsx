@@ -0,0 +1,75 @@
+import { useEffect, useState } from "react";
+import { getOfflineStatus, formatError, type OfflineStatus } from "@lib/tauri";
+import { saveOfflinePreference } from "@lib/offlineMode";
+import s from "./styles.module.scss";
+
+type DatasetStatus = OfflineStatus["datasets"][number];
+
+const DATASET_LABELS: Record<DatasetStatus["dataset"], string> = {
+  particle_physics: "Particle physics",
+  block_emissions: "Block particles",
+  block_animations: "Block animations",
+};
+
+const SOURCE_LABELS: Record<DatasetStatus["source"], string> = {
+  cached: "Extracted",
+  bundled: "Bundled fallback",
+  unavailable: "Unavailable",
+};
+
+export const OfflineMode = () => {
+  const [status, setStatus] = useState<OfflineStatus | null>(null);
+  const [error, setError] = useState<string>();
+
+  // Load the mode and dataset sources currently in effect on mount
+  useEffect(() => {
+    getOfflineStatus()
+      .then(setStatus)
+      .catch((err) => setError(formatError(err)));
+  }, []);
+
+  const handleToggle = async (enabled: boolean) => {
+    try {
+      await saveOfflinePreference(enabled);
+      setStatus(await getOfflineStatus());
+      setError(undefined);
+    } catch (err) {
+      setError(formatError(err));
+    }
+  };
+
+  return (
+    <div className={s.root}>
+      <label className={s.toggle}>
+        <input
+          type="checkbox"
+          checked={status?.offlineMode ?? false}
+          onChange={(e) => void handleToggle(e.target.checked)}
+        />
+        <span>
+          <strong>Offline mode</strong>
+          <small>
+            Never download mappings or tools. Particle and animation data
+            that hasn't been extracted yet comes from the copy bundled with
+            the app.
+          </small>
+        </span>
+      </label>
+
+      {error && <div className={s.error}>{error}</div>}
+
+      {status?.version && (
+        <ul className={s.datasets}>
+          {status.datasets.map((dataset) => (
+            <li key={dataset.dataset} title={dataset.reason ?? undefined}>
+              {DATASET_LABELS[dataset.dataset]} ({status.version}):{" "}
+              <span className={s[dataset.source]}>
+                {SOURCE_LABELS[dataset.source]}
+              </span>
+            </li>
+          ))}
+        </ul>
+      )}
+    </div>
+  );
+};
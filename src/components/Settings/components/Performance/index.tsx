@@ -0,0 +1,123 @@
+import { useEffect, useState } from "react";
+import { useSelectPacksDir } from "@state/selectors";
+import { getLowMemoryMode, runPerfSelftest } from "@lib/tauri";
+import type { PerfReport } from "@lib/tauri";
+import { saveLowMemoryPreference } from "@lib/lowMemoryMode";
+import s from "./styles.module.scss";
+
+export const Performance = () => {
+  const [lowMemory, setLowMemory] = useState(false);
+  const [loading, setLoading] = useState(true);
+  const [error, setError] = useState<string | null>(null);
+  const packsDir = useSelectPacksDir();
+  const [report, setReport] = useState<PerfReport | null>(null);
+  const [testing, setTesting] = useState(false);
+
+  // Load the mode currently in effect on mount
+  useEffect(() => {
+    const loadMode = async () => {
+      try {
+        setLowMemory(await getLowMemoryMode());
+      } catch (err) {
+        console.error("[Performance] Failed to load low-memory mode:", err);
+      } finally {
+        setLoading(false);
+      }
+    };
+    void loadMode();
+  }, []);
+
+  const handleToggle = async (enabled: boolean) => {
+    setError(null);
+    try {
+      setLowMemory(await saveLowMemoryPreference(enabled));
+    } catch (err) {
+      console.error("[Performance] Failed to set low-memory mode:", err);
+      setError("Failed to change low-memory mode");
+    }
+  };
+
+  // Measure this machine and use the recommended thread count from now on
+  const handleSelftest = async () => {
+    if (!packsDir) return;
+    setError(null);
+    setTesting(true);
+    try {
+      setReport(await runPerfSelftest(packsDir, true));
+    } catch (err) {
+      console.error("[Performance] Self-test failed:", err);
+      setError("Performance self-test failed");
+    } finally {
+      setTesting(false);
+    }
+  };
+
+  const formatSpeed = (speed: number | null) =>
+    speed === null ? "n/a" : `${speed.toFixed(0)} MB/s`;
+
+  return (
+    <div className={s.root}>
+      <div className={s.header}>
+        <h3>Performance</h3>
+        <p>
+          Trade speed for memory on machines where large scans or builds run
+          out of RAM.
+        </p>
+      </div>
+
+      {error && <div className={s.error}>{error}</div>}
+
+      {loading ? (
+        <div className={s.loading}>Loading...</div>
+      ) : (
+        <label className={s.toggle}>
+          <input
+            type="checkbox"
+            checked={lowMemory}
+            onChange={(e) => void handleToggle(e.target.checked)}
+          />
+          <span>
+            <strong>Low-memory mode</strong>
+            <small>
+              Processes packs one at a time, skips loading pack icons and
+              streams files while building. Takes effect on the next scan.
+            </small>
+          </span>
+        </label>
+      )}
+
+      <div className={s.selftest}>
+        <button
+          className={s.selftestButton}
+          onClick={() => void handleSelftest()}
+          disabled={!packsDir || testing}
+        >
+          {testing ? "Measuring..." : "Run performance self-test"}
+        </button>
+        {!packsDir && <small>Select a packs folder first.</small>}
+
+        {report && (
+          <div className={s.report}>
+            <p>
+              {report.threadCount} cores · disk{" "}
+              {formatSpeed(report.diskReadMbPerSec)} · ZIP{" "}
+              {formatSpeed(report.zipExtractMbPerSec)}
+            </p>
+            <p>
+              Now using <strong>{report.recommended.threadPoolSize}</strong>{" "}
+              thread(s) for scans and builds.
+              {report.recommended.lowMemoryMode &&
+                !lowMemory &&
+                " Low-memory mode is recommended for this machine."}
+            </p>
+            <ul>
+              {report.recommended.reasons.map((reason) => (
+                <li key={reason}>{reason}</li>
+              ))}
+            </ul>
+          </div>
+        )}
+      </div>
+    </div>
+  );
+};
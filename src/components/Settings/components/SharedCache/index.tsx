@@ -0,0 +1,87 @@
+import { useEffect, useState } from "react";
+import {
+  getSharedCacheStatus,
+  openFolderDialog,
+  formatError,
+  type SharedCacheStatus,
+} from "@lib/tauri";
+import { saveSharedCachePreference } from "@lib/sharedCache";
+import s from "./styles.module.scss";
+
+export const SharedCache = () => {
+  const [status, setStatus] = useState<SharedCacheStatus | null>(null);
+  const [error, setError] = useState<string>();
+
+  // Load the locations currently in effect on mount
+  useEffect(() => {
+    getSharedCacheStatus()
+      .then(setStatus)
+      .catch((err) => setError(formatError(err)));
+  }, []);
+
+  const handleBrowse = async () => {
+    try {
+      const selected = await openFolderDialog();
+      if (!selected) return;
+      setStatus(await saveSharedCachePreference(selected));
+      setError(undefined);
+    } catch (err) {
+      setError(formatError(err));
+    }
+  };
+
+  const handleClear = async () => {
+    try {
+      setStatus(await saveSharedCachePreference(null));
+      setError(undefined);
+    } catch (err) {
+      setError(formatError(err));
+    }
+  };
+
+  return (
+    <div className={s.root}>
+      <div className={s.header}>
+        <h3>Shared Cache</h3>
+        <p>
+          Keep extracted vanilla textures and decompiled sources in a folder
+          several computers can reach, such as a network drive, so each version
+          is only extracted once.
+        </p>
+      </div>
+
+      {error && <div className={s.error}>{error}</div>}
+
+      <div className={s.location}>
+        <input
+          type="text"
+          value={status?.sharedPath ?? ""}
+          placeholder="Not shared (this computer only)"
+          readOnly
+          className={s.pathInput}
+        />
+        <button
+          className={s.browseButton}
+          onClick={() => void handleBrowse()}
+        >
+          Browse
+        </button>
+        {status?.sharedPath && (
+          <button
+            className={s.clearButton}
+            onClick={() => void handleClear()}
+          >
+            Stop sharing
+          </button>
+        )}
+      </div>
+
+      {status?.sharedPath && !status.writable && (
+        <small className={s.note}>
+          This folder is read-only here. Versions already in it are used;
+          others are extracted to {status.localPath}.
+        </small>
+      )}
+    </div>
+  );
+};
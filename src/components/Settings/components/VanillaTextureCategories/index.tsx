@@ -0,0 +1,84 @@
+import { useEffect, useState } from "react";
+import {
+  getVanillaTextureCategories,
+  initializeVanillaTextures,
+  formatError,
+  type VanillaTextureCategory,
+} from "@lib/tauri";
+import { saveTextureCategories } from "@lib/vanillaTextureCategories";
+import s from "./styles.module.scss";
+
+const CATEGORIES: { id: VanillaTextureCategory; label: string }[] = [
+  { id: "entity", label: "Entities (mob skins)" },
+  { id: "gui", label: "GUI sprites" },
+  { id: "particle", label: "Particles" },
+  { id: "mob_effect", label: "Status effect icons" },
+  { id: "misc", label: "Misc overlays" },
+  { id: "other", label: "Everything else (environment, paintings, ...)" },
+];
+
+export const VanillaTextureCategories = () => {
+  const [enabled, setEnabled] = useState<VanillaTextureCategory[]>([]);
+  const [extracting, setExtracting] = useState(false);
+  const [error, setError] = useState<string>();
+
+  // Load the categories currently in effect on mount
+  useEffect(() => {
+    getVanillaTextureCategories()
+      .then(setEnabled)
+      .catch((err) => setError(formatError(err)));
+  }, []);
+
+  const handleToggle = async (
+    category: VanillaTextureCategory,
+    checked: boolean,
+  ) => {
+    const next = checked
+      ? [...enabled, category]
+      : enabled.filter((id) => id !== category);
+    try {
+      setEnabled(await saveTextureCategories(next));
+      setError(undefined);
+      if (checked) {
+        // Extracts the newly enabled category into the cache
+        setExtracting(true);
+        await initializeVanillaTextures();
+      }
+    } catch (err) {
+      setError(formatError(err));
+    } finally {
+      setExtracting(false);
+    }
+  };
+
+  return (
+    <div className={s.root}>
+      <div className={s.header}>
+        <h3>Vanilla Textures to Extract</h3>
+        <p>
+          Block, item and colormap textures are always extracted. The others
+          are needed for entity previews and for browsing GUI and particle
+          sprites.
+        </p>
+      </div>
+
+      {error && <div className={s.error}>{error}</div>}
+
+      <div className={s.categories}>
+        {CATEGORIES.map(({ id, label }) => (
+          <label key={id} className={s.category}>
+            <input
+              type="checkbox"
+              checked={enabled.includes(id)}
+              disabled={extracting}
+              onChange={(e) => void handleToggle(id, e.target.checked)}
+            />
+            <span>{label}</span>
+          </label>
+        ))}
+      </div>
+
+      {extracting && <small className={s.note}>Extracting textures…</small>}
+    </div>
+  );
+};
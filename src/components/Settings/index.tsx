@@ -16,6 +16,7 @@ export const Settings = ({
   minecraftTab,
   vanillaVersionTab,
   targetVersionTab,
+  performanceTab,
 }: SettingsProps) => {
   const handleOpenChange = (open: boolean) => {
     if (!open) {
@@ -53,6 +54,7 @@ export const Settings = ({
                 Vanilla Textures
               </TabsTrigger>
               <TabsTrigger value="target-version">Target Version</TabsTrigger>
+              <TabsTrigger value="performance">Performance</TabsTrigger>
             </TabsList>
 
             <TabsContent value="minecraft">{minecraftTab}</TabsContent>
@@ -60,6 +62,7 @@ export const Settings = ({
               {vanillaVersionTab}
             </TabsContent>
             <TabsContent value="target-version">{targetVersionTab}</TabsContent>
+            <TabsContent value="performance">{performanceTab}</TabsContent>
           </Tabs>
         </div>
       </DrawerContent>
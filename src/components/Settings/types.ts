@@ -6,4 +6,5 @@ export interface SettingsProps {
     minecraftTab: ReactNode;
     vanillaVersionTab: ReactNode;
     targetVersionTab: ReactNode;
+    performanceTab: ReactNode;
 }
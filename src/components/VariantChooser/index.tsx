@@ -5,6 +5,8 @@ import { readBlockModel, type BlockModel } from "@lib/tauri/blockModels";
 import { resolveTextureRef } from "@lib/utils/blockGeometry";
 import { useSelectPacksDir } from "@state/selectors";
 import { useStore } from "@/state/store";
+import { resolutionLabel } from "@lib/utils/textureResolution";
+import type { TextureInfo } from "@state";
 import type { VariantChooserProps } from "./types";
 import s from "./styles.module.scss";
 
@@ -48,6 +50,9 @@ const pickModelTextureId = (
   return null;
 };
 
+const describeTexture = (info: TextureInfo): string =>
+  `${info.width}×${info.height}, ${info.bitDepth}-bit`;
+
 export const VariantChooser = ({
   providers,
   onSelectProvider,
@@ -55,6 +60,9 @@ export const VariantChooser = ({
 }: VariantChooserProps) => {
   const packs = useStore((state) => state.packs);
   const packsDir = useSelectPacksDir();
+  const textureInfo = useStore((state) =>
+    assetId ? state.textureInfoByAsset[assetId] : undefined,
+  );
   const [textureUrls, setTextureUrls] = useState<Record<string, string>>({});
   const [loadingTextures, setLoadingTextures] = useState<Record<string, boolean>>({});
 
@@ -172,6 +180,17 @@ export const VariantChooser = ({
                 {provider.isWinner && !provider.isPenciled && (
                   <span className={s.badge}>Active</span>
                 )}
+                {textureInfo?.[provider.packId] && (
+                  <span
+                    className={`${s.badge} ${s.resolutionBadge}`}
+                    title={describeTexture(textureInfo[provider.packId])}
+                  >
+                    {resolutionLabel(
+                      textureInfo[provider.packId],
+                      textureInfo["minecraft:vanilla"],
+                    )}
+                  </span>
+                )}
               </div>
             </div>
           </div>
@@ -8,7 +8,7 @@ export interface ParticleEmission {
   particleId: string;
   condition?: string;
   /** Which method this emission comes from (determines call rate):
-   * - "animateTick": ~2% per tick (random block sampling)
+   * - "animateTick": ~17% per tick near the player (random block sampling)
    * - "particleTick": 100% per tick (called every tick)
    */
   emissionSource?: string;
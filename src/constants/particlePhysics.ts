@@ -121,6 +121,11 @@ export function setExtractedPhysics(data: ExtractedPhysicsData): void {
   console.log(
     `[particlePhysics] Set extracted physics for ${data.version} (${Object.keys(data.particles).length} particles)`,
   );
+  if (data.partial) {
+    console.warn(
+      "[particlePhysics] Physics are partial (read without Java); install Java and re-extract for full particle behavior.",
+    );
+  }
 }
 
 /**
@@ -0,0 +1,49 @@
+/**
+ * Cache maintenance preference
+ *
+ * The backend keeps the TTLs in memory only, so the user's choice is saved in
+ * localStorage and pushed to the backend on startup, before the background
+ * task first runs.
+ */
+import { setCacheMaintenanceSettings } from "@lib/tauri";
+import type { MaintenanceSettings } from "@lib/tauri";
+
+const STORAGE_KEY = "weaverbird_cache_maintenance";
+
+/**
+ * Saved maintenance settings (null when never changed)
+ */
+export function loadCacheMaintenancePreference(): MaintenanceSettings | null {
+  try {
+    const stored = localStorage.getItem(STORAGE_KEY);
+    return stored ? (JSON.parse(stored) as MaintenanceSettings) : null;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Apply the settings to the backend and save them once accepted
+ * @returns The settings now in effect
+ */
+export async function saveCacheMaintenancePreference(
+  settings: MaintenanceSettings,
+): Promise<MaintenanceSettings> {
+  const applied = await setCacheMaintenanceSettings(settings);
+  try {
+    localStorage.setItem(STORAGE_KEY, JSON.stringify(applied));
+  } catch (error) {
+    console.warn("[cacheMaintenance] Failed to save preference:", error);
+  }
+  return applied;
+}
+
+/**
+ * Apply the saved settings to the backend; call on startup
+ */
+export async function applyStoredCacheMaintenance(): Promise<void> {
+  const settings = loadCacheMaintenancePreference();
+  if (settings) {
+    await setCacheMaintenanceSettings(settings);
+  }
+}
@@ -0,0 +1,49 @@
+/**
+ * Download mirror and proxy preference
+ *
+ * The backend keeps the settings in memory only, so the user's choice is saved
+ * in localStorage and pushed to the backend on startup, before anything is
+ * downloaded.
+ */
+import { setDownloadSettings } from "@lib/tauri";
+import type { DownloadSettings } from "@lib/tauri";
+
+const STORAGE_KEY = "weaverbird_download_settings";
+
+/**
+ * Saved download settings (null when never changed)
+ */
+export function loadDownloadSettingsPreference(): DownloadSettings | null {
+  try {
+    const stored = localStorage.getItem(STORAGE_KEY);
+    return stored ? (JSON.parse(stored) as DownloadSettings) : null;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Apply the settings to the backend and save them once accepted
+ * @returns The settings now in effect
+ */
+export async function saveDownloadSettingsPreference(
+  settings: DownloadSettings,
+): Promise<DownloadSettings> {
+  const applied = await setDownloadSettings(settings);
+  try {
+    localStorage.setItem(STORAGE_KEY, JSON.stringify(applied));
+  } catch (error) {
+    console.warn("[downloadSettings] Failed to save preference:", error);
+  }
+  return applied;
+}
+
+/**
+ * Apply the saved settings to the backend; call before any extraction
+ */
+export async function applyStoredDownloadSettings(): Promise<void> {
+  const settings = loadDownloadSettingsPreference();
+  if (settings) {
+    await setDownloadSettings(settings);
+  }
+}
@@ -31,6 +31,32 @@ function seededRandom(seed: number): number {
   return ((t ^ (t >>> 14)) >>> 0) / 4294967296;
 }
 
+/**
+ * Wrap an angle into [-halfTurn, halfTurn).
+ */
+function wrapAngle(angle: number, halfTurn: number): number {
+  const turn = 2 * halfTurn;
+  return ((((angle + halfTurn) % turn) + turn) % turn) - halfTurn;
+}
+
+/**
+ * EMF keyframe(k, v0, v1, ...): linear through the values, k indexing them.
+ * Looping runs the last value back into the first.
+ */
+function keyframe(args: number[], loop: boolean): number {
+  const frames = args.slice(1);
+  if (frames.length === 0) return 0;
+  const count = frames.length;
+  const k = loop
+    ? ((args[0] % count) + count) % count
+    : Math.max(0, Math.min(count - 1, args[0]));
+  const index = Math.min(Math.floor(k), count - 1);
+  const next = loop
+    ? frames[(index + 1) % count]
+    : frames[Math.min(index + 1, count - 1)];
+  return frames[index] + (next - frames[index]) * (k - index);
+}
+
 // ============================================================================
 // Built-in Functions
 // ============================================================================
@@ -120,6 +146,30 @@ const BUILTIN_FUNCTIONS: Record<string, BuiltinFunction> = {
   // Debug functions (no-op, return first arg or 0)
   print: (args) => args[0] ?? 0,
   printb: (args) => args[0] ?? 0,
+
+  // EMF extensions
+  keyframe: (args) => keyframe(args, false),
+  keyframeloop: (args) => keyframe(args, true),
+  catmullrom: ([t, p0, p1, p2, p3]) =>
+    0.5 *
+    (2 * p1 +
+      (p2 - p0) * t +
+      (2 * p0 - 5 * p1 + 4 * p2 - p3) * t * t +
+      (3 * p1 - p0 - 3 * p2 + p3) * t * t * t),
+  quadbezier: ([t, p0, p1, p2]) =>
+    (1 - t) * (1 - t) * p0 + 2 * (1 - t) * t * p1 + t * t * p2,
+  cubicbezier: ([t, p0, p1, p2, p3]) =>
+    (1 - t) ** 3 * p0 +
+    3 * (1 - t) ** 2 * t * p1 +
+    3 * (1 - t) * t * t * p2 +
+    t ** 3 * p3,
+  wrapdeg: (args) => wrapAngle(args[0], 180),
+  wraprad: (args) => wrapAngle(args[0], Math.PI),
+  // Shortest turn from the first angle to the second
+  degdiff: (args) => wrapAngle(args[1] - args[0], 180),
+  raddiff: (args) => wrapAngle(args[1] - args[0], Math.PI),
+  randomb: (args, context) =>
+    BUILTIN_FUNCTIONS.random(args, context) < 0.5 ? 1 : 0,
 };
 
 // ============================================================================
@@ -464,6 +464,18 @@ describe("Expression Evaluator", () => {
     const context = createTestContext();
     expect(evaluateAST(parseExpression("5 / 0"), context)).toBe(0);
   });
+
+  it("should evaluate EMF keyframe and angle functions", () => {
+    const context = createTestContext();
+    const run = (source: string) =>
+      evaluateAST(parseExpression(source), context);
+    expect(run("keyframe(1.5, 0, 10, 30)")).toBe(20);
+    expect(run("keyframe(7, 0, 10, 30)")).toBe(30);
+    expect(run("keyframeloop(2.5, 0, 10, 30)")).toBe(15);
+    expect(run("catmullrom(0, 5, 1, 2, 9)")).toBe(1);
+    expect(run("cubicbezier(1, 0, 4, 4, 8)")).toBe(8);
+    expect(run("wrapdeg(270) + degdiff(350, 10)")).toBe(-70);
+  });
 });
 
 describe("Compile Expression", () => {
@@ -55,6 +55,18 @@ export const FUNCTIONS = new Set([
   // Debug (no-op in our implementation)
   "print",
   "printb",
+
+  // EMF extensions (Fresh Animations and similar packs)
+  "keyframe",
+  "keyframeloop",
+  "catmullrom",
+  "quadbezier",
+  "cubicbezier",
+  "wrapdeg",
+  "wraprad",
+  "degdiff",
+  "raddiff",
+  "randomb",
 ]);
 
 /** Known constants */
@@ -0,0 +1,130 @@
+import type { ParsedPart } from "./jemLoader";
+
+/**
+ * Attachment points of held items and armor
+ *
+ * Vanilla renders held items and head items in a bone's space with a fixed
+ * offset (ItemInHandLayer, CustomHeadLayer), and armor as a second copy of
+ * the biped bones, inflated. These are the same transforms converted to the
+ * parsed model's Blockbench space (pixels, Y up, front towards -Z), so the
+ * previewer can add an item as a child of the bone's group: position, then
+ * rotation (XYZ order, degrees), then scale. The item model's own display
+ * transform (thirdperson_righthand, head, ...) is applied on top of that.
+ */
+
+export type AttachmentSlot = "right_hand" | "left_hand" | "head";
+
+export interface EntityAttachment {
+  slot: AttachmentSlot;
+  /** Bone the item follows, as named in the model */
+  bone: string;
+  /** Offset from the bone's pivot in pixels */
+  position: [number, number, number];
+  /** Rotation in degrees */
+  rotation: [number, number, number];
+  scale: number;
+}
+
+export type ArmorSlot = "head" | "chest" | "legs" | "feet";
+
+export interface ArmorAttachment {
+  slot: ArmorSlot;
+  /** Bones whose boxes the armor layer copies */
+  bones: string[];
+  /** Pixels added on every side of those boxes */
+  inflate: number;
+  /** Armor texture layer: leggings use the inner one */
+  layer: "outer" | "inner";
+}
+
+export interface EntityAttachments {
+  items: EntityAttachment[];
+  armor: ArmorAttachment[];
+}
+
+/** Vanilla biped armor: helmet, chestplate and boots share the outer layer */
+const BIPED_ARMOR: ArmorAttachment[] = [
+  { slot: "head", bones: ["head", "headwear"], inflate: 1, layer: "outer" },
+  {
+    slot: "chest",
+    bones: ["body", "right_arm", "left_arm"],
+    inflate: 1,
+    layer: "outer",
+  },
+  {
+    slot: "legs",
+    bones: ["body", "right_leg", "left_leg"],
+    inflate: 0.5,
+    layer: "inner",
+  },
+  {
+    slot: "feet",
+    bones: ["right_leg", "left_leg"],
+    inflate: 1,
+    layer: "outer",
+  },
+];
+
+function collectPartNames(parts: ParsedPart[], names: Set<string>): void {
+  for (const part of parts) {
+    names.add(part.name);
+    collectPartNames(part.children, names);
+  }
+}
+
+/**
+ * Find the attachment points a parsed model offers
+ *
+ * Held items need arms named right_arm/left_arm, head items a head, and armor
+ * the full biped rig (head, body, arms, legs), as in zombies, skeletons,
+ * piglins, players and armor stands.
+ */
+export function resolveAttachments(parts: ParsedPart[]): EntityAttachments {
+  const names = new Set<string>();
+  collectPartNames(parts, names);
+  const items: EntityAttachment[] = [];
+
+  // ItemInHandLayer: the arm's transform, then rotate X -90 and Y 180, then
+  // translate (±1, 2, -10) pixels in model space (Y down, X mirrored)
+  for (const [slot, bone, side] of [
+    ["right_hand", "right_arm", 1],
+    ["left_hand", "left_arm", -1],
+  ] as const) {
+    if (!names.has(bone)) continue;
+    items.push({
+      slot,
+      bone,
+      position: [side, -10, -2],
+      rotation: [-90, 0, 0],
+      scale: 1,
+    });
+  }
+
+  // CustomHeadLayer: 4 pixels up from the neck pivot, at 0.625 scale
+  if (names.has("head")) {
+    items.push({
+      slot: "head",
+      bone: "head",
+      position: [0, 4, 0],
+      rotation: [0, 0, 0],
+      scale: 0.625,
+    });
+  }
+
+  const isBiped = [
+    "head",
+    "body",
+    "right_arm",
+    "left_arm",
+    "right_leg",
+    "left_leg",
+  ].every((name) => names.has(name));
+  const armor = isBiped
+    ? BIPED_ARMOR.map((piece) => ({
+        ...piece,
+        bones: piece.bones.filter((bone) => names.has(bone)),
+      }))
+    : [];
+
+  return { items, armor };
+}
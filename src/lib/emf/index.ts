@@ -6,6 +6,12 @@ export {
   mergeVariantTextures,
   applyVariantPartMask,
 } from "./jemLoader";
+export { resolveAttachments } from "./attachments";
+export type {
+  EntityAttachment,
+  EntityAttachments,
+  ArmorAttachment,
+} from "./attachments";
 export { jemToThreeJS } from "./jemThreeJSConverter";
 export type {
   JEMFile,
@@ -136,6 +136,24 @@ describe("JEM Loader", () => {
       expect(body.boxes[0].texturePath).toBe("minecraft:textures/entity/bar.png");
       expect(body.boxes[0].textureSize).toEqual([16, 16]);
     });
+
+    it("should expose hand, head and armor attachments of biped models", () => {
+      const skeleton = parseJEM(skeletonJEM as JEMFile);
+      const slots = skeleton.attachments.items.map((a) => a.slot);
+      expect(slots).toEqual(["right_hand", "left_hand", "head"]);
+      expect(skeleton.attachments.items[0].bone).toBe("right_arm");
+      expect(skeleton.attachments.armor.map((a) => a.slot)).toEqual([
+        "head",
+        "chest",
+        "legs",
+        "feet",
+      ]);
+
+      // A cow has a head but no hands, and doesn't wear armor
+      const cow = parseJEM(cowJEM as JEMFile);
+      expect(cow.attachments.items.map((a) => a.slot)).toEqual(["head"]);
+      expect(cow.attachments.armor).toEqual([]);
+    });
   });
 
   describe("jemToThreeJS", () => {
@@ -3,6 +3,8 @@ import { parseModelPart } from "./jemParsing";
 import { jemToThreeJS } from "./jemThreeJSConverter";
 import { log } from "./jemUtilities";
 import { mergeBox } from "./jemLoaderUtils";
+import { resolveAttachments } from "./attachments";
+import type { EntityAttachments } from "./attachments";
 
 /**
  * JEM Loader - Parses OptiFine Entity Models following Blockbench's exact logic
@@ -81,6 +83,8 @@ export interface ParsedEntityModel {
   shadowSize: number;
   parts: ParsedPart[];
   animations?: AnimationLayer[];
+  /** Where held items, head items and armor go */
+  attachments: EntityAttachments;
 }
 
 export interface ParseOptions {
@@ -185,6 +189,7 @@ export function parseJEM(
     textureSize,
     shadowSize,
     parts,
+    attachments: resolveAttachments(parts),
   };
   if (animations.length > 0) result.animations = animations;
   return result;
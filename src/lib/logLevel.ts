@@ -0,0 +1,47 @@
+/**
+ * Backend log level preference
+ *
+ * The backend keeps the level in memory only, so the user's choice is saved in
+ * localStorage and pushed to the backend on startup.
+ */
+import { setLogLevel } from "@lib/tauri";
+import type { LogLevel } from "@lib/tauri";
+
+const STORAGE_KEY = "weaverbird_log_level";
+
+/**
+ * Saved log level (null when never changed)
+ */
+export function loadLogLevelPreference(): LogLevel | null {
+  try {
+    return localStorage.getItem(STORAGE_KEY) as LogLevel | null;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Apply the level to the backend and save it once accepted
+ * @returns The level now in effect
+ */
+export async function saveLogLevelPreference(
+  level: LogLevel,
+): Promise<LogLevel> {
+  const applied = await setLogLevel(level);
+  try {
+    localStorage.setItem(STORAGE_KEY, applied);
+  } catch (error) {
+    console.warn("[logLevel] Failed to save preference:", error);
+  }
+  return applied;
+}
+
+/**
+ * Apply the saved level to the backend; call on startup
+ */
+export async function applyStoredLogLevel(): Promise<void> {
+  const level = loadLogLevelPreference();
+  if (level) {
+    await setLogLevel(level);
+  }
+}
@@ -0,0 +1,44 @@
+/**
+ * Low-memory mode preference
+ *
+ * The backend keeps the mode in memory only, so the user's choice is saved in
+ * localStorage and pushed to the backend on startup.
+ */
+import { setLowMemoryMode } from "@lib/tauri";
+
+const STORAGE_KEY = "weaverbird_low_memory_mode";
+
+/**
+ * Saved low-memory preference (off when never set)
+ */
+export function loadLowMemoryPreference(): boolean {
+  try {
+    return localStorage.getItem(STORAGE_KEY) === "true";
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Save the preference and apply it to the backend
+ * @returns The mode now in effect
+ */
+export async function saveLowMemoryPreference(
+  enabled: boolean,
+): Promise<boolean> {
+  try {
+    localStorage.setItem(STORAGE_KEY, String(enabled));
+  } catch (error) {
+    console.warn("[lowMemoryMode] Failed to save preference:", error);
+  }
+  return setLowMemoryMode(enabled);
+}
+
+/**
+ * Apply the saved preference to the backend; call before the first scan
+ */
+export async function applyStoredLowMemoryMode(): Promise<void> {
+  if (loadLowMemoryPreference()) {
+    await setLowMemoryMode(true);
+  }
+}
@@ -0,0 +1,44 @@
+/**
+ * Offline mode preference
+ *
+ * The backend keeps the mode in memory only, so the user's choice is saved in
+ * localStorage and pushed to the backend on startup.
+ */
+import { setOfflineMode } from "@lib/tauri";
+
+const STORAGE_KEY = "weaverbird_offline_mode";
+
+/**
+ * Saved offline preference (off when never set)
+ */
+export function loadOfflinePreference(): boolean {
+  try {
+    return localStorage.getItem(STORAGE_KEY) === "true";
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Save the preference and apply it to the backend
+ * @returns The mode now in effect
+ */
+export async function saveOfflinePreference(
+  enabled: boolean,
+): Promise<boolean> {
+  try {
+    localStorage.setItem(STORAGE_KEY, String(enabled));
+  } catch (error) {
+    console.warn("[offlineMode] Failed to save preference:", error);
+  }
+  return setOfflineMode(enabled);
+}
+
+/**
+ * Apply the saved preference to the backend; call before any extraction
+ */
+export async function applyStoredOfflineMode(): Promise<void> {
+  if (loadOfflinePreference()) {
+    await setOfflineMode(true);
+  }
+}
@@ -7,37 +7,20 @@
  * Data sourced from: https://minecraft.wiki/w/Pack_format
  */
 
+import packFormats from "../../src-tauri/data/pack_formats.json";
+
 /**
  * Resource Pack format data from Minecraft wiki
  * Maps pack format numbers to their corresponding Minecraft version ranges
  *
+ * Read from src-tauri/data/pack_formats.json, the table the backend's
+ * pack_format.rs is built from, so both sides always agree.
  * Source: https://minecraft.wiki/w/Pack_format (Resource Pack section)
- * Last updated: 2025-11-27
  */
-const PACK_FORMAT_DATA = [
-  { packFormat: 1, versions: "1.6.1 – 1.8.9" },
-  { packFormat: 2, versions: "1.9 – 1.10.2" },
-  { packFormat: 3, versions: "1.11 – 1.12.2" },
-  { packFormat: 4, versions: "1.13 – 1.14.4" },
-  { packFormat: 5, versions: "1.15 – 1.16.1" },
-  { packFormat: 6, versions: "1.16.2 – 1.16.5" },
-  { packFormat: 7, versions: "1.17 – 1.17.1" },
-  { packFormat: 8, versions: "1.18 – 1.18.2" },
-  { packFormat: 9, versions: "1.19 – 1.19.2" },
-  { packFormat: 12, versions: "1.19.3" },
-  { packFormat: 13, versions: "1.19.4" },
-  { packFormat: 15, versions: "1.20 – 1.20.1" },
-  { packFormat: 18, versions: "1.20.2" },
-  { packFormat: 22, versions: "1.20.3 – 1.20.4" },
-  { packFormat: 32, versions: "1.20.5 – 1.20.6" },
-  { packFormat: 34, versions: "1.21 – 1.21.1" },
-  { packFormat: 42, versions: "1.21.2 – 1.21.3" },
-  { packFormat: 46, versions: "1.21.4" },
-  { packFormat: 55, versions: "1.21.5" },
-  { packFormat: 63, versions: "1.21.6" },
-  { packFormat: 64, versions: "1.21.7 – 1.21.8" },
-  { packFormat: 69, versions: "1.21.9 – 1.21.10" },
-];
+const PACK_FORMAT_DATA = packFormats.map(({ packFormat, first, last }) => ({
+  packFormat,
+  versions: first === last ? first : `${first} – ${last}`,
+}));
 
 /**
  * Create bidirectional mappings for fast lookups
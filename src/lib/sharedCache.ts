@@ -0,0 +1,54 @@
+/**
+ * Shared cache folder preference
+ *
+ * The backend keeps the folder in memory only, so the user's choice is saved in
+ * localStorage and pushed to the backend on startup, before vanilla textures
+ * are initialized.
+ */
+import { setSharedCacheDir } from "@lib/tauri";
+import type { SharedCacheStatus } from "@lib/tauri";
+
+const STORAGE_KEY = "weaverbird_shared_cache_dir";
+
+/**
+ * Saved shared cache folder (null when caches are local)
+ */
+export function loadSharedCachePreference(): string | null {
+  try {
+    return localStorage.getItem(STORAGE_KEY);
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Save the folder and apply it to the backend
+ * @returns The cache locations now in effect
+ */
+export async function saveSharedCachePreference(
+  path: string | null,
+): Promise<SharedCacheStatus> {
+  const status = await setSharedCacheDir(path);
+  try {
+    if (path) {
+      localStorage.setItem(STORAGE_KEY, path);
+    } else {
+      localStorage.removeItem(STORAGE_KEY);
+    }
+  } catch (error) {
+    console.warn("[sharedCache] Failed to save preference:", error);
+  }
+  return status;
+}
+
+/**
+ * Apply the saved folder to the backend; call before vanilla textures are
+ * initialized. A folder that has gone away (unmounted share) falls back to
+ * local caches for this session but stays saved.
+ */
+export async function applyStoredSharedCache(): Promise<void> {
+  const path = loadSharedCachePreference();
+  if (path) {
+    await setSharedCacheDir(path);
+  }
+}
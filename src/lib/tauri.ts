@@ -4,9 +4,16 @@
  * Modern Tauri v2 pattern with structured error handling
  */
 
-import { invoke } from "@tauri-apps/api/core";
+import { convertFileSrc, invoke } from "@tauri-apps/api/core";
 import { open } from "@tauri-apps/plugin-dialog";
-import type { OverrideWirePayload, ScanResult } from "@state";
+import type {
+  AssetRecord,
+  DuplicateAsset,
+  DuplicatePacks,
+  OverrideWirePayload,
+  PackMeta,
+  ScanResult,
+} from "@state";
 import { normalizeAssetId } from "@lib/assetUtils";
 
 /**
@@ -20,9 +27,20 @@ export interface AppError {
     | "IO_ERROR"
     | "SCAN_ERROR"
     | "BUILD_ERROR"
-    | "INTERNAL_ERROR";
+    | "INTERNAL_ERROR"
+    | "CANCELLED"
+    | "CACHE_BUSY"
+    | "PACK_NOT_FOUND"
+    | "ZIP_CORRUPT"
+    | "MAPPINGS_UNAVAILABLE"
+    | "JAVA_MISSING";
   message: string;
   details?: string;
+  /**
+   * Context for remediation: packId (PACK_NOT_FOUND), path (ZIP_CORRUPT),
+   * version (MAPPINGS_UNAVAILABLE) or dir (CACHE_BUSY)
+   */
+  data?: Record<string, unknown>;
 }
 
 /**
@@ -55,23 +73,168 @@ export function formatError(error: unknown): string {
   return String(error);
 }
 
+/**
+ * New ID for a scan, build or vanilla extraction, to stop it with `cancelJob`
+ */
+export function createJobId(): string {
+  return crypto.randomUUID();
+}
+
+/**
+ * Stop a scan, build or vanilla extraction started with a job ID; the
+ * command then rejects with a CANCELLED error
+ * @returns Whether the job was still running
+ */
+export async function cancelJob(jobId: string): Promise<boolean> {
+  return invoke<boolean>("cancel_job", { jobId });
+}
+
+/** Event carrying a `JobInfo` whenever a job's status or progress changes */
+export const JOB_UPDATED_EVENT = "job-updated";
+
+/** Jobs of one kind run one at a time, in submission order */
+export type JobKind = "scan" | "build" | "vanilla_extraction" | "decompilation";
+
+export type JobStatus =
+  | "queued"
+  | "running"
+  | "succeeded"
+  | "failed"
+  | "cancelled";
+
+/**
+ * A heavy command to run in the background, with its arguments; the build
+ * request uses snake_case keys, as in `InterruptedBuild`
+ */
+export type JobRequest =
+  | { kind: "scan"; packsDir: string }
+  | { kind: "build"; request: Record<string, unknown> }
+  | {
+      kind: "initialize_vanilla_textures";
+      categories?: VanillaTextureCategory[];
+    }
+  | { kind: "initialize_vanilla_textures_from_dir"; minecraftDir: string }
+  | { kind: "set_vanilla_texture_version"; version: string }
+  | { kind: "extract_particle_physics"; version: string }
+  | { kind: "extract_block_emissions"; version: string };
+
+/** Latest state of a background job */
+export interface JobInfo {
+  id: string;
+  kind: JobKind;
+  status: JobStatus;
+  progress: {
+    phase: string;
+    completed: number;
+    total: number;
+    bytes: number | null;
+  } | null;
+  /** What the command returns, once succeeded */
+  result: unknown;
+  /** Why the job failed or was cancelled */
+  error: AppError | null;
+  /** Seconds since the Unix epoch */
+  submittedAt: number;
+  finishedAt: number | null;
+}
+
+/**
+ * Queue a heavy command as a background job
+ *
+ * Follow it with `getJob` or the `job-updated` event, stop it with
+ * `cancelJob`.
+ * @returns The job's ID
+ */
+export async function submitJob(request: JobRequest): Promise<string> {
+  return invoke<string>("submit_job", { request });
+}
+
+/**
+ * Latest state of a job, or null if it is unknown or finished long ago
+ */
+export async function getJob(jobId: string): Promise<JobInfo | null> {
+  return invoke<JobInfo | null>("get_job", { jobId });
+}
+
+/**
+ * Queued, running and recently finished jobs, oldest first
+ */
+export async function listJobs(): Promise<JobInfo[]> {
+  return invoke<JobInfo[]>("list_jobs");
+}
+
 /**
  * Scan a resource packs folder for all packs and assets
+ * @param jobId - Optional ID (see `createJobId`) to cancel the scan with
  */
-export async function scanPacksFolder(path: string): Promise<ScanResult> {
-  return invoke<ScanResult>("scan_packs_folder", { packsDir: path });
+export async function scanPacksFolder(
+  path: string,
+  jobId?: string,
+): Promise<ScanResult> {
+  return invoke<ScanResult>("scan_packs_folder", {
+    packsDir: path,
+    jobId: jobId ?? null,
+  });
 }
 
 /**
  * Build the Weaver Nest output pack
+ * @param jobId - Optional ID (see `createJobId`) to cancel the build with; a
+ *   cancelled build can be resumed or rolled back like an interrupted one
  */
-export async function buildWeaverNest(request: {
-  packsDir: string;
-  packOrder: string[];
-  overrides: Record<string, OverrideWirePayload>;
+export async function buildWeaverNest(
+  request: {
+    packsDir: string;
+    packOrder: string[];
+    overrides: Record<string, OverrideWirePayload>;
+    outputDir: string;
+    compression?: { method: "store" | "deflate" | "zstd"; level?: number };
+    targetPackFormat?: number;
+    mcmetaPairing?: "same_pack" | "borrow_matching" | "strip";
+    /** Leave out files byte-identical to their vanilla copy */
+    dedupeIdentical?: boolean;
+    /** Which provider wins an asset without an override */
+    conflictStrategy?: "pack_order" | "highest_resolution";
+  },
+  jobId?: string,
+): Promise<string> {
+  return invoke<string>("build_weaver_nest", {
+    ...request,
+    jobId: jobId ?? null,
+  });
+}
+
+/** A nest build that started but never finished (e.g. the app was killed) */
+export interface InterruptedBuild {
   outputDir: string;
-}): Promise<string> {
-  return invoke<string>("build_weaver_nest", request);
+  /** RFC 3339 start time */
+  startedAt: string;
+  /** Output files written before the build stopped */
+  filesWritten: number;
+  /** The build request as sent (snake_case keys) */
+  request: Record<string, unknown>;
+}
+
+/**
+ * List nest builds whose journal is still in their output directory
+ */
+export async function listInterruptedBuilds(): Promise<InterruptedBuild[]> {
+  return invoke<InterruptedBuild[]>("list_interrupted_builds");
+}
+
+/**
+ * Resume (re-run, keeping finished files) or roll back (delete the files they
+ * wrote) interrupted nest builds
+ * @returns Build message or rollback summary per output directory, in order
+ */
+export async function recoverInterruptedBuilds(
+  outputDirs: string[],
+  action: "resume" | "rollback",
+): Promise<BatchResult<string>[]> {
+  return invoke<BatchResult<string>[]>("recover_interrupted_builds", {
+    outputDirs,
+    action,
+  });
 }
 
 /**
@@ -100,10 +263,20 @@ export async function openFolderDialog(
 
 /**
  * Initialize vanilla textures (extract from Minecraft JAR if needed)
- * Should be called on app startup
+ * Should be called on app startup. Categories missing from the cache are
+ * extracted into it without extracting the rest again.
+ * @param categories - Optional texture categories to extract from now on;
+ *   the current selection is kept when omitted
+ * @param jobId - Optional ID (see `createJobId`) to cancel the extraction with
  */
-export async function initializeVanillaTextures(): Promise<string> {
-  return invoke<string>("initialize_vanilla_textures");
+export async function initializeVanillaTextures(
+  categories?: VanillaTextureCategory[],
+  jobId?: string,
+): Promise<string> {
+  return invoke<string>("initialize_vanilla_textures", {
+    categories: categories ?? null,
+    jobId: jobId ?? null,
+  });
 }
 
 function addUnderscoreBeforeDigits(assetId: string): string {
@@ -152,40 +325,89 @@ async function resolveTexturePath(
 /**
  * Get the file path to a vanilla texture
  * @param assetId - Asset ID like "minecraft:block/stone"
+ * @param version - Cached version to read from; defaults to the one in use
  * @returns Absolute path to the texture PNG file
  */
-export async function getVanillaTexturePath(assetId: string): Promise<string> {
+export async function getVanillaTexturePath(
+  assetId: string,
+  version?: string,
+): Promise<string> {
   return resolveTexturePath(assetId, (candidateId) =>
-    invoke<string>("get_vanilla_texture_path", { assetId: candidateId }),
+    invoke<string>("get_vanilla_texture_path", {
+      assetId: candidateId,
+      version: version ?? null,
+    }),
   );
 }
 
+export interface VanillaProvenance {
+  version: string;
+  jarPath: string;
+  /** Lowercase hex SHA-1 of the client JAR */
+  jarSha1: string;
+  jarSize: number;
+  jarModified: number;
+  assetIndex: string | null;
+  extractedAt: string;
+}
+
+/**
+ * Get the file path to a vanilla texture and the client JAR it was extracted
+ * from; provenance is null for caches extracted by older versions
+ * @param assetId - Asset ID like "minecraft:block/stone"
+ */
+export async function getVanillaTextureProvenance(
+  assetId: string,
+): Promise<{ path: string; provenance: VanillaProvenance | null }> {
+  return invoke("get_vanilla_texture_provenance", { assetId });
+}
+
 /**
- * Check if Minecraft is installed at the default location
- * @returns true if Minecraft installation found
+ * How a Minecraft directory was installed. Flatpak and Snap installs are
+ * sandboxed: the game only sees folders it was granted, so packs placed
+ * elsewhere (or symlinked) may not show up in game
  */
-export async function checkMinecraftInstalled(): Promise<boolean> {
-  return invoke<boolean>("check_minecraft_installed");
+export type InstallFlavor = "native" | "flatpak" | "snap" | "portable";
+
+export interface MinecraftInstall {
+  path: string;
+  flavor: InstallFlavor;
+}
+
+/**
+ * Check if Minecraft is installed at the default, Flatpak, Snap or portable
+ * location
+ * @returns The installation found, or null if there is none
+ */
+export async function checkMinecraftInstalled(): Promise<
+  MinecraftInstall | null
+> {
+  return invoke<MinecraftInstall | null>("check_minecraft_installed");
 }
 
 /**
  * Get suggested Minecraft installation paths for the current platform
- * @returns Array of likely Minecraft installation paths
+ * @returns Likely Minecraft installation paths and how each was installed
  */
-export async function getSuggestedMinecraftPaths(): Promise<string[]> {
-  return invoke<string[]>("get_suggested_minecraft_paths");
+export async function getSuggestedMinecraftPaths(): Promise<
+  MinecraftInstall[]
+> {
+  return invoke<MinecraftInstall[]>("get_suggested_minecraft_paths");
 }
 
 /**
  * Initialize vanilla textures from a custom Minecraft directory
  * @param minecraftDir - Path to the .minecraft directory
+ * @param jobId - Optional ID (see `createJobId`) to cancel the extraction with
  * @returns Path to the vanilla textures cache directory
  */
 export async function initializeVanillaTexturesFromCustomDir(
   minecraftDir: string,
+  jobId?: string,
 ): Promise<string> {
   return invoke<string>("initialize_vanilla_textures_from_custom_dir", {
     minecraftDir,
+    jobId: jobId ?? null,
   });
 }
 
@@ -207,15 +429,75 @@ export async function getCachedVanillaVersion(): Promise<string | null> {
   return invoke<string | null>("get_cached_vanilla_version");
 }
 
+/**
+ * List the vanilla versions kept side by side in the texture cache
+ * @returns Versions, newest first
+ */
+export async function listCachedVanillaVersions(): Promise<string[]> {
+  return invoke<string[]>("list_cached_vanilla_versions");
+}
+
+export type VanillaTextureChange =
+  | "unchanged"
+  | "modified"
+  | "added"
+  | "removed";
+
+export interface VanillaTextureDiff {
+  change: VanillaTextureChange;
+  /** [width, height] in each version */
+  sizeA: [number, number] | null;
+  sizeB: [number, number] | null;
+  changedPixels: number;
+  /** Share of pixels that changed, 0 to 1 */
+  changedFraction: number;
+  /** [x, y, width, height] of the changed area */
+  changedBounds: [number, number, number, number] | null;
+  animationChanged: boolean;
+}
+
+export interface VanillaAssetComparison {
+  assetId: string;
+  versionA: string;
+  versionB: string;
+  pathA: string | null;
+  pathB: string | null;
+  diff: VanillaTextureDiff;
+}
+
+/**
+ * Compare a vanilla texture between two cached versions
+ * @param assetId - Asset ID like "minecraft:block/grass_block_side"
+ * @param versionA - Version to compare from
+ * @param versionB - Version to compare to
+ * @returns Both texture paths and what changed
+ */
+export async function compareVanillaAsset(
+  assetId: string,
+  versionA: string,
+  versionB: string,
+): Promise<VanillaAssetComparison> {
+  return invoke<VanillaAssetComparison>("compare_vanilla_asset", {
+    assetId,
+    versionA,
+    versionB,
+  });
+}
+
 /**
  * Set the vanilla texture version to use
  * @param version - Version identifier (e.g., "1.21.4")
+ * @param jobId - Optional ID (see `createJobId`) to cancel the extraction with
  * @returns Path to vanilla textures cache
  */
 export async function setVanillaTextureVersion(
   version: string,
+  jobId?: string,
 ): Promise<string> {
-  return invoke<string>("set_vanilla_texture_version", { version });
+  return invoke<string>("set_vanilla_texture_version", {
+    version,
+    jobId: jobId ?? null,
+  });
 }
 
 /**
@@ -228,6 +510,11 @@ export interface LauncherInfo {
   found: boolean;
   icon: string;
   icon_path?: string;
+  /**
+   * Where resource packs go, relative to minecraft_dir; `<instance>` stands
+   * for one instance folder
+   */
+  resourcepacks_layout: string;
 }
 
 /**
@@ -267,6 +554,71 @@ export async function getLauncherResourcepacksDir(
   return invoke<string>("get_launcher_resourcepacks_dir", { launcherInfo });
 }
 
+/**
+ * One instance (profile, modpack) of a launcher
+ */
+export interface LauncherInstance {
+  id: string;
+  name: string;
+  minecraft_version: string | null;
+  /** "fabric", "forge", "neoforge", "quilt"; null for vanilla or unknown */
+  loader: string | null;
+  loader_version: string | null;
+  instance_dir: string;
+  resourcepacks_dir: string;
+}
+
+/**
+ * List the instances of a multi-instance launcher
+ * @param launcherInfo - Launcher information
+ * @returns Instances sorted by name
+ */
+export async function listLauncherInstances(
+  launcherInfo: LauncherInfo,
+): Promise<LauncherInstance[]> {
+  return invoke<LauncherInstance[]>("list_launcher_instances", {
+    launcherInfo,
+  });
+}
+
+/** How the nest is put into the resourcepacks folder */
+export type InstallMode = "copy" | "symlink";
+
+export interface NestInstallResult {
+  installedPath: string;
+  /** options.txt entry, e.g. "file/WeaverNest.zip" */
+  packEntry: string;
+  /** Whether options.txt was edited to enable the nest */
+  enabled: boolean;
+}
+
+/**
+ * Install the built nest into a launcher instance
+ * @param nestPath - Built nest, WeaverNest.zip or a pack folder
+ * @param launcherInfo - Launcher information
+ * @param instanceId - Instance id from listLauncherInstances; optional for
+ *   launchers with a single instance
+ * @param mode - Copy, or symlink so rebuilds apply without reinstalling
+ * @param enable - Enable the nest on top of the pack stack in options.txt.
+ *   Minecraft rewrites options.txt on exit, so close the game first
+ * @returns Where the nest was installed
+ */
+export async function installNestToLauncher(
+  nestPath: string,
+  launcherInfo: LauncherInfo,
+  instanceId?: string,
+  mode: InstallMode = "copy",
+  enable = true,
+): Promise<NestInstallResult> {
+  return invoke<NestInstallResult>("install_nest_to_launcher", {
+    nestPath,
+    launcherInfo,
+    instanceId: instanceId ?? null,
+    mode,
+    enable,
+  });
+}
+
 /**
  * Get the full path to a texture file from a resource pack
  * @param packPath - Base path to the resource pack
@@ -292,6 +644,820 @@ export async function getPackTexturePath(
   );
 }
 
+/**
+ * URL that streams a file over the weaverbird:// protocol as raw bytes,
+ * avoiding base64 strings in IPC payloads
+ * @param packId - ID of a pack from the last scan, or null for the vanilla
+ *   cache
+ * @param entryPath - Pack-relative file path (e.g., "pack.png")
+ * @param options.ktx2 - Ask for a PNG as a GPU-compressed KTX2 (BC3) texture,
+ *   for the 3D preview of high-res packs. The response is still a PNG when the
+ *   texture can't be compressed; check its Content-Type ("image/ktx2")
+ * @returns URL usable as an img src or fetch target
+ */
+export function assetProtocolUrl(
+  packId: string | null,
+  entryPath: string,
+  options?: { ktx2?: boolean },
+): string {
+  const base = convertFileSrc("", "weaverbird");
+  const entry = entryPath.split("/").map(encodeURIComponent).join("/");
+  const url =
+    packId === null
+      ? `${base}asset/vanilla/${entry}`
+      : `${base}asset/pack/${entry}?pack=${encodeURIComponent(packId)}`;
+  if (!options?.ktx2) {
+    return url;
+  }
+  return `${url}${packId === null ? "?" : "&"}format=ktx2`;
+}
+
+/**
+ * Outcome of one request in a batch command; exactly one field is set
+ */
+export interface BatchResult<T> {
+  result?: T;
+  error?: { code: string; message: string; details?: string };
+}
+
+/**
+ * Locate many pack textures in one IPC call
+ * @param requests - Textures to locate, same fields as getPackTexturePath
+ * @returns One result or error per request, in request order
+ */
+export async function getPackTexturePathsBatch(
+  requests: {
+    packPath: string;
+    assetId: string;
+    isZip: boolean;
+    versionFolders?: string[];
+  }[],
+): Promise<BatchResult<string>[]> {
+  return invoke<BatchResult<string>[]>("get_pack_texture_paths_batch", {
+    requests,
+  });
+}
+
+/** Where `resolveTexturePathsBulk` found an asset's winning texture */
+export interface ResolvedTexturePath {
+  /** Pack the texture comes from; "minecraft:vanilla" when no pack has it */
+  packId: string;
+  path: string;
+}
+
+/**
+ * Resolve the winning texture of a page of assets in one IPC call, for the
+ * virtualized asset grid
+ * @param assetIds - Texture asset IDs of the visible rows
+ * @param orderedPacks - Packs from the scan, highest priority first
+ * @returns One result or error per asset, in assetIds order
+ */
+export async function resolveTexturePathsBulk(
+  assetIds: string[],
+  orderedPacks: PackMeta[],
+): Promise<BatchResult<ResolvedTexturePath>[]> {
+  return invoke<BatchResult<ResolvedTexturePath>[]>(
+    "resolve_texture_paths_bulk",
+    { assetIds, orderedPacks },
+  );
+}
+
+/**
+ * Get a cached, downscaled copy of a texture for grid cards
+ *
+ * Pixel art is scaled nearest-neighbor so each frame fits in size x size;
+ * animation strips keep their frames and .mcmeta.
+ * @param packPath - Base path to the resource pack, or null for vanilla
+ * @param assetId - Asset ID (e.g., "minecraft:block/stone")
+ * @param isZip - Whether the pack is a ZIP file
+ * @param size - Edge length to fit each frame into (defaults to 64)
+ * @returns Full path to the thumbnail PNG
+ */
+export async function getAssetThumbnail(
+  packPath: string | null,
+  assetId: string,
+  isZip: boolean,
+  size?: number,
+): Promise<string> {
+  return resolveTexturePath(assetId, (candidateId) =>
+    invoke<string>("get_asset_thumbnail", {
+      packPath,
+      assetId: candidateId,
+      isZip,
+      size,
+    }),
+  );
+}
+
+/**
+ * Frame layout and playback order of an animated texture
+ */
+export interface AnimationFrames {
+  sheetWidth: number;
+  sheetHeight: number;
+  frameWidth: number;
+  frameHeight: number;
+  /** Every frame of the sheet in index order (row-major) */
+  frames: {
+    index: number;
+    x: number;
+    y: number;
+    width: number;
+    height: number;
+  }[];
+  /** Playback order with each frame's duration in ticks */
+  sequence: { index: number; time: number }[];
+  interpolate: boolean;
+  cycleTicks: number;
+  /** Each sheet frame as base64 PNG, only when images were requested */
+  images?: string[];
+}
+
+/**
+ * Get the frames of an animated texture from its .mcmeta
+ * @param packPath - Pack folder or ZIP path, or null for vanilla
+ * @param assetId - Asset ID (e.g., "minecraft:block/water_still")
+ * @param includeImages - Also return each frame sliced out as base64 PNG
+ * @returns Frames and sequence, or null if the texture isn't animated
+ */
+export async function getAnimationFrames(
+  packPath: string | null,
+  assetId: string,
+  includeImages?: boolean,
+): Promise<AnimationFrames | null> {
+  return invoke<AnimationFrames | null>("get_animation_frames", {
+    packPath,
+    assetId,
+    includeImages,
+  });
+}
+
+/**
+ * Payload of the "asset-updated" event emitted by refreshAsset
+ */
+export interface AssetUpdatedEvent {
+  assetId: string;
+  packId: string;
+  /** Path of the pack the asset belongs to (texture cache key) */
+  packPath: string;
+  /** Number of cached files removed on the backend */
+  invalidated: number;
+}
+
+/**
+ * Drop every cached copy of an asset after it was edited in an external editor
+ *
+ * Emits "asset-updated" so open previews reload the asset.
+ * @param assetId - Asset that changed (e.g., "minecraft:block/stone")
+ * @param packId - Pack containing the edited file
+ * @param packsDir - Resource packs directory
+ */
+export async function refreshAsset(
+  assetId: string,
+  packId: string,
+  packsDir: string,
+): Promise<AssetUpdatedEvent> {
+  return invoke<AssetUpdatedEvent>("refresh_asset", {
+    assetId,
+    packId,
+    packsDir,
+  });
+}
+
+/**
+ * An asset opened in an external editor
+ */
+export interface EditSession {
+  id: string;
+  assetId: string;
+  packId: string;
+  packPath: string;
+  /** Pack-relative path of the edited file */
+  entry: string;
+  /** File handed to the editor */
+  editPath: string;
+  /** True when the pack's own file is edited (folder packs) */
+  inPlace: boolean;
+}
+
+/**
+ * Open an asset in an external editor and watch it for saves
+ *
+ * Folder pack files are edited in place and each save emits "asset-updated".
+ * ZIP pack files are extracted to a temp workspace and each save emits
+ * "asset-edited" with the session, so the change can be written back.
+ * @param editor - Editor executable (or .app on macOS); omit for the system default
+ */
+export async function openAssetInEditor(
+  assetId: string,
+  packId: string,
+  packsDir: string,
+  editor?: string,
+): Promise<EditSession> {
+  return invoke<EditSession>("open_asset_in_editor", {
+    assetId,
+    packId,
+    packsDir,
+    editor,
+  });
+}
+
+/**
+ * Write an edited ZIP asset into a working copy of its pack
+ * @returns Path of the working copy folder
+ */
+export async function writeBackEditedAsset(sessionId: string): Promise<string> {
+  return invoke<string>("write_back_edited_asset", { sessionId });
+}
+
+/**
+ * Stop watching an edited asset and delete its temp files
+ */
+export async function closeEditSession(sessionId: string): Promise<void> {
+  return invoke<void>("close_edit_session", { sessionId });
+}
+
+/**
+ * Which vanilla file to copy for an asset ID
+ */
+export type VanillaAssetKind = "texture" | "model" | "blockstate";
+
+/**
+ * Copy a vanilla texture, model or blockstate into a folder pack to start an override
+ *
+ * Textures bring their .mcmeta along; existing pack files are never overwritten.
+ * @param assetId - Asset to copy (e.g., "minecraft:block/stone")
+ * @param packId - Folder pack to copy into
+ * @param packsDir - Resource packs directory
+ * @param kind - Which file to copy (defaults to the texture)
+ * @returns Pack-relative paths of the files written
+ */
+export async function copyVanillaAssetToPack(
+  assetId: string,
+  packId: string,
+  packsDir: string,
+  kind?: VanillaAssetKind,
+): Promise<string[]> {
+  return invoke<string[]>("copy_vanilla_asset_to_pack", {
+    assetId,
+    packId,
+    packsDir,
+    kind,
+  });
+}
+
+/**
+ * Extract a zip pack into an editable folder
+ *
+ * Emits "pack-archive-progress" events with [current, total] entries.
+ * @param dest - Empty (or missing) folder to extract into
+ * @returns Path of the extracted folder
+ */
+export async function unpackPack(
+  packId: string,
+  packsDir: string,
+  dest: string,
+): Promise<string> {
+  return invoke<string>("unpack_pack", { packId, packsDir, dest });
+}
+
+/**
+ * Zip a pack folder into a new archive
+ *
+ * Emits "pack-archive-progress" events with [current, total] entries.
+ * @param dir - Pack folder containing pack.mcmeta
+ * @param destZip - Path of the zip to create; must not exist yet
+ * @returns Path of the created zip
+ */
+export async function repackPack(dir: string, destZip: string): Promise<string> {
+  return invoke<string>("repack_pack", { dir, destZip });
+}
+
+/**
+ * Result of importing a pack
+ */
+export interface PackImportResult {
+  /** The imported pack; absent when it was skipped as a duplicate */
+  pack?: PackMeta;
+  /** Library packs with the same content, plus the imported file name */
+  duplicates?: DuplicatePacks;
+}
+
+/**
+ * Copy a ZIP or pack folder into the packs directory
+ *
+ * Packs whose content is already in the library (even under another file
+ * name) are reported in `duplicates` and not copied unless allowDuplicate is set.
+ */
+export async function importPack(
+  sourcePath: string,
+  packsDir: string,
+  allowDuplicate?: boolean,
+): Promise<PackImportResult> {
+  return invoke<PackImportResult>("import_pack", {
+    sourcePath,
+    packsDir,
+    allowDuplicate,
+  });
+}
+
+/**
+ * Files that several packs (vanilla included) provide with identical content,
+ * sorted by file path
+ */
+export async function findDuplicateAssets(
+  packsDir: string,
+): Promise<DuplicateAsset[]> {
+  return invoke<DuplicateAsset[]>("find_duplicate_assets", { packsDir });
+}
+
+/**
+ * Turn the backend's low-memory mode on or off
+ *
+ * While on, scans and builds process packs one at a time, pack icons are
+ * left out of scan results and nest builds stream files instead of buffering
+ * them. Returns the mode now in effect.
+ */
+export async function setLowMemoryMode(enabled: boolean): Promise<boolean> {
+  return invoke<boolean>("set_low_memory_mode", { enabled });
+}
+
+export interface PerfReport {
+  /** Logical cores available to the app */
+  threadCount: number;
+  /** Packs directory read speed; null when it had no files */
+  diskReadMbPerSec: number | null;
+  diskBytesRead: number;
+  /** ZIP decompression speed; null when there were no ZIP packs */
+  zipExtractMbPerSec: number | null;
+  zipBytesExtracted: number;
+  durationMs: number;
+  recommended: {
+    threadPoolSize: number;
+    lowMemoryMode: boolean;
+    /** Why these values were picked, one line per decision */
+    reasons: string[];
+  };
+}
+
+/**
+ * Benchmark disk reads and ZIP extraction on this machine and recommend
+ * settings; with apply, scans and builds use the recommended thread count
+ */
+export async function runPerfSelftest(
+  packsDir: string,
+  apply?: boolean,
+): Promise<PerfReport> {
+  return invoke<PerfReport>("run_perf_selftest", { packsDir, apply });
+}
+
+/** Latency of one backend command since the app started */
+export interface CommandMetrics {
+  command: string;
+  count: number;
+  totalMs: number;
+  /** Percentiles over the most recent calls */
+  p50Ms: number;
+  p95Ms: number;
+  maxMs: number;
+}
+
+/**
+ * Call count and latency of every backend command called so far, the most
+ * time-consuming first; useful to attach to performance reports
+ */
+export async function getPerfMetrics(): Promise<CommandMetrics[]> {
+  return invoke<CommandMetrics[]>("get_perf_metrics");
+}
+
+export interface SyntheticSuiteConfig {
+  /** Number of packs; every second one is written as a ZIP */
+  packs?: number;
+  /** Textures only one pack provides, per pack */
+  uniqueTextures?: number;
+  /** Textures every pack provides, so the build has to pick a winner */
+  sharedTextures?: number;
+  /** Corrupt PNGs and model JSONs per pack */
+  brokenFiles?: number;
+}
+
+export interface SelftestStep {
+  name: string;
+  passed: boolean;
+  durationMs: number;
+  /** What was checked, or why it failed */
+  detail: string;
+}
+
+export interface SelftestReport {
+  config: Required<SyntheticSuiteConfig>;
+  steps: SelftestStep[];
+  passed: boolean;
+}
+
+/**
+ * Generate synthetic packs and run them through scan, validation and build.
+ * Only available in backends built with the `selftest` feature; others reject.
+ */
+export async function runSelftestSuite(
+  config?: SyntheticSuiteConfig,
+): Promise<SelftestReport> {
+  return invoke<SelftestReport>("run_selftest_suite", { config });
+}
+
+/**
+ * Whether the backend's low-memory mode is on
+ */
+export async function getLowMemoryMode(): Promise<boolean> {
+  return invoke<boolean>("get_low_memory_mode");
+}
+
+export type LogLevel = "error" | "warn" | "info" | "debug" | "trace" | "off";
+
+/**
+ * Change the level the backend logs at. Returns the level now in effect.
+ */
+export async function setLogLevel(level: LogLevel): Promise<LogLevel> {
+  return invoke<LogLevel>("set_log_level", { level });
+}
+
+/**
+ * Level the backend currently logs at
+ */
+export async function getLogLevel(): Promise<LogLevel> {
+  return invoke<LogLevel>("get_log_level");
+}
+
+/**
+ * The backend's most recent log lines, oldest first, for attaching to bug
+ * reports. Older lines are in the daily log files under the cache folder.
+ * @param limit - Most lines to return (default 500)
+ */
+export async function getRecentLogs(limit?: number): Promise<string[]> {
+  return invoke<string[]>("get_recent_logs", { limit });
+}
+
+export interface SharedCacheStatus {
+  /** Shared cache folder, null when every cache is local */
+  sharedPath: string | null;
+  /** False when the shared folder is read-only; missing caches go local */
+  writable: boolean;
+  /** This machine's own cache folder */
+  localPath: string;
+}
+
+/**
+ * Keep vanilla textures and decompiled sources in a shared (e.g. network)
+ * folder so several machines reuse one extraction; null keeps them local
+ */
+export async function setSharedCacheDir(
+  path: string | null,
+): Promise<SharedCacheStatus> {
+  return invoke<SharedCacheStatus>("set_shared_cache_dir", { path });
+}
+
+/**
+ * Where vanilla textures and decompiled sources are currently cached
+ */
+export async function getSharedCacheStatus(): Promise<SharedCacheStatus> {
+  return invoke<SharedCacheStatus>("get_shared_cache_status");
+}
+
+/**
+ * Set the language of backend messages (validation errors, compatibility
+ * issues, build warnings) from the app's preferred locales
+ * @returns The locale in effect; "en-US" when none of them is available
+ */
+export async function setBackendLocale(
+  locales: readonly string[],
+): Promise<string> {
+  return invoke<string>("set_backend_locale", { locales: [...locales] });
+}
+
+export type ColorVisionDeficiency =
+  | "protanopia"
+  | "deuteranopia"
+  | "tritanopia"
+  | "achromatopsia";
+
+export interface ColorblindSimulation {
+  deficiency: ColorVisionDeficiency;
+  /** "#rrggbb" */
+  foreground: string;
+  background: string;
+  contrastRatio: number;
+  /** CIE76 difference between the simulated tones */
+  colorDifference: number;
+  /** Recolored texture as base64 PNG, only when images were requested */
+  image?: string;
+}
+
+export interface ContrastAnalysis {
+  assetId: string;
+  file: string;
+  foreground: string;
+  background: string;
+  /** Single-tone texture (e.g. font glyphs) measured against the GUI panel gray */
+  againstGuiPanel: boolean;
+  contrastRatio: number;
+  /** 4.5 for fonts, 3 for other GUI graphics */
+  requiredRatio: number;
+  colorDifference: number;
+  simulations: ColorblindSimulation[];
+  issues: string[];
+}
+
+export interface ContrastReport {
+  packId: string;
+  analyses: ContrastAnalysis[];
+  /** Requested assets the pack has no texture for */
+  missing: string[];
+  /** All analyses' issues prefixed with the asset ID */
+  issues: string[];
+}
+
+/**
+ * Measure contrast of GUI/font textures in a pack and simulate how they look
+ * with common color vision deficiencies
+ */
+export async function analyzeContrast(
+  packId: string,
+  packsDir: string,
+  assetIds: string[],
+  includeImages?: boolean,
+): Promise<ContrastReport> {
+  return invoke<ContrastReport>("analyze_contrast", {
+    packId,
+    packsDir,
+    assetIds,
+    includeImages,
+  });
+}
+
+export interface VersionCompatibility {
+  packFormat: number;
+  /** Releases using this pack_format, e.g. "1.20.3 – 1.20.4" */
+  versions: string;
+  /** Whether pack.mcmeta loads without the "incompatible" warning */
+  status: "compatible" | "too_old" | "too_new" | "unknown";
+  /** Every asset loads as-is: nothing ignored or needing conversion */
+  assetsLoad: boolean;
+  /** Files converting to this format would rename */
+  renamed: { from: string; to: string }[];
+  /** Models converting to this format would rewrite */
+  rewritten: string[];
+  /** Files these releases ignore that can't be converted automatically */
+  ignored: { path: string; reason: string }[];
+}
+
+export interface CompatibilityMatrix {
+  packId: string;
+  fromFormat: number;
+  /** Oldest to newest pack_format */
+  versions: VersionCompatibility[];
+  /** Release range where every asset loads as-is, e.g. "1.13 – 1.20.1" */
+  worksOn: string | null;
+}
+
+/**
+ * Check a pack against every known Minecraft release using the pack_format
+ * history and rename tables
+ */
+export async function getPackCompatibility(
+  packId: string,
+  packsDir: string,
+): Promise<CompatibilityMatrix> {
+  return invoke<CompatibilityMatrix>("get_pack_compatibility", {
+    packId,
+    packsDir,
+  });
+}
+
+/**
+ * Last scan of a packs directory stored in the on-disk scan index, or null if
+ * it was never indexed; lets the library show before a fresh scan finishes
+ */
+export async function loadScanIndex(
+  packsDir: string,
+): Promise<ScanResult | null> {
+  return invoke<ScanResult | null>("load_scan_index", { packsDir });
+}
+
+export interface AssetSearchHit {
+  assetId: string;
+  /** Higher is better; only comparable between hits of the same query */
+  score: number;
+}
+
+/**
+ * Ranked fuzzy search over the assets of the last scan
+ *
+ * Whitespace-separated terms must all match; typos and abbreviations
+ * ("cbst" -> cobblestone) still find assets, ranked below exact matches.
+ */
+export async function searchAssets(
+  query: string,
+  limit?: number,
+): Promise<AssetSearchHit[]> {
+  return invoke<AssetSearchHit[]>("search_assets", { query, limit });
+}
+
+/**
+ * Search the scan index for assets carrying a label (case-insensitive)
+ */
+export async function searchIndexedAssets(
+  packsDir: string,
+  label: string,
+  limit?: number,
+): Promise<AssetRecord[]> {
+  return invoke<AssetRecord[]>("search_indexed_assets", {
+    packsDir,
+    label,
+    limit,
+  });
+}
+
+/**
+ * Pack IDs providing an asset according to the scan index, in scan order
+ */
+export async function getIndexedProviders(
+  packsDir: string,
+  assetId: string,
+): Promise<string[]> {
+  return invoke<string[]>("get_indexed_providers", { packsDir, assetId });
+}
+
+/**
+ * Payload of the "pack-library-changed" event emitted by deletePack and renamePack
+ */
+export interface PackLibraryChange {
+  /** ID the pack had before the change */
+  packId: string;
+  /** The pack after a rename; absent when it was deleted */
+  pack?: PackMeta;
+}
+
+/**
+ * Move a pack to the OS trash
+ *
+ * Emits "pack-library-changed" without a pack.
+ */
+export async function deletePack(
+  packId: string,
+  packsDir: string,
+): Promise<PackLibraryChange> {
+  return invoke<PackLibraryChange>("delete_pack", { packId, packsDir });
+}
+
+/**
+ * Rename a pack's zip file or folder
+ *
+ * Emits "pack-library-changed" with the pack under its new ID.
+ * @param newName - New name, without the .zip extension
+ * @param description - New pack.mcmeta description (folder packs only)
+ */
+export async function renamePack(
+  packId: string,
+  newName: string,
+  packsDir: string,
+  description?: string,
+): Promise<PackLibraryChange> {
+  return invoke<PackLibraryChange>("rename_pack", {
+    packId,
+    newName,
+    description,
+    packsDir,
+  });
+}
+
+/**
+ * A sprite atlas definition from a pack's atlases/ folder (1.19.3+)
+ */
+export interface AtlasDefinition {
+  /** Atlas ID (e.g., "minecraft:blocks") */
+  id: string;
+  /** Pack-relative path (e.g., "assets/minecraft/atlases/blocks.json") */
+  file: string;
+  /** Sprite sources registered by this pack, in file order */
+  sources: Record<string, unknown>[];
+}
+
+/**
+ * List the sprite atlas definitions a pack registers
+ * @param packPath - Path to the pack (directory or ZIP file)
+ * @param isZip - Whether the pack is a ZIP file
+ * @returns Atlas definitions sorted by atlas ID
+ */
+export async function getPackAtlases(
+  packPath: string,
+  isZip: boolean,
+): Promise<AtlasDefinition[]> {
+  return invoke<AtlasDefinition[]>("get_pack_atlases", { packPath, isZip });
+}
+
+/**
+ * One rule of an OptiFine random entity .properties file
+ */
+export interface RandomEntityRule {
+  /** Variant numbers the rule chooses between */
+  textures: number[];
+  /** Weight per variant, in `textures` order (empty for equal weights) */
+  weights: number[];
+  /** Remaining conditions (biomes, heights, name, ...) */
+  conditions: Record<string, string>;
+}
+
+/**
+ * The random texture variants a pack defines for one entity texture
+ */
+export interface RandomEntityVariants {
+  /** Base texture asset ID (e.g., "minecraft:entity/creeper/creeper") */
+  assetId: string;
+  /** Variants sorted by index; variant 1 is the base texture */
+  variants: { index: number; file: string }[];
+  rules: RandomEntityRule[];
+  /** Pack-relative path of the .properties file */
+  properties: string | null;
+}
+
+/**
+ * List the OptiFine random texture variants a pack defines for an entity texture
+ * @param packPath - Path to the pack (directory or ZIP file)
+ * @param isZip - Whether the pack is a ZIP file
+ * @param assetId - Base texture asset ID
+ * @returns The variants, or null if the pack has none for this texture
+ */
+export async function getRandomEntityVariants(
+  packPath: string,
+  isZip: boolean,
+  assetId: string,
+): Promise<RandomEntityVariants | null> {
+  return invoke<RandomEntityVariants | null>("get_random_entity_variants", {
+    packPath,
+    isZip,
+    assetId,
+  });
+}
+
+/**
+ * What is known about an entity when picking its random variant; a rule
+ * condition on an unset field never matches
+ */
+export interface EntityContext {
+  /** Biome ID ("desert" or "minecraft:desert") */
+  biome?: string;
+  /** Block height the entity spawned at */
+  y?: number;
+  /** Custom name (name tag) */
+  name?: string;
+  baby?: boolean;
+  /** Villager profession ("farmer") */
+  profession?: string;
+  /** Collar, wool or shulker color ("red") */
+  color?: string;
+  weather?: "clear" | "rain" | "thunder";
+  /** Moon phase, 0-7 */
+  moonPhase?: number;
+  /** Time of day in ticks, 0-23999 */
+  dayTime?: number;
+}
+
+/**
+ * The random variant OptiFine would show for an entity
+ */
+export interface EntityVariantPick {
+  index: number;
+  /** Pack-relative path, or null if the pack lacks the variant a rule names */
+  file: string | null;
+  /** Index into `rules` of the rule that chose it; null for the base texture or uniform picks */
+  rule: number | null;
+}
+
+/**
+ * Pick the random texture variant OptiFine would show for an entity
+ * @param packPath - Path to the pack (directory or ZIP file)
+ * @param isZip - Whether the pack is a ZIP file
+ * @param assetId - Base texture asset ID
+ * @param context - Biome, height, name and so on of the entity
+ * @param seed - Stands in for the entity's UUID; the same seed picks the same variant
+ * @returns The chosen variant, or null if the pack has no random textures for it
+ */
+export async function pickEntityVariant(
+  packPath: string,
+  isZip: boolean,
+  assetId: string,
+  context: EntityContext,
+  seed: number,
+): Promise<EntityVariantPick | null> {
+  return invoke<EntityVariantPick | null>("pick_entity_variant", {
+    packPath,
+    isZip,
+    assetId,
+    context,
+    seed,
+  });
+}
+
 /**
  * Get all entities that have version variants in JEM files
  * Scans all packs for JEM files in version-specific folders
@@ -403,6 +1569,8 @@ export interface ExtractedPhysicsData {
   schema_version?: number;
   version: string;
   particles: Record<string, ExtractedParticlePhysics>;
+  /** Only constructor constants were read (no Java to decompile with) */
+  partial?: boolean;
 }
 
 /**
@@ -432,6 +1600,8 @@ export async function isParticlePhysicsCached(version: string): Promise<boolean>
  *
  * Downloads Mojang mappings, sets up CFR decompiler, and extracts physics
  * values from decompiled particle classes. Results are cached per-version.
+ * Without Java, constructor constants are read from the class files instead
+ * and the result is marked `partial` (and not cached).
  *
  * This is an expensive operation - call sparingly and show a loading indicator.
  *
@@ -444,6 +1614,316 @@ export async function extractParticlePhysics(
   return invoke<ExtractedPhysicsData>("extract_particle_physics", { version });
 }
 
+/**
+ * A particle sprite resolved to the pack that provides its texture
+ */
+export interface ResolvedParticleSprite {
+  /** Sprite ID from the particle definition (e.g., "minecraft:flame") */
+  sprite: string;
+  /** Texture asset ID (e.g., "minecraft:particle/flame") */
+  assetId: string;
+  packId: string;
+  packPath: string;
+  isZip: boolean;
+  /** Pack-relative PNG path */
+  entryPath: string;
+}
+
+/**
+ * Everything the particle preview needs for one particle in one version
+ */
+export interface ParticleDefinition {
+  particleId: string;
+  version: string;
+  /** Extracted (or bundled) physics; null until extracted */
+  physics: ExtractedParticlePhysics | null;
+  /** Sprite IDs from particles/<id>.json in the version's client JAR */
+  vanillaTextures: string[];
+  /** Winning sprite list, each sprite resolved across the pack stack */
+  textures: {
+    particleId: string;
+    /** Pack whose particles/<id>.json supplied the list ("minecraft:vanilla" for the JAR) */
+    definitionPackId: string;
+    sprites: ResolvedParticleSprite[];
+  };
+}
+
+/**
+ * Get a particle's physics, vanilla sprite list and resolved sprite textures
+ * in one call
+ *
+ * @param particleId - Particle ID (e.g., "flame" or "minecraft:flame")
+ * @param version - Minecraft version string (e.g., "1.21.4")
+ * @param packOrder - Pack IDs in priority order (highest first)
+ * @param packsDir - Directory containing resource packs
+ */
+export async function getParticleDefinition(
+  particleId: string,
+  version: string,
+  packOrder: string[],
+  packsDir: string,
+): Promise<ParticleDefinition> {
+  return invoke<ParticleDefinition>("get_particle_definition", {
+    particleId,
+    version,
+    packOrder,
+    packsDir,
+  });
+}
+
+export type OfflineDataset =
+  | "particle_physics"
+  | "block_emissions"
+  | "block_animations";
+
+export interface OfflineStatus {
+  /** Whether offline mode is switched on */
+  offlineMode: boolean;
+  version: string | null;
+  /** True when any dataset is unavailable or its extraction failed */
+  degraded: boolean;
+  datasets: {
+    dataset: OfflineDataset;
+    source: "cached" | "bundled" | "unavailable";
+    /** Why extraction fell back, when it was attempted and failed */
+    reason: string | null;
+  }[];
+}
+
+/**
+ * Turn offline mode on or off; while on, nothing is downloaded and
+ * extraction falls back to the datasets bundled with the app
+ * @returns The mode now in effect
+ */
+export async function setOfflineMode(enabled: boolean): Promise<boolean> {
+  return invoke<boolean>("set_offline_mode", { enabled });
+}
+
+/**
+ * Where particle physics, block emissions and animations come from for a
+ * version (defaults to the cached vanilla version)
+ */
+export async function getOfflineStatus(
+  version?: string,
+): Promise<OfflineStatus> {
+  return invoke<OfflineStatus>("get_offline_status", { version });
+}
+
+/**
+ * Mirrors, proxy and retry policy for Mojang and tool downloads
+ */
+export interface DownloadSettings {
+  /** Base URL replacing piston-meta.mojang.com */
+  metaMirror: string | null;
+  /** Base URL replacing piston-data.mojang.com */
+  dataMirror: string | null;
+  /** Base URL replacing github.com (CFR decompiler) */
+  toolsMirror: string | null;
+  /** HTTP(S) proxy for every download */
+  proxy: string | null;
+  /** Seconds to wait for a connection or the next chunk of data */
+  timeoutSecs: number;
+  /** Further attempts after a failed request */
+  retries: number;
+}
+
+/**
+ * Set download mirrors, proxy and retries (in memory only; persist it
+ * separately)
+ * @returns The settings now in effect
+ */
+export async function setDownloadSettings(
+  settings: DownloadSettings,
+): Promise<DownloadSettings> {
+  return invoke<DownloadSettings>("set_download_settings", { settings });
+}
+
+/**
+ * Download settings currently in effect
+ */
+export async function getDownloadSettings(): Promise<DownloadSettings> {
+  return invoke<DownloadSettings>("get_download_settings");
+}
+
+/**
+ * Optional groups of vanilla textures; block, item and colormap textures are
+ * always extracted
+ */
+export type VanillaTextureCategory =
+  | "entity"
+  | "gui"
+  | "particle"
+  | "mob_effect"
+  | "misc"
+  | "other";
+
+/**
+ * Choose which optional vanilla texture categories are extracted. A category
+ * the cache lacks is extracted on the next vanilla texture initialization.
+ * @returns The categories now extracted
+ */
+export async function setVanillaTextureCategories(
+  categories: VanillaTextureCategory[],
+): Promise<VanillaTextureCategory[]> {
+  return invoke<VanillaTextureCategory[]>("set_vanilla_texture_categories", {
+    categories,
+  });
+}
+
+/**
+ * Optional vanilla texture categories currently extracted
+ */
+export async function getVanillaTextureCategories(): Promise<
+  VanillaTextureCategory[]
+> {
+  return invoke<VanillaTextureCategory[]>("get_vanilla_texture_categories");
+}
+
+/**
+ * Extraction caches that can be measured and cleared
+ */
+export type CacheCategory =
+  | "vanilla_textures"
+  | "decompiled_sources"
+  | "extracted_data"
+  | "mappings"
+  | "cfr";
+
+export interface CacheUsage {
+  totalBytes: number;
+  categories: {
+    category: CacheCategory;
+    bytes: number;
+    /** Per Minecraft version, largest first; empty for CFR */
+    versions: { version: string; bytes: number }[];
+  }[];
+}
+
+/**
+ * Disk space taken by each extraction cache, per Minecraft version
+ */
+export async function getCacheUsage(): Promise<CacheUsage> {
+  return invoke<CacheUsage>("get_cache_usage");
+}
+
+/**
+ * Delete an extraction cache, or only one version of it. Cleared caches are
+ * rebuilt on demand.
+ * @returns Bytes freed
+ */
+export async function clearCache(
+  category: CacheCategory,
+  version?: string,
+): Promise<number> {
+  return invoke<number>("clear_cache", { category, version });
+}
+
+/** Event carrying a `MaintenanceReport` after each background maintenance run */
+export const CACHE_MAINTENANCE_EVENT = "cache-maintenance-completed";
+
+/**
+ * Cache TTLs for background maintenance; a TTL of zero keeps that cache
+ */
+export interface MaintenanceSettings {
+  /** Whether the background task runs at all */
+  enabled: boolean;
+  /** Thumbnails not shown for this many days are deleted */
+  thumbnailTtlDays: number;
+  /** Decompiled sources of versions not used for this many days are deleted */
+  decompiledTtlDays: number;
+  /** Partial downloads and editing workspaces older than this are deleted */
+  tempTtlHours: number;
+}
+
+export interface MaintenanceReport {
+  thumbnailBytes: number;
+  decompiledBytes: number;
+  /** Minecraft versions whose decompiled sources were deleted */
+  decompiledVersions: string[];
+  tempBytes: number;
+  reclaimedBytes: number;
+}
+
+/**
+ * Set the cache TTLs and turn background maintenance on or off
+ * @returns The settings now in effect
+ */
+export async function setCacheMaintenanceSettings(
+  settings: MaintenanceSettings,
+): Promise<MaintenanceSettings> {
+  return invoke<MaintenanceSettings>("set_cache_maintenance_settings", {
+    settings,
+  });
+}
+
+/**
+ * Cache TTLs currently in effect
+ */
+export async function getCacheMaintenanceSettings(): Promise<MaintenanceSettings> {
+  return invoke<MaintenanceSettings>("get_cache_maintenance_settings");
+}
+
+/**
+ * Prune every cache past its TTL now, even while background maintenance is off
+ */
+export async function runCacheMaintenance(): Promise<MaintenanceReport> {
+  return invoke<MaintenanceReport>("run_cache_maintenance");
+}
+
+export type AssetNodeKind =
+  | "blockstate"
+  | "model"
+  | "texture"
+  | "item"
+  | "entity";
+
+/**
+ * Reference graph of an asset. Node IDs carry their kind
+ * ("model:minecraft:block/oak_log").
+ */
+export interface AssetGraph {
+  root: string;
+  /** Breadth-first from the root */
+  nodes: {
+    id: string;
+    kind: AssetNodeKind;
+    /** Resource location ("minecraft:block/oak_log") */
+    location: string;
+    /** Pack the file is read from; null when no pack has it */
+    packId: string | null;
+  }[];
+  edges: {
+    from: string;
+    to: string;
+    kind: "model" | "parent" | "texture" | "override";
+    /** Texture variables, blockstate variants or multipart conditions */
+    label: string | null;
+  }[];
+  /** True when the depth or node limit left references unexplored */
+  truncated: boolean;
+}
+
+/**
+ * Follow an asset's references (blockstate -> models -> textures, item ->
+ * models, entity -> textures) through the pack stack
+ * @param rootAsset - Asset ID ("minecraft:block/oak_log") or node ID
+ *   ("entity:minecraft:cow")
+ * @param depth - Edges to follow from the root
+ */
+export async function getAssetGraph(
+  rootAsset: string,
+  depth: number,
+  packOrder: string[],
+  packsDir: string,
+): Promise<AssetGraph> {
+  return invoke<AssetGraph>("get_asset_graph", {
+    rootAsset,
+    depth,
+    packOrder,
+    packsDir,
+  });
+}
+
 // ============================================================================
 // BLOCK PARTICLE EMISSIONS
 // ============================================================================
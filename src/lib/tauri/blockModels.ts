@@ -1,5 +1,6 @@
 import { invoke } from "@tauri-apps/api/core";
 import { applySchemaEnhancements } from "./blockModelsUtils";
+import type { JEMFile } from "@lib/emf/jemLoader";
 
 /**
  * Minecraft block model JSON structure
@@ -9,6 +10,18 @@ export interface BlockModel {
   textures?: Record<string, string>;
   elements?: ModelElement[];
   ambientocclusion?: boolean;
+  display?: Record<string, DisplayTransform>;
+}
+
+/**
+ * How a model is posed in one display context (gui, ground, fixed, ...)
+ *
+ * Applied in the order rotation, translation (1/16ths of a block), scale.
+ */
+export interface DisplayTransform {
+  rotation: [number, number, number];
+  translation: [number, number, number];
+  scale: [number, number, number];
 }
 
 /**
@@ -85,23 +98,52 @@ export interface ResolutionResult {
   models: ResolvedModel[];
 }
 
+/**
+ * World coordinates of a block, which the game picks weighted variants by
+ */
+export interface BlockPosition {
+  x: number;
+  y: number;
+  z: number;
+}
+
+/**
+ * Minecraft version a workspace is pinned to; vanilla fallback data must match it
+ */
+export interface TargetVersion {
+  version: string;
+  packFormat?: number;
+}
+
+/**
+ * What resolution does when a pack lacks an asset: fall back to vanilla,
+ * fail, or substitute a placeholder model
+ */
+export type MissingAssetPolicy = "use_vanilla" | "pack_only" | "placeholder";
+
 /**
  * Read a Minecraft block model JSON file from a resource pack
  *
  * @param packId - ID of the resource pack to read from
  * @param modelId - Model ID (e.g., "minecraft:block/dirt" or "block/dirt")
  * @param packsDir - Directory containing resource packs
+ * @param target - Optional workspace target version for vanilla fallback
+ * @param policy - Optional missing-asset policy (defaults to vanilla fallback)
  * @returns Fully resolved BlockModel with parent inheritance applied
  */
 export async function readBlockModel(
   packId: string,
   modelId: string,
   packsDir: string,
+  target?: TargetVersion,
+  policy?: MissingAssetPolicy,
 ): Promise<BlockModel> {
   return invoke<BlockModel>("read_block_model", {
     packId,
     modelId,
     packsDir,
+    target: target ?? null,
+    policy: policy ?? null,
   });
 }
 
@@ -111,17 +153,23 @@ export async function readBlockModel(
  * @param packId - ID of the resource pack to read from
  * @param blockId - Block ID (e.g., "minecraft:furnace")
  * @param packsDir - Directory containing resource packs
+ * @param target - Optional workspace target version for vanilla fallback
+ * @param policy - Optional missing-asset policy (defaults to vanilla fallback)
  * @returns Schema describing the block's properties and default state
  */
 export async function getBlockStateSchema(
   packId: string,
   blockId: string,
   packsDir: string,
+  target?: TargetVersion,
+  policy?: MissingAssetPolicy,
 ): Promise<BlockStateSchema> {
   const schema = await invoke<BlockStateSchema>("get_block_state_schema", {
     packId,
     blockId,
     packsDir,
+    target: target ?? null,
+    policy: policy ?? null,
   });
 
   applySchemaEnhancements(schema, blockId);
@@ -137,6 +185,10 @@ export async function getBlockStateSchema(
  * @param packsDir - Directory containing resource packs
  * @param stateProps - Optional state properties (e.g., {facing: "north", lit: "true"})
  * @param seed - Optional seed for deterministic random selection
+ * @param target - Optional workspace target version for vanilla fallback
+ * @param policy - Optional missing-asset policy (defaults to vanilla fallback)
+ * @param position - Optional world position; weighted variants are then picked
+ *   as the game picks them there, instead of by seed
  * @returns Resolution result with models and transformations
  */
 export async function resolveBlockState(
@@ -145,6 +197,9 @@ export async function resolveBlockState(
   packsDir: string,
   stateProps?: Record<string, string>,
   seed?: number,
+  target?: TargetVersion,
+  policy?: MissingAssetPolicy,
+  position?: BlockPosition,
 ): Promise<ResolutionResult> {
   return invoke<ResolutionResult>("resolve_block_state", {
     packId,
@@ -152,6 +207,493 @@ export async function resolveBlockState(
     packsDir,
     stateProps: stateProps ?? null,
     seed: seed ?? null,
+    target: target ?? null,
+    policy: policy ?? null,
+    position: position ?? null,
+  });
+}
+
+/**
+ * Resolve many blockstates in one IPC call
+ *
+ * Packs are scanned once for the whole batch; a failing request doesn't fail
+ * the others.
+ *
+ * @param packsDir - Directory containing resource packs
+ * @param requests - Blockstates to resolve
+ * @param target - Optional workspace target version for vanilla fallback
+ * @param policy - Optional missing-asset policy (defaults to vanilla fallback)
+ * @returns One result or error per request, in request order
+ */
+export async function resolveBlockStatesBatch(
+  packsDir: string,
+  requests: {
+    packId: string;
+    blockId: string;
+    stateProps?: Record<string, string>;
+    seed?: number;
+    position?: BlockPosition;
+  }[],
+  target?: TargetVersion,
+  policy?: MissingAssetPolicy,
+): Promise<
+  { result?: ResolutionResult; error?: { code: string; message: string } }[]
+> {
+  return invoke("resolve_block_states_batch", {
+    packsDir,
+    requests,
+    target: target ?? null,
+    policy: policy ?? null,
+  });
+}
+
+/**
+ * One page of a block's state permutations, each resolved to its models
+ */
+export interface StatePermutationPage {
+  blockId: string;
+  total: number;
+  truncated: boolean;
+  offset: number;
+  permutations: ResolutionResult[];
+}
+
+/**
+ * List every state permutation of a block, resolved, a page at a time
+ *
+ * Permutations are ordered with the last property changing fastest. Blocks
+ * with huge state spaces are capped; `truncated` tells when that happened.
+ *
+ * @param packId - ID of the resource pack to read from
+ * @param blockId - Block ID (e.g., "minecraft:redstone_wire")
+ * @param packsDir - Directory containing resource packs
+ * @param offset - Optional index of the first permutation to return
+ * @param limit - Optional page size (defaults to 64, at most 256)
+ * @param target - Optional workspace target version for vanilla fallback
+ * @param policy - Optional missing-asset policy (defaults to vanilla fallback)
+ * @returns The requested page and the total number of permutations
+ */
+export async function listBlockStatePermutations(
+  packId: string,
+  blockId: string,
+  packsDir: string,
+  offset?: number,
+  limit?: number,
+  target?: TargetVersion,
+  policy?: MissingAssetPolicy,
+): Promise<StatePermutationPage> {
+  return invoke<StatePermutationPage>("list_block_state_permutations", {
+    packId,
+    blockId,
+    packsDir,
+    offset: offset ?? null,
+    limit: limit ?? null,
+    target: target ?? null,
+    policy: policy ?? null,
+  });
+}
+
+/**
+ * An entry of an item model's `overrides`
+ */
+export interface ItemOverride {
+  predicate: Record<string, number>;
+  model: string;
+}
+
+/**
+ * An item resolved for a set of predicate values
+ */
+export interface ResolvedItemModel {
+  itemId: string;
+  modelId: string;
+  overrideIndex: number | null;
+  builtin: "generated" | "entity" | null;
+  model: BlockModel;
+  overrides: ItemOverride[];
+}
+
+/**
+ * Resolve an item model, picking the override the game would show
+ *
+ * @param packId - ID of the resource pack to read from
+ * @param itemId - Item ID (e.g., "minecraft:bow")
+ * @param packsDir - Directory containing resource packs
+ * @param predicates - Optional predicate values (e.g., {custom_model_data: 2}); missing ones count as 0
+ * @param target - Optional workspace target version for vanilla fallback
+ * @param policy - Optional missing-asset policy (defaults to vanilla fallback)
+ * @returns Selected model with parent inheritance applied, and the item's overrides
+ */
+export async function resolveItemModel(
+  packId: string,
+  itemId: string,
+  packsDir: string,
+  predicates?: Record<string, number>,
+  target?: TargetVersion,
+  policy?: MissingAssetPolicy,
+): Promise<ResolvedItemModel> {
+  return invoke<ResolvedItemModel>("resolve_item_model", {
+    packId,
+    itemId,
+    packsDir,
+    predicates: predicates ?? null,
+    target: target ?? null,
+    policy: policy ?? null,
+  });
+}
+
+/**
+ * Problem with a model's textures; each renders as the missing texture in game
+ */
+export type TextureProblem =
+  | { kind: "unresolved_variable"; variable: string; reference: string }
+  | {
+      kind: "undefined_face_variable";
+      element: number;
+      face: string;
+      reference: string;
+    }
+  | { kind: "missing_texture"; variable: string; texture: string };
+
+/**
+ * A model's textures after parent merging, with what is broken about them
+ */
+export interface ModelDiagnosis {
+  modelId: string;
+  chain: string[];
+  textures: Record<string, string>;
+  problems: TextureProblem[];
+}
+
+/**
+ * Diagnose a model's texture variables and the textures they point at
+ *
+ * @param packId - ID of the resource pack the model is in
+ * @param modelId - Model ID (e.g., "minecraft:block/furnace")
+ * @param packsDir - Directory containing resource packs
+ * @param target - Optional workspace target version for vanilla fallback
+ * @returns Resolved texture map and broken references
+ */
+export async function diagnoseModel(
+  packId: string,
+  modelId: string,
+  packsDir: string,
+  target?: TargetVersion,
+): Promise<ModelDiagnosis> {
+  return invoke<ModelDiagnosis>("diagnose_model", {
+    packId,
+    modelId,
+    packsDir,
+    target: target ?? null,
+  });
+}
+
+/**
+ * A problem found in a pack's blockstate or model JSON
+ */
+export interface LintDiagnostic {
+  file: string;
+  /** 1-based line the problem is most likely on */
+  line: number | null;
+  severity: "error" | "warning";
+  code: string;
+  message: string;
+}
+
+/**
+ * Lint findings for a whole pack
+ */
+export interface LintReport {
+  packId: string;
+  blockstatesChecked: number;
+  modelsChecked: number;
+  errors: number;
+  warnings: number;
+  truncated: boolean;
+  diagnostics: LintDiagnostic[];
+}
+
+/**
+ * Lint every blockstate and model JSON file in a pack
+ *
+ * @param packId - ID of the resource pack to lint
+ * @param packsDir - Directory containing resource packs
+ * @param target - Optional workspace target version for vanilla fallback
+ * @returns Diagnostics for a problems panel, with counts by severity
+ */
+export async function lintPack(
+  packId: string,
+  packsDir: string,
+  target?: TargetVersion,
+): Promise<LintReport> {
+  return invoke<LintReport>("lint_pack", {
+    packId,
+    packsDir,
+    target: target ?? null,
+  });
+}
+
+/**
+ * A broken or dangling reference between a pack's files
+ */
+export type IntegrityIssue =
+  | { kind: "missing_texture"; model: string; variable: string; texture: string }
+  | { kind: "orphan_mcmeta"; file: string }
+  | { kind: "missing_sound"; file: string; event: string; sound: string }
+  | { kind: "missing_cit_file"; rule: string; file: string }
+  | { kind: "unused_file"; file: string };
+
+/**
+ * Integrity findings for a whole pack
+ */
+export interface PackIntegrityReport {
+  packId: string;
+  filesChecked: number;
+  truncated: boolean;
+  issues: IntegrityIssue[];
+}
+
+/**
+ * Check the references between a pack's files: missing textures, sounds and
+ * CIT files, .mcmeta files without their texture, and unused files
+ *
+ * @param packId - ID of the resource pack to check
+ * @param packsDir - Directory containing resource packs
+ * @param target - Optional workspace target version for vanilla fallback
+ * @returns The issues found, in file order
+ */
+export async function checkPackIntegrity(
+  packId: string,
+  packsDir: string,
+  target?: TargetVersion,
+): Promise<PackIntegrityReport> {
+  return invoke<PackIntegrityReport>("check_pack_integrity", {
+    packId,
+    packsDir,
+    target: target ?? null,
+  });
+}
+
+/**
+ * Files written by a model export and the size of the mesh
+ */
+export interface ExportSummary {
+  files: string[];
+  vertices: number;
+  triangles: number;
+  materials: number;
+}
+
+/**
+ * Export a block's resolved models to glTF or OBJ, e.g. for Blender
+ *
+ * glTF exports are a single file with textures embedded; OBJ exports write
+ * an .mtl and the textures next to the .obj.
+ *
+ * @param packId - ID of the resource pack to read from
+ * @param blockId - Block ID (e.g., "minecraft:oak_stairs")
+ * @param packsDir - Directory containing resource packs
+ * @param outputPath - File to write, ending in .gltf or .obj
+ * @param stateProps - Optional state properties (e.g., {facing: "north"})
+ * @param target - Optional workspace target version for vanilla fallback
+ * @param policy - Optional missing-asset policy (defaults to vanilla fallback)
+ * @returns Files written and mesh statistics
+ */
+export async function exportBlockModel(
+  packId: string,
+  blockId: string,
+  packsDir: string,
+  outputPath: string,
+  stateProps?: Record<string, string>,
+  target?: TargetVersion,
+  policy?: MissingAssetPolicy,
+): Promise<ExportSummary> {
+  return invoke<ExportSummary>("export_block_model", {
+    packId,
+    blockId,
+    packsDir,
+    stateProps: stateProps ?? null,
+    outputPath,
+    target: target ?? null,
+    policy: policy ?? null,
+  });
+}
+
+/**
+ * Result of importing a Blockbench project into a pack
+ */
+export interface BbmodelImport {
+  assetId: string;
+  /** "model" for block/item projects, "entity" for OptiFine entity projects */
+  kind: "model" | "entity";
+  /** Pack-relative paths written, the model file first */
+  files: string[];
+  /** What couldn't be converted exactly (multi-axis rotations, rotated cubes, ...) */
+  warnings: string[];
+}
+
+/**
+ * Import a Blockbench .bbmodel project into a pack
+ *
+ * Block and item projects become model JSON; OptiFine entity projects become
+ * a JEM model. ZIP packs are written to their working copy.
+ *
+ * @param projectPath - Path to the .bbmodel file
+ * @param targetPack - ID of the pack to write into
+ * @param assetId - Model ID (e.g., "minecraft:block/lamp") or entity ID (e.g., "minecraft:cow")
+ * @param packsDir - Directory containing resource packs
+ * @returns Files written and conversion warnings
+ */
+export async function importBbmodel(
+  projectPath: string,
+  targetPack: string,
+  assetId: string,
+  packsDir: string,
+): Promise<BbmodelImport> {
+  return invoke<BbmodelImport>("import_bbmodel", {
+    projectPath,
+    targetPack,
+    assetId,
+    packsDir,
+  });
+}
+
+/**
+ * A problem found in an OptiFine CEM model
+ */
+export interface JemIssue {
+  /** JSON path (e.g., "models[2].boxes[0]"); empty for the whole file */
+  path: string;
+  severity: "error" | "warning";
+  code: string;
+  message: string;
+}
+
+/**
+ * Result of validating a .jem file
+ */
+export interface JemValidation {
+  /** Entity the file models (e.g., "cow") */
+  entity: string;
+  /** Vanilla template part names were checked against (e.g., "cow_21.4") */
+  template: string | null;
+  /** Parsed model with JPM imports filled in, null if it didn't parse */
+  model: JEMFile | null;
+  issues: JemIssue[];
+}
+
+/**
+ * Validate a CEM model (.jem) in a pack, including its JPM imports
+ *
+ * @param packId - ID of the resource pack containing the model
+ * @param jemPath - Pack-relative path (e.g., "assets/minecraft/optifine/cem/cow.jem")
+ * @param packsDir - Directory containing resource packs
+ * @param target - Optional target version whose part layout to check against
+ * @returns The parsed model and every problem found
+ */
+export async function validateJem(
+  packId: string,
+  jemPath: string,
+  packsDir: string,
+  target?: TargetVersion,
+): Promise<JemValidation> {
+  return invoke<JemValidation>("validate_jem", {
+    packId,
+    jemPath,
+    packsDir,
+    target: target ?? null,
+  });
+}
+
+/**
+ * Inputs for one frame of CEM animation
+ */
+export interface CemAnimationInputs {
+  /** Entity inputs by name (e.g., limb_swing, head_yaw, is_child as 0/1) */
+  entity?: Record<string, number>;
+  /** Custom variables ("var.x", "varb.y") from the previous frame */
+  variables?: Record<string, number>;
+}
+
+/**
+ * A part's animated values; rotations in radians, translations in pixels
+ */
+export interface CemPartTransform {
+  tx: number;
+  ty: number;
+  tz: number;
+  rx: number;
+  ry: number;
+  rz: number;
+  sx: number;
+  sy: number;
+  sz: number;
+  visible: boolean;
+  visibleBoxes: boolean;
+}
+
+/**
+ * Everything a model's animations produced for one frame
+ */
+export interface CemAnimationFrame {
+  parts: Record<string, CemPartTransform>;
+  /** Pass back as `variables` for the next frame */
+  variables: Record<string, number>;
+  render: Record<string, number>;
+  /** Animation entries that couldn't be compiled */
+  errors: { target: string; message: string }[];
+}
+
+/**
+ * Evaluate a CEM model's animations for one frame in the backend
+ *
+ * @param model - Parsed model, e.g. from validateJem
+ * @param inputs - Entity inputs and the previous frame's variables
+ * @returns Part transforms, variables and compile errors
+ */
+export async function evaluateCemAnimations(
+  model: JEMFile,
+  inputs: CemAnimationInputs,
+): Promise<CemAnimationFrame> {
+  return invoke<CemAnimationFrame>("evaluate_cem_animations", {
+    model,
+    inputs,
+  });
+}
+
+/**
+ * Vanilla textures of one entity, as texture asset IDs
+ */
+export interface EntityTextures {
+  /** Texture without a variant (e.g., "minecraft:entity/cow/cow") */
+  default: string | null;
+  /**
+   * Textures by group, then name: "variant" for registry variants (plus
+   * "tame"/"angry" for wolves), subfolders like "profession", shared
+   * prefixes like "markings", and "texture" for the rest of the folder
+   */
+  variants: Record<string, Record<string, string>>;
+}
+
+/**
+ * Entity textures of a Minecraft version
+ */
+export interface EntityTextureMap {
+  version: string;
+  entities: Record<string, EntityTextures>;
+}
+
+/**
+ * Map entity IDs to their vanilla textures, read from the version's JAR
+ *
+ * @param target - Optional workspace target version (defaults to the cached vanilla version)
+ * @returns Textures per entity; pass the asset IDs to getPackTexturePath
+ */
+export async function getEntityTextures(
+  target?: TargetVersion | null,
+): Promise<EntityTextureMap> {
+  return invoke<EntityTextureMap>("get_entity_textures", {
+    target: target ?? null,
   });
 }
 
@@ -164,16 +706,114 @@ export async function resolveBlockState(
  * @param packId - ID of the resource pack to read from
  * @param modelId - Model ID (e.g., "minecraft:block/acacia_log_horizontal")
  * @param packsDir - Directory containing resource packs
+ * @param target - Optional workspace target version for vanilla fallback
+ * @param policy - Optional missing-asset policy (defaults to vanilla fallback)
  * @returns BlockModel with parent inheritance applied
  */
 export async function loadModelJson(
   packId: string,
   modelId: string,
   packsDir: string,
+  target?: TargetVersion,
+  policy?: MissingAssetPolicy,
 ): Promise<BlockModel> {
   return invoke<BlockModel>("load_model_json", {
     packId,
     modelId,
     packsDir,
+    target: target ?? null,
+    policy: policy ?? null,
+  });
+}
+
+/**
+ * Colormap a biome tint is sampled from
+ */
+export type ColormapType = "grass" | "foliage" | "dry_foliage";
+
+/**
+ * Where to sample a colormap: a biome ID, or a temperature and downfall
+ */
+export interface Climate {
+  biome?: string;
+  temperature?: number;
+  downfall?: number;
+}
+
+/**
+ * A tint color, with the multiplier applied to tinted faces
+ */
+export interface Tint {
+  rgb: [number, number, number];
+  hex: string;
+  multiplier: [number, number, number];
+}
+
+/**
+ * Where a block's tint comes from
+ */
+export type TintSource =
+  | { kind: "colormap"; colormap: ColormapType }
+  | { kind: "water" }
+  | { kind: "fixed"; color: number };
+
+/**
+ * A block's tint in a biome
+ */
+export interface BlockTint {
+  blockId: string;
+  source: TintSource;
+  tint: Tint;
+}
+
+/**
+ * Compute a biome tint from a colormap, as the game samples it
+ *
+ * @param colormapType - Colormap to sample
+ * @param climate - Biome ID, or temperature and downfall
+ * @param packId - Optional pack whose colormap overrides vanilla's
+ * @param packsDir - Directory containing resource packs, needed with packId
+ * @param target - Optional workspace target version for vanilla fallback
+ * @returns Tint color and RGB multiplier
+ */
+export async function computeTint(
+  colormapType: ColormapType,
+  climate: Climate,
+  packId?: string,
+  packsDir?: string,
+  target?: TargetVersion,
+): Promise<Tint> {
+  return invoke<Tint>("compute_tint", {
+    colormapType,
+    climate,
+    packId: packId ?? null,
+    packsDir: packsDir ?? null,
+    target: target ?? null,
+  });
+}
+
+/**
+ * Compute the tint applied to a block's faces that have a tintindex
+ *
+ * @param blockId - Block ID (e.g., "minecraft:oak_leaves")
+ * @param climate - Biome ID, or temperature and downfall
+ * @param packId - Optional pack whose colormaps override vanilla's
+ * @param packsDir - Directory containing resource packs, needed with packId
+ * @param target - Optional workspace target version for vanilla fallback
+ * @returns The block's tint, or null for blocks the game doesn't tint
+ */
+export async function computeBlockTint(
+  blockId: string,
+  climate: Climate,
+  packId?: string,
+  packsDir?: string,
+  target?: TargetVersion,
+): Promise<BlockTint | null> {
+  return invoke<BlockTint | null>("compute_block_tint", {
+    blockId,
+    climate,
+    packId: packId ?? null,
+    packsDir: packsDir ?? null,
+    target: target ?? null,
   });
 }
@@ -0,0 +1,24 @@
+import { describe, expect, it } from "vitest";
+import { frameSize, resolutionLabel } from "./textureResolution";
+
+const info = (width: number, height: number) => ({
+  width,
+  height,
+  bitDepth: 8,
+  colorType: 6,
+});
+
+describe("textureResolution", () => {
+  it("uses the frame size of animated textures", () => {
+    expect(frameSize(info(32, 512))).toBe(32);
+  });
+
+  it("labels textures by their size without a vanilla reference", () => {
+    expect(resolutionLabel(info(64, 64))).toBe("64x");
+  });
+
+  it("scales labels relative to the vanilla texture", () => {
+    expect(resolutionLabel(info(256, 256), info(64, 64))).toBe("64x");
+    expect(resolutionLabel(info(16, 16), info(16, 16))).toBe("16x");
+  });
+});
@@ -0,0 +1,35 @@
+/**
+ * Texture Resolution Utilities
+ *
+ * Pack resolutions are named after the block texture size they correspond to
+ * ("16x" is vanilla, "128x" is eight times as detailed). Entity and GUI
+ * textures have other sizes in vanilla, so a texture's resolution is compared
+ * with the vanilla copy of the same texture when one is known.
+ */
+import type { TextureInfo } from "@state";
+
+/** Vanilla block texture size, the reference for "Nx" labels */
+const VANILLA_BLOCK_SIZE = 16;
+
+/**
+ * Side length of one frame; animated textures stack square frames vertically
+ */
+export function frameSize(info: TextureInfo): number {
+  return Math.min(info.width, info.height);
+}
+
+/**
+ * Badge label for a texture, e.g. "32x"
+ *
+ * @param info - Header of the pack's texture
+ * @param vanilla - Header of the vanilla texture, if known
+ */
+export function resolutionLabel(
+  info: TextureInfo,
+  vanilla?: TextureInfo,
+): string {
+  const size = frameSize(info);
+  if (!vanilla) return `${size}x`;
+  const scale = size / frameSize(vanilla);
+  return `${Math.round(scale * VANILLA_BLOCK_SIZE)}x`;
+}
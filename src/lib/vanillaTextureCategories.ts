@@ -0,0 +1,54 @@
+/**
+ * Vanilla texture categories preference
+ *
+ * The backend keeps the categories in memory only, so the user's choice is
+ * saved in localStorage and pushed to the backend before vanilla textures are
+ * initialized.
+ */
+import {
+  setVanillaTextureCategories,
+  type VanillaTextureCategory,
+} from "@lib/tauri";
+
+const STORAGE_KEY = "weaverbird_vanilla_texture_categories";
+
+/**
+ * Saved categories, or null when never set (the backend extracts all)
+ */
+export function loadTextureCategories(): VanillaTextureCategory[] | null {
+  try {
+    const stored = localStorage.getItem(STORAGE_KEY);
+    return stored ? (JSON.parse(stored) as VanillaTextureCategory[]) : null;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Save the categories and apply them to the backend
+ * @returns The categories now extracted
+ */
+export async function saveTextureCategories(
+  categories: VanillaTextureCategory[],
+): Promise<VanillaTextureCategory[]> {
+  try {
+    localStorage.setItem(STORAGE_KEY, JSON.stringify(categories));
+  } catch (error) {
+    console.warn(
+      "[vanillaTextureCategories] Failed to save preference:",
+      error,
+    );
+  }
+  return setVanillaTextureCategories(categories);
+}
+
+/**
+ * Apply the saved categories to the backend; call before initializing vanilla
+ * textures
+ */
+export async function applyStoredTextureCategories(): Promise<void> {
+  const categories = loadTextureCategories();
+  if (categories) {
+    await setVanillaTextureCategories(categories);
+  }
+}
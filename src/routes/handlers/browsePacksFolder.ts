@@ -2,6 +2,7 @@
  * Handler for browsing and loading resource packs folder
  */
 
+import { useStore } from "@state";
 import type { PackMeta } from "@state";
 import { openFolderDialog, scanPacksFolder, getEntityVersionVariants, formatError } from "@lib/tauri";
 
@@ -69,6 +70,7 @@ export async function browsePacksFolder(params: {
 
     console.log("[browsePacksFolder] Ingesting providers (batch)...");
     ingestAllProviders(result.providers);
+    useStore.getState().ingestTextureInfo(result.texture_info ?? {});
     console.log("[browsePacksFolder] Providers ingested");
 
     // Load entity version variants
@@ -86,6 +88,11 @@ export async function browsePacksFolder(params: {
     if (result.packs.length > 0) {
       console.log("[browsePacksFolder] Setting pack order...");
       setPackOrder(result.packs.map((p: PackMeta) => p.id));
+      // Built-in packs (Programmer Art, High Contrast) are opt-in
+      const { disablePack } = useStore.getState();
+      result.packs
+        .filter((p: PackMeta) => p.builtin)
+        .forEach((p: PackMeta) => disablePack(p.id));
     }
 
     console.log("[browsePacksFolder] All done! Setting success message...");
@@ -2,6 +2,7 @@
  * Handler for launcher selection change
  */
 
+import { useStore } from "@state";
 import type { PackMeta } from "@state";
 import type { LauncherInfo } from "@lib/tauri";
 import { getLauncherResourcepacksDir, scanPacksFolder, getEntityVersionVariants, formatError } from "@lib/tauri";
@@ -76,6 +77,7 @@ export async function handleLauncherChangeLogic(params: {
 
     ingestAssets(result.assets);
     ingestAllProviders(result.providers);
+    useStore.getState().ingestTextureInfo(result.texture_info ?? {});
 
     // Load entity version variants
     try {
@@ -90,6 +92,11 @@ export async function handleLauncherChangeLogic(params: {
     // Set pack order to scan order
     if (result.packs.length > 0) {
       setPackOrder(result.packs.map((p: PackMeta) => p.id));
+      // Built-in packs (Programmer Art, High Contrast) are opt-in
+      const { disablePack } = useStore.getState();
+      result.packs
+        .filter((p: PackMeta) => p.builtin)
+        .forEach((p: PackMeta) => disablePack(p.id));
     }
 
     setSuccessMessage(
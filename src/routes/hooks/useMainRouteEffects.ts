@@ -1,14 +1,33 @@
 /**
  * Hook that handles initialization effects for the MainRoute component.
+ * - Saved low-memory and offline modes applied to the backend
+ * - Saved cache maintenance TTLs and log level applied to the backend
  * - Launcher detection on startup
  * - Menu event listeners (macOS)
- * - Vanilla texture initialization
+ * - Saved shared cache folder applied, then vanilla texture initialization
+ * - Recovery of nest builds interrupted by a crash
+ * - Live texture reload when an asset is refreshed
  * - Search query page reset
  */
 import { useEffect } from "react";
 import { listen } from "@tauri-apps/api/event";
-import { initializeVanillaTextures, detectLaunchers } from "@lib/tauri";
-import type { LauncherInfo } from "@lib/tauri";
+import { ask } from "@tauri-apps/plugin-dialog";
+import {
+  initializeVanillaTextures,
+  detectLaunchers,
+  listInterruptedBuilds,
+  recoverInterruptedBuilds,
+  setBackendLocale,
+} from "@lib/tauri";
+import type { AssetUpdatedEvent, LauncherInfo } from "@lib/tauri";
+import { uncacheTexture } from "@lib/three/textureLoader";
+import { applyStoredLowMemoryMode } from "@lib/lowMemoryMode";
+import { applyStoredOfflineMode } from "@lib/offlineMode";
+import { applyStoredDownloadSettings } from "@lib/downloadSettings";
+import { applyStoredCacheMaintenance } from "@lib/cacheMaintenance";
+import { applyStoredLogLevel } from "@lib/logLevel";
+import { applyStoredSharedCache } from "@lib/sharedCache";
+import { applyStoredTextureCategories } from "@lib/vanillaTextureCategories";
 
 interface UseMainRouteEffectsProps {
   setAvailableLaunchers: (launchers: LauncherInfo[]) => void;
@@ -25,6 +44,79 @@ export function useMainRouteEffects({
   setCurrentPage,
   searchQuery,
 }: UseMainRouteEffectsProps): void {
+  // Apply the saved low-memory preference before the first scan
+  useEffect(() => {
+    applyStoredLowMemoryMode().catch((error) => {
+      console.warn("Failed to apply low-memory mode:", error);
+    });
+  }, []);
+
+  // Apply the saved offline preference before anything is downloaded
+  useEffect(() => {
+    applyStoredOfflineMode().catch((error) => {
+      console.warn("Failed to apply offline mode:", error);
+    });
+  }, []);
+
+  // Apply the saved mirrors and proxy before anything is downloaded
+  useEffect(() => {
+    applyStoredDownloadSettings().catch((error) => {
+      console.warn("Failed to apply download settings:", error);
+    });
+  }, []);
+
+  // Apply the saved cache TTLs before background maintenance first runs
+  useEffect(() => {
+    applyStoredCacheMaintenance().catch((error) => {
+      console.warn("Failed to apply cache maintenance settings:", error);
+    });
+  }, []);
+
+  // Apply the saved log level
+  useEffect(() => {
+    applyStoredLogLevel().catch((error) => {
+      console.warn("Failed to apply log level:", error);
+    });
+  }, []);
+
+  // Match backend messages to the app language
+  useEffect(() => {
+    setBackendLocale(navigator.languages).catch((error) => {
+      console.warn("Failed to set backend locale:", error);
+    });
+  }, []);
+
+  // Offer to resume or roll back nest builds a crash left half-written
+  useEffect(() => {
+    const recoverBuilds = async () => {
+      const builds = await listInterruptedBuilds();
+      for (const build of builds) {
+        const resume = await ask(
+          `A Weaver Nest build into ${build.outputDir} was interrupted after ${build.filesWritten} file(s). Resume it, or roll back the files it wrote?`,
+          {
+            title: "Interrupted build",
+            kind: "warning",
+            okLabel: "Resume",
+            cancelLabel: "Roll back",
+          },
+        );
+        const [outcome] = await recoverInterruptedBuilds(
+          [build.outputDir],
+          resume ? "resume" : "rollback",
+        );
+        if (outcome?.error) {
+          console.warn(
+            `Failed to recover build in ${build.outputDir}:`,
+            outcome.error.message,
+          );
+        }
+      }
+    };
+    recoverBuilds().catch((error) => {
+      console.warn("Failed to check for interrupted builds:", error);
+    });
+  }, []);
+
   // Detect launchers on startup
   useEffect(() => {
     const loadLaunchers = async () => {
@@ -51,6 +143,20 @@ export function useMainRouteEffects({
     });
   }, [setSettingsOpen]);
 
+  // Drop cached textures of refreshed assets so open previews reload them
+  useEffect(() => {
+    const unlistenPromise = listen<AssetUpdatedEvent>(
+      "asset-updated",
+      (event) => {
+        uncacheTexture(event.payload.packPath, event.payload.assetId);
+      },
+    );
+
+    return () => {
+      void unlistenPromise.then((fn) => fn());
+    };
+  }, []);
+
   // Initialize vanilla textures on startup
   useEffect(() => {
     let cancelled = false;
@@ -58,6 +164,23 @@ export function useMainRouteEffects({
     const initVanillaTextures = async () => {
       if (cancelled) return;
 
+      // Textures may already be extracted in the shared cache
+      try {
+        await applyStoredSharedCache();
+      } catch (error) {
+        console.warn(
+          "Shared cache folder unavailable, using local cache:",
+          error,
+        );
+      }
+
+      // Before initializing, so newly enabled categories get extracted
+      try {
+        await applyStoredTextureCategories();
+      } catch (error) {
+        console.warn("Failed to apply vanilla texture categories:", error);
+      }
+
       try {
         await initializeVanillaTextures();
         console.log("Vanilla textures initialized");
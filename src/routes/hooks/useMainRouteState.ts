@@ -5,6 +5,7 @@
 import { useState, useMemo, useEffect } from "react";
 import type { ItemDisplayMode } from "@lib/itemDisplayModes";
 import { getBlockItemPair } from "@lib/assetUtils";
+import { assetProtocolUrl } from "@lib/tauri";
 import {
   useStore,
   useSelectPacksInOrder,
@@ -144,7 +145,7 @@ export function useMainRouteState() {
         name: p.name,
         size: p.size,
         description: p.description,
-        icon_data: p.icon_data,
+        icon_url: p.has_icon ? assetProtocolUrl(p.id, "pack.png") : undefined,
       })),
     [packs],
   );
@@ -156,7 +157,7 @@ export function useMainRouteState() {
         name: p.name,
         size: p.size,
         description: p.description,
-        icon_data: p.icon_data,
+        icon_url: p.has_icon ? assetProtocolUrl(p.id, "pack.png") : undefined,
       })),
     [disabledPacks],
   );
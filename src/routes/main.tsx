@@ -18,6 +18,14 @@ import { Settings } from "@components/Settings";
 import { MinecraftLocations } from "@components/Settings/components/MinecraftLocations";
 import { VanillaTextureVersion } from "@components/Settings/components/VanillaTextureVersion";
 import { TargetVersion } from "@components/Settings/components/TargetVersion";
+import { Performance } from "@components/Settings/components/Performance";
+import { Diagnostics } from "@components/Settings/components/Diagnostics";
+import { SharedCache } from "@components/Settings/components/SharedCache";
+import { OfflineMode } from "@components/Settings/components/OfflineMode";
+import { DownloadSettings } from "@components/Settings/components/DownloadSettings";
+import { CacheUsage } from "@components/Settings/components/CacheUsage";
+import { CacheMaintenance } from "@components/Settings/components/CacheMaintenance";
+import { VanillaTextureCategories } from "@components/Settings/components/VanillaTextureCategories";
 import { CanvasTypeSelector } from "@components/CanvasTypeSelector";
 import { BlockyTabs } from "@/ui/components/blocky-tabs/BlockyTabs";
 
@@ -201,8 +209,24 @@ export default function MainRoute() {
         isOpen={state.settingsOpen}
         onClose={() => state.setSettingsOpen(false)}
         minecraftTab={<MinecraftLocations />}
-        vanillaVersionTab={<VanillaTextureVersion />}
+        vanillaVersionTab={
+          <>
+            <VanillaTextureVersion />
+            <VanillaTextureCategories />
+            <SharedCache />
+            <OfflineMode />
+            <DownloadSettings />
+            <CacheUsage />
+            <CacheMaintenance />
+          </>
+        }
         targetVersionTab={<TargetVersion />}
+        performanceTab={
+          <>
+            <Performance />
+            <Diagnostics />
+          </>
+        }
       />
     </div>
   );
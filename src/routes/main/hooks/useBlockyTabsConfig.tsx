@@ -15,14 +15,14 @@ interface UseBlockyTabsConfigProps {
     name: string;
     size: number;
     description?: string;
-    icon_data?: string;
+    icon_url?: string;
   }>;
   disabledPackListItems: Array<{
     id: string;
     name: string;
     size: number;
     description?: string;
-    icon_data?: string;
+    icon_url?: string;
   }>;
   handleReorderPacks: (newOrder: string[]) => void;
   handleReorderDisabledPacks: (newOrder: string[]) => void;
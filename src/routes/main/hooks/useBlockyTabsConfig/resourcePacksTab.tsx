@@ -9,14 +9,14 @@ interface ResourcePacksTabProps {
     name: string;
     size: string;
     description?: string;
-    icon_data?: string;
+    icon_url?: string;
   }>;
   disabledPackListItems: Array<{
     id: string;
     name: string;
     size: string;
     description?: string;
-    icon_data?: string;
+    icon_url?: string;
   }>;
   handleReorderPacks: (newOrder: string[]) => void;
   handleReorderDisabledPacks: (newOrder: string[]) => void;
@@ -2,7 +2,13 @@
  * Asset management actions for Zustand store
  */
 import type { StateCreator } from "zustand";
-import type { AppState, PackId, AssetId, AssetRecord } from "../types";
+import type {
+  AppState,
+  PackId,
+  AssetId,
+  AssetRecord,
+  TextureInfo,
+} from "../types";
 import type { StoreActions } from "../storeActions";
 
 type WeaverbirdStore = AppState & StoreActions;
@@ -29,6 +35,14 @@ export const createAssetActions = (set: SetFn) => ({
     });
   },
 
+  ingestTextureInfo: (
+    textureInfo: Record<AssetId, Record<PackId, TextureInfo>>,
+  ) => {
+    set((state) => {
+      state.textureInfoByAsset = textureInfo;
+    });
+  },
+
   setOverride: (
     assetId: AssetId,
     packId: PackId | undefined,
@@ -95,4 +95,51 @@ export const createPackActions = (set: SetFn) => ({
       state.packFormats = formats;
     });
   },
+
+  /**
+   * Apply a deleted (no pack) or renamed pack to every ID-keyed index
+   * without rescanning the packs folder
+   */
+  applyPackLibraryChange: (packId: PackId, pack?: PackMeta) => {
+    set((state) => {
+      const newId = pack?.id;
+      const replaceId = (ids: PackId[]) =>
+        ids.flatMap((id) => {
+          if (id !== packId) return [id];
+          return newId === undefined ? [] : [newId];
+        });
+
+      delete state.packs[packId];
+      if (pack) {
+        state.packs[pack.id] = pack;
+      }
+      state.packOrder = replaceId(state.packOrder);
+      state.disabledPackIds = replaceId(state.disabledPackIds);
+
+      for (const [assetId, providers] of Object.entries(
+        state.providersByAsset,
+      )) {
+        if (providers.includes(packId)) {
+          state.providersByAsset[assetId] = replaceId(providers);
+        }
+      }
+
+      for (const [assetId, entry] of Object.entries(state.overrides)) {
+        if (!entry || entry.packId !== packId) continue;
+        if (newId === undefined) {
+          state.overrides[assetId] = undefined;
+        } else {
+          entry.packId = newId;
+        }
+      }
+
+      if (packId in state.packFormats) {
+        const format = state.packFormats[packId];
+        delete state.packFormats[packId];
+        if (newId !== undefined) {
+          state.packFormats[newId] = format;
+        }
+      }
+    });
+  },
 });
@@ -30,6 +30,7 @@ export {
   useSelectSetPackFormat,
   useSelectIngestPacks,
   useSelectSetPackFormats,
+  useSelectApplyPackLibraryChange,
   useSelectIngestAssets,
   useSelectIngestProviders,
   useSelectIngestAllProviders,
@@ -15,6 +15,7 @@ export const initialState: AppState = {
   disabledPackIds: [],
   assets: {},
   providersByAsset: {},
+  textureInfoByAsset: {},
   overrides: {},
   packFormats: {},
 
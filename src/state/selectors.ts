@@ -224,6 +224,8 @@ export const useSelectIngestPacks = () =>
   useStore((state) => state.ingestPacks);
 export const useSelectSetPackFormats = () =>
   useStore((state) => state.setPackFormats);
+export const useSelectApplyPackLibraryChange = () =>
+  useStore((state) => state.applyPackLibraryChange);
 export const useSelectDisablePack = () =>
   useStore((state) => state.disablePack);
 export const useSelectEnablePack = () => useStore((state) => state.enablePack);
@@ -8,6 +8,7 @@ import type {
   PackMeta,
   AssetRecord,
   EntityAnimationVariant,
+  TextureInfo,
 } from "./types";
 
 export interface StoreActions {
@@ -18,11 +19,15 @@ export interface StoreActions {
   enablePack: (packId: PackId, targetIndex?: number) => void;
   setDisabledPackOrder: (order: PackId[]) => void;
   setPackFormats: (formats: Record<string, number>) => void;
+  applyPackLibraryChange: (packId: PackId, pack?: PackMeta) => void;
 
   // Asset management
   ingestAssets: (assets: AssetRecord[]) => void;
   ingestProviders: (assetId: AssetId, providerIds: PackId[]) => void;
   ingestAllProviders: (providers: Record<AssetId, PackId[]>) => void;
+  ingestTextureInfo: (
+    textureInfo: Record<AssetId, Record<PackId, TextureInfo>>,
+  ) => void;
 
   // Overrides (pencil functionality)
   setOverride: (
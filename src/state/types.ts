@@ -20,8 +20,51 @@ export interface PackMeta {
   size: number;
   is_zip: boolean;
   description?: string;
-  icon_data?: string; // Base64-encoded PNG
+  has_icon?: boolean; // Has a pack.png, loaded with assetProtocolUrl(id, "pack.png")
   pack_format?: number; // Pack format version from pack.mcmeta
+  supported_formats?: { min: number; max: number }; // supported_formats range from pack.mcmeta
+  overlays?: PackOverlay[]; // Overlay directories from pack.mcmeta (pack_format 18+)
+  texture_resolution?: number; // Most common block texture width (16, 32, ...)
+  cem_dialect?: CemDialect; // Entity feature dialect of its CEM and random entity files
+  builtin?: boolean; // Built into the Minecraft JAR (Programmer Art, High Contrast); read-only
+  features?: string[]; // Experimental feature flags the pack needs ("minecraft:trade_rebalance")
+  credits?: PackCredits; // Author and license from weaverbird.json and LICENSE files
+  quarantine?: QuarantineReason[]; // Suspicious contents; the pack isn't indexed
+  resolved_path?: string; // Where the pack really is when path is a symlink or junction
+}
+
+/**
+ * Custom entity feature dialect a pack is written for: plain OptiFine, or
+ * EMF/ETF extensions (Fresh Animations style) that OptiFine alone loses
+ */
+export type CemDialect = "optifine" | "emf";
+
+/**
+ * Why a scan quarantined a pack
+ */
+export type QuarantineReason =
+  | { kind: "executable"; path: string } // .exe, .dll, .bat, .sh, ...
+  | { kind: "class_file"; path: string } // Compiled Java class
+  | { kind: "compression_ratio"; path: string; ratio: number } // Zip bomb
+  | { kind: "unsafe_path"; path: string }; // Entry escaping the pack ("../")
+
+/**
+ * Authorship and licensing a pack declares in sidecar files; nest builds list
+ * them in CREDITS.txt
+ */
+export interface PackCredits {
+  author?: string;
+  homepage?: string;
+  license?: string; // License name or SPDX identifier ("CC-BY-4.0")
+  license_file?: string; // License file at the pack root ("LICENSE.txt")
+}
+
+/**
+ * Overlay entry from pack.mcmeta, active for an inclusive pack_format range
+ */
+export interface PackOverlay {
+  directory: string;
+  formats: { min: number; max: number };
 }
 
 /**
@@ -64,6 +107,8 @@ export interface EntitiesState {
   disabledPackIds: PackId[];
   assets: Record<AssetId, AssetRecord>;
   providersByAsset: Record<AssetId, PackId[]>;
+  /** PNG header of each provider's texture, for resolution badges */
+  textureInfoByAsset: Record<AssetId, Record<PackId, TextureInfo>>;
   overrides: Record<AssetId, OverrideEntry | undefined>;
   packFormats: Record<string, number>; // Maps pack ID to pack format version
 }
@@ -78,6 +123,11 @@ export interface LauncherInfo {
   found: boolean;
   icon: string;
   icon_path?: string;
+  /**
+   * Where resource packs go, relative to minecraft_dir; `<instance>` stands
+   * for one instance folder
+   */
+  resourcepacks_layout: string;
 }
 
 /**
@@ -202,4 +252,57 @@ export interface ScanResult {
   packs: PackMeta[];
   assets: AssetRecord[];
   providers: Record<AssetId, PackId[]>;
+  /** Blocks that more than one pack defines OptiFine CTM for */
+  ctm_conflicts?: CtmConflict[];
+  /** Packs with identical content, e.g. the same ZIP under two file names */
+  duplicate_packs?: DuplicatePacks[];
+  /** Texture dimensions per asset and providing pack */
+  texture_info?: Record<AssetId, Record<PackId, TextureInfo>>;
+  /** ZIP packs that were damaged or still downloading */
+  errors?: PackScanError[];
+}
+
+/** A ZIP pack the scan couldn't read as it is */
+export interface PackScanError {
+  packId: PackId;
+  path: string;
+  reason: string;
+  /** Copy rebuilt from the readable entries, which the pack was scanned from */
+  repairedPath?: string | null;
+  /** Still being written (e.g. downloading); scan again later */
+  retry: boolean;
+}
+
+/** Dimensions and format of a texture, read from its PNG header */
+export interface TextureInfo {
+  width: number;
+  height: number;
+  /** Bits per sample (1, 2, 4, 8 or 16) */
+  bitDepth: number;
+  /** PNG color type (0 gray, 2 RGB, 3 indexed, 4 gray + alpha, 6 RGBA) */
+  colorType: number;
+}
+
+export interface CtmConflict {
+  assetId: AssetId;
+  packs: PackId[];
+}
+
+export interface DuplicatePacks {
+  /** SHA-256 over the packs' file paths and contents */
+  contentHash: string;
+  packIds: PackId[];
+  /** True when the copies go by different names */
+  differentNames: boolean;
+}
+
+export interface DuplicateAsset {
+  assetId: AssetId;
+  /** Pack-relative path of the file */
+  file: string;
+  /** xxh3-64 of the file content, as 16 hex digits */
+  contentHash: string;
+  /** Size of the file in bytes */
+  size: number;
+  packIds: PackId[];
 }